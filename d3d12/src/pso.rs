@@ -115,6 +115,16 @@ impl<'a> CachedPSO<'a> {
         )
     }
 
+    pub fn from_bytes(data: &'a [u8]) -> Self {
+        CachedPSO(
+            d3d12::D3D12_CACHED_PIPELINE_STATE {
+                CachedBlobSizeInBytes: data.len(),
+                pCachedBlob: data.as_ptr().cast(),
+            },
+            PhantomData,
+        )
+    }
+
     // `blob` may not be null.
     pub fn from_blob(blob: &'a Blob) -> Self {
         CachedPSO(
@@ -2,6 +2,7 @@ use crate::{com::ComPtr, sync::Fence, CommandList, HRESULT};
 use winapi::um::d3d12;
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug)]
 pub enum Priority {
     Normal = d3d12::D3D12_COMMAND_QUEUE_PRIORITY_NORMAL,
     High = d3d12::D3D12_COMMAND_QUEUE_PRIORITY_HIGH,
@@ -29,4 +30,8 @@ impl CommandQueue {
     pub fn signal(&self, fence: &Fence, value: u64) -> HRESULT {
         unsafe { self.Signal(fence.as_mut_ptr(), value) }
     }
+
+    pub fn wait(&self, fence: &Fence, value: u64) -> HRESULT {
+        unsafe { self.Wait(fence.as_mut_ptr(), value) }
+    }
 }
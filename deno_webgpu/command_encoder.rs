@@ -177,6 +177,7 @@ pub fn op_webgpu_command_encoder_begin_render_pass(
                     clear_value: attachment.stencil_clear_value,
                     read_only: attachment.stencil_read_only,
                 },
+                resolve: None,
             });
     }
 
@@ -206,6 +207,8 @@ pub fn op_webgpu_command_encoder_begin_render_pass(
         depth_stencil_attachment: processed_depth_stencil_attachment.as_ref(),
         timestamp_writes: timestamp_writes.as_ref(),
         occlusion_query_set: occlusion_query_set_resource,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: None,
     };
 
     let render_pass = wgpu_core::command::RenderPass::new(command_encoder_resource.1, &descriptor);
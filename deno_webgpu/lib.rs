@@ -363,6 +363,9 @@ fn deserialize_features(features: &wgpu_types::Features) -> Vec<&'static str> {
     if features.contains(wgpu_types::Features::SHADER_I16) {
         return_features.push("shader-i16");
     }
+    if features.contains(wgpu_types::Features::SHADER_INT64) {
+        return_features.push("shader-int64");
+    }
     if features.contains(wgpu_types::Features::SHADER_PRIMITIVE_INDEX) {
         return_features.push("shader-primitive-index");
     }
@@ -634,6 +637,10 @@ impl From<GpuRequiredFeatures> for wgpu_types::Features {
             wgpu_types::Features::SHADER_I16,
             required_features.0.contains("shader-i16"),
         );
+        features.set(
+            wgpu_types::Features::SHADER_INT64,
+            required_features.0.contains("shader-int64"),
+        );
         features.set(
             wgpu_types::Features::SHADER_PRIMITIVE_INDEX,
             required_features.0.contains("shader-primitive-index"),
@@ -669,6 +676,10 @@ pub async fn op_webgpu_request_device(
         label: Some(Cow::Owned(label)),
         required_features: required_features.into(),
         required_limits: required_limits.unwrap_or_default(),
+        compute_only: false,
+        queue_priority: wgpu_types::QueuePriority::Normal,
+        additional_queues: 0,
+        cache_bind_groups: false,
     };
 
     let (device, _queue, maybe_err) = gfx_select!(adapter => instance.adapter_request_device(
@@ -113,6 +113,7 @@ pub fn op_webgpu_create_compute_pipeline(
             entry_point: Cow::from(compute.entry_point),
             // TODO(lucacasonato): support args.compute.constants
         },
+        cache: None,
     };
     let implicit_pipelines = match layout {
         GPUPipelineLayoutOrGPUAutoLayoutMode::Layout(_) => None,
@@ -380,12 +381,14 @@ pub fn op_webgpu_create_render_pipeline(
                 entry_point: Cow::Owned(args.vertex.entry_point),
             },
             buffers: Cow::Owned(vertex_buffers),
+            capture_buffers: Cow::Borrowed(&[]),
         },
         primitive: args.primitive.into(),
         depth_stencil: args.depth_stencil.map(Into::into),
         multisample: args.multisample,
         fragment,
         multiview: None,
+        cache: None,
     };
 
     let implicit_pipelines = match args.layout {
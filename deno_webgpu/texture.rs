@@ -123,6 +123,7 @@ pub fn op_webgpu_create_texture_view(
         format: args.format,
         dimension: args.dimension,
         range: args.range,
+        swizzle: Default::default(),
     };
 
     gfx_put!(texture => instance.texture_create_view(
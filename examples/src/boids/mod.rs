@@ -144,6 +144,7 @@ impl crate::framework::Example for Example {
                         attributes: &wgpu::vertex_attr_array![2 => Float32x2],
                     },
                 ],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &draw_shader,
@@ -154,6 +155,7 @@ impl crate::framework::Example for Example {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         // create compute pipeline
@@ -163,6 +165,7 @@ impl crate::framework::Example for Example {
             layout: Some(&compute_pipeline_layout),
             module: &compute_shader,
             entry_point: "main",
+            cache: None,
         });
 
         // buffer for the three 2d triangle vertices of each instance
@@ -279,6 +282,8 @@ impl crate::framework::Example for Example {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
         };
 
         // get command encoder
@@ -116,6 +116,8 @@ impl Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
             rpass.set_pipeline(&self.pipeline);
             rpass.set_bind_group(0, &self.global_group, &[]);
@@ -204,6 +206,7 @@ impl crate::framework::Example for Example {
                 module: &shader,
                 entry_point: "vs_main",
                 buffers: &[],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
@@ -222,6 +225,7 @@ impl crate::framework::Example for Example {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         let texture = {
@@ -98,6 +98,7 @@ impl crate::framework::Example for Example {
                     module: &shader_triangle_and_lines,
                     entry_point: "vs_main",
                     buffers: &[],
+                    capture_buffers: &[],
                 },
                 fragment: Some(wgpu::FragmentState {
                     module: &shader_triangle_and_lines,
@@ -111,6 +112,7 @@ impl crate::framework::Example for Example {
                 depth_stencil: None,
                 multisample: wgpu::MultisampleState::default(),
                 multiview: None,
+                cache: None,
             });
 
         let pipeline_triangle_regular =
@@ -121,6 +123,7 @@ impl crate::framework::Example for Example {
                     module: &shader_triangle_and_lines,
                     entry_point: "vs_main",
                     buffers: &[],
+                    capture_buffers: &[],
                 },
                 fragment: Some(wgpu::FragmentState {
                     module: &shader_triangle_and_lines,
@@ -131,6 +134,7 @@ impl crate::framework::Example for Example {
                 depth_stencil: None,
                 multisample: wgpu::MultisampleState::default(),
                 multiview: None,
+                cache: None,
             });
 
         let pipeline_lines = if device
@@ -145,6 +149,7 @@ impl crate::framework::Example for Example {
                         module: &shader_triangle_and_lines,
                         entry_point: "vs_main",
                         buffers: &[],
+                        capture_buffers: &[],
                     },
                     fragment: Some(wgpu::FragmentState {
                         module: &shader_triangle_and_lines,
@@ -159,6 +164,7 @@ impl crate::framework::Example for Example {
                     depth_stencil: None,
                     multisample: wgpu::MultisampleState::default(),
                     multiview: None,
+                    cache: None,
                 }),
             )
         } else {
@@ -206,6 +212,7 @@ impl crate::framework::Example for Example {
                         module: &shader,
                         entry_point: "vs_main",
                         buffers: &[],
+                        capture_buffers: &[],
                     },
                     fragment: Some(wgpu::FragmentState {
                         module: &shader,
@@ -216,6 +223,7 @@ impl crate::framework::Example for Example {
                     depth_stencil: None,
                     multisample: wgpu::MultisampleState::default(),
                     multiview: None,
+                    cache: None,
                 }),
                 bind_group_layout,
             )
@@ -269,6 +277,8 @@ impl crate::framework::Example for Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
 
             rpass.set_pipeline(&self.pipeline_triangle_conservative);
@@ -290,6 +300,8 @@ impl crate::framework::Example for Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
 
             rpass.set_pipeline(&self.pipeline_upscale);
@@ -319,6 +319,10 @@ impl ExampleContext {
                     label: None,
                     required_features: (optional_features & adapter_features) | required_features,
                     required_limits: needed_limits,
+                    compute_only: false,
+                    queue_priority: wgpu::QueuePriority::Normal,
+                    additional_queues: 0,
+                    cache_bind_groups: false,
                 },
                 trace_dir.ok().as_ref().map(std::path::Path::new),
             )
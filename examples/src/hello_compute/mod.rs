@@ -50,6 +50,10 @@ async fn execute_gpu(numbers: &[u32]) -> Option<Vec<u32>> {
                 label: None,
                 required_features: wgpu::Features::empty(),
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                compute_only: true,
+                queue_priority: wgpu::QueuePriority::Normal,
+                additional_queues: 0,
+                cache_bind_groups: false,
             },
             None,
         )
@@ -109,6 +113,7 @@ async fn execute_gpu_inner(
         layout: None,
         module: &cs_module,
         entry_point: "main",
+        cache: None,
     });
 
     // Instantiates the bind group, once again specifying the binding of buffers.
@@ -19,6 +19,10 @@ async fn run() {
                 label: None,
                 required_features: wgpu::Features::empty(),
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                compute_only: false,
+                queue_priority: wgpu::QueuePriority::Normal,
+                additional_queues: 0,
+                cache_bind_groups: false,
             },
             None,
         )
@@ -103,12 +107,14 @@ async fn execute(
         layout: Some(&pipeline_layout),
         module: &shaders_module,
         entry_point: "patient_main",
+        cache: None,
     });
     let hasty_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
         label: None,
         layout: Some(&pipeline_layout),
         module: &shaders_module,
         entry_point: "hasty_main",
+        cache: None,
     });
 
     //----------------------------------------------------------
@@ -32,6 +32,10 @@ async fn run(event_loop: EventLoop<()>, window: Window) {
                 // Make sure we use the texture resolution limits from the adapter, so we can support images the size of the swapchain.
                 required_limits: wgpu::Limits::downlevel_webgl2_defaults()
                     .using_resolution(adapter.limits()),
+                compute_only: false,
+                queue_priority: wgpu::QueuePriority::Normal,
+                additional_queues: 0,
+                cache_bind_groups: false,
             },
             None,
         )
@@ -60,6 +64,7 @@ async fn run(event_loop: EventLoop<()>, window: Window) {
             module: &shader,
             entry_point: "vs_main",
             buffers: &[],
+            capture_buffers: &[],
         },
         fragment: Some(wgpu::FragmentState {
             module: &shader,
@@ -70,6 +75,7 @@ async fn run(event_loop: EventLoop<()>, window: Window) {
         depth_stencil: None,
         multisample: wgpu::MultisampleState::default(),
         multiview: None,
+        cache: None,
     });
 
     let mut config = surface
@@ -125,6 +131,8 @@ async fn run(event_loop: EventLoop<()>, window: Window) {
                                     depth_stencil_attachment: None,
                                     timestamp_writes: None,
                                     occlusion_query_set: None,
+                                    pipeline_statistics_query: None,
+                                    shading_rate_attachment: None,
                                 });
                             rpass.set_pipeline(&render_pipeline);
                             rpass.draw(0..3, 0..1);
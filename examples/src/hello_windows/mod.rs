@@ -75,6 +75,10 @@ async fn run(event_loop: EventLoop<()>, viewports: Vec<(Arc<Window>, wgpu::Color
                 label: None,
                 required_features: wgpu::Features::empty(),
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                compute_only: false,
+                queue_priority: wgpu::QueuePriority::Normal,
+                additional_queues: 0,
+                cache_bind_groups: false,
             },
             None,
         )
@@ -132,6 +136,8 @@ async fn run(event_loop: EventLoop<()>, viewports: Vec<(Arc<Window>, wgpu::Color
                                         depth_stencil_attachment: None,
                                         timestamp_writes: None,
                                         occlusion_query_set: None,
+                                        pipeline_statistics_query: None,
+                                        shading_rate_attachment: None,
                                     });
                             }
 
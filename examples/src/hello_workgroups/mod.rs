@@ -32,6 +32,10 @@ async fn run() {
                 label: None,
                 required_features: wgpu::Features::empty(),
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                compute_only: false,
+                queue_priority: wgpu::QueuePriority::Normal,
+                additional_queues: 0,
+                cache_bind_groups: false,
             },
             None,
         )
@@ -110,6 +114,7 @@ async fn run() {
         layout: Some(&pipeline_layout),
         module: &shader,
         entry_point: "main",
+        cache: None,
     });
 
     //----------------------------------------------------------
@@ -94,6 +94,7 @@ impl Example {
                 module: &shader,
                 entry_point: "vs_main",
                 buffers: &[],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
@@ -107,6 +108,7 @@ impl Example {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         let bind_group_layout = pipeline.get_bind_group_layout(0);
@@ -133,6 +135,7 @@ impl Example {
                     mip_level_count: Some(1),
                     base_array_layer: 0,
                     array_layer_count: None,
+                    swizzle: wgpu::ComponentMapping::IDENTITY,
                 })
             })
             .collect::<Vec<_>>();
@@ -169,6 +172,8 @@ impl Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
             if let Some(ref query_sets) = query_sets {
                 rpass.write_timestamp(&query_sets.timestamp, timestamp_query_index_base);
@@ -291,6 +296,7 @@ impl crate::framework::Example for Example {
                 module: &shader,
                 entry_point: "vs_main",
                 buffers: &[],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
@@ -306,6 +312,7 @@ impl crate::framework::Example for Example {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         // Create bind group
@@ -490,6 +497,8 @@ impl crate::framework::Example for Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
             rpass.set_pipeline(&self.draw_pipeline);
             rpass.set_bind_group(0, &self.bind_group, &[]);
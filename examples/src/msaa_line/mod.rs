@@ -59,6 +59,7 @@ impl Example {
                     step_mode: wgpu::VertexStepMode::Vertex,
                     attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4],
                 }],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: shader,
@@ -76,6 +77,7 @@ impl Example {
                 ..Default::default()
             },
             multiview: None,
+            cache: None,
         });
         let mut encoder =
             device.create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {
@@ -305,6 +307,8 @@ impl crate::framework::Example for Example {
                     depth_stencil_attachment: None,
                     timestamp_writes: None,
                     occlusion_query_set: None,
+                    pipeline_statistics_query: None,
+                    shading_rate_attachment: None,
                 })
                 .execute_bundles(iter::once(&self.bundle));
         }
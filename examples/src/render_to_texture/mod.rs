@@ -21,6 +21,10 @@ async fn run(_path: Option<String>) {
                 label: None,
                 required_features: wgpu::Features::empty(),
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                compute_only: false,
+                queue_priority: wgpu::QueuePriority::Normal,
+                additional_queues: 0,
+                cache_bind_groups: false,
             },
             None,
         )
@@ -60,6 +64,7 @@ async fn run(_path: Option<String>) {
             module: &shader,
             entry_point: "vs_main",
             buffers: &[],
+            capture_buffers: &[],
         },
         fragment: Some(wgpu::FragmentState {
             module: &shader,
@@ -70,6 +75,7 @@ async fn run(_path: Option<String>) {
         depth_stencil: None,
         multisample: wgpu::MultisampleState::default(),
         multiview: None,
+        cache: None,
     });
 
     log::info!("Wgpu context set up.");
@@ -93,6 +99,8 @@ async fn run(_path: Option<String>) {
             })],
             depth_stencil_attachment: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
             timestamp_writes: None,
         });
         render_pass.set_pipeline(&pipeline);
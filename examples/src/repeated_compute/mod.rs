@@ -172,6 +172,10 @@ impl WgpuContext {
                     label: None,
                     required_features: wgpu::Features::empty(),
                     required_limits: wgpu::Limits::downlevel_defaults(),
+                    compute_only: false,
+                    queue_priority: wgpu::QueuePriority::Normal,
+                    additional_queues: 0,
+                    cache_bind_groups: false,
                 },
                 None,
             )
@@ -245,6 +249,7 @@ impl WgpuContext {
             layout: Some(&pipeline_layout),
             module: &shader,
             entry_point: "main",
+            cache: None,
         });
 
         WgpuContext {
@@ -398,6 +398,7 @@ impl crate::framework::Example for Example {
                     mip_level_count: None,
                     base_array_layer: i as u32,
                     array_layer_count: Some(1),
+                    swizzle: wgpu::ComponentMapping::IDENTITY,
                 }))
             })
             .collect::<Vec<_>>();
@@ -501,6 +502,7 @@ impl crate::framework::Example for Example {
                     module: &shader,
                     entry_point: "vs_bake",
                     buffers: &[vb_desc.clone()],
+                    capture_buffers: &[],
                 },
                 fragment: None,
                 primitive: wgpu::PrimitiveState {
@@ -525,6 +527,7 @@ impl crate::framework::Example for Example {
                 }),
                 multisample: wgpu::MultisampleState::default(),
                 multiview: None,
+                cache: None,
             });
 
             Pass {
@@ -633,6 +636,7 @@ impl crate::framework::Example for Example {
                     module: &shader,
                     entry_point: "vs_main",
                     buffers: &[vb_desc],
+                    capture_buffers: &[],
                 },
                 fragment: Some(wgpu::FragmentState {
                     module: &shader,
@@ -657,6 +661,7 @@ impl crate::framework::Example for Example {
                 }),
                 multisample: wgpu::MultisampleState::default(),
                 multiview: None,
+                cache: None,
             });
 
             Pass {
@@ -770,9 +775,12 @@ impl crate::framework::Example for Example {
                             store: wgpu::StoreOp::Store,
                         }),
                         stencil_ops: None,
+                        resolve: None,
                     }),
                     timestamp_writes: None,
                     occlusion_query_set: None,
+                    pipeline_statistics_query: None,
+                    shading_rate_attachment: None,
                 });
                 pass.set_pipeline(&self.shadow_pass.pipeline);
                 pass.set_bind_group(0, &self.shadow_pass.bind_group, &[]);
@@ -814,9 +822,12 @@ impl crate::framework::Example for Example {
                         store: wgpu::StoreOp::Discard,
                     }),
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
             pass.set_pipeline(&self.forward_pass.pipeline);
             pass.set_bind_group(0, &self.forward_pass.bind_group, &[]);
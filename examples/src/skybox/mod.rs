@@ -200,6 +200,7 @@ impl crate::framework::Example for Example {
                 module: &shader,
                 entry_point: "vs_sky",
                 buffers: &[],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
@@ -219,6 +220,7 @@ impl crate::framework::Example for Example {
             }),
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
         let entity_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             label: Some("Entity"),
@@ -231,6 +233,7 @@ impl crate::framework::Example for Example {
                     step_mode: wgpu::VertexStepMode::Vertex,
                     attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3],
                 }],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
@@ -250,6 +253,7 @@ impl crate::framework::Example for Example {
             }),
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
@@ -440,9 +444,12 @@ impl crate::framework::Example for Example {
                         store: wgpu::StoreOp::Discard,
                     }),
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
 
             rpass.set_bind_group(0, &self.bind_group, &[]);
@@ -132,6 +132,7 @@ impl<const SRGB: bool> crate::framework::Example for Example<SRGB> {
                 module: &shader,
                 entry_point: "vs_main",
                 buffers: &vertex_buffers,
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
@@ -149,6 +150,7 @@ impl<const SRGB: bool> crate::framework::Example for Example<SRGB> {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         // Done
@@ -196,6 +198,8 @@ impl<const SRGB: bool> crate::framework::Example for Example<SRGB> {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
             rpass.push_debug_group("Prepare data for draw.");
             rpass.set_pipeline(&self.pipeline);
@@ -326,6 +326,7 @@ impl crate::framework::Example for Example {
                     step_mode: wgpu::VertexStepMode::Vertex,
                     attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Sint32],
                 }],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: fragment_shader_module,
@@ -339,6 +340,7 @@ impl crate::framework::Example for Example {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         Self {
@@ -379,6 +381,8 @@ impl crate::framework::Example for Example {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
         });
 
         rpass.set_pipeline(&self.pipeline);
@@ -216,6 +216,10 @@ async fn run() {
                 label: None,
                 required_features: features,
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                compute_only: false,
+                queue_priority: wgpu::QueuePriority::Normal,
+                additional_queues: 0,
+                cache_bind_groups: false,
             },
             None,
         )
@@ -288,6 +292,7 @@ fn compute_pass(
         layout: None,
         module,
         entry_point: "main_cs",
+        cache: None,
     });
     let bind_group_layout = compute_pipeline.get_bind_group_layout(0);
     let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -343,6 +348,7 @@ fn render_pass(
             module,
             entry_point: "vs_main",
             buffers: &[],
+            capture_buffers: &[],
         },
         fragment: Some(wgpu::FragmentState {
             module,
@@ -353,6 +359,7 @@ fn render_pass(
         depth_stencil: None,
         multisample: wgpu::MultisampleState::default(),
         multiview: None,
+        cache: None,
     });
 
     let render_target = device.create_texture(&wgpu::TextureDescriptor {
@@ -388,6 +395,8 @@ fn render_pass(
             end_of_pass_write_index: Some(*next_unused_query + 1),
         }),
         occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: None,
     });
     *next_unused_query += 2;
 
@@ -115,6 +115,10 @@ impl WgpuContext {
                     label: None,
                     required_features: wgpu::Features::empty(),
                     required_limits: wgpu::Limits::downlevel_defaults(),
+                    compute_only: false,
+                    queue_priority: wgpu::QueuePriority::Normal,
+                    additional_queues: 0,
+                    cache_bind_groups: false,
                 },
                 None,
             )
@@ -180,6 +184,7 @@ impl WgpuContext {
                 module: &shader,
                 entry_point: "vs_main",
                 buffers: &[],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
@@ -190,6 +195,7 @@ impl WgpuContext {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         let surface_config = surface
@@ -320,6 +326,8 @@ async fn run(event_loop: EventLoop<()>, window: Arc<Window>) {
                                         )],
                                         depth_stencil_attachment: None,
                                         occlusion_query_set: None,
+                                        pipeline_statistics_query: None,
+                                        shading_rate_attachment: None,
                                         timestamp_writes: None,
                                     });
                                 render_pass.set_pipeline(&wgpu_context_ref.pipeline);
@@ -522,6 +522,7 @@ impl crate::framework::Example for Example {
                     step_mode: wgpu::VertexStepMode::Vertex,
                     attributes: &wgpu::vertex_attr_array![0 => Sint16x2, 1 => Sint8x4],
                 }],
+                capture_buffers: &[],
             },
             // Fragment shader and output targets
             fragment: Some(wgpu::FragmentState {
@@ -572,6 +573,7 @@ impl crate::framework::Example for Example {
             // No multisampling is used.
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         // Same idea as the water pipeline.
@@ -586,6 +588,7 @@ impl crate::framework::Example for Example {
                     step_mode: wgpu::VertexStepMode::Vertex,
                     attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Unorm8x4],
                 }],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &terrain_module,
@@ -606,6 +609,7 @@ impl crate::framework::Example for Example {
             }),
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
         // A render bundle to draw the terrain.
@@ -745,9 +749,12 @@ impl crate::framework::Example for Example {
                         store: wgpu::StoreOp::Store,
                     }),
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
 
             rpass.execute_bundles([&self.terrain_bundle]);
@@ -772,9 +779,12 @@ impl crate::framework::Example for Example {
                         store: wgpu::StoreOp::Store,
                     }),
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
             rpass.set_pipeline(&self.terrain_pipeline);
             rpass.set_bind_group(0, &self.terrain_normal_bind_group, &[]);
@@ -798,9 +808,12 @@ impl crate::framework::Example for Example {
                     view: &self.depth_buffer,
                     depth_ops: None,
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
 
             rpass.set_pipeline(&self.water_pipeline);
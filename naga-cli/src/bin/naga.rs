@@ -58,7 +58,7 @@ struct Args {
 
     /// the shader model to use if targeting HLSL
     ///
-    /// May be `50`, 51`, or `60`
+    /// May be `50`, `51`, `60`, `61`, `62`, `63`, `64`, `65` or `66`
     #[argh(option)]
     shader_model: Option<ShaderModelArg>,
 
@@ -79,7 +79,8 @@ struct Args {
     #[argh(switch, short = 'g')]
     generate_debug_symbols: bool,
 
-    /// compact the module's IR and revalidate.
+    /// compact the module's IR, removing the functions that no entry point
+    /// calls, and revalidate.
     ///
     /// Output files will reflect the compacted IR. If you want to see the IR as
     /// it was before compaction, use the `--before-compaction` option.
@@ -97,6 +98,27 @@ struct Args {
     #[argh(switch)]
     bulk_validate: bool,
 
+    /// batch mode: compile the shaders listed in the given JSON manifest, and
+    /// print a JSON report of the results.
+    ///
+    /// The manifest is an object with a `shaders` array. Each shader has an
+    /// `input` path relative to the manifest, a list of `outputs` relative to
+    /// the output directory, an optional `entry_point`, and optional `defines`:
+    /// preprocessor definitions for GLSL inputs, or values of overridable
+    /// constants for other inputs. The other options apply to every shader.
+    #[argh(option)]
+    manifest: Option<String>,
+
+    /// in batch mode, the directory to write the outputs to. Defaults to the
+    /// directory of the manifest.
+    #[argh(option)]
+    output_dir: Option<String>,
+
+    /// in batch mode, how many shaders to compile in parallel. Defaults to the
+    /// available parallelism.
+    #[argh(option)]
+    jobs: Option<usize>,
+
     /// show version
     #[argh(switch)]
     version: bool,
@@ -150,6 +172,12 @@ impl FromStr for ShaderModelArg {
             "50" => ShaderModel::V5_0,
             "51" => ShaderModel::V5_1,
             "60" => ShaderModel::V6_0,
+            "61" => ShaderModel::V6_1,
+            "62" => ShaderModel::V6_2,
+            "63" => ShaderModel::V6_3,
+            "64" => ShaderModel::V6_4,
+            "65" => ShaderModel::V6_5,
+            "66" => ShaderModel::V6_6,
             _ => return Err(format!("Invalid value for --shader-model: {s}")),
         }))
     }
@@ -174,11 +202,12 @@ impl FromStr for GlslProfileArg {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct Parameters<'a> {
     validation_flags: naga::valid::ValidationFlags,
     bounds_check_policies: naga::proc::BoundsCheckPolicies,
     entry_point: Option<String>,
+    defines: naga::FastHashMap<String, String>,
     keep_coordinate_space: bool,
     spv_in: naga::front::spv::Options,
     spv_out: naga::back::spv::Options<'a>,
@@ -188,38 +217,26 @@ struct Parameters<'a> {
     hlsl: naga::back::hlsl::Options,
 }
 
-trait PrettyResult {
-    type Target;
-    fn unwrap_pretty(self) -> Self::Target;
+fn print_err(error: &dyn Error) {
+    eprint!("{}", format_err(error));
 }
 
-fn print_err(error: &dyn Error) {
-    eprint!("{error}");
+/// Formats `error` followed by its sources, one per line.
+fn format_err(error: &dyn Error) -> String {
+    use std::fmt::Write;
 
+    let mut formatted = error.to_string();
     let mut e = error.source();
     if e.is_some() {
-        eprintln!(": ");
-    } else {
-        eprintln!();
+        formatted.push_str(": ");
     }
+    formatted.push('\n');
 
     while let Some(source) = e {
-        eprintln!("\t{source}");
+        writeln!(formatted, "\t{source}").unwrap();
         e = source.source();
     }
-}
-
-impl<T, E: Error> PrettyResult for Result<T, E> {
-    type Target = T;
-    fn unwrap_pretty(self) -> T {
-        match self {
-            Result::Ok(value) => value,
-            Result::Err(error) => {
-                print_err(&error);
-                std::process::exit(1);
-            }
-        }
-    }
+    formatted
 }
 
 fn main() {
@@ -300,6 +317,9 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
     if args.bulk_validate {
         return bulk_validate(args, &params);
     }
+    if let Some(ref manifest) = args.manifest {
+        return batch(&args, &params, manifest);
+    }
 
     let (input_path, input) = if let Some(path) = args.files.first() {
         let path = Path::new(path);
@@ -339,19 +359,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
 
     let output_paths = args.files.get(1..).unwrap_or(&[]);
 
-    // Decide which capabilities our output formats can support.
-    let validation_caps =
-        output_paths
-            .iter()
-            .fold(naga::valid::Capabilities::all(), |caps, path| {
-                use naga::valid::Capabilities as C;
-                let missing = match Path::new(path).extension().and_then(|ex| ex.to_str()) {
-                    Some("wgsl") => C::CLIP_DISTANCE | C::CULL_DISTANCE,
-                    Some("metal") => C::CULL_DISTANCE,
-                    _ => C::empty(),
-                };
-                caps & !missing
-            });
+    let validation_caps = output_capabilities(output_paths);
 
     // Validate the IR before compaction.
     let info = match naga::valid::Validator::new(params.validation_flags, validation_caps)
@@ -378,7 +386,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 write_output(&module, &info, &params, before_compaction)?;
             }
 
-            naga::compact::compact(&mut module);
+            naga::compact::compact(&mut module, naga::compact::KeepUnused::No);
 
             // Re-validate the IR after compaction.
             match naga::valid::Validator::new(params.validation_flags, validation_caps)
@@ -424,6 +432,21 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Decide which capabilities our output formats can support.
+fn output_capabilities(output_paths: &[String]) -> naga::valid::Capabilities {
+    output_paths
+        .iter()
+        .fold(naga::valid::Capabilities::all(), |caps, path| {
+            use naga::valid::Capabilities as C;
+            let missing = match Path::new(path).extension().and_then(|ex| ex.to_str()) {
+                Some("wgsl") => C::CLIP_DISTANCE | C::CULL_DISTANCE,
+                Some("metal") => C::CULL_DISTANCE,
+                _ => C::empty(),
+            };
+            caps & !missing
+        })
+}
+
 struct Parsed {
     module: naga::Module,
     input_text: Option<String>,
@@ -456,7 +479,7 @@ fn parse_input(
                 }
             }
         }
-        ext @ ("vert" | "frag" | "comp" | "glsl") => {
+        ext @ ("vert" | "frag" | "comp" | "geom" | "tesc" | "tese" | "glsl") => {
             let input = String::from_utf8(input)?;
             let mut parser = naga::front::glsl::Frontend::default();
 
@@ -468,6 +491,9 @@ fn parse_input(
                                 "vert" => naga::ShaderStage::Vertex,
                                 "frag" => naga::ShaderStage::Fragment,
                                 "comp" => naga::ShaderStage::Compute,
+                                "geom" => naga::ShaderStage::Geometry,
+                                "tesc" => naga::ShaderStage::TessellationControl,
+                                "tese" => naga::ShaderStage::TessellationEvaluation,
                                 "glsl" => {
                                     let internal_name = input_path.to_string_lossy();
                                     match Path::new(&internal_name[..internal_name.len()-5])
@@ -479,21 +505,29 @@ fn parse_input(
                                         "vert" => naga::ShaderStage::Vertex,
                                         "frag" => naga::ShaderStage::Fragment,
                                         "comp" => naga::ShaderStage::Compute,
+                                        "geom" => naga::ShaderStage::Geometry,
+                                        "tesc" => naga::ShaderStage::TessellationControl,
+                                        "tese" => naga::ShaderStage::TessellationEvaluation,
+                                "tesc" => naga::ShaderStage::TessellationControl,
+                                "tese" => naga::ShaderStage::TessellationEvaluation,
                                         _ => unreachable!(),
                                     }
                                 },
                                 _ => unreachable!(),
                             },
-                            defines: Default::default(),
+                            defines: params.defines.clone(),
+                            include_resolver: None,
+                            entry_point: None,
                         },
                         &input,
                     )
-                    .unwrap_or_else(|error| {
+                    .map_err(|error| {
                         let filename = input_path.file_name().and_then(std::ffi::OsStr::to_str).unwrap_or("glsl");
-                        let mut writer = StandardStream::stderr(ColorChoice::Auto);
-                        error.emit_to_writer_with_path(&mut writer, &input, filename);
-                        std::process::exit(1);
-                    }),
+                        format!(
+                            "Could not parse GLSL:\n{}",
+                            error.emit_to_string_with_path(&input, filename)
+                        )
+                    })?,
                 Some(input),
             )
         }
@@ -544,8 +578,7 @@ fn write_output(
                 ))?,
                 &options,
                 &pipeline_options,
-            )
-            .unwrap_pretty();
+            )?;
             fs::write(output_path, msl)?;
         }
         "spv" => {
@@ -558,7 +591,7 @@ fn write_output(
                         .entry_points
                         .iter()
                         .position(|ep| ep.name == *name)
-                        .expect("Unable to find the entry point");
+                        .ok_or(CliError("Unable to find the entry point"))?;
                     pipeline_options_owned = spv::PipelineOptions {
                         entry_point: name.clone(),
                         shader_stage: module.entry_points[ep_index].stage,
@@ -576,8 +609,7 @@ fn write_output(
                 ))?,
                 &params.spv_out,
                 pipeline_options,
-            )
-            .unwrap_pretty();
+            )?;
             let bytes = spv
                 .iter()
                 .fold(Vec::with_capacity(spv.len() * 4), |mut v, w| {
@@ -615,8 +647,7 @@ fn write_output(
                 &params.glsl,
                 &pipeline_options,
                 params.bounds_check_policies,
-            )
-            .unwrap_pretty();
+            )?;
             writer.write()?;
             fs::write(output_path, buffer)?;
         }
@@ -630,15 +661,13 @@ fn write_output(
             use naga::back::hlsl;
             let mut buffer = String::new();
             let mut writer = hlsl::Writer::new(&mut buffer, &params.hlsl);
-            writer
-                .write(
-                    module,
-                    info.as_ref().ok_or(CliError(
-                        "Generating hlsl output requires validation to \
-                         succeed, and it failed in a previous step",
-                    ))?,
-                )
-                .unwrap_pretty();
+            writer.write(
+                module,
+                info.as_ref().ok_or(CliError(
+                    "Generating hlsl output requires validation to \
+                     succeed, and it failed in a previous step",
+                ))?,
+            )?;
             fs::write(output_path, buffer)?;
         }
         "wgsl" => {
@@ -651,8 +680,7 @@ fn write_output(
                      succeed, and it failed in a previous step",
                 ))?,
                 wgsl::WriterFlags::empty(),
-            )
-            .unwrap_pretty();
+            )?;
             fs::write(output_path, wgsl)?;
         }
         other => {
@@ -710,6 +738,186 @@ fn bulk_validate(args: Args, params: &Parameters) -> Result<(), Box<dyn std::err
     Ok(())
 }
 
+/// A shader to compile in batch mode, see `--manifest`.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestShader {
+    input: String,
+    outputs: Vec<String>,
+    #[serde(default)]
+    entry_point: Option<String>,
+    #[serde(default)]
+    defines: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    shaders: Vec<ManifestShader>,
+}
+
+/// The result of compiling a [`ManifestShader`].
+#[derive(serde::Serialize)]
+struct ShaderReport {
+    input: String,
+    entry_point: Option<String>,
+    outputs: Vec<String>,
+    error: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct BatchReport {
+    succeeded: usize,
+    failed: usize,
+    shaders: Vec<ShaderReport>,
+}
+
+fn batch(
+    args: &Args,
+    params: &Parameters,
+    manifest_path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    let manifest_path = Path::new(manifest_path);
+    let manifest: Manifest = serde_json::from_slice(&fs::read(manifest_path)?)?;
+    let input_dir = manifest_path.parent().unwrap_or(Path::new(""));
+    let output_dir = args.output_dir.as_ref().map_or(input_dir, Path::new);
+
+    let jobs = args
+        .jobs
+        .or_else(|| std::thread::available_parallelism().ok().map(usize::from))
+        .unwrap_or(1)
+        .clamp(1, manifest.shaders.len().max(1));
+
+    // Each thread takes the next shader of the manifest until there are none left.
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(Vec::with_capacity(manifest.shaders.len()));
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(shader) = manifest.shaders.get(index) else {
+                    break;
+                };
+                let outputs: Vec<_> = shader
+                    .outputs
+                    .iter()
+                    .map(|output| output_dir.join(output).to_string_lossy().into_owned())
+                    .collect();
+                let error = compile_shader(
+                    args,
+                    params,
+                    shader,
+                    &input_dir.join(&shader.input),
+                    &outputs,
+                )
+                .err()
+                .map(|error| format_err(error.as_ref()));
+                let report = ShaderReport {
+                    input: shader.input.clone(),
+                    entry_point: shader.entry_point.clone(),
+                    outputs,
+                    error,
+                };
+                reports.lock().unwrap().push((index, report));
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort_by_key(|&(index, _)| index);
+    let shaders: Vec<_> = reports.into_iter().map(|(_, report)| report).collect();
+    let failed = shaders
+        .iter()
+        .filter(|report| report.error.is_some())
+        .count();
+    let report = BatchReport {
+        succeeded: shaders.len() - failed,
+        failed,
+        shaders,
+    };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    if failed != 0 {
+        return Err(CliError("Some shaders of the manifest failed to compile").into());
+    }
+    Ok(())
+}
+
+/// Compiles a shader of the manifest to all of its `outputs`.
+fn compile_shader(
+    args: &Args,
+    params: &Parameters,
+    shader: &ManifestShader,
+    input_path: &Path,
+    outputs: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let is_glsl = matches!(
+        input_path.extension().and_then(|ext| ext.to_str()),
+        Some("vert" | "frag" | "comp" | "geom" | "tesc" | "tese" | "glsl")
+    );
+
+    let mut params = params.clone();
+    if shader.entry_point.is_some() {
+        params.entry_point = shader.entry_point.clone();
+    }
+    if is_glsl {
+        params.defines = shader
+            .defines
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+    }
+
+    let input = fs::read(input_path)?;
+    let Parsed {
+        mut module,
+        input_text,
+    } = parse_input(input_path, input, &params)?;
+
+    // The defines of other inputs give values to overridable constants.
+    if !is_glsl && !shader.defines.is_empty() {
+        let mut constants = naga::proc::PipelineConstants::new();
+        for (name, value) in shader.defines.iter() {
+            let value = value.parse().map_err(|_| {
+                format!("Value of overridable constant {name:?} isn't a number: {value:?}")
+            })?;
+            constants.insert(name.clone(), value);
+        }
+        naga::proc::process_overrides(&mut module, &constants)?;
+    }
+
+    let validate = |module: &naga::Module| {
+        naga::valid::Validator::new(params.validation_flags, output_capabilities(outputs))
+            .validate(module)
+            .map_err(|error| match input_text {
+                Some(ref input) => {
+                    let filename = input_path.file_name().and_then(std::ffi::OsStr::to_str);
+                    error.emit_to_string_with_path(input, filename.unwrap_or("input"))
+                }
+                None => format_err(&error),
+            })
+    };
+    let mut info = validate(&module)?;
+    if args.compact {
+        naga::compact::compact(&mut module, naga::compact::KeepUnused::No);
+        info = validate(&module)?;
+    }
+
+    let info = Some(info);
+    for output in outputs {
+        if let Some(parent) = Path::new(output).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        write_output(&module, &info, &params, output)?;
+    }
+    Ok(())
+}
+
 use codespan_reporting::{
     diagnostic::{Diagnostic, Label},
     files::SimpleFile,
@@ -33,6 +33,8 @@ fn parse_glsl(stage: naga::ShaderStage, inputs: &[Box<[u8]>]) {
     let options = naga::front::glsl::Options {
         stage,
         defines: Default::default(),
+        include_resolver: None,
+        entry_point: None,
     };
     for input in inputs.iter() {
         let string = std::str::from_utf8(input).unwrap();
@@ -36,6 +36,8 @@ mod fuzz {
             Options {
                 stage: proxy.stage.into(),
                 defines: proxy.defines,
+                include_resolver: None,
+                entry_point: None,
             }
         }
     }
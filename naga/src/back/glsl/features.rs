@@ -48,6 +48,8 @@ bitflags::bitflags! {
         ///
         /// We can always support this, either through the language or a polyfill
         const INSTANCE_INDEX = 1 << 22;
+        /// Subgroup operations and built-ins
+        const SUBGROUP_OPERATIONS = 1 << 23;
     }
 }
 
@@ -125,6 +127,7 @@ impl FeaturesManager {
         check_feature!(TEXTURE_SAMPLES, 150);
         check_feature!(TEXTURE_LEVELS, 130);
         check_feature!(IMAGE_SIZE, 430, 310);
+        check_feature!(SUBGROUP_OPERATIONS, 430, 310);
 
         // Return an error if there are missing features
         if missing.is_empty() {
@@ -244,6 +247,22 @@ impl FeaturesManager {
             writeln!(out, "#extension GL_EXT_blend_func_extended : require")?;
         }
 
+        if self.0.contains(Features::SUBGROUP_OPERATIONS) {
+            // https://github.com/KhronosGroup/GLSL/blob/main/extensions/khr/GL_KHR_shader_subgroup.txt
+            writeln!(out, "#extension GL_KHR_shader_subgroup_basic : require")?;
+            writeln!(out, "#extension GL_KHR_shader_subgroup_vote : require")?;
+            writeln!(
+                out,
+                "#extension GL_KHR_shader_subgroup_arithmetic : require"
+            )?;
+            writeln!(out, "#extension GL_KHR_shader_subgroup_ballot : require")?;
+            writeln!(out, "#extension GL_KHR_shader_subgroup_shuffle : require")?;
+            writeln!(
+                out,
+                "#extension GL_KHR_shader_subgroup_shuffle_relative : require"
+            )?;
+        }
+
         if self.0.contains(Features::INSTANCE_INDEX) {
             if options.writer_flags.contains(WriterFlags::DRAW_PARAMETERS) {
                 // https://registry.khronos.org/OpenGL/extensions/ARB/ARB_shader_draw_parameters.txt
@@ -396,7 +415,11 @@ impl<'a, W> Writer<'a, W> {
             }
             match global.space {
                 AddressSpace::WorkGroup => self.features.request(Features::COMPUTE_SHADER),
-                AddressSpace::Storage { .. } => self.features.request(Features::BUFFER_STORAGE),
+                AddressSpace::Storage { .. } => {
+                    if !self.storage_as_uniform(global)? {
+                        self.features.request(Features::BUFFER_STORAGE)
+                    }
+                }
                 AddressSpace::PushConstant => {
                     if push_constant_used {
                         return Err(Error::MultiplePushConstants);
@@ -469,6 +492,9 @@ impl<'a, W> Writer<'a, W> {
                         }
                     }
                 }
+                Expression::SubgroupBallotResult | Expression::SubgroupOperationResult { .. } => {
+                    features.request(Features::SUBGROUP_OPERATIONS)
+                }
                 _ => {}
             }
             }
@@ -510,6 +536,12 @@ impl<'a, W> Writer<'a, W> {
                             crate::BuiltIn::InstanceIndex => {
                                 self.features.request(Features::INSTANCE_INDEX)
                             }
+                            crate::BuiltIn::NumSubgroups
+                            | crate::BuiltIn::SubgroupId
+                            | crate::BuiltIn::SubgroupSize
+                            | crate::BuiltIn::SubgroupInvocationId => {
+                                self.features.request(Features::SUBGROUP_OPERATIONS)
+                            }
                             _ => {}
                         },
                         Binding::Location {
@@ -517,6 +549,7 @@ impl<'a, W> Writer<'a, W> {
                             interpolation,
                             sampling,
                             second_blend_source,
+                            patch: _,
                         } => {
                             if interpolation == Some(Interpolation::Linear) {
                                 self.features.request(Features::NOPERSPECTIVE_QUALIFIER);
@@ -533,4 +566,39 @@ impl<'a, W> Writer<'a, W> {
             }
         }
     }
+
+    /// Checks if the storage buffer `global` is written as a uniform block,
+    /// see [`WriterFlags::READ_ONLY_STORAGE_AS_UNIFORM`].
+    ///
+    /// Returns an error if it must be, but can't.
+    pub(super) fn storage_as_uniform(&self, global: &crate::GlobalVariable) -> BackendResult<bool> {
+        let AddressSpace::Storage { access } = global.space else {
+            return Ok(false);
+        };
+        if !self
+            .options
+            .writer_flags
+            .contains(WriterFlags::READ_ONLY_STORAGE_AS_UNIFORM)
+            || self.options.version.supports_storage_buffers()
+        {
+            return Ok(false);
+        }
+
+        let reason = if access.contains(crate::StorageAccess::STORE) {
+            "it isn't read-only"
+        } else if self.module.types[global.ty]
+            .inner
+            .is_dynamically_sized(&self.module.types)
+        {
+            "it is runtime-sized"
+        } else if !super::follows_uniform_layout(self.module, global.ty) {
+            "its type doesn't follow the uniform layout rules"
+        } else {
+            return Ok(true);
+        };
+        Err(Error::StorageAsUniform {
+            name: global.name.clone(),
+            reason,
+        })
+    }
 }
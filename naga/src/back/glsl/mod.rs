@@ -113,7 +113,8 @@ impl crate::AddressSpace {
             | crate::AddressSpace::Uniform
             | crate::AddressSpace::Storage { .. }
             | crate::AddressSpace::Handle
-            | crate::AddressSpace::PushConstant => false,
+            | crate::AddressSpace::PushConstant
+            | crate::AddressSpace::TaskPayload => false,
         }
     }
 }
@@ -185,6 +186,10 @@ impl Version {
         *self >= Version::Desktop(130) || *self >= Version::new_gles(310)
     }
 
+    fn supports_storage_buffers(&self) -> bool {
+        *self >= Version::Desktop(400) || *self >= Version::new_gles(310)
+    }
+
     fn supports_std430_layout(&self) -> bool {
         *self >= Version::Desktop(430) || *self >= Version::new_gles(310)
     }
@@ -252,6 +257,16 @@ bitflags::bitflags! {
         /// The variable gl_PointSize is intended for a shader to write the size of the point to be rasterized. It is measured in pixels.
         /// If gl_PointSize is not written to, its value is undefined in subsequent pipe stages.
         const FORCE_POINT_SIZE = 0x20;
+        /// Write read-only storage buffers as uniform blocks when the version
+        /// has no shader storage buffers, like ES 300.
+        ///
+        /// The buffers must then be bound as uniform buffers. Writable or
+        /// runtime-sized buffers, and buffers whose type doesn't follow the
+        /// uniform layout rules, produce an [`Error::StorageAsUniform`].
+        const READ_ONLY_STORAGE_AS_UNIFORM = 0x40;
+        /// Name temporaries after the variables, struct members and functions
+        /// their values come from, instead of `_e` followed by a number.
+        const READABLE_NAMES = 0x80;
     }
 }
 
@@ -435,7 +450,15 @@ impl fmt::Display for VaryingName<'_> {
             }
             crate::Binding::Location { location, .. } => {
                 let prefix = match (self.stage, self.options.output) {
-                    (ShaderStage::Compute, _) => unreachable!(),
+                    (
+                        ShaderStage::Compute
+                        | ShaderStage::Task
+                        | ShaderStage::Mesh
+                        | ShaderStage::Geometry
+                        | ShaderStage::TessellationControl
+                        | ShaderStage::TessellationEvaluation,
+                        _,
+                    ) => unreachable!(),
                     // pipeline to vertex
                     (ShaderStage::Vertex, false) => "p2vs",
                     // vertex to fragment
@@ -458,6 +481,11 @@ impl ShaderStage {
             ShaderStage::Compute => "cs",
             ShaderStage::Fragment => "fs",
             ShaderStage::Vertex => "vs",
+            ShaderStage::Task => "ts",
+            ShaderStage::Mesh => "ms",
+            ShaderStage::Geometry => "gs",
+            ShaderStage::TessellationControl => "tcs",
+            ShaderStage::TessellationEvaluation => "tes",
         }
     }
 }
@@ -495,6 +523,13 @@ pub enum Error {
     /// A image was used with multiple samplers, which isn't supported.
     #[error("A image was used with multiple samplers")]
     ImageMultipleSamplers,
+    /// A storage buffer couldn't be written as a uniform block, see
+    /// [`WriterFlags::READ_ONLY_STORAGE_AS_UNIFORM`].
+    #[error("Storage buffer {name:?} can't be written as a uniform block because {reason}")]
+    StorageAsUniform {
+        name: Option<String>,
+        reason: &'static str,
+    },
     #[error("{0}")]
     Custom(String),
 }
@@ -571,6 +606,18 @@ impl<'a, W: Write> Writer<'a, W> {
             return Err(Error::VersionNotSupported);
         }
 
+        if let ShaderStage::Task
+        | ShaderStage::Mesh
+        | ShaderStage::Geometry
+        | ShaderStage::TessellationControl
+        | ShaderStage::TessellationEvaluation = pipeline_options.shader_stage
+        {
+            return Err(Error::Custom(format!(
+                "{:?} shaders are not supported",
+                pipeline_options.shader_stage
+            )));
+        }
+
         // Try to find the entry point and corresponding index
         let ep_idx = module
             .entry_points
@@ -1133,11 +1180,17 @@ impl<'a, W: Write> Writer<'a, W> {
         handle: Handle<crate::GlobalVariable>,
         global: &crate::GlobalVariable,
     ) -> BackendResult {
+        let space = if self.storage_as_uniform(global)? {
+            crate::AddressSpace::Uniform
+        } else {
+            global.space
+        };
+
         if self.options.version.supports_explicit_locations() {
             if let Some(ref br) = global.binding {
                 match self.options.binding_map.get(br) {
                     Some(binding) => {
-                        let layout = match global.space {
+                        let layout = match space {
                             crate::AddressSpace::Storage { .. } => {
                                 if self.options.version.supports_std430_layout() {
                                     "std430, "
@@ -1152,7 +1205,7 @@ impl<'a, W: Write> Writer<'a, W> {
                     }
                     None => {
                         log::debug!("unassigned binding for {:?}", global.name);
-                        if let crate::AddressSpace::Storage { .. } = global.space {
+                        if let crate::AddressSpace::Storage { .. } = space {
                             if self.options.version.supports_std430_layout() {
                                 write!(self.out, "layout(std430) ")?
                             }
@@ -1162,15 +1215,15 @@ impl<'a, W: Write> Writer<'a, W> {
             }
         }
 
-        if let crate::AddressSpace::Storage { access } = global.space {
+        if let crate::AddressSpace::Storage { access } = space {
             self.write_storage_access(access)?;
         }
 
-        if let Some(storage_qualifier) = glsl_storage_qualifier(global.space) {
+        if let Some(storage_qualifier) = glsl_storage_qualifier(space) {
             write!(self.out, "{storage_qualifier} ")?;
         }
 
-        match global.space {
+        match space {
             crate::AddressSpace::Private => {
                 self.write_simple_global(handle, global)?;
             }
@@ -1191,6 +1244,9 @@ impl<'a, W: Write> Writer<'a, W> {
             crate::AddressSpace::Function => unreachable!(),
             // Textures and samplers are handled directly in `Writer::write`.
             crate::AddressSpace::Handle => unreachable!(),
+            // Task payloads are only used by task and mesh shaders, which
+            // `Writer::new` rejects.
+            crate::AddressSpace::TaskPayload => unreachable!(),
         }
 
         Ok(())
@@ -1284,6 +1340,33 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Returns the name of the temporary holding the value of `handle`.
+    ///
+    /// With [`WriterFlags::READABLE_NAMES`], this is the name given by the
+    /// frontend, or the readable name of `source`, when there is one.
+    fn bake_name(
+        &mut self,
+        handle: Handle<crate::Expression>,
+        source: Handle<crate::Expression>,
+        ctx: &back::FunctionCtx,
+    ) -> String {
+        if self
+            .options
+            .writer_flags
+            .contains(WriterFlags::READABLE_NAMES)
+        {
+            let name = ctx
+                .named_expressions
+                .get(&handle)
+                .cloned()
+                .or_else(|| ctx.readable_name(source, self.module));
+            if let Some(name) = name {
+                return self.namer.call(&name);
+            }
+        }
+        format!("{}{}", back::BAKE_PREFIX, handle.index())
+    }
+
     /// Helper method used to find which expressions of a given function require baking
     ///
     /// # Notes
@@ -1436,6 +1519,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 interpolation,
                 sampling,
                 second_blend_source,
+                patch: _,
             } => (location, interpolation, sampling, second_blend_source),
             crate::Binding::BuiltIn(built_in) => {
                 if let crate::BuiltIn::Position { invariant: true } = built_in {
@@ -1475,7 +1559,12 @@ impl<'a, W: Write> Writer<'a, W> {
         let emit_interpolation_and_auxiliary = match self.entry_point.stage {
             ShaderStage::Vertex => output,
             ShaderStage::Fragment => !output,
-            ShaderStage::Compute => false,
+            ShaderStage::Compute
+            | ShaderStage::Task
+            | ShaderStage::Mesh
+            | ShaderStage::Geometry
+            | ShaderStage::TessellationControl
+            | ShaderStage::TessellationEvaluation => false,
         };
 
         // Write the I/O locations, if allowed
@@ -1534,6 +1623,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 interpolation: None,
                 sampling: None,
                 second_blend_source,
+                patch: false,
             },
             stage: self.entry_point.stage,
             options: VaryingOptions::from_writer_options(self.options, output),
@@ -1966,7 +2056,7 @@ impl<'a, W: Write> Writer<'a, W> {
                         // Also, we use sanitized names! It defense backend from generating variable with name from reserved keywords.
                         Some(self.namer.call(name))
                     } else if self.need_bake_expressions.contains(&handle) {
-                        Some(format!("{}{}", back::BAKE_PREFIX, handle.index()))
+                        Some(self.bake_name(handle, handle, ctx))
                     } else {
                         None
                     };
@@ -2294,7 +2384,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 // This is done in `Emit` by never emitting a variable name for pointer variables
                 self.write_barrier(crate::Barrier::WORK_GROUP, level)?;
 
-                let result_name = format!("{}{}", back::BAKE_PREFIX, result.index());
+                let result_name = self.bake_name(result, pointer, ctx);
                 write!(self.out, "{level}")?;
                 // Expressions cannot have side effects, so just writing the expression here is fine.
                 self.write_named_expr(pointer, result_name, result, ctx)?;
@@ -2319,7 +2409,7 @@ impl<'a, W: Write> Writer<'a, W> {
             } => {
                 write!(self.out, "{level}")?;
                 if let Some(expr) = result {
-                    let name = format!("{}{}", back::BAKE_PREFIX, expr.index());
+                    let name = self.bake_name(expr, expr, ctx);
                     let result = self.module.functions[function].result.as_ref().unwrap();
                     self.write_type(result.ty)?;
                     write!(self.out, " {name}")?;
@@ -2352,7 +2442,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 result,
             } => {
                 write!(self.out, "{level}")?;
-                let res_name = format!("{}{}", back::BAKE_PREFIX, result.index());
+                let res_name = self.bake_name(result, pointer, ctx);
                 let res_ty = ctx.resolve_type(result, &self.module.types);
                 self.write_value_type(res_ty)?;
                 write!(self.out, " {res_name} = ")?;
@@ -2379,6 +2469,99 @@ impl<'a, W: Write> Writer<'a, W> {
                 writeln!(self.out, ");")?;
             }
             Statement::RayQuery { .. } => unreachable!(),
+            // `debugPrintfEXT` only exists in GLSL targeting Vulkan.
+            Statement::DebugPrintf { .. } => {
+                return Err(Error::Custom(
+                    "debug printf is not supported, it must be lowered to buffer writes".into(),
+                ))
+            }
+            // Geometry shaders are rejected by `Writer::new`.
+            Statement::EmitVertex { .. } | Statement::EndPrimitive => {
+                return Err(Error::Custom(
+                    "geometry shader statements are not supported".into(),
+                ))
+            }
+            Statement::SubgroupBallot { result, predicate } => {
+                write!(self.out, "{level}")?;
+                let res_name = self.bake_name(result, result, ctx);
+                let res_ty = ctx.resolve_type(result, &self.module.types);
+                self.write_value_type(res_ty)?;
+                write!(self.out, " {res_name} = ")?;
+                self.named_expressions.insert(result, res_name);
+
+                write!(self.out, "subgroupBallot(")?;
+                match predicate {
+                    Some(predicate) => self.write_expr(predicate, ctx)?,
+                    None => write!(self.out, "true")?,
+                }
+                writeln!(self.out, ");")?;
+            }
+            Statement::SubgroupCollectiveOperation {
+                op,
+                collective_op,
+                argument,
+                result,
+            } => {
+                write!(self.out, "{level}")?;
+                let res_name = self.bake_name(result, argument, ctx);
+                let res_ty = ctx.resolve_type(result, &self.module.types);
+                self.write_value_type(res_ty)?;
+                write!(self.out, " {res_name} = ")?;
+                self.named_expressions.insert(result, res_name);
+
+                use crate::{CollectiveOperation as co, SubgroupOperation as sg};
+                let fun = match (collective_op, op) {
+                    (co::Reduce, sg::All) => "subgroupAll",
+                    (co::Reduce, sg::Any) => "subgroupAny",
+                    (co::Reduce, sg::Add) => "subgroupAdd",
+                    (co::Reduce, sg::Mul) => "subgroupMul",
+                    (co::Reduce, sg::Max) => "subgroupMax",
+                    (co::Reduce, sg::Min) => "subgroupMin",
+                    (co::Reduce, sg::And) => "subgroupAnd",
+                    (co::Reduce, sg::Or) => "subgroupOr",
+                    (co::Reduce, sg::Xor) => "subgroupXor",
+                    (co::ExclusiveScan, sg::Add) => "subgroupExclusiveAdd",
+                    (co::ExclusiveScan, sg::Mul) => "subgroupExclusiveMul",
+                    (co::InclusiveScan, sg::Add) => "subgroupInclusiveAdd",
+                    (co::InclusiveScan, sg::Mul) => "subgroupInclusiveMul",
+                    _ => {
+                        return Err(Error::Custom(format!(
+                            "invalid subgroup operation {op:?} for {collective_op:?}"
+                        )))
+                    }
+                };
+                write!(self.out, "{fun}(")?;
+                self.write_expr(argument, ctx)?;
+                writeln!(self.out, ");")?;
+            }
+            Statement::SubgroupGather {
+                mode,
+                argument,
+                result,
+            } => {
+                write!(self.out, "{level}")?;
+                let res_name = self.bake_name(result, argument, ctx);
+                let res_ty = ctx.resolve_type(result, &self.module.types);
+                self.write_value_type(res_ty)?;
+                write!(self.out, " {res_name} = ")?;
+                self.named_expressions.insert(result, res_name);
+
+                let (fun, index) = match mode {
+                    crate::GatherMode::BroadcastFirst => ("subgroupBroadcastFirst", None),
+                    crate::GatherMode::Broadcast(index) => ("subgroupBroadcast", Some(index)),
+                    crate::GatherMode::Shuffle(index) => ("subgroupShuffle", Some(index)),
+                    crate::GatherMode::ShuffleDown(index) => ("subgroupShuffleDown", Some(index)),
+                    crate::GatherMode::ShuffleUp(index) => ("subgroupShuffleUp", Some(index)),
+                    crate::GatherMode::ShuffleXor(index) => ("subgroupShuffleXor", Some(index)),
+                };
+                write!(self.out, "{fun}(")?;
+                self.write_expr(argument, ctx)?;
+                if let Some(index) = index {
+                    write!(self.out, ", ")?;
+                    self.write_expr(index, ctx)?;
+                }
+                writeln!(self.out, ");")?;
+            }
         }
 
         Ok(())
@@ -2448,9 +2631,12 @@ impl<'a, W: Write> Writer<'a, W> {
                     crate::Literal::U32(value) => write!(self.out, "{}u", value)?,
                     crate::Literal::I32(value) => write!(self.out, "{}", value)?,
                     crate::Literal::Bool(value) => write!(self.out, "{}", value)?,
-                    crate::Literal::I64(_) => {
+                    crate::Literal::I64(_) | crate::Literal::U64(_) => {
                         return Err(Error::Custom("GLSL has no 64-bit integer type".into()));
                     }
+                    crate::Literal::F16(_) => {
+                        return Err(Error::UnsupportedScalar(crate::Scalar::F16));
+                    }
                     crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
                         return Err(Error::Custom(
                             "Abstract types should not appear in IR presented to backends".into(),
@@ -3575,7 +3761,9 @@ impl<'a, W: Write> Writer<'a, W> {
             Expression::CallResult(_)
             | Expression::AtomicResult { .. }
             | Expression::RayQueryProceedResult
-            | Expression::WorkGroupUniformLoadResult { .. } => unreachable!(),
+            | Expression::WorkGroupUniformLoadResult { .. }
+            | Expression::SubgroupBallotResult
+            | Expression::SubgroupOperationResult { .. } => unreachable!(),
             // `ArrayLength` is written as `expr.length()` and we convert it to a uint
             Expression::ArrayLength(expr) => {
                 write!(self.out, "uint(")?;
@@ -4144,6 +4332,9 @@ impl<'a, W: Write> Writer<'a, W> {
         if flags.contains(crate::Barrier::WORK_GROUP) {
             writeln!(self.out, "{level}memoryBarrierShared();")?;
         }
+        if flags.contains(crate::Barrier::SUB_GROUP) {
+            writeln!(self.out, "{level}subgroupMemoryBarrier();")?;
+        }
         writeln!(self.out, "{level}barrier();")?;
         Ok(())
     }
@@ -4413,6 +4604,54 @@ const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'s
         Bi::WorkGroupId => "gl_WorkGroupID",
         Bi::WorkGroupSize => "gl_WorkGroupSize",
         Bi::NumWorkGroups => "gl_NumWorkGroups",
+        // geometry
+        Bi::InvocationId => "gl_InvocationID",
+        // tessellation
+        Bi::TessLevelOuter => "gl_TessLevelOuter",
+        Bi::TessLevelInner => "gl_TessLevelInner",
+        Bi::TessCoord => "gl_TessCoord",
+        Bi::PatchVertices => "gl_PatchVerticesIn",
+        // subgroup
+        Bi::NumSubgroups => "gl_NumSubgroups",
+        Bi::SubgroupId => "gl_SubgroupID",
+        Bi::SubgroupSize => "gl_SubgroupSize",
+        Bi::SubgroupInvocationId => "gl_SubgroupInvocationID",
+        // task and mesh shaders are rejected by `Writer::new`
+        Bi::MeshTaskSize
+        | Bi::Vertices
+        | Bi::Primitives
+        | Bi::VertexCount
+        | Bi::PrimitiveCount
+        | Bi::PointIndex
+        | Bi::LineIndices
+        | Bi::TriangleIndices
+        | Bi::CullPrimitive => unreachable!(),
+    }
+}
+
+/// Checks if the `std140` layout of `ty` matches the offsets and strides
+/// given in the IR, which follow the `std430` rules for storage buffers.
+fn follows_uniform_layout(module: &crate::Module, ty: Handle<crate::Type>) -> bool {
+    match module.types[ty].inner {
+        TypeInner::Scalar(_) | TypeInner::Vector { .. } => true,
+        // The columns of `std140` matrices are aligned to 16 bytes.
+        TypeInner::Matrix { rows, .. } => rows != crate::VectorSize::Bi,
+        TypeInner::Array { base, stride, .. } => {
+            stride % 16 == 0 && follows_uniform_layout(module, base)
+        }
+        TypeInner::Struct { ref members, span } => {
+            span % 16 == 0
+                && members.iter().all(|member| {
+                    let aligned = match module.types[member.ty].inner {
+                        TypeInner::Array { .. } | TypeInner::Struct { .. } => {
+                            member.offset % 16 == 0
+                        }
+                        _ => true,
+                    };
+                    aligned && follows_uniform_layout(module, member.ty)
+                })
+        }
+        _ => false,
     }
 }
 
@@ -4428,6 +4667,7 @@ const fn glsl_storage_qualifier(space: crate::AddressSpace) -> Option<&'static s
         As::Handle => Some("uniform"),
         As::WorkGroup => Some("shared"),
         As::PushConstant => Some("uniform"),
+        As::TaskPayload => Some("taskPayloadSharedEXT"),
     }
 }
 
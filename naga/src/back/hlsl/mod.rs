@@ -131,6 +131,12 @@ pub enum ShaderModel {
     V5_0,
     V5_1,
     V6_0,
+    V6_1,
+    V6_2,
+    V6_3,
+    V6_4,
+    V6_5,
+    V6_6,
 }
 
 impl ShaderModel {
@@ -139,6 +145,12 @@ impl ShaderModel {
             Self::V5_0 => "5_0",
             Self::V5_1 => "5_1",
             Self::V6_0 => "6_0",
+            Self::V6_1 => "6_1",
+            Self::V6_2 => "6_2",
+            Self::V6_3 => "6_3",
+            Self::V6_4 => "6_4",
+            Self::V6_5 => "6_5",
+            Self::V6_6 => "6_6",
         }
     }
 }
@@ -149,6 +161,11 @@ impl crate::ShaderStage {
             Self::Vertex => "vs",
             Self::Fragment => "ps",
             Self::Compute => "cs",
+            Self::Task => "as",
+            Self::Mesh => "ms",
+            Self::Geometry => "gs",
+            Self::TessellationControl => "hs",
+            Self::TessellationEvaluation => "ds",
         }
     }
 }
@@ -193,6 +210,20 @@ pub struct Options {
     pub push_constants_target: Option<BindTarget>,
     /// Should workgroup variables be zero initialized (by polyfilling)?
     pub zero_initialize_workgroup_memory: bool,
+    /// Access binding arrays through `ResourceDescriptorHeap` and
+    /// `SamplerDescriptorHeap` instead of declaring them as registers.
+    ///
+    /// The register of a binding array's bind target is used as the index of
+    /// its first descriptor in the heap, and the space is ignored. Indices are
+    /// always treated as non-uniform.
+    ///
+    /// Requires shader model 6.6.
+    #[cfg_attr(feature = "deserialize", serde(default))]
+    pub descriptor_heap_binding_arrays: bool,
+    /// Name temporaries after the variables, struct members and functions
+    /// their values come from, instead of `_e` or `_expr` followed by a number.
+    #[cfg_attr(feature = "deserialize", serde(default))]
+    pub readable_names: bool,
 }
 
 impl Default for Options {
@@ -204,6 +235,8 @@ impl Default for Options {
             special_constants_binding: None,
             push_constants_target: None,
             zero_initialize_workgroup_memory: true,
+            descriptor_heap_binding_arrays: false,
+            readable_names: false,
         }
     }
 }
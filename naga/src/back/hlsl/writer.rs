@@ -1,7 +1,7 @@
 use super::{
     help::{WrappedArrayLength, WrappedConstructor, WrappedImageQuery, WrappedStructMatrixAccess},
     storage::StoreValue,
-    BackendResult, Error, Options,
+    BackendResult, Error, Options, ShaderModel,
 };
 use crate::{
     back,
@@ -106,6 +106,27 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         self.need_bake_expressions.clear();
     }
 
+    /// Returns a readable name for the temporary holding the value of `handle`
+    /// if [`Options::readable_names`] is set: the name given by the frontend,
+    /// or the readable name of `source`, when there is one.
+    fn readable_bake_name(
+        &mut self,
+        module: &Module,
+        handle: Handle<crate::Expression>,
+        source: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx,
+    ) -> Option<String> {
+        if !self.options.readable_names {
+            return None;
+        }
+        let name = func_ctx
+            .named_expressions
+            .get(&handle)
+            .cloned()
+            .or_else(|| func_ctx.readable_name(source, module))?;
+        Some(self.namer.call(&name))
+    }
+
     /// Helper method used to find which expressions of a given function require baking
     ///
     /// # Notes
@@ -184,6 +205,28 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         module: &Module,
         module_info: &valid::ModuleInfo,
     ) -> Result<super::ReflectionInfo, Error> {
+        if self.options.descriptor_heap_binding_arrays
+            && self.options.shader_model < ShaderModel::V6_6
+        {
+            return Err(Error::Custom(
+                "Accessing binding arrays through the descriptor heap requires shader model 6.6"
+                    .to_string(),
+            ));
+        }
+
+        if let Some(ep) = module.entry_points.iter().find(|ep| {
+            matches!(
+                ep.stage,
+                ShaderStage::Task
+                    | ShaderStage::Mesh
+                    | ShaderStage::Geometry
+                    | ShaderStage::TessellationControl
+                    | ShaderStage::TessellationEvaluation
+            )
+        }) {
+            return Err(Error::Unimplemented(format!("{:?} shaders", ep.stage)));
+        }
+
         self.reset(module);
 
         // Write special constants, if needed
@@ -637,6 +680,39 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    /// Helper method used to write the function that replaces the declaration of
+    /// a binding array when it's accessed through the descriptor heap
+    ///
+    /// The function is given the name of the binding array, so accesses only
+    /// need to call it instead of indexing the array.
+    ///
+    /// # Notes
+    /// Always adds a newline
+    fn write_descriptor_heap_accessor(
+        &mut self,
+        module: &Module,
+        handle: Handle<crate::GlobalVariable>,
+        base: Handle<crate::Type>,
+        first_descriptor: u32,
+    ) -> BackendResult {
+        let heap = match module.types[base].inner {
+            TypeInner::Sampler { .. } => "SamplerDescriptorHeap",
+            _ => "ResourceDescriptorHeap",
+        };
+
+        self.write_type(module, base)?;
+        let name = &self.names[&NameKey::GlobalVariable(handle)];
+        writeln!(self.out, " {name}(uint index) {{")?;
+        writeln!(
+            self.out,
+            "{}return {heap}[NonUniformResourceIndex({first_descriptor}u + index)];",
+            back::INDENT
+        )?;
+        writeln!(self.out, "}}")?;
+
+        Ok(())
+    }
+
     /// Helper method used to write global variables
     /// # Notes
     /// Always adds a newline
@@ -660,6 +736,17 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             }
         }
 
+        if self.options.descriptor_heap_binding_arrays
+            && global.space == crate::AddressSpace::Handle
+        {
+            if let (Some(binding), &TypeInner::BindingArray { base, .. }) =
+                (global.binding.as_ref(), inner)
+            {
+                let bt = self.options.resolve_resource_binding(binding).unwrap();
+                return self.write_descriptor_heap_accessor(module, handle, base, bt.register);
+            }
+        }
+
         // https://docs.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-variable-register
         let register_ty = match global.space {
             crate::AddressSpace::Function => unreachable!("Function address space"),
@@ -711,6 +798,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 write!(self.out, "ConstantBuffer<")?;
                 "b"
             }
+            crate::AddressSpace::TaskPayload => {
+                return Err(Error::Unimplemented("task payloads".to_string()))
+            }
         };
 
         // If the global is a push constant write the type now because it will be a
@@ -1329,7 +1419,10 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         // Also, we use sanitized names! It defense backend from generating variable with name from reserved keywords.
                         Some(self.namer.call(name))
                     } else if self.need_bake_expressions.contains(&handle) {
-                        Some(format!("_expr{}", handle.index()))
+                        Some(
+                            self.readable_bake_name(module, handle, handle, func_ctx)
+                                .unwrap_or_else(|| format!("_expr{}", handle.index())),
+                        )
                     } else {
                         None
                     };
@@ -1810,7 +1903,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 write!(self.out, "{level}")?;
                 if let Some(expr) = result {
                     write!(self.out, "const ")?;
-                    let name = format!("{}{}", back::BAKE_PREFIX, expr.index());
+                    let name = self
+                        .readable_bake_name(module, expr, expr, func_ctx)
+                        .unwrap_or_else(|| format!("{}{}", back::BAKE_PREFIX, expr.index()));
                     let expr_ty = &func_ctx.info[expr].ty;
                     match *expr_ty {
                         proc::TypeResolution::Handle(handle) => self.write_type(module, handle)?,
@@ -1838,7 +1933,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 result,
             } => {
                 write!(self.out, "{level}")?;
-                let res_name = format!("{}{}", back::BAKE_PREFIX, result.index());
+                let res_name = self
+                    .readable_bake_name(module, result, pointer, func_ctx)
+                    .unwrap_or_else(|| format!("{}{}", back::BAKE_PREFIX, result.index()));
                 match func_ctx.info[result].ty {
                     proc::TypeResolution::Handle(handle) => self.write_type(module, handle)?,
                     proc::TypeResolution::Value(ref value) => {
@@ -1895,7 +1992,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             Statement::WorkGroupUniformLoad { pointer, result } => {
                 self.write_barrier(crate::Barrier::WORK_GROUP, level)?;
                 write!(self.out, "{level}")?;
-                let name = format!("_expr{}", result.index());
+                let name = self
+                    .readable_bake_name(module, result, pointer, func_ctx)
+                    .unwrap_or_else(|| format!("_expr{}", result.index()));
                 self.write_named_expr(module, pointer, name, result, func_ctx)?;
 
                 self.write_barrier(crate::Barrier::WORK_GROUP, level)?;
@@ -2000,6 +2099,100 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 writeln!(self.out, "{level}}}")?
             }
             Statement::RayQuery { .. } => unreachable!(),
+            Statement::DebugPrintf { .. } => {
+                return Err(Error::Unimplemented(
+                    "debug printf, it must be lowered to buffer writes".into(),
+                ))
+            }
+            Statement::EmitVertex { .. } | Statement::EndPrimitive => {
+                return Err(Error::Unimplemented("geometry shaders".into()))
+            }
+            Statement::SubgroupBallot { result, predicate } => {
+                write!(self.out, "{level}")?;
+                let name = format!("{}{}", back::BAKE_PREFIX, result.index());
+                write!(self.out, "const uint4 {name} = ")?;
+                self.named_expressions.insert(result, name);
+
+                write!(self.out, "WaveActiveBallot(")?;
+                match predicate {
+                    Some(predicate) => self.write_expr(module, predicate, func_ctx)?,
+                    None => write!(self.out, "true")?,
+                }
+                writeln!(self.out, ");")?;
+            }
+            Statement::SubgroupCollectiveOperation {
+                op,
+                collective_op,
+                argument,
+                result,
+            } => {
+                self.write_subgroup_result_declaration(module, result, level, func_ctx)?;
+
+                use crate::{CollectiveOperation as co, SubgroupOperation as sg};
+                match (collective_op, op) {
+                    (co::Reduce, sg::All) => write!(self.out, "WaveActiveAllTrue(")?,
+                    (co::Reduce, sg::Any) => write!(self.out, "WaveActiveAnyTrue(")?,
+                    (co::Reduce, sg::Add) => write!(self.out, "WaveActiveSum(")?,
+                    (co::Reduce, sg::Mul) => write!(self.out, "WaveActiveProduct(")?,
+                    (co::Reduce, sg::Max) => write!(self.out, "WaveActiveMax(")?,
+                    (co::Reduce, sg::Min) => write!(self.out, "WaveActiveMin(")?,
+                    (co::Reduce, sg::And) => write!(self.out, "WaveActiveBitAnd(")?,
+                    (co::Reduce, sg::Or) => write!(self.out, "WaveActiveBitOr(")?,
+                    (co::Reduce, sg::Xor) => write!(self.out, "WaveActiveBitXor(")?,
+                    (co::ExclusiveScan, sg::Add) => write!(self.out, "WavePrefixSum(")?,
+                    (co::ExclusiveScan, sg::Mul) => write!(self.out, "WavePrefixProduct(")?,
+                    // HLSL only has exclusive scans, so combine them with the
+                    // value of the invocation itself.
+                    (co::InclusiveScan, sg::Add) => {
+                        self.write_expr(module, argument, func_ctx)?;
+                        write!(self.out, " + WavePrefixSum(")?;
+                    }
+                    (co::InclusiveScan, sg::Mul) => {
+                        self.write_expr(module, argument, func_ctx)?;
+                        write!(self.out, " * WavePrefixProduct(")?;
+                    }
+                    _ => {
+                        return Err(Error::Custom(format!(
+                            "invalid subgroup operation {op:?} for {collective_op:?}"
+                        )))
+                    }
+                }
+                self.write_expr(module, argument, func_ctx)?;
+                writeln!(self.out, ");")?;
+            }
+            Statement::SubgroupGather {
+                mode,
+                argument,
+                result,
+            } => {
+                self.write_subgroup_result_declaration(module, result, level, func_ctx)?;
+
+                match mode {
+                    crate::GatherMode::BroadcastFirst => write!(self.out, "WaveReadLaneFirst(")?,
+                    _ => write!(self.out, "WaveReadLaneAt(")?,
+                }
+                self.write_expr(module, argument, func_ctx)?;
+                match mode {
+                    crate::GatherMode::BroadcastFirst => {}
+                    crate::GatherMode::Broadcast(index) | crate::GatherMode::Shuffle(index) => {
+                        write!(self.out, ", ")?;
+                        self.write_expr(module, index, func_ctx)?;
+                    }
+                    crate::GatherMode::ShuffleDown(index) => {
+                        write!(self.out, ", WaveGetLaneIndex() + ")?;
+                        self.write_expr(module, index, func_ctx)?;
+                    }
+                    crate::GatherMode::ShuffleUp(index) => {
+                        write!(self.out, ", WaveGetLaneIndex() - ")?;
+                        self.write_expr(module, index, func_ctx)?;
+                    }
+                    crate::GatherMode::ShuffleXor(index) => {
+                        write!(self.out, ", WaveGetLaneIndex() ^ ")?;
+                        self.write_expr(module, index, func_ctx)?;
+                    }
+                }
+                writeln!(self.out, ");")?;
+            }
         }
 
         Ok(())
@@ -2036,9 +2229,11 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 // decimal part even it's zero
                 crate::Literal::F64(value) => write!(self.out, "{value:?}L")?,
                 crate::Literal::F32(value) => write!(self.out, "{value:?}")?,
+                crate::Literal::F16(value) => write!(self.out, "{:?}h", f32::from(value))?,
                 crate::Literal::U32(value) => write!(self.out, "{}u", value)?,
                 crate::Literal::I32(value) => write!(self.out, "{}", value)?,
                 crate::Literal::I64(value) => write!(self.out, "{}L", value)?,
+                crate::Literal::U64(value) => write!(self.out, "{}uL", value)?,
                 crate::Literal::Bool(value) => write!(self.out, "{}", value)?,
                 crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
                     return Err(Error::Custom(
@@ -2245,6 +2440,15 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     };
 
                     self.write_expr(module, base, func_ctx)?;
+                    if self.options.descriptor_heap_binding_arrays
+                        && matches!(*resolved, TypeInner::BindingArray { .. })
+                    {
+                        // The binding array was replaced by an accessor function
+                        write!(self.out, "(")?;
+                        self.write_expr(module, index, func_ctx)?;
+                        write!(self.out, ")")?;
+                        return Ok(());
+                    }
                     write!(self.out, "[")?;
                     if non_uniform_qualifier {
                         write!(self.out, "NonUniformResourceIndex(")?;
@@ -2278,6 +2482,11 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                                 // Write vector access as a swizzle
                                 write!(writer.out, ".{}", back::COMPONENTS[index as usize])?
                             }
+                            TypeInner::BindingArray { .. }
+                                if writer.options.descriptor_heap_binding_arrays =>
+                            {
+                                write!(writer.out, "({index})")?
+                            }
                             TypeInner::Matrix { .. }
                             | TypeInner::Array { .. }
                             | TypeInner::BindingArray { .. } => write!(writer.out, "[{index}]")?,
@@ -3158,7 +3367,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             Expression::CallResult(_)
             | Expression::AtomicResult { .. }
             | Expression::WorkGroupUniformLoadResult { .. }
-            | Expression::RayQueryProceedResult => {}
+            | Expression::RayQueryProceedResult
+            | Expression::SubgroupBallotResult
+            | Expression::SubgroupOperationResult { .. } => {}
         }
 
         if !closing_bracket.is_empty() {
@@ -3218,6 +3429,26 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    /// Writes the start of the declaration of the baked `result` of a
+    /// subgroup statement, up to its initializer.
+    fn write_subgroup_result_declaration(
+        &mut self,
+        module: &Module,
+        result: Handle<crate::Expression>,
+        level: back::Level,
+        func_ctx: &back::FunctionCtx,
+    ) -> BackendResult {
+        write!(self.out, "{level}const ")?;
+        let name = format!("{}{}", back::BAKE_PREFIX, result.index());
+        match func_ctx.info[result].ty {
+            proc::TypeResolution::Handle(handle) => self.write_type(module, handle)?,
+            proc::TypeResolution::Value(ref value) => self.write_value_type(module, value)?,
+        };
+        write!(self.out, " {name} = ")?;
+        self.named_expressions.insert(result, name);
+        Ok(())
+    }
+
     fn write_barrier(&mut self, barrier: crate::Barrier, level: back::Level) -> BackendResult {
         if barrier.contains(crate::Barrier::STORAGE) {
             writeln!(self.out, "{level}DeviceMemoryBarrierWithGroupSync();")?;
@@ -3364,3 +3595,27 @@ fn get_inner_matrix_of_global_uniform(
     }
     None
 }
+
+#[test]
+fn test_descriptor_heap_shader_model() {
+    use crate::valid::{Capabilities, ValidationFlags};
+    let module = crate::Module::default();
+    let info = crate::valid::Validator::new(ValidationFlags::empty(), Capabilities::empty())
+        .validate(&module)
+        .unwrap();
+
+    let mut options = Options {
+        shader_model: ShaderModel::V6_5,
+        descriptor_heap_binding_arrays: true,
+        ..Default::default()
+    };
+    let mut buffer = String::new();
+    let result = super::Writer::new(&mut buffer, &options).write(&module, &info);
+    assert!(matches!(result, Err(Error::Custom(_))));
+
+    options.shader_model = ShaderModel::V6_6;
+    let mut buffer = String::new();
+    super::Writer::new(&mut buffer, &options)
+        .write(&module, &info)
+        .unwrap();
+}
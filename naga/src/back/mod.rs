@@ -153,6 +153,67 @@ impl FunctionCtx<'_> {
     }
 }
 
+impl FunctionCtx<'_> {
+    /// Returns a readable name for the value of `expression`, see
+    /// [`readable_name`].
+    fn readable_name(
+        &self,
+        expression: crate::Handle<crate::Expression>,
+        module: &crate::Module,
+    ) -> Option<String> {
+        let function = match self.ty {
+            FunctionType::Function(handle) => &module.functions[handle],
+            FunctionType::EntryPoint(index) => &module.entry_points[index as usize].function,
+        };
+        readable_name(module, function, self.info, expression)
+    }
+}
+
+/// Returns a readable name for the value of `expression`, made of the names of
+/// the variables, struct members and functions it comes from.
+///
+/// Backends use it for the temporaries they would otherwise name with
+/// [`BAKE_PREFIX`], when asked to keep the names readable.
+fn readable_name(
+    module: &crate::Module,
+    function: &crate::Function,
+    info: &crate::valid::FunctionInfo,
+    expression: crate::Handle<crate::Expression>,
+) -> Option<String> {
+    use crate::Expression as Ex;
+
+    match function.expressions[expression] {
+        Ex::LocalVariable(handle) => function.local_variables[handle].name.clone(),
+        Ex::GlobalVariable(handle) => module.global_variables[handle].name.clone(),
+        Ex::FunctionArgument(index) => function.arguments[index as usize].name.clone(),
+        Ex::CallResult(handle) => module.functions[handle].name.clone(),
+        Ex::AccessIndex { base, index } => {
+            let base_name = readable_name(module, function, info, base)?;
+            let inner = match *info[base].ty.inner_with(&module.types) {
+                crate::TypeInner::Pointer { base, .. } => &module.types[base].inner,
+                ref other => other,
+            };
+            let member = match *inner {
+                crate::TypeInner::Struct { ref members, .. } => {
+                    members[index as usize].name.as_ref()
+                }
+                _ => None,
+            };
+            Some(match member {
+                Some(member) => format!("{base_name}_{member}"),
+                None => base_name,
+            })
+        }
+        Ex::Access { base, .. } | Ex::Load { pointer: base } => {
+            readable_name(module, function, info, base)
+        }
+        Ex::ImageSample { image, .. } | Ex::ImageLoad { image, .. } => {
+            readable_name(module, function, info, image)
+        }
+        _ => None,
+    }
+}
+
 impl crate::Expression {
     /// Returns the ref count, upon reaching which this expression
     /// should be considered for baking.
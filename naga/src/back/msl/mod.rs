@@ -75,6 +75,10 @@ pub struct EntryPointResources {
     /// one for the size of each bound buffer that contains a runtime array,
     /// in order of [`crate::GlobalVariable`] declarations.
     pub sizes_buffer: Option<Slot>,
+
+    /// The slots of the argument buffers holding the resources of each bind
+    /// group, indexed by group, if [`Options::argument_buffers`] is enabled.
+    pub argument_buffers: std::collections::BTreeMap<u32, Slot>,
 }
 
 pub type EntryPointResourceMap = std::collections::BTreeMap<String, EntryPointResources>;
@@ -143,6 +147,8 @@ pub enum Error {
     UnsupportedArrayOfType(Handle<crate::Type>),
     #[error("ray tracing is not supported prior to MSL 2.3")]
     UnsupportedRayTracing,
+    #[error("argument buffers are not supported prior to MSL 2.0")]
+    UnsupportedArgumentBuffers,
 }
 
 #[derive(Clone, Debug, PartialEq, thiserror::Error)]
@@ -157,6 +163,10 @@ pub enum EntryPointError {
     MissingPushConstants,
     #[error("mapping for sizes buffer is missing")]
     MissingSizesBuffer,
+    #[error("mapping for the argument buffer of group {0} is missing")]
+    MissingArgumentBuffer(u32),
+    #[error("{0:?} shaders are not supported")]
+    UnsupportedStage(crate::ShaderStage),
 }
 
 /// Points in the MSL code where we might emit a pipeline input or output.
@@ -204,6 +214,19 @@ pub struct Options {
     pub bounds_check_policies: index::BoundsCheckPolicies,
     /// Should workgroup variables be zero initialized (by polyfilling)?
     pub zero_initialize_workgroup_memory: bool,
+    /// Pass the resources of each bind group in a (tier 2) argument buffer,
+    /// bound to the slot in [`EntryPointResources::argument_buffers`].
+    ///
+    /// Each resource is given the `binding` of its [`crate::ResourceBinding`]
+    /// as its id in the argument buffer, and binding arrays take up one id per
+    /// element. Inline samplers, push constants and the sizes buffer are still
+    /// passed separately.
+    #[cfg_attr(feature = "deserialize", serde(default))]
+    pub argument_buffers: bool,
+    /// Name temporaries after the variables, struct members and functions
+    /// their values come from, instead of `_e` followed by a number.
+    #[cfg_attr(feature = "deserialize", serde(default))]
+    pub readable_names: bool,
 }
 
 impl Default for Options {
@@ -216,6 +239,8 @@ impl Default for Options {
             fake_missing_bindings: true,
             bounds_check_policies: index::BoundsCheckPolicies::default(),
             zero_initialize_workgroup_memory: true,
+            argument_buffers: false,
+            readable_names: false,
         }
     }
 }
@@ -275,6 +300,7 @@ impl Options {
                 interpolation,
                 sampling,
                 second_blend_source,
+                patch: _,
             } => match mode {
                 LocationMode::VertexInput => Ok(ResolvedBinding::Attribute(location)),
                 LocationMode::FragmentOutput => {
@@ -368,6 +394,28 @@ impl Options {
         }
     }
 
+    fn resolve_argument_buffer(
+        &self,
+        ep: &crate::EntryPoint,
+        group: u32,
+    ) -> Result<ResolvedBinding, EntryPointError> {
+        let slot = self
+            .get_entry_point_resources(ep)
+            .and_then(|res| res.argument_buffers.get(&group).copied());
+        match slot {
+            Some(slot) => Ok(ResolvedBinding::Resource(BindTarget {
+                buffer: Some(slot),
+                ..Default::default()
+            })),
+            None if self.fake_missing_bindings => Ok(ResolvedBinding::User {
+                prefix: "fake",
+                index: 0,
+                interpolation: None,
+            }),
+            None => Err(EntryPointError::MissingArgumentBuffer(group)),
+        }
+    }
+
     fn resolve_sizes_buffer(
         &self,
         ep: &crate::EntryPoint,
@@ -437,9 +485,27 @@ impl ResolvedBinding {
                     Bi::WorkGroupId => "threadgroup_position_in_grid",
                     Bi::WorkGroupSize => "dispatch_threads_per_threadgroup",
                     Bi::NumWorkGroups => "threadgroups_per_grid",
-                    Bi::CullDistance | Bi::ViewIndex => {
-                        return Err(Error::UnsupportedBuiltIn(built_in))
-                    }
+                    // subgroup
+                    Bi::NumSubgroups => "simdgroups_per_threadgroup",
+                    Bi::SubgroupId => "simdgroup_index_in_threadgroup",
+                    Bi::SubgroupSize => "threads_per_simdgroup",
+                    Bi::SubgroupInvocationId => "thread_index_in_simdgroup",
+                    Bi::CullDistance
+                    | Bi::ViewIndex
+                    | Bi::InvocationId
+                    | Bi::TessLevelOuter
+                    | Bi::TessLevelInner
+                    | Bi::TessCoord
+                    | Bi::PatchVertices
+                    | Bi::MeshTaskSize
+                    | Bi::Vertices
+                    | Bi::Primitives
+                    | Bi::VertexCount
+                    | Bi::PrimitiveCount
+                    | Bi::PointIndex
+                    | Bi::LineIndices
+                    | Bi::TriangleIndices
+                    | Bi::CullPrimitive => return Err(Error::UnsupportedBuiltIn(built_in)),
                 };
                 write!(out, "{name}")?;
             }
@@ -239,6 +239,9 @@ struct TypedGlobalVariable<'a> {
     usage: valid::GlobalUse,
     binding: Option<&'a super::ResolvedBinding>,
     reference: bool,
+    /// Declare the variable as a pointer instead of a reference, like the
+    /// members of argument buffers. Only applies if `reference` is set.
+    pointer: bool,
 }
 
 impl<'a> TypedGlobalVariable<'a> {
@@ -283,7 +286,7 @@ impl<'a> TypedGlobalVariable<'a> {
                 } else {
                     ""
                 };
-                (space, access, "&")
+                (space, access, if self.pointer { "*" } else { "&" })
             }
             _ => ("", "", ""),
         };
@@ -322,10 +325,22 @@ impl crate::Scalar {
     const fn to_msl_name(self) -> &'static str {
         use crate::ScalarKind as Sk;
         match self {
+            Self {
+                kind: Sk::Float,
+                width: 2,
+            } => "half",
             Self {
                 kind: Sk::Float,
                 width: _,
             } => "float",
+            Self {
+                kind: Sk::Sint,
+                width: 8,
+            } => "long",
+            Self {
+                kind: Sk::Uint,
+                width: 8,
+            } => "ulong",
             Self {
                 kind: Sk::Sint,
                 width: _,
@@ -412,7 +427,8 @@ impl crate::AddressSpace {
             | Self::Private
             | Self::WorkGroup
             | Self::PushConstant
-            | Self::Handle => true,
+            | Self::Handle
+            | Self::TaskPayload => true,
             Self::Function => false,
         }
     }
@@ -426,7 +442,7 @@ impl crate::AddressSpace {
             // and that should be OK.
             Self::Storage { .. } => true,
             // These should always be read-write.
-            Self::Private | Self::WorkGroup => false,
+            Self::Private | Self::WorkGroup | Self::TaskPayload => false,
             // These translate to `constant` address space, no need for qualifiers.
             Self::Uniform | Self::PushConstant => false,
             // Not applicable.
@@ -441,6 +457,7 @@ impl crate::AddressSpace {
             Self::Storage { .. } => Some("device"),
             Self::Private | Self::Function => Some("thread"),
             Self::WorkGroup => Some("threadgroup"),
+            Self::TaskPayload => Some("object_data"),
         }
     }
 }
@@ -515,6 +532,8 @@ struct ExpressionContext<'a> {
     pipeline_options: &'a PipelineOptions,
     lang_version: (u8, u8),
     policies: index::BoundsCheckPolicies,
+    /// Copied from [`Options::readable_names`].
+    readable_names: bool,
 
     /// A bitset containing the `Expression` handle indexes of expressions used
     /// as indices in `ReadZeroSkipWrite`-policy accesses. These may need to be
@@ -1267,6 +1286,9 @@ impl<W: Write> Writer<W> {
                         write!(self.out, "{value}{suffix}")?;
                     }
                 }
+                crate::Literal::F16(value) => {
+                    write!(self.out, "{:?}h", f32::from(value))?;
+                }
                 crate::Literal::U32(value) => {
                     write!(self.out, "{value}u")?;
                 }
@@ -1276,6 +1298,9 @@ impl<W: Write> Writer<W> {
                 crate::Literal::I64(value) => {
                     write!(self.out, "{value}L")?;
                 }
+                crate::Literal::U64(value) => {
+                    write!(self.out, "{value}uL")?;
+                }
                 crate::Literal::Bool(value) => {
                     write!(self.out, "{value}")?;
                 }
@@ -1961,7 +1986,9 @@ impl<W: Write> Writer<W> {
             crate::Expression::CallResult(_)
             | crate::Expression::AtomicResult { .. }
             | crate::Expression::WorkGroupUniformLoadResult { .. }
-            | crate::Expression::RayQueryProceedResult => {
+            | crate::Expression::RayQueryProceedResult
+            | crate::Expression::SubgroupBallotResult
+            | crate::Expression::SubgroupOperationResult { .. } => {
                 unreachable!()
             }
             crate::Expression::ArrayLength(expr) => {
@@ -2530,6 +2557,42 @@ impl<W: Write> Writer<W> {
         }
     }
 
+    /// Returns the name of the temporary holding the value of `handle`.
+    ///
+    /// This is its [`Self::readable_bake_name`], if there is one.
+    fn bake_name(
+        &mut self,
+        handle: Handle<crate::Expression>,
+        source: Handle<crate::Expression>,
+        context: &ExpressionContext,
+    ) -> String {
+        self.readable_bake_name(handle, source, context)
+            .unwrap_or_else(|| format!("{}{}", back::BAKE_PREFIX, handle.index()))
+    }
+
+    /// Returns a readable name for the temporary holding the value of `handle`
+    /// if [`Options::readable_names`] is set: the name given by the frontend,
+    /// or the readable name of `source`, when there is one.
+    fn readable_bake_name(
+        &mut self,
+        handle: Handle<crate::Expression>,
+        source: Handle<crate::Expression>,
+        context: &ExpressionContext,
+    ) -> Option<String> {
+        if !context.readable_names {
+            return None;
+        }
+        let name = context
+            .function
+            .named_expressions
+            .get(&handle)
+            .cloned()
+            .or_else(|| {
+                back::readable_name(context.module, context.function, context.info, source)
+            })?;
+        Some(self.namer.call(&name))
+    }
+
     fn start_baking_expression(
         &mut self,
         handle: Handle<crate::Expression>,
@@ -2682,7 +2745,7 @@ impl<W: Write> Writer<W> {
                                 };
 
                             if bake {
-                                Some(format!("{}{}", back::BAKE_PREFIX, handle.index()))
+                                Some(self.bake_name(handle, handle, &context.expression))
                             } else {
                                 None
                             }
@@ -2837,7 +2900,7 @@ impl<W: Write> Writer<W> {
                 } => {
                     write!(self.out, "{level}")?;
                     if let Some(expr) = result {
-                        let name = format!("{}{}", back::BAKE_PREFIX, expr.index());
+                        let name = self.bake_name(expr, expr, &context.expression);
                         self.start_baking_expression(expr, &context.expression, &name)?;
                         self.named_expressions.insert(expr, name);
                     }
@@ -2887,7 +2950,7 @@ impl<W: Write> Writer<W> {
                     result,
                 } => {
                     write!(self.out, "{level}")?;
-                    let res_name = format!("{}{}", back::BAKE_PREFIX, result.index());
+                    let res_name = self.bake_name(result, pointer, &context.expression);
                     self.start_baking_expression(result, &context.expression, &res_name)?;
                     self.named_expressions.insert(result, res_name);
                     match *fun {
@@ -2934,7 +2997,9 @@ impl<W: Write> Writer<W> {
                     self.write_barrier(crate::Barrier::WORK_GROUP, level)?;
 
                     write!(self.out, "{level}")?;
-                    let name = self.namer.call("");
+                    let name = self
+                        .readable_bake_name(result, pointer, &context.expression)
+                        .unwrap_or_else(|| self.namer.call(""));
                     self.start_baking_expression(result, &context.expression, &name)?;
                     self.put_load(pointer, &context.expression, true)?;
                     self.named_expressions.insert(result, name);
@@ -3022,7 +3087,7 @@ impl<W: Write> Writer<W> {
                         }
                         crate::RayQueryFunction::Proceed { result } => {
                             write!(self.out, "{level}")?;
-                            let name = format!("{}{}", back::BAKE_PREFIX, result.index());
+                            let name = self.bake_name(result, result, &context.expression);
                             self.start_baking_expression(result, &context.expression, &name)?;
                             self.named_expressions.insert(result, name);
                             self.put_expression(query, &context.expression, true)?;
@@ -3040,6 +3105,87 @@ impl<W: Write> Writer<W> {
                         }
                     }
                 }
+                crate::Statement::DebugPrintf { .. } => {
+                    return Err(Error::FeatureNotImplemented(
+                        "debug printf, it must be lowered to buffer writes".to_string(),
+                    ));
+                }
+                crate::Statement::EmitVertex { .. } | crate::Statement::EndPrimitive => {
+                    return Err(Error::FeatureNotImplemented("geometry shaders".to_string()));
+                }
+                crate::Statement::SubgroupBallot { result, predicate } => {
+                    write!(self.out, "{level}")?;
+                    let name = self.bake_name(result, result, &context.expression);
+                    self.start_baking_expression(result, &context.expression, &name)?;
+                    self.named_expressions.insert(result, name);
+                    write!(
+                        self.out,
+                        "{NAMESPACE}::uint4((uint64_t){NAMESPACE}::simd_ballot("
+                    )?;
+                    match predicate {
+                        Some(predicate) => {
+                            self.put_expression(predicate, &context.expression, true)?
+                        }
+                        None => write!(self.out, "true")?,
+                    }
+                    writeln!(self.out, "), 0, 0, 0);")?;
+                }
+                crate::Statement::SubgroupCollectiveOperation {
+                    op,
+                    collective_op,
+                    argument,
+                    result,
+                } => {
+                    write!(self.out, "{level}")?;
+                    let name = self.bake_name(result, argument, &context.expression);
+                    self.start_baking_expression(result, &context.expression, &name)?;
+                    self.named_expressions.insert(result, name);
+                    use crate::{CollectiveOperation as co, SubgroupOperation as sg};
+                    let fun = match (collective_op, op) {
+                        (co::Reduce, sg::All) => "simd_all",
+                        (co::Reduce, sg::Any) => "simd_any",
+                        (co::Reduce, sg::Add) => "simd_sum",
+                        (co::Reduce, sg::Mul) => "simd_product",
+                        (co::Reduce, sg::Max) => "simd_max",
+                        (co::Reduce, sg::Min) => "simd_min",
+                        (co::Reduce, sg::And) => "simd_and",
+                        (co::Reduce, sg::Or) => "simd_or",
+                        (co::Reduce, sg::Xor) => "simd_xor",
+                        (co::ExclusiveScan, sg::Add) => "simd_prefix_exclusive_sum",
+                        (co::ExclusiveScan, sg::Mul) => "simd_prefix_exclusive_product",
+                        (co::InclusiveScan, sg::Add) => "simd_prefix_inclusive_sum",
+                        (co::InclusiveScan, sg::Mul) => "simd_prefix_inclusive_product",
+                        _ => return Err(Error::Validation),
+                    };
+                    write!(self.out, "{NAMESPACE}::{fun}(")?;
+                    self.put_expression(argument, &context.expression, true)?;
+                    writeln!(self.out, ");")?;
+                }
+                crate::Statement::SubgroupGather {
+                    mode,
+                    argument,
+                    result,
+                } => {
+                    write!(self.out, "{level}")?;
+                    let name = self.bake_name(result, argument, &context.expression);
+                    self.start_baking_expression(result, &context.expression, &name)?;
+                    self.named_expressions.insert(result, name);
+                    let (fun, index) = match mode {
+                        crate::GatherMode::BroadcastFirst => ("simd_broadcast_first", None),
+                        crate::GatherMode::Broadcast(index) => ("simd_broadcast", Some(index)),
+                        crate::GatherMode::Shuffle(index) => ("simd_shuffle", Some(index)),
+                        crate::GatherMode::ShuffleDown(index) => ("simd_shuffle_down", Some(index)),
+                        crate::GatherMode::ShuffleUp(index) => ("simd_shuffle_up", Some(index)),
+                        crate::GatherMode::ShuffleXor(index) => ("simd_shuffle_xor", Some(index)),
+                    };
+                    write!(self.out, "{NAMESPACE}::{fun}(")?;
+                    self.put_expression(argument, &context.expression, true)?;
+                    if let Some(index) = index {
+                        write!(self.out, ", ")?;
+                        self.put_expression(index, &context.expression, true)?;
+                    }
+                    writeln!(self.out, ");")?;
+                }
             }
         }
 
@@ -3116,6 +3262,10 @@ impl<W: Write> Writer<W> {
         options: &Options,
         pipeline_options: &PipelineOptions,
     ) -> Result<TranslationInfo, Error> {
+        if options.argument_buffers && options.lang_version < (2, 0) {
+            return Err(Error::UnsupportedArgumentBuffers);
+        }
+
         self.names.clear();
         self.namer.reset(
             module,
@@ -3612,6 +3762,7 @@ impl<W: Write> Writer<W> {
                     usage: fun_info[handle],
                     binding: None,
                     reference: true,
+                    pointer: false,
                 };
                 let separator =
                     separate(index + 1 != pass_through_globals.len() || supports_array_length);
@@ -3640,6 +3791,7 @@ impl<W: Write> Writer<W> {
                     info: fun_info,
                     lang_version: options.lang_version,
                     policies: options.bounds_check_policies,
+                    readable_names: options.readable_names,
                     guarded_indices,
                     module,
                     mod_info,
@@ -3691,6 +3843,17 @@ impl<W: Write> Writer<W> {
                 ep_index
             );
 
+            if let crate::ShaderStage::Task
+            | crate::ShaderStage::Mesh
+            | crate::ShaderStage::Geometry
+            | crate::ShaderStage::TessellationControl
+            | crate::ShaderStage::TessellationEvaluation = ep.stage
+            {
+                info.entry_point_names
+                    .push(Err(super::EntryPointError::UnsupportedStage(ep.stage)));
+                continue;
+            }
+
             // Is any global variable used by this entry point dynamically sized?
             let supports_array_length = module
                 .global_variables
@@ -3718,6 +3881,13 @@ impl<W: Write> Writer<W> {
                                     break;
                                 }
                             };
+                            if options.argument_buffers {
+                                if let Err(err) = options.resolve_argument_buffer(ep, br.group) {
+                                    ep_error = Some(err);
+                                    break;
+                                }
+                                continue;
+                            }
                             let target = options.get_resource_binding_target(ep, br);
                             let good = match target {
                                 Some(target) => {
@@ -3751,7 +3921,8 @@ impl<W: Write> Writer<W> {
                         }
                         crate::AddressSpace::Function
                         | crate::AddressSpace::Private
-                        | crate::AddressSpace::WorkGroup => {}
+                        | crate::AddressSpace::WorkGroup
+                        | crate::AddressSpace::TaskPayload => {}
                     }
                 }
                 if supports_array_length {
@@ -3784,6 +3955,11 @@ impl<W: Write> Writer<W> {
                 crate::ShaderStage::Compute { .. } => {
                     ("kernel", LocationMode::Uniform, LocationMode::Uniform)
                 }
+                crate::ShaderStage::Task
+                | crate::ShaderStage::Mesh
+                | crate::ShaderStage::Geometry
+                | crate::ShaderStage::TessellationControl
+                | crate::ShaderStage::TessellationEvaluation => unreachable!(),
             };
 
             // Since `Namer.reset` wasn't expecting struct members to be
@@ -3939,6 +4115,55 @@ impl<W: Write> Writer<W> {
                 None => "void",
             };
 
+            // Gather the resources of each bind group into argument buffers,
+            // and emit a struct type named `<fun>Group<group>` for each one.
+            let mut argument_buffers = std::collections::BTreeMap::<u32, Vec<_>>::new();
+            if options.argument_buffers {
+                for (handle, var) in module.global_variables.iter() {
+                    if fun_info[handle].is_empty() {
+                        continue;
+                    }
+                    if let Some(ref binding) = var.binding {
+                        let resolved = options.resolve_resource_binding(ep, binding).ok();
+                        // Inline samplers stay out of the argument buffers
+                        if resolved
+                            .as_ref()
+                            .and_then(|resolved| resolved.as_inline_sampler(options))
+                            .is_some()
+                        {
+                            continue;
+                        }
+                        argument_buffers.entry(binding.group).or_default().push((
+                            handle,
+                            binding.binding,
+                            resolved,
+                        ));
+                    }
+                }
+            }
+            let mut argument_buffer_names = Vec::with_capacity(argument_buffers.len());
+            for (&group, resources) in argument_buffers.iter() {
+                let struct_name = format!("{fun_name}Group{group}");
+                writeln!(self.out, "struct {struct_name} {{")?;
+                for &(handle, id, ref resolved) in resources.iter() {
+                    let tyvar = TypedGlobalVariable {
+                        module,
+                        names: &self.names,
+                        handle,
+                        usage: fun_info[handle],
+                        binding: resolved.as_ref(),
+                        reference: true,
+                        pointer: true,
+                    };
+                    write!(self.out, "{}", back::INDENT)?;
+                    tyvar.try_fmt(&mut self.out)?;
+                    writeln!(self.out, " [[id({id})]];")?;
+                }
+                writeln!(self.out, "}};")?;
+                argument_buffer_names
+                    .push((struct_name, self.namer.call(&format!("group{group}"))));
+            }
+
             // Write the entry point function's name, and begin its argument list.
             writeln!(self.out, "{em_str} {result_type_name} {fun_name}(")?;
             let mut is_first_argument = true;
@@ -4144,6 +4369,10 @@ impl<W: Write> Writer<W> {
                         continue;
                     }
                 }
+                // Resources in argument buffers are unpacked in the EP body
+                if options.argument_buffers && var.binding.is_some() {
+                    continue;
+                }
 
                 let tyvar = TypedGlobalVariable {
                     module,
@@ -4152,6 +4381,7 @@ impl<W: Write> Writer<W> {
                     usage,
                     binding: resolved.as_ref(),
                     reference: true,
+                    pointer: false,
                 };
                 let separator = if is_first_argument {
                     is_first_argument = false;
@@ -4171,6 +4401,23 @@ impl<W: Write> Writer<W> {
                 writeln!(self.out)?;
             }
 
+            // Then pass the argument buffers of the bind groups.
+            for (&group, &(ref struct_name, ref name)) in
+                argument_buffers.keys().zip(argument_buffer_names.iter())
+            {
+                // this is checked earlier
+                let resolved = options.resolve_argument_buffer(ep, group).unwrap();
+                let separator = if is_first_argument {
+                    is_first_argument = false;
+                    ' '
+                } else {
+                    ','
+                };
+                write!(self.out, "{separator} constant {struct_name}& {name}")?;
+                resolved.try_fmt(&mut self.out)?;
+                writeln!(self.out)?;
+            }
+
             // If this entry uses any variable-length arrays, their sizes are
             // passed as a final struct-typed argument.
             if supports_array_length {
@@ -4216,6 +4463,7 @@ impl<W: Write> Writer<W> {
                         usage,
                         binding: None,
                         reference: false,
+                        pointer: false,
                     };
                     write!(self.out, "{}", back::INDENT)?;
                     tyvar.try_fmt(&mut self.out)?;
@@ -4247,6 +4495,32 @@ impl<W: Write> Writer<W> {
                 }
             }
 
+            // Take the resources out of the argument buffers, so that the body
+            // can keep referring to them by name.
+            for (resources, &(_, ref name)) in
+                argument_buffers.values().zip(argument_buffer_names.iter())
+            {
+                for &(handle, _, ref resolved) in resources.iter() {
+                    let tyvar = TypedGlobalVariable {
+                        module,
+                        names: &self.names,
+                        handle,
+                        usage: fun_info[handle],
+                        binding: resolved.as_ref(),
+                        reference: true,
+                        pointer: false,
+                    };
+                    let deref = match module.global_variables[handle].space {
+                        crate::AddressSpace::Handle => "",
+                        _ => "*",
+                    };
+                    let member_name = &self.names[&NameKey::GlobalVariable(handle)];
+                    write!(self.out, "{}", back::INDENT)?;
+                    tyvar.try_fmt(&mut self.out)?;
+                    writeln!(self.out, " = {deref}{name}.{member_name};")?;
+                }
+            }
+
             // Now take the arguments that we gathered into structs, and the
             // structs that we flattened into arguments, and emit local
             // variables with initializers that put everything back the way the
@@ -4315,6 +4589,7 @@ impl<W: Write> Writer<W> {
                     info: fun_info,
                     lang_version: options.lang_version,
                     policies: options.bounds_check_policies,
+                    readable_names: options.readable_names,
                     guarded_indices,
                     module,
                     mod_info,
@@ -4381,6 +4656,12 @@ impl<W: Write> Writer<W> {
                 "{level}{NAMESPACE}::threadgroup_barrier({NAMESPACE}::mem_flags::mem_threadgroup);",
             )?;
         }
+        if flags.contains(crate::Barrier::SUB_GROUP) {
+            writeln!(
+                self.out,
+                "{level}{NAMESPACE}::simdgroup_barrier({NAMESPACE}::mem_flags::mem_threadgroup);",
+            )?;
+        }
         Ok(())
     }
 }
@@ -71,7 +71,7 @@ impl Writer {
     // between SPIR-V and our IR.
     // The `position_id` argument is a pointer to a `vecN<f32>`,
     // whose `y` component we will negate.
-    fn write_epilogue_position_y_flip(
+    pub(super) fn write_epilogue_position_y_flip(
         &mut self,
         position_id: Word,
         body: &mut Vec<Instruction>,
@@ -145,7 +145,7 @@ impl Writer {
         Ok(())
     }
 
-    fn write_entry_point_return(
+    pub(super) fn write_entry_point_return(
         &mut self,
         value_id: Word,
         ir_result: &crate::FunctionResult,
@@ -1128,7 +1128,9 @@ impl<'w> BlockContext<'w> {
             crate::Expression::CallResult(_)
             | crate::Expression::AtomicResult { .. }
             | crate::Expression::WorkGroupUniformLoadResult { .. }
-            | crate::Expression::RayQueryProceedResult => self.cached[expr_handle],
+            | crate::Expression::RayQueryProceedResult
+            | crate::Expression::SubgroupBallotResult
+            | crate::Expression::SubgroupOperationResult { .. } => self.cached[expr_handle],
             crate::Expression::As {
                 expr,
                 kind,
@@ -2001,7 +2003,26 @@ impl<'w> BlockContext<'w> {
                 }
                 crate::Statement::Return { value: Some(value) } => {
                     let value_id = self.cached[value];
+                    if let Some(invocation_id) = self
+                        .function
+                        .entry_point_context
+                        .as_ref()
+                        .and_then(|context| context.invocation_id)
+                    {
+                        return self.write_tessellation_control_return(
+                            value_id,
+                            invocation_id,
+                            block,
+                        );
+                    }
                     let instruction = match self.function.entry_point_context {
+                        Some(ref context) if context.emits_mesh_tasks => {
+                            self.writer.write_emit_mesh_tasks(
+                                value_id,
+                                context.task_payload_id,
+                                &mut block.body,
+                            )
+                        }
                         // If this is an entry point, and we need to return anything,
                         // let's instead store the output variables and return `void`.
                         Some(ref context) => {
@@ -2019,7 +2040,8 @@ impl<'w> BlockContext<'w> {
                     return Ok(());
                 }
                 crate::Statement::Return { value: None } => {
-                    self.function.consume(block, Instruction::return_void());
+                    let instruction = self.write_entry_point_void_return(&mut block);
+                    self.function.consume(block, instruction);
                     return Ok(());
                 }
                 crate::Statement::Kill => {
@@ -2333,6 +2355,43 @@ impl<'w> BlockContext<'w> {
                 crate::Statement::RayQuery { query, ref fun } => {
                     self.write_ray_query_function(query, fun, &mut block);
                 }
+                crate::Statement::DebugPrintf { .. } => {
+                    return Err(Error::FeatureNotImplemented(
+                        "debug printf, it must be lowered to buffer writes",
+                    ));
+                }
+                crate::Statement::EmitVertex { value } => {
+                    let crate::proc::TypeResolution::Handle(ty) = self.fun_info[value].ty else {
+                        return Err(Error::Validation("emitted vertices must be structs"));
+                    };
+                    let value_id = self.cached[value];
+                    self.writer
+                        .write_emit_vertex(self.ir_module, ty, value_id, &mut block.body)?;
+                }
+                crate::Statement::EndPrimitive => {
+                    block.body.push(Instruction::end_primitive());
+                }
+                crate::Statement::SubgroupBallot {
+                    ref predicate,
+                    result,
+                } => {
+                    self.write_subgroup_ballot(predicate, result, &mut block)?;
+                }
+                crate::Statement::SubgroupCollectiveOperation {
+                    ref op,
+                    ref collective_op,
+                    argument,
+                    result,
+                } => {
+                    self.write_subgroup_operation(op, collective_op, argument, result, &mut block)?;
+                }
+                crate::Statement::SubgroupGather {
+                    ref mode,
+                    argument,
+                    result,
+                } => {
+                    self.write_subgroup_gather(mode, argument, result, &mut block)?;
+                }
             }
         }
 
@@ -2345,7 +2404,7 @@ impl<'w> BlockContext<'w> {
                     let null_id = self.writer.get_constant_null(type_id);
                     Instruction::return_value(null_id)
                 }
-                _ => Instruction::return_void(),
+                _ => self.write_entry_point_void_return(&mut block),
             },
             BlockExit::Branch { target } => Instruction::branch(target),
             BlockExit::BreakIf {
@@ -0,0 +1,123 @@
+/*!
+Generating SPIR-V for geometry shaders.
+
+Naga's geometry shaders emit their vertices with [`EmitVertex`], which can
+appear in any function they call. The output variables holding the members of
+the emitted vertices are therefore shared by every function emitting vertices
+of the same type, and are added to the interface of each geometry entry point
+outputting that type. [`EmitVertex`] stores to them like an entry point
+returning the vertex, then uses `OpEmitVertex`.
+
+[`EmitVertex`]: crate::Statement::EmitVertex
+*/
+
+use super::{Error, Instruction, LookupType, ResultMember, Writer};
+use crate::arena::Handle;
+use spirv::Word;
+
+impl Writer {
+    pub(super) fn write_geometry_execution_modes(
+        &mut self,
+        function_id: Word,
+        geometry_info: &crate::GeometryStageInfo,
+    ) -> Result<(), Error> {
+        use spirv::ExecutionMode as Em;
+
+        self.require_any("geometry shaders", &[spirv::Capability::Geometry])?;
+
+        let input = match geometry_info.input_primitive {
+            crate::GeometryInputPrimitive::Points => Em::InputPoints,
+            crate::GeometryInputPrimitive::Lines => Em::InputLines,
+            crate::GeometryInputPrimitive::LinesAdjacency => Em::InputLinesAdjacency,
+            crate::GeometryInputPrimitive::Triangles => Em::Triangles,
+            crate::GeometryInputPrimitive::TrianglesAdjacency => Em::InputTrianglesAdjacency,
+        };
+        let output = match geometry_info.output_primitive {
+            crate::GeometryOutputPrimitive::Points => Em::OutputPoints,
+            crate::GeometryOutputPrimitive::LineStrip => Em::OutputLineStrip,
+            crate::GeometryOutputPrimitive::TriangleStrip => Em::OutputTriangleStrip,
+        };
+        for (mode, args) in [
+            (input, &[][..]),
+            (output, &[][..]),
+            (Em::OutputVertices, &[geometry_info.max_vertices][..]),
+            (Em::Invocations, &[geometry_info.invocations][..]),
+        ] {
+            Instruction::execution_mode(function_id, mode, args)
+                .to_words(&mut self.logical_layout.execution_modes);
+        }
+        Ok(())
+    }
+
+    /// Adds the output variables of the vertices emitted by a geometry
+    /// shader to `varying_ids`.
+    pub(super) fn write_geometry_interface(
+        &mut self,
+        ir_module: &crate::Module,
+        geometry_info: &crate::GeometryStageInfo,
+        varying_ids: &mut Vec<Word>,
+    ) -> Result<(), Error> {
+        let ty = geometry_info.vertex_output_type;
+        let outputs = self.take_geometry_outputs(ir_module, ty)?;
+        varying_ids.extend(outputs.iter().map(|output| output.id));
+        self.geometry_outputs.insert(ty, outputs);
+        Ok(())
+    }
+
+    /// Writes the instructions emitting the vertex `value_id` of type `ty`.
+    pub(super) fn write_emit_vertex(
+        &mut self,
+        ir_module: &crate::Module,
+        ty: Handle<crate::Type>,
+        value_id: Word,
+        body: &mut Vec<Instruction>,
+    ) -> Result<(), Error> {
+        let outputs = self.take_geometry_outputs(ir_module, ty)?;
+        let result = self.write_entry_point_return(
+            value_id,
+            &crate::FunctionResult { ty, binding: None },
+            &outputs,
+            body,
+        );
+        self.geometry_outputs.insert(ty, outputs);
+        result?;
+        body.push(Instruction::emit_vertex());
+        Ok(())
+    }
+
+    /// Removes the output variables of emitted vertices of type `ty` from
+    /// `self.geometry_outputs`, writing them if they don't exist yet.
+    fn take_geometry_outputs(
+        &mut self,
+        ir_module: &crate::Module,
+        ty: Handle<crate::Type>,
+    ) -> Result<Vec<ResultMember>, Error> {
+        if let Some(outputs) = self.geometry_outputs.remove(&ty) {
+            return Ok(outputs);
+        }
+
+        let crate::TypeInner::Struct { ref members, .. } = ir_module.types[ty].inner else {
+            return Err(Error::Validation("emitted vertices must be structs"));
+        };
+        let mut outputs = Vec::with_capacity(members.len());
+        for member in members {
+            let binding = member.binding.as_ref().ok_or(Error::Validation(
+                "emitted vertex members must have bindings",
+            ))?;
+            let id = self.write_varying(
+                ir_module,
+                crate::ShaderStage::Geometry,
+                spirv::StorageClass::Output,
+                member.name.as_deref(),
+                member.ty,
+                binding,
+            )?;
+            outputs.push(ResultMember {
+                id,
+                type_id: self.get_type_id(LookupType::Handle(member.ty)),
+                built_in: binding.to_built_in(),
+            });
+        }
+        Ok(outputs)
+    }
+}
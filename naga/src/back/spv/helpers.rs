@@ -29,6 +29,7 @@ pub(super) const fn map_storage_class(space: crate::AddressSpace) -> spirv::Stor
         crate::AddressSpace::Uniform => spirv::StorageClass::Uniform,
         crate::AddressSpace::WorkGroup => spirv::StorageClass::Workgroup,
         crate::AddressSpace::PushConstant => spirv::StorageClass::PushConstant,
+        crate::AddressSpace::TaskPayload => spirv::StorageClass::TaskPayloadWorkgroupEXT,
     }
 }
 
@@ -49,6 +50,22 @@ pub(super) fn contains_builtin(
     }
 }
 
+/// Returns true if values of type `ty` contain 16-bit floats.
+pub(super) fn contains_float16(ty: Handle<crate::Type>, arena: &UniqueArena<crate::Type>) -> bool {
+    match arena[ty].inner {
+        crate::TypeInner::Scalar(scalar)
+        | crate::TypeInner::Vector { scalar, .. }
+        | crate::TypeInner::Matrix { scalar, .. } => scalar == crate::Scalar::F16,
+        crate::TypeInner::Array { base, .. } | crate::TypeInner::BindingArray { base, .. } => {
+            contains_float16(base, arena)
+        }
+        crate::TypeInner::Struct { ref members, .. } => members
+            .iter()
+            .any(|member| contains_float16(member.ty, arena)),
+        _ => false,
+    }
+}
+
 impl crate::AddressSpace {
     pub(super) const fn to_spirv_semantics_and_scope(
         self,
@@ -1037,6 +1037,113 @@ impl super::Instruction {
         instruction.add_operand(semantics_id);
         instruction
     }
+
+    //
+    //  Mesh Shading Instructions
+    //
+
+    pub(super) fn emit_mesh_tasks(
+        group_count_x: Word,
+        group_count_y: Word,
+        group_count_z: Word,
+        payload_id: Option<Word>,
+    ) -> Self {
+        let mut instruction = Self::new(Op::EmitMeshTasksEXT);
+        instruction.add_operand(group_count_x);
+        instruction.add_operand(group_count_y);
+        instruction.add_operand(group_count_z);
+        if let Some(payload_id) = payload_id {
+            instruction.add_operand(payload_id);
+        }
+        instruction
+    }
+
+    pub(super) fn set_mesh_outputs(vertex_count_id: Word, primitive_count_id: Word) -> Self {
+        let mut instruction = Self::new(Op::SetMeshOutputsEXT);
+        instruction.add_operand(vertex_count_id);
+        instruction.add_operand(primitive_count_id);
+        instruction
+    }
+
+    //
+    //  Geometry Instructions
+    //
+
+    pub(super) const fn emit_vertex() -> Self {
+        Self::new(Op::EmitVertex)
+    }
+
+    pub(super) const fn end_primitive() -> Self {
+        Self::new(Op::EndPrimitive)
+    }
+
+    //
+    //  Subgroup Instructions
+    //
+
+    pub(super) fn group_non_uniform_ballot(
+        result_type_id: Word,
+        id: Word,
+        exec_scope_id: Word,
+        predicate: Word,
+    ) -> Self {
+        let mut instruction = Self::new(Op::GroupNonUniformBallot);
+        instruction.set_type(result_type_id);
+        instruction.set_result(id);
+        instruction.add_operand(exec_scope_id);
+        instruction.add_operand(predicate);
+        instruction
+    }
+
+    pub(super) fn group_non_uniform_broadcast_first(
+        result_type_id: Word,
+        id: Word,
+        exec_scope_id: Word,
+        value: Word,
+    ) -> Self {
+        let mut instruction = Self::new(Op::GroupNonUniformBroadcastFirst);
+        instruction.set_type(result_type_id);
+        instruction.set_result(id);
+        instruction.add_operand(exec_scope_id);
+        instruction.add_operand(value);
+        instruction
+    }
+
+    pub(super) fn group_non_uniform_gather(
+        op: Op,
+        result_type_id: Word,
+        id: Word,
+        exec_scope_id: Word,
+        value: Word,
+        index: Word,
+    ) -> Self {
+        let mut instruction = Self::new(op);
+        instruction.set_type(result_type_id);
+        instruction.set_result(id);
+        instruction.add_operand(exec_scope_id);
+        instruction.add_operand(value);
+        instruction.add_operand(index);
+        instruction
+    }
+
+    pub(super) fn group_non_uniform_arithmetic(
+        op: Op,
+        result_type_id: Word,
+        id: Word,
+        exec_scope_id: Word,
+        group_op: Option<spirv::GroupOperation>,
+        value: Word,
+    ) -> Self {
+        let mut instruction = Self::new(op);
+        instruction.set_type(result_type_id);
+        instruction.set_result(id);
+        instruction.add_operand(exec_scope_id);
+        if let Some(group_op) = group_op {
+            instruction.add_operand(group_op as u32);
+        }
+        instruction.add_operand(value);
+        instruction
+    }
 }
 
 impl From<crate::StorageFormat> for spirv::ImageFormat {
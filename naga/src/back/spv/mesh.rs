@@ -0,0 +1,454 @@
+/*!
+Generating SPIR-V for task and mesh shaders.
+
+Naga's task shaders return the size of the grid of mesh shaders they launch,
+which we pass to `OpEmitMeshTasksEXT` along with the task payload.
+
+Naga's mesh shaders write their vertices and primitives to a workgroup
+variable, described by [`MeshStageInfo`]. `SPV_EXT_mesh_shader` instead
+expects them in per-vertex and per-primitive arrays of output variables, so
+each mesh shader gets an epilogue function, called before every return, that
+sets the number of vertices and primitives with `OpSetMeshOutputsEXT`, and has
+the invocations of the workgroup copy them over.
+
+[`MeshStageInfo`]: crate::MeshStageInfo
+*/
+
+use super::{
+    Block, BlockContext, EntryPointContext, Error, Function, Instruction, LocalType,
+    LookupFunctionType, LookupType, Writer, WriterFlags,
+};
+use crate::{arena::Handle, valid::FunctionInfo};
+use spirv::Word;
+
+/// An output variable holding a member of the vertices or primitives output
+/// by a mesh shader.
+struct MeshOutput {
+    /// The index of the member in the vertex or primitive struct.
+    member_index: Word,
+    ty: Handle<crate::Type>,
+    built_in: Option<crate::BuiltIn>,
+    /// The id of the array variable, with an element per vertex or primitive.
+    variable_id: Word,
+}
+
+impl Writer {
+    pub(super) fn request_mesh_shading(&mut self) -> Result<(), Error> {
+        if self.physical_layout.version < 0x10400 {
+            return Err(Error::FeatureNotImplemented(
+                "task and mesh shaders before SPIR-V 1.4",
+            ));
+        }
+        self.require_any(
+            "task and mesh shaders",
+            &[spirv::Capability::MeshShadingEXT],
+        )?;
+        self.use_extension("SPV_EXT_mesh_shader");
+        Ok(())
+    }
+
+    pub(super) fn write_mesh_execution_modes(
+        &mut self,
+        function_id: Word,
+        mesh_info: &crate::MeshStageInfo,
+    ) {
+        use spirv::ExecutionMode as Em;
+
+        let topology = match mesh_info.topology {
+            crate::MeshOutputTopology::Points => Em::OutputPoints,
+            crate::MeshOutputTopology::Lines => Em::OutputLinesEXT,
+            crate::MeshOutputTopology::Triangles => Em::OutputTrianglesEXT,
+        };
+        for (mode, args) in [
+            (Em::OutputVertices, &[mesh_info.max_vertices][..]),
+            (Em::OutputPrimitivesEXT, &[mesh_info.max_primitives][..]),
+            (topology, &[][..]),
+        ] {
+            Instruction::execution_mode(function_id, mode, args)
+                .to_words(&mut self.logical_layout.execution_modes);
+        }
+    }
+
+    /// Returns the `OpEmitMeshTasksEXT` ending a task shader, which launches
+    /// a grid of mesh shaders of size `size_id`, a `vec3<u32>`.
+    pub(super) fn write_emit_mesh_tasks(
+        &mut self,
+        size_id: Word,
+        payload_id: Option<Word>,
+        body: &mut Vec<Instruction>,
+    ) -> Instruction {
+        let uint_type_id = self.get_uint_type_id();
+        let mut group_count_ids = [0; 3];
+        for (index, group_count_id) in group_count_ids.iter_mut().enumerate() {
+            *group_count_id = self.id_gen.next();
+            body.push(Instruction::composite_extract(
+                uint_type_id,
+                *group_count_id,
+                size_id,
+                &[index as u32],
+            ));
+        }
+        let [x, y, z] = group_count_ids;
+        Instruction::emit_mesh_tasks(x, y, z, payload_id)
+    }
+
+    /// Writes the epilogue of a mesh shader, returning its function id.
+    ///
+    /// The output variables it writes are added to `varying_ids`.
+    pub(super) fn write_mesh_epilogue(
+        &mut self,
+        ir_module: &crate::Module,
+        info: &FunctionInfo,
+        mesh_info: &crate::MeshStageInfo,
+        workgroup_size: [u32; 3],
+        varying_ids: &mut Vec<Word>,
+    ) -> Result<Word, Error> {
+        let output_handle = mesh_info.output_variable;
+        let output_variable_id = self.global_variables[output_handle.index()].var_id;
+        // Used globals are already part of the interface.
+        if info[output_handle].is_empty() {
+            varying_ids.push(output_variable_id);
+        }
+
+        let output_type = ir_module.global_variables[output_handle].ty;
+        let crate::TypeInner::Struct { ref members, .. } = ir_module.types[output_type].inner
+        else {
+            return Err(Error::Validation("mesh output variable must be a struct"));
+        };
+        let member_index = |built_in| {
+            members
+                .iter()
+                .position(|member| member.binding == Some(crate::Binding::BuiltIn(built_in)))
+                .map(|index| index as Word)
+                .ok_or(Error::Validation(
+                    "mesh output variable is missing a member",
+                ))
+        };
+        let vertices_index = member_index(crate::BuiltIn::Vertices)?;
+        let primitives_index = member_index(crate::BuiltIn::Primitives)?;
+        let vertex_count_index = member_index(crate::BuiltIn::VertexCount)?;
+        let primitive_count_index = member_index(crate::BuiltIn::PrimitiveCount)?;
+
+        let vertex_outputs = self.write_mesh_outputs(
+            ir_module,
+            mesh_info.vertex_output_type,
+            mesh_info.max_vertices,
+            varying_ids,
+        )?;
+        let primitive_outputs = self.write_mesh_outputs(
+            ir_module,
+            mesh_info.primitive_output_type,
+            mesh_info.max_primitives,
+            varying_ids,
+        )?;
+
+        let invocation_index_variable_id = self.id_gen.next();
+        let class = spirv::StorageClass::Input;
+        let pointer_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: None,
+            scalar: crate::Scalar::U32,
+            pointer_space: Some(class),
+        }));
+        Instruction::variable(pointer_type_id, invocation_index_variable_id, class, None)
+            .to_words(&mut self.logical_layout.declarations);
+        self.decorate(
+            invocation_index_variable_id,
+            spirv::Decoration::BuiltIn,
+            &[spirv::BuiltIn::LocalInvocationIndex as u32],
+        );
+        varying_ids.push(invocation_index_variable_id);
+
+        let function_id = self.id_gen.next();
+        let function_type = self.get_function_type(LookupFunctionType {
+            parameter_type_ids: Vec::new(),
+            return_type_id: self.void_type,
+        });
+        let mut function = Function {
+            signature: Some(Instruction::function(
+                self.void_type,
+                function_id,
+                spirv::FunctionControl::empty(),
+                function_type,
+            )),
+            ..Default::default()
+        };
+
+        // Wait for all invocations to be done writing their outputs.
+        let mut block = Block::new(self.id_gen.next());
+        self.write_barrier(crate::Barrier::WORK_GROUP, &mut block);
+
+        let uint_type_id = self.get_uint_type_id();
+        let uint_pointer_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: None,
+            scalar: crate::Scalar::U32,
+            pointer_space: Some(spirv::StorageClass::Workgroup),
+        }));
+        let mut count_ids = [0; 2];
+        for (count_id, index) in count_ids
+            .iter_mut()
+            .zip([vertex_count_index, primitive_count_index])
+        {
+            let access_id = self.id_gen.next();
+            let index_id = self.get_index_constant(index);
+            block.body.push(Instruction::access_chain(
+                uint_pointer_type_id,
+                access_id,
+                output_variable_id,
+                &[index_id],
+            ));
+            *count_id = self.id_gen.next();
+            block
+                .body
+                .push(Instruction::load(uint_type_id, *count_id, access_id, None));
+        }
+        let [vertex_count_id, primitive_count_id] = count_ids;
+        block.body.push(Instruction::set_mesh_outputs(
+            vertex_count_id,
+            primitive_count_id,
+        ));
+
+        let invocation_index_id = self.id_gen.next();
+        block.body.push(Instruction::load(
+            uint_type_id,
+            invocation_index_id,
+            invocation_index_variable_id,
+            None,
+        ));
+        let stride_id = self.get_index_constant(workgroup_size.iter().product());
+
+        for (array_index, count_id, outputs) in [
+            (vertices_index, vertex_count_id, vertex_outputs),
+            (primitives_index, primitive_count_id, primitive_outputs),
+        ] {
+            block = self.write_mesh_output_loop(
+                ir_module,
+                &mut function,
+                block,
+                MeshOutputLoop {
+                    source_id: output_variable_id,
+                    array_index,
+                    start_id: invocation_index_id,
+                    stride_id,
+                    count_id,
+                },
+                &outputs,
+            )?;
+        }
+
+        function.consume(block, Instruction::return_void());
+        function.to_words(&mut self.logical_layout.function_definitions);
+        Instruction::function_end().to_words(&mut self.logical_layout.function_definitions);
+
+        Ok(function_id)
+    }
+
+    /// Writes an array output variable per member of `ty`, with `count`
+    /// elements.
+    fn write_mesh_outputs(
+        &mut self,
+        ir_module: &crate::Module,
+        ty: Handle<crate::Type>,
+        count: u32,
+        varying_ids: &mut Vec<Word>,
+    ) -> Result<Vec<MeshOutput>, Error> {
+        let crate::TypeInner::Struct { ref members, .. } = ir_module.types[ty].inner else {
+            return Err(Error::Validation("mesh outputs must be structs"));
+        };
+
+        let class = spirv::StorageClass::Output;
+        let length_id = self.get_index_constant(count);
+        let mut outputs = Vec::with_capacity(members.len());
+        for (index, member) in members.iter().enumerate() {
+            let binding = member
+                .binding
+                .as_ref()
+                .ok_or(Error::Validation("mesh outputs must have bindings"))?;
+            self.request_float16_storage(&ir_module.types, member.ty, class)?;
+
+            let element_type_id = self.get_type_id(LookupType::Handle(member.ty));
+            let array_type_id = self.id_gen.next();
+            Instruction::type_array(array_type_id, element_type_id, length_id)
+                .to_words(&mut self.logical_layout.declarations);
+            let pointer_type_id = self.id_gen.next();
+            Instruction::type_pointer(pointer_type_id, class, array_type_id)
+                .to_words(&mut self.logical_layout.declarations);
+            let variable_id = self.id_gen.next();
+            Instruction::variable(pointer_type_id, variable_id, class, None)
+                .to_words(&mut self.logical_layout.declarations);
+
+            if self
+                .flags
+                .contains(WriterFlags::DEBUG | WriterFlags::LABEL_VARYINGS)
+            {
+                if let Some(ref name) = member.name {
+                    self.debugs.push(Instruction::name(variable_id, name));
+                }
+            }
+            self.decorate_varying(
+                ir_module,
+                crate::ShaderStage::Mesh,
+                class,
+                variable_id,
+                member.ty,
+                binding,
+            )?;
+            varying_ids.push(variable_id);
+
+            outputs.push(MeshOutput {
+                member_index: index as Word,
+                ty: member.ty,
+                built_in: binding.to_built_in(),
+                variable_id,
+            });
+        }
+        Ok(outputs)
+    }
+
+    /// Writes a loop copying the elements of an array member of the mesh
+    /// output variable to the `outputs`, starting in `block`.
+    ///
+    /// Returns the block following the loop.
+    fn write_mesh_output_loop(
+        &mut self,
+        ir_module: &crate::Module,
+        function: &mut Function,
+        block: Block,
+        desc: MeshOutputLoop,
+        outputs: &[MeshOutput],
+    ) -> Result<Block, Error> {
+        let uint_type_id = self.get_uint_type_id();
+        let bool_type_id = self.get_bool_type_id();
+
+        let preceding_id = block.label_id;
+        let header_id = self.id_gen.next();
+        let body_id = self.id_gen.next();
+        let continuing_id = self.id_gen.next();
+        let merge_id = self.id_gen.next();
+        function.consume(block, Instruction::branch(header_id));
+
+        let index_id = self.id_gen.next();
+        let next_index_id = self.id_gen.next();
+        let mut header = Block::new(header_id);
+        header.body.push(Instruction::phi(
+            uint_type_id,
+            index_id,
+            &[
+                (desc.start_id, preceding_id),
+                (next_index_id, continuing_id),
+            ],
+        ));
+        let condition_id = self.id_gen.next();
+        header.body.push(Instruction::binary(
+            spirv::Op::ULessThan,
+            bool_type_id,
+            condition_id,
+            index_id,
+            desc.count_id,
+        ));
+        header.body.push(Instruction::loop_merge(
+            merge_id,
+            continuing_id,
+            spirv::SelectionControl::NONE,
+        ));
+        function.consume(
+            header,
+            Instruction::branch_conditional(condition_id, body_id, merge_id),
+        );
+
+        let mut body = Block::new(body_id);
+        let array_index_id = self.get_index_constant(desc.array_index);
+        for output in outputs {
+            let type_id = self.get_type_id(LookupType::Handle(output.ty));
+
+            let source_pointer_type_id =
+                self.get_pointer_id(&ir_module.types, output.ty, spirv::StorageClass::Workgroup)?;
+            let member_index_id = self.get_index_constant(output.member_index);
+            let source_id = self.id_gen.next();
+            body.body.push(Instruction::access_chain(
+                source_pointer_type_id,
+                source_id,
+                desc.source_id,
+                &[array_index_id, index_id, member_index_id],
+            ));
+            let value_id = self.id_gen.next();
+            body.body
+                .push(Instruction::load(type_id, value_id, source_id, None));
+
+            let destination_pointer_type_id =
+                self.get_pointer_id(&ir_module.types, output.ty, spirv::StorageClass::Output)?;
+            let destination_id = self.id_gen.next();
+            body.body.push(Instruction::access_chain(
+                destination_pointer_type_id,
+                destination_id,
+                output.variable_id,
+                &[index_id],
+            ));
+            body.body
+                .push(Instruction::store(destination_id, value_id, None));
+
+            if let Some(crate::BuiltIn::Position { .. }) = output.built_in {
+                if self.flags.contains(WriterFlags::ADJUST_COORDINATE_SPACE) {
+                    self.write_epilogue_position_y_flip(destination_id, &mut body.body)?;
+                }
+            }
+        }
+        function.consume(body, Instruction::branch(continuing_id));
+
+        let mut continuing = Block::new(continuing_id);
+        continuing.body.push(Instruction::binary(
+            spirv::Op::IAdd,
+            uint_type_id,
+            next_index_id,
+            index_id,
+            desc.stride_id,
+        ));
+        function.consume(continuing, Instruction::branch(header_id));
+
+        Ok(Block::new(merge_id))
+    }
+}
+
+/// The ids a loop copying mesh shader outputs works with.
+struct MeshOutputLoop {
+    /// The mesh output variable.
+    source_id: Word,
+    /// The index of the array member of the mesh output variable to copy.
+    array_index: Word,
+    /// The index of the first element copied by this invocation.
+    start_id: Word,
+    /// The number of elements between the ones copied by this invocation.
+    stride_id: Word,
+    /// The number of elements to copy.
+    count_id: Word,
+}
+
+impl<'w> BlockContext<'w> {
+    /// Returns the instruction ending an entry point that doesn't return a
+    /// value, after writing what must happen before it to `block`.
+    pub(super) fn write_entry_point_void_return(&mut self, block: &mut Block) -> Instruction {
+        match self.function.entry_point_context {
+            // Falling off the end of a task shader launches no mesh shaders.
+            Some(ref context) if context.emits_mesh_tasks => {
+                let payload_id = context.task_payload_id;
+                let uint3_type_id = self.writer.get_uint3_type_id();
+                let size_id = self.writer.get_constant_null(uint3_type_id);
+                self.writer
+                    .write_emit_mesh_tasks(size_id, payload_id, &mut block.body)
+            }
+            Some(EntryPointContext {
+                mesh_epilogue_id: Some(epilogue_id),
+                ..
+            }) => {
+                let id = self.gen_id();
+                block.body.push(Instruction::function_call(
+                    self.writer.void_type,
+                    id,
+                    epilogue_id,
+                    &[],
+                ));
+                Instruction::return_void()
+            }
+            _ => Instruction::return_void(),
+        }
+    }
+}
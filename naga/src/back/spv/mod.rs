@@ -5,14 +5,18 @@ Backend for [SPIR-V][spv] (Standard Portable Intermediate Representation).
 */
 
 mod block;
+mod geometry;
 mod helpers;
 mod image;
 mod index;
 mod instructions;
 mod layout;
+mod mesh;
 mod ray;
 mod recyclable;
 mod selection;
+mod subgroup;
+mod tessellation;
 mod writer;
 
 pub use spirv::Capability;
@@ -133,6 +137,17 @@ struct ResultMember {
 struct EntryPointContext {
     argument_ids: Vec<Word>,
     results: Vec<ResultMember>,
+    /// Whether this is a task shader, which returns by emitting mesh tasks.
+    emits_mesh_tasks: bool,
+    /// The task payload passed to `OpEmitMeshTasksEXT`, if this is a task
+    /// shader that uses one.
+    task_payload_id: Option<Word>,
+    /// The function writing the outputs of a mesh shader, which must be called
+    /// before it returns.
+    mesh_epilogue_id: Option<Word>,
+    /// The invocation id of a tessellation control shader, which indexes the
+    /// per-vertex output arrays it returns to.
+    invocation_id: Option<Word>,
 }
 
 #[derive(Default)]
@@ -618,6 +633,11 @@ pub struct Writer {
     cached_constants: crate::FastHashMap<CachedConstant, Word>,
     global_variables: Vec<GlobalVariable>,
     binding_map: BindingMap,
+    transform_feedback: TransformFeedbackMap,
+
+    /// The output variables holding the members of the vertices emitted by
+    /// geometry shaders, by vertex type.
+    geometry_outputs: crate::FastHashMap<Handle<crate::Type>, Vec<ResultMember>>,
 
     // Cached expressions are only meaningful within a BlockContext, but we
     // retain the table here between functions to save heap allocations.
@@ -659,6 +679,22 @@ pub struct BindingInfo {
 // Using `BTreeMap` instead of `HashMap` so that we can hash itself.
 pub type BindingMap = std::collections::BTreeMap<crate::ResourceBinding, BindingInfo>;
 
+/// Where a vertex shader output is written to by transform feedback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct TransformFeedbackTarget {
+    /// The transform feedback buffer the output is written to.
+    pub buffer: u32,
+    /// The stride, in bytes, between vertices in the buffer.
+    pub stride: u32,
+    /// The offset, in bytes, of the output within a vertex.
+    pub offset: u32,
+}
+
+/// Map of vertex shader output locations to their transform feedback target.
+pub type TransformFeedbackMap = std::collections::BTreeMap<u32, TransformFeedbackTarget>;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ZeroInitializeWorkgroupMemoryMode {
     /// Via `VK_KHR_zero_initialize_workgroup_memory` or Vulkan 1.3
@@ -679,6 +715,12 @@ pub struct Options<'a> {
     /// Map of resources to information about the binding.
     pub binding_map: BindingMap,
 
+    /// Vertex shader outputs captured with transform feedback, by location.
+    ///
+    /// If this isn't empty, vertex entry points are written with the `Xfb`
+    /// execution mode, which requires the `TransformFeedback` capability.
+    pub transform_feedback: TransformFeedbackMap,
+
     /// If given, the set of capabilities modules are allowed to use. Code that
     /// requires capabilities beyond these is rejected with an error.
     ///
@@ -707,6 +749,7 @@ impl<'a> Default for Options<'a> {
             lang_version: (1, 0),
             flags,
             binding_map: BindingMap::default(),
+            transform_feedback: TransformFeedbackMap::default(),
             capabilities: None,
             bounds_check_policies: crate::proc::BoundsCheckPolicies::default(),
             zero_initialize_workgroup_memory: ZeroInitializeWorkgroupMemoryMode::Polyfill,
@@ -0,0 +1,199 @@
+/*!
+Generating SPIR-V for subgroup operations.
+
+Naga's subgroup statements map to the `OpGroupNonUniform*` instructions, which
+all operate in the `Subgroup` scope and require SPIR-V 1.3.
+*/
+
+use super::{Block, BlockContext, Error, Instruction, LocalType, LookupType, Writer};
+use crate::{arena::Handle, TypeInner};
+
+impl Writer {
+    /// Requires the `GroupNonUniform` capability used by every subgroup
+    /// operation and built-in.
+    pub(super) fn request_subgroup(&mut self, what: &'static str) -> Result<(), Error> {
+        if self.physical_layout.version < 0x10300 {
+            return Err(Error::FeatureNotImplemented(
+                "subgroup operations before SPIR-V 1.3",
+            ));
+        }
+        self.require_any(what, &[spirv::Capability::GroupNonUniform])
+    }
+}
+
+impl BlockContext<'_> {
+    pub(super) fn write_subgroup_ballot(
+        &mut self,
+        predicate: &Option<Handle<crate::Expression>>,
+        result: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> Result<(), Error> {
+        self.writer.request_subgroup("subgroup ballot")?;
+        self.writer.require_any(
+            "subgroup ballot",
+            &[spirv::Capability::GroupNonUniformBallot],
+        )?;
+
+        let vec4_u32_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: Some(crate::VectorSize::Quad),
+            scalar: crate::Scalar::U32,
+            pointer_space: None,
+        }));
+        let exec_scope_id = self.get_scope_constant(spirv::Scope::Subgroup as u32);
+        let predicate = match *predicate {
+            Some(predicate) => self.cached[predicate],
+            None => self.writer.get_constant_scalar(crate::Literal::Bool(true)),
+        };
+        let id = self.gen_id();
+        block.body.push(Instruction::group_non_uniform_ballot(
+            vec4_u32_type_id,
+            id,
+            exec_scope_id,
+            predicate,
+        ));
+        self.cached[result] = id;
+        Ok(())
+    }
+
+    pub(super) fn write_subgroup_operation(
+        &mut self,
+        op: &crate::SubgroupOperation,
+        collective_op: &crate::CollectiveOperation,
+        argument: Handle<crate::Expression>,
+        result: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> Result<(), Error> {
+        use crate::SubgroupOperation as sg;
+
+        self.writer.request_subgroup("subgroup operations")?;
+        match *op {
+            sg::All | sg::Any => self.writer.require_any(
+                "subgroup vote operations",
+                &[spirv::Capability::GroupNonUniformVote],
+            )?,
+            _ => self.writer.require_any(
+                "subgroup arithmetic operations",
+                &[spirv::Capability::GroupNonUniformArithmetic],
+            )?,
+        }
+
+        let (is_scalar, scalar) = match *self.fun_info[result].ty.inner_with(&self.ir_module.types)
+        {
+            TypeInner::Scalar(scalar) => (true, scalar),
+            TypeInner::Vector { scalar, .. } => (false, scalar),
+            _ => return Err(Error::Validation("invalid subgroup operand type")),
+        };
+        let result_type_id = self.get_expression_type_id(&self.fun_info[result].ty);
+
+        use crate::ScalarKind as sk;
+        use spirv::Op;
+        let spirv_op = match (scalar.kind, *op) {
+            (sk::Bool, sg::All) if is_scalar => Op::GroupNonUniformAll,
+            (sk::Bool, sg::Any) if is_scalar => Op::GroupNonUniformAny,
+            (sk::Sint | sk::Uint, sg::Add) => Op::GroupNonUniformIAdd,
+            (sk::Float, sg::Add) => Op::GroupNonUniformFAdd,
+            (sk::Sint | sk::Uint, sg::Mul) => Op::GroupNonUniformIMul,
+            (sk::Float, sg::Mul) => Op::GroupNonUniformFMul,
+            (sk::Sint, sg::Max) => Op::GroupNonUniformSMax,
+            (sk::Uint, sg::Max) => Op::GroupNonUniformUMax,
+            (sk::Float, sg::Max) => Op::GroupNonUniformFMax,
+            (sk::Sint, sg::Min) => Op::GroupNonUniformSMin,
+            (sk::Uint, sg::Min) => Op::GroupNonUniformUMin,
+            (sk::Float, sg::Min) => Op::GroupNonUniformFMin,
+            (sk::Sint | sk::Uint, sg::And) => Op::GroupNonUniformBitwiseAnd,
+            (sk::Sint | sk::Uint, sg::Or) => Op::GroupNonUniformBitwiseOr,
+            (sk::Sint | sk::Uint, sg::Xor) => Op::GroupNonUniformBitwiseXor,
+            (_, _) => return Err(Error::Validation("invalid subgroup operand type")),
+        };
+
+        let group_op = match *op {
+            sg::All | sg::Any => None,
+            _ => Some(match *collective_op {
+                crate::CollectiveOperation::Reduce => spirv::GroupOperation::Reduce,
+                crate::CollectiveOperation::InclusiveScan => spirv::GroupOperation::InclusiveScan,
+                crate::CollectiveOperation::ExclusiveScan => spirv::GroupOperation::ExclusiveScan,
+            }),
+        };
+
+        let exec_scope_id = self.get_scope_constant(spirv::Scope::Subgroup as u32);
+        let id = self.gen_id();
+        block.body.push(Instruction::group_non_uniform_arithmetic(
+            spirv_op,
+            result_type_id,
+            id,
+            exec_scope_id,
+            group_op,
+            self.cached[argument],
+        ));
+        self.cached[result] = id;
+        Ok(())
+    }
+
+    pub(super) fn write_subgroup_gather(
+        &mut self,
+        mode: &crate::GatherMode,
+        argument: Handle<crate::Expression>,
+        result: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> Result<(), Error> {
+        self.writer.request_subgroup("subgroup gather")?;
+        match *mode {
+            crate::GatherMode::BroadcastFirst | crate::GatherMode::Broadcast(_) => {
+                self.writer.require_any(
+                    "subgroup broadcast",
+                    &[spirv::Capability::GroupNonUniformBallot],
+                )?;
+            }
+            crate::GatherMode::Shuffle(_) | crate::GatherMode::ShuffleXor(_) => {
+                self.writer.require_any(
+                    "subgroup shuffle",
+                    &[spirv::Capability::GroupNonUniformShuffle],
+                )?;
+            }
+            crate::GatherMode::ShuffleDown(_) | crate::GatherMode::ShuffleUp(_) => {
+                self.writer.require_any(
+                    "subgroup relative shuffle",
+                    &[spirv::Capability::GroupNonUniformShuffleRelative],
+                )?;
+            }
+        }
+
+        let result_type_id = self.get_expression_type_id(&self.fun_info[result].ty);
+        let exec_scope_id = self.get_scope_constant(spirv::Scope::Subgroup as u32);
+        let arg_id = self.cached[argument];
+        let id = self.gen_id();
+        let instruction = match *mode {
+            crate::GatherMode::BroadcastFirst => Instruction::group_non_uniform_broadcast_first(
+                result_type_id,
+                id,
+                exec_scope_id,
+                arg_id,
+            ),
+            crate::GatherMode::Broadcast(index)
+            | crate::GatherMode::Shuffle(index)
+            | crate::GatherMode::ShuffleDown(index)
+            | crate::GatherMode::ShuffleUp(index)
+            | crate::GatherMode::ShuffleXor(index) => {
+                let op = match *mode {
+                    crate::GatherMode::BroadcastFirst => unreachable!(),
+                    crate::GatherMode::Broadcast(_) => spirv::Op::GroupNonUniformBroadcast,
+                    crate::GatherMode::Shuffle(_) => spirv::Op::GroupNonUniformShuffle,
+                    crate::GatherMode::ShuffleDown(_) => spirv::Op::GroupNonUniformShuffleDown,
+                    crate::GatherMode::ShuffleUp(_) => spirv::Op::GroupNonUniformShuffleUp,
+                    crate::GatherMode::ShuffleXor(_) => spirv::Op::GroupNonUniformShuffleXor,
+                };
+                Instruction::group_non_uniform_gather(
+                    op,
+                    result_type_id,
+                    id,
+                    exec_scope_id,
+                    arg_id,
+                    self.cached[index],
+                )
+            }
+        };
+        block.body.push(instruction);
+        self.cached[result] = id;
+        Ok(())
+    }
+}
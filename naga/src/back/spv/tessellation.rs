@@ -0,0 +1,221 @@
+/*!
+Generating SPIR-V for tessellation shaders.
+
+Naga's tessellation control shaders return the values of their own vertex of
+the output patch, along with the values of the whole patch, as described by
+[`TessellationStageInfo`]. SPIR-V instead expects the values of the vertices in
+array output variables, with an element per vertex of the output patch. So the
+members of the result holding a value per vertex are stored to the element
+indexed by the invocation id, and the values of the patch are only stored by
+the invocation whose id is 0.
+
+[`TessellationStageInfo`]: crate::TessellationStageInfo
+*/
+
+use super::{Block, BlockContext, Error, Instruction, LookupType, Writer, WriterFlags};
+use crate::arena::Handle;
+use spirv::Word;
+
+impl Writer {
+    /// Writes the execution modes of a tessellation entry point, returning
+    /// its execution model.
+    pub(super) fn write_tessellation_execution_modes(
+        &mut self,
+        function_id: Word,
+        tessellation_info: &crate::TessellationStageInfo,
+    ) -> Result<spirv::ExecutionModel, Error> {
+        use spirv::ExecutionMode as Em;
+
+        self.require_any("tessellation shaders", &[spirv::Capability::Tessellation])?;
+
+        match *tessellation_info {
+            crate::TessellationStageInfo::Control { output_vertices } => {
+                Instruction::execution_mode(function_id, Em::OutputVertices, &[output_vertices])
+                    .to_words(&mut self.logical_layout.execution_modes);
+                Ok(spirv::ExecutionModel::TessellationControl)
+            }
+            crate::TessellationStageInfo::Evaluation {
+                primitive,
+                spacing,
+                clockwise,
+                point_mode,
+            } => {
+                let primitive = match primitive {
+                    crate::TessellationPrimitive::Triangles => Em::Triangles,
+                    crate::TessellationPrimitive::Quads => Em::Quads,
+                    crate::TessellationPrimitive::Isolines => Em::Isolines,
+                };
+                let spacing = match spacing {
+                    crate::TessellationSpacing::Equal => Em::SpacingEqual,
+                    crate::TessellationSpacing::FractionalEven => Em::SpacingFractionalEven,
+                    crate::TessellationSpacing::FractionalOdd => Em::SpacingFractionalOdd,
+                };
+                let order = if clockwise {
+                    Em::VertexOrderCw
+                } else {
+                    Em::VertexOrderCcw
+                };
+                let point_mode = point_mode.then_some(Em::PointMode);
+                for mode in [Some(primitive), Some(spacing), Some(order), point_mode]
+                    .into_iter()
+                    .flatten()
+                {
+                    Instruction::execution_mode(function_id, mode, &[])
+                        .to_words(&mut self.logical_layout.execution_modes);
+                }
+                Ok(spirv::ExecutionModel::TessellationEvaluation)
+            }
+        }
+    }
+
+    /// Writes the output variable of a per-vertex member of the result of a
+    /// tessellation control shader, holding an array of `output_vertices`
+    /// elements of type `ty`.
+    pub(super) fn write_tessellation_control_output(
+        &mut self,
+        ir_module: &crate::Module,
+        debug_name: Option<&str>,
+        ty: Handle<crate::Type>,
+        binding: &crate::Binding,
+        output_vertices: u32,
+    ) -> Result<Word, Error> {
+        let class = spirv::StorageClass::Output;
+        self.request_float16_storage(&ir_module.types, ty, class)?;
+
+        let length_id = self.get_index_constant(output_vertices);
+        let element_type_id = self.get_type_id(LookupType::Handle(ty));
+        let array_type_id = self.id_gen.next();
+        Instruction::type_array(array_type_id, element_type_id, length_id)
+            .to_words(&mut self.logical_layout.declarations);
+        let pointer_type_id = self.id_gen.next();
+        Instruction::type_pointer(pointer_type_id, class, array_type_id)
+            .to_words(&mut self.logical_layout.declarations);
+        let id = self.id_gen.next();
+        Instruction::variable(pointer_type_id, id, class, None)
+            .to_words(&mut self.logical_layout.declarations);
+
+        if self
+            .flags
+            .contains(WriterFlags::DEBUG | WriterFlags::LABEL_VARYINGS)
+        {
+            if let Some(name) = debug_name {
+                self.debugs.push(Instruction::name(id, name));
+            }
+        }
+
+        self.decorate_varying(
+            ir_module,
+            crate::ShaderStage::TessellationControl,
+            class,
+            id,
+            ty,
+            binding,
+        )?;
+        Ok(id)
+    }
+}
+
+impl BlockContext<'_> {
+    /// Writes the return of the value `value_id` by a tessellation control
+    /// shader invocation `invocation_id`, ending `block`.
+    pub(super) fn write_tessellation_control_return(
+        &mut self,
+        value_id: Word,
+        invocation_id: Word,
+        mut block: Block,
+    ) -> Result<(), Error> {
+        let ir_result = self.ir_function.result.as_ref().unwrap();
+        let members: Vec<_> = match ir_result.binding {
+            Some(ref binding) => vec![(ir_result.ty, binding)],
+            None => match self.ir_module.types[ir_result.ty].inner {
+                crate::TypeInner::Struct { ref members, .. } => members
+                    .iter()
+                    .map(|member| (member.ty, member.binding.as_ref().unwrap()))
+                    .collect(),
+                _ => {
+                    return Err(Error::Validation(
+                        "missing result binding on an entry point",
+                    ))
+                }
+            },
+        };
+
+        let Some(ref context) = self.function.entry_point_context else {
+            unreachable!("tessellation control shaders are entry points");
+        };
+        let mut patch_body = Vec::new();
+        for (index, (res_member, (ty, binding))) in context.results.iter().zip(members).enumerate()
+        {
+            let member_value_id = match ir_result.binding {
+                Some(_) => value_id,
+                None => {
+                    let member_value_id = self.writer.id_gen.next();
+                    block.body.push(Instruction::composite_extract(
+                        res_member.type_id,
+                        member_value_id,
+                        value_id,
+                        &[index as u32],
+                    ));
+                    member_value_id
+                }
+            };
+
+            if binding.is_per_vertex() {
+                let pointer_type_id = self.writer.get_pointer_id(
+                    &self.ir_module.types,
+                    ty,
+                    spirv::StorageClass::Output,
+                )?;
+                let pointer_id = self.writer.id_gen.next();
+                block.body.push(Instruction::access_chain(
+                    pointer_type_id,
+                    pointer_id,
+                    res_member.id,
+                    &[invocation_id],
+                ));
+                block
+                    .body
+                    .push(Instruction::store(pointer_id, member_value_id, None));
+            } else {
+                patch_body.push(Instruction::store(res_member.id, member_value_id, None));
+            }
+        }
+
+        if patch_body.is_empty() {
+            self.function.consume(block, Instruction::return_void());
+            return Ok(());
+        }
+
+        // Only the first invocation stores the values of the patch.
+        let bool_type_id = self.writer.get_bool_type_id();
+        let zero_id = self.writer.get_index_constant(0);
+        let condition_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IEqual,
+            bool_type_id,
+            condition_id,
+            invocation_id,
+            zero_id,
+        ));
+        let store_id = self.gen_id();
+        let merge_id = self.gen_id();
+        block.body.push(Instruction::selection_merge(
+            merge_id,
+            spirv::SelectionControl::NONE,
+        ));
+        self.function.consume(
+            block,
+            Instruction::branch_conditional(condition_id, store_id, merge_id),
+        );
+        self.function.consume(
+            Block {
+                label_id: store_id,
+                body: patch_body,
+            },
+            Instruction::branch(merge_id),
+        );
+        self.function
+            .consume(Block::new(merge_id), Instruction::return_void());
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use super::{
     block::DebugInfoInner,
-    helpers::{contains_builtin, global_needs_wrapper, map_storage_class},
+    helpers::{contains_builtin, contains_float16, global_needs_wrapper, map_storage_class},
     make_local, Block, BlockContext, CachedConstant, CachedExpressions, DebugInfo,
     EntryPointContext, Error, Function, FunctionArgument, GlobalVariable, IdGenerator, Instruction,
     LocalType, LocalVariable, LogicalLayout, LookupFunctionType, LookupType, LoopContext, Options,
@@ -18,10 +18,14 @@ use std::collections::hash_map::Entry;
 struct FunctionInterface<'a> {
     varying_ids: &'a mut Vec<Word>,
     stage: crate::ShaderStage,
+    workgroup_size: [u32; 3],
+    task_payload: Option<Handle<crate::GlobalVariable>>,
+    mesh_info: Option<&'a crate::MeshStageInfo>,
+    tessellation_info: Option<&'a crate::TessellationStageInfo>,
 }
 
 impl Function {
-    fn to_words(&self, sink: &mut impl Extend<Word>) {
+    pub(super) fn to_words(&self, sink: &mut impl Extend<Word>) {
         self.signature.as_ref().unwrap().to_words(sink);
         for argument in self.parameters.iter() {
             argument.instruction.to_words(sink);
@@ -75,6 +79,8 @@ impl Writer {
             cached_constants: crate::FastHashMap::default(),
             global_variables: Vec::new(),
             binding_map: options.binding_map.clone(),
+            transform_feedback: options.transform_feedback.clone(),
+            geometry_outputs: crate::FastHashMap::default(),
             saved_cached: CachedExpressions::default(),
             gl450_ext_inst_id,
             temp_list: Vec::new(),
@@ -107,6 +113,7 @@ impl Writer {
             zero_initialize_workgroup_memory: self.zero_initialize_workgroup_memory,
             capabilities_available: take(&mut self.capabilities_available),
             binding_map: take(&mut self.binding_map),
+            transform_feedback: take(&mut self.transform_feedback),
 
             // Initialized afresh:
             id_gen,
@@ -126,6 +133,7 @@ impl Writer {
             constant_ids: take(&mut self.constant_ids).recycle(),
             cached_constants: take(&mut self.cached_constants).recycle(),
             global_variables: take(&mut self.global_variables).recycle(),
+            geometry_outputs: take(&mut self.geometry_outputs).recycle(),
             saved_cached: take(&mut self.saved_cached).recycle(),
             temp_list: take(&mut self.temp_list).recycle(),
         };
@@ -336,6 +344,10 @@ impl Writer {
         let mut ep_context = EntryPointContext {
             argument_ids: Vec::new(),
             results: Vec::new(),
+            emits_mesh_tasks: false,
+            task_payload_id: None,
+            mesh_epilogue_id: None,
+            invocation_id: None,
         };
 
         let mut local_invocation_id = None;
@@ -374,6 +386,11 @@ impl Writer {
                     if binding == &crate::Binding::BuiltIn(crate::BuiltIn::LocalInvocationId) {
                         local_invocation_id = Some(id);
                     }
+                    if binding == &crate::Binding::BuiltIn(crate::BuiltIn::InvocationId)
+                        && iface.stage == crate::ShaderStage::TessellationControl
+                    {
+                        ep_context.invocation_id = Some(id);
+                    }
 
                     id
                 } else if let crate::TypeInner::Struct { ref members, .. } =
@@ -403,6 +420,11 @@ impl Writer {
                         if binding == &crate::Binding::BuiltIn(crate::BuiltIn::GlobalInvocationId) {
                             local_invocation_id = Some(id);
                         }
+                        if binding == &crate::Binding::BuiltIn(crate::BuiltIn::InvocationId)
+                            && iface.stage == crate::ShaderStage::TessellationControl
+                        {
+                            ep_context.invocation_id = Some(id);
+                        }
                     }
                     prelude.body.push(Instruction::composite_construct(
                         argument_type_id,
@@ -446,18 +468,37 @@ impl Writer {
                 if let Some(ref mut iface) = interface {
                     let mut has_point_size = false;
                     let class = spirv::StorageClass::Output;
-                    if let Some(ref binding) = result.binding {
+                    if result.binding == Some(crate::Binding::BuiltIn(crate::BuiltIn::MeshTaskSize))
+                    {
+                        // Task shaders pass the size of the grid of mesh
+                        // shaders they launch to `OpEmitMeshTasksEXT`, rather
+                        // than storing it to an output variable.
+                        ep_context.emits_mesh_tasks = true;
+                    } else if let Some(ref binding) = result.binding {
                         has_point_size |=
                             *binding == crate::Binding::BuiltIn(crate::BuiltIn::PointSize);
                         let type_id = self.get_type_id(LookupType::Handle(result.ty));
-                        let varying_id = self.write_varying(
-                            ir_module,
-                            iface.stage,
-                            class,
-                            None,
-                            result.ty,
-                            binding,
-                        )?;
+                        let varying_id = match iface.tessellation_info {
+                            Some(&crate::TessellationStageInfo::Control { output_vertices })
+                                if binding.is_per_vertex() =>
+                            {
+                                self.write_tessellation_control_output(
+                                    ir_module,
+                                    None,
+                                    result.ty,
+                                    binding,
+                                    output_vertices,
+                                )?
+                            }
+                            _ => self.write_varying(
+                                ir_module,
+                                iface.stage,
+                                class,
+                                None,
+                                result.ty,
+                                binding,
+                            )?,
+                        };
                         iface.varying_ids.push(varying_id);
                         ep_context.results.push(ResultMember {
                             id: varying_id,
@@ -473,14 +514,26 @@ impl Writer {
                             let binding = member.binding.as_ref().unwrap();
                             has_point_size |=
                                 *binding == crate::Binding::BuiltIn(crate::BuiltIn::PointSize);
-                            let varying_id = self.write_varying(
-                                ir_module,
-                                iface.stage,
-                                class,
-                                name,
-                                member.ty,
-                                binding,
-                            )?;
+                            let varying_id = match iface.tessellation_info {
+                                Some(&crate::TessellationStageInfo::Control {
+                                    output_vertices,
+                                }) if binding.is_per_vertex() => self
+                                    .write_tessellation_control_output(
+                                        ir_module,
+                                        name,
+                                        member.ty,
+                                        binding,
+                                        output_vertices,
+                                    )?,
+                                _ => self.write_varying(
+                                    ir_module,
+                                    iface.stage,
+                                    class,
+                                    name,
+                                    member.ty,
+                                    binding,
+                                )?,
+                            };
                             iface.varying_ids.push(varying_id);
                             ep_context.results.push(ResultMember {
                                 id: varying_id,
@@ -541,7 +594,23 @@ impl Writer {
             function_type,
         ));
 
-        if interface.is_some() {
+        if let Some(ref mut iface) = interface {
+            if let Some(handle) = iface.task_payload {
+                // Unused payloads are left out of the interface, so they can
+                // only be passed along if the task shader uses them.
+                if ep_context.emits_mesh_tasks && !info[handle].is_empty() {
+                    ep_context.task_payload_id = Some(self.global_variables[handle.index()].var_id);
+                }
+            }
+            if let Some(mesh_info) = iface.mesh_info {
+                ep_context.mesh_epilogue_id = Some(self.write_mesh_epilogue(
+                    ir_module,
+                    info,
+                    mesh_info,
+                    iface.workgroup_size,
+                    iface.varying_ids,
+                )?);
+            }
             function.entry_point_context = Some(ep_context);
         }
 
@@ -680,7 +749,10 @@ impl Writer {
                     super::ZeroInitializeWorkgroupMemoryMode::Polyfill,
                     Some(
                         ref mut interface @ FunctionInterface {
-                            stage: crate::ShaderStage::Compute,
+                            stage:
+                                crate::ShaderStage::Compute
+                                | crate::ShaderStage::Task
+                                | crate::ShaderStage::Mesh,
                             ..
                         },
                     ),
@@ -750,12 +822,25 @@ impl Writer {
             Some(FunctionInterface {
                 varying_ids: &mut interface_ids,
                 stage: entry_point.stage,
+                workgroup_size: entry_point.workgroup_size,
+                task_payload: entry_point.task_payload,
+                mesh_info: entry_point.mesh_info.as_ref(),
+                tessellation_info: entry_point.tessellation_info.as_ref(),
             }),
             debug_info,
         )?;
 
         let exec_model = match entry_point.stage {
-            crate::ShaderStage::Vertex => spirv::ExecutionModel::Vertex,
+            crate::ShaderStage::Vertex => {
+                if !self.transform_feedback.is_empty() {
+                    self.require_any(
+                        "transform feedback",
+                        &[spirv::Capability::TransformFeedback],
+                    )?;
+                    self.write_execution_mode(function_id, spirv::ExecutionMode::Xfb)?;
+                }
+                spirv::ExecutionModel::Vertex
+            }
             crate::ShaderStage::Fragment => {
                 self.write_execution_mode(function_id, spirv::ExecutionMode::OriginUpperLeft)?;
                 if let Some(ref result) = entry_point.function.result {
@@ -784,6 +869,44 @@ impl Writer {
                 .to_words(&mut self.logical_layout.execution_modes);
                 spirv::ExecutionModel::GLCompute
             }
+            crate::ShaderStage::Task | crate::ShaderStage::Mesh => {
+                self.request_mesh_shading()?;
+                Instruction::execution_mode(
+                    function_id,
+                    spirv::ExecutionMode::LocalSize,
+                    &entry_point.workgroup_size,
+                )
+                .to_words(&mut self.logical_layout.execution_modes);
+                match entry_point.mesh_info {
+                    Some(ref mesh_info) => {
+                        self.write_mesh_execution_modes(function_id, mesh_info);
+                        spirv::ExecutionModel::MeshEXT
+                    }
+                    None if entry_point.stage == crate::ShaderStage::Mesh => {
+                        return Err(Error::Validation("mesh shader without mesh stage info"));
+                    }
+                    None => spirv::ExecutionModel::TaskEXT,
+                }
+            }
+            crate::ShaderStage::Geometry => {
+                let geometry_info = entry_point.geometry_info.as_ref().ok_or(Error::Validation(
+                    "geometry shader without geometry stage info",
+                ))?;
+                self.write_geometry_execution_modes(function_id, geometry_info)?;
+                self.write_geometry_interface(ir_module, geometry_info, &mut interface_ids)?;
+                spirv::ExecutionModel::Geometry
+            }
+            crate::ShaderStage::TessellationControl
+            | crate::ShaderStage::TessellationEvaluation => {
+                let tessellation_info =
+                    entry_point
+                        .tessellation_info
+                        .as_ref()
+                        .ok_or(Error::Validation(
+                            "tessellation shader without tessellation stage info",
+                        ))?;
+                self.write_tessellation_execution_modes(function_id, tessellation_info)?
+            }
         };
         //self.check(exec_model.required_capabilities())?;
 
@@ -818,8 +941,14 @@ impl Writer {
                 Instruction::type_int(id, bits, signedness)
             }
             Sk::Float => {
-                if bits == 64 {
-                    self.capabilities_used.insert(spirv::Capability::Float64);
+                match bits {
+                    16 => {
+                        self.capabilities_used.insert(spirv::Capability::Float16);
+                    }
+                    64 => {
+                        self.capabilities_used.insert(spirv::Capability::Float64);
+                    }
+                    _ => {}
                 }
                 Instruction::type_float(id, bits)
             }
@@ -830,6 +959,30 @@ impl Writer {
         }
     }
 
+    /// Requests the capabilities to access the 16-bit floats of values of type
+    /// `ty` in `class`.
+    pub(super) fn request_float16_storage(
+        &mut self,
+        types: &UniqueArena<crate::Type>,
+        ty: Handle<crate::Type>,
+        class: spirv::StorageClass,
+    ) -> Result<(), Error> {
+        let capability = match class {
+            spirv::StorageClass::StorageBuffer => spirv::Capability::StorageBuffer16BitAccess,
+            spirv::StorageClass::Uniform => spirv::Capability::UniformAndStorageBuffer16BitAccess,
+            spirv::StorageClass::PushConstant => spirv::Capability::StoragePushConstant16,
+            spirv::StorageClass::Input | spirv::StorageClass::Output => {
+                spirv::Capability::StorageInputOutput16
+            }
+            _ => return Ok(()),
+        };
+        if contains_float16(ty, types) {
+            self.require_any("16-bit floats in this address space", &[capability])?;
+            self.use_extension("SPV_KHR_16bit_storage");
+        }
+        Ok(())
+    }
+
     fn request_type_capabilities(&mut self, inner: &crate::TypeInner) -> Result<(), Error> {
         match *inner {
             crate::TypeInner::Image {
@@ -1180,11 +1333,19 @@ impl Writer {
                 Instruction::constant_64bit(type_id, id, bits as u32, (bits >> 32) as u32)
             }
             crate::Literal::F32(value) => Instruction::constant_32bit(type_id, id, value.to_bits()),
+            // Values narrower than 32 bits take a whole word, with the high
+            // order bits set to 0
+            crate::Literal::F16(value) => {
+                Instruction::constant_32bit(type_id, id, u32::from(value.to_bits()))
+            }
             crate::Literal::U32(value) => Instruction::constant_32bit(type_id, id, value),
             crate::Literal::I32(value) => Instruction::constant_32bit(type_id, id, value as u32),
             crate::Literal::I64(value) => {
                 Instruction::constant_64bit(type_id, id, value as u32, (value >> 32) as u32)
             }
+            crate::Literal::U64(value) => {
+                Instruction::constant_64bit(type_id, id, value as u32, (value >> 32) as u32)
+            }
             crate::Literal::Bool(true) => Instruction::constant_true(type_id, id),
             crate::Literal::Bool(false) => Instruction::constant_false(type_id, id),
             crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
@@ -1293,6 +1454,8 @@ impl Writer {
     pub(super) fn write_barrier(&mut self, flags: crate::Barrier, block: &mut Block) {
         let memory_scope = if flags.contains(crate::Barrier::STORAGE) {
             spirv::Scope::Device
+        } else if flags.contains(crate::Barrier::SUB_GROUP) {
+            spirv::Scope::Subgroup
         } else {
             spirv::Scope::Workgroup
         };
@@ -1305,7 +1468,15 @@ impl Writer {
             spirv::MemorySemantics::WORKGROUP_MEMORY,
             flags.contains(crate::Barrier::WORK_GROUP),
         );
-        let exec_scope_id = self.get_index_constant(spirv::Scope::Workgroup as u32);
+        semantics.set(
+            spirv::MemorySemantics::SUBGROUP_MEMORY,
+            flags.contains(crate::Barrier::SUB_GROUP),
+        );
+        let exec_scope_id = if flags.contains(crate::Barrier::SUB_GROUP) {
+            self.get_index_constant(spirv::Scope::Subgroup as u32)
+        } else {
+            self.get_index_constant(spirv::Scope::Workgroup as u32)
+        };
         let mem_scope_id = self.get_index_constant(memory_scope as u32);
         let semantics_id = self.get_index_constant(semantics.bits());
         block.body.push(Instruction::control_barrier(
@@ -1441,7 +1612,7 @@ impl Writer {
     /// [`EntryPoint`]: crate::EntryPoint
     /// [`Input`]: spirv::StorageClass::Input
     /// [`Output`]: spirv::StorageClass::Output
-    fn write_varying(
+    pub(super) fn write_varying(
         &mut self,
         ir_module: &crate::Module,
         stage: crate::ShaderStage,
@@ -1450,6 +1621,8 @@ impl Writer {
         ty: Handle<crate::Type>,
         binding: &crate::Binding,
     ) -> Result<Word, Error> {
+        self.request_float16_storage(&ir_module.types, ty, class)?;
+
         let id = self.id_gen.next();
         let pointer_type_id = self.get_pointer_id(&ir_module.types, ty, class)?;
         Instruction::variable(pointer_type_id, id, class, None)
@@ -1464,6 +1637,20 @@ impl Writer {
             }
         }
 
+        self.decorate_varying(ir_module, stage, class, id, ty, binding)?;
+        Ok(id)
+    }
+
+    /// Decorates the variable `id` of the varying of type `ty` with `binding`.
+    pub(super) fn decorate_varying(
+        &mut self,
+        ir_module: &crate::Module,
+        stage: crate::ShaderStage,
+        class: spirv::StorageClass,
+        id: Word,
+        ty: Handle<crate::Type>,
+        binding: &crate::Binding,
+    ) -> Result<(), Error> {
         use spirv::{BuiltIn, Decoration};
 
         match *binding {
@@ -1472,8 +1659,20 @@ impl Writer {
                 interpolation,
                 sampling,
                 second_blend_source,
+                patch,
             } => {
                 self.decorate(id, Decoration::Location, &[location]);
+                if patch {
+                    self.decorate(id, Decoration::Patch, &[]);
+                }
+
+                if class == spirv::StorageClass::Output && stage == crate::ShaderStage::Vertex {
+                    if let Some(target) = self.transform_feedback.get(&location).copied() {
+                        self.decorate(id, Decoration::XfbBuffer, &[target.buffer]);
+                        self.decorate(id, Decoration::XfbStride, &[target.stride]);
+                        self.decorate(id, Decoration::Offset, &[target.offset]);
+                    }
+                }
 
                 let no_decorations =
                     // VUID-StandaloneSpirv-Flat-06202
@@ -1523,10 +1722,12 @@ impl Writer {
                             self.decorate(id, Decoration::Invariant, &[]);
                         }
 
-                        if class == spirv::StorageClass::Output {
-                            BuiltIn::Position
-                        } else {
+                        if class == spirv::StorageClass::Input
+                            && stage == crate::ShaderStage::Fragment
+                        {
                             BuiltIn::FragCoord
+                        } else {
+                            BuiltIn::Position
                         }
                     }
                     Bi::ViewIndex => {
@@ -1551,17 +1752,35 @@ impl Writer {
                         BuiltIn::CullDistance
                     }
                     Bi::InstanceIndex => BuiltIn::InstanceIndex,
-                    Bi::PointSize => BuiltIn::PointSize,
+                    Bi::PointSize => {
+                        match stage {
+                            crate::ShaderStage::Geometry => self.require_any(
+                                "`point_size` built-in in geometry shaders",
+                                &[spirv::Capability::GeometryPointSize],
+                            )?,
+                            crate::ShaderStage::TessellationControl
+                            | crate::ShaderStage::TessellationEvaluation => self.require_any(
+                                "`point_size` built-in in tessellation shaders",
+                                &[spirv::Capability::TessellationPointSize],
+                            )?,
+                            _ => {}
+                        }
+                        BuiltIn::PointSize
+                    }
                     Bi::VertexIndex => BuiltIn::VertexIndex,
                     // fragment
                     Bi::FragDepth => BuiltIn::FragDepth,
                     Bi::PointCoord => BuiltIn::PointCoord,
                     Bi::FrontFacing => BuiltIn::FrontFacing,
                     Bi::PrimitiveIndex => {
-                        self.require_any(
-                            "`primitive_index` built-in",
-                            &[spirv::Capability::Geometry],
-                        )?;
+                        let capability = match stage {
+                            crate::ShaderStage::TessellationControl
+                            | crate::ShaderStage::TessellationEvaluation => {
+                                spirv::Capability::Tessellation
+                            }
+                            _ => spirv::Capability::Geometry,
+                        };
+                        self.require_any("`primitive_index` built-in", &[capability])?;
                         BuiltIn::PrimitiveId
                     }
                     Bi::SampleIndex => {
@@ -1580,6 +1799,53 @@ impl Writer {
                     Bi::WorkGroupId => BuiltIn::WorkgroupId,
                     Bi::WorkGroupSize => BuiltIn::WorkgroupSize,
                     Bi::NumWorkGroups => BuiltIn::NumWorkgroups,
+                    // geometry
+                    Bi::InvocationId => BuiltIn::InvocationId,
+                    // tessellation
+                    Bi::TessLevelOuter => {
+                        self.decorate(id, Decoration::Patch, &[]);
+                        BuiltIn::TessLevelOuter
+                    }
+                    Bi::TessLevelInner => {
+                        self.decorate(id, Decoration::Patch, &[]);
+                        BuiltIn::TessLevelInner
+                    }
+                    Bi::TessCoord => BuiltIn::TessCoord,
+                    Bi::PatchVertices => BuiltIn::PatchVertices,
+                    // mesh
+                    Bi::PointIndex => BuiltIn::PrimitivePointIndicesEXT,
+                    Bi::LineIndices => BuiltIn::PrimitiveLineIndicesEXT,
+                    Bi::TriangleIndices => BuiltIn::PrimitiveTriangleIndicesEXT,
+                    Bi::CullPrimitive => {
+                        self.decorate(id, Decoration::PerPrimitiveEXT, &[]);
+                        BuiltIn::CullPrimitiveEXT
+                    }
+                    Bi::MeshTaskSize
+                    | Bi::Vertices
+                    | Bi::Primitives
+                    | Bi::VertexCount
+                    | Bi::PrimitiveCount => {
+                        return Err(Error::Validation(
+                            "task and mesh shader output built-ins can't be varyings",
+                        ))
+                    }
+                    // subgroup
+                    Bi::NumSubgroups => {
+                        self.request_subgroup("`num_subgroups` built-in")?;
+                        BuiltIn::NumSubgroups
+                    }
+                    Bi::SubgroupId => {
+                        self.request_subgroup("`subgroup_id` built-in")?;
+                        BuiltIn::SubgroupId
+                    }
+                    Bi::SubgroupSize => {
+                        self.request_subgroup("`subgroup_size` built-in")?;
+                        BuiltIn::SubgroupSize
+                    }
+                    Bi::SubgroupInvocationId => {
+                        self.request_subgroup("`subgroup_invocation_id` built-in")?;
+                        BuiltIn::SubgroupLocalInvocationId
+                    }
                 };
 
                 self.decorate(id, Decoration::BuiltIn, &[built_in as u32]);
@@ -1613,7 +1879,7 @@ impl Writer {
             }
         }
 
-        Ok(id)
+        Ok(())
     }
 
     fn write_global_variable(
@@ -1627,6 +1893,7 @@ impl Writer {
         let class = map_storage_class(global_variable.space);
 
         //self.check(class.required_capabilities())?;
+        self.request_float16_storage(&ir_module.types, global_variable.ty, class)?;
 
         if self.flags.contains(WriterFlags::DEBUG) {
             if let Some(ref name) = global_variable.name {
@@ -1798,7 +2065,7 @@ impl Writer {
         Ok(())
     }
 
-    fn get_function_type(&mut self, lookup_function_type: LookupFunctionType) -> Word {
+    pub(super) fn get_function_type(&mut self, lookup_function_type: LookupFunctionType) -> Word {
         match self
             .lookup_function_type
             .entry(lookup_function_type.clone())
@@ -1925,7 +2192,16 @@ impl Writer {
             // for the globals it actually uses. Emit dummies for the others,
             // to preserve the indices in `global_variables`.
             let gvar = match ep_index {
-                Some(index) if mod_info.get_entry_point(index)[handle].is_empty() => {
+                // Mesh shaders write their output variable after returning,
+                // even if they don't use it themselves.
+                Some(index)
+                    if mod_info.get_entry_point(index)[handle].is_empty()
+                        && ir_module.entry_points[index]
+                            .mesh_info
+                            .as_ref()
+                            .map(|mesh_info| mesh_info.output_variable)
+                            != Some(handle) =>
+                {
                     GlobalVariable::dummy()
                 }
                 _ => {
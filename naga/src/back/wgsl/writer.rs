@@ -19,6 +19,9 @@ enum Attribute {
     Location(u32),
     SecondBlendSource,
     Stage(ShaderStage),
+    /// The mesh stage, writing its output to the given variable.
+    MeshStage(Handle<crate::GlobalVariable>),
+    Payload(Handle<crate::GlobalVariable>),
     WorkGroupSize([u32; 3]),
 }
 
@@ -58,6 +61,9 @@ bitflags::bitflags! {
     pub struct WriterFlags: u32 {
         /// Always annotate the type information instead of inferring.
         const EXPLICIT_TYPES = 0x1;
+        /// Name temporaries after the variables, struct members and functions
+        /// their values come from, instead of `_e` followed by a number.
+        const READABLE_NAMES = 0x2;
     }
 }
 
@@ -115,6 +121,16 @@ impl<W: Write> Writer<W> {
             }
         }
 
+        // Write the enable directives, which come before any declaration
+        if module
+            .types
+            .iter()
+            .any(|(_, ty)| ty.inner.scalar() == Some(crate::Scalar::F16))
+        {
+            writeln!(self.out, "enable f16;")?;
+            writeln!(self.out)?;
+        }
+
         // Write all structs
         for (handle, ty) in module.types.iter() {
             if let TypeInner::Struct { ref members, .. } = ty.inner {
@@ -170,13 +186,31 @@ impl<W: Write> Writer<W> {
 
         // Write all entry points
         for (index, ep) in module.entry_points.iter().enumerate() {
-            let attributes = match ep.stage {
+            let mut attributes = match ep.stage {
                 ShaderStage::Vertex | ShaderStage::Fragment => vec![Attribute::Stage(ep.stage)],
-                ShaderStage::Compute => vec![
-                    Attribute::Stage(ShaderStage::Compute),
+                ShaderStage::Compute | ShaderStage::Task => vec![
+                    Attribute::Stage(ep.stage),
                     Attribute::WorkGroupSize(ep.workgroup_size),
                 ],
+                ShaderStage::Mesh => {
+                    let mesh_info = ep.mesh_info.as_ref().ok_or_else(|| {
+                        Error::Custom("Mesh stage without an output variable".to_string())
+                    })?;
+                    vec![
+                        Attribute::MeshStage(mesh_info.output_variable),
+                        Attribute::WorkGroupSize(ep.workgroup_size),
+                    ]
+                }
+                ShaderStage::Geometry => {
+                    return Err(Error::Unimplemented("geometry shaders".into()))
+                }
+                ShaderStage::TessellationControl | ShaderStage::TessellationEvaluation => {
+                    return Err(Error::Unimplemented("tessellation shaders".into()))
+                }
             };
+            if let Some(task_payload) = ep.task_payload {
+                attributes.insert(1, Attribute::Payload(task_payload));
+            }
 
             self.write_attributes(&attributes)?;
             // Add a newline after attribute
@@ -209,6 +243,11 @@ impl<W: Write> Writer<W> {
                     ShaderStage::Compute => "ComputeOutput",
                     ShaderStage::Fragment => "FragmentOutput",
                     ShaderStage::Vertex => "VertexOutput",
+                    ShaderStage::Task => "TaskOutput",
+                    ShaderStage::Mesh => "MeshOutput",
+                    ShaderStage::Geometry => "GeometryOutput",
+                    ShaderStage::TessellationControl => "TessellationControlOutput",
+                    ShaderStage::TessellationEvaluation => "TessellationEvaluationOutput",
                 };
 
                 write!(self.out, "{name}")?;
@@ -331,9 +370,29 @@ impl<W: Write> Writer<W> {
                         ShaderStage::Vertex => "vertex",
                         ShaderStage::Fragment => "fragment",
                         ShaderStage::Compute => "compute",
+                        ShaderStage::Task => "task",
+                        ShaderStage::Mesh => {
+                            return Err(Error::Custom(
+                                "Mesh stages are written with their output variable".to_string(),
+                            ))
+                        }
+                        ShaderStage::Geometry => {
+                            return Err(Error::Unimplemented("geometry shaders".into()))
+                        }
+                        ShaderStage::TessellationControl | ShaderStage::TessellationEvaluation => {
+                            return Err(Error::Unimplemented("tessellation shaders".into()))
+                        }
                     };
                     write!(self.out, "@{stage_str} ")?;
                 }
+                Attribute::MeshStage(output_variable) => {
+                    let name = &self.names[&NameKey::GlobalVariable(output_variable)];
+                    write!(self.out, "@mesh({name}) ")?;
+                }
+                Attribute::Payload(task_payload) => {
+                    let name = &self.names[&NameKey::GlobalVariable(task_payload)];
+                    write!(self.out, "@payload({name}) ")?;
+                }
                 Attribute::WorkGroupSize(size) => {
                     write!(
                         self.out,
@@ -634,7 +693,7 @@ impl<W: Write> Writer<W> {
                             _ => false,
                         };
                         if min_ref_count <= info.ref_count || required_baking_expr {
-                            Some(format!("{}{}", back::BAKE_PREFIX, handle.index()))
+                            Some(self.bake_name(module, handle, handle, func_ctx))
                         } else {
                             None
                         }
@@ -726,7 +785,7 @@ impl<W: Write> Writer<W> {
             } => {
                 write!(self.out, "{level}")?;
                 if let Some(expr) = result {
-                    let name = format!("{}{}", back::BAKE_PREFIX, expr.index());
+                    let name = self.bake_name(module, expr, expr, func_ctx);
                     self.start_named_expr(module, expr, func_ctx, &name)?;
                     self.named_expressions.insert(expr, name);
                 }
@@ -747,7 +806,7 @@ impl<W: Write> Writer<W> {
                 result,
             } => {
                 write!(self.out, "{level}")?;
-                let res_name = format!("{}{}", back::BAKE_PREFIX, result.index());
+                let res_name = self.bake_name(module, result, pointer, func_ctx);
                 self.start_named_expr(module, result, func_ctx, &res_name)?;
                 self.named_expressions.insert(result, res_name);
 
@@ -765,7 +824,7 @@ impl<W: Write> Writer<W> {
             Statement::WorkGroupUniformLoad { pointer, result } => {
                 write!(self.out, "{level}")?;
                 // TODO: Obey named expressions here.
-                let res_name = format!("{}{}", back::BAKE_PREFIX, result.index());
+                let res_name = self.bake_name(module, result, pointer, func_ctx);
                 self.start_named_expr(module, result, func_ctx, &res_name)?;
                 self.named_expressions.insert(result, res_name);
                 write!(self.out, "workgroupUniformLoad(")?;
@@ -917,8 +976,102 @@ impl<W: Write> Writer<W> {
                 if barrier.contains(crate::Barrier::WORK_GROUP) {
                     writeln!(self.out, "{level}workgroupBarrier();")?;
                 }
+
+                if barrier.contains(crate::Barrier::SUB_GROUP) {
+                    writeln!(self.out, "{level}subgroupBarrier();")?;
+                }
             }
             Statement::RayQuery { .. } => unreachable!(),
+            Statement::DebugPrintf {
+                ref format,
+                ref arguments,
+            } => {
+                let format = string_literal(format);
+                write!(self.out, "{level}debugPrintf({format}")?;
+                for &argument in arguments {
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, argument, func_ctx)?;
+                }
+                writeln!(self.out, ");")?
+            }
+            Statement::EmitVertex { .. } | Statement::EndPrimitive => {
+                return Err(Error::Unimplemented("geometry shaders".into()))
+            }
+            Statement::SubgroupBallot { result, predicate } => {
+                write!(self.out, "{level}")?;
+                let res_name = self.bake_name(module, result, result, func_ctx);
+                self.start_named_expr(module, result, func_ctx, &res_name)?;
+                self.named_expressions.insert(result, res_name);
+
+                write!(self.out, "subgroupBallot(")?;
+                match predicate {
+                    Some(predicate) => self.write_expr(module, predicate, func_ctx)?,
+                    None => write!(self.out, "true")?,
+                }
+                writeln!(self.out, ");")?;
+            }
+            Statement::SubgroupCollectiveOperation {
+                op,
+                collective_op,
+                argument,
+                result,
+            } => {
+                write!(self.out, "{level}")?;
+                let res_name = self.bake_name(module, result, argument, func_ctx);
+                self.start_named_expr(module, result, func_ctx, &res_name)?;
+                self.named_expressions.insert(result, res_name);
+
+                use crate::{CollectiveOperation as co, SubgroupOperation as sg};
+                let fun = match (collective_op, op) {
+                    (co::Reduce, sg::All) => "subgroupAll",
+                    (co::Reduce, sg::Any) => "subgroupAny",
+                    (co::Reduce, sg::Add) => "subgroupAdd",
+                    (co::Reduce, sg::Mul) => "subgroupMul",
+                    (co::Reduce, sg::Max) => "subgroupMax",
+                    (co::Reduce, sg::Min) => "subgroupMin",
+                    (co::Reduce, sg::And) => "subgroupAnd",
+                    (co::Reduce, sg::Or) => "subgroupOr",
+                    (co::Reduce, sg::Xor) => "subgroupXor",
+                    (co::ExclusiveScan, sg::Add) => "subgroupExclusiveAdd",
+                    (co::ExclusiveScan, sg::Mul) => "subgroupExclusiveMul",
+                    (co::InclusiveScan, sg::Add) => "subgroupInclusiveAdd",
+                    (co::InclusiveScan, sg::Mul) => "subgroupInclusiveMul",
+                    _ => {
+                        return Err(Error::Custom(format!(
+                            "invalid subgroup operation {op:?} for {collective_op:?}"
+                        )))
+                    }
+                };
+                write!(self.out, "{fun}(")?;
+                self.write_expr(module, argument, func_ctx)?;
+                writeln!(self.out, ");")?;
+            }
+            Statement::SubgroupGather {
+                mode,
+                argument,
+                result,
+            } => {
+                write!(self.out, "{level}")?;
+                let res_name = self.bake_name(module, result, argument, func_ctx);
+                self.start_named_expr(module, result, func_ctx, &res_name)?;
+                self.named_expressions.insert(result, res_name);
+
+                let (fun, index) = match mode {
+                    crate::GatherMode::BroadcastFirst => ("subgroupBroadcastFirst", None),
+                    crate::GatherMode::Broadcast(index) => ("subgroupBroadcast", Some(index)),
+                    crate::GatherMode::Shuffle(index) => ("subgroupShuffle", Some(index)),
+                    crate::GatherMode::ShuffleDown(index) => ("subgroupShuffleDown", Some(index)),
+                    crate::GatherMode::ShuffleUp(index) => ("subgroupShuffleUp", Some(index)),
+                    crate::GatherMode::ShuffleXor(index) => ("subgroupShuffleXor", Some(index)),
+                };
+                write!(self.out, "{fun}(")?;
+                self.write_expr(module, argument, func_ctx)?;
+                if let Some(index) = index {
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, index, func_ctx)?;
+                }
+                writeln!(self.out, ");")?;
+            }
         }
 
         Ok(())
@@ -983,6 +1136,30 @@ impl<W: Write> Writer<W> {
         }
     }
 
+    /// Returns the name of the temporary holding the value of `handle`.
+    ///
+    /// With [`WriterFlags::READABLE_NAMES`], this is the name given by the
+    /// frontend, or the readable name of `source`, when there is one.
+    fn bake_name(
+        &mut self,
+        module: &Module,
+        handle: Handle<crate::Expression>,
+        source: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx,
+    ) -> String {
+        if self.flags.contains(WriterFlags::READABLE_NAMES) {
+            let name = func_ctx
+                .named_expressions
+                .get(&handle)
+                .cloned()
+                .or_else(|| func_ctx.readable_name(source, module));
+            if let Some(name) = name {
+                return self.namer.call(&name);
+            }
+        }
+        format!("{}{}", back::BAKE_PREFIX, handle.index())
+    }
+
     fn start_named_expr(
         &mut self,
         module: &Module,
@@ -1089,6 +1266,7 @@ impl<W: Write> Writer<W> {
         match expressions[expr] {
             Expression::Literal(literal) => match literal {
                 crate::Literal::F32(value) => write!(self.out, "{}f", value)?,
+                crate::Literal::F16(value) => write!(self.out, "{}h", value)?,
                 crate::Literal::U32(value) => write!(self.out, "{}u", value)?,
                 crate::Literal::I32(value) => {
                     // `-2147483648i` is not valid WGSL. The most negative `i32`
@@ -1102,9 +1280,16 @@ impl<W: Write> Writer<W> {
                 }
                 crate::Literal::Bool(value) => write!(self.out, "{}", value)?,
                 crate::Literal::F64(value) => write!(self.out, "{:?}lf", value)?,
-                crate::Literal::I64(_) => {
-                    return Err(Error::Custom("unsupported i64 literal".to_string()));
+                crate::Literal::I64(value) => {
+                    // Like `i32`, the most negative `i64` value can't be
+                    // written as a negated literal.
+                    if value == i64::MIN {
+                        write!(self.out, "({}li - 1li)", i64::MIN + 1)?;
+                    } else {
+                        write!(self.out, "{}li", value)?;
+                    }
                 }
+                crate::Literal::U64(value) => write!(self.out, "{}lu", value)?,
                 crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
                     return Err(Error::Custom(
                         "Abstract types should not appear in IR presented to backends".into(),
@@ -1682,7 +1867,9 @@ impl<W: Write> Writer<W> {
             Expression::CallResult(_)
             | Expression::AtomicResult { .. }
             | Expression::RayQueryProceedResult
-            | Expression::WorkGroupUniformLoadResult { .. } => {}
+            | Expression::WorkGroupUniformLoadResult { .. }
+            | Expression::SubgroupBallotResult
+            | Expression::SubgroupOperationResult { .. } => {}
         }
 
         Ok(())
@@ -1783,12 +1970,30 @@ fn builtin_str(built_in: crate::BuiltIn) -> Result<&'static str, Error> {
         Bi::SampleMask => "sample_mask",
         Bi::PrimitiveIndex => "primitive_index",
         Bi::ViewIndex => "view_index",
+        Bi::MeshTaskSize => "mesh_task_size",
+        Bi::Vertices => "vertices",
+        Bi::Primitives => "primitives",
+        Bi::VertexCount => "vertex_count",
+        Bi::PrimitiveCount => "primitive_count",
+        Bi::PointIndex => "point_index",
+        Bi::LineIndices => "line_indices",
+        Bi::TriangleIndices => "triangle_indices",
+        Bi::CullPrimitive => "cull_primitive",
+        Bi::NumSubgroups => "num_subgroups",
+        Bi::SubgroupId => "subgroup_id",
+        Bi::SubgroupSize => "subgroup_size",
+        Bi::SubgroupInvocationId => "subgroup_invocation_id",
         Bi::BaseInstance
         | Bi::BaseVertex
         | Bi::ClipDistance
         | Bi::CullDistance
         | Bi::PointSize
         | Bi::PointCoord
+        | Bi::InvocationId
+        | Bi::TessLevelOuter
+        | Bi::TessLevelInner
+        | Bi::TessCoord
+        | Bi::PatchVertices
         | Bi::WorkGroupSize => {
             return Err(Error::Custom(format!("Unsupported builtin {built_in:?}")))
         }
@@ -1819,6 +2024,10 @@ const fn scalar_kind_str(scalar: crate::Scalar) -> &'static str {
             kind: Sk::Float,
             width: 4,
         } => "f32",
+        Scalar {
+            kind: Sk::Float,
+            width: 2,
+        } => "f16",
         Scalar {
             kind: Sk::Sint,
             width: 4,
@@ -1827,6 +2036,14 @@ const fn scalar_kind_str(scalar: crate::Scalar) -> &'static str {
             kind: Sk::Uint,
             width: 4,
         } => "u32",
+        Scalar {
+            kind: Sk::Sint,
+            width: 8,
+        } => "i64",
+        Scalar {
+            kind: Sk::Uint,
+            width: 8,
+        } => "u64",
         Scalar {
             kind: Sk::Bool,
             width: 1,
@@ -1922,6 +2139,7 @@ const fn address_space_str(
             }
             As::PushConstant => "push_constant",
             As::WorkGroup => "workgroup",
+            As::TaskPayload => "task_payload",
             As::Handle => return (None, None),
             As::Function => "function",
         }),
@@ -1929,6 +2147,24 @@ const fn address_space_str(
     )
 }
 
+/// Returns `value` as a double quoted string literal, escaping quotes,
+/// backslashes, newlines and tabs.
+fn string_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            _ => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
 fn map_binding_to_attribute(binding: &crate::Binding) -> Vec<Attribute> {
     match *binding {
         crate::Binding::BuiltIn(built_in) => {
@@ -1943,6 +2179,7 @@ fn map_binding_to_attribute(binding: &crate::Binding) -> Vec<Attribute> {
             interpolation,
             sampling,
             second_blend_source: false,
+            patch: _,
         } => vec![
             Attribute::Location(location),
             Attribute::Interpolate(interpolation, sampling),
@@ -1952,6 +2189,7 @@ fn map_binding_to_attribute(binding: &crate::Binding) -> Vec<Attribute> {
             interpolation,
             sampling,
             second_blend_source: true,
+            patch: _,
         } => vec![
             Attribute::Location(location),
             Attribute::SecondBlendSource,
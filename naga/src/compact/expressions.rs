@@ -71,7 +71,8 @@ impl<'tracer> ExpressionTracer<'tracer> {
                 | Ex::GlobalVariable(_)
                 | Ex::LocalVariable(_)
                 | Ex::CallResult(_)
-                | Ex::RayQueryProceedResult => {}
+                | Ex::RayQueryProceedResult
+                | Ex::SubgroupBallotResult => {}
 
                 Ex::Constant(handle) => {
                     self.constants_used.insert(handle);
@@ -185,6 +186,7 @@ impl<'tracer> ExpressionTracer<'tracer> {
                 } => self.expressions_used.insert(expr),
                 Ex::AtomicResult { ty, comparison: _ } => self.types_used.insert(ty),
                 Ex::WorkGroupUniformLoadResult { ty } => self.types_used.insert(ty),
+                Ex::SubgroupOperationResult { ty } => self.types_used.insert(ty),
                 Ex::ArrayLength(expr) => self.expressions_used.insert(expr),
                 Ex::RayQueryGetIntersection {
                     query,
@@ -217,7 +219,8 @@ impl ModuleMap {
             | Ex::GlobalVariable(_)
             | Ex::LocalVariable(_)
             | Ex::CallResult(_)
-            | Ex::RayQueryProceedResult => {}
+            | Ex::RayQueryProceedResult
+            | Ex::SubgroupBallotResult => {}
 
             // Expressions that contain handles that need to be adjusted.
             Ex::Constant(ref mut constant) => self.constants.adjust(constant),
@@ -344,6 +347,7 @@ impl ModuleMap {
                 comparison: _,
             } => self.types.adjust(ty),
             Ex::WorkGroupUniformLoadResult { ref mut ty } => self.types.adjust(ty),
+            Ex::SubgroupOperationResult { ref mut ty } => self.types.adjust(ty),
             Ex::ArrayLength(ref mut expr) => adjust(expr),
             Ex::RayQueryGetIntersection {
                 ref mut query,
@@ -8,6 +8,7 @@ pub struct FunctionTracer<'a> {
     pub types_used: &'a mut HandleSet<crate::Type>,
     pub constants_used: &'a mut HandleSet<crate::Constant>,
     pub const_expressions_used: &'a mut HandleSet<crate::Expression>,
+    pub functions_used: &'a mut HandleSet<crate::Function>,
 
     /// Function-local expressions used.
     pub expressions_used: HandleSet<crate::Expression>,
@@ -101,6 +102,6 @@ impl FunctionMap {
         assert!(reuse.is_empty());
 
         // Adjust statements.
-        self.adjust_body(function);
+        self.adjust_body(function, module_map);
     }
 }
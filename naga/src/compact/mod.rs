@@ -7,15 +7,27 @@ mod types;
 use crate::{arena, compact::functions::FunctionTracer};
 use handle_set_map::{HandleMap, HandleSet};
 
-/// Remove unused types, expressions, and constants from `module`.
+/// Whether [`compact`] should keep the functions that aren't used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeepUnused {
+    /// Remove the functions that can't be reached from an entry point.
+    No,
+    /// Keep all the functions, as if they were used.
+    Yes,
+}
+
+/// Remove unused types, expressions, constants and functions from `module`.
 ///
-/// Assuming that all globals, named constants, special types,
-/// functions and entry points in `module` are used, determine which
+/// Assuming that all globals, named constants, special types and
+/// entry points in `module` are used, determine which functions,
 /// types, constants, and expressions (both function-local and global
 /// constant expressions) are actually used, and remove the rest,
 /// adjusting all handles as necessary. The result should be a module
 /// functionally identical to the original.
 ///
+/// If `keep_unused` is [`KeepUnused::Yes`], all functions are treated
+/// as used, even if no entry point calls them.
+///
 /// This may be useful to apply to modules generated in the snapshot
 /// tests. Our backends often generate temporary names based on handle
 /// indices, which means that adding or removing unused arena entries
@@ -28,7 +40,7 @@ use handle_set_map::{HandleMap, HandleSet};
 /// # Panics
 ///
 /// If `module` has not passed validation, this may panic.
-pub fn compact(module: &mut crate::Module) {
+pub fn compact(module: &mut crate::Module, keep_unused: KeepUnused) {
     let mut module_tracer = ModuleTracer::new(module);
 
     // We treat all globals as used by definition.
@@ -54,37 +66,61 @@ pub fn compact(module: &mut crate::Module) {
         }
     }
 
-    // We assume that all functions are used.
-    //
-    // Observe which types, constant expressions, constants, and
-    // expressions each function uses, and produce maps for each
-    // function from pre-compaction to post-compaction expression
-    // handles.
-    log::trace!("tracing functions");
-    let function_maps: Vec<FunctionMap> = module
-        .functions
-        .iter()
-        .map(|(_, f)| {
-            log::trace!("tracing function {:?}", f.name);
-            let mut function_tracer = module_tracer.as_function(f);
-            function_tracer.trace();
-            FunctionMap::from(function_tracer)
-        })
-        .collect();
+    if keep_unused == KeepUnused::Yes {
+        module_tracer
+            .functions_used
+            .insert_iter(module.functions.iter().map(|(handle, _)| handle));
+    }
 
-    // Similiarly, observe what each entry point actually uses.
+    // Observe which types, constant expressions, constants,
+    // expressions and functions each entry point uses, and produce
+    // maps for each entry point from pre-compaction to
+    // post-compaction expression handles.
     log::trace!("tracing entry points");
     let entry_point_maps: Vec<FunctionMap> = module
         .entry_points
         .iter()
         .map(|e| {
             log::trace!("tracing entry point {:?}", e.function.name);
+            if let Some(ref mesh_info) = e.mesh_info {
+                module_tracer
+                    .types_used
+                    .insert(mesh_info.vertex_output_type);
+                module_tracer
+                    .types_used
+                    .insert(mesh_info.primitive_output_type);
+            }
+            if let Some(ref geometry_info) = e.geometry_info {
+                module_tracer
+                    .types_used
+                    .insert(geometry_info.vertex_output_type);
+            }
             let mut used = module_tracer.as_function(&e.function);
             used.trace();
             FunctionMap::from(used)
         })
         .collect();
 
+    // Similarly, observe what each used function uses. Functions can
+    // only call the ones declared before them, so walking the arena
+    // backwards sees every caller before its callees.
+    log::trace!("tracing functions");
+    let mut function_maps: Vec<Option<FunctionMap>> = module
+        .functions
+        .iter()
+        .rev()
+        .map(|(handle, f)| {
+            if !module_tracer.functions_used.contains(handle) {
+                return None;
+            }
+            log::trace!("tracing function {:?}", f.name);
+            let mut function_tracer = module_tracer.as_function(f);
+            function_tracer.trace();
+            Some(FunctionMap::from(function_tracer))
+        })
+        .collect();
+    function_maps.reverse();
+
     // Given that the above steps have marked all the constant
     // expressions used directly by globals, constants, functions, and
     // entry points, walk the constant expression arena to find all
@@ -172,15 +208,33 @@ pub fn compact(module: &mut crate::Module) {
     // named expression tables.
     let mut reused_named_expressions = crate::NamedExpressions::default();
 
-    // Compact each function.
-    for ((_, function), map) in module.functions.iter_mut().zip(function_maps.iter()) {
-        log::trace!("compacting function {:?}", function.name);
-        map.compact(function, &module_map, &mut reused_named_expressions);
-    }
+    // Drop unused functions, and compact the remaining ones.
+    log::trace!("compacting functions");
+    module
+        .functions
+        .retain_mut(|handle, function| match function_maps[handle.index()] {
+            Some(ref map) => {
+                log::trace!("compacting function {:?}", function.name);
+                map.compact(function, &module_map, &mut reused_named_expressions);
+                true
+            }
+            None => false,
+        });
 
     // Compact each entry point.
     for (entry, map) in module.entry_points.iter_mut().zip(entry_point_maps.iter()) {
         log::trace!("compacting entry point {:?}", entry.function.name);
+        if let Some(ref mut mesh_info) = entry.mesh_info {
+            module_map.types.adjust(&mut mesh_info.vertex_output_type);
+            module_map
+                .types
+                .adjust(&mut mesh_info.primitive_output_type);
+        }
+        if let Some(ref mut geometry_info) = entry.geometry_info {
+            module_map
+                .types
+                .adjust(&mut geometry_info.vertex_output_type);
+        }
         map.compact(
             &mut entry.function,
             &module_map,
@@ -194,6 +248,7 @@ struct ModuleTracer<'module> {
     types_used: HandleSet<crate::Type>,
     constants_used: HandleSet<crate::Constant>,
     const_expressions_used: HandleSet<crate::Expression>,
+    functions_used: HandleSet<crate::Function>,
 }
 
 impl<'module> ModuleTracer<'module> {
@@ -203,6 +258,7 @@ impl<'module> ModuleTracer<'module> {
             types_used: HandleSet::for_arena(&module.types),
             constants_used: HandleSet::for_arena(&module.constants),
             const_expressions_used: HandleSet::for_arena(&module.const_expressions),
+            functions_used: HandleSet::for_arena(&module.functions),
         }
     }
 
@@ -252,6 +308,7 @@ impl<'module> ModuleTracer<'module> {
             types_used: &mut self.types_used,
             constants_used: &mut self.constants_used,
             const_expressions_used: &mut self.const_expressions_used,
+            functions_used: &mut self.functions_used,
             expressions_used: HandleSet::for_arena(&function.expressions),
         }
     }
@@ -261,6 +318,7 @@ struct ModuleMap {
     types: HandleMap<crate::Type>,
     constants: HandleMap<crate::Constant>,
     const_expressions: HandleMap<crate::Expression>,
+    functions: HandleMap<crate::Function>,
 }
 
 impl From<ModuleTracer<'_>> for ModuleMap {
@@ -269,6 +327,7 @@ impl From<ModuleTracer<'_>> for ModuleMap {
             types: HandleMap::from_set(used.types_used),
             constants: HandleMap::from_set(used.constants_used),
             const_expressions: HandleMap::from_set(used.const_expressions_used),
+            functions: HandleMap::from_set(used.functions_used),
         }
     }
 }
@@ -1,5 +1,5 @@
 use super::functions::FunctionTracer;
-use super::FunctionMap;
+use super::{FunctionMap, ModuleMap};
 use crate::arena::Handle;
 
 impl FunctionTracer<'_> {
@@ -82,10 +82,11 @@ impl FunctionTracer<'_> {
                         self.expressions_used.insert(result);
                     }
                     St::Call {
-                        function: _,
+                        function,
                         ref arguments,
                         result,
                     } => {
+                        self.functions_used.insert(function);
                         for expr in arguments {
                             self.expressions_used.insert(*expr);
                         }
@@ -97,12 +98,45 @@ impl FunctionTracer<'_> {
                         self.expressions_used.insert(query);
                         self.trace_ray_query_function(fun);
                     }
+                    St::DebugPrintf { ref arguments, .. } => {
+                        for expr in arguments {
+                            self.expressions_used.insert(*expr);
+                        }
+                    }
+                    St::EmitVertex { value } => {
+                        self.expressions_used.insert(value);
+                    }
+                    St::SubgroupBallot { result, predicate } => {
+                        if let Some(predicate) = predicate {
+                            self.expressions_used.insert(predicate);
+                        }
+                        self.expressions_used.insert(result);
+                    }
+                    St::SubgroupCollectiveOperation {
+                        op: _,
+                        collective_op: _,
+                        argument,
+                        result,
+                    } => {
+                        self.expressions_used.insert(argument);
+                        self.expressions_used.insert(result);
+                    }
+                    St::SubgroupGather {
+                        ref mode,
+                        argument,
+                        result,
+                    } => {
+                        self.trace_gather_mode(mode);
+                        self.expressions_used.insert(argument);
+                        self.expressions_used.insert(result);
+                    }
 
                     // Trivial statements.
                     St::Break
                     | St::Continue
                     | St::Kill
                     | St::Barrier(_)
+                    | St::EndPrimitive
                     | St::Return { value: None } => {}
                 }
             }
@@ -144,10 +178,24 @@ impl FunctionTracer<'_> {
             Qf::Terminate => {}
         }
     }
+
+    fn trace_gather_mode(&mut self, mode: &crate::GatherMode) {
+        use crate::GatherMode as Gm;
+        match *mode {
+            Gm::BroadcastFirst => {}
+            Gm::Broadcast(index)
+            | Gm::Shuffle(index)
+            | Gm::ShuffleDown(index)
+            | Gm::ShuffleUp(index)
+            | Gm::ShuffleXor(index) => {
+                self.expressions_used.insert(index);
+            }
+        }
+    }
 }
 
 impl FunctionMap {
-    pub fn adjust_body(&self, function: &mut crate::Function) {
+    pub fn adjust_body(&self, function: &mut crate::Function, module_map: &ModuleMap) {
         let block = &mut function.body;
         let mut worklist: Vec<&mut [crate::Statement]> = vec![block];
         let adjust = |handle: &mut Handle<crate::Expression>| {
@@ -232,10 +280,11 @@ impl FunctionMap {
                         adjust(result);
                     }
                     St::Call {
-                        function: _,
+                        ref mut function,
                         ref mut arguments,
                         ref mut result,
                     } => {
+                        module_map.functions.adjust(function);
                         for expr in arguments {
                             adjust(expr);
                         }
@@ -250,12 +299,48 @@ impl FunctionMap {
                         adjust(query);
                         self.adjust_ray_query_function(fun);
                     }
+                    St::DebugPrintf {
+                        ref mut arguments, ..
+                    } => {
+                        for expr in arguments {
+                            adjust(expr);
+                        }
+                    }
+                    St::EmitVertex { ref mut value } => adjust(value),
+                    St::SubgroupBallot {
+                        ref mut result,
+                        ref mut predicate,
+                    } => {
+                        if let Some(ref mut predicate) = *predicate {
+                            adjust(predicate);
+                        }
+                        adjust(result);
+                    }
+                    St::SubgroupCollectiveOperation {
+                        op: _,
+                        collective_op: _,
+                        ref mut argument,
+                        ref mut result,
+                    } => {
+                        adjust(argument);
+                        adjust(result);
+                    }
+                    St::SubgroupGather {
+                        ref mut mode,
+                        ref mut argument,
+                        ref mut result,
+                    } => {
+                        self.adjust_gather_mode(mode);
+                        adjust(argument);
+                        adjust(result);
+                    }
 
                     // Trivial statements.
                     St::Break
                     | St::Continue
                     | St::Kill
                     | St::Barrier(_)
+                    | St::EndPrimitive
                     | St::Return { value: None } => {}
                 }
             }
@@ -297,4 +382,18 @@ impl FunctionMap {
             Qf::Terminate => {}
         }
     }
+
+    fn adjust_gather_mode(&self, mode: &mut crate::GatherMode) {
+        use crate::GatherMode as Gm;
+        match *mode {
+            Gm::BroadcastFirst => {}
+            Gm::Broadcast(ref mut index)
+            | Gm::Shuffle(ref mut index)
+            | Gm::ShuffleDown(ref mut index)
+            | Gm::ShuffleUp(ref mut index)
+            | Gm::ShuffleXor(ref mut index) => {
+                self.expressions.adjust(index);
+            }
+        }
+    }
 }
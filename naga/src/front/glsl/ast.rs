@@ -193,6 +193,7 @@ pub struct TypeQualifiers<'a> {
     pub span: Span,
     pub storage: (StorageQualifier, Span),
     pub invariant: Option<Span>,
+    pub patch: Option<Span>,
     pub interpolation: Option<(Interpolation, Span)>,
     pub precision: Option<(Precision, Span)>,
     pub sampling: Option<(Sampling, Span)>,
@@ -214,6 +215,15 @@ impl<'a> TypeQualifiers<'a> {
             });
         }
 
+        if let Some(meta) = self.patch {
+            errors.push(super::Error {
+                kind: super::ErrorKind::SemanticError(
+                    "Patch qualifier can only be used in in/out variables".into(),
+                ),
+                meta,
+            });
+        }
+
         if let Some((_, meta)) = self.interpolation {
             errors.push(super::Error {
                 kind: super::ErrorKind::SemanticError(
@@ -391,4 +401,13 @@ impl ParameterQualifier {
 pub enum Profile {
     /// The `core` profile, default when no profile is specified.
     Core,
+    /// The `compatibility` profile.
+    ///
+    /// The features deprecated by the core profile aren't supported.
+    Compatibility,
+    /// The `es` profile, used by OpenGL ES shaders.
+    ///
+    /// Precision qualifiers are accepted but ignored, all types use full
+    /// precision.
+    Es,
 }
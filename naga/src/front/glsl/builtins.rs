@@ -292,6 +292,41 @@ pub fn inject_builtin(
                 f,
             )
         }
+        "textureQueryLevels" | "textureSamples" => {
+            let query = match name {
+                // int textureQueryLevels(gsampler);
+                "textureQueryLevels" => ImageQuery::NumLevels,
+                // int textureSamples(gsampler2DMS);
+                "textureSamples" => ImageQuery::NumSamples,
+                _ => unreachable!(),
+            };
+            let f = |kind, dim, arrayed, multi, shadow| {
+                // Only multisampled images have samples, and they have a single level
+                if multi != (query == ImageQuery::NumSamples) {
+                    return;
+                }
+
+                let class = match shadow {
+                    true => ImageClass::Depth { multi },
+                    false => ImageClass::Sampled { kind, multi },
+                };
+
+                let image = TypeInner::Image {
+                    dim,
+                    arrayed,
+                    class,
+                };
+
+                declaration
+                    .overloads
+                    .push(module.add_builtin(vec![image], MacroCall::TextureQuery(query)))
+            };
+
+            texture_args_generator(
+                TextureArgsOptions::SHADOW | TextureArgsOptions::MULTI | variations.into(),
+                f,
+            )
+        }
         "texelFetch" | "texelFetchOffset" => {
             let offset = "texelFetchOffset" == name;
             let f = |kind, dim, arrayed, multi, _shadow| {
@@ -995,6 +1030,129 @@ fn inject_standard_builtins(
         "barrier" => declaration
             .overloads
             .push(module.add_builtin(Vec::new(), MacroCall::Barrier)),
+        "EmitVertex" => declaration
+            .overloads
+            .push(module.add_builtin(Vec::new(), MacroCall::EmitVertex)),
+        "EndPrimitive" => declaration
+            .overloads
+            .push(module.add_builtin(Vec::new(), MacroCall::EndPrimitive)),
+        "subgroupBarrier" | "subgroupMemoryBarrier" => declaration
+            .overloads
+            .push(module.add_builtin(Vec::new(), MacroCall::SubgroupBarrier)),
+        "subgroupBallot" => declaration.overloads.push(module.add_builtin(
+            vec![TypeInner::Scalar(Scalar::BOOL)],
+            MacroCall::SubgroupBallot,
+        )),
+        "subgroupAll" | "subgroupAny" => {
+            let op = match name {
+                "subgroupAll" => crate::SubgroupOperation::All,
+                _ => crate::SubgroupOperation::Any,
+            };
+            declaration.overloads.push(module.add_builtin(
+                vec![TypeInner::Scalar(Scalar::BOOL)],
+                MacroCall::SubgroupOperation(op, crate::CollectiveOperation::Reduce),
+            ))
+        }
+        "subgroupAdd"
+        | "subgroupMul"
+        | "subgroupMin"
+        | "subgroupMax"
+        | "subgroupAnd"
+        | "subgroupOr"
+        | "subgroupXor"
+        | "subgroupInclusiveAdd"
+        | "subgroupInclusiveMul"
+        | "subgroupExclusiveAdd"
+        | "subgroupExclusiveMul" => {
+            use crate::{CollectiveOperation as Co, SubgroupOperation as So};
+
+            let (op, collective_op) = match name {
+                "subgroupAdd" => (So::Add, Co::Reduce),
+                "subgroupMul" => (So::Mul, Co::Reduce),
+                "subgroupMin" => (So::Min, Co::Reduce),
+                "subgroupMax" => (So::Max, Co::Reduce),
+                "subgroupAnd" => (So::And, Co::Reduce),
+                "subgroupOr" => (So::Or, Co::Reduce),
+                "subgroupXor" => (So::Xor, Co::Reduce),
+                "subgroupInclusiveAdd" => (So::Add, Co::InclusiveScan),
+                "subgroupInclusiveMul" => (So::Mul, Co::InclusiveScan),
+                "subgroupExclusiveAdd" => (So::Add, Co::ExclusiveScan),
+                _ => (So::Mul, Co::ExclusiveScan),
+            };
+            // Bitwise operations don't take floats
+            let bitwise = matches!(op, So::And | So::Or | So::Xor);
+
+            // bits layout
+            // bit 0 through 1 - dims
+            // bit 2 through 3 - kind
+            for bits in 0..0b1100 {
+                let size = match bits & 0b11 {
+                    0b00 => None,
+                    0b01 => Some(VectorSize::Bi),
+                    0b10 => Some(VectorSize::Tri),
+                    _ => Some(VectorSize::Quad),
+                };
+                let scalar = match bits >> 2 {
+                    0b00 if bitwise => continue,
+                    0b00 => Scalar::F32,
+                    0b01 => Scalar::I32,
+                    _ => Scalar::U32,
+                };
+
+                let ty = match size {
+                    Some(size) => TypeInner::Vector { size, scalar },
+                    None => TypeInner::Scalar(scalar),
+                };
+
+                declaration.overloads.push(
+                    module.add_builtin(vec![ty], MacroCall::SubgroupOperation(op, collective_op)),
+                )
+            }
+        }
+        "subgroupBroadcastFirst"
+        | "subgroupBroadcast"
+        | "subgroupShuffle"
+        | "subgroupShuffleXor"
+        | "subgroupShuffleUp"
+        | "subgroupShuffleDown" => {
+            let mode = match name {
+                "subgroupBroadcastFirst" => GatherKind::BroadcastFirst,
+                "subgroupBroadcast" => GatherKind::Broadcast,
+                "subgroupShuffle" => GatherKind::Shuffle,
+                "subgroupShuffleXor" => GatherKind::ShuffleXor,
+                "subgroupShuffleUp" => GatherKind::ShuffleUp,
+                _ => GatherKind::ShuffleDown,
+            };
+
+            // bits layout
+            // bit 0 through 1 - dims
+            // bit 2 through 3 - kind
+            for bits in 0..0b1100 {
+                let size = match bits & 0b11 {
+                    0b00 => None,
+                    0b01 => Some(VectorSize::Bi),
+                    0b10 => Some(VectorSize::Tri),
+                    _ => Some(VectorSize::Quad),
+                };
+                let scalar = match bits >> 2 {
+                    0b00 => Scalar::F32,
+                    0b01 => Scalar::I32,
+                    _ => Scalar::U32,
+                };
+
+                let mut args = vec![match size {
+                    Some(size) => TypeInner::Vector { size, scalar },
+                    None => TypeInner::Scalar(scalar),
+                }];
+                if mode != GatherKind::BroadcastFirst {
+                    args.push(TypeInner::Scalar(Scalar::U32));
+                }
+
+                declaration
+                    .overloads
+                    .push(module.add_builtin(args, MacroCall::SubgroupGather(mode)))
+            }
+        }
         // Add common builtins with floats
         _ => inject_common_builtin(declaration, module, name, 4),
     }
@@ -1282,6 +1440,33 @@ fn inject_common_builtin(
                 ))
             }
         }
+        "matrixCompMult" => {
+            // bits layout
+            // bit 0 through 3 - dims
+            for bits in 0..0b1001 {
+                let (rows, columns) = match bits {
+                    0b0000 => (VectorSize::Bi, VectorSize::Bi),
+                    0b0001 => (VectorSize::Bi, VectorSize::Tri),
+                    0b0010 => (VectorSize::Bi, VectorSize::Quad),
+                    0b0011 => (VectorSize::Tri, VectorSize::Bi),
+                    0b0100 => (VectorSize::Tri, VectorSize::Tri),
+                    0b0101 => (VectorSize::Tri, VectorSize::Quad),
+                    0b0110 => (VectorSize::Quad, VectorSize::Bi),
+                    0b0111 => (VectorSize::Quad, VectorSize::Tri),
+                    _ => (VectorSize::Quad, VectorSize::Quad),
+                };
+
+                let ty = || TypeInner::Matrix {
+                    columns,
+                    rows,
+                    scalar: float_scalar,
+                };
+
+                declaration
+                    .overloads
+                    .push(module.add_builtin(vec![ty(), ty()], MacroCall::MatrixCompMult))
+            }
+        }
         "inverse" | "determinant" => {
             // bits layout
             // bit 0 through 1 - dims
@@ -1490,6 +1675,18 @@ fn inject_common_builtin(
     }
 }
 
+/// The kind of a [`crate::GatherMode`], without its index since it's only
+/// known once the builtin is called
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GatherKind {
+    BroadcastFirst,
+    Broadcast,
+    Shuffle,
+    ShuffleDown,
+    ShuffleUp,
+    ShuffleXor,
+}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum TextureLevelType {
     None,
@@ -1511,6 +1708,7 @@ pub enum MacroCall {
     TextureSize {
         arrayed: bool,
     },
+    TextureQuery(ImageQuery),
     ImageLoad {
         multi: bool,
     },
@@ -1526,10 +1724,21 @@ pub enum MacroCall {
     Mod(Option<VectorSize>),
     Splatted(MathFunction, Option<VectorSize>, usize),
     MixBoolean,
+    /// Component-wise multiplication of two matrices, done column by column
+    /// since naga's multiplication of matrices is the linear algebraic one
+    MatrixCompMult,
     Clamp(Option<VectorSize>),
     BitCast(Sk),
     Derivate(Axis, Ctrl),
     Barrier,
+    /// Calls the function emitting the values of the outputs of a geometry
+    /// shader as a vertex, which is only known once all of them are declared
+    EmitVertex,
+    EndPrimitive,
+    SubgroupBarrier,
+    SubgroupBallot,
+    SubgroupOperation(crate::SubgroupOperation, crate::CollectiveOperation),
+    SubgroupGather(GatherKind),
     /// SmoothStep needs a separate variant because it might need it's inputs
     /// to be splatted depending on the overload
     SmoothStep {
@@ -1577,7 +1786,7 @@ impl MacroCall {
                             base: coords,
                             index: size as u32 - 1,
                         },
-                        Span::default(),
+                        meta,
                     )?;
                     let left = if let VectorSize::Bi = size {
                         ctx.add_expression(
@@ -1585,18 +1794,16 @@ impl MacroCall {
                                 base: coords,
                                 index: 0,
                             },
-                            Span::default(),
+                            meta,
                         )?
                     } else {
                         let size = match size {
                             VectorSize::Tri => VectorSize::Bi,
                             _ => VectorSize::Tri,
                         };
-                        right = ctx.add_expression(
-                            Expression::Splat { size, value: right },
-                            Span::default(),
-                        )?;
-                        ctx.vector_resize(size, coords, Span::default())?
+                        right =
+                            ctx.add_expression(Expression::Splat { size, value: right }, meta)?;
+                        ctx.vector_resize(size, coords, meta)?
                     };
                     coords = ctx.add_expression(
                         Expression::Binary {
@@ -1604,7 +1811,7 @@ impl MacroCall {
                             left,
                             right,
                         },
-                        Span::default(),
+                        meta,
                     )?;
                 }
 
@@ -1686,7 +1893,7 @@ impl MacroCall {
                             level: args.get(1).copied(),
                         },
                     },
-                    Span::default(),
+                    meta,
                 )?;
 
                 if arrayed {
@@ -1697,7 +1904,7 @@ impl MacroCall {
                             for index in 0..(ori_size as u32) {
                                 components.push(ctx.add_expression(
                                     Expression::AccessIndex { base: expr, index },
-                                    Span::default(),
+                                    meta,
                                 )?)
                             }
 
@@ -1717,7 +1924,7 @@ impl MacroCall {
                             image: args[0],
                             query: ImageQuery::NumLayers,
                         },
-                        Span::default(),
+                        meta,
                     )?);
 
                     let ty = ctx.module.types.insert(
@@ -1740,7 +1947,25 @@ impl MacroCall {
                         kind: Sk::Sint,
                         convert: Some(4),
                     },
-                    Span::default(),
+                    meta,
+                )?
+            }
+            MacroCall::TextureQuery(query) => {
+                let expr = ctx.add_expression(
+                    Expression::ImageQuery {
+                        image: args[0],
+                        query,
+                    },
+                    meta,
+                )?;
+
+                ctx.add_expression(
+                    Expression::As {
+                        expr,
+                        kind: Sk::Sint,
+                        convert: Some(4),
+                    },
+                    meta,
                 )?
             }
             MacroCall::ImageLoad { multi } => {
@@ -1758,7 +1983,7 @@ impl MacroCall {
                         sample,
                         level,
                     },
-                    Span::default(),
+                    meta,
                 )?
             }
             MacroCall::ImageStore => {
@@ -1783,7 +2008,7 @@ impl MacroCall {
                     arg2: args.get(2).copied(),
                     arg3: args.get(3).copied(),
                 },
-                Span::default(),
+                meta,
             )?,
             mc @ (MacroCall::FindLsbUint | MacroCall::FindMsbUint) => {
                 let fun = match mc {
@@ -1799,7 +2024,7 @@ impl MacroCall {
                         arg2: None,
                         arg3: None,
                     },
-                    Span::default(),
+                    meta,
                 )?;
                 ctx.add_expression(
                     Expression::As {
@@ -1807,7 +2032,7 @@ impl MacroCall {
                         kind: Sk::Sint,
                         convert: Some(4),
                     },
-                    Span::default(),
+                    meta,
                 )?
             }
             MacroCall::BitfieldInsert => {
@@ -1817,7 +2042,7 @@ impl MacroCall {
                         kind: Sk::Uint,
                         convert: Some(4),
                     },
-                    Span::default(),
+                    meta,
                 )?;
                 let conv_arg_3 = ctx.add_expression(
                     Expression::As {
@@ -1825,7 +2050,7 @@ impl MacroCall {
                         kind: Sk::Uint,
                         convert: Some(4),
                     },
-                    Span::default(),
+                    meta,
                 )?;
                 ctx.add_expression(
                     Expression::Math {
@@ -1835,7 +2060,7 @@ impl MacroCall {
                         arg2: Some(conv_arg_2),
                         arg3: Some(conv_arg_3),
                     },
-                    Span::default(),
+                    meta,
                 )?
             }
             MacroCall::BitfieldExtract => {
@@ -1845,7 +2070,7 @@ impl MacroCall {
                         kind: Sk::Uint,
                         convert: Some(4),
                     },
-                    Span::default(),
+                    meta,
                 )?;
                 let conv_arg_2 = ctx.add_expression(
                     Expression::As {
@@ -1853,7 +2078,7 @@ impl MacroCall {
                         kind: Sk::Uint,
                         convert: Some(4),
                     },
-                    Span::default(),
+                    meta,
                 )?;
                 ctx.add_expression(
                     Expression::Math {
@@ -1863,7 +2088,7 @@ impl MacroCall {
                         arg2: Some(conv_arg_2),
                         arg3: None,
                     },
-                    Span::default(),
+                    meta,
                 )?
             }
             MacroCall::Relational(fun) => ctx.add_expression(
@@ -1871,10 +2096,10 @@ impl MacroCall {
                     fun,
                     argument: args[0],
                 },
-                Span::default(),
+                meta,
             )?,
             MacroCall::Unary(op) => {
-                ctx.add_expression(Expression::Unary { op, expr: args[0] }, Span::default())?
+                ctx.add_expression(Expression::Unary { op, expr: args[0] }, meta)?
             }
             MacroCall::Binary(op) => ctx.add_expression(
                 Expression::Binary {
@@ -1882,7 +2107,7 @@ impl MacroCall {
                     left: args[0],
                     right: args[1],
                 },
-                Span::default(),
+                meta,
             )?,
             MacroCall::Mod(size) => {
                 ctx.implicit_splat(&mut args[1], meta, size)?;
@@ -1895,7 +2120,7 @@ impl MacroCall {
                         left: args[0],
                         right: args[1],
                     },
-                    Span::default(),
+                    meta,
                 )?;
                 let floor = ctx.add_expression(
                     Expression::Math {
@@ -1905,7 +2130,7 @@ impl MacroCall {
                         arg2: None,
                         arg3: None,
                     },
-                    Span::default(),
+                    meta,
                 )?;
                 let mult = ctx.add_expression(
                     Expression::Binary {
@@ -1913,7 +2138,7 @@ impl MacroCall {
                         left: floor,
                         right: args[1],
                     },
-                    Span::default(),
+                    meta,
                 )?;
                 ctx.add_expression(
                     Expression::Binary {
@@ -1921,7 +2146,7 @@ impl MacroCall {
                         left: args[0],
                         right: mult,
                     },
-                    Span::default(),
+                    meta,
                 )?
             }
             MacroCall::Splatted(fun, size, i) => {
@@ -1935,7 +2160,7 @@ impl MacroCall {
                         arg2: args.get(2).copied(),
                         arg3: args.get(3).copied(),
                     },
-                    Span::default(),
+                    meta,
                 )?
             }
             MacroCall::MixBoolean => ctx.add_expression(
@@ -1944,8 +2169,43 @@ impl MacroCall {
                     accept: args[1],
                     reject: args[0],
                 },
-                Span::default(),
+                meta,
             )?,
+            MacroCall::MatrixCompMult => {
+                let ty = ctx.resolve_type_handle(args[0], meta)?;
+                let columns = match ctx.module.types[ty].inner {
+                    TypeInner::Matrix { columns, .. } => columns,
+                    _ => unreachable!(),
+                };
+
+                let mut components = Vec::with_capacity(columns as usize);
+                for index in 0..columns as u32 {
+                    let left = ctx.add_expression(
+                        Expression::AccessIndex {
+                            base: args[0],
+                            index,
+                        },
+                        meta,
+                    )?;
+                    let right = ctx.add_expression(
+                        Expression::AccessIndex {
+                            base: args[1],
+                            index,
+                        },
+                        meta,
+                    )?;
+                    components.push(ctx.add_expression(
+                        Expression::Binary {
+                            op: BinaryOperator::Multiply,
+                            left,
+                            right,
+                        },
+                        meta,
+                    )?);
+                }
+
+                ctx.add_expression(Expression::Compose { ty, components }, meta)?
+            }
             MacroCall::Clamp(size) => {
                 ctx.implicit_splat(&mut args[1], meta, size)?;
                 ctx.implicit_splat(&mut args[2], meta, size)?;
@@ -1958,7 +2218,7 @@ impl MacroCall {
                         arg2: args.get(2).copied(),
                         arg3: args.get(3).copied(),
                     },
-                    Span::default(),
+                    meta,
                 )?
             }
             MacroCall::BitCast(kind) => ctx.add_expression(
@@ -1967,7 +2227,7 @@ impl MacroCall {
                     kind,
                     convert: None,
                 },
-                Span::default(),
+                meta,
             )?,
             MacroCall::Derivate(axis, ctrl) => ctx.add_expression(
                 Expression::Derivative {
@@ -1975,7 +2235,7 @@ impl MacroCall {
                     ctrl,
                     expr: args[0],
                 },
-                Span::default(),
+                meta,
             )?,
             MacroCall::Barrier => {
                 ctx.emit_restart();
@@ -1983,6 +2243,87 @@ impl MacroCall {
                     .push(crate::Statement::Barrier(crate::Barrier::all()), meta);
                 return Ok(None);
             }
+            MacroCall::EmitVertex | MacroCall::EndPrimitive => {
+                let statement = match (*self, frontend.emit_vertex) {
+                    (MacroCall::EmitVertex, Some(function)) => crate::Statement::Call {
+                        function,
+                        arguments: Vec::new(),
+                        result: None,
+                    },
+                    (MacroCall::EndPrimitive, Some(_)) => crate::Statement::EndPrimitive,
+                    _ => {
+                        return Err(Error {
+                            kind: ErrorKind::SemanticError(
+                                "Vertices can only be emitted by geometry shaders".into(),
+                            ),
+                            meta,
+                        })
+                    }
+                };
+                ctx.emit_restart();
+                ctx.body.push(statement, meta);
+                return Ok(None);
+            }
+            MacroCall::SubgroupBarrier => {
+                ctx.emit_restart();
+                ctx.body
+                    .push(crate::Statement::Barrier(crate::Barrier::SUB_GROUP), meta);
+                return Ok(None);
+            }
+            MacroCall::SubgroupBallot => {
+                ctx.emit_end();
+                let result = ctx.add_expression(Expression::SubgroupBallotResult, meta)?;
+                ctx.body.push(
+                    crate::Statement::SubgroupBallot {
+                        result,
+                        predicate: Some(args[0]),
+                    },
+                    meta,
+                );
+                ctx.emit_start();
+                result
+            }
+            MacroCall::SubgroupOperation(op, collective_op) => {
+                let ty = ctx.resolve_type_handle(args[0], meta)?;
+                ctx.emit_end();
+                let result =
+                    ctx.add_expression(Expression::SubgroupOperationResult { ty }, meta)?;
+                ctx.body.push(
+                    crate::Statement::SubgroupCollectiveOperation {
+                        op,
+                        collective_op,
+                        argument: args[0],
+                        result,
+                    },
+                    meta,
+                );
+                ctx.emit_start();
+                result
+            }
+            MacroCall::SubgroupGather(mode) => {
+                let mode = match mode {
+                    GatherKind::BroadcastFirst => crate::GatherMode::BroadcastFirst,
+                    GatherKind::Broadcast => crate::GatherMode::Broadcast(args[1]),
+                    GatherKind::Shuffle => crate::GatherMode::Shuffle(args[1]),
+                    GatherKind::ShuffleDown => crate::GatherMode::ShuffleDown(args[1]),
+                    GatherKind::ShuffleUp => crate::GatherMode::ShuffleUp(args[1]),
+                    GatherKind::ShuffleXor => crate::GatherMode::ShuffleXor(args[1]),
+                };
+                let ty = ctx.resolve_type_handle(args[0], meta)?;
+                ctx.emit_end();
+                let result =
+                    ctx.add_expression(Expression::SubgroupOperationResult { ty }, meta)?;
+                ctx.body.push(
+                    crate::Statement::SubgroupGather {
+                        mode,
+                        argument: args[0],
+                        result,
+                    },
+                    meta,
+                );
+                ctx.emit_start();
+                result
+            }
             MacroCall::SmoothStep { splatted } => {
                 ctx.implicit_splat(&mut args[0], meta, splatted)?;
                 ctx.implicit_splat(&mut args[1], meta, splatted)?;
@@ -1995,7 +2336,7 @@ impl MacroCall {
                         arg2: args.get(2).copied(),
                         arg3: None,
                     },
-                    Span::default(),
+                    meta,
                 )?
             }
         }))
@@ -2031,8 +2372,12 @@ fn texture_call(
             meta,
         )?)
     } else {
+        // Only the samplerless functions (like `texelFetch`) accept bare textures
         Err(Error {
-            kind: ErrorKind::SemanticError("Bad call".into()),
+            kind: ErrorKind::SemanticError(
+                "Sampling a texture requires combining it with a sampler, like `sampler2D(tex, samp)`"
+                    .into(),
+            ),
             meta,
         })
     }
@@ -2083,14 +2428,14 @@ impl Frontend {
 
             let coordinate = match (image_size, coord_size) {
                 (Some(size), Some(coord_s)) if size != coord_s => {
-                    ctx.vector_resize(size, coord, Span::default())?
+                    ctx.vector_resize(size, coord, meta)?
                 }
                 (None, Some(_)) => ctx.add_expression(
                     Expression::AccessIndex {
                         base: coord,
                         index: 0,
                     },
-                    Span::default(),
+                    meta,
                 )?,
                 _ => coord,
             };
@@ -2101,30 +2446,28 @@ impl Frontend {
                 let index = coord_index;
                 coord_index += 1;
 
-                Some(ctx.add_expression(
-                    Expression::AccessIndex { base: coord, index },
-                    Span::default(),
-                )?)
+                Some(ctx.add_expression(Expression::AccessIndex { base: coord, index }, meta)?)
             } else {
                 None
             };
             let mut used_extra = false;
-            let depth_ref = match shadow {
-                true => {
-                    let index = coord_index;
+            let depth_ref =
+                match shadow {
+                    true => {
+                        let index = coord_index;
 
-                    if index == 4 {
-                        used_extra = true;
-                        extra
-                    } else {
-                        Some(ctx.add_expression(
-                            Expression::AccessIndex { base: coord, index },
-                            Span::default(),
-                        )?)
+                        if index == 4 {
+                            used_extra = true;
+                            extra
+                        } else {
+                            Some(ctx.add_expression(
+                                Expression::AccessIndex { base: coord, index },
+                                meta,
+                            )?)
+                        }
                     }
-                }
-                false => None,
-            };
+                    false => None,
+                };
 
             Ok(CoordComponents {
                 coordinate,
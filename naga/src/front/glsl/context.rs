@@ -1013,18 +1013,17 @@ impl<'a> Context<'a> {
                     // pointer type which is required for dynamic indexing
                     if !constant_index {
                         if let Some((constant, ty)) = var.constant {
-                            let init = self
-                                .add_expression(Expression::Constant(constant), Span::default())?;
+                            let init = self.add_expression(Expression::Constant(constant), meta)?;
                             let local = self.locals.append(
                                 LocalVariable {
                                     name: None,
                                     ty,
                                     init: Some(init),
                                 },
-                                Span::default(),
+                                meta,
                             );
 
-                            self.add_expression(Expression::LocalVariable(local), Span::default())?
+                            self.add_expression(Expression::LocalVariable(local), meta)?
                         } else {
                             var.expr
                         }
@@ -1120,6 +1119,22 @@ impl<'a> Context<'a> {
                     }
                 }
 
+                // Constant expressions can't contain statements, but since neither branch
+                // can have side effects the result can be picked using the condition's value.
+                if self.is_const {
+                    return match self.module.to_ctx().eval_expr_to_literal(condition) {
+                        Some(Literal::Bool(true)) => Ok((Some(accept), meta)),
+                        Some(Literal::Bool(false)) => Ok((Some(reject), meta)),
+                        _ => Err(Error {
+                            kind: ErrorKind::SemanticError(
+                                "Condition of a constant ternary expression must be a constant bool"
+                                    .into(),
+                            ),
+                            meta,
+                        }),
+                    };
+                }
+
                 // We need to get the type of the resulting expression to create the local,
                 // this must be done after implicit conversions to ensure both branches have
                 // the same type.
@@ -1308,6 +1323,13 @@ impl<'a> Context<'a> {
                                 self.forced_conversion(&mut array_length, meta, Scalar::I32)?;
                                 array_length
                             }
+                            // The length of a vector is its number of components, and
+                            // the length of a matrix its number of columns
+                            TypeInner::Vector { size, .. }
+                            | TypeInner::Matrix { columns: size, .. } => self.add_expression(
+                                Expression::Literal(Literal::I32(size as i32)),
+                                meta,
+                            )?,
                             // let the error be handled in type checking if it's not a dynamic array
                             _ => {
                                 let mut array_length = self
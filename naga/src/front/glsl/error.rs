@@ -166,8 +166,12 @@ impl ParseError {
     }
 
     pub fn emit_to_string(&self, source: &str) -> String {
+        self.emit_to_string_with_path(source, "glsl")
+    }
+
+    pub fn emit_to_string_with_path(&self, source: &str, path: &str) -> String {
         let mut writer = NoColor::new(Vec::new());
-        self.emit_to_writer(&mut writer, source);
+        self.emit_to_writer_with_path(&mut writer, source, path);
         String::from_utf8(writer.into_inner()).unwrap()
     }
 }
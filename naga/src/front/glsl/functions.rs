@@ -7,9 +7,11 @@ use super::{
     Frontend, Result,
 };
 use crate::{
-    front::glsl::types::type_power, proc::ensure_block_returns, AddressSpace, Block, EntryPoint,
-    Expression, Function, FunctionArgument, FunctionResult, Handle, Literal, LocalVariable, Scalar,
-    ScalarKind, Span, Statement, StructMember, Type, TypeInner,
+    front::glsl::types::type_power,
+    proc::{ensure_block_returns, Layouter},
+    AddressSpace, Block, EntryPoint, Expression, Function, FunctionArgument, FunctionResult,
+    Handle, Literal, LocalVariable, Scalar, ScalarKind, ShaderStage, Span, Statement, StructMember,
+    Type, TypeInner,
 };
 use std::iter;
 
@@ -400,6 +402,15 @@ impl Frontend {
             }
         }
 
+        if components.len() < size as usize {
+            self.errors.push(Error {
+                kind: ErrorKind::SemanticError(
+                    "Not enough data provided to vector constructor".into(),
+                ),
+                meta,
+            });
+        }
+
         components.truncate(size as usize);
 
         ctx.add_expression(Expression::Compose { ty, components }, meta)
@@ -441,6 +452,18 @@ impl Frontend {
                     }
                 }
 
+                // Components of the last argument that aren't needed are ignored
+                let count = columns as usize * rows as usize;
+                if flattened.len() < count {
+                    self.errors.push(Error {
+                        kind: ErrorKind::SemanticError(
+                            "Not enough data provided to matrix constructor".into(),
+                        ),
+                        meta,
+                    });
+                }
+                flattened.truncate(count);
+
                 let ty = ctx.module.types.insert(
                     Type {
                         name: None,
@@ -516,6 +539,34 @@ impl Frontend {
         raw_args: &[Handle<HirExpr>],
         meta: Span,
     ) -> Result<Option<Handle<Expression>>> {
+        // Naga's IR has no geometry streams or subgroup scans other than
+        // additions and multiplications, so report their builtins as such
+        // instead of as unknown functions
+        let unsupported = match name.as_str() {
+            "EmitStreamVertex" | "EndStreamPrimitive" => Some("geometry shader streams"),
+            "subgroupInclusiveMin"
+            | "subgroupInclusiveMax"
+            | "subgroupInclusiveAnd"
+            | "subgroupInclusiveOr"
+            | "subgroupInclusiveXor"
+            | "subgroupExclusiveMin"
+            | "subgroupExclusiveMax"
+            | "subgroupExclusiveAnd"
+            | "subgroupExclusiveOr"
+            | "subgroupExclusiveXor" => {
+                Some("subgroup scans other than additions and multiplications")
+            }
+            _ => None,
+        };
+        if let Some(feature) = unsupported {
+            if !self.lookup_function.contains_key(&name) {
+                return Err(Error {
+                    kind: ErrorKind::NotImplemented(feature),
+                    meta,
+                });
+            }
+        }
+
         // Grow the typifier to be able to index it later without needing
         // to hold the context mutably
         for &(expr, span) in args.iter() {
@@ -918,7 +969,7 @@ impl Frontend {
         // Since the original expression might be a pointer and we want a value
         // for the proxy writes, we might need to load the pointer.
         let value = if original_pointer_space.is_some() {
-            ctx.add_expression(Expression::Load { pointer: original }, Span::default())?
+            ctx.add_expression(Expression::Load { pointer: original }, call_argument.1)?
         } else {
             original
         };
@@ -946,10 +997,10 @@ impl Frontend {
                     ty,
                     init: None,
                 },
-                Span::default(),
+                call_argument.1,
             );
             let spill_expr =
-                ctx.add_expression(Expression::LocalVariable(spill_var), Span::default())?;
+                ctx.add_expression(Expression::LocalVariable(spill_var), call_argument.1)?;
 
             // If the argument is also copied in we must store the value of the
             // original variable to the spill variable.
@@ -959,7 +1010,7 @@ impl Frontend {
                         pointer: spill_expr,
                         value,
                     },
-                    Span::default(),
+                    call_argument.1,
                 );
             }
 
@@ -990,7 +1041,7 @@ impl Frontend {
                             base: vector,
                             index: *component as u32,
                         },
-                        Span::default(),
+                        call_argument.1,
                     )?;
 
                     let spill_component = ctx.add_expression(
@@ -998,7 +1049,7 @@ impl Frontend {
                             base: spill_expr,
                             index: i as u32,
                         },
-                        Span::default(),
+                        call_argument.1,
                     )?;
 
                     proxy_writes.push(ProxyWrite {
@@ -1201,12 +1252,24 @@ impl Frontend {
     /// - build its return value from whatever values the GLSL `main` left in
     ///   the Naga globals representing GLSL `output` variables.
     ///
+    /// Geometry shaders are the exception: their per-vertex inputs are split
+    /// into an array argument per leaf, and rather than being returned, their
+    /// outputs are emitted as a vertex by the function `EmitVertex()` calls,
+    /// as described by the entry point's [`GeometryStageInfo`].
+    ///
+    /// The per-vertex inputs of tessellation shaders are split the same way.
+    /// Tessellation control shaders return the element of their per-vertex
+    /// output arrays selected by `gl_InvocationID`, along with their per-patch
+    /// outputs, as described by the entry point's [`TessellationStageInfo`].
+    ///
     /// Upon entry, [`ctx.body`] should contain code, accumulated by prior calls
     /// to [`ParsingContext::parse_external_declaration`][pxd], to initialize
     /// private global variables as needed. This code gets spliced into the
     /// entry point before the call to `main`.
     ///
     /// [`GlobalVariable`]: crate::GlobalVariable
+    /// [`GeometryStageInfo`]: crate::GeometryStageInfo
+    /// [`TessellationStageInfo`]: crate::TessellationStageInfo
     /// [`Private`]: crate::AddressSpace::Private
     /// [`ctx.body`]: Context::body
     /// [pxd]: super::ParsingContext::parse_external_declaration
@@ -1239,6 +1302,24 @@ impl Frontend {
 
             let ty = ctx.module.global_variables[arg.handle].ty;
 
+            let per_vertex_arrays = matches!(
+                self.meta.stage,
+                ShaderStage::Geometry
+                    | ShaderStage::TessellationControl
+                    | ShaderStage::TessellationEvaluation
+            );
+            if per_vertex_arrays && arg.binding.is_per_vertex() {
+                ctx.per_vertex_arg_walker(
+                    &mut self.layouter,
+                    arg.name.clone(),
+                    arg.binding.clone(),
+                    pointer,
+                    ty,
+                    &mut arguments,
+                )?;
+                continue;
+            }
+
             ctx.arg_type_walker(
                 arg.name.clone(),
                 arg.binding.clone(),
@@ -1262,6 +1343,39 @@ impl Frontend {
             )?
         }
 
+        // Tessellation control shaders return the values of the vertex of
+        // the output patch selected by their invocation id.
+        let invocation_id = if self.meta.stage == ShaderStage::TessellationControl {
+            let binding = crate::Binding::BuiltIn(crate::BuiltIn::InvocationId);
+            let idx = match arguments
+                .iter()
+                .position(|arg| arg.binding.as_ref() == Some(&binding))
+            {
+                Some(idx) => idx,
+                None => {
+                    let ty = ctx.module.types.insert(
+                        Type {
+                            name: None,
+                            inner: TypeInner::Scalar(Scalar::U32),
+                        },
+                        Span::default(),
+                    );
+                    arguments.push(FunctionArgument {
+                        name: Some("gl_InvocationID".into()),
+                        ty,
+                        binding: Some(binding),
+                    });
+                    arguments.len() - 1
+                }
+            };
+            Some(
+                ctx.expressions
+                    .append(Expression::FunctionArgument(idx as u32), Default::default()),
+            )
+        } else {
+            None
+        };
+
         ctx.body.extend_block(global_init_body);
 
         ctx.body.push(
@@ -1273,6 +1387,65 @@ impl Frontend {
             Default::default(),
         );
 
+        // Geometry shaders output their vertices as they emit them, rather
+        // than returning them.
+        let (result, geometry_info) = if self.meta.stage == ShaderStage::Geometry {
+            let geometry_info = self.add_emit_vertex(&mut ctx)?;
+            ctx.body
+                .push(Statement::Return { value: None }, Default::default());
+            (None, Some(geometry_info))
+        } else {
+            let outputs = self.compose_outputs(&mut ctx, invocation_id)?;
+            ctx.body.push(
+                Statement::Return {
+                    value: outputs.map(|(_, value)| value),
+                },
+                Default::default(),
+            );
+            (
+                outputs.map(|(ty, _)| FunctionResult { ty, binding: None }),
+                None,
+            )
+        };
+
+        let tessellation_info = self.tessellation_info()?;
+
+        let Context {
+            body, expressions, ..
+        } = ctx;
+
+        ctx.module.entry_points.push(EntryPoint {
+            name: "main".to_string(),
+            stage: self.meta.stage,
+            early_depth_test: Some(crate::EarlyDepthTest { conservative: None })
+                .filter(|_| self.meta.early_fragment_tests),
+            workgroup_size: self.meta.workgroup_size,
+            task_payload: None,
+            mesh_info: None,
+            geometry_info,
+            tessellation_info,
+            function: Function {
+                arguments,
+                expressions,
+                body,
+                result,
+                ..Default::default()
+            },
+        });
+
+        Ok(())
+    }
+
+    /// Loads the values of the GLSL output variables in `ctx`, returning a
+    /// struct composed of them and its type, if there are any.
+    ///
+    /// The per-vertex outputs of tessellation control shaders are arrays,
+    /// of which only the element `invocation_id` is loaded.
+    fn compose_outputs(
+        &self,
+        ctx: &mut Context,
+        invocation_id: Option<Handle<Expression>>,
+    ) -> Result<Option<(Handle<Type>, Handle<Expression>)>> {
         let mut span = 0;
         let mut members = Vec::new();
         let mut components = Vec::new();
@@ -1282,11 +1455,24 @@ impl Frontend {
                 continue;
             }
 
-            let pointer = ctx
+            let mut pointer = ctx
                 .expressions
                 .append(Expression::GlobalVariable(arg.handle), Default::default());
 
-            let ty = ctx.module.global_variables[arg.handle].ty;
+            let mut ty = ctx.module.global_variables[arg.handle].ty;
+
+            if let Some(index) = invocation_id.filter(|_| arg.binding.is_per_vertex()) {
+                if let TypeInner::Array { base, .. } = ctx.module.types[ty].inner {
+                    pointer = ctx.add_expression(
+                        Expression::Access {
+                            base: pointer,
+                            index,
+                        },
+                        Span::default(),
+                    )?;
+                    ty = base;
+                }
+            }
 
             ctx.arg_type_walker(
                 arg.name.clone(),
@@ -1316,56 +1502,214 @@ impl Frontend {
             )?
         }
 
-        let (ty, value) = if !components.is_empty() {
-            let ty = ctx.module.types.insert(
-                Type {
-                    name: None,
-                    inner: TypeInner::Struct { members, span },
-                },
-                Default::default(),
-            );
+        if components.is_empty() {
+            return Ok(None);
+        }
 
-            let len = ctx.expressions.len();
-            let res = ctx
-                .expressions
-                .append(Expression::Compose { ty, components }, Default::default());
-            ctx.body.push(
-                Statement::Emit(ctx.expressions.range_from(len)),
-                Default::default(),
-            );
+        let ty = ctx.module.types.insert(
+            Type {
+                name: None,
+                inner: TypeInner::Struct { members, span },
+            },
+            Default::default(),
+        );
 
-            (Some(ty), Some(res))
-        } else {
-            (None, None)
+        let len = ctx.expressions.len();
+        let res = ctx
+            .expressions
+            .append(Expression::Compose { ty, components }, Default::default());
+        ctx.body.push(
+            Statement::Emit(ctx.expressions.range_from(len)),
+            Default::default(),
+        );
+
+        Ok(Some((ty, res)))
+    }
+
+    /// Fills in the function called by `EmitVertex()`, which emits the values
+    /// of the output variables of a geometry shader as a vertex.
+    ///
+    /// Returns the [`GeometryStageInfo`] of the entry point.
+    ///
+    /// [`GeometryStageInfo`]: crate::GeometryStageInfo
+    fn add_emit_vertex(&self, ctx: &mut Context) -> Result<crate::GeometryStageInfo> {
+        let missing = |what: &str| Error {
+            kind: ErrorKind::SemanticError(format!("Geometry shaders must declare {what}").into()),
+            meta: Span::default(),
         };
+        let input_primitive = self
+            .meta
+            .geometry_input_primitive
+            .ok_or_else(|| missing("an input primitive"))?;
+        let output_primitive = self
+            .meta
+            .geometry_output_primitive
+            .ok_or_else(|| missing("an output primitive"))?;
+        if self.meta.geometry_max_vertices == 0 {
+            return Err(missing("a `max_vertices` count above zero"));
+        }
 
-        ctx.body
-            .push(Statement::Return { value }, Default::default());
+        let (vertex_output_type, expressions, body) = {
+            let mut emit_ctx = Context::new(self, ctx.module, false)?;
+            let (ty, value) = self
+                .compose_outputs(&mut emit_ctx, None)?
+                .ok_or_else(|| missing("outputs"))?;
+            emit_ctx
+                .body
+                .push(Statement::EmitVertex { value }, Default::default());
+            (ty, emit_ctx.expressions, emit_ctx.body)
+        };
 
-        let Context {
-            body, expressions, ..
-        } = ctx;
+        let emit_vertex = &mut ctx.module.functions[self.emit_vertex.unwrap()];
+        emit_vertex.expressions = expressions;
+        emit_vertex.body = body;
 
-        ctx.module.entry_points.push(EntryPoint {
-            name: "main".to_string(),
-            stage: self.meta.stage,
-            early_depth_test: Some(crate::EarlyDepthTest { conservative: None })
-                .filter(|_| self.meta.early_fragment_tests),
-            workgroup_size: self.meta.workgroup_size,
-            function: Function {
-                arguments,
-                expressions,
-                body,
-                result: ty.map(|ty| FunctionResult { ty, binding: None }),
-                ..Default::default()
-            },
-        });
+        Ok(crate::GeometryStageInfo {
+            input_primitive,
+            output_primitive,
+            max_vertices: self.meta.geometry_max_vertices,
+            invocations: self.meta.geometry_invocations,
+            vertex_output_type,
+        })
+    }
 
-        Ok(())
+    /// Returns the [`TessellationStageInfo`] of the entry point of
+    /// tessellation shaders.
+    ///
+    /// [`TessellationStageInfo`]: crate::TessellationStageInfo
+    fn tessellation_info(&self) -> Result<Option<crate::TessellationStageInfo>> {
+        let missing = |stage: &str, what: &str| Error {
+            kind: ErrorKind::SemanticError(
+                format!("Tessellation {stage} shaders must declare {what}").into(),
+            ),
+            meta: Span::default(),
+        };
+        Ok(match self.meta.stage {
+            ShaderStage::TessellationControl => {
+                if self.meta.tessellation_output_vertices == 0 {
+                    return Err(missing("control", "a `vertices` count above zero"));
+                }
+                Some(crate::TessellationStageInfo::Control {
+                    output_vertices: self.meta.tessellation_output_vertices,
+                })
+            }
+            ShaderStage::TessellationEvaluation => Some(crate::TessellationStageInfo::Evaluation {
+                primitive: self
+                    .meta
+                    .tessellation_primitive
+                    .ok_or_else(|| missing("evaluation", "a primitive"))?,
+                spacing: self.meta.tessellation_spacing,
+                clockwise: self.meta.tessellation_clockwise,
+                point_mode: self.meta.tessellation_point_mode,
+            }),
+            _ => None,
+        })
     }
 }
 
 impl Context<'_> {
+    /// Walks the per-vertex input `ty` of a geometry or tessellation shader,
+    /// an array with a value for each vertex of the input primitive or patch,
+    /// adding an argument to `arguments` for each of its leaves.
+    ///
+    /// Every argument is an array of a leaf's values for each vertex, which
+    /// are stored to their place in the GLSL variable at `pointer`.
+    fn per_vertex_arg_walker(
+        &mut self,
+        layouter: &mut Layouter,
+        name: Option<String>,
+        binding: crate::Binding,
+        pointer: Handle<Expression>,
+        ty: Handle<Type>,
+        arguments: &mut Vec<FunctionArgument>,
+    ) -> Result<()> {
+        // `add_global_var` gives every per-vertex input a constant size
+        let TypeInner::Array {
+            base,
+            size: crate::ArraySize::Constant(vertex_count),
+            ..
+        } = self.module.types[ty].inner
+        else {
+            return Ok(());
+        };
+
+        // The leaves of `base`, with their pointers for each vertex
+        let mut leaves: Vec<(_, _, _, Vec<Handle<Expression>>)> = Vec::new();
+        for index in 0..vertex_count.get() {
+            let vertex_pointer = self.add_expression(
+                Expression::AccessIndex {
+                    base: pointer,
+                    index,
+                },
+                Span::default(),
+            )?;
+
+            let mut leaf = 0;
+            self.arg_type_walker(
+                name.clone(),
+                binding.clone(),
+                vertex_pointer,
+                base,
+                &mut |_, name, pointer, ty, binding| {
+                    match leaves.get_mut(leaf) {
+                        Some(&mut (_, _, _, ref mut pointers)) => pointers.push(pointer),
+                        None => leaves.push((name, ty, binding, vec![pointer])),
+                    }
+                    leaf += 1;
+                },
+            )?
+        }
+
+        for (name, leaf_ty, binding, pointers) in leaves {
+            let ty = if leaf_ty == base {
+                ty
+            } else {
+                layouter.update(self.module.to_ctx()).unwrap();
+                let stride = layouter[leaf_ty].to_stride();
+                self.module.types.insert(
+                    Type {
+                        name: None,
+                        inner: TypeInner::Array {
+                            base: leaf_ty,
+                            size: crate::ArraySize::Constant(vertex_count),
+                            stride,
+                        },
+                    },
+                    Span::default(),
+                )
+            };
+
+            let idx = arguments.len() as u32;
+            arguments.push(FunctionArgument {
+                name,
+                ty,
+                binding: Some(binding),
+            });
+
+            let argument = self
+                .expressions
+                .append(Expression::FunctionArgument(idx), Default::default());
+            for (index, pointer) in (0..).zip(pointers) {
+                let len = self.expressions.len();
+                let value = self.expressions.append(
+                    Expression::AccessIndex {
+                        base: argument,
+                        index,
+                    },
+                    Default::default(),
+                );
+                self.body.push(
+                    Statement::Emit(self.expressions.range_from(len)),
+                    Default::default(),
+                );
+                self.body
+                    .push(Statement::Store { pointer, value }, Default::default());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Helper function for building the input/output interface of the entry point
     ///
     /// Calls `f` with the data of the entry point argument, flattening composite types
@@ -1400,9 +1744,15 @@ impl Context<'_> {
                 size: crate::ArraySize::Constant(size),
                 ..
             } => {
-                let mut location = match binding {
-                    crate::Binding::Location { location, .. } => location,
-                    crate::Binding::BuiltIn(_) => return Ok(()),
+                let (mut location, patch) = match binding {
+                    crate::Binding::Location {
+                        location, patch, ..
+                    } => (location, patch),
+                    // Arrays of builtins, like `gl_TessLevelOuter`
+                    crate::Binding::BuiltIn(_) => {
+                        f(self, name, pointer, ty, binding);
+                        return Ok(());
+                    }
                 };
 
                 let interpolation =
@@ -1428,6 +1778,7 @@ impl Context<'_> {
                         interpolation,
                         sampling: None,
                         second_blend_source: false,
+                        patch,
                     };
                     location += 1;
 
@@ -1435,8 +1786,16 @@ impl Context<'_> {
                 }
             }
             TypeInner::Struct { ref members, .. } => {
-                let mut location = match binding {
-                    crate::Binding::Location { location, .. } => location,
+                let (mut location, patch) = match binding {
+                    crate::Binding::Location {
+                        location, patch, ..
+                    } => (location, patch),
+                    // Blocks of builtins, like the `gl_PerVertex` of `gl_in`
+                    crate::Binding::BuiltIn(_)
+                        if members.iter().all(|member| member.binding.is_some()) =>
+                    {
+                        (0, false)
+                    }
                     crate::Binding::BuiltIn(_) => return Ok(()),
                 };
 
@@ -1464,6 +1823,7 @@ impl Context<'_> {
                                 interpolation,
                                 sampling: None,
                                 second_blend_source: false,
+                                patch,
                             };
                             location += 1;
                             binding
@@ -0,0 +1,118 @@
+//! Expansion of `#include` directives.
+//!
+//! The preprocessor doesn't know about `#include`, so the directives are
+//! replaced with the contents of the included files before the source is
+//! lexed, in the style of `GL_GOOGLE_include_directive`.
+
+use std::borrow::Cow;
+
+use super::{Error, ErrorKind, Result, Span};
+
+/// Callback used to resolve the `#include` directives of a shader.
+///
+/// The callback receives the path as written in the directive, without the
+/// surrounding quotes or angle brackets, and returns the contents of the
+/// included file, or `None` if it couldn't be found.
+///
+/// The included files are expanded before any other preprocessing happens, so
+/// directives inside of inactive `#if` blocks are still expanded, and the spans
+/// of errors refer to the source after the expansion.
+pub struct IncludeResolver(Box<dyn Fn(&str) -> Option<String> + Send + Sync>);
+
+impl IncludeResolver {
+    /// Creates a resolver from a callback.
+    pub fn new(resolver: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+        IncludeResolver(Box::new(resolver))
+    }
+}
+
+impl std::fmt::Debug for IncludeResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IncludeResolver").finish_non_exhaustive()
+    }
+}
+
+/// Returns the path of the `#include` directive on `line`, if there is one.
+fn include_path(line: &str) -> Option<&str> {
+    let rest = line
+        .trim_start()
+        .strip_prefix('#')?
+        .trim_start()
+        .strip_prefix("include")?
+        .trim();
+
+    if let Some(path) = rest.strip_prefix('"') {
+        path.strip_suffix('"')
+    } else if let Some(path) = rest.strip_prefix('<') {
+        path.strip_suffix('>')
+    } else {
+        None
+    }
+}
+
+/// Replaces all the `#include` directives in `source` using `resolver`.
+pub(super) fn expand_includes<'a>(
+    source: &'a str,
+    resolver: Option<&IncludeResolver>,
+) -> Result<Cow<'a, str>> {
+    let Some(resolver) = resolver else {
+        return Ok(Cow::Borrowed(source));
+    };
+    if !source.lines().any(|line| include_path(line).is_some()) {
+        return Ok(Cow::Borrowed(source));
+    }
+
+    let mut output = String::with_capacity(source.len());
+    expand(source, resolver, &mut Vec::new(), &mut output, None)?;
+    Ok(Cow::Owned(output))
+}
+
+/// Appends `source` to `output` with its includes expanded.
+///
+/// `stack` holds the paths of the files currently being expanded, to detect
+/// recursive includes. Errors in included files are reported at `outer`, the
+/// span of the directive in the original source.
+fn expand(
+    source: &str,
+    resolver: &IncludeResolver,
+    stack: &mut Vec<String>,
+    output: &mut String,
+    outer: Option<Span>,
+) -> Result<()> {
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let Some(path) = include_path(line) else {
+            output.push_str(line);
+            continue;
+        };
+
+        let meta = outer
+            .unwrap_or_else(|| Span::new(start as u32, (start + line.trim_end().len()) as u32));
+
+        if stack.iter().any(|included| included == path) {
+            return Err(Error {
+                kind: ErrorKind::SemanticError(format!("Recursive include of \"{path}\"").into()),
+                meta,
+            });
+        }
+
+        let contents = (resolver.0)(path).ok_or_else(|| Error {
+            kind: ErrorKind::SemanticError(format!("Couldn't resolve include \"{path}\"").into()),
+            meta,
+        })?;
+
+        stack.push(path.to_owned());
+        expand(&contents, resolver, stack, output, Some(meta))?;
+        stack.pop();
+
+        // Keep the line following the directive on its own line
+        if line.ends_with('\n') && !output.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Validation of the interface between consecutive stages of a pipeline.
+
+use thiserror::Error;
+
+use crate::{Binding, Handle, Interpolation, Module, Sampling, Type, TypeInner};
+
+/// Mismatch between the outputs of a stage and the inputs of the next one.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum InterfaceError {
+    /// The module doesn't have an entry point.
+    #[error("The module has no entry point")]
+    MissingEntryPoint,
+    /// An input isn't written by the previous stage.
+    #[error("Input at location {0} isn't written by the previous stage")]
+    MissingOutput(u32),
+    /// An input has a different type than the output at the same location.
+    #[error(
+        "Input at location {location} has type {input:?} but the previous stage writes {output:?}"
+    )]
+    TypeMismatch {
+        location: u32,
+        output: TypeInner,
+        input: TypeInner,
+    },
+    /// An input is interpolated differently to the output at the same location.
+    #[error("Input at location {location} uses {input:?} interpolation but the previous stage uses {output:?}")]
+    InterpolationMismatch {
+        location: u32,
+        output: (Option<Interpolation>, Option<Sampling>),
+        input: (Option<Interpolation>, Option<Sampling>),
+    },
+}
+
+/// A user defined variable of a stage's interface.
+struct Varying<'a> {
+    location: u32,
+    ty: &'a TypeInner,
+    interpolation: (Option<Interpolation>, Option<Sampling>),
+}
+
+/// Adds the varyings of a value of type `ty` with `binding` to `varyings`.
+fn collect<'a>(
+    module: &'a Module,
+    ty: Handle<Type>,
+    binding: Option<&Binding>,
+    varyings: &mut Vec<Varying<'a>>,
+) {
+    match binding {
+        Some(&Binding::Location {
+            location,
+            interpolation,
+            sampling,
+            ..
+        }) => varyings.push(Varying {
+            location,
+            ty: &module.types[ty].inner,
+            interpolation: (interpolation, sampling),
+        }),
+        Some(&Binding::BuiltIn(_)) => {}
+        None => {
+            if let TypeInner::Struct { ref members, .. } = module.types[ty].inner {
+                for member in members {
+                    collect(module, member.ty, member.binding.as_ref(), varyings);
+                }
+            }
+        }
+    }
+}
+
+/// Checks that the inputs of the entry point of `consumer` are written by the
+/// entry point of `producer`, like the vertex and fragment shaders of a pipeline.
+///
+/// Every input must have an output at the same location with the same type and
+/// interpolation. Outputs that aren't read by the next stage are allowed.
+///
+/// Returns all the mismatches that were found.
+pub fn validate_interface(producer: &Module, consumer: &Module) -> Result<(), Vec<InterfaceError>> {
+    let (Some(producer_ep), Some(consumer_ep)) =
+        (producer.entry_points.first(), consumer.entry_points.first())
+    else {
+        return Err(vec![InterfaceError::MissingEntryPoint]);
+    };
+
+    let mut outputs = Vec::new();
+    if let Some(ref result) = producer_ep.function.result {
+        collect(producer, result.ty, result.binding.as_ref(), &mut outputs);
+    }
+
+    let mut inputs = Vec::new();
+    for argument in consumer_ep.function.arguments.iter() {
+        collect(
+            consumer,
+            argument.ty,
+            argument.binding.as_ref(),
+            &mut inputs,
+        );
+    }
+
+    let mut errors = Vec::new();
+    for input in inputs {
+        let location = input.location;
+        let Some(output) = outputs.iter().find(|output| output.location == location) else {
+            errors.push(InterfaceError::MissingOutput(location));
+            continue;
+        };
+
+        if output.ty != input.ty {
+            errors.push(InterfaceError::TypeMismatch {
+                location,
+                output: output.ty.clone(),
+                input: input.ty.clone(),
+            });
+        } else if output.interpolation != input.interpolation {
+            errors.push(InterfaceError::InterpolationMismatch {
+                location,
+                output: output.interpolation,
+                input: input.interpolation,
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
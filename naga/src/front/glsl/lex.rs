@@ -73,6 +73,7 @@ impl<'a> Iterator for Lexer<'a> {
                     "buffer" => TokenValue::Buffer,
                     "shared" => TokenValue::Shared,
                     "invariant" => TokenValue::Invariant,
+                    "patch" => TokenValue::Patch,
                     "flat" => TokenValue::Interpolation(crate::Interpolation::Flat),
                     "noperspective" => TokenValue::Interpolation(crate::Interpolation::Linear),
                     "smooth" => TokenValue::Interpolation(crate::Interpolation::Perspective),
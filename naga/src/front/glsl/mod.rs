@@ -9,11 +9,21 @@ To begin, take a look at the documentation for the [`Frontend`].
 - 450
 - 460
 
+The `compatibility` profile is accepted, but the features it keeps from older
+versions aren't supported.
+
+## OpenGL ES
+- 300
+- 310
+- 320
+
 [glsl]: https://www.khronos.org/registry/OpenGL/index_gl.php
 */
 
 pub use ast::{Precision, Profile};
 pub use error::{Error, ErrorKind, ExpectedToken, ParseError};
+pub use include::IncludeResolver;
+pub use interface::{validate_interface, InterfaceError};
 pub use token::TokenValue;
 
 use crate::{proc::Layouter, FastHashMap, FastHashSet, Handle, Module, ShaderStage, Span, Type};
@@ -25,6 +35,8 @@ mod builtins;
 mod context;
 mod error;
 mod functions;
+mod include;
+mod interface;
 mod lex;
 mod offset;
 mod parser;
@@ -56,6 +68,15 @@ pub struct Options {
     /// ```
     /// for each key value pair in the map.
     pub defines: FastHashMap<String, String>,
+    /// Resolver for the `#include` directives of the shader.
+    ///
+    /// If this is `None`, `#include` directives are left to the preprocessor,
+    /// which rejects them.
+    pub include_resolver: Option<IncludeResolver>,
+    /// Name to give to the entry point of the produced [`Module`].
+    ///
+    /// If this is `None`, the entry point keeps the name of the `main` function.
+    pub entry_point: Option<String>,
 }
 
 impl From<ShaderStage> for Options {
@@ -63,6 +84,8 @@ impl From<ShaderStage> for Options {
         Options {
             stage,
             defines: FastHashMap::default(),
+            include_resolver: None,
+            entry_point: None,
         }
     }
 }
@@ -90,6 +113,38 @@ pub struct ShaderMetadata {
     /// Defaults to `false`.
     pub early_fragment_tests: bool,
 
+    /// The primitive consumed by geometry shaders, set with an input layout
+    /// qualifier like `layout(triangles) in;`.
+    pub geometry_input_primitive: Option<crate::GeometryInputPrimitive>,
+    /// The primitive produced by geometry shaders, set with an output layout
+    /// qualifier like `layout(triangle_strip) out;`.
+    pub geometry_output_primitive: Option<crate::GeometryOutputPrimitive>,
+    /// The maximum number of vertices emitted by geometry shaders, set with
+    /// the `max_vertices` output layout qualifier. Defaults to `0`.
+    pub geometry_max_vertices: u32,
+    /// The number of times geometry shaders are invoked for each primitive,
+    /// set with the `invocations` input layout qualifier. Defaults to `1`.
+    pub geometry_invocations: u32,
+
+    /// The number of vertices of the patches output by tessellation control
+    /// shaders, set with the `vertices` output layout qualifier. Defaults to
+    /// `0`.
+    pub tessellation_output_vertices: u32,
+    /// The primitives tessellation evaluation shaders subdivide patches into,
+    /// set with an input layout qualifier like `layout(triangles) in;`.
+    pub tessellation_primitive: Option<crate::TessellationPrimitive>,
+    /// The spacing of the segments of subdivided patches, set with an input
+    /// layout qualifier like `layout(fractional_odd_spacing) in;`. Defaults to
+    /// equal spacing.
+    pub tessellation_spacing: crate::TessellationSpacing,
+    /// Whether the triangles generated by the tessellator are wound
+    /// clockwise, set with the `cw` input layout qualifier. Defaults to
+    /// `false`.
+    pub tessellation_clockwise: bool,
+    /// Whether the tessellator generates points, set with the `point_mode`
+    /// input layout qualifier. Defaults to `false`.
+    pub tessellation_point_mode: bool,
+
     /// The shader can request extensions via the
     /// `#extension` preprocessor directive, in the directive a behavior
     /// parameter is used to control whether the extension should be disabled,
@@ -107,6 +162,15 @@ impl ShaderMetadata {
         self.stage = stage;
         self.workgroup_size = [u32::from(stage == ShaderStage::Compute); 3];
         self.early_fragment_tests = false;
+        self.geometry_input_primitive = None;
+        self.geometry_output_primitive = None;
+        self.geometry_max_vertices = 0;
+        self.geometry_invocations = 1;
+        self.tessellation_output_vertices = 0;
+        self.tessellation_primitive = None;
+        self.tessellation_spacing = crate::TessellationSpacing::Equal;
+        self.tessellation_clockwise = false;
+        self.tessellation_point_mode = false;
         self.extensions.clear();
     }
 }
@@ -119,6 +183,15 @@ impl Default for ShaderMetadata {
             stage: ShaderStage::Vertex,
             workgroup_size: [0; 3],
             early_fragment_tests: false,
+            geometry_input_primitive: None,
+            geometry_output_primitive: None,
+            geometry_max_vertices: 0,
+            geometry_invocations: 1,
+            tessellation_output_vertices: 0,
+            tessellation_primitive: None,
+            tessellation_spacing: crate::TessellationSpacing::Equal,
+            tessellation_clockwise: false,
+            tessellation_point_mode: false,
             extensions: FastHashSet::default(),
         }
     }
@@ -171,6 +244,9 @@ pub struct Frontend {
     global_variables: Vec<(String, GlobalLookup)>,
 
     entry_args: Vec<EntryArg>,
+    /// The function emitting the current values of the outputs of geometry
+    /// shaders as a vertex, called by `EmitVertex()`.
+    emit_vertex: Option<Handle<crate::Function>>,
 
     layouter: Layouter,
 
@@ -185,6 +261,7 @@ impl Frontend {
         self.lookup_type.clear();
         self.global_variables.clear();
         self.entry_args.clear();
+        self.emit_vertex = None;
         self.layouter.clear();
     }
 
@@ -199,11 +276,22 @@ impl Frontend {
     ) -> std::result::Result<Module, ParseError> {
         self.reset(options.stage);
 
-        let lexer = lex::Lexer::new(source, &options.defines);
+        let source = match include::expand_includes(source, options.include_resolver.as_ref()) {
+            Ok(source) => source,
+            Err(e) => return Err(vec![e].into()),
+        };
+
+        let lexer = lex::Lexer::new(&source, &options.defines);
         let mut ctx = ParsingContext::new(lexer);
 
         match ctx.parse(self) {
-            Ok(module) => {
+            Ok(mut module) => {
+                if let Some(ref name) = options.entry_point {
+                    for entry_point in module.entry_points.iter_mut() {
+                        entry_point.name = name.clone();
+                    }
+                }
+
                 if self.errors.is_empty() {
                     Ok(module)
                 } else {
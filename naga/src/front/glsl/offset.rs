@@ -128,6 +128,9 @@ pub fn calculate_offset(
 
             (align, align * columns as u32)
         }
+        // 9. If the member is a structure, the base alignment of the structure is N, where
+        // N is the largest base alignment value of any of its members, and rounded up to
+        // the base alignment of a vec4.
         TypeInner::Struct { ref members, .. } => {
             let mut span = 0;
             let mut align = Alignment::ONE;
@@ -147,6 +150,11 @@ pub fn calculate_offset(
                 span += info.span;
             }
 
+            // See comment at the beginning of the function
+            if StructLayout::Std430 != layout {
+                align = align.max(Alignment::MIN_UNIFORM);
+            }
+
             span = align.round_up(span);
 
             let ty_span = types.get_span(ty);
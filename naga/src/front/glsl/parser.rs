@@ -9,7 +9,7 @@ use super::{
     variables::{GlobalOrConstant, VarDeclaration},
     Frontend, Result,
 };
-use crate::{arena::Handle, proc::U32EvalError, Expression, Module, Span, Type};
+use crate::{arena::Handle, proc::U32EvalError, Expression, Module, ShaderStage, Span, Type};
 use pp_rs::token::{PreprocessorError, Token as PPToken, TokenValue as PPTokenValue};
 use std::iter::Peekable;
 
@@ -23,6 +23,10 @@ pub struct ParsingContext<'source> {
     /// Used to store tokens already consumed by the parser but that need to be backtracked
     backtracked_token: Option<Token>,
     last_meta: Span,
+    /// Number of braces opened by the consumed tokens that haven't been closed yet
+    brace_depth: usize,
+    /// Whether the last consumed token ended an external declaration
+    declaration_end: bool,
 }
 
 impl<'source> ParsingContext<'source> {
@@ -31,6 +35,8 @@ impl<'source> ParsingContext<'source> {
             lexer: lexer.peekable(),
             backtracked_token: None,
             last_meta: Span::default(),
+            brace_depth: 0,
+            declaration_end: false,
         }
     }
 
@@ -84,6 +90,7 @@ impl<'source> ParsingContext<'source> {
         loop {
             if let Some(token) = self.backtracked_token.take() {
                 self.last_meta = token.meta;
+                self.declaration_end = self.ends_declaration(&token);
                 break Some(token);
             }
 
@@ -91,7 +98,16 @@ impl<'source> ParsingContext<'source> {
 
             match res.kind {
                 LexerResultKind::Token(token) => {
+                    // Backtracked tokens were already counted when they were first consumed
+                    match token.value {
+                        TokenValue::LeftBrace => self.brace_depth += 1,
+                        TokenValue::RightBrace => {
+                            self.brace_depth = self.brace_depth.saturating_sub(1)
+                        }
+                        _ => {}
+                    }
                     self.last_meta = token.meta;
+                    self.declaration_end = self.ends_declaration(&token);
                     break Some(token);
                 }
                 LexerResultKind::Directive(directive) => {
@@ -105,6 +121,33 @@ impl<'source> ParsingContext<'source> {
         }
     }
 
+    fn ends_declaration(&self, token: &Token) -> bool {
+        self.brace_depth == 0
+            && matches!(token.value, TokenValue::Semicolon | TokenValue::RightBrace)
+    }
+
+    /// Skips the tokens of the external declaration that failed to parse, so
+    /// that parsing can resume at the next one.
+    ///
+    /// `start` is the span of the last token consumed before the declaration,
+    /// it's used to make sure that at least one token is skipped.
+    fn skip_declaration(&mut self, frontend: &mut Frontend, start: Span) {
+        if self.last_meta == start {
+            self.declaration_end = false;
+        }
+
+        while !self.declaration_end {
+            if self.next(frontend).is_none() {
+                return;
+            }
+        }
+
+        // Declarations like blocks and structs end with a semicolon after the brace
+        if self.bump_if(frontend, TokenValue::Semicolon).is_none() {
+            self.declaration_end = false;
+        }
+    }
+
     pub fn bump(&mut self, frontend: &mut Frontend) -> Result<Token> {
         self.next(frontend).ok_or(Error {
             kind: ErrorKind::EndOfFile,
@@ -168,8 +211,35 @@ impl<'source> ParsingContext<'source> {
         // Body and expression arena for global initialization
         let mut ctx = Context::new(frontend, &mut module, false)?;
 
+        // The function emitting the vertices of geometry shaders is filled in
+        // with the entry point, but it must come before every function that
+        // calls it.
+        if frontend.meta.stage == ShaderStage::Geometry {
+            frontend.emit_vertex = Some(ctx.module.functions.append(
+                crate::Function {
+                    name: Some("EmitVertex".to_string()),
+                    ..Default::default()
+                },
+                Span::default(),
+            ));
+        }
+
+        let mut failed = false;
         while self.peek(frontend).is_some() {
-            self.parse_external_declaration(frontend, &mut ctx)?;
+            // Errors are recorded and parsing resumes at the next declaration, so
+            // that all of them can be reported at once.
+            let start = self.last_meta;
+            if let Err(e) = self.parse_external_declaration(frontend, &mut ctx) {
+                frontend.errors.push(e);
+                self.skip_declaration(frontend, start);
+                failed = true;
+            }
+        }
+
+        // The entry point might have been one of the declarations that failed,
+        // so don't report it as missing.
+        if failed {
+            return Ok(module);
         }
 
         // Add an `EntryPoint` to `parser.module` for `main`, if a
@@ -245,48 +315,73 @@ impl Frontend {
                     })
                 }
 
-                match tokens.next() {
+                let version = match tokens.next() {
                     Some(PPToken {
                         value: PPTokenValue::Integer(int),
                         location,
-                    }) => match int.value {
-                        440 | 450 | 460 => self.meta.version = int.value as u16,
-                        _ => self.errors.push(Error {
-                            kind: ErrorKind::InvalidVersion(int.value),
+                    }) => Some((int.value, location)),
+                    Some(PPToken { value, location }) => {
+                        self.errors.push(Error {
+                            kind: ErrorKind::PreprocessorError(PreprocessorError::UnexpectedToken(
+                                value,
+                            )),
                             meta: location.into(),
-                        }),
-                    },
-                    Some(PPToken { value, location }) => self.errors.push(Error {
-                        kind: ErrorKind::PreprocessorError(PreprocessorError::UnexpectedToken(
-                            value,
-                        )),
-                        meta: location.into(),
-                    }),
-                    None => self.errors.push(Error {
-                        kind: ErrorKind::PreprocessorError(PreprocessorError::UnexpectedNewLine),
-                        meta,
-                    }),
+                        });
+                        None
+                    }
+                    None => {
+                        self.errors.push(Error {
+                            kind: ErrorKind::PreprocessorError(
+                                PreprocessorError::UnexpectedNewLine,
+                            ),
+                            meta,
+                        });
+                        None
+                    }
                 };
 
-                match tokens.next() {
+                let profile = match tokens.next() {
                     Some(PPToken {
                         value: PPTokenValue::Ident(name),
                         location,
                     }) => match name.as_str() {
-                        "core" => self.meta.profile = Profile::Core,
+                        "core" => Profile::Core,
+                        "compatibility" => Profile::Compatibility,
+                        "es" => Profile::Es,
+                        _ => {
+                            self.errors.push(Error {
+                                kind: ErrorKind::InvalidProfile(name),
+                                meta: location.into(),
+                            });
+                            Profile::Core
+                        }
+                    },
+                    Some(PPToken { value, location }) => {
+                        self.errors.push(Error {
+                            kind: ErrorKind::PreprocessorError(PreprocessorError::UnexpectedToken(
+                                value,
+                            )),
+                            meta: location.into(),
+                        });
+                        Profile::Core
+                    }
+                    None => Profile::Core,
+                };
+                self.meta.profile = profile;
+
+                // The supported versions depend on the profile
+                if let Some((version, location)) = version {
+                    match (profile, version) {
+                        (Profile::Es, 300 | 310 | 320)
+                        | (Profile::Core | Profile::Compatibility, 440 | 450 | 460) => {
+                            self.meta.version = version as u16
+                        }
                         _ => self.errors.push(Error {
-                            kind: ErrorKind::InvalidProfile(name),
+                            kind: ErrorKind::InvalidVersion(version),
                             meta: location.into(),
                         }),
-                    },
-                    Some(PPToken { value, location }) => self.errors.push(Error {
-                        kind: ErrorKind::PreprocessorError(PreprocessorError::UnexpectedToken(
-                            value,
-                        )),
-                        meta: location.into(),
-                    }),
-                    None => {}
-                };
+                    }
+                }
 
                 if let Some(PPToken { value, location }) = tokens.next() {
                     self.errors.push(Error {
@@ -13,8 +13,8 @@ use crate::{
         Error, ErrorKind, Frontend, Span,
     },
     proc::Alignment,
-    AddressSpace, Expression, FunctionResult, Handle, Scalar, ScalarKind, Statement, StructMember,
-    Type, TypeInner,
+    AddressSpace, Expression, FunctionResult, Handle, Scalar, ScalarKind, ShaderStage, Statement,
+    StructMember, Type, TypeInner,
 };
 
 use super::{DeclarationContext, ParsingContext, Result};
@@ -445,25 +445,35 @@ impl<'source> ParsingContext<'source> {
                         }
                     }
                     TokenValue::Semicolon => {
-                        if let Some(value) =
-                            qualifiers.uint_layout_qualifier("local_size_x", &mut frontend.errors)
-                        {
-                            frontend.meta.workgroup_size[0] = value;
-                        }
-                        if let Some(value) =
-                            qualifiers.uint_layout_qualifier("local_size_y", &mut frontend.errors)
-                        {
-                            frontend.meta.workgroup_size[1] = value;
-                        }
-                        if let Some(value) =
-                            qualifiers.uint_layout_qualifier("local_size_z", &mut frontend.errors)
-                        {
-                            frontend.meta.workgroup_size[2] = value;
+                        // The workgroup size qualifiers are left in place for
+                        // other stages so that they are reported as unexpected.
+                        if frontend.meta.stage == ShaderStage::Compute {
+                            for (i, name) in ["local_size_x", "local_size_y", "local_size_z"]
+                                .into_iter()
+                                .enumerate()
+                            {
+                                if let Some(value) =
+                                    qualifiers.uint_layout_qualifier(name, &mut frontend.errors)
+                                {
+                                    frontend.meta.workgroup_size[i] = value;
+                                }
+                            }
                         }
 
                         frontend.meta.early_fragment_tests |= qualifiers
                             .none_layout_qualifier("early_fragment_tests", &mut frontend.errors);
 
+                        match frontend.meta.stage {
+                            ShaderStage::Geometry => {
+                                geometry_layout_qualifiers(frontend, &mut qualifiers)
+                            }
+                            ShaderStage::TessellationControl
+                            | ShaderStage::TessellationEvaluation => {
+                                tessellation_layout_qualifiers(frontend, &mut qualifiers)
+                            }
+                            _ => {}
+                        }
+
                         qualifiers.unused_errors(&mut frontend.errors);
 
                         Ok(Some(qualifiers.span))
@@ -536,9 +546,16 @@ impl<'source> ParsingContext<'source> {
         let layout = match qualifiers.layout_qualifiers.remove(&QualifierKey::Layout) {
             Some((QualifierValue::Layout(l), _)) => l,
             None => {
-                if let StorageQualifier::AddressSpace(AddressSpace::Storage { .. }) =
-                    qualifiers.storage.0
-                {
+                // Push constant blocks use the same rules as storage blocks
+                let storage = matches!(
+                    qualifiers.storage.0,
+                    StorageQualifier::AddressSpace(AddressSpace::Storage { .. })
+                );
+                let push_constant = qualifiers
+                    .layout_qualifiers
+                    .contains_key(&QualifierKey::String("push_constant".into()));
+
+                if storage || push_constant {
                     StructLayout::Std430
                 } else {
                     StructLayout::Std140
@@ -626,7 +643,21 @@ impl<'source> ParsingContext<'source> {
         let mut align = Alignment::ONE;
 
         loop {
-            // TODO: type_qualifier
+            let mut qualifiers = self.parse_type_qualifiers(frontend, ctx)?;
+            let offset_meta = qualifiers
+                .layout_qualifiers
+                .get(&QualifierKey::String("offset".into()))
+                .map(|&(_, meta)| meta);
+            let mut explicit_offset = qualifiers
+                .uint_layout_qualifier("offset", &mut frontend.errors)
+                .zip(offset_meta);
+            if qualifiers.storage.0 != StorageQualifier::default() {
+                frontend.errors.push(Error {
+                    kind: ErrorKind::SemanticError("Members can't have storage qualifiers".into()),
+                    meta: qualifiers.storage.1,
+                });
+            }
+            qualifiers.unused_errors(&mut frontend.errors);
 
             let (base_ty, mut meta) = self.parse_type_non_void(frontend, ctx)?;
 
@@ -649,6 +680,30 @@ impl<'source> ParsingContext<'source> {
                 span = member_alignment.round_up(span);
                 align = member_alignment.max(align);
 
+                // The offset qualifier only applies to the first declarator
+                if let Some((offset, offset_meta)) = explicit_offset.take() {
+                    if !member_alignment.is_aligned(offset) {
+                        frontend.errors.push(Error {
+                            kind: ErrorKind::SemanticError(
+                                format!(
+                                    "Offset {offset} isn't a multiple of the member's alignment"
+                                )
+                                .into(),
+                            ),
+                            meta: offset_meta,
+                        });
+                    } else if offset < span {
+                        frontend.errors.push(Error {
+                            kind: ErrorKind::SemanticError(
+                                format!("Offset {offset} overlaps the previous members").into(),
+                            ),
+                            meta: offset_meta,
+                        });
+                    } else {
+                        span = offset;
+                    }
+                }
+
                 members.push(StructMember {
                     name: Some(name),
                     ty: info.ty,
@@ -675,3 +730,94 @@ impl<'source> ParsingContext<'source> {
         Ok(span)
     }
 }
+
+/// Consumes the layout qualifiers of the primitives of geometry shaders from
+/// an `in;` or `out;` declaration, storing them in the [`ShaderMetadata`].
+///
+/// [`ShaderMetadata`]: crate::front::glsl::ShaderMetadata
+fn geometry_layout_qualifiers(frontend: &mut Frontend, qualifiers: &mut TypeQualifiers) {
+    use crate::{GeometryInputPrimitive as Gip, GeometryOutputPrimitive as Gop};
+
+    match qualifiers.storage.0 {
+        StorageQualifier::Input => {
+            for (name, primitive) in [
+                ("points", Gip::Points),
+                ("lines", Gip::Lines),
+                ("lines_adjacency", Gip::LinesAdjacency),
+                ("triangles", Gip::Triangles),
+                ("triangles_adjacency", Gip::TrianglesAdjacency),
+            ] {
+                if qualifiers.none_layout_qualifier(name, &mut frontend.errors) {
+                    frontend.meta.geometry_input_primitive = Some(primitive);
+                }
+            }
+            if let Some(value) =
+                qualifiers.uint_layout_qualifier("invocations", &mut frontend.errors)
+            {
+                frontend.meta.geometry_invocations = value;
+            }
+        }
+        StorageQualifier::Output => {
+            for (name, primitive) in [
+                ("points", Gop::Points),
+                ("line_strip", Gop::LineStrip),
+                ("triangle_strip", Gop::TriangleStrip),
+            ] {
+                if qualifiers.none_layout_qualifier(name, &mut frontend.errors) {
+                    frontend.meta.geometry_output_primitive = Some(primitive);
+                }
+            }
+            if let Some(value) =
+                qualifiers.uint_layout_qualifier("max_vertices", &mut frontend.errors)
+            {
+                frontend.meta.geometry_max_vertices = value;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Consumes the layout qualifiers of the patches of tessellation shaders from
+/// an `in;` or `out;` declaration, storing them in the [`ShaderMetadata`].
+///
+/// [`ShaderMetadata`]: crate::front::glsl::ShaderMetadata
+fn tessellation_layout_qualifiers(frontend: &mut Frontend, qualifiers: &mut TypeQualifiers) {
+    use crate::{TessellationPrimitive as Tp, TessellationSpacing as Ts};
+
+    match (frontend.meta.stage, qualifiers.storage.0) {
+        (ShaderStage::TessellationControl, StorageQualifier::Output) => {
+            if let Some(value) = qualifiers.uint_layout_qualifier("vertices", &mut frontend.errors)
+            {
+                frontend.meta.tessellation_output_vertices = value;
+            }
+        }
+        (ShaderStage::TessellationEvaluation, StorageQualifier::Input) => {
+            for (name, primitive) in [
+                ("triangles", Tp::Triangles),
+                ("quads", Tp::Quads),
+                ("isolines", Tp::Isolines),
+            ] {
+                if qualifiers.none_layout_qualifier(name, &mut frontend.errors) {
+                    frontend.meta.tessellation_primitive = Some(primitive);
+                }
+            }
+            for (name, spacing) in [
+                ("equal_spacing", Ts::Equal),
+                ("fractional_even_spacing", Ts::FractionalEven),
+                ("fractional_odd_spacing", Ts::FractionalOdd),
+            ] {
+                if qualifiers.none_layout_qualifier(name, &mut frontend.errors) {
+                    frontend.meta.tessellation_spacing = spacing;
+                }
+            }
+            for (name, clockwise) in [("cw", true), ("ccw", false)] {
+                if qualifiers.none_layout_qualifier(name, &mut frontend.errors) {
+                    frontend.meta.tessellation_clockwise = clockwise;
+                }
+            }
+            frontend.meta.tessellation_point_mode |=
+                qualifiers.none_layout_qualifier("point_mode", &mut frontend.errors);
+        }
+        _ => {}
+    }
+}
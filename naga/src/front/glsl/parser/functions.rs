@@ -113,6 +113,14 @@ impl<'source> ParsingContext<'source> {
             }
             TokenValue::Discard => {
                 let meta = self.bump(frontend)?.meta;
+                if frontend.meta.stage != crate::ShaderStage::Fragment {
+                    frontend.errors.push(Error {
+                        kind: ErrorKind::SemanticError(
+                            "discard is only available in fragment shaders".into(),
+                        ),
+                        meta,
+                    });
+                }
                 ctx.body.push(Statement::Kill, meta);
                 terminator.get_or_insert(ctx.body.len());
 
@@ -149,6 +149,7 @@ impl<'source> ParsingContext<'source> {
     pub fn peek_type_qualifier(&mut self, frontend: &mut Frontend) -> bool {
         self.peek(frontend).map_or(false, |t| match t.value {
             TokenValue::Invariant
+            | TokenValue::Patch
             | TokenValue::Interpolation(_)
             | TokenValue::Sampling(_)
             | TokenValue::PrecisionQualifier(_)
@@ -197,6 +198,18 @@ impl<'source> ParsingContext<'source> {
 
                     qualifiers.invariant = Some(token.meta);
                 }
+                TokenValue::Patch => {
+                    if qualifiers.patch.is_some() {
+                        frontend.errors.push(Error {
+                            kind: ErrorKind::SemanticError(
+                                "Cannot use more than one patch qualifier per declaration".into(),
+                            ),
+                            meta: token.meta,
+                        })
+                    }
+
+                    qualifiers.patch = Some(token.meta);
+                }
                 TokenValue::Interpolation(i) => {
                     if qualifiers.interpolation.is_some() {
                         frontend.errors.push(Error {
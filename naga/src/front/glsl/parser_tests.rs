@@ -3,7 +3,7 @@ use super::{
     error::ExpectedToken,
     error::{Error, ErrorKind, ParseError},
     token::TokenValue,
-    Frontend, Options, Span,
+    validate_interface, Frontend, IncludeResolver, InterfaceError, Options, Span,
 };
 use crate::ShaderStage;
 use pp_rs::token::PreprocessorError;
@@ -517,6 +517,69 @@ fn functions() {
         .unwrap();
 }
 
+#[test]
+fn function_call_write_back() {
+    use crate::{Block, Expression, Function, Statement};
+
+    fn stores_after_call(body: &Block) -> usize {
+        body.iter()
+            .skip_while(|stmt| !matches!(**stmt, Statement::Call { .. }))
+            .filter(|stmt| matches!(**stmt, Statement::Store { .. }))
+            .count()
+    }
+
+    fn find_function<'a>(module: &'a crate::Module, name: &str) -> &'a Function {
+        module
+            .functions
+            .iter()
+            .find(|(_, function)| function.name.as_deref() == Some(name))
+            .unwrap()
+            .1
+    }
+
+    let mut frontend = Frontend::default();
+
+    // Arguments that can't be passed by pointer directly are spilled into a
+    // local and written back to their original place after the call.
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void scale(inout vec2 v, float factor) { v *= factor; }
+        void split(vec2 v, out int x, out int y) { x = int(v.x); y = int(v.y); }
+
+        void swizzle() {
+            vec4 a = vec4(1.0);
+            scale(a.zy, 2);
+        }
+
+        void convert() {
+            float x, y;
+            split(vec2(1.0, 2.0), x, y);
+        }
+
+        void main() {
+            swizzle();
+            convert();
+        }
+        "#,
+        )
+        .unwrap();
+
+    // One store for each component of the swizzle.
+    let function = find_function(&module, "swizzle");
+    assert_eq!(stores_after_call(&function.body), 2);
+
+    // One store for each converted output argument.
+    let function = find_function(&module, "convert");
+    assert_eq!(stores_after_call(&function.body), 2);
+    assert!(function
+        .expressions
+        .iter()
+        .any(|(_, expr)| matches!(*expr, Expression::As { .. })));
+}
+
 #[test]
 fn constants() {
     use crate::{Constant, Expression, Type, TypeInner};
@@ -856,3 +919,1601 @@ fn expressions() {
         )
         .unwrap();
 }
+
+#[test]
+fn vertex_builtins() {
+    use crate::{Binding, BuiltIn, TypeInner};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void main() {
+            float x = float(gl_VertexIndex);
+            float y = float(gl_InstanceIndex);
+            gl_Position = vec4(x, y, 0.0, 1.0);
+            gl_Position.z = 0.5;
+        }
+        "#,
+        )
+        .unwrap();
+
+    // Builtins are only declared once, no matter how often they're used.
+    let globals: Vec<_> = module
+        .global_variables
+        .iter()
+        .filter_map(|(_, var)| var.name.as_deref())
+        .collect();
+    assert_eq!(
+        globals,
+        ["gl_VertexIndex", "gl_InstanceIndex", "gl_Position"]
+    );
+
+    let entry_point = &module.entry_points[0].function;
+    let inputs: Vec<_> = entry_point
+        .arguments
+        .iter()
+        .map(|arg| arg.binding.clone())
+        .collect();
+    assert_eq!(
+        inputs,
+        [
+            Some(Binding::BuiltIn(BuiltIn::VertexIndex)),
+            Some(Binding::BuiltIn(BuiltIn::InstanceIndex)),
+        ]
+    );
+
+    let result = entry_point.result.as_ref().unwrap();
+    match module.types[result.ty].inner {
+        TypeInner::Struct { ref members, .. } => assert_eq!(
+            members[0].binding,
+            Some(Binding::BuiltIn(BuiltIn::Position { invariant: false }))
+        ),
+        ref other => panic!("unexpected entry point result {other:?}"),
+    }
+}
+
+#[test]
+fn fragment_builtins() {
+    use crate::{Binding, BuiltIn};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #  version 450
+        layout(location = 0) out vec4 o_color;
+
+        void main() {
+            vec2 coord = gl_FrontFacing ? gl_FragCoord.xy : gl_PointCoord;
+            gl_FragDepth = 0.5;
+            o_color = vec4(coord, 0.0, 1.0);
+        }
+        "#,
+        )
+        .unwrap();
+
+    let inputs: Vec<_> = module.entry_points[0]
+        .function
+        .arguments
+        .iter()
+        .map(|arg| arg.binding.clone())
+        .collect();
+    assert_eq!(
+        inputs,
+        [
+            Some(Binding::BuiltIn(BuiltIn::FrontFacing)),
+            Some(Binding::BuiltIn(BuiltIn::Position { invariant: false })),
+            Some(Binding::BuiltIn(BuiltIn::PointCoord)),
+        ]
+    );
+
+    // Fragment builtins can't be used by other stages.
+    let errors = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void main() {
+            gl_Position = gl_FragCoord;
+            gl_FragDepth = 0.5;
+        }
+        "#,
+        )
+        .err()
+        .unwrap()
+        .errors;
+    let kinds: Vec<_> = errors.into_iter().map(|error| error.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ErrorKind::SemanticError("gl_FragCoord is only available in fragment shaders".into()),
+            ErrorKind::SemanticError("gl_FragDepth is only available in fragment shaders".into()),
+        ]
+    );
+}
+
+#[test]
+fn compute_shaders() {
+    use crate::{Binding, BuiltIn};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Compute),
+            r#"
+        #  version 450
+        layout(local_size_x = 8, local_size_y = 4) in;
+
+        layout(std430, set = 0, binding = 0) buffer Data {
+            uint data[];
+        };
+
+        void main() {
+            data[gl_GlobalInvocationID.x] = gl_LocalInvocationID.y + gl_WorkGroupID.z;
+        }
+        "#,
+        )
+        .unwrap();
+
+    let entry_point = &module.entry_points[0];
+    assert_eq!(entry_point.stage, ShaderStage::Compute);
+    assert_eq!(entry_point.workgroup_size, [8, 4, 1]);
+    let inputs: Vec<_> = entry_point
+        .function
+        .arguments
+        .iter()
+        .map(|arg| arg.binding.clone())
+        .collect();
+    assert_eq!(
+        inputs,
+        [
+            Some(Binding::BuiltIn(BuiltIn::GlobalInvocationId)),
+            Some(Binding::BuiltIn(BuiltIn::LocalInvocationId)),
+            Some(Binding::BuiltIn(BuiltIn::WorkGroupId)),
+        ]
+    );
+
+    // The workgroup size can only be declared by compute shaders.
+    let errors = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        layout(local_size_x = 8) in;
+
+        void main() {}
+        "#,
+        )
+        .err()
+        .unwrap()
+        .errors;
+    let kinds: Vec<_> = errors.into_iter().map(|error| error.kind).collect();
+    assert_eq!(
+        kinds,
+        [ErrorKind::SemanticError("Unexpected qualifier".into())]
+    );
+}
+
+#[test]
+fn includes() {
+    let mut frontend = Frontend::default();
+
+    let options = Options {
+        include_resolver: Some(IncludeResolver::new(|path| match path {
+            "common.glsl" => {
+                Some("#include <constants.glsl>\nfloat twice(float v) { return v * TWO; }".into())
+            }
+            "constants.glsl" => Some("#define TWO 2.0".into()),
+            "recursive.glsl" => Some("#include \"recursive.glsl\"".into()),
+            _ => None,
+        })),
+        ..Options::from(ShaderStage::Vertex)
+    };
+
+    frontend
+        .parse(
+            &options,
+            r#"
+        #  version 450
+        #include "common.glsl"
+
+        void main() {
+            float a = twice(TWO);
+        }
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        frontend
+            .parse(
+                &options,
+                "#version 450\n#include \"missing.glsl\"\nvoid main() {}"
+            )
+            .err()
+            .unwrap(),
+        ParseError {
+            errors: vec![Error {
+                kind: ErrorKind::SemanticError("Couldn't resolve include \"missing.glsl\"".into()),
+                meta: Span::new(13, 36),
+            }],
+        },
+    );
+
+    assert_eq!(
+        frontend
+            .parse(
+                &options,
+                "#version 450\n#include \"recursive.glsl\"\nvoid main() {}"
+            )
+            .err()
+            .unwrap(),
+        ParseError {
+            errors: vec![Error {
+                kind: ErrorKind::SemanticError("Recursive include of \"recursive.glsl\"".into()),
+                meta: Span::new(13, 38),
+            }],
+        },
+    );
+}
+
+#[test]
+fn control_flow_lowering() {
+    use crate::{Block, Statement};
+
+    fn kinds(body: &Block) -> Vec<&'static str> {
+        body.iter()
+            .filter_map(|stmt| match *stmt {
+                Statement::If { .. } => Some("if"),
+                Statement::Loop { .. } => Some("loop"),
+                Statement::Switch { .. } => Some("switch"),
+                Statement::Kill => Some("kill"),
+                _ => None,
+            })
+            .collect()
+    }
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #  version 450
+        void main() {
+            int x = 0;
+            for (int i = 0; i < 4; i++) {
+                if (i == 2) {
+                    continue;
+                }
+                x += i;
+            }
+            while (x > 0) {
+                x--;
+            }
+            do {
+                x++;
+            } while (x < 2);
+            switch (x) {
+                case 0:
+                    x = 1;
+                case 1:
+                    break;
+                default:
+                    discard;
+            }
+            if (x == 3) {
+                discard;
+            } else {
+                return;
+            }
+        }
+        "#,
+        )
+        .unwrap();
+
+    let main = module
+        .functions
+        .iter()
+        .find(|(_, function)| function.name.as_deref() == Some("main"))
+        .unwrap()
+        .1;
+    assert_eq!(kinds(&main.body), ["loop", "loop", "loop", "switch", "if"]);
+
+    let Statement::Switch { ref cases, .. } = *main
+        .body
+        .iter()
+        .find(|stmt| matches!(**stmt, Statement::Switch { .. }))
+        .unwrap()
+    else {
+        unreachable!()
+    };
+    let fall_through: Vec<_> = cases.iter().map(|case| case.fall_through).collect();
+    assert_eq!(fall_through, [true, false, false]);
+    assert_eq!(kinds(&cases[2].body), ["kill"]);
+
+    // Only fragment shaders can discard.
+    let errors = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void main() {
+            discard;
+        }
+        "#,
+        )
+        .err()
+        .unwrap()
+        .errors;
+    let kinds: Vec<_> = errors.into_iter().map(|error| error.kind).collect();
+    assert_eq!(
+        kinds,
+        [ErrorKind::SemanticError(
+            "discard is only available in fragment shaders".into()
+        )]
+    );
+}
+
+#[test]
+fn block_layouts() {
+    use crate::TypeInner;
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        struct Pair {
+            float a;
+            float b;
+        };
+
+        layout(std140, set = 0, binding = 0) uniform Std140Block {
+            Pair pair;
+            float scalars[2];
+            float after;
+        };
+
+        layout(std430, set = 0, binding = 1) buffer Std430Block {
+            Pair pair;
+            float scalars[2];
+            float after;
+        } std430_block;
+
+        void main() {
+            float a = pair.a + after + std430_block.pair.b + std430_block.after;
+        }
+        "#,
+        )
+        .unwrap();
+
+    let offsets = |name: &str| -> (Vec<u32>, u32) {
+        let ty = module
+            .types
+            .iter()
+            .find(|(_, ty)| ty.name.as_deref() == Some(name))
+            .unwrap()
+            .1;
+        match ty.inner {
+            TypeInner::Struct {
+                ref members, span, ..
+            } => (members.iter().map(|member| member.offset).collect(), span),
+            ref other => panic!("unexpected block type {other:?}"),
+        }
+    };
+
+    // Structures and arrays are aligned to 16 bytes with std140, and arrays of
+    // scalars have a stride of 16 bytes.
+    assert_eq!(offsets("Std140Block"), (vec![0, 16, 48], 64));
+    assert_eq!(offsets("Std430Block"), (vec![0, 8, 16], 20));
+}
+
+#[test]
+fn arrays() {
+    use crate::{Expression, Literal};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        layout(std430, set = 0, binding = 0) buffer Data {
+            float values[];
+        };
+
+        void main() {
+            float sized[5] = float[5](1.0, 2.0, 3.0, 4.0, 5.0);
+            int runtime = values.length();
+            int array = sized.length();
+            int vector = vec3(1.0).length();
+            int matrix = mat4x2(1.0).length();
+        }
+        "#,
+        )
+        .unwrap();
+
+    let main = module
+        .functions
+        .iter()
+        .find(|(_, function)| function.name.as_deref() == Some("main"))
+        .unwrap()
+        .1;
+    let expressions: Vec<_> = main.expressions.iter().map(|(_, expr)| expr).collect();
+
+    assert!(expressions
+        .iter()
+        .any(|expr| matches!(**expr, Expression::ArrayLength(_))));
+    // The lengths of vectors and matrices are known statically.
+    assert!(expressions.contains(&&Expression::Literal(Literal::I32(3))));
+    assert!(expressions.contains(&&Expression::Literal(Literal::I32(4))));
+}
+
+#[test]
+fn constructors() {
+    let mut frontend = Frontend::default();
+
+    frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void main() {
+            vec4 a = vec4(vec2(1.0), 2, 3u);
+            vec3 b = vec3(a);
+            ivec2 c = ivec2(b);
+            mat3 d = mat3(mat2(2.0));
+            mat2 e = mat2(a.xyz, b);
+            mat2x3 f = mat2x3(a.xyz, 1, 2, 3);
+            vec4 g = vec4(e);
+        }
+        "#,
+        )
+        .unwrap();
+
+    let errors = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void main() {
+            vec4 a = vec4(1.0, 2.0);
+            mat2 b = mat2(1.0, 2.0, 3.0);
+        }
+        "#,
+        )
+        .err()
+        .unwrap()
+        .errors;
+    let kinds: Vec<_> = errors.into_iter().map(|error| error.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ErrorKind::SemanticError("Not enough data provided to vector constructor".into()),
+            ErrorKind::SemanticError("Not enough data provided to matrix constructor".into()),
+        ]
+    );
+}
+
+#[test]
+fn texture_queries() {
+    use crate::{Expression, ImageQuery};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #  version 450
+        layout(set = 0, binding = 0) uniform texture2D tex;
+        layout(set = 0, binding = 1) uniform texture2DMS tex_ms;
+        layout(set = 0, binding = 2) uniform sampler samp;
+
+        void main() {
+            int levels = textureQueryLevels(sampler2D(tex, samp));
+            int samples = textureSamples(sampler2DMS(tex_ms, samp));
+        }
+        "#,
+        )
+        .unwrap();
+
+    let main = module
+        .functions
+        .iter()
+        .find(|(_, function)| function.name.as_deref() == Some("main"))
+        .unwrap()
+        .1;
+    let queries: Vec<_> = main
+        .expressions
+        .iter()
+        .filter_map(|(_, expr)| match *expr {
+            Expression::ImageQuery { query, .. } => Some(query),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(queries, [ImageQuery::NumLevels, ImageQuery::NumSamples]);
+
+    // Multisampled images don't have levels to query.
+    assert!(frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #  version 450
+        layout(set = 0, binding = 0) uniform texture2DMS tex_ms;
+        layout(set = 0, binding = 1) uniform sampler samp;
+
+        void main() {
+            int levels = textureQueryLevels(sampler2DMS(tex_ms, samp));
+        }
+        "#,
+        )
+        .is_err());
+}
+
+#[test]
+fn matrix_comp_mult() {
+    use crate::{BinaryOperator, Expression};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void main() {
+            mat2x3 a = mat2x3(1.0);
+            mat2x3 b = matrixCompMult(a, mat2x3(2.0));
+        }
+        "#,
+        )
+        .unwrap();
+
+    let main = module
+        .functions
+        .iter()
+        .find(|(_, function)| function.name.as_deref() == Some("main"))
+        .unwrap()
+        .1;
+
+    // Each column is multiplied separately.
+    let columns = main
+        .expressions
+        .iter()
+        .filter(|&(_, expr)| {
+            matches!(
+                *expr,
+                Expression::Binary {
+                    op: BinaryOperator::Multiply,
+                    ..
+                }
+            )
+        })
+        .count();
+    assert_eq!(columns, 2);
+}
+
+#[test]
+fn swizzle_stores() {
+    use crate::{Expression, Statement};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void main() {
+            vec4 v = vec4(0.0);
+            v.zx = vec2(1.0, 2.0);
+            v.yw += vec2(3.0);
+        }
+        "#,
+        )
+        .unwrap();
+
+    let main = module
+        .functions
+        .iter()
+        .find(|(_, function)| function.name.as_deref() == Some("main"))
+        .unwrap()
+        .1;
+
+    // Stores to swizzles are split into a store per component, from the
+    // component of the value at the same position in the swizzle.
+    let stores: Vec<_> = main
+        .body
+        .iter()
+        .filter_map(|stmt| match *stmt {
+            Statement::Store { pointer, value } => {
+                match (&main.expressions[pointer], &main.expressions[value]) {
+                    (
+                        &Expression::AccessIndex { index: dst, .. },
+                        &Expression::AccessIndex { index: src, .. },
+                    ) => Some((dst, src)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(stores, [(2, 0), (0, 1), (1, 0), (3, 1)]);
+}
+
+#[test]
+fn increment_decrement_results() {
+    use crate::{BinaryOperator, Expression, Statement};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void main() {
+            int a = 1;
+            int post = a++;
+            int pre = --a;
+            a <<= 2;
+            a %= 3;
+        }
+        "#,
+        )
+        .unwrap();
+
+    let main = module
+        .functions
+        .iter()
+        .find(|(_, function)| function.name.as_deref() == Some("main"))
+        .unwrap()
+        .1;
+    let stored = |name: &str| {
+        main.body
+            .iter()
+            .find_map(|stmt| match *stmt {
+                Statement::Store { pointer, value } => match main.expressions[pointer] {
+                    Expression::LocalVariable(var)
+                        if main.local_variables[var].name.as_deref() == Some(name) =>
+                    {
+                        Some(&main.expressions[value])
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .unwrap()
+    };
+
+    // Postfix operators evaluate to the value before the update, prefix
+    // operators to the updated value.
+    assert!(matches!(*stored("post"), Expression::Load { .. }));
+    assert!(matches!(
+        *stored("pre"),
+        Expression::Binary {
+            op: BinaryOperator::Subtract,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn geometry_builtins() {
+    use crate::{GeometryInputPrimitive, GeometryOutputPrimitive, Statement};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Geometry),
+            r#"
+        #  version 450
+        layout(triangles, invocations = 2) in;
+        layout(line_strip, max_vertices = 4) out;
+
+        layout(location = 0) in vec2 uv[];
+        layout(location = 0) out vec2 v_uv;
+
+        void main() {
+            for (int i = 0; i < 3; i++) {
+                gl_Position = gl_in[i].gl_Position;
+                v_uv = uv[i];
+                EmitVertex();
+            }
+            EndPrimitive();
+        }
+        "#,
+        )
+        .unwrap();
+
+    let entry_point = &module.entry_points[0];
+    assert_eq!(entry_point.stage, ShaderStage::Geometry);
+    assert!(entry_point.function.result.is_none());
+    let info = entry_point.geometry_info.as_ref().unwrap();
+    assert_eq!(info.input_primitive, GeometryInputPrimitive::Triangles);
+    assert_eq!(info.output_primitive, GeometryOutputPrimitive::LineStrip);
+    assert_eq!((info.max_vertices, info.invocations), (4, 2));
+
+    // `EmitVertex()` calls the function emitting the outputs
+    let (_, emit_vertex) = module
+        .functions
+        .iter()
+        .find(|&(_, function)| function.name.as_deref() == Some("EmitVertex"))
+        .unwrap();
+    assert!(emit_vertex
+        .body
+        .iter()
+        .any(|statement| matches!(*statement, Statement::EmitVertex { .. })));
+
+    crate::valid::Validator::new(
+        crate::valid::ValidationFlags::all(),
+        crate::valid::Capabilities::GEOMETRY_SHADER,
+    )
+    .validate(&module)
+    .unwrap();
+
+    // Only geometry shaders can emit vertices
+    let errors = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        void main() {
+            gl_Position = vec4(0.0);
+            EmitVertex();
+        }
+        "#,
+        )
+        .err()
+        .unwrap()
+        .errors;
+    assert!(matches!(errors[0].kind, ErrorKind::SemanticError(_)));
+    assert!(frontend
+        .parse(
+            &Options::from(ShaderStage::Geometry),
+            r#"
+        #  version 450
+        void main() {
+            EmitStreamVertex(0);
+        }
+        "#,
+        )
+        .is_err());
+}
+
+#[test]
+fn subgroup_builtins() {
+    use crate::{CollectiveOperation, Statement, SubgroupOperation};
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Compute),
+            r#"
+        #  version 450
+        #extension GL_KHR_shader_subgroup_arithmetic : require
+        #extension GL_KHR_shader_subgroup_ballot : require
+        #extension GL_KHR_shader_subgroup_shuffle : require
+        layout(local_size_x = 64) in;
+
+        layout(std430, binding = 0) buffer Data {
+            float values[];
+        };
+
+        void main() {
+            uint id = gl_SubgroupInvocationID;
+            uvec4 ballot = subgroupBallot(id < gl_SubgroupSize / 2u);
+            float sum = subgroupAdd(values[id]);
+            float prefix = subgroupExclusiveAdd(values[id]);
+            float first = subgroupBroadcastFirst(sum);
+            float other = subgroupShuffleXor(prefix, 1u);
+            subgroupBarrier();
+            values[gl_SubgroupID * gl_NumSubgroups + id] = sum + prefix + first + other;
+        }
+        "#,
+        )
+        .unwrap();
+
+    let body = &module.entry_points[0].function.body;
+    assert!(body
+        .iter()
+        .any(|statement| matches!(*statement, Statement::SubgroupBallot { .. })));
+    assert!(body.iter().any(|statement| matches!(
+        *statement,
+        Statement::SubgroupCollectiveOperation {
+            op: SubgroupOperation::Add,
+            collective_op: CollectiveOperation::ExclusiveScan,
+            ..
+        }
+    )));
+    assert!(body
+        .iter()
+        .any(|statement| matches!(*statement, Statement::SubgroupGather { .. })));
+
+    crate::valid::Validator::new(
+        crate::valid::ValidationFlags::all(),
+        crate::valid::Capabilities::SUBGROUP | crate::valid::Capabilities::SUBGROUP_BARRIER,
+    )
+    .validate(&module)
+    .unwrap();
+
+    let errors = frontend
+        .parse(
+            &Options::from(ShaderStage::Compute),
+            r#"
+        #  version 450
+        layout(local_size_x = 64) in;
+
+        void main() {
+            float m = subgroupInclusiveMax(1.0);
+        }
+        "#,
+        )
+        .err()
+        .unwrap()
+        .errors;
+    let kinds: Vec<_> = errors.into_iter().map(|error| error.kind).collect();
+    assert_eq!(
+        kinds,
+        [ErrorKind::NotImplemented(
+            "subgroup scans other than additions and multiplications"
+        )]
+    );
+}
+
+#[test]
+fn tessellation_builtins() {
+    use crate::{
+        Binding, TessellationPrimitive, TessellationSpacing, TessellationStageInfo, TypeInner,
+    };
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::TessellationControl),
+            r#"
+        #  version 450
+        layout(vertices = 4) out;
+
+        layout(location = 0) in vec2 uv[];
+        layout(location = 0) out vec2 v_uv[];
+        layout(location = 1) patch out vec4 color;
+
+        void main() {
+            gl_out[gl_InvocationID].gl_Position = gl_in[gl_InvocationID].gl_Position;
+            v_uv[gl_InvocationID] = uv[gl_InvocationID];
+            color = vec4(1.0);
+            gl_TessLevelOuter[0] = float(gl_PatchVerticesIn);
+            gl_TessLevelInner[0] = 2.0;
+        }
+        "#,
+        )
+        .unwrap();
+
+    let entry_point = &module.entry_points[0];
+    assert_eq!(entry_point.stage, ShaderStage::TessellationControl);
+    assert!(matches!(
+        entry_point.tessellation_info,
+        Some(TessellationStageInfo::Control { output_vertices: 4 })
+    ));
+    // The outputs of the patch are marked as such
+    let result = entry_point.function.result.as_ref().unwrap();
+    let TypeInner::Struct { ref members, .. } = module.types[result.ty].inner else {
+        panic!("expected the outputs in a struct");
+    };
+    assert!(members.iter().any(|member| matches!(
+        member.binding,
+        Some(Binding::Location {
+            location: 1,
+            patch: true,
+            ..
+        })
+    )));
+
+    crate::valid::Validator::new(
+        crate::valid::ValidationFlags::all(),
+        crate::valid::Capabilities::TESSELLATION_SHADER,
+    )
+    .validate(&module)
+    .unwrap();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::TessellationEvaluation),
+            r#"
+        #  version 450
+        layout(quads, fractional_even_spacing, cw, point_mode) in;
+
+        layout(location = 0) in vec2 v_uv[];
+        layout(location = 1) patch in vec4 color;
+        layout(location = 0) out vec4 frag_color;
+
+        void main() {
+            gl_Position = vec4(gl_TessCoord, gl_TessLevelOuter[0]);
+            frag_color = color * v_uv[0].x;
+        }
+        "#,
+        )
+        .unwrap();
+
+    assert!(matches!(
+        module.entry_points[0].tessellation_info,
+        Some(TessellationStageInfo::Evaluation {
+            primitive: TessellationPrimitive::Quads,
+            spacing: TessellationSpacing::FractionalEven,
+            clockwise: true,
+            point_mode: true,
+        })
+    ));
+
+    crate::valid::Validator::new(
+        crate::valid::ValidationFlags::all(),
+        crate::valid::Capabilities::TESSELLATION_SHADER,
+    )
+    .validate(&module)
+    .unwrap();
+
+    // Per-patch varyings only exist in tessellation shaders
+    assert!(frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        layout(location = 0) patch out vec4 color;
+
+        void main() {
+            color = vec4(1.0);
+        }
+        "#,
+        )
+        .is_err());
+    // Control shader outputs need the size of the output patch
+    assert!(frontend
+        .parse(
+            &Options::from(ShaderStage::TessellationControl),
+            r#"
+        #  version 450
+        layout(location = 0) out vec2 v_uv[];
+
+        void main() {}
+        "#,
+        )
+        .is_err());
+}
+
+#[test]
+fn profiles() {
+    let mut frontend = Frontend::default();
+
+    frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #version 310 es
+        precision mediump float;
+
+        layout(location = 0) out vec4 o_color;
+
+        void main() {
+            o_color = vec4(1.0);
+        }
+        "#,
+        )
+        .unwrap();
+    assert_eq!(
+        (frontend.metadata().version, frontend.metadata().profile),
+        (310, Profile::Es)
+    );
+
+    frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            "#version 450 compatibility\nvoid main() {}",
+        )
+        .unwrap();
+    assert_eq!(
+        (frontend.metadata().version, frontend.metadata().profile),
+        (450, Profile::Compatibility)
+    );
+
+    // ES and desktop versions can't be mixed.
+    assert_eq!(
+        frontend
+            .parse(
+                &Options::from(ShaderStage::Vertex),
+                "#version 450 es\nvoid main() {}",
+            )
+            .err()
+            .unwrap(),
+        ParseError {
+            errors: vec![Error {
+                kind: ErrorKind::InvalidVersion(450),
+                meta: Span::new(9, 12),
+            }],
+        },
+    );
+    assert_eq!(
+        frontend
+            .parse(
+                &Options::from(ShaderStage::Vertex),
+                "#version 310\nvoid main() {}",
+            )
+            .err()
+            .unwrap(),
+        ParseError {
+            errors: vec![Error {
+                kind: ErrorKind::InvalidVersion(310),
+                meta: Span::new(9, 12),
+            }],
+        },
+    );
+}
+
+#[test]
+fn constant_expressions() {
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #version 450
+        const int N = 4;
+        const int M = N > 2 ? N * 2 : 1;
+        const int L = max(N, 6) - min(N, 1);
+
+        float a[M];
+        float b[L];
+
+        void main() {
+            int x = 0;
+            switch (x) {
+                case N - 1:
+                    break;
+                case M:
+                    break;
+            }
+        }
+        "#,
+        )
+        .unwrap();
+
+    let array_size = |name: &str| {
+        let (_, global) = module
+            .global_variables
+            .iter()
+            .find(|(_, global)| global.name.as_deref() == Some(name))
+            .unwrap();
+        match module.types[global.ty].inner {
+            crate::TypeInner::Array {
+                size: crate::ArraySize::Constant(size),
+                ..
+            } => size.get(),
+            ref other => panic!("unexpected type {other:?}"),
+        }
+    };
+    assert_eq!(array_size("a"), 8);
+    assert_eq!(array_size("b"), 5);
+
+    let (_, main) = module
+        .functions
+        .iter()
+        .find(|(_, f)| f.name.as_deref() == Some("main"))
+        .unwrap();
+    let values: Vec<_> = main
+        .body
+        .iter()
+        .find_map(|stmt| match *stmt {
+            crate::Statement::Switch { ref cases, .. } => {
+                Some(cases.iter().map(|case| case.value).collect::<Vec<_>>())
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(
+        values,
+        [
+            crate::SwitchValue::I32(3),
+            crate::SwitchValue::I32(8),
+            crate::SwitchValue::Default
+        ]
+    );
+
+    // The condition of a constant ternary must still be a bool.
+    assert_eq!(
+        frontend
+            .parse(
+                &Options::from(ShaderStage::Vertex),
+                r#"
+        #version 450
+        const int N = 1 ? 1 : 2;
+        void main() {}
+        "#,
+            )
+            .err()
+            .unwrap()
+            .errors
+            .into_iter()
+            .map(|error| error.kind)
+            .collect::<Vec<_>>(),
+        [ErrorKind::SemanticError(
+            "Condition of a constant ternary expression must be a constant bool".into()
+        )]
+    );
+}
+
+#[test]
+fn expression_spans() {
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #version 450
+        layout(set = 0, binding = 0) uniform texture2D tex;
+        layout(set = 0, binding = 1) uniform sampler samp;
+        layout(location = 0) flat in int index;
+        layout(location = 0) out vec4 o_color;
+
+        const float weights[2] = float[2](0.25, 0.75);
+
+        void scale(inout vec2 v) {
+            v *= 2.0;
+        }
+
+        void main() {
+            vec4 color = textureProj(sampler2D(tex, samp), vec3(0.5, 0.5, 2.0));
+            scale(color.yx);
+            o_color = color * weights[index] + mod(color, 0.5);
+        }
+        "#,
+        )
+        .unwrap();
+
+    let (_, main) = module
+        .functions
+        .iter()
+        .find(|(_, f)| f.name.as_deref() == Some("main"))
+        .unwrap();
+    for (handle, expr) in main.expressions.iter() {
+        assert!(
+            main.expressions.get_span(handle).is_defined(),
+            "{expr:?} has no span"
+        );
+    }
+    for (handle, local) in main.local_variables.iter() {
+        assert!(
+            main.local_variables.get_span(handle).is_defined(),
+            "{local:?} has no span"
+        );
+    }
+}
+
+#[test]
+fn error_recovery() {
+    let mut frontend = Frontend::default();
+
+    // Parsing continues after a declaration fails, so all of them are reported.
+    let errors = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        float a = ;
+
+        float first() {
+            return 1.0 +;
+        }
+
+        struct Data {
+            vec4 +;
+        };
+
+        void main() {
+            float b = first() +;
+        }
+        "#,
+        )
+        .err()
+        .unwrap()
+        .errors;
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| match error.kind {
+                ErrorKind::InvalidToken(ref token, _) => token.clone(),
+                ref other => panic!("unexpected error {other:?}"),
+            })
+            .collect::<Vec<_>>(),
+        [
+            TokenValue::Semicolon,
+            TokenValue::Semicolon,
+            TokenValue::Plus,
+            TokenValue::Semicolon
+        ]
+    );
+}
+
+#[test]
+fn push_constant_blocks() {
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        layout(push_constant) uniform PushConstants {
+            float values[2];
+            layout(offset = 16) vec4 color;
+            highp int index;
+        } pc;
+
+        void main() {}
+        "#,
+        )
+        .unwrap();
+
+    let (_, global) = module
+        .global_variables
+        .iter()
+        .find(|(_, global)| global.name.as_deref() == Some("pc"))
+        .unwrap();
+    assert_eq!(global.space, crate::AddressSpace::PushConstant);
+    assert_eq!(global.binding, None);
+    match module.types[global.ty].inner {
+        crate::TypeInner::Struct { ref members, span } => {
+            // Push constants use the std430 layout by default
+            assert_eq!(
+                members.iter().map(|m| m.offset).collect::<Vec<_>>(),
+                [0, 16, 32]
+            );
+            assert_eq!(span, 48);
+            match module.types[members[0].ty].inner {
+                crate::TypeInner::Array { stride, .. } => assert_eq!(stride, 4),
+                ref other => panic!("unexpected type {other:?}"),
+            }
+        }
+        ref other => panic!("unexpected type {other:?}"),
+    }
+
+    let errors = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        layout(push_constant) uniform First {
+            vec4 a;
+            layout(offset = 8) float b;
+            layout(offset = 18) float c;
+        };
+        layout(push_constant) uniform Second {
+            vec4 d;
+        };
+
+        void main() {}
+        "#,
+        )
+        .err()
+        .unwrap()
+        .errors;
+    assert_eq!(
+        errors
+            .into_iter()
+            .map(|error| error.kind)
+            .collect::<Vec<_>>(),
+        [
+            ErrorKind::SemanticError("Offset 8 overlaps the previous members".into()),
+            ErrorKind::SemanticError("Offset 18 isn't a multiple of the member's alignment".into()),
+            ErrorKind::SemanticError("Only one push constant block can be declared".into()),
+        ]
+    );
+}
+
+#[test]
+fn separate_textures_and_samplers() {
+    use crate::Expression;
+
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #  version 450
+        layout(set = 0, binding = 0) uniform texture2D tex;
+        layout(set = 0, binding = 1) uniform sampler samp;
+        layout(location = 0) out vec4 o_color;
+
+        void main() {
+            o_color = texture(sampler2D(tex, samp), vec2(0.5));
+            // Samplerless functions can use the texture directly
+            o_color += texelFetch(tex, ivec2(0), 0);
+            ivec2 size = textureSize(tex, 0);
+        }
+        "#,
+        )
+        .unwrap();
+
+    let global = |name: &str| {
+        module
+            .global_variables
+            .iter()
+            .find(|(_, global)| global.name.as_deref() == Some(name))
+            .unwrap()
+            .0
+    };
+    let (tex, samp) = (global("tex"), global("samp"));
+    assert_eq!(
+        module.global_variables[tex].space,
+        crate::AddressSpace::Handle
+    );
+    assert_eq!(
+        module.global_variables[samp].space,
+        crate::AddressSpace::Handle
+    );
+
+    let (_, main) = module
+        .functions
+        .iter()
+        .find(|(_, f)| f.name.as_deref() == Some("main"))
+        .unwrap();
+    let global_of = |expr| match main.expressions[expr] {
+        Expression::GlobalVariable(handle) => handle,
+        ref other => panic!("unexpected expression {other:?}"),
+    };
+    let mut images = Vec::new();
+    for (_, expr) in main.expressions.iter() {
+        match *expr {
+            Expression::ImageSample { image, sampler, .. } => {
+                assert_eq!(global_of(sampler), samp);
+                images.push(("sample", global_of(image)));
+            }
+            Expression::ImageLoad { image, .. } => images.push(("load", global_of(image))),
+            Expression::ImageQuery { image, .. } => images.push(("query", global_of(image))),
+            _ => {}
+        }
+    }
+    assert_eq!(images, [("sample", tex), ("load", tex), ("query", tex)]);
+
+    // Sampling functions need a sampler.
+    let errors = frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #  version 450
+        layout(set = 0, binding = 0) uniform texture2D tex;
+        layout(location = 0) out vec4 o_color;
+
+        void main() {
+            o_color = texture(tex, vec2(0.5));
+        }
+        "#,
+        )
+        .err()
+        .unwrap()
+        .errors;
+    assert_eq!(
+        errors
+            .into_iter()
+            .map(|error| error.kind)
+            .collect::<Vec<_>>(),
+        [ErrorKind::SemanticError(
+            "Sampling a texture requires combining it with a sampler, like `sampler2D(tex, samp)`"
+                .into()
+        )]
+    );
+}
+
+#[test]
+fn entry_point_name() {
+    let mut frontend = Frontend::default();
+
+    let source = r#"
+        #  version 450
+        layout(location = 0) out vec4 o_color;
+
+        void main() {
+            o_color = vec4(COLOR);
+        }
+        "#;
+
+    let mut options = Options::from(ShaderStage::Fragment);
+    options.defines.insert("COLOR".into(), "1.0".into());
+    let module = frontend.parse(&options, source).unwrap();
+    assert_eq!(module.entry_points[0].name, "main");
+
+    // Each permutation can get its own entry point name.
+    options.defines.insert("COLOR".into(), "0.5".into());
+    options.entry_point = Some("fs_half".into());
+    let module = frontend.parse(&options, source).unwrap();
+    assert_eq!(
+        module
+            .entry_points
+            .iter()
+            .map(|ep| ep.name.as_str())
+            .collect::<Vec<_>>(),
+        ["fs_half"]
+    );
+}
+
+#[test]
+fn stage_interface() {
+    use crate::{Interpolation, Scalar, TypeInner, VectorSize};
+
+    let mut frontend = Frontend::default();
+
+    let vertex = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        layout(location = 0) out vec2 uv;
+        layout(location = 1) flat out int id;
+        layout(location = 3) out float w;
+
+        void main() {
+            uv = vec2(0.0);
+            id = 0;
+            w = 1.0;
+            gl_Position = vec4(0.0);
+        }
+        "#,
+        )
+        .unwrap();
+
+    let fragment = frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #  version 450
+        layout(location = 0) in vec2 uv;
+        layout(location = 1) flat in int id;
+        layout(location = 0) out vec4 o_color;
+
+        void main() {
+            o_color = vec4(uv, float(id), 1.0);
+        }
+        "#,
+        )
+        .unwrap();
+    assert_eq!(validate_interface(&vertex, &fragment), Ok(()));
+
+    let fragment = frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #  version 450
+        layout(location = 0) in vec3 uv;
+        layout(location = 1) flat in int id;
+        layout(location = 2) in float extra;
+        layout(location = 3) noperspective in float w;
+        layout(location = 0) out vec4 o_color;
+
+        void main() {
+            o_color = vec4(uv, float(id) + extra + w);
+        }
+        "#,
+        )
+        .unwrap();
+    assert_eq!(
+        validate_interface(&vertex, &fragment),
+        Err(vec![
+            InterfaceError::TypeMismatch {
+                location: 0,
+                output: TypeInner::Vector {
+                    size: VectorSize::Bi,
+                    scalar: Scalar::F32
+                },
+                input: TypeInner::Vector {
+                    size: VectorSize::Tri,
+                    scalar: Scalar::F32
+                },
+            },
+            InterfaceError::MissingOutput(2),
+            InterfaceError::InterpolationMismatch {
+                location: 3,
+                output: (Some(Interpolation::Perspective), None),
+                input: (Some(Interpolation::Linear), None),
+            },
+        ])
+    );
+}
+
+#[test]
+fn builtin_interface_names() {
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Vertex),
+            r#"
+        #  version 450
+        layout(location = 0) out vec2 v_uv;
+
+        void main() {
+            v_uv = vec2(gl_VertexIndex);
+            gl_Position = vec4(0.0);
+        }
+        "#,
+        )
+        .unwrap();
+
+    // The builtins keep their GLSL names in the entry point's interface, so
+    // that backends can generate readable code.
+    let function = &module.entry_points[0].function;
+    assert_eq!(
+        function
+            .arguments
+            .iter()
+            .map(|arg| arg.name.as_deref())
+            .collect::<Vec<_>>(),
+        [Some("gl_VertexIndex")]
+    );
+    let result_ty = function.result.as_ref().unwrap().ty;
+    match module.types[result_ty].inner {
+        crate::TypeInner::Struct { ref members, .. } => assert_eq!(
+            members
+                .iter()
+                .map(|member| member.name.as_deref())
+                .collect::<Vec<_>>(),
+            [Some("v_uv"), Some("gl_Position")]
+        ),
+        ref other => panic!("unexpected type {other:?}"),
+    }
+}
+
+#[test]
+fn float16() {
+    let mut frontend = Frontend::default();
+
+    let module = frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #  version 450
+        #extension GL_EXT_shader_explicit_arithmetic_types_float16 : require
+        layout(location = 0) in vec4 v_color;
+        layout(location = 0) out vec4 o_color;
+
+        float16_t luminance(f16vec3 color) {
+            return color.r * float16_t(0.25) + color.g * float16_t(0.5) + color.b * float16_t(0.25);
+        }
+
+        void main() {
+            o_color = vec4(float(luminance(f16vec3(v_color.rgb))));
+        }
+        "#,
+        )
+        .unwrap();
+
+    let function = &module.functions.iter().next().unwrap().1;
+    let result_ty = function.result.as_ref().unwrap().ty;
+    assert_eq!(
+        module.types[result_ty].inner,
+        crate::TypeInner::Scalar(crate::Scalar::F16)
+    );
+
+    let validate = |capabilities| {
+        crate::valid::Validator::new(crate::valid::ValidationFlags::all(), capabilities)
+            .validate(&module)
+    };
+    validate(crate::valid::Capabilities::FLOAT16).unwrap();
+    validate(crate::valid::Capabilities::empty()).unwrap_err();
+}
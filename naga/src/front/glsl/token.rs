@@ -45,6 +45,8 @@ pub enum TokenValue {
     MemoryQualifier(crate::StorageAccess),
 
     Invariant,
+    /// The `patch` qualifier of per-patch tessellation varyings.
+    Patch,
     Interpolation(Interpolation),
     Sampling(Sampling),
     Precision,
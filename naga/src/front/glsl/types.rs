@@ -14,6 +14,10 @@ pub fn parse_type(type_name: &str) -> Option<Type> {
             name: None,
             inner: TypeInner::Scalar(Scalar::F32),
         }),
+        "float16_t" => Some(Type {
+            name: None,
+            inner: TypeInner::Scalar(Scalar::F16),
+        }),
         "double" => Some(Type {
             name: None,
             inner: TypeInner::Scalar(Scalar::F64),
@@ -40,6 +44,7 @@ pub fn parse_type(type_name: &str) -> Option<Type> {
                     "i" => Scalar::I32,
                     "u" => Scalar::U32,
                     "d" => Scalar::F64,
+                    "f16" => Scalar::F16,
                     _ => return None,
                 })
             }
@@ -203,8 +208,9 @@ pub const fn type_power(scalar: Scalar) -> Option<u32> {
     Some(match scalar.kind {
         ScalarKind::Sint => 0,
         ScalarKind::Uint => 1,
-        ScalarKind::Float if scalar.width == 4 => 2,
-        ScalarKind::Float => 3,
+        ScalarKind::Float if scalar.width == 2 => 2,
+        ScalarKind::Float if scalar.width == 4 => 3,
+        ScalarKind::Float => 4,
         ScalarKind::Bool | ScalarKind::AbstractInt | ScalarKind::AbstractFloat => return None,
     })
 }
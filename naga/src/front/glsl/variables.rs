@@ -10,6 +10,10 @@ use crate::{
     TypeInner, VectorSize,
 };
 
+/// The maximum number of vertices of a tessellation patch,
+/// `gl_MaxPatchVertices`.
+const MAX_PATCH_VERTICES: u32 = 32;
+
 pub struct VarDeclaration<'a, 'key> {
     pub qualifiers: &'a mut TypeQualifiers<'key>,
     pub ty: Handle<Type>,
@@ -65,7 +69,7 @@ impl Frontend {
 
         let idx = self.entry_args.len();
         self.entry_args.push(EntryArg {
-            name: None,
+            name: Some(name.into()),
             binding: Binding::BuiltIn(data.builtin),
             handle,
             storage: data.storage,
@@ -105,6 +109,19 @@ impl Frontend {
             return Ok(Some(var));
         }
 
+        let fragment_only = matches!(
+            name,
+            "gl_FragCoord" | "gl_FragDepth" | "gl_FrontFacing" | "gl_PointCoord"
+        );
+        if fragment_only && self.meta.stage != ShaderStage::Fragment {
+            self.errors.push(Error {
+                kind: ErrorKind::SemanticError(
+                    format!("{name} is only available in fragment shaders").into(),
+                ),
+                meta,
+            });
+        }
+
         let data = match name {
             "gl_Position" => BuiltInData {
                 inner: TypeInner::Vector {
@@ -189,19 +206,149 @@ impl Frontend {
                         "gl_CullDistance" => BuiltIn::CullDistance,
                         _ => unreachable!(),
                     },
-                    mutable: self.meta.stage == ShaderStage::Vertex,
+                    mutable: matches!(
+                        self.meta.stage,
+                        ShaderStage::Vertex
+                            | ShaderStage::Geometry
+                            | ShaderStage::TessellationEvaluation
+                    ),
                     storage: StorageQualifier::Output,
                 }
             }
+            // The vertices of the primitive or patch consumed by geometry and
+            // tessellation shaders, and of the patch output by tessellation
+            // control shaders.
+            "gl_in" | "gl_out" => {
+                let input = name == "gl_in";
+                let Some(size) = self.per_vertex_size(input, meta)? else {
+                    return Ok(None);
+                };
+                let vec4 = ctx.module.types.insert(
+                    Type {
+                        name: None,
+                        inner: TypeInner::Vector {
+                            size: VectorSize::Quad,
+                            scalar: Scalar::F32,
+                        },
+                    },
+                    meta,
+                );
+                let per_vertex = ctx.module.types.insert(
+                    Type {
+                        name: Some("gl_PerVertex".into()),
+                        inner: TypeInner::Struct {
+                            members: vec![crate::StructMember {
+                                name: Some("gl_Position".into()),
+                                ty: vec4,
+                                binding: Some(Binding::BuiltIn(BuiltIn::Position {
+                                    invariant: false,
+                                })),
+                                offset: 0,
+                            }],
+                            span: 16,
+                        },
+                    },
+                    meta,
+                );
+
+                BuiltInData {
+                    inner: TypeInner::Array {
+                        base: per_vertex,
+                        size,
+                        stride: 16,
+                    },
+                    // The binding of the only member, which makes the whole
+                    // variable hold a value per vertex.
+                    builtin: BuiltIn::Position { invariant: false },
+                    mutable: !input,
+                    storage: match input {
+                        true => StorageQualifier::Input,
+                        false => StorageQualifier::Output,
+                    },
+                }
+            }
+            "gl_TessLevelOuter" | "gl_TessLevelInner"
+                if matches!(
+                    self.meta.stage,
+                    ShaderStage::TessellationControl | ShaderStage::TessellationEvaluation
+                ) =>
+            {
+                let base = ctx.module.types.insert(
+                    Type {
+                        name: None,
+                        inner: TypeInner::Scalar(Scalar::F32),
+                    },
+                    meta,
+                );
+                let (size, builtin) = match name {
+                    "gl_TessLevelOuter" => (4, BuiltIn::TessLevelOuter),
+                    _ => (2, BuiltIn::TessLevelInner),
+                };
+                let control = self.meta.stage == ShaderStage::TessellationControl;
+
+                BuiltInData {
+                    inner: TypeInner::Array {
+                        base,
+                        size: crate::ArraySize::Constant(std::num::NonZeroU32::new(size).unwrap()),
+                        stride: 4,
+                    },
+                    builtin,
+                    mutable: control,
+                    storage: match control {
+                        true => StorageQualifier::Output,
+                        false => StorageQualifier::Input,
+                    },
+                }
+            }
+            "gl_TessCoord" if self.meta.stage == ShaderStage::TessellationEvaluation => {
+                BuiltInData {
+                    inner: TypeInner::Vector {
+                        size: VectorSize::Tri,
+                        scalar: Scalar::F32,
+                    },
+                    builtin: BuiltIn::TessCoord,
+                    mutable: false,
+                    storage: StorageQualifier::Input,
+                }
+            }
+            "gl_PrimitiveID" if self.meta.stage == ShaderStage::Geometry => BuiltInData {
+                inner: TypeInner::Scalar(Scalar::U32),
+                builtin: BuiltIn::PrimitiveIndex,
+                mutable: true,
+                storage: StorageQualifier::Output,
+            },
             _ => {
                 let builtin = match name {
                     "gl_BaseVertex" => BuiltIn::BaseVertex,
                     "gl_BaseInstance" => BuiltIn::BaseInstance,
                     "gl_PrimitiveID" => BuiltIn::PrimitiveIndex,
+                    "gl_PrimitiveIDIn" if self.meta.stage == ShaderStage::Geometry => {
+                        BuiltIn::PrimitiveIndex
+                    }
+                    "gl_InvocationID"
+                        if matches!(
+                            self.meta.stage,
+                            ShaderStage::Geometry | ShaderStage::TessellationControl
+                        ) =>
+                    {
+                        BuiltIn::InvocationId
+                    }
+                    "gl_PatchVerticesIn"
+                        if matches!(
+                            self.meta.stage,
+                            ShaderStage::TessellationControl | ShaderStage::TessellationEvaluation
+                        ) =>
+                    {
+                        BuiltIn::PatchVertices
+                    }
                     "gl_InstanceIndex" => BuiltIn::InstanceIndex,
                     "gl_VertexIndex" => BuiltIn::VertexIndex,
                     "gl_SampleID" => BuiltIn::SampleIndex,
                     "gl_LocalInvocationIndex" => BuiltIn::LocalInvocationIndex,
+                    "gl_NumSubgroups" => BuiltIn::NumSubgroups,
+                    "gl_SubgroupID" => BuiltIn::SubgroupId,
+                    "gl_SubgroupSize" => BuiltIn::SubgroupSize,
+                    "gl_SubgroupInvocationID" => BuiltIn::SubgroupInvocationId,
                     _ => return Ok(None),
                 };
 
@@ -217,6 +364,46 @@ impl Frontend {
         self.add_builtin(ctx, name, data, meta)
     }
 
+    /// Returns the size of the arrays of per-vertex inputs, or outputs if
+    /// `input` is false, which hold a value for every vertex of a primitive
+    /// or patch, if the stage has any.
+    ///
+    /// Geometry shader inputs must be declared after their input primitive,
+    /// and tessellation control shader outputs after the number of vertices
+    /// of their output patch.
+    fn per_vertex_size(&self, input: bool, meta: Span) -> Result<Option<crate::ArraySize>> {
+        let count = match (self.meta.stage, input) {
+            (ShaderStage::Geometry, true) => {
+                let primitive = self.meta.geometry_input_primitive.ok_or_else(|| Error {
+                    kind: ErrorKind::SemanticError(
+                        "Geometry shader inputs must be declared after the input primitive".into(),
+                    ),
+                    meta,
+                })?;
+                primitive.vertex_count()
+            }
+            // Like `gl_in`, the inputs of tessellation shaders hold a value
+            // for as many vertices as a patch can have, `gl_MaxPatchVertices`.
+            (ShaderStage::TessellationControl | ShaderStage::TessellationEvaluation, true) => {
+                MAX_PATCH_VERTICES
+            }
+            (ShaderStage::TessellationControl, false) => {
+                if self.meta.tessellation_output_vertices == 0 {
+                    return Err(Error {
+                        kind: ErrorKind::SemanticError(
+                            "Tessellation control shader outputs must be declared after the number of output vertices"
+                                .into(),
+                        ),
+                        meta,
+                    });
+                }
+                self.meta.tessellation_output_vertices
+            }
+            _ => return Ok(None),
+        };
+        Ok(std::num::NonZeroU32::new(count).map(crate::ArraySize::Constant))
+    }
+
     pub(crate) fn make_variable_invariant(
         &mut self,
         ctx: &mut Context,
@@ -418,6 +605,54 @@ impl Frontend {
         let (ret, lookup) = match storage {
             StorageQualifier::Input | StorageQualifier::Output => {
                 let input = storage == StorageQualifier::Input;
+                let patch = qualifiers.patch.take().is_some();
+                if patch {
+                    let per_patch = match self.meta.stage {
+                        ShaderStage::TessellationControl => !input,
+                        ShaderStage::TessellationEvaluation => input,
+                        _ => false,
+                    };
+                    if !per_patch {
+                        self.errors.push(Error {
+                            kind: ErrorKind::SemanticError(
+                                "Patch qualifier can only be used in tessellation control shader outputs and tessellation evaluation shader inputs"
+                                    .into(),
+                            ),
+                            meta,
+                        });
+                    }
+                }
+                // Varyings holding a value for every vertex of a primitive or
+                // patch must be arrays of its size.
+                let size = match patch {
+                    true => None,
+                    false => self.per_vertex_size(input, meta)?,
+                };
+                if let Some(size) = size {
+                    ty = match ctx.module.types[ty].inner {
+                        TypeInner::Array {
+                            base,
+                            size: crate::ArraySize::Dynamic,
+                            stride,
+                        } => ctx.module.types.insert(
+                            Type {
+                                name: None,
+                                inner: TypeInner::Array { base, size, stride },
+                            },
+                            meta,
+                        ),
+                        TypeInner::Array { size: declared, .. } if declared == size => ty,
+                        _ => {
+                            return Err(Error {
+                                kind: ErrorKind::SemanticError(
+                                    "Per-vertex varyings must be arrays of the size of the primitive or patch"
+                                        .into(),
+                                ),
+                                meta,
+                            })
+                        }
+                    };
+                }
                 // TODO: glslang seems to use a counter for variables without
                 // explicit location (even if that causes collisions)
                 let location = qualifiers
@@ -451,6 +686,7 @@ impl Frontend {
                         interpolation,
                         sampling,
                         second_blend_source: false,
+                        patch,
                     },
                     handle,
                     storage,
@@ -540,6 +776,20 @@ impl Frontend {
                         TypeInner::Sampler { .. } => space = AddressSpace::Handle,
                         _ => {
                             if qualifiers.none_layout_qualifier("push_constant", &mut self.errors) {
+                                let declared = ctx
+                                    .module
+                                    .global_variables
+                                    .iter()
+                                    .any(|(_, var)| var.space == AddressSpace::PushConstant);
+                                if declared {
+                                    self.errors.push(Error {
+                                        kind: ErrorKind::SemanticError(
+                                            "Only one push constant block can be declared".into(),
+                                        ),
+                                        meta,
+                                    });
+                                }
+
                                 space = AddressSpace::PushConstant
                             }
                         }
@@ -560,6 +560,10 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
                 stage: ep.stage,
                 early_depth_test: ep.early_depth_test,
                 workgroup_size: ep.workgroup_size,
+                task_payload: None,
+                mesh_info: None,
+                geometry_info: None,
+                tessellation_info: None,
                 function,
             });
         }
@@ -251,6 +251,7 @@ impl Decoration {
                 interpolation,
                 sampling,
                 second_blend_source: false,
+                patch: false,
             }),
             _ => Err(Error::MissingDecoration(spirv::Decoration::Location)),
         }
@@ -3810,7 +3811,13 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 | S::Store { .. }
                 | S::ImageStore { .. }
                 | S::Atomic { .. }
-                | S::RayQuery { .. } => {}
+                | S::RayQuery { .. }
+                | S::DebugPrintf { .. }
+                | S::EmitVertex { .. }
+                | S::EndPrimitive
+                | S::SubgroupBallot { .. }
+                | S::SubgroupCollectiveOperation { .. }
+                | S::SubgroupGather { .. } => {}
                 S::Call {
                     function: ref mut callee,
                     ref arguments,
@@ -3942,10 +3949,16 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 Op::TypeSampledImage => self.parse_type_sampled_image(inst),
                 Op::TypeSampler => self.parse_type_sampler(inst, &mut module),
                 Op::Constant | Op::SpecConstant => self.parse_constant(inst, &mut module),
-                Op::ConstantComposite => self.parse_composite_constant(inst, &mut module),
+                Op::ConstantComposite | Op::SpecConstantComposite => {
+                    self.parse_composite_constant(inst, &mut module)
+                }
                 Op::ConstantNull | Op::Undef => self.parse_null_constant(inst, &mut module),
-                Op::ConstantTrue => self.parse_bool_constant(inst, true, &mut module),
-                Op::ConstantFalse => self.parse_bool_constant(inst, false, &mut module),
+                Op::ConstantTrue | Op::SpecConstantTrue => {
+                    self.parse_bool_constant(inst, true, &mut module)
+                }
+                Op::ConstantFalse | Op::SpecConstantFalse => {
+                    self.parse_bool_constant(inst, false, &mut module)
+                }
                 Op::Variable => self.parse_global_variable(inst, &mut module),
                 Op::Function => {
                     self.switch(ModuleState::Function, inst.op)?;
@@ -4868,6 +4881,11 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 let low = self.next()?;
                 match width {
                     4 => crate::Literal::U32(low),
+                    8 => {
+                        inst.expect(5)?;
+                        let high = self.next()?;
+                        crate::Literal::U64(u64::from(high) << 32 | u64::from(low))
+                    }
                     _ => return Err(Error::InvalidTypeWidth(width as u32)),
                 }
             }
@@ -4892,6 +4910,7 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             }) => {
                 let low = self.next()?;
                 match width {
+                    2 => crate::Literal::F16(half::f16::from_bits(low as u16)),
                     4 => crate::Literal::F32(f32::from_bits(low)),
                     8 => {
                         inst.expect(5)?;
@@ -5338,6 +5357,63 @@ mod test {
         ];
         let _ = super::parse_u8_slice(&bin, &Default::default()).unwrap();
     }
+
+    #[test]
+    fn spec_constants() {
+        let words: [u32; 33] = [
+            // Magic number, version 1.0, generator, bound and reserved word.
+            0x07230203, 0x00010000, 0, 5, 0, // OpMemoryModel Logical GLSL450
+            0x0003000e, 0, 1, // OpName %2 "flag"
+            0x00040005, 2, 0x67616c66, 0, // OpDecorate %2 SpecId 7
+            0x00040047, 2, 1, 7, // OpDecorate %4 SpecId 8
+            0x00040047, 4, 1, 8, // %1 = OpTypeBool
+            0x00020014, 1, // %2 = OpSpecConstantTrue %1
+            0x00030030, 1, 2, // %3 = OpTypeInt 32 1
+            0x00040015, 3, 32, 1, // %4 = OpSpecConstant %3 5
+            0x00040032, 3, 4, 5,
+        ];
+        let bin: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+        let mut module = super::parse_u8_slice(&bin, &Default::default()).unwrap();
+        let overrides: Vec<_> = module
+            .constants
+            .iter()
+            .map(|(_, constant)| match constant.r#override {
+                crate::Override::ByNameOrId(id) => Some(id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(overrides, [Some(7), Some(8)]);
+
+        // Unknown ids are rejected
+        let constants = [("9".to_string(), 1.0)].into_iter().collect();
+        assert_eq!(
+            crate::proc::process_overrides(&mut module, &constants),
+            Err(crate::proc::PipelineConstantError::MissingConstant(
+                "9".to_string()
+            ))
+        );
+
+        // The first constant is found by name, and the second one by id
+        let constants = [("flag".to_string(), 0.0), ("8".to_string(), 12.0)]
+            .into_iter()
+            .collect();
+        crate::proc::process_overrides(&mut module, &constants).unwrap();
+        let values: Vec<_> = module
+            .constants
+            .iter()
+            .map(|(_, constant)| {
+                assert_eq!(constant.r#override, crate::Override::None);
+                match module.const_expressions[constant.init] {
+                    crate::Expression::Literal(literal) => literal,
+                    ref other => panic!("Unexpected initializer {other:?}"),
+                }
+            })
+            .collect();
+        assert_eq!(
+            values,
+            [crate::Literal::Bool(false), crate::Literal::I32(12)]
+        );
+    }
 }
 
 /// Helper function to check if `child` is in the scope of `parent`
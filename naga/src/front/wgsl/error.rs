@@ -131,8 +131,6 @@ pub enum NumberError {
     Invalid,
     #[error("numeric literal not representable by target type")]
     NotRepresentable,
-    #[error("unimplemented f16 type")]
-    UnimplementedF16,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -165,6 +163,7 @@ pub enum Error<'a> {
     InvalidForInitializer(Span),
     /// A break if appeared outside of a continuing block
     InvalidBreakIf(Span),
+    InvalidStringEscape(Span),
     InvalidGatherComponent(Span),
     InvalidConstructorComponentType(Span, i32),
     InvalidIdentifierUnderscore(Span),
@@ -179,6 +178,11 @@ pub enum Error<'a> {
     UnknownType(Span),
     UnknownStorageFormat(Span),
     UnknownConservativeDepth(Span),
+    UnknownEnableExtension(Span),
+    ExtensionNotEnabled {
+        span: Span,
+        extension: &'static str,
+    },
     SizeAttributeTooLow(Span, u32),
     AlignAttributeTooLow(Span, Alignment),
     NonPowerOfTwoAlignAttribute(Span),
@@ -250,6 +254,8 @@ pub enum Error<'a> {
     ExpectedNonNegative(Span),
     ExpectedPositiveArrayLength(Span),
     MissingWorkgroupSize(Span),
+    ExpectedGlobalVariable(Span),
+    InvalidMeshOutputType(Span),
     ConstantEvaluatorError(ConstantEvaluatorError, Span),
     AutoConversion {
         dest_span: Span,
@@ -283,6 +289,7 @@ impl<'a> Error<'a> {
                             Token::Attribute => "@".to_string(),
                             Token::Number(_) => "number".to_string(),
                             Token::Word(s) => s.to_string(),
+                            Token::String(_) => "string".to_string(),
                             Token::Operation(c) => format!("operation ('{c}')"),
                             Token::LogicalOperation(c) => format!("logical operation ('{c}')"),
                             Token::ShiftOperation(c) => format!("bitshift ('{c}{c}')"),
@@ -351,7 +358,7 @@ impl<'a> Error<'a> {
             Error::UnknownScalarType(bad_span) => ParseError {
                 message: format!("unknown scalar type: '{}'", &source[bad_span]),
                 labels: vec![(bad_span, "unknown scalar type".into())],
-                notes: vec!["Valid scalar types are f32, f64, i32, u32, bool".into()],
+                notes: vec!["Valid scalar types are f16, f32, f64, i32, u32, bool".into()],
             },
             Error::BadTextureSampleType { span, scalar } => ParseError {
                 message: format!(
@@ -406,6 +413,11 @@ impl<'a> Error<'a> {
                 labels: vec![(bad_span, "not in a continuing block".into())],
                 notes: vec![],
             },
+            Error::InvalidStringEscape(bad_span) => ParseError {
+                message: format!("invalid escape sequence in string `{}`", &source[bad_span]),
+                labels: vec![(bad_span, "invalid escape sequence".into())],
+                notes: vec!["supported escape sequences are `\\\\`, `\\\"`, `\\n` and `\\t`".into()],
+            },
             Error::InvalidGatherComponent(bad_span) => ParseError {
                 message: format!(
                     "textureGather component '{}' doesn't exist, must be 0, 1, 2, or 3",
@@ -450,6 +462,16 @@ impl<'a> Error<'a> {
                 labels: vec![(bad_span, "unknown attribute".into())],
                 notes: vec![],
             },
+            Error::UnknownEnableExtension(bad_span) => ParseError {
+                message: format!("unknown enable-extension: '{}'", &source[bad_span]),
+                labels: vec![(bad_span, "unknown enable-extension".into())],
+                notes: vec![],
+            },
+            Error::ExtensionNotEnabled { span, extension } => ParseError {
+                message: format!("the `{extension}` extension is not enabled"),
+                labels: vec![(span, format!("requires the `{extension}` extension").into())],
+                notes: vec![format!("add `enable {extension};` at the start of the shader")],
+            },
             Error::UnknownBuiltin(bad_span) => ParseError {
                 message: format!("unknown builtin: '{}'", &source[bad_span]),
                 labels: vec![(bad_span, "unknown builtin".into())],
@@ -730,6 +752,19 @@ impl<'a> Error<'a> {
                 )],
                 notes: vec![],
             },
+            Error::ExpectedGlobalVariable(span) => ParseError {
+                message: format!("`{}` is not a global variable", &source[span]),
+                labels: vec![(span, "expected a global variable".into())],
+                notes: vec![],
+            },
+            Error::InvalidMeshOutputType(span) => ParseError {
+                message: "invalid mesh shader output variable".to_string(),
+                labels: vec![(span, "its type is not a valid mesh output struct".into())],
+                notes: vec![
+                    "the struct must have `vertices` and `primitives` arrays, and `vertex_count` and `primitive_count` built-in members".into(),
+                    "the primitives must have a `point_index`, `line_indices` or `triangle_indices` built-in member".into(),
+                ],
+            },
             Error::AutoConversion { dest_span, ref dest_type, source_span, ref source_type } => ParseError {
                 message: format!("automatic conversions cannot convert `{source_type}` to `{dest_type}`"),
                 labels: vec![
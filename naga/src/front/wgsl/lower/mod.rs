@@ -986,7 +986,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         // Constant evaluation may leave abstract-typed literals and
         // compositions in expression arenas, so we need to compact the module
         // to remove unused expressions and types.
-        crate::compact::compact(&mut module);
+        crate::compact::compact(&mut module, crate::compact::KeepUnused::Yes);
 
         Ok(module)
     }
@@ -1078,11 +1078,27 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 [0; 3]
             };
 
+            let task_payload = entry
+                .task_payload
+                .map(|ident| self.global_variable(ident, ctx))
+                .transpose()?;
+            let mesh_info = entry
+                .mesh_output_variable
+                .map(|ident| {
+                    let output_variable = self.global_variable(ident, ctx)?;
+                    Self::mesh_stage_info(output_variable, ident.span, ctx.module)
+                })
+                .transpose()?;
+
             ctx.module.entry_points.push(crate::EntryPoint {
                 name: f.name.name.to_string(),
                 stage: entry.stage,
                 early_depth_test: entry.early_depth_test,
                 workgroup_size,
+                task_payload,
+                mesh_info,
+                geometry_info: None,
+                tessellation_info: None,
                 function,
             });
             Ok(LoweredGlobalDecl::EntryPoint)
@@ -1092,6 +1108,83 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         }
     }
 
+    /// Resolves an identifier that must refer to a global variable.
+    fn global_variable(
+        &self,
+        ident: ast::Ident<'source>,
+        ctx: &GlobalContext<'source, '_, '_>,
+    ) -> Result<Handle<crate::GlobalVariable>, Error<'source>> {
+        match ctx.globals.get(ident.name) {
+            Some(&LoweredGlobalDecl::Var(handle)) => Ok(handle),
+            Some(_) => Err(Error::ExpectedGlobalVariable(ident.span)),
+            None => Err(Error::UnknownIdent(ident.span, ident.name)),
+        }
+    }
+
+    /// Describes the output of a mesh shader from the type of the variable it
+    /// writes its output to.
+    ///
+    /// The validator checks the rest of the requirements on the type.
+    fn mesh_stage_info(
+        output_variable: Handle<crate::GlobalVariable>,
+        span: Span,
+        module: &crate::Module,
+    ) -> Result<crate::MeshStageInfo, Error<'source>> {
+        let ty = module.global_variables[output_variable].ty;
+        let crate::TypeInner::Struct { ref members, .. } = module.types[ty].inner else {
+            return Err(Error::InvalidMeshOutputType(span));
+        };
+        let array_member = |built_in| {
+            let member = members
+                .iter()
+                .find(|member| member.binding == Some(crate::Binding::BuiltIn(built_in)))?;
+            match module.types[member.ty].inner {
+                crate::TypeInner::Array {
+                    base,
+                    size: crate::ArraySize::Constant(size),
+                    ..
+                } => Some((base, size.get())),
+                _ => None,
+            }
+        };
+        let (vertex_output_type, max_vertices) =
+            array_member(crate::BuiltIn::Vertices).ok_or(Error::InvalidMeshOutputType(span))?;
+        let (primitive_output_type, max_primitives) =
+            array_member(crate::BuiltIn::Primitives).ok_or(Error::InvalidMeshOutputType(span))?;
+
+        let crate::TypeInner::Struct {
+            members: ref primitive_members,
+            ..
+        } = module.types[primitive_output_type].inner
+        else {
+            return Err(Error::InvalidMeshOutputType(span));
+        };
+        let topology = primitive_members
+            .iter()
+            .find_map(|member| match member.binding {
+                Some(crate::Binding::BuiltIn(crate::BuiltIn::PointIndex)) => {
+                    Some(crate::MeshOutputTopology::Points)
+                }
+                Some(crate::Binding::BuiltIn(crate::BuiltIn::LineIndices)) => {
+                    Some(crate::MeshOutputTopology::Lines)
+                }
+                Some(crate::Binding::BuiltIn(crate::BuiltIn::TriangleIndices)) => {
+                    Some(crate::MeshOutputTopology::Triangles)
+                }
+                _ => None,
+            })
+            .ok_or(Error::InvalidMeshOutputType(span))?;
+
+        Ok(crate::MeshStageInfo {
+            topology,
+            max_vertices,
+            max_primitives,
+            vertex_output_type,
+            primitive_output_type,
+            output_variable,
+        })
+    }
+
     fn block(
         &mut self,
         b: &ast::Block<'source>,
@@ -1370,6 +1463,24 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 block.extend(emitter.finish(&ctx.function.expressions));
                 return Ok(());
             }
+            ast::StatementKind::DebugPrintf {
+                ref format,
+                ref arguments,
+            } => {
+                let mut emitter = Emitter::default();
+                emitter.start(&ctx.function.expressions);
+
+                let arguments = arguments
+                    .iter()
+                    .map(|&arg| self.expression(arg, &mut ctx.as_expression(block, &mut emitter)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                block.extend(emitter.finish(&ctx.function.expressions));
+
+                crate::Statement::DebugPrintf {
+                    format: format.clone(),
+                    arguments,
+                }
+            }
             ast::StatementKind::Assign {
                 target: ast_target,
                 op,
@@ -1528,8 +1639,11 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             ast::Expression::Literal(literal) => {
                 let literal = match literal {
                     ast::Literal::Number(Number::F32(f)) => crate::Literal::F32(f),
+                    ast::Literal::Number(Number::F16(f)) => crate::Literal::F16(f),
                     ast::Literal::Number(Number::I32(i)) => crate::Literal::I32(i),
                     ast::Literal::Number(Number::U32(u)) => crate::Literal::U32(u),
+                    ast::Literal::Number(Number::I64(i)) => crate::Literal::I64(i),
+                    ast::Literal::Number(Number::U64(u)) => crate::Literal::U64(u),
                     ast::Literal::Number(Number::F64(f)) => crate::Literal::F64(f),
                     ast::Literal::Number(Number::AbstractInt(i)) => crate::Literal::AbstractInt(i),
                     ast::Literal::Number(Number::AbstractFloat(f)) => {
@@ -2727,6 +2841,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     second_blend_source,
                     interpolation,
                     sampling,
+                    patch: false,
                 };
                 binding.apply_default_interpolation(&ctx.module.types[ty].inner);
                 Some(binding)
@@ -105,6 +105,10 @@ pub struct EntryPoint<'a> {
     pub stage: crate::ShaderStage,
     pub early_depth_test: Option<crate::EarlyDepthTest>,
     pub workgroup_size: Option<[Option<Handle<Expression<'a>>>; 3]>,
+    /// The variable named by the `@payload` attribute of a task or mesh shader.
+    pub task_payload: Option<Ident<'a>>,
+    /// The variable named by the `@mesh` attribute of a mesh shader.
+    pub mesh_output_variable: Option<Ident<'a>>,
 }
 
 #[cfg(doc)]
@@ -292,6 +296,10 @@ pub enum StatementKind<'a> {
         function: Ident<'a>,
         arguments: Vec<Handle<Expression<'a>>>,
     },
+    DebugPrintf {
+        format: String,
+        arguments: Vec<Handle<Expression<'a>>>,
+    },
     Assign {
         target: Handle<Expression<'a>>,
         op: Option<crate::BinaryOperator>,
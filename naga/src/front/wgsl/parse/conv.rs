@@ -11,6 +11,7 @@ pub fn map_address_space(word: &str, span: Span) -> Result<crate::AddressSpace,
             access: crate::StorageAccess::default(),
         }),
         "push_constant" => Ok(crate::AddressSpace::PushConstant),
+        "task_payload" => Ok(crate::AddressSpace::TaskPayload),
         "function" => Ok(crate::AddressSpace::Function),
         _ => Err(Error::UnknownAddressSpace(span)),
     }
@@ -35,6 +36,17 @@ pub fn map_built_in(word: &str, span: Span) -> Result<crate::BuiltIn, Error<'_>>
         "local_invocation_index" => crate::BuiltIn::LocalInvocationIndex,
         "workgroup_id" => crate::BuiltIn::WorkGroupId,
         "num_workgroups" => crate::BuiltIn::NumWorkGroups,
+        // task
+        "mesh_task_size" => crate::BuiltIn::MeshTaskSize,
+        // mesh
+        "vertices" => crate::BuiltIn::Vertices,
+        "primitives" => crate::BuiltIn::Primitives,
+        "vertex_count" => crate::BuiltIn::VertexCount,
+        "primitive_count" => crate::BuiltIn::PrimitiveCount,
+        "point_index" => crate::BuiltIn::PointIndex,
+        "line_indices" => crate::BuiltIn::LineIndices,
+        "triangle_indices" => crate::BuiltIn::TriangleIndices,
+        "cull_primitive" => crate::BuiltIn::CullPrimitive,
         _ => return Err(Error::UnknownBuiltin(span)),
     })
 }
@@ -107,7 +119,10 @@ pub fn map_storage_format(word: &str, span: Span) -> Result<crate::StorageFormat
 pub fn get_scalar_type(word: &str) -> Option<Scalar> {
     use crate::ScalarKind as Sk;
     match word {
-        // "f16" => Some(Scalar { kind: Sk::Float, width: 2 }),
+        "f16" => Some(Scalar {
+            kind: Sk::Float,
+            width: 2,
+        }),
         "f32" => Some(Scalar {
             kind: Sk::Float,
             width: 4,
@@ -124,6 +139,14 @@ pub fn get_scalar_type(word: &str) -> Option<Scalar> {
             kind: Sk::Uint,
             width: 4,
         }),
+        "i64" => Some(Scalar {
+            kind: Sk::Sint,
+            width: 8,
+        }),
+        "u64" => Some(Scalar {
+            kind: Sk::Uint,
+            width: 8,
+        }),
         "bool" => Some(Scalar {
             kind: Sk::Bool,
             width: crate::BOOL_WIDTH,
@@ -252,3 +275,23 @@ pub fn map_conservative_depth(
         _ => Err(Error::UnknownConservativeDepth(span)),
     }
 }
+
+/// Resolves the escape sequences in the contents of a string token.
+pub fn unescape_string(raw: &str, span: Span) -> Result<String, Error<'_>> {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => value.push('\\'),
+            Some('"') => value.push('"'),
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            _ => return Err(Error::InvalidStringEscape(span)),
+        }
+    }
+    Ok(value)
+}
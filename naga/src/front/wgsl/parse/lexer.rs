@@ -13,6 +13,9 @@ pub enum Token<'a> {
     Attribute,
     Number(Result<Number, NumberError>),
     Word(&'a str),
+    /// The contents of a double quoted string, without the quotes and with
+    /// escape sequences left in place.
+    String(&'a str),
     Operation(char),
     LogicalOperation(char),
     ShiftOperation(char),
@@ -138,6 +141,21 @@ fn consume_token(input: &str, generic: bool) -> (Token<'_>, &str) {
             }
         }
         '~' => (Token::Operation(cur), chars.as_str()),
+        '"' => {
+            let og_chars = chars.as_str();
+            let mut escaped = false;
+            for (index, c) in og_chars.char_indices() {
+                match c {
+                    '"' if !escaped => {
+                        return (Token::String(&og_chars[..index]), &og_chars[index + 1..])
+                    }
+                    '\\' => escaped = !escaped,
+                    _ if is_comment_end(c) => break,
+                    _ => escaped = false,
+                }
+            }
+            (Token::Unknown(cur), og_chars)
+        }
         '=' | '!' => {
             let og_chars = chars.as_str();
             match chars.next() {
@@ -204,6 +222,8 @@ pub(in crate::front::wgsl) struct Lexer<'a> {
     pub(in crate::front::wgsl) source: &'a str,
     // The byte offset of the end of the last non-trivia token.
     last_end_offset: usize,
+    /// Whether the `f16` extension was enabled by an `enable` directive.
+    pub(in crate::front::wgsl) f16_enabled: bool,
 }
 
 impl<'a> Lexer<'a> {
@@ -212,6 +232,7 @@ impl<'a> Lexer<'a> {
             input,
             source: input,
             last_end_offset: 0,
+            f16_enabled: false,
         }
     }
 
@@ -377,9 +398,9 @@ impl<'a> Lexer<'a> {
     pub(in crate::front::wgsl) fn next_scalar_generic(&mut self) -> Result<Scalar, Error<'a>> {
         self.expect_generic_paren('<')?;
         let pair = match self.next() {
-            (Token::Word(word), span) => {
-                conv::get_scalar_type(word).ok_or(Error::UnknownScalarType(span))
-            }
+            (Token::Word(word), span) => self
+                .scalar_type(word, span)?
+                .ok_or(Error::UnknownScalarType(span)),
             (_, span) => Err(Error::UnknownScalarType(span)),
         }?;
         self.expect_generic_paren('>')?;
@@ -394,7 +415,8 @@ impl<'a> Lexer<'a> {
     ) -> Result<(Scalar, Span), Error<'a>> {
         self.expect_generic_paren('<')?;
         let pair = match self.next() {
-            (Token::Word(word), span) => conv::get_scalar_type(word)
+            (Token::Word(word), span) => self
+                .scalar_type(word, span)?
                 .map(|scalar| (scalar, span))
                 .ok_or(Error::UnknownScalarType(span)),
             (_, span) => Err(Error::UnknownScalarType(span)),
@@ -403,6 +425,23 @@ impl<'a> Lexer<'a> {
         Ok(pair)
     }
 
+    /// Returns the scalar type named `word`, if any, checking that the
+    /// extension it requires was enabled.
+    pub(in crate::front::wgsl) fn scalar_type(
+        &self,
+        word: &str,
+        span: Span,
+    ) -> Result<Option<Scalar>, Error<'a>> {
+        let scalar = conv::get_scalar_type(word);
+        if scalar == Some(Scalar::F16) && !self.f16_enabled {
+            return Err(Error::ExtensionNotEnabled {
+                span,
+                extension: "f16",
+            });
+        }
+        Ok(scalar)
+    }
+
     pub(in crate::front::wgsl) fn next_storage_access(
         &mut self,
     ) -> Result<crate::StorageAccess, Error<'a>> {
@@ -458,6 +497,8 @@ fn sub_test(source: &str, expected_tokens: &[Token]) {
 
 #[test]
 fn test_numbers() {
+    use half::f16;
+
     // WGSL spec examples //
 
     // decimal integer
@@ -482,14 +523,22 @@ fn test_numbers() {
             Token::Number(Ok(Number::AbstractFloat(0.01))),
             Token::Number(Ok(Number::AbstractFloat(12.34))),
             Token::Number(Ok(Number::F32(0.))),
-            Token::Number(Err(NumberError::UnimplementedF16)),
+            Token::Number(Ok(Number::F16(f16::from_f32(0.)))),
             Token::Number(Ok(Number::AbstractFloat(0.001))),
             Token::Number(Ok(Number::AbstractFloat(43.75))),
             Token::Number(Ok(Number::F32(16.))),
             Token::Number(Ok(Number::AbstractFloat(0.1875))),
-            Token::Number(Err(NumberError::UnimplementedF16)),
+            Token::Number(Ok(Number::F16(f16::from_f32(12.)))),
             Token::Number(Ok(Number::AbstractFloat(0.12109375))),
-            Token::Number(Err(NumberError::UnimplementedF16)),
+            Token::Number(Ok(Number::F16(f16::from_f32(12.5)))),
+        ],
+    );
+    sub_test(
+        "65504h 65520h 0x1.001p0h",
+        &[
+            Token::Number(Ok(Number::F16(f16::MAX))),
+            Token::Number(Err(NumberError::NotRepresentable)),
+            Token::Number(Err(NumberError::NotRepresentable)),
         ],
     );
 
@@ -533,6 +582,25 @@ fn test_numbers() {
         ],
     );
 
+    // min / max decimal 64-bit signed integer
+    sub_test(
+        "0li 9223372036854775807li 9223372036854775808li",
+        &[
+            Token::Number(Ok(Number::I64(0))),
+            Token::Number(Ok(Number::I64(i64::MAX))),
+            Token::Number(Err(NumberError::NotRepresentable)),
+        ],
+    );
+    // min / max hexadecimal 64-bit unsigned integer
+    sub_test(
+        "0x0lu 0xFFFFFFFFFFFFFFFFlu 0x10000000000000000lu",
+        &[
+            Token::Number(Ok(Number::U64(u64::MIN))),
+            Token::Number(Ok(Number::U64(u64::MAX))),
+            Token::Number(Err(NumberError::NotRepresentable)),
+        ],
+    );
+
     // min/max decimal abstract int
     sub_test(
         "0 9223372036854775807 9223372036854775808",
@@ -667,6 +735,15 @@ fn test_tokens() {
     sub_test("No¾", &[Token::Word("No"), Token::Unknown('¾')]);
     sub_test("No好", &[Token::Word("No好")]);
     sub_test("_No", &[Token::Word("_No")]);
+    sub_test(
+        r#""x = %d\n" "\"quoted\"" "\\""#,
+        &[
+            Token::String(r"x = %d\n"),
+            Token::String(r#"\"quoted\""#),
+            Token::String(r"\\"),
+        ],
+    );
+    sub_test("\"open", &[Token::Unknown('"'), Token::Word("open")]);
     sub_test(
         "*/*/***/*//=/*****//",
         &[
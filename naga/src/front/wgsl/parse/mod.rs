@@ -278,7 +278,7 @@ impl Parser {
         span: Span,
         ctx: &mut ExpressionContext<'a, '_, '_>,
     ) -> Result<Option<ast::ConstructorType<'a>>, Error<'a>> {
-        if let Some(scalar) = conv::get_scalar_type(word) {
+        if let Some(scalar) = lexer.scalar_type(word, span)? {
             return Ok(Some(ast::ConstructorType::Scalar(scalar)));
         }
 
@@ -631,6 +631,14 @@ impl Parser {
             (Token::Number(res), span) => {
                 let _ = lexer.next();
                 let num = res.map_err(|err| Error::BadNumber(span, err))?;
+                if let Number::F16(_) = num {
+                    if !lexer.f16_enabled {
+                        return Err(Error::ExtensionNotEnabled {
+                            span,
+                            extension: "f16",
+                        });
+                    }
+                }
                 ast::Expression::Literal(ast::Literal::Number(num))
             }
             (Token::Word("RAY_FLAG_NONE"), _) => {
@@ -1058,9 +1066,10 @@ impl Parser {
         &mut self,
         lexer: &mut Lexer<'a>,
         word: &'a str,
+        span: Span,
         ctx: &mut ExpressionContext<'a, '_, '_>,
     ) -> Result<Option<ast::Type<'a>>, Error<'a>> {
-        if let Some(scalar) = conv::get_scalar_type(word) {
+        if let Some(scalar) = lexer.scalar_type(word, span)? {
             return Ok(Some(ast::Type::Scalar(scalar)));
         }
 
@@ -1463,7 +1472,7 @@ impl Parser {
 
         let (name, span) = lexer.next_ident_with_span()?;
 
-        let ty = match self.type_decl_impl(lexer, name, ctx)? {
+        let ty = match self.type_decl_impl(lexer, name, span, ctx)? {
             Some(ty) => ty,
             None => {
                 ctx.unresolved.insert(ast::Dependency {
@@ -1939,6 +1948,25 @@ impl Parser {
                         lexer.expect(Token::Separator(';'))?;
                         ast::StatementKind::Kill
                     }
+                    "debugPrintf" => {
+                        let _ = lexer.next();
+                        lexer.open_arguments()?;
+                        let format = match lexer.next() {
+                            (Token::String(raw), span) => conv::unescape_string(raw, span)?,
+                            (_, span) => {
+                                return Err(Error::Unexpected(
+                                    span,
+                                    ExpectedToken::Token(Token::String("")),
+                                ))
+                            }
+                        };
+                        let mut arguments = Vec::new();
+                        while lexer.next_argument()? {
+                            arguments.push(self.general_expression(lexer, ctx)?);
+                        }
+                        lexer.expect(Token::Separator(';'))?;
+                        ast::StatementKind::DebugPrintf { format, arguments }
+                    }
                     // assignment or a function call
                     _ => {
                         self.function_call_or_assignment_statement(lexer, ctx, block)?;
@@ -2168,6 +2196,8 @@ impl Parser {
         let mut compute_span = Span::new(0, 0);
         let mut workgroup_size = ParsedAttribute::default();
         let mut early_depth_test = ParsedAttribute::default();
+        let mut task_payload = ParsedAttribute::default();
+        let mut mesh_output_variable = ParsedAttribute::default();
         let (mut bind_index, mut bind_group) =
             (ParsedAttribute::default(), ParsedAttribute::default());
 
@@ -2203,6 +2233,32 @@ impl Parser {
                     stage.set(crate::ShaderStage::Compute, name_span)?;
                     compute_span = name_span;
                 }
+                ("task", name_span) => {
+                    stage.set(crate::ShaderStage::Task, name_span)?;
+                    compute_span = name_span;
+                }
+                ("mesh", name_span) => {
+                    stage.set(crate::ShaderStage::Mesh, name_span)?;
+                    compute_span = name_span;
+                    lexer.expect(Token::Paren('('))?;
+                    let (name, span) = lexer.next_ident_with_span()?;
+                    ctx.unresolved.insert(ast::Dependency {
+                        ident: name,
+                        usage: span,
+                    });
+                    mesh_output_variable.set(ast::Ident { name, span }, name_span)?;
+                    lexer.expect(Token::Paren(')'))?;
+                }
+                ("payload", name_span) => {
+                    lexer.expect(Token::Paren('('))?;
+                    let (name, span) = lexer.next_ident_with_span()?;
+                    ctx.unresolved.insert(ast::Dependency {
+                        ident: name,
+                        usage: span,
+                    });
+                    task_payload.set(ast::Ident { name, span }, name_span)?;
+                    lexer.expect(Token::Paren(')'))?;
+                }
                 ("workgroup_size", name_span) => {
                     lexer.expect(Token::Paren('('))?;
                     let mut new_workgroup_size = [None; 3];
@@ -2292,13 +2348,19 @@ impl Parser {
                 let function = self.function_decl(lexer, out, &mut dependencies)?;
                 Some(ast::GlobalDeclKind::Fn(ast::Function {
                     entry_point: if let Some(stage) = stage.value {
-                        if stage == ShaderStage::Compute && workgroup_size.value.is_none() {
+                        let is_compute_like = matches!(
+                            stage,
+                            ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh
+                        );
+                        if is_compute_like && workgroup_size.value.is_none() {
                             return Err(Error::MissingWorkgroupSize(compute_span));
                         }
                         Some(ast::EntryPoint {
                             stage,
                             early_depth_test: early_depth_test.value,
                             workgroup_size: workgroup_size.value,
+                            task_payload: task_payload.value,
+                            mesh_output_variable: mesh_output_variable.value,
                         })
                     } else {
                         None
@@ -2329,11 +2391,42 @@ impl Parser {
         }
     }
 
+    /// Parses the list of extensions of an `enable` directive, following the
+    /// `enable` keyword.
+    fn enable_directive<'a>(&mut self, lexer: &mut Lexer<'a>) -> Result<(), Error<'a>> {
+        loop {
+            match lexer.next_ident_with_span()? {
+                ("f16", _) => lexer.f16_enabled = true,
+                (_, span) => return Err(Error::UnknownEnableExtension(span)),
+            }
+            match lexer.next() {
+                (Token::Separator(';'), _) => return Ok(()),
+                (Token::Separator(','), _) if lexer.peek().0 == Token::Separator(';') => {
+                    let _ = lexer.next();
+                    return Ok(());
+                }
+                (Token::Separator(','), _) => {}
+                (_, span) => {
+                    return Err(Error::Unexpected(
+                        span,
+                        ExpectedToken::Token(Token::Separator(';')),
+                    ))
+                }
+            }
+        }
+    }
+
     pub fn parse<'a>(&mut self, source: &'a str) -> Result<ast::TranslationUnit<'a>, Error<'a>> {
         self.reset();
 
         let mut lexer = Lexer::new(source);
         let mut tu = ast::TranslationUnit::default();
+
+        // Directives come before any declaration
+        while lexer.skip(Token::Word("enable")) {
+            self.enable_directive(&mut lexer)?;
+        }
+
         loop {
             match self.global_decl(&mut lexer, &mut tu) {
                 Err(error) => return Err(error),
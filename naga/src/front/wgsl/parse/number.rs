@@ -12,6 +12,12 @@ pub enum Number {
     I32(i32),
     /// Concrete u32
     U32(u32),
+    /// Concrete i64
+    I64(i64),
+    /// Concrete u64
+    U64(u64),
+    /// Concrete f16
+    F16(half::f16),
     /// Concrete f32
     F32(f32),
     /// Concrete f64
@@ -31,6 +37,8 @@ enum Kind {
 enum IntKind {
     I32,
     U32,
+    I64,
+    U64,
 }
 
 #[derive(Debug)]
@@ -43,9 +51,9 @@ enum FloatKind {
 // The following regexes (from the WGSL spec) will be matched:
 
 // int_literal:
-// | / 0                                                                [iu]?   /
-// | / [1-9][0-9]*                                                      [iu]?   /
-// | / 0[xX][0-9a-fA-F]+                                                [iu]?   /
+// | / 0                                                                (l?[iu])?   /
+// | / [1-9][0-9]*                                                      (l?[iu])?   /
+// | / 0[xX][0-9a-fA-F]+                                                (l?[iu])?   /
 
 // decimal_float_literal:
 // | / 0                                                                [fh]    /
@@ -210,7 +218,12 @@ fn parse(input: &str) -> (Result<Number, NumberError>, &str) {
                     rest_to_str!(bytes),
                 )
             } else {
-                let kind = consume_map!(bytes, [b'i' => IntKind::I32, b'u' => IntKind::U32]);
+                let kind = consume_map!(bytes, [
+                    b'i' => IntKind::I32,
+                    b'u' => IntKind::U32,
+                    b'l', b'i' => IntKind::I64,
+                    b'l', b'u' => IntKind::U64,
+                ]);
 
                 (parse_hex_int(digits, kind), rest_to_str!(bytes))
             }
@@ -270,6 +283,8 @@ fn parse(input: &str) -> (Result<Number, NumberError>, &str) {
                 let kind = consume_map!(bytes, [
                     b'i' => Kind::Int(IntKind::I32),
                     b'u' => Kind::Int(IntKind::U32),
+                    b'l', b'i' => Kind::Int(IntKind::I64),
+                    b'l', b'u' => Kind::Int(IntKind::U64),
                     b'h' => Kind::Float(FloatKind::F16),
                     b'f' => Kind::Float(FloatKind::F32),
                     b'l', b'f' => Kind::Float(FloatKind::F64),
@@ -354,7 +369,17 @@ fn parse_hex_float(input: &str, kind: Option<FloatKind>) -> Result<Number, Numbe
             // can only be ParseHexfErrorKind::Inexact but we can't check since it's private
             _ => Err(NumberError::NotRepresentable),
         },
-        Some(FloatKind::F16) => Err(NumberError::UnimplementedF16),
+        Some(FloatKind::F16) => match hexf_parse::parse_hexf64(input, false) {
+            // hexf_parse has no f16 parser, so check that the f64 is exact
+            // once converted ourselves
+            Ok(num) => {
+                let num16 = half::f16::from_f64(num);
+                (f64::from(num16) == num)
+                    .then_some(Number::F16(num16))
+                    .ok_or(NumberError::NotRepresentable)
+            }
+            _ => Err(NumberError::NotRepresentable),
+        },
         Some(FloatKind::F32) => match hexf_parse::parse_hexf32(input, false) {
             Ok(num) => Ok(Number::F32(num)),
             // can only be ParseHexfErrorKind::Inexact but we can't check since it's private
@@ -390,7 +415,12 @@ fn parse_dec_float(input: &str, kind: Option<FloatKind>) -> Result<Number, Numbe
                 .then_some(Number::F64(num))
                 .ok_or(NumberError::NotRepresentable)
         }
-        Some(FloatKind::F16) => Err(NumberError::UnimplementedF16),
+        Some(FloatKind::F16) => {
+            let num = half::f16::from_f64(input.parse::<f64>().unwrap()); // will never fail
+            num.is_finite()
+                .then_some(Number::F16(num))
+                .ok_or(NumberError::NotRepresentable)
+        }
     }
 }
 
@@ -416,5 +446,13 @@ fn parse_int(input: &str, kind: Option<IntKind>, radix: u32) -> Result<Number, N
             Ok(num) => Ok(Number::U32(num)),
             Err(e) => Err(map_err(e)),
         },
+        Some(IntKind::I64) => match i64::from_str_radix(input, radix) {
+            Ok(num) => Ok(Number::I64(num)),
+            Err(e) => Err(map_err(e)),
+        },
+        Some(IntKind::U64) => match u64::from_str_radix(input, radix) {
+            Ok(num) => Ok(Number::U64(num)),
+            Err(e) => Err(map_err(e)),
+        },
     }
 }
@@ -25,6 +25,16 @@ fn parse_types() {
     parse_str("var t: texture_storage_3d<r32float,read>;").unwrap();
 }
 
+#[test]
+fn parse_enable() {
+    parse_str("enable f16; const a : f16 = 1.5h;").unwrap();
+    parse_str("enable f16,; var<private> a : vec2<f16>;").unwrap();
+    assert!(parse_str("const a : f16 = 1.5;").is_err());
+    assert!(parse_str("const a = 1.5h;").is_err());
+    assert!(parse_str("enable f64;").is_err());
+    assert!(parse_str("const a = 1; enable f16;").is_err());
+}
+
 #[test]
 fn parse_type_inference() {
     parse_str(
@@ -283,11 +283,13 @@ mod block;
 pub mod compact;
 pub mod front;
 pub mod keywords;
+mod link;
 pub mod proc;
 mod span;
 pub mod valid;
 
 pub use crate::arena::{Arena, Handle, Range, UniqueArena};
+pub use crate::link::{link, LinkError};
 
 pub use crate::span::{SourceLocation, Span, SpanContext, WithSpan};
 #[cfg(feature = "arbitrary")]
@@ -384,6 +386,11 @@ pub enum ShaderStage {
     Vertex,
     Fragment,
     Compute,
+    Task,
+    Mesh,
+    Geometry,
+    TessellationControl,
+    TessellationEvaluation,
 }
 
 /// Addressing space of variables.
@@ -406,6 +413,9 @@ pub enum AddressSpace {
     Handle,
     /// Push constants.
     PushConstant,
+    /// Data written by a task shader workgroup, and read by the mesh shader
+    /// workgroups it launches.
+    TaskPayload,
 }
 
 /// Built-in inputs and outputs.
@@ -414,7 +424,9 @@ pub enum AddressSpace {
 #[cfg_attr(feature = "deserialize", derive(Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub enum BuiltIn {
-    Position { invariant: bool },
+    Position {
+        invariant: bool,
+    },
     ViewIndex,
     // vertex
     BaseInstance,
@@ -438,6 +450,46 @@ pub enum BuiltIn {
     WorkGroupId,
     WorkGroupSize,
     NumWorkGroups,
+    // geometry
+    /// The index of the invocation of a geometry shader, when it is run more
+    /// than once per primitive, or of a tessellation control shader, which is
+    /// the vertex of the output patch it computes.
+    InvocationId,
+    // tessellation
+    /// The tessellation levels of the outer edges and of the inside of a
+    /// patch, output by a tessellation control shader.
+    TessLevelOuter,
+    TessLevelInner,
+    /// The coordinates of a vertex generated by the tessellator, within the
+    /// patch it subdivides.
+    TessCoord,
+    /// The number of vertices of the input patch of a tessellation stage.
+    PatchVertices,
+    // task
+    /// The number of mesh shader workgroups launched by a task shader
+    /// workgroup, returned by the task shader.
+    MeshTaskSize,
+    // mesh
+    /// The members of the struct of a [`MeshStageInfo::output_variable`].
+    Vertices,
+    Primitives,
+    VertexCount,
+    PrimitiveCount,
+    /// The indices of the vertices of a primitive output by a mesh shader.
+    PointIndex,
+    LineIndices,
+    TriangleIndices,
+    /// Whether a primitive output by a mesh shader is discarded.
+    CullPrimitive,
+    // subgroup
+    /// The number of subgroups in the workgroup of a compute invocation, and
+    /// the index of its subgroup within the workgroup.
+    NumSubgroups,
+    SubgroupId,
+    /// The number of invocations in a subgroup, and the index of an
+    /// invocation within its subgroup.
+    SubgroupSize,
+    SubgroupInvocationId,
 }
 
 /// Number of bytes per scalar.
@@ -882,14 +934,24 @@ pub enum Literal {
     F64(f64),
     /// May not be NaN or infinity.
     F32(f32),
+    /// May not be NaN or infinity.
+    F16(#[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_f16))] half::f16),
     U32(u32),
     I32(i32),
     I64(i64),
+    U64(u64),
     Bool(bool),
     AbstractInt(i64),
     AbstractFloat(f64),
 }
 
+/// Generates the value of a [`Literal::F16`] from its bits, since `half`
+/// doesn't implement `Arbitrary`.
+#[cfg(feature = "arbitrary")]
+fn arbitrary_f16(u: &mut arbitrary::Unstructured) -> arbitrary::Result<half::f16> {
+    Ok(half::f16::from_bits(u.arbitrary()?))
+}
+
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "clone", derive(Clone))]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
@@ -959,6 +1021,10 @@ pub enum Binding {
         second_blend_source: bool,
         interpolation: Option<Interpolation>,
         sampling: Option<Sampling>,
+        /// Indicates a value per patch rather than per vertex, output by a
+        /// tessellation control shader and input by a tessellation evaluation
+        /// shader.
+        patch: bool,
     },
 }
 
@@ -1111,6 +1177,60 @@ pub enum AtomicFunction {
     Exchange { compare: Option<Handle<Expression>> },
 }
 
+/// An operation combining the values of the invocations of a subgroup, used
+/// by [`Statement::SubgroupCollectiveOperation`].
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum SubgroupOperation {
+    All = 0,
+    Any = 1,
+    Add = 2,
+    Mul = 3,
+    Min = 4,
+    Max = 5,
+    And = 6,
+    Or = 7,
+    Xor = 8,
+}
+
+/// Which invocations of a subgroup a [`SubgroupOperation`] combines the
+/// values of.
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum CollectiveOperation {
+    /// Every active invocation of the subgroup.
+    Reduce = 0,
+    /// The active invocations with an id lower than or equal to this one.
+    InclusiveScan = 1,
+    /// The active invocations with an id lower than this one.
+    ExclusiveScan = 2,
+}
+
+/// Which invocation of a subgroup a [`Statement::SubgroupGather`] reads the
+/// value of.
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum GatherMode {
+    /// All gather from the active lane with the smallest index
+    BroadcastFirst,
+    /// All gather from the same lane at the index given by the expression
+    Broadcast(Handle<Expression>),
+    /// Each gathers from a different lane at the index given by the expression
+    Shuffle(Handle<Expression>),
+    /// Each gathers from their lane plus the shift given by the expression
+    ShuffleDown(Handle<Expression>),
+    /// Each gathers from their lane minus the shift given by the expression
+    ShuffleUp(Handle<Expression>),
+    /// Each gathers from their lane xored with the given by the expression
+    ShuffleXor(Handle<Expression>),
+}
+
 /// Hint at which precision to compute a derivative.
 #[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
@@ -1295,6 +1415,8 @@ bitflags::bitflags! {
         const STORAGE = 0x1;
         /// Barrier affects all `AddressSpace::WorkGroup` accesses.
         const WORK_GROUP = 0x2;
+        /// Barrier synchronizes execution across all invocations within a subgroup that execute this instruction.
+        const SUB_GROUP = 0x4;
     }
 }
 
@@ -1593,6 +1715,15 @@ pub enum Expression {
         query: Handle<Expression>,
         committed: bool,
     },
+    /// Result of a [`SubgroupBallot`] statement.
+    ///
+    /// [`SubgroupBallot`]: Statement::SubgroupBallot
+    SubgroupBallotResult,
+    /// Result of a [`SubgroupCollectiveOperation`] or [`SubgroupGather`] statement.
+    ///
+    /// [`SubgroupCollectiveOperation`]: Statement::SubgroupCollectiveOperation
+    /// [`SubgroupGather`]: Statement::SubgroupGather
+    SubgroupOperationResult { ty: Handle<Type> },
 }
 
 pub use block::Block;
@@ -1865,6 +1996,66 @@ pub enum Statement {
         /// The specific operation we're performing on `query`.
         fun: RayQueryFunction,
     },
+    /// Emits a vertex from a geometry shader.
+    ///
+    /// The `value` is the vertex, and must be of the
+    /// [`GeometryStageInfo::vertex_output_type`] of the entry point.
+    EmitVertex { value: Handle<Expression> },
+    /// Ends the primitive being output by a geometry shader, so that the
+    /// next emitted vertex starts a new one.
+    EndPrimitive,
+    /// Prints a formatted message for debugging purposes.
+    ///
+    /// The `format` string follows the conventions of `debugPrintfEXT`: every
+    /// `%d`, `%i`, `%u`, `%x`, `%X`, `%f`, `%e`, `%g` or `%c` conversion
+    /// consumes one argument, `%v<N><conversion>` consumes a vector of `N`
+    /// components and `%%` prints a percent sign.
+    ///
+    /// Every argument must be a scalar or vector of 32-bit integers or floats.
+    ///
+    /// Backends that cannot print from shaders natively expect this statement
+    /// to be lowered beforehand, see [`proc::lower_debug_printf`].
+    ///
+    /// [`proc::lower_debug_printf`]: crate::proc::lower_debug_printf
+    DebugPrintf {
+        /// The format string.
+        format: String,
+        /// The values substituted into `format`.
+        arguments: Vec<Handle<Expression>>,
+    },
+    /// Calculate a bitmask using a boolean from each active thread in the subgroup
+    SubgroupBallot {
+        /// The [`SubgroupBallotResult`] expression representing this ballot's result.
+        ///
+        /// [`SubgroupBallotResult`]: Expression::SubgroupBallotResult
+        result: Handle<Expression>,
+        /// The value from this thread to store in the ballot
+        predicate: Option<Handle<Expression>>,
+    },
+    /// Gather a value from another active thread in the subgroup
+    SubgroupGather {
+        /// Specifies which thread to gather from
+        mode: GatherMode,
+        /// The value to broadcast over
+        argument: Handle<Expression>,
+        /// The [`SubgroupOperationResult`] expression representing this gather's result.
+        ///
+        /// [`SubgroupOperationResult`]: Expression::SubgroupOperationResult
+        result: Handle<Expression>,
+    },
+    /// Compute a collective operation across all active threads in the subgroup
+    SubgroupCollectiveOperation {
+        /// What operation to compute
+        op: SubgroupOperation,
+        /// How to combine the results
+        collective_op: CollectiveOperation,
+        /// The value to compute over
+        argument: Handle<Expression>,
+        /// The [`SubgroupOperationResult`] expression representing this operation's result.
+        ///
+        /// [`SubgroupOperationResult`]: Expression::SubgroupOperationResult
+        result: Handle<Expression>,
+    },
 }
 
 /// A function argument.
@@ -1975,12 +2166,206 @@ pub struct EntryPoint {
     pub stage: ShaderStage,
     /// Early depth test for fragment stages.
     pub early_depth_test: Option<EarlyDepthTest>,
-    /// Workgroup size for compute stages
+    /// Workgroup size for compute, task and mesh stages
     pub workgroup_size: [u32; 3],
+    /// The task payload written by a task stage, or read by a mesh stage.
+    ///
+    /// The variable must be in the [`AddressSpace::TaskPayload`] address
+    /// space.
+    pub task_payload: Option<Handle<GlobalVariable>>,
+    /// The output of a mesh stage.
+    pub mesh_info: Option<MeshStageInfo>,
+    /// The primitives consumed and produced by a geometry stage.
+    pub geometry_info: Option<GeometryStageInfo>,
+    /// The patches processed by a tessellation stage.
+    pub tessellation_info: Option<TessellationStageInfo>,
     /// The entrance function.
     pub function: Function,
 }
 
+/// The topology of the primitives output by a mesh shader.
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[allow(missing_docs)] // The names are self evident
+pub enum MeshOutputTopology {
+    Points,
+    Lines,
+    Triangles,
+}
+
+/// The output of a mesh shader.
+///
+/// Instead of returning its output, a mesh shader workgroup writes it to
+/// [`output_variable`], a workgroup variable whose type is a struct with the
+/// following members:
+///
+/// - an array of [`vertex_output_type`] with the [`BuiltIn::Vertices`]
+///   binding, holding [`max_vertices`] vertices,
+///
+/// - an array of [`primitive_output_type`] with the [`BuiltIn::Primitives`]
+///   binding, holding [`max_primitives`] primitives,
+///
+/// - a `u32` with the [`BuiltIn::VertexCount`] binding, and
+///
+/// - a `u32` with the [`BuiltIn::PrimitiveCount`] binding.
+///
+/// The vertex and primitive counts are the number of vertices and primitives
+/// actually output by the workgroup, when it finishes.
+///
+/// The members of [`vertex_output_type`] have the same bindings as the result
+/// of a vertex shader. The members of [`primitive_output_type`] are the
+/// indices of the vertices of the primitive, with the
+/// [`BuiltIn::PointIndex`], [`BuiltIn::LineIndices`] or
+/// [`BuiltIn::TriangleIndices`] binding depending on [`topology`], and
+/// optionally a `bool` with the [`BuiltIn::CullPrimitive`] binding.
+///
+/// [`output_variable`]: MeshStageInfo::output_variable
+/// [`vertex_output_type`]: MeshStageInfo::vertex_output_type
+/// [`primitive_output_type`]: MeshStageInfo::primitive_output_type
+/// [`max_vertices`]: MeshStageInfo::max_vertices
+/// [`max_primitives`]: MeshStageInfo::max_primitives
+/// [`topology`]: MeshStageInfo::topology
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct MeshStageInfo {
+    pub topology: MeshOutputTopology,
+    pub max_vertices: u32,
+    pub max_primitives: u32,
+    pub vertex_output_type: Handle<Type>,
+    pub primitive_output_type: Handle<Type>,
+    pub output_variable: Handle<GlobalVariable>,
+}
+
+/// The topology of the primitives consumed by a geometry shader.
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[allow(missing_docs)] // The names are self evident
+pub enum GeometryInputPrimitive {
+    Points,
+    Lines,
+    LinesAdjacency,
+    Triangles,
+    TrianglesAdjacency,
+}
+
+/// The topology of the primitives output by a geometry shader.
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[allow(missing_docs)] // The names are self evident
+pub enum GeometryOutputPrimitive {
+    Points,
+    LineStrip,
+    TriangleStrip,
+}
+
+/// The primitives consumed and produced by a geometry shader.
+///
+/// A geometry shader is run for every primitive of [`input_primitive`]
+/// topology. Its arguments with a [`Location`] binding, or a
+/// [`BuiltIn::Position`], [`BuiltIn::PointSize`], [`BuiltIn::ClipDistance`]
+/// or [`BuiltIn::CullDistance`] binding, are arrays holding the value for
+/// every vertex of the primitive.
+///
+/// Instead of returning its output, a geometry shader emits vertices of type
+/// [`vertex_output_type`] with [`Statement::EmitVertex`], which are assembled
+/// into primitives of [`output_primitive`] topology until the next
+/// [`Statement::EndPrimitive`]. The members of [`vertex_output_type`] have the
+/// same bindings as the result of a vertex shader, and may also hold a
+/// [`BuiltIn::PrimitiveIndex`].
+///
+/// [`input_primitive`]: GeometryStageInfo::input_primitive
+/// [`output_primitive`]: GeometryStageInfo::output_primitive
+/// [`vertex_output_type`]: GeometryStageInfo::vertex_output_type
+/// [`Location`]: Binding::Location
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct GeometryStageInfo {
+    pub input_primitive: GeometryInputPrimitive,
+    pub output_primitive: GeometryOutputPrimitive,
+    /// The maximum number of vertices emitted by an invocation.
+    pub max_vertices: u32,
+    /// The number of times the shader is run for every primitive, see
+    /// [`BuiltIn::InvocationId`].
+    pub invocations: u32,
+    pub vertex_output_type: Handle<Type>,
+}
+
+/// The primitives a patch is subdivided into by the tessellator.
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[allow(missing_docs)] // The names are self evident
+pub enum TessellationPrimitive {
+    Triangles,
+    Quads,
+    Isolines,
+}
+
+/// How the tessellation levels of a patch are rounded into a number of
+/// segments.
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[allow(missing_docs)] // The names are self evident
+pub enum TessellationSpacing {
+    Equal,
+    FractionalEven,
+    FractionalOdd,
+}
+
+/// The patches processed by a tessellation stage.
+///
+/// A tessellation control shader is run for each of the vertices of the
+/// patches it outputs, which it can tell apart with [`BuiltIn::InvocationId`].
+/// Its arguments holding a value per vertex, see [`Binding::is_per_vertex`],
+/// are arrays with a value for each vertex of the input patch. It returns the
+/// values of its own vertex, along with the values of the whole patch: the
+/// members of its result with a [`Location`] binding whose `patch` is set, or
+/// the [`BuiltIn::TessLevelOuter`] and [`BuiltIn::TessLevelInner`] bindings.
+/// The values of the patch are only output by the invocation whose
+/// [`BuiltIn::InvocationId`] is 0.
+///
+/// A tessellation evaluation shader is run for each vertex generated by the
+/// tessellator. Its arguments holding a value per vertex are arrays with a
+/// value for each vertex of the patch output by the control shader, and its
+/// other arguments hold the values of the patch. It returns the same outputs
+/// as a vertex shader.
+///
+/// [`Location`]: Binding::Location
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum TessellationStageInfo {
+    Control {
+        /// The number of vertices of the output patches.
+        output_vertices: u32,
+    },
+    Evaluation {
+        primitive: TessellationPrimitive,
+        spacing: TessellationSpacing,
+        /// Whether the generated triangles are wound clockwise.
+        clockwise: bool,
+        /// Whether the tessellator generates points rather than
+        /// [`primitive`]s.
+        ///
+        /// [`primitive`]: TessellationStageInfo::Evaluation::primitive
+        point_mode: bool,
+    },
+}
+
 /// Return types predeclared for the frexp, modf, and atomicCompareExchangeWeak built-in functions.
 ///
 /// These cannot be spelled in WGSL source.
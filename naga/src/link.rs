@@ -0,0 +1,676 @@
+/*!
+Linking of several modules into one.
+
+Functions that only have a declaration in a module, like the prototypes of the
+GLSL frontend, are resolved to the function with the same name and argument
+types defined by another module.
+
+[`link`] is the entry point.
+*/
+
+use crate::arena::{Arena, Handle};
+use crate::{
+    Block, Constant, EntryPoint, Expression, FastHashMap, Function, GlobalVariable, Module,
+    Override, PredeclaredType, Statement, Type, TypeInner,
+};
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum LinkError {
+    #[error("Constant `{0}` is defined with different types or values")]
+    ConflictingConstant(String),
+    #[error("Global variable `{0}` is declared differently by several modules")]
+    ConflictingGlobal(String),
+    #[error("Function `{0}` is called but defined more than once with the same arguments")]
+    ConflictingFunction(String),
+    #[error("Function `{0}` is declared with a different result than its definition")]
+    SignatureMismatch(String),
+    #[error("Function `{0}` is called but never defined")]
+    UndefinedFunction(String),
+    #[error("Function `{0}` calls itself through other modules")]
+    RecursiveFunction(String),
+    #[error("Entry point `{0}` is defined more than once for the same stage")]
+    ConflictingEntryPoint(String),
+}
+
+/// Combines `modules` into a single module.
+///
+/// - Identical types are merged.
+///
+/// - Named constants and global variables are shared by all the modules that
+///   declare them. Declaring them with different types, values, address
+///   spaces or bindings is an error.
+///
+/// - A function whose body is empty is a declaration. Calls to it are
+///   resolved to the function with the same name and argument types defined
+///   by one of the modules, which must have the same result. Functions that
+///   aren't called through a declaration, like the `main` functions of GLSL
+///   shaders, may share their name.
+///
+/// - The entry points of all the modules are kept.
+///
+/// The modules must have passed validation. Functions and constants that end
+/// up unused aren't removed, compaction can take care of them.
+pub fn link(modules: &[Module]) -> Result<Module, LinkError> {
+    let mut linker = Linker::default();
+    for source in modules {
+        let map = linker.add_declarations(source)?;
+        linker.maps.push(map);
+    }
+
+    // Find the definition for every name and list of argument types
+    for (index, source) in modules.iter().enumerate() {
+        for (handle, function) in source.functions.iter() {
+            let Some(key) = linker.function_key(index, function) else {
+                continue;
+            };
+            if function.body.is_empty() {
+                continue;
+            }
+            linker
+                .definitions
+                .entry(key)
+                .or_default()
+                .push((index, handle));
+        }
+    }
+
+    for (index, source) in modules.iter().enumerate() {
+        for (handle, function) in source.functions.iter() {
+            // Declarations are only resolved when they are called
+            if function.body.is_empty() && function.name.is_some() {
+                continue;
+            }
+            linker.add_function(modules, index, handle)?;
+        }
+    }
+
+    for (index, source) in modules.iter().enumerate() {
+        for ep in source.entry_points.iter() {
+            if linker
+                .module
+                .entry_points
+                .iter()
+                .any(|other| other.name == ep.name && other.stage == ep.stage)
+            {
+                return Err(LinkError::ConflictingEntryPoint(ep.name.clone()));
+            }
+            let mut callees = Vec::new();
+            collect_calls(&ep.function.body, &mut callees);
+            for callee in callees {
+                linker.add_function(modules, index, callee)?;
+            }
+            let function = linker.map_function(index, &ep.function);
+            let map = &linker.maps[index];
+            let mesh_info = ep.mesh_info.as_ref().map(|info| crate::MeshStageInfo {
+                vertex_output_type: map.types[info.vertex_output_type.index()],
+                primitive_output_type: map.types[info.primitive_output_type.index()],
+                output_variable: map.global_variables[info.output_variable.index()],
+                ..info.clone()
+            });
+            let geometry_info = ep
+                .geometry_info
+                .as_ref()
+                .map(|info| crate::GeometryStageInfo {
+                    vertex_output_type: map.types[info.vertex_output_type.index()],
+                    ..info.clone()
+                });
+            linker.module.entry_points.push(EntryPoint {
+                name: ep.name.clone(),
+                stage: ep.stage,
+                early_depth_test: ep.early_depth_test,
+                workgroup_size: ep.workgroup_size,
+                task_payload: ep
+                    .task_payload
+                    .map(|global| map.global_variables[global.index()]),
+                mesh_info,
+                geometry_info,
+                tessellation_info: ep.tessellation_info.clone(),
+                function,
+            });
+        }
+    }
+
+    Ok(linker.module)
+}
+
+/// Where the function of a source module ended up in the linked module.
+#[derive(Clone, Copy)]
+enum FunctionState {
+    Pending,
+    /// The function, or the functions it calls, are being added.
+    InProgress,
+    Added(Handle<Function>),
+}
+
+/// Handles of the linked module, indexed by the handles of a source module.
+struct ModuleMap {
+    types: Vec<Handle<Type>>,
+    constants: Vec<Option<Handle<Constant>>>,
+    const_expressions: Vec<Handle<Expression>>,
+    global_variables: Vec<Handle<GlobalVariable>>,
+    functions: Vec<FunctionState>,
+}
+
+#[derive(Default)]
+struct Linker {
+    module: Module,
+    maps: Vec<ModuleMap>,
+    named_constants: FastHashMap<String, Handle<Constant>>,
+    named_globals: FastHashMap<String, Handle<GlobalVariable>>,
+    /// Location of the definition of each function, by name and argument types.
+    definitions: FastHashMap<(String, Vec<Handle<Type>>), Vec<(usize, Handle<Function>)>>,
+}
+
+impl Linker {
+    /// Adds the types, constants and global variables of `source`.
+    fn add_declarations(&mut self, source: &Module) -> Result<ModuleMap, LinkError> {
+        let mut map = ModuleMap {
+            types: Vec::with_capacity(source.types.len()),
+            constants: vec![None; source.constants.len()],
+            const_expressions: Vec::with_capacity(source.const_expressions.len()),
+            global_variables: Vec::with_capacity(source.global_variables.len()),
+            functions: vec![FunctionState::Pending; source.functions.len()],
+        };
+
+        for (handle, ty) in source.types.iter() {
+            let mut inner = ty.inner.clone();
+            match inner {
+                TypeInner::Pointer { ref mut base, .. }
+                | TypeInner::Array { ref mut base, .. }
+                | TypeInner::BindingArray { ref mut base, .. } => *base = map.types[base.index()],
+                TypeInner::Struct {
+                    ref mut members, ..
+                } => {
+                    for member in members.iter_mut() {
+                        member.ty = map.types[member.ty.index()];
+                    }
+                }
+                _ => {}
+            }
+            let new_ty = Type {
+                name: ty.name.clone(),
+                inner,
+            };
+            let span = source.types.get_span(handle);
+            map.types.push(self.module.types.insert(new_ty, span));
+        }
+
+        let special = &source.special_types;
+        let ray_desc = special.ray_desc.map(|ty| map.types[ty.index()]);
+        let ray_intersection = special.ray_intersection.map(|ty| map.types[ty.index()]);
+        let special_types = &mut self.module.special_types;
+        special_types.ray_desc = special_types.ray_desc.or(ray_desc);
+        special_types.ray_intersection = special_types.ray_intersection.or(ray_intersection);
+        for (predeclared, &ty) in special.predeclared_types.iter() {
+            // `PredeclaredType` is only `Clone` with the `clone` feature
+            let predeclared = match *predeclared {
+                PredeclaredType::AtomicCompareExchangeWeakResult(scalar) => {
+                    PredeclaredType::AtomicCompareExchangeWeakResult(scalar)
+                }
+                PredeclaredType::ModfResult { size, width } => {
+                    PredeclaredType::ModfResult { size, width }
+                }
+                PredeclaredType::FrexpResult { size, width } => {
+                    PredeclaredType::FrexpResult { size, width }
+                }
+            };
+            special_types
+                .predeclared_types
+                .entry(predeclared)
+                .or_insert(map.types[ty.index()]);
+        }
+
+        // Constants are added when their first use is found, but always after
+        // their initializer.
+        for (handle, expr) in source.const_expressions.iter() {
+            if let Expression::Constant(constant) = *expr {
+                self.add_constant(source, &mut map, constant)?;
+            }
+            let expr = map_expression(expr, &map, &[]);
+            let span = source.const_expressions.get_span(handle);
+            map.const_expressions
+                .push(self.module.const_expressions.append(expr, span));
+        }
+        for (handle, _) in source.constants.iter() {
+            self.add_constant(source, &mut map, handle)?;
+        }
+
+        for (handle, global) in source.global_variables.iter() {
+            let new_global = GlobalVariable {
+                name: global.name.clone(),
+                space: global.space,
+                binding: global.binding.clone(),
+                ty: map.types[global.ty.index()],
+                init: global.init.map(|init| map.const_expressions[init.index()]),
+            };
+
+            if let Some(ref name) = global.name {
+                if let Some(&existing) = self.named_globals.get(name) {
+                    let other = &self.module.global_variables[existing];
+                    let same_init = match (other.init, new_global.init) {
+                        (None, None) => true,
+                        (Some(a), Some(b)) => self.const_expressions_equal(a, b),
+                        _ => false,
+                    };
+                    if other.space != new_global.space
+                        || other.binding != new_global.binding
+                        || other.ty != new_global.ty
+                        || !same_init
+                    {
+                        return Err(LinkError::ConflictingGlobal(name.clone()));
+                    }
+                    map.global_variables.push(existing);
+                    continue;
+                }
+            }
+
+            let span = source.global_variables.get_span(handle);
+            let new_handle = self.module.global_variables.append(new_global, span);
+            if let Some(ref name) = global.name {
+                self.named_globals.insert(name.clone(), new_handle);
+            }
+            map.global_variables.push(new_handle);
+        }
+
+        Ok(map)
+    }
+
+    /// Adds the constant `handle` of `source`, if it wasn't already.
+    ///
+    /// Its initializer must have been added already.
+    fn add_constant(
+        &mut self,
+        source: &Module,
+        map: &mut ModuleMap,
+        handle: Handle<Constant>,
+    ) -> Result<(), LinkError> {
+        if map.constants[handle.index()].is_some() {
+            return Ok(());
+        }
+
+        let constant = &source.constants[handle];
+        let new_constant = Constant {
+            name: constant.name.clone(),
+            r#override: match constant.r#override {
+                Override::None => Override::None,
+                Override::ByName => Override::ByName,
+                Override::ByNameOrId(id) => Override::ByNameOrId(id),
+            },
+            ty: map.types[constant.ty.index()],
+            init: map.const_expressions[constant.init.index()],
+        };
+
+        if let Some(ref name) = constant.name {
+            if let Some(&existing) = self.named_constants.get(name) {
+                let other = &self.module.constants[existing];
+                if other.ty != new_constant.ty
+                    || other.r#override != new_constant.r#override
+                    || !self.const_expressions_equal(other.init, new_constant.init)
+                {
+                    return Err(LinkError::ConflictingConstant(name.clone()));
+                }
+                map.constants[handle.index()] = Some(existing);
+                return Ok(());
+            }
+        }
+
+        let span = source.constants.get_span(handle);
+        let new_handle = self.module.constants.append(new_constant, span);
+        if let Some(ref name) = constant.name {
+            self.named_constants.insert(name.clone(), new_handle);
+        }
+        map.constants[handle.index()] = Some(new_handle);
+        Ok(())
+    }
+
+    /// Checks if two constant expressions of the linked module have the same
+    /// value.
+    fn const_expressions_equal(&self, a: Handle<Expression>, b: Handle<Expression>) -> bool {
+        if a == b {
+            return true;
+        }
+        let constants = &self.module.constants;
+        match (
+            &self.module.const_expressions[a],
+            &self.module.const_expressions[b],
+        ) {
+            (&Expression::Constant(a), &Expression::Constant(b)) => {
+                self.const_expressions_equal(constants[a].init, constants[b].init)
+            }
+            (&Expression::Constant(a), _) => self.const_expressions_equal(constants[a].init, b),
+            (_, &Expression::Constant(b)) => self.const_expressions_equal(a, constants[b].init),
+            (
+                &Expression::Compose {
+                    ty: a_ty,
+                    components: ref a_components,
+                },
+                &Expression::Compose {
+                    ty: b_ty,
+                    components: ref b_components,
+                },
+            ) => {
+                a_ty == b_ty
+                    && a_components.len() == b_components.len()
+                    && a_components
+                        .iter()
+                        .zip(b_components.iter())
+                        .all(|(&a, &b)| self.const_expressions_equal(a, b))
+            }
+            (
+                &Expression::Splat {
+                    size: a_size,
+                    value: a_value,
+                },
+                &Expression::Splat {
+                    size: b_size,
+                    value: b_value,
+                },
+            ) => a_size == b_size && self.const_expressions_equal(a_value, b_value),
+            (a, b) => a == b,
+        }
+    }
+
+    /// Returns the name and the argument types of `function`, from the module
+    /// at `index`, used to match declarations to definitions.
+    fn function_key(
+        &self,
+        index: usize,
+        function: &Function,
+    ) -> Option<(String, Vec<Handle<Type>>)> {
+        let map = &self.maps[index];
+        let name = function.name.clone()?;
+        let arguments = function
+            .arguments
+            .iter()
+            .map(|argument| map.types[argument.ty.index()])
+            .collect();
+        Some((name, arguments))
+    }
+
+    /// Adds the function `handle` of the module at `index`, after the functions
+    /// it calls, and returns its handle in the linked module.
+    ///
+    /// Declarations return the handle of their definition.
+    fn add_function(
+        &mut self,
+        modules: &[Module],
+        index: usize,
+        handle: Handle<Function>,
+    ) -> Result<Handle<Function>, LinkError> {
+        let source = &modules[index];
+        let function = &source.functions[handle];
+        match self.maps[index].functions[handle.index()] {
+            FunctionState::Added(new_handle) => return Ok(new_handle),
+            FunctionState::InProgress => {
+                return Err(LinkError::RecursiveFunction(
+                    function.name.clone().unwrap_or_default(),
+                ))
+            }
+            FunctionState::Pending => {}
+        }
+        self.maps[index].functions[handle.index()] = FunctionState::InProgress;
+
+        let new_handle = if function.body.is_empty() && function.name.is_some() {
+            let key = self.function_key(index, function).unwrap();
+            let (def_index, def_handle) = match self.definitions.get(&key).map(Vec::as_slice) {
+                Some(&[definition]) => definition,
+                Some(&[_, _, ..]) => return Err(LinkError::ConflictingFunction(key.0)),
+                _ => return Err(LinkError::UndefinedFunction(key.0)),
+            };
+            let definition = &modules[def_index].functions[def_handle];
+            let map = &self.maps[index];
+            let def_map = &self.maps[def_index];
+            let same_result = match (&function.result, &definition.result) {
+                (&None, &None) => true,
+                (&Some(ref a), &Some(ref b)) => {
+                    map.types[a.ty.index()] == def_map.types[b.ty.index()] && a.binding == b.binding
+                }
+                _ => false,
+            };
+            if !same_result {
+                return Err(LinkError::SignatureMismatch(key.0));
+            }
+            self.add_function(modules, def_index, def_handle)?
+        } else {
+            let mut callees = Vec::new();
+            collect_calls(&function.body, &mut callees);
+            for callee in callees {
+                self.add_function(modules, index, callee)?;
+            }
+
+            let new_function = self.map_function(index, function);
+            let span = source.functions.get_span(handle);
+            self.module.functions.append(new_function, span)
+        };
+
+        self.maps[index].functions[handle.index()] = FunctionState::Added(new_handle);
+        Ok(new_handle)
+    }
+
+    /// Returns a copy of `function`, from the module at `index`, that refers to
+    /// the handles of the linked module.
+    ///
+    /// All the functions it calls must have been added.
+    fn map_function(&self, index: usize, function: &Function) -> Function {
+        let map = &self.maps[index];
+        let functions: Vec<_> = map
+            .functions
+            .iter()
+            .map(|state| match *state {
+                FunctionState::Added(handle) => Some(handle),
+                FunctionState::Pending | FunctionState::InProgress => None,
+            })
+            .collect();
+
+        let mut arguments = function.arguments.clone();
+        for argument in arguments.iter_mut() {
+            argument.ty = map.types[argument.ty.index()];
+        }
+        let mut result = function.result.clone();
+        if let Some(ref mut result) = result {
+            result.ty = map.types[result.ty.index()];
+        }
+
+        let mut local_variables = Arena::new();
+        for (handle, local) in function.local_variables.iter() {
+            let mut local = local.clone();
+            local.ty = map.types[local.ty.index()];
+            let span = function.local_variables.get_span(handle);
+            local_variables.append(local, span);
+        }
+
+        // Expressions keep their handles, only the handles to module items
+        // need to be adjusted.
+        let mut expressions = Arena::new();
+        for (handle, expr) in function.expressions.iter() {
+            let span = function.expressions.get_span(handle);
+            expressions.append(map_expression(expr, map, &functions), span);
+        }
+
+        let mut body = function.body.clone();
+        map_calls(&mut body, &functions);
+
+        Function {
+            name: function.name.clone(),
+            arguments,
+            result,
+            local_variables,
+            expressions,
+            named_expressions: function.named_expressions.clone(),
+            body,
+        }
+    }
+}
+
+/// Returns a copy of `expr` that refers to the handles of the linked module.
+///
+/// Handles to other expressions are kept as they are.
+fn map_expression(
+    expr: &Expression,
+    map: &ModuleMap,
+    functions: &[Option<Handle<Function>>],
+) -> Expression {
+    let mut expr = expr.clone();
+    match expr {
+        Expression::Constant(ref mut constant) => {
+            *constant = map.constants[constant.index()].unwrap();
+        }
+        Expression::ZeroValue(ref mut ty)
+        | Expression::Compose { ref mut ty, .. }
+        | Expression::AtomicResult { ref mut ty, .. }
+        | Expression::WorkGroupUniformLoadResult { ref mut ty }
+        | Expression::SubgroupOperationResult { ref mut ty } => {
+            *ty = map.types[ty.index()];
+        }
+        Expression::GlobalVariable(ref mut global) => {
+            *global = map.global_variables[global.index()];
+        }
+        Expression::CallResult(ref mut function) => {
+            *function = functions[function.index()].unwrap();
+        }
+        _ => {}
+    }
+    expr
+}
+
+/// Adds the functions called in `block` to `calls`.
+fn collect_calls(block: &Block, calls: &mut Vec<Handle<Function>>) {
+    for statement in block.iter() {
+        match *statement {
+            Statement::Block(ref block) => collect_calls(block, calls),
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => {
+                collect_calls(accept, calls);
+                collect_calls(reject, calls);
+            }
+            Statement::Switch { ref cases, .. } => {
+                for case in cases {
+                    collect_calls(&case.body, calls);
+                }
+            }
+            Statement::Loop {
+                ref body,
+                ref continuing,
+                ..
+            } => {
+                collect_calls(body, calls);
+                collect_calls(continuing, calls);
+            }
+            Statement::Call { function, .. } => calls.push(function),
+            _ => {}
+        }
+    }
+}
+
+/// Adjusts the functions called in `block` to the handles of the linked module.
+fn map_calls(block: &mut Block, functions: &[Option<Handle<Function>>]) {
+    for statement in block.iter_mut() {
+        match *statement {
+            Statement::Block(ref mut block) => map_calls(block, functions),
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                map_calls(accept, functions);
+                map_calls(reject, functions);
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases.iter_mut() {
+                    map_calls(&mut case.body, functions);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ..
+            } => {
+                map_calls(body, functions);
+                map_calls(continuing, functions);
+            }
+            Statement::Call {
+                ref mut function, ..
+            } => *function = functions[function.index()].unwrap(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(all(test, feature = "glsl-in"))]
+mod tests {
+    use super::*;
+    use crate::front::glsl::{Frontend, Options};
+
+    const LIBRARY: &str = r#"
+        #version 450
+        float helper(float x) {
+            return x * 2.0;
+        }
+        void main() {}
+    "#;
+
+    const SHADER: &str = r#"
+        #version 450
+        float helper(float x);
+        void main() {
+            gl_Position = vec4(helper(1.0));
+        }
+    "#;
+
+    fn parse(stage: crate::ShaderStage, source: &str) -> Module {
+        Frontend::default()
+            .parse(&Options::from(stage), source)
+            .unwrap()
+    }
+
+    #[test]
+    fn resolve_declarations() {
+        let library = parse(crate::ShaderStage::Compute, LIBRARY);
+        let shader = parse(crate::ShaderStage::Vertex, SHADER);
+
+        let module = link(&[library, shader]).unwrap();
+        assert_eq!(module.entry_points.len(), 2);
+        // The definition of `helper` and both `main` functions
+        assert_eq!(module.functions.len(), 3);
+        assert!(module
+            .functions
+            .iter()
+            .all(|(_, function)| !function.body.is_empty()));
+
+        crate::valid::Validator::new(
+            crate::valid::ValidationFlags::all(),
+            crate::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .unwrap();
+    }
+
+    #[test]
+    fn link_errors() {
+        let undefined = parse(
+            crate::ShaderStage::Vertex,
+            r#"
+                #version 450
+                float missing(float x);
+                void main() {
+                    gl_Position = vec4(missing(1.0));
+                }
+            "#,
+        );
+        assert_eq!(
+            link(&[undefined]).unwrap_err(),
+            LinkError::UndefinedFunction("missing".to_string())
+        );
+
+        let library = parse(crate::ShaderStage::Compute, LIBRARY);
+        let other_library = parse(crate::ShaderStage::Fragment, LIBRARY);
+        let shader = parse(crate::ShaderStage::Vertex, SHADER);
+        assert_eq!(
+            link(&[library, other_library, shader]).unwrap_err(),
+            LinkError::ConflictingFunction("helper".to_string())
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::iter;
 
 use arrayvec::ArrayVec;
+use half::f16;
 
 use crate::{
     arena::{Arena, Handle, UniqueArena},
@@ -351,6 +352,8 @@ pub enum ConstantEvaluatorError {
     ImageExpression,
     #[error("Constants don't support ray query expressions")]
     RayQueryExpression,
+    #[error("Constants don't support subgroup expressions")]
+    SubgroupExpression,
     #[error("Cannot access the type")]
     InvalidAccessBase,
     #[error("Cannot access at the index")]
@@ -677,6 +680,9 @@ impl<'a> ConstantEvaluator<'a> {
             Expression::RayQueryProceedResult | Expression::RayQueryGetIntersection { .. } => {
                 Err(ConstantEvaluatorError::RayQueryExpression)
             }
+            Expression::SubgroupBallotResult | Expression::SubgroupOperationResult { .. } => {
+                Err(ConstantEvaluatorError::SubgroupExpression)
+            }
         }
     }
 
@@ -856,6 +862,16 @@ impl<'a> ConstantEvaluator<'a> {
                     }
                 )
             }
+            crate::MathFunction::Max => {
+                component_wise_scalar!(self, span, [arg, arg1.unwrap()], |e1, e2| {
+                    Ok([e1.max(e2)])
+                })
+            }
+            crate::MathFunction::Min => {
+                component_wise_scalar!(self, span, [arg, arg1.unwrap()], |e1, e2| {
+                    Ok([e1.min(e2)])
+                })
+            }
             crate::MathFunction::Cos => {
                 component_wise_float!(self, span, [arg], |e| { Ok([e.cos()]) })
             }
@@ -1150,8 +1166,9 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::I32(v) => v,
                         Literal::U32(v) => v as i32,
                         Literal::F32(v) => v as i32,
+                        Literal::F16(v) => f32::from(v) as i32,
                         Literal::Bool(v) => v as i32,
-                        Literal::F64(_) | Literal::I64(_) => {
+                        Literal::F64(_) | Literal::I64(_) | Literal::U64(_) => {
                             return make_error();
                         }
                         Literal::AbstractInt(v) => i32::try_from_abstract(v)?,
@@ -1161,8 +1178,9 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::I32(v) => v as u32,
                         Literal::U32(v) => v,
                         Literal::F32(v) => v as u32,
+                        Literal::F16(v) => f32::from(v) as u32,
                         Literal::Bool(v) => v as u32,
-                        Literal::F64(_) | Literal::I64(_) => {
+                        Literal::F64(_) | Literal::I64(_) | Literal::U64(_) => {
                             return make_error();
                         }
                         Literal::AbstractInt(v) => u32::try_from_abstract(v)?,
@@ -1172,30 +1190,61 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::I32(v) => v as f32,
                         Literal::U32(v) => v as f32,
                         Literal::F32(v) => v,
+                        Literal::F16(v) => f32::from(v),
                         Literal::Bool(v) => v as u32 as f32,
-                        Literal::F64(_) | Literal::I64(_) => {
+                        Literal::F64(_) | Literal::I64(_) | Literal::U64(_) => {
                             return make_error();
                         }
                         Literal::AbstractInt(v) => f32::try_from_abstract(v)?,
                         Literal::AbstractFloat(v) => f32::try_from_abstract(v)?,
                     }),
+                    Sc::F16 => Literal::F16(match literal {
+                        Literal::I32(v) => f16::from_f32(v as f32),
+                        Literal::U32(v) => f16::from_f32(v as f32),
+                        Literal::F32(v) => f16::from_f32(v),
+                        Literal::F16(v) => v,
+                        Literal::Bool(v) => f16::from_f32(v as u32 as f32),
+                        Literal::F64(_) | Literal::I64(_) | Literal::U64(_) => {
+                            return make_error();
+                        }
+                        Literal::AbstractInt(v) => f16::try_from_abstract(v)?,
+                        Literal::AbstractFloat(v) => f16::try_from_abstract(v)?,
+                    }),
                     Sc::F64 => Literal::F64(match literal {
                         Literal::I32(v) => v as f64,
                         Literal::U32(v) => v as f64,
                         Literal::F32(v) => v as f64,
+                        Literal::F16(v) => f64::from(v),
                         Literal::F64(v) => v,
                         Literal::Bool(v) => v as u32 as f64,
-                        Literal::I64(_) => return make_error(),
+                        Literal::I64(_) | Literal::U64(_) => return make_error(),
                         Literal::AbstractInt(v) => f64::try_from_abstract(v)?,
                         Literal::AbstractFloat(v) => f64::try_from_abstract(v)?,
                     }),
+                    Sc::I64 => Literal::I64(match literal {
+                        Literal::I32(v) => v as i64,
+                        Literal::U32(v) => v as i64,
+                        Literal::I64(v) => v,
+                        Literal::U64(v) => v as i64,
+                        Literal::AbstractInt(v) => v,
+                        _ => return make_error(),
+                    }),
+                    Sc::U64 => Literal::U64(match literal {
+                        Literal::I32(v) => v as u64,
+                        Literal::U32(v) => v as u64,
+                        Literal::I64(v) => v as u64,
+                        Literal::U64(v) => v,
+                        _ => return make_error(),
+                    }),
                     Sc::BOOL => Literal::Bool(match literal {
                         Literal::I32(v) => v != 0,
                         Literal::U32(v) => v != 0,
                         Literal::F32(v) => v != 0.0,
+                        Literal::F16(v) => v != f16::ZERO,
                         Literal::Bool(v) => v,
                         Literal::F64(_)
                         | Literal::I64(_)
+                        | Literal::U64(_)
                         | Literal::AbstractInt(_)
                         | Literal::AbstractFloat(_) => {
                             return make_error();
@@ -1341,6 +1390,7 @@ impl<'a> ConstantEvaluator<'a> {
                 UnaryOperator::Negate => match value {
                     Literal::I32(v) => Literal::I32(v.wrapping_neg()),
                     Literal::F32(v) => Literal::F32(-v),
+                    Literal::F16(v) => Literal::F16(-v),
                     Literal::AbstractInt(v) => Literal::AbstractInt(v.wrapping_neg()),
                     Literal::AbstractFloat(v) => Literal::AbstractFloat(-v),
                     _ => return Err(ConstantEvaluatorError::InvalidUnaryOpArg),
@@ -1472,6 +1522,14 @@ impl<'a> ConstantEvaluator<'a> {
                             BinaryOperator::Modulo => a % b,
                             _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
                         }),
+                        (Literal::F16(a), Literal::F16(b)) => Literal::F16(match op {
+                            BinaryOperator::Add => a + b,
+                            BinaryOperator::Subtract => a - b,
+                            BinaryOperator::Multiply => a * b,
+                            BinaryOperator::Divide => a / b,
+                            BinaryOperator::Modulo => a % b,
+                            _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
+                        }),
                         (Literal::AbstractInt(a), Literal::AbstractInt(b)) => {
                             Literal::AbstractInt(match op {
                                 BinaryOperator::Add => a.checked_add(b).ok_or_else(|| {
@@ -1752,12 +1810,14 @@ impl<'a> ConstantEvaluator<'a> {
 mod tests {
     use std::vec;
 
+    use half::f16;
+
     use crate::{
-        Arena, Constant, Expression, Literal, ScalarKind, Type, TypeInner, UnaryOperator,
-        UniqueArena, VectorSize,
+        Arena, BinaryOperator, Constant, Expression, Literal, ScalarKind, Type, TypeInner,
+        UnaryOperator, UniqueArena, VectorSize,
     };
 
-    use super::{Behavior, ConstantEvaluator};
+    use super::{Behavior, ConstantEvaluator, ConstantEvaluatorError};
 
     #[test]
     fn unary_op() {
@@ -1942,6 +2002,99 @@ mod tests {
         );
     }
 
+    #[test]
+    fn f16() {
+        let mut types = UniqueArena::new();
+        let constants = Arena::new();
+        let mut const_expressions = Arena::new();
+
+        let abstract_float = const_expressions.append(
+            Expression::Literal(Literal::AbstractFloat(1.5)),
+            Default::default(),
+        );
+        let too_large = const_expressions.append(
+            Expression::Literal(Literal::AbstractFloat(1e6)),
+            Default::default(),
+        );
+        let quarter = const_expressions.append(
+            Expression::Literal(Literal::F16(f16::from_f32(0.25))),
+            Default::default(),
+        );
+
+        let mut solver = ConstantEvaluator {
+            behavior: Behavior::Wgsl,
+            types: &mut types,
+            constants: &constants,
+            expressions: &mut const_expressions,
+            function_local_data: None,
+        };
+
+        let cast = solver
+            .try_eval_and_append(
+                &Expression::As {
+                    expr: abstract_float,
+                    kind: ScalarKind::Float,
+                    convert: Some(2),
+                },
+                Default::default(),
+            )
+            .unwrap();
+        let sum = solver
+            .try_eval_and_append(
+                &Expression::Binary {
+                    op: BinaryOperator::Add,
+                    left: cast,
+                    right: quarter,
+                },
+                Default::default(),
+            )
+            .unwrap();
+        let negated = solver
+            .try_eval_and_append(
+                &Expression::Unary {
+                    op: UnaryOperator::Negate,
+                    expr: sum,
+                },
+                Default::default(),
+            )
+            .unwrap();
+        let widened = solver
+            .try_eval_and_append(
+                &Expression::As {
+                    expr: negated,
+                    kind: ScalarKind::Float,
+                    convert: Some(4),
+                },
+                Default::default(),
+            )
+            .unwrap();
+        let overflow = solver.try_eval_and_append(
+            &Expression::As {
+                expr: too_large,
+                kind: ScalarKind::Float,
+                convert: Some(2),
+            },
+            Default::default(),
+        );
+
+        assert_eq!(
+            const_expressions[cast],
+            Expression::Literal(Literal::F16(f16::from_f32(1.5)))
+        );
+        assert_eq!(
+            const_expressions[sum],
+            Expression::Literal(Literal::F16(f16::from_f32(1.75)))
+        );
+        assert_eq!(
+            const_expressions[widened],
+            Expression::Literal(Literal::F32(-1.75))
+        );
+        assert!(matches!(
+            overflow,
+            Err(ConstantEvaluatorError::AutomaticConversionLossy { to_type: "f16", .. })
+        ));
+    }
+
     #[test]
     fn access() {
         let mut types = UniqueArena::new();
@@ -2317,6 +2470,34 @@ impl TryFromAbstract<f64> for f32 {
     }
 }
 
+impl TryFromAbstract<i64> for f16 {
+    fn try_from_abstract(value: i64) -> Result<Self, ConstantEvaluatorError> {
+        let f = f16::from_f64(value as f64);
+        // Unlike for `f32`, the range of `i64` is far larger than the range
+        // of `f16`, roughly ±65504.
+        if f.is_infinite() {
+            return Err(ConstantEvaluatorError::AutomaticConversionLossy {
+                value: format!("{value:?}"),
+                to_type: "f16",
+            });
+        }
+        Ok(f)
+    }
+}
+
+impl TryFromAbstract<f64> for f16 {
+    fn try_from_abstract(value: f64) -> Result<f16, ConstantEvaluatorError> {
+        let f = f16::from_f64(value);
+        if f.is_infinite() {
+            return Err(ConstantEvaluatorError::AutomaticConversionLossy {
+                value: format!("{value:?}"),
+                to_type: "f16",
+            });
+        }
+        Ok(f)
+    }
+}
+
 impl TryFromAbstract<i64> for f64 {
     fn try_from_abstract(value: i64) -> Result<Self, ConstantEvaluatorError> {
         let f = value as f64;
@@ -0,0 +1,532 @@
+/*!
+Lowering of [`Statement::DebugPrintf`] into writes to a capture buffer.
+
+[`Statement::DebugPrintf`]: crate::Statement::DebugPrintf
+*/
+
+use crate::arena::{Arena, Handle, UniqueArena};
+use crate::{
+    valid::{FunctionInfo, ModuleInfo},
+    Block, Expression, Span, Statement,
+};
+
+/// The format string and argument layout of a lowered [`Statement::DebugPrintf`].
+///
+/// [`Statement::DebugPrintf`]: crate::Statement::DebugPrintf
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugPrintfFormat {
+    /// The format string, as given to the statement.
+    pub format: String,
+    /// The arguments substituted into `format`, in order.
+    pub arguments: Vec<DebugPrintfArgument>,
+}
+
+impl DebugPrintfFormat {
+    /// Number of `u32` words of a record written for this format, including
+    /// the format index.
+    pub fn record_words(&self) -> u32 {
+        1 + self
+            .arguments
+            .iter()
+            .map(|argument| argument.components)
+            .sum::<u32>()
+    }
+}
+
+/// The type of a single [`Statement::DebugPrintf`] argument.
+///
+/// [`Statement::DebugPrintf`]: crate::Statement::DebugPrintf
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DebugPrintfArgument {
+    /// Kind of every component. Components are always 32 bits wide.
+    pub kind: crate::ScalarKind,
+    /// Number of components, 1 for scalars.
+    pub components: u32,
+}
+
+/// Replaces every [`Statement::DebugPrintf`] in `module` with writes into a
+/// capture buffer bound at `binding`.
+///
+/// The capture buffer is a storage buffer with the following layout, in `u32`
+/// words:
+///
+/// - Word 0 is atomically incremented by the size of every record, whether
+///   it fit into the buffer or not. Comparing it with the size of the buffer
+///   tells if records were dropped.
+///
+/// - The remaining words hold the records, each starting with the index of
+///   its format in `formats`, followed by the bits of every argument
+///   component as described by [`DebugPrintfFormat::arguments`].
+///
+/// Records that do not fit into the buffer are dropped, so the buffer should
+/// be reset to zero before it is used again.
+///
+/// The format of every lowered statement is appended to `formats`, which lets
+/// several modules share one capture buffer and one format table. Statements
+/// with a format that is already in `formats` reuse its index, so lowering the
+/// same statements again does not grow the table.
+///
+/// `info` must be the result of validating `module`. Nothing is changed if
+/// `module` contains no [`Statement::DebugPrintf`], in which case `false` is
+/// returned and the capture buffer is not used.
+///
+/// [`Statement::DebugPrintf`]: crate::Statement::DebugPrintf
+pub fn lower_debug_printf(
+    module: &mut crate::Module,
+    info: &ModuleInfo,
+    binding: crate::ResourceBinding,
+    formats: &mut Vec<DebugPrintfFormat>,
+) -> bool {
+    let uses_printf = module
+        .functions
+        .iter()
+        .map(|(_, function)| &function.body)
+        .chain(module.entry_points.iter().map(|ep| &ep.function.body))
+        .any(contains_debug_printf);
+    if !uses_printf {
+        return false;
+    }
+
+    let (capture, u32_ty) = add_capture_global(module, binding);
+
+    let mut lowerer = Lowerer {
+        types: &module.types,
+        capture,
+        u32_ty,
+        formats,
+    };
+    for (handle, function) in module.functions.iter_mut() {
+        lowerer.lower_block(&mut function.body, &mut function.expressions, &info[handle]);
+    }
+    for (index, ep) in module.entry_points.iter_mut().enumerate() {
+        lowerer.lower_block(
+            &mut ep.function.body,
+            &mut ep.function.expressions,
+            info.get_entry_point(index),
+        );
+    }
+    true
+}
+
+fn contains_debug_printf(block: &Block) -> bool {
+    block.iter().any(|statement| match *statement {
+        Statement::DebugPrintf { .. } => true,
+        Statement::Block(ref block) => contains_debug_printf(block),
+        Statement::If {
+            ref accept,
+            ref reject,
+            ..
+        } => contains_debug_printf(accept) || contains_debug_printf(reject),
+        Statement::Switch { ref cases, .. } => {
+            cases.iter().any(|case| contains_debug_printf(&case.body))
+        }
+        Statement::Loop {
+            ref body,
+            ref continuing,
+            ..
+        } => contains_debug_printf(body) || contains_debug_printf(continuing),
+        _ => false,
+    })
+}
+
+/// Adds the `struct { count: atomic<u32>, data: array<u32> }` capture buffer,
+/// returning it along with the `u32` type.
+fn add_capture_global(
+    module: &mut crate::Module,
+    binding: crate::ResourceBinding,
+) -> (Handle<crate::GlobalVariable>, Handle<crate::Type>) {
+    let atomic_ty = module.types.insert(
+        crate::Type {
+            name: None,
+            inner: crate::TypeInner::Atomic(crate::Scalar::U32),
+        },
+        Span::UNDEFINED,
+    );
+    let u32_ty = module.types.insert(
+        crate::Type {
+            name: None,
+            inner: crate::TypeInner::Scalar(crate::Scalar::U32),
+        },
+        Span::UNDEFINED,
+    );
+    let array_ty = module.types.insert(
+        crate::Type {
+            name: None,
+            inner: crate::TypeInner::Array {
+                base: u32_ty,
+                size: crate::ArraySize::Dynamic,
+                stride: 4,
+            },
+        },
+        Span::UNDEFINED,
+    );
+    let struct_ty = module.types.insert(
+        crate::Type {
+            name: Some("NagaDebugPrintfCapture".to_string()),
+            inner: crate::TypeInner::Struct {
+                members: vec![
+                    crate::StructMember {
+                        name: Some("count".to_string()),
+                        ty: atomic_ty,
+                        binding: None,
+                        offset: 0,
+                    },
+                    crate::StructMember {
+                        name: Some("data".to_string()),
+                        ty: array_ty,
+                        binding: None,
+                        offset: 4,
+                    },
+                ],
+                span: 8,
+            },
+        },
+        Span::UNDEFINED,
+    );
+    let global = module.global_variables.append(
+        crate::GlobalVariable {
+            name: Some("naga_debug_printf".to_string()),
+            space: crate::AddressSpace::Storage {
+                access: crate::StorageAccess::LOAD | crate::StorageAccess::STORE,
+            },
+            binding: Some(binding),
+            ty: struct_ty,
+            init: None,
+        },
+        Span::UNDEFINED,
+    );
+    (global, u32_ty)
+}
+
+struct Lowerer<'a> {
+    types: &'a UniqueArena<crate::Type>,
+    capture: Handle<crate::GlobalVariable>,
+    u32_ty: Handle<crate::Type>,
+    formats: &'a mut Vec<DebugPrintfFormat>,
+}
+
+impl Lowerer<'_> {
+    fn lower_block(
+        &mut self,
+        block: &mut Block,
+        expressions: &mut Arena<Expression>,
+        info: &FunctionInfo,
+    ) {
+        let mut replacements = Vec::new();
+        for (index, (statement, span)) in block.span_iter_mut().enumerate() {
+            match *statement {
+                Statement::Block(ref mut block) => self.lower_block(block, expressions, info),
+                Statement::If {
+                    ref mut accept,
+                    ref mut reject,
+                    ..
+                } => {
+                    self.lower_block(accept, expressions, info);
+                    self.lower_block(reject, expressions, info);
+                }
+                Statement::Switch { ref mut cases, .. } => {
+                    for case in cases.iter_mut() {
+                        self.lower_block(&mut case.body, expressions, info);
+                    }
+                }
+                Statement::Loop {
+                    ref mut body,
+                    ref mut continuing,
+                    ..
+                } => {
+                    self.lower_block(body, expressions, info);
+                    self.lower_block(continuing, expressions, info);
+                }
+                Statement::DebugPrintf {
+                    ref format,
+                    ref arguments,
+                } => {
+                    let span = span.map_or(Span::default(), |span| *span);
+                    let replacement =
+                        self.lower_statement(format, arguments, span, expressions, info);
+                    replacements.push((index, replacement));
+                }
+                _ => {}
+            }
+        }
+
+        for (index, replacement) in replacements.into_iter().rev() {
+            block.splice(index..index + 1, replacement);
+        }
+    }
+
+    /// Builds the statements writing one record, roughly:
+    ///
+    /// ```text
+    /// let offset = atomicAdd(&capture.count, words);
+    /// if offset + words <= arrayLength(&capture.data) {
+    ///     capture.data[offset] = format_index;
+    ///     capture.data[offset + 1] = bitcast<u32>(argument0);
+    ///     ...
+    /// }
+    /// ```
+    fn lower_statement(
+        &mut self,
+        format: &str,
+        arguments: &[Handle<Expression>],
+        span: Span,
+        expressions: &mut Arena<Expression>,
+        info: &FunctionInfo,
+    ) -> Block {
+        let mut components = Vec::new();
+        let mut layout = Vec::with_capacity(arguments.len());
+        for &argument in arguments {
+            let (kind, count) = match *info[argument].ty.inner_with(self.types) {
+                crate::TypeInner::Scalar(scalar) => (scalar.kind, None),
+                crate::TypeInner::Vector { size, scalar } => (scalar.kind, Some(size as u32)),
+                ref other => unreachable!("invalid debug printf argument {other:?}"),
+            };
+            layout.push(DebugPrintfArgument {
+                kind,
+                components: count.unwrap_or(1),
+            });
+            components.push((argument, kind, count));
+        }
+
+        let format_index = match self
+            .formats
+            .iter()
+            .position(|known| known.format == format && known.arguments == layout)
+        {
+            Some(index) => index,
+            None => {
+                self.formats.push(DebugPrintfFormat {
+                    format: format.to_string(),
+                    arguments: layout,
+                });
+                self.formats.len() - 1
+            }
+        };
+        let words = self.formats[format_index].record_words();
+        let format_index = format_index as u32;
+
+        let literal = |expressions: &mut Arena<Expression>, value| {
+            expressions.append(Expression::Literal(crate::Literal::U32(value)), span)
+        };
+
+        let mut body = Block::new();
+        let capture = expressions.append(Expression::GlobalVariable(self.capture), span);
+        let word_count = literal(expressions, words);
+        let offsets = (1..words)
+            .map(|word| literal(expressions, word))
+            .collect::<Vec<_>>();
+        let format_value = literal(expressions, format_index);
+
+        let mut emitter = super::Emitter::default();
+        emitter.start(expressions);
+        let count = expressions.append(
+            Expression::AccessIndex {
+                base: capture,
+                index: 0,
+            },
+            span,
+        );
+        body.extend(emitter.finish(expressions));
+
+        let offset = expressions.append(
+            Expression::AtomicResult {
+                ty: self.u32_ty,
+                comparison: false,
+            },
+            span,
+        );
+        body.push(
+            Statement::Atomic {
+                pointer: count,
+                fun: crate::AtomicFunction::Add,
+                value: word_count,
+                result: offset,
+            },
+            span,
+        );
+
+        emitter.start(expressions);
+        let data = expressions.append(
+            Expression::AccessIndex {
+                base: capture,
+                index: 1,
+            },
+            span,
+        );
+        let length = expressions.append(Expression::ArrayLength(data), span);
+        let end = expressions.append(
+            Expression::Binary {
+                op: crate::BinaryOperator::Add,
+                left: offset,
+                right: word_count,
+            },
+            span,
+        );
+        let fits = expressions.append(
+            Expression::Binary {
+                op: crate::BinaryOperator::LessEqual,
+                left: end,
+                right: length,
+            },
+            span,
+        );
+        body.extend(emitter.finish(expressions));
+
+        let mut values = vec![format_value];
+        let mut accept = Block::new();
+        emitter.start(expressions);
+        for (argument, kind, count) in components {
+            let scalars = match count {
+                None => vec![argument],
+                Some(count) => (0..count)
+                    .map(|index| {
+                        expressions.append(
+                            Expression::AccessIndex {
+                                base: argument,
+                                index,
+                            },
+                            span,
+                        )
+                    })
+                    .collect(),
+            };
+            for scalar in scalars {
+                values.push(match kind {
+                    crate::ScalarKind::Uint => scalar,
+                    _ => expressions.append(
+                        Expression::As {
+                            expr: scalar,
+                            kind: crate::ScalarKind::Uint,
+                            convert: None,
+                        },
+                        span,
+                    ),
+                });
+            }
+        }
+        let mut stores = Vec::with_capacity(values.len());
+        for (word, value) in values.into_iter().enumerate() {
+            let index = match word {
+                0 => offset,
+                _ => expressions.append(
+                    Expression::Binary {
+                        op: crate::BinaryOperator::Add,
+                        left: offset,
+                        right: offsets[word - 1],
+                    },
+                    span,
+                ),
+            };
+            let pointer = expressions.append(Expression::Access { base: data, index }, span);
+            stores.push(Statement::Store { pointer, value });
+        }
+        accept.extend(emitter.finish(expressions));
+        for store in stores {
+            accept.push(store, span);
+        }
+
+        body.push(
+            Statement::If {
+                condition: fits,
+                accept,
+                reject: Block::new(),
+            },
+            span,
+        );
+        body
+    }
+}
+
+#[cfg(all(test, feature = "wgsl-in"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lower_wgsl() {
+        let source = r#"
+            @compute @workgroup_size(1)
+            fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+                if id.x == 0u {
+                    debugPrintf("id = %v3u, value = %f", id, 1.5);
+                }
+            }
+        "#;
+        let mut module = crate::front::wgsl::parse_str(source).unwrap();
+        let mut validator = crate::valid::Validator::new(
+            crate::valid::ValidationFlags::all(),
+            crate::valid::Capabilities::DEBUG_PRINTF,
+        );
+        let info = validator.validate(&module).unwrap();
+
+        let mut formats = Vec::new();
+        assert!(lower_debug_printf(
+            &mut module,
+            &info,
+            crate::ResourceBinding {
+                group: 1,
+                binding: 0,
+            },
+            &mut formats,
+        ));
+        assert_eq!(
+            formats,
+            [DebugPrintfFormat {
+                format: "id = %v3u, value = %f".to_string(),
+                arguments: vec![
+                    DebugPrintfArgument {
+                        kind: crate::ScalarKind::Uint,
+                        components: 3,
+                    },
+                    DebugPrintfArgument {
+                        kind: crate::ScalarKind::Float,
+                        components: 1,
+                    },
+                ],
+            }]
+        );
+        assert_eq!(formats[0].record_words(), 5);
+        assert!(!contains_debug_printf(
+            &module.entry_points[0].function.body
+        ));
+
+        // The lowered module must not need the capability anymore.
+        crate::valid::Validator::new(
+            crate::valid::ValidationFlags::all(),
+            crate::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .unwrap();
+    }
+    #[test]
+    fn reuse_formats() {
+        let source = r#"
+            @compute @workgroup_size(1)
+            fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+                debugPrintf("x = %u", id.x);
+                debugPrintf("x = %u", id.y);
+                debugPrintf("x = %f", 1.5);
+            }
+        "#;
+        let mut formats = Vec::new();
+        for _ in 0..2 {
+            let mut module = crate::front::wgsl::parse_str(source).unwrap();
+            let info = crate::valid::Validator::new(
+                crate::valid::ValidationFlags::all(),
+                crate::valid::Capabilities::DEBUG_PRINTF,
+            )
+            .validate(&module)
+            .unwrap();
+            assert!(lower_debug_printf(
+                &mut module,
+                &info,
+                crate::ResourceBinding {
+                    group: 1,
+                    binding: 0,
+                },
+                &mut formats,
+            ));
+        }
+        // The same string with other arguments is a separate format.
+        assert_eq!(formats.len(), 2);
+    }
+}
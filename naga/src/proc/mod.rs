@@ -3,20 +3,26 @@
 */
 
 mod constant_evaluator;
+mod debug_printf;
 mod emitter;
 pub mod index;
 mod layouter;
 mod namer;
+mod optimize;
+mod overrides;
 mod terminator;
 mod typifier;
 
 pub use constant_evaluator::{
     ConstantEvaluator, ConstantEvaluatorError, ExpressionConstnessTracker,
 };
+pub use debug_printf::{lower_debug_printf, DebugPrintfArgument, DebugPrintfFormat};
 pub use emitter::Emitter;
 pub use index::{BoundsCheckPolicies, BoundsCheckPolicy, IndexableLength, IndexableLengthError};
 pub use layouter::{Alignment, LayoutError, LayoutErrorInner, Layouter, TypeLayout};
 pub use namer::{EntryPointIndex, NameKey, Namer};
+pub use optimize::{optimize, remove_unreachable_branches};
+pub use overrides::{process_overrides, PipelineConstantError, PipelineConstants};
 pub use terminator::ensure_block_returns;
 pub use typifier::{ResolveContext, ResolveError, TypeResolution};
 
@@ -90,6 +96,10 @@ impl super::Scalar {
         kind: crate::ScalarKind::Uint,
         width: 4,
     };
+    pub const F16: Self = Self {
+        kind: crate::ScalarKind::Float,
+        width: 2,
+    };
     pub const F32: Self = Self {
         kind: crate::ScalarKind::Float,
         width: 4,
@@ -102,6 +112,10 @@ impl super::Scalar {
         kind: crate::ScalarKind::Sint,
         width: 8,
     };
+    pub const U64: Self = Self {
+        kind: crate::ScalarKind::Uint,
+        width: 8,
+    };
     pub const BOOL: Self = Self {
         kind: crate::ScalarKind::Bool,
         width: crate::BOOL_WIDTH,
@@ -154,9 +168,11 @@ impl PartialEq for crate::Literal {
         match (*self, *other) {
             (Self::F64(a), Self::F64(b)) => a.to_bits() == b.to_bits(),
             (Self::F32(a), Self::F32(b)) => a.to_bits() == b.to_bits(),
+            (Self::F16(a), Self::F16(b)) => a.to_bits() == b.to_bits(),
             (Self::U32(a), Self::U32(b)) => a == b,
             (Self::I32(a), Self::I32(b)) => a == b,
             (Self::I64(a), Self::I64(b)) => a == b,
+            (Self::U64(a), Self::U64(b)) => a == b,
             (Self::Bool(a), Self::Bool(b)) => a == b,
             _ => false,
         }
@@ -190,6 +206,14 @@ impl std::hash::Hash for crate::Literal {
                 hasher.write_u8(5);
                 v.hash(hasher);
             }
+            Self::U64(v) => {
+                hasher.write_u8(6);
+                v.hash(hasher);
+            }
+            Self::F16(v) => {
+                hasher.write_u8(7);
+                v.to_bits().hash(hasher);
+            }
         }
     }
 }
@@ -199,9 +223,13 @@ impl crate::Literal {
         match (value, scalar.kind, scalar.width) {
             (value, crate::ScalarKind::Float, 8) => Some(Self::F64(value as _)),
             (value, crate::ScalarKind::Float, 4) => Some(Self::F32(value as _)),
+            (value, crate::ScalarKind::Float, 2) => {
+                Some(Self::F16(half::f16::from_f32_const(value as _)))
+            }
             (value, crate::ScalarKind::Uint, 4) => Some(Self::U32(value as _)),
             (value, crate::ScalarKind::Sint, 4) => Some(Self::I32(value as _)),
             (value, crate::ScalarKind::Sint, 8) => Some(Self::I64(value as _)),
+            (value, crate::ScalarKind::Uint, 8) => Some(Self::U64(value as _)),
             (1, crate::ScalarKind::Bool, 4) => Some(Self::Bool(true)),
             (0, crate::ScalarKind::Bool, 4) => Some(Self::Bool(false)),
             _ => None,
@@ -218,8 +246,9 @@ impl crate::Literal {
 
     pub const fn width(&self) -> crate::Bytes {
         match *self {
-            Self::F64(_) | Self::I64(_) => 8,
+            Self::F64(_) | Self::I64(_) | Self::U64(_) => 8,
             Self::F32(_) | Self::U32(_) | Self::I32(_) => 4,
+            Self::F16(_) => 2,
             Self::Bool(_) => crate::BOOL_WIDTH,
             Self::AbstractInt(_) | Self::AbstractFloat(_) => crate::ABSTRACT_WIDTH,
         }
@@ -228,9 +257,11 @@ impl crate::Literal {
         match *self {
             Self::F64(_) => crate::Scalar::F64,
             Self::F32(_) => crate::Scalar::F32,
+            Self::F16(_) => crate::Scalar::F16,
             Self::U32(_) => crate::Scalar::U32,
             Self::I32(_) => crate::Scalar::I32,
             Self::I64(_) => crate::Scalar::I64,
+            Self::U64(_) => crate::Scalar::U64,
             Self::Bool(_) => crate::Scalar::BOOL,
             Self::AbstractInt(_) => crate::Scalar::ABSTRACT_INT,
             Self::AbstractFloat(_) => crate::Scalar::ABSTRACT_FLOAT,
@@ -416,7 +447,8 @@ impl super::AddressSpace {
         match self {
             crate::AddressSpace::Function
             | crate::AddressSpace::Private
-            | crate::AddressSpace::WorkGroup => Sa::LOAD | Sa::STORE,
+            | crate::AddressSpace::WorkGroup
+            | crate::AddressSpace::TaskPayload => Sa::LOAD | Sa::STORE,
             crate::AddressSpace::Uniform => Sa::LOAD,
             crate::AddressSpace::Storage { access } => access,
             crate::AddressSpace::Handle => Sa::LOAD,
@@ -593,6 +625,38 @@ impl crate::Binding {
             Self::Location { .. } => None,
         }
     }
+
+    /// Returns whether an input with this binding holds the values of every
+    /// vertex of a primitive, in stages that consume whole primitives or
+    /// patches.
+    ///
+    /// In tessellation control shaders, this is also whether an output holds
+    /// the value of a vertex rather than of the whole patch.
+    pub const fn is_per_vertex(&self) -> bool {
+        match *self {
+            Self::BuiltIn(
+                crate::BuiltIn::Position { .. }
+                | crate::BuiltIn::PointSize
+                | crate::BuiltIn::ClipDistance
+                | crate::BuiltIn::CullDistance,
+            ) => true,
+            Self::Location { patch, .. } => !patch,
+            Self::BuiltIn(_) => false,
+        }
+    }
+}
+
+impl crate::GeometryInputPrimitive {
+    /// Returns the number of vertices of a primitive of this topology.
+    pub const fn vertex_count(self) -> u32 {
+        match self {
+            Self::Points => 1,
+            Self::Lines => 2,
+            Self::LinesAdjacency => 4,
+            Self::Triangles => 3,
+            Self::TrianglesAdjacency => 6,
+        }
+    }
 }
 
 impl super::SwizzleComponent {
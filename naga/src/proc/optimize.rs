@@ -0,0 +1,907 @@
+/*!
+Simplification of function bodies.
+
+Frontends like the SPIR-V one translate their input instruction by instruction,
+which leaves operations on constant operands, identities like `x * 1.0`, and
+stores to variables that are never read in the IR. [`optimize`] removes them.
+*/
+
+use crate::arena::{Arena, Handle, Range};
+use crate::{
+    BinaryOperator, Block, Constant, Expression, Function, Literal, LocalVariable, Module,
+    Override, Statement, UnaryOperator,
+};
+
+/// Simplifies the bodies of the functions and entry points of `module`.
+///
+/// - Unary and binary operations on scalar literals, or on constants that
+///   aren't overridable, are replaced by their result. Operations that would
+///   overflow, divide by zero or produce a non-finite value are kept.
+///
+/// - Adding or subtracting zero, and multiplying or dividing by one, are
+///   replaced by the other operand. For floating-point values, this ignores
+///   the sign of a zero result. Expressions used by a named `let` are kept,
+///   to keep their name.
+///
+/// - Stores to local variables whose value is never read are removed.
+///
+/// - Unreachable branches are removed, like [`remove_unreachable_branches`]
+///   does.
+///
+/// The expressions that are no longer used stay in the arenas, compaction can
+/// remove them.
+pub fn optimize(module: &mut Module) {
+    let Module {
+        ref constants,
+        ref const_expressions,
+        ref mut functions,
+        ref mut entry_points,
+        ..
+    } = *module;
+    let optimizer = Optimizer {
+        constants,
+        const_expressions,
+    };
+
+    for (_, function) in functions.iter_mut() {
+        optimizer.optimize_function(function, true);
+    }
+    for ep in entry_points.iter_mut() {
+        optimizer.optimize_function(&mut ep.function, true);
+    }
+}
+
+/// Replaces the `if` statements of `module` whose condition is known with the
+/// branch that is taken.
+///
+/// Operations on literals are folded first, like [`optimize`] does, so once
+/// [`process_overrides`] gave a value to the overridable constants, the code
+/// they disable is removed before backends write it, and drivers don't have
+/// to compile it.
+///
+/// Returns `true` if `module` was changed, which needs to be validated again.
+///
+/// [`process_overrides`]: super::process_overrides
+pub fn remove_unreachable_branches(module: &mut Module) -> bool {
+    let Module {
+        ref constants,
+        ref const_expressions,
+        ref mut functions,
+        ref mut entry_points,
+        ..
+    } = *module;
+    let optimizer = Optimizer {
+        constants,
+        const_expressions,
+    };
+
+    let mut changed = false;
+    for (_, function) in functions.iter_mut() {
+        changed |= optimizer.optimize_function(function, false);
+    }
+    for ep in entry_points.iter_mut() {
+        changed |= optimizer.optimize_function(&mut ep.function, false);
+    }
+    changed
+}
+
+struct Optimizer<'a> {
+    constants: &'a Arena<Constant>,
+    const_expressions: &'a Arena<Expression>,
+}
+
+impl Optimizer<'_> {
+    /// Folds the expressions and removes the unreachable branches of
+    /// `function`, and applies the other simplifications if `simplify` is set.
+    ///
+    /// Returns `true` if `function` was changed.
+    fn optimize_function(&self, function: &mut Function, simplify: bool) -> bool {
+        let mut changed = false;
+
+        // `replacements[i]` is the expression that replaces the one at index `i`
+        let mut replacements = vec![None; function.expressions.len()];
+        let handles: Vec<_> = function
+            .expressions
+            .iter()
+            .map(|(handle, _)| handle)
+            .collect();
+        for handle in handles {
+            let mut expr = function.expressions[handle].clone();
+            for_each_operand(&mut expr, &mut |operand| replace(operand, &replacements));
+
+            if let Some(literal) = self.fold(&expr, &function.expressions) {
+                expr = Expression::Literal(literal);
+                changed = true;
+            } else if simplify && !function.named_expressions.contains_key(&handle) {
+                replacements[handle.index()] = self.simplify(&expr, &function.expressions);
+            }
+            function.expressions[handle] = expr;
+        }
+
+        let dead_locals = if simplify {
+            adjust_body(function, &replacements)
+        } else {
+            vec![false; function.local_variables.len()]
+        };
+        let mut context = BlockContext {
+            optimizer: self,
+            expressions: &function.expressions,
+            dead_locals: &dead_locals,
+            changed,
+        };
+        context.rebuild_block(&mut function.body);
+        let changed = context.changed;
+
+        for (_, local) in function.local_variables.iter_mut() {
+            if let Some(ref mut init) = local.init {
+                replace(init, &replacements);
+            }
+        }
+
+        changed
+    }
+
+    /// Returns the value of `handle` if it is a literal, or a constant that
+    /// isn't overridable with a literal value.
+    fn literal(
+        &self,
+        handle: Handle<Expression>,
+        expressions: &Arena<Expression>,
+    ) -> Option<Literal> {
+        match expressions[handle] {
+            Expression::Literal(literal) => Some(literal),
+            Expression::Constant(constant) => {
+                let constant = &self.constants[constant];
+                if constant.r#override != Override::None {
+                    return None;
+                }
+                match self.const_expressions[constant.init] {
+                    Expression::Literal(literal) => Some(literal),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the result of `expr`, if its operands are literals.
+    fn fold(&self, expr: &Expression, expressions: &Arena<Expression>) -> Option<Literal> {
+        match *expr {
+            Expression::Unary { op, expr } => fold_unary(op, self.literal(expr, expressions)?),
+            Expression::Binary { op, left, right } => fold_binary(
+                op,
+                self.literal(left, expressions)?,
+                self.literal(right, expressions)?,
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns the operand that `expr` is equivalent to, if it is an identity
+    /// operation.
+    fn simplify(
+        &self,
+        expr: &Expression,
+        expressions: &Arena<Expression>,
+    ) -> Option<Handle<Expression>> {
+        let Expression::Binary { op, left, right } = *expr else {
+            return None;
+        };
+        let is = |handle, value| {
+            self.literal(handle, expressions)
+                .map_or(false, |literal| is_scalar_value(literal, value))
+        };
+        match op {
+            BinaryOperator::Add if is(left, 0) => Some(right),
+            BinaryOperator::Add | BinaryOperator::Subtract if is(right, 0) => Some(left),
+            BinaryOperator::Multiply if is(left, 1) => Some(right),
+            BinaryOperator::Multiply | BinaryOperator::Divide if is(right, 1) => Some(left),
+            _ => None,
+        }
+    }
+}
+
+/// Checks if `literal` is a number equal to `value`.
+fn is_scalar_value(literal: Literal, value: i8) -> bool {
+    match literal {
+        Literal::F64(v) => v == f64::from(value),
+        Literal::F32(v) => v == f32::from(value),
+        Literal::F16(v) => f32::from(v) == f32::from(value),
+        Literal::U32(v) => i64::from(v) == i64::from(value),
+        Literal::I32(v) => v == i32::from(value),
+        Literal::I64(v) => v == i64::from(value),
+        Literal::U64(v) => i128::from(v) == i128::from(value),
+        Literal::Bool(_) | Literal::AbstractInt(_) | Literal::AbstractFloat(_) => false,
+    }
+}
+
+fn fold_unary(op: UnaryOperator, value: Literal) -> Option<Literal> {
+    Some(match (op, value) {
+        (UnaryOperator::Negate, Literal::F64(v)) => Literal::F64(-v),
+        (UnaryOperator::Negate, Literal::F32(v)) => Literal::F32(-v),
+        (UnaryOperator::Negate, Literal::F16(v)) => Literal::F16(-v),
+        (UnaryOperator::Negate, Literal::I32(v)) => Literal::I32(v.wrapping_neg()),
+        (UnaryOperator::Negate, Literal::I64(v)) => Literal::I64(v.wrapping_neg()),
+        (UnaryOperator::LogicalNot, Literal::Bool(v)) => Literal::Bool(!v),
+        (UnaryOperator::BitwiseNot, Literal::U32(v)) => Literal::U32(!v),
+        (UnaryOperator::BitwiseNot, Literal::I32(v)) => Literal::I32(!v),
+        (UnaryOperator::BitwiseNot, Literal::I64(v)) => Literal::I64(!v),
+        (UnaryOperator::BitwiseNot, Literal::U64(v)) => Literal::U64(!v),
+        _ => return None,
+    })
+}
+
+fn fold_binary(op: BinaryOperator, left: Literal, right: Literal) -> Option<Literal> {
+    use BinaryOperator as Bo;
+
+    Some(match (left, right) {
+        (Literal::F32(a), Literal::F32(b)) => match op {
+            Bo::Add => finite_f32(a + b)?,
+            Bo::Subtract => finite_f32(a - b)?,
+            Bo::Multiply => finite_f32(a * b)?,
+            Bo::Divide => finite_f32(a / b)?,
+            Bo::Modulo => finite_f32(a % b)?,
+            _ => Literal::Bool(compare(op, a.partial_cmp(&b)?)?),
+        },
+        (Literal::F16(a), Literal::F16(b)) => match op {
+            Bo::Add => finite_f16(a + b)?,
+            Bo::Subtract => finite_f16(a - b)?,
+            Bo::Multiply => finite_f16(a * b)?,
+            Bo::Divide => finite_f16(a / b)?,
+            Bo::Modulo => finite_f16(a % b)?,
+            _ => Literal::Bool(compare(op, a.partial_cmp(&b)?)?),
+        },
+        (Literal::F64(a), Literal::F64(b)) => match op {
+            Bo::Add => finite_f64(a + b)?,
+            Bo::Subtract => finite_f64(a - b)?,
+            Bo::Multiply => finite_f64(a * b)?,
+            Bo::Divide => finite_f64(a / b)?,
+            Bo::Modulo => finite_f64(a % b)?,
+            _ => Literal::Bool(compare(op, a.partial_cmp(&b)?)?),
+        },
+        (Literal::I32(a), Literal::I32(b)) => Literal::I32(match op {
+            Bo::Add => a.wrapping_add(b),
+            Bo::Subtract => a.wrapping_sub(b),
+            Bo::Multiply => a.wrapping_mul(b),
+            Bo::Divide => a.checked_div(b)?,
+            Bo::Modulo => a.checked_rem(b)?,
+            Bo::And => a & b,
+            Bo::ExclusiveOr => a ^ b,
+            Bo::InclusiveOr => a | b,
+            _ => return Some(Literal::Bool(compare(op, a.cmp(&b))?)),
+        }),
+        (Literal::U32(a), Literal::U32(b)) => Literal::U32(match op {
+            Bo::Add => a.wrapping_add(b),
+            Bo::Subtract => a.wrapping_sub(b),
+            Bo::Multiply => a.wrapping_mul(b),
+            Bo::Divide => a.checked_div(b)?,
+            Bo::Modulo => a.checked_rem(b)?,
+            Bo::And => a & b,
+            Bo::ExclusiveOr => a ^ b,
+            Bo::InclusiveOr => a | b,
+            Bo::ShiftLeft => a.checked_shl(b)?,
+            Bo::ShiftRight => a.checked_shr(b)?,
+            _ => return Some(Literal::Bool(compare(op, a.cmp(&b))?)),
+        }),
+        (Literal::I64(a), Literal::I64(b)) => Literal::I64(match op {
+            Bo::Add => a.wrapping_add(b),
+            Bo::Subtract => a.wrapping_sub(b),
+            Bo::Multiply => a.wrapping_mul(b),
+            Bo::Divide => a.checked_div(b)?,
+            Bo::Modulo => a.checked_rem(b)?,
+            Bo::And => a & b,
+            Bo::ExclusiveOr => a ^ b,
+            Bo::InclusiveOr => a | b,
+            _ => return Some(Literal::Bool(compare(op, a.cmp(&b))?)),
+        }),
+        (Literal::U64(a), Literal::U64(b)) => Literal::U64(match op {
+            Bo::Add => a.wrapping_add(b),
+            Bo::Subtract => a.wrapping_sub(b),
+            Bo::Multiply => a.wrapping_mul(b),
+            Bo::Divide => a.checked_div(b)?,
+            Bo::Modulo => a.checked_rem(b)?,
+            Bo::And => a & b,
+            Bo::ExclusiveOr => a ^ b,
+            Bo::InclusiveOr => a | b,
+            _ => return Some(Literal::Bool(compare(op, a.cmp(&b))?)),
+        }),
+        (Literal::I32(a), Literal::U32(b)) => Literal::I32(match op {
+            Bo::ShiftLeft => a.checked_shl(b)?,
+            Bo::ShiftRight => a.checked_shr(b)?,
+            _ => return None,
+        }),
+        (Literal::Bool(a), Literal::Bool(b)) => Literal::Bool(match op {
+            Bo::Equal => a == b,
+            Bo::NotEqual => a != b,
+            Bo::LogicalAnd | Bo::And => a & b,
+            Bo::LogicalOr | Bo::InclusiveOr => a | b,
+            _ => return None,
+        }),
+        _ => return None,
+    })
+}
+
+/// Returns the result of the comparison `op`, given the ordering of its
+/// operands.
+fn compare(op: BinaryOperator, ordering: std::cmp::Ordering) -> Option<bool> {
+    use std::cmp::Ordering;
+    Some(match op {
+        BinaryOperator::Equal => ordering == Ordering::Equal,
+        BinaryOperator::NotEqual => ordering != Ordering::Equal,
+        BinaryOperator::Less => ordering == Ordering::Less,
+        BinaryOperator::LessEqual => ordering != Ordering::Greater,
+        BinaryOperator::Greater => ordering == Ordering::Greater,
+        BinaryOperator::GreaterEqual => ordering != Ordering::Less,
+        _ => return None,
+    })
+}
+
+fn finite_f32(value: f32) -> Option<Literal> {
+    value.is_finite().then_some(Literal::F32(value))
+}
+
+fn finite_f16(value: half::f16) -> Option<Literal> {
+    value.is_finite().then_some(Literal::F16(value))
+}
+
+fn finite_f64(value: f64) -> Option<Literal> {
+    value.is_finite().then_some(Literal::F64(value))
+}
+
+fn replace(handle: &mut Handle<Expression>, replacements: &[Option<Handle<Expression>>]) {
+    if let Some(replacement) = replacements[handle.index()] {
+        *handle = replacement;
+    }
+}
+
+/// Adjusts the operands of the statements of `function` to the expressions
+/// that replace them, and returns the local variables that are only ever
+/// stored to, indexed by the index of their handle.
+fn adjust_body(function: &mut Function, replacements: &[Option<Handle<Expression>>]) -> Vec<bool> {
+    // The local variable the pointer of each expression points into, if any
+    let mut roots: Vec<Option<Handle<LocalVariable>>> =
+        Vec::with_capacity(function.expressions.len());
+    let mut dead = vec![true; function.local_variables.len()];
+    let mut mark_used = |roots: &[Option<Handle<LocalVariable>>],
+                         operand: &mut Handle<Expression>| {
+        if let Some(local) = roots[operand.index()] {
+            dead[local.index()] = false;
+        }
+    };
+
+    for (_, expr) in function.expressions.iter_mut() {
+        let root = match *expr {
+            Expression::LocalVariable(local) => Some(local),
+            // Indexing into a local variable isn't a read by itself
+            Expression::Access {
+                base,
+                ref mut index,
+            } => {
+                mark_used(&roots, index);
+                roots[base.index()]
+            }
+            Expression::AccessIndex { base, .. } => roots[base.index()],
+            _ => {
+                for_each_operand(expr, &mut |operand| mark_used(&roots, operand));
+                None
+            }
+        };
+        roots.push(root);
+    }
+
+    let mut worklist: Vec<&mut [Statement]> = vec![&mut function.body];
+    while let Some(block) = worklist.pop() {
+        for statement in block {
+            for_each_statement_operand(statement, &mut |operand| replace(operand, replacements));
+            match *statement {
+                // Only the value of stores reads anything
+                Statement::Store { ref mut value, .. } => mark_used(&roots, value),
+                _ => {
+                    for_each_statement_operand(statement, &mut |operand| mark_used(&roots, operand))
+                }
+            }
+            match *statement {
+                Statement::Block(ref mut block) => worklist.push(block),
+                Statement::If {
+                    ref mut accept,
+                    ref mut reject,
+                    ..
+                } => {
+                    worklist.push(accept);
+                    worklist.push(reject);
+                }
+                Statement::Switch { ref mut cases, .. } => {
+                    for case in cases {
+                        worklist.push(&mut case.body);
+                    }
+                }
+                Statement::Loop {
+                    ref mut body,
+                    ref mut continuing,
+                    ..
+                } => {
+                    worklist.push(body);
+                    worklist.push(continuing);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    dead
+}
+
+struct BlockContext<'a> {
+    optimizer: &'a Optimizer<'a>,
+    expressions: &'a Arena<Expression>,
+    dead_locals: &'a [bool],
+    /// Whether a statement was removed.
+    changed: bool,
+}
+
+impl BlockContext<'_> {
+    /// Removes the stores to dead locals and the unreachable branches from
+    /// `block`, and splits emit ranges around the expressions that were folded
+    /// to literals.
+    fn rebuild_block(&mut self, block: &mut Block) {
+        let mut old = std::mem::take(block);
+        for (statement, span) in old.span_iter_mut() {
+            let span = span.copied().unwrap_or_default();
+            let mut statement = std::mem::replace(statement, Statement::Break);
+            match statement {
+                Statement::Emit(range) => {
+                    for range in self.split_emit(range) {
+                        block.push(Statement::Emit(range), span);
+                    }
+                    continue;
+                }
+                Statement::Store { pointer, .. } if self.stores_to_dead_local(pointer) => {
+                    self.changed = true;
+                    continue;
+                }
+                Statement::Block(ref mut body) => self.rebuild_block(body),
+                Statement::If {
+                    condition,
+                    ref mut accept,
+                    ref mut reject,
+                } => {
+                    let known = self.optimizer.literal(condition, self.expressions);
+                    if let Some(Literal::Bool(value)) = known {
+                        // The taken branch keeps its own scope
+                        let mut taken = std::mem::take(if value { accept } else { reject });
+                        self.rebuild_block(&mut taken);
+                        if !taken.is_empty() {
+                            block.push(Statement::Block(taken), span);
+                        }
+                        self.changed = true;
+                        continue;
+                    }
+                    self.rebuild_block(accept);
+                    self.rebuild_block(reject);
+                }
+                Statement::Switch { ref mut cases, .. } => {
+                    for case in cases.iter_mut() {
+                        self.rebuild_block(&mut case.body);
+                    }
+                }
+                Statement::Loop {
+                    ref mut body,
+                    ref mut continuing,
+                    ..
+                } => {
+                    self.rebuild_block(body);
+                    self.rebuild_block(continuing);
+                }
+                _ => {}
+            }
+            block.push(statement, span);
+        }
+    }
+
+    /// Returns the parts of `range` that don't contain expressions that are
+    /// already in scope, like literals.
+    fn split_emit(&self, range: Range<Expression>) -> Vec<Range<Expression>> {
+        let mut ranges = Vec::new();
+        let mut current: Option<(Handle<Expression>, Handle<Expression>)> = None;
+        for handle in range {
+            if self.expressions[handle].needs_pre_emit() {
+                ranges.extend(current.take());
+            } else {
+                current = Some(match current {
+                    Some((first, _)) => (first, handle),
+                    None => (handle, handle),
+                });
+            }
+        }
+        ranges.extend(current);
+        ranges
+            .into_iter()
+            .map(|(first, last)| Range::new_from_bounds(first, last))
+            .collect()
+    }
+
+    fn stores_to_dead_local(&self, pointer: Handle<Expression>) -> bool {
+        let mut pointer = pointer;
+        loop {
+            match self.expressions[pointer] {
+                Expression::LocalVariable(local) => return self.dead_locals[local.index()],
+                Expression::Access { base, .. } | Expression::AccessIndex { base, .. } => {
+                    pointer = base
+                }
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// Calls `f` on the handle of every expression used by `expr`.
+fn for_each_operand(expr: &mut Expression, f: &mut impl FnMut(&mut Handle<Expression>)) {
+    match *expr {
+        Expression::Literal(_)
+        | Expression::Constant(_)
+        | Expression::ZeroValue(_)
+        | Expression::FunctionArgument(_)
+        | Expression::GlobalVariable(_)
+        | Expression::LocalVariable(_)
+        | Expression::CallResult(_)
+        | Expression::AtomicResult { .. }
+        | Expression::WorkGroupUniformLoadResult { .. }
+        | Expression::RayQueryProceedResult
+        | Expression::SubgroupBallotResult
+        | Expression::SubgroupOperationResult { .. } => {}
+        Expression::Compose {
+            ref mut components, ..
+        } => {
+            for component in components.iter_mut() {
+                f(component);
+            }
+        }
+        Expression::Access {
+            ref mut base,
+            ref mut index,
+        } => {
+            f(base);
+            f(index);
+        }
+        Expression::AccessIndex { ref mut base, .. } => f(base),
+        Expression::Splat { ref mut value, .. } => f(value),
+        Expression::Swizzle { ref mut vector, .. } => f(vector),
+        Expression::Load { ref mut pointer } => f(pointer),
+        Expression::ImageSample {
+            ref mut image,
+            ref mut sampler,
+            ref mut coordinate,
+            ref mut array_index,
+            ref mut level,
+            ref mut depth_ref,
+            ..
+        } => {
+            f(image);
+            f(sampler);
+            f(coordinate);
+            for_option(array_index, f);
+            match *level {
+                crate::SampleLevel::Auto | crate::SampleLevel::Zero => {}
+                crate::SampleLevel::Exact(ref mut expr)
+                | crate::SampleLevel::Bias(ref mut expr) => f(expr),
+                crate::SampleLevel::Gradient {
+                    ref mut x,
+                    ref mut y,
+                } => {
+                    f(x);
+                    f(y);
+                }
+            }
+            for_option(depth_ref, f);
+        }
+        Expression::ImageLoad {
+            ref mut image,
+            ref mut coordinate,
+            ref mut array_index,
+            ref mut sample,
+            ref mut level,
+        } => {
+            f(image);
+            f(coordinate);
+            for_option(array_index, f);
+            for_option(sample, f);
+            for_option(level, f);
+        }
+        Expression::ImageQuery {
+            ref mut image,
+            ref mut query,
+        } => {
+            f(image);
+            if let crate::ImageQuery::Size {
+                level: Some(ref mut level),
+            } = *query
+            {
+                f(level);
+            }
+        }
+        Expression::Unary { ref mut expr, .. }
+        | Expression::Derivative { ref mut expr, .. }
+        | Expression::As { ref mut expr, .. }
+        | Expression::ArrayLength(ref mut expr) => f(expr),
+        Expression::Binary {
+            ref mut left,
+            ref mut right,
+            ..
+        } => {
+            f(left);
+            f(right);
+        }
+        Expression::Select {
+            ref mut condition,
+            ref mut accept,
+            ref mut reject,
+        } => {
+            f(condition);
+            f(accept);
+            f(reject);
+        }
+        Expression::Relational {
+            ref mut argument, ..
+        } => f(argument),
+        Expression::Math {
+            ref mut arg,
+            ref mut arg1,
+            ref mut arg2,
+            ref mut arg3,
+            ..
+        } => {
+            f(arg);
+            for_option(arg1, f);
+            for_option(arg2, f);
+            for_option(arg3, f);
+        }
+        Expression::RayQueryGetIntersection { ref mut query, .. } => f(query),
+    }
+}
+
+/// Calls `f` on the handle of every expression used by `statement` itself,
+/// excluding the statements it contains.
+fn for_each_statement_operand(
+    statement: &mut Statement,
+    f: &mut impl FnMut(&mut Handle<Expression>),
+) {
+    match *statement {
+        Statement::Emit(_)
+        | Statement::Block(_)
+        | Statement::Break
+        | Statement::Continue
+        | Statement::Kill
+        | Statement::Barrier(_)
+        | Statement::EndPrimitive
+        | Statement::Return { value: None } => {}
+        Statement::If {
+            ref mut condition, ..
+        } => f(condition),
+        Statement::Switch {
+            ref mut selector, ..
+        } => f(selector),
+        Statement::Loop {
+            ref mut break_if, ..
+        } => {
+            for_option(break_if, f);
+        }
+        Statement::Return {
+            value: Some(ref mut value),
+        }
+        | Statement::EmitVertex { ref mut value } => f(value),
+        Statement::Store {
+            ref mut pointer,
+            ref mut value,
+        } => {
+            f(pointer);
+            f(value);
+        }
+        Statement::ImageStore {
+            ref mut image,
+            ref mut coordinate,
+            ref mut array_index,
+            ref mut value,
+        } => {
+            f(image);
+            f(coordinate);
+            for_option(array_index, f);
+            f(value);
+        }
+        Statement::Atomic {
+            ref mut pointer,
+            ref mut fun,
+            ref mut value,
+            ref mut result,
+        } => {
+            f(pointer);
+            if let crate::AtomicFunction::Exchange {
+                compare: Some(ref mut compare),
+            } = *fun
+            {
+                f(compare);
+            }
+            f(value);
+            f(result);
+        }
+        Statement::WorkGroupUniformLoad {
+            ref mut pointer,
+            ref mut result,
+        } => {
+            f(pointer);
+            f(result);
+        }
+        Statement::Call {
+            ref mut arguments,
+            ref mut result,
+            ..
+        } => {
+            for argument in arguments.iter_mut() {
+                f(argument);
+            }
+            for_option(result, f);
+        }
+        Statement::RayQuery {
+            ref mut query,
+            ref mut fun,
+        } => {
+            f(query);
+            match *fun {
+                crate::RayQueryFunction::Initialize {
+                    ref mut acceleration_structure,
+                    ref mut descriptor,
+                } => {
+                    f(acceleration_structure);
+                    f(descriptor);
+                }
+                crate::RayQueryFunction::Proceed { ref mut result } => f(result),
+                crate::RayQueryFunction::Terminate => {}
+            }
+        }
+        Statement::DebugPrintf {
+            ref mut arguments, ..
+        } => {
+            for argument in arguments.iter_mut() {
+                f(argument);
+            }
+        }
+        Statement::SubgroupBallot {
+            ref mut result,
+            ref mut predicate,
+        } => {
+            for_option(predicate, f);
+            f(result);
+        }
+        Statement::SubgroupCollectiveOperation {
+            ref mut argument,
+            ref mut result,
+            ..
+        } => {
+            f(argument);
+            f(result);
+        }
+        Statement::SubgroupGather {
+            ref mut mode,
+            ref mut argument,
+            ref mut result,
+        } => {
+            match *mode {
+                crate::GatherMode::BroadcastFirst => {}
+                crate::GatherMode::Broadcast(ref mut index)
+                | crate::GatherMode::Shuffle(ref mut index)
+                | crate::GatherMode::ShuffleDown(ref mut index)
+                | crate::GatherMode::ShuffleUp(ref mut index)
+                | crate::GatherMode::ShuffleXor(ref mut index) => f(index),
+            }
+            f(argument);
+            f(result);
+        }
+    }
+}
+
+fn for_option(
+    handle: &mut Option<Handle<Expression>>,
+    f: &mut impl FnMut(&mut Handle<Expression>),
+) {
+    if let Some(ref mut handle) = *handle {
+        f(handle);
+    }
+}
+
+#[cfg(all(test, feature = "wgsl-in"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_literals() {
+        assert_eq!(
+            fold_binary(BinaryOperator::Add, Literal::I32(i32::MAX), Literal::I32(1)),
+            Some(Literal::I32(i32::MIN))
+        );
+        assert_eq!(
+            fold_binary(BinaryOperator::Less, Literal::F32(1.0), Literal::F32(2.0)),
+            Some(Literal::Bool(true))
+        );
+        assert_eq!(
+            fold_binary(BinaryOperator::Divide, Literal::U32(1), Literal::U32(0)),
+            None
+        );
+        assert_eq!(
+            fold_binary(BinaryOperator::Divide, Literal::F32(1.0), Literal::F32(0.0)),
+            None
+        );
+        assert_eq!(
+            fold_unary(UnaryOperator::Negate, Literal::I32(1)),
+            Some(Literal::I32(-1))
+        );
+    }
+
+    #[test]
+    fn optimize_wgsl() {
+        let source = r#"
+            @fragment
+            fn main(@location(0) x: f32) -> @location(0) vec4<f32> {
+                var unused: f32;
+                unused = x;
+                return vec4(x * 1.0 + 0.0);
+            }
+        "#;
+        let mut module = crate::front::wgsl::parse_str(source).unwrap();
+        optimize(&mut module);
+
+        let function = &module.entry_points[0].function;
+        assert!(!function
+            .body
+            .iter()
+            .any(|statement| matches!(*statement, Statement::Store { .. })));
+        let Some(&Statement::Return { value: Some(value) }) = function.body.last() else {
+            panic!("missing return");
+        };
+        let Expression::Splat { value, .. } = function.expressions[value] else {
+            panic!("expected a splat");
+        };
+        assert_eq!(function.expressions[value], Expression::FunctionArgument(0));
+
+        crate::valid::Validator::new(
+            crate::valid::ValidationFlags::all(),
+            crate::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .unwrap();
+    }
+
+    #[test]
+    fn remove_known_branches() {
+        let source = r#"
+            const ENABLED: bool = true;
+
+            @fragment
+            fn main() -> @location(0) vec4<f32> {
+                var color = vec4(0.0);
+                if ENABLED {
+                    color = vec4(1.0);
+                } else {
+                    color = vec4(2.0);
+                }
+                return color;
+            }
+        "#;
+        let mut module = crate::front::wgsl::parse_str(source).unwrap();
+        assert!(remove_unreachable_branches(&mut module));
+
+        let body = &module.entry_points[0].function.body;
+        assert!(!body
+            .iter()
+            .any(|statement| matches!(*statement, Statement::If { .. })));
+        assert!(body
+            .iter()
+            .any(|statement| matches!(*statement, Statement::Block(_))));
+        assert!(!remove_unreachable_branches(&mut module));
+
+        crate::valid::Validator::new(
+            crate::valid::ValidationFlags::all(),
+            crate::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+/*!
+Substitution of the values of overridable [`Constant`]s at pipeline creation.
+
+[`Constant`]: crate::Constant
+*/
+
+use crate::{Expression, Literal, Module, Override, Scalar, ScalarKind, TypeInner};
+
+/// Values of overridable constants, indexed by the name of the constant or by
+/// its numeric id written in decimal, like `"7"`.
+///
+/// Like WebGPU pipeline constants, every value is given as a `f64`, which is
+/// converted to the type of the constant.
+pub type PipelineConstants = std::collections::HashMap<String, f64>;
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum PipelineConstantError {
+    #[error("no overridable constant is identified by {0:?}")]
+    MissingConstant(String),
+    #[error("overridable constant {0:?} isn't a scalar")]
+    UnsupportedType(String),
+    #[error("value {value} can't be represented by the type of overridable constant {key:?}")]
+    InvalidValue { key: String, value: f64 },
+}
+
+/// Replaces the values of the overridable constants of `module` with the ones
+/// given in `pipeline_constants`.
+///
+/// A constant with [`Override::ByNameOrId`] is looked up by its id first, and
+/// then by its name. The constants that are given a value are no longer
+/// overridable, so backends see them as regular constants. The other ones keep
+/// their default value.
+///
+/// Every key of `pipeline_constants` must identify an overridable constant.
+pub fn process_overrides(
+    module: &mut Module,
+    pipeline_constants: &PipelineConstants,
+) -> Result<(), PipelineConstantError> {
+    let mut used_keys = crate::FastHashSet::default();
+    let mut substitutions = Vec::new();
+    for (handle, constant) in module.constants.iter() {
+        let id_key = match constant.r#override {
+            Override::None => continue,
+            Override::ByName => None,
+            Override::ByNameOrId(id) => Some(id.to_string()),
+        };
+        let Some((key, &value)) = id_key
+            .iter()
+            .chain(constant.name.as_ref())
+            .find_map(|key| pipeline_constants.get_key_value(key))
+        else {
+            continue;
+        };
+        used_keys.insert(key);
+
+        let scalar = match module.types[constant.ty].inner {
+            TypeInner::Scalar(scalar) => scalar,
+            _ => return Err(PipelineConstantError::UnsupportedType(key.clone())),
+        };
+        let literal =
+            literal_from_f64(scalar, value).ok_or_else(|| PipelineConstantError::InvalidValue {
+                key: key.clone(),
+                value,
+            })?;
+        substitutions.push((handle, literal));
+    }
+
+    if let Some(key) = pipeline_constants
+        .keys()
+        .find(|&key| !used_keys.contains(key))
+    {
+        return Err(PipelineConstantError::MissingConstant(key.clone()));
+    }
+
+    for (handle, literal) in substitutions {
+        let span = module.constants.get_span(handle);
+        let init = module
+            .const_expressions
+            .append(Expression::Literal(literal), span);
+        let constant = &mut module.constants[handle];
+        constant.init = init;
+        constant.r#override = Override::None;
+    }
+
+    Ok(())
+}
+
+/// Converts a pipeline constant value to a literal of type `scalar`, if the
+/// value is in its range. Integer types only accept whole numbers.
+fn literal_from_f64(scalar: Scalar, value: f64) -> Option<Literal> {
+    let integer = value.fract() == 0.0;
+    Some(match (scalar.kind, scalar.width) {
+        (ScalarKind::Bool, _) => Literal::Bool(value != 0.0),
+        (ScalarKind::Sint, 4)
+            if integer && (i32::MIN as f64..=i32::MAX as f64).contains(&value) =>
+        {
+            Literal::I32(value as i32)
+        }
+        (ScalarKind::Sint, 8) if integer && (i64::MIN as f64..i64::MAX as f64).contains(&value) => {
+            Literal::I64(value as i64)
+        }
+        (ScalarKind::Uint, 4) if integer && (0.0..=u32::MAX as f64).contains(&value) => {
+            Literal::U32(value as u32)
+        }
+        (ScalarKind::Uint, 8) if integer && (0.0..u64::MAX as f64).contains(&value) => {
+            Literal::U64(value as u64)
+        }
+        (ScalarKind::Float, 4) if value.abs() <= f64::from(f32::MAX) => Literal::F32(value as f32),
+        (ScalarKind::Float, 2) if value.abs() <= f64::from(half::f16::MAX) => {
+            Literal::F16(half::f16::from_f64(value))
+        }
+        (ScalarKind::Float, 8) if value.is_finite() => Literal::F64(value),
+        _ => return None,
+    })
+}
@@ -35,8 +35,14 @@ pub fn ensure_block_returns(block: &mut crate::Block) {
             | S::ImageStore { .. }
             | S::Call { .. }
             | S::RayQuery { .. }
+            | S::DebugPrintf { .. }
+            | S::EmitVertex { .. }
+            | S::EndPrimitive
             | S::Atomic { .. }
             | S::WorkGroupUniformLoad { .. }
+            | S::SubgroupBallot { .. }
+            | S::SubgroupCollectiveOperation { .. }
+            | S::SubgroupGather { .. }
             | S::Barrier(_)),
         )
         | None => block.push(S::Return { value: None }, Default::default()),
@@ -147,6 +147,14 @@ pub struct SamplingKey {
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 pub struct ExpressionInfo {
     pub uniformity: Uniformity,
+    /// If this expression may be evaluated by only some of the invocations,
+    /// store the reason here.
+    ///
+    /// This is only tracked for expressions evaluated by [`Emit`] statements.
+    /// Other expressions are evaluated up front, and are always `None`.
+    ///
+    /// [`Emit`]: crate::Statement::Emit
+    pub non_uniform_control_flow: Option<UniformityDisruptor>,
     pub ref_count: usize,
     assignable_global: Option<Handle<crate::GlobalVariable>>,
     pub ty: TypeResolution,
@@ -156,6 +164,7 @@ impl ExpressionInfo {
     const fn new() -> Self {
         ExpressionInfo {
             uniformity: Uniformity::new(),
+            non_uniform_control_flow: None,
             ref_count: 0,
             assignable_global: None,
             // this doesn't matter at this point, will be overwritten
@@ -263,6 +272,12 @@ pub struct FunctionInfo {
 
     /// Indicates that the function is using dual source blending.
     pub dual_source_blending: bool,
+
+    /// The types of the vertices emitted by this function and its callees,
+    /// with [`Statement::EmitVertex`].
+    ///
+    /// [`Statement::EmitVertex`]: crate::Statement::EmitVertex
+    pub emitted_vertex_types: crate::FastHashSet<Handle<crate::Type>>,
 }
 
 impl FunctionInfo {
@@ -272,6 +287,38 @@ impl FunctionInfo {
     pub const fn expression_count(&self) -> usize {
         self.expressions.len()
     }
+    /// Returns whether `expression` produces the same value for all the
+    /// invocations that evaluate it.
+    pub fn is_uniform(&self, expression: Handle<crate::Expression>) -> bool {
+        self[expression].uniformity.non_uniform_result.is_none()
+    }
+
+    /// Returns the expressions of `function` that compute derivatives, explicitly
+    /// or to select the level of detail of a texture sample, but may be evaluated
+    /// by only some of the invocations, along with the reason.
+    ///
+    /// The values of such expressions are undefined. Since the validator
+    /// doesn't reject them in fragment shaders yet, this lets tools warn about
+    /// them instead. `function` must be the function this info was computed for.
+    pub fn non_uniform_derivatives<'a>(
+        &'a self,
+        function: &'a crate::Function,
+    ) -> impl Iterator<Item = (Handle<crate::Expression>, UniformityDisruptor)> + 'a {
+        function
+            .expressions
+            .iter()
+            .filter(|&(_, expression)| match *expression {
+                crate::Expression::Derivative { .. } => true,
+                crate::Expression::ImageSample { level, .. } => level.implicit_derivatives(),
+                _ => false,
+            })
+            .filter_map(|(handle, _)| {
+                self[handle]
+                    .non_uniform_control_flow
+                    .map(|cause| (handle, cause))
+            })
+    }
+
     pub fn dominates_global_use(&self, other: &Self) -> bool {
         for (self_global_uses, other_global_uses) in
             self.global_uses.iter().zip(other.global_uses.iter())
@@ -300,6 +347,8 @@ impl ops::Index<Handle<crate::Expression>> for FunctionInfo {
 
 /// Disruptor of the uniform control flow.
 #[derive(Clone, Copy, Debug, thiserror::Error)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum UniformityDisruptor {
     #[error("Expression {0:?} produced non-uniform result, and control flow depends on it")]
@@ -411,6 +460,9 @@ impl FunctionInfo {
             *mine |= *other;
         }
 
+        self.emitted_vertex_types
+            .extend(callee.emitted_vertex_types.iter().cloned());
+
         Ok(FunctionUniformity {
             result: callee.uniformity.clone(),
             exit: if callee.may_kill {
@@ -570,8 +622,9 @@ impl FunctionInfo {
                 let uniform = match var.space {
                     // local data is non-uniform
                     As::Function | As::Private => false,
-                    // workgroup memory is exclusively accessed by the group
-                    As::WorkGroup => true,
+                    // workgroup memory is exclusively accessed by the group, and
+                    // the task payload is shared by all the invocations
+                    As::WorkGroup | As::TaskPayload => true,
                     // uniform data
                     As::Uniform | As::PushConstant => true,
                     // storage data is only uniform when read-only
@@ -718,7 +771,10 @@ impl FunctionInfo {
                 requirements: UniformityRequirements::empty(),
             },
             E::CallResult(function) => other_functions[function.index()].uniformity.clone(),
-            E::AtomicResult { .. } | E::RayQueryProceedResult => Uniformity {
+            E::AtomicResult { .. }
+            | E::RayQueryProceedResult
+            | E::SubgroupBallotResult
+            | E::SubgroupOperationResult { .. } => Uniformity {
                 non_uniform_result: Some(handle),
                 requirements: UniformityRequirements::empty(),
             },
@@ -777,6 +833,7 @@ impl FunctionInfo {
                 S::Emit(ref range) => {
                     let mut requirements = UniformityRequirements::empty();
                     for expr in range.clone() {
+                        self.expressions[expr.index()].non_uniform_control_flow = disruptor;
                         let req = self.expressions[expr.index()].uniformity.requirements;
                         if self
                             .flags
@@ -982,6 +1039,57 @@ impl FunctionInfo {
                     }
                     FunctionUniformity::new()
                 }
+                S::DebugPrintf { ref arguments, .. } => {
+                    for &argument in arguments {
+                        let _ = self.add_ref(argument);
+                    }
+                    FunctionUniformity::new()
+                }
+                S::EmitVertex { value } => {
+                    let _ = self.add_ref(value);
+                    // Vertices that aren't structs are rejected by the validator.
+                    if let TypeResolution::Handle(ty) = self.expressions[value.index()].ty {
+                        self.emitted_vertex_types.insert(ty);
+                    }
+                    FunctionUniformity::new()
+                }
+                S::EndPrimitive => FunctionUniformity::new(),
+                S::SubgroupBallot {
+                    result: _,
+                    predicate,
+                } => {
+                    if let Some(predicate) = predicate {
+                        let _ = self.add_ref(predicate);
+                    }
+                    FunctionUniformity::new()
+                }
+                S::SubgroupCollectiveOperation {
+                    op: _,
+                    collective_op: _,
+                    argument,
+                    result: _,
+                } => {
+                    let _ = self.add_ref(argument);
+                    FunctionUniformity::new()
+                }
+                S::SubgroupGather {
+                    mode,
+                    argument,
+                    result: _,
+                } => {
+                    let _ = self.add_ref(argument);
+                    match mode {
+                        crate::GatherMode::BroadcastFirst => {}
+                        crate::GatherMode::Broadcast(index)
+                        | crate::GatherMode::Shuffle(index)
+                        | crate::GatherMode::ShuffleDown(index)
+                        | crate::GatherMode::ShuffleUp(index)
+                        | crate::GatherMode::ShuffleXor(index) => {
+                            let _ = self.add_ref(index);
+                        }
+                    }
+                    FunctionUniformity::new()
+                }
             };
 
             disruptor = disruptor.or(uniformity.exit_disruptor());
@@ -1023,6 +1131,7 @@ impl ModuleInfo {
             expressions: vec![ExpressionInfo::new(); fun.expressions.len()].into_boxed_slice(),
             sampling: crate::FastHashSet::default(),
             dual_source_blending: false,
+            emitted_vertex_types: crate::FastHashSet::default(),
         };
         let resolve_context =
             ResolveContext::with_locals(module, &fun.local_variables, &fun.arguments);
@@ -1056,6 +1165,58 @@ impl ModuleInfo {
     pub fn get_entry_point(&self, index: usize) -> &FunctionInfo {
         &self.entry_points[index]
     }
+
+    /// Returns the expressions of all the functions and entry points of
+    /// `module` that compute derivatives in non-uniform control flow.
+    ///
+    /// See [`FunctionInfo::non_uniform_derivatives`].
+    pub fn non_uniform_derivatives<'a>(
+        &'a self,
+        module: &'a crate::Module,
+    ) -> impl Iterator<Item = NonUniformDerivative> + 'a {
+        let functions = module
+            .functions
+            .iter()
+            .map(|(handle, function)| (FunctionOrigin::Function(handle), function, &self[handle]));
+        let entry_points = module.entry_points.iter().enumerate().map(|(index, ep)| {
+            (
+                FunctionOrigin::EntryPoint(index),
+                &ep.function,
+                self.get_entry_point(index),
+            )
+        });
+        functions
+            .chain(entry_points)
+            .flat_map(|(origin, function, info)| {
+                info.non_uniform_derivatives(function)
+                    .map(move |(expression, cause)| NonUniformDerivative {
+                        function: origin,
+                        expression,
+                        cause,
+                    })
+            })
+    }
+}
+
+/// A function of a module, which is either a regular function or an entry point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FunctionOrigin {
+    Function(Handle<crate::Function>),
+    /// The index of the entry point in [`Module::entry_points`].
+    ///
+    /// [`Module::entry_points`]: crate::Module::entry_points
+    EntryPoint(usize),
+}
+
+/// An expression computing derivatives in non-uniform control flow, as
+/// reported by [`ModuleInfo::non_uniform_derivatives`].
+#[derive(Clone, Copy, Debug)]
+pub struct NonUniformDerivative {
+    /// The function containing the expression.
+    pub function: FunctionOrigin,
+    pub expression: Handle<crate::Expression>,
+    /// Why the expression may be evaluated by only some of the invocations.
+    pub cause: UniformityDisruptor,
 }
 
 #[test]
@@ -1136,6 +1297,7 @@ fn uniform_control_flow() {
         expressions: vec![ExpressionInfo::new(); expressions.len()].into_boxed_slice(),
         sampling: crate::FastHashSet::default(),
         dual_source_blending: false,
+        emitted_vertex_types: crate::FastHashSet::default(),
     };
     let resolve_context = ResolveContext {
         constants: &Arena::new(),
@@ -1279,3 +1441,45 @@ fn uniform_control_flow() {
     );
     assert_eq!(info[non_uniform_global], GlobalUse::READ | GlobalUse::WRITE);
 }
+
+#[cfg(feature = "wgsl-in")]
+#[test]
+fn non_uniform_derivatives() {
+    let source = r#"
+        @group(0) @binding(0) var t: texture_2d<f32>;
+        @group(0) @binding(1) var s: sampler;
+
+        fn sample_level(uv: vec2<f32>) -> vec4<f32> {
+            return textureSampleLevel(t, s, uv, 0.0);
+        }
+
+        @fragment
+        fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+            var color = textureSample(t, s, uv);
+            if uv.x > 0.5 {
+                color += textureSample(t, s, uv * 2.0) + sample_level(uv);
+                color.x += dpdx(uv.y);
+            }
+            return color;
+        }
+    "#;
+    let module = crate::front::wgsl::parse_str(source).unwrap();
+    let info = super::Validator::new(super::ValidationFlags::all(), Default::default())
+        .validate(&module)
+        .unwrap();
+
+    let function = &module.entry_points[0].function;
+    let found: Vec<_> = info.non_uniform_derivatives(&module).collect();
+    assert_eq!(found.len(), 2);
+    for derivative in found {
+        assert_eq!(derivative.function, FunctionOrigin::EntryPoint(0));
+        assert!(matches!(
+            function.expressions[derivative.expression],
+            crate::Expression::ImageSample { .. } | crate::Expression::Derivative { .. }
+        ));
+        let UniformityDisruptor::Expression(condition) = derivative.cause else {
+            panic!("unexpected disruptor {:?}", derivative.cause);
+        };
+        assert!(!info.get_entry_point(0).is_uniform(condition));
+    }
+}
@@ -1590,6 +1590,7 @@ impl super::Validator {
                     return Err(ExpressionError::InvalidRayQueryType(query));
                 }
             },
+            E::SubgroupBallotResult | E::SubgroupOperationResult { .. } => ShaderStages::all(),
         };
         Ok(stages)
     }
@@ -1633,6 +1634,7 @@ pub fn check_literal_value(literal: crate::Literal) -> Result<(), LiteralError>
     let is_nan = match literal {
         crate::Literal::F64(v) => v.is_nan(),
         crate::Literal::F32(v) => v.is_nan(),
+        crate::Literal::F16(v) => v.is_nan(),
         _ => false,
     };
     if is_nan {
@@ -1642,6 +1644,7 @@ pub fn check_literal_value(literal: crate::Literal) -> Result<(), LiteralError>
     let is_infinite = match literal {
         crate::Literal::F64(v) => v.is_infinite(),
         crate::Literal::F32(v) => v.is_infinite(),
+        crate::Literal::F16(v) => v.is_infinite(),
         _ => false,
     };
     if is_infinite {
@@ -1751,14 +1754,13 @@ fn f64_const_literals() {
     assert!(result.is_ok());
 }
 
-/// Using I64 in a function's expression arena is forbidden.
+/// Using I64 in a function's expression arena requires the `SHADER_INT64` capability.
 #[cfg(feature = "validate")]
 #[test]
 fn i64_runtime_literals() {
     let result = validate_with_expression(
         crate::Expression::Literal(crate::Literal::I64(1729)),
-        // There is no capability that enables this.
-        super::Capabilities::all(),
+        super::Capabilities::default(),
     );
     let error = result.unwrap_err().into_inner();
     assert!(matches!(
@@ -1766,32 +1768,78 @@ fn i64_runtime_literals() {
         crate::valid::ValidationError::Function {
             source: super::FunctionError::Expression {
                 source: super::ExpressionError::Literal(super::LiteralError::Width(
-                    super::r#type::WidthError::Unsupported64Bit
+                    super::r#type::WidthError::MissingCapability {
+                        name: "i64",
+                        flag: "SHADER_INT64",
+                    }
                 ),),
                 ..
             },
             ..
         }
     ));
+
+    let result = validate_with_expression(
+        crate::Expression::Literal(crate::Literal::I64(1729)),
+        super::Capabilities::default() | super::Capabilities::SHADER_INT64,
+    );
+    assert!(result.is_ok());
 }
 
-/// Using I64 in a module's constant expression arena is forbidden.
+/// Using I64 in a module's constant expression arena requires the `SHADER_INT64` capability.
 #[cfg(feature = "validate")]
 #[test]
 fn i64_const_literals() {
     let result = validate_with_const_expression(
         crate::Expression::Literal(crate::Literal::I64(1729)),
-        // There is no capability that enables this.
-        super::Capabilities::all(),
+        super::Capabilities::default(),
+    );
+    let error = result.unwrap_err().into_inner();
+    assert!(matches!(
+        error,
+        crate::valid::ValidationError::ConstExpression {
+            source: super::ConstExpressionError::Literal(super::LiteralError::Width(
+                super::r#type::WidthError::MissingCapability {
+                    name: "i64",
+                    flag: "SHADER_INT64",
+                },
+            ),),
+            ..
+        }
+    ));
+
+    let result = validate_with_const_expression(
+        crate::Expression::Literal(crate::Literal::I64(1729)),
+        super::Capabilities::default() | super::Capabilities::SHADER_INT64,
+    );
+    assert!(result.is_ok());
+}
+
+/// Using U64 in a module's constant expression arena requires the `SHADER_INT64` capability.
+#[cfg(feature = "validate")]
+#[test]
+fn u64_const_literals() {
+    let result = validate_with_const_expression(
+        crate::Expression::Literal(crate::Literal::U64(1729)),
+        super::Capabilities::default(),
     );
     let error = result.unwrap_err().into_inner();
     assert!(matches!(
         error,
         crate::valid::ValidationError::ConstExpression {
             source: super::ConstExpressionError::Literal(super::LiteralError::Width(
-                super::r#type::WidthError::Unsupported64Bit,
+                super::r#type::WidthError::MissingCapability {
+                    name: "u64",
+                    flag: "SHADER_INT64",
+                },
             ),),
             ..
         }
     ));
+
+    let result = validate_with_const_expression(
+        crate::Expression::Literal(crate::Literal::U64(1729)),
+        super::Capabilities::default() | super::Capabilities::SHADER_INT64,
+    );
+    assert!(result.is_ok());
 }
@@ -47,6 +47,22 @@ pub enum AtomicError {
     ResultTypeMismatch(Handle<crate::Expression>),
 }
 
+#[derive(Clone, Debug, thiserror::Error)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum SubgroupError {
+    #[error("Operand {0:?} has invalid type.")]
+    InvalidOperand(Handle<crate::Expression>),
+    #[error("Result type for {0:?} doesn't match the statement")]
+    ResultTypeMismatch(Handle<crate::Expression>),
+    #[error("The invocation id {0:?} of a subgroup gather is not a `u32`")]
+    InvalidInvocationIdExprType(Handle<crate::Expression>),
+    #[error("Subgroup operation {op:?} can't be used as a {collective_op:?}")]
+    UnsupportedOperation {
+        op: crate::SubgroupOperation,
+        collective_op: crate::CollectiveOperation,
+    },
+}
+
 #[derive(Clone, Debug, thiserror::Error)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum LocalVariableError {
@@ -127,6 +143,8 @@ pub enum FunctionError {
     },
     #[error("Atomic operation is invalid")]
     InvalidAtomic(#[from] AtomicError),
+    #[error("Subgroup operation is invalid")]
+    InvalidSubgroup(#[from] SubgroupError),
     #[error("Ray Query {0:?} is not a local variable")]
     InvalidRayQueryExpression(Handle<crate::Expression>),
     #[error("Acceleration structure {0:?} is not a matching expression")]
@@ -155,6 +173,12 @@ pub enum FunctionError {
     WorkgroupUniformLoadExpressionMismatch(Handle<crate::Expression>),
     #[error("The expression {0:?} is not valid as a WorkGroupUniformLoad argument. It should be a Pointer in Workgroup address space")]
     WorkgroupUniformLoadInvalidPointer(Handle<crate::Expression>),
+    #[error("Capability {0:?} is required")]
+    MissingCapability(super::Capabilities),
+    #[error("The debug printf argument {0:?} is not a scalar or vector of 32-bit numbers")]
+    InvalidDebugPrintfArgument(Handle<crate::Expression>),
+    #[error("The emitted vertex {0:?} is not a struct")]
+    InvalidEmittedVertex(Handle<crate::Expression>),
 }
 
 bitflags::bitflags! {
@@ -400,6 +424,134 @@ impl super::Validator {
         Ok(())
     }
 
+    fn require_subgroup(&self, span: crate::Span) -> Result<(), WithSpan<FunctionError>> {
+        if !self.capabilities.contains(super::Capabilities::SUBGROUP) {
+            return Err(
+                FunctionError::MissingCapability(super::Capabilities::SUBGROUP)
+                    .with_span_static(span, "subgroup operation"),
+            );
+        }
+        Ok(())
+    }
+
+    fn validate_subgroup_operation(
+        &mut self,
+        op: &crate::SubgroupOperation,
+        collective_op: &crate::CollectiveOperation,
+        argument: Handle<crate::Expression>,
+        result: Handle<crate::Expression>,
+        context: &BlockContext,
+    ) -> Result<(), WithSpan<FunctionError>> {
+        let argument_inner = context.resolve_type(argument, &self.valid_expression_set)?;
+
+        let (is_scalar, scalar) = match *argument_inner {
+            crate::TypeInner::Scalar(scalar) => (true, scalar),
+            crate::TypeInner::Vector { scalar, .. } => (false, scalar),
+            _ => {
+                log::error!("Subgroup operand type {:?}", argument_inner);
+                return Err(SubgroupError::InvalidOperand(argument)
+                    .with_span_handle(argument, context.expressions)
+                    .into_other());
+            }
+        };
+
+        use crate::ScalarKind as sk;
+        use crate::SubgroupOperation as sg;
+        match (scalar.kind, *op) {
+            (sk::Bool, sg::All | sg::Any) if is_scalar => {}
+            (sk::Sint | sk::Uint | sk::Float, sg::Add | sg::Mul | sg::Min | sg::Max) => {}
+            (sk::Sint | sk::Uint, sg::And | sg::Or | sg::Xor) => {}
+            (_, _) => {
+                log::error!("Subgroup operand type {:?}", argument_inner);
+                return Err(SubgroupError::InvalidOperand(argument)
+                    .with_span_handle(argument, context.expressions)
+                    .into_other());
+            }
+        };
+
+        use crate::CollectiveOperation as co;
+        match (*collective_op, *op) {
+            (co::Reduce, _) => {}
+            (co::InclusiveScan | co::ExclusiveScan, sg::Add | sg::Mul) => {}
+            (_, _) => {
+                return Err(SubgroupError::UnsupportedOperation {
+                    op: *op,
+                    collective_op: *collective_op,
+                }
+                .with_span()
+                .into_other());
+            }
+        };
+
+        self.emit_expression(result, context)?;
+        match context.expressions[result] {
+            crate::Expression::SubgroupOperationResult { ty }
+                if context.types[ty].inner == *argument_inner => {}
+            _ => {
+                return Err(SubgroupError::ResultTypeMismatch(result)
+                    .with_span_handle(result, context.expressions)
+                    .into_other())
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_subgroup_gather(
+        &mut self,
+        mode: &crate::GatherMode,
+        argument: Handle<crate::Expression>,
+        result: Handle<crate::Expression>,
+        context: &BlockContext,
+    ) -> Result<(), WithSpan<FunctionError>> {
+        match *mode {
+            crate::GatherMode::BroadcastFirst => {}
+            crate::GatherMode::Broadcast(index)
+            | crate::GatherMode::Shuffle(index)
+            | crate::GatherMode::ShuffleDown(index)
+            | crate::GatherMode::ShuffleUp(index)
+            | crate::GatherMode::ShuffleXor(index) => {
+                let index_ty = context.resolve_type(index, &self.valid_expression_set)?;
+                if *index_ty != crate::TypeInner::Scalar(crate::Scalar::U32) {
+                    log::error!("Subgroup gather index type {:?}", index_ty);
+                    return Err(SubgroupError::InvalidInvocationIdExprType(index)
+                        .with_span_handle(index, context.expressions)
+                        .into_other());
+                }
+            }
+        }
+
+        let argument_inner = context.resolve_type(argument, &self.valid_expression_set)?;
+        if !matches!(
+            *argument_inner,
+            crate::TypeInner::Scalar(crate::Scalar { kind, .. })
+                | crate::TypeInner::Vector {
+                    scalar: crate::Scalar { kind, .. },
+                    ..
+                }
+            if matches!(
+                kind,
+                crate::ScalarKind::Uint | crate::ScalarKind::Sint | crate::ScalarKind::Float
+            )
+        ) {
+            log::error!("Subgroup gather operand type {:?}", argument_inner);
+            return Err(SubgroupError::InvalidOperand(argument)
+                .with_span_handle(argument, context.expressions)
+                .into_other());
+        }
+
+        self.emit_expression(result, context)?;
+        match context.expressions[result] {
+            crate::Expression::SubgroupOperationResult { ty }
+                if context.types[ty].inner == *argument_inner => {}
+            _ => {
+                return Err(SubgroupError::ResultTypeMismatch(result)
+                    .with_span_handle(result, context.expressions)
+                    .into_other())
+            }
+        }
+        Ok(())
+    }
+
     fn validate_block_impl(
         &mut self,
         statements: &crate::Block,
@@ -613,8 +765,20 @@ impl super::Validator {
                     stages &= super::ShaderStages::FRAGMENT;
                     finished = true;
                 }
-                S::Barrier(_) => {
-                    stages &= super::ShaderStages::COMPUTE;
+                S::Barrier(barrier) => {
+                    stages &= super::ShaderStages::COMPUTE
+                        | super::ShaderStages::TASK
+                        | super::ShaderStages::MESH;
+                    if barrier.contains(crate::Barrier::SUB_GROUP)
+                        && !self
+                            .capabilities
+                            .contains(super::Capabilities::SUBGROUP_BARRIER)
+                    {
+                        return Err(FunctionError::MissingCapability(
+                            super::Capabilities::SUBGROUP_BARRIER,
+                        )
+                        .with_span_static(span, "subgroup barrier"));
+                    }
                 }
                 S::Store { pointer, value } => {
                     let mut current = pointer;
@@ -818,7 +982,9 @@ impl super::Validator {
                     self.validate_atomic(pointer, fun, value, result, context)?;
                 }
                 S::WorkGroupUniformLoad { pointer, result } => {
-                    stages &= super::ShaderStages::COMPUTE;
+                    stages &= super::ShaderStages::COMPUTE
+                        | super::ShaderStages::TASK
+                        | super::ShaderStages::MESH;
                     let pointer_inner =
                         context.resolve_type(pointer, &self.valid_expression_set)?;
                     match *pointer_inner {
@@ -904,6 +1070,87 @@ impl super::Validator {
                         crate::RayQueryFunction::Terminate => {}
                     }
                 }
+                S::DebugPrintf { ref arguments, .. } => {
+                    if !self
+                        .capabilities
+                        .contains(super::Capabilities::DEBUG_PRINTF)
+                    {
+                        return Err(FunctionError::MissingCapability(
+                            super::Capabilities::DEBUG_PRINTF,
+                        )
+                        .with_span_static(span, "debug printf"));
+                    }
+                    for &argument in arguments {
+                        match *context.resolve_type(argument, &self.valid_expression_set)? {
+                            Ti::Scalar(crate::Scalar {
+                                kind:
+                                    crate::ScalarKind::Sint
+                                    | crate::ScalarKind::Uint
+                                    | crate::ScalarKind::Float,
+                                width: 4,
+                            })
+                            | Ti::Vector {
+                                scalar:
+                                    crate::Scalar {
+                                        kind:
+                                            crate::ScalarKind::Sint
+                                            | crate::ScalarKind::Uint
+                                            | crate::ScalarKind::Float,
+                                        width: 4,
+                                    },
+                                ..
+                            } => {}
+                            _ => {
+                                return Err(FunctionError::InvalidDebugPrintfArgument(argument)
+                                    .with_span_handle(argument, context.expressions))
+                            }
+                        }
+                    }
+                }
+                S::EmitVertex { value } => {
+                    stages &= super::ShaderStages::GEOMETRY;
+                    if !matches!(
+                        *context.resolve_type(value, &self.valid_expression_set)?,
+                        Ti::Struct { .. }
+                    ) {
+                        return Err(FunctionError::InvalidEmittedVertex(value)
+                            .with_span_handle(value, context.expressions));
+                    }
+                }
+                S::EndPrimitive => {
+                    stages &= super::ShaderStages::GEOMETRY;
+                }
+                S::SubgroupBallot { result, predicate } => {
+                    self.require_subgroup(span)?;
+                    if let Some(predicate) = predicate {
+                        let predicate_inner =
+                            context.resolve_type(predicate, &self.valid_expression_set)?;
+                        if *predicate_inner != Ti::Scalar(crate::Scalar::BOOL) {
+                            log::error!("Subgroup ballot predicate type {:?}", predicate_inner);
+                            return Err(SubgroupError::InvalidOperand(predicate)
+                                .with_span_handle(predicate, context.expressions)
+                                .into_other());
+                        }
+                    }
+                    self.emit_expression(result, context)?;
+                }
+                S::SubgroupCollectiveOperation {
+                    ref op,
+                    ref collective_op,
+                    argument,
+                    result,
+                } => {
+                    self.require_subgroup(span)?;
+                    self.validate_subgroup_operation(op, collective_op, argument, result, context)?;
+                }
+                S::SubgroupGather {
+                    ref mode,
+                    argument,
+                    result,
+                } => {
+                    self.require_subgroup(span)?;
+                    self.validate_subgroup_gather(mode, argument, result, context)?;
+                }
             }
         }
         Ok(BlockInfo { stages, finished })
@@ -155,6 +155,19 @@ impl super::Validator {
         };
 
         for entry_point in entry_points.iter() {
+            if let Some(task_payload) = entry_point.task_payload {
+                task_payload.check_valid_for(global_variables)?;
+            }
+            if let Some(ref mesh_info) = entry_point.mesh_info {
+                validate_type(mesh_info.vertex_output_type)?;
+                validate_type(mesh_info.primitive_output_type)?;
+                mesh_info
+                    .output_variable
+                    .check_valid_for(global_variables)?;
+            }
+            if let Some(ref geometry_info) = entry_point.geometry_info {
+                validate_type(geometry_info.vertex_output_type)?;
+            }
             validate_function(None, &entry_point.function)?;
         }
 
@@ -390,7 +403,9 @@ impl super::Validator {
             }
             crate::Expression::AtomicResult { .. }
             | crate::Expression::RayQueryProceedResult
-            | crate::Expression::WorkGroupUniformLoadResult { .. } => (),
+            | crate::Expression::WorkGroupUniformLoadResult { .. }
+            | crate::Expression::SubgroupBallotResult
+            | crate::Expression::SubgroupOperationResult { .. } => (),
             crate::Expression::ArrayLength(array) => {
                 handle.check_dep(array)?;
             }
@@ -535,10 +550,53 @@ impl super::Validator {
                 }
                 Ok(())
             }
+            crate::Statement::DebugPrintf { ref arguments, .. } => {
+                for arg in arguments.iter().copied() {
+                    validate_expr(arg)?;
+                }
+                Ok(())
+            }
+            crate::Statement::EmitVertex { value } => {
+                validate_expr(value)?;
+                Ok(())
+            }
+            crate::Statement::SubgroupBallot { result, predicate } => {
+                validate_expr_opt(predicate)?;
+                validate_expr(result)?;
+                Ok(())
+            }
+            crate::Statement::SubgroupCollectiveOperation {
+                op: _,
+                collective_op: _,
+                argument,
+                result,
+            } => {
+                validate_expr(argument)?;
+                validate_expr(result)?;
+                Ok(())
+            }
+            crate::Statement::SubgroupGather {
+                mode,
+                argument,
+                result,
+            } => {
+                validate_expr(argument)?;
+                match mode {
+                    crate::GatherMode::BroadcastFirst => {}
+                    crate::GatherMode::Broadcast(index)
+                    | crate::GatherMode::Shuffle(index)
+                    | crate::GatherMode::ShuffleDown(index)
+                    | crate::GatherMode::ShuffleUp(index)
+                    | crate::GatherMode::ShuffleXor(index) => validate_expr(index)?,
+                }
+                validate_expr(result)?;
+                Ok(())
+            }
             crate::Statement::Break
             | crate::Statement::Continue
             | crate::Statement::Kill
-            | crate::Statement::Barrier(_) => Ok(()),
+            | crate::Statement::Barrier(_)
+            | crate::Statement::EndPrimitive => Ok(()),
         })
     }
 }
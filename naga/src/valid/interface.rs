@@ -73,6 +73,8 @@ pub enum VaryingError {
         location: u32,
         attribute: &'static str,
     },
+    #[error("The type {0:?} is not an array of one value per vertex of the input primitive")]
+    InvalidPerVertexType(Handle<crate::Type>),
 }
 
 #[derive(Clone, Debug, thiserror::Error)]
@@ -107,6 +109,52 @@ pub enum EntryPointError {
         "Invalid locations {location_mask:?} are set while dual source blending. Only location 0 may be set."
     )]
     InvalidLocationsWhileDualSourceBlending { location_mask: BitSet },
+    #[error("Task shaders must return a `@builtin(mesh_task_size)` output value")]
+    MissingMeshTaskSize,
+    #[error("Mesh shaders can't return a value")]
+    UnexpectedMeshResult,
+    #[error("Mesh shaders must have an output variable")]
+    MissingMeshInfo,
+    #[error("Mesh output is not applicable")]
+    UnexpectedMeshInfo,
+    #[error("Task payload is not applicable")]
+    UnexpectedTaskPayload,
+    #[error("Task payload {0:?} is not in the task payload address space")]
+    InvalidTaskPayload(Handle<crate::GlobalVariable>),
+    #[error("Mesh output variable {0:?} is not in the workgroup address space")]
+    InvalidMeshOutputSpace(Handle<crate::GlobalVariable>),
+    #[error("The type of mesh output variable {0:?} doesn't match the mesh output")]
+    InvalidMeshOutputType(Handle<crate::GlobalVariable>),
+    #[error("Mesh shaders must output at least one vertex and one primitive")]
+    EmptyMeshOutput,
+    #[error("Mesh primitive output error")]
+    MeshPrimitive(#[source] VaryingError),
+    #[error("Mesh primitive outputs can only hold built-in values")]
+    MeshPrimitiveLocation,
+    #[error("Mesh primitive outputs must hold the vertex indices of their {0:?} topology, and optionally whether they are culled")]
+    InvalidMeshPrimitiveOutput(crate::MeshOutputTopology),
+    #[error("Geometry shaders can't return a value")]
+    UnexpectedGeometryResult,
+    #[error("Geometry shaders must declare their input and output primitives")]
+    MissingGeometryInfo,
+    #[error("Geometry primitives are not applicable")]
+    UnexpectedGeometryInfo,
+    #[error("Geometry shaders must output at least one vertex, and be invoked at least once")]
+    EmptyGeometryOutput,
+    #[error("The geometry vertex output type {0:?} is not a struct")]
+    InvalidGeometryVertexType(Handle<crate::Type>),
+    #[error("The function emits vertices that aren't of the geometry vertex output type")]
+    InvalidEmittedVertexType,
+    #[error("Tessellation shaders must declare the patches they process")]
+    MissingTessellationInfo,
+    #[error("Tessellation patches are not applicable, or don't match the stage")]
+    UnexpectedTessellationInfo,
+    #[error("Tessellation control shaders must output at least one vertex")]
+    EmptyTessellationOutput,
+    #[error(
+        "Tessellation control shaders returning a value must take the `InvocationId` built-in"
+    )]
+    MissingInvocationId,
 }
 
 fn storage_usage(access: crate::StorageAccess) -> GlobalUse {
@@ -123,6 +171,15 @@ fn storage_usage(access: crate::StorageAccess) -> GlobalUse {
 struct VaryingContext<'a> {
     stage: crate::ShaderStage,
     output: bool,
+    /// Whether varyings holding a value per vertex, see
+    /// [`Binding::is_per_vertex`], hold an array of values for every vertex
+    /// of the input primitive or patch.
+    ///
+    /// [`Binding::is_per_vertex`]: crate::Binding::is_per_vertex
+    per_vertex_arrays: bool,
+    /// The number of vertices in the input primitive of a geometry stage,
+    /// which the arrays of per-vertex inputs must hold.
+    vertex_count: Option<u32>,
     second_blend_source: bool,
     types: &'a UniqueArena<crate::Type>,
     type_info: &'a Vec<super::r#type::TypeInfo>,
@@ -140,6 +197,18 @@ impl VaryingContext<'_> {
     ) -> Result<(), VaryingError> {
         use crate::{BuiltIn as Bi, ShaderStage as St, TypeInner as Ti, VectorSize as Vs};
 
+        let ty = if self.per_vertex_arrays && binding.is_per_vertex() {
+            match self.types[ty].inner {
+                Ti::Array {
+                    base,
+                    size: crate::ArraySize::Constant(size),
+                    ..
+                } if self.vertex_count.map_or(true, |count| size.get() == count) => base,
+                _ => return Err(VaryingError::InvalidPerVertexType(ty)),
+            }
+        } else {
+            ty
+        };
         let ty_inner = &self.types[ty].inner;
         match *binding {
             crate::Binding::BuiltIn(built_in) => {
@@ -162,6 +231,26 @@ impl VaryingContext<'_> {
                     Bi::PrimitiveIndex => Capabilities::PRIMITIVE_INDEX,
                     Bi::ViewIndex => Capabilities::MULTIVIEW,
                     Bi::SampleIndex => Capabilities::MULTISAMPLED_SHADING,
+                    Bi::InvocationId if self.stage == St::TessellationControl => {
+                        Capabilities::TESSELLATION_SHADER
+                    }
+                    Bi::InvocationId => Capabilities::GEOMETRY_SHADER,
+                    Bi::TessLevelOuter | Bi::TessLevelInner | Bi::TessCoord | Bi::PatchVertices => {
+                        Capabilities::TESSELLATION_SHADER
+                    }
+                    Bi::MeshTaskSize
+                    | Bi::Vertices
+                    | Bi::Primitives
+                    | Bi::VertexCount
+                    | Bi::PrimitiveCount
+                    | Bi::PointIndex
+                    | Bi::LineIndices
+                    | Bi::TriangleIndices
+                    | Bi::CullPrimitive => Capabilities::MESH_SHADER,
+                    Bi::NumSubgroups
+                    | Bi::SubgroupId
+                    | Bi::SubgroupSize
+                    | Bi::SubgroupInvocationId => Capabilities::SUBGROUP,
                     _ => Capabilities::empty(),
                 };
                 if !self.capabilities.contains(required) {
@@ -174,7 +263,13 @@ impl VaryingContext<'_> {
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::ClipDistance | Bi::CullDistance => (
-                        self.stage == St::Vertex && self.output,
+                        match self.stage {
+                            St::Vertex | St::Mesh => self.output,
+                            St::Geometry | St::TessellationControl | St::TessellationEvaluation => {
+                                true
+                            }
+                            St::Fragment | St::Compute | St::Task => false,
+                        },
                         match *ty_inner {
                             Ti::Array { base, .. } => {
                                 self.types[base].inner == Ti::Scalar(crate::Scalar::F32)
@@ -183,7 +278,13 @@ impl VaryingContext<'_> {
                         },
                     ),
                     Bi::PointSize => (
-                        self.stage == St::Vertex && self.output,
+                        match self.stage {
+                            St::Vertex | St::Mesh => self.output,
+                            St::Geometry | St::TessellationControl | St::TessellationEvaluation => {
+                                true
+                            }
+                            St::Fragment | St::Compute | St::Task => false,
+                        },
                         *ty_inner == Ti::Scalar(crate::Scalar::F32),
                     ),
                     Bi::PointCoord => (
@@ -196,9 +297,12 @@ impl VaryingContext<'_> {
                     ),
                     Bi::Position { .. } => (
                         match self.stage {
-                            St::Vertex => self.output,
+                            St::Vertex | St::Mesh => self.output,
                             St::Fragment => !self.output,
-                            St::Compute => false,
+                            St::Geometry | St::TessellationControl | St::TessellationEvaluation => {
+                                true
+                            }
+                            St::Compute | St::Task => false,
                         },
                         *ty_inner
                             == Ti::Vector {
@@ -208,8 +312,12 @@ impl VaryingContext<'_> {
                     ),
                     Bi::ViewIndex => (
                         match self.stage {
-                            St::Vertex | St::Fragment => !self.output,
-                            St::Compute => false,
+                            St::Vertex
+                            | St::Fragment
+                            | St::Geometry
+                            | St::TessellationControl
+                            | St::TessellationEvaluation => !self.output,
+                            St::Compute | St::Task | St::Mesh => false,
                         },
                         *ty_inner == Ti::Scalar(crate::Scalar::I32),
                     ),
@@ -222,7 +330,52 @@ impl VaryingContext<'_> {
                         *ty_inner == Ti::Scalar(crate::Scalar::BOOL),
                     ),
                     Bi::PrimitiveIndex => (
-                        self.stage == St::Fragment && !self.output,
+                        match self.stage {
+                            St::Fragment | St::TessellationControl | St::TessellationEvaluation => {
+                                !self.output
+                            }
+                            St::Geometry => true,
+                            St::Vertex | St::Compute | St::Task | St::Mesh => false,
+                        },
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
+                    Bi::InvocationId => (
+                        matches!(self.stage, St::Geometry | St::TessellationControl)
+                            && !self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
+                    Bi::TessLevelOuter | Bi::TessLevelInner => (
+                        match self.stage {
+                            St::TessellationControl => self.output,
+                            St::TessellationEvaluation => !self.output,
+                            _ => false,
+                        },
+                        match *ty_inner {
+                            Ti::Array {
+                                base,
+                                size: crate::ArraySize::Constant(size),
+                                ..
+                            } => {
+                                let expected = if built_in == Bi::TessLevelOuter { 4 } else { 2 };
+                                self.types[base].inner == Ti::Scalar(crate::Scalar::F32)
+                                    && size.get() == expected
+                            }
+                            _ => false,
+                        },
+                    ),
+                    Bi::TessCoord => (
+                        self.stage == St::TessellationEvaluation && !self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::F32,
+                            },
+                    ),
+                    Bi::PatchVertices => (
+                        matches!(
+                            self.stage,
+                            St::TessellationControl | St::TessellationEvaluation
+                        ) && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::SampleIndex => (
@@ -234,7 +387,7 @@ impl VaryingContext<'_> {
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::LocalInvocationIndex => (
-                        self.stage == St::Compute && !self.output,
+                        matches!(self.stage, St::Compute | St::Task | St::Mesh) && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::GlobalInvocationId
@@ -242,13 +395,57 @@ impl VaryingContext<'_> {
                     | Bi::WorkGroupId
                     | Bi::WorkGroupSize
                     | Bi::NumWorkGroups => (
-                        self.stage == St::Compute && !self.output,
+                        matches!(self.stage, St::Compute | St::Task | St::Mesh) && !self.output,
                         *ty_inner
                             == Ti::Vector {
                                 size: Vs::Tri,
                                 scalar: crate::Scalar::U32,
                             },
                     ),
+                    Bi::MeshTaskSize => (
+                        self.stage == St::Task && self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    // These are only valid as members of the type of a mesh
+                    // output variable, which is validated separately.
+                    Bi::Vertices | Bi::Primitives | Bi::VertexCount | Bi::PrimitiveCount => {
+                        (false, true)
+                    }
+                    Bi::PointIndex => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
+                    Bi::LineIndices => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Bi,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    Bi::TriangleIndices => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    Bi::CullPrimitive => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::BOOL),
+                    ),
+                    Bi::NumSubgroups | Bi::SubgroupId => (
+                        matches!(self.stage, St::Compute | St::Task | St::Mesh) && !self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
+                    Bi::SubgroupSize | Bi::SubgroupInvocationId => {
+                        (!self.output, *ty_inner == Ti::Scalar(crate::Scalar::U32))
+                    }
                 };
 
                 if !visible {
@@ -264,6 +461,7 @@ impl VaryingContext<'_> {
                 interpolation,
                 sampling,
                 second_blend_source,
+                patch,
             } => {
                 // Only IO-shareable types may be stored in locations.
                 if !self.type_info[ty.index()]
@@ -302,6 +500,17 @@ impl VaryingContext<'_> {
                     }
 
                     self.second_blend_source = true;
+                } else if patch {
+                    // Per-patch varyings are assigned locations separately
+                    // from per-vertex ones.
+                    let visible = match self.stage {
+                        St::TessellationControl => self.output,
+                        St::TessellationEvaluation => !self.output,
+                        _ => false,
+                    };
+                    if !visible {
+                        return Err(VaryingError::InvalidAttributeInStage("patch", self.stage));
+                    }
                 } else if !self.location_mask.insert(location as usize) {
                     if self.flags.contains(super::ValidationFlags::BINDINGS) {
                         return Err(VaryingError::BindingCollision { location });
@@ -309,9 +518,14 @@ impl VaryingContext<'_> {
                 }
 
                 let needs_interpolation = match self.stage {
-                    crate::ShaderStage::Vertex => self.output,
+                    crate::ShaderStage::Vertex
+                    | crate::ShaderStage::Mesh
+                    | crate::ShaderStage::Geometry
+                    | crate::ShaderStage::TessellationEvaluation => self.output,
                     crate::ShaderStage::Fragment => !self.output,
-                    crate::ShaderStage::Compute => false,
+                    crate::ShaderStage::Compute
+                    | crate::ShaderStage::Task
+                    | crate::ShaderStage::TessellationControl => false,
                 };
 
                 // It doesn't make sense to specify a sampling when `interpolation` is `Flat`, but
@@ -486,6 +700,14 @@ impl super::Validator {
             }
             crate::AddressSpace::Private => (TypeFlags::CONSTRUCTIBLE, false),
             crate::AddressSpace::WorkGroup => (TypeFlags::DATA | TypeFlags::SIZED, false),
+            crate::AddressSpace::TaskPayload => {
+                if !self.capabilities.contains(Capabilities::MESH_SHADER) {
+                    return Err(GlobalVariableError::UnsupportedCapability(
+                        Capabilities::MESH_SHADER,
+                    ));
+                }
+                (TypeFlags::DATA | TypeFlags::SIZED, false)
+            }
             crate::AddressSpace::PushConstant => {
                 if !self.capabilities.contains(Capabilities::PUSH_CONSTANT) {
                     return Err(GlobalVariableError::UnsupportedCapability(
@@ -553,7 +775,41 @@ impl super::Validator {
             }
         }
 
-        if ep.stage == crate::ShaderStage::Compute {
+        if let crate::ShaderStage::Task | crate::ShaderStage::Mesh = ep.stage {
+            let required = Capabilities::MESH_SHADER;
+            if !self.capabilities.contains(required) {
+                return Err(
+                    EntryPointError::Result(VaryingError::UnsupportedCapability(required))
+                        .with_span(),
+                );
+            }
+        }
+
+        if ep.stage == crate::ShaderStage::Geometry {
+            let required = Capabilities::GEOMETRY_SHADER;
+            if !self.capabilities.contains(required) {
+                return Err(
+                    EntryPointError::Result(VaryingError::UnsupportedCapability(required))
+                        .with_span(),
+                );
+            }
+        }
+
+        if let crate::ShaderStage::TessellationControl
+        | crate::ShaderStage::TessellationEvaluation = ep.stage
+        {
+            let required = Capabilities::TESSELLATION_SHADER;
+            if !self.capabilities.contains(required) {
+                return Err(
+                    EntryPointError::Result(VaryingError::UnsupportedCapability(required))
+                        .with_span(),
+                );
+            }
+        }
+
+        if let crate::ShaderStage::Compute | crate::ShaderStage::Task | crate::ShaderStage::Mesh =
+            ep.stage
+        {
             if ep
                 .workgroup_size
                 .iter()
@@ -565,6 +821,65 @@ impl super::Validator {
             return Err(EntryPointError::UnexpectedWorkgroupSize.with_span());
         }
 
+        if let Some(task_payload) = ep.task_payload {
+            if !matches!(
+                ep.stage,
+                crate::ShaderStage::Task | crate::ShaderStage::Mesh
+            ) {
+                return Err(EntryPointError::UnexpectedTaskPayload.with_span());
+            }
+            if module.global_variables[task_payload].space != crate::AddressSpace::TaskPayload {
+                return Err(EntryPointError::InvalidTaskPayload(task_payload)
+                    .with_span_handle(task_payload, &module.global_variables));
+            }
+        }
+
+        match (ep.stage, ep.mesh_info.as_ref()) {
+            (crate::ShaderStage::Mesh, Some(mesh_info)) => {
+                self.validate_mesh_info(mesh_info, module)?;
+            }
+            (crate::ShaderStage::Mesh, None) => {
+                return Err(EntryPointError::MissingMeshInfo.with_span());
+            }
+            (_, Some(_)) => return Err(EntryPointError::UnexpectedMeshInfo.with_span()),
+            (_, None) => {}
+        }
+
+        match (ep.stage, ep.geometry_info.as_ref()) {
+            (crate::ShaderStage::Geometry, Some(geometry_info)) => {
+                self.validate_geometry_info(geometry_info, module)?;
+            }
+            (crate::ShaderStage::Geometry, None) => {
+                return Err(EntryPointError::MissingGeometryInfo.with_span());
+            }
+            (_, Some(_)) => return Err(EntryPointError::UnexpectedGeometryInfo.with_span()),
+            (_, None) => {}
+        }
+
+        match (ep.stage, ep.tessellation_info.as_ref()) {
+            (
+                crate::ShaderStage::TessellationControl,
+                Some(&crate::TessellationStageInfo::Control { output_vertices }),
+            ) => {
+                if output_vertices == 0 {
+                    return Err(EntryPointError::EmptyTessellationOutput.with_span());
+                }
+            }
+            (
+                crate::ShaderStage::TessellationEvaluation,
+                Some(&crate::TessellationStageInfo::Evaluation { .. }),
+            ) => {}
+            (
+                crate::ShaderStage::TessellationControl
+                | crate::ShaderStage::TessellationEvaluation,
+                None,
+            ) => {
+                return Err(EntryPointError::MissingTessellationInfo.with_span());
+            }
+            (_, Some(_)) => return Err(EntryPointError::UnexpectedTessellationInfo.with_span()),
+            (_, None) => {}
+        }
+
         let mut info = self
             .validate_function(&ep.function, module, mod_info, true)
             .map_err(WithSpan::into_other)?;
@@ -576,6 +891,11 @@ impl super::Validator {
                 crate::ShaderStage::Vertex => ShaderStages::VERTEX,
                 crate::ShaderStage::Fragment => ShaderStages::FRAGMENT,
                 crate::ShaderStage::Compute => ShaderStages::COMPUTE,
+                crate::ShaderStage::Task => ShaderStages::TASK,
+                crate::ShaderStage::Mesh => ShaderStages::MESH,
+                crate::ShaderStage::Geometry => ShaderStages::GEOMETRY,
+                crate::ShaderStage::TessellationControl => ShaderStages::TESSELLATION_CONTROL,
+                crate::ShaderStage::TessellationEvaluation => ShaderStages::TESSELLATION_EVALUATION,
             };
 
             if !info.available_stages.contains(stage_bit) {
@@ -583,6 +903,21 @@ impl super::Validator {
             }
         }
 
+        // Every emitted vertex must be of the type the stage outputs.
+        if !info.emitted_vertex_types.is_empty() {
+            let vertex_output_type = ep
+                .geometry_info
+                .as_ref()
+                .map(|geometry_info| geometry_info.vertex_output_type);
+            if info
+                .emitted_vertex_types
+                .iter()
+                .any(|&ty| Some(ty) != vertex_output_type)
+            {
+                return Err(EntryPointError::InvalidEmittedVertexType.with_span());
+            }
+        }
+
         self.location_mask.clear();
         let mut argument_built_ins = crate::FastHashSet::default();
         // TODO: add span info to function arguments
@@ -590,6 +925,16 @@ impl super::Validator {
             let mut ctx = VaryingContext {
                 stage: ep.stage,
                 output: false,
+                per_vertex_arrays: matches!(
+                    ep.stage,
+                    crate::ShaderStage::Geometry
+                        | crate::ShaderStage::TessellationControl
+                        | crate::ShaderStage::TessellationEvaluation
+                ),
+                vertex_count: ep
+                    .geometry_info
+                    .as_ref()
+                    .map(|geometry_info| geometry_info.input_primitive.vertex_count()),
                 second_blend_source: false,
                 types: &module.types,
                 type_info: &self.types,
@@ -602,12 +947,23 @@ impl super::Validator {
                 .map_err_inner(|e| EntryPointError::Argument(index as u32, e).with_span())?;
         }
 
+        // The invocation id selects the vertex of the output patch that a
+        // tessellation control shader returns.
+        if ep.stage == crate::ShaderStage::TessellationControl
+            && ep.function.result.is_some()
+            && !argument_built_ins.contains(&crate::BuiltIn::InvocationId)
+        {
+            return Err(EntryPointError::MissingInvocationId.with_span());
+        }
+
         self.location_mask.clear();
         if let Some(ref fr) = ep.function.result {
             let mut result_built_ins = crate::FastHashSet::default();
             let mut ctx = VaryingContext {
                 stage: ep.stage,
                 output: true,
+                per_vertex_arrays: false,
+                vertex_count: None,
                 second_blend_source: false,
                 types: &module.types,
                 type_info: &self.types,
@@ -635,8 +991,21 @@ impl super::Validator {
             {
                 return Err(EntryPointError::MissingVertexOutputPosition.with_span());
             }
+            if ep.stage == crate::ShaderStage::Task
+                && fr.binding != Some(crate::Binding::BuiltIn(crate::BuiltIn::MeshTaskSize))
+            {
+                return Err(EntryPointError::MissingMeshTaskSize.with_span());
+            }
+            if ep.stage == crate::ShaderStage::Mesh {
+                return Err(EntryPointError::UnexpectedMeshResult.with_span());
+            }
+            if ep.stage == crate::ShaderStage::Geometry {
+                return Err(EntryPointError::UnexpectedGeometryResult.with_span());
+            }
         } else if ep.stage == crate::ShaderStage::Vertex {
             return Err(EntryPointError::MissingVertexOutputPosition.with_span());
+        } else if ep.stage == crate::ShaderStage::Task {
+            return Err(EntryPointError::MissingMeshTaskSize.with_span());
         }
 
         {
@@ -682,6 +1051,16 @@ impl super::Validator {
                 },
                 crate::AddressSpace::Private | crate::AddressSpace::WorkGroup => GlobalUse::all(),
                 crate::AddressSpace::PushConstant => GlobalUse::READ,
+                crate::AddressSpace::TaskPayload if ep.task_payload == Some(var_handle) => {
+                    match ep.stage {
+                        crate::ShaderStage::Task => GlobalUse::all(),
+                        crate::ShaderStage::Mesh => GlobalUse::READ | GlobalUse::QUERY,
+                        _ => GlobalUse::empty(),
+                    }
+                }
+                // Task payloads can only be used by the entry points they
+                // are the payload of.
+                crate::AddressSpace::TaskPayload => GlobalUse::empty(),
             };
             if !allowed_usage.contains(usage) {
                 log::warn!("\tUsage error for: {:?}", var);
@@ -706,4 +1085,185 @@ impl super::Validator {
 
         Ok(info)
     }
+
+    fn validate_mesh_info(
+        &mut self,
+        mesh_info: &crate::MeshStageInfo,
+        module: &crate::Module,
+    ) -> Result<(), WithSpan<EntryPointError>> {
+        use crate::BuiltIn as Bi;
+
+        let handle = mesh_info.output_variable;
+        let output_variable = &module.global_variables[handle];
+        if output_variable.space != crate::AddressSpace::WorkGroup {
+            return Err(EntryPointError::InvalidMeshOutputSpace(handle)
+                .with_span_handle(handle, &module.global_variables));
+        }
+        if mesh_info.max_vertices == 0 || mesh_info.max_primitives == 0 {
+            return Err(EntryPointError::EmptyMeshOutput.with_span());
+        }
+
+        // The type of the output variable must hold exactly the members
+        // described in the documentation of `MeshStageInfo`.
+        let output_type_good = match module.types[output_variable.ty].inner {
+            crate::TypeInner::Struct { ref members, .. } => {
+                let mut built_ins = crate::FastHashSet::default();
+                members.len() == 4
+                    && members.iter().all(|member| {
+                        let Some(crate::Binding::BuiltIn(built_in)) = member.binding else {
+                            return false;
+                        };
+                        let array = match built_in {
+                            Bi::Vertices => {
+                                Some((mesh_info.vertex_output_type, mesh_info.max_vertices))
+                            }
+                            Bi::Primitives => {
+                                Some((mesh_info.primitive_output_type, mesh_info.max_primitives))
+                            }
+                            Bi::VertexCount | Bi::PrimitiveCount => None,
+                            _ => return false,
+                        };
+                        let member_inner = &module.types[member.ty].inner;
+                        let type_good = match array {
+                            Some((base, count)) => match *member_inner {
+                                crate::TypeInner::Array {
+                                    base: member_base,
+                                    size: crate::ArraySize::Constant(size),
+                                    ..
+                                } => member_base == base && size.get() == count,
+                                _ => false,
+                            },
+                            None => *member_inner == crate::TypeInner::Scalar(crate::Scalar::U32),
+                        };
+                        type_good && built_ins.insert(built_in)
+                    })
+            }
+            _ => false,
+        };
+        let is_struct = |ty: Handle<crate::Type>| {
+            matches!(module.types[ty].inner, crate::TypeInner::Struct { .. })
+        };
+        if !output_type_good
+            || !is_struct(mesh_info.vertex_output_type)
+            || !is_struct(mesh_info.primitive_output_type)
+        {
+            return Err(EntryPointError::InvalidMeshOutputType(handle)
+                .with_span_handle(handle, &module.global_variables));
+        }
+
+        // Vertices are output like the result of a vertex shader.
+        self.location_mask.clear();
+        let mut vertex_built_ins = crate::FastHashSet::default();
+        let mut ctx = VaryingContext {
+            stage: crate::ShaderStage::Mesh,
+            output: true,
+            per_vertex_arrays: false,
+            vertex_count: None,
+            second_blend_source: false,
+            types: &module.types,
+            type_info: &self.types,
+            location_mask: &mut self.location_mask,
+            built_ins: &mut vertex_built_ins,
+            capabilities: self.capabilities,
+            flags: self.flags,
+        };
+        ctx.validate(mesh_info.vertex_output_type, None)
+            .map_err_inner(|e| EntryPointError::Result(e).with_span())?;
+        if let Some(&built_in) = vertex_built_ins.iter().find(|&&built_in| {
+            matches!(
+                built_in,
+                Bi::PointIndex | Bi::LineIndices | Bi::TriangleIndices | Bi::CullPrimitive
+            )
+        }) {
+            return Err(
+                EntryPointError::Result(VaryingError::InvalidBuiltInStage(built_in)).with_span(),
+            );
+        }
+        if !vertex_built_ins.contains(&Bi::Position { invariant: false }) {
+            return Err(EntryPointError::MissingVertexOutputPosition.with_span());
+        }
+
+        // Primitives only hold built-in values.
+        if let crate::TypeInner::Struct { ref members, .. } =
+            module.types[mesh_info.primitive_output_type].inner
+        {
+            if members
+                .iter()
+                .any(|member| matches!(member.binding, Some(crate::Binding::Location { .. })))
+            {
+                return Err(EntryPointError::MeshPrimitiveLocation.with_span());
+            }
+        }
+        self.location_mask.clear();
+        let mut primitive_built_ins = crate::FastHashSet::default();
+        let mut ctx = VaryingContext {
+            stage: crate::ShaderStage::Mesh,
+            output: true,
+            per_vertex_arrays: false,
+            vertex_count: None,
+            second_blend_source: false,
+            types: &module.types,
+            type_info: &self.types,
+            location_mask: &mut self.location_mask,
+            built_ins: &mut primitive_built_ins,
+            capabilities: self.capabilities,
+            flags: self.flags,
+        };
+        ctx.validate(mesh_info.primitive_output_type, None)
+            .map_err_inner(|e| EntryPointError::MeshPrimitive(e).with_span())?;
+        let indices = match mesh_info.topology {
+            crate::MeshOutputTopology::Points => Bi::PointIndex,
+            crate::MeshOutputTopology::Lines => Bi::LineIndices,
+            crate::MeshOutputTopology::Triangles => Bi::TriangleIndices,
+        };
+        if !primitive_built_ins.contains(&indices)
+            || primitive_built_ins
+                .iter()
+                .any(|&built_in| built_in != indices && built_in != Bi::CullPrimitive)
+        {
+            return Err(
+                EntryPointError::InvalidMeshPrimitiveOutput(mesh_info.topology).with_span(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn validate_geometry_info(
+        &mut self,
+        geometry_info: &crate::GeometryStageInfo,
+        module: &crate::Module,
+    ) -> Result<(), WithSpan<EntryPointError>> {
+        if geometry_info.max_vertices == 0 || geometry_info.invocations == 0 {
+            return Err(EntryPointError::EmptyGeometryOutput.with_span());
+        }
+
+        let ty = geometry_info.vertex_output_type;
+        if !matches!(module.types[ty].inner, crate::TypeInner::Struct { .. }) {
+            return Err(
+                EntryPointError::InvalidGeometryVertexType(ty).with_span_handle(ty, &module.types)
+            );
+        }
+
+        // Vertices are output like the result of a vertex shader.
+        self.location_mask.clear();
+        let mut vertex_built_ins = crate::FastHashSet::default();
+        let mut ctx = VaryingContext {
+            stage: crate::ShaderStage::Geometry,
+            output: true,
+            per_vertex_arrays: false,
+            vertex_count: None,
+            second_blend_source: false,
+            types: &module.types,
+            type_info: &self.types,
+            location_mask: &mut self.location_mask,
+            built_ins: &mut vertex_built_ins,
+            capabilities: self.capabilities,
+            flags: self.flags,
+        };
+        ctx.validate(ty, None)
+            .map_err_inner(|e| EntryPointError::Result(e).with_span())?;
+
+        Ok(())
+    }
 }
@@ -22,11 +22,14 @@ use std::ops;
 // merge the corresponding matches over expressions and statements.
 
 use crate::span::{AddSpan as _, WithSpan};
-pub use analyzer::{ExpressionInfo, FunctionInfo, GlobalUse, Uniformity, UniformityRequirements};
+pub use analyzer::{
+    ExpressionInfo, FunctionInfo, FunctionOrigin, GlobalUse, NonUniformDerivative, Uniformity,
+    UniformityDisruptor, UniformityRequirements,
+};
 pub use compose::ComposeError;
 pub use expression::{check_literal_value, LiteralError};
 pub use expression::{ConstExpressionError, ExpressionError};
-pub use function::{CallError, FunctionError, LocalVariableError};
+pub use function::{CallError, FunctionError, LocalVariableError, SubgroupError};
 pub use interface::{EntryPointError, GlobalVariableError, VaryingError};
 pub use r#type::{Disalignment, TypeError, TypeFlags};
 
@@ -77,7 +80,7 @@ bitflags::bitflags! {
     #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
     #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-    pub struct Capabilities: u16 {
+    pub struct Capabilities: u32 {
         /// Support for [`AddressSpace:PushConstant`].
         const PUSH_CONSTANT = 0x1;
         /// Float values with width = 8.
@@ -108,6 +111,22 @@ bitflags::bitflags! {
         const DUAL_SOURCE_BLENDING = 0x2000;
         /// Support for arrayed cube textures.
         const CUBE_ARRAY_TEXTURES = 0x4000;
+        /// Support for [`Statement::DebugPrintf`](crate::Statement::DebugPrintf).
+        const DEBUG_PRINTF = 0x8000;
+        /// Float values with width = 2.
+        const FLOAT16 = 0x10000;
+        /// Integer values with width = 8.
+        const SHADER_INT64 = 0x20000;
+        /// Support for task and mesh shaders.
+        const MESH_SHADER = 0x40000;
+        /// Support for geometry shaders.
+        const GEOMETRY_SHADER = 0x80000;
+        /// Support for tessellation control and evaluation shaders.
+        const TESSELLATION_SHADER = 0x100000;
+        /// Support for subgroup operations and builtins.
+        const SUBGROUP = 0x200000;
+        /// Support for subgroup barriers.
+        const SUBGROUP_BARRIER = 0x400000;
     }
 }
 
@@ -126,6 +145,11 @@ bitflags::bitflags! {
         const VERTEX = 0x1;
         const FRAGMENT = 0x2;
         const COMPUTE = 0x4;
+        const TASK = 0x8;
+        const MESH = 0x10;
+        const GEOMETRY = 0x20;
+        const TESSELLATION_CONTROL = 0x40;
+        const TESSELLATION_EVALUATION = 0x80;
     }
 }
 
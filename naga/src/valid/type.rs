@@ -141,9 +141,6 @@ pub enum WidthError {
         flag: &'static str,
     },
 
-    #[error("64-bit integers are not yet supported")]
-    Unsupported64Bit,
-
     #[error("Abstract types may only appear in constant expressions")]
     Abstract,
 }
@@ -189,9 +186,12 @@ const fn ptr_space_argument_flag(space: crate::AddressSpace) -> TypeFlags {
     use crate::AddressSpace as As;
     match space {
         As::Function | As::Private => TypeFlags::ARGUMENT,
-        As::Uniform | As::Storage { .. } | As::Handle | As::PushConstant | As::WorkGroup => {
-            TypeFlags::empty()
-        }
+        As::Uniform
+        | As::Storage { .. }
+        | As::Handle
+        | As::PushConstant
+        | As::WorkGroup
+        | As::TaskPayload => TypeFlags::empty(),
     }
 }
 
@@ -241,15 +241,34 @@ impl super::Validator {
                         });
                     }
                     true
+                } else if scalar.width == 2 {
+                    if !self.capabilities.contains(Capabilities::FLOAT16) {
+                        return Err(WidthError::MissingCapability {
+                            name: "f16",
+                            flag: "FLOAT16",
+                        });
+                    }
+                    true
                 } else {
                     scalar.width == 4
                 }
             }
             crate::ScalarKind::Sint | crate::ScalarKind::Uint => {
                 if scalar.width == 8 {
-                    return Err(WidthError::Unsupported64Bit);
+                    if !self.capabilities.contains(Capabilities::SHADER_INT64) {
+                        return Err(WidthError::MissingCapability {
+                            name: if scalar.kind == crate::ScalarKind::Sint {
+                                "i64"
+                            } else {
+                                "u64"
+                            },
+                            flag: "SHADER_INT64",
+                        });
+                    }
+                    true
+                } else {
+                    scalar.width == 4
                 }
-                scalar.width == 4
             }
             crate::ScalarKind::AbstractInt | crate::ScalarKind::AbstractFloat => {
                 return Err(WidthError::Abstract);
@@ -0,0 +1,30 @@
+struct UniformIndex {
+    index: u32
+};
+
+@group(0) @binding(0)
+var texture_array: binding_array<texture_2d<f32>, 5>;
+@group(0) @binding(1)
+var samp: binding_array<sampler, 5>;
+@group(0) @binding(2)
+var<uniform> uni: UniformIndex;
+
+struct FragmentIn {
+    @location(0) index: u32,
+};
+
+@fragment
+fn main(fragment_in: FragmentIn) -> @location(0) vec4<f32> {
+    let uniform_index = uni.index;
+    let non_uniform_index = fragment_in.index;
+
+    var v4 = vec4<f32>(0.0);
+
+    let uv = vec2<f32>(0.0);
+
+    v4 += textureSample(texture_array[0], samp[0], uv);
+    v4 += textureSample(texture_array[uniform_index], samp[uniform_index], uv);
+    v4 += textureSample(texture_array[non_uniform_index], samp[non_uniform_index], uv);
+
+    return v4;
+}
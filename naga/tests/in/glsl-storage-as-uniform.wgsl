@@ -0,0 +1,13 @@
+struct Lights {
+    colors: array<vec4<f32>, 4>,
+    count: u32,
+}
+
+@group(0) @binding(0)
+var<storage> lights: Lights;
+
+@fragment
+fn main() -> @location(0) vec4<f32> {
+    let color = lights.colors[1];
+    return color;
+}
@@ -0,0 +1,12 @@
+var<private> v: i64 = 1li;
+const k: u64 = 20lu;
+
+fn f(x: i64, y: u64) -> i64 {
+   var z = y * k;
+   return x + 30li;
+}
+
+@compute @workgroup_size(1)
+fn main() {
+   f(6li, 7lu);
+}
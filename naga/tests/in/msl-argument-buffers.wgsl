@@ -0,0 +1,11 @@
+@group(0) @binding(0) var tex: texture_2d<f32>;
+@group(0) @binding(1) var samp: sampler;
+@group(0) @binding(2) var<uniform> uv_offset: vec2<f32>;
+@group(1) @binding(0) var<storage, read_write> result_value: vec4<f32>;
+
+@fragment
+fn fs_main(@builtin(position) pos: vec4<f32>) -> @location(0) vec4<f32> {
+    let color = textureSample(tex, samp, pos.xy + uv_offset);
+    result_value = color;
+    return color;
+}
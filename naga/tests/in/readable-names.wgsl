@@ -0,0 +1,17 @@
+struct Light {
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> light: Light;
+
+fn attenuation(distance: f32) -> f32 {
+    return light.intensity / (distance * distance);
+}
+
+@fragment
+fn main(@location(0) distance: f32) -> @location(0) vec4<f32> {
+    let factor = attenuation(distance);
+    return vec4<f32>(light.color * factor, 1.0);
+}
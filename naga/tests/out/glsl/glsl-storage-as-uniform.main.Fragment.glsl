@@ -0,0 +1,19 @@
+#version 300 es
+
+precision highp float;
+precision highp int;
+
+struct Lights {
+    vec4 colors[4];
+    uint count;
+};
+uniform Lights_block_0Fragment { Lights _group_0_binding_0_fs; };
+
+layout(location = 0) out vec4 _fs2p_location0;
+
+void main() {
+    vec4 color = _group_0_binding_0_fs.colors[1];
+    _fs2p_location0 = color;
+    return;
+}
+
@@ -0,0 +1,40 @@
+struct UniformIndex {
+    uint index;
+};
+
+struct FragmentIn {
+    nointerpolation uint index : LOC0;
+};
+
+Texture2D<float4> texture_array(uint index) {
+    return ResourceDescriptorHeap[NonUniformResourceIndex(10u + index)];
+}
+SamplerState samp(uint index) {
+    return SamplerDescriptorHeap[NonUniformResourceIndex(0u + index)];
+}
+cbuffer uni : register(b0) { UniformIndex uni; }
+
+struct FragmentInput_main {
+    nointerpolation uint index : LOC0;
+};
+
+float4 main(FragmentInput_main fragmentinput_main) : SV_Target0
+{
+    FragmentIn fragment_in = { fragmentinput_main.index };
+    float4 v4_ = (0.0).xxxx;
+
+    uint uniform_index = uni.index;
+    uint non_uniform_index = fragment_in.index;
+    float2 uv = (0.0).xx;
+    float4 _expr14 = texture_array(0).Sample(samp(0), uv);
+    float4 _expr15 = v4_;
+    v4_ = (_expr15 + _expr14);
+    float4 _expr21 = texture_array(uniform_index).Sample(samp(uniform_index), uv);
+    float4 _expr22 = v4_;
+    v4_ = (_expr22 + _expr21);
+    float4 _expr28 = texture_array(non_uniform_index).Sample(samp(non_uniform_index), uv);
+    float4 _expr29 = v4_;
+    v4_ = (_expr29 + _expr28);
+    float4 _expr31 = v4_;
+    return _expr31;
+}
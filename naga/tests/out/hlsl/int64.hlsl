@@ -0,0 +1,18 @@
+static const uint64_t k = 20uL;
+
+static int64_t v = 1L;
+
+int64_t f(int64_t x, uint64_t y)
+{
+    uint64_t z = (uint64_t)0;
+
+    z = (y * k);
+    return (x + 30L);
+}
+
+[numthreads(1, 1, 1)]
+void main()
+{
+    const int64_t _e2 = f(6L, 7uL);
+    return;
+}
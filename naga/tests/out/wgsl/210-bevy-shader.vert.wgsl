@@ -10,7 +10,7 @@ struct VertexOutput {
     @location(0) v_Position: vec3<f32>,
     @location(1) v_Normal: vec3<f32>,
     @location(2) v_Uv: vec2<f32>,
-    @builtin(position) member: vec4<f32>,
+    @builtin(position) gl_Position: vec4<f32>,
 }
 
 var<private> Vertex_Position_1: vec3<f32>;
@@ -11,7 +11,7 @@ struct VertexOutput {
     @location(1) v_WorldNormal: vec3<f32>,
     @location(2) v_Uv: vec2<f32>,
     @location(3) v_WorldTangent: vec4<f32>,
-    @builtin(position) member: vec4<f32>,
+    @builtin(position) gl_Position: vec4<f32>,
 }
 
 var<private> Vertex_Position_1: vec3<f32>;
@@ -0,0 +1,16 @@
+const k: u64 = 20lu;
+
+var<private> v: i64 = 1li;
+
+fn f(x: i64, y: u64) -> i64 {
+    var z: u64;
+
+    z = (y * k);
+    return (x + 30li);
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn main() {
+    let _e2 = f(6li, 7lu);
+    return;
+}
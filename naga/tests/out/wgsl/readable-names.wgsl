@@ -0,0 +1,19 @@
+struct Light {
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+@group(0) @binding(0) 
+var<uniform> light: Light;
+
+fn attenuation(distance: f32) -> f32 {
+    let light_intensity = light.intensity;
+    return (light_intensity / (distance * distance));
+}
+
+@fragment 
+fn main(@location(0) distance_1: f32) -> @location(0) vec4<f32> {
+    let factor = attenuation(distance_1);
+    let light_color = light.color;
+    return vec4<f32>((light_color * factor), 1f);
+}
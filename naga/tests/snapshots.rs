@@ -57,6 +57,8 @@ struct SpirvOutParameters {
 struct WgslOutParameters {
     #[serde(default)]
     explicit_types: bool,
+    #[serde(default)]
+    readable_names: bool,
 }
 
 #[derive(Default, serde::Deserialize)]
@@ -285,7 +287,7 @@ fn check_targets(
 
     #[cfg(feature = "compact")]
     let info = {
-        naga::compact::compact(module);
+        naga::compact::compact(module, naga::compact::KeepUnused::Yes);
 
         #[cfg(feature = "serialize")]
         {
@@ -419,6 +421,7 @@ fn write_output_spv(
         },
         bounds_check_policies,
         binding_map: params.binding_map.clone(),
+        transform_feedback: spv::TransformFeedbackMap::default(),
         zero_initialize_workgroup_memory: spv::ZeroInitializeWorkgroupMemoryMode::Polyfill,
         debug_info,
     };
@@ -565,6 +568,12 @@ fn write_output_hlsl(
             naga::ShaderStage::Vertex => &mut config.vertex,
             naga::ShaderStage::Fragment => &mut config.fragment,
             naga::ShaderStage::Compute => &mut config.compute,
+            // The HLSL backend doesn't write these stages.
+            naga::ShaderStage::Task
+            | naga::ShaderStage::Mesh
+            | naga::ShaderStage::Geometry
+            | naga::ShaderStage::TessellationControl
+            | naga::ShaderStage::TessellationEvaluation => unreachable!(),
         }
         .push(hlsl_snapshots::ConfigItem {
             entry_point: name.clone(),
@@ -592,6 +601,7 @@ fn write_output_wgsl(
 
     let mut flags = wgsl::WriterFlags::empty();
     flags.set(wgsl::WriterFlags::EXPLICIT_TYPES, params.explicit_types);
+    flags.set(wgsl::WriterFlags::READABLE_NAMES, params.readable_names);
 
     let string = wgsl::write_string(module, info, flags).expect("WGSL write failed");
 
@@ -744,6 +754,7 @@ fn convert_wgsl() {
             "binding-arrays",
             Targets::WGSL | Targets::HLSL | Targets::METAL | Targets::SPIRV,
         ),
+        ("binding-arrays-descriptor-heap", Targets::HLSL),
         (
             "binding-buffer-arrays",
             Targets::WGSL | Targets::SPIRV, //TODO: more backends, eventually merge into "binding-arrays"
@@ -777,6 +788,9 @@ fn convert_wgsl() {
             Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
         ),
         ("msl-varyings", Targets::METAL),
+        ("msl-argument-buffers", Targets::METAL),
+        ("glsl-storage-as-uniform", Targets::GLSL),
+        ("readable-names", Targets::WGSL),
         (
             "const-exprs",
             Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
@@ -790,6 +804,10 @@ fn convert_wgsl() {
             "f64",
             Targets::SPIRV | Targets::GLSL | Targets::HLSL | Targets::WGSL,
         ),
+        (
+            "int64",
+            Targets::SPIRV | Targets::METAL | Targets::HLSL | Targets::WGSL,
+        ),
         (
             "abstract-types-const",
             Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::WGSL,
@@ -894,6 +912,8 @@ fn convert_glsl_variations_check() {
             &naga::front::glsl::Options {
                 stage: naga::ShaderStage::Fragment,
                 defines: Default::default(),
+                include_resolver: None,
+                entry_point: None,
             },
             &source,
         )
@@ -926,9 +946,14 @@ fn convert_glsl_folder() {
                         "vert" => naga::ShaderStage::Vertex,
                         "frag" => naga::ShaderStage::Fragment,
                         "comp" => naga::ShaderStage::Compute,
+                        "geom" => naga::ShaderStage::Geometry,
+                        "tesc" => naga::ShaderStage::TessellationControl,
+                        "tese" => naga::ShaderStage::TessellationEvaluation,
                         ext => panic!("Unknown extension for glsl file {ext}"),
                     },
                     defines: Default::default(),
+                    include_resolver: None,
+                    entry_point: None,
                 },
                 &input.read_source(),
             )
@@ -176,3 +176,127 @@ fn storage_image_formats() {
         "#,
     );
 }
+
+#[test]
+fn float16() {
+    require_and_forbid(
+        &[Ca::Float16],
+        &[Ca::StorageBuffer16BitAccess, Ca::StorageInputOutput16],
+        r#"
+        enable f16;
+        fn f(x: f16) -> f16 { return x * 2.0h; }
+    "#,
+    );
+
+    require(
+        &[Ca::Float16, Ca::StorageBuffer16BitAccess],
+        r#"
+        enable f16;
+        @group(0) @binding(0)
+        var<storage, read_write> values: array<f16>;
+    "#,
+    );
+
+    require_and_forbid(
+        &[Ca::Float16, Ca::StorageInputOutput16],
+        &[Ca::StorageBuffer16BitAccess],
+        r#"
+        enable f16;
+        @fragment
+        fn f(@location(0) x: f16) -> @location(0) vec4<f32> {
+            return vec4(f32(x));
+        }
+    "#,
+    );
+}
+
+#[cfg(feature = "glsl-in")]
+#[test]
+fn geometry_stage() {
+    use naga::back::spv;
+    use naga::front::glsl;
+    use naga::valid;
+
+    let source = r#"
+        #version 450
+        layout(points) in;
+        layout(points, max_vertices = 1) out;
+
+        void main() {
+            gl_Position = gl_in[0].gl_Position;
+            EmitVertex();
+            EndPrimitive();
+        }
+    "#;
+
+    let module = glsl::Frontend::default()
+        .parse(&glsl::Options::from(naga::ShaderStage::Geometry), source)
+        .expect("expected GLSL to parse successfully");
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .expect("validation failed");
+
+    let mut words = vec![];
+    let mut writer = spv::Writer::new(&spv::Options::default()).unwrap();
+    writer
+        .write(&module, &info, None, &None, &mut words)
+        .unwrap();
+    assert!(writer.get_capabilities_used().contains(&Ca::Geometry));
+}
+
+#[cfg(feature = "glsl-in")]
+#[test]
+fn tessellation_stages() {
+    use naga::back::spv;
+    use naga::front::glsl;
+    use naga::valid;
+
+    let control = r#"
+        #version 450
+        layout(vertices = 3) out;
+        layout(location = 0) in vec2 uv[];
+        layout(location = 0) out vec2 v_uv[];
+        layout(location = 1) patch out float level;
+
+        void main() {
+            gl_out[gl_InvocationID].gl_Position = gl_in[gl_InvocationID].gl_Position;
+            v_uv[gl_InvocationID] = uv[gl_InvocationID];
+            level = 4.0;
+            gl_TessLevelOuter[0] = level;
+            gl_TessLevelInner[0] = level;
+        }
+    "#;
+    let evaluation = r#"
+        #version 450
+        layout(triangles, fractional_odd_spacing, cw) in;
+        layout(location = 0) in vec2 v_uv[];
+        layout(location = 1) patch in float level;
+        layout(location = 0) out vec2 uv;
+
+        void main() {
+            gl_Position = gl_TessCoord.x * gl_in[0].gl_Position
+                + gl_TessCoord.y * gl_in[1].gl_Position
+                + gl_TessCoord.z * gl_in[2].gl_Position;
+            uv = v_uv[0] * level;
+        }
+    "#;
+
+    for (stage, source) in [
+        (naga::ShaderStage::TessellationControl, control),
+        (naga::ShaderStage::TessellationEvaluation, evaluation),
+    ] {
+        let module = glsl::Frontend::default()
+            .parse(&glsl::Options::from(stage), source)
+            .expect("expected GLSL to parse successfully");
+        let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+            .validate(&module)
+            .expect("validation failed");
+
+        let mut words = vec![];
+        let mut writer = spv::Writer::new(&spv::Options::default()).unwrap();
+        writer
+            .write(&module, &info, None, &None, &mut words)
+            .unwrap();
+        assert!(writer.get_capabilities_used().contains(&Ca::Tessellation));
+    }
+}
@@ -371,7 +371,56 @@ fn unknown_scalar_type() {
 2 │             const a: vec2<something>;
   │                           ^^^^^^^^^ unknown scalar type
   │
-  = note: Valid scalar types are f32, f64, i32, u32, bool
+  = note: Valid scalar types are f16, f32, f64, i32, u32, bool
+
+"#,
+    );
+}
+
+#[test]
+fn unknown_enable_extension() {
+    check(
+        r#"
+            enable foo;
+        "#,
+        r#"error: unknown enable-extension: 'foo'
+  ┌─ wgsl:2:20
+  │
+2 │             enable foo;
+  │                    ^^^ unknown enable-extension
+
+"#,
+    );
+}
+
+#[test]
+fn f16_not_enabled() {
+    check(
+        r#"
+            const a: f16 = 1.0;
+        "#,
+        r#"error: the `f16` extension is not enabled
+  ┌─ wgsl:2:22
+  │
+2 │             const a: f16 = 1.0;
+  │                      ^^^ requires the `f16` extension
+  │
+  = note: add `enable f16;` at the start of the shader
+
+"#,
+    );
+
+    check(
+        r#"
+            const a = 1.0h;
+        "#,
+        r#"error: the `f16` extension is not enabled
+  ┌─ wgsl:2:23
+  │
+2 │             const a = 1.0h;
+  │                       ^^^^ requires the `f16` extension
+  │
+  = note: add `enable f16;` at the start of the shader
 
 "#,
     );
@@ -2071,7 +2120,7 @@ fn compaction_preserves_spans() {
         }
     "#; //     ^^^   correct error span: 95..98
     let mut module = naga::front::wgsl::parse_str(source).expect("source ought to parse");
-    naga::compact::compact(&mut module);
+    naga::compact::compact(&mut module, naga::compact::KeepUnused::Yes);
     let err = naga::valid::Validator::new(
         naga::valid::ValidationFlags::all(),
         naga::valid::Capabilities::default(),
@@ -2098,3 +2147,196 @@ fn compaction_preserves_spans() {
         panic!("Error message has wrong span:\n\n{err:#?}");
     }
 }
+
+#[test]
+fn compaction_removes_unused_functions() {
+    let source = r#"
+        fn used() -> f32 { return called_by_used(); }
+        fn called_by_used() -> f32 { return 1.0; }
+        fn unused() -> f32 { return used(); }
+
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            return vec4(used());
+        }
+    "#;
+    let mut module = naga::front::wgsl::parse_str(source).expect("source ought to parse");
+    naga::compact::compact(&mut module, naga::compact::KeepUnused::Yes);
+    assert_eq!(module.functions.len(), 3);
+
+    naga::compact::compact(&mut module, naga::compact::KeepUnused::No);
+    let names: Vec<_> = module
+        .functions
+        .iter()
+        .map(|(_, function)| function.name.as_deref().unwrap())
+        .collect();
+    assert_eq!(names, ["called_by_used", "used"]);
+
+    // The calls refer to the new function handles.
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::default(),
+    )
+    .validate(&module)
+    .expect("compacted module ought to validate");
+}
+
+const MESH_SHADER: &str = r#"
+    struct Vertex {
+        @builtin(position) position: vec4<f32>,
+        @location(0) color: vec4<f32>,
+    }
+
+    struct Primitive {
+        @builtin(triangle_indices) indices: vec3<u32>,
+        @builtin(cull_primitive) cull: bool,
+    }
+
+    struct MeshOutput {
+        @builtin(vertices) vertices: array<Vertex, 3>,
+        @builtin(primitives) primitives: array<Primitive, 1>,
+        @builtin(vertex_count) vertex_count: u32,
+        @builtin(primitive_count) primitive_count: u32,
+    }
+
+    var<task_payload> color: vec4<f32>;
+    var<workgroup> mesh_output: MeshOutput;
+
+    @task @payload(color) @workgroup_size(1)
+    fn ts() -> @builtin(mesh_task_size) vec3<u32> {
+        color = vec4(1.0, 0.0, 0.0, 1.0);
+        return vec3(1u, 1u, 1u);
+    }
+
+    @mesh(mesh_output) @payload(color) @workgroup_size(3)
+    fn ms(@builtin(local_invocation_index) index: u32) {
+        mesh_output.vertex_count = 3u;
+        mesh_output.primitive_count = 1u;
+        let x = f32(index) - 1.0;
+        mesh_output.vertices[index].position = vec4(x, f32(index % 2u), 0.0, 1.0);
+        mesh_output.vertices[index].color = color;
+        if index == 0u {
+            mesh_output.primitives[0].indices = vec3(0u, 1u, 2u);
+            mesh_output.primitives[0].cull = false;
+        }
+    }
+"#;
+
+fn validate_with_capabilities(
+    source: &str,
+    capabilities: naga::valid::Capabilities,
+) -> Result<naga::valid::ModuleInfo, naga::valid::ValidationError> {
+    let module = naga::front::wgsl::parse_str(source).expect("source ought to parse");
+    naga::valid::Validator::new(naga::valid::ValidationFlags::all(), capabilities)
+        .validate(&module)
+        .map_err(|e| e.into_inner())
+}
+
+#[test]
+fn mesh_shader() {
+    validate_with_capabilities(MESH_SHADER, naga::valid::Capabilities::MESH_SHADER)
+        .expect("mesh shader ought to validate");
+
+    let error = validate_with_capabilities(MESH_SHADER, naga::valid::Capabilities::default());
+    assert!(
+        matches!(
+            error,
+            Err(naga::valid::ValidationError::GlobalVariable {
+                source: naga::valid::GlobalVariableError::UnsupportedCapability(
+                    naga::valid::Capabilities::MESH_SHADER
+                ),
+                ..
+            })
+        ),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn task_shader_missing_mesh_task_size() {
+    let error = validate_with_capabilities(
+        r#"
+        @task @workgroup_size(1)
+        fn ts() -> @location(0) vec4<u32> {
+            return vec4(1u);
+        }
+        "#,
+        naga::valid::Capabilities::MESH_SHADER,
+    );
+    assert!(
+        matches!(
+            error,
+            Err(naga::valid::ValidationError::EntryPoint {
+                source: naga::valid::EntryPointError::MissingMeshTaskSize,
+                ..
+            })
+        ),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn mesh_primitive_location() {
+    let error = validate_with_capabilities(
+        r#"
+        struct Vertex {
+            @builtin(position) position: vec4<f32>,
+        }
+
+        struct Primitive {
+            @builtin(point_index) index: u32,
+            @location(0) color: vec4<f32>,
+        }
+
+        struct MeshOutput {
+            @builtin(vertices) vertices: array<Vertex, 1>,
+            @builtin(primitives) primitives: array<Primitive, 1>,
+            @builtin(vertex_count) vertex_count: u32,
+            @builtin(primitive_count) primitive_count: u32,
+        }
+
+        var<workgroup> mesh_output: MeshOutput;
+
+        @mesh(mesh_output) @workgroup_size(1)
+        fn ms() {
+            mesh_output.vertex_count = 0u;
+        }
+        "#,
+        naga::valid::Capabilities::MESH_SHADER,
+    );
+    assert!(
+        matches!(
+            error,
+            Err(naga::valid::ValidationError::EntryPoint {
+                source: naga::valid::EntryPointError::MeshPrimitiveLocation,
+                ..
+            })
+        ),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn task_payload_outside_task_shader() {
+    let error = validate_with_capabilities(
+        r#"
+        var<task_payload> payload: u32;
+
+        @compute @workgroup_size(1)
+        fn main() {
+            payload = 1u;
+        }
+        "#,
+        naga::valid::Capabilities::MESH_SHADER,
+    );
+    assert!(
+        matches!(
+            error,
+            Err(naga::valid::ValidationError::EntryPoint {
+                source: naga::valid::EntryPointError::InvalidGlobalUsage(_, _),
+                ..
+            })
+        ),
+        "{error:?}"
+    );
+}
@@ -70,6 +70,14 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                 trace::Command::CopyTextureToTexture { src, dst, size } => self
                     .command_encoder_copy_texture_to_texture::<A>(encoder, &src, &dst, &size)
                     .unwrap(),
+                trace::Command::ResolveTexture {
+                    src,
+                    dst,
+                    size,
+                    mode,
+                } => self
+                    .command_encoder_resolve_texture::<A>(encoder, &src, &dst, &size, mode)
+                    .unwrap(),
                 trace::Command::ClearBuffer { dst, offset, size } => self
                     .command_encoder_clear_buffer::<A>(encoder, dst, offset, size)
                     .unwrap(),
@@ -110,6 +118,14 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                 trace::Command::InsertDebugMarker(marker) => self
                     .command_encoder_insert_debug_marker::<A>(encoder, &marker)
                     .unwrap(),
+                trace::Command::AliasingBarrier => {
+                    self.command_encoder_aliasing_barrier::<A>(encoder).unwrap()
+                }
+                trace::Command::BuildAccelerationStructures { blas, tlas } => self
+                    .command_encoder_build_acceleration_structures_unsafe_tlas::<A>(
+                        encoder, &blas, &tlas,
+                    )
+                    .unwrap(),
                 trace::Command::RunComputePass {
                     base,
                     timestamp_writes,
@@ -127,6 +143,10 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                     target_depth_stencil,
                     timestamp_writes,
                     occlusion_query_set_id,
+                    pipeline_statistics_query,
+                    shading_rate_attachment,
+                    resuming,
+                    suspending,
                 } => {
                     self.command_encoder_run_render_pass_impl::<A>(
                         encoder,
@@ -135,6 +155,10 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                         target_depth_stencil.as_ref(),
                         timestamp_writes.as_ref(),
                         occlusion_query_set_id,
+                        pipeline_statistics_query.as_ref(),
+                        shading_rate_attachment.as_ref(),
+                        resuming,
+                        suspending,
                     )
                     .unwrap();
                 }
@@ -294,6 +318,19 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
             Action::DestroyComputePipeline(id) => {
                 self.compute_pipeline_drop::<A>(id);
             }
+            Action::CreatePipelineCache { id, desc } => {
+                // The traced data may come from a different adapter, so the
+                // cache is always created empty when replaying.
+                let desc = wgc::pipeline::PipelineCacheDescriptor { data: None, ..desc };
+                let (_, error) =
+                    unsafe { self.device_create_pipeline_cache::<A>(device, &desc, id) };
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyPipelineCache(id) => {
+                self.pipeline_cache_drop::<A>(id);
+            }
             Action::CreateRenderPipeline {
                 id,
                 desc,
@@ -313,6 +350,25 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                     panic!("{e}");
                 }
             }
+            Action::CreateMeshPipeline {
+                id,
+                desc,
+                implicit_context,
+            } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let implicit_ids =
+                    implicit_context
+                        .as_ref()
+                        .map(|ic| wgc::device::ImplicitPipelineIds {
+                            root_id: ic.root_id,
+                            group_ids: &ic.group_ids,
+                        });
+                let (_, error) =
+                    self.device_create_mesh_pipeline::<A>(device, &desc, id, implicit_ids);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
             Action::DestroyRenderPipeline(id) => {
                 self.render_pipeline_drop::<A>(id);
             }
@@ -341,6 +397,82 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
             Action::DestroyQuerySet(id) => {
                 self.query_set_drop::<A>(id);
             }
+            Action::CreateTilePool { id, desc } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let (_, error) = self.device_create_tile_pool::<A>(device, &desc, id);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyTilePool(id) => {
+                self.tile_pool_drop::<A>(id);
+            }
+            Action::CreateMemoryHeap { id, desc } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let (_, error) = self.device_create_memory_heap::<A>(device, &desc, id);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyMemoryHeap(id) => {
+                self.memory_heap_drop::<A>(id);
+            }
+            Action::CreateExternalFence { id, desc } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let (_, error) = self.device_create_external_fence::<A>(device, &desc, id);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyExternalFence(id) => {
+                self.external_fence_drop::<A>(id);
+            }
+            Action::CreatePlacedBuffer {
+                id,
+                desc,
+                heap,
+                offset,
+            } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let (_, error) =
+                    self.device_create_buffer_in_heap::<A>(device, &desc, heap, offset, id);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::CreatePlacedTexture {
+                id,
+                desc,
+                heap,
+                offset,
+            } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let (_, error) =
+                    self.device_create_texture_in_heap::<A>(device, &desc, heap, offset, id);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::CreateBlas { id, desc, sizes } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let (_, _, error) = self.device_create_blas::<A>(device, &desc, sizes, id);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyBlas(id) => {
+                self.blas_drop::<A>(id);
+            }
+            Action::CreateTlas { id, desc } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let (_, error) = self.device_create_tlas::<A>(device, &desc, id);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyTlas(id) => {
+                self.tlas_drop::<A>(id);
+            }
             Action::WriteBuffer {
                 id,
                 data,
@@ -368,6 +500,10 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                 self.queue_write_texture::<A>(device, &to, &bin, &layout, &size)
                     .unwrap();
             }
+            Action::BindTexturePages { texture, bindings } => {
+                self.queue_bind_texture_pages::<A>(device, texture, &bindings)
+                    .unwrap();
+            }
             Action::Submit(_index, ref commands) if commands.is_empty() => {
                 self.queue_submit::<A>(device, &[]).unwrap();
             }
@@ -10,6 +10,7 @@ mod params;
 mod poll;
 mod report;
 mod run;
+mod subtest;
 
 #[cfg(target_arch = "wasm32")]
 pub use init::initialize_html_canvas;
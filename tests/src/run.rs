@@ -1,4 +1,4 @@
-use std::{panic::AssertUnwindSafe, sync::Arc};
+use std::{any::Any, panic::AssertUnwindSafe, sync::Arc};
 
 use futures_lite::FutureExt;
 use wgpu::{Adapter, Device, Instance, Queue};
@@ -9,6 +9,7 @@ use crate::{
     isolation,
     params::TestInfo,
     report::AdapterReport,
+    subtest::Subtests,
     GpuTestConfiguration,
 };
 
@@ -22,6 +23,15 @@ pub struct TestingContext {
     pub device_features: wgpu::Features,
     pub device_limits: wgpu::Limits,
     pub queue: Queue,
+    pub(crate) subtests: Arc<Subtests>,
+}
+
+/// Returns the message of a panic payload, if it is a string.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> Option<&str> {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
 }
 
 /// Execute the given test configuration with the given adapter index.
@@ -67,15 +77,17 @@ pub async fn execute_test(
         config.params.required_limits.clone(),
     ));
 
+    let subtests = Arc::new(Subtests::default());
     let context = TestingContext {
         instance,
         adapter,
-        adapter_info,
+        adapter_info: adapter_info.clone(),
         adapter_downlevel_capabilities,
         device: Arc::new(device),
         device_features: config.params.required_features,
         device_limits: config.params.required_limits.clone(),
         queue,
+        subtests: Arc::clone(&subtests),
     };
 
     let mut failures = Vec::new();
@@ -86,10 +98,7 @@ pub async fn execute_test(
         .await;
 
     if let Err(panic) = panic_res {
-        let message = panic
-            .downcast_ref::<&str>()
-            .copied()
-            .or_else(|| panic.downcast_ref::<String>().map(String::as_str));
+        let message = panic_message(&*panic);
 
         let result = FailureResult::panic();
 
@@ -102,6 +111,10 @@ pub async fn execute_test(
         failures.push(result)
     }
 
+    if let Some(message) = subtests.report(&config.name, &adapter_info) {
+        failures.push(FailureResult::panic().with_message(message));
+    }
+
     // Check whether any validation errors were reported during the test run.
     cfg_if::cfg_if!(
         if #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))] {
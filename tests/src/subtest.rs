@@ -0,0 +1,91 @@
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+use parking_lot::Mutex;
+
+use crate::{run::panic_message, TestingContext};
+
+struct SubtestResult {
+    name: String,
+    /// The panic message of the sub-test, if it failed.
+    failure: Option<String>,
+}
+
+/// Results of the sub-tests run by a test, reported once it finishes.
+#[derive(Default)]
+pub(crate) struct Subtests {
+    results: Mutex<Vec<SubtestResult>>,
+}
+
+impl Subtests {
+    /// Prints a summary of the sub-tests that ran, and returns the list of
+    /// failed sub-tests if there are any.
+    pub(crate) fn report(
+        &self,
+        test_name: &str,
+        adapter_info: &wgpu::AdapterInfo,
+    ) -> Option<String> {
+        let results = self.results.lock();
+        if results.is_empty() {
+            return None;
+        }
+
+        eprintln!(
+            "Sub-tests of {test_name} on {} ({:?}, {} {}):",
+            adapter_info.name, adapter_info.backend, adapter_info.driver, adapter_info.driver_info
+        );
+        for result in results.iter() {
+            match result.failure {
+                Some(ref message) => eprintln!("    FAILED  {}: {message}", result.name),
+                None => eprintln!("    PASSED  {}", result.name),
+            }
+        }
+
+        let failures: Vec<_> = results.iter().filter(|r| r.failure.is_some()).collect();
+        eprintln!(
+            "{} of {} sub-tests passed",
+            results.len() - failures.len(),
+            results.len()
+        );
+        if failures.is_empty() {
+            return None;
+        }
+
+        let mut message = format!("{} of {} sub-tests failed:", failures.len(), results.len());
+        for result in failures {
+            message += &format!(
+                "\n    {}: {}",
+                result.name,
+                result.failure.as_ref().unwrap()
+            );
+        }
+        Some(message)
+    }
+}
+
+impl TestingContext {
+    /// Runs `test` as a named sub-case of the current test.
+    ///
+    /// A panic in `test` is recorded instead of ending the test, so that all the
+    /// sub-cases get to run. Once the test finishes, the harness prints whether each
+    /// sub-case passed, and fails the test once with a list of all the failed ones.
+    ///
+    /// Returns the result of `test`, or `None` if it panicked.
+    pub fn subtest<T>(&self, name: impl fmt::Display, test: impl FnOnce() -> T) -> Option<T> {
+        let result = panic::catch_unwind(AssertUnwindSafe(test));
+        let (value, failure) = match result {
+            Ok(value) => (Some(value), None),
+            Err(panic) => {
+                let message = panic_message(&*panic).unwrap_or("<unknown panic>");
+                (None, Some(message.to_owned()))
+            }
+        };
+        self.subtests.results.lock().push(SubtestResult {
+            name: name.to_string(),
+            failure,
+        });
+        value
+    }
+}
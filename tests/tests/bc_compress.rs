@@ -0,0 +1,158 @@
+//! Tests for `wgpu::util::BcCompressor`.
+
+use wgpu::util::{BcCompressor, DeviceExt, TextureDataOrder};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const SIZE: u32 = 8;
+
+/// Compresses an 8x8 `Rgba8Unorm` texture with the given texels into `format`,
+/// returning the blocks with rows of blocks `COPY_BYTES_PER_ROW_ALIGNMENT` apart.
+async fn compress(ctx: &TestingContext, texels: &[u8], format: wgpu::TextureFormat) -> Vec<u8> {
+    let size = wgpu::Extent3d {
+        width: SIZE,
+        height: SIZE,
+        depth_or_array_layers: 1,
+    };
+    let source = ctx.device.create_texture_with_data(
+        &ctx.queue,
+        &wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        },
+        TextureDataOrder::LayerMajor,
+        texels,
+    );
+    let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (wgpu::COPY_BYTES_PER_ROW_ALIGNMENT * SIZE / 4) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let compressor = BcCompressor::new(&ctx.device);
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    compressor.compress(&ctx.device, &mut encoder, &source, &target);
+    encoder.copy_texture_to_buffer(
+        target.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &read_buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let slice = read_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+    slice.get_mapped_range().to_vec()
+}
+
+async fn compress_solid_color(
+    ctx: &TestingContext,
+    color: [u8; 4],
+    format: wgpu::TextureFormat,
+) -> Vec<u8> {
+    compress(ctx, &color.repeat((SIZE * SIZE) as usize), format).await
+}
+
+#[gpu_test]
+static BC1_SOLID_COLOR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_COMPRESSION_BC))
+    .run_async(|ctx| async move {
+        let data =
+            compress_solid_color(&ctx, [255, 0, 0, 255], wgpu::TextureFormat::Bc1RgbaUnorm).await;
+
+        // Both endpoints are pure red in 565 and every index selects the first endpoint.
+        let expected = [0x00, 0xF8, 0x00, 0xF8, 0, 0, 0, 0];
+        for row in data.chunks(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize) {
+            for block in row[..16].chunks(8) {
+                assert_eq!(block, expected);
+            }
+        }
+    });
+
+#[gpu_test]
+static BC3_SOLID_COLOR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_COMPRESSION_BC))
+    .run_async(|ctx| async move {
+        let data =
+            compress_solid_color(&ctx, [0, 0, 255, 128], wgpu::TextureFormat::Bc3RgbaUnorm).await;
+
+        // Both alpha endpoints are 128 and every alpha index selects the first one,
+        // followed by a colour block that is pure blue.
+        let expected = [
+            128, 128, 0, 0, 0, 0, 0, 0, 0x1F, 0x00, 0x1F, 0x00, 0, 0, 0, 0,
+        ];
+        for row in data.chunks(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize) {
+            for block in row[..32].chunks(16) {
+                assert_eq!(block, expected);
+            }
+        }
+    });
+
+#[gpu_test]
+static BC3_ALPHA_GRADIENT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_COMPRESSION_BC))
+    .run_async(|ctx| async move {
+        // Alpha goes from 0 to 255 across the four columns of each block.
+        let alphas = [0, 85, 170, 255];
+        let texels = (0..SIZE * SIZE)
+            .flat_map(|i| [255, 0, 0, alphas[(i % 4) as usize]])
+            .collect::<Vec<u8>>();
+        let data = compress(&ctx, &texels, wgpu::TextureFormat::Bc3RgbaUnorm).await;
+
+        // The endpoints are 255 and 0. The columns select the second endpoint
+        // (index 1), 2/7 of the way up (index 6), 5/7 of the way up (index 3)
+        // and the first endpoint (index 0), packed as 3 bit indices in texel order.
+        let expected_alpha = [255, 0, 0xF1, 0x10, 0x0F, 0xF1, 0x10, 0x0F];
+        let expected_color = [0x00, 0xF8, 0x00, 0xF8, 0, 0, 0, 0];
+        for row in data.chunks(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize) {
+            for block in row[..32].chunks(16) {
+                assert_eq!(block[..8], expected_alpha);
+                assert_eq!(block[8..], expected_color);
+            }
+        }
+    });
+
+#[gpu_test]
+static BC7_SOLID_COLOR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_COMPRESSION_BC))
+    .run_async(|ctx| async move {
+        let data =
+            compress_solid_color(&ctx, [0, 128, 255, 255], wgpu::TextureFormat::Bc7RgbaUnorm).await;
+
+        let first = &data[..16];
+        // Mode 6 is selected by the seventh bit.
+        assert_eq!(first[0] & 0x7F, 0x40);
+        for row in data.chunks(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize) {
+            for block in row[..32].chunks(16) {
+                assert_eq!(block, first);
+            }
+        }
+    });
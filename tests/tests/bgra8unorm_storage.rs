@@ -49,6 +49,7 @@ static BGRA8_UNORM_STORAGE: GpuTestConfiguration = GpuTestConfiguration::new()
             base_array_layer: 0,
             mip_level_count: Some(1),
             array_layer_count: Some(1),
+            swizzle: wgpu::ComponentMapping::IDENTITY,
         });
 
         let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -97,6 +98,7 @@ static BGRA8_UNORM_STORAGE: GpuTestConfiguration = GpuTestConfiguration::new()
             layout: Some(&pl),
             entry_point: "main",
             module: &module,
+            cache: None,
         });
 
         let mut encoder =
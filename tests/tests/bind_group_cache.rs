@@ -0,0 +1,144 @@
+//! Tests for `DeviceDescriptor::cache_bind_groups`.
+
+use wgpu::util::DeviceExt;
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const SHADER_SRC: &str = "
+@group(0) @binding(0)
+var<storage, read_write> output: array<u32>;
+
+@compute @workgroup_size(1)
+fn main() {
+    output[0] += 1u;
+}
+";
+
+/// Checks the number of bind group IDs that are allocated and kept by the user, and
+/// of distinct bind groups behind the latter. The report is only available on wgpu-core.
+fn assert_bind_groups(ctx: &TestingContext, allocated: usize, kept: usize, distinct: usize) {
+    #[cfg(any(
+        not(target_arch = "wasm32"),
+        target_os = "emscripten",
+        feature = "webgl"
+    ))]
+    if let Some(report) = ctx.instance.generate_report() {
+        let report = report.hub_report(ctx.adapter_info.backend).bind_groups;
+        assert_eq!(
+            (
+                report.num_allocated,
+                report.num_kept_from_user,
+                report.num_distinct_kept_from_user
+            ),
+            (allocated, kept, distinct)
+        );
+    }
+    #[cfg(not(any(
+        not(target_arch = "wasm32"),
+        target_os = "emscripten",
+        feature = "webgl"
+    )))]
+    let _ = (ctx, allocated, kept, distinct);
+}
+
+#[gpu_test]
+static BIND_GROUP_CACHE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().test_features_limits())
+    .run_async(|ctx| async move {
+        let (device, queue) = ctx
+            .adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    required_features: ctx.device.features(),
+                    required_limits: ctx.device.limits(),
+                    cache_bind_groups: true,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        let storage = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: &[0; 4],
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        });
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: "main",
+            cache: None,
+        });
+        let layout = pipeline.get_bind_group_layout(0);
+
+        let create_bind_group = |label| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: storage.as_entire_binding(),
+                }],
+            })
+        };
+
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        // Both handles share a bind group; dropping one must not invalidate the other.
+        let first = create_bind_group("first");
+        let second = create_bind_group("second");
+        assert_bind_groups(&ctx, 2, 2, 1);
+        drop(first);
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &second, &[]);
+            pass.dispatch_workgroups(1, 1, 1);
+        }
+        queue.submit(Some(encoder.finish()));
+        drop(second);
+        device.poll(wgpu::Maintain::wait()).panic_on_timeout();
+
+        // Once every handle is gone the cached bind group is released, and an
+        // identical descriptor creates a new one.
+        //
+        // The bind group frees the ID it was created with. The ID of a cache hit
+        // is leaked, so the one of `second` stays allocated.
+        assert_bind_groups(&ctx, 1, 0, 0);
+        let third = create_bind_group("third");
+        assert_bind_groups(&ctx, 2, 1, 1);
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &third, &[]);
+            pass.dispatch_workgroups(1, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&storage, 0, &readback, 0, 4);
+        queue.submit(Some(encoder.finish()));
+
+        assert!(device.pop_error_scope().await.is_none());
+
+        readback
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, Result::unwrap);
+        device.poll(wgpu::Maintain::wait()).panic_on_timeout();
+
+        let data = readback.slice(..).get_mapped_range();
+        assert_eq!(u32::from_ne_bytes(data[..].try_into().unwrap()), 2);
+    });
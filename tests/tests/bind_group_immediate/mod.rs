@@ -0,0 +1,107 @@
+use wgpu::util::DeviceExt;
+use wgpu_test::{gpu_test, image, FailureCase, GpuTestConfiguration, TestParameters};
+
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// Draws each of the two pixels of the target with a color taken from a
+/// different uniform buffer, bound with `set_bind_group_immediate`.
+#[gpu_test]
+static BIND_GROUP_IMMEDIATE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Offscreen texture"),
+            size: wgpu::Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FORMAT,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let shader = ctx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("uniform_color.wgsl"));
+        let pipeline = ctx
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: None,
+                layout: None,
+                vertex: wgpu::VertexState {
+                    entry_point: "vs_main",
+                    module: &shader,
+                    buffers: &[],
+                    capture_buffers: &[],
+                },
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    entry_point: "fs_main",
+                    module: &shader,
+                    targets: &[Some(FORMAT.into())],
+                }),
+                multiview: None,
+                cache: None,
+            });
+
+        let create_color_buffer = |color: [f32; 4]| {
+            ctx.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: bytemuck::cast_slice(&color),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                })
+        };
+        let red = create_color_buffer([1.0, 0.0, 0.0, 1.0]);
+        let green = create_color_buffer([0.0, 1.0, 0.0, 1.0]);
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &texture_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
+            });
+            pass.set_pipeline(&pipeline);
+            for (x, buffer) in [&red, &green].into_iter().enumerate() {
+                pass.set_scissor_rect(x as u32, 0, 1, 1);
+                pass.set_bind_group_immediate(
+                    0,
+                    &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    }],
+                );
+                pass.draw(0..3, 0..1);
+            }
+        }
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &texture);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        readback_buffer
+            .assert_buffer_contents(&ctx, &[255, 0, 0, 255, 0, 255, 0, 255])
+            .await;
+    });
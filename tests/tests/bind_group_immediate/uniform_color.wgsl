@@ -0,0 +1,20 @@
+@group(0) @binding(0)
+var<uniform> color: vec4<f32>;
+
+// meant to be called with 3 vertex indices: 0, 1, 2
+// draws one large triangle covering the whole clip space
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let x = i32(vertex_index) / 2;
+    let y = i32(vertex_index) & 1;
+    return vec4<f32>(
+        f32(x) * 4.0 - 1.0,
+        1.0 - f32(y) * 4.0,
+        0.0, 1.0
+    );
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return color;
+}
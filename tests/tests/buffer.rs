@@ -1,4 +1,6 @@
-use wgpu_test::{gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext};
+use wgpu_test::{
+    gpu_test, valid, FailureCase, GpuTestConfiguration, TestParameters, TestingContext,
+};
 
 async fn test_empty_buffer_range(ctx: &TestingContext, buffer_size: u64, label: &str) {
     let r = wgpu::BufferUsages::MAP_READ;
@@ -164,3 +166,58 @@ static MAP_OFFSET: GpuTestConfiguration = GpuTestConfiguration::new().run_async(
         assert_eq!(*byte, 0);
     }
 });
+
+#[gpu_test]
+static MAP_DISJOINT_RANGES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        // This test reads back a ring buffer of two slots, copying into one
+        // slot while the other one is still mapped.
+
+        let src_buf = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 256,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let ring_buf = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 512,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let slots = [ring_buf.slice(0..256), ring_buf.slice(256..512)];
+
+        for frame in 0..4u8 {
+            let slot_index = frame as usize % 2;
+            ctx.queue.write_buffer(&src_buf, 0, &[frame + 1; 256]);
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+            encoder.copy_buffer_to_buffer(&src_buf, 0, &ring_buf, slot_index as u64 * 256, 256);
+            valid(&ctx.device, || ctx.queue.submit(Some(encoder.finish())));
+
+            slots[slot_index].map_async(wgpu::MapMode::Read, Result::unwrap);
+
+            ctx.async_poll(wgpu::Maintain::wait())
+                .await
+                .panic_on_timeout();
+
+            if frame > 0 {
+                // The previous slot was mapped before the copy, and kept its contents.
+                let previous = &slots[1 - slot_index];
+                assert!(previous
+                    .get_mapped_range()
+                    .iter()
+                    .all(|&byte| byte == frame));
+                previous.unmap();
+            }
+            assert!(slots[slot_index]
+                .get_mapped_range()
+                .iter()
+                .all(|&byte| byte == frame + 1));
+        }
+    });
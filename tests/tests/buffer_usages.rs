@@ -1,7 +1,7 @@
 //! Tests for buffer usages validation.
 
 use wgpu::{BufferUsages as Bu, MapMode as Ma};
-use wgpu_test::{fail_if, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+use wgpu_test::{fail, fail_if, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
 use wgt::BufferAddress;
 
 const BUFFER_SIZE: BufferAddress = 1234;
@@ -170,3 +170,84 @@ static BUFFER_MAP_ASYNC_MAP_STATE: GpuTestConfiguration = GpuTestConfiguration::
             }
         }
     });
+
+const PRIMARY_USAGE: Bu = Bu::MAP_READ
+    .union(Bu::MAP_WRITE)
+    .union(Bu::COPY_SRC)
+    .union(Bu::COPY_DST);
+
+#[gpu_test]
+static BUFFER_MAPPABLE_PRIMARY_ROUND_TRIP: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::MAPPABLE_PRIMARY_BUFFERS))
+    .run_async(|ctx| async move {
+        // Written and read back by the CPU without any staging buffer.
+        let [source, target] = [true, false].map(|mapped_at_creation| {
+            ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 256,
+                usage: PRIMARY_USAGE,
+                mapped_at_creation,
+            })
+        });
+        source.slice(..).get_mapped_range_mut().fill(5);
+        source.unmap();
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&source, 0, &target, 0, 256);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        target.slice(..).map_async(Ma::Read, Result::unwrap);
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        assert!(target
+            .slice(..)
+            .get_mapped_range()
+            .iter()
+            .all(|&byte| byte == 5));
+    });
+
+#[gpu_test]
+static BUFFER_MAPPABLE_PRIMARY_SUBMIT_WHILE_MAPPED: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(TestParameters::default().features(wgpu::Features::MAPPABLE_PRIMARY_BUFFERS))
+        .run_sync(|ctx| {
+            let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 256,
+                usage: PRIMARY_USAGE,
+                mapped_at_creation: true,
+            });
+            let target = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 256,
+                usage: Bu::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.copy_buffer_to_buffer(&buffer, 0, &target, 0, 256);
+            fail(&ctx.device, || {
+                ctx.queue.submit(Some(encoder.finish()));
+            });
+        });
+
+#[gpu_test]
+static DEVICE_MEMORY_ARCHITECTURE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        if ctx.adapter_info.backend == wgpu::Backend::BrowserWebGpu {
+            return;
+        }
+        let expected = match ctx.adapter_info.device_type {
+            wgpu::DeviceType::DiscreteGpu => wgpu::MemoryArchitecture::Discrete,
+            wgpu::DeviceType::IntegratedGpu | wgpu::DeviceType::Cpu => {
+                wgpu::MemoryArchitecture::Unified
+            }
+            _ => return,
+        };
+        assert_eq!(ctx.device.memory_architecture(), expected);
+    });
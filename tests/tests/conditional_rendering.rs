@@ -0,0 +1,268 @@
+//! Tests for `RenderPass::begin_conditional_rendering`.
+
+use wgpu_test::{
+    fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext,
+};
+
+/// Each group of 3 vertices is a triangle covering a single pixel of a 2x1
+/// render target, from left to right.
+const SHADER: &str = r#"
+    @vertex
+    fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+        let left = -1.0 + f32(index / 3u);
+        switch index % 3u {
+            case 0u: { return vec4f(left, -3.0, 0.0, 1.0); }
+            case 1u: { return vec4f(left, 3.0, 0.0, 1.0); }
+            default: { return vec4f(left + 1.0, 0.0, 0.0, 1.0); }
+        }
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+"#;
+
+const WIDTH: u32 = 2;
+
+fn create_target(ctx: &TestingContext) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: WIDTH,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+fn create_pipeline(ctx: &TestingContext) -> wgpu::RenderPipeline {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+                capture_buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+            cache: None,
+        })
+}
+
+fn begin_render_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+    occlusion_query_set: Option<&'a wgpu::QuerySet>,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: None,
+    })
+}
+
+/// Runs an occlusion query passing no samples, and another passing some, then
+/// draws each pixel conditionally on one of them. Returns which pixels were
+/// drawn to.
+async fn draw_with_occlusion_conditions(ctx: &TestingContext) -> Vec<bool> {
+    let target = create_target(ctx);
+    let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+    let pipeline = create_pipeline(ctx);
+    let query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+        label: None,
+        ty: wgpu::QueryType::Occlusion,
+        count: 2,
+    });
+    // Each result is resolved to its own aligned offset.
+    let second_offset = wgpu::QUERY_RESOLVE_BUFFER_ALIGNMENT;
+    let condition_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: second_offset + 8,
+        usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::INDIRECT,
+        mapped_at_creation: false,
+    });
+    let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = begin_render_pass(&mut encoder, &view, Some(&query_set));
+        pass.set_pipeline(&pipeline);
+        pass.begin_occlusion_query(0);
+        pass.draw(0..0, 0..1);
+        pass.end_occlusion_query();
+        pass.begin_occlusion_query(1);
+        pass.draw(0..3, 0..1);
+        pass.end_occlusion_query();
+    }
+    encoder.resolve_query_set(&query_set, 0..1, &condition_buffer, 0);
+    encoder.resolve_query_set(&query_set, 1..2, &condition_buffer, second_offset);
+    {
+        let mut pass = begin_render_pass(&mut encoder, &view, None);
+        pass.set_pipeline(&pipeline);
+        pass.begin_conditional_rendering(&condition_buffer, 0);
+        pass.draw(0..3, 0..1);
+        pass.end_conditional_rendering();
+        pass.begin_conditional_rendering(&condition_buffer, second_offset);
+        pass.draw(3..6, 0..1);
+        pass.end_conditional_rendering();
+    }
+    encoder.copy_texture_to_buffer(
+        target.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &read_buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: None,
+                rows_per_image: None,
+            },
+        },
+        wgpu::Extent3d {
+            width: WIDTH,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let slice = read_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+    let data = slice.get_mapped_range();
+    data[..WIDTH as usize * 4]
+        .chunks(4)
+        .map(|pixel| pixel == [255; 4])
+        .collect()
+}
+
+#[gpu_test]
+static CONDITIONAL_RENDERING: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::CONDITIONAL_RENDERING))
+    .run_async(|ctx| async move {
+        assert_eq!(draw_with_occlusion_conditions(&ctx).await, [false, true]);
+    });
+
+/// Without the feature, no draws are skipped.
+#[gpu_test]
+static CONDITIONAL_RENDERING_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().expect_fail(FailureCase::webgl2()))
+    .run_async(|ctx| async move {
+        assert_eq!(draw_with_occlusion_conditions(&ctx).await, [true, true]);
+    });
+
+#[gpu_test]
+static CONDITIONAL_RENDERING_INVALID: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_sync(|ctx| {
+        let target = create_target(&ctx);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let condition_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        });
+        let uniform_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM,
+            mapped_at_creation: false,
+        });
+
+        let run_pass = |commands: &dyn Fn(&mut wgpu::RenderPass<'_>)| {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut pass = begin_render_pass(&mut encoder, &view, None);
+                commands(&mut pass);
+            }
+            encoder.finish()
+        };
+
+        run_pass(&|pass| {
+            pass.begin_conditional_rendering(&condition_buffer, 8);
+            pass.end_conditional_rendering();
+        });
+
+        // Unaligned offset.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.begin_conditional_rendering(&condition_buffer, 4);
+                pass.end_conditional_rendering();
+            })
+        });
+        // The condition is read past the end of the buffer.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.begin_conditional_rendering(&condition_buffer, 16);
+                pass.end_conditional_rendering();
+            })
+        });
+        // Missing `INDIRECT` usage.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.begin_conditional_rendering(&uniform_buffer, 0);
+                pass.end_conditional_rendering();
+            })
+        });
+        // Nested conditional rendering.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.begin_conditional_rendering(&condition_buffer, 0);
+                pass.begin_conditional_rendering(&condition_buffer, 8);
+                pass.end_conditional_rendering();
+                pass.end_conditional_rendering();
+            })
+        });
+        // Ended without being begun.
+        fail(&ctx.device, || {
+            run_pass(&|pass| pass.end_conditional_rendering())
+        });
+        // Not ended before the end of the pass.
+        fail(&ctx.device, || {
+            run_pass(&|pass| pass.begin_conditional_rendering(&condition_buffer, 0))
+        });
+    });
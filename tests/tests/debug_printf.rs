@@ -0,0 +1,86 @@
+//! Tests for `Features::SHADER_DEBUG_PRINTF`.
+
+use std::sync::{Arc, Mutex};
+
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+const SHADER: &str = r#"
+    @group(0) @binding(0)
+    var<storage, read_write> values: array<f32>;
+
+    @compute @workgroup_size(4)
+    fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+        values[id.x] = f32(id.x) * 0.5;
+        debugPrintf("invocation %u wrote %.1f", id.x, values[id.x]);
+    }
+"#;
+
+#[gpu_test]
+static DEBUG_PRINTF_COMPUTE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::SHADER_DEBUG_PRINTF))
+    .run_async(|ctx| async move {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let sink = messages.clone();
+        ctx.device.set_debug_printf_callback(move |message| {
+            sink.lock().unwrap().push(message.to_string())
+        });
+
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                cache: None,
+            });
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(1, 1, 1);
+        }
+        ctx.queue.submit(Some(encoder.finish()));
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+
+        let mut messages = messages.lock().unwrap().clone();
+        messages.sort();
+        assert_eq!(
+            messages,
+            [
+                "invocation 0 wrote 0.0",
+                "invocation 1 wrote 0.5",
+                "invocation 2 wrote 1.0",
+                "invocation 3 wrote 1.5",
+            ]
+        );
+    });
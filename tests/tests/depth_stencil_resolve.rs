@@ -0,0 +1,135 @@
+use wgpu_test::{fail, gpu_test, image, GpuTestConfiguration, TestParameters};
+
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Covers the whole target at a depth of 0.5.
+const SHADER: &str = "
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let x = i32(vertex_index) / 2;
+    let y = i32(vertex_index) & 1;
+    return vec4<f32>(f32(x) * 4.0 - 1.0, 1.0 - f32(y) * 4.0, 0.5, 1.0);
+}
+";
+
+fn create_pipeline(device: &wgpu::Device) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+    });
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: "vs_main",
+            buffers: &[],
+            capture_buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: 4,
+            ..Default::default()
+        },
+        fragment: None,
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn create_depth_texture(device: &wgpu::Device, sample_count: u32) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 2,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+fn depth_attachment<'a>(
+    view: &'a wgpu::TextureView,
+    resolve_view: &'a wgpu::TextureView,
+) -> wgpu::RenderPassDepthStencilAttachment<'a> {
+    wgpu::RenderPassDepthStencilAttachment {
+        view,
+        depth_ops: Some(wgpu::Operations {
+            load: wgpu::LoadOp::Clear(1.0),
+            store: wgpu::StoreOp::Discard,
+        }),
+        stencil_ops: None,
+        resolve: Some(wgpu::RenderPassDepthStencilResolve {
+            view: resolve_view,
+            mode: wgpu::ResolveMode::SampleZero,
+        }),
+    }
+}
+
+/// The resolve target receives the depth of the multisampled attachment, even though the
+/// attachment itself is discarded.
+#[gpu_test]
+static RESOLVE_DEPTH_SAMPLE_ZERO: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::DEPTH_STENCIL_RESOLVE))
+    .run_async(|ctx| async move {
+        let texture = create_depth_texture(&ctx.device, 4);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let resolve_texture = create_depth_texture(&ctx.device, 1);
+        let resolve_view = resolve_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_pipeline(&ctx.device);
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                depth_stencil_attachment: Some(depth_attachment(&view, &resolve_view)),
+                ..Default::default()
+            });
+            pass.set_pipeline(&pipeline);
+            pass.draw(0..3, 0..1);
+        }
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &resolve_texture);
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &resolve_texture);
+
+        ctx.queue.submit([encoder.finish()]);
+
+        readback_buffer
+            .assert_buffer_contents(&ctx, bytemuck::cast_slice(&[0.5f32, 0.5]))
+            .await;
+    });
+
+/// Depth-stencil resolve targets must be single-sampled.
+#[gpu_test]
+static RESOLVE_DEPTH_INTO_MULTISAMPLED: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::DEPTH_STENCIL_RESOLVE))
+    .run_sync(|ctx| {
+        let texture = create_depth_texture(&ctx.device, 4);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let resolve_texture = create_depth_texture(&ctx.device, 4);
+        let resolve_view = resolve_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        fail(&ctx.device, || {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            drop(encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                depth_stencil_attachment: Some(depth_attachment(&view, &resolve_view)),
+                ..Default::default()
+            }));
+            encoder.finish()
+        });
+    });
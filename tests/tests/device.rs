@@ -333,6 +333,8 @@ static DEVICE_DESTROY_THEN_MORE: GpuTestConfiguration = GpuTestConfiguration::ne
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
         });
 
@@ -449,12 +451,14 @@ static DEVICE_DESTROY_THEN_MORE: GpuTestConfiguration = GpuTestConfiguration::ne
                         module: &shader_module,
                         entry_point: "",
                         buffers: &[],
+                        capture_buffers: &[],
                     },
                     primitive: wgpu::PrimitiveState::default(),
                     depth_stencil: None,
                     multisample: wgpu::MultisampleState::default(),
                     fragment: None,
                     multiview: None,
+                    cache: None,
                 });
         });
 
@@ -466,6 +470,7 @@ static DEVICE_DESTROY_THEN_MORE: GpuTestConfiguration = GpuTestConfiguration::ne
                     layout: None,
                     module: &shader_module,
                     entry_point: "",
+                    cache: None,
                 });
         });
 
@@ -549,3 +554,240 @@ static DEVICE_DROP_THEN_LOST: GpuTestConfiguration = GpuTestConfiguration::new()
             "Device lost callback should have been called."
         );
     });
+
+#[gpu_test]
+static DEVICE_MARK_LOST_THEN_RECOVER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        // The WebGPU backend doesn't implement `Device::mark_lost`.
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        // This test checks that a lost device resolves `Device::lost`, ignores the
+        // work submitted to it, and can be replaced by a new device from the same
+        // adapter.
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 256,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.clear_buffer(&buffer, 0, None);
+        let command_buffer = encoder.finish();
+
+        ctx.device.mark_lost("Simulated device loss.");
+
+        let (reason, message) = ctx.device.lost().await;
+        assert!(
+            matches!(reason, wgt::DeviceLostReason::Unknown),
+            "Device lost info reason should match DeviceLostReason::Unknown."
+        );
+        assert_eq!(message, "Simulated device loss.");
+
+        // A callback set after the loss is called right away.
+        let was_called = std::sync::Arc::<std::sync::atomic::AtomicBool>::new(false.into());
+        let was_called_clone = was_called.clone();
+        ctx.device
+            .set_device_lost_callback(move |_reason, _message| {
+                was_called_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+            });
+        assert!(
+            was_called.load(std::sync::atomic::Ordering::SeqCst),
+            "Device lost callback should have been called."
+        );
+
+        // Submitting to the lost device does nothing.
+        ctx.queue.submit(Some(command_buffer));
+        ctx.async_poll(wgpu::Maintain::wait()).await;
+
+        // Rebuild on a new device from the same adapter.
+        let (device, queue) = ctx
+            .adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: None,
+                    required_features: ctx.device_features,
+                    required_limits: ctx.device_limits.clone(),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 256,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&buffer, 0, &[1; 256]);
+        queue.submit(None);
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, |result| result.unwrap());
+        device.poll(wgpu::Maintain::wait()).panic_on_timeout();
+        assert!(buffer
+            .slice(..)
+            .get_mapped_range()
+            .iter()
+            .all(|&byte| byte == 1));
+    });
+
+#[gpu_test]
+static DEVICE_LOST_BY_DRIVER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        // The WebGPU backend doesn't implement `Device::mark_lost_by_driver`.
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        // This test checks that a device lost by the driver calls its device lost
+        // callback, and fails the mappings waiting on its submissions.
+        let lost = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let lost_clone = lost.clone();
+        ctx.device
+            .set_device_lost_callback(move |reason, _message| {
+                assert!(matches!(reason, wgt::DeviceLostReason::Unknown));
+                lost_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+            });
+
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 256,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.clear_buffer(&buffer, 0, None);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let map_result = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let map_result_clone = map_result.clone();
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                *map_result_clone.lock().unwrap() = Some(result);
+            });
+
+        ctx.device.mark_lost_by_driver();
+        ctx.async_poll(wgpu::Maintain::wait()).await;
+
+        assert!(
+            lost.load(std::sync::atomic::Ordering::SeqCst),
+            "Device lost callback should have been called."
+        );
+        let map_result = map_result.lock().unwrap().take();
+        assert!(
+            matches!(map_result, Some(Err(_))),
+            "Pending mapping should have failed, got {map_result:?}."
+        );
+    });
+
+#[gpu_test]
+static WATCHDOG_IGNORES_FAST_SUBMISSIONS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().skip(FailureCase::webgl2()))
+    .run_async(|ctx| async move {
+        let reports = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = reports.clone();
+        ctx.device
+            .enable_watchdog(std::time::Duration::from_secs(10), move |_| {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("watched"),
+            });
+        encoder.push_debug_group("group");
+        encoder.pop_debug_group();
+        ctx.queue.submit(Some(encoder.finish()));
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+
+        ctx.device.disable_watchdog();
+        assert_eq!(reports.load(std::sync::atomic::Ordering::SeqCst), 0);
+    });
+
+#[gpu_test]
+static COMPUTE_ONLY_DEVICE_REJECTS_RENDERING: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        // The WebGPU backend ignores `compute_only`.
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let (device, queue) = ctx
+            .adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: None,
+                    required_features: wgpu::Features::empty(),
+                    required_limits: wgpu::Limits::downlevel_defaults(),
+                    compute_only: true,
+                    queue_priority: wgpu::QueuePriority::Normal,
+                    additional_queues: 0,
+                    cache_bind_groups: false,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Compute and transfer work is still allowed.
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        queue.submit(Some(encoder.finish()));
+
+        fail(&device, || {
+            let mut encoder =
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
+            });
+            encoder.finish()
+        });
+    });
+
+#[gpu_test]
+static EXPLAIN_FEATURES: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    let features = ctx.adapter.features();
+    for feature in wgpu::Features::all().iter() {
+        let support = ctx.adapter.explain_feature(feature);
+        assert_eq!(
+            support.is_available(),
+            features.contains(feature),
+            "{feature:?}: {support}"
+        );
+    }
+    assert!(ctx.adapter.explain_feature(features).is_available());
+});
@@ -0,0 +1,297 @@
+//! Tests for `RenderPass::execute_indirect`.
+
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt, DrawIndirectArgs, VertexBufferIndirectArgs},
+    IndirectArgument,
+};
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const SHADER: &str = r#"
+    var<push_constant> color: vec4f;
+
+    @vertex
+    fn vs_main(@location(0) position: vec2f) -> @builtin(position) vec4f {
+        return vec4f(position, 0.0, 1.0);
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return color;
+    }
+"#;
+
+/// Each command binds the vertices of its triangle, sets its color and draws it.
+const ARGUMENTS: [IndirectArgument; 3] = [
+    IndirectArgument::VertexBuffer { slot: 0 },
+    IndirectArgument::PushConstants {
+        stages: wgpu::ShaderStages::FRAGMENT,
+        offset: 0,
+        size: 16,
+    },
+    IndirectArgument::Draw,
+];
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .features(wgpu::Features::DEVICE_GENERATED_COMMANDS | wgpu::Features::PUSH_CONSTANTS)
+        .limits(wgpu::Limits {
+            max_push_constant_size: 16,
+            ..Default::default()
+        })
+}
+
+fn create_target(ctx: &TestingContext) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 2,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+fn create_pipeline(ctx: &TestingContext) -> wgpu::RenderPipeline {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    let layout = ctx
+        .device
+        .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[],
+            push_constant_ranges: &[wgpu::PushConstantRange {
+                stages: wgpu::ShaderStages::FRAGMENT,
+                range: 0..16,
+            }],
+        });
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: 8,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x2],
+                }],
+                capture_buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview: None,
+            cache: None,
+        })
+}
+
+fn begin_render_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: None,
+    })
+}
+
+#[gpu_test]
+static EXECUTE_INDIRECT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_async(|ctx| async move {
+        let target = create_target(&ctx);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_pipeline(&ctx);
+
+        // One triangle covering each pixel.
+        let vertices: [[f32; 2]; 6] = [
+            [-1.0, -3.0],
+            [-1.0, 3.0],
+            [0.0, 0.0],
+            [1.0, -3.0],
+            [1.0, 3.0],
+            [0.0, 0.0],
+        ];
+        let vertex_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: &vertices
+                .iter()
+                .flatten()
+                .flat_map(|v| v.to_le_bytes())
+                .collect::<Vec<_>>(),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let address = vertex_buffer.device_address();
+
+        let colors: [[f32; 4]; 2] = [[1.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 1.0]];
+        let mut commands = Vec::new();
+        for (index, color) in colors.iter().enumerate() {
+            let vertex_args = VertexBufferIndirectArgs {
+                address: address + index as u64 * 24,
+                size: 24,
+                stride: 8,
+            };
+            let draw_args = DrawIndirectArgs {
+                vertex_count: 3,
+                instance_count: 1,
+                first_vertex: 0,
+                first_instance: 0,
+            };
+            commands.extend_from_slice(vertex_args.as_bytes());
+            commands.extend(color.iter().flat_map(|c| c.to_le_bytes()));
+            commands.extend_from_slice(draw_args.as_bytes());
+        }
+        assert_eq!(
+            commands.len(),
+            IndirectArgument::stride(&ARGUMENTS) as usize * colors.len()
+        );
+        let indirect_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: &commands,
+            usage: wgpu::BufferUsages::INDIRECT,
+        });
+
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = begin_render_pass(&mut encoder, &view);
+            pass.set_pipeline(&pipeline);
+            pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+            unsafe { pass.execute_indirect(&ARGUMENTS, &indirect_buffer, 0, 2) };
+        }
+        encoder.copy_texture_to_buffer(
+            target.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &read_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: None,
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data = slice.get_mapped_range();
+        assert_eq!(data[..8], [255, 0, 0, 255, 0, 255, 0, 255]);
+    });
+
+#[gpu_test]
+static EXECUTE_INDIRECT_INVALID_ARGUMENTS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        let target = create_target(&ctx);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_pipeline(&ctx);
+        let indirect_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 256,
+            usage: wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        });
+
+        let invalid_arguments: [&[IndirectArgument]; 3] = [
+            // Missing draw.
+            &ARGUMENTS[..2],
+            // Draw before the state changes.
+            &[IndirectArgument::Draw, ARGUMENTS[0]],
+            // Vertex buffer starting at 4 bytes.
+            &[
+                IndirectArgument::PushConstants {
+                    stages: wgpu::ShaderStages::FRAGMENT,
+                    offset: 0,
+                    size: 4,
+                },
+                ARGUMENTS[0],
+                IndirectArgument::Draw,
+            ],
+        ];
+        for arguments in invalid_arguments {
+            fail(&ctx.device, || {
+                let mut encoder = ctx
+                    .device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+                {
+                    let mut pass = begin_render_pass(&mut encoder, &view);
+                    pass.set_pipeline(&pipeline);
+                    unsafe { pass.execute_indirect(arguments, &indirect_buffer, 0, 1) };
+                }
+                encoder.finish()
+            });
+        }
+    });
+
+#[gpu_test]
+static EXECUTE_INDIRECT_REQUIRES_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_sync(|ctx| {
+        let target = create_target(&ctx);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let indirect_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        });
+
+        fail(&ctx.device, || {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut pass = begin_render_pass(&mut encoder, &view);
+                unsafe { pass.execute_indirect(&[IndirectArgument::Draw], &indirect_buffer, 0, 1) };
+            }
+            encoder.finish()
+        });
+    });
@@ -49,6 +49,8 @@ static DROP_ENCODER_AFTER_ERROR: GpuTestConfiguration = GpuTestConfiguration::ne
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
         });
 
         // Set a bad viewport on renderpass, triggering an error.
@@ -62,3 +64,58 @@ static DROP_ENCODER_AFTER_ERROR: GpuTestConfiguration = GpuTestConfiguration::ne
         // The encoder is still open!
         drop(encoder);
     });
+
+#[gpu_test]
+static COMMAND_DISASSEMBLY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let create_buffer = |label| {
+            ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: 16,
+                usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+        let src = create_buffer("src");
+        let dst = create_buffer("dst");
+
+        let record = || {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("frame"),
+                });
+            encoder.push_debug_group("upload");
+            encoder.copy_buffer_to_buffer(&src, 0, &dst, 0, 16);
+            encoder.pop_debug_group();
+            encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("culling"),
+                timestamp_writes: None,
+            });
+            encoder.finish()
+        };
+
+        assert_eq!(record().disassembly(), None);
+
+        ctx.device.set_command_disassembly(true);
+        let command_buffer = record();
+        ctx.device.set_command_disassembly(false);
+
+        let disassembly = command_buffer.disassembly().unwrap();
+        let lines: Vec<_> = disassembly.lines().map(str::trim).collect();
+        assert_eq!(
+            lines,
+            [
+                "command buffer \"frame\"",
+                "push debug group \"upload\"",
+                "copy 16 bytes from buffer src at 0 to buffer dst at 0",
+                "pop debug group",
+                "begin compute pass \"culling\"",
+                "end compute pass",
+            ]
+        );
+        ctx.queue.submit([command_buffer]);
+    });
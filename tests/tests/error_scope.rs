@@ -0,0 +1,79 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use wgpu_test::{gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext};
+
+/// Creates a buffer that is invalid because its size isn't a multiple of 4 while mapped at
+/// creation.
+fn create_invalid_buffer(ctx: &TestingContext) -> wgpu::Buffer {
+    ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 3,
+        usage: wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: true,
+    })
+}
+
+#[gpu_test]
+static ERROR_SCOPE_NESTED_FILTERS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        ctx.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Internal);
+        let _buffer = create_invalid_buffer(&ctx);
+
+        // The validation error skips the scopes with other filters.
+        assert!(ctx.device.pop_error_scope().await.is_none());
+        assert!(ctx.device.pop_error_scope().await.is_none());
+        let error = ctx.device.pop_error_scope().await;
+        assert!(matches!(error, Some(wgpu::Error::Validation { .. })));
+    });
+
+#[gpu_test]
+static ERROR_SCOPE_INNERMOST_MATCHING: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let _first = create_invalid_buffer(&ctx);
+        let _second = create_invalid_buffer(&ctx);
+
+        // Both errors are caught by the innermost scope, which only keeps the first one.
+        assert!(ctx.device.pop_error_scope().await.is_some());
+        assert!(ctx.device.pop_error_scope().await.is_none());
+    });
+
+#[gpu_test]
+static ERROR_SCOPE_POP_EMPTY: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        assert!(ctx.device.pop_error_scope().await.is_none());
+    });
+
+#[gpu_test]
+static ERROR_SCOPE_UNCAPTURED: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            // The browser reports uncaptured errors asynchronously.
+            .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let uncaptured = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&uncaptured);
+        ctx.device.on_uncaptured_error(Box::new(move |error| {
+            assert!(matches!(error, wgpu::Error::Validation { .. }));
+            counter.fetch_add(1, Ordering::Relaxed);
+        }));
+
+        // Errors caught by a scope don't reach the handler.
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let _caught = create_invalid_buffer(&ctx);
+        assert!(pollster::block_on(ctx.device.pop_error_scope()).is_some());
+        assert_eq!(uncaptured.load(Ordering::Relaxed), 0);
+
+        // Neither do errors of a kind the scope does not catch.
+        ctx.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let _uncaptured = create_invalid_buffer(&ctx);
+        assert!(pollster::block_on(ctx.device.pop_error_scope()).is_none());
+        assert_eq!(uncaptured.load(Ordering::Relaxed), 1);
+    });
@@ -0,0 +1,36 @@
+#![cfg(not(target_arch = "wasm32"))]
+//! Tests for EXTERNAL_FENCE feature
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters};
+
+#[gpu_test]
+static CREATE_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    fail(&ctx.device, || {
+        ctx.device
+            .create_external_fence(&wgpu::ExternalFenceDescriptor { label: None })
+    });
+});
+
+#[gpu_test]
+static SIGNAL_AND_WAIT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTERNAL_FENCE))
+    .run_sync(|ctx| {
+        let fence = ctx
+            .device
+            .create_external_fence(&wgpu::ExternalFenceDescriptor {
+                label: Some("fence"),
+            });
+
+        let encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        ctx.queue
+            .submit_with_external_signals(Some(encoder.finish()), &[(&fence, 1)]);
+
+        // The queue is waiting on its own signal, so this can't deadlock.
+        ctx.queue.wait_for_external_fence(&fence, 1);
+        let index = ctx.queue.submit(None);
+        ctx.device
+            .poll(wgpu::Maintain::WaitForSubmissionIndex(index))
+            .panic_on_timeout();
+    });
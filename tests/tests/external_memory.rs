@@ -0,0 +1,72 @@
+#![cfg(not(target_arch = "wasm32"))]
+//! Tests for EXTERNAL_MEMORY feature
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters};
+
+const DATA: [u8; 16] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+const DESCRIPTOR: wgpu::BufferDescriptor = wgpu::BufferDescriptor {
+    label: None,
+    size: DATA.len() as wgpu::BufferAddress,
+    usage: wgpu::BufferUsages::STORAGE
+        .union(wgpu::BufferUsages::COPY_SRC)
+        .union(wgpu::BufferUsages::COPY_DST),
+    mapped_at_creation: false,
+};
+
+#[gpu_test]
+static CREATE_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    fail(&ctx.device, || {
+        ctx.device.create_external_buffer(&DESCRIPTOR)
+    });
+});
+
+#[gpu_test]
+static CREATE_MAPPABLE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTERNAL_MEMORY))
+    .run_sync(|ctx| {
+        fail(&ctx.device, || {
+            ctx.device.create_external_buffer(&wgpu::BufferDescriptor {
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                ..DESCRIPTOR
+            })
+        });
+    });
+
+#[gpu_test]
+static EXPORT_AND_IMPORT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTERNAL_MEMORY))
+    .run_async(|ctx| async move {
+        let exported = ctx.device.create_external_buffer(&DESCRIPTOR);
+        ctx.queue.write_buffer(&exported, 0, &DATA);
+
+        let memory = exported.export_external_memory();
+        assert!(memory.size >= DESCRIPTOR.size);
+        let imported = unsafe { ctx.device.import_external_buffer(&DESCRIPTOR, memory) };
+
+        // The import holds its own reference to the memory.
+        #[cfg(unix)]
+        if let wgpu::ExternalMemoryHandle::OpaqueFd(fd) = memory.handle {
+            use std::os::fd::FromRawFd as _;
+            drop(unsafe { std::os::fd::OwnedFd::from_raw_fd(fd) });
+        }
+
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: DESCRIPTOR.size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&imported, 0, &read_buffer, 0, DESCRIPTOR.size);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        assert_eq!(&*slice.get_mapped_range(), &DATA);
+    });
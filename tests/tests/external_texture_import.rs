@@ -0,0 +1,65 @@
+#![cfg(not(target_arch = "wasm32"))]
+//! Tests for EXTERNAL_TEXTURE_IMPORT feature
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters};
+
+const DESCRIPTOR: wgpu::TextureDescriptor = wgpu::TextureDescriptor {
+    label: None,
+    size: wgpu::Extent3d {
+        width: 64,
+        height: 64,
+        depth_or_array_layers: 1,
+    },
+    mip_level_count: 1,
+    sample_count: 1,
+    dimension: wgpu::TextureDimension::D2,
+    format: wgpu::TextureFormat::Rgba8Unorm,
+    usage: wgpu::TextureUsages::TEXTURE_BINDING,
+    view_formats: &[],
+};
+
+// The descriptors are rejected before the source is looked at, so no real image is needed.
+fn dummy_source() -> wgpu::ExternalImageSource {
+    wgpu::ExternalImageSource::DmaBuf(wgpu::DmaBufImage {
+        fd: -1,
+        modifier: 0,
+        planes: vec![wgpu::DmaBufPlane::default()],
+    })
+}
+
+#[gpu_test]
+static IMPORT_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    fail(&ctx.device, || unsafe {
+        ctx.device
+            .import_external_texture(&DESCRIPTOR, dummy_source())
+    });
+});
+
+#[gpu_test]
+static IMPORT_MIPMAPPED_TEXTURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTERNAL_TEXTURE_IMPORT))
+    .run_sync(|ctx| {
+        let desc = wgpu::TextureDescriptor {
+            mip_level_count: 2,
+            ..DESCRIPTOR
+        };
+        fail(&ctx.device, || unsafe {
+            ctx.device.import_external_texture(&desc, dummy_source())
+        });
+    });
+
+#[gpu_test]
+static IMPORT_ARRAY_TEXTURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTERNAL_TEXTURE_IMPORT))
+    .run_sync(|ctx| {
+        let desc = wgpu::TextureDescriptor {
+            size: wgpu::Extent3d {
+                depth_or_array_layers: 2,
+                ..DESCRIPTOR.size
+            },
+            ..DESCRIPTOR
+        };
+        fail(&ctx.device, || unsafe {
+            ctx.device.import_external_texture(&desc, dummy_source())
+        });
+    });
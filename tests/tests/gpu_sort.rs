@@ -0,0 +1,129 @@
+//! Tests for `wgpu::util::PrefixSum` and `wgpu::util::RadixSort`.
+
+use wgpu::util::{DeviceExt, PrefixSum, RadixSort};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+fn create_buffer(ctx: &TestingContext, data: &[u32]) -> wgpu::Buffer {
+    ctx.device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: &data
+                .iter()
+                .flat_map(|x| x.to_le_bytes())
+                .collect::<Vec<_>>(),
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_DST
+                | wgpu::BufferUsages::COPY_SRC,
+        })
+}
+
+async fn read_buffers(
+    ctx: &TestingContext,
+    mut encoder: wgpu::CommandEncoder,
+    buffers: &[&wgpu::Buffer],
+) -> Vec<Vec<u32>> {
+    let read_buffers = buffers
+        .iter()
+        .map(|buffer| {
+            let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: buffer.size(),
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            encoder.copy_buffer_to_buffer(buffer, 0, &read_buffer, 0, buffer.size());
+            read_buffer
+        })
+        .collect::<Vec<_>>();
+    ctx.queue.submit(Some(encoder.finish()));
+
+    for read_buffer in &read_buffers {
+        read_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| ());
+    }
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+    read_buffers
+        .iter()
+        .map(|read_buffer| {
+            read_buffer
+                .slice(..)
+                .get_mapped_range()
+                .chunks_exact(4)
+                .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+                .collect()
+        })
+        .collect()
+}
+
+#[gpu_test]
+static PREFIX_SUM: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        let prefix_sum = PrefixSum::with_workgroup_size(&ctx.device, 64);
+        // Enough elements for three levels of blocks, not a multiple of the workgroup size.
+        let count = 64 * 64 + 100;
+        let data = (0..count).map(|i| i % 7).collect::<Vec<u32>>();
+        // The element past `count` must be left alone.
+        let buffer = create_buffer(&ctx, &[&data[..], &[1234]].concat());
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        prefix_sum.scan(&ctx.device, &mut encoder, &buffer, count);
+        let result = read_buffers(&ctx, encoder, &[&buffer]).await.remove(0);
+
+        let mut expected = data
+            .iter()
+            .scan(0, |sum, &x| {
+                let prefix = *sum;
+                *sum += x;
+                Some(prefix)
+            })
+            .collect::<Vec<_>>();
+        expected.push(1234);
+        assert_eq!(result, expected);
+    });
+
+#[gpu_test]
+static RADIX_SORT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        let radix_sort = RadixSort::new(&ctx.device);
+        let count = 1000;
+        // Plenty of duplicate keys, values record the original order to check stability.
+        let keys = (0..count)
+            .map(|i: u32| i.wrapping_mul(2_654_435_761) >> 20)
+            .collect::<Vec<_>>();
+        let values = (0..count).collect::<Vec<_>>();
+        let keys_buffer = create_buffer(&ctx, &keys);
+        let values_buffer = create_buffer(&ctx, &values);
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        // 12 bits take an odd number of passes, exercising the final copy.
+        radix_sort.sort(
+            &ctx.device,
+            &mut encoder,
+            &keys_buffer,
+            &values_buffer,
+            count,
+            12,
+        );
+        let result = read_buffers(&ctx, encoder, &[&keys_buffer, &values_buffer]).await;
+
+        let mut expected = keys.into_iter().zip(values).collect::<Vec<_>>();
+        expected.sort_by_key(|&(key, _)| key);
+        let (expected_keys, expected_values): (Vec<_>, Vec<_>) = expected.into_iter().unzip();
+        assert_eq!(result[0], expected_keys);
+        assert_eq!(result[1], expected_values);
+    });
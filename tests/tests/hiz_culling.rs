@@ -0,0 +1,163 @@
+//! Tests for `wgpu::util::OcclusionCuller`.
+
+use wgpu::util::{
+    DeviceExt, DrawIndexedIndirectArgs, InstanceBounds, OcclusionCullDescriptor, OcclusionCuller,
+};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+const SIZE: u32 = 64;
+const IDENTITY: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+#[gpu_test]
+static CULL_HIDDEN_AND_OUTSIDE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        let depth = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth32Float,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        // With an identity matrix, the bounds are directly in normalized device coordinates.
+        let bounds = [
+            // In front of the cleared depth.
+            InstanceBounds {
+                min: [-0.5, -0.5, 0.2],
+                max: [0.5, 0.5, 0.3],
+            },
+            // Behind the cleared depth.
+            InstanceBounds {
+                min: [-0.5, -0.5, 0.7],
+                max: [0.5, 0.5, 0.8],
+            },
+            // Outside of the view.
+            InstanceBounds {
+                min: [2.0, 2.0, 0.2],
+                max: [3.0, 3.0, 0.3],
+            },
+        ];
+        let draws = (0..bounds.len() as u32)
+            .map(|index| DrawIndexedIndirectArgs {
+                index_count: 36,
+                instance_count: 1,
+                first_index: 0,
+                base_vertex: 0,
+                first_instance: index,
+            })
+            .collect::<Vec<_>>();
+
+        let bounds_buffer = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: &bounds.iter().flat_map(|b| b.as_bytes()).collect::<Vec<_>>(),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+        let draws_buffer = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: &draws
+                    .iter()
+                    .flat_map(|d| d.as_bytes().iter().copied())
+                    .collect::<Vec<_>>(),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+        let draw_size = std::mem::size_of::<DrawIndexedIndirectArgs>() as u64;
+        let visible_draws = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: draw_size * bounds.len() as u64,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let visible_count = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::COPY_DST
+                | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4 + draw_size * bounds.len() as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let culler = OcclusionCuller::new(&ctx.device);
+        let pyramid = culler.create_pyramid(&ctx.device, SIZE, SIZE, false);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &depth.create_view(&wgpu::TextureViewDescriptor::default()),
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0.5),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+                resolve: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
+        });
+        culler.build_pyramid(&ctx.device, &mut encoder, &depth, &pyramid);
+        culler.cull(
+            &ctx.device,
+            &mut encoder,
+            &pyramid,
+            &OcclusionCullDescriptor {
+                view_proj: IDENTITY,
+                instance_count: bounds.len() as u32,
+                bounds: &bounds_buffer,
+                draws: &draws_buffer,
+                visible_draws: &visible_draws,
+                visible_count: &visible_count,
+            },
+        );
+        encoder.copy_buffer_to_buffer(&visible_count, 0, &read_buffer, 0, 4);
+        encoder.copy_buffer_to_buffer(
+            &visible_draws,
+            0,
+            &read_buffer,
+            4,
+            draw_size * bounds.len() as u64,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data = slice.get_mapped_range();
+        assert_eq!(data[..4], 1u32.to_le_bytes());
+        assert_eq!(&data[4..4 + draw_size as usize], draws[0].as_bytes());
+    });
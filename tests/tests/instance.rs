@@ -2,3 +2,43 @@ use wgpu_test::{gpu_test, GpuTestConfiguration};
 
 #[gpu_test]
 static INITIALIZE: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|_ctx| {});
+
+#[gpu_test]
+static DEBUG_CALLBACK: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        wgpu_test::TestParameters::default().skip(wgpu_test::FailureCase::backend(
+            wgpu::Backends::BROWSER_WEBGPU,
+        )),
+    )
+    .run_sync(|ctx| {
+        let messages = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        ctx.instance.set_debug_callback({
+            let messages = messages.clone();
+            move |message| messages.lock().unwrap().push(message.clone())
+        });
+
+        let _buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        ctx.instance.remove_debug_callback();
+        let count = messages.lock().unwrap().len();
+
+        // wgpu's own logs are reported as well.
+        assert!(messages
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|message| message.backend.is_none()
+                && message.message.contains("Device::create_buffer")));
+
+        let _buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        assert_eq!(messages.lock().unwrap().len(), count);
+    });
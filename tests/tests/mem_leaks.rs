@@ -97,6 +97,7 @@ async fn draw_test_with_reports(
                 buffers: &[],
                 entry_point: "vs_main_builtin",
                 module: &shader,
+                capture_buffers: &[],
             },
             primitive: wgpu::PrimitiveState::default(),
             depth_stencil: None,
@@ -111,6 +112,7 @@ async fn draw_test_with_reports(
                 })],
             }),
             multiview: None,
+            cache: None,
         });
 
     let global_report = ctx.instance.generate_report().unwrap();
@@ -188,6 +190,8 @@ async fn draw_test_with_reports(
         depth_stencil_attachment: None,
         timestamp_writes: None,
         occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: None,
     });
 
     rpass.set_pipeline(&pipeline);
@@ -0,0 +1,177 @@
+//! Tests for `Features::MEMORY_HEAPS`.
+
+use wgpu_test::{fail, gpu_test, valid, GpuTestConfiguration, TestParameters, TestingContext};
+
+const HEAP_SIZE: wgpu::BufferAddress = 1 << 22;
+
+const DATA: [u8; 256] = {
+    let mut data = [0; 256];
+    let mut i = 0;
+    while i < data.len() {
+        data[i] = i as u8;
+        i += 1;
+    }
+    data
+};
+
+fn create_heap(ctx: &TestingContext, memory_type: wgpu::MemoryHeapType) -> wgpu::MemoryHeap {
+    ctx.device.create_memory_heap(&wgpu::MemoryHeapDescriptor {
+        label: None,
+        size: HEAP_SIZE,
+        memory_type,
+    })
+}
+
+fn buffer_descriptor(usage: wgpu::BufferUsages) -> wgpu::BufferDescriptor<'static> {
+    wgpu::BufferDescriptor {
+        label: None,
+        size: DATA.len() as wgpu::BufferAddress,
+        usage,
+        mapped_at_creation: false,
+    }
+}
+
+fn texture_descriptor() -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 64,
+            height: 64,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    }
+}
+
+#[gpu_test]
+static MEMORY_HEAP_ROUND_TRIP: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::MEMORY_HEAPS))
+    .run_async(|ctx| async move {
+        let upload_heap = create_heap(&ctx, wgpu::MemoryHeapType::Upload);
+        let device_heap = create_heap(&ctx, wgpu::MemoryHeapType::DeviceLocal);
+        let readback_heap = create_heap(&ctx, wgpu::MemoryHeapType::Readback);
+
+        let upload = ctx.device.create_buffer_in_heap(
+            &wgpu::BufferDescriptor {
+                mapped_at_creation: true,
+                ..buffer_descriptor(wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC)
+            },
+            &upload_heap,
+            0,
+        );
+        upload
+            .slice(..)
+            .get_mapped_range_mut()
+            .copy_from_slice(&DATA);
+        upload.unmap();
+
+        // Two buffers sharing the start of the heap.
+        let usage = wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST;
+        let first = ctx
+            .device
+            .create_buffer_in_heap(&buffer_descriptor(usage), &device_heap, 0);
+        let second = ctx
+            .device
+            .create_buffer_in_heap(&buffer_descriptor(usage), &device_heap, 0);
+
+        let readback = ctx.device.create_buffer_in_heap(
+            &buffer_descriptor(wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST),
+            &readback_heap,
+            0,
+        );
+
+        // The heaps are kept alive by the buffers placed in them.
+        drop((upload_heap, device_heap, readback_heap));
+
+        let size = DATA.len() as wgpu::BufferAddress;
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&second, 0, &first, 0, size);
+        encoder.aliasing_barrier();
+        encoder.copy_buffer_to_buffer(&upload, 0, &first, 0, size);
+        encoder.copy_buffer_to_buffer(&first, 0, &readback, 0, size);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        assert_eq!(*slice.get_mapped_range(), DATA);
+    });
+
+#[gpu_test]
+static MEMORY_HEAP_VALIDATION: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::MEMORY_HEAPS))
+    .run_sync(|ctx| {
+        fail(&ctx.device, || {
+            ctx.device.create_memory_heap(&wgpu::MemoryHeapDescriptor {
+                label: None,
+                size: 0,
+                memory_type: wgpu::MemoryHeapType::DeviceLocal,
+            })
+        });
+
+        let device_heap = create_heap(&ctx, wgpu::MemoryHeapType::DeviceLocal);
+        let upload_heap = create_heap(&ctx, wgpu::MemoryHeapType::Upload);
+
+        let desc = buffer_descriptor(wgpu::BufferUsages::STORAGE);
+        let requirements = ctx.device.buffer_memory_requirements(&desc);
+        assert!(requirements.size >= desc.size);
+        assert!(requirements.alignment.is_power_of_two());
+
+        valid(&ctx.device, || {
+            ctx.device
+                .create_buffer_in_heap(&desc, &device_heap, requirements.alignment)
+        });
+        valid(&ctx.device, || {
+            let offset = HEAP_SIZE - requirements.size;
+            let offset = offset - offset % requirements.alignment;
+            ctx.device
+                .create_buffer_in_heap(&desc, &device_heap, offset)
+        });
+
+        // The buffer must be aligned and within the heap.
+        if requirements.alignment > 1 {
+            fail(&ctx.device, || {
+                ctx.device.create_buffer_in_heap(&desc, &device_heap, 1)
+            });
+        }
+        fail(&ctx.device, || {
+            ctx.device
+                .create_buffer_in_heap(&desc, &device_heap, HEAP_SIZE)
+        });
+
+        // Mappable buffers need a host visible heap, and vice versa.
+        fail(&ctx.device, || {
+            ctx.device.create_buffer_in_heap(
+                &buffer_descriptor(wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST),
+                &device_heap,
+                0,
+            )
+        });
+        fail(&ctx.device, || {
+            ctx.device.create_buffer_in_heap(&desc, &upload_heap, 0)
+        });
+
+        let texture_desc = texture_descriptor();
+        let requirements = ctx.device.texture_memory_requirements(&texture_desc);
+        assert!(requirements.size >= 64 * 64 * 4);
+
+        valid(&ctx.device, || {
+            ctx.device
+                .create_texture_in_heap(&texture_desc, &device_heap, 0)
+        });
+
+        // Textures can only be placed in device local heaps.
+        fail(&ctx.device, || {
+            ctx.device
+                .create_texture_in_heap(&texture_desc, &upload_heap, 0)
+        });
+    });
@@ -0,0 +1,263 @@
+//! Tests for task and mesh shader pipelines.
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+/// A task shader launching a single mesh workgroup, which outputs a triangle
+/// covering the whole render target, colored by the task payload.
+const SHADER: &str = r#"
+    struct Vertex {
+        @builtin(position) position: vec4<f32>,
+        @location(0) color: vec4<f32>,
+    }
+
+    struct Primitive {
+        @builtin(triangle_indices) indices: vec3<u32>,
+    }
+
+    struct MeshOutput {
+        @builtin(vertices) vertices: array<Vertex, 3>,
+        @builtin(primitives) primitives: array<Primitive, 1>,
+        @builtin(vertex_count) vertex_count: u32,
+        @builtin(primitive_count) primitive_count: u32,
+    }
+
+    var<task_payload> color: vec4<f32>;
+    var<workgroup> mesh_output: MeshOutput;
+
+    @task @payload(color) @workgroup_size(1)
+    fn ts() -> @builtin(mesh_task_size) vec3<u32> {
+        color = vec4(0.0, 1.0, 0.0, 1.0);
+        return vec3(1u, 1u, 1u);
+    }
+
+    @mesh(mesh_output) @payload(color) @workgroup_size(1)
+    fn ms() {
+        mesh_output.vertex_count = 3u;
+        mesh_output.primitive_count = 1u;
+        mesh_output.vertices[0].position = vec4(-1.0, -1.0, 0.0, 1.0);
+        mesh_output.vertices[1].position = vec4(3.0, -1.0, 0.0, 1.0);
+        mesh_output.vertices[2].position = vec4(-1.0, 3.0, 0.0, 1.0);
+        for (var i = 0u; i < 3u; i++) {
+            mesh_output.vertices[i].color = color;
+        }
+        mesh_output.primitives[0].indices = vec3(0u, 1u, 2u);
+    }
+
+    @fragment
+    fn fs(vertex: Vertex) -> @location(0) vec4<f32> {
+        return vertex.color;
+    }
+"#;
+
+fn create_pipeline(ctx: &TestingContext, use_task: bool) -> wgpu::RenderPipeline {
+    let module = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    ctx.device
+        .create_mesh_pipeline(&wgpu::MeshPipelineDescriptor {
+            label: Some("pipeline"),
+            layout: None,
+            task: use_task.then_some(wgpu::TaskState {
+                module: &module,
+                entry_point: "ts",
+            }),
+            mesh: wgpu::MeshState {
+                module: &module,
+                entry_point: "ms",
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &module,
+                entry_point: "fs",
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+            cache: None,
+        })
+}
+
+/// Draws the triangle of [`SHADER`] into a single pixel render target and
+/// checks that it's colored by the task shader.
+#[gpu_test]
+static DRAW_MESH_TASKS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXPERIMENTAL_MESH_SHADER))
+    .run_async(draw_mesh_tasks);
+
+async fn draw_mesh_tasks(ctx: TestingContext) {
+    let pipeline = create_pipeline(&ctx, true);
+
+    let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("target"),
+        size: wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let cpu_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("cpu_buffer"),
+        size: 4,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("encoder"),
+        });
+    {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("render_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
+        });
+        rpass.set_pipeline(&pipeline);
+        rpass.draw_mesh_tasks(1, 1, 1);
+    }
+    encoder.copy_texture_to_buffer(
+        wgpu::ImageCopyTexture {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::ImageCopyBuffer {
+            buffer: &cpu_buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: None,
+                rows_per_image: None,
+            },
+        },
+        wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+    );
+    ctx.queue.submit([encoder.finish()]);
+
+    cpu_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+
+    let data = cpu_buffer.slice(..).get_mapped_range();
+    assert_eq!(&data[..], &[0, 255, 0, 255]);
+}
+
+#[gpu_test]
+static MESH_PIPELINE_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_sync(|ctx| {
+        // Without the feature, the shader module itself fails validation.
+        fail(&ctx.device, || create_pipeline(&ctx, false));
+    });
+
+const VERTEX_SHADER: &str = r#"
+    @vertex
+    fn vs() -> @builtin(position) vec4<f32> {
+        return vec4(0.0);
+    }
+
+    @fragment
+    fn fs() -> @location(0) vec4<f32> {
+        return vec4(1.0);
+    }
+"#;
+
+/// Mesh tasks can only be drawn with a mesh pipeline.
+#[gpu_test]
+static DRAW_MESH_TASKS_WITH_VERTEX_PIPELINE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXPERIMENTAL_MESH_SHADER))
+    .run_sync(|ctx| {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("vertex_shader"),
+                source: wgpu::ShaderSource::Wgsl(VERTEX_SHADER.into()),
+            });
+        let pipeline = ctx
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("vertex_pipeline"),
+                layout: None,
+                vertex: wgpu::VertexState {
+                    module: &module,
+                    entry_point: "vs",
+                    buffers: &[],
+                    capture_buffers: &[],
+                },
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    module: &module,
+                    entry_point: "fs",
+                    targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                }),
+                multiview: None,
+                cache: None,
+            });
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("target"),
+            size: wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
+            });
+            rpass.set_pipeline(&pipeline);
+            rpass.draw_mesh_tasks(1, 1, 1);
+        }
+        fail(&ctx.device, || encoder.finish());
+    });
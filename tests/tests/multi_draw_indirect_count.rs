@@ -0,0 +1,231 @@
+//! Tests for `RenderPass::multi_draw_indirect_count`.
+
+use wgpu::util::{BufferInitDescriptor, DeviceExt, DrawIndirectArgs};
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+/// Each group of 3 vertices is a triangle covering a single pixel of a 4x1
+/// render target, from left to right.
+const SHADER: &str = r#"
+    @vertex
+    fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+        let left = -1.0 + 0.5 * f32(index / 3u);
+        switch index % 3u {
+            case 0u: { return vec4f(left, -3.0, 0.0, 1.0); }
+            case 1u: { return vec4f(left, 3.0, 0.0, 1.0); }
+            default: { return vec4f(left + 0.5, 0.0, 0.0, 1.0); }
+        }
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+"#;
+
+const WIDTH: u32 = 4;
+
+fn parameters() -> TestParameters {
+    TestParameters::default().features(wgpu::Features::MULTI_DRAW_INDIRECT_COUNT)
+}
+
+fn create_target(ctx: &TestingContext) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: WIDTH,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+fn create_pipeline(ctx: &TestingContext) -> wgpu::RenderPipeline {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+                capture_buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+            cache: None,
+        })
+}
+
+fn begin_render_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: None,
+    })
+}
+
+/// Draws with a count above `max_count`, then with one below it, and checks
+/// which pixels were drawn to.
+#[gpu_test]
+static MULTI_DRAW_INDIRECT_COUNT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_async(|ctx| async move {
+        let target = create_target(&ctx);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_pipeline(&ctx);
+
+        let mut commands = Vec::new();
+        for pixel in 0..WIDTH {
+            let args = DrawIndirectArgs {
+                vertex_count: 3,
+                instance_count: 1,
+                first_vertex: pixel * 3,
+                first_instance: 0,
+            };
+            commands.extend_from_slice(args.as_bytes());
+        }
+        let indirect_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: &commands,
+            usage: wgpu::BufferUsages::INDIRECT,
+        });
+        // The first count is clamped to `max_count`, the second isn't.
+        let count_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&[WIDTH, 1]),
+            usage: wgpu::BufferUsages::INDIRECT,
+        });
+
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = begin_render_pass(&mut encoder, &view);
+            pass.set_pipeline(&pipeline);
+            // Draws to pixels 0 and 1.
+            pass.multi_draw_indirect_count(&indirect_buffer, 0, &count_buffer, 0, 2);
+            // Draws to pixel 3 only.
+            pass.multi_draw_indirect_count(&indirect_buffer, 3 * 16, &count_buffer, 4, 1);
+        }
+        encoder.copy_texture_to_buffer(
+            target.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &read_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: None,
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width: WIDTH,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data = slice.get_mapped_range();
+        let drawn: Vec<bool> = data[..WIDTH as usize * 4]
+            .chunks(4)
+            .map(|pixel| pixel == [255; 4])
+            .collect();
+        assert_eq!(drawn, [true, true, false, true]);
+    });
+
+#[gpu_test]
+static MULTI_DRAW_INDIRECT_COUNT_INVALID: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        let target = create_target(&ctx);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_pipeline(&ctx);
+        let indirect_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 64,
+            usage: wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        });
+        let count_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 8,
+            usage: wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        });
+
+        // (indirect offset, count offset, max count)
+        let invalid_draws = [
+            // The indirect buffer only holds 4 draws.
+            (0, 0, 5),
+            (16, 0, 4),
+            // The count is read past the end of the count buffer.
+            (0, 8, 1),
+            // Unaligned offsets.
+            (2, 0, 1),
+            (0, 2, 1),
+        ];
+        for (indirect_offset, count_offset, max_count) in invalid_draws {
+            fail(&ctx.device, || {
+                let mut encoder = ctx
+                    .device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+                {
+                    let mut pass = begin_render_pass(&mut encoder, &view);
+                    pass.set_pipeline(&pipeline);
+                    pass.multi_draw_indirect_count(
+                        &indirect_buffer,
+                        indirect_offset,
+                        &count_buffer,
+                        count_offset,
+                        max_count,
+                    );
+                }
+                encoder.finish()
+            });
+        }
+    });
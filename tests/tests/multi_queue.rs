@@ -0,0 +1,135 @@
+//! Tests for `DeviceDescriptor::additional_queues`.
+
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestingContext};
+
+const SIZE: wgpu::BufferAddress = 256;
+
+async fn request_device_with_queues(
+    ctx: &TestingContext,
+    additional_queues: u32,
+) -> (wgpu::Device, Vec<wgpu::Queue>) {
+    ctx.adapter
+        .request_device_with_queues(
+            &wgpu::DeviceDescriptor {
+                required_features: ctx.device.features(),
+                required_limits: ctx.device.limits(),
+                additional_queues,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap()
+}
+
+fn create_buffer(device: &wgpu::Device, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: SIZE,
+        usage,
+        mapped_at_creation: false,
+    })
+}
+
+fn copy(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    src: &wgpu::Buffer,
+    dst: &wgpu::Buffer,
+) -> wgpu::SubmissionIndex {
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.copy_buffer_to_buffer(src, 0, dst, 0, SIZE);
+    queue.submit(Some(encoder.finish()))
+}
+
+fn check_contents(device: &wgpu::Device, buffer: &wgpu::Buffer, expected: u8) {
+    buffer
+        .slice(..)
+        .map_async(wgpu::MapMode::Read, Result::unwrap);
+    device.poll(wgpu::Maintain::wait()).panic_on_timeout();
+
+    let view = buffer.slice(..).get_mapped_range();
+    assert!(view.iter().all(|&byte| byte == expected));
+}
+
+#[gpu_test]
+static MULTI_QUEUE_SHARED_BUFFER: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        if ctx.adapter.max_additional_queues() == 0 {
+            return;
+        }
+        let (device, queues) = request_device_with_queues(&ctx, 1).await;
+        assert_eq!(queues.len(), 2);
+
+        let buffer = create_buffer(
+            &device,
+            wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        );
+        let read_buffer = create_buffer(
+            &device,
+            wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        );
+
+        // The write goes to the first queue, the copy reading it to the second one.
+        queues[0].write_buffer(&buffer, 0, &[7; SIZE as usize]);
+        queues[0].submit(None);
+        copy(&device, &queues[1], &buffer, &read_buffer);
+
+        check_contents(&device, &read_buffer, 7);
+    });
+
+#[gpu_test]
+static MULTI_QUEUE_WAIT_FOR_SUBMISSION: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        if ctx.adapter.max_additional_queues() == 0 {
+            return;
+        }
+        let (device, queues) = request_device_with_queues(&ctx, 1).await;
+
+        let source = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: SIZE,
+            usage: wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: true,
+        });
+        source.slice(..).get_mapped_range_mut().fill(3);
+        source.unmap();
+        let buffer = create_buffer(
+            &device,
+            wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        );
+        let read_buffer = create_buffer(
+            &device,
+            wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        );
+
+        let index = copy(&device, &queues[1], &source, &buffer);
+        queues[0].wait_for_submission(&index);
+        copy(&device, &queues[0], &buffer, &read_buffer);
+
+        check_contents(&device, &read_buffer, 3);
+    });
+
+#[gpu_test]
+static MULTI_QUEUE_TOO_MANY: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        // Additional queues can't be requested at all on the WebGPU backend.
+        if ctx.adapter_info.backend == wgpu::Backend::BrowserWebGpu {
+            return;
+        }
+        let error = ctx
+            .adapter
+            .request_device_with_queues(
+                &wgpu::DeviceDescriptor {
+                    additional_queues: ctx.adapter.max_additional_queues() + 1,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Too many additional queues were requested"));
+    });
@@ -1,130 +1,239 @@
 use std::borrow::Cow;
-use wgpu_test::{gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
+use wgpu_test::{
+    fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext,
+};
 
 #[gpu_test]
 static OCCLUSION_QUERY: GpuTestConfiguration = GpuTestConfiguration::new()
     .parameters(TestParameters::default().expect_fail(FailureCase::webgl2()))
-    .run_async(|ctx| async move {
-        // Create depth texture
-        let depth_texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Depth texture"),
-            size: wgpu::Extent3d {
-                width: 64,
-                height: 64,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Depth32Float,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            view_formats: &[],
-        });
-        let depth_texture_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
-
-        // Setup pipeline using a simple shader with hardcoded vertices
-        let shader = ctx
-            .device
-            .create_shader_module(wgpu::ShaderModuleDescriptor {
-                label: Some("Shader module"),
-                source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("shader.wgsl"))),
-            });
-        let pipeline = ctx
-            .device
-            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                label: Some("Pipeline"),
-                layout: None,
-                vertex: wgpu::VertexState {
-                    module: &shader,
-                    entry_point: "vs_main",
-                    buffers: &[],
-                },
-                fragment: None,
-                primitive: wgpu::PrimitiveState::default(),
-                depth_stencil: Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth32Float,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Less,
-                    stencil: wgpu::StencilState::default(),
-                    bias: wgpu::DepthBiasState::default(),
-                }),
-                multisample: wgpu::MultisampleState::default(),
-                multiview: None,
+    .run_async(|ctx| occlusion_query_test(ctx, false));
+
+/// The same queries as [`OCCLUSION_QUERY`], recorded in a render bundle that writes
+/// them to the occlusion query set of the executing pass.
+#[gpu_test]
+static OCCLUSION_QUERY_IN_RENDER_BUNDLE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::RENDER_BUNDLE_OCCLUSION_QUERY)
+            .expect_fail(FailureCase::webgl2()),
+    )
+    .run_async(|ctx| occlusion_query_test(ctx, true));
+
+/// Render bundles can only begin occlusion queries with
+/// `Features::RENDER_BUNDLE_OCCLUSION_QUERY`.
+#[gpu_test]
+static OCCLUSION_QUERY_IN_RENDER_BUNDLE_WITHOUT_FEATURE: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(
+            TestParameters::default()
+                // The WebGPU backend panics instead of reporting an error.
+                .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+        )
+        .run_sync(|ctx| {
+            let mut bundle_encoder =
+                ctx.device
+                    .create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {
+                        label: None,
+                        color_formats: &[],
+                        depth_stencil: Some(wgpu::RenderBundleDepthStencil {
+                            format: wgpu::TextureFormat::Depth32Float,
+                            depth_read_only: false,
+                            stencil_read_only: true,
+                        }),
+                        sample_count: 1,
+                        multiview: None,
+                    });
+            bundle_encoder.begin_occlusion_query(0);
+            bundle_encoder.end_occlusion_query();
+            fail(&ctx.device, || {
+                bundle_encoder.finish(&wgpu::RenderBundleDescriptor::default())
             });
+        });
+
+async fn occlusion_query_test(ctx: TestingContext, in_render_bundle: bool) {
+    // Create depth texture
+    let depth_texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Depth texture"),
+        size: wgpu::Extent3d {
+            width: 64,
+            height: 64,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Depth32Float,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let depth_texture_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-        // Create occlusion query set
-        let query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
-            label: Some("Query set"),
-            ty: wgpu::QueryType::Occlusion,
-            count: 3,
+    // Setup pipeline using a simple shader with hardcoded vertices
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader module"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("shader.wgsl"))),
+        });
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+                capture_buffers: &[],
+            },
+            fragment: None,
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
         });
 
-        let mut encoder = ctx
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render pass"),
-                color_attachments: &[],
-                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
-                    view: &depth_texture_view,
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(1.0),
-                        store: wgpu::StoreOp::Store,
+    // Create occlusion query set
+    let query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+        label: Some("Query set"),
+        ty: wgpu::QueryType::Occlusion,
+        count: 3,
+    });
+
+    let render_bundle = in_render_bundle.then(|| {
+        let mut bundle_encoder =
+            ctx.device
+                .create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {
+                    label: Some("Render bundle encoder"),
+                    color_formats: &[],
+                    depth_stencil: Some(wgpu::RenderBundleDepthStencil {
+                        format: wgpu::TextureFormat::Depth32Float,
+                        depth_read_only: false,
+                        stencil_read_only: true,
                     }),
-                    stencil_ops: None,
+                    sample_count: 1,
+                    multiview: None,
+                });
+        bundle_encoder.set_pipeline(&pipeline);
+        record_queries(&mut bundle_encoder);
+        bundle_encoder.finish(&wgpu::RenderBundleDescriptor::default())
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Render pass"),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &depth_texture_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
                 }),
-                timestamp_writes: None,
-                occlusion_query_set: Some(&query_set),
-            });
-            render_pass.set_pipeline(&pipeline);
-
-            // Not occluded (z = 1.0, nothing drawn yet)
-            render_pass.begin_occlusion_query(0);
-            render_pass.draw(4..7, 0..1);
-            render_pass.end_occlusion_query();
-
-            // Not occluded (z = 0.0)
-            render_pass.begin_occlusion_query(1);
-            render_pass.draw(0..3, 0..1);
-            render_pass.end_occlusion_query();
-
-            // Occluded (z = 1.0)
-            render_pass.begin_occlusion_query(2);
-            render_pass.draw(4..7, 0..1);
-            render_pass.end_occlusion_query();
+                stencil_ops: None,
+                resolve: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: Some(&query_set),
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
+        });
+        match render_bundle {
+            Some(ref render_bundle) => render_pass.execute_bundles(Some(render_bundle)),
+            None => {
+                render_pass.set_pipeline(&pipeline);
+                record_queries(&mut render_pass);
+            }
         }
+    }
 
-        // Resolve query set to buffer
-        let query_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Query buffer"),
-            size: std::mem::size_of::<u64>() as u64 * 3,
-            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
-            mapped_at_creation: false,
-        });
-        encoder.resolve_query_set(&query_set, 0..3, &query_buffer, 0);
+    // Resolve query set to buffer
+    let query_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Query buffer"),
+        size: std::mem::size_of::<u64>() as u64 * 3,
+        usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    encoder.resolve_query_set(&query_set, 0..3, &query_buffer, 0);
 
-        let mapping_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Mapping buffer"),
-            size: query_buffer.size(),
-            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-        encoder.copy_buffer_to_buffer(&query_buffer, 0, &mapping_buffer, 0, query_buffer.size());
-
-        ctx.queue.submit(Some(encoder.finish()));
-
-        mapping_buffer
-            .slice(..)
-            .map_async(wgpu::MapMode::Read, |_| ());
-        ctx.async_poll(wgpu::Maintain::wait())
-            .await
-            .panic_on_timeout();
-        let query_buffer_view = mapping_buffer.slice(..).get_mapped_range();
-        let query_data: &[u64; 3] = bytemuck::from_bytes(&query_buffer_view);
-
-        // WebGPU only defines query results as zero/non-zero
-        assert_ne!(query_data[0], 0);
-        assert_ne!(query_data[1], 0);
-        assert_eq!(query_data[2], 0);
+    let mapping_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Mapping buffer"),
+        size: query_buffer.size(),
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
     });
+    encoder.copy_buffer_to_buffer(&query_buffer, 0, &mapping_buffer, 0, query_buffer.size());
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    mapping_buffer
+        .slice(..)
+        .map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+    let query_buffer_view = mapping_buffer.slice(..).get_mapped_range();
+    let query_data: &[u64; 3] = bytemuck::from_bytes(&query_buffer_view);
+
+    // WebGPU only defines query results as zero/non-zero
+    assert_ne!(query_data[0], 0);
+    assert_ne!(query_data[1], 0);
+    assert_eq!(query_data[2], 0);
+}
+
+/// Abstracts over the two kinds of encoders the queries can be recorded in.
+trait QueryEncoder {
+    fn draw(&mut self, vertices: std::ops::Range<u32>);
+    fn begin_occlusion_query(&mut self, query_index: u32);
+    fn end_occlusion_query(&mut self);
+}
+
+impl QueryEncoder for wgpu::RenderPass<'_> {
+    fn draw(&mut self, vertices: std::ops::Range<u32>) {
+        wgpu::RenderPass::draw(self, vertices, 0..1);
+    }
+    fn begin_occlusion_query(&mut self, query_index: u32) {
+        wgpu::RenderPass::begin_occlusion_query(self, query_index);
+    }
+    fn end_occlusion_query(&mut self) {
+        wgpu::RenderPass::end_occlusion_query(self);
+    }
+}
+
+impl QueryEncoder for wgpu::RenderBundleEncoder<'_> {
+    fn draw(&mut self, vertices: std::ops::Range<u32>) {
+        wgpu::RenderBundleEncoder::draw(self, vertices, 0..1);
+    }
+    fn begin_occlusion_query(&mut self, query_index: u32) {
+        wgpu::RenderBundleEncoder::begin_occlusion_query(self, query_index);
+    }
+    fn end_occlusion_query(&mut self) {
+        wgpu::RenderBundleEncoder::end_occlusion_query(self);
+    }
+}
+
+fn record_queries(encoder: &mut impl QueryEncoder) {
+    // Not occluded (z = 1.0, nothing drawn yet)
+    encoder.begin_occlusion_query(0);
+    encoder.draw(4..7);
+    encoder.end_occlusion_query();
+
+    // Not occluded (z = 0.0)
+    encoder.begin_occlusion_query(1);
+    encoder.draw(0..3);
+    encoder.end_occlusion_query();
+
+    // Occluded (z = 1.0)
+    encoder.begin_occlusion_query(2);
+    encoder.draw(4..7);
+    encoder.end_occlusion_query();
+}
@@ -0,0 +1,107 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use wgpu_test::{gpu_test, image, GpuTestConfiguration};
+
+const TEXTURE_SIZE: u32 = 2;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// Records the two slices of a pass on separate threads and checks that they
+/// are executed in slice order: the second slice draws over the first.
+#[gpu_test]
+static PARALLEL_RENDER_PASS_SLICE_ORDER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Offscreen texture"),
+            size: wgpu::Extent3d {
+                width: TEXTURE_SIZE,
+                height: TEXTURE_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FORMAT,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let shader = ctx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("solid_colors.wgsl"));
+
+        let create_pipeline = |fs_entry_point| {
+            ctx.device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some(fs_entry_point),
+                    layout: None,
+                    vertex: wgpu::VertexState {
+                        entry_point: "vs_main",
+                        module: &shader,
+                        buffers: &[],
+                        capture_buffers: &[],
+                    },
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    fragment: Some(wgpu::FragmentState {
+                        entry_point: fs_entry_point,
+                        module: &shader,
+                        targets: &[Some(FORMAT.into())],
+                    }),
+                    multiview: None,
+                    cache: None,
+                })
+        };
+        let pipelines = [create_pipeline("fs_white"), create_pipeline("fs_red")];
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass_parallel(
+                &ctx.device,
+                &wgpu::RenderPassDescriptor {
+                    label: Some("Parallel render pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &texture_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                    pipeline_statistics_query: None,
+                    shading_rate_attachment: None,
+                },
+                &wgpu::RenderBundleEncoderDescriptor {
+                    label: None,
+                    color_formats: &[Some(FORMAT)],
+                    depth_stencil: None,
+                    sample_count: 1,
+                    multiview: None,
+                },
+                pipelines.len(),
+            );
+            std::thread::scope(|scope| {
+                for (slice, pipeline) in pass.slices_mut().iter_mut().zip(&pipelines) {
+                    scope.spawn(move || {
+                        slice.set_pipeline(pipeline);
+                        slice.draw(0..3, 0..1);
+                    });
+                }
+            });
+            pass.finish();
+        }
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &texture);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let expected = [255, 0, 0, 255].repeat((TEXTURE_SIZE * TEXTURE_SIZE) as usize);
+        readback_buffer
+            .assert_buffer_contents(&ctx, &expected)
+            .await;
+    });
@@ -1,6 +1,6 @@
 use std::{borrow::Cow, num::NonZeroU32};
 
-use wgpu_test::{gpu_test, image::ReadbackBuffers, GpuTestConfiguration, TestParameters};
+use wgpu_test::{fail, gpu_test, image::ReadbackBuffers, GpuTestConfiguration, TestParameters};
 
 #[gpu_test]
 static PARTIALLY_BOUNDED_ARRAY: GpuTestConfiguration = GpuTestConfiguration::new()
@@ -12,7 +12,10 @@ static PARTIALLY_BOUNDED_ARRAY: GpuTestConfiguration = GpuTestConfiguration::new
                     | wgpu::Features::PARTIALLY_BOUND_BINDING_ARRAY
                     | wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
             )
-            .limits(wgpu::Limits::downlevel_defaults()),
+            .limits(wgpu::Limits {
+                max_binding_array_elements_per_shader_stage: 4,
+                ..wgpu::Limits::downlevel_defaults()
+            }),
     )
     .run_async(|ctx| async move {
         let device = &ctx.device;
@@ -69,6 +72,7 @@ static PARTIALLY_BOUNDED_ARRAY: GpuTestConfiguration = GpuTestConfiguration::new
             layout: Some(&pipeline_layout),
             module: &cs_module,
             entry_point: "main",
+            cache: None,
         });
 
         let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -101,3 +105,77 @@ static PARTIALLY_BOUNDED_ARRAY: GpuTestConfiguration = GpuTestConfiguration::new
             .assert_buffer_contents(&ctx, bytemuck::bytes_of(&[4.0f32, 3.0, 2.0, 1.0]))
             .await;
     });
+
+const LARGE_ARRAY_LENGTH: u32 = 100_000;
+
+fn large_array_layout_entry(count: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: NonZeroU32::new(count),
+    }
+}
+
+/// Update-after-bind binding arrays can be much larger than the sampled texture limit,
+/// and only the beginning of them needs to be bound.
+#[gpu_test]
+static LARGE_PARTIALLY_BOUND_ARRAY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(
+                wgpu::Features::TEXTURE_BINDING_ARRAY
+                    | wgpu::Features::PARTIALLY_BOUND_BINDING_ARRAY,
+            )
+            .limits(wgpu::Limits {
+                max_binding_array_elements_per_shader_stage: LARGE_ARRAY_LENGTH,
+                ..wgpu::Limits::downlevel_defaults()
+            }),
+    )
+    .run_sync(|ctx| {
+        let device = &ctx.device;
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bind group layout"),
+            entries: &[large_array_layout_entry(LARGE_ARRAY_LENGTH)],
+        });
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("bind group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureViewArray(&[&texture_view, &texture_view]),
+            }],
+        });
+
+        // The binding array limit is still enforced.
+        fail(device, || {
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("too large bind group layout"),
+                entries: &[large_array_layout_entry(
+                    device.limits().max_binding_array_elements_per_shader_stage + 1,
+                )],
+            })
+        });
+    });
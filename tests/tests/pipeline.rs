@@ -28,8 +28,188 @@ static PIPELINE_DEFAULT_LAYOUT_BAD_MODULE: GpuTestConfiguration = GpuTestConfigu
                     layout: None,
                     module: &module,
                     entry_point: "doesn't exist",
+                    cache: None,
                 });
 
             pipeline.get_bind_group_layout(0);
         });
     });
+
+const ASYNC_COMPUTE_SHADER: &str = "
+@group(0) @binding(0)
+var<storage, read_write> output: u32;
+
+@compute @workgroup_size(1)
+fn main() {
+    output = 42u;
+}
+";
+
+#[gpu_test]
+static PIPELINE_ASYNC_COMPUTE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(ASYNC_COMPUTE_SHADER.into()),
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline_async(&wgpu::ComputePipelineDescriptor {
+                label: Some("async compute pipeline"),
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                cache: None,
+            })
+            .await;
+
+        let storage = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: storage.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(1, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&storage, 0, &readback, 0, 4);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        readback.slice(..).map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data = readback.slice(..).get_mapped_range();
+        assert_eq!(u32::from_ne_bytes(data[..].try_into().unwrap()), 42);
+    });
+
+const ASYNC_RENDER_SHADER: &str = "
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(f32(index), 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+";
+
+#[gpu_test]
+static PIPELINE_ASYNC_RENDER: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(ASYNC_RENDER_SHADER.into()),
+            });
+        let desc = wgpu::RenderPipelineDescriptor {
+            label: Some("async render pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &module,
+                entry_point: "vs_main",
+                buffers: &[],
+                capture_buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &module,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+            cache: None,
+        };
+
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        // Dropping the future before it resolves cancels the compilation.
+        drop(ctx.device.create_render_pipeline_async(&desc));
+        let _pipeline = ctx.device.create_render_pipeline_async(&desc).await;
+        assert!(ctx.device.pop_error_scope().await.is_none());
+    });
+
+#[gpu_test]
+static PIPELINE_ASYNC_INVALID_ENTRY_POINT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS))
+    .run_async(|ctx| async move {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(ASYNC_COMPUTE_SHADER.into()),
+            });
+
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let _pipeline = ctx
+            .device
+            .create_compute_pipeline_async(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "doesn't exist",
+                cache: None,
+            })
+            .await;
+        assert!(ctx.device.pop_error_scope().await.is_some());
+    });
+
+#[gpu_test]
+static PIPELINE_ASYNC_ERROR_SCOPE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS))
+    .run_async(|ctx| async move {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(ASYNC_COMPUTE_SHADER.into()),
+            });
+
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let future = ctx
+            .device
+            .create_compute_pipeline_async(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "doesn't exist",
+                cache: None,
+            });
+        // The error belongs to the scope that was open when the future was created, even if
+        // another one is pushed before the compilation finishes.
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let _pipeline = future.await;
+        assert!(ctx.device.pop_error_scope().await.is_none());
+        assert!(ctx.device.pop_error_scope().await.is_some());
+    });
@@ -0,0 +1,102 @@
+use wgpu_test::{fail, gpu_test, valid, GpuTestConfiguration, TestParameters, TestingContext};
+
+const SHADER: &str = r#"
+    @group(0) @binding(0)
+    var<storage, read_write> output: array<u32>;
+
+    @compute @workgroup_size(1)
+    fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+        output[id.x] = id.x * 2u;
+    }
+"#;
+
+fn create_pipeline(ctx: &TestingContext, cache: Option<&wgpu::PipelineCache>) {
+    let module = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    ctx.device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("pipeline cache test"),
+            layout: None,
+            module: &module,
+            entry_point: "main",
+            cache,
+        });
+}
+
+#[gpu_test]
+static PIPELINE_CACHE_ROUND_TRIP: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::PIPELINE_CACHE))
+    .run_sync(|ctx| {
+        let cache = valid(&ctx.device, || unsafe {
+            ctx.device
+                .create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+                    label: Some("empty cache"),
+                    data: None,
+                    fallback: false,
+                })
+        });
+        valid(&ctx.device, || create_pipeline(&ctx, Some(&cache)));
+
+        let Some(data) = cache.get_data() else {
+            return;
+        };
+        let cache = valid(&ctx.device, || unsafe {
+            ctx.device
+                .create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+                    label: Some("loaded cache"),
+                    data: Some(&data),
+                    fallback: false,
+                })
+        });
+        valid(&ctx.device, || create_pipeline(&ctx, Some(&cache)));
+    });
+
+#[gpu_test]
+static PIPELINE_CACHE_FALLBACK: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::PIPELINE_CACHE))
+    .run_sync(|ctx| {
+        let data = [0xde, 0xad, 0xbe, 0xef];
+        let cache = valid(&ctx.device, || unsafe {
+            ctx.device
+                .create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+                    label: None,
+                    data: Some(&data),
+                    fallback: true,
+                })
+        });
+        valid(&ctx.device, || create_pipeline(&ctx, Some(&cache)));
+    });
+
+#[gpu_test]
+static PIPELINE_CACHE_REQUIRES_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_sync(|ctx| {
+        let cache = fail(&ctx.device, || unsafe {
+            ctx.device
+                .create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+                    label: None,
+                    data: None,
+                    fallback: false,
+                })
+        });
+        fail(&ctx.device, || create_pipeline(&ctx, Some(&cache)));
+    });
+
+#[gpu_test]
+static PIPELINE_CACHE_INVALID_DATA: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::PIPELINE_CACHE))
+    .run_sync(|ctx| {
+        let data = [0xde, 0xad, 0xbe, 0xef];
+        fail(&ctx.device, || unsafe {
+            ctx.device
+                .create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+                    label: None,
+                    data: Some(&data),
+                    fallback: false,
+                })
+        });
+    });
@@ -0,0 +1,225 @@
+//! Tests for pipeline statistics and occlusion queries in render passes.
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const SHADER: &str = r#"
+    @vertex
+    fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+        let uv = vec2f(f32(index & 1u), f32(index >> 1u)) * 4.0 - 1.0;
+        return vec4f(uv, 0.0, 1.0);
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+"#;
+
+fn parameters() -> TestParameters {
+    TestParameters::default().features(wgpu::Features::PIPELINE_STATISTICS_QUERY)
+}
+
+fn create_pipeline(ctx: &TestingContext) -> wgpu::RenderPipeline {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+                capture_buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+            cache: None,
+        })
+}
+
+fn create_target_view(ctx: &TestingContext) -> wgpu::TextureView {
+    ctx.device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn color_attachment(view: &wgpu::TextureView) -> Option<wgpu::RenderPassColorAttachment<'_>> {
+    Some(wgpu::RenderPassColorAttachment {
+        view,
+        resolve_target: None,
+        ops: wgpu::Operations::default(),
+    })
+}
+
+/// Writes the statistics of a whole pass, while occlusion queries are used
+/// within it.
+#[gpu_test]
+static PASS_PIPELINE_STATISTICS_QUERY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_async(|ctx| async move {
+        let view = create_target_view(&ctx);
+        let pipeline = create_pipeline(&ctx);
+        let statistics_query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: None,
+            ty: wgpu::QueryType::PipelineStatistics(
+                wgpu::PipelineStatisticsTypes::VERTEX_SHADER_INVOCATIONS,
+            ),
+            count: 1,
+        });
+        let occlusion_query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: None,
+            ty: wgpu::QueryType::Occlusion,
+            count: 1,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[color_attachment(&view)],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: Some(&occlusion_query_set),
+                pipeline_statistics_query: Some(wgpu::RenderPassPipelineStatisticsQuery {
+                    query_set: &statistics_query_set,
+                    query_index: 0,
+                }),
+                shading_rate_attachment: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.begin_occlusion_query(0);
+            pass.draw(0..3, 0..1);
+            pass.end_occlusion_query();
+        }
+
+        // Each result is resolved to its own aligned offset.
+        let occlusion_offset = wgpu::QUERY_RESOLVE_BUFFER_ALIGNMENT;
+        let size = occlusion_offset + 8;
+        let query_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let mapping_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        encoder.resolve_query_set(&statistics_query_set, 0..1, &query_buffer, 0);
+        encoder.resolve_query_set(&occlusion_query_set, 0..1, &query_buffer, occlusion_offset);
+        encoder.copy_buffer_to_buffer(&query_buffer, 0, &mapping_buffer, 0, size);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        mapping_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let view = mapping_buffer.slice(..).get_mapped_range();
+        let statistics: u64 = bytemuck::pod_read_unaligned(&view[..8]);
+        let occlusion: u64 = bytemuck::pod_read_unaligned(&view[occlusion_offset as usize..]);
+
+        assert_ne!(statistics, 0, "no vertex shader invocations were counted");
+        assert_ne!(occlusion, 0, "the triangle was occluded");
+    });
+
+/// Queries begun in a render pass must be ended in it, and a pass wide
+/// statistics query can't be mixed with ones begun within the pass.
+#[gpu_test]
+static RENDER_PASS_QUERY_SCOPES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        let view = create_target_view(&ctx);
+        let statistics_query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: None,
+            ty: wgpu::QueryType::PipelineStatistics(
+                wgpu::PipelineStatisticsTypes::VERTEX_SHADER_INVOCATIONS,
+            ),
+            count: 2,
+        });
+        let occlusion_query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: None,
+            ty: wgpu::QueryType::Occlusion,
+            count: 1,
+        });
+
+        let run_pass = |pass_statistics: bool, commands: &dyn Fn(&mut wgpu::RenderPass<'_>)| {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: None,
+                    color_attachments: &[color_attachment(&view)],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: Some(&occlusion_query_set),
+                    pipeline_statistics_query: pass_statistics.then_some(
+                        wgpu::RenderPassPipelineStatisticsQuery {
+                            query_set: &statistics_query_set,
+                            query_index: 0,
+                        },
+                    ),
+                    shading_rate_attachment: None,
+                });
+                commands(&mut pass);
+            }
+            encoder.finish()
+        };
+
+        // Occlusion and pipeline statistics queries are scoped separately.
+        run_pass(false, &|pass| {
+            pass.begin_occlusion_query(0);
+            pass.begin_pipeline_statistics_query(&statistics_query_set, 1);
+            pass.end_occlusion_query();
+            pass.end_pipeline_statistics_query();
+        });
+
+        fail(&ctx.device, || {
+            run_pass(false, &|pass| pass.begin_occlusion_query(0))
+        });
+        fail(&ctx.device, || {
+            run_pass(false, &|pass| {
+                pass.begin_pipeline_statistics_query(&statistics_query_set, 1)
+            })
+        });
+        fail(&ctx.device, || {
+            run_pass(true, &|pass| {
+                pass.begin_pipeline_statistics_query(&statistics_query_set, 1);
+                pass.end_pipeline_statistics_query();
+            })
+        });
+        fail(&ctx.device, || {
+            run_pass(true, &|pass| pass.end_pipeline_statistics_query())
+        });
+    });
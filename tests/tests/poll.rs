@@ -4,7 +4,7 @@ use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
     BindGroupLayoutEntry, BindingResource, BindingType, Buffer, BufferBindingType,
     BufferDescriptor, BufferUsages, CommandBuffer, CommandEncoderDescriptor, ComputePassDescriptor,
-    Maintain, ShaderStages,
+    Maintain, PollStatus, ShaderStages,
 };
 
 use wgpu_test::{gpu_test, GpuTestConfiguration, TestingContext};
@@ -125,3 +125,48 @@ static WAIT_OUT_OF_ORDER: GpuTestConfiguration =
             .await
             .panic_on_timeout();
     });
+
+#[gpu_test]
+static POLL_UNTIL_SUBMISSION: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    let data = DummyWorkData::new(&ctx);
+
+    let index = ctx.queue.submit(Some(data.cmd_buf));
+    let status = ctx
+        .device
+        .poll_until(&index, std::time::Duration::from_secs(10));
+    assert_eq!(status, PollStatus::Complete);
+
+    // Waiting again for a finished submission returns right away.
+    let status = ctx.device.poll_until(&index, std::time::Duration::ZERO);
+    assert_eq!(status, PollStatus::Complete);
+});
+
+#[gpu_test]
+static SUBMIT_ASYNC: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let data = DummyWorkData::new(&ctx);
+
+        let future = ctx.queue.submit_async(Some(data.cmd_buf));
+        ctx.async_poll(Maintain::wait()).await.panic_on_timeout();
+        future.await;
+    });
+
+#[cfg(not(target_arch = "wasm32"))]
+#[gpu_test]
+static AUTOMATIC_POLLING: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    ctx.device.set_automatic_polling(true);
+
+    let data = DummyWorkData::new(&ctx);
+    ctx.queue.submit(Some(data.cmd_buf));
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    ctx.queue
+        .on_submitted_work_done(move || sender.send(()).unwrap());
+
+    // Nothing polls the device on this thread.
+    receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .expect("work done callback was not called");
+
+    ctx.device.set_automatic_polling(false);
+});
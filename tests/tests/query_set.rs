@@ -21,3 +21,22 @@ static DROP_FAILED_TIMESTAMP_QUERY_SET: GpuTestConfiguration = GpuTestConfigurat
 
         assert!(pollster::block_on(ctx.device.pop_error_scope()).is_some());
     });
+
+#[gpu_test]
+static TIMESTAMP_CALIBRATION: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_sync(|ctx| {
+        // Not all backends can correlate the clocks.
+        let Some(first) = ctx.queue.get_timestamp_calibration() else {
+            return;
+        };
+        let second = ctx.queue.get_timestamp_calibration().unwrap();
+        assert!(first.cpu_timestamp <= second.cpu_timestamp);
+        assert!(first.gpu_timestamp <= second.gpu_timestamp);
+
+        // CPU timestamps come from the clock of presentation timestamps.
+        let presentation = ctx.adapter.get_presentation_timestamp();
+        if !presentation.is_invalid() {
+            assert!(second.cpu_timestamp <= presentation);
+        }
+    });
@@ -1,6 +1,7 @@
 //! Tests for buffer copy validation.
 
 use wgpu_test::{fail, gpu_test, GpuTestConfiguration};
+use wgt::BufferSize;
 
 #[gpu_test]
 static QUEUE_WRITE_TEXTURE_OVERFLOW: GpuTestConfiguration =
@@ -45,3 +46,75 @@ static QUEUE_WRITE_TEXTURE_OVERFLOW: GpuTestConfiguration =
             );
         });
     });
+
+#[gpu_test]
+static QUEUE_WRITE_BUFFER_REGIONS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        ctx.queue
+            .write_buffer_regions(&buffer, &[(0, &[1; 8]), (32, &[2; 4]), (60, &[])]);
+        {
+            let mut view = ctx
+                .queue
+                .write_buffer_regions_with(
+                    &buffer,
+                    &[
+                        (16, BufferSize::new(8).unwrap()),
+                        (48, BufferSize::new(16).unwrap()),
+                    ],
+                )
+                .unwrap();
+            assert_eq!(view.region_count(), 2);
+            view.region_mut(0).fill(3);
+            view.region_mut(1).fill(4);
+        }
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.copy_buffer_to_buffer(&buffer, 0, &read_buffer, 0, 64);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        read_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, Result::unwrap);
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+
+        let view = read_buffer.slice(..).get_mapped_range();
+        let mut expected = [0u8; 64];
+        expected[0..8].fill(1);
+        expected[16..24].fill(3);
+        expected[32..36].fill(2);
+        expected[48..64].fill(4);
+        assert_eq!(&view[..], &expected[..]);
+    });
+
+#[gpu_test]
+static QUEUE_WRITE_BUFFER_REGIONS_OVERRUN: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_sync(|ctx| {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        fail(&ctx.device, || {
+            ctx.queue
+                .write_buffer_regions(&buffer, &[(0, &[0; 4]), (12, &[0; 8])]);
+        });
+    });
@@ -0,0 +1,247 @@
+//! Tests for acceleration structures and ray queries.
+
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const SHADER: &str = r#"
+    @group(0) @binding(0)
+    var acc_struct: acceleration_structure;
+
+    struct Output {
+        kind: u32,
+        t: f32,
+    }
+
+    @group(0) @binding(1)
+    var<storage, read_write> output: Output;
+
+    @compute @workgroup_size(1)
+    fn main() {
+        var rq: ray_query;
+        let desc = RayDesc(RAY_FLAG_NONE, 0xFFu, 0.1, 100.0, vec3<f32>(0.0), vec3<f32>(0.0, 0.0, 1.0));
+        rayQueryInitialize(&rq, acc_struct, desc);
+        while (rayQueryProceed(&rq)) {}
+
+        let intersection = rayQueryGetCommittedIntersection(&rq);
+        output.kind = intersection.kind;
+        output.t = intersection.t;
+    }
+"#;
+
+fn triangle_size() -> wgpu::BlasTriangleGeometrySizeDescriptor {
+    wgpu::BlasTriangleGeometrySizeDescriptor {
+        vertex_format: wgpu::VertexFormat::Float32x3,
+        vertex_count: 3,
+        index_format: None,
+        index_count: None,
+        flags: wgpu::AccelerationStructureGeometryFlags::OPAQUE,
+    }
+}
+
+/// Builds an acceleration structure holding a single triangle in the `z = 2`
+/// plane, and checks that a ray query along the `z` axis hits it.
+#[gpu_test]
+static RAY_QUERY_HITS_TRIANGLE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(
+        wgpu::Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE
+            | wgpu::Features::EXPERIMENTAL_RAY_QUERY,
+    ))
+    .run_async(ray_query_hits_triangle);
+
+async fn ray_query_hits_triangle(ctx: TestingContext) {
+    let vertices: [[f32; 3]; 3] = [[-1.0, -1.0, 2.0], [1.0, -1.0, 2.0], [0.0, 1.0, 2.0]];
+    let vertex_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: Some("vertices"),
+        contents: bytemuck::cast_slice(&vertices),
+        usage: wgpu::BufferUsages::BLAS_INPUT,
+    });
+
+    let size = triangle_size();
+    let blas = ctx.device.create_blas(
+        &wgpu::CreateBlasDescriptor {
+            label: Some("blas"),
+            flags: wgpu::AccelerationStructureFlags::PREFER_FAST_TRACE,
+        },
+        wgpu::BlasGeometrySizeDescriptors::Triangles {
+            descriptors: vec![size.clone()],
+        },
+    );
+    let tlas = ctx.device.create_tlas(&wgpu::CreateTlasDescriptor {
+        label: Some("tlas"),
+        max_instances: 1,
+        flags: wgpu::AccelerationStructureFlags::PREFER_FAST_TRACE,
+    });
+
+    // A single instance with the identity transform, a custom index of 0, a
+    // mask of 0xFF, a shader binding table offset of 0 and no flags.
+    let mut instance = Vec::with_capacity(wgpu::TLAS_INSTANCE_SIZE as usize);
+    let transform: [f32; 12] = [
+        1.0, 0.0, 0.0, 0.0, //
+        0.0, 1.0, 0.0, 0.0, //
+        0.0, 0.0, 1.0, 0.0,
+    ];
+    instance.extend_from_slice(bytemuck::cast_slice(&transform));
+    instance.extend_from_slice(&(0xFF_u32 << 24).to_le_bytes());
+    instance.extend_from_slice(&0_u32.to_le_bytes());
+    instance.extend_from_slice(&blas.handle().unwrap().to_le_bytes());
+    let instance_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: Some("instances"),
+        contents: &instance,
+        usage: wgpu::BufferUsages::TLAS_INPUT,
+    });
+
+    let output_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("output"),
+        size: 8,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let cpu_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("cpu_buffer"),
+        size: 8,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let module = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    let pipeline = ctx
+        .device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("pipeline"),
+            layout: None,
+            module: &module,
+            entry_point: "main",
+            cache: None,
+        });
+    let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("bind_group"),
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::AccelerationStructure(&tlas),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: output_buffer.as_entire_binding(),
+            },
+        ],
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("encoder"),
+        });
+    // SAFETY: The only instance references `blas`, which is built by the same
+    // call and outlives the submission.
+    unsafe {
+        encoder.build_acceleration_structures_unsafe_tlas(
+            &[wgpu::BlasBuildEntry {
+                blas: &blas,
+                geometry: wgpu::BlasGeometries::TriangleGeometries(vec![
+                    wgpu::BlasTriangleGeometry {
+                        size: &size,
+                        vertex_buffer: &vertex_buffer,
+                        first_vertex: 0,
+                        vertex_stride: 12,
+                        index_buffer: None,
+                        index_buffer_offset: None,
+                        transform_buffer: None,
+                        transform_buffer_offset: None,
+                    },
+                ]),
+            }],
+            &[wgpu::TlasBuildEntry {
+                tlas: &tlas,
+                instance_buffer: &instance_buffer,
+                instance_count: 1,
+            }],
+        );
+    }
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("compute_pass"),
+            timestamp_writes: None,
+        });
+        cpass.set_pipeline(&pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.dispatch_workgroups(1, 1, 1);
+    }
+    encoder.copy_buffer_to_buffer(&output_buffer, 0, &cpu_buffer, 0, 8);
+    ctx.queue.submit([encoder.finish()]);
+
+    cpu_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+
+    let data = cpu_buffer.slice(..).get_mapped_range();
+    let kind = u32::from_le_bytes(data[0..4].try_into().unwrap());
+    let t = f32::from_le_bytes(data[4..8].try_into().unwrap());
+    // `RAY_QUERY_INTERSECTION_TRIANGLE`
+    assert_eq!(kind, 1);
+    assert!((t - 2.0).abs() < 1e-4, "hit at {t}");
+}
+
+#[gpu_test]
+static BLAS_INVALID_SIZES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE),
+    )
+    .run_sync(|ctx| {
+        let desc = wgpu::CreateBlasDescriptor {
+            label: None,
+            flags: wgpu::AccelerationStructureFlags::PREFER_FAST_TRACE,
+        };
+
+        // Non-indexed geometries are lists of triangles.
+        let blas = fail(&ctx.device, || {
+            ctx.device.create_blas(
+                &desc,
+                wgpu::BlasGeometrySizeDescriptors::Triangles {
+                    descriptors: vec![wgpu::BlasTriangleGeometrySizeDescriptor {
+                        vertex_count: 4,
+                        ..triangle_size()
+                    }],
+                },
+            )
+        });
+        assert_eq!(blas.handle(), None);
+
+        // Vertices need floating point or normalized positions.
+        fail(&ctx.device, || {
+            ctx.device.create_blas(
+                &desc,
+                wgpu::BlasGeometrySizeDescriptors::Triangles {
+                    descriptors: vec![wgpu::BlasTriangleGeometrySizeDescriptor {
+                        vertex_format: wgpu::VertexFormat::Uint32x3,
+                        ..triangle_size()
+                    }],
+                },
+            )
+        });
+    });
+
+#[gpu_test]
+static BLAS_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_sync(|ctx| {
+        fail(&ctx.device, || {
+            ctx.device.create_blas(
+                &wgpu::CreateBlasDescriptor {
+                    label: None,
+                    flags: wgpu::AccelerationStructureFlags::PREFER_FAST_TRACE,
+                },
+                wgpu::BlasGeometrySizeDescriptors::Triangles {
+                    descriptors: vec![triangle_size()],
+                },
+            )
+        });
+    });
@@ -103,6 +103,7 @@ async fn multi_stage_data_binding_test(ctx: TestingContext) {
                 module: &vs_sm,
                 entry_point: "vs_main",
                 buffers: &[],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &fs_sm,
@@ -117,6 +118,7 @@ async fn multi_stage_data_binding_test(ctx: TestingContext) {
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
 
     let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
@@ -157,6 +159,8 @@ async fn multi_stage_data_binding_test(ctx: TestingContext) {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
         });
 
         rpass.set_pipeline(&pipeline);
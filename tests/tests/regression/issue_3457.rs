@@ -64,6 +64,7 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
                             attributes: &vertex_attr_array![5 => Float32],
                         },
                     ],
+                    capture_buffers: &[],
                 },
                 primitive: PrimitiveState::default(),
                 depth_stencil: None,
@@ -78,6 +79,7 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
                     })],
                 }),
                 multiview: None,
+                cache: None,
             });
 
         let single_pipeline = ctx
@@ -93,6 +95,7 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
                         step_mode: VertexStepMode::Vertex,
                         attributes: &vertex_attr_array![0 => Float32x4],
                     }],
+                    capture_buffers: &[],
                 },
                 primitive: PrimitiveState::default(),
                 depth_stencil: None,
@@ -107,6 +110,7 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
                     })],
                 }),
                 multiview: None,
+                cache: None,
             });
 
         let view = ctx
@@ -144,6 +148,8 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
         });
 
         double_rpass.set_pipeline(&double_pipeline);
@@ -179,6 +185,8 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
         });
 
         single_rpass.set_pipeline(&single_pipeline);
@@ -0,0 +1,182 @@
+use wgpu_test::{fail, gpu_test, image, FailureCase, GpuTestConfiguration, TestParameters};
+
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// Outputs the blend constant, so each draw can pick its color without any resources.
+const SHADER: &str = "
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let x = i32(vertex_index) / 2;
+    let y = i32(vertex_index) & 1;
+    return vec4<f32>(f32(x) * 4.0 - 1.0, 1.0 - f32(y) * 4.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+";
+
+fn create_pipeline(device: &wgpu::Device) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+    });
+    let blend_constant = wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::Constant,
+        dst_factor: wgpu::BlendFactor::Zero,
+        operation: wgpu::BlendOperation::Add,
+    };
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: "vs_main",
+            buffers: &[],
+            capture_buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: "fs_main",
+            targets: &[Some(wgpu::ColorTargetState {
+                format: FORMAT,
+                blend: Some(wgpu::BlendState {
+                    color: blend_constant,
+                    alpha: blend_constant,
+                }),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn color_attachment(
+    view: &wgpu::TextureView,
+    load: wgpu::LoadOp<wgpu::Color>,
+) -> Option<wgpu::RenderPassColorAttachment<'_>> {
+    Some(wgpu::RenderPassColorAttachment {
+        view,
+        resolve_target: None,
+        ops: wgpu::Operations {
+            load,
+            store: wgpu::StoreOp::Store,
+        },
+    })
+}
+
+fn draw_pixel<'a>(
+    pass: &mut wgpu::RenderPass<'a>,
+    pipeline: &'a wgpu::RenderPipeline,
+    x: u32,
+    color: wgpu::Color,
+) {
+    pass.set_pipeline(pipeline);
+    pass.set_scissor_rect(x, 0, 1, 1);
+    pass.set_blend_constant(color);
+    pass.draw(0..3, 0..1);
+}
+
+fn create_target(device: &wgpu::Device) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 2,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+/// Splits a render pass over two command buffers. The second part must keep what the first
+/// part drew, even though it asks for the target to be cleared.
+#[gpu_test]
+static RENDER_PASS_SUSPEND_RESUME: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let texture = create_target(&ctx.device);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_pipeline(&ctx.device);
+
+        let mut first = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = first.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[color_attachment(
+                    &view,
+                    wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                )],
+                ..Default::default()
+            });
+            draw_pixel(&mut pass, &pipeline, 0, wgpu::Color::RED);
+            pass.suspend();
+        }
+
+        let mut second = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = second.resume_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[color_attachment(
+                    &view,
+                    wgpu::LoadOp::Clear(wgpu::Color::BLUE),
+                )],
+                ..Default::default()
+            });
+            draw_pixel(&mut pass, &pipeline, 1, wgpu::Color::GREEN);
+        }
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+        readback_buffer.copy_from(&ctx.device, &mut second, &texture);
+
+        ctx.queue.submit([first.finish(), second.finish()]);
+
+        readback_buffer
+            .assert_buffer_contents(&ctx, &[255, 0, 0, 255, 0, 255, 0, 255])
+            .await;
+    });
+
+/// Nothing else can be recorded to an encoder after a suspended render pass.
+#[gpu_test]
+static COMMANDS_AFTER_SUSPENDED_RENDER_PASS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let texture = create_target(&ctx.device);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder
+            .begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[color_attachment(
+                    &view,
+                    wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                )],
+                ..Default::default()
+            })
+            .suspend();
+
+        fail(&ctx.device, || encoder.clear_buffer(&buffer, 0, None));
+    });
@@ -15,3 +15,85 @@ static BUFFER_SIZE_AND_USAGE: GpuTestConfiguration = GpuTestConfiguration::new()
         wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST
     );
 });
+
+#[gpu_test]
+static TEXTURE_DESCRIPTOR_AND_VIEWS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let desc = wgpu::TextureDescriptor {
+            label: Some("texture"),
+            size: wgpu::Extent3d {
+                width: 64,
+                height: 32,
+                depth_or_array_layers: 4,
+            },
+            mip_level_count: 3,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[wgpu::TextureFormat::Rgba8UnormSrgb],
+        };
+        let texture = ctx.device.create_texture(&desc);
+
+        assert_eq!(
+            texture.descriptor(),
+            wgpu::TextureDescriptor {
+                label: None,
+                ..desc
+            }
+        );
+        assert_eq!(texture.view_formats(), desc.view_formats);
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            base_mip_level: 1,
+            base_array_layer: 1,
+            ..Default::default()
+        });
+        assert_eq!(view.texture(), texture.global_id());
+        assert_eq!(view.format(), wgpu::TextureFormat::Rgba8Unorm);
+        assert_eq!(view.dimension(), wgpu::TextureViewDimension::D2Array);
+        assert_eq!(view.descriptor().mip_level_count, Some(2));
+        assert_eq!(view.descriptor().array_layer_count, Some(3));
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(wgpu::TextureFormat::Rgba8UnormSrgb),
+            dimension: Some(wgpu::TextureViewDimension::D2),
+            base_array_layer: 2,
+            ..Default::default()
+        });
+        assert_eq!(view.format(), wgpu::TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(view.dimension(), wgpu::TextureViewDimension::D2);
+        assert_eq!(view.descriptor().mip_level_count, Some(3));
+        assert_eq!(view.descriptor().array_layer_count, Some(1));
+    });
+
+#[gpu_test]
+static BIND_GROUP_LAYOUT_ENTRIES: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let entries = [
+            wgpu::BindGroupLayoutEntry {
+                binding: 3,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ];
+        let layout = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &entries,
+            });
+
+        assert_eq!(layout.entries(), [entries[1], entries[0]]);
+    });
@@ -1,4 +1,4 @@
-use wgpu_test::{fail, gpu_test, valid, GpuTestConfiguration};
+use wgpu_test::{fail, gpu_test, valid, FailureCase, GpuTestConfiguration, TestParameters};
 
 #[gpu_test]
 static BAD_BUFFER: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
@@ -46,3 +46,63 @@ static BAD_TEXTURE: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(
     valid(&ctx.device, || texture.destroy());
     valid(&ctx.device, || texture.destroy());
 });
+
+#[gpu_test]
+static TRY_CREATE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        // Errors are raised asynchronously by the browser.
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        // The error is returned, and not reported to the error scope.
+        let result = valid(&ctx.device, || {
+            ctx.device.try_create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 99999999,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            })
+        });
+        assert!(matches!(
+            result.unwrap_err().error(),
+            wgpu::Error::Validation { .. }
+        ));
+
+        let buffer = ctx
+            .device
+            .try_create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 256,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+            .unwrap();
+        assert_eq!(buffer.size(), 256);
+
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let result = valid(&ctx.device, || {
+            texture.try_create_view(&wgpu::TextureViewDescriptor {
+                base_mip_level: 1,
+                ..Default::default()
+            })
+        });
+        assert!(result.is_err());
+
+        // Errors are reported again once the capture ended.
+        fail(&ctx.device, || {
+            buffer.slice(..).map_async(wgpu::MapMode::Write, |_| {})
+        });
+    });
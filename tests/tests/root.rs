@@ -5,37 +5,65 @@ mod regression {
     mod issue_4122;
 }
 
+mod bc_compress;
 mod bgra8unorm_storage;
+mod bind_group_cache;
+mod bind_group_immediate;
 mod bind_group_layout_dedup;
 mod buffer;
 mod buffer_copy;
 mod buffer_usages;
 mod clear_texture;
+mod conditional_rendering;
 mod create_surface_error;
+mod debug_printf;
+mod depth_stencil_resolve;
 mod device;
+mod device_generated_commands;
 mod encoder;
+mod error_scope;
 mod external_texture;
+mod external_fence;
+mod external_memory;
+mod external_texture_import;
 mod float32_filterable;
+mod gpu_sort;
+mod hiz_culling;
 mod instance;
 mod life_cycle;
 mod mem_leaks;
+mod memory_heap;
+mod mesh_shader;
+mod multi_draw_indirect_count;
+mod multi_queue;
 mod nv12_texture;
 mod occlusion_query;
+mod parallel_render_pass;
 mod partially_bounded_arrays;
 mod pipeline;
+mod pipeline_cache;
+mod pipeline_statistics_query;
 mod poll;
 mod push_constants;
 mod query_set;
 mod queue_transfer;
+mod ray_tracing;
+mod render_pass_suspend;
 mod resource_descriptor_accessor;
 mod resource_error;
 mod scissor_tests;
 mod shader;
 mod shader_primitive_index;
 mod shader_view_format;
+mod shading_rate;
 mod texture_bounds;
+mod texture_from_raw;
 mod texture_view_creation;
+mod texture_view_swizzle;
+mod tiled_texture;
 mod transfer;
+mod upload_manager;
+mod vertex_capture;
 mod vertex_indices;
 mod write_texture;
 mod zero_init_texture_after_discard;
@@ -45,6 +45,7 @@ async fn scissor_test_impl(
                 entry_point: "vs_main",
                 module: &shader,
                 buffers: &[],
+                capture_buffers: &[],
             },
             primitive: wgpu::PrimitiveState::default(),
             depth_stencil: None,
@@ -59,6 +60,7 @@ async fn scissor_test_impl(
                 })],
             }),
             multiview: None,
+            cache: None,
         });
 
     let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
@@ -85,6 +87,8 @@ async fn scissor_test_impl(
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
             render_pass.set_pipeline(&pipeline);
             render_pass.set_scissor_rect(
@@ -0,0 +1,37 @@
+use wgpu::{CompilationMessageType, ShaderModuleDescriptor, ShaderSource};
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration};
+
+#[gpu_test]
+static SHADER_COMPILE_SUCCESS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let module = ctx.device.create_shader_module(ShaderModuleDescriptor {
+            label: None,
+            source: ShaderSource::Wgsl("@compute @workgroup_size(1) fn main() {}".into()),
+        });
+
+        let compilation_info = module.get_compilation_info().await;
+        assert!(compilation_info
+            .messages
+            .iter()
+            .all(|message| message.message_type != CompilationMessageType::Error));
+    });
+
+#[gpu_test]
+static SHADER_COMPILE_ERROR: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let module = fail(&ctx.device, || {
+            ctx.device.create_shader_module(ShaderModuleDescriptor {
+                label: None,
+                source: ShaderSource::Wgsl("fn main() {}\nfn broken() { let x = ; }".into()),
+            })
+        });
+
+        let compilation_info = module.get_compilation_info().await;
+        let error = compilation_info
+            .messages
+            .iter()
+            .find(|message| message.message_type == CompilationMessageType::Error)
+            .expect("Expected a compilation error");
+        assert_eq!(error.location.map(|location| location.line_number), Some(2));
+    });
@@ -131,6 +131,7 @@ async fn pulling_common(
                 }],
                 entry_point: "vs_main",
                 module: &shader,
+                capture_buffers: &[],
             },
             primitive: wgpu::PrimitiveState::default(),
             depth_stencil: None,
@@ -145,6 +146,7 @@ async fn pulling_common(
                 })],
             }),
             multiview: None,
+            cache: None,
         });
 
     let width = 2;
@@ -185,6 +187,8 @@ async fn pulling_common(
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
         });
 
         rpass.set_pipeline(&pipeline);
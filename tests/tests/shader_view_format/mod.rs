@@ -94,6 +94,7 @@ async fn reinterpret(
                 module: shader,
                 entry_point: "vs_main",
                 buffers: &[],
+                capture_buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: shader,
@@ -107,6 +108,7 @@ async fn reinterpret(
             depth_stencil: None,
             multisample: wgpu::MultisampleState::default(),
             multiview: None,
+            cache: None,
         });
     let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
         layout: &pipeline.get_bind_group_layout(0),
@@ -142,6 +144,8 @@ async fn reinterpret(
         depth_stencil_attachment: None,
         timestamp_writes: None,
         occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: None,
     });
     rpass.set_pipeline(&pipeline);
     rpass.set_bind_group(0, &bind_group, &[]);
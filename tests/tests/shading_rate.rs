@@ -0,0 +1,278 @@
+//! Tests for `RenderPass::set_shading_rate` and shading rate attachments.
+
+use wgpu_test::{fail, gpu_test, valid, GpuTestConfiguration, TestParameters, TestingContext};
+
+/// Writes a different color to every pixel, so coarse shading shows up as
+/// blocks of identical pixels.
+const SHADER: &str = r#"
+    @vertex
+    fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+        let uv = vec2f(f32((index << 1u) & 2u), f32(index & 2u));
+        return vec4f(uv * 2.0 - 1.0, 0.0, 1.0);
+    }
+
+    @fragment
+    fn fs_main(@builtin(position) position: vec4f) -> @location(0) vec4f {
+        return vec4f(position.xy / 256.0, 0.0, 1.0);
+    }
+"#;
+
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+fn create_target(ctx: &TestingContext, size: u32) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+fn create_rate_texture(
+    ctx: &TestingContext,
+    size: u32,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats: &[],
+    })
+}
+
+fn create_pipeline(ctx: &TestingContext) -> wgpu::RenderPipeline {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+                capture_buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(FORMAT.into())],
+            }),
+            multiview: None,
+            cache: None,
+        })
+}
+
+fn begin_render_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+    shading_rate_view: Option<&'a wgpu::TextureView>,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations::default(),
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: shading_rate_view
+            .map(|view| wgpu::RenderPassShadingRateAttachment { view }),
+    })
+}
+
+/// Renders to a `size` by `size` target and returns its rows of pixels.
+async fn render(
+    ctx: &TestingContext,
+    size: u32,
+    rate: Option<wgpu::ShadingRate>,
+    shading_rate_view: Option<&wgpu::TextureView>,
+) -> Vec<Vec<[u8; 4]>> {
+    let target = create_target(ctx, size);
+    let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+    let pipeline = create_pipeline(ctx);
+    let bytes_per_row = wgpu::util::align_to(size * 4, wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (bytes_per_row * size) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = begin_render_pass(&mut encoder, &view, shading_rate_view);
+        pass.set_pipeline(&pipeline);
+        if let Some(rate) = rate {
+            pass.set_shading_rate(rate);
+        }
+        pass.draw(0..3, 0..1);
+    }
+    encoder.copy_texture_to_buffer(
+        target.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &read_buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: None,
+            },
+        },
+        target.size(),
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let slice = read_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+    let data = slice.get_mapped_range();
+    data.chunks(bytes_per_row as usize)
+        .map(|row| {
+            row[..size as usize * 4]
+                .chunks(4)
+                .map(|pixel| pixel.try_into().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns whether every `width` by `height` block of pixels has a single color.
+fn is_coarse(pixels: &[Vec<[u8; 4]>], (width, height): (u32, u32)) -> bool {
+    pixels.iter().enumerate().all(|(y, row)| {
+        row.iter().enumerate().all(|(x, pixel)| {
+            let block_x = x - x % width as usize;
+            let block_y = y - y % height as usize;
+            *pixel == pixels[block_y][block_x]
+        })
+    })
+}
+
+#[gpu_test]
+static SHADING_RATE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::SHADING_RATE))
+    .run_async(|ctx| async move {
+        let fine = render(&ctx, 8, None, None).await;
+        assert!(is_coarse(&fine, (1, 1)));
+        assert!(!is_coarse(&fine, (2, 2)));
+
+        for rate in [
+            wgpu::ShadingRate::Rate1x2,
+            wgpu::ShadingRate::Rate2x1,
+            wgpu::ShadingRate::Rate2x2,
+        ] {
+            let coarse = render(&ctx, 8, Some(rate), None).await;
+            assert!(is_coarse(&coarse, rate.block_size()), "{rate:?}");
+        }
+    });
+
+#[gpu_test]
+static SHADING_RATE_ATTACHMENT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::SHADING_RATE | wgpu::Features::SHADING_RATE_ATTACHMENT),
+    )
+    .run_async(|ctx| async move {
+        let tile_size = ctx.device.limits().shading_rate_tile_size;
+        let rate_texture = create_rate_texture(
+            &ctx,
+            2,
+            wgpu::TextureFormat::R8Uint,
+            wgpu::TextureUsages::SHADING_RATE_ATTACHMENT | wgpu::TextureUsages::COPY_DST,
+        );
+        ctx.queue.write_texture(
+            rate_texture.as_image_copy(),
+            &[wgpu::ShadingRate::Rate2x2 as u8; 4],
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(2),
+                rows_per_image: None,
+            },
+            rate_texture.size(),
+        );
+        let rate_view = rate_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // The attachment takes precedence over the rate set in the pass.
+        let coarse = render(
+            &ctx,
+            tile_size * 2,
+            Some(wgpu::ShadingRate::Rate1x1),
+            Some(&rate_view),
+        )
+        .await;
+        assert!(is_coarse(&coarse, (2, 2)));
+        assert!(!is_coarse(&coarse, (4, 4)));
+    });
+
+#[gpu_test]
+static SHADING_RATE_ATTACHMENT_VALIDATION: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::SHADING_RATE_ATTACHMENT))
+    .run_sync(|ctx| {
+        let tile_size = ctx.device.limits().shading_rate_tile_size;
+        let target = create_target(&ctx, tile_size * 2);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let usage = wgpu::TextureUsages::SHADING_RATE_ATTACHMENT;
+
+        // Only `R8Uint` textures can be shading rate attachments.
+        fail(&ctx.device, || {
+            create_rate_texture(&ctx, 2, wgpu::TextureFormat::R8Unorm, usage)
+        });
+
+        let run_pass = |rate_view: &wgpu::TextureView| {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            drop(begin_render_pass(&mut encoder, &view, Some(rate_view)));
+            encoder.finish()
+        };
+
+        let rate_view = create_rate_texture(&ctx, 2, wgpu::TextureFormat::R8Uint, usage)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        valid(&ctx.device, || run_pass(&rate_view));
+
+        // Every tile of the render targets needs a texel.
+        let small_view = create_rate_texture(&ctx, 1, wgpu::TextureFormat::R8Uint, usage)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        fail(&ctx.device, || run_pass(&small_view));
+
+        // The texture must have been created with the usage.
+        let unusable_view = create_rate_texture(
+            &ctx,
+            2,
+            wgpu::TextureFormat::R8Uint,
+            wgpu::TextureUsages::TEXTURE_BINDING,
+        )
+        .create_view(&wgpu::TextureViewDescriptor::default());
+        fail(&ctx.device, || run_pass(&unusable_view));
+    });
@@ -0,0 +1,178 @@
+#![cfg(all(
+    not(target_arch = "wasm32"),
+    not(target_os = "macos"),
+    not(target_os = "ios")
+))]
+//! Tests for `Texture::from_raw_vulkan_image`, with images shaped like the
+//! stereo swapchain images of OpenXR runtimes.
+
+use wgpu_test::{
+    fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext,
+};
+
+const SIZE: u32 = 16;
+const COLORS: [wgpu::Color; 2] = [wgpu::Color::RED, wgpu::Color::GREEN];
+
+fn descriptor(usage: wgpu::TextureUsages) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 2,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage,
+        view_formats: &[],
+    }
+}
+
+fn layer_view(texture: &wgpu::Texture, layer: u32) -> wgpu::TextureView {
+    texture.create_view(&wgpu::TextureViewDescriptor {
+        dimension: Some(wgpu::TextureViewDimension::D2),
+        base_array_layer: layer,
+        array_layer_count: Some(1),
+        ..Default::default()
+    })
+}
+
+fn clear_layer(encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView, color: wgpu::Color) {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(color),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: None,
+    });
+}
+
+/// Creates a texture standing in for a swapchain image of another API, left in
+/// the color attachment layout like OpenXR runtimes hand them out.
+fn create_image(ctx: &TestingContext, desc: &wgpu::TextureDescriptor<'_>) -> (wgpu::Texture, u64) {
+    let texture = ctx.device.create_texture(desc);
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    for layer in 0..2 {
+        clear_layer(
+            &mut encoder,
+            &layer_view(&texture, layer),
+            wgpu::Color::BLACK,
+        );
+    }
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let mut image = None;
+    unsafe {
+        texture.as_hal::<wgpu::hal::api::Vulkan, _>(|texture| {
+            // The tests don't link to ash to get the handle as an integer.
+            image = texture.map(|texture| std::mem::transmute(texture.raw_handle()));
+        })
+    };
+    (texture, image.unwrap())
+}
+
+#[gpu_test]
+static STEREO_LAYERS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().skip(FailureCase::backend(
+        wgpu::Backends::all() - wgpu::Backends::VULKAN,
+    )))
+    .run_async(|ctx| async move {
+        let desc =
+            descriptor(wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC);
+        let (image_texture, image) = create_image(&ctx, &desc);
+        let texture = unsafe { wgpu::Texture::from_raw_vulkan_image(&ctx.device, image, &desc) };
+
+        // A single view over both eyes, as used with multiview rendering.
+        texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+
+        let bytes_per_row = wgpu::util::align_to(SIZE * 4, wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (bytes_per_row * SIZE * 2) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        for (layer, &color) in COLORS.iter().enumerate() {
+            clear_layer(&mut encoder, &layer_view(&texture, layer as u32), color);
+        }
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &read_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(SIZE),
+                },
+            },
+            texture.size(),
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        // The copy left the image readable, but the submission handed it back in
+        // the color attachment layout, where the original texture expects it.
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        clear_layer(
+            &mut encoder,
+            &layer_view(&image_texture, 0),
+            wgpu::Color::BLACK,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data = slice.get_mapped_range();
+        for (layer, expected) in [[255, 0, 0, 255], [0, 255, 0, 255]].iter().enumerate() {
+            let layer_data = &data[(layer * (bytes_per_row * SIZE) as usize)..];
+            for row in layer_data
+                .chunks(bytes_per_row as usize)
+                .take(SIZE as usize)
+            {
+                for pixel in row[..(SIZE * 4) as usize].chunks(4) {
+                    assert_eq!(pixel, expected, "layer {layer}");
+                }
+            }
+        }
+    });
+
+#[gpu_test]
+static WITHOUT_RENDER_ATTACHMENT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().skip(FailureCase::backend(
+        wgpu::Backends::all() - wgpu::Backends::VULKAN,
+    )))
+    .run_sync(|ctx| {
+        let (_image_texture, image) =
+            create_image(&ctx, &descriptor(wgpu::TextureUsages::RENDER_ATTACHMENT));
+        fail(&ctx.device, || unsafe {
+            wgpu::Texture::from_raw_vulkan_image(
+                &ctx.device,
+                image,
+                &descriptor(wgpu::TextureUsages::COPY_SRC),
+            )
+        });
+    });
@@ -0,0 +1,170 @@
+//! Tests for TEXTURE_COMPONENT_SWIZZLE feature
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters};
+
+const SHADER_SRC: &str = "
+@group(0) @binding(0) var tex: texture_2d<f32>;
+@group(0) @binding(1) var<storage, read_write> output: vec4<f32>;
+
+@compute @workgroup_size(1)
+fn main() {
+    output = textureLoad(tex, vec2<u32>(0u), 0);
+}
+";
+
+const RRR1: wgpu::ComponentMapping = wgpu::ComponentMapping {
+    r: wgpu::ComponentSwizzle::R,
+    g: wgpu::ComponentSwizzle::R,
+    b: wgpu::ComponentSwizzle::R,
+    a: wgpu::ComponentSwizzle::One,
+};
+
+fn create_texture(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats: &[],
+    })
+}
+
+/// A single channel texture viewed as RRR1 reads back its red channel everywhere.
+#[gpu_test]
+static SAMPLE_SWIZZLED_VIEW: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_COMPONENT_SWIZZLE))
+    .run_async(|ctx| async move {
+        let device = &ctx.device;
+        let texture = create_texture(
+            device,
+            wgpu::TextureFormat::R8Unorm,
+            wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        );
+        ctx.queue.write_texture(
+            texture.as_image_copy(),
+            &[255],
+            wgpu::ImageDataLayout::default(),
+            texture.size(),
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            swizzle: RRR1,
+            ..Default::default()
+        });
+
+        let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: "main",
+            cache: None,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(1, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output_buffer, 0, &readback_buffer, 0, 16);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let buffer_slice = readback_buffer.slice(..);
+        buffer_slice.map_async(wgpu::MapMode::Read, Result::unwrap);
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+
+        {
+            let data = buffer_slice.get_mapped_range();
+            let texel: &[f32] = bytemuck::cast_slice(&data);
+            assert_eq!(texel, [1.0, 1.0, 1.0, 1.0]);
+        }
+
+        readback_buffer.unmap();
+    });
+
+/// Swizzled views can only be sampled, not bound as storage textures.
+#[gpu_test]
+static STORAGE_SWIZZLED_VIEW: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_COMPONENT_SWIZZLE))
+    .run_sync(|ctx| {
+        let texture = create_texture(
+            &ctx.device,
+            wgpu::TextureFormat::Rgba8Unorm,
+            wgpu::TextureUsages::STORAGE_BINDING,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            swizzle: RRR1,
+            ..Default::default()
+        });
+        let layout = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                }],
+            });
+
+        fail(&ctx.device, || {
+            ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                }],
+            })
+        });
+    });
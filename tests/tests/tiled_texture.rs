@@ -0,0 +1,212 @@
+//! Tests for `TextureUsages::TILED` textures and `Queue::bind_texture_pages`.
+
+use wgpu_test::{fail, gpu_test, valid, GpuTestConfiguration, TestParameters, TestingContext};
+
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// Two by two tiles of `FORMAT`.
+const SIZE: u32 = 256;
+
+fn create_texture(ctx: &TestingContext, size: u32, usage: wgpu::TextureUsages) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage,
+        view_formats: &[],
+    })
+}
+
+fn create_tiled_texture(ctx: &TestingContext) -> wgpu::Texture {
+    create_texture(
+        ctx,
+        SIZE,
+        wgpu::TextureUsages::TILED | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+    )
+}
+
+fn create_tile_pool(ctx: &TestingContext, tile_count: u32) -> wgpu::TilePool {
+    ctx.device.create_tile_pool(&wgpu::TilePoolDescriptor {
+        label: None,
+        tile_count,
+    })
+}
+
+/// Binds the `row`th row of tiles of `texture` to `memory`.
+fn bind_row(
+    ctx: &TestingContext,
+    texture: &wgpu::Texture,
+    row: u32,
+    memory: Option<(&wgpu::TilePool, u32)>,
+) {
+    let (tile_width, _) = FORMAT.tile_dimensions().unwrap();
+    ctx.queue
+        .bind_texture_pages(texture, &[binding(0, 0, row, SIZE / tile_width, memory)]);
+}
+
+/// A binding of a single row of `width` tiles, starting at tile `(x, y)`.
+fn binding(
+    mip_level: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    memory: Option<(&wgpu::TilePool, u32)>,
+) -> wgpu::TextureTileBinding<'_> {
+    wgpu::TextureTileBinding {
+        mip_level,
+        origin: wgpu::Origin3d { x, y, z: 0 },
+        size: wgpu::Extent3d {
+            width,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        memory,
+    }
+}
+
+/// Reads back the rows of texels of `texture`.
+async fn read_texture(ctx: &TestingContext, texture: &wgpu::Texture) -> Vec<Vec<u8>> {
+    let bytes_per_row = SIZE * 4;
+    let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (bytes_per_row * SIZE) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &read_buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: None,
+            },
+        },
+        texture.size(),
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let slice = read_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+    let data = slice.get_mapped_range();
+    data.chunks(bytes_per_row as usize)
+        .map(|row| row.to_vec())
+        .collect()
+}
+
+#[gpu_test]
+static TILED_TEXTURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TILED_TEXTURE))
+    .run_async(|ctx| async move {
+        let (_, tile_height) = FORMAT.tile_dimensions().unwrap();
+        let texture = create_tiled_texture(&ctx);
+        let pool = create_tile_pool(&ctx, 4);
+
+        // Only the top row of tiles is backed by memory.
+        bind_row(&ctx, &texture, 0, Some((&pool, 0)));
+        ctx.queue.write_texture(
+            texture.as_image_copy(),
+            &vec![0xFF; (SIZE * 4 * tile_height) as usize],
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(SIZE * 4),
+                rows_per_image: None,
+            },
+            wgpu::Extent3d {
+                width: SIZE,
+                height: tile_height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        // Unbound tiles read zero.
+        let rows = read_texture(&ctx, &texture).await;
+        let (top, bottom) = rows.split_at(tile_height as usize);
+        assert!(top.iter().flatten().all(|&byte| byte == 0xFF));
+        assert!(bottom.iter().flatten().all(|&byte| byte == 0));
+
+        // Newly bound tiles are zeroed, and existing bindings are kept.
+        bind_row(&ctx, &texture, 1, Some((&pool, 2)));
+        let rows = read_texture(&ctx, &texture).await;
+        let (top, bottom) = rows.split_at(tile_height as usize);
+        assert!(top.iter().flatten().all(|&byte| byte == 0xFF));
+        assert!(bottom.iter().flatten().all(|&byte| byte == 0));
+
+        // The texture keeps the pool alive.
+        drop(pool);
+        let rows = read_texture(&ctx, &texture).await;
+        assert!(rows[0].iter().all(|&byte| byte == 0xFF));
+
+        bind_row(&ctx, &texture, 0, None);
+        let rows = read_texture(&ctx, &texture).await;
+        assert!(rows.iter().flatten().all(|&byte| byte == 0));
+    });
+
+#[gpu_test]
+static TILED_TEXTURE_VALIDATION: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TILED_TEXTURE))
+    .run_sync(|ctx| {
+        let usage = wgpu::TextureUsages::TILED | wgpu::TextureUsages::COPY_DST;
+
+        // Tiled textures can't be rendered to.
+        fail(&ctx.device, || {
+            create_texture(&ctx, SIZE, usage | wgpu::TextureUsages::RENDER_ATTACHMENT)
+        });
+
+        // Every mip level must be made of whole tiles.
+        fail(&ctx.device, || create_texture(&ctx, SIZE - 64, usage));
+
+        // Pools need at least one tile.
+        fail(&ctx.device, || create_tile_pool(&ctx, 0));
+
+        let texture = create_tiled_texture(&ctx);
+        let pool = create_tile_pool(&ctx, 2);
+        let bind = |texture: &wgpu::Texture, binding: wgpu::TextureTileBinding<'_>| {
+            ctx.queue.bind_texture_pages(texture, &[binding])
+        };
+
+        valid(&ctx.device, || {
+            bind(&texture, binding(0, 0, 1, 2, Some((&pool, 0))))
+        });
+        valid(&ctx.device, || bind(&texture, binding(0, 0, 1, 2, None)));
+
+        // Only tiled textures can be bound.
+        let untiled = create_texture(&ctx, SIZE, wgpu::TextureUsages::COPY_DST);
+        fail(&ctx.device, || {
+            bind(&untiled, binding(0, 0, 0, 1, Some((&pool, 0))))
+        });
+
+        // The mip level and region must be within the texture.
+        fail(&ctx.device, || {
+            bind(&texture, binding(1, 0, 0, 1, Some((&pool, 0))))
+        });
+        fail(&ctx.device, || {
+            bind(&texture, binding(0, 1, 0, 2, Some((&pool, 0))))
+        });
+        fail(&ctx.device, || {
+            bind(&texture, binding(0, 0, 2, 1, Some((&pool, 0))))
+        });
+
+        // The tiles must be within the pool.
+        fail(&ctx.device, || {
+            bind(&texture, binding(0, 0, 0, 2, Some((&pool, 1))))
+        });
+        fail(&ctx.device, || {
+            bind(&texture, binding(0, 0, 0, 1, Some((&pool, 2))))
+        });
+    });
@@ -1,4 +1,4 @@
-use wgpu_test::{fail, gpu_test, GpuTestConfiguration};
+use wgpu_test::{fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
 
 #[gpu_test]
 static COPY_OVERFLOW_Z: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
@@ -63,3 +63,161 @@ static COPY_OVERFLOW_Z: GpuTestConfiguration = GpuTestConfiguration::new().run_s
         ctx.queue.submit(Some(encoder.finish()));
     });
 });
+
+fn create_2d_texture(
+    device: &wgpu::Device,
+    size: u32,
+    sample_count: u32,
+    usage: wgpu::TextureUsages,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        dimension: wgpu::TextureDimension::D2,
+        size: wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage,
+        mip_level_count: 1,
+        sample_count,
+        view_formats: &[],
+    })
+}
+
+#[gpu_test]
+static RESOLVE_SINGLE_SAMPLED: GpuTestConfiguration = GpuTestConfiguration::new()
+    // WebGPU has no explicit resolves.
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let usage = wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST;
+        let src = create_2d_texture(&ctx.device, 4, 1, usage);
+        let dst = create_2d_texture(&ctx.device, 4, 1, usage);
+
+        fail(&ctx.device, || {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.resolve_texture(
+                src.as_image_copy(),
+                dst.as_image_copy(),
+                wgpu::Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+                wgpu::ResolveMode::Average,
+            );
+            ctx.queue.submit(Some(encoder.finish()));
+        });
+    });
+
+#[gpu_test]
+static RESOLVE_REGION: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES),
+    )
+    .run_async(|ctx| async move {
+        let flags = ctx
+            .adapter
+            .get_texture_format_features(wgpu::TextureFormat::Rgba8Unorm)
+            .flags;
+        if !flags.contains(wgpu::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_REGION)
+            || !flags.sample_count_supported(4)
+        {
+            return;
+        }
+
+        let src = create_2d_texture(
+            &ctx.device,
+            4,
+            4,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        );
+        let dst = create_2d_texture(
+            &ctx.device,
+            4,
+            1,
+            wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+        );
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64 * 4,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let src_view = src.create_view(&wgpu::TextureViewDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &src_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::RED),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
+        });
+        // Only the bottom right quarter is resolved, into the top left corner.
+        encoder.resolve_texture(
+            wgpu::ImageCopyTexture {
+                texture: &src,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x: 2, y: 2, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            dst.as_image_copy(),
+            wgpu::Extent3d {
+                width: 2,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            wgpu::ResolveMode::Average,
+        );
+        encoder.copy_texture_to_buffer(
+            dst.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &read_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                    rows_per_image: None,
+                },
+            },
+            dst.size(),
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data = slice.get_mapped_range();
+        for (y, row) in data
+            .chunks(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize)
+            .enumerate()
+        {
+            for (x, texel) in row[..16].chunks(4).enumerate() {
+                let expected = if x < 2 && y < 2 {
+                    [255, 0, 0, 255]
+                } else {
+                    [0, 0, 0, 0]
+                };
+                assert_eq!(texel, expected, "texel ({x}, {y})");
+            }
+        }
+    });
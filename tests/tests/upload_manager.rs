@@ -0,0 +1,140 @@
+//! Tests for `wgpu::util::UploadManager`.
+
+use wgpu::util::UploadManager;
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestingContext};
+
+async fn read_buffer(ctx: &TestingContext, buffer: &wgpu::Buffer) -> Vec<u8> {
+    let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: buffer.size(),
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_buffer_to_buffer(buffer, 0, &read_buffer, 0, buffer.size());
+    ctx.queue.submit(Some(encoder.finish()));
+
+    read_buffer
+        .slice(..)
+        .map_async(wgpu::MapMode::Read, Result::unwrap);
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+    let data = read_buffer.slice(..).get_mapped_range().to_vec();
+    data
+}
+
+#[gpu_test]
+static UPLOAD_MANAGER_BUFFER: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 1024,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        // A small chunk size, so the uploads are spread over several chunks and the
+        // largest one gets a chunk of its own.
+        let mut uploads = UploadManager::new(256);
+
+        for round in 0..2u8 {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            let futures = [(0, 64), (64, 192), (256, 768)].map(|(offset, size)| {
+                uploads.write_buffer(
+                    &ctx.device,
+                    &mut encoder,
+                    &buffer,
+                    offset,
+                    &vec![round + offset as u8; size],
+                )
+            });
+            uploads.submit(&ctx.queue, Some(encoder.finish()));
+            ctx.async_poll(wgpu::Maintain::wait())
+                .await
+                .panic_on_timeout();
+            for future in futures {
+                future.await;
+            }
+
+            let data = read_buffer(&ctx, &buffer).await;
+            assert!(data[..64].iter().all(|&byte| byte == round));
+            assert!(data[64..256].iter().all(|&byte| byte == round + 64));
+            assert!(data[256..].iter().all(|&byte| byte == round));
+        }
+    });
+
+#[gpu_test]
+static UPLOAD_MANAGER_TEXTURE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        // Rows of 20 bytes, which need to be padded in the staging buffer.
+        let size = wgpu::Extent3d {
+            width: 5,
+            height: 3,
+            depth_or_array_layers: 2,
+        };
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let data: Vec<u8> = (0..5 * 4 * 3 * 2).map(|i| i as u8).collect();
+
+        let mut uploads = UploadManager::new(1024);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let future = uploads.write_texture(
+            &ctx.device,
+            &mut encoder,
+            texture.as_image_copy(),
+            &data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(5 * 4),
+                rows_per_image: Some(3),
+            },
+            size,
+        );
+        uploads.submit(&ctx.queue, Some(encoder.finish()));
+
+        let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 256 * 3 * 2,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(256),
+                    rows_per_image: Some(3),
+                },
+            },
+            size,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        future.await;
+
+        let result = read_buffer(&ctx, &readback).await;
+        for (row, expected) in data.chunks(5 * 4).enumerate() {
+            assert_eq!(&result[row * 256..row * 256 + 5 * 4], expected);
+        }
+    });
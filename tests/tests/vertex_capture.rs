@@ -0,0 +1,375 @@
+//! Tests for `RenderPass::begin_capture`.
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+/// Each captured vertex is `(vertex_index, 0, 1, 2)`.
+const SHADER: &str = r#"
+    struct VertexOutput {
+        @builtin(position) position: vec4f,
+        @location(0) value: vec4f,
+        @location(1) @interpolate(flat) index: u32,
+    }
+
+    @vertex
+    fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+        var out: VertexOutput;
+        out.position = vec4f(0.0, 0.0, 0.0, 1.0);
+        out.value = vec4f(f32(index), 0.0, 1.0, 2.0);
+        out.index = index;
+        return out;
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+"#;
+
+const STRIDE: wgpu::BufferAddress = 16;
+
+const VALUE_ATTRIBUTES: &[wgpu::VertexAttribute] = &wgpu::vertex_attr_array![0 => Float32x4];
+
+fn create_target(ctx: &TestingContext) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+fn create_pipeline(
+    ctx: &TestingContext,
+    topology: wgpu::PrimitiveTopology,
+    capture_buffers: &[wgpu::VertexCaptureBufferLayout<'_>],
+) -> wgpu::RenderPipeline {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+                capture_buffers,
+            },
+            primitive: wgpu::PrimitiveState {
+                topology,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+            cache: None,
+        })
+}
+
+fn create_capture_pipeline(ctx: &TestingContext) -> wgpu::RenderPipeline {
+    create_pipeline(
+        ctx,
+        wgpu::PrimitiveTopology::TriangleList,
+        &[wgpu::VertexCaptureBufferLayout {
+            array_stride: STRIDE,
+            attributes: VALUE_ATTRIBUTES,
+        }],
+    )
+}
+
+fn capture(
+    array_stride: wgpu::BufferAddress,
+    attributes: &[wgpu::VertexAttribute],
+) -> [wgpu::VertexCaptureBufferLayout<'_>; 1] {
+    [wgpu::VertexCaptureBufferLayout {
+        array_stride,
+        attributes,
+    }]
+}
+
+fn begin_render_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations::default(),
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        shading_rate_attachment: None,
+    })
+}
+
+#[gpu_test]
+static VERTEX_CAPTURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::VERTEX_CAPTURE))
+    .run_async(|ctx| async move {
+        let target = create_target(&ctx);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_capture_pipeline(&ctx);
+        let size = 6 * STRIDE;
+        let capture_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::VERTEX_CAPTURE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = begin_render_pass(&mut encoder, &view);
+            pass.set_pipeline(&pipeline);
+            pass.begin_capture(&[capture_buffer.slice(..)]);
+            // Consecutive draws append to the captured vertices, and the
+            // incomplete triangle of the second draw is dropped.
+            pass.draw(0..3, 0..1);
+            pass.draw(3..7, 0..1);
+            pass.end_capture();
+        }
+        encoder.copy_buffer_to_buffer(&capture_buffer, 0, &read_buffer, 0, size);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data: Vec<f32> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        let expected: Vec<f32> = (0..6)
+            .flat_map(|index| [index as f32, 0.0, 1.0, 2.0])
+            .collect();
+        assert_eq!(data, expected);
+    });
+
+#[gpu_test]
+static VERTEX_CAPTURE_INVALID_PIPELINE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::VERTEX_CAPTURE))
+    .run_sync(|ctx| {
+        let list = wgpu::PrimitiveTopology::TriangleList;
+
+        // Unaligned stride.
+        fail(&ctx.device, || {
+            create_pipeline(&ctx, list, &capture(18, VALUE_ATTRIBUTES))
+        });
+        // The attribute doesn't fit in the stride.
+        fail(&ctx.device, || {
+            create_pipeline(&ctx, list, &capture(8, VALUE_ATTRIBUTES))
+        });
+        // Only 32-bit components can be captured.
+        fail(&ctx.device, || {
+            create_pipeline(
+                &ctx,
+                list,
+                &capture(STRIDE, &wgpu::vertex_attr_array![1 => Uint16x2]),
+            )
+        });
+        // The format doesn't match the output of the vertex stage.
+        fail(&ctx.device, || {
+            create_pipeline(
+                &ctx,
+                list,
+                &capture(STRIDE, &wgpu::vertex_attr_array![0 => Float32x2]),
+            )
+        });
+        // The vertex stage has no output at this location.
+        fail(&ctx.device, || {
+            create_pipeline(
+                &ctx,
+                list,
+                &capture(STRIDE, &wgpu::vertex_attr_array![2 => Float32x4]),
+            )
+        });
+        // Overlapping attributes.
+        fail(&ctx.device, || {
+            create_pipeline(
+                &ctx,
+                list,
+                &capture(
+                    32,
+                    &[
+                        wgpu::VertexAttribute {
+                            format: wgpu::VertexFormat::Float32x4,
+                            offset: 0,
+                            shader_location: 0,
+                        },
+                        wgpu::VertexAttribute {
+                            format: wgpu::VertexFormat::Uint32,
+                            offset: 12,
+                            shader_location: 1,
+                        },
+                    ],
+                ),
+            )
+        });
+        // Strip topologies can't be captured.
+        fail(&ctx.device, || {
+            create_pipeline(
+                &ctx,
+                wgpu::PrimitiveTopology::TriangleStrip,
+                &capture(STRIDE, VALUE_ATTRIBUTES),
+            )
+        });
+    });
+
+#[gpu_test]
+static VERTEX_CAPTURE_INVALID_PASS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::VERTEX_CAPTURE))
+    .run_sync(|ctx| {
+        let target = create_target(&ctx);
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_capture_pipeline(&ctx);
+        let plain_pipeline = create_pipeline(&ctx, wgpu::PrimitiveTopology::TriangleList, &[]);
+        let capture_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 3 * STRIDE,
+            usage: wgpu::BufferUsages::VERTEX_CAPTURE,
+            mapped_at_creation: false,
+        });
+        let storage_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 3 * STRIDE,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let index_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 12,
+            usage: wgpu::BufferUsages::INDEX,
+            mapped_at_creation: false,
+        });
+
+        let run_pass = |commands: &dyn Fn(&mut wgpu::RenderPass<'_>)| {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut pass = begin_render_pass(&mut encoder, &view);
+                commands(&mut pass);
+            }
+            encoder.finish()
+        };
+
+        run_pass(&|pass| {
+            pass.set_pipeline(&pipeline);
+            pass.begin_capture(&[capture_buffer.slice(..)]);
+            pass.draw(0..3, 0..1);
+            pass.end_capture();
+        });
+
+        // The pipeline doesn't capture its vertices.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.set_pipeline(&plain_pipeline);
+                pass.begin_capture(&[capture_buffer.slice(..)]);
+                pass.end_capture();
+            })
+        });
+        // The pipeline captures into a buffer that wasn't given.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.set_pipeline(&pipeline);
+                pass.begin_capture(&[]);
+                pass.end_capture();
+            })
+        });
+        // Missing `VERTEX_CAPTURE` usage.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.set_pipeline(&pipeline);
+                pass.begin_capture(&[storage_buffer.slice(..)]);
+                pass.end_capture();
+            })
+        });
+        // Unaligned offset.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.set_pipeline(&pipeline);
+                pass.begin_capture(&[capture_buffer.slice(2..)]);
+                pass.end_capture();
+            })
+        });
+        // More vertices are drawn than the buffer fits.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.set_pipeline(&pipeline);
+                pass.begin_capture(&[capture_buffer.slice(..)]);
+                pass.draw(0..3, 0..1);
+                pass.draw(0..3, 0..1);
+                pass.end_capture();
+            })
+        });
+        // Only non-indexed direct draws can be captured.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.set_pipeline(&pipeline);
+                pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                pass.begin_capture(&[capture_buffer.slice(..)]);
+                pass.draw_indexed(0..3, 0, 0..1);
+                pass.end_capture();
+            })
+        });
+        // The pipeline can't change while capturing.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.set_pipeline(&pipeline);
+                pass.begin_capture(&[capture_buffer.slice(..)]);
+                pass.set_pipeline(&pipeline);
+                pass.end_capture();
+            })
+        });
+        // Ended without being begun.
+        fail(&ctx.device, || run_pass(&|pass| pass.end_capture()));
+        // Not ended before the end of the pass.
+        fail(&ctx.device, || {
+            run_pass(&|pass| {
+                pass.set_pipeline(&pipeline);
+                pass.begin_capture(&[capture_buffer.slice(..)]);
+            })
+        });
+    });
+
+#[gpu_test]
+static VERTEX_CAPTURE_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_sync(|ctx| {
+        fail(&ctx.device, || create_capture_pipeline(&ctx));
+        fail(&ctx.device, || {
+            ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: STRIDE,
+                usage: wgpu::BufferUsages::VERTEX_CAPTURE,
+                mapped_at_creation: false,
+            })
+        });
+    });
@@ -267,6 +267,7 @@ async fn vertex_index_common(ctx: TestingContext) {
             buffers: &[],
             entry_point: "vs_main_builtin",
             module: &shader,
+            capture_buffers: &[],
         },
         primitive: wgpu::PrimitiveState::default(),
         depth_stencil: None,
@@ -281,6 +282,7 @@ async fn vertex_index_common(ctx: TestingContext) {
             })],
         }),
         multiview: None,
+        cache: None,
     };
     let builtin_pipeline = ctx.device.create_render_pipeline(&pipeline_desc);
     pipeline_desc.vertex.entry_point = "vs_main_buffers";
@@ -336,7 +338,6 @@ async fn vertex_index_common(ctx: TestingContext) {
 
     let features = ctx.adapter.features();
 
-    let mut failed = false;
     for test in tests {
         let pipeline = match test.id_source {
             IdSource::Buffers => &buffer_pipeline,
@@ -384,6 +385,8 @@ async fn vertex_index_common(ctx: TestingContext) {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            shading_rate_attachment: None,
         });
 
         rpass.set_vertex_buffer(0, identity_buffer.slice(..));
@@ -439,25 +442,14 @@ async fn vertex_index_common(ctx: TestingContext) {
             .panic_on_timeout();
         let data: Vec<u32> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
 
-        if data != expected {
-            eprintln!(
-                "Failed: Got: {:?} Expected: {:?} - Case {:?} getting indices from {:?} using {:?} draw calls",
-                data,
-                expected,
-                test.case,
-                test.id_source,
-                test.draw_call_kind
-            );
-            failed = true;
-        } else {
-            eprintln!(
-                "Passed: Case {:?} getting indices from {:?} using {:?} draw calls",
+        ctx.subtest(
+            format!(
+                "Case {:?} getting indices from {:?} using {:?} draw calls",
                 test.case, test.id_source, test.draw_call_kind
-            );
-        }
+            ),
+            || assert_eq!(data, expected),
+        );
     }
-
-    assert!(!failed);
 }
 
 #[gpu_test]
@@ -161,9 +161,12 @@ impl<'ctx> TestCase<'ctx> {
                         load: LoadOp::Clear(0xFFFFFFFF),
                         store: StoreOp::Store,
                     }),
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
             ctx.queue.submit([encoder.finish()]);
         } else {
@@ -245,10 +248,13 @@ impl<'ctx> TestCase<'ctx> {
                             load: LoadOp::Load,
                             store: StoreOp::Discard,
                         }),
+                        resolve: None,
                     },
                 ),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
     }
 
@@ -270,10 +276,13 @@ impl<'ctx> TestCase<'ctx> {
                             load: LoadOp::Clear(0),
                             store: StoreOp::Store,
                         }),
+                        resolve: None,
                     },
                 ),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
     }
 
@@ -295,10 +304,13 @@ impl<'ctx> TestCase<'ctx> {
                             load: LoadOp::Load,
                             store: StoreOp::Discard,
                         }),
+                        resolve: None,
                     },
                 ),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                shading_rate_attachment: None,
             });
     }
 
@@ -8,7 +8,7 @@ use crate::{
     hal_api::HalApi,
     id::{
         BindGroupId, BindGroupLayoutId, BufferId, PipelineLayoutId, SamplerId, TextureId,
-        TextureViewId,
+        TextureViewId, TlasId,
     },
     init_tracker::{BufferInitTrackerAction, TextureInitTrackerAction},
     resource::{Resource, ResourceInfo, ResourceType},
@@ -85,6 +85,8 @@ pub enum CreateBindGroupError {
     InvalidTexture(TextureId),
     #[error("Sampler {0:?} is invalid")]
     InvalidSampler(SamplerId),
+    #[error("Top level acceleration structure {0:?} is invalid")]
+    InvalidTlas(TlasId),
     #[error(
         "Binding count declared with at most {expected} items, but {actual} items were provided"
     )]
@@ -166,6 +168,11 @@ pub enum CreateBindGroupError {
     },
     #[error("Storage texture bindings must have a single mip level, but given a view with mip_level_count = {mip_level_count:?} at binding {binding}")]
     InvalidStorageTextureMipLevelCount { binding: u32, mip_level_count: u32 },
+    #[error("Storage texture bindings can't swizzle their components, but given a view with swizzle = {swizzle:?} at binding {binding}")]
+    InvalidStorageTextureSwizzle {
+        binding: u32,
+        swizzle: wgt::ComponentMapping,
+    },
     #[error("Sampler binding {binding} expects comparison = {layout_cmp}, but given a sampler with comparison = {sampler_cmp}")]
     WrongSamplerComparison {
         binding: u32,
@@ -239,6 +246,8 @@ pub enum BindingTypeMaxCountErrorKind {
     StorageBuffers,
     StorageTextures,
     UniformBuffers,
+    BindingArrayElements,
+    BindingArraySamplerElements,
 }
 
 #[derive(Debug, Default)]
@@ -310,11 +319,38 @@ pub(crate) struct BindingTypeMaxCountValidator {
     storage_buffers: PerStageBindingTypeCounter,
     storage_textures: PerStageBindingTypeCounter,
     uniform_buffers: PerStageBindingTypeCounter,
+    binding_array_elements: PerStageBindingTypeCounter,
+    binding_array_sampler_elements: PerStageBindingTypeCounter,
 }
 
 impl BindingTypeMaxCountValidator {
-    pub(crate) fn add_binding(&mut self, binding: &wgt::BindGroupLayoutEntry) {
+    /// Counts `binding` towards the limits of its type, or towards the binding
+    /// array limits if it's an array of an update-after-bind layout.
+    pub(crate) fn add_binding(
+        &mut self,
+        binding: &wgt::BindGroupLayoutEntry,
+        update_after_bind: bool,
+    ) {
         let count = binding.count.map_or(1, |count| count.get());
+        if update_after_bind && binding.count.is_some() {
+            match binding.ty {
+                // Arrays of uniform buffers are never update-after-bind.
+                wgt::BindingType::Buffer {
+                    ty: wgt::BufferBindingType::Uniform,
+                    ..
+                } => {}
+                wgt::BindingType::Sampler { .. } => {
+                    self.binding_array_sampler_elements
+                        .add(binding.visibility, count);
+                    return;
+                }
+                wgt::BindingType::AccelerationStructure => return,
+                _ => {
+                    self.binding_array_elements.add(binding.visibility, count);
+                    return;
+                }
+            }
+        }
         match binding.ty {
             wgt::BindingType::Buffer {
                 ty: wgt::BufferBindingType::Uniform,
@@ -345,7 +381,8 @@ impl BindingTypeMaxCountValidator {
             wgt::BindingType::StorageTexture { .. } => {
                 self.storage_textures.add(binding.visibility, count);
             }
-            wgt::BindingType::AccelerationStructure => todo!(),
+            // There is no limit on the number of acceleration structures bound.
+            wgt::BindingType::AccelerationStructure => {}
         }
     }
 
@@ -357,6 +394,10 @@ impl BindingTypeMaxCountValidator {
         self.storage_buffers.merge(&other.storage_buffers);
         self.storage_textures.merge(&other.storage_textures);
         self.uniform_buffers.merge(&other.uniform_buffers);
+        self.binding_array_elements
+            .merge(&other.binding_array_elements);
+        self.binding_array_sampler_elements
+            .merge(&other.binding_array_sampler_elements);
     }
 
     pub(crate) fn validate(&self, limits: &wgt::Limits) -> Result<(), BindingTypeMaxCountError> {
@@ -400,14 +441,28 @@ impl BindingTypeMaxCountValidator {
             limits.max_uniform_buffers_per_shader_stage,
             BindingTypeMaxCountErrorKind::UniformBuffers,
         )?;
+        self.binding_array_elements.validate(
+            limits.max_binding_array_elements_per_shader_stage,
+            BindingTypeMaxCountErrorKind::BindingArrayElements,
+        )?;
+        self.binding_array_sampler_elements.validate(
+            limits.max_binding_array_sampler_elements_per_shader_stage,
+            BindingTypeMaxCountErrorKind::BindingArraySamplerElements,
+        )?;
         Ok(())
     }
 }
 
 /// Bindable resource and the slot to bind it to.
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "trace", derive(Serialize))]
-#[cfg_attr(feature = "replay", derive(Deserialize))]
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(
+    any(feature = "serial-pass", feature = "trace"),
+    derive(serde::Serialize)
+)]
+#[cfg_attr(
+    any(feature = "serial-pass", feature = "replay"),
+    derive(serde::Deserialize)
+)]
 pub struct BindGroupEntry<'a> {
     /// Slot for which binding provides resource. Corresponds to an entry of the same
     /// binding index in the [`BindGroupLayoutDescriptor`].
@@ -444,7 +499,8 @@ pub struct BindGroupLayoutDescriptor<'a> {
     pub entries: Cow<'a, [wgt::BindGroupLayoutEntry]>,
 }
 
-pub type BindGroupLayouts<A> = crate::storage::Storage<BindGroupLayout<A>, BindGroupLayoutId>;
+pub type BindGroupLayouts<'a, A> =
+    crate::storage::Storage<'a, BindGroupLayout<A>, BindGroupLayoutId>;
 
 /// Bind group layout.
 #[derive(Debug)]
@@ -734,8 +790,14 @@ impl<A: HalApi> Resource<PipelineLayoutId> for PipelineLayout<A> {
 
 #[repr(C)]
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
-#[cfg_attr(feature = "trace", derive(Serialize))]
-#[cfg_attr(feature = "replay", derive(Deserialize))]
+#[cfg_attr(
+    any(feature = "serial-pass", feature = "trace"),
+    derive(serde::Serialize)
+)]
+#[cfg_attr(
+    any(feature = "serial-pass", feature = "replay"),
+    derive(serde::Deserialize)
+)]
 pub struct BufferBinding {
     pub buffer_id: BufferId,
     pub offset: wgt::BufferAddress,
@@ -744,9 +806,15 @@ pub struct BufferBinding {
 
 // Note: Duplicated in `wgpu-rs` as `BindingResource`
 // They're different enough that it doesn't make sense to share a common type
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "trace", derive(serde::Serialize))]
-#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(
+    any(feature = "serial-pass", feature = "trace"),
+    derive(serde::Serialize)
+)]
+#[cfg_attr(
+    any(feature = "serial-pass", feature = "replay"),
+    derive(serde::Deserialize)
+)]
 pub enum BindingResource<'a> {
     Buffer(BufferBinding),
     BufferArray(Cow<'a, [BufferBinding]>),
@@ -754,6 +822,57 @@ pub enum BindingResource<'a> {
     SamplerArray(Cow<'a, [SamplerId]>),
     TextureView(TextureViewId),
     TextureViewArray(Cow<'a, [TextureViewId]>),
+    AccelerationStructure(TlasId),
+}
+
+impl BindingResource<'_> {
+    pub(crate) fn to_static(&self) -> BindingResource<'static> {
+        match *self {
+            Self::Buffer(ref binding) => BindingResource::Buffer(binding.clone()),
+            Self::BufferArray(ref bindings) => {
+                BindingResource::BufferArray(Cow::Owned(bindings.to_vec()))
+            }
+            Self::Sampler(id) => BindingResource::Sampler(id),
+            Self::SamplerArray(ref ids) => BindingResource::SamplerArray(Cow::Owned(ids.to_vec())),
+            Self::TextureView(id) => BindingResource::TextureView(id),
+            Self::TextureViewArray(ref ids) => {
+                BindingResource::TextureViewArray(Cow::Owned(ids.to_vec()))
+            }
+            Self::AccelerationStructure(id) => BindingResource::AccelerationStructure(id),
+        }
+    }
+}
+
+/// Identifies a bind group in [`Device::bind_group_pool`].
+///
+/// Two descriptors produce the same key if they use the same layout and
+/// bind the same resources to the same slots, regardless of their label or
+/// the order in which the entries were listed.
+///
+/// [`Device::bind_group_pool`]: crate::device::resource::Device::bind_group_pool
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub(crate) struct BindGroupCacheKey {
+    layout: BindGroupLayoutId,
+    entries: Vec<BindGroupEntry<'static>>,
+}
+
+impl BindGroupCacheKey {
+    pub(crate) fn new<A: HalApi>(layout: &BindGroupLayout<A>, desc: &BindGroupDescriptor) -> Self {
+        let mut entries = desc
+            .entries
+            .iter()
+            .map(|entry| BindGroupEntry {
+                binding: entry.binding,
+                resource: entry.resource.to_static(),
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.binding);
+
+        Self {
+            layout: layout.as_info().id(),
+            entries,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Error)]
@@ -844,10 +963,20 @@ pub struct BindGroup<A: HalApi> {
     /// Actual binding sizes for buffers that don't have `min_binding_size`
     /// specified in BGL. Listed in the order of iteration of `BGL.entries`.
     pub(crate) late_buffer_binding_sizes: Vec<wgt::BufferSize>,
+    /// Set if this bind group lives in [`Device::bind_group_pool`], so that
+    /// dropping it also evicts it from the pool.
+    ///
+    /// [`Device::bind_group_pool`]: crate::device::resource::Device::bind_group_pool
+    pub(crate) cache_key: Option<BindGroupCacheKey>,
 }
 
 impl<A: HalApi> Drop for BindGroup<A> {
     fn drop(&mut self) {
+        if let Some(key) = self.cache_key.take() {
+            if let Some(pool) = self.device.bind_group_pool.as_ref() {
+                pool.remove(&key);
+            }
+        }
         if let Some(raw) = self.raw.take() {
             resource_log!("Destroy raw BindGroup {:?}", self.info.label());
 
@@ -946,6 +1075,10 @@ pub enum GetBindGroupLayoutError {
     InvalidGroupIndex(u32),
 }
 
+#[derive(Clone, Debug, Error)]
+#[error("Bind group layout is invalid")]
+pub struct InvalidBindGroupLayout;
+
 #[derive(Clone, Debug, Error, Eq, PartialEq)]
 #[error("Buffer is bound with size {bound_size} where the shader expects {shader_size} in group[{group_index}] compact index {compact_index}")]
 pub struct LateMinBufferBindingSizeMismatch {
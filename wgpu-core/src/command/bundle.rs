@@ -84,11 +84,11 @@ use crate::{
     binding_model::{buffer_binding_type_alignment, BindGroup, BindGroupLayout, PipelineLayout},
     command::{
         BasePass, BindGroupStateChange, ColorAttachmentError, DrawError, MapPassErr,
-        PassErrorScope, RenderCommand, RenderCommandError, StateChange,
+        PassErrorScope, QueryUseError, RenderCommand, RenderCommandError, StateChange,
     },
     conv,
     device::{
-        AttachmentData, Device, DeviceError, MissingDownlevelFlags,
+        AttachmentData, Device, DeviceError, MissingDownlevelFlags, MissingFeatures,
         RenderPassCompatibilityCheckType, RenderPassContext, SHADER_STAGE_COUNT,
     },
     error::{ErrorFormatter, PrettyError},
@@ -97,7 +97,7 @@ use crate::{
     id::{self, RenderBundleId},
     init_tracker::{BufferInitTrackerAction, MemoryInitKind, TextureInitTrackerAction},
     pipeline::{self, PipelineFlags, RenderPipeline},
-    resource::{Resource, ResourceInfo, ResourceType},
+    resource::{QuerySet, Resource, ResourceInfo, ResourceType},
     resource_log,
     track::RenderBundleScope,
     validation::check_buffer_usage,
@@ -267,11 +267,11 @@ impl RenderBundleEncoder {
 
         let mut state = State {
             trackers: RenderBundleScope::new(
-                &*buffer_guard,
-                &*texture_guard,
-                &*bind_group_guard,
-                &*pipeline_guard,
-                &*query_set_guard,
+                &buffer_guard,
+                &texture_guard,
+                &bind_group_guard,
+                &pipeline_guard,
+                &query_set_guard,
             ),
             pipeline: None,
             bind: (0..hal::MAX_BIND_GROUPS).map(|_| None).collect(),
@@ -285,6 +285,8 @@ impl RenderBundleEncoder {
 
         let base = self.base.as_ref();
         let mut next_dynamic_offset = 0;
+        let mut active_occlusion_query = None;
+        let mut occlusion_queries = Vec::new();
 
         for &command in base.commands {
             match command {
@@ -299,7 +301,7 @@ impl RenderBundleEncoder {
                         .trackers
                         .bind_groups
                         .write()
-                        .add_single(&*bind_group_guard, bind_group_id)
+                        .add_single(&bind_group_guard, bind_group_id)
                         .ok_or(RenderCommandError::InvalidBindGroup(bind_group_id))
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(bind_group.device.info.id())
@@ -365,7 +367,7 @@ impl RenderBundleEncoder {
                         .trackers
                         .render_pipelines
                         .write()
-                        .add_single(&*pipeline_guard, pipeline_id)
+                        .add_single(&pipeline_guard, pipeline_id)
                         .ok_or(RenderCommandError::InvalidPipeline(pipeline_id))
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(pipeline.device.info.id())
@@ -385,6 +387,13 @@ impl RenderBundleEncoder {
                             .map_pass_err(scope);
                     }
 
+                    if pipeline.flags.contains(PipelineFlags::MESH_SHADER) {
+                        return Err(RenderCommandError::Unimplemented(
+                            "mesh pipelines in render bundles",
+                        ))
+                        .map_pass_err(scope);
+                    }
+
                     let pipeline_state = PipelineState::new(pipeline);
 
                     commands.push(command);
@@ -408,7 +417,7 @@ impl RenderBundleEncoder {
                         .trackers
                         .buffers
                         .write()
-                        .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDEX)
+                        .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDEX)
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(buffer.device.info.id())
                         .map_pass_err(scope)?;
@@ -447,7 +456,7 @@ impl RenderBundleEncoder {
                         .trackers
                         .buffers
                         .write()
-                        .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::VERTEX)
+                        .merge_single(&buffer_guard, buffer_id, hal::BufferUses::VERTEX)
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(buffer.device.info.id())
                         .map_pass_err(scope)?;
@@ -564,14 +573,19 @@ impl RenderBundleEncoder {
                 RenderCommand::MultiDrawIndirect {
                     buffer_id,
                     offset,
-                    count: None,
-                    indexed: false,
+                    count,
+                    indexed,
                 } => {
                     let scope = PassErrorScope::Draw {
-                        indexed: false,
+                        indexed,
                         indirect: true,
                         pipeline: state.pipeline_id(),
                     };
+                    if count.is_some() {
+                        device
+                            .require_features(wgt::Features::MULTI_DRAW_INDIRECT)
+                            .map_pass_err(scope)?;
+                    }
                     device
                         .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
                         .map_pass_err(scope)?;
@@ -583,38 +597,71 @@ impl RenderBundleEncoder {
                         .trackers
                         .buffers
                         .write()
-                        .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                        .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(buffer.device.info.id())
                         .map_pass_err(scope)?;
                     check_buffer_usage(buffer.usage, wgt::BufferUsages::INDIRECT)
                         .map_pass_err(scope)?;
 
+                    let end_offset = offset + indirect_stride(indexed) * count.map_or(1, |c| c.get()) as u64;
+                    if end_offset > buffer.size {
+                        return Err(RenderBundleErrorInner::IndirectBufferOverrun {
+                            count,
+                            offset,
+                            end_offset,
+                            buffer_size: buffer.size,
+                        })
+                        .map_pass_err(scope);
+                    }
                     buffer_memory_init_actions.extend(buffer.initialization_status.read().create_action(
                         buffer,
-                        offset..(offset + mem::size_of::<wgt::DrawIndirectArgs>() as u64),
+                        offset..end_offset,
                         MemoryInitKind::NeedsInitializedMemory,
                     ));
 
+                    if indexed {
+                        let index = match state.index {
+                            Some(ref mut index) => index,
+                            None => return Err(DrawError::MissingIndexBuffer).map_pass_err(scope),
+                        };
+                        commands.extend(index.flush());
+                    }
                     commands.extend(state.flush_vertices());
                     commands.extend(state.flush_binds(used_bind_groups, base.dynamic_offsets));
                     commands.push(command);
                 }
-                RenderCommand::MultiDrawIndirect {
+                RenderCommand::MultiDrawIndirectCount {
                     buffer_id,
                     offset,
-                    count: None,
-                    indexed: true,
+                    count_buffer_id,
+                    count_buffer_offset,
+                    max_count,
+                    indexed,
                 } => {
                     let scope = PassErrorScope::Draw {
-                        indexed: true,
+                        indexed,
                         indirect: true,
                         pipeline: state.pipeline_id(),
                     };
+                    device
+                        .require_features(wgt::Features::MULTI_DRAW_INDIRECT_COUNT)
+                        .map_pass_err(scope)?;
                     device
                         .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
                         .map_pass_err(scope)?;
 
+                    if offset % 4 != 0 {
+                        return Err(RenderBundleErrorInner::UnalignedIndirectBufferOffset(offset))
+                            .map_pass_err(scope);
+                    }
+                    if count_buffer_offset % 4 != 0 {
+                        return Err(RenderBundleErrorInner::UnalignedIndirectCountBufferOffset(
+                            count_buffer_offset,
+                        ))
+                        .map_pass_err(scope);
+                    }
+
                     let pipeline = state.pipeline(scope)?;
                     let used_bind_groups = pipeline.used_bind_groups;
 
@@ -622,55 +669,130 @@ impl RenderBundleEncoder {
                         .trackers
                         .buffers
                         .write()
-                        .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                        .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(buffer.device.info.id())
                         .map_pass_err(scope)?;
                     check_buffer_usage(buffer.usage, wgt::BufferUsages::INDIRECT)
                         .map_pass_err(scope)?;
 
+                    let count_buffer = state
+                        .trackers
+                        .buffers
+                        .write()
+                        .merge_single(&buffer_guard, count_buffer_id, hal::BufferUses::INDIRECT)
+                        .map_pass_err(scope)?;
+                    self.check_valid_to_use(count_buffer.device.info.id())
+                        .map_pass_err(scope)?;
+                    check_buffer_usage(count_buffer.usage, wgt::BufferUsages::INDIRECT)
+                        .map_pass_err(scope)?;
+
+                    // `max_count` draws are read at most, as the GPU clamps the
+                    // count read from `count_buffer` to it.
+                    let end_offset = offset.saturating_add(indirect_stride(indexed) * max_count as u64);
+                    if end_offset > buffer.size {
+                        return Err(RenderBundleErrorInner::IndirectBufferOverrun {
+                            count: None,
+                            offset,
+                            end_offset,
+                            buffer_size: buffer.size,
+                        })
+                        .map_pass_err(scope);
+                    }
+                    let end_count_offset = count_buffer_offset.saturating_add(4);
+                    if end_count_offset > count_buffer.size {
+                        return Err(RenderBundleErrorInner::IndirectCountBufferOverrun {
+                            begin_count_offset: count_buffer_offset,
+                            end_count_offset,
+                            count_buffer_size: count_buffer.size,
+                        })
+                        .map_pass_err(scope);
+                    }
                     buffer_memory_init_actions.extend(buffer.initialization_status.read().create_action(
                         buffer,
-                        offset..(offset + mem::size_of::<wgt::DrawIndirectArgs>() as u64),
+                        offset..end_offset,
+                        MemoryInitKind::NeedsInitializedMemory,
+                    ));
+                    buffer_memory_init_actions.extend(count_buffer.initialization_status.read().create_action(
+                        count_buffer,
+                        count_buffer_offset..end_count_offset,
                         MemoryInitKind::NeedsInitializedMemory,
                     ));
 
-                    let index = match state.index {
-                        Some(ref mut index) => index,
-                        None => return Err(DrawError::MissingIndexBuffer).map_pass_err(scope),
-                    };
-
-                    commands.extend(index.flush());
+                    if indexed {
+                        let index = match state.index {
+                            Some(ref mut index) => index,
+                            None => return Err(DrawError::MissingIndexBuffer).map_pass_err(scope),
+                        };
+                        commands.extend(index.flush());
+                    }
                     commands.extend(state.flush_vertices());
                     commands.extend(state.flush_binds(used_bind_groups, base.dynamic_offsets));
                     commands.push(command);
                 }
-                RenderCommand::MultiDrawIndirect { .. }
-                | RenderCommand::MultiDrawIndirectCount { .. } => unimplemented!(),
+                RenderCommand::BeginOcclusionQuery { query_index } => {
+                    let scope = PassErrorScope::BeginOcclusionQuery;
+                    device
+                        .require_features(wgt::Features::RENDER_BUNDLE_OCCLUSION_QUERY)
+                        .map_pass_err(scope)?;
+                    if let Some(active_query_index) = active_occlusion_query.replace(query_index) {
+                        return Err(QueryUseError::AlreadyStarted {
+                            active_query_index,
+                            new_query_index: query_index,
+                        })
+                        .map_pass_err(scope);
+                    }
+                    occlusion_queries.push(query_index);
+                    commands.push(command);
+                }
+                RenderCommand::EndOcclusionQuery => {
+                    let scope = PassErrorScope::EndOcclusionQuery;
+                    if active_occlusion_query.take().is_none() {
+                        return Err(QueryUseError::AlreadyStopped).map_pass_err(scope);
+                    }
+                    commands.push(command);
+                }
+                RenderCommand::DrawMeshTasks { .. }
+                | RenderCommand::DrawMeshTasksIndirect { .. }
+                | RenderCommand::ExecuteIndirect { .. } => unimplemented!(),
                 RenderCommand::PushDebugGroup { color: _, len: _ } => unimplemented!(),
                 RenderCommand::InsertDebugMarker { color: _, len: _ } => unimplemented!(),
                 RenderCommand::PopDebugGroup => unimplemented!(),
                 RenderCommand::WriteTimestamp { .. } // Must check the TIMESTAMP_QUERY_INSIDE_PASSES feature
-                | RenderCommand::BeginOcclusionQuery { .. }
-                | RenderCommand::EndOcclusionQuery
                 | RenderCommand::BeginPipelineStatisticsQuery { .. }
                 | RenderCommand::EndPipelineStatisticsQuery => unimplemented!(),
+                RenderCommand::BeginConditionalRendering { .. }
+                | RenderCommand::EndConditionalRendering => unimplemented!(),
+                RenderCommand::SetVertexCaptureBuffer { .. }
+                | RenderCommand::BeginVertexCapture
+                | RenderCommand::EndVertexCapture => unimplemented!(),
                 RenderCommand::ExecuteBundle(_)
+                | RenderCommand::SetBindGroupImmediate { .. }
                 | RenderCommand::SetBlendConstant(_)
                 | RenderCommand::SetStencilReference(_)
                 | RenderCommand::SetViewport { .. }
-                | RenderCommand::SetScissor(_) => unreachable!("not supported by a render bundle"),
+                | RenderCommand::SetScissor(_)
+                | RenderCommand::SetShadingRate(_) => unreachable!("not supported by a render bundle"),
             }
         }
 
+        if let Some(query_index) = active_occlusion_query {
+            return Err(QueryUseError::NotEnded { query_index })
+                .map_pass_err(PassErrorScope::Bundle);
+        }
+
         Ok(RenderBundle {
             base: BasePass {
                 label: desc.label.as_ref().map(|cow| cow.to_string()),
                 commands,
                 dynamic_offsets: state.flat_dynamic_offsets,
                 string_data: Vec::new(),
-                push_constant_data: Vec::new(),
+                // The `SetPushConstant` commands refer to values recorded by the encoder.
+                push_constant_data: base.push_constant_data.to_vec(),
+                indirect_arguments: Vec::new(),
+                immediate_bind_group_entries: Vec::new(),
             },
+            occlusion_queries,
             is_depth_read_only: self.is_depth_read_only,
             is_stencil_read_only: self.is_stencil_read_only,
             device: device.clone(),
@@ -761,6 +883,9 @@ pub struct RenderBundle<A: HalApi> {
     pub(crate) used: RenderBundleScope<A>,
     pub(super) buffer_memory_init_actions: Vec<BufferInitTrackerAction<A>>,
     pub(super) texture_memory_init_actions: Vec<TextureInitTrackerAction<A>>,
+    /// The indices of the occlusion queries this bundle begins, in the
+    /// occlusion query set of the render pass executing it.
+    pub(super) occlusion_queries: Vec<u32>,
     pub(super) context: RenderPassContext,
     pub(crate) info: ResourceInfo<RenderBundleId>,
     discard_hal_labels: bool,
@@ -792,10 +917,18 @@ impl<A: HalApi> RenderBundle<A> {
     /// Note that the function isn't expected to fail, generally.
     /// All the validation has already been done by this point.
     /// The only failure condition is if some of the used buffers are destroyed.
-    pub(super) unsafe fn execute(&self, raw: &mut A::CommandEncoder) -> Result<(), ExecutionError> {
+    ///
+    /// `occlusion_query_set` is the occlusion query set of the render pass,
+    /// which must have been validated for [`RenderBundle::occlusion_queries`].
+    pub(super) unsafe fn execute(
+        &self,
+        raw: &mut A::CommandEncoder,
+        occlusion_query_set: Option<&QuerySet<A>>,
+    ) -> Result<(), ExecutionError> {
         let trackers = &self.used;
         let mut offsets = self.base.dynamic_offsets.as_slice();
         let mut pipeline_layout = None::<Arc<PipelineLayout<A>>>;
+        let mut active_occlusion_query = None;
         if !self.discard_hal_labels {
             if let Some(ref label) = self.base.label {
                 unsafe { raw.begin_debug_marker(label) };
@@ -830,6 +963,9 @@ impl<A: HalApi> RenderBundle<A> {
                     let render_pipelines = trackers.render_pipelines.read();
                     let pipeline = render_pipelines.get(pipeline_id).unwrap();
                     unsafe { raw.set_render_pipeline(pipeline.raw()) };
+                    if let Some(ref debug_printf) = self.device.debug_printf {
+                        unsafe { debug_printf.bind(raw, pipeline.layout.raw()) };
+                    }
 
                     pipeline_layout = Some(pipeline.layout.clone());
                 }
@@ -938,8 +1074,8 @@ impl<A: HalApi> RenderBundle<A> {
                 RenderCommand::MultiDrawIndirect {
                     buffer_id,
                     offset,
-                    count: None,
-                    indexed: false,
+                    count,
+                    indexed,
                 } => {
                     let buffers = trackers.buffers.read();
                     let buffer = buffers
@@ -947,13 +1083,19 @@ impl<A: HalApi> RenderBundle<A> {
                         .ok_or(ExecutionError::DestroyedBuffer(buffer_id))?
                         .raw(&snatch_guard)
                         .ok_or(ExecutionError::DestroyedBuffer(buffer_id))?;
-                    unsafe { raw.draw_indirect(buffer, offset, 1) };
+                    let count = count.map_or(1, |c| c.get());
+                    match indexed {
+                        false => unsafe { raw.draw_indirect(buffer, offset, count) },
+                        true => unsafe { raw.draw_indexed_indirect(buffer, offset, count) },
+                    }
                 }
-                RenderCommand::MultiDrawIndirect {
+                RenderCommand::MultiDrawIndirectCount {
                     buffer_id,
                     offset,
-                    count: None,
-                    indexed: true,
+                    count_buffer_id,
+                    count_buffer_offset,
+                    max_count,
+                    indexed,
                 } => {
                     let buffers = trackers.buffers.read();
                     let buffer = buffers
@@ -961,29 +1103,76 @@ impl<A: HalApi> RenderBundle<A> {
                         .ok_or(ExecutionError::DestroyedBuffer(buffer_id))?
                         .raw(&snatch_guard)
                         .ok_or(ExecutionError::DestroyedBuffer(buffer_id))?;
-                    unsafe { raw.draw_indexed_indirect(buffer, offset, 1) };
+                    let count_buffer = buffers
+                        .get(count_buffer_id)
+                        .ok_or(ExecutionError::DestroyedBuffer(count_buffer_id))?
+                        .raw(&snatch_guard)
+                        .ok_or(ExecutionError::DestroyedBuffer(count_buffer_id))?;
+                    match indexed {
+                        false => unsafe {
+                            raw.draw_indirect_count(
+                                buffer,
+                                offset,
+                                count_buffer,
+                                count_buffer_offset,
+                                max_count,
+                            )
+                        },
+                        true => unsafe {
+                            raw.draw_indexed_indirect_count(
+                                buffer,
+                                offset,
+                                count_buffer,
+                                count_buffer_offset,
+                                max_count,
+                            )
+                        },
+                    }
+                }
+                RenderCommand::DrawMeshTasks { .. }
+                | RenderCommand::DrawMeshTasksIndirect { .. } => {
+                    return Err(ExecutionError::Unimplemented("mesh-shading"))
                 }
-                RenderCommand::MultiDrawIndirect { .. }
-                | RenderCommand::MultiDrawIndirectCount { .. } => {
-                    return Err(ExecutionError::Unimplemented("multi-draw-indirect"))
+                RenderCommand::ExecuteIndirect { .. } => {
+                    return Err(ExecutionError::Unimplemented("execute-indirect"))
                 }
                 RenderCommand::PushDebugGroup { .. }
                 | RenderCommand::InsertDebugMarker { .. }
                 | RenderCommand::PopDebugGroup => {
                     return Err(ExecutionError::Unimplemented("debug-markers"))
                 }
+                RenderCommand::BeginOcclusionQuery { query_index } => {
+                    let query_set = occlusion_query_set.unwrap();
+                    unsafe { raw.begin_query(query_set.raw(), query_index) };
+                    active_occlusion_query = Some(query_index);
+                }
+                RenderCommand::EndOcclusionQuery => {
+                    let query_set = occlusion_query_set.unwrap();
+                    unsafe {
+                        raw.end_query(query_set.raw(), active_occlusion_query.take().unwrap())
+                    };
+                }
                 RenderCommand::WriteTimestamp { .. }
-                | RenderCommand::BeginOcclusionQuery { .. }
-                | RenderCommand::EndOcclusionQuery
                 | RenderCommand::BeginPipelineStatisticsQuery { .. }
                 | RenderCommand::EndPipelineStatisticsQuery => {
                     return Err(ExecutionError::Unimplemented("queries"))
                 }
+                RenderCommand::BeginConditionalRendering { .. }
+                | RenderCommand::EndConditionalRendering => {
+                    return Err(ExecutionError::Unimplemented("conditional-rendering"))
+                }
+                RenderCommand::SetVertexCaptureBuffer { .. }
+                | RenderCommand::BeginVertexCapture
+                | RenderCommand::EndVertexCapture => {
+                    return Err(ExecutionError::Unimplemented("vertex-capture"))
+                }
                 RenderCommand::ExecuteBundle(_)
+                | RenderCommand::SetBindGroupImmediate { .. }
                 | RenderCommand::SetBlendConstant(_)
                 | RenderCommand::SetStencilReference(_)
                 | RenderCommand::SetViewport { .. }
-                | RenderCommand::SetScissor(_) => unreachable!(),
+                | RenderCommand::SetScissor(_)
+                | RenderCommand::SetShadingRate(_) => unreachable!(),
             }
         }
 
@@ -1009,6 +1198,14 @@ impl<A: HalApi> Resource<RenderBundleId> for RenderBundle<A> {
     }
 }
 
+/// Returns the size of the arguments of an indirect draw.
+fn indirect_stride(indexed: bool) -> u64 {
+    match indexed {
+        false => mem::size_of::<wgt::DrawIndirectArgs>() as u64,
+        true => mem::size_of::<wgt::DrawIndexedIndirectArgs>() as u64,
+    }
+}
+
 /// A render bundle's current index buffer state.
 ///
 /// [`RenderBundleEncoder::finish`] records the currently set index buffer here,
@@ -1429,6 +1626,28 @@ pub(super) enum RenderBundleErrorInner {
     Draw(#[from] DrawError),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
+    QueryUse(#[from] QueryUseError),
+    #[error("Indirect draw uses bytes {offset}..{end_offset} {} which overruns indirect buffer of size {buffer_size}",
+        count.map_or_else(String::new, |v| format!("(using count {v})")))]
+    IndirectBufferOverrun {
+        count: Option<NonZeroU32>,
+        offset: u64,
+        end_offset: u64,
+        buffer_size: u64,
+    },
+    #[error("Indirect draw uses bytes {begin_count_offset}..{end_count_offset} which overruns indirect buffer of size {count_buffer_size}")]
+    IndirectCountBufferOverrun {
+        begin_count_offset: u64,
+        end_count_offset: u64,
+        count_buffer_size: u64,
+    },
+    #[error("Indirect buffer offset {0} is not a multiple of 4")]
+    UnalignedIndirectBufferOffset(wgt::BufferAddress),
+    #[error("Indirect count buffer offset {0} is not a multiple of 4")]
+    UnalignedIndirectCountBufferOffset(wgt::BufferAddress),
 }
 
 impl<T> From<T> for RenderBundleErrorInner
@@ -1479,7 +1698,7 @@ where
 pub mod bundle_ffi {
     use super::{RenderBundleEncoder, RenderCommand};
     use crate::{id, RawString};
-    use std::{convert::TryInto, slice};
+    use std::{convert::TryInto, num::NonZeroU32, slice};
     use wgt::{BufferAddress, BufferSize, DynamicOffset, IndexFormat};
 
     /// # Safety
@@ -1660,6 +1879,96 @@ pub mod bundle_ffi {
         });
     }
 
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_bundle_multi_draw_indirect(
+        bundle: &mut RenderBundleEncoder,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count: u32,
+    ) {
+        bundle.base.commands.push(RenderCommand::MultiDrawIndirect {
+            buffer_id,
+            offset,
+            count: NonZeroU32::new(count),
+            indexed: false,
+        });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_bundle_multi_draw_indexed_indirect(
+        bundle: &mut RenderBundleEncoder,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count: u32,
+    ) {
+        bundle.base.commands.push(RenderCommand::MultiDrawIndirect {
+            buffer_id,
+            offset,
+            count: NonZeroU32::new(count),
+            indexed: true,
+        });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_bundle_multi_draw_indirect_count(
+        bundle: &mut RenderBundleEncoder,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        bundle
+            .base
+            .commands
+            .push(RenderCommand::MultiDrawIndirectCount {
+                buffer_id,
+                offset,
+                count_buffer_id,
+                count_buffer_offset,
+                max_count,
+                indexed: false,
+            });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_bundle_multi_draw_indexed_indirect_count(
+        bundle: &mut RenderBundleEncoder,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        bundle
+            .base
+            .commands
+            .push(RenderCommand::MultiDrawIndirectCount {
+                buffer_id,
+                offset,
+                count_buffer_id,
+                count_buffer_offset,
+                max_count,
+                indexed: true,
+            });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_bundle_begin_occlusion_query(
+        bundle: &mut RenderBundleEncoder,
+        query_index: u32,
+    ) {
+        bundle
+            .base
+            .commands
+            .push(RenderCommand::BeginOcclusionQuery { query_index });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_bundle_end_occlusion_query(bundle: &mut RenderBundleEncoder) {
+        bundle.base.commands.push(RenderCommand::EndOcclusionQuery);
+    }
+
     /// # Safety
     ///
     /// This function is unsafe as there is no guarantee that the given `label`
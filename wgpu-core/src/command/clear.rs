@@ -93,6 +93,16 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         if let Some(ref mut list) = cmd_buf_data.commands {
             list.push(TraceCommand::ClearBuffer { dst, offset, size });
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            let dst = hub.buffers.label_for_resource(dst);
+            match size {
+                Some(size) => disassembly.line(format_args!(
+                    "clear buffer {dst} bytes {offset}..{}",
+                    offset + size
+                )),
+                None => disassembly.line(format_args!("clear buffer {dst} bytes {offset}..")),
+            }
+        }
 
         let (dst_buffer, dst_pending) = {
             let buffer_guard = hub.buffers.read();
@@ -149,6 +159,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 MemoryInitKind::ImplicitlyInitialized,
             ),
         );
+        cmd_buf_data
+            .mappable_buffer_uses
+            .extend(dst_buffer.mappable_range_use(offset..end));
 
         // actual hal barrier & operation
         let dst_barrier = dst_pending.map(|pending| pending.into_hal(&dst_buffer, &snatch_guard));
@@ -183,6 +196,12 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 subresource_range: *subresource_range,
             });
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!(
+                "clear texture {} {subresource_range:?}",
+                hub.textures.label_for_resource(dst)
+            ));
+        }
 
         if !cmd_buf.support_clear_texture {
             return Err(ClearError::MissingClearTextureFeature);
@@ -337,8 +356,8 @@ fn clear_texture_via_buffer_copies<A: HalApi>(
 ) {
     assert!(!texture_desc.format.is_depth_stencil_format());
 
-    if texture_desc.format == wgt::TextureFormat::NV12 {
-        // TODO: Currently COPY_DST for NV12 textures is unsupported.
+    if texture_desc.format.is_multi_planar_format() {
+        // TODO: Currently COPY_DST for planar textures is unsupported.
         return;
     }
 
@@ -469,6 +488,8 @@ fn clear_texture_via_render_passes<A: HalApi>(
                             ),
                             usage: hal::TextureUses::DEPTH_STENCIL_WRITE,
                         },
+                        resolve_target: None,
+                        resolve_mode: wgt::ResolveMode::default(),
                         depth_ops: hal::AttachmentOps::STORE,
                         stencil_ops: hal::AttachmentOps::STORE,
                         clear_value: (0.0, 0),
@@ -485,6 +506,7 @@ fn clear_texture_via_render_passes<A: HalApi>(
                     multiview: None,
                     timestamp_writes: None,
                     occlusion_query_set: None,
+                    shading_rate_attachment: None,
                 });
                 encoder.end_render_pass();
             }
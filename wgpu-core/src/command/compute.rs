@@ -309,6 +309,8 @@ impl<A: HalApi> State<A> {
 
     // `extra_buffer` is there to represent the indirect buffer that is also
     // part of the usage scope.
+    //
+    // Returns the number of inserted transitions.
     fn flush_states(
         &mut self,
         raw_encoder: &mut A::CommandEncoder,
@@ -316,7 +318,7 @@ impl<A: HalApi> State<A> {
         bind_group_guard: &Storage<BindGroup<A>, id::BindGroupId>,
         indirect_buffer: Option<id::BufferId>,
         snatch_guard: &SnatchGuard,
-    ) -> Result<(), UsageConflict> {
+    ) -> Result<usize, UsageConflict> {
         for id in self.binder.list_active() {
             unsafe { self.scope.merge_bind_group(&bind_group_guard[id].used)? };
             // Note: stateless trackers are not merged: the lifetime reference
@@ -341,8 +343,11 @@ impl<A: HalApi> State<A> {
 
         log::trace!("Encoding dispatch barriers");
 
-        CommandBuffer::drain_barriers(raw_encoder, base_trackers, snatch_guard);
-        Ok(())
+        Ok(CommandBuffer::drain_barriers(
+            raw_encoder,
+            base_trackers,
+            snatch_guard,
+        ))
     }
 }
 
@@ -393,11 +398,18 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             });
         }
 
+        if let Some(label) = base.label {
+            cmd_buf_data.record_debug_marker(device, label);
+        }
+
         let encoder = &mut cmd_buf_data.encoder;
         let status = &mut cmd_buf_data.status;
         let tracker = &mut cmd_buf_data.trackers;
         let buffer_memory_init_actions = &mut cmd_buf_data.buffer_memory_init_actions;
         let texture_memory_actions = &mut cmd_buf_data.texture_memory_actions;
+        let disassembly = &mut cmd_buf_data.disassembly;
+        // Barriers inserted before each dispatch, only collected for the disassembly.
+        let mut dispatch_barriers = Vec::new();
 
         // We automatically keep extending command buffers over time, and because
         // we want to insert a command buffer _before_ what we're about to record,
@@ -416,7 +428,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let mut state = State {
             binder: Binder::new(),
             pipeline: None,
-            scope: UsageScope::new(&*buffer_guard, &*texture_guard),
+            scope: UsageScope::new(&buffer_guard, &texture_guard),
             debug_scope_depth: 0,
         };
         let mut temp_offsets = Vec::new();
@@ -427,7 +439,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let timestamp_writes = if let Some(tw) = timestamp_writes {
             let query_set: &resource::QuerySet<A> = tracker
                 .query_sets
-                .add_single(&*query_set_guard, tw.query_set)
+                .add_single(&query_set_guard, tw.query_set)
                 .ok_or(ComputePassErrorInner::InvalidQuerySet(tw.query_set))
                 .map_pass_err(pass_scope)?;
 
@@ -462,15 +474,15 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let snatch_guard = device.snatchable_lock.read();
 
         tracker.set_size(
-            Some(&*buffer_guard),
-            Some(&*texture_guard),
+            Some(&buffer_guard),
+            Some(&texture_guard),
             None,
             None,
-            Some(&*bind_group_guard),
-            Some(&*pipeline_guard),
+            Some(&bind_group_guard),
+            Some(&pipeline_guard),
             None,
             None,
-            Some(&*query_set_guard),
+            Some(&query_set_guard),
         );
 
         let discard_hal_labels = self
@@ -519,7 +531,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
                     let bind_group = tracker
                         .bind_groups
-                        .add_single(&*bind_group_guard, bind_group_id)
+                        .add_single(&bind_group_guard, bind_group_id)
                         .ok_or(ComputePassErrorInner::InvalidBindGroup(index as usize))
                         .map_pass_err(scope)?;
                     bind_group
@@ -573,12 +585,15 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
                     let pipeline: &pipeline::ComputePipeline<A> = tracker
                         .compute_pipelines
-                        .add_single(&*pipeline_guard, pipeline_id)
+                        .add_single(&pipeline_guard, pipeline_id)
                         .ok_or(ComputePassErrorInner::InvalidPipeline(pipeline_id))
                         .map_pass_err(scope)?;
 
                     unsafe {
                         raw.set_compute_pipeline(pipeline.raw());
+                        if let Some(ref debug_printf) = device.debug_printf {
+                            debug_printf.bind(raw, pipeline.layout.raw());
+                        }
                     }
 
                     // Rebind resources
@@ -682,15 +697,18 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     };
                     state.is_ready().map_pass_err(scope)?;
 
-                    state
+                    let barrier_count = state
                         .flush_states(
                             raw,
                             &mut intermediate_trackers,
-                            &*bind_group_guard,
+                            &bind_group_guard,
                             None,
                             &snatch_guard,
                         )
                         .map_pass_err(scope)?;
+                    if disassembly.is_some() {
+                        dispatch_barriers.push(barrier_count);
+                    }
 
                     let groups_size_limit = cmd_buf.limits.max_compute_workgroups_per_dimension;
 
@@ -726,7 +744,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     let indirect_buffer = state
                         .scope
                         .buffers
-                        .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                        .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
                         .map_pass_err(scope)?;
                     check_buffer_usage(indirect_buffer.usage, wgt::BufferUsages::INDIRECT)
                         .map_pass_err(scope)?;
@@ -757,15 +775,18 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         ),
                     );
 
-                    state
+                    let barrier_count = state
                         .flush_states(
                             raw,
                             &mut intermediate_trackers,
-                            &*bind_group_guard,
+                            &bind_group_guard,
                             Some(buffer_id),
                             &snatch_guard,
                         )
                         .map_pass_err(scope)?;
+                    if disassembly.is_some() {
+                        dispatch_barriers.push(barrier_count);
+                    }
                     unsafe {
                         raw.dispatch_indirect(buf_raw, offset);
                     }
@@ -817,7 +838,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
                     let query_set: &resource::QuerySet<A> = tracker
                         .query_sets
-                        .add_single(&*query_set_guard, query_set_id)
+                        .add_single(&query_set_guard, query_set_id)
                         .ok_or(ComputePassErrorInner::InvalidQuerySet(query_set_id))
                         .map_pass_err(scope)?;
 
@@ -833,7 +854,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
                     let query_set: &resource::QuerySet<A> = tracker
                         .query_sets
-                        .add_single(&*query_set_guard, query_set_id)
+                        .add_single(&query_set_guard, query_set_id)
                         .ok_or(ComputePassErrorInner::InvalidQuerySet(query_set_id))
                         .map_pass_err(scope)?;
 
@@ -850,7 +871,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 ComputeCommand::EndPipelineStatisticsQuery => {
                     let scope = PassErrorScope::EndPipelineStatisticsQuery;
 
-                    end_pipeline_statistics_query(raw, &*query_set_guard, &mut active_query)
+                    end_pipeline_statistics_query(raw, &query_set_guard, &mut active_query)
                         .map_pass_err(scope)?;
                 }
             }
@@ -877,12 +898,24 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             &mut tracker.textures,
             device,
         );
-        CommandBuffer::insert_barriers_from_tracker(
+        let barrier_count = CommandBuffer::insert_barriers_from_tracker(
             transit,
             tracker,
             &intermediate_trackers,
             &snatch_guard,
         );
+        if let Some(disassembly) = disassembly {
+            // The listing looks up labels in the registries.
+            drop((
+                bind_group_guard,
+                pipeline_guard,
+                query_set_guard,
+                buffer_guard,
+                texture_guard,
+            ));
+            disassembly.barriers(barrier_count);
+            disassembly.compute_pass(hub, &base, &dispatch_barriers);
+        }
         // Close the command buffer, and swap it with the previous.
         encoder.close_and_swap().map_pass_err(pass_scope)?;
 
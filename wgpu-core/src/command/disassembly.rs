@@ -0,0 +1,524 @@
+/*! Textual listings of recorded command buffers.
+
+Once enabled with [`Global::device_set_command_disassembly`], every command
+encoder of the device writes a readable listing of the commands it records:
+passes and their attachments, the labels of pipelines, bind groups and other
+resources, draw and dispatch parameters, and the number of barriers wgpu
+inserted. Listings of two frames can be diffed to find what changed between
+them, without taking a full GPU capture.
+
+The listing is meant to be read by people, its format may change at any time.
+
+[`Global::device_set_command_disassembly`]: crate::global::Global::device_set_command_disassembly
+*/
+
+use std::fmt::{self, Write as _};
+
+use super::{
+    BasePassRef, ComputeCommand, ImageCopyBuffer, ImageCopyTexture, LoadOp, PassChannel,
+    RenderCommand, RenderPassColorAttachment, RenderPassDepthStencilAttachment, StoreOp,
+};
+use crate::{hal_api::HalApi, hub::Hub};
+
+/// The listing of a command buffer, written while its commands are recorded.
+pub(crate) struct Disassembly {
+    text: String,
+    depth: usize,
+}
+
+impl Disassembly {
+    pub(crate) fn new(label: Option<&str>) -> Self {
+        let mut disassembly = Self {
+            text: String::new(),
+            depth: 0,
+        };
+        disassembly.line(format_args!("command buffer {:?}", label.unwrap_or("")));
+        disassembly.depth = 1;
+        disassembly
+    }
+
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub(crate) fn line(&mut self, args: fmt::Arguments) {
+        for _ in 0..self.depth {
+            self.text.push_str("  ");
+        }
+        let _ = self.text.write_fmt(args);
+        self.text.push('\n');
+    }
+
+    pub(crate) fn push_debug_group(&mut self, label: &str) {
+        self.line(format_args!("push debug group {label:?}"));
+        self.depth += 1;
+    }
+
+    pub(crate) fn pop_debug_group(&mut self) {
+        // Unbalanced pops are reported by validation, not here.
+        self.depth = self.depth.saturating_sub(1).max(1);
+        self.line(format_args!("pop debug group"));
+    }
+
+    /// Records the barriers inserted before the next command.
+    pub(crate) fn barriers(&mut self, count: usize) {
+        if count != 0 {
+            self.line(format_args!("barriers: {count} transitions"));
+        }
+    }
+
+    pub(crate) fn render_pass<A: HalApi>(
+        &mut self,
+        hub: &Hub<A>,
+        base: &BasePassRef<RenderCommand>,
+        color_attachments: &[Option<RenderPassColorAttachment>],
+        depth_stencil_attachment: Option<&RenderPassDepthStencilAttachment>,
+    ) {
+        self.line(format_args!(
+            "begin render pass {:?}",
+            base.label.unwrap_or("")
+        ));
+        self.depth += 1;
+        for (index, attachment) in color_attachments.iter().enumerate() {
+            let Some(attachment) = attachment else {
+                continue;
+            };
+            let view = hub.texture_views.label_for_resource(attachment.view);
+            let channel = channel(&attachment.channel, |color| {
+                format!("{} {} {} {}", color.r, color.g, color.b, color.a)
+            });
+            match attachment.resolve_target {
+                Some(resolve_target) => {
+                    let resolve_target = hub.texture_views.label_for_resource(resolve_target);
+                    self.line(format_args!(
+                        "color attachment {index}: {view}, {channel}, resolved to {resolve_target}"
+                    ))
+                }
+                None => self.line(format_args!("color attachment {index}: {view}, {channel}")),
+            }
+        }
+        if let Some(attachment) = depth_stencil_attachment {
+            let view = hub.texture_views.label_for_resource(attachment.view);
+            let depth = channel(&attachment.depth, |depth| depth.to_string());
+            let stencil = channel(&attachment.stencil, |stencil| stencil.to_string());
+            match attachment.resolve {
+                Some(ref resolve) => {
+                    let resolve_target = hub.texture_views.label_for_resource(resolve.view);
+                    let mode = resolve.mode;
+                    self.line(format_args!(
+                        "depth stencil attachment: {view}, depth {depth}, stencil {stencil}, resolved to {resolve_target} with {mode:?}"
+                    ))
+                }
+                None => self.line(format_args!(
+                    "depth stencil attachment: {view}, depth {depth}, stencil {stencil}"
+                )),
+            }
+        }
+
+        let mut dynamic_offsets = base.dynamic_offsets;
+        let mut string_data = base.string_data;
+        let mut indirect_arguments = base.indirect_arguments;
+        let mut immediate_entries = base.immediate_bind_group_entries;
+        for command in base.commands {
+            match *command {
+                RenderCommand::SetBindGroup {
+                    index,
+                    num_dynamic_offsets,
+                    bind_group_id,
+                } => {
+                    let bind_group = hub.bind_groups.label_for_resource(bind_group_id);
+                    let (offsets, rest) =
+                        dynamic_offsets.split_at(num_dynamic_offsets.min(dynamic_offsets.len()));
+                    dynamic_offsets = rest;
+                    self.line(format_args!(
+                        "set bind group {index}: {bind_group}, dynamic offsets {offsets:?}"
+                    ));
+                }
+                RenderCommand::SetBindGroupImmediate { index, num_entries } => {
+                    let (entries, rest) =
+                        immediate_entries.split_at(num_entries.min(immediate_entries.len()));
+                    immediate_entries = rest;
+                    self.line(format_args!(
+                        "set immediate bind group {index}: {} entries",
+                        entries.len()
+                    ));
+                }
+                RenderCommand::SetPipeline(pipeline_id) => {
+                    let pipeline = hub.render_pipelines.label_for_resource(pipeline_id);
+                    self.line(format_args!("set pipeline {pipeline}"));
+                }
+                RenderCommand::SetIndexBuffer {
+                    buffer_id,
+                    index_format,
+                    offset,
+                    size,
+                } => {
+                    let buffer = hub.buffers.label_for_resource(buffer_id);
+                    self.line(format_args!(
+                        "set index buffer {buffer} {index_format:?}, {}",
+                        range(offset, size)
+                    ));
+                }
+                RenderCommand::SetVertexBuffer {
+                    slot,
+                    buffer_id,
+                    offset,
+                    size,
+                } => {
+                    let buffer = hub.buffers.label_for_resource(buffer_id);
+                    self.line(format_args!(
+                        "set vertex buffer {slot}: {buffer}, {}",
+                        range(offset, size)
+                    ));
+                }
+                RenderCommand::SetBlendConstant(ref color) => self.line(format_args!(
+                    "set blend constant {} {} {} {}",
+                    color.r, color.g, color.b, color.a
+                )),
+                RenderCommand::SetStencilReference(value) => {
+                    self.line(format_args!("set stencil reference {value}"))
+                }
+                RenderCommand::SetViewport {
+                    ref rect,
+                    depth_min,
+                    depth_max,
+                } => self.line(format_args!(
+                    "set viewport x {} y {} w {} h {}, depth {depth_min}..{depth_max}",
+                    rect.x, rect.y, rect.w, rect.h
+                )),
+                RenderCommand::SetScissor(ref rect) => self.line(format_args!(
+                    "set scissor x {} y {} w {} h {}",
+                    rect.x, rect.y, rect.w, rect.h
+                )),
+                RenderCommand::SetShadingRate(rate) => {
+                    self.line(format_args!("set shading rate {rate:?}"))
+                }
+                RenderCommand::SetPushConstant {
+                    stages,
+                    offset,
+                    size_bytes,
+                    values_offset: _,
+                } => self.line(format_args!(
+                    "set push constants {stages:?}, {size_bytes} bytes at {offset}"
+                )),
+                RenderCommand::Draw {
+                    vertex_count,
+                    instance_count,
+                    first_vertex,
+                    first_instance,
+                } => self.line(format_args!(
+                    "draw vertices {first_vertex}..{}, instances {first_instance}..{}",
+                    first_vertex as u64 + vertex_count as u64,
+                    first_instance as u64 + instance_count as u64
+                )),
+                RenderCommand::DrawIndexed {
+                    index_count,
+                    instance_count,
+                    first_index,
+                    base_vertex,
+                    first_instance,
+                } => self.line(format_args!(
+                    "draw indexed indices {first_index}..{}, base vertex {base_vertex}, instances {first_instance}..{}",
+                    first_index as u64 + index_count as u64,
+                    first_instance as u64 + instance_count as u64
+                )),
+                RenderCommand::MultiDrawIndirect {
+                    buffer_id,
+                    offset,
+                    count,
+                    indexed,
+                } => {
+                    let buffer = hub.buffers.label_for_resource(buffer_id);
+                    let indexed = if indexed { "indexed " } else { "" };
+                    match count {
+                        Some(count) => self.line(format_args!(
+                            "multi draw {indexed}indirect {count} draws from {buffer} at {offset}"
+                        )),
+                        None => self
+                            .line(format_args!("draw {indexed}indirect from {buffer} at {offset}")),
+                    }
+                }
+                RenderCommand::MultiDrawIndirectCount {
+                    buffer_id,
+                    offset,
+                    count_buffer_id,
+                    count_buffer_offset,
+                    max_count,
+                    indexed,
+                } => {
+                    let buffer = hub.buffers.label_for_resource(buffer_id);
+                    let count_buffer = hub.buffers.label_for_resource(count_buffer_id);
+                    let indexed = if indexed { "indexed " } else { "" };
+                    self.line(format_args!(
+                        "multi draw {indexed}indirect count from {buffer} at {offset}, \
+                        count from {count_buffer} at {count_buffer_offset}, at most {max_count} draws"
+                    ));
+                }
+                RenderCommand::DrawMeshTasks {
+                    group_count_x,
+                    group_count_y,
+                    group_count_z,
+                } => self.line(format_args!(
+                    "draw mesh tasks {group_count_x}x{group_count_y}x{group_count_z}"
+                )),
+                RenderCommand::DrawMeshTasksIndirect { buffer_id, offset } => {
+                    let buffer = hub.buffers.label_for_resource(buffer_id);
+                    self.line(format_args!(
+                        "draw mesh tasks indirect from {buffer} at {offset}"
+                    ));
+                }
+                RenderCommand::ExecuteIndirect {
+                    num_arguments,
+                    buffer_id,
+                    offset,
+                    count_buffer_id,
+                    count_buffer_offset,
+                    max_count,
+                } => {
+                    let (arguments, rest) = indirect_arguments
+                        .split_at(num_arguments.min(indirect_arguments.len()));
+                    indirect_arguments = rest;
+                    let buffer = hub.buffers.label_for_resource(buffer_id);
+                    match count_buffer_id {
+                        Some(count_buffer_id) => {
+                            let count_buffer = hub.buffers.label_for_resource(count_buffer_id);
+                            self.line(format_args!(
+                                "execute indirect {arguments:?} from {buffer} at {offset}, \
+                                count from {count_buffer} at {count_buffer_offset}, at most {max_count} draws"
+                            ));
+                        }
+                        None => self.line(format_args!(
+                            "execute indirect {arguments:?} {max_count} draws from {buffer} at {offset}"
+                        )),
+                    }
+                }
+                RenderCommand::PushDebugGroup { color: _, len } => {
+                    let label = take_string(&mut string_data, len);
+                    self.push_debug_group(&label);
+                }
+                RenderCommand::PopDebugGroup => self.pop_debug_group(),
+                RenderCommand::InsertDebugMarker { color: _, len } => {
+                    let label = take_string(&mut string_data, len);
+                    self.line(format_args!("insert debug marker {label:?}"));
+                }
+                RenderCommand::WriteTimestamp {
+                    query_set_id,
+                    query_index,
+                } => {
+                    let query_set = hub.query_sets.label_for_resource(query_set_id);
+                    self.line(format_args!("write timestamp {query_set} [{query_index}]"));
+                }
+                RenderCommand::BeginOcclusionQuery { query_index } => {
+                    self.line(format_args!("begin occlusion query [{query_index}]"))
+                }
+                RenderCommand::EndOcclusionQuery => self.line(format_args!("end occlusion query")),
+                RenderCommand::BeginPipelineStatisticsQuery {
+                    query_set_id,
+                    query_index,
+                } => {
+                    let query_set = hub.query_sets.label_for_resource(query_set_id);
+                    self.line(format_args!(
+                        "begin pipeline statistics query {query_set} [{query_index}]"
+                    ));
+                }
+                RenderCommand::EndPipelineStatisticsQuery => {
+                    self.line(format_args!("end pipeline statistics query"))
+                }
+                RenderCommand::BeginConditionalRendering { buffer_id, offset } => {
+                    let buffer = hub.buffers.label_for_resource(buffer_id);
+                    self.line(format_args!(
+                        "begin conditional rendering on {buffer} at {offset}"
+                    ));
+                }
+                RenderCommand::EndConditionalRendering => {
+                    self.line(format_args!("end conditional rendering"))
+                }
+                RenderCommand::SetVertexCaptureBuffer {
+                    slot,
+                    buffer_id,
+                    offset,
+                    size,
+                } => {
+                    let buffer = hub.buffers.label_for_resource(buffer_id);
+                    self.line(format_args!(
+                        "set vertex capture buffer {slot}: {buffer}, {}",
+                        range(offset, size)
+                    ));
+                }
+                RenderCommand::BeginVertexCapture => {
+                    self.line(format_args!("begin vertex capture"))
+                }
+                RenderCommand::EndVertexCapture => self.line(format_args!("end vertex capture")),
+                RenderCommand::ExecuteBundle(bundle_id) => {
+                    let bundle = hub.render_bundles.label_for_resource(bundle_id);
+                    self.line(format_args!("execute bundle {bundle}"));
+                }
+            }
+        }
+        self.depth -= 1;
+        self.line(format_args!("end render pass"));
+    }
+
+    /// Lists a compute pass, with the number of barriers inserted before
+    /// each of its dispatches in `dispatch_barriers`.
+    pub(crate) fn compute_pass<A: HalApi>(
+        &mut self,
+        hub: &Hub<A>,
+        base: &BasePassRef<ComputeCommand>,
+        dispatch_barriers: &[usize],
+    ) {
+        self.line(format_args!(
+            "begin compute pass {:?}",
+            base.label.unwrap_or("")
+        ));
+        self.depth += 1;
+
+        let mut dynamic_offsets = base.dynamic_offsets;
+        let mut string_data = base.string_data;
+        let mut dispatch_barriers = dispatch_barriers.iter();
+        for command in base.commands {
+            match *command {
+                ComputeCommand::SetBindGroup {
+                    index,
+                    num_dynamic_offsets,
+                    bind_group_id,
+                } => {
+                    let bind_group = hub.bind_groups.label_for_resource(bind_group_id);
+                    let (offsets, rest) =
+                        dynamic_offsets.split_at(num_dynamic_offsets.min(dynamic_offsets.len()));
+                    dynamic_offsets = rest;
+                    self.line(format_args!(
+                        "set bind group {index}: {bind_group}, dynamic offsets {offsets:?}"
+                    ));
+                }
+                ComputeCommand::SetPipeline(pipeline_id) => {
+                    let pipeline = hub.compute_pipelines.label_for_resource(pipeline_id);
+                    self.line(format_args!("set pipeline {pipeline}"));
+                }
+                ComputeCommand::SetPushConstant {
+                    offset,
+                    size_bytes,
+                    values_offset: _,
+                } => self.line(format_args!(
+                    "set push constants {size_bytes} bytes at {offset}"
+                )),
+                ComputeCommand::Dispatch([x, y, z]) => {
+                    self.barriers(dispatch_barriers.next().copied().unwrap_or(0));
+                    self.line(format_args!("dispatch {x} {y} {z}"));
+                }
+                ComputeCommand::DispatchIndirect { buffer_id, offset } => {
+                    self.barriers(dispatch_barriers.next().copied().unwrap_or(0));
+                    let buffer = hub.buffers.label_for_resource(buffer_id);
+                    self.line(format_args!("dispatch indirect from {buffer} at {offset}"));
+                }
+                ComputeCommand::PushDebugGroup { color: _, len } => {
+                    let label = take_string(&mut string_data, len);
+                    self.push_debug_group(&label);
+                }
+                ComputeCommand::PopDebugGroup => self.pop_debug_group(),
+                ComputeCommand::InsertDebugMarker { color: _, len } => {
+                    let label = take_string(&mut string_data, len);
+                    self.line(format_args!("insert debug marker {label:?}"));
+                }
+                ComputeCommand::WriteTimestamp {
+                    query_set_id,
+                    query_index,
+                } => {
+                    let query_set = hub.query_sets.label_for_resource(query_set_id);
+                    self.line(format_args!("write timestamp {query_set} [{query_index}]"));
+                }
+                ComputeCommand::BeginPipelineStatisticsQuery {
+                    query_set_id,
+                    query_index,
+                } => {
+                    let query_set = hub.query_sets.label_for_resource(query_set_id);
+                    self.line(format_args!(
+                        "begin pipeline statistics query {query_set} [{query_index}]"
+                    ));
+                }
+                ComputeCommand::EndPipelineStatisticsQuery => {
+                    self.line(format_args!("end pipeline statistics query"))
+                }
+            }
+        }
+        self.depth -= 1;
+        self.line(format_args!("end compute pass"));
+    }
+}
+
+/// Describes the buffer side of a copy between a buffer and a texture.
+pub(crate) fn image_copy_buffer<A: HalApi>(hub: &Hub<A>, copy: &ImageCopyBuffer) -> String {
+    let buffer = hub.buffers.label_for_resource(copy.buffer);
+    let layout = &copy.layout;
+    format!(
+        "buffer {buffer} at {}, {:?} bytes per row, {:?} rows per image",
+        layout.offset, layout.bytes_per_row, layout.rows_per_image
+    )
+}
+
+/// Describes the texture side of a copy.
+pub(crate) fn image_copy_texture<A: HalApi>(hub: &Hub<A>, copy: &ImageCopyTexture) -> String {
+    let texture = hub.textures.label_for_resource(copy.texture);
+    let origin = copy.origin;
+    format!(
+        "texture {texture} mip {}, origin {} {} {}, {:?}",
+        copy.mip_level, origin.x, origin.y, origin.z, copy.aspect
+    )
+}
+
+fn channel<V>(channel: &PassChannel<V>, value: impl Fn(&V) -> String) -> String {
+    if channel.read_only {
+        return "read only".to_string();
+    }
+    let load = match channel.load_op {
+        LoadOp::Clear => format!("clear to {}", value(&channel.clear_value)),
+        LoadOp::Load => "load".to_string(),
+    };
+    let store = match channel.store_op {
+        StoreOp::Discard => "discard",
+        StoreOp::Store => "store",
+    };
+    format!("{load}, {store}")
+}
+
+fn range(offset: wgt::BufferAddress, size: Option<wgt::BufferSize>) -> String {
+    match size {
+        Some(size) => format!("bytes {offset}..{}", offset + size.get()),
+        None => format!("bytes {offset}.."),
+    }
+}
+
+fn take_string(string_data: &mut &[u8], len: usize) -> String {
+    let (bytes, rest) = string_data.split_at(len.min(string_data.len()));
+    *string_data = rest;
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indent_debug_groups() {
+        let mut disassembly = Disassembly::new(Some("frame"));
+        disassembly.line(format_args!("clear buffer"));
+        disassembly.push_debug_group("shadows");
+        disassembly.barriers(0);
+        disassembly.barriers(2);
+        disassembly.line(format_args!("dispatch 1 1 1"));
+        disassembly.pop_debug_group();
+        // Unbalanced pops keep the commands inside of the command buffer.
+        disassembly.pop_debug_group();
+
+        assert_eq!(
+            disassembly.text(),
+            "command buffer \"frame\"\n  \
+            clear buffer\n  \
+            push debug group \"shadows\"\n    \
+            barriers: 2 transitions\n    \
+            dispatch 1 1 1\n  \
+            pop debug group\n  \
+            pop debug group\n"
+        );
+    }
+}
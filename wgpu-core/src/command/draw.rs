@@ -50,6 +50,10 @@ pub enum DrawError {
     },
     #[error(transparent)]
     BindingSizeTooSmall(#[from] LateMinBufferBindingSizeMismatch),
+    #[error("Mesh tasks can only be drawn with a mesh pipeline")]
+    MissingMeshPipeline,
+    #[error("Mesh pipelines can only be drawn with `draw_mesh_tasks`")]
+    UnexpectedMeshPipeline,
 }
 
 /// Error encountered when encoding a render command.
@@ -150,6 +154,21 @@ pub enum RenderCommand {
         num_dynamic_offsets: usize,
         bind_group_id: id::BindGroupId,
     },
+
+    /// Bind a bind group built from entries stored in
+    /// [`BasePass::immediate_bind_group_entries`], against the layout the
+    /// current pipeline expects at `index`.
+    ///
+    /// [`BasePass::immediate_bind_group_entries`]: crate::command::BasePass::immediate_bind_group_entries
+    SetBindGroupImmediate {
+        index: u32,
+
+        /// Number of values to consume from
+        /// [`BasePass::immediate_bind_group_entries`].
+        ///
+        /// [`BasePass::immediate_bind_group_entries`]: crate::command::BasePass::immediate_bind_group_entries
+        num_entries: usize,
+    },
     SetPipeline(id::RenderPipelineId),
     SetIndexBuffer {
         buffer_id: id::BufferId,
@@ -172,6 +191,7 @@ pub enum RenderCommand {
         depth_max: f32,
     },
     SetScissor(Rect<u32>),
+    SetShadingRate(wgt::ShadingRate),
 
     /// Set a range of push constants to values stored in [`BasePass::push_constant_data`].
     ///
@@ -228,6 +248,30 @@ pub enum RenderCommand {
         max_count: u32,
         indexed: bool,
     },
+    DrawMeshTasks {
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    },
+    DrawMeshTasksIndirect {
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+    },
+    /// Execute up to `max_count` draws whose state changes and arguments
+    /// are read from `buffer_id`.
+    ExecuteIndirect {
+        /// Number of values to consume from [`BasePass::indirect_arguments`],
+        /// describing the layout of each draw in `buffer_id`.
+        ///
+        /// [`BasePass::indirect_arguments`]: crate::command::BasePass::indirect_arguments
+        num_arguments: usize,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        /// Buffer holding the number of draws to execute, clamped to `max_count`.
+        count_buffer_id: Option<id::BufferId>,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    },
     PushDebugGroup {
         color: u32,
         len: usize,
@@ -250,5 +294,24 @@ pub enum RenderCommand {
         query_index: u32,
     },
     EndPipelineStatisticsQuery,
+    /// Skip the following draws if the `u64` at `offset` in `buffer_id` is
+    /// zero, until [`RenderCommand::EndConditionalRendering`].
+    BeginConditionalRendering {
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+    },
+    EndConditionalRendering,
+    /// Bind a buffer to `slot` for the next [`RenderCommand::BeginVertexCapture`].
+    SetVertexCaptureBuffer {
+        slot: u32,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    },
+    /// Write the outputs of the vertex stage to the buffers set with
+    /// [`RenderCommand::SetVertexCaptureBuffer`], until
+    /// [`RenderCommand::EndVertexCapture`].
+    BeginVertexCapture,
+    EndVertexCapture,
     ExecuteBundle(id::RenderBundleId),
 }
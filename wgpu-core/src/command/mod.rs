@@ -2,23 +2,27 @@ mod bind;
 mod bundle;
 mod clear;
 mod compute;
+mod disassembly;
 mod draw;
 mod memory_init;
 mod query;
+mod ray_tracing;
 mod render;
 mod transfer;
 
+use std::ops::Range;
 use std::slice;
-use std::sync::Arc;
+use std::sync::{atomic::Ordering, Arc};
 
 pub(crate) use self::clear::clear_texture;
 pub use self::{
     bundle::*, clear::ClearError, compute::*, draw::*, query::*, render::*, transfer::*,
 };
 
+use self::disassembly::Disassembly;
 use self::memory_init::CommandBufferTextureMemoryActions;
 
-use crate::device::{Device, DeviceError};
+use crate::device::{queue::TempResource, Device, DeviceError, MissingFeatures};
 use crate::error::{ErrorFormatter, PrettyError};
 use crate::hub::Hub;
 use crate::id::CommandBufferId;
@@ -35,6 +39,7 @@ use crate::{
 use hal::CommandEncoder as _;
 use parking_lot::Mutex;
 use thiserror::Error;
+use wgt::BufferAddress;
 
 #[cfg(feature = "trace")]
 use crate::device::trace::Command as TraceCommand;
@@ -109,6 +114,9 @@ pub struct BakedCommands<A: HalApi> {
     pub(crate) trackers: Tracker<A>,
     buffer_memory_init_actions: Vec<BufferInitTrackerAction<A>>,
     texture_memory_actions: CommandBufferTextureMemoryActions<A>,
+    /// The label of the command buffer followed by its [`CommandBufferMutable::debug_markers`].
+    pub(crate) debug_markers: Vec<String>,
+    pub(crate) temp_resources: Vec<TempResource<A>>,
 }
 
 pub(crate) struct DestroyedBufferError(pub id::BufferId);
@@ -121,6 +129,22 @@ pub struct CommandBufferMutable<A: HalApi> {
     buffer_memory_init_actions: Vec<BufferInitTrackerAction<A>>,
     texture_memory_actions: CommandBufferTextureMemoryActions<A>,
     pub(crate) pending_query_resets: QueryResetMap<A>,
+    /// Ranges of mappable buffers used by copies, see
+    /// [`Buffer::mappable_range_use`](crate::resource::Buffer::mappable_range_use).
+    pub(crate) mappable_buffer_uses: Vec<(id::BufferId, Range<BufferAddress>)>,
+    /// Labels of passes and debug markers, only recorded while the device has a watchdog.
+    pub(crate) debug_markers: Vec<String>,
+    /// Listing of the recorded commands, only written while the device
+    /// disassembles command buffers, see [`Global::device_set_command_disassembly`].
+    pub(crate) disassembly: Option<Disassembly>,
+    /// Resources created internally by the recorded commands, like the scratch
+    /// buffers of acceleration structure builds, kept alive until the command
+    /// buffer has been executed.
+    pub(crate) temp_resources: Vec<TempResource<A>>,
+    /// The render pass resumed by the first command, see [`RenderPass::resume`].
+    pub(crate) resumed_render_pass: Option<SuspendedRenderPass>,
+    /// The render pass suspended by the last command, see [`RenderPass::suspend`].
+    pub(crate) suspended_render_pass: Option<SuspendedRenderPass>,
     #[cfg(feature = "trace")]
     pub(crate) commands: Option<Vec<TraceCommand>>,
 }
@@ -134,6 +158,13 @@ impl<A: HalApi> CommandBufferMutable<A> {
 
         Ok((encoder, tracker))
     }
+
+    /// Remembers `label` for the reports of the watchdog of `device`, if any.
+    pub(crate) fn record_debug_marker(&mut self, device: &Device<A>, label: &str) {
+        if device.watchdog.lock().is_some() {
+            self.debug_markers.push(label.to_string());
+        }
+    }
 }
 
 pub struct CommandBuffer<A: HalApi> {
@@ -179,6 +210,10 @@ impl<A: HalApi> CommandBuffer<A> {
                     .as_str(),
             ),
             data: Mutex::new(Some(CommandBufferMutable {
+                disassembly: device
+                    .command_disassembly
+                    .load(Ordering::Relaxed)
+                    .then(|| Disassembly::new(label.as_deref())),
                 encoder: CommandEncoder {
                     raw: encoder,
                     is_open: false,
@@ -190,6 +225,11 @@ impl<A: HalApi> CommandBuffer<A> {
                 buffer_memory_init_actions: Default::default(),
                 texture_memory_actions: Default::default(),
                 pending_query_resets: QueryResetMap::new(),
+                mappable_buffer_uses: Vec::new(),
+                debug_markers: Vec::new(),
+                temp_resources: Vec::new(),
+                resumed_render_pass: None,
+                suspended_render_pass: None,
                 #[cfg(feature = "trace")]
                 commands: if enable_tracing {
                     Some(Vec::new())
@@ -200,43 +240,51 @@ impl<A: HalApi> CommandBuffer<A> {
         }
     }
 
+    /// Returns the number of inserted transitions.
     pub(crate) fn insert_barriers_from_tracker(
         raw: &mut A::CommandEncoder,
         base: &mut Tracker<A>,
         head: &Tracker<A>,
         snatch_guard: &SnatchGuard,
-    ) {
+    ) -> usize {
         profiling::scope!("insert_barriers");
 
         base.buffers.set_from_tracker(&head.buffers);
         base.textures.set_from_tracker(&head.textures);
 
-        Self::drain_barriers(raw, base, snatch_guard);
+        Self::drain_barriers(raw, base, snatch_guard)
     }
 
+    /// Returns the number of inserted transitions.
     pub(crate) fn insert_barriers_from_scope(
         raw: &mut A::CommandEncoder,
         base: &mut Tracker<A>,
         head: &UsageScope<A>,
         snatch_guard: &SnatchGuard,
-    ) {
+    ) -> usize {
         profiling::scope!("insert_barriers");
 
         base.buffers.set_from_usage_scope(&head.buffers);
         base.textures.set_from_usage_scope(&head.textures);
 
-        Self::drain_barriers(raw, base, snatch_guard);
+        Self::drain_barriers(raw, base, snatch_guard)
     }
 
+    /// Returns the number of inserted transitions.
     pub(crate) fn drain_barriers(
         raw: &mut A::CommandEncoder,
         base: &mut Tracker<A>,
         snatch_guard: &SnatchGuard,
-    ) {
+    ) -> usize {
         profiling::scope!("drain_barriers");
 
-        let buffer_barriers = base.buffers.drain_transitions(snatch_guard);
+        let mut buffer_count = 0;
+        let buffer_barriers = base
+            .buffers
+            .drain_transitions(snatch_guard)
+            .inspect(|_| buffer_count += 1);
         let (transitions, textures) = base.textures.drain_transitions(snatch_guard);
+        let texture_count = transitions.len();
         let texture_barriers = transitions
             .into_iter()
             .enumerate()
@@ -246,6 +294,7 @@ impl<A: HalApi> CommandBuffer<A> {
             raw.transition_buffers(buffer_barriers);
             raw.transition_textures(texture_barriers);
         }
+        buffer_count + texture_count
     }
 }
 
@@ -254,13 +303,19 @@ impl<A: HalApi> CommandBuffer<A> {
         hub: &Hub<A>,
         id: id::CommandEncoderId,
     ) -> Result<Arc<Self>, CommandEncoderError> {
-        let storage = hub.command_buffers.read();
-        match storage.get(id) {
-            Ok(cmd_buf) => match cmd_buf.data.lock().as_ref().unwrap().status {
-                CommandEncoderStatus::Recording => Ok(cmd_buf.clone()),
-                CommandEncoderStatus::Finished => Err(CommandEncoderError::NotRecording),
-                CommandEncoderStatus::Error => Err(CommandEncoderError::Invalid),
-            },
+        match hub.command_buffers.get(id) {
+            Ok(cmd_buf) => {
+                let data = cmd_buf.data.lock();
+                let data = data.as_ref().unwrap();
+                match data.status {
+                    CommandEncoderStatus::Recording if data.suspended_render_pass.is_some() => {
+                        Err(CommandEncoderError::RenderPassSuspended)
+                    }
+                    CommandEncoderStatus::Recording => Ok(cmd_buf.clone()),
+                    CommandEncoderStatus::Finished => Err(CommandEncoderError::NotRecording),
+                    CommandEncoderStatus::Error => Err(CommandEncoderError::Invalid),
+                }
+            }
             Err(_) => Err(CommandEncoderError::Invalid),
         }
     }
@@ -284,6 +339,13 @@ impl<A: HalApi> CommandBuffer<A> {
             trackers: data.trackers,
             buffer_memory_init_actions: data.buffer_memory_init_actions,
             texture_memory_actions: data.texture_memory_actions,
+            debug_markers: data
+                .encoder
+                .label
+                .into_iter()
+                .chain(data.debug_markers)
+                .collect(),
+            temp_resources: data.temp_resources,
         }
     }
 
@@ -323,6 +385,8 @@ pub struct BasePassRef<'a, C> {
     pub dynamic_offsets: &'a [wgt::DynamicOffset],
     pub string_data: &'a [u8],
     pub push_constant_data: &'a [u32],
+    pub indirect_arguments: &'a [wgt::IndirectArgument],
+    pub immediate_bind_group_entries: &'a [crate::binding_model::BindGroupEntry<'static>],
 }
 
 /// A stream of commands for a render pass or compute pass.
@@ -369,6 +433,18 @@ pub struct BasePass<C> {
     /// See the documentation for [`RenderCommand::SetPushConstant`]
     /// and [`ComputeCommand::SetPushConstant`] for details.
     pub push_constant_data: Vec<u32>,
+
+    /// Argument layouts consumed by [`RenderCommand::ExecuteIndirect`] commands.
+    ///
+    /// Each successive `ExecuteIndirect` consumes the next
+    /// `num_arguments` values from this list.
+    pub indirect_arguments: Vec<wgt::IndirectArgument>,
+
+    /// Entries consumed by [`RenderCommand::SetBindGroupImmediate`] commands.
+    ///
+    /// Each successive `SetBindGroupImmediate` consumes the next
+    /// `num_entries` values from this list.
+    pub immediate_bind_group_entries: Vec<crate::binding_model::BindGroupEntry<'static>>,
 }
 
 impl<C: Clone> BasePass<C> {
@@ -379,6 +455,8 @@ impl<C: Clone> BasePass<C> {
             dynamic_offsets: Vec::new(),
             string_data: Vec::new(),
             push_constant_data: Vec::new(),
+            indirect_arguments: Vec::new(),
+            immediate_bind_group_entries: Vec::new(),
         }
     }
 
@@ -390,6 +468,8 @@ impl<C: Clone> BasePass<C> {
             dynamic_offsets: base.dynamic_offsets.to_vec(),
             string_data: base.string_data.to_vec(),
             push_constant_data: base.push_constant_data.to_vec(),
+            indirect_arguments: base.indirect_arguments.to_vec(),
+            immediate_bind_group_entries: base.immediate_bind_group_entries.to_vec(),
         }
     }
 
@@ -400,6 +480,8 @@ impl<C: Clone> BasePass<C> {
             dynamic_offsets: &self.dynamic_offsets,
             string_data: &self.string_data,
             push_constant_data: &self.push_constant_data,
+            indirect_arguments: &self.indirect_arguments,
+            immediate_bind_group_entries: &self.immediate_bind_group_entries,
         }
     }
 }
@@ -413,6 +495,10 @@ pub enum CommandEncoderError {
     NotRecording,
     #[error(transparent)]
     Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("No commands can be recorded after a suspended render pass")]
+    RenderPassSuspended,
 }
 
 impl<G: GlobalIdentityHandlerFactory> Global<G> {
@@ -454,6 +540,23 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (encoder_id, error)
     }
 
+    /// Returns the listing of the commands recorded so far in a command encoder
+    /// or buffer, if its device disassembled command buffers when it was created.
+    ///
+    /// See [`Global::device_set_command_disassembly`].
+    pub fn command_buffer_disassembly<A: HalApi>(
+        &self,
+        command_buffer_id: CommandBufferId,
+    ) -> Option<String> {
+        api_log!("CommandBuffer::disassembly {command_buffer_id:?}");
+
+        let hub = A::hub(self);
+        let cmd_buf = hub.command_buffers.get(command_buffer_id).ok()?;
+        let cmd_buf_data = cmd_buf.data.lock();
+        let disassembly = cmd_buf_data.as_ref()?.disassembly.as_ref()?;
+        Some(disassembly.text().to_string())
+    }
+
     pub fn command_encoder_push_debug_group<A: HalApi>(
         &self,
         encoder_id: id::CommandEncoderId,
@@ -471,6 +574,10 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         if let Some(ref mut list) = cmd_buf_data.commands {
             list.push(TraceCommand::PushDebugGroup(label.to_string()));
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.push_debug_group(label);
+        }
+        cmd_buf_data.record_debug_marker(&cmd_buf.device, label);
 
         let cmd_buf_raw = cmd_buf_data.encoder.open()?;
         if !self
@@ -503,6 +610,10 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         if let Some(ref mut list) = cmd_buf_data.commands {
             list.push(TraceCommand::InsertDebugMarker(label.to_string()));
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!("insert debug marker {label:?}"));
+        }
+        cmd_buf_data.record_debug_marker(&cmd_buf.device, label);
 
         if !self
             .instance
@@ -534,6 +645,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         if let Some(ref mut list) = cmd_buf_data.commands {
             list.push(TraceCommand::PopDebugGroup);
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.pop_debug_group();
+        }
 
         let cmd_buf_raw = cmd_buf_data.encoder.open()?;
         if !self
@@ -547,6 +661,43 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         }
         Ok(())
     }
+
+    /// Makes the memory shared by aliasing resources in a memory heap
+    /// available to whichever of them is used next.
+    ///
+    /// This must be recorded between the last use of one placed resource and
+    /// the first use of another one overlapping it, whose contents are then
+    /// undefined.
+    pub fn command_encoder_aliasing_barrier<A: HalApi>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+    ) -> Result<(), CommandEncoderError> {
+        profiling::scope!("CommandEncoder::aliasing_barrier");
+        api_log!("CommandEncoder::aliasing_barrier");
+
+        let hub = A::hub(self);
+
+        let cmd_buf = CommandBuffer::get_encoder(hub, encoder_id)?;
+        cmd_buf
+            .device
+            .require_features(wgt::Features::MEMORY_HEAPS)?;
+        let mut cmd_buf_data = cmd_buf.data.lock();
+        let cmd_buf_data = cmd_buf_data.as_mut().unwrap();
+
+        #[cfg(feature = "trace")]
+        if let Some(ref mut list) = cmd_buf_data.commands {
+            list.push(TraceCommand::AliasingBarrier);
+        }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!("aliasing barrier"));
+        }
+
+        let cmd_buf_raw = cmd_buf_data.encoder.open()?;
+        unsafe {
+            cmd_buf_raw.aliasing_barrier();
+        }
+        Ok(())
+    }
 }
 
 fn push_constant_clear<PushFn>(offset: u32, size_bytes: u32, mut push_fn: PushFn)
@@ -636,6 +787,12 @@ impl BindGroupStateChange {
         }
         false
     }
+    /// Forgets the bind group at `index`, after something else was bound there.
+    fn forget(&mut self, index: u32) {
+        if let Some(current_bind_group) = self.last_states.get_mut(index as usize) {
+            current_bind_group.reset();
+        }
+    }
     fn reset(&mut self) {
         self.last_states = [StateChange::new(); hal::MAX_BIND_GROUPS];
     }
@@ -659,6 +816,8 @@ pub enum PassErrorScope {
     Pass(id::CommandEncoderId),
     #[error("In a set_bind_group command")]
     SetBindGroup(id::BindGroupId),
+    #[error("In a set_bind_group_immediate command")]
+    SetBindGroupImmediate,
     #[error("In a set_pipeline command")]
     SetPipelineRender(id::RenderPipelineId),
     #[error("In a set_pipeline command")]
@@ -673,12 +832,19 @@ pub enum PassErrorScope {
     SetViewport,
     #[error("In a set_scissor_rect command")]
     SetScissorRect,
+    #[error("In a set_shading_rate command")]
+    SetShadingRate,
     #[error("In a draw command, indexed:{indexed} indirect:{indirect}")]
     Draw {
         indexed: bool,
         indirect: bool,
         pipeline: Option<id::RenderPipelineId>,
     },
+    #[error("In a draw_mesh_tasks command, indirect:{indirect}")]
+    DrawMeshTasks {
+        indirect: bool,
+        pipeline: Option<id::RenderPipelineId>,
+    },
     #[error("While resetting queries after the renderpass was ran")]
     QueryReset,
     #[error("In a write_timestamp command")]
@@ -691,6 +857,16 @@ pub enum PassErrorScope {
     BeginPipelineStatisticsQuery,
     #[error("In a end_pipeline_statistics_query command")]
     EndPipelineStatisticsQuery,
+    #[error("In a begin_conditional_rendering command")]
+    BeginConditionalRendering(id::BufferId),
+    #[error("In a end_conditional_rendering command")]
+    EndConditionalRendering,
+    #[error("In a set_vertex_capture_buffer command")]
+    SetVertexCaptureBuffer(id::BufferId),
+    #[error("In a begin_vertex_capture command")]
+    BeginVertexCapture,
+    #[error("In a end_vertex_capture command")]
+    EndVertexCapture,
     #[error("In a execute_bundle command")]
     ExecuteBundle,
     #[error("In a dispatch command, indirect:{indirect}")]
@@ -724,8 +900,17 @@ impl PrettyError for PassErrorScope {
             Self::SetIndexBuffer(id) => {
                 fmt.buffer_label(&id);
             }
+            Self::BeginConditionalRendering(id) => {
+                fmt.buffer_label(&id);
+            }
+            Self::SetVertexCaptureBuffer(id) => {
+                fmt.buffer_label(&id);
+            }
             Self::Draw {
                 pipeline: Some(id), ..
+            }
+            | Self::DrawMeshTasks {
+                pipeline: Some(id), ..
             } => {
                 fmt.render_pipeline_label(&id);
             }
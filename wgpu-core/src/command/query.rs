@@ -149,6 +149,8 @@ pub enum QueryUseError {
     },
     #[error("Query was stopped while there was no active query")]
     AlreadyStopped,
+    #[error("Query {query_index} was still active at the end of the pass")]
+    NotEnded { query_index: u32 },
     #[error("A query of type {query_type:?} was started using a query set of type {set_type:?}")]
     IncompatibleType {
         set_type: SimplifiedQueryType,
@@ -182,7 +184,7 @@ pub enum ResolveError {
 }
 
 impl<A: HalApi> QuerySet<A> {
-    fn validate_query(
+    pub(super) fn validate_query(
         &self,
         query_set_id: id::QuerySetId,
         query_type: SimplifiedQueryType,
@@ -366,6 +368,12 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 query_index,
             });
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!(
+                "write timestamp {} [{query_index}]",
+                hub.query_sets.label_for_resource(query_set_id)
+            ));
+        }
 
         let encoder = &mut cmd_buf_data.encoder;
         let tracker = &mut cmd_buf_data.trackers;
@@ -375,7 +383,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let query_set_guard = hub.query_sets.read();
         let query_set = tracker
             .query_sets
-            .add_single(&*query_set_guard, query_set_id)
+            .add_single(&query_set_guard, query_set_id)
             .ok_or(QueryError::InvalidQuerySet(query_set_id))?;
 
         query_set.validate_and_write_timestamp(raw_encoder, query_set_id, query_index, None)?;
@@ -408,10 +416,19 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 destination_offset,
             });
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!(
+                "resolve queries {} [{start_query}..{}] to buffer {} at {destination_offset}",
+                hub.query_sets.label_for_resource(query_set_id),
+                start_query as u64 + query_count as u64,
+                hub.buffers.label_for_resource(destination),
+            ));
+        }
 
         let encoder = &mut cmd_buf_data.encoder;
         let tracker = &mut cmd_buf_data.trackers;
         let buffer_memory_init_actions = &mut cmd_buf_data.buffer_memory_init_actions;
+        let mappable_buffer_uses = &mut cmd_buf_data.mappable_buffer_uses;
         let raw_encoder = encoder.open()?;
 
         if destination_offset % wgt::QUERY_RESOLVE_BUFFER_ALIGNMENT != 0 {
@@ -420,7 +437,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let query_set_guard = hub.query_sets.read();
         let query_set = tracker
             .query_sets
-            .add_single(&*query_set_guard, query_set_id)
+            .add_single(&query_set_guard, query_set_id)
             .ok_or(QueryError::InvalidQuerySet(query_set_id))?;
 
         let (dst_buffer, dst_pending) = {
@@ -481,6 +498,8 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             buffer_start_offset..buffer_end_offset,
             MemoryInitKind::ImplicitlyInitialized,
         ));
+        mappable_buffer_uses
+            .extend(dst_buffer.mappable_range_use(buffer_start_offset..buffer_end_offset));
 
         let raw_dst_buffer = dst_buffer
             .raw(&snatch_guard)
@@ -0,0 +1,546 @@
+#[cfg(feature = "trace")]
+use crate::device::trace::Command as TraceCommand;
+use crate::{
+    api_log,
+    command::{CommandBuffer, CommandBufferMutable},
+    conv,
+    device::{queue::TempResource, DeviceError},
+    global::Global,
+    hal_api::HalApi,
+    hal_label,
+    id::{BufferId, CommandEncoderId},
+    identity::GlobalIdentityHandlerFactory,
+    init_tracker::MemoryInitKind,
+    ray_tracing::{
+        self, BlasBuildEntry, BlasGeometries, BlasTriangleGeometry,
+        BuildAccelerationStructureError as Error, TlasBuildEntry,
+    },
+    resource::{Blas, Buffer, Resource, ResourceInfo, StagingBuffer, Tlas},
+    snatch::SnatchGuard,
+    storage::Storage,
+    track::PendingTransition,
+    FastHashSet,
+};
+
+use hal::{CommandEncoder as _, Device as _};
+use parking_lot::Mutex;
+use wgt::{BufferAddress, BufferUsages};
+
+use std::{ops::Range, sync::Arc};
+
+/// The buffers read by the builds of a single call, and the transitions they
+/// need before the builds.
+struct InputBuffers<A: HalApi> {
+    buffers: Vec<Arc<Buffer<A>>>,
+    transitions: Vec<(usize, PendingTransition<hal::BufferUses>)>,
+}
+
+impl<A: HalApi> InputBuffers<A> {
+    /// Validates that `range` of `buffer_id` can be read as a build input with
+    /// `usage`, and tracks it. Returns the index of the buffer in `self.buffers`.
+    fn track(
+        &mut self,
+        cmd_buf_data: &mut CommandBufferMutable<A>,
+        buffer_guard: &Storage<Buffer<A>, BufferId>,
+        snatch_guard: &SnatchGuard,
+        buffer_id: BufferId,
+        usage: BufferUsages,
+        range: Range<BufferAddress>,
+    ) -> Result<usize, Error> {
+        let buffer = buffer_guard
+            .get(buffer_id)
+            .map_err(|_| Error::InvalidBuffer(buffer_id))?;
+        if buffer.raw.get(snatch_guard).is_none() {
+            return Err(Error::InvalidBuffer(buffer_id));
+        }
+        if !buffer.usage.contains(usage) {
+            return Err(if usage == BufferUsages::BLAS_INPUT {
+                Error::MissingBlasInputUsageFlag(buffer_id)
+            } else {
+                Error::MissingTlasInputUsageFlag(buffer_id)
+            });
+        }
+        if range.end > buffer.size {
+            return Err(Error::BufferOverrun {
+                buffer: buffer_id,
+                start: range.start,
+                end: range.end,
+                size: buffer.size,
+            });
+        }
+
+        let (buffer, pending) = cmd_buf_data
+            .trackers
+            .buffers
+            .set_single(buffer, conv::map_buffer_usage(usage))
+            .ok_or(Error::InvalidBuffer(buffer_id))?;
+        cmd_buf_data.buffer_memory_init_actions.extend(
+            buffer.initialization_status.read().create_action(
+                &buffer,
+                range,
+                MemoryInitKind::NeedsInitializedMemory,
+            ),
+        );
+
+        let index = self.buffers.len();
+        if let Some(pending) = pending {
+            self.transitions.push((index, pending));
+        }
+        self.buffers.push(buffer);
+        Ok(index)
+    }
+}
+
+/// Checks that `offset` into `buffer` is aligned, and converts it to the
+/// 32 bits offsets of the hal.
+fn check_offset(
+    buffer: BufferId,
+    offset: BufferAddress,
+    alignment: BufferAddress,
+) -> Result<u32, Error> {
+    if offset % alignment != 0 {
+        return Err(Error::UnalignedOffset {
+            buffer,
+            offset,
+            alignment,
+        });
+    }
+    u32::try_from(offset).map_err(|_| Error::OffsetTooLarge { buffer, offset })
+}
+
+/// The input buffers of a triangle geometry, as indices into
+/// [`InputBuffers::buffers`], with the offsets into them.
+struct TriangleInputs {
+    vertex: usize,
+    index: Option<(usize, u32)>,
+    transform: Option<(usize, u32)>,
+}
+
+struct BlasBuild<'a, A: HalApi> {
+    blas: &'a Arc<Blas<A>>,
+    geometries: &'a [BlasTriangleGeometry],
+    inputs: Vec<TriangleInputs>,
+}
+
+struct TlasBuild<'a, A: HalApi> {
+    tlas: &'a Arc<Tlas<A>>,
+    instance_count: u32,
+    instances: usize,
+}
+
+impl<G: GlobalIdentityHandlerFactory> Global<G> {
+    /// Builds bottom level acceleration structures from their geometries, and
+    /// then top level acceleration structures from their instances.
+    ///
+    /// The instances aren't validated: when a top level acceleration structure
+    /// is used, the bottom level acceleration structures its instances
+    /// reference must be alive and built.
+    pub fn command_encoder_build_acceleration_structures_unsafe_tlas<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        blas_entries: &[BlasBuildEntry],
+        tlas_entries: &[TlasBuildEntry],
+    ) -> Result<(), Error> {
+        profiling::scope!("CommandEncoder::build_acceleration_structures_unsafe_tlas");
+        api_log!(
+            "CommandEncoder::build_acceleration_structures_unsafe_tlas {} blas, {} tlas",
+            blas_entries.len(),
+            tlas_entries.len()
+        );
+
+        let hub = A::hub(self);
+
+        let cmd_buf = CommandBuffer::get_encoder(hub, command_encoder_id)?;
+        let mut cmd_buf_data = cmd_buf.data.lock();
+        let cmd_buf_data = cmd_buf_data.as_mut().unwrap();
+
+        let device = &cmd_buf.device;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+        device.require_features(wgt::Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE)?;
+
+        #[cfg(feature = "trace")]
+        if let Some(ref mut list) = cmd_buf_data.commands {
+            list.push(TraceCommand::BuildAccelerationStructures {
+                blas: blas_entries.to_vec(),
+                tlas: tlas_entries.to_vec(),
+            });
+        }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            for entry in blas_entries {
+                let BlasGeometries::TriangleGeometries(ref geometries) = entry.geometries;
+                disassembly.line(format_args!(
+                    "build blas {} from {} triangle geometries",
+                    hub.blas_s.label_for_resource(entry.blas_id),
+                    geometries.len(),
+                ));
+            }
+            for entry in tlas_entries {
+                disassembly.line(format_args!(
+                    "build tlas {} from {} instances of buffer {}",
+                    hub.tlas_s.label_for_resource(entry.tlas_id),
+                    entry.instance_count,
+                    hub.buffers.label_for_resource(entry.instance_buffer_id),
+                ));
+            }
+        }
+
+        if blas_entries.is_empty() && tlas_entries.is_empty() {
+            log::trace!("Ignoring build_acceleration_structures without entries");
+            return Ok(());
+        }
+
+        let snatch_guard = device.snatchable_lock.read();
+        let buffer_guard = hub.buffers.read();
+        let blas_guard = hub.blas_s.read();
+        let tlas_guard = hub.tlas_s.read();
+
+        let mut inputs = InputBuffers {
+            buffers: Vec::new(),
+            transitions: Vec::new(),
+        };
+
+        let mut built_blas_s = FastHashSet::default();
+        let mut blas_builds = Vec::with_capacity(blas_entries.len());
+        for entry in blas_entries {
+            let blas_id = entry.blas_id;
+            let blas = blas_guard
+                .get(blas_id)
+                .map_err(|_| Error::InvalidBlas(blas_id))?;
+            if blas.device.as_info().id() != device.as_info().id() {
+                return Err(DeviceError::WrongDevice.into());
+            }
+            if !built_blas_s.insert(blas_id) {
+                return Err(Error::DuplicateBlas(blas_id));
+            }
+
+            let wgt::BlasGeometrySizeDescriptors::Triangles {
+                descriptors: ref created_sizes,
+            } = blas.sizes;
+            let BlasGeometries::TriangleGeometries(ref geometries) = entry.geometries;
+            if geometries.len() > created_sizes.len() {
+                return Err(Error::IncompatibleBlasGeometries(blas_id));
+            }
+
+            let mut triangle_inputs = Vec::with_capacity(geometries.len());
+            for (index, (geometry, created_size)) in
+                geometries.iter().zip(created_sizes).enumerate()
+            {
+                let size = &geometry.size;
+                let fits = size.vertex_format == created_size.vertex_format
+                    && size.index_format == created_size.index_format
+                    && size.flags == created_size.flags
+                    && size.vertex_count <= created_size.vertex_count
+                    && size.index_count.is_some() == created_size.index_count.is_some()
+                    && size.index_count <= created_size.index_count;
+                if !fits {
+                    return Err(Error::IncompatibleBlasGeometries(blas_id));
+                }
+                if size.index_count.unwrap_or(size.vertex_count) % 3 != 0 {
+                    return Err(Error::InvalidPrimitiveCount {
+                        blas: blas_id,
+                        index,
+                    });
+                }
+
+                // The format was validated when the acceleration structure was created.
+                let component_size = ray_tracing::vertex_component_size(size.vertex_format)
+                    .expect("invalid vertex format");
+                if geometry.vertex_stride % component_size != 0 {
+                    return Err(Error::UnalignedVertexStride(geometry.vertex_stride));
+                }
+                let vertex_start = u64::from(geometry.first_vertex) * geometry.vertex_stride;
+                let vertex_end = match size.vertex_count.checked_sub(1) {
+                    Some(last_vertex) => {
+                        vertex_start
+                            + u64::from(last_vertex) * geometry.vertex_stride
+                            + size.vertex_format.size()
+                    }
+                    None => vertex_start,
+                };
+                let vertex = inputs.track(
+                    cmd_buf_data,
+                    &buffer_guard,
+                    &snatch_guard,
+                    geometry.vertex_buffer,
+                    BufferUsages::BLAS_INPUT,
+                    vertex_start..vertex_end,
+                )?;
+
+                let index_input = match (size.index_format, geometry.index_buffer) {
+                    (Some(format), Some(buffer_id)) => {
+                        let offset = geometry.index_buffer_offset.unwrap_or(0);
+                        let index_size = ray_tracing::index_format_size(format);
+                        let hal_offset = check_offset(buffer_id, offset, index_size)?;
+                        let end = offset + u64::from(size.index_count.unwrap_or(0)) * index_size;
+                        let buffer = inputs.track(
+                            cmd_buf_data,
+                            &buffer_guard,
+                            &snatch_guard,
+                            buffer_id,
+                            BufferUsages::BLAS_INPUT,
+                            offset..end,
+                        )?;
+                        Some((buffer, hal_offset))
+                    }
+                    (None, None) => None,
+                    _ => {
+                        return Err(Error::IndexBufferMismatch {
+                            blas: blas_id,
+                            index,
+                        })
+                    }
+                };
+
+                let transform_input = match geometry.transform_buffer {
+                    Some(buffer_id) => {
+                        let offset = geometry.transform_buffer_offset.unwrap_or(0);
+                        let hal_offset = check_offset(buffer_id, offset, 16)?;
+                        // A row-major 3x4 matrix of `f32`.
+                        let buffer = inputs.track(
+                            cmd_buf_data,
+                            &buffer_guard,
+                            &snatch_guard,
+                            buffer_id,
+                            BufferUsages::BLAS_INPUT,
+                            offset..offset + 48,
+                        )?;
+                        Some((buffer, hal_offset))
+                    }
+                    None => None,
+                };
+
+                triangle_inputs.push(TriangleInputs {
+                    vertex,
+                    index: index_input,
+                    transform: transform_input,
+                });
+            }
+
+            blas_builds.push(BlasBuild {
+                blas,
+                geometries,
+                inputs: triangle_inputs,
+            });
+        }
+
+        let mut built_tlas_s = FastHashSet::default();
+        let mut tlas_builds = Vec::with_capacity(tlas_entries.len());
+        for entry in tlas_entries {
+            let tlas_id = entry.tlas_id;
+            let tlas = tlas_guard
+                .get(tlas_id)
+                .map_err(|_| Error::InvalidTlas(tlas_id))?;
+            if tlas.device.as_info().id() != device.as_info().id() {
+                return Err(DeviceError::WrongDevice.into());
+            }
+            if !built_tlas_s.insert(tlas_id) {
+                return Err(Error::DuplicateTlas(tlas_id));
+            }
+            if entry.instance_count > tlas.max_instance_count {
+                return Err(Error::TooManyInstances {
+                    tlas: tlas_id,
+                    count: entry.instance_count,
+                    maximum: tlas.max_instance_count,
+                });
+            }
+
+            let instances = inputs.track(
+                cmd_buf_data,
+                &buffer_guard,
+                &snatch_guard,
+                entry.instance_buffer_id,
+                BufferUsages::TLAS_INPUT,
+                0..u64::from(entry.instance_count) * wgt::TLAS_INSTANCE_SIZE,
+            )?;
+
+            tlas_builds.push(TlasBuild {
+                tlas,
+                instance_count: entry.instance_count,
+                instances,
+            });
+        }
+
+        for build in blas_builds.iter() {
+            cmd_buf_data
+                .trackers
+                .blas_s
+                .insert_single(build.blas.as_info().id(), build.blas.clone());
+        }
+        for build in tlas_builds.iter() {
+            cmd_buf_data
+                .trackers
+                .tlas_s
+                .insert_single(build.tlas.as_info().id(), build.tlas.clone());
+        }
+
+        // All the builds share a scratch buffer, which lives until the command
+        // buffer has been executed.
+        let scratch_alignment = BufferAddress::from(
+            device
+                .alignments
+                .ray_tracing_scratch_buffer_alignment
+                .max(1),
+        );
+        let mut scratch_size = 0;
+        let scratch_offsets = blas_builds
+            .iter()
+            .map(|build| build.blas.size_info.build_scratch_size)
+            .chain(
+                tlas_builds
+                    .iter()
+                    .map(|build| build.tlas.size_info.build_scratch_size),
+            )
+            .map(|build_scratch_size| {
+                let offset = scratch_size;
+                scratch_size += wgt::math::align_to(build_scratch_size, scratch_alignment);
+                offset
+            })
+            .collect::<Vec<_>>();
+
+        let scratch_raw = unsafe {
+            device.raw().create_buffer(&hal::BufferDescriptor {
+                label: hal_label(
+                    Some("(wgpu internal) Acceleration structure scratch"),
+                    device.instance_flags,
+                ),
+                size: scratch_size,
+                usage: hal::BufferUses::ACCELERATION_STRUCTURE_SCRATCH,
+                memory_flags: hal::MemoryFlags::empty(),
+            })
+        }
+        .map_err(DeviceError::from)?;
+        let scratch = hub.staging_buffers.request().init(StagingBuffer {
+            raw: Mutex::new(Some(scratch_raw)),
+            device: device.clone(),
+            size: scratch_size,
+            is_coherent: false,
+            info: ResourceInfo::new("<StagingBuffer>"),
+        });
+
+        let InputBuffers {
+            buffers: input_buffers,
+            transitions,
+        } = inputs;
+        let input_raw = |index: usize| input_buffers[index].raw.get(&snatch_guard).unwrap();
+
+        let blas_hal_entries = blas_builds
+            .iter()
+            .map(|build| {
+                hal::AccelerationStructureEntries::Triangles(
+                    build
+                        .geometries
+                        .iter()
+                        .zip(build.inputs.iter())
+                        .map(|(geometry, inputs)| hal::AccelerationStructureTriangles {
+                            vertex_buffer: Some(input_raw(inputs.vertex)),
+                            vertex_format: geometry.size.vertex_format,
+                            first_vertex: geometry.first_vertex,
+                            vertex_count: geometry.size.vertex_count,
+                            vertex_stride: geometry.vertex_stride,
+                            indices: inputs.index.map(|(buffer, offset)| {
+                                hal::AccelerationStructureTriangleIndices {
+                                    format: geometry.size.index_format.unwrap(),
+                                    buffer: Some(input_raw(buffer)),
+                                    offset,
+                                    count: geometry.size.index_count.unwrap(),
+                                }
+                            }),
+                            transform: inputs.transform.map(|(buffer, offset)| {
+                                hal::AccelerationStructureTriangleTransform {
+                                    buffer: input_raw(buffer),
+                                    offset,
+                                }
+                            }),
+                            flags: geometry.size.flags,
+                        })
+                        .collect(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let tlas_hal_entries = tlas_builds
+            .iter()
+            .map(|build| {
+                hal::AccelerationStructureEntries::Instances(hal::AccelerationStructureInstances {
+                    buffer: Some(input_raw(build.instances)),
+                    offset: 0,
+                    count: build.instance_count,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let scratch_guard = scratch.raw.lock();
+        let scratch_buffer = scratch_guard.as_ref().unwrap();
+        let (blas_scratch_offsets, tlas_scratch_offsets) =
+            scratch_offsets.split_at(blas_builds.len());
+        let blas_descriptors = blas_builds
+            .iter()
+            .zip(blas_hal_entries.iter())
+            .zip(blas_scratch_offsets)
+            .map(|((build, entries), &scratch_buffer_offset)| {
+                hal::BuildAccelerationStructureDescriptor {
+                    entries,
+                    mode: hal::AccelerationStructureBuildMode::Build,
+                    flags: build.blas.flags,
+                    source_acceleration_structure: None,
+                    destination_acceleration_structure: build.blas.raw(),
+                    scratch_buffer,
+                    scratch_buffer_offset,
+                }
+            });
+        let tlas_descriptors = tlas_builds
+            .iter()
+            .zip(tlas_hal_entries.iter())
+            .zip(tlas_scratch_offsets)
+            .map(|((build, entries), &scratch_buffer_offset)| {
+                hal::BuildAccelerationStructureDescriptor {
+                    entries,
+                    mode: hal::AccelerationStructureBuildMode::Build,
+                    flags: build.tlas.flags,
+                    source_acceleration_structure: None,
+                    destination_acceleration_structure: build.tlas.raw(),
+                    scratch_buffer,
+                    scratch_buffer_offset,
+                }
+            });
+        let buffer_barriers = transitions
+            .into_iter()
+            .map(|(index, pending)| pending.into_hal(&input_buffers[index], &snatch_guard));
+
+        use hal::AccelerationStructureUses as Asu;
+        let cmd_buf_raw = cmd_buf_data.encoder.open()?;
+        unsafe {
+            cmd_buf_raw.transition_buffers(buffer_barriers);
+            // Previous reads of the acceleration structures must be done before they are written.
+            cmd_buf_raw.place_acceleration_structure_barrier(hal::AccelerationStructureBarrier {
+                usage: Asu::BUILD_INPUT | Asu::SHADER_INPUT..Asu::BUILD_OUTPUT,
+            });
+            if !blas_builds.is_empty() {
+                cmd_buf_raw
+                    .build_acceleration_structures(blas_builds.len() as u32, blas_descriptors);
+                // The top level acceleration structures may instantiate the
+                // bottom level ones that were just built.
+                cmd_buf_raw.place_acceleration_structure_barrier(
+                    hal::AccelerationStructureBarrier {
+                        usage: Asu::BUILD_OUTPUT..Asu::BUILD_INPUT,
+                    },
+                );
+            }
+            if !tlas_builds.is_empty() {
+                cmd_buf_raw
+                    .build_acceleration_structures(tlas_builds.len() as u32, tlas_descriptors);
+            }
+            cmd_buf_raw.place_acceleration_structure_barrier(hal::AccelerationStructureBarrier {
+                usage: Asu::BUILD_OUTPUT..Asu::BUILD_INPUT | Asu::SHADER_INPUT,
+            });
+        }
+        drop(scratch_guard);
+
+        cmd_buf_data
+            .temp_resources
+            .push(TempResource::StagingBuffer(scratch));
+
+        Ok(())
+    }
+}
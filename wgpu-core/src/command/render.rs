@@ -1,7 +1,9 @@
 use crate::resource::Resource;
 use crate::{
     api_log,
-    binding_model::BindError,
+    binding_model::{
+        BindError, BindGroup, BindGroupDescriptor, CreateBindGroupError, PipelineLayout,
+    },
     command::{
         self,
         bind::Binder,
@@ -9,20 +11,24 @@ use crate::{
         memory_init::{fixup_discarded_surfaces, SurfacesInDiscardState},
         BasePass, BasePassRef, BindGroupStateChange, CommandBuffer, CommandEncoderError,
         CommandEncoderStatus, DrawError, ExecutionError, MapPassErr, PassErrorScope, QueryUseError,
-        RenderCommand, RenderCommandError, StateChange,
+        RenderCommand, RenderCommandError, SimplifiedQueryType, StateChange,
     },
     device::{
-        AttachmentData, Device, DeviceError, MissingDownlevelFlags, MissingFeatures,
-        RenderPassCompatibilityCheckType, RenderPassCompatibilityError, RenderPassContext,
+        queue::TempResource, AttachmentData, ComputeOnlyDevice, Device, DeviceError,
+        MissingDownlevelFlags, MissingFeatures, RenderPassCompatibilityCheckType,
+        RenderPassCompatibilityError, RenderPassContext,
     },
     error::{ErrorFormatter, PrettyError},
     global::Global,
     hal_api::HalApi,
     hal_label, id,
     identity::GlobalIdentityHandlerFactory,
-    init_tracker::{MemoryInitKind, TextureInitRange, TextureInitTrackerAction},
+    init_tracker::{
+        BufferInitTrackerAction, MemoryInitKind, TextureInitRange, TextureInitTrackerAction,
+    },
     pipeline::{self, PipelineFlags},
     resource::{Buffer, QuerySet, Texture, TextureView, TextureViewNotRenderableReason},
+    snatch::SnatchGuard,
     storage::Storage,
     track::{TextureSelector, Tracker, UsageConflict, UsageScope},
     validation::{
@@ -118,6 +124,26 @@ impl<V> PassChannel<V> {
     }
 }
 
+impl<V: Clone> PassChannel<V> {
+    /// The channel as used by a pass that resumes and/or suspends a render pass.
+    ///
+    /// A resuming pass loads the attachment instead of clearing it and a
+    /// suspending pass stores it, so the contents carry over between the parts
+    /// of the render pass.
+    fn continued(&self, resuming: bool, suspending: bool) -> Self {
+        Self {
+            load_op: if resuming { LoadOp::Load } else { self.load_op },
+            store_op: if suspending {
+                StoreOp::Store
+            } else {
+                self.store_op
+            },
+            clear_value: self.clear_value.clone(),
+            read_only: self.read_only,
+        }
+    }
+}
+
 /// Describes a color attachment to a render pass.
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
@@ -144,6 +170,20 @@ pub struct RenderPassDepthStencilAttachment {
     pub depth: PassChannel<f32>,
     /// What operations will be performed on the stencil part of the attachment.
     pub stencil: PassChannel<u32>,
+    /// The view that will receive the resolved depth and stencil, if multisampling is used.
+    pub resolve: Option<RenderPassDepthStencilResolve>,
+}
+
+/// Describes the resolve target of a depth/stencil attachment.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(feature = "serial-pass", feature = "trace"), derive(Serialize))]
+#[cfg_attr(any(feature = "serial-pass", feature = "replay"), derive(Deserialize))]
+pub struct RenderPassDepthStencilResolve {
+    /// The single-sampled view to resolve the attachment into.
+    pub view: id::TextureViewId,
+    /// How the samples of each aspect of the attachment are combined.
+    pub mode: wgt::ResolveMode,
 }
 
 impl RenderPassDepthStencilAttachment {
@@ -184,6 +224,33 @@ impl RenderPassDepthStencilAttachment {
     }
 }
 
+/// The attachments of a render pass suspended at the end of a command buffer,
+/// which the first render pass of the next submitted command buffer must resume.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SuspendedRenderPass {
+    colors: ArrayVec<
+        Option<(id::TextureViewId, Option<id::TextureViewId>)>,
+        { hal::MAX_COLOR_ATTACHMENTS },
+    >,
+    depth_stencil: Option<(id::TextureViewId, Option<id::TextureViewId>)>,
+}
+
+impl SuspendedRenderPass {
+    fn new(
+        color_attachments: &[Option<RenderPassColorAttachment>],
+        depth_stencil_attachment: Option<&RenderPassDepthStencilAttachment>,
+    ) -> Self {
+        Self {
+            colors: color_attachments
+                .iter()
+                .map(|at| at.as_ref().map(|at| (at.view, at.resolve_target)))
+                .collect(),
+            depth_stencil: depth_stencil_attachment
+                .map(|at| (at.view, at.resolve.as_ref().map(|resolve| resolve.view))),
+        }
+    }
+}
+
 /// Location to write a timestamp to (beginning or end of the pass).
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
@@ -209,6 +276,29 @@ pub struct RenderPassTimestampWrites {
     pub end_of_pass_write_index: Option<u32>,
 }
 
+/// Describes a pipeline statistics query covering a whole render pass.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(any(feature = "serial-pass", feature = "trace"), derive(Serialize))]
+#[cfg_attr(any(feature = "serial-pass", feature = "replay"), derive(Deserialize))]
+pub struct RenderPassPipelineStatisticsQuery {
+    /// The query set to write the statistics to.
+    pub query_set: id::QuerySetId,
+    /// The index of the query set at which the statistics of this pass are written.
+    pub query_index: u32,
+}
+
+/// Describes the shading rate attachment of a render pass.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(any(feature = "serial-pass", feature = "trace"), derive(Serialize))]
+#[cfg_attr(any(feature = "serial-pass", feature = "replay"), derive(Deserialize))]
+pub struct RenderPassShadingRateAttachment {
+    /// The view to use as the shading rate attachment. Each texel holds a
+    /// [`wgt::ShadingRate`] for one tile of the render targets.
+    pub view: id::TextureViewId,
+}
+
 /// Describes the attachments of a render pass.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct RenderPassDescriptor<'a> {
@@ -221,6 +311,10 @@ pub struct RenderPassDescriptor<'a> {
     pub timestamp_writes: Option<&'a RenderPassTimestampWrites>,
     /// Defines where the occlusion query results will be stored for this pass.
     pub occlusion_query_set: Option<id::QuerySetId>,
+    /// Defines where the pipeline statistics of this pass will be written, if any.
+    pub pipeline_statistics_query: Option<&'a RenderPassPipelineStatisticsQuery>,
+    /// The attachment controlling the shading rate of each tile of this pass, if any.
+    pub shading_rate_attachment: Option<&'a RenderPassShadingRateAttachment>,
 }
 
 #[cfg_attr(feature = "serial-pass", derive(Deserialize, Serialize))]
@@ -231,6 +325,10 @@ pub struct RenderPass {
     depth_stencil_target: Option<RenderPassDepthStencilAttachment>,
     timestamp_writes: Option<RenderPassTimestampWrites>,
     occlusion_query_set_id: Option<id::QuerySetId>,
+    pipeline_statistics_query: Option<RenderPassPipelineStatisticsQuery>,
+    shading_rate_attachment: Option<RenderPassShadingRateAttachment>,
+    resuming: bool,
+    suspending: bool,
 
     // Resource binding dedupe state.
     #[cfg_attr(feature = "serial-pass", serde(skip))]
@@ -248,6 +346,10 @@ impl RenderPass {
             depth_stencil_target: desc.depth_stencil_attachment.cloned(),
             timestamp_writes: desc.timestamp_writes.cloned(),
             occlusion_query_set_id: desc.occlusion_query_set,
+            pipeline_statistics_query: desc.pipeline_statistics_query.cloned(),
+            shading_rate_attachment: desc.shading_rate_attachment.cloned(),
+            resuming: false,
+            suspending: false,
 
             current_bind_groups: BindGroupStateChange::new(),
             current_pipeline: StateChange::new(),
@@ -266,9 +368,32 @@ impl RenderPass {
             target_depth_stencil: self.depth_stencil_target,
             timestamp_writes: self.timestamp_writes,
             occlusion_query_set_id: self.occlusion_query_set_id,
+            pipeline_statistics_query: self.pipeline_statistics_query,
+            shading_rate_attachment: self.shading_rate_attachment,
+            resuming: self.resuming,
+            suspending: self.suspending,
         }
     }
 
+    /// Makes this pass resume the render pass suspended at the end of the
+    /// command buffer submitted right before the one of this pass.
+    ///
+    /// The pass must be the first command of its encoder and have the same
+    /// attachments as the suspended pass. Its load operations are ignored.
+    pub fn resume(&mut self) {
+        self.resuming = true;
+    }
+
+    /// Suspends this pass at its end, to be resumed by the first render pass
+    /// of the command buffer submitted right after the one of this pass.
+    ///
+    /// No other commands can be recorded to the encoder after this pass. Its
+    /// store operations are ignored and its resolve targets are only written
+    /// by the part of the render pass that isn't suspended.
+    pub fn suspend(&mut self) {
+        self.suspending = true;
+    }
+
     pub fn set_index_buffer(
         &mut self,
         buffer_id: id::BufferId,
@@ -417,6 +542,31 @@ impl VertexState {
     }
 }
 
+#[derive(Debug, Default)]
+struct VertexCaptureState {
+    /// The `(buffer, offset, size)` set on each slot for the next capture.
+    buffers: [Option<(id::BufferId, BufferAddress, Option<BufferSize>)>;
+        hal::MAX_VERTEX_CAPTURE_BUFFERS],
+    /// How the current pipeline captures its vertices, if it does.
+    pipeline: Option<pipeline::VertexCapture>,
+    /// Whether a capture was begun and not yet ended.
+    active: bool,
+    /// The number of vertices the bound buffers of the active capture can hold.
+    capacity: u64,
+    /// The number of vertices written by the active capture so far.
+    captured: u64,
+}
+
+impl VertexCaptureState {
+    fn check_inactive(&self) -> Result<(), RenderPassErrorInner> {
+        if self.active {
+            Err(RenderPassErrorInner::CommandDuringVertexCapture)
+        } else {
+            Ok(())
+        }
+    }
+}
+
 #[derive(Debug)]
 struct State<A: HalApi> {
     pipeline_flags: PipelineFlags,
@@ -426,11 +576,16 @@ struct State<A: HalApi> {
     pipeline: Option<id::RenderPipelineId>,
     index: IndexState,
     vertex: VertexState,
+    vertex_capture: VertexCaptureState,
     debug_scope_depth: u32,
 }
 
 impl<A: HalApi> State<A> {
     fn is_ready(&self, indexed: bool) -> Result<(), DrawError> {
+        if self.pipeline_flags.contains(PipelineFlags::MESH_SHADER) {
+            return Err(DrawError::UnexpectedMeshPipeline);
+        }
+
         // Determine how many vertex buffers have already been bound
         let vertex_buffer_count = self.vertex.inputs.iter().take_while(|v| v.bound).count() as u32;
         // Compare with the needed quantity
@@ -476,12 +631,37 @@ impl<A: HalApi> State<A> {
         Ok(())
     }
 
+    fn is_ready_for_mesh_tasks(&self) -> Result<(), DrawError> {
+        if self.pipeline.is_none() {
+            return Err(DrawError::MissingPipeline);
+        }
+        if !self.pipeline_flags.contains(PipelineFlags::MESH_SHADER) {
+            return Err(DrawError::MissingMeshPipeline);
+        }
+
+        let bind_mask = self.binder.invalid_mask();
+        if bind_mask != 0 {
+            return Err(DrawError::IncompatibleBindGroup {
+                index: bind_mask.trailing_zeros(),
+                diff: self.binder.bgl_diff(),
+            });
+        }
+        if self.blend_constant == OptionalState::Required {
+            return Err(DrawError::MissingBlendConstant);
+        }
+
+        self.binder.check_late_buffer_bindings()?;
+
+        Ok(())
+    }
+
     /// Reset the `RenderBundle`-related states.
     fn reset_bundle(&mut self) {
         self.binder.reset();
         self.pipeline = None;
         self.index.reset();
         self.vertex.reset();
+        self.vertex_capture.pipeline = None;
     }
 }
 
@@ -492,6 +672,7 @@ impl<A: HalApi> State<A> {
 pub enum AttachmentErrorLocation {
     Color { index: usize, resolve: bool },
     Depth,
+    DepthResolve,
 }
 
 impl fmt::Display for AttachmentErrorLocation {
@@ -509,6 +690,9 @@ impl fmt::Display for AttachmentErrorLocation {
                 "color attachment at index {index}'s resolve texture view"
             ),
             AttachmentErrorLocation::Depth => write!(f, "depth attachment's texture view"),
+            AttachmentErrorLocation::DepthResolve => {
+                write!(f, "depth attachment's resolve texture view")
+            }
         }
     }
 }
@@ -528,6 +712,8 @@ pub enum RenderPassErrorInner {
     #[error(transparent)]
     Device(DeviceError),
     #[error(transparent)]
+    ComputeOnlyDevice(#[from] ComputeOnlyDevice),
+    #[error(transparent)]
     ColorAttachment(#[from] ColorAttachmentError),
     #[error(transparent)]
     Encoder(#[from] CommandEncoderError),
@@ -540,6 +726,11 @@ pub enum RenderPassErrorInner {
         location: AttachmentErrorLocation,
         format: wgt::TextureFormat,
     },
+    #[error("Depth-stencil attachments of format {format:?} can't be resolved with {mode:?}")]
+    UnsupportedDepthStencilResolveMode {
+        format: wgt::TextureFormat,
+        mode: wgt::ResolveMode,
+    },
     #[error("No color attachments or depth attachments were provided, at least one attachment of any kind must be provided")]
     MissingAttachments,
     #[error("The {location} is not renderable:")]
@@ -582,6 +773,10 @@ pub enum RenderPassErrorInner {
     OutOfMemory,
     #[error("The bind group at index {0:?} is invalid")]
     InvalidBindGroup(usize),
+    #[error("An immediate bind group was set at index {0}, but the current pipeline has no bind group layout at that index")]
+    MissingImmediateBindGroupLayout(u32),
+    #[error(transparent)]
+    CreateBindGroup(#[from] CreateBindGroupError),
     #[error("Unable to clear non-present/read-only depth")]
     InvalidDepthOps,
     #[error("Unable to clear non-present/read-only stencil")]
@@ -606,6 +801,14 @@ pub enum RenderPassErrorInner {
         end_count_offset: u64,
         count_buffer_size: u64,
     },
+    #[error("Indirect buffer offset {0} is not a multiple of 4")]
+    UnalignedIndirectBufferOffset(BufferAddress),
+    #[error("Indirect count buffer offset {0} is not a multiple of 4")]
+    UnalignedIndirectCountBufferOffset(BufferAddress),
+    #[error("Indirect arguments are invalid: {0}")]
+    InvalidIndirectArguments(&'static str),
+    #[error("Execute indirect offset {offset} is not a multiple of {alignment}")]
+    UnalignedIndirectOffset { offset: u64, alignment: u64 },
     #[error("Cannot pop debug group, because number of pushed debug groups is zero")]
     InvalidPopDebugGroup,
     #[error(transparent)]
@@ -642,6 +845,57 @@ pub enum RenderPassErrorInner {
     InvalidQuerySet(id::QuerySetId),
     #[error("missing occlusion query set")]
     MissingOcclusionQuerySet,
+    #[error("Conditional rendering offset {0} is not a multiple of 8")]
+    UnalignedConditionalRenderingOffset(BufferAddress),
+    #[error("Conditional rendering reads bytes {offset}..{end_offset} which overruns buffer of size {buffer_size}")]
+    ConditionalRenderingBufferOverrun {
+        offset: BufferAddress,
+        end_offset: BufferAddress,
+        buffer_size: BufferAddress,
+    },
+    #[error("Conditional rendering was already begun")]
+    ConditionalRenderingAlreadyActive,
+    #[error("Conditional rendering was not begun")]
+    ConditionalRenderingNotActive,
+    #[error("Conditional rendering was still active at the end of the pass")]
+    ConditionalRenderingNotEnded,
+    #[error("The format of the shading rate attachment ({0:?}) is not `R8Uint`")]
+    InvalidShadingRateAttachmentFormat(wgt::TextureFormat),
+    #[error("The shading rate attachment must be a single-sampled 2D view of a single mip level and array layer")]
+    InvalidShadingRateAttachmentView,
+    #[error("The shading rate attachment has size {size:?}, but the render targets need at least {required:?}")]
+    ShadingRateAttachmentTooSmall {
+        size: wgt::Extent3d,
+        required: wgt::Extent3d,
+    },
+    #[error("Vertex capture buffer slot {index} is greater than the maximum {max}")]
+    VertexCaptureSlotOutOfRange { index: u32, max: u32 },
+    #[error("Vertex capture buffer offset {0} is not a multiple of 4")]
+    UnalignedVertexCaptureOffset(BufferAddress),
+    #[error("Vertex capture uses bytes {offset}..{end_offset} which overruns buffer of size {buffer_size}")]
+    VertexCaptureBufferOverrun {
+        offset: BufferAddress,
+        end_offset: BufferAddress,
+        buffer_size: BufferAddress,
+    },
+    #[error("The current pipeline does not capture its vertices")]
+    MissingVertexCapturePipeline,
+    #[error("Vertex capture buffer {0} is written by the pipeline, but was not set")]
+    MissingVertexCaptureBuffer(u32),
+    #[error(
+        "Draw writes {vertices} vertices, but the vertex capture buffers only fit {remaining} more"
+    )]
+    VertexCaptureBufferOverflow { vertices: u64, remaining: u64 },
+    #[error("Vertex capture was already begun")]
+    VertexCaptureAlreadyActive,
+    #[error("Vertex capture was not begun")]
+    VertexCaptureNotActive,
+    #[error("Vertex capture was still active at the end of the pass")]
+    VertexCaptureNotEnded,
+    #[error("The command is not supported while vertex capture is active")]
+    CommandDuringVertexCapture,
+    #[error("A resuming render pass must be the first command of its encoder")]
+    ResumingPassNotFirst,
 }
 
 impl PrettyError for RenderPassErrorInner {
@@ -705,6 +959,138 @@ where
     }
 }
 
+/// Merges the resources of `bind_group` into the pass and binds it at `index`.
+fn set_bind_group<A: HalApi>(
+    state: &mut State<A>,
+    info: &mut RenderPassInfo<'_, A>,
+    raw: &mut A::CommandEncoder,
+    buffer_memory_init_actions: &mut Vec<BufferInitTrackerAction<A>>,
+    texture_memory_actions: &mut CommandBufferTextureMemoryActions<A>,
+    snatch_guard: &SnatchGuard,
+    index: u32,
+    bind_group: &Arc<BindGroup<A>>,
+    offsets: &[wgt::DynamicOffset],
+) -> Result<(), RenderPassErrorInner> {
+    // merge the resource tracker in
+    unsafe {
+        info.usage_scope.merge_bind_group(&bind_group.used)?;
+    }
+    //Note: stateless trackers are not merged: the lifetime reference
+    // is held to the bind group itself.
+
+    buffer_memory_init_actions.extend(bind_group.used_buffer_ranges.iter().filter_map(|action| {
+        action
+            .buffer
+            .initialization_status
+            .read()
+            .check_action(action)
+    }));
+    for action in bind_group.used_texture_ranges.iter() {
+        info.pending_discard_init_fixups
+            .extend(texture_memory_actions.register_init_action(action));
+    }
+
+    let pipeline_layout = state.binder.pipeline_layout.clone();
+    let entries = state
+        .binder
+        .assign_group(index as usize, bind_group, offsets);
+    if !entries.is_empty() && pipeline_layout.is_some() {
+        let pipeline_layout = pipeline_layout.as_ref().unwrap().raw();
+        for (i, e) in entries.iter().enumerate() {
+            if let Some(group) = e.group.as_ref() {
+                let raw_bg = group
+                    .raw(snatch_guard)
+                    .ok_or(RenderPassErrorInner::InvalidBindGroup(i))?;
+                unsafe {
+                    raw.set_bind_group(
+                        pipeline_layout,
+                        index + i as u32,
+                        raw_bg,
+                        &e.dynamic_offsets,
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that `arguments` describe commands that the backends can execute
+/// with the pipeline `layout`.
+fn validate_indirect_arguments<A: HalApi>(
+    arguments: &[wgt::IndirectArgument],
+    max_vertex_buffers: u32,
+    layout: &PipelineLayout<A>,
+) -> Result<(), RenderPassErrorInner> {
+    let Some((draw, state_changes)) = arguments.split_last() else {
+        return Err(RenderPassErrorInner::InvalidIndirectArguments(
+            "the arguments are empty",
+        ));
+    };
+    if !matches!(
+        *draw,
+        wgt::IndirectArgument::Draw | wgt::IndirectArgument::DrawIndexed
+    ) {
+        return Err(RenderPassErrorInner::InvalidIndirectArguments(
+            "the last argument must be a draw",
+        ));
+    }
+
+    let mut vertex_slots = 0u64;
+    let mut offset = 0;
+    for argument in state_changes {
+        match *argument {
+            wgt::IndirectArgument::VertexBuffer { slot } => {
+                if slot >= max_vertex_buffers {
+                    return Err(RenderCommandError::VertexBufferIndexOutOfRange {
+                        index: slot,
+                        max: max_vertex_buffers,
+                    }
+                    .into());
+                }
+                if vertex_slots & (1 << slot) != 0 {
+                    return Err(RenderPassErrorInner::InvalidIndirectArguments(
+                        "a vertex buffer slot is bound more than once",
+                    ));
+                }
+                vertex_slots |= 1 << slot;
+                if offset % 8 != 0 {
+                    return Err(RenderPassErrorInner::InvalidIndirectArguments(
+                        "vertex buffer arguments must start at a multiple of 8 bytes",
+                    ));
+                }
+            }
+            wgt::IndirectArgument::PushConstants {
+                stages,
+                offset: push_constant_offset,
+                size,
+            } => {
+                if push_constant_offset % wgt::PUSH_CONSTANT_ALIGNMENT != 0
+                    || size % wgt::PUSH_CONSTANT_ALIGNMENT != 0
+                {
+                    return Err(RenderPassErrorInner::InvalidIndirectArguments(
+                        "push constant offsets and sizes must be multiples of 4 bytes",
+                    ));
+                }
+                layout
+                    .validate_push_constant_ranges(
+                        stages,
+                        push_constant_offset,
+                        push_constant_offset + size,
+                    )
+                    .map_err(RenderCommandError::from)?;
+            }
+            wgt::IndirectArgument::Draw | wgt::IndirectArgument::DrawIndexed => {
+                return Err(RenderPassErrorInner::InvalidIndirectArguments(
+                    "only the last argument may be a draw",
+                ));
+            }
+        }
+        offset += argument.size();
+    }
+    Ok(())
+}
+
 struct RenderAttachment<'a, A: HalApi> {
     texture: Arc<Texture<A>>,
     selector: &'a TextureSelector,
@@ -781,6 +1167,7 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
         depth_stencil_attachment: Option<&RenderPassDepthStencilAttachment>,
         timestamp_writes: Option<&RenderPassTimestampWrites>,
         occlusion_query_set: Option<id::QuerySetId>,
+        shading_rate_attachment: Option<&RenderPassShadingRateAttachment>,
         encoder: &mut CommandEncoder<A>,
         trackers: &mut Tracker<A>,
         texture_memory_actions: &mut CommandBufferTextureMemoryActions<A>,
@@ -991,11 +1378,85 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
             };
             render_attachments.push(view.to_render_attachment(usage));
 
+            let mut hal_resolve_target = None;
+            if let Some(ref resolve) = at.resolve {
+                device.require_features(wgt::Features::DEPTH_STENCIL_RESOLVE)?;
+
+                let resolve_view: &TextureView<A> = trackers
+                    .views
+                    .add_single(view_guard, resolve.view)
+                    .ok_or(RenderPassErrorInner::InvalidAttachment(resolve.view))?;
+
+                check_multiview(resolve_view)?;
+
+                let resolve_location = AttachmentErrorLocation::DepthResolve;
+                let render_extent = resolve_view.render_extent.map_err(|reason| {
+                    RenderPassErrorInner::TextureViewIsNotRenderable {
+                        location: resolve_location,
+                        reason,
+                    }
+                })?;
+                if view.render_extent.unwrap() != render_extent {
+                    return Err(RenderPassErrorInner::AttachmentsDimensionMismatch {
+                        expected_location: AttachmentErrorLocation::Depth,
+                        expected_extent: view.render_extent.unwrap(),
+                        actual_location: resolve_location,
+                        actual_extent: render_extent,
+                    });
+                }
+                if view.samples == 1 || resolve_view.samples != 1 {
+                    return Err(RenderPassErrorInner::InvalidResolveSampleCounts {
+                        location: resolve_location,
+                        src: view.samples,
+                        dst: resolve_view.samples,
+                    });
+                }
+                if view.desc.format != resolve_view.desc.format {
+                    return Err(RenderPassErrorInner::MismatchedResolveTextureFormat {
+                        location: resolve_location,
+                        src: view.desc.format,
+                        dst: resolve_view.desc.format,
+                    });
+                }
+                let required_flags = match resolve.mode {
+                    wgt::ResolveMode::SampleZero => wgt::TextureFormatFeatureFlags::empty(),
+                    wgt::ResolveMode::Average => {
+                        wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_DEPTH_AVERAGE
+                    }
+                    wgt::ResolveMode::Min | wgt::ResolveMode::Max => {
+                        wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_MIN_MAX
+                    }
+                };
+                if !resolve_view.format_features.flags.contains(required_flags) {
+                    return Err(RenderPassErrorInner::UnsupportedDepthStencilResolveMode {
+                        format: resolve_view.desc.format,
+                        mode: resolve.mode,
+                    });
+                }
+
+                texture_memory_actions.register_implicit_init(
+                    &resolve_view.parent,
+                    TextureInitRange::from(resolve_view.selector.clone()),
+                );
+                render_attachments
+                    .push(resolve_view.to_render_attachment(hal::TextureUses::DEPTH_STENCIL_WRITE));
+
+                hal_resolve_target = Some(hal::Attachment {
+                    view: resolve_view.raw(),
+                    usage: hal::TextureUses::DEPTH_STENCIL_WRITE,
+                });
+            }
+
             depth_stencil = Some(hal::DepthStencilAttachment {
                 target: hal::Attachment {
                     view: view.raw(),
                     usage,
                 },
+                resolve_target: hal_resolve_target,
+                resolve_mode: at
+                    .resolve
+                    .as_ref()
+                    .map_or(wgt::ResolveMode::default(), |resolve| resolve.mode),
                 depth_ops: at.depth.hal_ops(),
                 stencil_ops: at.stencil.hal_ops(),
                 clear_value: (at.depth.clear_value, at.stencil.clear_value),
@@ -1121,6 +1582,61 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
         let extent = extent.ok_or(RenderPassErrorInner::MissingAttachments)?;
         let multiview = detected_multiview.expect("Multiview was not detected, no attachments");
 
+        let shading_rate_attachment = if let Some(at) = shading_rate_attachment {
+            device.require_features(wgt::Features::SHADING_RATE_ATTACHMENT)?;
+
+            let view: &TextureView<A> = trackers
+                .views
+                .add_single(view_guard, at.view)
+                .ok_or(RenderPassErrorInner::InvalidAttachment(at.view))?;
+
+            if view.desc.format != wgt::TextureFormat::R8Uint {
+                return Err(RenderPassErrorInner::InvalidShadingRateAttachmentFormat(
+                    view.desc.format,
+                ));
+            }
+            if view.desc.dimension != TextureViewDimension::D2
+                || view.selector.mips.end - view.selector.mips.start != 1
+                || view.selector.layers.end - view.selector.layers.start != 1
+                || view.samples != 1
+            {
+                return Err(RenderPassErrorInner::InvalidShadingRateAttachmentView);
+            }
+
+            // Each texel of the attachment covers one tile of the render targets.
+            let tile_size = device.limits.shading_rate_tile_size;
+            let size = view
+                .parent
+                .desc
+                .size
+                .mip_level_size(view.selector.mips.start, view.parent.desc.dimension);
+            let required = wgt::Extent3d {
+                width: (extent.width + tile_size - 1) / tile_size,
+                height: (extent.height + tile_size - 1) / tile_size,
+                depth_or_array_layers: 1,
+            };
+            if size.width < required.width || size.height < required.height {
+                return Err(RenderPassErrorInner::ShadingRateAttachmentTooSmall { size, required });
+            }
+
+            pending_discard_init_fixups.extend(texture_memory_actions.register_init_action(
+                &TextureInitTrackerAction {
+                    texture: view.parent.clone(),
+                    range: TextureInitRange::from(view.selector.clone()),
+                    kind: MemoryInitKind::NeedsInitializedMemory,
+                },
+            ));
+            render_attachments
+                .push(view.to_render_attachment(hal::TextureUses::SHADING_RATE_ATTACHMENT));
+
+            Some(hal::Attachment {
+                view: view.raw(),
+                usage: hal::TextureUses::SHADING_RATE_ATTACHMENT,
+            })
+        } else {
+            None
+        };
+
         let view_data = AttachmentData {
             colors: color_attachments
                 .iter()
@@ -1187,6 +1703,7 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
             multiview,
             timestamp_writes,
             occlusion_query_set,
+            shading_rate_attachment,
         };
         unsafe {
             encoder.raw.begin_render_pass(&hal_desc);
@@ -1217,7 +1734,12 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
 
         for ra in self.render_attachments {
             let texture = &ra.texture;
-            check_texture_usage(texture.desc.usage, TextureUsages::RENDER_ATTACHMENT)?;
+            let expected_usage = if ra.usage == hal::TextureUses::SHADING_RATE_ATTACHMENT {
+                TextureUsages::SHADING_RATE_ATTACHMENT
+            } else {
+                TextureUsages::RENDER_ATTACHMENT
+            };
+            check_texture_usage(texture.desc.usage, expected_usage)?;
 
             // the tracker set of the pass is always in "extend" mode
             unsafe {
@@ -1259,6 +1781,8 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
                         view: view.raw(),
                         usage: hal::TextureUses::DEPTH_STENCIL_WRITE,
                     },
+                    resolve_target: None,
+                    resolve_mode: wgt::ResolveMode::default(),
                     depth_ops,
                     stencil_ops,
                     clear_value: (0.0, 0),
@@ -1266,6 +1790,7 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
                 multiview: self.multiview,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                shading_rate_attachment: None,
             };
             unsafe {
                 raw.begin_render_pass(&desc);
@@ -1292,6 +1817,10 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             pass.depth_stencil_target.as_ref(),
             pass.timestamp_writes.as_ref(),
             pass.occlusion_query_set_id,
+            pass.pipeline_statistics_query.as_ref(),
+            pass.shading_rate_attachment.as_ref(),
+            pass.resuming,
+            pass.suspending,
         )
     }
 
@@ -1304,6 +1833,10 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         depth_stencil_attachment: Option<&RenderPassDepthStencilAttachment>,
         timestamp_writes: Option<&RenderPassTimestampWrites>,
         occlusion_query_set_id: Option<id::QuerySetId>,
+        pipeline_statistics_query: Option<&RenderPassPipelineStatisticsQuery>,
+        shading_rate_attachment: Option<&RenderPassShadingRateAttachment>,
+        resuming: bool,
+        suspending: bool,
     ) -> Result<(), RenderPassError> {
         profiling::scope!(
             "CommandEncoder::run_render_pass {}",
@@ -1336,12 +1869,38 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     target_depth_stencil: depth_stencil_attachment.cloned(),
                     timestamp_writes: timestamp_writes.cloned(),
                     occlusion_query_set_id,
+                    pipeline_statistics_query: pipeline_statistics_query.cloned(),
+                    shading_rate_attachment: shading_rate_attachment.cloned(),
+                    resuming,
+                    suspending,
                 });
             }
 
             if !device.is_valid() {
                 return Err(DeviceError::Lost).map_pass_err(pass_scope);
             }
+            device.require_rendering().map_pass_err(pass_scope)?;
+
+            if let Some(label) = base.label {
+                cmd_buf_data.record_debug_marker(device, label);
+            }
+
+            if resuming {
+                if cmd_buf_data.encoder.is_open || !cmd_buf_data.encoder.list.is_empty() {
+                    return Err(RenderPassErrorInner::ResumingPassNotFirst)
+                        .map_pass_err(pass_scope);
+                }
+                cmd_buf_data.resumed_render_pass = Some(SuspendedRenderPass::new(
+                    color_attachments,
+                    depth_stencil_attachment,
+                ));
+            }
+            if suspending {
+                cmd_buf_data.suspended_render_pass = Some(SuspendedRenderPass::new(
+                    color_attachments,
+                    depth_stencil_attachment,
+                ));
+            }
 
             let encoder = &mut cmd_buf_data.encoder;
             let status = &mut cmd_buf_data.status;
@@ -1371,34 +1930,55 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 encoder_id
             );
 
+            // The resolve targets of a suspended pass are written by the pass
+            // resuming it.
+            let continued_color_attachments = color_attachments
+                .iter()
+                .map(|at| {
+                    at.as_ref().map(|at| RenderPassColorAttachment {
+                        view: at.view,
+                        resolve_target: at.resolve_target.filter(|_| !suspending),
+                        channel: at.channel.continued(resuming, suspending),
+                    })
+                })
+                .collect::<ArrayVec<_, { hal::MAX_COLOR_ATTACHMENTS }>>();
+            let continued_depth_stencil_attachment =
+                depth_stencil_attachment.map(|at| RenderPassDepthStencilAttachment {
+                    view: at.view,
+                    depth: at.depth.continued(resuming, suspending),
+                    stencil: at.stencil.continued(resuming, suspending),
+                    resolve: at.resolve.clone().filter(|_| !suspending),
+                });
+
             let mut info = RenderPassInfo::start(
                 device,
                 label,
-                color_attachments,
-                depth_stencil_attachment,
+                &continued_color_attachments,
+                continued_depth_stencil_attachment.as_ref(),
                 timestamp_writes,
                 occlusion_query_set_id,
+                shading_rate_attachment,
                 encoder,
                 tracker,
                 texture_memory_actions,
                 pending_query_resets,
-                &*view_guard,
-                &*buffer_guard,
-                &*texture_guard,
-                &*query_set_guard,
+                &view_guard,
+                &buffer_guard,
+                &texture_guard,
+                &query_set_guard,
             )
             .map_pass_err(pass_scope)?;
 
             tracker.set_size(
-                Some(&*buffer_guard),
-                Some(&*texture_guard),
-                Some(&*view_guard),
+                Some(&buffer_guard),
+                Some(&texture_guard),
+                Some(&view_guard),
                 None,
-                Some(&*bind_group_guard),
+                Some(&bind_group_guard),
                 None,
-                Some(&*render_pipeline_guard),
-                Some(&*bundle_guard),
-                Some(&*query_set_guard),
+                Some(&render_pipeline_guard),
+                Some(&bundle_guard),
+                Some(&query_set_guard),
             );
 
             let raw = &mut encoder.raw;
@@ -1411,12 +1991,37 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 pipeline: None,
                 index: IndexState::default(),
                 vertex: VertexState::default(),
+                vertex_capture: VertexCaptureState::default(),
                 debug_scope_depth: 0,
             };
             let mut temp_offsets = Vec::new();
             let mut dynamic_offset_count = 0;
             let mut string_offset = 0;
-            let mut active_query = None;
+            let mut indirect_argument_offset = 0;
+            let mut immediate_entry_offset = 0;
+            let mut active_occlusion_query = None;
+            let mut active_pipeline_statistics_query = None;
+            let mut conditional_rendering_active = false;
+
+            if let Some(statistics) = pipeline_statistics_query {
+                let scope = PassErrorScope::BeginPipelineStatisticsQuery;
+
+                let query_set = tracker
+                    .query_sets
+                    .add_single(&query_set_guard, statistics.query_set)
+                    .ok_or(RenderCommandError::InvalidQuerySet(statistics.query_set))
+                    .map_pass_err(scope)?;
+
+                query_set
+                    .validate_and_begin_pipeline_statistics_query(
+                        raw,
+                        statistics.query_set,
+                        statistics.query_index,
+                        Some(&mut cmd_buf_data.pending_query_resets),
+                        &mut active_pipeline_statistics_query,
+                    )
+                    .map_pass_err(scope)?;
+            }
 
             for command in base.commands {
                 match *command {
@@ -1446,7 +2051,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
                         let bind_group = tracker
                             .bind_groups
-                            .add_single(&*bind_group_guard, bind_group_id)
+                            .add_single(&bind_group_guard, bind_group_id)
                             .ok_or(RenderCommandError::InvalidBindGroup(bind_group_id))
                             .map_pass_err(scope)?;
 
@@ -1458,63 +2063,92 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             .validate_dynamic_bindings(index, &temp_offsets, &cmd_buf.limits)
                             .map_pass_err(scope)?;
 
-                        // merge the resource tracker in
-                        unsafe {
-                            info.usage_scope
-                                .merge_bind_group(&bind_group.used)
-                                .map_pass_err(scope)?;
-                        }
-                        //Note: stateless trackers are not merged: the lifetime reference
-                        // is held to the bind group itself.
+                        set_bind_group(
+                            &mut state,
+                            &mut info,
+                            raw,
+                            buffer_memory_init_actions,
+                            texture_memory_actions,
+                            &snatch_guard,
+                            index,
+                            bind_group,
+                            &temp_offsets,
+                        )
+                        .map_pass_err(scope)?;
+                    }
+                    RenderCommand::SetBindGroupImmediate { index, num_entries } => {
+                        api_log!("RenderPass::set_bind_group_immediate {index}");
 
-                        buffer_memory_init_actions.extend(
-                            bind_group.used_buffer_ranges.iter().filter_map(|action| {
-                                action
-                                    .buffer
-                                    .initialization_status
-                                    .read()
-                                    .check_action(action)
-                            }),
-                        );
-                        for action in bind_group.used_texture_ranges.iter() {
-                            info.pending_discard_init_fixups
-                                .extend(texture_memory_actions.register_init_action(action));
+                        let scope = PassErrorScope::SetBindGroupImmediate;
+                        let max_bind_groups = device.limits.max_bind_groups;
+                        if index >= max_bind_groups {
+                            return Err(RenderCommandError::BindGroupIndexOutOfRange {
+                                index,
+                                max: max_bind_groups,
+                            })
+                            .map_pass_err(scope);
                         }
 
-                        let pipeline_layout = state.binder.pipeline_layout.clone();
-                        let entries =
-                            state
-                                .binder
-                                .assign_group(index as usize, bind_group, &temp_offsets);
-                        if !entries.is_empty() && pipeline_layout.is_some() {
-                            let pipeline_layout = pipeline_layout.as_ref().unwrap().raw();
-                            for (i, e) in entries.iter().enumerate() {
-                                if let Some(group) = e.group.as_ref() {
-                                    let raw_bg = group
-                                        .raw(&snatch_guard)
-                                        .ok_or(RenderPassErrorInner::InvalidBindGroup(i))
-                                        .map_pass_err(scope)?;
-                                    unsafe {
-                                        raw.set_bind_group(
-                                            pipeline_layout,
-                                            index + i as u32,
-                                            raw_bg,
-                                            &e.dynamic_offsets,
-                                        );
-                                    }
-                                }
-                            }
-                        }
+                        let entries = &base.immediate_bind_group_entries
+                            [immediate_entry_offset..immediate_entry_offset + num_entries];
+                        immediate_entry_offset += num_entries;
+
+                        // The bind group is built for the layout that the current
+                        // pipeline expects at this index.
+                        let layout = state
+                            .binder
+                            .pipeline_layout
+                            .as_ref()
+                            .and_then(|layout| layout.bind_group_layouts.get(index as usize))
+                            .cloned()
+                            .ok_or(RenderPassErrorInner::MissingImmediateBindGroupLayout(index))
+                            .map_pass_err(scope)?;
+                        let desc = BindGroupDescriptor {
+                            label: None,
+                            layout: layout.as_info().id(),
+                            entries: Cow::Borrowed(entries),
+                        };
+                        let bind_group = device
+                            .create_bind_group_with_guards(
+                                &layout,
+                                &desc,
+                                &buffer_guard,
+                                &view_guard,
+                                &hub.samplers.read(),
+                                &hub.tlas_s.read(),
+                                &snatch_guard,
+                            )
+                            .map_pass_err(scope)?;
+                        // The bind group is never registered, so nothing else can refer
+                        // to it. The command buffer keeps it alive until it has executed.
+                        let bind_group = hub.bind_groups.request().init(bind_group);
+                        cmd_buf_data
+                            .temp_resources
+                            .push(TempResource::BindGroup(bind_group.clone()));
+
+                        set_bind_group(
+                            &mut state,
+                            &mut info,
+                            raw,
+                            buffer_memory_init_actions,
+                            texture_memory_actions,
+                            &snatch_guard,
+                            index,
+                            &bind_group,
+                            &[],
+                        )
+                        .map_pass_err(scope)?;
                     }
                     RenderCommand::SetPipeline(pipeline_id) => {
                         api_log!("RenderPass::set_pipeline {pipeline_id:?}");
 
                         let scope = PassErrorScope::SetPipelineRender(pipeline_id);
+                        state.vertex_capture.check_inactive().map_pass_err(scope)?;
                         state.pipeline = Some(pipeline_id);
 
                         let pipeline: &pipeline::RenderPipeline<A> = tracker
                             .render_pipelines
-                            .add_single(&*render_pipeline_guard, pipeline_id)
+                            .add_single(&render_pipeline_guard, pipeline_id)
                             .ok_or(RenderCommandError::InvalidPipeline(pipeline_id))
                             .map_pass_err(scope)?;
 
@@ -1547,6 +2181,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
                         unsafe {
                             raw.set_render_pipeline(pipeline.raw());
+                            if let Some(ref debug_printf) = device.debug_printf {
+                                debug_printf.bind(raw, pipeline.layout.raw());
+                            }
                         }
 
                         if pipeline.flags.contains(PipelineFlags::STENCIL_REFERENCE) {
@@ -1610,6 +2247,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         }
 
                         state.index.pipeline_format = pipeline.strip_index_format;
+                        state.vertex_capture.pipeline = pipeline.vertex_capture.clone();
 
                         let vertex_steps_len = pipeline.vertex_steps.len();
                         state.vertex.buffers_required = vertex_steps_len as u32;
@@ -1644,7 +2282,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         let buffer = info
                             .usage_scope
                             .buffers
-                            .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDEX)
+                            .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDEX)
                             .map_pass_err(scope)?;
 
                         if buffer.device.as_info().id() != device.as_info().id() {
@@ -1697,7 +2335,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         let buffer = info
                             .usage_scope
                             .buffers
-                            .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::VERTEX)
+                            .merge_single(&buffer_guard, buffer_id, hal::BufferUses::VERTEX)
                             .map_pass_err(scope)?;
 
                         if buffer.device.as_info().id() != device.as_info().id() {
@@ -1877,12 +2515,24 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             raw.set_scissor_rect(&r);
                         }
                     }
-                    RenderCommand::Draw {
-                        vertex_count,
-                        instance_count,
-                        first_vertex,
-                        first_instance,
-                    } => {
+                    RenderCommand::SetShadingRate(rate) => {
+                        api_log!("RenderPass::set_shading_rate {rate:?}");
+
+                        let scope = PassErrorScope::SetShadingRate;
+                        device
+                            .require_features(wgt::Features::SHADING_RATE)
+                            .map_pass_err(scope)?;
+
+                        unsafe {
+                            raw.set_shading_rate(rate);
+                        }
+                    }
+                    RenderCommand::Draw {
+                        vertex_count,
+                        instance_count,
+                        first_vertex,
+                        first_instance,
+                    } => {
                         api_log!(
                             "RenderPass::draw {vertex_count} {instance_count} {first_vertex} {first_instance}"
                         );
@@ -1916,6 +2566,28 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             .map_pass_err(scope);
                         }
 
+                        if state.vertex_capture.active {
+                            // Incomplete primitives are dropped, not captured.
+                            let vertices_per_primitive = state
+                                .vertex_capture
+                                .pipeline
+                                .as_ref()
+                                .map_or(1, |capture| capture.vertices_per_primitive);
+                            let vertices = (vertex_count - vertex_count % vertices_per_primitive)
+                                as u64
+                                * instance_count as u64;
+                            let remaining =
+                                state.vertex_capture.capacity - state.vertex_capture.captured;
+                            if vertices > remaining {
+                                return Err(RenderPassErrorInner::VertexCaptureBufferOverflow {
+                                    vertices,
+                                    remaining,
+                                })
+                                .map_pass_err(scope);
+                            }
+                            state.vertex_capture.captured += vertices;
+                        }
+
                         unsafe {
                             raw.draw(first_vertex, vertex_count, first_instance, instance_count);
                         }
@@ -1936,6 +2608,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             pipeline: state.pipeline,
                         };
                         state.is_ready(indexed).map_pass_err(scope)?;
+                        state.vertex_capture.check_inactive().map_pass_err(scope)?;
 
                         let last_index = first_index as u64 + index_count as u64;
                         let index_limit = state.index.limit;
@@ -1981,6 +2654,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             pipeline: state.pipeline,
                         };
                         state.is_ready(indexed).map_pass_err(scope)?;
+                        state.vertex_capture.check_inactive().map_pass_err(scope)?;
 
                         let stride = match indexed {
                             false => mem::size_of::<wgt::DrawIndirectArgs>(),
@@ -1999,7 +2673,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         let indirect_buffer = info
                             .usage_scope
                             .buffers
-                            .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                            .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
                             .map_pass_err(scope)?;
                         check_buffer_usage(indirect_buffer.usage, BufferUsages::INDIRECT)
                             .map_pass_err(scope)?;
@@ -2055,6 +2729,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             pipeline: state.pipeline,
                         };
                         state.is_ready(indexed).map_pass_err(scope)?;
+                        state.vertex_capture.check_inactive().map_pass_err(scope)?;
 
                         let stride = match indexed {
                             false => mem::size_of::<wgt::DrawIndirectArgs>(),
@@ -2068,10 +2743,23 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
                             .map_pass_err(scope)?;
 
+                        if offset % 4 != 0 {
+                            return Err(RenderPassErrorInner::UnalignedIndirectBufferOffset(
+                                offset,
+                            ))
+                            .map_pass_err(scope);
+                        }
+                        if count_buffer_offset % 4 != 0 {
+                            return Err(RenderPassErrorInner::UnalignedIndirectCountBufferOffset(
+                                count_buffer_offset,
+                            ))
+                            .map_pass_err(scope);
+                        }
+
                         let indirect_buffer = info
                             .usage_scope
                             .buffers
-                            .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                            .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
                             .map_pass_err(scope)?;
                         check_buffer_usage(indirect_buffer.usage, BufferUsages::INDIRECT)
                             .map_pass_err(scope)?;
@@ -2084,11 +2772,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         let count_buffer = info
                             .usage_scope
                             .buffers
-                            .merge_single(
-                                &*buffer_guard,
-                                count_buffer_id,
-                                hal::BufferUses::INDIRECT,
-                            )
+                            .merge_single(&buffer_guard, count_buffer_id, hal::BufferUses::INDIRECT)
                             .map_pass_err(scope)?;
                         check_buffer_usage(count_buffer.usage, BufferUsages::INDIRECT)
                             .map_pass_err(scope)?;
@@ -2098,7 +2782,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             .ok_or(RenderCommandError::DestroyedBuffer(count_buffer_id))
                             .map_pass_err(scope)?;
 
-                        let end_offset = offset + stride * max_count as u64;
+                        // `max_count` draws are read at most, as the GPU clamps the
+                        // count read from `count_buffer` to it.
+                        let end_offset = offset.saturating_add(stride * max_count as u64);
                         if end_offset > indirect_buffer.size {
                             return Err(RenderPassErrorInner::IndirectBufferOverrun {
                                 count: None,
@@ -2117,7 +2803,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         );
 
                         let begin_count_offset = count_buffer_offset;
-                        let end_count_offset = count_buffer_offset + 4;
+                        let end_count_offset = count_buffer_offset.saturating_add(4);
                         if end_count_offset > count_buffer.size {
                             return Err(RenderPassErrorInner::IndirectCountBufferOverrun {
                                 begin_count_offset,
@@ -2155,6 +2841,246 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             },
                         }
                     }
+                    RenderCommand::DrawMeshTasks {
+                        group_count_x,
+                        group_count_y,
+                        group_count_z,
+                    } => {
+                        api_log!("RenderPass::draw_mesh_tasks {group_count_x} {group_count_y} {group_count_z}");
+
+                        let scope = PassErrorScope::DrawMeshTasks {
+                            indirect: false,
+                            pipeline: state.pipeline,
+                        };
+                        device
+                            .require_features(wgt::Features::EXPERIMENTAL_MESH_SHADER)
+                            .map_pass_err(scope)?;
+                        state.is_ready_for_mesh_tasks().map_pass_err(scope)?;
+                        state.vertex_capture.check_inactive().map_pass_err(scope)?;
+
+                        unsafe {
+                            raw.draw_mesh_tasks(group_count_x, group_count_y, group_count_z);
+                        }
+                    }
+                    RenderCommand::DrawMeshTasksIndirect { buffer_id, offset } => {
+                        api_log!("RenderPass::draw_mesh_tasks_indirect {buffer_id:?} {offset}");
+
+                        let scope = PassErrorScope::DrawMeshTasks {
+                            indirect: true,
+                            pipeline: state.pipeline,
+                        };
+                        device
+                            .require_features(wgt::Features::EXPERIMENTAL_MESH_SHADER)
+                            .map_pass_err(scope)?;
+                        state.is_ready_for_mesh_tasks().map_pass_err(scope)?;
+                        state.vertex_capture.check_inactive().map_pass_err(scope)?;
+
+                        let indirect_buffer = info
+                            .usage_scope
+                            .buffers
+                            .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(indirect_buffer.usage, BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                        let indirect_raw = indirect_buffer
+                            .raw
+                            .get(&snatch_guard)
+                            .ok_or(RenderCommandError::DestroyedBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+
+                        let end_offset =
+                            offset + mem::size_of::<wgt::DrawMeshTasksIndirectArgs>() as u64;
+                        if end_offset > indirect_buffer.size {
+                            return Err(RenderPassErrorInner::IndirectBufferOverrun {
+                                count: None,
+                                offset,
+                                end_offset,
+                                buffer_size: indirect_buffer.size,
+                            })
+                            .map_pass_err(scope);
+                        }
+
+                        buffer_memory_init_actions.extend(
+                            indirect_buffer.initialization_status.read().create_action(
+                                indirect_buffer,
+                                offset..end_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
+
+                        unsafe {
+                            raw.draw_mesh_tasks_indirect(indirect_raw, offset, 1);
+                        }
+                    }
+                    RenderCommand::ExecuteIndirect {
+                        num_arguments,
+                        buffer_id,
+                        offset,
+                        count_buffer_id,
+                        count_buffer_offset,
+                        max_count,
+                    } => {
+                        api_log!("RenderPass::execute_indirect {buffer_id:?} {offset} {count_buffer_id:?} {count_buffer_offset:?} {max_count:?}");
+
+                        let arguments = &base.indirect_arguments
+                            [indirect_argument_offset..indirect_argument_offset + num_arguments];
+                        indirect_argument_offset += num_arguments;
+
+                        let indexed =
+                            matches!(arguments.last(), Some(wgt::IndirectArgument::DrawIndexed));
+                        let scope = PassErrorScope::Draw {
+                            indexed,
+                            indirect: true,
+                            pipeline: state.pipeline,
+                        };
+                        state.vertex_capture.check_inactive().map_pass_err(scope)?;
+
+                        device
+                            .require_features(wgt::Features::DEVICE_GENERATED_COMMANDS)
+                            .map_pass_err(scope)?;
+                        device
+                            .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
+                            .map_pass_err(scope)?;
+
+                        let pipeline_layout = state
+                            .binder
+                            .pipeline_layout
+                            .as_ref()
+                            .ok_or(DrawError::MissingPipeline)
+                            .map_pass_err(scope)?;
+                        validate_indirect_arguments(
+                            arguments,
+                            device.limits.max_vertex_buffers,
+                            pipeline_layout,
+                        )
+                        .map_pass_err(scope)?;
+
+                        // The vertex buffers bound by the commands count as bound while they execute.
+                        let vertex_slots =
+                            arguments.iter().filter_map(|argument| match *argument {
+                                wgt::IndirectArgument::VertexBuffer { slot } => Some(slot as usize),
+                                _ => None,
+                            });
+                        for slot in vertex_slots.clone() {
+                            let empty_slots = (1 + slot).saturating_sub(state.vertex.inputs.len());
+                            state
+                                .vertex
+                                .inputs
+                                .extend(iter::repeat(VertexBufferState::EMPTY).take(empty_slots));
+                            state.vertex.inputs[slot].bound = true;
+                        }
+                        state.is_ready(indexed).map_pass_err(scope)?;
+
+                        let pipeline = state
+                            .pipeline
+                            .and_then(|id| render_pipeline_guard.get(id).ok())
+                            .ok_or(DrawError::MissingPipeline)
+                            .map_pass_err(scope)?;
+
+                        let alignment = if vertex_slots.clone().next().is_some() {
+                            8
+                        } else {
+                            4
+                        };
+                        if offset % alignment != 0 {
+                            return Err(RenderPassErrorInner::UnalignedIndirectOffset {
+                                offset,
+                                alignment,
+                            })
+                            .map_pass_err(scope);
+                        }
+
+                        let indirect_buffer = info
+                            .usage_scope
+                            .buffers
+                            .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(indirect_buffer.usage, BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                        let indirect_raw = indirect_buffer
+                            .raw
+                            .get(&snatch_guard)
+                            .ok_or(RenderCommandError::DestroyedBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+
+                        let stride = wgt::IndirectArgument::stride(arguments) as u64;
+                        let end_offset = offset + stride * max_count as u64;
+                        if end_offset > indirect_buffer.size {
+                            return Err(RenderPassErrorInner::IndirectBufferOverrun {
+                                count: None,
+                                offset,
+                                end_offset,
+                                buffer_size: indirect_buffer.size,
+                            })
+                            .map_pass_err(scope);
+                        }
+                        buffer_memory_init_actions.extend(
+                            indirect_buffer.initialization_status.read().create_action(
+                                indirect_buffer,
+                                offset..end_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
+
+                        let count = match count_buffer_id {
+                            Some(count_buffer_id) => {
+                                let count_buffer = info
+                                    .usage_scope
+                                    .buffers
+                                    .merge_single(
+                                        &buffer_guard,
+                                        count_buffer_id,
+                                        hal::BufferUses::INDIRECT,
+                                    )
+                                    .map_pass_err(scope)?;
+                                check_buffer_usage(count_buffer.usage, BufferUsages::INDIRECT)
+                                    .map_pass_err(scope)?;
+                                let count_raw = count_buffer
+                                    .raw
+                                    .get(&snatch_guard)
+                                    .ok_or(RenderCommandError::DestroyedBuffer(count_buffer_id))
+                                    .map_pass_err(scope)?;
+
+                                let begin_count_offset = count_buffer_offset;
+                                let end_count_offset = count_buffer_offset + 4;
+                                if end_count_offset > count_buffer.size {
+                                    return Err(RenderPassErrorInner::IndirectCountBufferOverrun {
+                                        begin_count_offset,
+                                        end_count_offset,
+                                        count_buffer_size: count_buffer.size,
+                                    })
+                                    .map_pass_err(scope);
+                                }
+                                buffer_memory_init_actions.extend(
+                                    count_buffer.initialization_status.read().create_action(
+                                        count_buffer,
+                                        count_buffer_offset..end_count_offset,
+                                        MemoryInitKind::NeedsInitializedMemory,
+                                    ),
+                                );
+                                Some((count_raw, count_buffer_offset))
+                            }
+                            None => None,
+                        };
+
+                        unsafe {
+                            raw.execute_indirect(
+                                pipeline.raw(),
+                                pipeline.layout.raw(),
+                                arguments,
+                                indirect_raw,
+                                offset,
+                                count,
+                                max_count,
+                            );
+                        }
+
+                        // The bindings left by the commands are undefined.
+                        for slot in vertex_slots {
+                            state.vertex.inputs[slot].bound = false;
+                        }
+                        state.vertex.update_limits();
+                    }
                     RenderCommand::PushDebugGroup { color: _, len } => {
                         state.debug_scope_depth += 1;
                         if !discard_hal_labels {
@@ -2211,7 +3137,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
                         let query_set = tracker
                             .query_sets
-                            .add_single(&*query_set_guard, query_set_id)
+                            .add_single(&query_set_guard, query_set_id)
                             .ok_or(RenderCommandError::InvalidQuerySet(query_set_id))
                             .map_pass_err(scope)?;
 
@@ -2234,7 +3160,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
                         let query_set = tracker
                             .query_sets
-                            .add_single(&*query_set_guard, query_set_id)
+                            .add_single(&query_set_guard, query_set_id)
                             .ok_or(RenderCommandError::InvalidQuerySet(query_set_id))
                             .map_pass_err(scope)?;
 
@@ -2244,7 +3170,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                 query_set_id,
                                 query_index,
                                 Some(&mut cmd_buf_data.pending_query_resets),
-                                &mut active_query,
+                                &mut active_occlusion_query,
                             )
                             .map_pass_err(scope)?;
                     }
@@ -2252,7 +3178,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         api_log!("RenderPass::end_occlusion_query");
                         let scope = PassErrorScope::EndOcclusionQuery;
 
-                        end_occlusion_query(raw, &*query_set_guard, &mut active_query)
+                        end_occlusion_query(raw, &query_set_guard, &mut active_occlusion_query)
                             .map_pass_err(scope)?;
                     }
                     RenderCommand::BeginPipelineStatisticsQuery {
@@ -2264,7 +3190,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
                         let query_set = tracker
                             .query_sets
-                            .add_single(&*query_set_guard, query_set_id)
+                            .add_single(&query_set_guard, query_set_id)
                             .ok_or(RenderCommandError::InvalidQuerySet(query_set_id))
                             .map_pass_err(scope)?;
 
@@ -2274,7 +3200,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                 query_set_id,
                                 query_index,
                                 Some(&mut cmd_buf_data.pending_query_resets),
-                                &mut active_query,
+                                &mut active_pipeline_statistics_query,
                             )
                             .map_pass_err(scope)?;
                     }
@@ -2282,15 +3208,225 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         api_log!("RenderPass::end_pipeline_statistics_query");
                         let scope = PassErrorScope::EndPipelineStatisticsQuery;
 
-                        end_pipeline_statistics_query(raw, &*query_set_guard, &mut active_query)
+                        end_pipeline_statistics_query(
+                            raw,
+                            &query_set_guard,
+                            &mut active_pipeline_statistics_query,
+                        )
+                        .map_pass_err(scope)?;
+                    }
+                    RenderCommand::BeginConditionalRendering { buffer_id, offset } => {
+                        api_log!("RenderPass::begin_conditional_rendering {buffer_id:?} {offset}");
+                        let scope = PassErrorScope::BeginConditionalRendering(buffer_id);
+
+                        if conditional_rendering_active {
+                            return Err(RenderPassErrorInner::ConditionalRenderingAlreadyActive)
+                                .map_pass_err(scope);
+                        }
+                        if offset % 8 != 0 {
+                            return Err(RenderPassErrorInner::UnalignedConditionalRenderingOffset(
+                                offset,
+                            ))
+                            .map_pass_err(scope);
+                        }
+
+                        let buffer = info
+                            .usage_scope
+                            .buffers
+                            .merge_single(&buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(buffer.usage, BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                        let buffer_raw = buffer
+                            .raw
+                            .get(&snatch_guard)
+                            .ok_or(RenderCommandError::DestroyedBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+
+                        let end_offset = offset.saturating_add(mem::size_of::<u64>() as u64);
+                        if end_offset > buffer.size {
+                            return Err(RenderPassErrorInner::ConditionalRenderingBufferOverrun {
+                                offset,
+                                end_offset,
+                                buffer_size: buffer.size,
+                            })
+                            .map_pass_err(scope);
+                        }
+
+                        buffer_memory_init_actions.extend(
+                            buffer.initialization_status.read().create_action(
+                                buffer,
+                                offset..end_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
+
+                        conditional_rendering_active = true;
+                        // Without the feature, nothing is skipped.
+                        if device
+                            .features
+                            .contains(wgt::Features::CONDITIONAL_RENDERING)
+                        {
+                            unsafe {
+                                raw.begin_conditional_rendering(buffer_raw, offset);
+                            }
+                        }
+                    }
+                    RenderCommand::EndConditionalRendering => {
+                        api_log!("RenderPass::end_conditional_rendering");
+                        let scope = PassErrorScope::EndConditionalRendering;
+
+                        if !conditional_rendering_active {
+                            return Err(RenderPassErrorInner::ConditionalRenderingNotActive)
+                                .map_pass_err(scope);
+                        }
+                        conditional_rendering_active = false;
+                        if device
+                            .features
+                            .contains(wgt::Features::CONDITIONAL_RENDERING)
+                        {
+                            unsafe {
+                                raw.end_conditional_rendering();
+                            }
+                        }
+                    }
+                    RenderCommand::SetVertexCaptureBuffer {
+                        slot,
+                        buffer_id,
+                        offset,
+                        size,
+                    } => {
+                        api_log!("RenderPass::set_vertex_capture_buffer {slot} {buffer_id:?}");
+                        let scope = PassErrorScope::SetVertexCaptureBuffer(buffer_id);
+
+                        state.vertex_capture.check_inactive().map_pass_err(scope)?;
+                        let max = hal::MAX_VERTEX_CAPTURE_BUFFERS as u32;
+                        if slot >= max {
+                            return Err(RenderPassErrorInner::VertexCaptureSlotOutOfRange {
+                                index: slot,
+                                max,
+                            })
+                            .map_pass_err(scope);
+                        }
+                        state.vertex_capture.buffers[slot as usize] =
+                            Some((buffer_id, offset, size));
+                    }
+                    RenderCommand::BeginVertexCapture => {
+                        api_log!("RenderPass::begin_vertex_capture");
+                        let scope = PassErrorScope::BeginVertexCapture;
+
+                        device
+                            .require_features(wgt::Features::VERTEX_CAPTURE)
                             .map_pass_err(scope)?;
+                        if state.vertex_capture.active {
+                            return Err(RenderPassErrorInner::VertexCaptureAlreadyActive)
+                                .map_pass_err(scope);
+                        }
+                        let strides = state
+                            .vertex_capture
+                            .pipeline
+                            .as_ref()
+                            .ok_or(RenderPassErrorInner::MissingVertexCapturePipeline)
+                            .map_pass_err(scope)?
+                            .strides
+                            .clone();
+
+                        let mut capacity = u64::MAX;
+                        let mut bindings =
+                            ArrayVec::<_, { hal::MAX_VERTEX_CAPTURE_BUFFERS }>::new();
+                        for (slot, &stride) in strides.iter().enumerate() {
+                            let (buffer_id, offset, size) = state.vertex_capture.buffers[slot]
+                                .ok_or(RenderPassErrorInner::MissingVertexCaptureBuffer(
+                                    slot as u32,
+                                ))
+                                .map_pass_err(scope)?;
+
+                            let buffer = info
+                                .usage_scope
+                                .buffers
+                                .merge_single(
+                                    &buffer_guard,
+                                    buffer_id,
+                                    hal::BufferUses::VERTEX_CAPTURE,
+                                )
+                                .map_pass_err(scope)?;
+
+                            if buffer.device.as_info().id() != device.as_info().id() {
+                                return Err(DeviceError::WrongDevice).map_pass_err(scope);
+                            }
+
+                            check_buffer_usage(buffer.usage, BufferUsages::VERTEX_CAPTURE)
+                                .map_pass_err(scope)?;
+                            let buffer_raw = buffer
+                                .raw
+                                .get(&snatch_guard)
+                                .ok_or(RenderCommandError::DestroyedBuffer(buffer_id))
+                                .map_pass_err(scope)?;
+
+                            if offset % 4 != 0 {
+                                return Err(RenderPassErrorInner::UnalignedVertexCaptureOffset(
+                                    offset,
+                                ))
+                                .map_pass_err(scope);
+                            }
+                            let end_offset = match size {
+                                Some(size) => offset.saturating_add(size.get()),
+                                None => buffer.size,
+                            };
+                            if offset > end_offset || end_offset > buffer.size {
+                                return Err(RenderPassErrorInner::VertexCaptureBufferOverrun {
+                                    offset,
+                                    end_offset,
+                                    buffer_size: buffer.size,
+                                })
+                                .map_pass_err(scope);
+                            }
+
+                            // Only the captured vertices are written, so the rest of the range
+                            // has to be initialized.
+                            buffer_memory_init_actions.extend(
+                                buffer.initialization_status.read().create_action(
+                                    buffer,
+                                    offset..end_offset,
+                                    MemoryInitKind::NeedsInitializedMemory,
+                                ),
+                            );
+
+                            capacity = capacity.min((end_offset - offset) / stride);
+                            bindings.push(hal::BufferBinding {
+                                buffer: buffer_raw,
+                                offset,
+                                size,
+                            });
+                        }
+
+                        state.vertex_capture.active = true;
+                        state.vertex_capture.capacity = capacity;
+                        state.vertex_capture.captured = 0;
+                        unsafe {
+                            raw.begin_vertex_capture(&bindings);
+                        }
+                    }
+                    RenderCommand::EndVertexCapture => {
+                        api_log!("RenderPass::end_vertex_capture");
+                        let scope = PassErrorScope::EndVertexCapture;
+
+                        if !state.vertex_capture.active {
+                            return Err(RenderPassErrorInner::VertexCaptureNotActive)
+                                .map_pass_err(scope);
+                        }
+                        state.vertex_capture.active = false;
+                        unsafe {
+                            raw.end_vertex_capture();
+                        }
                     }
                     RenderCommand::ExecuteBundle(bundle_id) => {
                         api_log!("RenderPass::execute_bundle {bundle_id:?}");
                         let scope = PassErrorScope::ExecuteBundle;
+                        state.vertex_capture.check_inactive().map_pass_err(scope)?;
                         let bundle: &command::RenderBundle<A> = tracker
                             .bundles
-                            .add_single(&*bundle_guard, bundle_id)
+                            .add_single(&bundle_guard, bundle_id)
                             .ok_or(RenderCommandError::InvalidRenderBundle(bundle_id))
                             .map_pass_err(scope)?;
 
@@ -2337,7 +3473,41 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                 .extend(texture_memory_actions.register_init_action(action));
                         }
 
-                        unsafe { bundle.execute(raw) }
+                        // The occlusion queries of the bundle are in the query set
+                        // of the pass, and can't overlap with the queries of the pass.
+                        let occlusion_query_set = match bundle.occlusion_queries.first() {
+                            Some(&new_query_index) => {
+                                if let Some((_, active_query_index)) = active_occlusion_query {
+                                    return Err(QueryUseError::AlreadyStarted {
+                                        active_query_index,
+                                        new_query_index,
+                                    })
+                                    .map_pass_err(scope);
+                                }
+                                let query_set_id = occlusion_query_set_id
+                                    .ok_or(RenderPassErrorInner::MissingOcclusionQuerySet)
+                                    .map_pass_err(scope)?;
+                                let query_set = tracker
+                                    .query_sets
+                                    .add_single(&query_set_guard, query_set_id)
+                                    .ok_or(RenderCommandError::InvalidQuerySet(query_set_id))
+                                    .map_pass_err(scope)?;
+                                for &query_index in bundle.occlusion_queries.iter() {
+                                    query_set
+                                        .validate_query(
+                                            query_set_id,
+                                            SimplifiedQueryType::Occlusion,
+                                            query_index,
+                                            Some(&mut cmd_buf_data.pending_query_resets),
+                                        )
+                                        .map_pass_err(scope)?;
+                                }
+                                Some(query_set)
+                            }
+                            None => None,
+                        };
+
+                        unsafe { bundle.execute(raw, occlusion_query_set) }
                             .map_err(|e| match e {
                                 ExecutionError::DestroyedBuffer(id) => {
                                     RenderCommandError::DestroyedBuffer(id)
@@ -2364,6 +3534,28 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 }
             }
 
+            if conditional_rendering_active {
+                return Err(RenderPassErrorInner::ConditionalRenderingNotEnded)
+                    .map_pass_err(pass_scope);
+            }
+            if state.vertex_capture.active {
+                return Err(RenderPassErrorInner::VertexCaptureNotEnded).map_pass_err(pass_scope);
+            }
+            if pipeline_statistics_query.is_some() {
+                end_pipeline_statistics_query(
+                    raw,
+                    &query_set_guard,
+                    &mut active_pipeline_statistics_query,
+                )
+                .map_pass_err(PassErrorScope::EndPipelineStatisticsQuery)?;
+            }
+            // Queries are scoped to the pass they're begun in.
+            if let Some((_, query_index)) =
+                active_occlusion_query.or(active_pipeline_statistics_query)
+            {
+                return Err(QueryUseError::NotEnded { query_index }).map_pass_err(pass_scope);
+            }
+
             log::trace!("Merging renderpass into cmd_buf {:?}", encoder_id);
             let (trackers, pending_discard_init_fixups) =
                 info.finish(raw).map_pass_err(pass_scope)?;
@@ -2402,12 +3594,19 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 .map_err(RenderCommandError::InvalidQuerySet)
                 .map_pass_err(PassErrorScope::QueryReset)?;
 
-            super::CommandBuffer::insert_barriers_from_scope(
+            let barrier_count = super::CommandBuffer::insert_barriers_from_scope(
                 transit,
                 tracker,
                 &scope,
                 &snatch_guard,
             );
+
+            if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+                // The listing looks up labels in the registries.
+                drop(query_set_guard);
+                disassembly.barriers(barrier_count);
+                disassembly.render_pass(hub, &base, color_attachments, depth_stencil_attachment);
+            }
         }
 
         *status = CommandEncoderStatus::Recording;
@@ -2422,9 +3621,9 @@ pub mod render_ffi {
         super::{Rect, RenderCommand},
         RenderPass,
     };
-    use crate::{id, RawString};
+    use crate::{binding_model::BindGroupEntry, id, RawString};
     use std::{convert::TryInto, ffi, num::NonZeroU32, slice};
-    use wgt::{BufferAddress, BufferSize, Color, DynamicOffset, IndexFormat};
+    use wgt::{BufferAddress, BufferSize, Color, DynamicOffset, IndexFormat, IndirectArgument};
 
     /// # Safety
     ///
@@ -2459,6 +3658,34 @@ pub mod render_ffi {
         });
     }
 
+    /// Binds the resources in `entries` at `index` without creating a bind group
+    /// object. The entries are matched against the bind group layout of the
+    /// pipeline that is current when the command is executed.
+    ///
+    /// Not exposed over the C API, as [`BindGroupEntry`] is not FFI-safe.
+    pub fn wgpu_render_pass_set_bind_group_immediate(
+        pass: &mut RenderPass,
+        index: u32,
+        entries: &[BindGroupEntry],
+    ) {
+        // A later `set_bind_group` at this index must not be skipped as redundant.
+        pass.current_bind_groups.forget(index);
+
+        pass.base
+            .immediate_bind_group_entries
+            .extend(entries.iter().map(|entry| BindGroupEntry {
+                binding: entry.binding,
+                resource: entry.resource.to_static(),
+            }));
+
+        pass.base
+            .commands
+            .push(RenderCommand::SetBindGroupImmediate {
+                index,
+                num_entries: entries.len(),
+            });
+    }
+
     #[no_mangle]
     pub extern "C" fn wgpu_render_pass_set_pipeline(
         pass: &mut RenderPass,
@@ -2544,6 +3771,14 @@ pub mod render_ffi {
             .push(RenderCommand::SetScissor(Rect { x, y, w, h }));
     }
 
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_set_shading_rate(
+        pass: &mut RenderPass,
+        rate: wgt::ShadingRate,
+    ) {
+        pass.base.commands.push(RenderCommand::SetShadingRate(rate));
+    }
+
     /// # Safety
     ///
     /// This function is unsafe as there is no guarantee that the given pointer is
@@ -2619,6 +3854,31 @@ pub mod render_ffi {
         });
     }
 
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_draw_mesh_tasks(
+        pass: &mut RenderPass,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) {
+        pass.base.commands.push(RenderCommand::DrawMeshTasks {
+            group_count_x,
+            group_count_y,
+            group_count_z,
+        });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_draw_mesh_tasks_indirect(
+        pass: &mut RenderPass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+    ) {
+        pass.base
+            .commands
+            .push(RenderCommand::DrawMeshTasksIndirect { buffer_id, offset });
+    }
+
     #[no_mangle]
     pub extern "C" fn wgpu_render_pass_draw_indirect(
         pass: &mut RenderPass,
@@ -2719,6 +3979,60 @@ pub mod render_ffi {
             });
     }
 
+    /// # Safety
+    ///
+    /// This function is unsafe as there is no guarantee that the given pointer is
+    /// valid for `arguments_length` elements.
+    #[no_mangle]
+    pub unsafe extern "C" fn wgpu_render_pass_execute_indirect(
+        pass: &mut RenderPass,
+        arguments: *const IndirectArgument,
+        arguments_length: usize,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        max_count: u32,
+    ) {
+        let arguments = unsafe { slice::from_raw_parts(arguments, arguments_length) };
+        pass.base.indirect_arguments.extend_from_slice(arguments);
+
+        pass.base.commands.push(RenderCommand::ExecuteIndirect {
+            num_arguments: arguments_length,
+            buffer_id,
+            offset,
+            count_buffer_id: None,
+            count_buffer_offset: 0,
+            max_count,
+        });
+    }
+
+    /// # Safety
+    ///
+    /// This function is unsafe as there is no guarantee that the given pointer is
+    /// valid for `arguments_length` elements.
+    #[no_mangle]
+    pub unsafe extern "C" fn wgpu_render_pass_execute_indirect_count(
+        pass: &mut RenderPass,
+        arguments: *const IndirectArgument,
+        arguments_length: usize,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        let arguments = unsafe { slice::from_raw_parts(arguments, arguments_length) };
+        pass.base.indirect_arguments.extend_from_slice(arguments);
+
+        pass.base.commands.push(RenderCommand::ExecuteIndirect {
+            num_arguments: arguments_length,
+            buffer_id,
+            offset,
+            count_buffer_id: Some(count_buffer_id),
+            count_buffer_offset,
+            max_count,
+        });
+    }
+
     /// # Safety
     ///
     /// This function is unsafe as there is no guarantee that the given `label`
@@ -2810,6 +4124,52 @@ pub mod render_ffi {
             .push(RenderCommand::EndPipelineStatisticsQuery);
     }
 
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_begin_conditional_rendering(
+        pass: &mut RenderPass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+    ) {
+        pass.base
+            .commands
+            .push(RenderCommand::BeginConditionalRendering { buffer_id, offset });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_end_conditional_rendering(pass: &mut RenderPass) {
+        pass.base
+            .commands
+            .push(RenderCommand::EndConditionalRendering);
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_set_vertex_capture_buffer(
+        pass: &mut RenderPass,
+        slot: u32,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    ) {
+        pass.base
+            .commands
+            .push(RenderCommand::SetVertexCaptureBuffer {
+                slot,
+                buffer_id,
+                offset,
+                size,
+            });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_begin_vertex_capture(pass: &mut RenderPass) {
+        pass.base.commands.push(RenderCommand::BeginVertexCapture);
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_end_vertex_capture(pass: &mut RenderPass) {
+        pass.base.commands.push(RenderCommand::EndVertexCapture);
+    }
+
     /// # Safety
     ///
     /// This function is unsafe as there is no guarantee that the given pointer is
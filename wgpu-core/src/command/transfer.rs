@@ -4,7 +4,7 @@ use crate::{
     api_log,
     command::{clear_texture, CommandBuffer, CommandEncoderError},
     conv,
-    device::{Device, DeviceError, MissingDownlevelFlags},
+    device::{Device, DeviceError, MissingDownlevelFlags, MissingFeatures},
     error::{ErrorFormatter, PrettyError},
     global::Global,
     hal_api::HalApi,
@@ -25,7 +25,9 @@ use wgt::{BufferAddress, BufferUsages, Extent3d, TextureUsages};
 
 use std::{iter, sync::Arc};
 
-use super::{memory_init::CommandBufferTextureMemoryActions, ClearError, CommandEncoder};
+use super::{
+    disassembly, memory_init::CommandBufferTextureMemoryActions, ClearError, CommandEncoder,
+};
 
 pub type ImageCopyBuffer = wgt::ImageCopyBuffer<BufferId>;
 pub type ImageCopyTexture = wgt::ImageCopyTexture<TextureId>;
@@ -142,6 +144,22 @@ pub enum TransferError {
     InvalidSampleCount { sample_count: u32 },
     #[error("Requested mip level {requested} does no exist (count: {count})")]
     InvalidMipLevel { requested: u32, count: u32 },
+    #[error("Resolve source must be multisampled and destination single-sampled, got sample counts {src} and {dst}")]
+    InvalidResolveSampleCount { src: u32, dst: u32 },
+    #[error(
+        "Resolve source format ({src_format:?}) and destination format ({dst_format:?}) must be the same"
+    )]
+    ResolveFormatMismatch {
+        src_format: wgt::TextureFormat,
+        dst_format: wgt::TextureFormat,
+    },
+    #[error("Textures with format {format:?} can't be resolved with {mode:?}")]
+    UnsupportedResolve {
+        format: wgt::TextureFormat,
+        mode: wgt::ResolveMode,
+    },
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
 }
 
 impl PrettyError for TransferError {
@@ -593,6 +611,13 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 size,
             });
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!(
+                "copy {size} bytes from buffer {} at {source_offset} to buffer {} at {destination_offset}",
+                hub.buffers.label_for_resource(source),
+                hub.buffers.label_for_resource(destination),
+            ));
+        }
 
         let snatch_guard = device.snatchable_lock.read();
 
@@ -708,6 +733,12 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 MemoryInitKind::NeedsInitializedMemory,
             ),
         );
+        cmd_buf_data
+            .mappable_buffer_uses
+            .extend(dst_buffer.mappable_range_use(destination_offset..(destination_offset + size)));
+        cmd_buf_data
+            .mappable_buffer_uses
+            .extend(src_buffer.mappable_range_use(source_offset..(source_offset + size)));
 
         let region = hal::BufferCopy {
             src_offset: source_offset,
@@ -755,11 +786,19 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 size: *copy_size,
             });
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!(
+                "copy {copy_size:?} from {} to {}",
+                disassembly::image_copy_buffer(hub, source),
+                disassembly::image_copy_texture(hub, destination),
+            ));
+        }
 
         let encoder = &mut cmd_buf_data.encoder;
         let tracker = &mut cmd_buf_data.trackers;
         let buffer_memory_init_actions = &mut cmd_buf_data.buffer_memory_init_actions;
         let texture_memory_actions = &mut cmd_buf_data.texture_memory_actions;
+        let mappable_buffer_uses = &mut cmd_buf_data.mappable_buffer_uses;
 
         if copy_size.width == 0 || copy_size.height == 0 || copy_size.depth_or_array_layers == 0 {
             log::trace!("Ignoring copy_buffer_to_texture of size 0");
@@ -861,6 +900,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             source.layout.offset..(source.layout.offset + required_buffer_bytes_in_copy),
             MemoryInitKind::NeedsInitializedMemory,
         ));
+        mappable_buffer_uses.extend(src_buffer.mappable_range_use(
+            source.layout.offset..(source.layout.offset + required_buffer_bytes_in_copy),
+        ));
 
         let regions = (0..array_layer_count).map(|rel_array_layer| {
             let mut texture_base = dst_base.clone();
@@ -916,10 +958,18 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 size: *copy_size,
             });
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!(
+                "copy {copy_size:?} from {} to {}",
+                disassembly::image_copy_texture(hub, source),
+                disassembly::image_copy_buffer(hub, destination),
+            ));
+        }
         let encoder = &mut cmd_buf_data.encoder;
         let tracker = &mut cmd_buf_data.trackers;
         let buffer_memory_init_actions = &mut cmd_buf_data.buffer_memory_init_actions;
         let texture_memory_actions = &mut cmd_buf_data.texture_memory_actions;
+        let mappable_buffer_uses = &mut cmd_buf_data.mappable_buffer_uses;
 
         if copy_size.width == 0 || copy_size.height == 0 || copy_size.depth_or_array_layers == 0 {
             log::trace!("Ignoring copy_texture_to_buffer of size 0");
@@ -1030,6 +1080,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             destination.layout.offset..(destination.layout.offset + required_buffer_bytes_in_copy),
             MemoryInitKind::ImplicitlyInitialized,
         ));
+        mappable_buffer_uses.extend(dst_buffer.mappable_range_use(
+            destination.layout.offset..(destination.layout.offset + required_buffer_bytes_in_copy),
+        ));
 
         let regions = (0..array_layer_count).map(|rel_array_layer| {
             let mut texture_base = src_base.clone();
@@ -1091,6 +1144,13 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 size: *copy_size,
             });
         }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!(
+                "copy {copy_size:?} from {} to {}",
+                disassembly::image_copy_texture(hub, source),
+                disassembly::image_copy_texture(hub, destination),
+            ));
+        }
         let encoder = &mut cmd_buf_data.encoder;
         let tracker = &mut cmd_buf_data.trackers;
         let texture_memory_actions = &mut cmd_buf_data.texture_memory_actions;
@@ -1227,4 +1287,200 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
         Ok(())
     }
+
+    pub fn command_encoder_resolve_texture<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        source: &ImageCopyTexture,
+        destination: &ImageCopyTexture,
+        size: &Extent3d,
+        mode: wgt::ResolveMode,
+    ) -> Result<(), CopyError> {
+        profiling::scope!("CommandEncoder::resolve_texture");
+        api_log!(
+            "CommandEncoder::resolve_texture {:?} -> {:?} {size:?} {mode:?}",
+            source.texture,
+            destination.texture
+        );
+
+        let hub = A::hub(self);
+
+        let cmd_buf = CommandBuffer::get_encoder(hub, command_encoder_id)?;
+        let device = &cmd_buf.device;
+        if !device.is_valid() {
+            return Err(TransferError::InvalidDevice(cmd_buf.device.as_info().id()).into());
+        }
+
+        let snatch_guard = device.snatchable_lock.read();
+
+        let mut cmd_buf_data = cmd_buf.data.lock();
+        let cmd_buf_data = cmd_buf_data.as_mut().unwrap();
+
+        #[cfg(feature = "trace")]
+        if let Some(ref mut list) = cmd_buf_data.commands {
+            list.push(TraceCommand::ResolveTexture {
+                src: *source,
+                dst: *destination,
+                size: *size,
+                mode,
+            });
+        }
+        if let Some(ref mut disassembly) = cmd_buf_data.disassembly {
+            disassembly.line(format_args!(
+                "resolve {size:?} from {} to {} with {mode:?}",
+                disassembly::image_copy_texture(hub, source),
+                disassembly::image_copy_texture(hub, destination),
+            ));
+        }
+        let encoder = &mut cmd_buf_data.encoder;
+        let tracker = &mut cmd_buf_data.trackers;
+        let texture_memory_actions = &mut cmd_buf_data.texture_memory_actions;
+
+        if size.width == 0 || size.height == 0 || size.depth_or_array_layers == 0 {
+            log::trace!("Ignoring resolve_texture of size 0");
+            return Ok(());
+        }
+
+        let src_texture = hub
+            .textures
+            .get(source.texture)
+            .map_err(|_| TransferError::InvalidTexture(source.texture))?;
+        let dst_texture = hub
+            .textures
+            .get(destination.texture)
+            .map_err(|_| TransferError::InvalidTexture(destination.texture))?;
+
+        if src_texture.desc.sample_count == 1 || dst_texture.desc.sample_count != 1 {
+            return Err(TransferError::InvalidResolveSampleCount {
+                src: src_texture.desc.sample_count,
+                dst: dst_texture.desc.sample_count,
+            }
+            .into());
+        }
+        if src_texture.desc.format != dst_texture.desc.format {
+            return Err(TransferError::ResolveFormatMismatch {
+                src_format: src_texture.desc.format,
+                dst_format: dst_texture.desc.format,
+            }
+            .into());
+        }
+
+        let format = src_texture.desc.format;
+        let format_features = device.describe_format_features(&device.adapter, format)?;
+        let required_flags = match mode {
+            wgt::ResolveMode::Average => wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_REGION,
+            wgt::ResolveMode::Min | wgt::ResolveMode::Max => {
+                wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_REGION
+                    | wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_MIN_MAX
+            }
+            wgt::ResolveMode::SampleZero => {
+                return Err(TransferError::UnsupportedResolve { format, mode }.into())
+            }
+        };
+        if !format_features.flags.contains(required_flags) {
+            return Err(TransferError::UnsupportedResolve { format, mode }.into());
+        }
+
+        let (src_copy_size, array_layer_count) =
+            validate_texture_copy_range(source, &src_texture.desc, CopySide::Source, size)?;
+        let (dst_copy_size, _) = validate_texture_copy_range(
+            destination,
+            &dst_texture.desc,
+            CopySide::Destination,
+            size,
+        )?;
+
+        let (src_range, src_tex_base) = extract_texture_selector(source, size, &src_texture)?;
+        let (dst_range, dst_tex_base) = extract_texture_selector(destination, size, &dst_texture)?;
+        let texture_aspects = hal::FormatAspects::from(format);
+        if src_tex_base.aspect != texture_aspects {
+            return Err(TransferError::CopySrcMissingAspects.into());
+        }
+        if dst_tex_base.aspect != texture_aspects {
+            return Err(TransferError::CopyDstMissingAspects.into());
+        }
+
+        // Handle texture init *before* dealing with barrier transitions, as
+        // for `copy_texture_to_texture`.
+        handle_src_texture_init(
+            encoder,
+            tracker,
+            texture_memory_actions,
+            device,
+            source,
+            size,
+            &src_texture,
+        )?;
+        handle_dst_texture_init(
+            encoder,
+            tracker,
+            texture_memory_actions,
+            device,
+            destination,
+            size,
+            &dst_texture,
+        )?;
+
+        let src_pending = cmd_buf_data
+            .trackers
+            .textures
+            .set_single(&src_texture, src_range, hal::TextureUses::COPY_SRC)
+            .ok_or(TransferError::InvalidTexture(source.texture))?;
+        let src_raw = src_texture
+            .raw(&snatch_guard)
+            .ok_or(TransferError::InvalidTexture(source.texture))?;
+        if !src_texture.desc.usage.contains(TextureUsages::COPY_SRC) {
+            return Err(TransferError::MissingCopySrcUsageFlag.into());
+        }
+
+        let mut barriers: ArrayVec<_, 2> = src_pending
+            .map(|pending| pending.into_hal(src_raw))
+            .collect();
+
+        let dst_pending = cmd_buf_data
+            .trackers
+            .textures
+            .set_single(&dst_texture, dst_range, hal::TextureUses::COPY_DST)
+            .ok_or(TransferError::InvalidTexture(destination.texture))?;
+        let dst_raw = dst_texture
+            .raw(&snatch_guard)
+            .ok_or(TransferError::InvalidTexture(destination.texture))?;
+        if !dst_texture.desc.usage.contains(TextureUsages::COPY_DST) {
+            return Err(
+                TransferError::MissingCopyDstUsageFlag(None, Some(destination.texture)).into(),
+            );
+        }
+
+        barriers.extend(dst_pending.map(|pending| pending.into_hal(dst_raw)));
+
+        let hal_size = hal::CopyExtent {
+            width: src_copy_size.width.min(dst_copy_size.width),
+            height: src_copy_size.height.min(dst_copy_size.height),
+            depth: src_copy_size.depth.min(dst_copy_size.depth),
+        };
+        let regions = (0..array_layer_count).map(|rel_array_layer| {
+            let mut src_base = src_tex_base.clone();
+            let mut dst_base = dst_tex_base.clone();
+            src_base.array_layer += rel_array_layer;
+            dst_base.array_layer += rel_array_layer;
+            hal::TextureCopy {
+                src_base,
+                dst_base,
+                size: hal_size,
+            }
+        });
+        let cmd_buf_raw = cmd_buf_data.encoder.open()?;
+        unsafe {
+            cmd_buf_raw.transition_textures(barriers.into_iter());
+            cmd_buf_raw.resolve_texture(
+                src_raw,
+                hal::TextureUses::COPY_SRC,
+                dst_raw,
+                mode,
+                regions,
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -101,6 +101,18 @@ pub fn map_buffer_usage(usage: wgt::BufferUsages) -> hal::BufferUses {
         hal::BufferUses::QUERY_RESOLVE,
         usage.contains(wgt::BufferUsages::QUERY_RESOLVE),
     );
+    u.set(
+        hal::BufferUses::BOTTOM_LEVEL_ACCELERATION_STRUCTURE_INPUT,
+        usage.contains(wgt::BufferUsages::BLAS_INPUT),
+    );
+    u.set(
+        hal::BufferUses::TOP_LEVEL_ACCELERATION_STRUCTURE_INPUT,
+        usage.contains(wgt::BufferUsages::TLAS_INPUT),
+    );
+    u.set(
+        hal::BufferUses::VERTEX_CAPTURE,
+        usage.contains(wgt::BufferUsages::VERTEX_CAPTURE),
+    );
     u
 }
 
@@ -134,6 +146,10 @@ pub fn map_texture_usage(
         hal::TextureUses::DEPTH_STENCIL_READ | hal::TextureUses::DEPTH_STENCIL_WRITE,
         usage.contains(wgt::TextureUsages::RENDER_ATTACHMENT) && !is_color,
     );
+    u.set(
+        hal::TextureUses::SHADING_RATE_ATTACHMENT,
+        usage.contains(wgt::TextureUsages::SHADING_RATE_ATTACHMENT),
+    );
     u
 }
 
@@ -149,11 +165,13 @@ pub fn map_texture_usage_for_texture(
         } else if desc.usage.contains(wgt::TextureUsages::COPY_DST) {
             hal::TextureUses::COPY_DST // (set already)
         } else {
-            // Use COPY_DST only if we can't use COLOR_TARGET
+            // Use COPY_DST only if we can't use COLOR_TARGET, tiled textures
+            // can't be render targets and their tiles are zeroed with copies
             if format_features
                 .allowed_usages
                 .contains(wgt::TextureUsages::RENDER_ATTACHMENT)
                 && desc.dimension == wgt::TextureDimension::D2
+                && !desc.usage.contains(wgt::TextureUsages::TILED)
             // Render targets dimension must be 2d
             {
                 hal::TextureUses::COLOR_TARGET
@@ -185,6 +203,10 @@ pub fn map_texture_usage_from_hal(uses: hal::TextureUses) -> wgt::TextureUsages
         wgt::TextureUsages::RENDER_ATTACHMENT,
         uses.contains(hal::TextureUses::COLOR_TARGET),
     );
+    u.set(
+        wgt::TextureUsages::SHADING_RATE_ATTACHMENT,
+        uses.contains(hal::TextureUses::SHADING_RATE_ATTACHMENT),
+    );
     u
 }
 
@@ -240,11 +262,27 @@ pub fn check_texture_dimension_size(
     Ok(())
 }
 
-pub fn bind_group_layout_flags(features: wgt::Features) -> hal::BindGroupLayoutFlags {
+pub fn bind_group_layout_flags(
+    features: wgt::Features,
+    entries: &[wgt::BindGroupLayoutEntry],
+) -> hal::BindGroupLayoutFlags {
     let mut flags = hal::BindGroupLayoutFlags::empty();
+    let partially_bound = features.contains(wgt::Features::PARTIALLY_BOUND_BINDING_ARRAY);
+    flags.set(hal::BindGroupLayoutFlags::PARTIALLY_BOUND, partially_bound);
+    // Update-after-bind layouts can't have buffers with dynamic offsets.
+    let has_binding_arrays = entries.iter().any(|entry| entry.count.is_some());
+    let has_dynamic_offsets = entries.iter().any(|entry| {
+        matches!(
+            entry.ty,
+            wgt::BindingType::Buffer {
+                has_dynamic_offset: true,
+                ..
+            }
+        )
+    });
     flags.set(
-        hal::BindGroupLayoutFlags::PARTIALLY_BOUND,
-        features.contains(wgt::Features::PARTIALLY_BOUND_BINDING_ARRAY),
+        hal::BindGroupLayoutFlags::UPDATE_AFTER_BIND,
+        partially_bound && has_binding_arrays && !has_dynamic_offsets,
     );
     flags
 }
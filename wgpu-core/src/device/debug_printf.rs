@@ -0,0 +1,542 @@
+/*! Emulation of [`wgt::Features::SHADER_DEBUG_PRINTF`].
+
+Naga lowers every `debugPrintf` statement into writes to a capture buffer,
+see [`naga::proc::lower_debug_printf`]. The device owns that buffer and binds
+it to the bind group right after the last one the user may use, which every
+pipeline layout reserves when the feature is enabled.
+
+Every submission is followed by a small command buffer that copies the
+capture buffer into a mappable readback buffer and clears it again. Once
+the submission is done, [`Device::maintain`] decodes the records and hands
+the messages to the user's callback.
+
+[`Device::maintain`]: super::Device::maintain
+*/
+
+use crate::{hal_api::HalApi, hal_label, SubmissionIndex};
+
+use hal::{CommandEncoder as _, Device as _};
+use naga::proc::{DebugPrintfArgument, DebugPrintfFormat};
+use parking_lot::Mutex;
+use wgt::BufferAddress;
+
+use std::{iter, mem, num::NonZeroU64, sync::Arc};
+
+use super::DeviceError;
+
+/// Size of the capture buffer, including the word counting written words.
+pub(crate) const DEBUG_PRINTF_BUFFER_SIZE: BufferAddress = 1 << 20;
+
+const WORD_SIZE: BufferAddress = mem::size_of::<u32>() as BufferAddress;
+
+#[cfg(send_sync)]
+pub type DebugPrintfCallback = Arc<dyn Fn(&str) + Send + Sync + 'static>;
+#[cfg(not(send_sync))]
+pub type DebugPrintfCallback = Arc<dyn Fn(&str) + 'static>;
+
+/// Messages read back from a finished submission, to be passed to the
+/// callback with nothing locked.
+pub struct DebugPrintfMessages {
+    callback: Option<DebugPrintfCallback>,
+    messages: Vec<String>,
+}
+
+impl DebugPrintfMessages {
+    pub(crate) fn fire(self) {
+        for message in self.messages {
+            match self.callback {
+                Some(ref callback) => callback(&message),
+                None => log::info!("Shader debug printf: {message}"),
+            }
+        }
+    }
+}
+
+struct Readbacks<A: HalApi> {
+    /// Readback buffers written by a submission that may still be running.
+    in_flight: Vec<(SubmissionIndex, A::Buffer)>,
+    /// Readback buffers that can be used by the next submission.
+    free: Vec<A::Buffer>,
+}
+
+pub(crate) struct DebugPrintf<A: HalApi> {
+    /// Index of the bind group holding the capture buffer.
+    pub(crate) group_index: u32,
+    bind_group_layout: A::BindGroupLayout,
+    /// Layout used to fill the bind groups between the user's and ours.
+    empty_bind_group_layout: A::BindGroupLayout,
+    buffer: A::Buffer,
+    bind_group: A::BindGroup,
+    /// Formats of the lowered statements, indexed by the first word of a record.
+    ///
+    /// Identical statements share their format, so recreating the same shader
+    /// modules does not grow this.
+    formats: Mutex<Vec<DebugPrintfFormat>>,
+    readbacks: Mutex<Readbacks<A>>,
+    callback: Mutex<Option<DebugPrintfCallback>>,
+}
+
+impl<A: HalApi> DebugPrintf<A> {
+    /// Creates the capture buffer and its bind group.
+    ///
+    /// The buffer is cleared and left in the `STORAGE_READ_WRITE` state by
+    /// commands recorded into `encoder`, which must be submitted before any
+    /// user commands.
+    pub(crate) fn new(
+        device: &A::Device,
+        encoder: &mut A::CommandEncoder,
+        group_index: u32,
+        instance_flags: wgt::InstanceFlags,
+    ) -> Result<Self, DeviceError> {
+        let entry = wgt::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgt::ShaderStages::all(),
+            ty: wgt::BindingType::Buffer {
+                ty: wgt::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout = unsafe {
+            device.create_bind_group_layout(&hal::BindGroupLayoutDescriptor {
+                label: hal_label(
+                    Some("(wgpu internal) debug printf bind group layout"),
+                    instance_flags,
+                ),
+                flags: hal::BindGroupLayoutFlags::empty(),
+                entries: &[entry],
+            })
+        }?;
+        let empty_bind_group_layout = unsafe {
+            device.create_bind_group_layout(&hal::BindGroupLayoutDescriptor {
+                label: hal_label(
+                    Some("(wgpu internal) empty bind group layout"),
+                    instance_flags,
+                ),
+                flags: hal::BindGroupLayoutFlags::empty(),
+                entries: &[],
+            })
+        }?;
+        let buffer = unsafe {
+            device.create_buffer(&hal::BufferDescriptor {
+                label: hal_label(Some("(wgpu internal) debug printf buffer"), instance_flags),
+                size: DEBUG_PRINTF_BUFFER_SIZE,
+                usage: hal::BufferUses::STORAGE_READ_WRITE
+                    | hal::BufferUses::COPY_SRC
+                    | hal::BufferUses::COPY_DST,
+                memory_flags: hal::MemoryFlags::empty(),
+            })
+        }?;
+        let bind_group = unsafe {
+            device.create_bind_group(&hal::BindGroupDescriptor {
+                label: hal_label(
+                    Some("(wgpu internal) debug printf bind group"),
+                    instance_flags,
+                ),
+                layout: &bind_group_layout,
+                buffers: &[hal::BufferBinding {
+                    buffer: &buffer,
+                    offset: 0,
+                    size: None,
+                }],
+                samplers: &[],
+                textures: &[],
+                entries: &[hal::BindGroupEntry {
+                    binding: 0,
+                    resource_index: 0,
+                    count: 1,
+                }],
+                acceleration_structures: &[],
+            })
+        }?;
+
+        unsafe {
+            encoder.transition_buffers(iter::once(hal::BufferBarrier {
+                buffer: &buffer,
+                usage: hal::BufferUses::empty()..hal::BufferUses::COPY_DST,
+            }));
+            encoder.clear_buffer(&buffer, 0..DEBUG_PRINTF_BUFFER_SIZE);
+            encoder.transition_buffers(iter::once(hal::BufferBarrier {
+                buffer: &buffer,
+                usage: hal::BufferUses::COPY_DST..hal::BufferUses::STORAGE_READ_WRITE,
+            }));
+        }
+
+        // Records that did not fit into the buffer leave zeroed words behind,
+        // which decode as this empty placeholder and are skipped.
+        let formats = vec![DebugPrintfFormat {
+            format: String::new(),
+            arguments: Vec::new(),
+        }];
+
+        Ok(Self {
+            group_index,
+            bind_group_layout,
+            empty_bind_group_layout,
+            buffer,
+            bind_group,
+            formats: Mutex::new(formats),
+            readbacks: Mutex::new(Readbacks {
+                in_flight: Vec::new(),
+                free: Vec::new(),
+            }),
+            callback: Mutex::new(None),
+        })
+    }
+
+    pub(crate) fn dispose(self, device: &A::Device) {
+        let readbacks = self.readbacks.into_inner();
+        unsafe {
+            for buffer in readbacks
+                .free
+                .into_iter()
+                .chain(readbacks.in_flight.into_iter().map(|(_, buffer)| buffer))
+            {
+                device.destroy_buffer(buffer);
+            }
+            device.destroy_bind_group(self.bind_group);
+            device.destroy_buffer(self.buffer);
+            device.destroy_bind_group_layout(self.bind_group_layout);
+            device.destroy_bind_group_layout(self.empty_bind_group_layout);
+        }
+    }
+
+    pub(crate) fn set_callback(&self, callback: Option<DebugPrintfCallback>) {
+        *self.callback.lock() = callback;
+    }
+
+    /// Lowers the `debugPrintf` statements of a validated module, registering
+    /// their formats with the device.
+    ///
+    /// Returns `false` if `module` does not use debug printf and was left untouched.
+    pub(crate) fn lower(&self, module: &mut naga::Module, info: &naga::valid::ModuleInfo) -> bool {
+        naga::proc::lower_debug_printf(
+            module,
+            info,
+            naga::ResourceBinding {
+                group: self.group_index,
+                binding: 0,
+            },
+            &mut self.formats.lock(),
+        )
+    }
+
+    /// Returns the layouts to append to the bind group layouts of a pipeline
+    /// layout using `group_count` groups, so that the capture buffer can be bound.
+    pub(crate) fn extra_bind_group_layouts(
+        &self,
+        group_count: usize,
+    ) -> impl Iterator<Item = &A::BindGroupLayout> {
+        let padding = (self.group_index as usize).saturating_sub(group_count);
+        iter::repeat(&self.empty_bind_group_layout)
+            .take(padding)
+            .chain(iter::once(&self.bind_group_layout))
+    }
+
+    /// Binds the capture buffer for the pipeline that was just set on `encoder`.
+    pub(crate) unsafe fn bind(
+        &self,
+        encoder: &mut A::CommandEncoder,
+        pipeline_layout: &A::PipelineLayout,
+    ) {
+        unsafe { encoder.set_bind_group(pipeline_layout, self.group_index, &self.bind_group, &[]) };
+    }
+
+    /// Records the copy of the capture buffer into a readback buffer, to be
+    /// submitted after all the user's command buffers of `submit_index`.
+    pub(crate) fn encode_readback(
+        &self,
+        device: &A::Device,
+        encoder: &mut A::CommandEncoder,
+        submit_index: SubmissionIndex,
+        instance_flags: wgt::InstanceFlags,
+    ) -> Result<A::CommandBuffer, DeviceError> {
+        let mut readbacks = self.readbacks.lock();
+        let readback = match readbacks.free.pop() {
+            Some(buffer) => buffer,
+            None => unsafe {
+                device.create_buffer(&hal::BufferDescriptor {
+                    label: hal_label(
+                        Some("(wgpu internal) debug printf readback buffer"),
+                        instance_flags,
+                    ),
+                    size: DEBUG_PRINTF_BUFFER_SIZE,
+                    usage: hal::BufferUses::MAP_READ | hal::BufferUses::COPY_DST,
+                    memory_flags: hal::MemoryFlags::empty(),
+                })
+            }?,
+        };
+
+        let cmd_buf = unsafe {
+            encoder.begin_encoding(hal_label(
+                Some("(wgpu internal) debug printf readback"),
+                instance_flags,
+            ))?;
+            encoder.transition_buffers(
+                [
+                    hal::BufferBarrier {
+                        buffer: &self.buffer,
+                        usage: hal::BufferUses::STORAGE_READ_WRITE..hal::BufferUses::COPY_SRC,
+                    },
+                    hal::BufferBarrier {
+                        buffer: &readback,
+                        usage: hal::BufferUses::MAP_READ..hal::BufferUses::COPY_DST,
+                    },
+                ]
+                .into_iter(),
+            );
+            encoder.copy_buffer_to_buffer(
+                &self.buffer,
+                &readback,
+                iter::once(hal::BufferCopy {
+                    src_offset: 0,
+                    dst_offset: 0,
+                    size: NonZeroU64::new(DEBUG_PRINTF_BUFFER_SIZE).unwrap(),
+                }),
+            );
+            encoder.transition_buffers(
+                [
+                    hal::BufferBarrier {
+                        buffer: &self.buffer,
+                        usage: hal::BufferUses::COPY_SRC..hal::BufferUses::COPY_DST,
+                    },
+                    hal::BufferBarrier {
+                        buffer: &readback,
+                        usage: hal::BufferUses::COPY_DST..hal::BufferUses::MAP_READ,
+                    },
+                ]
+                .into_iter(),
+            );
+            encoder.clear_buffer(&self.buffer, 0..DEBUG_PRINTF_BUFFER_SIZE);
+            encoder.transition_buffers(iter::once(hal::BufferBarrier {
+                buffer: &self.buffer,
+                usage: hal::BufferUses::COPY_DST..hal::BufferUses::STORAGE_READ_WRITE,
+            }));
+            encoder.end_encoding()?
+        };
+
+        readbacks.in_flight.push((submit_index, readback));
+        Ok(cmd_buf)
+    }
+
+    /// Decodes the readback buffers of every submission up to `last_done`.
+    pub(crate) fn read_back(
+        &self,
+        device: &A::Device,
+        last_done: SubmissionIndex,
+    ) -> Option<DebugPrintfMessages> {
+        let mut readbacks = self.readbacks.lock();
+        let done = readbacks
+            .in_flight
+            .iter()
+            .take_while(|&&(index, _)| index <= last_done)
+            .count();
+        if done == 0 {
+            return None;
+        }
+
+        let formats = self.formats.lock();
+        let mut messages = Vec::new();
+        for (_, buffer) in readbacks.in_flight.drain(..done).collect::<Vec<_>>() {
+            match unsafe { read_words(device, &buffer) } {
+                Ok(words) => decode_records(&formats, &words, &mut messages),
+                Err(err) => log::error!("Failed to read debug printf output: {err}"),
+            }
+            readbacks.free.push(buffer);
+        }
+
+        Some(DebugPrintfMessages {
+            callback: self.callback.lock().clone(),
+            messages,
+        })
+    }
+}
+
+unsafe fn read_words<A: HalApi>(
+    device: &A::Device,
+    buffer: &A::Buffer,
+) -> Result<Vec<u32>, DeviceError> {
+    let mapping = unsafe { device.map_buffer(buffer, 0..DEBUG_PRINTF_BUFFER_SIZE) }?;
+    if !mapping.is_coherent {
+        unsafe { device.invalidate_mapped_ranges(buffer, iter::once(0..DEBUG_PRINTF_BUFFER_SIZE)) };
+    }
+    let bytes = unsafe {
+        std::slice::from_raw_parts(mapping.ptr.as_ptr(), DEBUG_PRINTF_BUFFER_SIZE as usize)
+    };
+    let total_words = (DEBUG_PRINTF_BUFFER_SIZE / WORD_SIZE) as usize;
+    let count = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+    let words = bytes[4..(count.min(total_words - 1) + 1) * 4]
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect();
+    unsafe { device.unmap_buffer(buffer) }?;
+
+    if count > total_words - 1 {
+        log::warn!(
+            "Debug printf output of a submission exceeded {DEBUG_PRINTF_BUFFER_SIZE} bytes, \
+            some messages were dropped"
+        );
+    }
+    Ok(words)
+}
+
+fn decode_records(formats: &[DebugPrintfFormat], mut words: &[u32], messages: &mut Vec<String>) {
+    while let Some((&index, rest)) = words.split_first() {
+        let Some(format) = formats.get(index as usize) else {
+            log::error!("Invalid debug printf format index {index}");
+            return;
+        };
+        let arg_words = format.record_words() as usize - 1;
+        if rest.len() < arg_words {
+            return;
+        }
+        if index != 0 {
+            messages.push(format_message(
+                &format.format,
+                &format.arguments,
+                &rest[..arg_words],
+            ));
+        }
+        words = &rest[arg_words..];
+    }
+}
+
+/// Substitutes `words` into a C-style format string.
+///
+/// Supported conversions are `%d`, `%i`, `%u`, `%x`, `%X`, `%o`, `%c`, `%f`,
+/// `%F`, `%e`, `%E`, `%g`, `%G`, and `%a`, optionally with a precision like
+/// `%.3f`, and prefixed with `vN` for vectors of `N` components, like `%v3f`.
+/// `%%` is a literal `%`.
+fn format_message(format: &str, arguments: &[DebugPrintfArgument], words: &[u32]) -> String {
+    let mut message = String::with_capacity(format.len());
+    let mut arguments = arguments.iter();
+    let mut words = words.iter().copied();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            message.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            message.push('%');
+            continue;
+        }
+
+        // Flags and width are accepted, but ignored.
+        while chars
+            .peek()
+            .map_or(false, |&c| "-+ #0".contains(c) || c.is_ascii_digit())
+        {
+            chars.next();
+        }
+        let mut precision = None;
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            let mut value = 0;
+            while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                chars.next();
+                value = value * 10 + digit as usize;
+            }
+            precision = Some(value);
+        }
+        if chars.peek() == Some(&'v') {
+            chars.next();
+            chars.next();
+        }
+        let Some(conversion) = chars.next() else {
+            message.push('%');
+            break;
+        };
+
+        let Some(argument) = arguments.next() else {
+            message.push('%');
+            message.push(conversion);
+            continue;
+        };
+        for component in 0..argument.components {
+            if component != 0 {
+                message.push_str(", ");
+            }
+            let bits = words.next().unwrap_or(0);
+            format_value(&mut message, conversion, precision, argument.kind, bits);
+        }
+    }
+
+    message
+}
+
+fn format_value(
+    out: &mut String,
+    conversion: char,
+    precision: Option<usize>,
+    kind: naga::ScalarKind,
+    bits: u32,
+) {
+    use std::fmt::Write as _;
+
+    let float = match kind {
+        naga::ScalarKind::Float => f32::from_bits(bits) as f64,
+        naga::ScalarKind::Sint => bits as i32 as f64,
+        _ => bits as f64,
+    };
+    let precision = precision.unwrap_or(6);
+    let _ = match conversion {
+        'd' | 'i' => match kind {
+            naga::ScalarKind::Float => write!(out, "{}", f32::from_bits(bits) as i64),
+            _ => write!(out, "{}", bits as i32),
+        },
+        'u' => write!(out, "{bits}"),
+        'x' => write!(out, "{bits:x}"),
+        'X' => write!(out, "{bits:X}"),
+        'o' => write!(out, "{bits:o}"),
+        'c' => write!(out, "{}", char::from_u32(bits).unwrap_or('\u{FFFD}')),
+        'f' | 'F' => write!(out, "{float:.precision$}"),
+        'e' => write!(out, "{float:.precision$e}"),
+        'E' => write!(out, "{float:.precision$E}"),
+        'g' | 'G' | 'a' => write!(out, "{float}"),
+        other => write!(out, "%{other}"),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argument(kind: naga::ScalarKind, components: u32) -> DebugPrintfArgument {
+        DebugPrintfArgument { kind, components }
+    }
+
+    #[test]
+    fn format_scalars_and_vectors() {
+        let message = format_message(
+            "id = %v3u, value = %.2f, %d%%",
+            &[
+                argument(naga::ScalarKind::Uint, 3),
+                argument(naga::ScalarKind::Float, 1),
+                argument(naga::ScalarKind::Sint, 1),
+            ],
+            &[1, 2, 3, 1.5f32.to_bits(), -4i32 as u32],
+        );
+        assert_eq!(message, "id = 1, 2, 3, value = 1.50, -4%");
+    }
+
+    #[test]
+    fn skip_dropped_records() {
+        let formats = [
+            DebugPrintfFormat {
+                format: String::new(),
+                arguments: Vec::new(),
+            },
+            DebugPrintfFormat {
+                format: "%x".to_string(),
+                arguments: vec![argument(naga::ScalarKind::Uint, 1)],
+            },
+        ];
+        let mut messages = Vec::new();
+        decode_records(&formats, &[1, 255, 0, 0, 1], &mut messages);
+        assert_eq!(messages, ["ff"]);
+    }
+}
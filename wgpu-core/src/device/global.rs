@@ -33,7 +33,10 @@ use std::{
     sync::{atomic::Ordering, Arc},
 };
 
-use super::{ImplicitPipelineIds, InvalidDevice, UserClosures};
+use super::{
+    watchdog::Watchdog, DebugPrintfCallback, ImplicitPipelineIds, InvalidDevice, UserClosures,
+    WatchdogCallback,
+};
 
 impl<G: GlobalIdentityHandlerFactory> Global<G> {
     pub fn adapter_is_surface_supported<A: HalApi>(
@@ -142,6 +145,36 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         Ok(device.downlevel.clone())
     }
 
+    pub fn device_memory_architecture<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+    ) -> Result<wgt::MemoryArchitecture, InvalidDevice> {
+        let hub = A::hub(self);
+
+        let device = hub.devices.get(device_id).map_err(|_| InvalidDevice)?;
+        if !device.is_valid() {
+            return Err(InvalidDevice);
+        }
+
+        Ok(device.adapter.raw.capabilities.memory_architecture)
+    }
+
+    /// Returns the queues requested with
+    /// [`wgt::DeviceDescriptor::additional_queues`], in order.
+    pub fn device_additional_queues<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+    ) -> Result<Vec<QueueId>, InvalidDevice> {
+        let hub = A::hub(self);
+
+        let device = hub.devices.get(device_id).map_err(|_| InvalidDevice)?;
+        if !device.is_valid() {
+            return Err(InvalidDevice);
+        }
+
+        Ok(device.additional_queue_ids.read().clone())
+    }
+
     pub fn device_create_buffer<A: HalApi>(
         &self,
         device_id: DeviceId,
@@ -149,7 +182,32 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         id_in: Input<G, id::BufferId>,
     ) -> (id::BufferId, Option<CreateBufferError>) {
         profiling::scope!("Device::create_buffer");
+        self.create_buffer_impl::<A>(device_id, desc, None, id_in)
+    }
+
+    /// Creates a buffer at `offset` bytes into the memory heap `heap_id`.
+    ///
+    /// The buffer may alias other resources placed in the same memory, see
+    /// [`Global::command_encoder_aliasing_barrier`].
+    pub fn device_create_buffer_in_heap<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        heap_id: id::MemoryHeapId,
+        offset: wgt::BufferAddress,
+        id_in: Input<G, id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        profiling::scope!("Device::create_buffer_in_heap");
+        self.create_buffer_impl::<A>(device_id, desc, Some((heap_id, offset)), id_in)
+    }
 
+    fn create_buffer_impl<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        placement: Option<(id::MemoryHeapId, wgt::BufferAddress)>,
+        id_in: Input<G, id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
         let hub = A::hub(self);
         let fid = hub.buffers.prepare::<G>(id_in);
 
@@ -170,6 +228,14 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 break CreateBufferError::InvalidUsage(desc.usage);
             }
 
+            let heap = match placement {
+                Some((heap_id, offset)) => match hub.memory_heaps.get(heap_id) {
+                    Ok(heap) => Some((heap, offset)),
+                    Err(_) => break resource::PlacementError::InvalidHeap(heap_id).into(),
+                },
+                None => None,
+            };
+
             #[cfg(feature = "trace")]
             if let Some(ref mut trace) = *device.trace.lock() {
                 let mut desc = desc.clone();
@@ -177,10 +243,19 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 if mapped_at_creation && !desc.usage.contains(wgt::BufferUsages::MAP_WRITE) {
                     desc.usage |= wgt::BufferUsages::COPY_DST;
                 }
-                trace.add(trace::Action::CreateBuffer(fid.id(), desc));
+                trace.add(match placement {
+                    Some((heap, offset)) => trace::Action::CreatePlacedBuffer {
+                        id: fid.id(),
+                        desc,
+                        heap,
+                        offset,
+                    },
+                    None => trace::Action::CreateBuffer(fid.id(), desc),
+                });
             }
 
-            let buffer = match device.create_buffer(desc, false) {
+            let placement = heap.as_ref().map(|(heap, offset)| (heap, *offset));
+            let buffer = match device.create_buffer(desc, false, placement) {
                 Ok(buffer) => buffer,
                 Err(e) => {
                     break e;
@@ -192,22 +267,18 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             } else if desc.usage.contains(wgt::BufferUsages::MAP_WRITE) {
                 // buffer is mappable, so we are just doing that at start
                 let map_size = buffer.size;
-                let ptr = if map_size == 0 {
-                    std::ptr::NonNull::dangling()
-                } else {
+                let mut mappings = resource::BufferMappings::new(HostMap::Write);
+                if map_size != 0 {
                     match map_buffer(device.raw(), &buffer, 0, map_size, HostMap::Write) {
-                        Ok(ptr) => ptr,
+                        Ok(ptr) => mappings.ptr = Some(ptr),
                         Err(e) => {
                             to_destroy.push(buffer);
                             break e.into();
                         }
                     }
-                };
-                *buffer.map_state.lock() = resource::BufferMapState::Active {
-                    ptr,
-                    range: 0..map_size,
-                    host: HostMap::Write,
-                };
+                }
+                mappings.active.push(0..map_size);
+                *buffer.map_state.lock() = resource::BufferMapState::Mapped(mappings);
                 hal::BufferUses::MAP_WRITE
             } else {
                 // buffer needs staging area for initialization only
@@ -219,7 +290,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     usage: wgt::BufferUsages::MAP_WRITE | wgt::BufferUsages::COPY_SRC,
                     mapped_at_creation: false,
                 };
-                let stage = match device.create_buffer(&stage_desc, true) {
+                let stage = match device.create_buffer(&stage_desc, true, None) {
                     Ok(stage) => stage,
                     Err(e) => {
                         to_destroy.push(buffer);
@@ -281,6 +352,109 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (id, Some(error))
     }
 
+    /// Creates a buffer whose memory other APIs can import, see
+    /// [`Global::buffer_export_external_memory`].
+    pub fn device_create_external_buffer<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        id_in: Input<G, id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        profiling::scope!("Device::create_external_buffer");
+
+        let hub = A::hub(self);
+        let fid = hub.buffers.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateBuffer(fid.id(), desc.clone()));
+            }
+
+            let buffer = match device.create_external_buffer(desc) {
+                Ok(buffer) => buffer,
+                Err(err) => break err,
+            };
+
+            let (id, buffer) = fid.assign(buffer);
+            api_log!("Device::create_external_buffer({desc:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .buffers
+                .insert_single(id, buffer, hal::BufferUses::empty());
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// # Safety
+    ///
+    /// - `memory` must refer to memory of the kind the backend of the device
+    ///   shares, allocated by another API with at least `memory.size` bytes.
+    /// - Access to the memory must be synchronized with the other API, for
+    ///   example with external fences.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub unsafe fn device_import_external_buffer<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        memory: wgt::ExternalMemory,
+        id_in: Input<G, id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        profiling::scope!("Device::import_external_buffer");
+
+        let hub = A::hub(self);
+        let fid = hub.buffers.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            // NB: The replay gets zeroed memory of its own instead of the imported contents
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateBuffer(fid.id(), desc.clone()));
+            }
+
+            let buffer = match device.import_external_buffer(desc, memory) {
+                Ok(buffer) => buffer,
+                Err(err) => break err,
+            };
+
+            let (id, buffer) = fid.assign(buffer);
+            api_log!("Device::import_external_buffer({desc:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .buffers
+                .insert_single(id, buffer, hal::BufferUses::empty());
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
     /// Assign `id_in` an error with the given `label`.
     ///
     /// Ensure that future attempts to use `id_in` as a buffer ID will propagate
@@ -345,12 +519,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
     ) -> Result<(), WaitIdleError> {
         let hub = A::hub(self);
 
-        let last_submission = {
-            let buffer_guard = hub.buffers.write();
-            match buffer_guard.get(buffer_id) {
-                Ok(buffer) => buffer.info.submission_index(),
-                Err(_) => return Ok(()),
-            }
+        let last_submission = match hub.buffers.get(buffer_id) {
+            Ok(buffer) => buffer.info.submission_index(),
+            Err(_) => return Ok(()),
         };
 
         hub.devices
@@ -474,6 +645,28 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         Ok(())
     }
 
+    /// Returns the memory of a buffer made by
+    /// [`Global::device_create_external_buffer`] or
+    /// [`Global::device_import_external_buffer`]. The caller owns the handle,
+    /// if the kind of handle can be owned.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn buffer_export_external_memory<A: HalApi>(
+        &self,
+        buffer_id: id::BufferId,
+    ) -> Result<wgt::ExternalMemory, DeviceError> {
+        api_log!("Buffer::export_external_memory {buffer_id:?}");
+
+        let hub = A::hub(self);
+        let buffer = hub
+            .buffers
+            .get(buffer_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        let device = &buffer.device;
+        let snatch_guard = device.snatchable_lock.read();
+        let raw = buffer.raw(&snatch_guard).ok_or(DeviceError::Invalid)?;
+        unsafe { device.raw().export_external_buffer(raw) }.map_err(DeviceError::from)
+    }
+
     pub fn buffer_label<A: HalApi>(&self, id: id::BufferId) -> String {
         A::hub(self).buffers.label_for_resource(id)
     }
@@ -548,7 +741,32 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         id_in: Input<G, id::TextureId>,
     ) -> (id::TextureId, Option<resource::CreateTextureError>) {
         profiling::scope!("Device::create_texture");
+        self.create_texture_impl::<A>(device_id, desc, None, id_in)
+    }
 
+    /// Creates a texture at `offset` bytes into the memory heap `heap_id`.
+    ///
+    /// The texture may alias other resources placed in the same memory, see
+    /// [`Global::command_encoder_aliasing_barrier`].
+    pub fn device_create_texture_in_heap<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        heap_id: id::MemoryHeapId,
+        offset: wgt::BufferAddress,
+        id_in: Input<G, id::TextureId>,
+    ) -> (id::TextureId, Option<resource::CreateTextureError>) {
+        profiling::scope!("Device::create_texture_in_heap");
+        self.create_texture_impl::<A>(device_id, desc, Some((heap_id, offset)), id_in)
+    }
+
+    fn create_texture_impl<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        placement: Option<(id::MemoryHeapId, wgt::BufferAddress)>,
+        id_in: Input<G, id::TextureId>,
+    ) -> (id::TextureId, Option<resource::CreateTextureError>) {
         let hub = A::hub(self);
 
         let fid = hub.textures.prepare::<G>(id_in);
@@ -561,12 +779,29 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
+            let heap = match placement {
+                Some((heap_id, offset)) => match hub.memory_heaps.get(heap_id) {
+                    Ok(heap) => Some((heap, offset)),
+                    Err(_) => break resource::PlacementError::InvalidHeap(heap_id).into(),
+                },
+                None => None,
+            };
+
             #[cfg(feature = "trace")]
             if let Some(ref mut trace) = *device.trace.lock() {
-                trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
+                trace.add(match placement {
+                    Some((heap, offset)) => trace::Action::CreatePlacedTexture {
+                        id: fid.id(),
+                        desc: desc.clone(),
+                        heap,
+                        offset,
+                    },
+                    None => trace::Action::CreateTexture(fid.id(), desc.clone()),
+                });
             }
 
-            let texture = match device.create_texture(&device.adapter, desc) {
+            let placement = heap.as_ref().map(|(heap, offset)| (heap, *offset));
+            let texture = match device.create_texture(&device.adapter, desc, placement) {
                 Ok(texture) => texture,
                 Err(error) => break error,
             };
@@ -589,6 +824,61 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (id, Some(error))
     }
 
+    /// # Safety
+    ///
+    /// - `source` must hold a valid image matching `desc`, which outlives the texture.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub unsafe fn device_import_external_texture<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        source: wgt::ExternalImageSource,
+        id_in: Input<G, id::TextureId>,
+    ) -> (id::TextureId, Option<resource::CreateTextureError>) {
+        profiling::scope!("Device::import_external_texture");
+
+        let hub = A::hub(self);
+
+        let fid = hub.textures.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            // NB: The contents of the external image will not be recorded in the replay
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
+            }
+
+            let texture = match device.import_external_texture(&device.adapter, desc, source) {
+                Ok(texture) => texture,
+                Err(error) => break error,
+            };
+
+            let (id, resource) = fid.assign(texture);
+            api_log!("Device::import_external_texture({desc:?}) -> {id:?}");
+
+            device.trackers.lock().textures.insert_single(
+                id,
+                resource,
+                hal::TextureUses::UNINITIALIZED,
+            );
+
+            return (id, None);
+        };
+
+        log::error!("Device::import_external_texture error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
     /// # Safety
     ///
     /// - `hal_texture` must be created from `device_id` corresponding raw handle.
@@ -663,22 +953,91 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (id, Some(error))
     }
 
+    /// Creates a texture around a `VkImage` owned by another API, like the
+    /// swapchain images of an OpenXR runtime.
+    ///
+    /// The image is adopted in, and returned to at the end of every submission
+    /// using it, the `COLOR_ATTACHMENT_OPTIMAL` layout, or
+    /// `DEPTH_STENCIL_ATTACHMENT_OPTIMAL` for depth formats. It isn't destroyed
+    /// with the texture.
+    ///
     /// # Safety
     ///
-    /// - `hal_buffer` must be created from `device_id` corresponding raw handle.
-    /// - `hal_buffer` must be created respecting `desc`
-    /// - `hal_buffer` must be initialized
-    pub unsafe fn create_buffer_from_hal<A: HalApi>(
+    /// - `image` must be a `VkImage` of the device of `device_id`, created
+    ///   respecting `desc`, and outlive the texture.
+    /// - `image` must be in the layout above whenever work using the texture is
+    ///   submitted.
+    #[cfg(vulkan)]
+    pub unsafe fn device_create_texture_from_vulkan_image(
         &self,
-        hal_buffer: A::Buffer,
         device_id: DeviceId,
-        desc: &resource::BufferDescriptor,
-        id_in: Input<G, id::BufferId>,
-    ) -> (id::BufferId, Option<CreateBufferError>) {
-        profiling::scope!("Device::create_buffer");
+        image: u64,
+        desc: &resource::TextureDescriptor,
+        id_in: Input<G, id::TextureId>,
+    ) -> (id::TextureId, Option<resource::CreateTextureError>) {
+        profiling::scope!("Device::create_texture_from_vulkan_image");
+
+        unsafe {
+            self.device_wrap_raw_texture::<hal::api::Vulkan>(device_id, desc, id_in, |hal_desc| {
+                // we don't want to link to ash for this
+                hal::vulkan::Device::texture_from_raw(
+                    std::mem::transmute(image),
+                    hal_desc,
+                    Some(Box::new(())),
+                )
+            })
+        }
+    }
+
+    /// Creates a texture around an `ID3D12Resource` owned by another API, like
+    /// the swapchain images of an OpenXR runtime.
+    ///
+    /// The resource is adopted in, and returned to at the end of every
+    /// submission using it, the `D3D12_RESOURCE_STATE_RENDER_TARGET` state, or
+    /// `D3D12_RESOURCE_STATE_DEPTH_WRITE` for depth formats. The texture holds a
+    /// reference of its own to the resource.
+    ///
+    /// # Safety
+    ///
+    /// - `resource` must be an `ID3D12Resource` of the device of `device_id`,
+    ///   created respecting `desc`.
+    /// - `resource` must be in the state above whenever work using the texture
+    ///   is submitted.
+    #[cfg(dx12)]
+    pub unsafe fn device_create_texture_from_d3d12_resource(
+        &self,
+        device_id: DeviceId,
+        resource: *mut std::ffi::c_void,
+        desc: &resource::TextureDescriptor,
+        id_in: Input<G, id::TextureId>,
+    ) -> (id::TextureId, Option<resource::CreateTextureError>) {
+        profiling::scope!("Device::create_texture_from_d3d12_resource");
+
+        unsafe {
+            self.device_wrap_raw_texture::<hal::api::Dx12>(device_id, desc, id_in, |hal_desc| {
+                hal::dx12::Device::texture_from_raw_resource(
+                    resource.cast(),
+                    hal_desc.format,
+                    hal_desc.dimension,
+                    hal_desc.size,
+                    hal_desc.mip_level_count,
+                    hal_desc.sample_count,
+                )
+            })
+        }
+    }
 
+    #[cfg(any(vulkan, dx12))]
+    unsafe fn device_wrap_raw_texture<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        id_in: Input<G, id::TextureId>,
+        wrap: impl FnOnce(&hal::TextureDescriptor) -> A::Texture,
+    ) -> (id::TextureId, Option<resource::CreateTextureError>) {
         let hub = A::hub(self);
-        let fid = hub.buffers.prepare::<G>(id_in);
+
+        let fid = hub.textures.prepare::<G>(id_in);
 
         let error = loop {
             let device = match hub.devices.get(device_id) {
@@ -689,11 +1048,67 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 break DeviceError::Lost.into();
             }
 
-            // NB: Any change done through the raw buffer handle will not be
-            // recorded in the replay
+            // NB: The contents of the wrapped image will not be recorded in the replay
             #[cfg(feature = "trace")]
-            if let Some(trace) = device.trace.lock().as_mut() {
-                trace.add(trace::Action::CreateBuffer(fid.id(), desc.clone()));
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
+            }
+
+            let texture = match unsafe { device.wrap_raw_texture(&device.adapter, desc, wrap) } {
+                Ok(texture) => texture,
+                Err(error) => break error,
+            };
+            let resting_uses = texture.resting_uses.unwrap();
+
+            let (id, resource) = fid.assign(texture);
+            api_log!("Device::wrap_raw_texture({desc:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .textures
+                .insert_single(id, resource, resting_uses);
+
+            return (id, None);
+        };
+
+        log::error!("Device::wrap_raw_texture error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// # Safety
+    ///
+    /// - `hal_buffer` must be created from `device_id` corresponding raw handle.
+    /// - `hal_buffer` must be created respecting `desc`
+    /// - `hal_buffer` must be initialized
+    pub unsafe fn create_buffer_from_hal<A: HalApi>(
+        &self,
+        hal_buffer: A::Buffer,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        id_in: Input<G, id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        profiling::scope!("Device::create_buffer");
+
+        let hub = A::hub(self);
+        let fid = hub.buffers.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            // NB: Any change done through the raw buffer handle will not be
+            // recorded in the replay
+            #[cfg(feature = "trace")]
+            if let Some(trace) = device.trace.lock().as_mut() {
+                trace.add(trace::Action::CreateBuffer(fid.id(), desc.clone()));
             }
 
             let buffer = device.create_buffer_from_hal(hal_buffer, desc);
@@ -1007,6 +1422,19 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         A::hub(self).bind_group_layouts.label_for_resource(id)
     }
 
+    /// Returns the entries of the bind group layout, sorted by binding.
+    pub fn bind_group_layout_entries<A: HalApi>(
+        &self,
+        bind_group_layout_id: id::BindGroupLayoutId,
+    ) -> Result<Vec<wgt::BindGroupLayoutEntry>, binding_model::InvalidBindGroupLayout> {
+        let hub = A::hub(self);
+        let layout = hub
+            .bind_group_layouts
+            .get(bind_group_layout_id)
+            .map_err(|_| binding_model::InvalidBindGroupLayout)?;
+        Ok(layout.entries.values().copied().collect())
+    }
+
     pub fn bind_group_layout_drop<A: HalApi>(&self, bind_group_layout_id: id::BindGroupLayoutId) {
         profiling::scope!("BindGroupLayout::drop");
         api_log!("BindGroupLayout::drop {bind_group_layout_id:?}");
@@ -1093,7 +1521,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         profiling::scope!("Device::create_bind_group");
 
         let hub = A::hub(self);
-        let fid = hub.bind_groups.prepare::<G>(id_in);
+        // The ID may be consumed inside the bind group pool's constructor, see
+        // `device_create_bind_group_layout`.
+        let mut fid = Some(hub.bind_groups.prepare::<G>(id_in));
 
         let error = loop {
             let device = match hub.devices.get(device_id) {
@@ -1118,12 +1548,49 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 break DeviceError::WrongDevice.into();
             }
 
-            let bind_group = match device.create_bind_group(&bind_group_layout, desc, hub) {
-                Ok(bind_group) => bind_group,
-                Err(e) => break e,
-            };
+            // Set if a new bind group was created and registered.
+            let mut created = None;
+
+            if let Some(pool) = device.bind_group_pool.as_ref() {
+                let key = binding_model::BindGroupCacheKey::new(&bind_group_layout, desc);
+                let pool_result = pool.get_or_init(key, |key| {
+                    let mut bind_group = device.create_bind_group(&bind_group_layout, desc, hub)?;
+                    bind_group.cache_key = Some(key);
+
+                    let (id_inner, arc) = fid.take().unwrap().assign(bind_group);
+                    created = Some((id_inner, arc.clone()));
+
+                    Ok(arc)
+                });
 
-            let (id, resource) = fid.assign(bind_group);
+                let bind_group = match pool_result {
+                    Ok(bind_group) => bind_group,
+                    Err(e) => break e,
+                };
+
+                // The pool handed out an existing bind group. It can only be reused
+                // if none of its resources have been destroyed in the meantime,
+                // otherwise we fall through and create an uncached one.
+                //
+                // Like for bind group layouts, calling `assign_existing` leaks the ID.
+                // The resource itself stays tracked under its original ID.
+                if created.is_none() && bind_group.raw(&device.snatchable_lock.read()).is_some() {
+                    let id = fid.take().unwrap().assign_existing(&bind_group);
+                    api_log!("Device::create_bind_group -> {id:?} (cached)");
+                    return (id, None);
+                }
+            }
+
+            let (id, resource) = match created {
+                Some(created) => created,
+                None => {
+                    let bind_group = match device.create_bind_group(&bind_group_layout, desc, hub) {
+                        Ok(bind_group) => bind_group,
+                        Err(e) => break e,
+                    };
+                    fid.take().unwrap().assign(bind_group)
+                }
+            };
             api_log!("Device::create_bind_group -> {id:?}");
 
             device
@@ -1134,7 +1601,10 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             return (id, None);
         };
 
-        let id = fid.assign_error(desc.label.borrow_or_default());
+        let id = fid
+            .take()
+            .unwrap()
+            .assign_error(desc.label.borrow_or_default());
         (id, Some(error))
     }
 
@@ -1149,12 +1619,14 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let hub = A::hub(self);
 
         if let Some(bind_group) = hub.bind_groups.unregister(bind_group_id) {
+            // Cached bind groups can be registered under several IDs, but are only
+            // tracked by the device under the one they were created with.
             bind_group
                 .device
                 .lock_life()
                 .suspected_resources
                 .bind_groups
-                .insert(bind_group_id, bind_group.clone());
+                .insert(bind_group.as_info().id(), bind_group.clone());
         }
     }
 
@@ -1464,13 +1936,370 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
     pub fn device_create_query_set<A: HalApi>(
         &self,
         device_id: DeviceId,
-        desc: &resource::QuerySetDescriptor,
-        id_in: Input<G, id::QuerySetId>,
-    ) -> (id::QuerySetId, Option<resource::CreateQuerySetError>) {
-        profiling::scope!("Device::create_query_set");
+        desc: &resource::QuerySetDescriptor,
+        id_in: Input<G, id::QuerySetId>,
+    ) -> (id::QuerySetId, Option<resource::CreateQuerySetError>) {
+        profiling::scope!("Device::create_query_set");
+
+        let hub = A::hub(self);
+        let fid = hub.query_sets.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateQuerySet {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                });
+            }
+
+            let query_set = match device.create_query_set(desc) {
+                Ok(query_set) => query_set,
+                Err(err) => break err,
+            };
+
+            let (id, resource) = fid.assign(query_set);
+            api_log!("Device::create_query_set -> {id:?}");
+            device
+                .trackers
+                .lock()
+                .query_sets
+                .insert_single(id, resource);
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error("");
+        (id, Some(error))
+    }
+
+    pub fn query_set_drop<A: HalApi>(&self, query_set_id: id::QuerySetId) {
+        profiling::scope!("QuerySet::drop");
+        api_log!("QuerySet::drop {query_set_id:?}");
+
+        let hub = A::hub(self);
+
+        if let Some(query_set) = hub.query_sets.unregister(query_set_id) {
+            let device = &query_set.device;
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::DestroyQuerySet(query_set_id));
+            }
+
+            device
+                .lock_life()
+                .suspected_resources
+                .query_sets
+                .insert(query_set_id, query_set.clone());
+        }
+    }
+
+    pub fn query_set_label<A: HalApi>(&self, id: id::QuerySetId) -> String {
+        A::hub(self).query_sets.label_for_resource(id)
+    }
+
+    pub fn device_create_tile_pool<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TilePoolDescriptor,
+        id_in: Input<G, id::TilePoolId>,
+    ) -> (id::TilePoolId, Option<resource::CreateTilePoolError>) {
+        profiling::scope!("Device::create_tile_pool");
+
+        let hub = A::hub(self);
+        let fid = hub.tile_pools.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTilePool {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                });
+            }
+
+            let tile_pool = match device.create_tile_pool(desc) {
+                Ok(tile_pool) => tile_pool,
+                Err(err) => break err,
+            };
+
+            let (id, _) = fid.assign(tile_pool);
+            api_log!("Device::create_tile_pool -> {id:?}");
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    pub fn tile_pool_label<A: HalApi>(&self, id: id::TilePoolId) -> String {
+        A::hub(self).tile_pools.label_for_resource(id)
+    }
+
+    pub fn tile_pool_drop<A: HalApi>(&self, tile_pool_id: id::TilePoolId) {
+        profiling::scope!("TilePool::drop");
+        api_log!("TilePool::drop {tile_pool_id:?}");
+
+        let hub = A::hub(self);
+        // Textures keep the pools their tiles have been bound to alive, so the
+        // pool can be destroyed as soon as it's unregistered.
+        drop(hub.tile_pools.unregister(tile_pool_id));
+    }
+
+    pub fn device_create_memory_heap<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::MemoryHeapDescriptor,
+        id_in: Input<G, id::MemoryHeapId>,
+    ) -> (id::MemoryHeapId, Option<resource::CreateMemoryHeapError>) {
+        profiling::scope!("Device::create_memory_heap");
+
+        let hub = A::hub(self);
+        let fid = hub.memory_heaps.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateMemoryHeap {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                });
+            }
+
+            let memory_heap = match device.create_memory_heap(desc) {
+                Ok(memory_heap) => memory_heap,
+                Err(err) => break err,
+            };
+
+            let (id, _) = fid.assign(memory_heap);
+            api_log!("Device::create_memory_heap -> {id:?}");
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    pub fn memory_heap_label<A: HalApi>(&self, id: id::MemoryHeapId) -> String {
+        A::hub(self).memory_heaps.label_for_resource(id)
+    }
+
+    pub fn memory_heap_drop<A: HalApi>(&self, memory_heap_id: id::MemoryHeapId) {
+        profiling::scope!("MemoryHeap::drop");
+        api_log!("MemoryHeap::drop {memory_heap_id:?}");
+
+        let hub = A::hub(self);
+        // Resources placed in the heap keep it alive, so it can be destroyed
+        // as soon as it's unregistered.
+        drop(hub.memory_heaps.unregister(memory_heap_id));
+    }
+
+    pub fn device_create_external_fence<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::ExternalFenceDescriptor,
+        id_in: Input<G, id::ExternalFenceId>,
+    ) -> (
+        id::ExternalFenceId,
+        Option<resource::CreateExternalFenceError>,
+    ) {
+        profiling::scope!("Device::create_external_fence");
+
+        let hub = A::hub(self);
+        let fid = hub.external_fences.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateExternalFence {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                });
+            }
+
+            let external_fence = match device.create_external_fence(desc) {
+                Ok(external_fence) => external_fence,
+                Err(err) => break err,
+            };
+
+            let (id, _) = fid.assign(external_fence);
+            api_log!("Device::create_external_fence -> {id:?}");
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// # Safety
+    ///
+    /// - `handle` must refer to a valid fence of the kind the backend of the
+    ///   device shares, which is left owned by the caller.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub unsafe fn device_import_external_fence<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::ExternalFenceDescriptor,
+        handle: wgt::ExternalFenceHandle,
+        id_in: Input<G, id::ExternalFenceId>,
+    ) -> (
+        id::ExternalFenceId,
+        Option<resource::CreateExternalFenceError>,
+    ) {
+        profiling::scope!("Device::import_external_fence");
+
+        let hub = A::hub(self);
+        let fid = hub.external_fences.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            // NB: The replay gets a fence of its own, which nothing outside of wgpu signals
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateExternalFence {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                });
+            }
+
+            let external_fence = match device.import_external_fence(desc, handle) {
+                Ok(external_fence) => external_fence,
+                Err(err) => break err,
+            };
+
+            let (id, _) = fid.assign(external_fence);
+            api_log!("Device::import_external_fence -> {id:?}");
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Returns a handle other APIs can import the fence with. The caller owns
+    /// the handle, if the kind of handle can be owned.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn external_fence_export<A: HalApi>(
+        &self,
+        external_fence_id: id::ExternalFenceId,
+    ) -> Result<wgt::ExternalFenceHandle, DeviceError> {
+        api_log!("ExternalFence::export {external_fence_id:?}");
+
+        let hub = A::hub(self);
+        let external_fence = hub
+            .external_fences
+            .get(external_fence_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        let device = &external_fence.device;
+        unsafe { device.raw().export_external_fence(external_fence.raw()) }
+            .map_err(DeviceError::from)
+    }
+
+    pub fn external_fence_label<A: HalApi>(&self, id: id::ExternalFenceId) -> String {
+        A::hub(self).external_fences.label_for_resource(id)
+    }
+
+    pub fn external_fence_drop<A: HalApi>(&self, external_fence_id: id::ExternalFenceId) {
+        profiling::scope!("ExternalFence::drop");
+        api_log!("ExternalFence::drop {external_fence_id:?}");
+
+        let hub = A::hub(self);
+        drop(hub.external_fences.unregister(external_fence_id));
+    }
+
+    /// Returns the size and alignment of the memory a buffer made from `desc`
+    /// would need in a memory heap.
+    pub fn device_buffer_memory_requirements<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, CreateBufferError> {
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+        device.buffer_memory_requirements(desc)
+    }
+
+    /// Returns the size and alignment of the memory a texture made from `desc`
+    /// would need in a memory heap.
+    pub fn device_texture_memory_requirements<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, resource::CreateTextureError> {
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+        device.texture_memory_requirements(&device.adapter, desc)
+    }
+
+    pub fn device_create_render_pipeline<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &pipeline::RenderPipelineDescriptor,
+        id_in: Input<G, id::RenderPipelineId>,
+        implicit_pipeline_ids: Option<ImplicitPipelineIds<G>>,
+    ) -> (
+        id::RenderPipelineId,
+        Option<pipeline::CreateRenderPipelineError>,
+    ) {
+        profiling::scope!("Device::create_render_pipeline");
 
         let hub = A::hub(self);
-        let fid = hub.query_sets.prepare::<G>(id_in);
+
+        let fid = hub.render_pipelines.prepare::<G>(id_in);
+        let implicit_context = implicit_pipeline_ids.map(|ipi| ipi.prepare(hub));
+        let implicit_error_context = implicit_context.clone();
 
         let error = loop {
             let device = match hub.devices.get(device_id) {
@@ -1480,72 +2309,74 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
-
             #[cfg(feature = "trace")]
             if let Some(ref mut trace) = *device.trace.lock() {
-                trace.add(trace::Action::CreateQuerySet {
+                trace.add(trace::Action::CreateRenderPipeline {
                     id: fid.id(),
                     desc: desc.clone(),
+                    implicit_context: implicit_context.clone(),
                 });
             }
 
-            let query_set = match device.create_query_set(desc) {
-                Ok(query_set) => query_set,
-                Err(err) => break err,
+            let pipeline = match device.create_render_pipeline(
+                &device.adapter,
+                desc.into(),
+                implicit_context,
+                hub,
+            ) {
+                Ok(pair) => pair,
+                Err(e) => break e,
             };
 
-            let (id, resource) = fid.assign(query_set);
-            api_log!("Device::create_query_set -> {id:?}");
+            let (id, resource) = fid.assign(pipeline);
+            api_log!("Device::create_render_pipeline -> {id:?}");
+
             device
                 .trackers
                 .lock()
-                .query_sets
+                .render_pipelines
                 .insert_single(id, resource);
 
             return (id, None);
         };
 
-        let id = fid.assign_error("");
-        (id, Some(error))
-    }
-
-    pub fn query_set_drop<A: HalApi>(&self, query_set_id: id::QuerySetId) {
-        profiling::scope!("QuerySet::drop");
-        api_log!("QuerySet::drop {query_set_id:?}");
-
-        let hub = A::hub(self);
-
-        if let Some(query_set) = hub.query_sets.unregister(query_set_id) {
-            let device = &query_set.device;
+        let id = fid.assign_error(desc.label.borrow_or_default());
 
-            #[cfg(feature = "trace")]
-            if let Some(ref mut trace) = *device.trace.lock() {
-                trace.add(trace::Action::DestroyQuerySet(query_set_id));
+        // We also need to assign errors to the implicit pipeline layout and the
+        // implicit bind group layout. We have to remove any existing entries first.
+        let mut pipeline_layout_guard = hub.pipeline_layouts.write();
+        let mut bgl_guard = hub.bind_group_layouts.write();
+        if let Some(ref ids) = implicit_error_context {
+            if pipeline_layout_guard.contains(ids.root_id) {
+                pipeline_layout_guard.remove(ids.root_id);
+            }
+            pipeline_layout_guard.insert_error(ids.root_id, IMPLICIT_BIND_GROUP_LAYOUT_ERROR_LABEL);
+            for &bgl_id in ids.group_ids.iter() {
+                if bgl_guard.contains(bgl_id) {
+                    bgl_guard.remove(bgl_id);
+                }
+                bgl_guard.insert_error(bgl_id, IMPLICIT_BIND_GROUP_LAYOUT_ERROR_LABEL);
             }
-
-            device
-                .lock_life()
-                .suspected_resources
-                .query_sets
-                .insert(query_set_id, query_set.clone());
         }
-    }
 
-    pub fn query_set_label<A: HalApi>(&self, id: id::QuerySetId) -> String {
-        A::hub(self).query_sets.label_for_resource(id)
+        log::error!("Device::create_render_pipeline error: {error}");
+
+        (id, Some(error))
     }
 
-    pub fn device_create_render_pipeline<A: HalApi>(
+    /// Creates a render pipeline whose primitives are generated by mesh
+    /// shaders. Requires [`wgt::Features::EXPERIMENTAL_MESH_SHADER`].
+    pub fn device_create_mesh_pipeline<A: HalApi>(
         &self,
         device_id: DeviceId,
-        desc: &pipeline::RenderPipelineDescriptor,
+        desc: &pipeline::MeshPipelineDescriptor,
         id_in: Input<G, id::RenderPipelineId>,
         implicit_pipeline_ids: Option<ImplicitPipelineIds<G>>,
     ) -> (
         id::RenderPipelineId,
         Option<pipeline::CreateRenderPipelineError>,
     ) {
-        profiling::scope!("Device::create_render_pipeline");
+        profiling::scope!("Device::create_mesh_pipeline");
 
         let hub = A::hub(self);
 
@@ -1563,21 +2394,25 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             }
             #[cfg(feature = "trace")]
             if let Some(ref mut trace) = *device.trace.lock() {
-                trace.add(trace::Action::CreateRenderPipeline {
+                trace.add(trace::Action::CreateMeshPipeline {
                     id: fid.id(),
                     desc: desc.clone(),
                     implicit_context: implicit_context.clone(),
                 });
             }
 
-            let pipeline =
-                match device.create_render_pipeline(&device.adapter, desc, implicit_context, hub) {
-                    Ok(pair) => pair,
-                    Err(e) => break e,
-                };
+            let pipeline = match device.create_render_pipeline(
+                &device.adapter,
+                desc.into(),
+                implicit_context,
+                hub,
+            ) {
+                Ok(pair) => pair,
+                Err(e) => break e,
+            };
 
             let (id, resource) = fid.assign(pipeline);
-            api_log!("Device::create_render_pipeline -> {id:?}");
+            api_log!("Device::create_mesh_pipeline -> {id:?}");
 
             device
                 .trackers
@@ -1607,7 +2442,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             }
         }
 
-        log::error!("Device::create_render_pipeline error: {error}");
+        log::error!("Device::create_mesh_pipeline error: {error}");
 
         (id, Some(error))
     }
@@ -1807,6 +2642,78 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         }
     }
 
+    /// # Safety
+    ///
+    /// The `data` argument of `desc` must have been returned by
+    /// [Self::pipeline_cache_get_data] for the same adapter.
+    pub unsafe fn device_create_pipeline_cache<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &pipeline::PipelineCacheDescriptor<'_>,
+        id_in: Input<G, id::PipelineCacheId>,
+    ) -> (
+        id::PipelineCacheId,
+        Option<pipeline::CreatePipelineCacheError>,
+    ) {
+        profiling::scope!("Device::create_pipeline_cache");
+
+        let hub = A::hub(self);
+        let fid = hub.pipeline_caches.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreatePipelineCache {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                });
+            }
+
+            let cache = match unsafe { device.create_pipeline_cache(desc) } {
+                Ok(cache) => cache,
+                Err(err) => break err,
+            };
+
+            let (id, _) = fid.assign(cache);
+            api_log!("Device::create_pipeline_cache -> {id:?}");
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    pub fn pipeline_cache_get_data<A: HalApi>(&self, id: id::PipelineCacheId) -> Option<Vec<u8>> {
+        api_log!("PipelineCache::get_data");
+        let hub = A::hub(self);
+
+        let cache = hub.pipeline_caches.get(id).ok()?;
+        // The cache may still be valid even if the device has been lost.
+        unsafe { cache.device.raw().pipeline_cache_get_data(cache.raw()) }
+    }
+
+    pub fn pipeline_cache_label<A: HalApi>(&self, id: id::PipelineCacheId) -> String {
+        A::hub(self).pipeline_caches.label_for_resource(id)
+    }
+
+    pub fn pipeline_cache_drop<A: HalApi>(&self, pipeline_cache_id: id::PipelineCacheId) {
+        profiling::scope!("PipelineCache::drop");
+        api_log!("PipelineCache::drop {pipeline_cache_id:?}");
+
+        let hub = A::hub(self);
+        // Pipelines only use the cache while they're being created, so it can
+        // be destroyed as soon as it's unregistered.
+        drop(hub.pipeline_caches.unregister(pipeline_cache_id));
+    }
+
     pub fn surface_configure<A: HalApi>(
         &self,
         surface_id: SurfaceId,
@@ -1941,6 +2848,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 if !device.is_valid() {
                     break DeviceError::Lost.into();
                 }
+                if let Err(e) = device.require_rendering() {
+                    break e.into();
+                }
 
                 #[cfg(feature = "trace")]
                 if let Some(ref mut trace) = *device.trace.lock() {
@@ -2126,6 +3036,64 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         Ok(queue_empty)
     }
 
+    /// Waits until the submission `submission_index` is done or `timeout` elapsed,
+    /// then maintains the device like [`Global::device_poll`].
+    ///
+    /// Timeouts above `u32::MAX` milliseconds are clamped.
+    pub fn device_poll_until<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        submission_index: queue::WrappedSubmissionIndex,
+        timeout: std::time::Duration,
+    ) -> Result<wgt::PollStatus, WaitIdleError> {
+        api_log!("Device::poll_until {submission_index:?} {timeout:?}");
+
+        if submission_index.queue_id != device_id {
+            return Err(WaitIdleError::WrongSubmissionIndex(
+                submission_index.queue_id,
+                device_id,
+            ));
+        }
+
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        if !device.is_valid() {
+            return Ok(wgt::PollStatus::DeviceLost);
+        }
+
+        let (closures, status) = {
+            let fence = device.fence.read();
+            let fence = fence.as_ref().unwrap();
+            let timeout_ms = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+            let done =
+                match device.wait_for_submission_index(fence, submission_index.index, timeout_ms) {
+                    Ok(done) => done,
+                    Err(hal::DeviceError::Lost) => {
+                        device.handle_hal_error(hal::DeviceError::Lost);
+                        false
+                    }
+                    Err(err) => return Err(device.handle_hal_error(err).into()),
+                };
+            // Maintaining a device lost by the driver calls its device lost closure.
+            let (closures, _) = device.maintain(fence, wgt::Maintain::Poll)?;
+            let status = if !device.is_valid() {
+                wgt::PollStatus::DeviceLost
+            } else if done {
+                wgt::PollStatus::Complete
+            } else {
+                wgt::PollStatus::TimedOut
+            };
+            (closures, status)
+        };
+
+        closures.fire();
+
+        Ok(status)
+    }
+
     /// Poll all devices belonging to the backend `A`.
     ///
     /// If `force_wait` is true, block until all buffer mappings are done.
@@ -2270,6 +3238,84 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         }
     }
 
+    /// Sets the closure receiving the messages of shader debug printf statements,
+    /// replacing the previous one. Messages are logged if no closure is set.
+    ///
+    /// Does nothing if [`wgt::Features::SHADER_DEBUG_PRINTF`] is not enabled.
+    pub fn device_set_debug_printf_callback<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        callback: Option<DebugPrintfCallback>,
+    ) {
+        let hub = A::hub(self);
+
+        if let Ok(device) = hub.devices.get(device_id) {
+            if let Some(ref debug_printf) = device.debug_printf {
+                debug_printf.set_callback(callback);
+            }
+        }
+    }
+
+    /// Starts timing every submission to the device, calling `callback` from
+    /// a background thread for every submission still running after `threshold`.
+    ///
+    /// Replaces the previous watchdog. Passing `None` stops watching the device.
+    pub fn device_set_watchdog<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        watchdog: Option<(std::time::Duration, WatchdogCallback)>,
+    ) -> Result<(), InvalidDevice> {
+        api_log!("Device::set_watchdog {device_id:?}");
+
+        let hub = A::hub(self);
+        let device = hub.devices.get(device_id).map_err(|_| InvalidDevice)?;
+
+        let watchdog = match watchdog {
+            Some((threshold, callback)) => match Watchdog::start(&device, threshold, callback) {
+                Ok(watchdog) => Some(watchdog),
+                Err(err) => {
+                    log::warn!("Failed to start the device watchdog: {err}");
+                    None
+                }
+            },
+            None => None,
+        };
+        *device.watchdog.lock() = watchdog;
+        Ok(())
+    }
+
+    /// Starts or stops a background thread polling the device while it has
+    /// work in flight, so that callbacks are called without calling
+    /// [`Global::device_poll`].
+    pub fn device_set_automatic_polling<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        enabled: bool,
+    ) -> Result<(), InvalidDevice> {
+        api_log!("Device::set_automatic_polling {device_id:?} {enabled}");
+
+        let hub = A::hub(self);
+        let device = hub.devices.get(device_id).map_err(|_| InvalidDevice)?;
+        device.set_automatic_polling(enabled);
+        Ok(())
+    }
+
+    /// Starts or stops writing a listing of the recorded commands in every
+    /// command encoder created afterwards on the device, to be read with
+    /// [`Global::command_buffer_disassembly`].
+    pub fn device_set_command_disassembly<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        enabled: bool,
+    ) -> Result<(), InvalidDevice> {
+        api_log!("Device::set_command_disassembly {device_id:?} {enabled}");
+
+        let hub = A::hub(self);
+        let device = hub.devices.get(device_id).map_err(|_| InvalidDevice)?;
+        device.command_disassembly.store(enabled, Ordering::Relaxed);
+        Ok(())
+    }
+
     pub fn device_destroy<A: HalApi>(&self, device_id: DeviceId) {
         api_log!("Device::destroy {device_id:?}");
 
@@ -2306,6 +3352,18 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         }
     }
 
+    /// Handles a [`hal::DeviceError::Lost`] on `device_id`, as if its driver had
+    /// returned it, to test the recovery from driver errors.
+    pub fn device_mark_lost_by_driver<A: HalApi>(&self, device_id: DeviceId) {
+        api_log!("Device::mark_lost_by_driver {device_id:?}");
+
+        let hub = A::hub(self);
+
+        if let Ok(device) = hub.devices.get(device_id) {
+            device.handle_hal_error(hal::DeviceError::Lost);
+        }
+    }
+
     pub fn queue_drop<A: HalApi>(&self, queue_id: QueueId) {
         profiling::scope!("Queue::drop");
         api_log!("Queue::drop {queue_id:?}");
@@ -2407,22 +3465,30 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
             {
                 let map_state = &mut *buffer.map_state.lock();
-                *map_state = match *map_state {
-                    resource::BufferMapState::Init { .. }
-                    | resource::BufferMapState::Active { .. } => {
+                if let resource::BufferMapState::Idle = *map_state {
+                    *map_state =
+                        resource::BufferMapState::Mapped(resource::BufferMappings::new(op.host));
+                }
+                // Disjoint ranges can be mapped at the same time, as long as
+                // they are all mapped for the same kind of access.
+                let mappings = match *map_state {
+                    resource::BufferMapState::Init { .. } => {
                         return Err((op, BufferAccessError::AlreadyMapped));
                     }
-                    resource::BufferMapState::Waiting(_) => {
-                        return Err((op, BufferAccessError::MapAlreadyPending));
-                    }
-                    resource::BufferMapState::Idle => {
-                        resource::BufferMapState::Waiting(resource::BufferPendingMapping {
-                            range,
-                            op,
-                            _parent_buffer: buffer.clone(),
-                        })
-                    }
+                    resource::BufferMapState::Mapped(ref mut mappings) => mappings,
+                    resource::BufferMapState::Idle => unreachable!(),
                 };
+                if mappings.host != op.host || mappings.overlaps_active(&range) {
+                    return Err((op, BufferAccessError::AlreadyMapped));
+                }
+                if mappings.overlaps_pending(&range) {
+                    return Err((op, BufferAccessError::MapAlreadyPending));
+                }
+                mappings.pending.push_back(resource::BufferPendingMapping {
+                    range,
+                    op,
+                    _parent_buffer: buffer.clone(),
+                });
             }
 
             {
@@ -2438,6 +3504,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         };
 
         buffer.device.lock_life().map(&buffer);
+        buffer.device.wake_poller();
 
         Ok(())
     }
@@ -2491,31 +3558,73 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 }
                 unsafe { Ok((ptr.as_ptr().offset(offset as isize), range_size)) }
             }
-            resource::BufferMapState::Active {
-                ref ptr, ref range, ..
-            } => {
+            resource::BufferMapState::Mapped(ref mappings) => {
+                let end = offset + range_size;
+                // Prefer the range containing the requested one, then the
+                // closest range before it for reporting the overrun.
+                let range = mappings
+                    .active
+                    .iter()
+                    .find(|range| range.start <= offset && end <= range.end)
+                    .or_else(|| {
+                        mappings
+                            .active
+                            .iter()
+                            .filter(|range| range.start <= offset)
+                            .max_by_key(|range| range.start)
+                    })
+                    .or_else(|| mappings.active.iter().min_by_key(|range| range.start))
+                    .ok_or(BufferAccessError::NotMapped)?;
                 if offset < range.start {
                     return Err(BufferAccessError::OutOfBoundsUnderrun {
                         index: offset,
                         min: range.start,
                     });
                 }
-                if offset + range_size > range.end {
+                if end > range.end {
                     return Err(BufferAccessError::OutOfBoundsOverrun {
-                        index: offset + range_size - 1,
+                        index: end - 1,
                         max: range.end,
                     });
                 }
-                // ptr points to the beginning of the range we mapped in map_async
-                // rather thant the beginning of the buffer.
-                let relative_offset = (offset - range.start) as isize;
-                unsafe { Ok((ptr.as_ptr().offset(relative_offset), range_size)) }
-            }
-            resource::BufferMapState::Idle | resource::BufferMapState::Waiting(_) => {
-                Err(BufferAccessError::NotMapped)
+                // ptr points to the beginning of the buffer, as the whole
+                // buffer is mapped while any range is active.
+                match mappings.ptr {
+                    Some(ptr) => unsafe { Ok((ptr.as_ptr().offset(offset as isize), range_size)) },
+                    None => Ok((std::ptr::NonNull::dangling().as_ptr(), range_size)),
+                }
             }
+            resource::BufferMapState::Idle => Err(BufferAccessError::NotMapped),
         }
     }
+
+    /// Returns the device address of the start of a vertex buffer, that
+    /// device generated commands use to bind it.
+    pub fn buffer_device_address<A: HalApi>(
+        &self,
+        buffer_id: id::BufferId,
+    ) -> Result<BufferAddress, BufferAccessError> {
+        profiling::scope!("Buffer::device_address");
+        api_log!("Buffer::device_address {buffer_id:?}");
+
+        let hub = A::hub(self);
+
+        let buffer = hub
+            .buffers
+            .get(buffer_id)
+            .map_err(|_| BufferAccessError::Invalid)?;
+        buffer
+            .device
+            .require_features(wgt::Features::DEVICE_GENERATED_COMMANDS)?;
+        check_buffer_usage(buffer.usage, wgt::BufferUsages::VERTEX)?;
+
+        let snatch_guard = buffer.device.snatchable_lock.read();
+        let raw = buffer
+            .raw(&snatch_guard)
+            .ok_or(BufferAccessError::Destroyed)?;
+        Ok(unsafe { buffer.device.raw().get_buffer_device_address(raw) })
+    }
+
     pub fn buffer_unmap<A: HalApi>(&self, buffer_id: id::BufferId) -> BufferAccessResult {
         profiling::scope!("unmap", "Buffer");
         api_log!("Buffer::unmap {buffer_id:?}");
@@ -2539,4 +3648,41 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
         buffer.unmap()
     }
+
+    /// Unmaps the range `offset..offset + size` of the buffer, which must have
+    /// been mapped on its own with [`Global::buffer_map_async`].
+    ///
+    /// The other ranges of the buffer stay mapped.
+    pub fn buffer_unmap_range<A: HalApi>(
+        &self,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+    ) -> BufferAccessResult {
+        profiling::scope!("unmap_range", "Buffer");
+        api_log!("Buffer::unmap_range {buffer_id:?} {offset}..{size:?}");
+
+        let hub = A::hub(self);
+
+        let buffer = hub
+            .buffers
+            .get(buffer_id)
+            .map_err(|_| BufferAccessError::Invalid)?;
+
+        let snatch_guard = buffer.device.snatchable_lock.read();
+        if buffer.is_destroyed(&snatch_guard) {
+            return Err(BufferAccessError::Destroyed);
+        }
+        drop(snatch_guard);
+
+        if !buffer.device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+
+        let end = match size {
+            Some(size) => offset + size,
+            None => buffer.size,
+        };
+        buffer.unmap_range(offset..end)
+    }
 }
@@ -7,14 +7,14 @@ use crate::{
     },
     hal_api::HalApi,
     id::{
-        self, BindGroupId, BindGroupLayoutId, BufferId, ComputePipelineId, PipelineLayoutId,
-        QuerySetId, RenderBundleId, RenderPipelineId, SamplerId, StagingBufferId, TextureId,
-        TextureViewId,
+        self, BindGroupId, BindGroupLayoutId, BlasId, BufferId, ComputePipelineId,
+        PipelineLayoutId, QuerySetId, RenderBundleId, RenderPipelineId, SamplerId, StagingBufferId,
+        TextureId, TextureViewId, TlasId,
     },
     pipeline::{ComputePipeline, RenderPipeline},
     resource::{
-        self, Buffer, DestroyedBuffer, DestroyedTexture, QuerySet, Resource, Sampler,
-        StagingBuffer, Texture, TextureView,
+        self, Blas, Buffer, DestroyedBuffer, DestroyedTexture, QuerySet, Resource, Sampler,
+        StagingBuffer, Texture, TextureView, Tlas,
     },
     track::{ResourceTracker, Tracker},
     FastHashMap, SubmissionIndex,
@@ -40,6 +40,8 @@ pub(crate) struct ResourceMaps<A: HalApi> {
     pub pipeline_layouts: FastHashMap<PipelineLayoutId, Arc<PipelineLayout<A>>>,
     pub render_bundles: FastHashMap<RenderBundleId, Arc<RenderBundle<A>>>,
     pub query_sets: FastHashMap<QuerySetId, Arc<QuerySet<A>>>,
+    pub blas_s: FastHashMap<BlasId, Arc<Blas<A>>>,
+    pub tlas_s: FastHashMap<TlasId, Arc<Tlas<A>>>,
     pub destroyed_buffers: FastHashMap<BufferId, Arc<DestroyedBuffer<A>>>,
     pub destroyed_textures: FastHashMap<TextureId, Arc<DestroyedTexture<A>>>,
 }
@@ -59,6 +61,8 @@ impl<A: HalApi> ResourceMaps<A> {
             pipeline_layouts: FastHashMap::default(),
             render_bundles: FastHashMap::default(),
             query_sets: FastHashMap::default(),
+            blas_s: FastHashMap::default(),
+            tlas_s: FastHashMap::default(),
             destroyed_buffers: FastHashMap::default(),
             destroyed_textures: FastHashMap::default(),
         }
@@ -78,6 +82,8 @@ impl<A: HalApi> ResourceMaps<A> {
             pipeline_layouts,
             render_bundles,
             query_sets,
+            blas_s,
+            tlas_s,
             destroyed_buffers,
             destroyed_textures,
         } = self;
@@ -93,6 +99,8 @@ impl<A: HalApi> ResourceMaps<A> {
         pipeline_layouts.clear();
         render_bundles.clear();
         query_sets.clear();
+        blas_s.clear();
+        tlas_s.clear();
         destroyed_buffers.clear();
         destroyed_textures.clear();
     }
@@ -111,6 +119,8 @@ impl<A: HalApi> ResourceMaps<A> {
             pipeline_layouts,
             render_bundles,
             query_sets,
+            blas_s,
+            tlas_s,
             destroyed_buffers,
             destroyed_textures,
         } = self;
@@ -126,6 +136,8 @@ impl<A: HalApi> ResourceMaps<A> {
         pipeline_layouts.extend(other.pipeline_layouts.drain());
         render_bundles.extend(other.render_bundles.drain());
         query_sets.extend(other.query_sets.drain());
+        blas_s.extend(other.blas_s.drain());
+        tlas_s.extend(other.tlas_s.drain());
         destroyed_buffers.extend(other.destroyed_buffers.drain());
         destroyed_textures.extend(other.destroyed_textures.drain());
     }
@@ -135,10 +147,14 @@ impl<A: HalApi> ResourceMaps<A> {
 struct ActiveSubmission<A: HalApi> {
     /// The index of the submission we track.
     ///
-    /// When `Device::fence`'s value is greater than or equal to this, our queue
-    /// submission has completed.
+    /// When the value of the fence of its queue is greater than or equal to
+    /// this, our queue submission has completed.
     index: SubmissionIndex,
 
+    /// The queue the submission was made to, 0 being the main queue of the
+    /// device and the rest its additional queues.
+    queue_index: usize,
+
     /// Resources to be freed once this queue submission has completed.
     ///
     /// When the device is polled, for completed submissions,
@@ -269,6 +285,7 @@ impl<A: HalApi> LifetimeTracker<A> {
     pub fn track_submission(
         &mut self,
         index: SubmissionIndex,
+        queue_index: usize,
         temp_resources: impl Iterator<Item = TempResource<A>>,
         encoders: Vec<EncoderInFlight<A>>,
     ) {
@@ -296,11 +313,17 @@ impl<A: HalApi> LifetimeTracker<A> {
                         .destroyed_textures
                         .insert(destroyed.id, destroyed);
                 }
+                TempResource::BindGroup(bind_group) => {
+                    last_resources
+                        .bind_groups
+                        .insert(bind_group.as_info().id(), bind_group);
+                }
             }
         }
 
         self.active.push(ActiveSubmission {
             index,
+            queue_index,
             last_resources,
             mapped: Vec::new(),
             encoders,
@@ -345,6 +368,30 @@ impl<A: HalApi> LifetimeTracker<A> {
     /// [`self.free_resources`]: LifetimeTracker::free_resources
     /// [`SubmittedWorkDoneClosure`]: crate::device::queue::SubmittedWorkDoneClosure
     #[must_use]
+    /// Returns the last submission index that is done along with all the
+    /// submissions before it, given the fence value of every queue.
+    pub fn last_done_index(&self, fence_values: &[hal::FenceValue]) -> SubmissionIndex {
+        let max = fence_values.iter().copied().max().unwrap_or(0);
+        self.active
+            .iter()
+            .find(|a| a.index > fence_values[a.queue_index])
+            .map_or(max, |a| max.min(a.index - 1))
+    }
+
+    /// Returns the last submission still in flight to each of the
+    /// `queue_count` queues, among the ones `filter` accepts.
+    pub fn last_active_submissions(
+        &self,
+        queue_count: usize,
+        filter: impl Fn(SubmissionIndex) -> bool,
+    ) -> Vec<Option<SubmissionIndex>> {
+        let mut last = vec![None; queue_count];
+        for a in self.active.iter().filter(|a| filter(a.index)) {
+            last[a.queue_index] = Some(a.index);
+        }
+        last
+    }
+
     pub fn triage_submissions(
         &mut self,
         last_done: SubmissionIndex,
@@ -400,6 +447,11 @@ impl<A: HalApi> LifetimeTracker<A> {
                 TempResource::DestroyedTexture(destroyed) => {
                     resources.destroyed_textures.insert(destroyed.id, destroyed);
                 }
+                TempResource::BindGroup(bind_group) => {
+                    resources
+                        .bind_groups
+                        .insert(bind_group.as_info().id(), bind_group);
+                }
             }
         }
     }
@@ -514,6 +566,9 @@ impl<A: HalApi> LifetimeTracker<A> {
                     .samplers
                     .insert(v.as_info().id(), v);
             }
+            for v in bind_group.used.acceleration_structures.drain_resources() {
+                self.suspected_resources.tlas_s.insert(v.as_info().id(), v);
+            }
 
             self.suspected_resources
                 .bind_group_layouts
@@ -670,6 +725,30 @@ impl<A: HalApi> LifetimeTracker<A> {
         self
     }
 
+    fn triage_suspected_tlas_s(&mut self, trackers: &Mutex<Tracker<A>>) -> &mut Self {
+        let mut trackers = trackers.lock();
+        let resource_map = &mut self.suspected_resources.tlas_s;
+        Self::triage_resources(
+            resource_map,
+            self.active.as_mut_slice(),
+            &mut trackers.tlas_s,
+            |maps| &mut maps.tlas_s,
+        );
+        self
+    }
+
+    fn triage_suspected_blas_s(&mut self, trackers: &Mutex<Tracker<A>>) -> &mut Self {
+        let mut trackers = trackers.lock();
+        let resource_map = &mut self.suspected_resources.blas_s;
+        Self::triage_resources(
+            resource_map,
+            self.active.as_mut_slice(),
+            &mut trackers.blas_s,
+            |maps| &mut maps.blas_s,
+        );
+        self
+    }
+
     fn triage_suspected_staging_buffers(&mut self) -> &mut Self {
         self.suspected_resources.staging_buffers.clear();
 
@@ -721,6 +800,8 @@ impl<A: HalApi> LifetimeTracker<A> {
         self.triage_suspected_pipeline_layouts();
         self.triage_suspected_bind_group_layouts();
         self.triage_suspected_query_sets(trackers);
+        self.triage_suspected_tlas_s(trackers);
+        self.triage_suspected_blas_s(trackers);
         self.triage_suspected_samplers(trackers);
         self.triage_suspected_staging_buffers();
         self.triage_suspected_texture_views(trackers);
@@ -783,50 +864,73 @@ impl<A: HalApi> LifetimeTracker<A> {
                 *buffer.map_state.lock() = resource::BufferMapState::Idle;
                 log::trace!("Buffer ready to map {:?} is not tracked anymore", buffer_id);
             } else {
-                let mapping = match std::mem::replace(
-                    &mut *buffer.map_state.lock(),
-                    resource::BufferMapState::Idle,
-                ) {
-                    resource::BufferMapState::Waiting(pending_mapping) => pending_mapping,
+                let snatch_guard = buffer.device.snatchable_lock.read();
+                // Each request queued the buffer once, so map the oldest
+                // pending range.
+                let mut map_state = buffer.map_state.lock();
+                let resource::BufferMapState::Mapped(ref mut mappings) = *map_state else {
                     // Mapping cancelled
-                    resource::BufferMapState::Idle => continue,
+                    continue;
+                };
+                let Some(mapping) = mappings.pending.pop_front() else {
                     // Mapping queued at least twice by map -> unmap -> map
-                    // and was already successfully mapped below
-                    active @ resource::BufferMapState::Active { .. } => {
-                        *buffer.map_state.lock() = active;
-                        continue;
-                    }
-                    _ => panic!("No pending mapping."),
+                    // and was already successfully mapped
+                    continue;
                 };
-                let status = if mapping.range.start != mapping.range.end {
-                    log::debug!("Buffer {:?} map state -> Active", buffer_id);
-                    let host = mapping.op.host;
-                    let size = mapping.range.end - mapping.range.start;
-                    match super::map_buffer(raw, &buffer, mapping.range.start, size, host) {
-                        Ok(ptr) => {
-                            *buffer.map_state.lock() = resource::BufferMapState::Active {
-                                ptr,
-                                range: mapping.range.start..mapping.range.start + size,
-                                host,
-                            };
-                            Ok(())
-                        }
-                        Err(e) => {
-                            log::error!("Mapping failed: {e}");
-                            Err(e)
+                log::debug!("Buffer {:?} range {:?} -> Active", buffer_id, mapping.range);
+                let result = match buffer.raw(&snatch_guard) {
+                    Some(raw_buffer) => super::map_buffer_range(
+                        raw,
+                        raw_buffer,
+                        &buffer,
+                        mappings,
+                        mapping.range.clone(),
+                    ),
+                    None => Err(resource::BufferAccessError::Destroyed),
+                };
+                let status = match result {
+                    Ok(()) => Ok(()),
+                    Err(e) => {
+                        log::error!("Mapping failed: {e}");
+                        if mappings.is_empty() {
+                            *map_state = resource::BufferMapState::Idle;
                         }
+                        Err(e)
                     }
-                } else {
-                    *buffer.map_state.lock() = resource::BufferMapState::Active {
-                        ptr: std::ptr::NonNull::dangling(),
-                        range: mapping.range,
-                        host: mapping.op.host,
-                    };
-                    Ok(())
                 };
+                drop(map_state);
+                drop(snatch_guard);
                 pending_callbacks.push((mapping.op, status));
             }
         }
         pending_callbacks
     }
+    /// Fail the mappings of the buffers in `self.ready_to_map` with
+    /// [`DeviceError::Lost`], as a lost device can't map them anymore.
+    ///
+    /// Return a list of mapping notifications to send.
+    #[must_use]
+    pub(crate) fn fail_mapping(&mut self) -> Vec<super::BufferMapPendingClosure> {
+        let mut pending_callbacks: Vec<super::BufferMapPendingClosure> =
+            Vec::with_capacity(self.ready_to_map.len());
+
+        for buffer in self.ready_to_map.drain(..) {
+            let mut map_state = buffer.map_state.lock();
+            let resource::BufferMapState::Mapped(ref mut mappings) = *map_state else {
+                // Mapping cancelled
+                continue;
+            };
+            let Some(mapping) = mappings.pending.pop_front() else {
+                continue;
+            };
+            if mappings.is_empty() {
+                *map_state = resource::BufferMapState::Idle;
+            }
+            pending_callbacks.push((
+                mapping.op,
+                Err(resource::BufferAccessError::Device(DeviceError::Lost)),
+            ));
+        }
+        pending_callbacks
+    }
 }
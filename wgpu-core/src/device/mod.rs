@@ -4,7 +4,7 @@ use crate::{
     hub::Hub,
     id::{self},
     identity::{GlobalIdentityHandlerFactory, Input},
-    resource::{Buffer, BufferAccessResult},
+    resource::{Buffer, BufferAccessResult, BufferMappings},
     resource::{BufferAccessError, BufferMapOperation},
     resource_log, Label, DOWNLEVEL_ERROR_MESSAGE,
 };
@@ -20,13 +20,22 @@ use std::{iter, num::NonZeroU32, ptr};
 
 pub mod any_device;
 pub(crate) mod bgl;
+pub(crate) mod debug_printf;
 pub mod global;
 mod life;
+mod poller;
 pub mod queue;
+mod ray_tracing;
 pub mod resource;
 #[cfg(any(feature = "trace", feature = "replay"))]
 pub mod trace;
-pub use {life::WaitIdleError, resource::Device};
+mod watchdog;
+pub use {
+    debug_printf::{DebugPrintfCallback, DebugPrintfMessages},
+    life::WaitIdleError,
+    resource::Device,
+    watchdog::WatchdogCallback,
+};
 
 pub const SHADER_STAGE_COUNT: usize = hal::MAX_CONCURRENT_SHADER_STAGES;
 // Should be large enough for the largest possible texture row. This
@@ -173,6 +182,7 @@ pub struct UserClosures {
     pub mappings: Vec<BufferMapPendingClosure>,
     pub submissions: SmallVec<[queue::SubmittedWorkDoneClosure; 1]>,
     pub device_lost_invocations: SmallVec<[DeviceLostInvocation; 1]>,
+    pub debug_printf_messages: SmallVec<[DebugPrintfMessages; 1]>,
 }
 
 impl UserClosures {
@@ -181,6 +191,8 @@ impl UserClosures {
         self.submissions.extend(other.submissions);
         self.device_lost_invocations
             .extend(other.device_lost_invocations);
+        self.debug_printf_messages
+            .extend(other.debug_printf_messages);
     }
 
     fn fire(self) {
@@ -194,6 +206,9 @@ impl UserClosures {
                 callback.call(status);
             }
         }
+        for messages in self.debug_printf_messages {
+            messages.fire();
+        }
         for closure in self.submissions {
             closure.call();
         }
@@ -329,12 +344,92 @@ fn map_buffer<A: HalApi>(
             .map_err(DeviceError::from)?
     };
 
+    unsafe {
+        prepare_mapped_range(
+            raw,
+            raw_buffer,
+            buffer,
+            mapping.ptr,
+            mapping.is_coherent,
+            offset..offset + size,
+            kind,
+        )
+    };
+
+    Ok(mapping.ptr)
+}
+
+/// Makes `range` of a buffer mapped by [`Global::buffer_map_async`]
+/// accessible from the host.
+///
+/// The whole buffer is mapped on the hal side for the first range, and the
+/// following ranges reuse that mapping.
+///
+/// [`Global::buffer_map_async`]: crate::global::Global::buffer_map_async
+fn map_buffer_range<A: HalApi>(
+    raw: &A::Device,
+    raw_buffer: &A::Buffer,
+    buffer: &Buffer<A>,
+    mappings: &mut BufferMappings<A>,
+    range: hal::MemoryRange,
+) -> Result<(), BufferAccessError> {
+    if range.start == range.end {
+        mappings.active.push(range);
+        return Ok(());
+    }
+
+    let base = match mappings.ptr {
+        Some(ptr) => ptr,
+        None => {
+            let mapping = unsafe {
+                raw.map_buffer(raw_buffer, 0..buffer.size)
+                    .map_err(DeviceError::from)?
+            };
+            mappings.ptr = Some(mapping.ptr);
+            mappings.is_coherent = mapping.is_coherent;
+            mapping.ptr
+        }
+    };
+
+    unsafe {
+        let ptr = ptr::NonNull::new_unchecked(base.as_ptr().offset(range.start as isize));
+        prepare_mapped_range(
+            raw,
+            raw_buffer,
+            buffer,
+            ptr,
+            mappings.is_coherent,
+            range.clone(),
+            mappings.host,
+        )
+    };
+    mappings.active.push(range);
+
+    Ok(())
+}
+
+/// Synchronizes a freshly mapped `range` of the buffer with the GPU, and zeroes
+/// its uninitialized parts.
+///
+/// `ptr` points to the start of `range`.
+unsafe fn prepare_mapped_range<A: HalApi>(
+    raw: &A::Device,
+    raw_buffer: &A::Buffer,
+    buffer: &Buffer<A>,
+    ptr: ptr::NonNull<u8>,
+    is_coherent: bool,
+    range: hal::MemoryRange,
+    kind: HostMap,
+) {
+    let offset = range.start;
+    let size = range.end - range.start;
+
     *buffer.sync_mapped_writes.lock() = match kind {
-        HostMap::Read if !mapping.is_coherent => unsafe {
-            raw.invalidate_mapped_ranges(raw_buffer, iter::once(offset..offset + size));
+        HostMap::Read if !is_coherent => unsafe {
+            raw.invalidate_mapped_ranges(raw_buffer, iter::once(range.clone()));
             None
         },
-        HostMap::Write if !mapping.is_coherent => Some(offset..offset + size),
+        HostMap::Write if !is_coherent => Some(range.clone()),
         _ => None,
     };
 
@@ -355,15 +450,10 @@ fn map_buffer<A: HalApi>(
     // reasonable way as all data is pushed to GPU anyways.
 
     // No need to flush if it is flushed later anyways.
-    let zero_init_needs_flush_now =
-        mapping.is_coherent && buffer.sync_mapped_writes.lock().is_none();
-    let mapped = unsafe { std::slice::from_raw_parts_mut(mapping.ptr.as_ptr(), size as usize) };
-
-    for uninitialized in buffer
-        .initialization_status
-        .write()
-        .drain(offset..(size + offset))
-    {
+    let zero_init_needs_flush_now = is_coherent && buffer.sync_mapped_writes.lock().is_none();
+    let mapped = unsafe { std::slice::from_raw_parts_mut(ptr.as_ptr(), size as usize) };
+
+    for uninitialized in buffer.initialization_status.write().drain(range) {
         // The mapping's pointer is already offset, however we track the
         // uninitialized range relative to the buffer's start.
         let fill_range =
@@ -374,8 +464,6 @@ fn map_buffer<A: HalApi>(
             unsafe { raw.flush_mapped_ranges(raw_buffer, iter::once(uninitialized)) };
         }
     }
-
-    Ok(mapping.ptr)
 }
 
 pub(crate) struct CommandAllocator<A: HalApi> {
@@ -456,6 +544,10 @@ pub struct MissingFeatures(pub wgt::Features);
 )]
 pub struct MissingDownlevelFlags(pub wgt::DownlevelFlags);
 
+#[derive(Clone, Debug, Error)]
+#[error("Device was created as compute-only and cannot be used for rendering or presentation")]
+pub struct ComputeOnlyDevice;
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
 #[cfg_attr(feature = "replay", derive(serde::Deserialize))]
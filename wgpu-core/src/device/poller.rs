@@ -0,0 +1,161 @@
+/*! A background thread polling a device.
+
+Once enabled with [`Global::device_set_automatic_polling`], or for every device
+of an instance created with [`wgt::InstanceFlags::AUTOMATIC_POLLING`], a thread
+maintains the device while it has work in flight, so that buffer mapping and
+work done callbacks are called without the user polling the device.
+
+The thread sleeps while the device is idle, and is woken up by submissions,
+buffer mappings and work done callbacks.
+
+[`Global::device_set_automatic_polling`]: crate::global::Global::device_set_automatic_polling
+*/
+
+use crate::{device::Device, hal_api::HalApi};
+
+use parking_lot::{Condvar, Mutex};
+
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Interval between two polls while the device has work in flight.
+#[cfg(send_sync)]
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+#[cfg_attr(not(send_sync), allow(dead_code))]
+struct Shared {
+    stopped: AtomicBool,
+    /// Set when there may be new work to poll for.
+    woken: Mutex<bool>,
+    condvar: Condvar,
+}
+
+#[cfg_attr(not(send_sync), allow(dead_code))]
+impl Shared {
+    /// Blocks until [`Poller::wake`] is called, or returns immediately if it was
+    /// called since the last wait.
+    fn wait_for_work(&self) {
+        let mut woken = self.woken.lock();
+        if !*woken {
+            self.condvar.wait(&mut woken);
+        }
+        *woken = false;
+    }
+}
+
+/// Handle to a running poller thread, stopping it when dropped.
+pub(crate) struct Poller {
+    shared: Arc<Shared>,
+}
+
+impl Poller {
+    #[cfg(send_sync)]
+    pub(crate) fn start<A: HalApi>(device: &Arc<Device<A>>) -> io::Result<Self> {
+        let shared = Arc::new(Shared {
+            stopped: AtomicBool::new(false),
+            // Poll once right away, in case work was submitted before starting.
+            woken: Mutex::new(true),
+            condvar: Condvar::new(),
+        });
+        let device = Arc::downgrade(device);
+        let thread_shared = shared.clone();
+        thread::Builder::new()
+            .name("wgpu-core poller".to_string())
+            .spawn(move || run(device, thread_shared))?;
+        Ok(Self { shared })
+    }
+
+    #[cfg(not(send_sync))]
+    pub(crate) fn start<A: HalApi>(_device: &Arc<Device<A>>) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the device cannot be shared with another thread",
+        ))
+    }
+
+    /// Lets the thread know that there is work to poll for.
+    pub(crate) fn wake(&self) {
+        *self.shared.woken.lock() = true;
+        self.shared.condvar.notify_one();
+    }
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        // The thread is not joined, as the device may be dropped from it.
+        self.shared.stopped.store(true, Ordering::Release);
+        self.wake();
+    }
+}
+
+#[cfg(send_sync)]
+fn run<A: HalApi>(device: std::sync::Weak<Device<A>>, shared: Arc<Shared>) {
+    loop {
+        shared.wait_for_work();
+
+        // Poll until the device is idle again.
+        loop {
+            if shared.stopped.load(Ordering::Acquire) {
+                return;
+            }
+
+            let queue_empty = {
+                // Only hold on to the device while polling it, so that it can
+                // be dropped while we sleep.
+                let Some(device) = device.upgrade() else {
+                    return;
+                };
+                let result = {
+                    let fence = device.fence.read();
+                    let Some(fence) = fence.as_ref() else {
+                        return;
+                    };
+                    device.maintain(fence, wgt::Maintain::Poll)
+                };
+                match result {
+                    Ok((closures, queue_empty)) => {
+                        closures.fire();
+                        queue_empty
+                    }
+                    Err(err) => {
+                        log::warn!("Automatic polling of the device failed: {err}");
+                        return;
+                    }
+                }
+            };
+
+            if queue_empty {
+                break;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wake_before_wait() {
+        let shared = Shared {
+            stopped: AtomicBool::new(false),
+            woken: Mutex::new(false),
+            condvar: Condvar::new(),
+        };
+        let poller = Poller {
+            shared: Arc::new(shared),
+        };
+        poller.wake();
+        // Returns right away, as the wake up was not missed.
+        poller.shared.wait_for_work();
+        assert!(!*poller.shared.woken.lock());
+    }
+}
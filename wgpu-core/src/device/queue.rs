@@ -2,12 +2,13 @@
 use crate::device::trace::Action;
 use crate::{
     api_log,
+    binding_model::BindGroup,
     command::{
         extract_texture_selector, validate_linear_texture_data, validate_texture_copy_range,
         ClearError, CommandBuffer, CopySide, ImageCopyTexture, TransferError,
     },
     conv,
-    device::{life::ResourceMaps, DeviceError, WaitIdleError},
+    device::{life::ResourceMaps, DeviceError, MissingFeatures, UserClosures, WaitIdleError},
     get_lowest_common_denom,
     global::Global,
     hal_api::HalApi,
@@ -16,10 +17,10 @@ use crate::{
     identity::{GlobalIdentityHandlerFactory, Input},
     init_tracker::{has_copy_partial_init_tracker_coverage, TextureInitRange},
     resource::{
-        Buffer, BufferAccessError, BufferMapState, DestroyedBuffer, DestroyedTexture, Resource,
-        ResourceInfo, ResourceType, StagingBuffer, Texture, TextureInner,
+        Buffer, BufferAccessError, BufferMapState, DestroyedBuffer, DestroyedTexture,
+        ExternalFence, Resource, ResourceInfo, ResourceType, StagingBuffer, Texture, TextureInner,
     },
-    resource_log, track, FastHashMap, SubmissionIndex,
+    resource_log, track, FastHashMap, FastHashSet, SubmissionIndex,
 };
 
 use hal::{CommandEncoder as _, Device as _, Queue as _};
@@ -37,6 +38,9 @@ use super::Device;
 pub struct Queue<A: HalApi> {
     pub device: Option<Arc<Device<A>>>,
     pub raw: Option<A::Queue>,
+    /// 0 for the main queue of the device, and `1 + i` for the `i`th of its
+    /// [`Device::additional_queue_ids`].
+    pub(crate) index: usize,
     pub info: ResourceInfo<QueueId>,
 }
 
@@ -55,7 +59,12 @@ impl<A: HalApi> Resource<QueueId> for Queue<A> {
 impl<A: HalApi> Drop for Queue<A> {
     fn drop(&mut self) {
         let queue = self.raw.take().unwrap();
-        self.device.as_ref().unwrap().release_queue(queue);
+        let device = self.device.as_ref().unwrap();
+        if self.index == 0 {
+            device.release_queue(queue);
+        } else {
+            device.release_additional_queue(queue);
+        }
     }
 }
 
@@ -129,9 +138,9 @@ pub struct WrappedSubmissionIndex {
     pub index: SubmissionIndex,
 }
 
-/// A texture or buffer to be freed soon.
+/// A texture, buffer or bind group to be freed soon.
 ///
-/// This is just a tagged raw texture or buffer, generally about to be added to
+/// This is just a tagged raw texture, buffer or bind group, generally about to be added to
 /// some other more specific container like:
 ///
 /// - `PendingWrites::temp_resources`: resources used by queue writes and
@@ -149,6 +158,9 @@ pub enum TempResource<A: HalApi> {
     DestroyedBuffer(Arc<DestroyedBuffer<A>>),
     DestroyedTexture(Arc<DestroyedTexture<A>>),
     Texture(Arc<Texture<A>>),
+    /// A bind group created by a render pass for
+    /// [`RenderCommand::SetBindGroupImmediate`](crate::command::RenderCommand::SetBindGroupImmediate).
+    BindGroup(Arc<BindGroup<A>>),
 }
 
 /// A queue execution for a particular command encoder.
@@ -358,6 +370,73 @@ pub enum QueueSubmitError {
     SurfaceUnconfigured,
     #[error("GPU got stuck :(")]
     StuckGpu,
+    #[error("A render pass suspended at the end of a command buffer was not resumed by the next command buffer of the submission")]
+    RenderPassNotResumed,
+    #[error("A render pass resumed at the start of a command buffer was not suspended with the same attachments by the previous command buffer of the submission")]
+    RenderPassNotSuspended,
+}
+
+/// Maps a region of tiles of a texture to consecutive tiles of a tile pool.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct TextureTileBinding {
+    pub mip_level: u32,
+    /// Origin of the region in tiles, where `z` is the first array layer.
+    pub origin: wgt::Origin3d,
+    /// Size of the region in tiles, where `depth_or_array_layers` is the
+    /// number of array layers.
+    pub size: wgt::Extent3d,
+    /// The pool and the index of the first tile of the pool to map the region
+    /// to, row by row and layer by layer. `None` unmaps the region.
+    pub memory: Option<(id::TilePoolId, u32)>,
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum QueueExternalFenceError {
+    #[error(transparent)]
+    Queue(#[from] DeviceError),
+    #[error("External fence {0:?} is invalid")]
+    InvalidFence(id::ExternalFenceId),
+    #[error("External fence {0:?} belongs to another device")]
+    WrongDevice(id::ExternalFenceId),
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum QueueBindTexturePagesError {
+    #[error(transparent)]
+    Queue(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Texture {0:?} is invalid")]
+    InvalidTexture(id::TextureId),
+    #[error("Tile pool {0:?} is invalid")]
+    InvalidTilePool(id::TilePoolId),
+    #[error("Texture {0:?} was not created with TextureUsages::TILED")]
+    NotTiled(id::TextureId),
+    #[error(
+        "Mip level {mip_level} is out of bounds, the texture has {mip_level_count} mip levels"
+    )]
+    MipLevelOutOfBounds {
+        mip_level: u32,
+        mip_level_count: u32,
+    },
+    #[error("Region of {size:?} tiles at {origin:?} is out of the {tiles:?} tiles of mip level {mip_level}")]
+    RegionOutOfBounds {
+        mip_level: u32,
+        origin: wgt::Origin3d,
+        size: wgt::Extent3d,
+        tiles: wgt::Extent3d,
+    },
+    #[error("Tiles {start}..{end} are out of the {tile_count} tiles of tile pool {pool:?}")]
+    PoolRangeOutOfBounds {
+        pool: id::TilePoolId,
+        start: u64,
+        end: u64,
+        tile_count: u32,
+    },
 }
 
 //TODO: move out common parts of write_xxx.
@@ -425,7 +504,93 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             pending_writes,
             &staging_buffer,
             buffer_id,
-            buffer_offset,
+            &[(buffer_offset, data_size)],
+        );
+
+        pending_writes.consume(staging_buffer);
+        result
+    }
+
+    /// Writes several regions of the buffer `buffer_id` through a single staging buffer.
+    ///
+    /// Every region is a pair of an offset into the buffer and the data to write there.
+    pub fn queue_write_buffer_regions<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        buffer_id: id::BufferId,
+        regions: &[(wgt::BufferAddress, &[u8])],
+    ) -> Result<(), QueueWriteError> {
+        profiling::scope!("Queue::write_buffer_regions");
+        api_log!(
+            "Queue::write_buffer_regions {buffer_id:?} {} regions",
+            regions.len()
+        );
+
+        let hub = A::hub(self);
+
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+
+        let device = queue.device.as_ref().unwrap();
+
+        #[cfg(feature = "trace")]
+        if let Some(ref mut trace) = *device.trace.lock() {
+            for &(buffer_offset, data) in regions {
+                let data_path = trace.make_binary("bin", data);
+                trace.add(Action::WriteBuffer {
+                    id: buffer_id,
+                    data: data_path,
+                    range: buffer_offset..buffer_offset + data.len() as wgt::BufferAddress,
+                    queued: true,
+                });
+            }
+        }
+
+        let data_size: wgt::BufferAddress = regions
+            .iter()
+            .map(|&(_, data)| data.len() as wgt::BufferAddress)
+            .sum();
+        if data_size == 0 {
+            log::trace!("Ignoring write_buffer_regions of size 0");
+            return Ok(());
+        }
+
+        // Platform validation requires that the staging buffer always be
+        // freed, even if an error occurs. All paths from here must call
+        // `device.pending_writes.consume`.
+        let (staging_buffer, staging_buffer_ptr) =
+            prepare_staging_buffer(device, data_size, device.instance_flags)?;
+        let mut pending_writes = device.pending_writes.lock();
+        let pending_writes = pending_writes.as_mut().unwrap();
+
+        let stage_fid = hub.staging_buffers.request();
+        let staging_buffer = stage_fid.init(staging_buffer);
+
+        if let Err(flush_error) = unsafe {
+            profiling::scope!("copy");
+            let mut dst = staging_buffer_ptr;
+            for &(_, data) in regions {
+                ptr::copy_nonoverlapping(data.as_ptr(), dst, data.len());
+                dst = dst.add(data.len());
+            }
+            staging_buffer.flush(device.raw())
+        } {
+            pending_writes.consume(staging_buffer);
+            return Err(flush_error.into());
+        }
+
+        let regions = regions
+            .iter()
+            .map(|&(buffer_offset, data)| (buffer_offset, data.len() as wgt::BufferAddress))
+            .collect::<Vec<_>>();
+        let result = self.queue_write_staging_buffer_impl(
+            device,
+            pending_writes,
+            &staging_buffer,
+            buffer_id,
+            &regions,
         );
 
         pending_writes.consume(staging_buffer);
@@ -499,7 +664,58 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             pending_writes,
             &staging_buffer,
             buffer_id,
-            buffer_offset,
+            &[(buffer_offset, staging_buffer.size)],
+        );
+
+        pending_writes.consume(staging_buffer);
+        result
+    }
+
+    /// Like [`Global::queue_write_staging_buffer`], but scatters the staging buffer
+    /// into several regions of the buffer.
+    ///
+    /// Every region is a pair of an offset into the buffer and a size. The data of
+    /// the regions is packed in the staging buffer in the same order.
+    pub fn queue_write_staging_buffer_regions<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        buffer_id: id::BufferId,
+        regions: &[(wgt::BufferAddress, wgt::BufferAddress)],
+        staging_buffer_id: id::StagingBufferId,
+    ) -> Result<(), QueueWriteError> {
+        profiling::scope!("Queue::write_staging_buffer_regions");
+        let hub = A::hub(self);
+
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+
+        let device = queue.device.as_ref().unwrap();
+
+        let staging_buffer = hub.staging_buffers.unregister(staging_buffer_id);
+        if staging_buffer.is_none() {
+            return Err(QueueWriteError::Transfer(TransferError::InvalidBuffer(
+                buffer_id,
+            )));
+        }
+        let staging_buffer = staging_buffer.unwrap();
+        let mut pending_writes = device.pending_writes.lock();
+        let pending_writes = pending_writes.as_mut().unwrap();
+
+        // See `queue_write_staging_buffer` for why all paths must call
+        // `device.pending_writes.consume`.
+        if let Err(flush_error) = unsafe { staging_buffer.flush(device.raw()) } {
+            pending_writes.consume(staging_buffer);
+            return Err(flush_error.into());
+        }
+
+        let result = self.queue_write_staging_buffer_impl(
+            device,
+            pending_writes,
+            &staging_buffer,
+            buffer_id,
+            regions,
         );
 
         pending_writes.consume(staging_buffer);
@@ -557,13 +773,17 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         Ok(())
     }
 
+    /// Copies `staging_buffer` into the buffer `buffer_id`.
+    ///
+    /// Every region is a pair of the offset into the destination buffer and the
+    /// size of the data, which is packed in the staging buffer in the same order.
     fn queue_write_staging_buffer_impl<A: HalApi>(
         &self,
         device: &Device<A>,
         pending_writes: &mut PendingWrites<A>,
         staging_buffer: &StagingBuffer<A>,
         buffer_id: id::BufferId,
-        buffer_offset: u64,
+        regions: &[(wgt::BufferAddress, wgt::BufferAddress)],
     ) -> Result<(), QueueWriteError> {
         let hub = A::hub(self);
 
@@ -588,17 +808,32 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             return Err(DeviceError::WrongDevice.into());
         }
 
-        let src_buffer_size = staging_buffer.size;
-        self.queue_validate_write_buffer_impl(&dst, buffer_id, buffer_offset, src_buffer_size)?;
+        let mut src_offset = 0;
+        let mut copies = Vec::with_capacity(regions.len());
+        for &(buffer_offset, size) in regions {
+            self.queue_validate_write_buffer_impl(&dst, buffer_id, buffer_offset, size)?;
+            if let Some(size) = wgt::BufferSize::new(size) {
+                copies.push(hal::BufferCopy {
+                    src_offset,
+                    dst_offset: buffer_offset,
+                    size,
+                });
+            }
+            src_offset += size;
+        }
+        if src_offset > staging_buffer.size {
+            return Err(TransferError::BufferOverrun {
+                start_offset: 0,
+                end_offset: src_offset,
+                buffer_size: staging_buffer.size,
+                side: CopySide::Source,
+            }
+            .into());
+        }
 
         dst.info
             .use_at(device.active_submission_index.load(Ordering::Relaxed) + 1);
 
-        let region = wgt::BufferSize::new(src_buffer_size).map(|size| hal::BufferCopy {
-            src_offset: 0,
-            dst_offset: buffer_offset,
-            size,
-        });
         let inner_buffer = staging_buffer.raw.lock();
         let barriers = iter::once(hal::BufferBarrier {
             buffer: inner_buffer.as_ref().unwrap(),
@@ -611,7 +846,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             encoder.copy_buffer_to_buffer(
                 inner_buffer.as_ref().unwrap(),
                 dst_raw,
-                region.into_iter(),
+                copies.into_iter(),
             );
         }
         let dst = hub.buffers.get(buffer_id).unwrap();
@@ -620,9 +855,10 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         // Ensure the overwritten bytes are marked as initialized so
         // they don't need to be nulled prior to mapping or binding.
         {
-            dst.initialization_status
-                .write()
-                .drain(buffer_offset..(buffer_offset + src_buffer_size));
+            let mut initialization_status = dst.initialization_status.write();
+            for &(buffer_offset, size) in regions {
+                initialization_status.drain(buffer_offset..(buffer_offset + size));
+            }
         }
 
         Ok(())
@@ -1099,7 +1335,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         profiling::scope!("Queue::submit");
         api_log!("Queue::submit {queue_id:?}");
 
-        let (submit_index, callbacks) = {
+        let (device_id, submit_index, callbacks) = {
             let hub = A::hub(self);
 
             let queue = hub
@@ -1111,18 +1347,41 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
             let mut fence = device.fence.write();
             let fence = fence.as_mut().unwrap();
+
+            // Submitting to a lost device does nothing, but still consumes the
+            // command buffers.
+            if !device.is_valid() {
+                let mut command_buffer_guard = hub.command_buffers.write();
+                for &cmb_id in command_buffer_ids {
+                    let _ = command_buffer_guard.replace_with_error(cmb_id);
+                }
+                return Ok(WrappedSubmissionIndex {
+                    queue_id: device.as_info().id(),
+                    index: device.active_submission_index.load(Ordering::Relaxed),
+                });
+            }
             let submit_index = device
                 .active_submission_index
                 .fetch_add(1, Ordering::Relaxed)
                 + 1;
             let mut active_executions = Vec::new();
+            let mut debug_markers = Vec::new();
+            let mut command_buffer_temp_resources = Vec::new();
 
             let mut used_surface_textures = track::TextureUsageScope::new();
 
+            // The submissions that last used the resources of this one, which
+            // it has to wait for if they were made to another queue.
+            let track_dependencies = !device.additional_fences.read().is_empty();
+            let mut dependencies = FastHashSet::default();
+
             let snatch_guard = device.snatchable_lock.read();
 
             let mut submit_surface_textures_owned = SmallVec::<[_; 2]>::new();
 
+            // The render pass suspended by the previous command buffer.
+            let mut suspended_render_pass = None;
+
             {
                 let mut command_buffer_guard = hub.command_buffers.write();
 
@@ -1150,7 +1409,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             Err(_) => continue,
                         };
 
-                        if cmdbuf.device.as_info().id() != queue_id {
+                        if cmdbuf.device.as_info().id() != device.as_info().id() {
                             return Err(DeviceError::WrongDevice.into());
                         }
 
@@ -1179,11 +1438,25 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             continue;
                         }
 
+                        {
+                            let cmd_buf_data = cmdbuf.data.lock();
+                            let cmd_buf_data = cmd_buf_data.as_ref().unwrap();
+                            if cmd_buf_data.resumed_render_pass != suspended_render_pass {
+                                return Err(match cmd_buf_data.resumed_render_pass {
+                                    Some(_) => QueueSubmitError::RenderPassNotSuspended,
+                                    None => QueueSubmitError::RenderPassNotResumed,
+                                });
+                            }
+                            suspended_render_pass = cmd_buf_data.suspended_render_pass.clone();
+                        }
+
                         // optimize the tracked states
                         // cmdbuf.trackers.optimize();
                         {
                             let cmd_buf_data = cmdbuf.data.lock();
                             let cmd_buf_trackers = &cmd_buf_data.as_ref().unwrap().trackers;
+                            let mappable_buffer_uses =
+                                &cmd_buf_data.as_ref().unwrap().mappable_buffer_uses;
 
                             // update submission IDs
                             for buffer in cmd_buf_trackers.buffers.used_resources() {
@@ -1194,13 +1467,19 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                         return Err(QueueSubmitError::DestroyedBuffer(id));
                                     }
                                 };
+                                if track_dependencies {
+                                    dependencies.insert(buffer.info.submission_index());
+                                }
                                 buffer.info.use_at(submit_index);
                                 if buffer.is_unique() {
-                                    if let BufferMapState::Active { .. } = *buffer.map_state.lock()
+                                    if let BufferMapState::Mapped(ref mappings) =
+                                        *buffer.map_state.lock()
                                     {
-                                        log::warn!("Dropped buffer has a pending mapping.");
-                                        unsafe { device.raw().unmap_buffer(raw_buf) }
-                                            .map_err(DeviceError::from)?;
+                                        if mappings.ptr.is_some() {
+                                            log::warn!("Dropped buffer has a pending mapping.");
+                                            unsafe { device.raw().unmap_buffer(raw_buf) }
+                                                .map_err(DeviceError::from)?;
+                                        }
                                     }
                                     temp_suspected
                                         .as_mut()
@@ -1210,17 +1489,26 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                 } else {
                                     match *buffer.map_state.lock() {
                                         BufferMapState::Idle => (),
+                                        // Copies may use the ranges of the buffer
+                                        // that aren't mapped.
+                                        BufferMapState::Mapped(ref mappings)
+                                            if buffer.is_copy_only()
+                                                && !mappable_buffer_uses.iter().any(
+                                                    |&(use_id, ref range)| {
+                                                        use_id == id && mappings.overlaps(range)
+                                                    },
+                                                ) => {}
                                         _ => return Err(QueueSubmitError::BufferStillMapped(id)),
                                     }
                                 }
                             }
                             for texture in cmd_buf_trackers.textures.used_resources() {
                                 let id = texture.info.id();
-                                let should_extend = match texture.inner.get(&snatch_guard) {
+                                let resting_uses = match texture.inner.get(&snatch_guard) {
                                     None => {
                                         return Err(QueueSubmitError::DestroyedTexture(id));
                                     }
-                                    Some(TextureInner::Native { .. }) => false,
+                                    Some(TextureInner::Native { .. }) => texture.resting_uses,
                                     Some(TextureInner::Surface {
                                         ref has_work,
                                         ref raw,
@@ -1232,9 +1520,12 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                             submit_surface_textures_owned.push(texture.clone());
                                         }
 
-                                        true
+                                        Some(hal::TextureUses::PRESENT)
                                     }
                                 };
+                                if track_dependencies {
+                                    dependencies.insert(texture.info.submission_index());
+                                }
                                 texture.info.use_at(submit_index);
                                 if texture.is_unique() {
                                     temp_suspected
@@ -1243,10 +1534,10 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                         .textures
                                         .insert(id, texture.clone());
                                 }
-                                if should_extend {
+                                if let Some(uses) = resting_uses {
                                     unsafe {
                                         used_surface_textures
-                                            .merge_single(&texture, None, hal::TextureUses::PRESENT)
+                                            .merge_single(&texture, None, uses)
                                             .unwrap();
                                     };
                                 }
@@ -1273,6 +1564,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                     for sampler in bg.used.samplers.used_resources() {
                                         sampler.info.use_at(submit_index);
                                     }
+                                    for tlas in bg.used.acceleration_structures.used_resources() {
+                                        tlas.info.use_at(submit_index);
+                                    }
                                     if bg.is_unique() {
                                         temp_suspected
                                             .as_mut()
@@ -1306,6 +1600,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                 }
                             }
                             for query_set in cmd_buf_trackers.query_sets.used_resources() {
+                                if track_dependencies {
+                                    dependencies.insert(query_set.info.submission_index());
+                                }
                                 query_set.info.use_at(submit_index);
                                 if query_set.is_unique() {
                                     temp_suspected
@@ -1315,6 +1612,32 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                         .insert(query_set.as_info().id(), query_set.clone());
                                 }
                             }
+                            for blas in cmd_buf_trackers.blas_s.used_resources() {
+                                if track_dependencies {
+                                    dependencies.insert(blas.info.submission_index());
+                                }
+                                blas.info.use_at(submit_index);
+                                if blas.is_unique() {
+                                    temp_suspected
+                                        .as_mut()
+                                        .unwrap()
+                                        .blas_s
+                                        .insert(blas.as_info().id(), blas.clone());
+                                }
+                            }
+                            for tlas in cmd_buf_trackers.tlas_s.used_resources() {
+                                if track_dependencies {
+                                    dependencies.insert(tlas.info.submission_index());
+                                }
+                                tlas.info.use_at(submit_index);
+                                if tlas.is_unique() {
+                                    temp_suspected
+                                        .as_mut()
+                                        .unwrap()
+                                        .tlas_s
+                                        .insert(tlas.as_info().id(), tlas.clone());
+                                }
+                            }
                             for bundle in cmd_buf_trackers.bundles.used_resources() {
                                 bundle.info.use_at(submit_index);
                                 // We need to update the submission indices for the contained
@@ -1338,6 +1661,8 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             }
                         }
                         let mut baked = cmdbuf.from_arc_into_baked();
+                        debug_markers.append(&mut baked.debug_markers);
+                        command_buffer_temp_resources.append(&mut baked.temp_resources);
                         // execute resource transitions
                         unsafe {
                             baked
@@ -1370,7 +1695,8 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         let transit = unsafe { baked.encoder.end_encoding().unwrap() };
                         baked.list.insert(0, transit);
 
-                        // Transition surface textures into `Present` state.
+                        // Transition surface textures into `Present` state, and the
+                        // textures wrapping images of other APIs back into theirs.
                         // Note: we could technically do it after all of the command buffers,
                         // but here we have a command encoder by hand, so it's easier to use it.
                         if !used_surface_textures.is_empty() {
@@ -1406,11 +1732,37 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             cmd_buffers: baked.list,
                         });
                     }
+                    if suspended_render_pass.is_some() {
+                        return Err(QueueSubmitError::RenderPassNotResumed);
+                    }
 
                     log::trace!("Device after submission {}", submit_index);
                 }
             }
 
+            // Copy out the debug printf messages after the user's commands ran.
+            if let Some(ref debug_printf) = device.debug_printf {
+                if !active_executions.is_empty() {
+                    let mut encoder = device
+                        .command_allocator
+                        .lock()
+                        .as_mut()
+                        .unwrap()
+                        .acquire_encoder(device.raw(), queue.raw.as_ref().unwrap())
+                        .map_err(DeviceError::from)?;
+                    let cmd_buffer = debug_printf.encode_readback(
+                        device.raw(),
+                        &mut encoder,
+                        submit_index,
+                        device.instance_flags,
+                    )?;
+                    active_executions.push(EncoderInFlight {
+                        raw: encoder,
+                        cmd_buffers: vec![cmd_buffer],
+                    });
+                }
+            }
+
             let mut pending_writes = device.pending_writes.lock();
             let pending_writes = pending_writes.as_mut().unwrap();
 
@@ -1421,7 +1773,15 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         None => {
                             return Err(QueueSubmitError::DestroyedTexture(id));
                         }
-                        Some(TextureInner::Native { .. }) => {}
+                        Some(TextureInner::Native { .. }) => {
+                            if let Some(uses) = texture.resting_uses {
+                                unsafe {
+                                    used_surface_textures
+                                        .merge_single(texture, None, uses)
+                                        .unwrap()
+                                };
+                            }
+                        }
                         Some(TextureInner::Surface {
                             ref has_work,
                             ref raw,
@@ -1462,6 +1822,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 }
             }
 
+            let has_pending_writes = pending_writes.is_active;
             let refs = pending_writes
                 .pre_submit()?
                 .into_iter()
@@ -1482,13 +1843,42 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 });
             }
 
-            unsafe {
-                queue
-                    .raw
-                    .as_ref()
-                    .unwrap()
-                    .submit(&refs, &submit_surface_textures, Some((fence, submit_index)))
-                    .map_err(DeviceError::from)?;
+            {
+                let raw_queue = queue.raw.as_ref().unwrap();
+                let mut additional_fences = device.additional_fences.write();
+                if track_dependencies {
+                    // The pending writes may use any resource.
+                    let last = device
+                        .lock_life()
+                        .last_active_submissions(1 + additional_fences.len(), |index| {
+                            has_pending_writes || dependencies.contains(&index)
+                        });
+                    let fences = iter::once(&*fence).chain(additional_fences.iter());
+                    for (queue_index, (fence, index)) in fences.zip(last).enumerate() {
+                        match index {
+                            Some(index) if queue_index != queue.index => unsafe {
+                                device
+                                    .check_submit_result(raw_queue.wait_for_fence(fence, index))?
+                            },
+                            _ => {}
+                        }
+                    }
+                }
+                let signal_fence = match queue.index {
+                    0 => &mut *fence,
+                    index => &mut additional_fences[index - 1],
+                };
+                device.check_submit_result(unsafe {
+                    raw_queue.submit(
+                        &refs,
+                        &submit_surface_textures,
+                        Some((signal_fence, submit_index)),
+                    )
+                })?;
+            }
+
+            if let Some(ref watchdog) = *device.watchdog.lock() {
+                watchdog.track_submission(submit_index, debug_markers);
             }
 
             profiling::scope!("cleanup");
@@ -1504,17 +1894,28 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             let mut pending_write_resources = mem::take(&mut pending_writes.temp_resources);
             device.lock_life().track_submission(
                 submit_index,
-                pending_write_resources.drain(..),
+                queue.index,
+                pending_write_resources
+                    .drain(..)
+                    .chain(command_buffer_temp_resources),
                 active_executions,
             );
+            device.wake_poller();
 
             // This will schedule destruction of all resources that are no longer needed
             // by the user but used in the command stream, among other things.
-            let (closures, _) = match device.maintain(fence, wgt::Maintain::Poll) {
-                Ok(closures) => closures,
-                Err(WaitIdleError::Device(err)) => return Err(QueueSubmitError::Queue(err)),
-                Err(WaitIdleError::StuckGpu) => return Err(QueueSubmitError::StuckGpu),
-                Err(WaitIdleError::WrongSubmissionIndex(..)) => unreachable!(),
+            //
+            // A device lost by this submission is lost by the next poll instead,
+            // as releasing its resources needs the locks we are holding.
+            let (closures, _) = if !device.is_valid() {
+                (UserClosures::default(), true)
+            } else {
+                match device.maintain(fence, wgt::Maintain::Poll) {
+                    Ok(closures) => closures,
+                    Err(WaitIdleError::Device(err)) => return Err(QueueSubmitError::Queue(err)),
+                    Err(WaitIdleError::StuckGpu) => return Err(QueueSubmitError::StuckGpu),
+                    Err(WaitIdleError::WrongSubmissionIndex(..)) => unreachable!(),
+                }
             };
 
             // pending_write_resources has been drained, so it's empty, but we
@@ -1522,18 +1923,137 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             pending_writes.temp_resources = pending_write_resources;
             device.lock_life().post_submit();
 
-            (submit_index, closures)
+            (device.as_info().id(), submit_index, closures)
         };
 
         // the closures should execute with nothing locked!
         callbacks.fire();
 
+        // Submission indices are shared by all the queues of the device, and
+        // identified by its main queue.
         Ok(WrappedSubmissionIndex {
-            queue_id,
+            queue_id: device_id,
             index: submit_index,
         })
     }
 
+    /// Makes the work submitted to `queue_id` afterwards wait, on the GPU,
+    /// until the submission `submission_index` is done.
+    ///
+    /// `submission_index` may come from any queue of the same device.
+    pub fn queue_wait_for_submission<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        submission_index: WrappedSubmissionIndex,
+    ) -> Result<(), WaitIdleError> {
+        api_log!("Queue::wait_for_submission {queue_id:?} {submission_index:?}");
+
+        let hub = A::hub(self);
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+        let device = queue.device.as_ref().unwrap();
+        let device_id = device.as_info().id();
+        if submission_index.queue_id != device_id {
+            return Err(WaitIdleError::WrongSubmissionIndex(
+                submission_index.queue_id,
+                device_id,
+            ));
+        }
+
+        let fence = device.fence.read();
+        let fence = fence.as_ref().unwrap();
+        let additional_fences = device.additional_fences.read();
+        // Nothing to wait for if the submission is done.
+        let last = device
+            .lock_life()
+            .last_active_submissions(1 + additional_fences.len(), |index| {
+                index == submission_index.index
+            });
+        let fences = iter::once(fence).chain(additional_fences.iter());
+        for (queue_index, (fence, index)) in fences.zip(last).enumerate() {
+            match index {
+                Some(index) if queue_index != queue.index => unsafe {
+                    queue
+                        .raw
+                        .as_ref()
+                        .unwrap()
+                        .wait_for_fence(fence, index)
+                        .map_err(DeviceError::from)?
+                },
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn queue_external_fence<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        external_fence_id: id::ExternalFenceId,
+    ) -> Result<(Arc<Queue<A>>, Arc<ExternalFence<A>>), QueueExternalFenceError> {
+        let hub = A::hub(self);
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+        let external_fence = hub
+            .external_fences
+            .get(external_fence_id)
+            .map_err(|_| QueueExternalFenceError::InvalidFence(external_fence_id))?;
+        if !Arc::ptr_eq(queue.device.as_ref().unwrap(), &external_fence.device) {
+            return Err(QueueExternalFenceError::WrongDevice(external_fence_id));
+        }
+        Ok((queue, external_fence))
+    }
+
+    /// Makes the work submitted to the queue after this call wait until
+    /// `external_fence` reaches `value`.
+    pub fn queue_wait_for_external_fence<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        external_fence_id: id::ExternalFenceId,
+        value: u64,
+    ) -> Result<(), QueueExternalFenceError> {
+        api_log!("Queue::wait_for_external_fence {external_fence_id:?} {value}");
+
+        let (queue, external_fence) =
+            self.queue_external_fence::<A>(queue_id, external_fence_id)?;
+        unsafe {
+            queue
+                .raw
+                .as_ref()
+                .unwrap()
+                .wait_for_fence(external_fence.raw(), value)
+                .map_err(DeviceError::from)?
+        };
+        Ok(())
+    }
+
+    /// Sets `external_fence` to `value` once all of the work submitted to the
+    /// queue so far is done.
+    pub fn queue_signal_external_fence<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        external_fence_id: id::ExternalFenceId,
+        value: u64,
+    ) -> Result<(), QueueExternalFenceError> {
+        api_log!("Queue::signal_external_fence {external_fence_id:?} {value}");
+
+        let (queue, external_fence) =
+            self.queue_external_fence::<A>(queue_id, external_fence_id)?;
+        unsafe {
+            queue
+                .raw
+                .as_ref()
+                .unwrap()
+                .signal_fence(external_fence.raw(), value)
+                .map_err(DeviceError::from)?
+        };
+        Ok(())
+    }
+
     pub fn queue_get_timestamp_period<A: HalApi>(
         &self,
         queue_id: QueueId,
@@ -1545,6 +2065,245 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         }
     }
 
+    pub fn queue_get_timestamp_calibration<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+    ) -> Result<Option<wgt::TimestampCalibration>, InvalidQueue> {
+        let hub = A::hub(self);
+        match hub.queues.get(queue_id) {
+            Ok(queue) => Ok(unsafe { queue.raw.as_ref().unwrap().get_timestamp_calibration() }),
+            Err(_) => Err(InvalidQueue),
+        }
+    }
+
+    /// Maps or unmaps tiles of a [`wgt::TextureUsages::TILED`] texture.
+    ///
+    /// The bindings take effect on the queue right away, after the work
+    /// submitted so far. Tiles of pools that are bound for the first time are
+    /// zeroed with the pending writes of the next submission.
+    pub fn queue_bind_texture_pages<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        texture_id: id::TextureId,
+        bindings: &[TextureTileBinding],
+    ) -> Result<(), QueueBindTexturePagesError> {
+        profiling::scope!("Queue::bind_texture_pages");
+        api_log!("Queue::bind_texture_pages {texture_id:?}");
+
+        let hub = A::hub(self);
+
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+
+        let device = queue.device.as_ref().unwrap();
+
+        #[cfg(feature = "trace")]
+        if let Some(ref mut trace) = *device.trace.lock() {
+            trace.add(Action::BindTexturePages {
+                texture: texture_id,
+                bindings: bindings.to_vec(),
+            });
+        }
+
+        device.require_features(wgt::Features::TILED_TEXTURE)?;
+
+        let texture = hub
+            .textures
+            .get(texture_id)
+            .map_err(|_| QueueBindTexturePagesError::InvalidTexture(texture_id))?;
+
+        if texture.device.as_info().id() != queue_id {
+            return Err(DeviceError::WrongDevice.into());
+        }
+
+        if !texture.desc.usage.contains(wgt::TextureUsages::TILED) {
+            return Err(QueueBindTexturePagesError::NotTiled(texture_id));
+        }
+
+        let (tile_width, tile_height) = texture.desc.format.tile_dimensions().unwrap();
+
+        let mut pools = Vec::with_capacity(bindings.len());
+        for binding in bindings {
+            let mip_size = texture.desc.mip_level_size(binding.mip_level).ok_or(
+                QueueBindTexturePagesError::MipLevelOutOfBounds {
+                    mip_level: binding.mip_level,
+                    mip_level_count: texture.desc.mip_level_count,
+                },
+            )?;
+            let tiles = wgt::Extent3d {
+                width: mip_size.width / tile_width,
+                height: mip_size.height / tile_height,
+                depth_or_array_layers: mip_size.depth_or_array_layers,
+            };
+            let (origin, size) = (binding.origin, binding.size);
+            if origin.x as u64 + size.width as u64 > tiles.width as u64
+                || origin.y as u64 + size.height as u64 > tiles.height as u64
+                || origin.z as u64 + size.depth_or_array_layers as u64
+                    > tiles.depth_or_array_layers as u64
+            {
+                return Err(QueueBindTexturePagesError::RegionOutOfBounds {
+                    mip_level: binding.mip_level,
+                    origin,
+                    size,
+                    tiles,
+                });
+            }
+
+            let pool = match binding.memory {
+                Some((pool_id, first_tile)) => {
+                    let pool = hub
+                        .tile_pools
+                        .get(pool_id)
+                        .map_err(|_| QueueBindTexturePagesError::InvalidTilePool(pool_id))?;
+                    if pool.device.as_info().id() != queue_id {
+                        return Err(DeviceError::WrongDevice.into());
+                    }
+
+                    let start = first_tile as u64;
+                    let end = start
+                        + size.width as u64
+                            * size.height as u64
+                            * size.depth_or_array_layers as u64;
+                    if end > pool.tile_count as u64 {
+                        return Err(QueueBindTexturePagesError::PoolRangeOutOfBounds {
+                            pool: pool_id,
+                            start,
+                            end,
+                            tile_count: pool.tile_count,
+                        });
+                    }
+                    Some((pool, first_tile))
+                }
+                None => None,
+            };
+            pools.push(pool);
+        }
+
+        let mut pending_writes = device.pending_writes.lock();
+        let pending_writes = pending_writes.as_mut().unwrap();
+
+        let snatch_guard = device.snatchable_lock.read();
+        let texture_raw = texture
+            .raw(&snatch_guard)
+            .ok_or(QueueBindTexturePagesError::InvalidTexture(texture_id))?;
+
+        let hal_bindings = bindings
+            .iter()
+            .zip(pools.iter())
+            .map(|(binding, pool)| hal::TextureTileBinding {
+                mip_level: binding.mip_level,
+                origin: binding.origin,
+                size: binding.size,
+                memory: pool
+                    .as_ref()
+                    .map(|&(ref pool, first_tile)| (pool.raw(), first_tile)),
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            queue
+                .raw
+                .as_ref()
+                .unwrap()
+                .bind_texture_tiles(texture_raw, &hal_bindings)
+        }
+        .map_err(DeviceError::from)?;
+
+        // Submitted work may still access the previously bound tiles.
+        {
+            let mut tile_pools = texture.tile_pools.lock();
+            for (pool, _) in pools.iter().flatten() {
+                if !tile_pools.iter().any(|bound| Arc::ptr_eq(bound, pool)) {
+                    tile_pools.push(pool.clone());
+                }
+            }
+        }
+
+        // Pool memory starts out uninitialized, so tiles are zeroed the first
+        // time they're bound, with one copy from the zero buffer per tile.
+        let (block_width, block_height) = texture.desc.format.block_dimensions();
+        let block_size = texture.desc.format.block_copy_size(None).unwrap();
+        let buffer_layout = wgt::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(tile_width / block_width * block_size),
+            rows_per_image: Some(tile_height / block_height),
+        };
+        let mut regions = Vec::new();
+        for (binding, pool) in bindings.iter().zip(pools.iter()) {
+            let Some((pool, first_tile)) = pool else {
+                continue;
+            };
+            let mut initialized_tiles = pool.initialized_tiles.lock();
+            let mut pool_tile = *first_tile as usize;
+            let (origin, size) = (binding.origin, binding.size);
+            for array_layer in origin.z..origin.z + size.depth_or_array_layers {
+                for y in origin.y..origin.y + size.height {
+                    for x in origin.x..origin.x + size.width {
+                        if !initialized_tiles[pool_tile] {
+                            initialized_tiles.set(pool_tile, true);
+                            regions.push(hal::BufferTextureCopy {
+                                buffer_layout,
+                                texture_base: hal::TextureCopyBase {
+                                    mip_level: binding.mip_level,
+                                    array_layer,
+                                    origin: wgt::Origin3d {
+                                        x: x * tile_width,
+                                        y: y * tile_height,
+                                        z: 0,
+                                    },
+                                    aspect: hal::FormatAspects::COLOR,
+                                },
+                                size: hal::CopyExtent {
+                                    width: tile_width,
+                                    height: tile_height,
+                                    depth: 1,
+                                },
+                            });
+                        }
+                        pool_tile += 1;
+                    }
+                }
+            }
+        }
+        if regions.is_empty() {
+            return Ok(());
+        }
+
+        let encoder = pending_writes.activate();
+
+        texture
+            .info
+            .use_at(device.active_submission_index.load(Ordering::Relaxed) + 1);
+
+        {
+            let mut trackers = device.trackers.lock();
+            let transition = trackers
+                .textures
+                .set_single(
+                    &texture,
+                    texture.full_range.clone(),
+                    hal::TextureUses::COPY_DST,
+                )
+                .ok_or(QueueBindTexturePagesError::InvalidTexture(texture_id))?;
+            unsafe {
+                encoder
+                    .transition_textures(transition.map(|pending| pending.into_hal(texture_raw)));
+                encoder.copy_buffer_to_texture(
+                    device.zero_buffer.as_ref().unwrap(),
+                    texture_raw,
+                    regions.into_iter(),
+                );
+            }
+        }
+
+        pending_writes
+            .dst_textures
+            .insert(texture_id, texture.clone());
+
+        Ok(())
+    }
+
     pub fn queue_on_submitted_work_done<A: HalApi>(
         &self,
         queue_id: QueueId,
@@ -1555,12 +2314,11 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         //TODO: flush pending writes
         let hub = A::hub(self);
         match hub.queues.get(queue_id) {
-            Ok(queue) => queue
-                .device
-                .as_ref()
-                .unwrap()
-                .lock_life()
-                .add_work_done_closure(closure),
+            Ok(queue) => {
+                let device = queue.device.as_ref().unwrap();
+                device.lock_life().add_work_done_closure(closure);
+                device.wake_poller();
+            }
             Err(_) => return Err(InvalidQueue),
         }
         Ok(())
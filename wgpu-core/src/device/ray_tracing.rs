@@ -0,0 +1,275 @@
+use std::sync::Arc;
+
+#[cfg(feature = "trace")]
+use crate::device::trace;
+use crate::{
+    api_log,
+    device::{Device, DeviceError},
+    global::Global,
+    hal_api::HalApi,
+    id::{self, DeviceId},
+    identity::{GlobalIdentityHandlerFactory, Input},
+    ray_tracing::{self, CreateBlasError, CreateTlasError},
+    resource::{Blas, ResourceInfo, Tlas},
+    LabelHelpers,
+};
+
+use hal::Device as _;
+
+impl<A: HalApi> Device<A> {
+    pub(crate) fn create_blas(
+        self: &Arc<Self>,
+        desc: &ray_tracing::BlasDescriptor,
+        sizes: &wgt::BlasGeometrySizeDescriptors,
+    ) -> Result<Blas<A>, CreateBlasError> {
+        self.require_features(wgt::Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE)?;
+
+        let wgt::BlasGeometrySizeDescriptors::Triangles { ref descriptors } = *sizes;
+        let mut entries = Vec::with_capacity(descriptors.len());
+        for (index, size) in descriptors.iter().enumerate() {
+            if ray_tracing::vertex_component_size(size.vertex_format).is_none() {
+                return Err(CreateBlasError::InvalidVertexFormat(size.vertex_format));
+            }
+            let indices = match (size.index_format, size.index_count) {
+                (Some(format), Some(count)) => {
+                    if count % 3 != 0 {
+                        return Err(CreateBlasError::InvalidIndexCount { index, count });
+                    }
+                    Some(hal::AccelerationStructureTriangleIndices {
+                        format,
+                        buffer: None,
+                        offset: 0,
+                        count,
+                    })
+                }
+                (None, None) => {
+                    if size.vertex_count % 3 != 0 {
+                        return Err(CreateBlasError::InvalidVertexCount {
+                            index,
+                            count: size.vertex_count,
+                        });
+                    }
+                    None
+                }
+                _ => return Err(CreateBlasError::MissingIndexData(index)),
+            };
+            entries.push(hal::AccelerationStructureTriangles::<A> {
+                vertex_buffer: None,
+                vertex_format: size.vertex_format,
+                first_vertex: 0,
+                vertex_count: size.vertex_count,
+                vertex_stride: 0,
+                indices,
+                transform: None,
+                flags: size.flags,
+            });
+        }
+
+        let size_info = unsafe {
+            self.raw().get_acceleration_structure_build_sizes(
+                &hal::GetAccelerationStructureBuildSizesDescriptor {
+                    entries: &hal::AccelerationStructureEntries::Triangles(entries),
+                    flags: desc.flags,
+                },
+            )
+        };
+
+        let raw = unsafe {
+            self.raw()
+                .create_acceleration_structure(&hal::AccelerationStructureDescriptor {
+                    label: desc.label.to_hal(self.instance_flags),
+                    size: size_info.acceleration_structure_size,
+                    format: hal::AccelerationStructureFormat::BottomLevel,
+                })
+        }
+        .map_err(DeviceError::from)?;
+        let handle = unsafe { self.raw().get_acceleration_structure_device_address(&raw) };
+
+        Ok(Blas {
+            raw: Some(raw),
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default()),
+            size_info,
+            sizes: sizes.clone(),
+            flags: desc.flags,
+            handle,
+        })
+    }
+
+    pub(crate) fn create_tlas(
+        self: &Arc<Self>,
+        desc: &ray_tracing::TlasDescriptor,
+    ) -> Result<Tlas<A>, CreateTlasError> {
+        self.require_features(wgt::Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE)?;
+
+        let size_info = unsafe {
+            self.raw().get_acceleration_structure_build_sizes(
+                &hal::GetAccelerationStructureBuildSizesDescriptor {
+                    entries: &hal::AccelerationStructureEntries::Instances(
+                        hal::AccelerationStructureInstances::<A> {
+                            buffer: None,
+                            offset: 0,
+                            count: desc.max_instances,
+                        },
+                    ),
+                    flags: desc.flags,
+                },
+            )
+        };
+
+        let raw = unsafe {
+            self.raw()
+                .create_acceleration_structure(&hal::AccelerationStructureDescriptor {
+                    label: desc.label.to_hal(self.instance_flags),
+                    size: size_info.acceleration_structure_size,
+                    format: hal::AccelerationStructureFormat::TopLevel,
+                })
+        }
+        .map_err(DeviceError::from)?;
+
+        Ok(Tlas {
+            raw: Some(raw),
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default()),
+            size_info,
+            max_instance_count: desc.max_instances,
+            flags: desc.flags,
+        })
+    }
+}
+
+impl<G: GlobalIdentityHandlerFactory> Global<G> {
+    /// Creates a bottom level acceleration structure, which can be built with
+    /// geometries fitting `sizes`.
+    ///
+    /// Also returns the handle that instances of top level acceleration
+    /// structures reference it by, if it could be created.
+    pub fn device_create_blas<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &ray_tracing::BlasDescriptor,
+        sizes: wgt::BlasGeometrySizeDescriptors,
+        id_in: Input<G, id::BlasId>,
+    ) -> (id::BlasId, Option<u64>, Option<CreateBlasError>) {
+        profiling::scope!("Device::create_blas");
+
+        let hub = A::hub(self);
+        let fid = hub.blas_s.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateBlas {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                    sizes: sizes.clone(),
+                });
+            }
+
+            let blas = match device.create_blas(desc, &sizes) {
+                Ok(blas) => blas,
+                Err(err) => break err,
+            };
+            let handle = blas.handle;
+
+            let (id, resource) = fid.assign(blas);
+            api_log!("Device::create_blas -> {id:?}");
+            device.trackers.lock().blas_s.insert_single(id, resource);
+
+            return (id, Some(handle), None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, None, Some(error))
+    }
+
+    pub fn device_create_tlas<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &ray_tracing::TlasDescriptor,
+        id_in: Input<G, id::TlasId>,
+    ) -> (id::TlasId, Option<CreateTlasError>) {
+        profiling::scope!("Device::create_tlas");
+
+        let hub = A::hub(self);
+        let fid = hub.tlas_s.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTlas {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                });
+            }
+
+            let tlas = match device.create_tlas(desc) {
+                Ok(tlas) => tlas,
+                Err(err) => break err,
+            };
+
+            let (id, resource) = fid.assign(tlas);
+            api_log!("Device::create_tlas -> {id:?}");
+            device.trackers.lock().tlas_s.insert_single(id, resource);
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    pub fn blas_drop<A: HalApi>(&self, blas_id: id::BlasId) {
+        profiling::scope!("Blas::drop");
+        api_log!("Blas::drop {blas_id:?}");
+
+        let hub = A::hub(self);
+
+        if let Some(blas) = hub.blas_s.unregister(blas_id) {
+            blas.device
+                .lock_life()
+                .suspected_resources
+                .blas_s
+                .insert(blas_id, blas.clone());
+        }
+    }
+
+    pub fn tlas_drop<A: HalApi>(&self, tlas_id: id::TlasId) {
+        profiling::scope!("Tlas::drop");
+        api_log!("Tlas::drop {tlas_id:?}");
+
+        let hub = A::hub(self);
+
+        if let Some(tlas) = hub.tlas_s.unregister(tlas_id) {
+            tlas.device
+                .lock_life()
+                .suspected_resources
+                .tlas_s
+                .insert(tlas_id, tlas.clone());
+        }
+    }
+
+    pub fn blas_label<A: HalApi>(&self, id: id::BlasId) -> String {
+        A::hub(self).blas_s.label_for_resource(id)
+    }
+
+    pub fn tlas_label<A: HalApi>(&self, id: id::TlasId) -> String {
+        A::hub(self).tlas_s.label_for_resource(id)
+    }
+}
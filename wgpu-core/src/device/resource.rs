@@ -1,12 +1,15 @@
 #[cfg(feature = "trace")]
 use crate::device::trace;
 use crate::{
-    binding_model::{self, BindGroupLayout, BindGroupLayoutEntryError},
+    binding_model::{
+        self, BindGroup, BindGroupCacheKey, BindGroupLayout, BindGroupLayoutEntryError,
+    },
     command, conv,
     device::life::{LifetimeTracker, WaitIdleError},
     device::queue::PendingWrites,
     device::{
-        bgl, AttachmentData, CommandAllocator, DeviceLostInvocation, MissingDownlevelFlags,
+        bgl, debug_printf::DebugPrintf, poller::Poller, watchdog::Watchdog, AttachmentData,
+        CommandAllocator, ComputeOnlyDevice, DeviceLostInvocation, MissingDownlevelFlags,
         MissingFeatures, RenderPassContext, CLEANUP_WAIT_MS,
     },
     hal_api::HalApi,
@@ -23,8 +26,8 @@ use crate::{
     registry::Registry,
     resource::ResourceInfo,
     resource::{
-        self, Buffer, QuerySet, Resource, ResourceType, Sampler, Texture, TextureView,
-        TextureViewNotRenderableReason,
+        self, Buffer, ExternalFence, MemoryHeap, QuerySet, Resource, ResourceType, Sampler,
+        Texture, TextureView, TextureViewNotRenderableReason, TilePool,
     },
     resource_log,
     snatch::{SnatchGuard, SnatchLock, Snatchable},
@@ -89,13 +92,38 @@ pub struct Device<A: HalApi> {
     pub(crate) adapter: Arc<Adapter<A>>,
     pub(crate) queue_id: RwLock<Option<QueueId>>,
     queue_to_drop: RwLock<Option<A::Queue>>,
+    /// The queues requested with [`wgt::DeviceDescriptor::additional_queues`],
+    /// in order.
+    pub(crate) additional_queue_ids: RwLock<Vec<QueueId>>,
+    additional_queues_to_drop: Mutex<Vec<A::Queue>>,
     pub(crate) zero_buffer: Option<A::Buffer>,
+    /// Capture buffer for shader debug printf, if
+    /// [`wgt::Features::SHADER_DEBUG_PRINTF`] is enabled.
+    pub(crate) debug_printf: Option<DebugPrintf<A>>,
+    /// Timer of submissions, see [`Global::device_set_watchdog`].
+    ///
+    /// [`Global::device_set_watchdog`]: crate::global::Global::device_set_watchdog
+    pub(crate) watchdog: Mutex<Option<Watchdog>>,
+    /// Thread polling the device, see [`Global::device_set_automatic_polling`].
+    ///
+    /// [`Global::device_set_automatic_polling`]: crate::global::Global::device_set_automatic_polling
+    pub(crate) poller: Mutex<Option<Poller>>,
+    /// Whether new command encoders write a listing of their commands, see
+    /// [`Global::device_set_command_disassembly`].
+    ///
+    /// [`Global::device_set_command_disassembly`]: crate::global::Global::device_set_command_disassembly
+    pub(crate) command_disassembly: AtomicBool,
     pub(crate) info: ResourceInfo<DeviceId>,
 
     pub(crate) command_allocator: Mutex<Option<CommandAllocator<A>>>,
     //Note: The submission index here corresponds to the last submission that is done.
     pub(crate) active_submission_index: AtomicU64, //SubmissionIndex,
     pub(crate) fence: RwLock<Option<A::Fence>>,
+    /// The fences signaled by the submissions to the additional queues, in
+    /// the order of [`Device::additional_queue_ids`].
+    ///
+    /// Locked after `fence`.
+    pub(crate) additional_fences: RwLock<Vec<A::Fence>>,
     pub(crate) snatchable_lock: SnatchLock,
 
     /// Is this device valid? Valid is closely associated with "lose the device",
@@ -111,6 +139,11 @@ pub struct Device<A: HalApi> {
     /// using ref-counted references for internal access.
     pub(crate) valid: AtomicBool,
 
+    /// Whether the driver reported the device as lost. Unlike a destroyed
+    /// device, the work in flight will never complete, so the device is lost
+    /// by the next [`Device::maintain`] without waiting for its queue.
+    lost_by_driver: AtomicBool,
+
     /// All live resources allocated with this [`Device`].
     ///
     /// Has to be locked temporarily only (locked last)
@@ -123,10 +156,15 @@ pub struct Device<A: HalApi> {
     pub(crate) temp_suspected: Mutex<Option<ResourceMaps<A>>>,
     /// Pool of bind group layouts, allowing deduplication.
     pub(crate) bgl_pool: ResourcePool<bgl::EntryMap, BindGroupLayout<A>>,
+    /// Pool of bind groups, allowing deduplication. Only present if the device
+    /// was requested with [`wgt::DeviceDescriptor::cache_bind_groups`].
+    pub(crate) bind_group_pool: Option<ResourcePool<BindGroupCacheKey, BindGroup<A>>>,
     pub(crate) alignments: hal::Alignments,
     pub(crate) limits: wgt::Limits,
     pub(crate) features: wgt::Features,
     pub(crate) downlevel: wgt::DownlevelCapabilities,
+    /// The device was requested with [`wgt::DeviceDescriptor::compute_only`].
+    pub(crate) compute_only: bool,
     pub(crate) instance_flags: wgt::InstanceFlags,
     pub(crate) pending_writes: Mutex<Option<PendingWrites<A>>>,
     #[cfg(feature = "trace")]
@@ -151,9 +189,18 @@ impl<A: HalApi> Drop for Device<A> {
         let pending_writes = self.pending_writes.lock().take().unwrap();
         pending_writes.dispose(&raw);
         self.command_allocator.lock().take().unwrap().dispose(&raw);
+        if let Some(debug_printf) = self.debug_printf.take() {
+            debug_printf.dispose(&raw);
+        }
         unsafe {
             raw.destroy_buffer(self.zero_buffer.take().unwrap());
             raw.destroy_fence(self.fence.write().take().unwrap());
+            for fence in self.additional_fences.write().drain(..) {
+                raw.destroy_fence(fence);
+            }
+            for queue in self.additional_queues_to_drop.lock().drain(..) {
+                raw.destroy_queue(queue);
+            }
             let queue = self.queue_to_drop.write().take().unwrap();
             raw.exit(queue);
         }
@@ -190,6 +237,40 @@ impl<A: HalApi> Device<A> {
             Err(MissingDownlevelFlags(flags))
         }
     }
+
+    pub(crate) fn set_automatic_polling(self: &Arc<Self>, enabled: bool) {
+        let mut poller = self.poller.lock();
+        if enabled == poller.is_some() {
+            return;
+        }
+        *poller = if enabled {
+            match Poller::start(self) {
+                Ok(poller) => Some(poller),
+                Err(err) => {
+                    log::warn!("Failed to start polling the device automatically: {err}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+    }
+
+    /// Lets the poller thread know that there is new work, if automatic polling
+    /// is enabled.
+    pub(crate) fn wake_poller(&self) {
+        if let Some(ref poller) = *self.poller.lock() {
+            poller.wake();
+        }
+    }
+
+    pub(crate) fn require_rendering(&self) -> Result<(), ComputeOnlyDevice> {
+        if self.compute_only {
+            Err(ComputeOnlyDevice)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl<A: HalApi> Device<A> {
@@ -207,6 +288,10 @@ impl<A: HalApi> Device<A> {
         }
         let fence =
             unsafe { raw_device.create_fence() }.map_err(|_| CreateDeviceError::OutOfMemory)?;
+        let additional_fences = (0..desc.additional_queues)
+            .map(|_| unsafe { raw_device.create_fence() })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| CreateDeviceError::OutOfMemory)?;
 
         let mut com_alloc = CommandAllocator {
             free_encoders: Vec::new(),
@@ -246,25 +331,53 @@ impl<A: HalApi> Device<A> {
                 }));
         }
 
+        let debug_printf = if desc
+            .required_features
+            .contains(wgt::Features::SHADER_DEBUG_PRINTF)
+        {
+            Some(DebugPrintf::new(
+                &raw_device,
+                &mut pending_writes.command_encoder,
+                desc.required_limits.max_bind_groups,
+                instance_flags,
+            )?)
+        } else {
+            None
+        };
+
         let alignments = adapter.raw.capabilities.alignments.clone();
         let downlevel = adapter.raw.capabilities.downlevel.clone();
+        let limits = wgt::Limits {
+            // This is a property of the adapter, not something that can be requested.
+            shading_rate_tile_size: adapter.raw.capabilities.limits.shading_rate_tile_size,
+            ..desc.required_limits.clone()
+        };
 
         Ok(Self {
             raw: Some(raw_device),
             adapter: adapter.clone(),
             queue_id: RwLock::new(None),
             queue_to_drop: RwLock::new(None),
+            additional_queue_ids: RwLock::new(Vec::new()),
+            additional_queues_to_drop: Mutex::new(Vec::new()),
             zero_buffer: Some(zero_buffer),
+            debug_printf,
+            watchdog: Mutex::new(None),
+            poller: Mutex::new(None),
+            command_disassembly: AtomicBool::new(false),
             info: ResourceInfo::new("<device>"),
             command_allocator: Mutex::new(Some(com_alloc)),
             active_submission_index: AtomicU64::new(0),
             fence: RwLock::new(Some(fence)),
+            additional_fences: RwLock::new(additional_fences),
             snatchable_lock: unsafe { SnatchLock::new() },
             valid: AtomicBool::new(true),
+            lost_by_driver: AtomicBool::new(false),
             trackers: Mutex::new(Tracker::new()),
             life_tracker: Mutex::new(life::LifetimeTracker::new()),
             temp_suspected: Mutex::new(Some(life::ResourceMaps::new())),
             bgl_pool: ResourcePool::new(),
+            bind_group_pool: desc.cache_bind_groups.then(ResourcePool::new),
             #[cfg(feature = "trace")]
             trace: Mutex::new(trace_path.and_then(|path| match trace::Trace::new(path) {
                 Ok(mut trace) => {
@@ -280,9 +393,10 @@ impl<A: HalApi> Device<A> {
                 }
             })),
             alignments,
-            limits: desc.required_limits.clone(),
+            limits,
             features: desc.required_features,
             downlevel,
+            compute_only: desc.compute_only,
             instance_flags,
             pending_writes: Mutex::new(Some(pending_writes)),
         })
@@ -292,14 +406,131 @@ impl<A: HalApi> Device<A> {
         self.valid.load(Ordering::Acquire)
     }
 
+    /// Converts an error of `hal` into a [`DeviceError`], marking the device
+    /// as invalid if the driver reported it as lost.
+    ///
+    /// This takes no lock: the device lost closure is called and the
+    /// resources are released by the next [`Device::maintain`].
+    pub(crate) fn handle_hal_error(&self, error: hal::DeviceError) -> DeviceError {
+        if let hal::DeviceError::Lost = error {
+            self.valid.store(false, Ordering::Release);
+            self.lost_by_driver.store(true, Ordering::Release);
+        }
+        DeviceError::from(error)
+    }
+
+    /// Checks the result of submitting work to a queue of this device.
+    ///
+    /// A device lost by the driver doesn't fail the submission, which is
+    /// dropped as if the device had been lost before it.
+    pub(crate) fn check_submit_result(
+        &self,
+        result: Result<(), hal::DeviceError>,
+    ) -> Result<(), DeviceError> {
+        match result {
+            Err(hal::DeviceError::Lost) => {
+                self.handle_hal_error(hal::DeviceError::Lost);
+                Ok(())
+            }
+            result => result.map_err(DeviceError::from),
+        }
+    }
+
+    /// Releases the resources of a device lost by the driver, and returns the
+    /// invocation of its device lost closure if it wasn't called yet.
+    fn lose_by_driver(&self) -> UserClosures {
+        self.release_gpu_resources();
+
+        let mut device_lost_invocations = SmallVec::new();
+        if let Some(closure) = self.lock_life().device_lost_closure.take() {
+            device_lost_invocations.push(DeviceLostInvocation {
+                closure,
+                reason: DeviceLostReason::Unknown,
+                message: String::from("Device lost by the driver."),
+            });
+        }
+        UserClosures {
+            device_lost_invocations,
+            ..Default::default()
+        }
+    }
+
     pub(crate) fn release_queue(&self, queue: A::Queue) {
         self.queue_to_drop.write().replace(queue);
     }
 
+    pub(crate) fn release_additional_queue(&self, queue: A::Queue) {
+        self.additional_queues_to_drop.lock().push(queue);
+    }
+
+    pub(crate) fn register_additional_queues(
+        self: &Arc<Self>,
+        hub: &Hub<A>,
+        queues: Vec<queue::Queue<A>>,
+    ) {
+        let mut ids = self.additional_queue_ids.write();
+        for mut queue in queues {
+            queue.device = Some(self.clone());
+            let (id, _) = hub.queues.request().assign(queue);
+            resource_log!("Created Queue {:?}", id);
+            ids.push(id);
+        }
+    }
+
     pub(crate) fn lock_life<'a>(&'a self) -> MutexGuard<'a, LifetimeTracker<A>> {
         self.life_tracker.lock()
     }
 
+    /// Returns the index of the last submission that is done, along with all
+    /// the submissions before it, on every queue of the device.
+    ///
+    /// `fence` is the fence of the main queue.
+    pub(crate) fn last_done_index(
+        &self,
+        fence: &A::Fence,
+    ) -> Result<SubmissionIndex, hal::DeviceError> {
+        let value = unsafe { self.raw().get_fence_value(fence) }?;
+        let additional_fences = self.additional_fences.read();
+        if additional_fences.is_empty() {
+            return Ok(value);
+        }
+        let mut values = vec![value];
+        for fence in additional_fences.iter() {
+            values.push(unsafe { self.raw().get_fence_value(fence) }?);
+        }
+        Ok(self.lock_life().last_done_index(&values))
+    }
+
+    /// Waits until the submissions up to `submission_index` are done, on every
+    /// queue of the device. Returns `false` on timeout.
+    ///
+    /// `fence` is the fence of the main queue.
+    pub(crate) fn wait_for_submission_index(
+        &self,
+        fence: &A::Fence,
+        submission_index: SubmissionIndex,
+        timeout_ms: u32,
+    ) -> Result<bool, hal::DeviceError> {
+        let additional_fences = self.additional_fences.read();
+        if additional_fences.is_empty() {
+            return unsafe { self.raw().wait(fence, submission_index, timeout_ms) };
+        }
+        let targets = self
+            .lock_life()
+            .last_active_submissions(1 + additional_fences.len(), |index| {
+                index <= submission_index
+            });
+        let fences = iter::once(fence).chain(additional_fences.iter());
+        for (fence, target) in fences.zip(targets) {
+            if let Some(target) = target {
+                if !unsafe { self.raw().wait(fence, target, timeout_ms) }? {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
     /// Check this device for completed commands.
     ///
     /// The `maintain` argument tells how the maintence function should behave, either
@@ -319,7 +550,18 @@ impl<A: HalApi> Device<A> {
         maintain: wgt::Maintain<queue::WrappedSubmissionIndex>,
     ) -> Result<(UserClosures, bool), WaitIdleError> {
         profiling::scope!("Device::maintain");
-        let last_done_index = if maintain.is_wait() {
+        let mut lost_by_driver = self.lost_by_driver.load(Ordering::Acquire);
+        let lost_closures = if lost_by_driver {
+            self.lose_by_driver()
+        } else {
+            UserClosures::default()
+        };
+
+        let last_done_index = if lost_by_driver {
+            // The submissions of a lost device never finish, so they're all
+            // treated as done to release their resources and call their closures.
+            Ok(self.active_submission_index.load(Ordering::Relaxed))
+        } else if maintain.is_wait() {
             let index_to_wait_for = match maintain {
                 wgt::Maintain::WaitForSubmissionIndex(submission_index) => {
                     // We don't need to check to see if the queue id matches
@@ -328,21 +570,22 @@ impl<A: HalApi> Device<A> {
                 }
                 _ => self.active_submission_index.load(Ordering::Relaxed),
             };
-            unsafe {
-                self.raw
-                    .as_ref()
-                    .unwrap()
-                    .wait(fence, index_to_wait_for, CLEANUP_WAIT_MS)
-                    .map_err(DeviceError::from)?
-            };
-            index_to_wait_for
+            self.wait_for_submission_index(fence, index_to_wait_for, CLEANUP_WAIT_MS)
+                .map(|_| index_to_wait_for)
         } else {
-            unsafe {
-                self.raw
-                    .as_ref()
-                    .unwrap()
-                    .get_fence_value(fence)
-                    .map_err(DeviceError::from)?
+            self.last_done_index(fence)
+        };
+        let last_done_index = match last_done_index {
+            Ok(index) => index,
+            Err(error) => {
+                let error = self.handle_hal_error(error);
+                if !self.lost_by_driver.load(Ordering::Acquire) {
+                    return Err(error.into());
+                }
+                // The resources of the device are released by the next call,
+                // as our caller may be holding locks needed to release them.
+                lost_by_driver = true;
+                self.active_submission_index.load(Ordering::Relaxed)
             }
         };
 
@@ -370,15 +613,19 @@ impl<A: HalApi> Device<A> {
             life_tracker.triage_mapped();
         }
 
-        let mapping_closures = life_tracker.handle_mapping(self.raw(), &self.trackers);
+        let mapping_closures = if lost_by_driver {
+            life_tracker.fail_mapping()
+        } else {
+            life_tracker.handle_mapping(self.raw(), &self.trackers)
+        };
 
         // Detect if we have been destroyed and now need to lose the device.
         // If we are invalid (set at start of destroy) and our queue is empty,
         // and we have a DeviceLostClosure, return the closure to be called by
         // our caller. This will complete the steps for both destroy and for
         // "lose the device".
-        let mut device_lost_invocations = SmallVec::new();
-        if !self.is_valid() && life_tracker.queue_empty() {
+        let mut device_lost_invocations = lost_closures.device_lost_invocations;
+        if !lost_by_driver && !self.is_valid() && life_tracker.queue_empty() {
             // We can release gpu resources associated with this device.
             self.release_gpu_resources();
 
@@ -393,10 +640,19 @@ impl<A: HalApi> Device<A> {
             }
         }
 
+        let debug_printf_messages = self
+            .debug_printf
+            .as_ref()
+            .filter(|_| !lost_by_driver)
+            .and_then(|debug_printf| debug_printf.read_back(self.raw(), last_done_index))
+            .into_iter()
+            .collect();
+
         let closures = UserClosures {
             mappings: mapping_closures,
             submissions: submission_closures,
             device_lost_invocations,
+            debug_printf_messages,
         };
         Ok((closures, life_tracker.queue_empty()))
     }
@@ -467,17 +723,30 @@ impl<A: HalApi> Device<A> {
                         .insert(resource.as_info().id(), resource.clone());
                 }
             }
+            for resource in trackers.blas_s.used_resources() {
+                if resource.is_unique() {
+                    temp_suspected
+                        .blas_s
+                        .insert(resource.as_info().id(), resource.clone());
+                }
+            }
+            for resource in trackers.tlas_s.used_resources() {
+                if resource.is_unique() {
+                    temp_suspected
+                        .tlas_s
+                        .insert(resource.as_info().id(), resource.clone());
+                }
+            }
         }
         self.lock_life().suspected_resources.extend(temp_suspected);
     }
 
-    pub(crate) fn create_buffer(
-        self: &Arc<Self>,
-        desc: &resource::BufferDescriptor,
+    /// Validates `desc` and makes the descriptor of the raw buffer behind it.
+    pub(crate) fn buffer_hal_descriptor<'a>(
+        &self,
+        desc: &'a resource::BufferDescriptor,
         transient: bool,
-    ) -> Result<Buffer<A>, resource::CreateBufferError> {
-        debug_assert_eq!(self.as_info().id().backend(), A::VARIANT);
-
+    ) -> Result<hal::BufferDescriptor<'a>, resource::CreateBufferError> {
         if desc.size > self.limits.max_buffer_size {
             return Err(resource::CreateBufferError::MaxBufferSize {
                 requested: desc.size,
@@ -502,6 +771,17 @@ impl<A: HalApi> Device<A> {
             return Err(resource::CreateBufferError::InvalidUsage(desc.usage));
         }
 
+        if desc
+            .usage
+            .intersects(wgt::BufferUsages::BLAS_INPUT | wgt::BufferUsages::TLAS_INPUT)
+        {
+            self.require_features(wgt::Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE)?;
+        }
+
+        if desc.usage.contains(wgt::BufferUsages::VERTEX_CAPTURE) {
+            self.require_features(wgt::Features::VERTEX_CAPTURE)?;
+        }
+
         if !self
             .features
             .contains(wgt::Features::MAPPABLE_PRIMARY_BUFFERS)
@@ -549,26 +829,113 @@ impl<A: HalApi> Device<A> {
         let mut memory_flags = hal::MemoryFlags::empty();
         memory_flags.set(hal::MemoryFlags::TRANSIENT, transient);
 
-        let hal_desc = hal::BufferDescriptor {
+        Ok(hal::BufferDescriptor {
             label: desc.label.to_hal(self.instance_flags),
             size: aligned_size,
             usage,
             memory_flags,
-        };
-        let buffer = unsafe { self.raw().create_buffer(&hal_desc) }.map_err(DeviceError::from)?;
+        })
+    }
+
+    pub(crate) fn create_buffer(
+        self: &Arc<Self>,
+        desc: &resource::BufferDescriptor,
+        transient: bool,
+        placement: Option<(&Arc<MemoryHeap<A>>, wgt::BufferAddress)>,
+    ) -> Result<Buffer<A>, resource::CreateBufferError> {
+        debug_assert_eq!(self.as_info().id().backend(), A::VARIANT);
+
+        let hal_desc = self.buffer_hal_descriptor(desc, transient)?;
+        let buffer = match placement {
+            Some((heap, offset)) => {
+                if heap.device.as_info().id() != self.as_info().id() {
+                    return Err(DeviceError::WrongDevice.into());
+                }
+                if !heap.memory_type.supports_buffer_usage(desc.usage) {
+                    return Err(resource::PlacementError::UnsupportedBufferUsage {
+                        usage: desc.usage,
+                        memory_type: heap.memory_type,
+                    }
+                    .into());
+                }
+                let requirements = unsafe { self.raw().get_buffer_memory_requirements(&hal_desc) }
+                    .map_err(DeviceError::from)?;
+                heap.check_placement(offset, requirements)?;
+                unsafe {
+                    self.raw()
+                        .create_placed_buffer(&hal_desc, heap.raw(), offset)
+                }
+            }
+            None => unsafe { self.raw().create_buffer(&hal_desc) },
+        }
+        .map_err(DeviceError::from)?;
 
         Ok(Buffer {
             raw: Snatchable::new(buffer),
             device: self.clone(),
             usage: desc.usage,
             size: desc.size,
-            initialization_status: RwLock::new(BufferInitTracker::new(aligned_size)),
+            initialization_status: RwLock::new(BufferInitTracker::new(hal_desc.size)),
             sync_mapped_writes: Mutex::new(None),
             map_state: Mutex::new(resource::BufferMapState::Idle),
             info: ResourceInfo::new(desc.label.borrow_or_default()),
+            heap: placement.map(|(heap, _)| heap.clone()),
         })
     }
 
+    pub(crate) fn buffer_memory_requirements(
+        &self,
+        desc: &resource::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, resource::CreateBufferError> {
+        self.require_features(wgt::Features::MEMORY_HEAPS)?;
+        let hal_desc = self.buffer_hal_descriptor(desc, false)?;
+        let requirements = unsafe { self.raw().get_buffer_memory_requirements(&hal_desc) }
+            .map_err(DeviceError::from)?;
+        Ok(requirements)
+    }
+
+    /// Validates `desc` for a buffer whose memory is shared with other APIs.
+    fn external_buffer_hal_descriptor<'a>(
+        &self,
+        desc: &'a resource::BufferDescriptor,
+    ) -> Result<hal::BufferDescriptor<'a>, resource::CreateBufferError> {
+        self.require_features(wgt::Features::EXTERNAL_MEMORY)?;
+        if desc.mapped_at_creation
+            || desc
+                .usage
+                .intersects(wgt::BufferUsages::MAP_READ | wgt::BufferUsages::MAP_WRITE)
+        {
+            return Err(resource::CreateBufferError::MappableExternal);
+        }
+        self.buffer_hal_descriptor(desc, false)
+    }
+
+    pub(crate) fn create_external_buffer(
+        self: &Arc<Self>,
+        desc: &resource::BufferDescriptor,
+    ) -> Result<Buffer<A>, resource::CreateBufferError> {
+        let hal_desc = self.external_buffer_hal_descriptor(desc)?;
+        let raw =
+            unsafe { self.raw().create_external_buffer(&hal_desc) }.map_err(DeviceError::from)?;
+
+        // Unlike imported memory, the new memory is zeroed on first use.
+        let buffer = self.create_buffer_from_hal(raw, desc);
+        *buffer.initialization_status.write() = BufferInitTracker::new(hal_desc.size);
+        Ok(buffer)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn import_external_buffer(
+        self: &Arc<Self>,
+        desc: &resource::BufferDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> Result<Buffer<A>, resource::CreateBufferError> {
+        let hal_desc = self.external_buffer_hal_descriptor(desc)?;
+        let raw = unsafe { self.raw().import_external_buffer(&hal_desc, memory) }
+            .map_err(DeviceError::from)?;
+        Ok(self.create_buffer_from_hal(raw, desc))
+    }
+
     pub(crate) fn create_texture_from_hal(
         self: &Arc<Self>,
         hal_texture: A::Texture,
@@ -595,6 +962,9 @@ impl<A: HalApi> Device<A> {
             },
             info: ResourceInfo::new(desc.label.borrow_or_default()),
             clear_mode: RwLock::new(clear_mode),
+            tile_pools: Mutex::new(Vec::new()),
+            heap: None,
+            resting_uses: None,
         }
     }
 
@@ -614,14 +984,19 @@ impl<A: HalApi> Device<A> {
             sync_mapped_writes: Mutex::new(None),
             map_state: Mutex::new(resource::BufferMapState::Idle),
             info: ResourceInfo::new(desc.label.borrow_or_default()),
+            heap: None,
         }
     }
 
-    pub(crate) fn create_texture(
-        self: &Arc<Self>,
+    /// Validates `desc` and makes the descriptor of the raw texture behind it.
+    pub(crate) fn texture_hal_descriptor<'a>(
+        &self,
         adapter: &Adapter<A>,
-        desc: &resource::TextureDescriptor,
-    ) -> Result<Texture<A>, resource::CreateTextureError> {
+        desc: &'a resource::TextureDescriptor,
+    ) -> Result<
+        (hal::TextureDescriptor<'a>, wgt::TextureFormatFeatures),
+        resource::CreateTextureError,
+    > {
         use resource::{CreateTextureError, TextureDimensionError};
 
         if desc.usage.is_empty() || desc.usage.contains_invalid_bits() {
@@ -650,6 +1025,16 @@ impl<A: HalApi> Device<A> {
                     desc.dimension,
                 ));
             }
+            // So are shading rate attachments
+            if desc
+                .usage
+                .contains(wgt::TextureUsages::SHADING_RATE_ATTACHMENT)
+            {
+                return Err(CreateTextureError::InvalidDimensionUsages(
+                    wgt::TextureUsages::SHADING_RATE_ATTACHMENT,
+                    desc.dimension,
+                ));
+            }
 
             // Compressed textures can only be 2D
             if desc.format.is_compressed() {
@@ -788,6 +1173,35 @@ impl<A: HalApi> Device<A> {
             ));
         }
 
+        let tiled = desc.usage.contains(wgt::TextureUsages::TILED);
+        if tiled {
+            if desc.dimension != wgt::TextureDimension::D2 {
+                return Err(CreateTextureError::InvalidDimensionUsages(
+                    wgt::TextureUsages::TILED,
+                    desc.dimension,
+                ));
+            }
+            if desc.usage.contains(wgt::TextureUsages::RENDER_ATTACHMENT) {
+                return Err(CreateTextureError::TiledRenderAttachment);
+            }
+
+            // Mip levels smaller than a tile would be packed into a mip tail,
+            // which can't be bound tile by tile.
+            let (tile_width, tile_height) = desc.format.tile_dimensions().unwrap();
+            for mip_level in 0..desc.mip_level_count {
+                let size = desc.mip_level_size(mip_level).unwrap();
+                if size.width % tile_width != 0 || size.height % tile_height != 0 {
+                    return Err(CreateTextureError::InvalidTiledMipSize {
+                        mip_level,
+                        size,
+                        tile_width,
+                        tile_height,
+                        format: desc.format,
+                    });
+                }
+            }
+        }
+
         let mut hal_view_formats = vec![];
         for format in desc.view_formats.iter() {
             if desc.format == *format {
@@ -812,17 +1226,50 @@ impl<A: HalApi> Device<A> {
             dimension: desc.dimension,
             format: desc.format,
             usage: hal_usage,
-            memory_flags: hal::MemoryFlags::empty(),
+            memory_flags: if tiled {
+                hal::MemoryFlags::TILED
+            } else {
+                hal::MemoryFlags::empty()
+            },
             view_formats: hal_view_formats,
         };
+        Ok((hal_desc, format_features))
+    }
 
-        let raw_texture = unsafe {
-            self.raw
-                .as_ref()
-                .unwrap()
-                .create_texture(&hal_desc)
-                .map_err(DeviceError::from)?
-        };
+    pub(crate) fn create_texture(
+        self: &Arc<Self>,
+        adapter: &Adapter<A>,
+        desc: &resource::TextureDescriptor,
+        placement: Option<(&Arc<MemoryHeap<A>>, wgt::BufferAddress)>,
+    ) -> Result<Texture<A>, resource::CreateTextureError> {
+        let (hal_desc, format_features) = self.texture_hal_descriptor(adapter, desc)?;
+        let hal_usage = hal_desc.usage;
+        let tiled = desc.usage.contains(wgt::TextureUsages::TILED);
+
+        let raw_texture = match placement {
+            Some((heap, offset)) => {
+                if heap.device.as_info().id() != self.as_info().id() {
+                    return Err(DeviceError::WrongDevice.into());
+                }
+                if !heap.memory_type.supports_textures() {
+                    return Err(
+                        resource::PlacementError::UnsupportedTexture(heap.memory_type).into(),
+                    );
+                }
+                if tiled {
+                    return Err(resource::PlacementError::TiledTexture.into());
+                }
+                let requirements = unsafe { self.raw().get_texture_memory_requirements(&hal_desc) }
+                    .map_err(DeviceError::from)?;
+                heap.check_placement(offset, requirements)?;
+                unsafe {
+                    self.raw()
+                        .create_placed_texture(&hal_desc, heap.raw(), offset)
+                }
+            }
+            None => unsafe { self.raw().create_texture(&hal_desc) },
+        }
+        .map_err(DeviceError::from)?;
 
         let clear_mode = if hal_usage
             .intersects(hal::TextureUses::DEPTH_STENCIL_WRITE | hal::TextureUses::COLOR_TARGET)
@@ -860,6 +1307,7 @@ impl<A: HalApi> Device<A> {
                                     base_array_layer: array_layer,
                                     array_layer_count: Some(1),
                                 },
+                                swizzle: wgt::ComponentMapping::IDENTITY,
                             };
                             clear_views.push(Some(
                                 unsafe { self.raw().create_texture_view(&raw_texture, &desc) }
@@ -890,9 +1338,108 @@ impl<A: HalApi> Device<A> {
         let mut texture =
             self.create_texture_from_hal(raw_texture, hal_usage, desc, format_features, clear_mode);
         texture.hal_usage = hal_usage;
+        texture.heap = placement.map(|(heap, _)| heap.clone());
+        if tiled {
+            // Unbound tiles read as zero, and tiles are zeroed when they're bound.
+            for mip_tracker in texture.initialization_status.get_mut().mips.iter_mut() {
+                drop(mip_tracker.drain(0..desc.array_layer_count()));
+            }
+        }
         Ok(texture)
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn import_external_texture(
+        self: &Arc<Self>,
+        adapter: &Adapter<A>,
+        desc: &resource::TextureDescriptor,
+        source: wgt::ExternalImageSource,
+    ) -> Result<Texture<A>, resource::CreateTextureError> {
+        self.require_features(wgt::Features::EXTERNAL_TEXTURE_IMPORT)
+            .map_err(resource::ExternalImportError::from)?;
+        if desc.dimension != wgt::TextureDimension::D2
+            || desc.mip_level_count != 1
+            || desc.sample_count != 1
+            || desc.size.depth_or_array_layers != 1
+        {
+            return Err(resource::ExternalImportError::UnsupportedDescriptor.into());
+        }
+        if desc.usage.contains(wgt::TextureUsages::TILED) {
+            return Err(resource::ExternalImportError::TiledTexture.into());
+        }
+
+        let (hal_desc, format_features) = self.texture_hal_descriptor(adapter, desc)?;
+        let hal_usage = hal_desc.usage;
+        let raw_texture = unsafe { self.raw().import_external_texture(&hal_desc, source) }
+            .map_err(DeviceError::from)?;
+
+        // The contents come from outside of wgpu, so they're never cleared.
+        let mut texture = self.create_texture_from_hal(
+            raw_texture,
+            hal_usage,
+            desc,
+            format_features,
+            resource::TextureClearMode::None,
+        );
+        texture.initialization_status = RwLock::new(TextureInitTracker::new(1, 0));
+        Ok(texture)
+    }
+
+    /// Creates a texture around an image of another API, which is rendered to
+    /// in place and handed back in its render target state.
+    ///
+    /// # Safety
+    ///
+    /// `wrap` must return a texture of the image, created respecting the hal
+    /// descriptor it is given.
+    #[cfg(any(vulkan, dx12))]
+    pub(crate) unsafe fn wrap_raw_texture(
+        self: &Arc<Self>,
+        adapter: &Adapter<A>,
+        desc: &resource::TextureDescriptor,
+        wrap: impl FnOnce(&hal::TextureDescriptor) -> A::Texture,
+    ) -> Result<Texture<A>, resource::CreateTextureError> {
+        if !desc.usage.contains(wgt::TextureUsages::RENDER_ATTACHMENT) {
+            return Err(resource::ExternalImportError::NotRenderAttachment.into());
+        }
+        if desc.usage.contains(wgt::TextureUsages::TILED) {
+            return Err(resource::ExternalImportError::TiledTexture.into());
+        }
+
+        let (hal_desc, format_features) = self.texture_hal_descriptor(adapter, desc)?;
+        let hal_usage = hal_desc.usage;
+        let raw_texture = wrap(&hal_desc);
+
+        let mut texture = self.create_texture_from_hal(
+            raw_texture,
+            hal_usage,
+            desc,
+            format_features,
+            resource::TextureClearMode::None,
+        );
+        texture.initialization_status =
+            RwLock::new(TextureInitTracker::new(desc.mip_level_count, 0));
+        texture.resting_uses = Some(if desc.format.is_depth_stencil_format() {
+            hal::TextureUses::DEPTH_STENCIL_WRITE
+        } else {
+            hal::TextureUses::COLOR_TARGET
+        });
+        Ok(texture)
+    }
+
+    pub(crate) fn texture_memory_requirements(
+        &self,
+        adapter: &Adapter<A>,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, resource::CreateTextureError> {
+        self.require_features(wgt::Features::MEMORY_HEAPS)
+            .map_err(|error| resource::CreateTextureError::MissingFeatures(desc.format, error))?;
+        let (hal_desc, _) = self.texture_hal_descriptor(adapter, desc)?;
+        let requirements = unsafe { self.raw().get_texture_memory_requirements(&hal_desc) }
+            .map_err(DeviceError::from)?;
+        Ok(requirements)
+    }
+
     pub(crate) fn create_texture_view(
         self: &Arc<Self>,
         texture: &Arc<Texture<A>>,
@@ -953,6 +1500,10 @@ impl<A: HalApi> Device<A> {
 
         // validate TextureViewDescriptor
 
+        if !desc.swizzle.is_identity() {
+            self.require_features(wgt::Features::TEXTURE_COMPONENT_SWIZZLE)?;
+        }
+
         let aspects = hal::FormatAspects::new(texture.desc.format, desc.range.aspect);
         if aspects.is_empty() {
             return Err(resource::CreateTextureViewError::InvalidAspect {
@@ -1107,6 +1658,10 @@ impl<A: HalApi> Device<A> {
                 break 'b Err(TextureViewNotRenderableReason::Aspects(aspects));
             }
 
+            if !desc.swizzle.is_identity() {
+                break 'b Err(TextureViewNotRenderableReason::Swizzle(desc.swizzle));
+            }
+
             break 'b Ok(texture
                 .desc
                 .compute_render_extent(desc.range.base_mip_level));
@@ -1131,7 +1686,12 @@ impl<A: HalApi> Device<A> {
             } else {
                 hal::TextureUses::RESOURCE
             };
-            texture.hal_usage & mask_copy & mask_dimension & mask_mip_level
+            let mask_swizzle = if desc.swizzle.is_identity() {
+                hal::TextureUses::all()
+            } else {
+                hal::TextureUses::RESOURCE
+            };
+            texture.hal_usage & mask_copy & mask_dimension & mask_mip_level & mask_swizzle
         };
 
         log::debug!(
@@ -1161,6 +1721,7 @@ impl<A: HalApi> Device<A> {
             dimension: resolved_dimension,
             usage,
             range: resolved_range,
+            swizzle: desc.swizzle,
         };
 
         let raw = unsafe {
@@ -1185,6 +1746,7 @@ impl<A: HalApi> Device<A> {
                 format: resolved_format,
                 dimension: resolved_dimension,
                 range: resolved_range,
+                swizzle: desc.swizzle,
             },
             format_features: texture.format_features,
             render_extent,
@@ -1373,6 +1935,14 @@ impl<A: HalApi> Device<A> {
             Caps::FLOAT64,
             self.features.contains(wgt::Features::SHADER_F64),
         );
+        caps.set(
+            Caps::FLOAT16,
+            self.features.contains(wgt::Features::SHADER_F16),
+        );
+        caps.set(
+            Caps::SHADER_INT64,
+            self.features.contains(wgt::Features::SHADER_INT64),
+        );
         caps.set(
             Caps::PRIMITIVE_INDEX,
             self.features
@@ -1421,12 +1991,26 @@ impl<A: HalApi> Device<A> {
             Caps::DUAL_SOURCE_BLENDING,
             self.features.contains(wgt::Features::DUAL_SOURCE_BLENDING),
         );
+        caps.set(
+            Caps::DEBUG_PRINTF,
+            self.features.contains(wgt::Features::SHADER_DEBUG_PRINTF),
+        );
         caps.set(
             Caps::CUBE_ARRAY_TEXTURES,
             self.downlevel
                 .flags
                 .contains(wgt::DownlevelFlags::CUBE_ARRAY_TEXTURES),
         );
+        caps.set(
+            Caps::RAY_QUERY,
+            self.features
+                .contains(wgt::Features::EXPERIMENTAL_RAY_QUERY),
+        );
+        caps.set(
+            Caps::MESH_SHADER,
+            self.features
+                .contains(wgt::Features::EXPERIMENTAL_MESH_SHADER),
+        );
 
         let debug_source =
             if self.instance_flags.contains(wgt::InstanceFlags::DEBUG) && !source.is_empty() {
@@ -1442,7 +2026,7 @@ impl<A: HalApi> Device<A> {
                 None
             };
 
-        let info = naga::valid::Validator::new(naga::valid::ValidationFlags::all(), caps)
+        let info = naga::valid::Validator::new(validation::SHADER_VALIDATION_FLAGS, caps)
             .validate(&module)
             .map_err(|inner| {
                 pipeline::CreateShaderModuleError::Validation(pipeline::ShaderError {
@@ -1454,8 +2038,43 @@ impl<A: HalApi> Device<A> {
 
         let interface =
             validation::Interface::new(&module, &info, self.limits.clone(), self.features);
+
+        // Backends write the code of the pipelines from the module, so remove
+        // the branches disabled by constants for drivers not to compile them.
+        let mut module = module.into_owned();
+        let info = if naga::proc::remove_unreachable_branches(&mut module) {
+            naga::valid::Validator::new(validation::SHADER_VALIDATION_FLAGS, caps)
+                .validate(&module)
+                .map_err(|inner| {
+                    pipeline::CreateShaderModuleError::Validation(pipeline::ShaderError {
+                        source: String::new(),
+                        label: desc.label.as_ref().map(|l| l.to_string()),
+                        inner: Box::new(inner),
+                    })
+                })?
+        } else {
+            info
+        };
+
+        // The interface is taken from the module as written by the user, so
+        // that the capture buffer does not have to be part of the pipeline layout.
+        let info = match self.debug_printf {
+            Some(ref debug_printf) if debug_printf.lower(&mut module, &info) => {
+                naga::valid::Validator::new(validation::SHADER_VALIDATION_FLAGS, caps)
+                    .validate(&module)
+                    .map_err(|inner| {
+                        pipeline::CreateShaderModuleError::Validation(pipeline::ShaderError {
+                            source: String::new(),
+                            label: desc.label.as_ref().map(|l| l.to_string()),
+                            inner: Box::new(inner),
+                        })
+                    })?
+            }
+            _ => info,
+        };
+
         let hal_shader = hal::ShaderInput::Naga(hal::NagaShader {
-            module,
+            module: Cow::Owned(module),
             info,
             debug_source,
         });
@@ -1684,7 +2303,10 @@ impl<A: HalApi> Device<A> {
                         },
                     )
                 }
-                Bt::AccelerationStructure => todo!(),
+                Bt::AccelerationStructure => {
+                    required_features |= wgt::Features::EXPERIMENTAL_RAY_QUERY;
+                    (None, WritableStorage::No)
+                }
             };
 
             // Validate the count parameter
@@ -1720,6 +2342,12 @@ impl<A: HalApi> Device<A> {
             {
                 required_downlevel_flags |= wgt::DownlevelFlags::FRAGMENT_WRITABLE_STORAGE;
             }
+            if entry
+                .visibility
+                .intersects(wgt::ShaderStages::TASK | wgt::ShaderStages::MESH)
+            {
+                required_features |= wgt::Features::EXPERIMENTAL_MESH_SHADER;
+            }
 
             self.require_features(required_features)
                 .map_err(BindGroupLayoutEntryError::MissingFeatures)
@@ -1735,9 +2363,9 @@ impl<A: HalApi> Device<A> {
                 })?;
         }
 
-        let bgl_flags = conv::bind_group_layout_flags(self.features);
-
         let hal_bindings = entry_map.values().copied().collect::<Vec<_>>();
+        let bgl_flags = conv::bind_group_layout_flags(self.features, &hal_bindings);
+
         let label = label.to_hal(self.instance_flags);
         let hal_desc = hal::BindGroupLayoutDescriptor {
             label,
@@ -1752,9 +2380,10 @@ impl<A: HalApi> Device<A> {
                 .map_err(DeviceError::from)?
         };
 
+        let update_after_bind = bgl_flags.contains(hal::BindGroupLayoutFlags::UPDATE_AFTER_BIND);
         let mut count_validator = binding_model::BindingTypeMaxCountValidator::default();
         for entry in entry_map.values() {
-            count_validator.add_binding(entry);
+            count_validator.add_binding(entry, update_after_bind);
         }
         // If a single bind group layout violates limits, the pipeline layout is
         // definitely going to violate limits too, lets catch it now.
@@ -1957,6 +2586,36 @@ impl<A: HalApi> Device<A> {
         layout: &Arc<BindGroupLayout<A>>,
         desc: &binding_model::BindGroupDescriptor,
         hub: &Hub<A>,
+    ) -> Result<binding_model::BindGroup<A>, binding_model::CreateBindGroupError> {
+        let buffer_guard = hub.buffers.read();
+        let texture_view_guard = hub.texture_views.read();
+        let sampler_guard = hub.samplers.read();
+        let tlas_guard = hub.tlas_s.read();
+        let snatch_guard = self.snatchable_lock.read();
+
+        self.create_bind_group_with_guards(
+            layout,
+            desc,
+            &buffer_guard,
+            &texture_view_guard,
+            &sampler_guard,
+            &tlas_guard,
+            &snatch_guard,
+        )
+    }
+
+    /// Like [`Device::create_bind_group`], for callers that already hold the
+    /// storages of the bound resources, like render passes creating
+    /// immediate bind groups.
+    pub(crate) fn create_bind_group_with_guards(
+        self: &Arc<Self>,
+        layout: &Arc<BindGroupLayout<A>>,
+        desc: &binding_model::BindGroupDescriptor,
+        buffer_guard: &Storage<Buffer<A>, id::BufferId>,
+        texture_view_guard: &Storage<TextureView<A>, id::TextureViewId>,
+        sampler_guard: &Storage<Sampler<A>, id::SamplerId>,
+        tlas_guard: &Storage<resource::Tlas<A>, id::TlasId>,
+        snatch_guard: &SnatchGuard,
     ) -> Result<binding_model::BindGroup<A>, binding_model::CreateBindGroupError> {
         use crate::binding_model::{BindingResource as Br, CreateBindGroupError as Error};
         {
@@ -1979,17 +2638,13 @@ impl<A: HalApi> Device<A> {
         // fill out the descriptors
         let mut used = BindGroupStates::new();
 
-        let buffer_guard = hub.buffers.read();
-        let texture_view_guard = hub.texture_views.read();
-        let sampler_guard = hub.samplers.read();
-
         let mut used_buffer_ranges = Vec::new();
         let mut used_texture_ranges = Vec::new();
         let mut hal_entries = Vec::with_capacity(desc.entries.len());
         let mut hal_buffers = Vec::new();
         let mut hal_samplers = Vec::new();
         let mut hal_textures = Vec::new();
-        let snatch_guard = self.snatchable_lock.read();
+        let mut hal_tlas_s = Vec::new();
         for entry in desc.entries.iter() {
             let binding = entry.binding;
             // Find the corresponding declaration in the layout
@@ -2007,9 +2662,9 @@ impl<A: HalApi> Device<A> {
                         &mut dynamic_binding_info,
                         &mut late_buffer_binding_sizes,
                         &mut used,
-                        &*buffer_guard,
+                        buffer_guard,
                         &self.limits,
-                        &snatch_guard,
+                        snatch_guard,
                     )?;
 
                     let res_index = hal_buffers.len();
@@ -2030,9 +2685,9 @@ impl<A: HalApi> Device<A> {
                             &mut dynamic_binding_info,
                             &mut late_buffer_binding_sizes,
                             &mut used,
-                            &*buffer_guard,
+                            buffer_guard,
                             &self.limits,
-                            &snatch_guard,
+                            snatch_guard,
                         )?;
                         hal_buffers.push(bb);
                     }
@@ -2043,7 +2698,7 @@ impl<A: HalApi> Device<A> {
                         wgt::BindingType::Sampler(ty) => {
                             let sampler = used
                                 .samplers
-                                .add_single(&*sampler_guard, id)
+                                .add_single(sampler_guard, id)
                                 .ok_or(Error::InvalidSampler(id))?;
 
                             if sampler.device.as_info().id() != self.as_info().id() {
@@ -2096,7 +2751,7 @@ impl<A: HalApi> Device<A> {
                     for &id in bindings_array.iter() {
                         let sampler = used
                             .samplers
-                            .add_single(&*sampler_guard, id)
+                            .add_single(sampler_guard, id)
                             .ok_or(Error::InvalidSampler(id))?;
                         if sampler.device.as_info().id() != self.as_info().id() {
                             return Err(DeviceError::WrongDevice.into());
@@ -2109,7 +2764,7 @@ impl<A: HalApi> Device<A> {
                 Br::TextureView(id) => {
                     let view = used
                         .views
-                        .add_single(&*texture_view_guard, id)
+                        .add_single(texture_view_guard, id)
                         .ok_or(Error::InvalidTextureView(id))?;
                     let (pub_usage, internal_use) = self.texture_use_parameters(
                         binding,
@@ -2139,7 +2794,7 @@ impl<A: HalApi> Device<A> {
                     for &id in bindings_array.iter() {
                         let view = used
                             .views
-                            .add_single(&*texture_view_guard, id)
+                            .add_single(texture_view_guard, id)
                             .ok_or(Error::InvalidTextureView(id))?;
                         let (pub_usage, internal_use) =
                             self.texture_use_parameters(binding, decl, view,
@@ -2159,6 +2814,29 @@ impl<A: HalApi> Device<A> {
 
                     (res_index, num_bindings)
                 }
+                Br::AccelerationStructure(id) => {
+                    match decl.ty {
+                        wgt::BindingType::AccelerationStructure => {}
+                        _ => {
+                            return Err(Error::WrongBindingType {
+                                binding,
+                                actual: decl.ty,
+                                expected: "AccelerationStructure",
+                            })
+                        }
+                    }
+                    let tlas = used
+                        .acceleration_structures
+                        .add_single(tlas_guard, id)
+                        .ok_or(Error::InvalidTlas(id))?;
+                    if tlas.device.as_info().id() != self.as_info().id() {
+                        return Err(DeviceError::WrongDevice.into());
+                    }
+
+                    let res_index = hal_tlas_s.len();
+                    hal_tlas_s.push(tlas.raw());
+                    (res_index, 1)
+                }
             };
 
             hal_entries.push(hal::BindGroupEntry {
@@ -2183,7 +2861,7 @@ impl<A: HalApi> Device<A> {
             buffers: &hal_buffers,
             samplers: &hal_samplers,
             textures: &hal_textures,
-            acceleration_structures: &[],
+            acceleration_structures: &hal_tlas_s,
         };
         let raw = unsafe {
             self.raw
@@ -2208,6 +2886,7 @@ impl<A: HalApi> Device<A> {
                 .indices()
                 .flat_map(|binding| late_buffer_binding_sizes.get(&binding).cloned())
                 .collect(),
+            cache_key: None,
         })
     }
 
@@ -2340,6 +3019,12 @@ impl<A: HalApi> Device<A> {
                         mip_level_count,
                     });
                 }
+                if !view.desc.swizzle.is_identity() {
+                    return Err(Error::InvalidStorageTextureSwizzle {
+                        binding,
+                        swizzle: view.desc.swizzle,
+                    });
+                }
 
                 let internal_use = match access {
                     wgt::StorageTextureAccess::WriteOnly => hal::TextureUses::STORAGE_READ_WRITE,
@@ -2454,10 +3139,14 @@ impl<A: HalApi> Device<A> {
             .validate(&self.limits)
             .map_err(Error::TooManyBindings)?;
 
-        let raw_bind_group_layouts = bind_group_layouts
+        let mut raw_bind_group_layouts = bind_group_layouts
             .iter()
             .map(|bgl| bgl.raw())
             .collect::<ArrayVec<_, { hal::MAX_BIND_GROUPS }>>();
+        if let Some(ref debug_printf) = self.debug_printf {
+            raw_bind_group_layouts
+                .extend(debug_printf.extra_bind_group_layouts(bind_group_layouts.len()));
+        }
 
         let hal_desc = hal::PipelineLayoutDescriptor {
             label: desc.label.to_hal(self.instance_flags),
@@ -2571,6 +3260,22 @@ impl<A: HalApi> Device<A> {
             None => None,
         };
 
+        let cache = match desc.cache {
+            Some(cache_id) => {
+                let cache = hub
+                    .pipeline_caches
+                    .get(cache_id)
+                    .map_err(|_| pipeline::CreateComputePipelineError::InvalidCache)?;
+
+                if cache.device.as_info().id() != self.as_info().id() {
+                    return Err(DeviceError::WrongDevice.into());
+                }
+
+                Some(cache)
+            }
+            None => None,
+        };
+
         let mut binding_layout_source = match pipeline_layout {
             Some(ref pipeline_layout) => {
                 validation::BindingLayoutSource::Provided(pipeline_layout.get_binding_maps())
@@ -2618,6 +3323,7 @@ impl<A: HalApi> Device<A> {
                 entry_point: desc.stage.entry_point.as_ref(),
                 module: shader_module.raw(),
             },
+            cache: cache.as_ref().map(|cache| cache.raw()),
         };
 
         let raw = unsafe {
@@ -2652,7 +3358,7 @@ impl<A: HalApi> Device<A> {
     pub(crate) fn create_render_pipeline(
         self: &Arc<Self>,
         adapter: &Adapter<A>,
-        desc: &pipeline::RenderPipelineDescriptor,
+        desc: pipeline::GeneralRenderPipelineDescriptor,
         implicit_context: Option<ImplicitPipelineContext>,
         hub: &Hub<A>,
     ) -> Result<pipeline::RenderPipeline<A>, pipeline::CreateRenderPipelineError> {
@@ -2670,9 +3376,20 @@ impl<A: HalApi> Device<A> {
             }
         }
 
+        self.require_rendering()?;
+
+        let vertex_state = match desc.vertex {
+            pipeline::RenderPipelineVertexProcessor::Vertex(vertex) => Some(vertex),
+            pipeline::RenderPipelineVertexProcessor::Mesh { .. } => {
+                self.require_features(wgt::Features::EXPERIMENTAL_MESH_SHADER)?;
+                None
+            }
+        };
+        let vertex_buffer_layouts = vertex_state.map_or(&[][..], |vertex| &vertex.buffers);
+
         let mut shader_binding_sizes = FastHashMap::default();
 
-        let num_attachments = desc.fragment.as_ref().map(|f| f.targets.len()).unwrap_or(0);
+        let num_attachments = desc.fragment.map(|f| f.targets.len()).unwrap_or(0);
         if num_attachments > hal::MAX_COLOR_ATTACHMENTS {
             return Err(pipeline::CreateRenderPipelineError::ColorAttachment(
                 command::ColorAttachmentError::TooMany {
@@ -2682,11 +3399,8 @@ impl<A: HalApi> Device<A> {
             ));
         }
 
-        let color_targets = desc
-            .fragment
-            .as_ref()
-            .map_or(&[][..], |fragment| &fragment.targets);
-        let depth_stencil_state = desc.depth_stencil.as_ref();
+        let color_targets = desc.fragment.map_or(&[][..], |fragment| &fragment.targets);
+        let depth_stencil_state = desc.depth_stencil;
 
         let cts: ArrayVec<_, { hal::MAX_COLOR_ATTACHMENTS }> =
             color_targets.iter().filter_map(|x| x.as_ref()).collect();
@@ -2703,12 +3417,12 @@ impl<A: HalApi> Device<A> {
         let mut io = validation::StageIo::default();
         let mut validated_stages = wgt::ShaderStages::empty();
 
-        let mut vertex_steps = Vec::with_capacity(desc.vertex.buffers.len());
-        let mut vertex_buffers = Vec::with_capacity(desc.vertex.buffers.len());
+        let mut vertex_steps = Vec::with_capacity(vertex_buffer_layouts.len());
+        let mut vertex_buffers = Vec::with_capacity(vertex_buffer_layouts.len());
         let mut total_attributes = 0;
         let mut shader_expects_dual_source_blending = false;
         let mut pipeline_expects_dual_source_blending = false;
-        for (i, vb_state) in desc.vertex.buffers.iter().enumerate() {
+        for (i, vb_state) in vertex_buffer_layouts.iter().enumerate() {
             vertex_steps.push(pipeline::VertexStep {
                 stride: vb_state.array_stride,
                 mode: vb_state.step_mode,
@@ -2791,6 +3505,106 @@ impl<A: HalApi> Device<A> {
             );
         }
 
+        let vertex_capture_layouts = vertex_state.map_or(&[][..], |vertex| &vertex.capture_buffers);
+        let mut vertex_capture = Vec::with_capacity(vertex_capture_layouts.len());
+        if !vertex_capture_layouts.is_empty() {
+            self.require_features(wgt::Features::VERTEX_CAPTURE)?;
+
+            if vertex_capture_layouts.len() > hal::MAX_VERTEX_CAPTURE_BUFFERS {
+                return Err(
+                    pipeline::CreateRenderPipelineError::TooManyVertexCaptureBuffers {
+                        given: vertex_capture_layouts.len() as u32,
+                        limit: hal::MAX_VERTEX_CAPTURE_BUFFERS as u32,
+                    },
+                );
+            }
+            if desc.primitive.topology.is_strip() {
+                return Err(
+                    pipeline::CreateRenderPipelineError::VertexCaptureStripTopology(
+                        desc.primitive.topology,
+                    ),
+                );
+            }
+
+            let mut captured_locations = FastHashMap::default();
+            for (i, vc_state) in vertex_capture_layouts.iter().enumerate() {
+                if vc_state.array_stride == 0
+                    || vc_state.array_stride % 4 != 0
+                    || vc_state.array_stride > wgt::MAX_VERTEX_CAPTURE_STRIDE
+                {
+                    return Err(
+                        pipeline::CreateRenderPipelineError::InvalidVertexCaptureStride {
+                            index: i as u32,
+                            stride: vc_state.array_stride,
+                        },
+                    );
+                }
+
+                let mut ranges = Vec::with_capacity(vc_state.attributes.len());
+                for attribute in vc_state.attributes.iter() {
+                    match attribute.format {
+                        wgt::VertexFormat::Float32
+                        | wgt::VertexFormat::Float32x2
+                        | wgt::VertexFormat::Float32x3
+                        | wgt::VertexFormat::Float32x4
+                        | wgt::VertexFormat::Uint32
+                        | wgt::VertexFormat::Uint32x2
+                        | wgt::VertexFormat::Uint32x3
+                        | wgt::VertexFormat::Uint32x4
+                        | wgt::VertexFormat::Sint32
+                        | wgt::VertexFormat::Sint32x2
+                        | wgt::VertexFormat::Sint32x3
+                        | wgt::VertexFormat::Sint32x4 => {}
+                        format => return Err(
+                            pipeline::CreateRenderPipelineError::UnsupportedVertexCaptureFormat {
+                                location: attribute.shader_location,
+                                format,
+                            },
+                        ),
+                    }
+
+                    let end = attribute.offset + attribute.format.size();
+                    if attribute.offset % 4 != 0 || end > vc_state.array_stride {
+                        return Err(
+                            pipeline::CreateRenderPipelineError::InvalidVertexCaptureOffset {
+                                location: attribute.shader_location,
+                                offset: attribute.offset,
+                            },
+                        );
+                    }
+
+                    if captured_locations
+                        .insert(attribute.shader_location, attribute.format)
+                        .is_some()
+                    {
+                        return Err(
+                            pipeline::CreateRenderPipelineError::VertexCaptureLocationClash(
+                                attribute.shader_location,
+                            ),
+                        );
+                    }
+
+                    ranges.push((attribute.offset..end, attribute.shader_location));
+                }
+
+                ranges.sort_by_key(|(range, _)| range.start);
+                for pair in ranges.windows(2) {
+                    if pair[0].0.end > pair[1].0.start {
+                        return Err(
+                            pipeline::CreateRenderPipelineError::VertexCaptureAttributesOverlap(
+                                pair[0].1, pair[1].1,
+                            ),
+                        );
+                    }
+                }
+
+                vertex_capture.push(hal::VertexCaptureBufferLayout {
+                    array_stride: vc_state.array_stride,
+                    attributes: vc_state.attributes.as_ref(),
+                });
+            }
+        }
+
         if desc.primitive.unclipped_depth {
             self.require_features(wgt::Features::DEPTH_CLIP_CONTROL)?;
         }
@@ -2954,6 +3768,22 @@ impl<A: HalApi> Device<A> {
             None => None,
         };
 
+        let cache = match desc.cache {
+            Some(cache_id) => {
+                let cache = hub
+                    .pipeline_caches
+                    .get(cache_id)
+                    .map_err(|_| pipeline::CreateRenderPipelineError::InvalidCache)?;
+
+                if cache.device.as_info().id() != self.as_info().id() {
+                    return Err(DeviceError::WrongDevice.into());
+                }
+
+                Some(cache)
+            }
+            None => None,
+        };
+
         let mut binding_layout_source = match pipeline_layout {
             Some(ref pipeline_layout) => {
                 validation::BindingLayoutSource::Provided(pipeline_layout.get_binding_maps())
@@ -2969,22 +3799,34 @@ impl<A: HalApi> Device<A> {
             sc
         };
 
-        let vertex_shader_module;
-        let vertex_stage = {
-            let stage_desc = &desc.vertex.stage;
-            let stage = wgt::ShaderStages::VERTEX;
+        // The stages generating the primitives, in order.
+        let geometry_stage_descs: ArrayVec<_, 2> = match desc.vertex {
+            pipeline::RenderPipelineVertexProcessor::Vertex(vertex) => {
+                [(&vertex.stage, wgt::ShaderStages::VERTEX)]
+                    .into_iter()
+                    .collect()
+            }
+            pipeline::RenderPipelineVertexProcessor::Mesh { task, mesh } => task
+                .map(|task| (task, wgt::ShaderStages::TASK))
+                .into_iter()
+                .chain(iter::once((mesh, wgt::ShaderStages::MESH)))
+                .collect(),
+        };
+        let geometry_stage = geometry_stage_descs.last().unwrap().1;
 
-            vertex_shader_module = hub.shader_modules.get(stage_desc.module).map_err(|_| {
+        let mut geometry_shader_modules = ArrayVec::<_, 2>::new();
+        for &(stage_desc, stage) in geometry_stage_descs.iter() {
+            let shader_module = hub.shader_modules.get(stage_desc.module).map_err(|_| {
                 pipeline::CreateRenderPipelineError::Stage {
                     stage,
                     error: validation::StageError::InvalidModule,
                 }
             })?;
-            if vertex_shader_module.device.as_info().id() != self.as_info().id() {
+            if shader_module.device.as_info().id() != self.as_info().id() {
                 return Err(DeviceError::WrongDevice.into());
             }
 
-            if let Some(ref interface) = vertex_shader_module.interface {
+            if let Some(ref interface) = shader_module.interface {
                 io = interface
                     .check_stage(
                         &mut binding_layout_source,
@@ -2992,21 +3834,39 @@ impl<A: HalApi> Device<A> {
                         &stage_desc.entry_point,
                         stage,
                         io,
-                        desc.depth_stencil.as_ref().map(|d| d.depth_compare),
+                        desc.depth_stencil.map(|d| d.depth_compare),
                     )
                     .map_err(|error| pipeline::CreateRenderPipelineError::Stage { stage, error })?;
                 validated_stages |= stage;
             }
 
-            hal::ProgrammableStage {
-                module: vertex_shader_module.raw(),
-                entry_point: stage_desc.entry_point.as_ref(),
+            geometry_shader_modules.push(shader_module);
+        }
+
+        // The outputs of the vertex stage are only known if it was validated.
+        if validated_stages.contains(wgt::ShaderStages::VERTEX) {
+            for attribute in vertex_capture_layouts
+                .iter()
+                .flat_map(|vc_state| vc_state.attributes.iter())
+            {
+                let output = io.get(&attribute.shader_location).ok_or(
+                    pipeline::CreateRenderPipelineError::MissingVertexCaptureOutput(
+                        attribute.shader_location,
+                    ),
+                )?;
+                validation::check_vertex_capture_format(attribute.format, &output.ty).map_err(
+                    |_| pipeline::CreateRenderPipelineError::IncompatibleVertexCaptureFormat {
+                        location: attribute.shader_location,
+                        format: attribute.format,
+                        shader: output.ty,
+                    },
+                )?;
             }
-        };
+        }
 
         let mut fragment_shader_module = None;
         let fragment_stage = match desc.fragment {
-            Some(ref fragment_state) => {
+            Some(fragment_state) => {
                 let stage = wgt::ShaderStages::FRAGMENT;
 
                 let shader_module = fragment_shader_module.insert(
@@ -3018,7 +3878,7 @@ impl<A: HalApi> Device<A> {
                         })?,
                 );
 
-                if validated_stages == wgt::ShaderStages::VERTEX {
+                if validated_stages.contains(geometry_stage) {
                     if let Some(ref interface) = shader_module.interface {
                         io = interface
                             .check_stage(
@@ -3027,7 +3887,7 @@ impl<A: HalApi> Device<A> {
                                 &fragment_state.stage.entry_point,
                                 stage,
                                 io,
-                                desc.depth_stencil.as_ref().map(|d| d.depth_compare),
+                                desc.depth_stencil.map(|d| d.depth_compare),
                             )
                             .map_err(|error| pipeline::CreateRenderPipelineError::Stage {
                                 stage,
@@ -3095,7 +3955,7 @@ impl<A: HalApi> Device<A> {
         }
         let last_stage = match desc.fragment {
             Some(_) => wgt::ShaderStages::FRAGMENT,
-            None => wgt::ShaderStages::VERTEX,
+            None => geometry_stage,
         };
         if desc.layout.is_none() && !validated_stages.contains(last_stage) {
             return Err(pipeline::ImplicitLayoutError::ReflectionError(last_stage).into());
@@ -3138,23 +3998,59 @@ impl<A: HalApi> Device<A> {
         let late_sized_buffer_groups =
             Device::make_late_sized_buffer_groups(&shader_binding_sizes, &pipeline_layout);
 
-        let pipeline_desc = hal::RenderPipelineDescriptor {
-            label: desc.label.to_hal(self.instance_flags),
-            layout: pipeline_layout.raw(),
-            vertex_buffers: &vertex_buffers,
-            vertex_stage,
-            primitive: desc.primitive,
-            depth_stencil: desc.depth_stencil.clone(),
-            multisample: desc.multisample,
-            fragment_stage,
-            color_targets,
-            multiview: desc.multiview,
-        };
-        let raw = unsafe {
-            self.raw
-                .as_ref()
-                .unwrap()
-                .create_render_pipeline(&pipeline_desc)
+        let mut geometry_stages: ArrayVec<_, 2> = geometry_stage_descs
+            .iter()
+            .zip(geometry_shader_modules.iter())
+            .map(|(&(stage_desc, _), shader_module)| hal::ProgrammableStage {
+                module: shader_module.raw(),
+                entry_point: stage_desc.entry_point.as_ref(),
+            })
+            .collect();
+        let raw = match desc.vertex {
+            pipeline::RenderPipelineVertexProcessor::Vertex(_) => {
+                let pipeline_desc = hal::RenderPipelineDescriptor {
+                    label: desc.label.to_hal(self.instance_flags),
+                    layout: pipeline_layout.raw(),
+                    vertex_buffers: &vertex_buffers,
+                    vertex_stage: geometry_stages.pop().unwrap(),
+                    vertex_capture: &vertex_capture,
+                    primitive: desc.primitive,
+                    depth_stencil: desc.depth_stencil.cloned(),
+                    multisample: desc.multisample,
+                    fragment_stage,
+                    color_targets,
+                    multiview: desc.multiview,
+                    cache: cache.as_ref().map(|cache| cache.raw()),
+                };
+                unsafe {
+                    self.raw
+                        .as_ref()
+                        .unwrap()
+                        .create_render_pipeline(&pipeline_desc)
+                }
+            }
+            pipeline::RenderPipelineVertexProcessor::Mesh { .. } => {
+                let mesh_stage = geometry_stages.pop().unwrap();
+                let pipeline_desc = hal::MeshPipelineDescriptor {
+                    label: desc.label.to_hal(self.instance_flags),
+                    layout: pipeline_layout.raw(),
+                    task_stage: geometry_stages.pop(),
+                    mesh_stage,
+                    primitive: desc.primitive,
+                    depth_stencil: desc.depth_stencil.cloned(),
+                    multisample: desc.multisample,
+                    fragment_stage,
+                    color_targets,
+                    multiview: desc.multiview,
+                    cache: cache.as_ref().map(|cache| cache.raw()),
+                };
+                unsafe {
+                    self.raw
+                        .as_ref()
+                        .unwrap()
+                        .create_mesh_pipeline(&pipeline_desc)
+                }
+            }
         }
         .map_err(|err| match err {
             hal::PipelineError::Device(error) => {
@@ -3185,6 +4081,9 @@ impl<A: HalApi> Device<A> {
         };
 
         let mut flags = pipeline::PipelineFlags::empty();
+        if vertex_state.is_none() {
+            flags |= pipeline::PipelineFlags::MESH_SHADER;
+        }
         for state in color_targets.iter().filter_map(|s| s.as_ref()) {
             if let Some(ref bs) = state.blend {
                 if bs.color.uses_constant() | bs.alpha.uses_constant() {
@@ -3206,7 +4105,7 @@ impl<A: HalApi> Device<A> {
 
         let shader_modules = {
             let mut shader_modules = ArrayVec::new();
-            shader_modules.push(vertex_shader_module);
+            shader_modules.extend(geometry_shader_modules);
             shader_modules.extend(fragment_shader_module);
             shader_modules
         };
@@ -3220,6 +4119,18 @@ impl<A: HalApi> Device<A> {
             flags,
             strip_index_format: desc.primitive.strip_index_format,
             vertex_steps,
+            vertex_capture: (!vertex_capture.is_empty()).then(|| pipeline::VertexCapture {
+                strides: vertex_capture
+                    .iter()
+                    .map(|layout| layout.array_stride)
+                    .collect(),
+                vertices_per_primitive: match desc.primitive.topology {
+                    wgt::PrimitiveTopology::PointList => 1,
+                    wgt::PrimitiveTopology::LineList | wgt::PrimitiveTopology::LineStrip => 2,
+                    wgt::PrimitiveTopology::TriangleList
+                    | wgt::PrimitiveTopology::TriangleStrip => 3,
+                },
+            }),
             late_sized_buffer_groups,
             info: ResourceInfo::new(desc.label.borrow_or_default()),
         };
@@ -3241,6 +4152,19 @@ impl<A: HalApi> Device<A> {
         {
             format_features.flags.set(tfsc::FILTERABLE, false);
         }
+        if !self
+            .features
+            .contains(wgt::Features::SHADING_RATE_ATTACHMENT)
+        {
+            format_features
+                .allowed_usages
+                .remove(wgt::TextureUsages::SHADING_RATE_ATTACHMENT);
+        }
+        if !self.features.contains(wgt::Features::TILED_TEXTURE) {
+            format_features
+                .allowed_usages
+                .remove(wgt::TextureUsages::TILED);
+        }
         format_features
     }
 
@@ -3274,22 +4198,11 @@ impl<A: HalApi> Device<A> {
     ) -> Result<(), WaitIdleError> {
         let guard = self.fence.read();
         let fence = guard.as_ref().unwrap();
-        let last_done_index = unsafe {
-            self.raw
-                .as_ref()
-                .unwrap()
-                .get_fence_value(fence)
-                .map_err(DeviceError::from)?
-        };
+        let last_done_index = self.last_done_index(fence).map_err(DeviceError::from)?;
         if last_done_index < submission_index {
             log::info!("Waiting for submission {:?}", submission_index);
-            unsafe {
-                self.raw
-                    .as_ref()
-                    .unwrap()
-                    .wait(fence, submission_index, !0)
-                    .map_err(DeviceError::from)?
-            };
+            self.wait_for_submission_index(fence, submission_index, !0)
+                .map_err(DeviceError::from)?;
             drop(guard);
             let closures = self.lock_life().triage_submissions(
                 submission_index,
@@ -3303,6 +4216,36 @@ impl<A: HalApi> Device<A> {
         Ok(())
     }
 
+    /// # Safety
+    ///
+    /// The `data` field on `desc` must have previously been returned from
+    /// [`crate::global::Global::pipeline_cache_get_data`].
+    pub(crate) unsafe fn create_pipeline_cache(
+        self: &Arc<Self>,
+        desc: &pipeline::PipelineCacheDescriptor,
+    ) -> Result<pipeline::PipelineCache<A>, pipeline::CreatePipelineCacheError> {
+        self.require_features(wgt::Features::PIPELINE_CACHE)?;
+
+        let mut cache_desc = hal::PipelineCacheDescriptor {
+            label: desc.label.to_hal(self.instance_flags),
+            data: desc.data.as_deref(),
+        };
+        let raw = match unsafe { self.raw().create_pipeline_cache(&cache_desc) } {
+            Err(hal::PipelineCacheError::Validation) if desc.fallback => {
+                log::warn!("Pipeline cache data was rejected, creating an empty cache instead");
+                cache_desc.data = None;
+                unsafe { self.raw().create_pipeline_cache(&cache_desc) }
+            }
+            result => result,
+        }?;
+
+        Ok(pipeline::PipelineCache {
+            raw: Some(raw),
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default()),
+        })
+    }
+
     pub(crate) fn create_query_set(
         self: &Arc<Self>,
         desc: &resource::QuerySetDescriptor,
@@ -3339,6 +4282,83 @@ impl<A: HalApi> Device<A> {
         })
     }
 
+    pub(crate) fn create_tile_pool(
+        self: &Arc<Self>,
+        desc: &resource::TilePoolDescriptor,
+    ) -> Result<TilePool<A>, resource::CreateTilePoolError> {
+        self.require_features(wgt::Features::TILED_TEXTURE)?;
+
+        if desc.tile_count == 0 {
+            return Err(resource::CreateTilePoolError::ZeroCount);
+        }
+
+        let hal_desc = desc.map_label(|label| label.to_hal(self.instance_flags));
+        let raw = unsafe { self.raw().create_tile_pool(&hal_desc) }.map_err(DeviceError::from)?;
+        Ok(TilePool {
+            raw: Some(raw),
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default()),
+            tile_count: desc.tile_count,
+            initialized_tiles: Mutex::new(bit_vec::BitVec::from_elem(
+                desc.tile_count as usize,
+                false,
+            )),
+        })
+    }
+
+    pub(crate) fn create_memory_heap(
+        self: &Arc<Self>,
+        desc: &resource::MemoryHeapDescriptor,
+    ) -> Result<MemoryHeap<A>, resource::CreateMemoryHeapError> {
+        self.require_features(wgt::Features::MEMORY_HEAPS)?;
+        if desc.size == 0 {
+            return Err(resource::CreateMemoryHeapError::ZeroSize);
+        }
+
+        let hal_desc = desc.map_label(|label| label.to_hal(self.instance_flags));
+        let raw = unsafe { self.raw().create_memory_heap(&hal_desc) }.map_err(DeviceError::from)?;
+
+        Ok(MemoryHeap {
+            raw: Some(raw),
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default()),
+            size: desc.size,
+            memory_type: desc.memory_type,
+        })
+    }
+
+    pub(crate) fn create_external_fence(
+        self: &Arc<Self>,
+        desc: &resource::ExternalFenceDescriptor,
+    ) -> Result<ExternalFence<A>, resource::CreateExternalFenceError> {
+        self.require_features(wgt::Features::EXTERNAL_FENCE)?;
+
+        let raw = unsafe { self.raw().create_external_fence() }.map_err(DeviceError::from)?;
+
+        Ok(ExternalFence {
+            raw: Some(raw),
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default()),
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn import_external_fence(
+        self: &Arc<Self>,
+        desc: &resource::ExternalFenceDescriptor,
+        handle: wgt::ExternalFenceHandle,
+    ) -> Result<ExternalFence<A>, resource::CreateExternalFenceError> {
+        self.require_features(wgt::Features::EXTERNAL_FENCE)?;
+
+        let raw = unsafe { self.raw().import_external_fence(handle) }.map_err(DeviceError::from)?;
+
+        Ok(ExternalFence {
+            raw: Some(raw),
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default()),
+        })
+    }
+
     pub(crate) fn lose(&self, message: &str) {
         // Follow the steps at https://gpuweb.github.io/gpuweb/#lose-the-device.
 
@@ -3405,13 +4425,10 @@ impl<A: HalApi> Device<A> {
     pub(crate) fn prepare_to_die(&self) {
         self.pending_writes.lock().as_mut().unwrap().deactivate();
         let current_index = self.active_submission_index.load(Ordering::Relaxed);
-        if let Err(error) = unsafe {
+        if let Err(error) = {
             let fence = self.fence.read();
             let fence = fence.as_ref().unwrap();
-            self.raw
-                .as_ref()
-                .unwrap()
-                .wait(fence, current_index, CLEANUP_WAIT_MS)
+            self.wait_for_submission_index(fence, current_index, CLEANUP_WAIT_MS)
         } {
             log::error!("failed to wait for the device: {error}");
         }
@@ -92,12 +92,23 @@ pub enum Action<'a> {
         implicit_context: Option<super::ImplicitPipelineContext>,
     },
     DestroyComputePipeline(id::ComputePipelineId),
+    CreatePipelineCache {
+        id: id::PipelineCacheId,
+        desc: crate::pipeline::PipelineCacheDescriptor<'a>,
+    },
+    DestroyPipelineCache(id::PipelineCacheId),
     CreateRenderPipeline {
         id: id::RenderPipelineId,
         desc: crate::pipeline::RenderPipelineDescriptor<'a>,
         #[cfg_attr(feature = "replay", serde(default))]
         implicit_context: Option<super::ImplicitPipelineContext>,
     },
+    CreateMeshPipeline {
+        id: id::RenderPipelineId,
+        desc: crate::pipeline::MeshPipelineDescriptor<'a>,
+        #[cfg_attr(feature = "replay", serde(default))]
+        implicit_context: Option<super::ImplicitPipelineContext>,
+    },
     DestroyRenderPipeline(id::RenderPipelineId),
     CreateRenderBundle {
         id: id::RenderBundleId,
@@ -110,6 +121,44 @@ pub enum Action<'a> {
         desc: crate::resource::QuerySetDescriptor<'a>,
     },
     DestroyQuerySet(id::QuerySetId),
+    CreateTilePool {
+        id: id::TilePoolId,
+        desc: crate::resource::TilePoolDescriptor<'a>,
+    },
+    DestroyTilePool(id::TilePoolId),
+    CreateMemoryHeap {
+        id: id::MemoryHeapId,
+        desc: crate::resource::MemoryHeapDescriptor<'a>,
+    },
+    DestroyMemoryHeap(id::MemoryHeapId),
+    CreateExternalFence {
+        id: id::ExternalFenceId,
+        desc: crate::resource::ExternalFenceDescriptor<'a>,
+    },
+    DestroyExternalFence(id::ExternalFenceId),
+    CreatePlacedBuffer {
+        id: id::BufferId,
+        desc: crate::resource::BufferDescriptor<'a>,
+        heap: id::MemoryHeapId,
+        offset: wgt::BufferAddress,
+    },
+    CreatePlacedTexture {
+        id: id::TextureId,
+        desc: crate::resource::TextureDescriptor<'a>,
+        heap: id::MemoryHeapId,
+        offset: wgt::BufferAddress,
+    },
+    CreateBlas {
+        id: id::BlasId,
+        desc: crate::ray_tracing::BlasDescriptor<'a>,
+        sizes: wgt::BlasGeometrySizeDescriptors,
+    },
+    DestroyBlas(id::BlasId),
+    CreateTlas {
+        id: id::TlasId,
+        desc: crate::ray_tracing::TlasDescriptor<'a>,
+    },
+    DestroyTlas(id::TlasId),
     WriteBuffer {
         id: id::BufferId,
         data: FileName,
@@ -122,6 +171,10 @@ pub enum Action<'a> {
         layout: wgt::ImageDataLayout,
         size: wgt::Extent3d,
     },
+    BindTexturePages {
+        texture: id::TextureId,
+        bindings: Vec<crate::device::queue::TextureTileBinding>,
+    },
     Submit(crate::SubmissionIndex, Vec<Command>),
 }
 
@@ -151,6 +204,12 @@ pub enum Command {
         dst: crate::command::ImageCopyTexture,
         size: wgt::Extent3d,
     },
+    ResolveTexture {
+        src: crate::command::ImageCopyTexture,
+        dst: crate::command::ImageCopyTexture,
+        size: wgt::Extent3d,
+        mode: wgt::ResolveMode,
+    },
     ClearBuffer {
         dst: id::BufferId,
         offset: wgt::BufferAddress,
@@ -174,6 +233,7 @@ pub enum Command {
     PushDebugGroup(String),
     PopDebugGroup,
     InsertDebugMarker(String),
+    AliasingBarrier,
     RunComputePass {
         base: crate::command::BasePass<crate::command::ComputeCommand>,
         timestamp_writes: Option<crate::command::ComputePassTimestampWrites>,
@@ -184,6 +244,14 @@ pub enum Command {
         target_depth_stencil: Option<crate::command::RenderPassDepthStencilAttachment>,
         timestamp_writes: Option<crate::command::RenderPassTimestampWrites>,
         occlusion_query_set_id: Option<id::QuerySetId>,
+        pipeline_statistics_query: Option<crate::command::RenderPassPipelineStatisticsQuery>,
+        shading_rate_attachment: Option<crate::command::RenderPassShadingRateAttachment>,
+        resuming: bool,
+        suspending: bool,
+    },
+    BuildAccelerationStructures {
+        blas: Vec<crate::ray_tracing::BlasBuildEntry>,
+        tlas: Vec<crate::ray_tracing::TlasBuildEntry>,
     },
 }
 
@@ -0,0 +1,205 @@
+/*! A background thread timing queue submissions.
+
+Once enabled with [`Global::device_set_watchdog`], every submission is
+recorded together with the debug markers of its command buffers. A thread
+watches the device's fence, and calls the user's callback once for every
+submission that is still running after the threshold elapsed. Choosing a
+threshold below the operating system's GPU timeout (two seconds on Windows)
+allows reporting shaders that never terminate before the device is lost.
+
+[`Global::device_set_watchdog`]: crate::global::Global::device_set_watchdog
+*/
+
+use crate::{device::Device, hal_api::HalApi, SubmissionIndex};
+
+use parking_lot::Mutex;
+
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+pub type WatchdogCallback = Box<dyn Fn(wgt::SubmissionTimeout) + Send + 'static>;
+
+/// Shortest interval between two checks of the fence.
+#[cfg(send_sync)]
+const MIN_CHECK_INTERVAL: Duration = Duration::from_millis(1);
+
+#[cfg_attr(not(send_sync), allow(dead_code))]
+struct PendingSubmission {
+    index: SubmissionIndex,
+    submitted_at: Instant,
+    debug_markers: Vec<String>,
+    reported: bool,
+}
+
+#[cfg_attr(not(send_sync), allow(dead_code))]
+struct Shared {
+    threshold: Duration,
+    stopped: AtomicBool,
+    pending: Mutex<VecDeque<PendingSubmission>>,
+}
+
+#[cfg_attr(not(send_sync), allow(dead_code))]
+impl Shared {
+    /// Forgets the submissions up to `last_done`, and returns the reports of the
+    /// submissions that exceeded the threshold for the first time.
+    fn check(&self, last_done: SubmissionIndex, now: Instant) -> Vec<wgt::SubmissionTimeout> {
+        let mut pending = self.pending.lock();
+        while pending
+            .front()
+            .map_or(false, |submission| submission.index <= last_done)
+        {
+            pending.pop_front();
+        }
+
+        let mut timeouts = Vec::new();
+        for submission in pending.iter_mut() {
+            let elapsed = now.duration_since(submission.submitted_at);
+            if elapsed < self.threshold {
+                // Later submissions were made even more recently.
+                break;
+            }
+            if !submission.reported {
+                submission.reported = true;
+                timeouts.push(wgt::SubmissionTimeout {
+                    elapsed,
+                    debug_markers: submission.debug_markers.clone(),
+                });
+            }
+        }
+        timeouts
+    }
+}
+
+/// Handle to a running watchdog thread, stopping it when dropped.
+pub(crate) struct Watchdog {
+    shared: Arc<Shared>,
+}
+
+impl Watchdog {
+    #[cfg(send_sync)]
+    pub(crate) fn start<A: HalApi>(
+        device: &Arc<Device<A>>,
+        threshold: Duration,
+        callback: WatchdogCallback,
+    ) -> io::Result<Self> {
+        let shared = Arc::new(Shared {
+            threshold,
+            stopped: AtomicBool::new(false),
+            pending: Mutex::new(VecDeque::new()),
+        });
+        let device = Arc::downgrade(device);
+        let thread_shared = shared.clone();
+        thread::Builder::new()
+            .name("wgpu-core watchdog".to_string())
+            .spawn(move || run(device, thread_shared, callback))?;
+        Ok(Self { shared })
+    }
+
+    #[cfg(not(send_sync))]
+    pub(crate) fn start<A: HalApi>(
+        _device: &Arc<Device<A>>,
+        _threshold: Duration,
+        _callback: WatchdogCallback,
+    ) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the device cannot be shared with another thread",
+        ))
+    }
+
+    /// Starts timing the submission `index`.
+    pub(crate) fn track_submission(&self, index: SubmissionIndex, debug_markers: Vec<String>) {
+        self.shared.pending.lock().push_back(PendingSubmission {
+            index,
+            submitted_at: Instant::now(),
+            debug_markers,
+            reported: false,
+        });
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        // The thread is not joined, as the device may be dropped from it.
+        self.shared.stopped.store(true, Ordering::Release);
+    }
+}
+
+#[cfg(send_sync)]
+fn run<A: HalApi>(
+    device: std::sync::Weak<Device<A>>,
+    shared: Arc<Shared>,
+    callback: WatchdogCallback,
+) {
+    let interval = (shared.threshold / 4).max(MIN_CHECK_INTERVAL);
+    loop {
+        thread::sleep(interval);
+        if shared.stopped.load(Ordering::Acquire) {
+            break;
+        }
+
+        let last_done = {
+            let Some(device) = device.upgrade() else {
+                break;
+            };
+            let fence = device.fence.read();
+            let Some(fence) = fence.as_ref() else {
+                break;
+            };
+            match device.last_done_index(fence) {
+                Ok(value) => value,
+                Err(err) => {
+                    log::warn!("Watchdog failed to read the device fence: {err}");
+                    break;
+                }
+            }
+        };
+
+        for timeout in shared.check(last_done, Instant::now()) {
+            callback(timeout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_once() {
+        let shared = Shared {
+            threshold: Duration::from_millis(100),
+            stopped: AtomicBool::new(false),
+            pending: Mutex::new(VecDeque::new()),
+        };
+        let start = Instant::now();
+        for index in 1..=3 {
+            shared.pending.lock().push_back(PendingSubmission {
+                index,
+                submitted_at: start + Duration::from_millis(index * 50),
+                debug_markers: vec![format!("submission {index}")],
+                reported: false,
+            });
+        }
+
+        let timeouts = shared.check(1, start + Duration::from_millis(210));
+        assert_eq!(timeouts.len(), 1);
+        assert_eq!(timeouts[0].debug_markers, ["submission 2"]);
+        assert_eq!(timeouts[0].elapsed, Duration::from_millis(110));
+
+        let timeouts = shared.check(1, start + Duration::from_millis(260));
+        assert_eq!(timeouts.len(), 1);
+        assert_eq!(timeouts[0].debug_markers, ["submission 3"]);
+
+        assert!(shared.check(3, start + Duration::from_secs(1)).is_empty());
+        assert!(shared.pending.lock().is_empty());
+    }
+}
@@ -10,7 +10,6 @@ use crate::{
     instance::{Instance, Surface},
     registry::{Registry, RegistryReport},
     resource_log,
-    storage::Element,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -142,34 +141,34 @@ impl<G: GlobalIdentityHandlerFactory> Drop for Global<G> {
     fn drop(&mut self) {
         profiling::scope!("Global::drop");
         resource_log!("Global::drop");
-        let mut surfaces_locked = self.surfaces.write();
-
-        // destroy hubs before the instance gets dropped
-        #[cfg(vulkan)]
-        {
-            self.hubs.vulkan.clear(&surfaces_locked, true);
-        }
-        #[cfg(metal)]
-        {
-            self.hubs.metal.clear(&surfaces_locked, true);
-        }
-        #[cfg(dx12)]
         {
-            self.hubs.dx12.clear(&surfaces_locked, true);
-        }
-        #[cfg(gles)]
-        {
-            self.hubs.gl.clear(&surfaces_locked, true);
+            let surfaces_locked = self.surfaces.read();
+
+            // destroy hubs before the instance gets dropped
+            #[cfg(vulkan)]
+            {
+                self.hubs.vulkan.clear(&surfaces_locked, true);
+            }
+            #[cfg(metal)]
+            {
+                self.hubs.metal.clear(&surfaces_locked, true);
+            }
+            #[cfg(dx12)]
+            {
+                self.hubs.dx12.clear(&surfaces_locked, true);
+            }
+            #[cfg(gles)]
+            {
+                self.hubs.gl.clear(&surfaces_locked, true);
+            }
         }
 
         // destroy surfaces
-        for element in surfaces_locked.map.drain(..) {
-            if let Element::Occupied(arc_surface, _) = element {
-                if let Some(surface) = Arc::into_inner(arc_surface) {
-                    self.instance.destroy_surface(surface);
-                } else {
-                    panic!("Surface cannot be destroyed because is still in use");
-                }
+        for arc_surface in self.surfaces.write().drain() {
+            if let Some(surface) = Arc::into_inner(arc_surface) {
+                self.instance.destroy_surface(surface);
+            } else {
+                panic!("Surface cannot be destroyed because is still in use");
             }
         }
     }
@@ -118,10 +118,13 @@ use crate::{
     id,
     identity::GlobalIdentityHandlerFactory,
     instance::{Adapter, HalSurface, Surface},
-    pipeline::{ComputePipeline, RenderPipeline, ShaderModule},
+    pipeline::{ComputePipeline, PipelineCache, RenderPipeline, ShaderModule},
     registry::{Registry, RegistryReport},
-    resource::{Buffer, QuerySet, Sampler, StagingBuffer, Texture, TextureView},
-    storage::{Element, Storage},
+    resource::{
+        Blas, Buffer, ExternalFence, MemoryHeap, QuerySet, Sampler, StagingBuffer, Texture,
+        TextureView, TilePool, Tlas,
+    },
+    storage::Storage,
 };
 use std::fmt::Debug;
 
@@ -138,11 +141,17 @@ pub struct HubReport {
     pub render_bundles: RegistryReport,
     pub render_pipelines: RegistryReport,
     pub compute_pipelines: RegistryReport,
+    pub pipeline_caches: RegistryReport,
     pub query_sets: RegistryReport,
     pub buffers: RegistryReport,
     pub textures: RegistryReport,
+    pub tile_pools: RegistryReport,
+    pub memory_heaps: RegistryReport,
+    pub external_fences: RegistryReport,
     pub texture_views: RegistryReport,
     pub samplers: RegistryReport,
+    pub blas_s: RegistryReport,
+    pub tlas_s: RegistryReport,
 }
 
 impl HubReport {
@@ -160,12 +169,13 @@ impl HubReport {
 /// ## Locking
 ///
 /// Each field in `Hub` is a [`Registry`] holding all the values of a
-/// particular type of resource, all protected by a single RwLock.
-/// So for example, to access any [`Buffer`], you must acquire a read
-/// lock on the `Hub`s entire buffers registry. The lock guard
-/// gives you access to the `Registry`'s [`Storage`], which you can
-/// then index with the buffer's id. (Yes, this design causes
-/// contention; see [#2272].)
+/// particular type of resource, split into shards that are each
+/// protected by their own RwLock. To access a single [`Buffer`], call
+/// [`Registry::get`], which only locks the shard of the buffer's id.
+/// To look up many buffers in a row, acquire a read lock on all the
+/// shards of the `Hub`s buffers registry with [`Registry::read`]. The
+/// returned [`Storage`] can then be indexed with the buffers' ids.
+/// (Sharding reduces the contention described in [#2272].)
 ///
 /// But most `wgpu` operations require access to several different
 /// kinds of resource, so you often need to hold locks on several
@@ -188,12 +198,18 @@ pub struct Hub<A: HalApi> {
     pub render_bundles: Registry<id::RenderBundleId, RenderBundle<A>>,
     pub render_pipelines: Registry<id::RenderPipelineId, RenderPipeline<A>>,
     pub compute_pipelines: Registry<id::ComputePipelineId, ComputePipeline<A>>,
+    pub pipeline_caches: Registry<id::PipelineCacheId, PipelineCache<A>>,
     pub query_sets: Registry<id::QuerySetId, QuerySet<A>>,
     pub buffers: Registry<id::BufferId, Buffer<A>>,
     pub staging_buffers: Registry<id::StagingBufferId, StagingBuffer<A>>,
     pub textures: Registry<id::TextureId, Texture<A>>,
+    pub tile_pools: Registry<id::TilePoolId, TilePool<A>>,
+    pub memory_heaps: Registry<id::MemoryHeapId, MemoryHeap<A>>,
+    pub external_fences: Registry<id::ExternalFenceId, ExternalFence<A>>,
     pub texture_views: Registry<id::TextureViewId, TextureView<A>>,
     pub samplers: Registry<id::SamplerId, Sampler<A>>,
+    pub blas_s: Registry<id::BlasId, Blas<A>>,
+    pub tlas_s: Registry<id::TlasId, Tlas<A>>,
 }
 
 impl<A: HalApi> Hub<A> {
@@ -210,12 +226,18 @@ impl<A: HalApi> Hub<A> {
             render_bundles: Registry::new(A::VARIANT, factory),
             render_pipelines: Registry::new(A::VARIANT, factory),
             compute_pipelines: Registry::new(A::VARIANT, factory),
+            pipeline_caches: Registry::new(A::VARIANT, factory),
             query_sets: Registry::new(A::VARIANT, factory),
             buffers: Registry::new(A::VARIANT, factory),
             staging_buffers: Registry::new(A::VARIANT, factory),
             textures: Registry::new(A::VARIANT, factory),
+            tile_pools: Registry::new(A::VARIANT, factory),
+            memory_heaps: Registry::new(A::VARIANT, factory),
+            external_fences: Registry::new(A::VARIANT, factory),
             texture_views: Registry::new(A::VARIANT, factory),
             samplers: Registry::new(A::VARIANT, factory),
+            blas_s: Registry::new(A::VARIANT, factory),
+            tlas_s: Registry::new(A::VARIANT, factory),
         }
     }
 
@@ -230,45 +252,47 @@ impl<A: HalApi> Hub<A> {
         use hal::Surface;
 
         let mut devices = self.devices.write();
-        for element in devices.map.iter() {
-            if let Element::Occupied(ref device, _) = *element {
-                device.prepare_to_die();
-            }
+        for (_, device) in devices.iter(A::VARIANT) {
+            device.prepare_to_die();
         }
 
-        self.command_buffers.write().map.clear();
-        self.samplers.write().map.clear();
-        self.texture_views.write().map.clear();
-        self.textures.write().map.clear();
-        self.buffers.write().map.clear();
-        self.bind_groups.write().map.clear();
-        self.shader_modules.write().map.clear();
-        self.bind_group_layouts.write().map.clear();
-        self.pipeline_layouts.write().map.clear();
-        self.compute_pipelines.write().map.clear();
-        self.render_pipelines.write().map.clear();
-        self.query_sets.write().map.clear();
-
-        for element in surface_guard.map.iter() {
-            if let Element::Occupied(ref surface, _epoch) = *element {
-                if let Some(ref mut present) = surface.presentation.lock().take() {
-                    if let Some(device) = present.device.downcast_ref::<A>() {
-                        let suf = A::get_surface(surface);
-                        unsafe {
-                            suf.unwrap().raw.unconfigure(device.raw());
-                            //TODO: we could destroy the surface here
-                        }
+        self.command_buffers.write().clear();
+        self.samplers.write().clear();
+        self.texture_views.write().clear();
+        self.textures.write().clear();
+        self.tile_pools.write().clear();
+        self.memory_heaps.write().clear();
+        self.external_fences.write().clear();
+        self.buffers.write().clear();
+        self.bind_groups.write().clear();
+        self.shader_modules.write().clear();
+        self.bind_group_layouts.write().clear();
+        self.pipeline_layouts.write().clear();
+        self.compute_pipelines.write().clear();
+        self.render_pipelines.write().clear();
+        self.pipeline_caches.write().clear();
+        self.query_sets.write().clear();
+        self.tlas_s.write().clear();
+        self.blas_s.write().clear();
+
+        for (_, surface) in surface_guard.iter(wgt::Backend::Empty) {
+            if let Some(ref mut present) = surface.presentation.lock().take() {
+                if let Some(device) = present.device.downcast_ref::<A>() {
+                    let suf = A::get_surface(surface);
+                    unsafe {
+                        suf.unwrap().raw.unconfigure(device.raw());
+                        //TODO: we could destroy the surface here
                     }
                 }
             }
         }
 
-        self.queues.write().map.clear();
-        devices.map.clear();
+        self.queues.write().clear();
+        devices.clear();
 
         if with_adapters {
             drop(devices);
-            self.adapters.write().map.clear();
+            self.adapters.write().clear();
         }
     }
 
@@ -292,11 +316,17 @@ impl<A: HalApi> Hub<A> {
             render_bundles: self.render_bundles.generate_report(),
             render_pipelines: self.render_pipelines.generate_report(),
             compute_pipelines: self.compute_pipelines.generate_report(),
+            pipeline_caches: self.pipeline_caches.generate_report(),
             query_sets: self.query_sets.generate_report(),
             buffers: self.buffers.generate_report(),
             textures: self.textures.generate_report(),
+            tile_pools: self.tile_pools.generate_report(),
+            memory_heaps: self.memory_heaps.generate_report(),
+            external_fences: self.external_fences.generate_report(),
             texture_views: self.texture_views.generate_report(),
             samplers: self.samplers.generate_report(),
+            blas_s: self.blas_s.generate_report(),
+            tlas_s: self.tlas_s.generate_report(),
         }
     }
 }
@@ -244,6 +244,9 @@ pub type BufferId = Id<crate::resource::Buffer<Dummy>>;
 pub type StagingBufferId = Id<crate::resource::StagingBuffer<Dummy>>;
 pub type TextureViewId = Id<crate::resource::TextureView<Dummy>>;
 pub type TextureId = Id<crate::resource::Texture<Dummy>>;
+pub type TilePoolId = Id<crate::resource::TilePool<Dummy>>;
+pub type MemoryHeapId = Id<crate::resource::MemoryHeap<Dummy>>;
+pub type ExternalFenceId = Id<crate::resource::ExternalFence<Dummy>>;
 pub type SamplerId = Id<crate::resource::Sampler<Dummy>>;
 // Binding model
 pub type BindGroupLayoutId = Id<crate::binding_model::BindGroupLayout<Dummy>>;
@@ -253,6 +256,7 @@ pub type BindGroupId = Id<crate::binding_model::BindGroup<Dummy>>;
 pub type ShaderModuleId = Id<crate::pipeline::ShaderModule<Dummy>>;
 pub type RenderPipelineId = Id<crate::pipeline::RenderPipeline<Dummy>>;
 pub type ComputePipelineId = Id<crate::pipeline::ComputePipeline<Dummy>>;
+pub type PipelineCacheId = Id<crate::pipeline::PipelineCache<Dummy>>;
 // Command
 pub type CommandEncoderId = CommandBufferId;
 pub type CommandBufferId = Id<crate::command::CommandBuffer<Dummy>>;
@@ -261,6 +265,9 @@ pub type ComputePassEncoderId = *mut crate::command::ComputePass;
 pub type RenderBundleEncoderId = *mut crate::command::RenderBundleEncoder;
 pub type RenderBundleId = Id<crate::command::RenderBundle<Dummy>>;
 pub type QuerySetId = Id<crate::resource::QuerySet<Dummy>>;
+// Ray tracing
+pub type BlasId = Id<crate::resource::Blas<Dummy>>;
+pub type TlasId = Id<crate::resource::Tlas<Dummy>>;
 
 #[test]
 fn test_id_backend() {
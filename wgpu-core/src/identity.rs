@@ -3,7 +3,7 @@ use wgt::Backend;
 
 use crate::{
     id::{self},
-    Epoch, FastHashMap, Index,
+    Epoch, Index,
 };
 use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 
@@ -37,8 +37,8 @@ use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 #[derive(Debug, Default)]
 pub(super) struct IdentityValues {
     free: Vec<(Index, Epoch)>,
-    //sorted by Index
-    used: FastHashMap<Epoch, Vec<Index>>,
+    /// One past the highest index handed out or marked as used so far.
+    next_index: Index,
     count: usize,
 }
 
@@ -52,24 +52,17 @@ impl IdentityValues {
         match self.free.pop() {
             Some((index, epoch)) => I::zip(index, epoch + 1, backend),
             None => {
-                let epoch = 1;
-                let used = self.used.entry(epoch).or_insert_with(Default::default);
-                let index = if let Some(i) = used.iter().max_by_key(|v| *v) {
-                    i + 1
-                } else {
-                    0
-                };
-                used.push(index);
-                I::zip(index, epoch, backend)
+                let index = self.next_index;
+                self.next_index += 1;
+                I::zip(index, 1, backend)
             }
         }
     }
 
     pub fn mark_as_used<I: id::TypedId>(&mut self, id: I) -> I {
         self.count += 1;
-        let (index, epoch, _backend) = id.unzip();
-        let used = self.used.entry(epoch).or_insert_with(Default::default);
-        used.push(index);
+        let (index, _epoch, _backend) = id.unzip();
+        self.next_index = self.next_index.max(index + 1);
         id
     }
 
@@ -162,13 +155,19 @@ pub trait GlobalIdentityHandlerFactory:
     + IdentityHandlerFactory<id::RenderBundleId>
     + IdentityHandlerFactory<id::RenderPipelineId>
     + IdentityHandlerFactory<id::ComputePipelineId>
+    + IdentityHandlerFactory<id::PipelineCacheId>
     + IdentityHandlerFactory<id::QuerySetId>
     + IdentityHandlerFactory<id::BufferId>
     + IdentityHandlerFactory<id::StagingBufferId>
     + IdentityHandlerFactory<id::TextureId>
+    + IdentityHandlerFactory<id::TilePoolId>
+    + IdentityHandlerFactory<id::MemoryHeapId>
+    + IdentityHandlerFactory<id::ExternalFenceId>
     + IdentityHandlerFactory<id::TextureViewId>
     + IdentityHandlerFactory<id::SamplerId>
     + IdentityHandlerFactory<id::SurfaceId>
+    + IdentityHandlerFactory<id::BlasId>
+    + IdentityHandlerFactory<id::TlasId>
 {
 }
 
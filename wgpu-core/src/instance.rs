@@ -49,6 +49,32 @@ fn check_limits(requested: &wgt::Limits, allowed: &wgt::Limits) -> Vec<FailedLim
     failed
 }
 
+/// Returns `limits` with the limits that only apply to rendering zeroed, so that
+/// validating them against the adapter always passes.
+///
+/// Used for devices created with [`wgt::DeviceDescriptor::compute_only`].
+fn without_render_limits(limits: &wgt::Limits) -> wgt::Limits {
+    wgt::Limits {
+        max_vertex_buffers: 0,
+        max_vertex_attributes: 0,
+        max_vertex_buffer_array_stride: 0,
+        max_inter_stage_shader_components: 0,
+        ..limits.clone()
+    }
+}
+
+#[test]
+fn compute_only_limits_ignore_render_limits() {
+    let requested = wgt::Limits {
+        max_vertex_buffers: u32::MAX,
+        max_inter_stage_shader_components: u32::MAX,
+        ..wgt::Limits::downlevel_defaults()
+    };
+    let allowed = wgt::Limits::downlevel_defaults();
+    assert_eq!(check_limits(&requested, &allowed).len(), 2);
+    assert!(check_limits(&without_render_limits(&requested), &allowed).is_empty());
+}
+
 #[test]
 fn downlevel_default_limits_less_than_default_limits() {
     let res = check_limits(&wgt::Limits::downlevel_defaults(), &wgt::Limits::default());
@@ -71,6 +97,8 @@ pub struct Instance {
     #[cfg(gles)]
     pub gl: Option<HalInstance<hal::api::Gles>>,
     pub flags: wgt::InstanceFlags,
+    /// Callback set with [`Global::instance_set_debug_callback`].
+    pub(crate) debug_callback: Mutex<Option<hal::auxil::debug_message::Registration>>,
 }
 
 impl Instance {
@@ -114,6 +142,7 @@ impl Instance {
             #[cfg(gles)]
             gl: init(hal::api::Gles, &instance_desc),
             flags: instance_desc.flags,
+            debug_callback: Mutex::new(None),
         }
     }
 
@@ -207,6 +236,23 @@ impl<A: HalApi> Adapter<A> {
             .min_storage_buffer_offset_alignment
             .max(MIN_BUFFER_OFFSET_ALIGNMENT_LOWER_BOUND);
 
+        // Debug printf is emulated with a storage buffer bound to a bind group that
+        // is reserved by the device, see `device::debug_printf`.
+        if raw
+            .capabilities
+            .downlevel
+            .flags
+            .contains(wgt::DownlevelFlags::COMPUTE_SHADERS)
+            && raw.capabilities.limits.max_storage_buffers_per_shader_stage > 0
+            && raw.capabilities.limits.max_bind_groups > 1
+        {
+            raw.features |= wgt::Features::SHADER_DEBUG_PRINTF;
+        }
+
+        // Render bundles are replayed into render passes, so they can use occlusion
+        // queries on every backend.
+        raw.features |= wgt::Features::RENDER_BUNDLE_OCCLUSION_QUERY;
+
         Self {
             raw,
             info: ResourceInfo::new("<Adapter>"),
@@ -249,6 +295,20 @@ impl<A: HalApi> Adapter<A> {
             wgt::TextureUsages::RENDER_ATTACHMENT,
             caps.intersects(Tfc::COLOR_ATTACHMENT | Tfc::DEPTH_STENCIL_ATTACHMENT),
         );
+        // Shading rate attachments always use this format.
+        allowed_usages.set(
+            wgt::TextureUsages::SHADING_RATE_ATTACHMENT,
+            format == wgt::TextureFormat::R8Uint
+                && self
+                    .raw
+                    .features
+                    .contains(wgt::Features::SHADING_RATE_ATTACHMENT),
+        );
+        allowed_usages.set(
+            wgt::TextureUsages::TILED,
+            format.tile_dimensions().is_some()
+                && self.raw.features.contains(wgt::Features::TILED_TEXTURE),
+        );
 
         let mut flags = wgt::TextureFormatFeatureFlags::empty();
         flags.set(
@@ -287,6 +347,18 @@ impl<A: HalApi> Adapter<A> {
             wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE,
             caps.contains(Tfc::MULTISAMPLE_RESOLVE),
         );
+        flags.set(
+            wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_REGION,
+            caps.contains(Tfc::MULTISAMPLE_RESOLVE_REGION),
+        );
+        flags.set(
+            wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_MIN_MAX,
+            caps.contains(Tfc::MULTISAMPLE_RESOLVE_MIN_MAX),
+        );
+        flags.set(
+            wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_DEPTH_AVERAGE,
+            caps.contains(Tfc::MULTISAMPLE_RESOLVE_DEPTH_AVERAGE),
+        );
 
         wgt::TextureFormatFeatures {
             allowed_usages,
@@ -300,7 +372,7 @@ impl<A: HalApi> Adapter<A> {
         desc: &DeviceDescriptor,
         instance_flags: wgt::InstanceFlags,
         trace_path: Option<&std::path::Path>,
-    ) -> Result<(Device<A>, Queue<A>), RequestDeviceError> {
+    ) -> Result<(Device<A>, Queue<A>, Vec<Queue<A>>), RequestDeviceError> {
         api_log!("Adapter::create_device");
 
         if let Ok(device) = Device::new(
@@ -314,9 +386,21 @@ impl<A: HalApi> Adapter<A> {
             let queue = Queue {
                 device: None,
                 raw: Some(hal_device.queue),
+                index: 0,
                 info: ResourceInfo::new("<Queue>"),
             };
-            return Ok((device, queue));
+            let additional_queues = hal_device
+                .additional_queues
+                .into_iter()
+                .enumerate()
+                .map(|(i, raw)| Queue {
+                    device: None,
+                    raw: Some(raw),
+                    index: 1 + i,
+                    info: ResourceInfo::new("<Queue>"),
+                })
+                .collect();
+            return Ok((device, queue, additional_queues));
         }
         Err(RequestDeviceError::OutOfMemory)
     }
@@ -326,7 +410,7 @@ impl<A: HalApi> Adapter<A> {
         desc: &DeviceDescriptor,
         instance_flags: wgt::InstanceFlags,
         trace_path: Option<&std::path::Path>,
-    ) -> Result<(Device<A>, Queue<A>), RequestDeviceError> {
+    ) -> Result<(Device<A>, Queue<A>, Vec<Queue<A>>), RequestDeviceError> {
         // Verify all features were exposed by the adapter
         if !self.raw.features.contains(desc.required_features) {
             return Err(RequestDeviceError::UnsupportedFeature(
@@ -351,7 +435,7 @@ impl<A: HalApi> Adapter<A> {
         if desc
             .required_features
             .contains(wgt::Features::MAPPABLE_PRIMARY_BUFFERS)
-            && self.raw.info.device_type == wgt::DeviceType::DiscreteGpu
+            && caps.memory_architecture == wgt::MemoryArchitecture::Discrete
         {
             log::warn!(
                 "Feature MAPPABLE_PRIMARY_BUFFERS enabled on a discrete gpu. \
@@ -363,20 +447,47 @@ impl<A: HalApi> Adapter<A> {
             //TODO
         }
 
-        if let Some(failed) = check_limits(&desc.required_limits, &caps.limits).pop() {
+        let required_limits = if desc.compute_only {
+            without_render_limits(&desc.required_limits)
+        } else {
+            desc.required_limits.clone()
+        };
+        if let Some(failed) = check_limits(&required_limits, &caps.limits).pop() {
             return Err(RequestDeviceError::LimitsExceeded(failed));
         }
 
+        // The bind group used for debug printf comes after all the user's bind groups.
+        if desc
+            .required_features
+            .contains(wgt::Features::SHADER_DEBUG_PRINTF)
+            && desc.required_limits.max_bind_groups >= caps.limits.max_bind_groups
+        {
+            return Err(RequestDeviceError::LimitsExceeded(FailedLimit {
+                name: "max_bind_groups",
+                requested: desc.required_limits.max_bind_groups as u64,
+                allowed: caps.limits.max_bind_groups as u64 - 1,
+            }));
+        }
+
+        if desc.additional_queues > caps.max_additional_queues {
+            return Err(RequestDeviceError::TooManyQueues {
+                requested: desc.additional_queues,
+                maximum: caps.max_additional_queues,
+            });
+        }
+
         let open = unsafe {
-            self.raw
-                .adapter
-                .open(desc.required_features, &desc.required_limits)
+            self.raw.adapter.open(
+                desc.required_features
+                    - wgt::Features::SHADER_DEBUG_PRINTF
+                    - wgt::Features::RENDER_BUNDLE_OCCLUSION_QUERY,
+                &required_limits,
+                desc.compute_only,
+                desc.queue_priority,
+                desc.additional_queues,
+            )
         }
-        .map_err(|err| match err {
-            hal::DeviceError::Lost => RequestDeviceError::DeviceLost,
-            hal::DeviceError::OutOfMemory => RequestDeviceError::OutOfMemory,
-            hal::DeviceError::ResourceCreationFailed => RequestDeviceError::Internal,
-        })?;
+        .map_err(RequestDeviceError::from)?;
 
         self.create_device_and_queue_from_hal(open, desc, instance_flags, trace_path)
     }
@@ -432,6 +543,18 @@ pub enum RequestDeviceError {
     OutOfMemory,
     #[error("Unsupported features were requested: {0:?}")]
     UnsupportedFeature(wgt::Features),
+    #[error("Too many additional queues were requested: {requested}, the maximum is {maximum}")]
+    TooManyQueues { requested: u32, maximum: u32 },
+}
+
+impl From<hal::DeviceError> for RequestDeviceError {
+    fn from(error: hal::DeviceError) -> Self {
+        match error {
+            hal::DeviceError::Lost => RequestDeviceError::DeviceLost,
+            hal::DeviceError::OutOfMemory => RequestDeviceError::OutOfMemory,
+            hal::DeviceError::ResourceCreationFailed => RequestDeviceError::Internal,
+        }
+    }
 }
 
 pub enum AdapterInputs<'a, I> {
@@ -662,6 +785,60 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         id
     }
 
+    /// Creates a surface presenting directly to a display through DRM/KMS,
+    /// without a window system. Only Vulkan supports it.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be a valid DRM file descriptor that is DRM master, such as a
+    /// lease of the connector, and must stay open as long as the surface exists.
+    #[cfg(all(vulkan, unix, not(apple), not(target_os = "android")))]
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn instance_create_surface_from_drm(
+        &self,
+        fd: i32,
+        plane: u32,
+        connector_id: u32,
+        width: u32,
+        height: u32,
+        refresh_rate: u32,
+        id_in: Input<G, SurfaceId>,
+    ) -> Result<SurfaceId, hal::InstanceError> {
+        profiling::scope!("Instance::instance_create_surface_from_drm");
+
+        let instance = self.instance.vulkan.as_ref().ok_or_else(|| {
+            hal::InstanceError::new(String::from("The Vulkan backend isn't enabled"))
+        })?;
+        let raw = unsafe {
+            instance.create_surface_from_drm(fd, plane, connector_id, width, height, refresh_rate)
+        }?;
+
+        let surface = Surface {
+            presentation: Mutex::new(None),
+            info: ResourceInfo::new("<Surface>"),
+            raw: AnySurface::new(HalSurface::<hal::api::Vulkan> { raw: Arc::new(raw) }),
+        };
+
+        let (id, _) = self.surfaces.prepare::<G>(id_in).assign(surface);
+        Ok(id)
+    }
+
+    /// Reports the messages of the backends' validation layers and debug runtimes,
+    /// and wgpu-core's own log output, to `callback` instead of logging them.
+    ///
+    /// Passing `None` removes the callback. Some backends report their messages
+    /// process-wide, so the callback also receives the messages of other instances.
+    pub fn instance_set_debug_callback(
+        &self,
+        callback: Option<hal::auxil::debug_message::DebugCallback>,
+    ) {
+        api_log!("Instance::set_debug_callback");
+
+        let registration = callback.map(hal::auxil::debug_message::register);
+        // The previous callback is unregistered when its registration is dropped.
+        *self.instance.debug_callback.lock() = registration;
+    }
+
     pub fn surface_drop(&self, id: SurfaceId) {
         profiling::scope!("Surface::drop");
 
@@ -1006,6 +1183,31 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             .map_err(|_| InvalidAdapter)
     }
 
+    /// Explains whether `feature` is available on the adapter, and if not, why.
+    ///
+    /// If `feature` contains several features, the first unavailable one is explained.
+    pub fn adapter_explain_feature<A: HalApi>(
+        &self,
+        adapter_id: AdapterId,
+        feature: wgt::Features,
+    ) -> Result<wgt::FeatureSupport, InvalidAdapter> {
+        let hub = A::hub(self);
+
+        let adapter = hub.adapters.get(adapter_id).map_err(|_| InvalidAdapter)?;
+        let Some(missing) = feature.difference(adapter.raw.features).iter().next() else {
+            return Ok(wgt::FeatureSupport::Available);
+        };
+        Ok(match adapter.raw.adapter.feature_requirement(missing) {
+            Some(requirement) => wgt::FeatureSupport::MissingRequirement {
+                backend: A::VARIANT,
+                requirement,
+            },
+            None => wgt::FeatureSupport::NotImplemented {
+                backend: A::VARIANT,
+            },
+        })
+    }
+
     pub fn adapter_limits<A: HalApi>(
         &self,
         adapter_id: AdapterId,
@@ -1030,6 +1232,20 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             .map_err(|_| InvalidAdapter)
     }
 
+    /// Returns the maximum of [`wgt::DeviceDescriptor::additional_queues`]
+    /// for devices of this adapter.
+    pub fn adapter_max_additional_queues<A: HalApi>(
+        &self,
+        adapter_id: AdapterId,
+    ) -> Result<u32, InvalidAdapter> {
+        let hub = A::hub(self);
+
+        hub.adapters
+            .get(adapter_id)
+            .map(|adapter| adapter.raw.capabilities.max_additional_queues)
+            .map_err(|_| InvalidAdapter)
+    }
+
     pub fn adapter_get_presentation_timestamp<A: HalApi>(
         &self,
         adapter_id: AdapterId,
@@ -1054,7 +1270,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         };
         if free {
             hub.adapters
-                .unregister_locked(adapter_id, &mut *adapters_locked);
+                .unregister_locked(adapter_id, &mut adapters_locked);
         }
     }
 }
@@ -1080,9 +1296,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(adapter) => adapter,
                 Err(_) => break RequestDeviceError::InvalidAdapter,
             };
-            let (device, mut queue) =
+            let (device, mut queue, additional_queues) =
                 match adapter.create_device_and_queue(desc, self.instance.flags, trace_path) {
-                    Ok((device, queue)) => (device, queue),
+                    Ok(queues) => queues,
                     Err(e) => break e,
                 };
             let (device_id, _) = device_fid.assign(device);
@@ -1095,6 +1311,14 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             resource_log!("Created Queue {:?}", queue_id);
 
             device.queue_id.write().replace(queue_id);
+            device.register_additional_queues(hub, additional_queues);
+            if self
+                .instance
+                .flags
+                .contains(wgt::InstanceFlags::AUTOMATIC_POLLING)
+            {
+                device.set_automatic_polling(true);
+            }
 
             return (device_id, queue_id, None);
         };
@@ -1108,6 +1332,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
     ///
     /// - `hal_device` must be created from `adapter_id` or its internal handle.
     /// - `desc` must be a subset of `hal_device` features and limits.
+    /// - `desc.additional_queues` must be the number of `hal_device.additional_queues`.
     pub unsafe fn create_device_from_hal<A: HalApi>(
         &self,
         adapter_id: AdapterId,
@@ -1128,12 +1353,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(adapter) => adapter,
                 Err(_) => break RequestDeviceError::InvalidAdapter,
             };
-            let (device, mut queue) = match adapter.create_device_and_queue_from_hal(
-                hal_device,
-                desc,
-                self.instance.flags,
-                trace_path,
-            ) {
+            let (device, mut queue, additional_queues) = match adapter
+                .create_device_and_queue_from_hal(hal_device, desc, self.instance.flags, trace_path)
+            {
                 Ok(device) => device,
                 Err(e) => break e,
             };
@@ -1147,6 +1369,14 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             resource_log!("Created Queue {:?}", queue_id);
 
             device.queue_id.write().replace(queue_id);
+            device.register_additional_queues(hub, additional_queues);
+            if self
+                .instance
+                .flags
+                .contains(wgt::InstanceFlags::AUTOMATIC_POLLING)
+            {
+                device.set_automatic_polling(true);
+            }
 
             return (device_id, queue_id, None);
         };
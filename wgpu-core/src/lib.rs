@@ -29,19 +29,21 @@
 //!
 //!  - **`metal`** --- Enable the `metal` backend.
 //!  - **`vulkan`** --- Enable the `vulkan` backend.
+//!
+//!    On macOS & iOS, the backend is only enabled together with `vulkan-portability`.
+//!  - **`vulkan-portability`** --- Enable the `vulkan` backend on macOS & iOS, through a Vulkan
+//!    portability implementation like MoltenVK.
 //!  - **`gles`** --- Enable the `GLES` backend.
 //!
 //!    This is used for all of GLES, OpenGL, and WebGL.
+//!    On macOS, the backend is only enabled together with `angle`.
+//!  - **`angle`** --- Enable the `GLES` backend on macOS, through
+//!    [ANGLE](https://github.com/google/angle).
 //!  - **`dx12`** --- Enable the `dx12` backend.
 
 // When we have no backends, we end up with a lot of dead or otherwise unreachable code.
 #![cfg_attr(
-    all(
-        not(all(feature = "vulkan", not(target_arch = "wasm32"))),
-        not(all(feature = "metal", any(target_os = "macos", target_os = "ios"))),
-        not(all(feature = "dx12", windows)),
-        not(feature = "gles"),
-    ),
+    all(not(vulkan), not(metal), not(dx12), not(gles)),
     allow(unused, clippy::let_and_return)
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
@@ -93,6 +95,7 @@ pub mod instance;
 pub mod pipeline;
 mod pool;
 pub mod present;
+pub mod ray_tracing;
 pub mod registry;
 pub mod resource;
 mod snatch;
@@ -252,13 +255,13 @@ macro_rules! define_backend_caller {
 //
 // expands to `expr` if the `"vulkan"` feature is enabled, or to a panic
 // otherwise.
-define_backend_caller! { gfx_if_vulkan, gfx_if_vulkan_hidden, "vulkan" if all(feature = "vulkan", not(target_arch = "wasm32")) }
-define_backend_caller! { gfx_if_metal, gfx_if_metal_hidden, "metal" if all(feature = "metal", any(target_os = "macos", target_os = "ios")) }
-define_backend_caller! { gfx_if_dx12, gfx_if_dx12_hidden, "dx12" if all(feature = "dx12", windows) }
-define_backend_caller! { gfx_if_gles, gfx_if_gles_hidden, "gles" if feature = "gles" }
+define_backend_caller! { gfx_if_vulkan, gfx_if_vulkan_hidden, "vulkan" if vulkan }
+define_backend_caller! { gfx_if_metal, gfx_if_metal_hidden, "metal" if metal }
+define_backend_caller! { gfx_if_dx12, gfx_if_dx12_hidden, "dx12" if dx12 }
+define_backend_caller! { gfx_if_gles, gfx_if_gles_hidden, "gles" if gles }
 define_backend_caller! { gfx_if_empty, gfx_if_empty_hidden, "empty" if all(
-    not(any(feature = "metal", feature = "vulkan", feature = "gles")),
-    any(target_os = "macos", target_os = "ios"),
+    not(any(metal, vulkan, gles)),
+    apple,
 ) }
 
 /// Dispatch on an [`Id`]'s backend to a backend-generic method.
@@ -330,23 +333,47 @@ macro_rules! gfx_select {
     };
 }
 
+/// Logs a message of wgpu-core, or reports it to the debug callbacks registered
+/// with [`hal::auxil::debug_message::register`] if there are any.
+///
+/// Only warnings and errors are reported: the API and resource logs are emitted
+/// for every call, and would otherwise be formatted and sent to the callbacks
+/// even when nobody reads them.
+macro_rules! log_or_report {
+    ($level:expr, $($arg:tt)+) => {{
+        let level = $level;
+        if level > log::Level::Warn
+            || !hal::auxil::debug_message::has_callbacks()
+            || !hal::auxil::debug_message::report(&wgt::DebugMessage {
+                severity: hal::auxil::debug_message::severity_of_level(level),
+                backend: None,
+                message: format!($($arg)+),
+                labels: Vec::new(),
+            })
+        {
+            log::log!(level, $($arg)+);
+        }
+    }};
+}
+pub(crate) use log_or_report;
+
 #[cfg(feature = "api_log_info")]
 macro_rules! api_log {
-    ($($arg:tt)+) => (log::info!($($arg)+))
+    ($($arg:tt)+) => ($crate::log_or_report!(log::Level::Info, $($arg)+))
 }
 #[cfg(not(feature = "api_log_info"))]
 macro_rules! api_log {
-    ($($arg:tt)+) => (log::trace!($($arg)+))
+    ($($arg:tt)+) => ($crate::log_or_report!(log::Level::Trace, $($arg)+))
 }
 pub(crate) use api_log;
 
 #[cfg(feature = "resource_log_info")]
 macro_rules! resource_log {
-    ($($arg:tt)+) => (log::info!($($arg)+))
+    ($($arg:tt)+) => ($crate::log_or_report!(log::Level::Info, $($arg)+))
 }
 #[cfg(not(feature = "resource_log_info"))]
 macro_rules! resource_log {
-    ($($arg:tt)+) => (log::trace!($($arg)+))
+    ($($arg:tt)+) => ($crate::log_or_report!(log::Level::Trace, $($arg)+))
 }
 pub(crate) use resource_log;
 
@@ -3,9 +3,12 @@ use crate::device::trace;
 use crate::{
     binding_model::{CreateBindGroupLayoutError, CreatePipelineLayoutError, PipelineLayout},
     command::ColorAttachmentError,
-    device::{Device, DeviceError, MissingDownlevelFlags, MissingFeatures, RenderPassContext},
+    device::{
+        ComputeOnlyDevice, Device, DeviceError, MissingDownlevelFlags, MissingFeatures,
+        RenderPassContext,
+    },
     hal_api::HalApi,
-    id::{ComputePipelineId, PipelineLayoutId, RenderPipelineId, ShaderModuleId},
+    id::{ComputePipelineId, PipelineCacheId, PipelineLayoutId, RenderPipelineId, ShaderModuleId},
     resource::{Resource, ResourceInfo, ResourceType},
     resource_log, validation, Label,
 };
@@ -244,6 +247,9 @@ pub struct ComputePipelineDescriptor<'a> {
     pub layout: Option<PipelineLayoutId>,
     /// The compiled compute stage and its entry point.
     pub stage: ProgrammableStageDescriptor<'a>,
+    /// The pipeline cache to use when creating this pipeline.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub cache: Option<PipelineCacheId>,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -253,6 +259,8 @@ pub enum CreateComputePipelineError {
     Device(#[from] DeviceError),
     #[error("Pipeline layout is invalid")]
     InvalidLayout,
+    #[error("Pipeline cache is invalid")]
+    InvalidCache,
     #[error("Unable to derive an implicit layout")]
     Implicit(#[from] ImplicitLayoutError),
     #[error("Error matching shader requirements against the pipeline")]
@@ -309,6 +317,83 @@ impl<A: HalApi> ComputePipeline<A> {
     }
 }
 
+/// Describes a pipeline cache.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct PipelineCacheDescriptor<'a> {
+    pub label: Label<'a>,
+    /// Data previously returned by [`Global::pipeline_cache_get_data`], if any.
+    ///
+    /// [`Global::pipeline_cache_get_data`]: crate::global::Global::pipeline_cache_get_data
+    pub data: Option<Cow<'a, [u8]>>,
+    /// Create an empty cache instead of failing if `data` is rejected by the backend.
+    pub fallback: bool,
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreatePipelineCacheError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("Pipeline cache data was rejected by the backend")]
+    Validation,
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+}
+
+impl From<hal::PipelineCacheError> for CreatePipelineCacheError {
+    fn from(error: hal::PipelineCacheError) -> Self {
+        match error {
+            hal::PipelineCacheError::Device(error) => Self::Device(error.into()),
+            hal::PipelineCacheError::Validation => Self::Validation,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PipelineCache<A: HalApi> {
+    pub(crate) raw: Option<A::PipelineCache>,
+    pub(crate) device: Arc<Device<A>>,
+    pub(crate) info: ResourceInfo<PipelineCacheId>,
+}
+
+impl<A: HalApi> Drop for PipelineCache<A> {
+    fn drop(&mut self) {
+        if let Some(raw) = self.raw.take() {
+            resource_log!("Destroy raw PipelineCache {:?}", self.info.label());
+
+            #[cfg(feature = "trace")]
+            if let Some(t) = self.device.trace.lock().as_mut() {
+                t.add(trace::Action::DestroyPipelineCache(self.info.id()));
+            }
+
+            unsafe {
+                use hal::Device;
+                self.device.raw().destroy_pipeline_cache(raw);
+            }
+        }
+    }
+}
+
+impl<A: HalApi> Resource<PipelineCacheId> for PipelineCache<A> {
+    const TYPE: ResourceType = "PipelineCache";
+
+    fn as_info(&self) -> &ResourceInfo<PipelineCacheId> {
+        &self.info
+    }
+
+    fn as_info_mut(&mut self) -> &mut ResourceInfo<PipelineCacheId> {
+        &mut self.info
+    }
+}
+
+impl<A: HalApi> PipelineCache<A> {
+    pub(crate) fn raw(&self) -> &A::PipelineCache {
+        self.raw.as_ref().unwrap()
+    }
+}
+
 /// Describes how the vertex buffer is interpreted.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
@@ -323,6 +408,19 @@ pub struct VertexBufferLayout<'a> {
     pub attributes: Cow<'a, [wgt::VertexAttribute]>,
 }
 
+/// Describes how vertex stage outputs are written to a capture buffer.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct VertexCaptureBufferLayout<'a> {
+    /// The stride, in bytes, between captured vertices in this buffer.
+    pub array_stride: wgt::BufferAddress,
+    /// The vertex stage outputs written to each captured vertex, keyed by
+    /// their shader location.
+    pub attributes: Cow<'a, [wgt::VertexAttribute]>,
+}
+
 /// Describes the vertex process in a render pipeline.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
@@ -332,6 +430,9 @@ pub struct VertexState<'a> {
     pub stage: ProgrammableStageDescriptor<'a>,
     /// The format of any vertex buffers used with this pipeline.
     pub buffers: Cow<'a, [VertexBufferLayout<'a>]>,
+    /// The buffers the vertex stage outputs are captured into, if any.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub capture_buffers: Cow<'a, [VertexCaptureBufferLayout<'a>]>,
 }
 
 /// Describes fragment processing in a render pipeline.
@@ -369,6 +470,102 @@ pub struct RenderPipelineDescriptor<'a> {
     /// If the pipeline will be used with a multiview render pass, this indicates how many array
     /// layers the attachments will have.
     pub multiview: Option<NonZeroU32>,
+    /// The pipeline cache to use when creating this pipeline.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub cache: Option<PipelineCacheId>,
+}
+
+/// Describes a render pipeline whose primitives are generated by a mesh
+/// stage, optionally launched by a task stage.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct MeshPipelineDescriptor<'a> {
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    pub layout: Option<PipelineLayoutId>,
+    /// The compiled task stage and its entry point, if any.
+    pub task: Option<ProgrammableStageDescriptor<'a>>,
+    /// The compiled mesh stage and its entry point.
+    pub mesh: ProgrammableStageDescriptor<'a>,
+    /// The properties of the pipeline at the rasterization level. The
+    /// topology is given by the mesh stage and ignored.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub primitive: wgt::PrimitiveState,
+    /// The effect of draw calls on the depth and stencil aspects of the output target, if any.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub depth_stencil: Option<wgt::DepthStencilState>,
+    /// The multi-sampling properties of the pipeline.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub multisample: wgt::MultisampleState,
+    /// The fragment processing state for this pipeline.
+    pub fragment: Option<FragmentState<'a>>,
+    /// If the pipeline will be used with a multiview render pass, this indicates how many array
+    /// layers the attachments will have.
+    pub multiview: Option<NonZeroU32>,
+    /// The pipeline cache to use when creating this pipeline.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub cache: Option<PipelineCacheId>,
+}
+
+/// The stages of a render pipeline that generate its primitives.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum RenderPipelineVertexProcessor<'a> {
+    Vertex(&'a VertexState<'a>),
+    Mesh {
+        task: Option<&'a ProgrammableStageDescriptor<'a>>,
+        mesh: &'a ProgrammableStageDescriptor<'a>,
+    },
+}
+
+/// Either a [`RenderPipelineDescriptor`] or a [`MeshPipelineDescriptor`],
+/// which are created the same way apart from their vertex processing.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GeneralRenderPipelineDescriptor<'a> {
+    pub label: &'a Label<'a>,
+    pub layout: Option<PipelineLayoutId>,
+    pub vertex: RenderPipelineVertexProcessor<'a>,
+    pub primitive: wgt::PrimitiveState,
+    pub depth_stencil: Option<&'a wgt::DepthStencilState>,
+    pub multisample: wgt::MultisampleState,
+    pub fragment: Option<&'a FragmentState<'a>>,
+    pub multiview: Option<NonZeroU32>,
+    pub cache: Option<PipelineCacheId>,
+}
+
+impl<'a> From<&'a RenderPipelineDescriptor<'a>> for GeneralRenderPipelineDescriptor<'a> {
+    fn from(desc: &'a RenderPipelineDescriptor<'a>) -> Self {
+        Self {
+            label: &desc.label,
+            layout: desc.layout,
+            vertex: RenderPipelineVertexProcessor::Vertex(&desc.vertex),
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.as_ref(),
+            multisample: desc.multisample,
+            fragment: desc.fragment.as_ref(),
+            multiview: desc.multiview,
+            cache: desc.cache,
+        }
+    }
+}
+
+impl<'a> From<&'a MeshPipelineDescriptor<'a>> for GeneralRenderPipelineDescriptor<'a> {
+    fn from(desc: &'a MeshPipelineDescriptor<'a>) -> Self {
+        Self {
+            label: &desc.label,
+            layout: desc.layout,
+            vertex: RenderPipelineVertexProcessor::Mesh {
+                task: desc.task.as_ref(),
+                mesh: &desc.mesh,
+            },
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.as_ref(),
+            multisample: desc.multisample,
+            fragment: desc.fragment.as_ref(),
+            multiview: desc.multiview,
+            cache: desc.cache,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Error)]
@@ -413,8 +610,12 @@ pub enum CreateRenderPipelineError {
     ColorAttachment(#[from] ColorAttachmentError),
     #[error(transparent)]
     Device(#[from] DeviceError),
+    #[error(transparent)]
+    ComputeOnlyDevice(#[from] ComputeOnlyDevice),
     #[error("Pipeline layout is invalid")]
     InvalidLayout,
+    #[error("Pipeline cache is invalid")]
+    InvalidCache,
     #[error("Unable to derive an implicit layout")]
     Implicit(#[from] ImplicitLayoutError),
     #[error("Color state [{0}] is invalid")]
@@ -474,6 +675,37 @@ pub enum CreateRenderPipelineError {
     PipelineExpectsShaderToUseDualSourceBlending,
     #[error("Shader entry point expects the pipeline to make use of dual-source blending.")]
     ShaderExpectsPipelineToUseDualSourceBlending,
+    #[error("The number of vertex capture buffers {given} exceeds the limit {limit}")]
+    TooManyVertexCaptureBuffers { given: u32, limit: u32 },
+    #[error("Vertex capture buffer {index} has invalid stride {stride}")]
+    InvalidVertexCaptureStride {
+        index: u32,
+        stride: wgt::BufferAddress,
+    },
+    #[error("Vertex capture attribute at location {location} has format {format:?}, which is not made of 32-bit components")]
+    UnsupportedVertexCaptureFormat {
+        location: wgt::ShaderLocation,
+        format: wgt::VertexFormat,
+    },
+    #[error("Vertex capture attribute at location {location} has invalid offset {offset}")]
+    InvalidVertexCaptureOffset {
+        location: wgt::ShaderLocation,
+        offset: wgt::BufferAddress,
+    },
+    #[error("Vertex capture attributes at locations {0} and {1} overlap")]
+    VertexCaptureAttributesOverlap(wgt::ShaderLocation, wgt::ShaderLocation),
+    #[error("Vertex stage output at location {0} is captured more than once")]
+    VertexCaptureLocationClash(wgt::ShaderLocation),
+    #[error("Vertex stage does not output location {0}, which is captured")]
+    MissingVertexCaptureOutput(wgt::ShaderLocation),
+    #[error("Vertex capture attribute at location {location} has format {format:?}, but the vertex stage outputs {shader}")]
+    IncompatibleVertexCaptureFormat {
+        location: wgt::ShaderLocation,
+        format: wgt::VertexFormat,
+        shader: validation::NumericType,
+    },
+    #[error("Vertex capture requires a list topology, not {0:?}")]
+    VertexCaptureStripTopology(wgt::PrimitiveTopology),
 }
 
 bitflags::bitflags! {
@@ -484,6 +716,9 @@ bitflags::bitflags! {
         const STENCIL_REFERENCE = 1 << 1;
         const WRITES_DEPTH = 1 << 2;
         const WRITES_STENCIL = 1 << 3;
+        /// The pipeline generates its primitives with mesh shaders, and is
+        /// drawn with `draw_mesh_tasks`.
+        const MESH_SHADER = 1 << 4;
     }
 }
 
@@ -506,6 +741,16 @@ impl Default for VertexStep {
     }
 }
 
+/// How a render pipeline writes its vertex stage outputs to capture buffers.
+#[derive(Clone, Debug)]
+pub struct VertexCapture {
+    /// The byte stride of each capture buffer.
+    pub strides: ArrayVec<wgt::BufferAddress, { hal::MAX_VERTEX_CAPTURE_BUFFERS }>,
+
+    /// The number of vertices each captured primitive is made of.
+    pub vertices_per_primitive: u32,
+}
+
 #[derive(Debug)]
 pub struct RenderPipeline<A: HalApi> {
     pub(crate) raw: Option<A::RenderPipeline>,
@@ -517,6 +762,7 @@ pub struct RenderPipeline<A: HalApi> {
     pub(crate) flags: PipelineFlags,
     pub(crate) strip_index_format: Option<wgt::IndexFormat>,
     pub(crate) vertex_steps: Vec<VertexStep>,
+    pub(crate) vertex_capture: Option<VertexCapture>,
     pub(crate) late_sized_buffer_groups: ArrayVec<LateSizedBufferGroup, { hal::MAX_BIND_GROUPS }>,
     pub(crate) info: ResourceInfo<RenderPipelineId>,
 }
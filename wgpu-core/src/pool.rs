@@ -95,7 +95,8 @@ impl<K: Clone + Eq + Hash, V> ResourcePool<K, V> {
 
     /// Remove the given entry map from the pool.
     ///
-    /// Must *only* be called in the Drop impl of [`BindGroupLayout`].
+    /// Must *only* be called in the Drop impl of the pooled resource, i.e. [`BindGroupLayout`]
+    /// or [`BindGroup`].
     pub fn remove(&self, key: &K) {
         let hashed_key = PreHashedKey::from_key(key);
 
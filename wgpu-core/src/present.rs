@@ -19,7 +19,7 @@ use crate::device::trace::Action;
 use crate::{
     conv,
     device::any_device::AnyDevice,
-    device::{DeviceError, MissingDownlevelFlags, WaitIdleError},
+    device::{ComputeOnlyDevice, DeviceError, MissingDownlevelFlags, WaitIdleError},
     global::Global,
     hal_api::HalApi,
     hal_label,
@@ -32,7 +32,7 @@ use crate::{
 };
 
 use hal::{Queue as _, Surface as _};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use thiserror::Error;
 use wgt::SurfaceStatus as Status;
 
@@ -71,6 +71,8 @@ pub enum ConfigureSurfaceError {
     InvalidViewFormat(wgt::TextureFormat, wgt::TextureFormat),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
+    #[error(transparent)]
+    ComputeOnlyDevice(#[from] ComputeOnlyDevice),
     #[error("`SurfaceOutput` must be dropped before a new `Surface` is made")]
     PreviousOutputExists,
     #[error("Both `Surface` width and height must be non-zero. Wait to recreate the `Surface` until the window has non-zero area.")]
@@ -200,6 +202,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     dimension: wgt::TextureViewDimension::D2,
                     usage: hal::TextureUses::COLOR_TARGET,
                     range: wgt::ImageSubresourceRange::default(),
+                    swizzle: wgt::ComponentMapping::IDENTITY,
                 };
                 let clear_view = unsafe {
                     hal::Device::create_texture_view(
@@ -231,6 +234,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     clear_mode: RwLock::new(resource::TextureClearMode::Surface {
                         clear_view: Some(clear_view),
                     }),
+                    tile_pools: Mutex::new(Vec::new()),
+                    heap: None,
+                    resting_uses: None,
                 };
 
                 let (id, resource) = fid.assign(texture);
@@ -0,0 +1,185 @@
+/*! Ray tracing acceleration structures.
+ *
+ * A bottom level acceleration structure (BLAS) holds triangle geometry, and a
+ * top level acceleration structure (TLAS) holds instances of bottom level
+ * acceleration structures. Top level acceleration structures can be bound to
+ * shaders, which traverse them with ray queries.
+ *
+ * The instances of a top level acceleration structure are read from a buffer
+ * filled by the user, which references bottom level acceleration structures by
+ * their handle. Those references aren't validated, so building a top level
+ * acceleration structure is unsafe.
+!*/
+
+use crate::{
+    command::CommandEncoderError,
+    device::{DeviceError, MissingFeatures},
+    id::{BlasId, BufferId, TlasId},
+    Label,
+};
+
+use thiserror::Error;
+
+pub type BlasDescriptor<'a> = wgt::CreateBlasDescriptor<Label<'a>>;
+pub type TlasDescriptor<'a> = wgt::CreateTlasDescriptor<Label<'a>>;
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateBlasError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Vertex format {0:?} can't be used for the geometry of an acceleration structure")]
+    InvalidVertexFormat(wgt::VertexFormat),
+    #[error("Geometry {index} has {count} vertices, which isn't a multiple of 3")]
+    InvalidVertexCount { index: usize, count: u32 },
+    #[error("Geometry {index} has {count} indices, which isn't a multiple of 3")]
+    InvalidIndexCount { index: usize, count: u32 },
+    #[error("Geometry {0} must give both an index format and an index count, or neither")]
+    MissingIndexData(usize),
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateTlasError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+}
+
+/// Error encountered while attempting to build acceleration structures.
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum BuildAccelerationStructureError {
+    #[error(transparent)]
+    Encoder(#[from] CommandEncoderError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Buffer {0:?} is invalid or destroyed")]
+    InvalidBuffer(BufferId),
+    #[error("Buffer {0:?} is missing the `BLAS_INPUT` usage flag")]
+    MissingBlasInputUsageFlag(BufferId),
+    #[error("Buffer {0:?} is missing the `TLAS_INPUT` usage flag")]
+    MissingTlasInputUsageFlag(BufferId),
+    #[error("Range {start}..{end} of buffer {buffer:?} is out of its bounds ({size} bytes)")]
+    BufferOverrun {
+        buffer: BufferId,
+        start: wgt::BufferAddress,
+        end: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+    },
+    #[error("Offset {offset} into buffer {buffer:?} must be a multiple of {alignment}")]
+    UnalignedOffset {
+        buffer: BufferId,
+        offset: wgt::BufferAddress,
+        alignment: wgt::BufferAddress,
+    },
+    #[error("Offset {offset} into buffer {buffer:?} doesn't fit in 32 bits")]
+    OffsetTooLarge {
+        buffer: BufferId,
+        offset: wgt::BufferAddress,
+    },
+    #[error(
+        "Vertex stride {0} must be a multiple of the size of the components of the vertex format"
+    )]
+    UnalignedVertexStride(wgt::BufferAddress),
+    #[error("Bottom level acceleration structure {0:?} is invalid or destroyed")]
+    InvalidBlas(BlasId),
+    #[error("Top level acceleration structure {0:?} is invalid or destroyed")]
+    InvalidTlas(TlasId),
+    #[error("Bottom level acceleration structure {0:?} is built more than once")]
+    DuplicateBlas(BlasId),
+    #[error("Top level acceleration structure {0:?} is built more than once")]
+    DuplicateTlas(TlasId),
+    #[error(
+        "Bottom level acceleration structure {0:?} is built with geometries that don't fit the sizes it was created with"
+    )]
+    IncompatibleBlasGeometries(BlasId),
+    #[error("Geometry {index} of bottom level acceleration structure {blas:?} has a vertex or index count that isn't a multiple of 3")]
+    InvalidPrimitiveCount { blas: BlasId, index: usize },
+    #[error("Geometry {index} of bottom level acceleration structure {blas:?} must have an index buffer if, and only if, it has an index format")]
+    IndexBufferMismatch { blas: BlasId, index: usize },
+    #[error("Top level acceleration structure {tlas:?} is built with {count} instances, but it was created with at most {maximum}")]
+    TooManyInstances {
+        tlas: TlasId,
+        count: u32,
+        maximum: u32,
+    },
+}
+
+impl From<DeviceError> for BuildAccelerationStructureError {
+    fn from(err: DeviceError) -> Self {
+        Self::Encoder(CommandEncoderError::Device(err))
+    }
+}
+
+/// A triangle geometry a bottom level acceleration structure is built with.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct BlasTriangleGeometry {
+    /// The size of the geometry, which must fit the size the acceleration
+    /// structure was created with, at the same index.
+    pub size: wgt::BlasTriangleGeometrySizeDescriptor,
+    pub vertex_buffer: BufferId,
+    /// The index of the first vertex in `vertex_buffer`.
+    pub first_vertex: u32,
+    pub vertex_stride: wgt::BufferAddress,
+    pub index_buffer: Option<BufferId>,
+    /// Offset in bytes into `index_buffer`.
+    pub index_buffer_offset: Option<wgt::BufferAddress>,
+    /// A buffer holding a row-major 3x4 transform matrix of `f32`s, applied
+    /// to the vertices.
+    pub transform_buffer: Option<BufferId>,
+    /// Offset in bytes into `transform_buffer`.
+    pub transform_buffer_offset: Option<wgt::BufferAddress>,
+}
+
+/// The geometries a bottom level acceleration structure is built with.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub enum BlasGeometries {
+    TriangleGeometries(Vec<BlasTriangleGeometry>),
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct BlasBuildEntry {
+    pub blas_id: BlasId,
+    pub geometries: BlasGeometries,
+}
+
+/// A top level acceleration structure build, reading `instance_count`
+/// instances of [`wgt::TLAS_INSTANCE_SIZE`] bytes from the start of
+/// `instance_buffer_id`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct TlasBuildEntry {
+    pub tlas_id: TlasId,
+    pub instance_buffer_id: BufferId,
+    pub instance_count: u32,
+}
+
+/// Returns the size of the components of the vertex positions of an
+/// acceleration structure geometry, or `None` if `format` can't be used for
+/// them.
+pub(crate) fn vertex_component_size(format: wgt::VertexFormat) -> Option<wgt::BufferAddress> {
+    use wgt::VertexFormat as Vf;
+    match format {
+        Vf::Float32x2 | Vf::Float32x3 => Some(4),
+        Vf::Float16x2 | Vf::Float16x4 | Vf::Snorm16x2 | Vf::Snorm16x4 => Some(2),
+        _ => None,
+    }
+}
+
+pub(crate) fn index_format_size(format: wgt::IndexFormat) -> wgt::BufferAddress {
+    match format {
+        wgt::IndexFormat::Uint16 => 2,
+        wgt::IndexFormat::Uint32 => 4,
+    }
+}
@@ -1,19 +1,24 @@
 use std::sync::Arc;
 
-use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use parking_lot::RwLock;
 use wgt::Backend;
 
 use crate::{
+    hash_utils::FastHashSet,
     id,
     identity::{IdentityHandlerFactory, IdentityManager},
     resource::Resource,
-    storage::{Element, InvalidId, Storage},
+    storage::{self, Element, InvalidId, Shard, Storage, StorageMut, SHARD_COUNT},
 };
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct RegistryReport {
     pub num_allocated: usize,
     pub num_kept_from_user: usize,
+    /// Like `num_kept_from_user`, but counts resources registered under
+    /// several IDs, like deduplicated bind group layouts and cached bind
+    /// groups, only once.
+    pub num_distinct_kept_from_user: usize,
     pub num_released_from_user: usize,
     pub num_error: usize,
     pub element_size: usize,
@@ -36,10 +41,14 @@ impl RegistryReport {
 /// if it's used in active submission or anyway kept alive from
 /// any other dependent resource
 ///
+/// The resources are split into [`SHARD_COUNT`] shards, each behind its own
+/// lock, so that threads creating or looking up resources of the same type
+/// rarely wait on each other.
+///
 #[derive(Debug)]
 pub struct Registry<I: id::TypedId, T: Resource<I>> {
     identity: Arc<IdentityManager<I>>,
-    storage: RwLock<Storage<T, I>>,
+    shards: [RwLock<Shard<T, I>>; SHARD_COUNT],
     backend: Backend,
 }
 
@@ -47,7 +56,7 @@ impl<I: id::TypedId, T: Resource<I>> Registry<I, T> {
     pub(crate) fn new<F: IdentityHandlerFactory<I>>(backend: Backend, factory: &F) -> Self {
         Self {
             identity: factory.spawn(),
-            storage: RwLock::new(Storage::new()),
+            shards: std::array::from_fn(|index| RwLock::new(Shard::new(index))),
             backend,
         }
     }
@@ -61,7 +70,7 @@ impl<I: id::TypedId, T: Resource<I>> Registry<I, T> {
 pub(crate) struct FutureId<'a, I: id::TypedId, T: Resource<I>> {
     id: I,
     identity: Arc<IdentityManager<I>>,
-    data: &'a RwLock<Storage<T, I>>,
+    data: &'a RwLock<Shard<T, I>>,
 }
 
 impl<I: id::TypedId + Copy, T: Resource<I>> FutureId<'_, I, T> {
@@ -108,60 +117,69 @@ impl<I: id::TypedId + Copy, T: Resource<I>> FutureId<'_, I, T> {
 }
 
 impl<I: id::TypedId, T: Resource<I>> Registry<I, T> {
+    fn shard(&self, id: I) -> &RwLock<Shard<T, I>> {
+        &self.shards[storage::shard_index(id.unzip().0)]
+    }
     pub(crate) fn prepare<F>(&self, id_in: F::Input) -> FutureId<I, T>
     where
         F: IdentityHandlerFactory<I>,
     {
+        let id = if F::autogenerate_ids() {
+            self.identity.process(self.backend)
+        } else {
+            self.identity.mark_as_used(F::input_to_id(id_in))
+        };
         FutureId {
-            id: if F::autogenerate_ids() {
-                self.identity.process(self.backend)
-            } else {
-                self.identity.mark_as_used(F::input_to_id(id_in))
-            },
+            id,
             identity: self.identity.clone(),
-            data: &self.storage,
+            data: self.shard(id),
         }
     }
     pub(crate) fn request(&self) -> FutureId<I, T> {
+        let id = self.identity.process(self.backend);
         FutureId {
-            id: self.identity.process(self.backend),
+            id,
             identity: self.identity.clone(),
-            data: &self.storage,
+            data: self.shard(id),
         }
     }
     pub(crate) fn try_get(&self, id: I) -> Result<Option<Arc<T>>, InvalidId> {
-        self.read().try_get(id).map(|o| o.cloned())
+        self.shard(id).read().try_get(id).map(|o| o.cloned())
     }
     pub(crate) fn get(&self, id: I) -> Result<Arc<T>, InvalidId> {
-        self.read().get_owned(id)
+        self.shard(id).read().get(id).cloned()
     }
-    pub(crate) fn read<'a>(&'a self) -> RwLockReadGuard<'a, Storage<T, I>> {
-        self.storage.read()
+    /// Locks all the shards for reading.
+    ///
+    /// Prefer [`Registry::get`] to look up a single resource.
+    pub(crate) fn read<'a>(&'a self) -> Storage<'a, T, I> {
+        Storage::new(std::array::from_fn(|index| self.shards[index].read()))
     }
-    pub(crate) fn write<'a>(&'a self) -> RwLockWriteGuard<'a, Storage<T, I>> {
-        self.storage.write()
+    /// Locks all the shards for writing.
+    pub(crate) fn write<'a>(&'a self) -> StorageMut<'a, T, I> {
+        StorageMut::new(std::array::from_fn(|index| self.shards[index].write()))
     }
-    pub fn unregister_locked(&self, id: I, storage: &mut Storage<T, I>) -> Option<Arc<T>> {
+    pub fn unregister_locked(&self, id: I, storage: &mut StorageMut<T, I>) -> Option<Arc<T>> {
         storage.remove(id)
     }
     pub fn force_replace(&self, id: I, mut value: T) {
-        let mut storage = self.storage.write();
+        let mut shard = self.shard(id).write();
         value.as_info_mut().set_id(id, &self.identity);
-        storage.force_replace(id, value)
+        shard.force_replace(id, value)
     }
     pub fn force_replace_with_error(&self, id: I, label: &str) {
-        let mut storage = self.storage.write();
-        storage.remove(id);
-        storage.insert_error(id, label);
+        let mut shard = self.shard(id).write();
+        shard.remove(id);
+        shard.insert_error(id, label);
     }
     pub(crate) fn unregister(&self, id: I) -> Option<Arc<T>> {
-        let value = self.storage.write().remove(id);
+        let value = self.shard(id).write().remove(id);
         //Returning None is legal if it's an error ID
         value
     }
 
     pub fn label_for_resource(&self, id: I) -> String {
-        let guard = self.storage.read();
+        let guard = self.shard(id).read();
 
         let type_name = guard.kind();
         match guard.get(id) {
@@ -182,19 +200,25 @@ impl<I: id::TypedId, T: Resource<I>> Registry<I, T> {
     }
 
     pub(crate) fn generate_report(&self) -> RegistryReport {
-        let storage = self.storage.read();
         let mut report = RegistryReport {
             element_size: std::mem::size_of::<T>(),
             ..Default::default()
         };
         report.num_allocated = self.identity.values.lock().count();
-        for element in storage.map.iter() {
-            match *element {
-                Element::Occupied(..) => report.num_kept_from_user += 1,
-                Element::Vacant => report.num_released_from_user += 1,
-                Element::Error(..) => report.num_error += 1,
+        let mut distinct = FastHashSet::default();
+        for shard in self.shards.iter() {
+            for element in shard.read().map.iter() {
+                match *element {
+                    Element::Occupied(ref value, _) => {
+                        report.num_kept_from_user += 1;
+                        distinct.insert(Arc::as_ptr(value));
+                    }
+                    Element::Vacant => report.num_released_from_user += 1,
+                    Element::Error(..) => report.num_error += 1,
+                }
             }
         }
+        report.num_distinct_kept_from_user = distinct.len();
         report
     }
 }
@@ -8,8 +8,9 @@ use crate::{
     global::Global,
     hal_api::HalApi,
     id::{
-        AdapterId, BufferId, DeviceId, QuerySetId, SamplerId, StagingBufferId, SurfaceId,
-        TextureId, TextureViewId, TypedId,
+        AdapterId, BlasId, BufferId, DeviceId, ExternalFenceId, MemoryHeapId, QuerySetId,
+        SamplerId, StagingBufferId, SurfaceId, TextureId, TextureViewId, TilePoolId, TlasId,
+        TypedId,
     },
     identity::{GlobalIdentityHandlerFactory, IdentityManager},
     init_tracker::{BufferInitTracker, TextureInitTracker},
@@ -28,6 +29,7 @@ use wgt::WasmNotSendSync;
 
 use std::{
     borrow::Borrow,
+    collections::VecDeque,
     fmt::Debug,
     iter, mem,
     ops::Range,
@@ -191,18 +193,76 @@ pub(crate) enum BufferMapState<A: HalApi> {
         stage_buffer: Arc<Buffer<A>>,
         needs_flush: bool,
     },
-    /// Waiting for GPU to be done before mapping
-    Waiting(BufferPendingMapping<A>),
-    /// Mapped
-    Active {
-        ptr: NonNull<u8>,
-        range: hal::MemoryRange,
-        host: HostMap,
-    },
+    /// Ranges mapped, or waiting for GPU to be done before mapping
+    Mapped(BufferMappings<A>),
     /// Not mapped
     Idle,
 }
 
+/// The ranges of a buffer mapped by [`Global::buffer_map_async`].
+///
+/// Disjoint ranges can be mapped at the same time, and each of them is
+/// unmapped independently. The whole buffer is mapped on the hal side while a
+/// range is active, so that the pointers to the other ranges stay valid.
+///
+/// [`Global::buffer_map_async`]: crate::global::Global::buffer_map_async
+#[derive(Debug)]
+pub(crate) struct BufferMappings<A: HalApi> {
+    pub host: HostMap,
+    /// Start of the buffer, once mapped on the hal side.
+    pub ptr: Option<NonNull<u8>>,
+    pub is_coherent: bool,
+    /// Ranges waiting for GPU to be done, in the order they were requested.
+    pub pending: VecDeque<BufferPendingMapping<A>>,
+    /// Ranges accessible from the host.
+    pub active: Vec<hal::MemoryRange>,
+}
+
+impl<A: HalApi> BufferMappings<A> {
+    pub(crate) fn new(host: HostMap) -> Self {
+        Self {
+            host,
+            ptr: None,
+            is_coherent: false,
+            pending: VecDeque::new(),
+            active: Vec::new(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.is_empty() && self.active.is_empty()
+    }
+
+    /// Returns true if `range` intersects a pending or an active range.
+    pub(crate) fn overlaps(&self, range: &Range<wgt::BufferAddress>) -> bool {
+        self.overlaps_pending(range) || self.overlaps_active(range)
+    }
+
+    /// Returns true if `range` intersects a pending range.
+    pub(crate) fn overlaps_pending(&self, range: &Range<wgt::BufferAddress>) -> bool {
+        self.pending
+            .iter()
+            .any(|pending| ranges_overlap(&pending.range, range))
+    }
+
+    /// Returns true if `range` intersects an active range.
+    pub(crate) fn overlaps_active(&self, range: &Range<wgt::BufferAddress>) -> bool {
+        self.active
+            .iter()
+            .any(|active| ranges_overlap(active, range))
+    }
+}
+
+fn ranges_overlap(a: &Range<wgt::BufferAddress>, b: &Range<wgt::BufferAddress>) -> bool {
+    if a.start == a.end || b.start == b.end {
+        // Empty ranges overlap the ranges they touch, so that they can't be
+        // mapped twice either.
+        a.start <= b.end && b.start <= a.end
+    } else {
+        a.start < b.end && b.start < a.end
+    }
+}
+
 #[cfg(send_sync)]
 unsafe impl<A: HalApi> Send for BufferMapState<A> {}
 #[cfg(send_sync)]
@@ -325,6 +385,8 @@ pub enum BufferAccessError {
     MapAlreadyPending,
     #[error(transparent)]
     MissingBufferUsage(#[from] MissingBufferUsageError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
     #[error("Buffer is not mapped")]
     NotMapped,
     #[error(
@@ -354,6 +416,8 @@ pub enum BufferAccessError {
     },
     #[error("Buffer map aborted")]
     MapAborted,
+    #[error("Range {0:?} of the buffer is not mapped")]
+    RangeNotMapped(Range<wgt::BufferAddress>),
 }
 
 pub type BufferAccessResult = Result<(), BufferAccessError>;
@@ -378,6 +442,8 @@ pub struct Buffer<A: HalApi> {
     pub(crate) sync_mapped_writes: Mutex<Option<hal::MemoryRange>>,
     pub(crate) info: ResourceInfo<BufferId>,
     pub(crate) map_state: Mutex<BufferMapState<A>>,
+    /// The heap a placed buffer lives in, which must outlive it.
+    pub(crate) heap: Option<Arc<MemoryHeap<A>>>,
 }
 
 impl<A: HalApi> Drop for Buffer<A> {
@@ -407,9 +473,39 @@ impl<A: HalApi> Buffer<A> {
         self.raw.get(guard).is_none()
     }
 
+    /// Returns the use of `range` of this buffer by a copy, to be checked
+    /// against the ranges that are still mapped when submitting it.
+    ///
+    /// Only mappable buffers can be submitted while partially mapped, so
+    /// nothing is recorded for the other buffers.
+    pub(crate) fn mappable_range_use(
+        &self,
+        range: Range<wgt::BufferAddress>,
+    ) -> Option<(BufferId, Range<wgt::BufferAddress>)> {
+        if self
+            .usage
+            .intersects(wgt::BufferUsages::MAP_READ | wgt::BufferUsages::MAP_WRITE)
+        {
+            Some((self.info.id(), range))
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if the buffer can only be used by copies, whose ranges are
+    /// recorded with [`Buffer::mappable_range_use`].
+    pub(crate) fn is_copy_only(&self) -> bool {
+        (wgt::BufferUsages::MAP_READ
+            | wgt::BufferUsages::MAP_WRITE
+            | wgt::BufferUsages::COPY_SRC
+            | wgt::BufferUsages::COPY_DST
+            | wgt::BufferUsages::QUERY_RESOLVE)
+            .contains(self.usage)
+    }
+
     // Note: This must not be called while holding a lock.
     pub(crate) fn unmap(self: &Arc<Self>) -> Result<(), BufferAccessError> {
-        if let Some((mut operation, status)) = self.unmap_inner()? {
+        for (mut operation, status) in self.unmap_inner()? {
             if let Some(callback) = operation.callback.take() {
                 callback.call(status);
             }
@@ -418,7 +514,97 @@ impl<A: HalApi> Buffer<A> {
         Ok(())
     }
 
-    fn unmap_inner(self: &Arc<Self>) -> Result<Option<BufferMapPendingClosure>, BufferAccessError> {
+    /// Unmaps a single range previously mapped with `map_async`, leaving the
+    /// other ranges of the buffer mapped.
+    ///
+    /// Note: This must not be called while holding a lock.
+    pub(crate) fn unmap_range(
+        self: &Arc<Self>,
+        range: Range<wgt::BufferAddress>,
+    ) -> Result<(), BufferAccessError> {
+        if let Some((mut operation, status)) = self.unmap_range_inner(range)? {
+            if let Some(callback) = operation.callback.take() {
+                callback.call(status);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unmap_range_inner(
+        self: &Arc<Self>,
+        range: Range<wgt::BufferAddress>,
+    ) -> Result<Option<BufferMapPendingClosure>, BufferAccessError> {
+        use hal::Device;
+
+        let device = &self.device;
+        let snatch_guard = device.snatchable_lock.read();
+        let raw_buf = self
+            .raw(&snatch_guard)
+            .ok_or(BufferAccessError::Destroyed)?;
+        let buffer_id = self.info.id();
+        let mut map_state = self.map_state.lock();
+        if let resource::BufferMapState::Init { .. } = *map_state {
+            // Buffers mapped at creation are only unmapped all at once.
+            if range != (0..self.size) {
+                return Err(BufferAccessError::RangeNotMapped(range));
+            }
+            drop(map_state);
+            drop(snatch_guard);
+            return self.unmap_inner().map(|_| None);
+        }
+        let resource::BufferMapState::Mapped(ref mut mappings) = *map_state else {
+            return Err(BufferAccessError::NotMapped);
+        };
+
+        let closure = if let Some(index) = mappings
+            .pending
+            .iter()
+            .position(|pending| pending.range == range)
+        {
+            let pending = mappings.pending.remove(index).unwrap();
+            Some((pending.op, Err(BufferAccessError::MapAborted)))
+        } else if let Some(index) = mappings.active.iter().position(|active| *active == range) {
+            let range = mappings.active.swap_remove(index);
+            if mappings.host == HostMap::Write {
+                #[cfg(feature = "trace")]
+                if let (Some(trace), Some(ptr)) = (device.trace.lock().as_mut(), mappings.ptr) {
+                    let data = trace.make_binary("bin", unsafe {
+                        std::slice::from_raw_parts(
+                            ptr.as_ptr().offset(range.start as isize),
+                            (range.end - range.start) as usize,
+                        )
+                    });
+                    trace.add(trace::Action::WriteBuffer {
+                        id: buffer_id,
+                        data,
+                        range: range.clone(),
+                        queued: false,
+                    });
+                }
+                let _ = range;
+            }
+            None
+        } else {
+            return Err(BufferAccessError::RangeNotMapped(range));
+        };
+
+        if mappings.is_empty() {
+            log::debug!("Buffer {:?} map state -> Idle", buffer_id);
+            if mappings.ptr.is_some() {
+                unsafe {
+                    device
+                        .raw()
+                        .unmap_buffer(raw_buf)
+                        .map_err(DeviceError::from)?
+                };
+            }
+            *map_state = resource::BufferMapState::Idle;
+        }
+        Ok(closure)
+    }
+
+    fn unmap_inner(self: &Arc<Self>) -> Result<Vec<BufferMapPendingClosure>, BufferAccessError> {
         use hal::Device;
 
         let device = &self.device;
@@ -492,35 +678,42 @@ impl<A: HalApi> Buffer<A> {
             resource::BufferMapState::Idle => {
                 return Err(BufferAccessError::NotMapped);
             }
-            resource::BufferMapState::Waiting(pending) => {
-                return Ok(Some((pending.op, Err(BufferAccessError::MapAborted))));
-            }
-            resource::BufferMapState::Active { ptr, range, host } => {
-                if host == HostMap::Write {
+            resource::BufferMapState::Mapped(mappings) => {
+                if mappings.host == HostMap::Write {
                     #[cfg(feature = "trace")]
-                    if let Some(ref mut trace) = *device.trace.lock() {
-                        let size = range.end - range.start;
-                        let data = trace.make_binary("bin", unsafe {
-                            std::slice::from_raw_parts(ptr.as_ptr(), size as usize)
-                        });
-                        trace.add(trace::Action::WriteBuffer {
-                            id: buffer_id,
-                            data,
-                            range: range.clone(),
-                            queued: false,
-                        });
+                    if let (Some(trace), Some(ptr)) = (device.trace.lock().as_mut(), mappings.ptr) {
+                        for range in mappings.active.iter() {
+                            let data = trace.make_binary("bin", unsafe {
+                                std::slice::from_raw_parts(
+                                    ptr.as_ptr().offset(range.start as isize),
+                                    (range.end - range.start) as usize,
+                                )
+                            });
+                            trace.add(trace::Action::WriteBuffer {
+                                id: buffer_id,
+                                data,
+                                range: range.clone(),
+                                queued: false,
+                            });
+                        }
                     }
-                    let _ = (ptr, range);
                 }
-                unsafe {
-                    device
-                        .raw()
-                        .unmap_buffer(raw_buf)
-                        .map_err(DeviceError::from)?
-                };
+                if mappings.ptr.is_some() {
+                    unsafe {
+                        device
+                            .raw()
+                            .unmap_buffer(raw_buf)
+                            .map_err(DeviceError::from)?
+                    };
+                }
+                return Ok(mappings
+                    .pending
+                    .into_iter()
+                    .map(|pending| (pending.op, Err(BufferAccessError::MapAborted)))
+                    .collect());
             }
         }
-        Ok(None)
+        Ok(Vec::new())
     }
 
     pub(crate) fn destroy(self: &Arc<Self>) -> Result<(), DestroyError> {
@@ -544,6 +737,7 @@ impl<A: HalApi> Buffer<A> {
             queue::TempResource::DestroyedBuffer(Arc::new(DestroyedBuffer {
                 raw: Some(raw),
                 device: Arc::clone(&self.device),
+                _heap: self.heap.clone(),
                 submission_index: self.info.submission_index(),
                 id: self.info.id.unwrap(),
                 label: self.info.label.clone(),
@@ -582,6 +776,12 @@ pub enum CreateBufferError {
     MaxBufferSize { requested: u64, maximum: u64 },
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
+    Placement(#[from] PlacementError),
+    #[error("Buffers sharing memory with other APIs can't be mapped")]
+    MappableExternal,
 }
 
 impl<A: HalApi> Resource<BufferId> for Buffer<A> {
@@ -601,6 +801,8 @@ impl<A: HalApi> Resource<BufferId> for Buffer<A> {
 pub struct DestroyedBuffer<A: HalApi> {
     raw: Option<A::Buffer>,
     device: Arc<Device<A>>,
+    /// Keeps the memory of a placed buffer until it's destroyed.
+    _heap: Option<Arc<MemoryHeap<A>>>,
     label: String,
     pub(crate) id: BufferId,
     pub(crate) submission_index: u64,
@@ -741,6 +943,15 @@ pub struct Texture<A: HalApi> {
     pub(crate) full_range: TextureSelector,
     pub(crate) info: ResourceInfo<TextureId>,
     pub(crate) clear_mode: RwLock<TextureClearMode<A>>,
+    /// The pools that tiles of a [`wgt::TextureUsages::TILED`] texture have been
+    /// bound to. Submitted work may still access them after the tiles are
+    /// rebound, so they're kept alive as long as the texture.
+    pub(crate) tile_pools: Mutex<Vec<Arc<TilePool<A>>>>,
+    /// The heap a placed texture lives in, which must outlive it.
+    pub(crate) heap: Option<Arc<MemoryHeap<A>>>,
+    /// The state a texture wrapping an image of another API, like an OpenXR
+    /// swapchain image, is returned to at the end of every submission using it.
+    pub(crate) resting_uses: Option<hal::TextureUses>,
 }
 
 impl<A: HalApi> Drop for Texture<A> {
@@ -855,6 +1066,7 @@ impl<A: HalApi> Texture<A> {
             queue::TempResource::DestroyedTexture(Arc::new(DestroyedTexture {
                 raw: Some(raw),
                 device: Arc::clone(&self.device),
+                _heap: self.heap.clone(),
                 submission_index: self.info.submission_index(),
                 id: self.info.id.unwrap(),
                 label: self.info.label.clone(),
@@ -971,6 +1183,8 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 pub struct DestroyedTexture<A: HalApi> {
     raw: Option<A::Texture>,
     device: Arc<Device<A>>,
+    /// Keeps the memory of a placed texture until it's destroyed.
+    _heap: Option<Arc<MemoryHeap<A>>>,
     label: String,
     pub(crate) id: TextureId,
     pub(crate) submission_index: u64,
@@ -1090,10 +1304,24 @@ pub enum CreateTextureError {
     InvalidSampleCount(u32, wgt::TextureFormat, Vec<u32>, Vec<u32>),
     #[error("Multisampled textures must have RENDER_ATTACHMENT usage")]
     MultisampledNotRenderAttachment,
+    #[error("Texture usages TILED and RENDER_ATTACHMENT can't be combined")]
+    TiledRenderAttachment,
+    #[error("Mip level {mip_level} of size {size:?} is not a multiple of the {tile_width}x{tile_height} texel tiles of {format:?}")]
+    InvalidTiledMipSize {
+        mip_level: u32,
+        size: wgt::Extent3d,
+        tile_width: u32,
+        tile_height: u32,
+        format: wgt::TextureFormat,
+    },
     #[error("Texture format {0:?} can't be used due to missing features")]
     MissingFeatures(wgt::TextureFormat, #[source] MissingFeatures),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
+    #[error(transparent)]
+    Placement(#[from] PlacementError),
+    #[error(transparent)]
+    ExternalImport(#[from] ExternalImportError),
 }
 
 impl<A: HalApi> Resource<TextureId> for Texture<A> {
@@ -1136,6 +1364,8 @@ pub struct TextureViewDescriptor<'a> {
     pub dimension: Option<wgt::TextureViewDimension>,
     /// Range within the texture that is accessible via this view.
     pub range: wgt::ImageSubresourceRange,
+    /// Sources of the components read through the view.
+    pub swizzle: wgt::ComponentMapping,
 }
 
 #[derive(Debug)]
@@ -1144,6 +1374,7 @@ pub(crate) struct HalTextureViewDescriptor {
     pub format: wgt::TextureFormat,
     pub dimension: wgt::TextureViewDimension,
     pub range: wgt::ImageSubresourceRange,
+    pub swizzle: wgt::ComponentMapping,
 }
 
 impl HalTextureViewDescriptor {
@@ -1166,6 +1397,8 @@ pub enum TextureViewNotRenderableReason {
         "The aspects of this texture view are a subset of the aspects in the original texture. Aspects: {0:?}"
     )]
     Aspects(hal::FormatAspects),
+    #[error("This texture view swizzles its components. Swizzle: {0:?}")]
+    Swizzle(wgt::ComponentMapping),
 }
 
 #[derive(Debug)]
@@ -1253,6 +1486,8 @@ pub enum CreateTextureViewError {
         texture: wgt::TextureFormat,
         view: wgt::TextureFormat,
     },
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -1449,6 +1684,344 @@ impl<A: HalApi> QuerySet<A> {
     }
 }
 
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateTilePoolError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Tile pools cannot be made with zero tiles")]
+    ZeroCount,
+}
+
+pub type TilePoolDescriptor<'a> = wgt::TilePoolDescriptor<Label<'a>>;
+
+/// Memory that the tiles of [`wgt::TextureUsages::TILED`] textures are bound to.
+#[derive(Debug)]
+pub struct TilePool<A: HalApi> {
+    pub(crate) raw: Option<A::TilePool>,
+    pub(crate) device: Arc<Device<A>>,
+    pub(crate) info: ResourceInfo<TilePoolId>,
+    pub(crate) tile_count: u32,
+    /// The tiles that have been zeroed, which is done when they're first bound.
+    pub(crate) initialized_tiles: Mutex<bit_vec::BitVec>,
+}
+
+impl<A: HalApi> Drop for TilePool<A> {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw TilePool {:?}", self.info.label());
+        if let Some(raw) = self.raw.take() {
+            #[cfg(feature = "trace")]
+            if let Some(t) = self.device.trace.lock().as_mut() {
+                t.add(trace::Action::DestroyTilePool(self.info.id()));
+            }
+
+            unsafe {
+                use hal::Device;
+                self.device.raw().destroy_tile_pool(raw);
+            }
+        }
+    }
+}
+
+impl<A: HalApi> Resource<TilePoolId> for TilePool<A> {
+    const TYPE: ResourceType = "TilePool";
+
+    fn as_info(&self) -> &ResourceInfo<TilePoolId> {
+        &self.info
+    }
+
+    fn as_info_mut(&mut self) -> &mut ResourceInfo<TilePoolId> {
+        &mut self.info
+    }
+}
+
+impl<A: HalApi> TilePool<A> {
+    pub(crate) fn raw(&self) -> &A::TilePool {
+        self.raw.as_ref().unwrap()
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateMemoryHeapError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Memory heaps cannot be made with a size of zero")]
+    ZeroSize,
+}
+
+/// Error encountered when placing a buffer or texture in a [`MemoryHeap`].
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum PlacementError {
+    #[error("Memory heap {0:?} is invalid")]
+    InvalidHeap(MemoryHeapId),
+    #[error("Buffers with usage {usage:?} can't be placed in {memory_type:?} memory heaps")]
+    UnsupportedBufferUsage {
+        usage: wgt::BufferUsages,
+        memory_type: wgt::MemoryHeapType,
+    },
+    #[error("Textures can't be placed in {0:?} memory heaps")]
+    UnsupportedTexture(wgt::MemoryHeapType),
+    #[error("Textures with TILED usage have no memory of their own to place")]
+    TiledTexture,
+    #[error("Offset {offset} is not a multiple of the required alignment {alignment}")]
+    UnalignedOffset {
+        offset: wgt::BufferAddress,
+        alignment: wgt::BufferAddress,
+    },
+    #[error(
+        "Placing {size} bytes at offset {offset} overruns the memory heap of {heap_size} bytes"
+    )]
+    OutOfBounds {
+        offset: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+        heap_size: wgt::BufferAddress,
+    },
+}
+
+/// Error encountered when importing a texture from an external image.
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum ExternalImportError {
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Imported textures must be 2D, with a single mip level, array layer and sample")]
+    UnsupportedDescriptor,
+    #[error("Textures with TILED usage can't be imported")]
+    TiledTexture,
+    #[error("Textures wrapping images of other APIs must have RENDER_ATTACHMENT usage")]
+    NotRenderAttachment,
+}
+
+pub type MemoryHeapDescriptor<'a> = wgt::MemoryHeapDescriptor<Label<'a>>;
+
+/// Memory that buffers and textures can be placed in, possibly overlapping.
+#[derive(Debug)]
+pub struct MemoryHeap<A: HalApi> {
+    pub(crate) raw: Option<A::MemoryHeap>,
+    pub(crate) device: Arc<Device<A>>,
+    pub(crate) info: ResourceInfo<MemoryHeapId>,
+    pub(crate) size: wgt::BufferAddress,
+    pub(crate) memory_type: wgt::MemoryHeapType,
+}
+
+impl<A: HalApi> Drop for MemoryHeap<A> {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw MemoryHeap {:?}", self.info.label());
+        if let Some(raw) = self.raw.take() {
+            #[cfg(feature = "trace")]
+            if let Some(t) = self.device.trace.lock().as_mut() {
+                t.add(trace::Action::DestroyMemoryHeap(self.info.id()));
+            }
+
+            unsafe {
+                use hal::Device;
+                self.device.raw().destroy_memory_heap(raw);
+            }
+        }
+    }
+}
+
+impl<A: HalApi> Resource<MemoryHeapId> for MemoryHeap<A> {
+    const TYPE: ResourceType = "MemoryHeap";
+
+    fn as_info(&self) -> &ResourceInfo<MemoryHeapId> {
+        &self.info
+    }
+
+    fn as_info_mut(&mut self) -> &mut ResourceInfo<MemoryHeapId> {
+        &mut self.info
+    }
+}
+
+impl<A: HalApi> MemoryHeap<A> {
+    pub(crate) fn raw(&self) -> &A::MemoryHeap {
+        self.raw.as_ref().unwrap()
+    }
+
+    /// Checks that a resource with `requirements` fits at `offset` of the heap.
+    pub(crate) fn check_placement(
+        &self,
+        offset: wgt::BufferAddress,
+        requirements: wgt::MemoryRequirements,
+    ) -> Result<(), PlacementError> {
+        if offset % requirements.alignment != 0 {
+            return Err(PlacementError::UnalignedOffset {
+                offset,
+                alignment: requirements.alignment,
+            });
+        }
+        if offset
+            .checked_add(requirements.size)
+            .map_or(true, |end| end > self.size)
+        {
+            return Err(PlacementError::OutOfBounds {
+                offset,
+                size: requirements.size,
+                heap_size: self.size,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateExternalFenceError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+}
+
+pub type ExternalFenceDescriptor<'a> = wgt::ExternalFenceDescriptor<Label<'a>>;
+
+/// A fence whose timeline can be shared with other APIs and processes.
+#[derive(Debug)]
+pub struct ExternalFence<A: HalApi> {
+    pub(crate) raw: Option<A::Fence>,
+    pub(crate) device: Arc<Device<A>>,
+    pub(crate) info: ResourceInfo<ExternalFenceId>,
+}
+
+impl<A: HalApi> Drop for ExternalFence<A> {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw ExternalFence {:?}", self.info.label());
+        if let Some(raw) = self.raw.take() {
+            #[cfg(feature = "trace")]
+            if let Some(t) = self.device.trace.lock().as_mut() {
+                t.add(trace::Action::DestroyExternalFence(self.info.id()));
+            }
+
+            unsafe {
+                use hal::Device;
+                self.device.raw().destroy_fence(raw);
+            }
+        }
+    }
+}
+
+impl<A: HalApi> Resource<ExternalFenceId> for ExternalFence<A> {
+    const TYPE: ResourceType = "ExternalFence";
+
+    fn as_info(&self) -> &ResourceInfo<ExternalFenceId> {
+        &self.info
+    }
+
+    fn as_info_mut(&mut self) -> &mut ResourceInfo<ExternalFenceId> {
+        &mut self.info
+    }
+}
+
+impl<A: HalApi> ExternalFence<A> {
+    pub(crate) fn raw(&self) -> &A::Fence {
+        self.raw.as_ref().unwrap()
+    }
+}
+
+/// A bottom level acceleration structure, holding triangle geometry.
+#[derive(Debug)]
+pub struct Blas<A: HalApi> {
+    pub(crate) raw: Option<A::AccelerationStructure>,
+    pub(crate) device: Arc<Device<A>>,
+    pub(crate) info: ResourceInfo<BlasId>,
+    pub(crate) size_info: hal::AccelerationStructureBuildSizes,
+    /// The sizes of the geometries the acceleration structure was created with,
+    /// which bound the geometries it can be built with.
+    pub(crate) sizes: wgt::BlasGeometrySizeDescriptors,
+    pub(crate) flags: wgt::AccelerationStructureFlags,
+    /// The device address of the acceleration structure, referenced by the
+    /// instances of top level acceleration structures.
+    pub(crate) handle: u64,
+}
+
+impl<A: HalApi> Drop for Blas<A> {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw Blas {:?}", self.info.label());
+        if let Some(raw) = self.raw.take() {
+            #[cfg(feature = "trace")]
+            if let Some(t) = self.device.trace.lock().as_mut() {
+                t.add(trace::Action::DestroyBlas(self.info.id()));
+            }
+
+            unsafe {
+                use hal::Device;
+                self.device.raw().destroy_acceleration_structure(raw);
+            }
+        }
+    }
+}
+
+impl<A: HalApi> Resource<BlasId> for Blas<A> {
+    const TYPE: ResourceType = "Blas";
+
+    fn as_info(&self) -> &ResourceInfo<BlasId> {
+        &self.info
+    }
+
+    fn as_info_mut(&mut self) -> &mut ResourceInfo<BlasId> {
+        &mut self.info
+    }
+}
+
+impl<A: HalApi> Blas<A> {
+    pub(crate) fn raw(&self) -> &A::AccelerationStructure {
+        self.raw.as_ref().unwrap()
+    }
+}
+
+/// A top level acceleration structure, holding instances of bottom level
+/// acceleration structures.
+#[derive(Debug)]
+pub struct Tlas<A: HalApi> {
+    pub(crate) raw: Option<A::AccelerationStructure>,
+    pub(crate) device: Arc<Device<A>>,
+    pub(crate) info: ResourceInfo<TlasId>,
+    pub(crate) size_info: hal::AccelerationStructureBuildSizes,
+    pub(crate) max_instance_count: u32,
+    pub(crate) flags: wgt::AccelerationStructureFlags,
+}
+
+impl<A: HalApi> Drop for Tlas<A> {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw Tlas {:?}", self.info.label());
+        if let Some(raw) = self.raw.take() {
+            #[cfg(feature = "trace")]
+            if let Some(t) = self.device.trace.lock().as_mut() {
+                t.add(trace::Action::DestroyTlas(self.info.id()));
+            }
+
+            unsafe {
+                use hal::Device;
+                self.device.raw().destroy_acceleration_structure(raw);
+            }
+        }
+    }
+}
+
+impl<A: HalApi> Resource<TlasId> for Tlas<A> {
+    const TYPE: ResourceType = "Tlas";
+
+    fn as_info(&self) -> &ResourceInfo<TlasId> {
+        &self.info
+    }
+
+    fn as_info_mut(&mut self) -> &mut ResourceInfo<TlasId> {
+        &mut self.info
+    }
+}
+
+impl<A: HalApi> Tlas<A> {
+    pub(crate) fn raw(&self) -> &A::AccelerationStructure {
+        self.raw.as_ref().unwrap()
+    }
+}
+
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum DestroyError {
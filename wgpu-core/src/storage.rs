@@ -1,10 +1,11 @@
 use std::{marker::PhantomData, ops, sync::Arc};
 
+use parking_lot::{RwLockReadGuard, RwLockWriteGuard};
 use wgt::Backend;
 
 use crate::{id, resource::Resource, Epoch, Index};
 
-/// An entry in a `Storage::map` table.
+/// An entry in a `Shard::map` table.
 #[derive(Debug)]
 pub(crate) enum Element<T> {
     /// There are no live ids with this index.
@@ -24,55 +25,63 @@ pub(crate) enum Element<T> {
 #[derive(Clone, Debug)]
 pub(crate) struct InvalidId;
 
-/// A table of `T` values indexed by the id type `I`.
+/// The number of shards a [`Registry`]'s table is split into.
+///
+/// Each shard is locked separately, so that threads creating, looking up or
+/// releasing resources of the same type only contend when their ids fall into
+/// the same shard. Ids are spread over the shards by their index, so that
+/// resources allocated one after another land in different shards.
+///
+/// [`Registry`]: crate::registry::Registry
+pub(crate) const SHARD_COUNT: usize = 16;
+
+/// Returns the shard holding the ids with the given index.
+pub(crate) fn shard_index(index: Index) -> usize {
+    index as usize % SHARD_COUNT
+}
+
+/// Returns the position of the ids with the given index in their shard.
+fn slot(index: Index) -> usize {
+    index as usize / SHARD_COUNT
+}
+
+/// One shard of a [`Registry`]'s table of `T` values.
 ///
-/// The table is represented as a vector indexed by the ids' index
-/// values, so you should use an id allocator like `IdentityManager`
-/// that keeps the index values dense and close to zero.
+/// The shard holds the ids whose index selects it in [`shard_index`]. Its
+/// vector is indexed by the ids' index values divided by [`SHARD_COUNT`], so
+/// you should use an id allocator like `IdentityManager` that keeps the index
+/// values dense and close to zero.
+///
+/// [`Registry`]: crate::registry::Registry
 #[derive(Debug)]
-pub struct Storage<T, I>
+pub(crate) struct Shard<T, I>
 where
     T: Resource<I>,
     I: id::TypedId,
 {
     pub(crate) map: Vec<Element<T>>,
+    index: usize,
     kind: &'static str,
     _phantom: PhantomData<I>,
 }
 
-impl<T, I> ops::Index<I> for Storage<T, I>
+impl<T, I> Shard<T, I>
 where
     T: Resource<I>,
     I: id::TypedId,
 {
-    type Output = Arc<T>;
-    fn index(&self, id: I) -> &Arc<T> {
-        self.get(id).unwrap()
-    }
-}
-impl<T, I> Storage<T, I>
-where
-    T: Resource<I>,
-    I: id::TypedId,
-{
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(index: usize) -> Self {
         Self {
             map: Vec::new(),
+            index,
             kind: T::TYPE,
             _phantom: PhantomData,
         }
     }
-}
 
-impl<T, I> Storage<T, I>
-where
-    T: Resource<I>,
-    I: id::TypedId,
-{
-    #[allow(dead_code)]
     pub(crate) fn contains(&self, id: I) -> bool {
         let (index, epoch, _) = id.unzip();
-        match self.map.get(index as usize) {
+        match self.map.get(slot(index)) {
             Some(&Element::Vacant) => false,
             Some(&Element::Occupied(_, storage_epoch) | &Element::Error(storage_epoch, _)) => {
                 storage_epoch == epoch
@@ -90,7 +99,7 @@ where
     /// be in a different hub.
     pub(crate) fn try_get(&self, id: I) -> Result<Option<&Arc<T>>, InvalidId> {
         let (index, epoch, _) = id.unzip();
-        let (result, storage_epoch) = match self.map.get(index as usize) {
+        let (result, storage_epoch) = match self.map.get(slot(index)) {
             Some(&Element::Occupied(ref v, epoch)) => (Ok(Some(v)), epoch),
             Some(&Element::Vacant) => return Ok(None),
             Some(&Element::Error(epoch, ..)) => (Err(InvalidId), epoch),
@@ -108,7 +117,7 @@ where
     /// Panics if there is an epoch mismatch, or the entry is empty.
     pub(crate) fn get(&self, id: I) -> Result<&Arc<T>, InvalidId> {
         let (index, epoch, _) = id.unzip();
-        let (result, storage_epoch) = match self.map.get(index as usize) {
+        let (result, storage_epoch) = match self.map.get(slot(index)) {
             Some(&Element::Occupied(ref v, epoch)) => (Ok(v), epoch),
             Some(&Element::Vacant) => panic!("{}[{:?}] does not exist", self.kind, id),
             Some(&Element::Error(epoch, ..)) => (Err(InvalidId), epoch),
@@ -122,25 +131,20 @@ where
         result
     }
 
-    /// Get an owned reference to an item behind a potentially invalid ID.
-    /// Panics if there is an epoch mismatch, or the entry is empty.
-    pub(crate) fn get_owned(&self, id: I) -> Result<Arc<T>, InvalidId> {
-        Ok(Arc::clone(self.get(id)?))
-    }
-
     pub(crate) fn label_for_invalid_id(&self, id: I) -> &str {
         let (index, _, _) = id.unzip();
-        match self.map.get(index as usize) {
+        match self.map.get(slot(index)) {
             Some(Element::Error(_, label)) => label,
             _ => "",
         }
     }
 
-    fn insert_impl(&mut self, index: usize, epoch: Epoch, element: Element<T>) {
-        if index >= self.map.len() {
-            self.map.resize_with(index + 1, || Element::Vacant);
+    fn insert_impl(&mut self, index: Index, epoch: Epoch, element: Element<T>) {
+        let slot = slot(index);
+        if slot >= self.map.len() {
+            self.map.resize_with(slot + 1, || Element::Vacant);
         }
-        match std::mem::replace(&mut self.map[index], element) {
+        match std::mem::replace(&mut self.map[slot], element) {
             Element::Vacant => {}
             Element::Occupied(_, storage_epoch) => {
                 assert_ne!(
@@ -164,23 +168,19 @@ where
     pub(crate) fn insert(&mut self, id: I, value: Arc<T>) {
         log::trace!("User is inserting {}{:?}", T::TYPE, id);
         let (index, epoch, _backend) = id.unzip();
-        self.insert_impl(index as usize, epoch, Element::Occupied(value, epoch))
+        self.insert_impl(index, epoch, Element::Occupied(value, epoch))
     }
 
     pub(crate) fn insert_error(&mut self, id: I, label: &str) {
         log::trace!("User is insering as error {}{:?}", T::TYPE, id);
         let (index, epoch, _) = id.unzip();
-        self.insert_impl(
-            index as usize,
-            epoch,
-            Element::Error(epoch, label.to_string()),
-        )
+        self.insert_impl(index, epoch, Element::Error(epoch, label.to_string()))
     }
 
     pub(crate) fn replace_with_error(&mut self, id: I) -> Result<Arc<T>, InvalidId> {
         let (index, epoch, _) = id.unzip();
         match std::mem::replace(
-            &mut self.map[index as usize],
+            &mut self.map[slot(index)],
             Element::Error(epoch, String::new()),
         ) {
             Element::Vacant => panic!("Cannot access vacant resource"),
@@ -195,13 +195,13 @@ where
     pub(crate) fn force_replace(&mut self, id: I, value: T) {
         log::trace!("User is replacing {}{:?}", T::TYPE, id);
         let (index, epoch, _) = id.unzip();
-        self.map[index as usize] = Element::Occupied(Arc::new(value), epoch);
+        self.map[slot(index)] = Element::Occupied(Arc::new(value), epoch);
     }
 
     pub(crate) fn remove(&mut self, id: I) -> Option<Arc<T>> {
         log::trace!("User is removing {}{:?}", T::TYPE, id);
         let (index, epoch, _) = id.unzip();
-        match std::mem::replace(&mut self.map[index as usize], Element::Vacant) {
+        match std::mem::replace(&mut self.map[slot(index)], Element::Vacant) {
             Element::Occupied(value, storage_epoch) => {
                 assert_eq!(epoch, storage_epoch);
                 Some(value)
@@ -212,11 +212,13 @@ where
     }
 
     pub(crate) fn iter(&self, backend: Backend) -> impl Iterator<Item = (I, &Arc<T>)> {
+        let shard = self.index;
         self.map
             .iter()
             .enumerate()
-            .filter_map(move |(index, x)| match *x {
+            .filter_map(move |(slot, x)| match *x {
                 Element::Occupied(ref value, storage_epoch) => {
+                    let index = slot * SHARD_COUNT + shard;
                     Some((I::zip(index as Index, storage_epoch, backend), value))
                 }
                 _ => None,
@@ -226,8 +228,158 @@ where
     pub(crate) fn kind(&self) -> &str {
         self.kind
     }
+}
+
+/// A table of `T` values indexed by the id type `I`.
+///
+/// This is a read-only view of all the shards of a [`Registry`], holding a
+/// read lock on each of them. It is obtained with [`Registry::read`], and is
+/// meant for code that looks up many resources of the same type in a row,
+/// like command encoding. Single lookups should use [`Registry::get`] instead,
+/// which only locks the shard of the id.
+///
+/// [`Registry`]: crate::registry::Registry
+/// [`Registry::read`]: crate::registry::Registry::read
+/// [`Registry::get`]: crate::registry::Registry::get
+#[derive(Debug)]
+pub struct Storage<'a, T, I>
+where
+    T: Resource<I>,
+    I: id::TypedId,
+{
+    shards: [RwLockReadGuard<'a, Shard<T, I>>; SHARD_COUNT],
+}
+
+impl<T, I> ops::Index<I> for Storage<'_, T, I>
+where
+    T: Resource<I>,
+    I: id::TypedId,
+{
+    type Output = Arc<T>;
+    fn index(&self, id: I) -> &Arc<T> {
+        self.get(id).unwrap()
+    }
+}
+
+impl<'a, T, I> Storage<'a, T, I>
+where
+    T: Resource<I>,
+    I: id::TypedId,
+{
+    pub(crate) fn new(shards: [RwLockReadGuard<'a, Shard<T, I>>; SHARD_COUNT]) -> Self {
+        Self { shards }
+    }
+
+    fn shard(&self, id: I) -> &Shard<T, I> {
+        &self.shards[shard_index(id.unzip().0)]
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn contains(&self, id: I) -> bool {
+        self.shard(id).contains(id)
+    }
+
+    /// Get a reference to an item behind a potentially invalid ID.
+    /// Panics if there is an epoch mismatch, or the entry is empty.
+    pub(crate) fn get(&self, id: I) -> Result<&Arc<T>, InvalidId> {
+        self.shard(id).get(id)
+    }
 
+    pub(crate) fn iter(&self, backend: Backend) -> Box<dyn Iterator<Item = (I, &Arc<T>)> + '_> {
+        Box::new(
+            self.shards
+                .iter()
+                .flat_map(move |shard| shard.iter(backend)),
+        )
+    }
+
+    /// Returns an upper bound of the index values of the ids in the table.
     pub(crate) fn len(&self) -> usize {
-        self.map.len()
+        let slots = self.shards.iter().map(|shard| shard.map.len()).max();
+        slots.unwrap_or(0) * SHARD_COUNT
+    }
+}
+
+/// A mutable view of all the shards of a [`Registry`], holding a write lock
+/// on each of them.
+///
+/// It is obtained with [`Registry::write`], for changes that must appear
+/// atomic to other threads.
+///
+/// [`Registry`]: crate::registry::Registry
+/// [`Registry::write`]: crate::registry::Registry::write
+#[derive(Debug)]
+pub struct StorageMut<'a, T, I>
+where
+    T: Resource<I>,
+    I: id::TypedId,
+{
+    shards: [RwLockWriteGuard<'a, Shard<T, I>>; SHARD_COUNT],
+}
+
+impl<'a, T, I> StorageMut<'a, T, I>
+where
+    T: Resource<I>,
+    I: id::TypedId,
+{
+    pub(crate) fn new(shards: [RwLockWriteGuard<'a, Shard<T, I>>; SHARD_COUNT]) -> Self {
+        Self { shards }
+    }
+
+    fn shard(&self, id: I) -> &Shard<T, I> {
+        &self.shards[shard_index(id.unzip().0)]
+    }
+
+    fn shard_mut(&mut self, id: I) -> &mut Shard<T, I> {
+        &mut self.shards[shard_index(id.unzip().0)]
+    }
+
+    pub(crate) fn contains(&self, id: I) -> bool {
+        self.shard(id).contains(id)
+    }
+
+    /// Get a reference to an item behind a potentially invalid ID.
+    /// Panics if there is an epoch mismatch, or the entry is empty.
+    pub(crate) fn get(&self, id: I) -> Result<&Arc<T>, InvalidId> {
+        self.shard(id).get(id)
+    }
+
+    pub(crate) fn insert_error(&mut self, id: I, label: &str) {
+        self.shard_mut(id).insert_error(id, label)
+    }
+
+    pub(crate) fn replace_with_error(&mut self, id: I) -> Result<Arc<T>, InvalidId> {
+        self.shard_mut(id).replace_with_error(id)
+    }
+
+    pub(crate) fn remove(&mut self, id: I) -> Option<Arc<T>> {
+        self.shard_mut(id).remove(id)
+    }
+
+    pub(crate) fn iter(&self, backend: Backend) -> Box<dyn Iterator<Item = (I, &Arc<T>)> + '_> {
+        Box::new(
+            self.shards
+                .iter()
+                .flat_map(move |shard| shard.iter(backend)),
+        )
+    }
+
+    /// Removes all the elements of the table, returning the resources it held.
+    pub(crate) fn drain(&mut self) -> Vec<Arc<T>> {
+        self.shards
+            .iter_mut()
+            .flat_map(|shard| shard.map.drain(..))
+            .filter_map(|element| match element {
+                Element::Occupied(value, _) => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Removes all the elements of the table.
+    pub(crate) fn clear(&mut self) {
+        for shard in self.shards.iter_mut() {
+            shard.map.clear();
+        }
     }
 }
@@ -322,6 +322,7 @@ pub(crate) struct BindGroupStates<A: HalApi> {
     pub textures: TextureBindGroupState<A>,
     pub views: StatelessBindGroupSate<id::TextureViewId, resource::TextureView<A>>,
     pub samplers: StatelessBindGroupSate<id::SamplerId, resource::Sampler<A>>,
+    pub acceleration_structures: StatelessBindGroupSate<id::TlasId, resource::Tlas<A>>,
 }
 
 impl<A: HalApi> BindGroupStates<A> {
@@ -331,6 +332,7 @@ impl<A: HalApi> BindGroupStates<A> {
             textures: TextureBindGroupState::new(),
             views: StatelessBindGroupSate::new(),
             samplers: StatelessBindGroupSate::new(),
+            acceleration_structures: StatelessBindGroupSate::new(),
         }
     }
 
@@ -343,6 +345,7 @@ impl<A: HalApi> BindGroupStates<A> {
         self.textures.optimize();
         self.views.optimize();
         self.samplers.optimize();
+        self.acceleration_structures.optimize();
     }
 }
 
@@ -500,6 +503,8 @@ pub(crate) struct Tracker<A: HalApi> {
     pub render_pipelines: StatelessTracker<A, id::RenderPipelineId, pipeline::RenderPipeline<A>>,
     pub bundles: StatelessTracker<A, id::RenderBundleId, command::RenderBundle<A>>,
     pub query_sets: StatelessTracker<A, id::QuerySetId, resource::QuerySet<A>>,
+    pub blas_s: StatelessTracker<A, id::BlasId, resource::Blas<A>>,
+    pub tlas_s: StatelessTracker<A, id::TlasId, resource::Tlas<A>>,
 }
 
 impl<A: HalApi> Tracker<A> {
@@ -514,6 +519,8 @@ impl<A: HalApi> Tracker<A> {
             render_pipelines: StatelessTracker::new(),
             bundles: StatelessTracker::new(),
             query_sets: StatelessTracker::new(),
+            blas_s: StatelessTracker::new(),
+            tlas_s: StatelessTracker::new(),
         }
     }
 
@@ -4,6 +4,17 @@ use std::{collections::hash_map::Entry, fmt};
 use thiserror::Error;
 use wgt::{BindGroupLayoutEntry, BindingType};
 
+/// The checks naga runs on shader modules.
+///
+/// With the `unchecked_shaders` feature, naga only analyzes modules, which is
+/// still needed to reflect their interface.
+pub(crate) const SHADER_VALIDATION_FLAGS: naga::valid::ValidationFlags =
+    if cfg!(feature = "unchecked_shaders") {
+        naga::valid::ValidationFlags::empty()
+    } else {
+        naga::valid::ValidationFlags::all()
+    };
+
 #[derive(Debug)]
 enum ResourceType {
     Buffer {
@@ -17,6 +28,7 @@ enum ResourceType {
     Sampler {
         comparison: bool,
     },
+    AccelerationStructure,
 }
 
 #[derive(Debug)]
@@ -517,6 +529,10 @@ impl Resource {
                     });
                 }
             }
+            ResourceType::AccelerationStructure => match entry.ty {
+                BindingType::AccelerationStructure => (),
+                _ => return Err(BindingError::WrongType),
+            },
         };
 
         Ok(())
@@ -594,6 +610,7 @@ impl Resource {
                     },
                 }
             }
+            ResourceType::AccelerationStructure => BindingType::AccelerationStructure,
         })
     }
 }
@@ -693,7 +710,7 @@ impl NumericType {
             | Tf::Depth24PlusStencil8 => {
                 panic!("Unexpected depth format")
             }
-            Tf::NV12 => panic!("Unexpected nv12 format"),
+            Tf::NV12 | Tf::P010 => panic!("Unexpected planar format"),
             Tf::Rgb9e5Ufloat => (NumericDimension::Vector(Vs::Tri), Scalar::F32),
             Tf::Bc1RgbaUnorm
             | Tf::Bc1RgbaUnormSrgb
@@ -775,6 +792,19 @@ pub fn check_texture_format(
     }
 }
 
+/// Return true if the vertex capture `format` exactly matches the provided vertex `output`.
+pub fn check_vertex_capture_format(
+    format: wgt::VertexFormat,
+    output: &NumericType,
+) -> Result<(), NumericType> {
+    let nt = NumericType::from_vertex_format(format);
+    if nt.is_subtype_of(output) && output.is_subtype_of(&nt) {
+        Ok(())
+    } else {
+        Err(nt)
+    }
+}
+
 pub enum BindingLayoutSource<'a> {
     /// The binding layout is derived from the pipeline layout.
     ///
@@ -892,6 +922,7 @@ impl Interface {
                     class,
                 },
                 naga::TypeInner::Sampler { comparison } => ResourceType::Sampler { comparison },
+                naga::TypeInner::AccelerationStructure => ResourceType::AccelerationStructure,
                 naga::TypeInner::Array { stride, .. } => ResourceType::Buffer {
                     size: wgt::BufferSize::new(stride as u64).unwrap(),
                 },
@@ -927,6 +958,24 @@ impl Interface {
                     &module.types,
                 );
             }
+            // Mesh shaders output the vertices in their output variable.
+            if let Some(ref mesh_info) = entry_point.mesh_info {
+                Self::populate(
+                    &mut ep.outputs,
+                    None,
+                    mesh_info.vertex_output_type,
+                    &module.types,
+                );
+            }
+            // Geometry shaders output the vertices they emit.
+            if let Some(ref geometry_info) = entry_point.geometry_info {
+                Self::populate(
+                    &mut ep.outputs,
+                    None,
+                    geometry_info.vertex_output_type,
+                    &module.types,
+                );
+            }
 
             for (var_handle, var) in module.global_variables.iter() {
                 let usage = info[var_handle];
@@ -968,6 +1017,8 @@ impl Interface {
             wgt::ShaderStages::VERTEX => naga::ShaderStage::Vertex,
             wgt::ShaderStages::FRAGMENT => naga::ShaderStage::Fragment,
             wgt::ShaderStages::COMPUTE => naga::ShaderStage::Compute,
+            wgt::ShaderStages::TASK => naga::ShaderStage::Task,
+            wgt::ShaderStages::MESH => naga::ShaderStage::Mesh,
             _ => unreachable!(),
         };
         let pair = (shader_stage, entry_point_name.to_string());
@@ -1006,6 +1057,9 @@ impl Interface {
                             break 'err Err(BindingError::Invisible);
                         }
 
+                        if cfg!(feature = "unchecked_shaders") {
+                            break 'err Ok(());
+                        }
                         res.check_binding_use(entry)
                     }
                     BindingLayoutSource::Derived(layouts) => {
@@ -1047,7 +1101,9 @@ impl Interface {
         //
         // We only need to do this if the binding layout is provided by the user, as derived
         // layouts will inherently be correctly tagged.
-        if let BindingLayoutSource::Provided(layouts) = layouts {
+        if let (BindingLayoutSource::Provided(layouts), false) =
+            (layouts, cfg!(feature = "unchecked_shaders"))
+        {
             for &(texture_handle, sampler_handle) in entry_point.sampling_pairs.iter() {
                 let texture_bind = &self.resources[texture_handle].bind;
                 let sampler_bind = &self.resources[sampler_handle].bind;
@@ -1089,7 +1145,9 @@ impl Interface {
         }
 
         // check workgroup size limits
-        if shader_stage == naga::ShaderStage::Compute {
+        if let naga::ShaderStage::Compute | naga::ShaderStage::Task | naga::ShaderStage::Mesh =
+            shader_stage
+        {
             let max_workgroup_size_limits = [
                 self.limits.max_compute_workgroup_size_x,
                 self.limits.max_compute_workgroup_size_y,
@@ -1146,9 +1204,14 @@ impl Interface {
                                             iv.ty.dim.num_components(),
                                         )
                                     }
-                                    naga::ShaderStage::Compute => (false, 0),
+                                    naga::ShaderStage::Compute
+                                    | naga::ShaderStage::Task
+                                    | naga::ShaderStage::Mesh
+                                    | naga::ShaderStage::Geometry
+                                    | naga::ShaderStage::TessellationControl
+                                    | naga::ShaderStage::TessellationEvaluation => (false, 0),
                                 };
-                                if compatible {
+                                if compatible || cfg!(feature = "unchecked_shaders") {
                                     Ok(num_components)
                                 } else {
                                     Err(InputError::WrongType(provided.ty))
@@ -1177,6 +1240,7 @@ impl Interface {
             && !self
                 .features
                 .contains(wgt::Features::SHADER_UNUSED_VERTEX_OUTPUT)
+            && !cfg!(feature = "unchecked_shaders")
         {
             for &index in inputs.keys() {
                 // This is a linear scan, but the count should be low enough
@@ -1192,7 +1256,7 @@ impl Interface {
             }
         }
 
-        if shader_stage == naga::ShaderStage::Vertex {
+        if let naga::ShaderStage::Vertex | naga::ShaderStage::Mesh = shader_stage {
             for output in entry_point.outputs.iter() {
                 //TODO: count builtins towards the limit?
                 inter_stage_components += match *output {
@@ -8,8 +8,13 @@ fn main() {
         webgl: { all(target_arch = "wasm32", not(target_os = "emscripten"), gles) },
         Emscripten: { all(target_os = "emscripten", gles) },
         dx12: { all(target_os = "windows", feature = "dx12") },
-        gles: { all(feature = "gles") },
-        metal: { all(any(target_os = "ios", target_os = "macos"), feature = "metal") },
-        vulkan: { all(not(target_arch = "wasm32"), feature = "vulkan") }
+        apple: { any(target_os = "ios", target_os = "macos") },
+        gles: { all(feature = "gles", any(not(apple), feature = "angle")) },
+        metal: { all(apple, feature = "metal") },
+        vulkan: { all(
+            not(target_arch = "wasm32"),
+            feature = "vulkan",
+            any(not(apple), feature = "vulkan-portability")
+        ) }
     }
 }
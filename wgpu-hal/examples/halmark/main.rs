@@ -124,9 +124,15 @@ impl<A: hal::Api> Example<A> {
             .ok_or("failed to get surface capabilities")?;
         log::info!("Surface caps: {:#?}", surface_caps);
 
-        let hal::OpenDevice { device, queue } = unsafe {
+        let hal::OpenDevice { device, queue, .. } = unsafe {
             adapter
-                .open(wgt::Features::empty(), &wgt::Limits::default())
+                .open(
+                    wgt::Features::empty(),
+                    &wgt::Limits::default(),
+                    false,
+                    wgt::QueuePriority::Normal,
+                    0,
+                )
                 .unwrap()
         };
 
@@ -253,6 +259,7 @@ impl<A: hal::Api> Example<A> {
                 entry_point: "vs_main",
             },
             vertex_buffers: &[],
+            vertex_capture: &[],
             fragment_stage: Some(hal::ProgrammableStage {
                 module: &shader,
                 entry_point: "fs_main",
@@ -269,6 +276,7 @@ impl<A: hal::Api> Example<A> {
                 write_mask: wgt::ColorWrites::default(),
             })],
             multiview: None,
+            cache: None,
         };
         let pipeline = unsafe { device.create_render_pipeline(&pipeline_desc).unwrap() };
 
@@ -422,6 +430,7 @@ impl<A: hal::Api> Example<A> {
             dimension: wgt::TextureViewDimension::D2,
             usage: hal::TextureUses::RESOURCE,
             range: wgt::ImageSubresourceRange::default(),
+            swizzle: wgt::ComponentMapping::IDENTITY,
         };
         let texture_view = unsafe { device.create_texture_view(&texture, &view_desc).unwrap() };
 
@@ -660,6 +669,7 @@ impl<A: hal::Api> Example<A> {
             dimension: wgt::TextureViewDimension::D2,
             usage: hal::TextureUses::COLOR_TARGET,
             range: wgt::ImageSubresourceRange::default(),
+            swizzle: wgt::ComponentMapping::IDENTITY,
         };
         let surface_tex_view = unsafe {
             self.device
@@ -692,6 +702,7 @@ impl<A: hal::Api> Example<A> {
             multiview: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            shading_rate_attachment: None,
         };
         unsafe {
             ctx.encoder.begin_render_pass(&pass_desc);
@@ -772,7 +783,7 @@ cfg_if::cfg_if! {
         type Api = hal::api::Metal;
     }
     // Wasm + Vulkan
-    else if #[cfg(all(not(target_arch = "wasm32"), feature = "vulkan"))] {
+    else if #[cfg(vulkan)] {
         type Api = hal::api::Vulkan;
     }
     // Windows + DX12
@@ -780,7 +791,7 @@ cfg_if::cfg_if! {
         type Api = hal::api::Dx12;
     }
     // Anything + GLES
-    else if #[cfg(feature = "gles")] {
+    else if #[cfg(gles)] {
         type Api = hal::api::Gles;
     }
     // Fallback
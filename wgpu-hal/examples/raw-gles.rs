@@ -10,7 +10,7 @@
 
 extern crate wgpu_hal as hal;
 
-#[cfg(not(any(windows, target_arch = "wasm32")))]
+#[cfg(all(gles, not(any(windows, target_arch = "wasm32"))))]
 fn main() {
     env_logger::init();
     println!("Initializing external GL context");
@@ -116,17 +116,28 @@ fn main() {
     fill_screen(&exposed, 640, 400);
 }
 
-#[cfg(any(windows, all(target_arch = "wasm32", not(target_os = "emscripten"))))]
+#[cfg(any(
+    not(gles),
+    windows,
+    all(target_arch = "wasm32", not(target_os = "emscripten"))
+))]
 fn main() {}
 
-#[cfg(any(not(any(windows, target_arch = "wasm32")), target_os = "emscripten"))]
+#[cfg(all(
+    gles,
+    any(not(any(windows, target_arch = "wasm32")), target_os = "emscripten")
+))]
 fn fill_screen(exposed: &hal::ExposedAdapter<hal::api::Gles>, width: u32, height: u32) {
     use hal::{Adapter as _, CommandEncoder as _, Device as _, Queue as _};
 
     let od = unsafe {
-        exposed
-            .adapter
-            .open(wgt::Features::empty(), &wgt::Limits::downlevel_defaults())
+        exposed.adapter.open(
+            wgt::Features::empty(),
+            &wgt::Limits::downlevel_defaults(),
+            false,
+            wgt::QueuePriority::Normal,
+            0,
+        )
     }
     .unwrap();
 
@@ -142,6 +153,7 @@ fn fill_screen(exposed: &hal::ExposedAdapter<hal::api::Gles>, width: u32, height
                     dimension: wgt::TextureViewDimension::D2,
                     usage: hal::TextureUses::COLOR_TARGET,
                     range: wgt::ImageSubresourceRange::default(),
+                    swizzle: wgt::ComponentMapping::IDENTITY,
                 },
             )
             .unwrap()
@@ -177,6 +189,7 @@ fn fill_screen(exposed: &hal::ExposedAdapter<hal::api::Gles>, width: u32, height
         multiview: None,
         timestamp_writes: None,
         occlusion_query_set: None,
+        shading_rate_attachment: None,
     };
     unsafe {
         encoder.begin_encoding(None).unwrap();
@@ -250,8 +250,17 @@ impl<A: hal::Api> Example<A> {
             .expect("Surface doesn't support presentation");
         log::info!("Surface caps: {:#?}", surface_caps);
 
-        let hal::OpenDevice { device, queue } =
-            unsafe { adapter.open(features, &wgt::Limits::default()).unwrap() };
+        let hal::OpenDevice { device, queue, .. } = unsafe {
+            adapter
+                .open(
+                    features,
+                    &wgt::Limits::default(),
+                    false,
+                    wgt::QueuePriority::Normal,
+                    0,
+                )
+                .unwrap()
+        };
 
         let window_size: (u32, u32) = window.inner_size().into();
         dbg!(&surface_caps.formats);
@@ -372,6 +381,7 @@ impl<A: hal::Api> Example<A> {
                     module: &shader_module,
                     entry_point: "main",
                 },
+                cache: None,
             })
         }
         .unwrap();
@@ -555,6 +565,7 @@ impl<A: hal::Api> Example<A> {
             dimension: wgt::TextureViewDimension::D2,
             usage: hal::TextureUses::STORAGE_READ_WRITE | hal::TextureUses::COPY_SRC,
             range: wgt::ImageSubresourceRange::default(),
+            swizzle: wgt::ComponentMapping::IDENTITY,
         };
         let texture_view = unsafe { device.create_texture_view(&texture, &view_desc).unwrap() };
 
@@ -887,6 +898,7 @@ impl<A: hal::Api> Example<A> {
             dimension: wgt::TextureViewDimension::D2,
             usage: hal::TextureUses::COPY_DST,
             range: wgt::ImageSubresourceRange::default(),
+            swizzle: wgt::ComponentMapping::IDENTITY,
         };
         let surface_tex_view = unsafe {
             self.device
@@ -1040,7 +1052,7 @@ cfg_if::cfg_if! {
         type Api = hal::api::Metal;
     }
     // Wasm + Vulkan
-    else if #[cfg(all(not(target_arch = "wasm32"), feature = "vulkan"))] {
+    else if #[cfg(vulkan)] {
         type Api = hal::api::Vulkan;
     }
     // Windows + DX12
@@ -1048,7 +1060,7 @@ cfg_if::cfg_if! {
         type Api = hal::api::Dx12;
     }
     // Anything + GLES
-    else if #[cfg(feature = "gles")] {
+    else if #[cfg(gles)] {
         type Api = hal::api::Gles;
     }
     // Fallback
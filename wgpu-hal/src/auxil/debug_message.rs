@@ -0,0 +1,168 @@
+/*! Routing of backend debug messages to user callbacks.
+
+Validation layers and debug runtimes report their messages through process-wide
+hooks on some backends (the D3D12 debug output and the GL debug callback), so
+the callbacks are kept in a process-wide list. Every message is reported to all
+the registered callbacks, and only logged when there are none.
+*/
+
+use parking_lot::{lock_api::RawRwLock as _, RwLock};
+
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// Callback receiving debug messages, see [`register`].
+pub type DebugCallback = Arc<dyn Fn(&wgt::DebugMessage) + Send + Sync + 'static>;
+
+static CALLBACKS: RwLock<Vec<(u64, DebugCallback)>> =
+    RwLock::const_new(parking_lot::RawRwLock::INIT, Vec::new());
+/// Shortcut to check if `CALLBACKS` is empty without locking it.
+static HAS_CALLBACKS: AtomicBool = AtomicBool::new(false);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Set while this thread is calling the callbacks.
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Keeps a callback given to [`register`] registered, until dropped.
+#[derive(Debug)]
+pub struct Registration {
+    id: u64,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut callbacks = CALLBACKS.write();
+        callbacks.retain(|&(id, _)| id != self.id);
+        HAS_CALLBACKS.store(!callbacks.is_empty(), Ordering::Release);
+    }
+}
+
+/// Reports the debug messages of all backends to `callback`, instead of logging them.
+pub fn register(callback: DebugCallback) -> Registration {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut callbacks = CALLBACKS.write();
+    callbacks.push((id, callback));
+    HAS_CALLBACKS.store(true, Ordering::Release);
+    Registration { id }
+}
+
+/// Returns true if there are callbacks to report messages to.
+pub fn has_callbacks() -> bool {
+    HAS_CALLBACKS.load(Ordering::Acquire)
+}
+
+/// Reports `message` to the registered callbacks.
+///
+/// Returns false if there are none, in which case the message should be logged.
+/// Messages caused by a callback itself are not reported back to the callbacks,
+/// so that they don't recurse, and are logged instead.
+pub fn report(message: &wgt::DebugMessage) -> bool {
+    if !has_callbacks() || REPORTING.with(Cell::get) {
+        return false;
+    }
+    // Don't hold the lock while calling, the callbacks may use wgpu themselves.
+    let callbacks = CALLBACKS
+        .read()
+        .iter()
+        .map(|(_, callback)| callback.clone())
+        .collect::<Vec<_>>();
+    REPORTING.with(|reporting| reporting.set(true));
+    for callback in callbacks.iter() {
+        // Messages are reported from driver callbacks, which must not unwind.
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| callback(message)));
+    }
+    REPORTING.with(|reporting| reporting.set(false));
+    !callbacks.is_empty()
+}
+
+/// Severity of a message logged at `level`.
+pub fn severity_of_level(level: log::Level) -> wgt::DebugMessageSeverity {
+    match level {
+        log::Level::Error => wgt::DebugMessageSeverity::Error,
+        log::Level::Warn => wgt::DebugMessageSeverity::Warning,
+        log::Level::Info => wgt::DebugMessageSeverity::Info,
+        log::Level::Debug | log::Level::Trace => wgt::DebugMessageSeverity::Verbose,
+    }
+}
+
+/// Reports a message of `backend` logged at `level`, or logs it if there are no callbacks.
+#[allow(dead_code)] // in some configurations this function is dead
+pub(crate) fn report_or_log(
+    backend: wgt::Backend,
+    level: log::Level,
+    message: &str,
+    labels: Vec<String>,
+) {
+    if has_callbacks() {
+        let message = wgt::DebugMessage {
+            severity: severity_of_level(level),
+            backend: Some(backend),
+            message: message.to_string(),
+            labels,
+        };
+        if report(&message) {
+            return;
+        }
+    }
+    let _ = std::panic::catch_unwind(|| {
+        log::log!(level, "{}", message);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    /// The callbacks are process-wide, so tests registering them must not run concurrently.
+    static SERIAL: Mutex<()> = parking_lot::const_mutex(());
+
+    #[test]
+    fn report_to_registered() {
+        let _serial = SERIAL.lock();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let message = wgt::DebugMessage {
+            severity: wgt::DebugMessageSeverity::Warning,
+            backend: Some(wgt::Backend::Vulkan),
+            message: "message".to_string(),
+            labels: vec!["label".to_string()],
+        };
+
+        let registration = register({
+            let received = received.clone();
+            Arc::new(move |message| received.lock().push(message.clone()))
+        });
+        assert!(report(&message));
+        drop(registration);
+        assert!(!report(&message));
+
+        assert_eq!(*received.lock(), [message]);
+    }
+    #[test]
+    fn no_reentrant_report() {
+        let _serial = SERIAL.lock();
+        let nested = Arc::new(Mutex::new(Vec::new()));
+        let message = wgt::DebugMessage {
+            severity: wgt::DebugMessageSeverity::Error,
+            backend: None,
+            message: "message".to_string(),
+            labels: Vec::new(),
+        };
+
+        let registration = register({
+            let nested = nested.clone();
+            Arc::new(move |message| nested.lock().push(report(message)))
+        });
+        assert!(report(&message));
+        drop(registration);
+
+        assert_eq!(*nested.lock(), [false]);
+    }
+}
@@ -92,9 +92,7 @@ unsafe extern "system" fn output_debug_string_handler(
         return excpt::EXCEPTION_CONTINUE_SEARCH;
     }
 
-    let _ = std::panic::catch_unwind(|| {
-        log::log!(level, "{}", message);
-    });
+    crate::auxil::debug_message::report_or_log(wgt::Backend::Dx12, level, message, Vec::new());
 
     if cfg!(debug_assertions) && level == log::Level::Error {
         // Set canary and continue
@@ -72,13 +72,12 @@ impl PresentationTimer {
     pub fn get_timestamp_ns(&self) -> u128 {
         // Always do u128 math _after_ hitting the timing function.
         match *self {
-            PresentationTimer::Dxgi { frequency } => {
+            PresentationTimer::Dxgi { .. } => {
                 let mut counter: LARGE_INTEGER = unsafe { mem::zeroed() };
                 let success = unsafe { QueryPerformanceCounter(&mut counter) };
                 assert_ne!(success, 0);
 
-                // counter * (1_000_000_000 / freq) but re-ordered to make more precise
-                (unsafe { *counter.QuadPart() } as u128 * 1_000_000_000) / frequency as u128
+                self.counter_to_ns(unsafe { *counter.QuadPart() } as u64)
             }
             PresentationTimer::IPresentationManager {
                 fnQueryInterruptTimePrecise,
@@ -91,4 +90,15 @@ impl PresentationTimer {
             }
         }
     }
+
+    /// Converts a tick count of this timer's clock to nanoseconds.
+    pub fn counter_to_ns(&self, counter: u64) -> u128 {
+        match *self {
+            PresentationTimer::Dxgi { frequency } => {
+                // counter * (1_000_000_000 / freq) but re-ordered to make more precise
+                (counter as u128 * 1_000_000_000) / frequency as u128
+            }
+            PresentationTimer::IPresentationManager { .. } => counter as u128 * 100,
+        }
+    }
 }
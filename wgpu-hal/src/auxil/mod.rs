@@ -4,6 +4,8 @@ pub(super) mod dxgi;
 #[cfg(all(native, feature = "renderdoc"))]
 pub(super) mod renderdoc;
 
+pub mod debug_message;
+
 pub mod db {
     pub mod amd {
         pub const VENDOR: u32 = 0x1002;
@@ -51,6 +53,12 @@ pub fn map_naga_stage(stage: naga::ShaderStage) -> wgt::ShaderStages {
         naga::ShaderStage::Vertex => wgt::ShaderStages::VERTEX,
         naga::ShaderStage::Fragment => wgt::ShaderStages::FRAGMENT,
         naga::ShaderStage::Compute => wgt::ShaderStages::COMPUTE,
+        naga::ShaderStage::Task => wgt::ShaderStages::TASK,
+        naga::ShaderStage::Mesh => wgt::ShaderStages::MESH,
+        // wgpu has no geometry or tessellation pipelines.
+        naga::ShaderStage::Geometry
+        | naga::ShaderStage::TessellationControl
+        | naga::ShaderStage::TessellationEvaluation => wgt::ShaderStages::NONE,
     }
 }
 
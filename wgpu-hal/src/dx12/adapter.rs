@@ -6,7 +6,10 @@ use parking_lot::Mutex;
 use std::{mem, ptr, sync::Arc, thread};
 use winapi::{
     shared::{
-        dxgi, dxgi1_2, dxgiformat::DXGI_FORMAT_B8G8R8A8_UNORM, minwindef::DWORD, windef, winerror,
+        dxgi, dxgi1_2,
+        dxgiformat::{DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_FORMAT_P010},
+        minwindef::DWORD,
+        windef, winerror,
     },
     um::{d3d12 as d3d12_ty, d3d12sdklayers, winuser},
 };
@@ -46,27 +49,87 @@ impl super::Adapter {
         &self.raw
     }
 
+    /// Opens a device on a queue created outside of wgpu, from the device of
+    /// this adapter.
+    ///
+    /// # Safety
+    ///
+    /// - `queue` must be a valid direct command queue, created from the device
+    ///   the adapter was exposed with.
+    pub unsafe fn device_from_raw(
+        &self,
+        queue: *mut d3d12_ty::ID3D12CommandQueue,
+        limits: &wgt::Limits,
+    ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
+        let queue = unsafe { d3d12::ComPtr::from_raw(queue) };
+        unsafe { self.open_with_queues(queue, Vec::new(), limits) }
+    }
+
+    unsafe fn open_with_queues(
+        &self,
+        queue: d3d12::CommandQueue,
+        additional_queues: Vec<d3d12::CommandQueue>,
+        limits: &wgt::Limits,
+    ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
+        let residency = super::residency::Residency::new(&self.raw, &self.device);
+        if residency.is_none() {
+            log::info!("Residency management is not supported by the adapter");
+        }
+
+        let device = super::Device::new(
+            self.device.clone(),
+            queue.clone(),
+            limits,
+            self.private_caps,
+            &self.library,
+            residency.clone(),
+            self.dxc_container.clone(),
+        )?;
+        Ok(crate::OpenDevice {
+            device,
+            queue: super::Queue {
+                raw: queue,
+                temp_lists: Mutex::new(Vec::new()),
+                residency: residency.clone(),
+            },
+            additional_queues: additional_queues
+                .into_iter()
+                .map(|raw| super::Queue {
+                    raw,
+                    temp_lists: Mutex::new(Vec::new()),
+                    residency: residency.clone(),
+                })
+                .collect(),
+        })
+    }
+
     #[allow(trivial_casts)]
+    /// Exposes `adapter`, using `raw_device` if it's given, or a new device
+    /// otherwise.
     pub(super) fn expose(
         adapter: d3d12::DxgiAdapter,
+        raw_device: Option<d3d12::Device>,
         library: &Arc<d3d12::D3D12Lib>,
         instance_flags: wgt::InstanceFlags,
         dxc_container: Option<Arc<shader_compilation::DxcContainer>>,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
         // Create the device so that we can get the capabilities.
-        let device = {
-            profiling::scope!("ID3D12Device::create_device");
-            match library.create_device(&adapter, d3d12::FeatureLevel::L11_0) {
-                Ok(pair) => match pair.into_result() {
-                    Ok(device) => device,
+        let device = match raw_device {
+            Some(device) => device,
+            None => {
+                profiling::scope!("ID3D12Device::create_device");
+                match library.create_device(&adapter, d3d12::FeatureLevel::L11_0) {
+                    Ok(pair) => match pair.into_result() {
+                        Ok(device) => device,
+                        Err(err) => {
+                            log::warn!("Device creation failed: {}", err);
+                            return None;
+                        }
+                    },
                     Err(err) => {
-                        log::warn!("Device creation failed: {}", err);
+                        log::warn!("Device creation function is not found: {:?}", err);
                         return None;
                     }
-                },
-                Err(err) => {
-                    log::warn!("Device creation function is not found: {:?}", err);
-                    return None;
                 }
             }
         };
@@ -155,7 +218,7 @@ impl super::Adapter {
             )
         });
 
-        let _depth_bounds_test_supported = {
+        let (_depth_bounds_test_supported, resolve_subresource_region) = {
             let mut features2: d3d12_ty::D3D12_FEATURE_DATA_D3D12_OPTIONS2 =
                 unsafe { mem::zeroed() };
             let hr = unsafe {
@@ -165,7 +228,14 @@ impl super::Adapter {
                     mem::size_of::<d3d12_ty::D3D12_FEATURE_DATA_D3D12_OPTIONS2>() as _,
                 )
             };
-            hr == 0 && features2.DepthBoundsTestSupported != 0
+            (
+                hr == 0 && features2.DepthBoundsTestSupported != 0,
+                // `ResolveSubresourceRegion` is only exposed by drivers
+                // supporting programmable sample positions.
+                hr == 0
+                    && features2.ProgrammableSamplePositionsTier
+                        != d3d12_ty::D3D12_PROGRAMMABLE_SAMPLE_POSITIONS_TIER_NOT_SUPPORTED,
+            )
         };
 
         let casting_fully_typed_format_supported = {
@@ -181,6 +251,30 @@ impl super::Adapter {
             hr == 0 && features3.CastingFullyTypedFormatSupported != 0
         };
 
+        let shading_rate_options = {
+            let mut features6: crate::dx12::types::D3D12_FEATURE_DATA_D3D12_OPTIONS6 =
+                unsafe { mem::zeroed() };
+            let hr = unsafe {
+                device.CheckFeatureSupport(
+                    30, // D3D12_FEATURE_D3D12_OPTIONS6
+                    &mut features6 as *mut _ as *mut _,
+                    mem::size_of::<crate::dx12::types::D3D12_FEATURE_DATA_D3D12_OPTIONS6>() as _,
+                )
+            };
+            if hr != 0 {
+                features6.VariableShadingRateTier =
+                    crate::dx12::types::D3D12_VARIABLE_SHADING_RATE_TIER_NOT_SUPPORTED;
+            }
+            features6
+        };
+        // The 2x4, 4x2 and 4x4 rates are only supported with `AdditionalShadingRatesSupported`.
+        let shading_rate = shading_rate_options.VariableShadingRateTier
+            >= crate::dx12::types::D3D12_VARIABLE_SHADING_RATE_TIER_1
+            && shading_rate_options.AdditionalShadingRatesSupported != 0;
+        let shading_rate_attachment = shading_rate
+            && shading_rate_options.VariableShadingRateTier
+                >= crate::dx12::types::D3D12_VARIABLE_SHADING_RATE_TIER_2;
+
         let private_caps = super::PrivateCapabilities {
             instance_flags,
             heterogeneous_resource_heaps: options.ResourceHeapTier
@@ -194,6 +288,7 @@ impl super::Adapter {
             },
             heap_create_not_zeroed: false, //TODO: winapi support for Options7
             casting_fully_typed_format_supported,
+            resolve_subresource_region,
             // See https://github.com/gfx-rs/wgpu/issues/3552
             suballocation_supported: !info.name.contains("Iris(R) Xe"),
         };
@@ -250,7 +345,15 @@ impl super::Adapter {
             | wgt::Features::SHADER_PRIMITIVE_INDEX
             | wgt::Features::RG11B10UFLOAT_RENDERABLE
             | wgt::Features::DUAL_SOURCE_BLENDING
-            | wgt::Features::TEXTURE_FORMAT_NV12;
+            | wgt::Features::TEXTURE_FORMAT_NV12
+            | wgt::Features::DEVICE_GENERATED_COMMANDS
+            | wgt::Features::CONDITIONAL_RENDERING
+            | wgt::Features::VERTEX_CAPTURE
+            | wgt::Features::PIPELINE_CACHE
+            | wgt::Features::TEXTURE_COMPONENT_SWIZZLE
+            | wgt::Features::EXTERNAL_TEXTURE_IMPORT
+            | wgt::Features::EXTERNAL_FENCE
+            | wgt::Features::EXTERNAL_MEMORY;
 
         //TODO: in order to expose this, we need to run a compute shader
         // that extract the necessary statistics out of the D3D12 result.
@@ -275,6 +378,16 @@ impl super::Adapter {
             shader_model_support.HighestShaderModel >= d3d12_ty::D3D_SHADER_MODEL_5_1,
         );
 
+        // Descriptor tables of version 1.0 root signatures are volatile, and only accessed
+        // descriptors need to be initialized with resource binding tier 3.
+        let partially_bound_binding_arrays = shader_model_support.HighestShaderModel
+            >= d3d12_ty::D3D_SHADER_MODEL_5_1
+            && options.ResourceBindingTier >= d3d12_ty::D3D12_RESOURCE_BINDING_TIER_3;
+        features.set(
+            wgt::Features::PARTIALLY_BOUND_BINDING_ARRAY,
+            partially_bound_binding_arrays,
+        );
+
         let bgra8unorm_storage_supported = {
             let mut bgra8unorm_info: d3d12_ty::D3D12_FEATURE_DATA_FORMAT_SUPPORT =
                 unsafe { mem::zeroed() };
@@ -294,9 +407,44 @@ impl super::Adapter {
             bgra8unorm_storage_supported,
         );
 
+        let p010_supported = {
+            let mut p010_info: d3d12_ty::D3D12_FEATURE_DATA_FORMAT_SUPPORT =
+                unsafe { mem::zeroed() };
+            p010_info.Format = DXGI_FORMAT_P010;
+            let hr = unsafe {
+                device.CheckFeatureSupport(
+                    d3d12_ty::D3D12_FEATURE_FORMAT_SUPPORT,
+                    &mut p010_info as *mut _ as *mut _,
+                    mem::size_of::<d3d12_ty::D3D12_FEATURE_DATA_FORMAT_SUPPORT>() as _,
+                )
+            };
+            let required = d3d12_ty::D3D12_FORMAT_SUPPORT1_TEXTURE2D
+                | d3d12_ty::D3D12_FORMAT_SUPPORT1_SHADER_SAMPLE;
+            hr == 0 && (p010_info.Support1 & required == required)
+        };
+        features.set(wgt::Features::TEXTURE_FORMAT_P010, p010_supported);
+
+        // Tier 2 reads unmapped tiles as zero and allows mapping a tile to several places.
+        features.set(
+            wgt::Features::TILED_TEXTURE,
+            options.TiledResourcesTier >= d3d12_ty::D3D12_TILED_RESOURCES_TIER_2,
+        );
+
+        // Tier 1 heaps can only hold one kind of resource.
+        features.set(
+            wgt::Features::MEMORY_HEAPS,
+            options.ResourceHeapTier != d3d12_ty::D3D12_RESOURCE_HEAP_TIER_1,
+        );
+
         // float32-filterable should always be available on d3d12
         features.set(wgt::Features::FLOAT32_FILTERABLE, true);
 
+        features.set(wgt::Features::SHADING_RATE, shading_rate);
+        features.set(
+            wgt::Features::SHADING_RATE_ATTACHMENT,
+            shading_rate_attachment,
+        );
+
         // TODO: Determine if IPresentationManager is supported
         let presentation_timer = auxil::dxgi::time::PresentationTimer::new_dxgi();
 
@@ -390,6 +538,22 @@ impl super::Adapter {
                     // store buffer sizes using 32 bit ints (a situation we have already encountered with vulkan).
                     max_buffer_size: i32::MAX as u64,
                     max_non_sampler_bindings: 1_000_000,
+                    max_binding_array_elements_per_shader_stage: if partially_bound_binding_arrays {
+                        full_heap_count
+                    } else {
+                        0
+                    },
+                    max_binding_array_sampler_elements_per_shader_stage:
+                        if partially_bound_binding_arrays {
+                            d3d12_ty::D3D12_MAX_SHADER_VISIBLE_SAMPLER_HEAP_SIZE
+                        } else {
+                            0
+                        },
+                    shading_rate_tile_size: if shading_rate_attachment {
+                        shading_rate_options.ShadingRateImageTileSize
+                    } else {
+                        0
+                    },
                 },
                 alignments: crate::Alignments {
                     buffer_copy_offset: wgt::BufferSize::new(
@@ -400,8 +564,16 @@ impl super::Adapter {
                         d3d12_ty::D3D12_TEXTURE_DATA_PITCH_ALIGNMENT as u64,
                     )
                     .unwrap(),
+                    ray_tracing_scratch_buffer_alignment: 0,
                 },
                 downlevel,
+                // D3D12 doesn't limit the number of queues, this keeps it reasonable.
+                max_additional_queues: 8,
+                memory_architecture: if features_architecture.UMA != 0 {
+                    wgt::MemoryArchitecture::Unified
+                } else {
+                    wgt::MemoryArchitecture::Discrete
+                },
             },
         })
     }
@@ -412,34 +584,47 @@ impl crate::Adapter<super::Api> for super::Adapter {
         &self,
         _features: wgt::Features,
         limits: &wgt::Limits,
+        _compute_only: bool,
+        queue_priority: wgt::QueuePriority,
+        additional_queues: u32,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
-        let queue = {
+        let create_queue = |priority| {
             profiling::scope!("ID3D12Device::CreateCommandQueue");
-            self.device
-                .create_command_queue(
-                    d3d12::CmdListType::Direct,
-                    d3d12::Priority::Normal,
-                    d3d12::CommandQueueFlags::empty(),
-                    0,
-                )
-                .into_device_result("Queue creation")?
+            self.device.create_command_queue(
+                d3d12::CmdListType::Direct,
+                priority,
+                d3d12::CommandQueueFlags::empty(),
+                0,
+            )
         };
-
-        let device = super::Device::new(
-            self.device.clone(),
-            queue.clone(),
-            limits,
-            self.private_caps,
-            &self.library,
-            self.dxc_container.clone(),
-        )?;
-        Ok(crate::OpenDevice {
-            device,
-            queue: super::Queue {
-                raw: queue,
-                temp_lists: Mutex::new(Vec::new()),
-            },
-        })
+        let mut priority = match queue_priority {
+            wgt::QueuePriority::Low | wgt::QueuePriority::Normal => None,
+            wgt::QueuePriority::High => Some(d3d12::Priority::High),
+            wgt::QueuePriority::Realtime => Some(d3d12::Priority::GlobalRealtime),
+        };
+        let queue = match priority.map(create_queue) {
+            Some(result) if result.1 >= 0 => result,
+            Some(_) => {
+                // Global realtime priority requires the process to have the
+                // `SeIncreaseBasePriorityPrivilege` privilege.
+                log::warn!(
+                    "Unable to create a queue with priority {queue_priority:?}, using the default priority"
+                );
+                priority = None;
+                create_queue(d3d12::Priority::Normal)
+            }
+            None => create_queue(d3d12::Priority::Normal),
+        }
+        .into_device_result("Queue creation")?;
+        // The additional queues have the same priority as the main one.
+        let additional_queues = (0..additional_queues)
+            .map(|_| {
+                create_queue(priority.unwrap_or(d3d12::Priority::Normal))
+                    .into_device_result("Queue creation")
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        unsafe { self.open_with_queues(queue, additional_queues, limits) }
     }
 
     #[allow(trivial_casts)]
@@ -555,6 +740,12 @@ impl crate::Adapter<super::Api> for super::Adapter {
             Tfc::MULTISAMPLE_RESOLVE,
             data.Support1 & d3d12_ty::D3D12_FORMAT_SUPPORT1_MULTISAMPLE_RESOLVE != 0,
         );
+        caps.set(
+            Tfc::MULTISAMPLE_RESOLVE_REGION | Tfc::MULTISAMPLE_RESOLVE_MIN_MAX,
+            self.private_caps.resolve_subresource_region
+                && format.has_color_aspect()
+                && data.Support1 & d3d12_ty::D3D12_FORMAT_SUPPORT1_MULTISAMPLE_RESOLVE != 0,
+        );
 
         let mut ms_levels = d3d12_ty::D3D12_FEATURE_DATA_MULTISAMPLE_QUALITY_LEVELS {
             Format: raw_format,
@@ -640,4 +831,21 @@ impl crate::Adapter<super::Api> for super::Adapter {
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
         wgt::PresentationTimestamp(self.presentation_timer.get_timestamp_ns())
     }
+
+    fn feature_requirement(&self, feature: wgt::Features) -> Option<&'static str> {
+        use wgt::Features as F;
+        Some(match feature {
+            F::VERTEX_WRITABLE_STORAGE => "feature level 11.1",
+            F::CONSERVATIVE_RASTERIZATION => "conservative rasterization tier 1",
+            F::TEXTURE_BINDING_ARRAY
+            | F::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING
+            | F::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING => {
+                "shader model 5.1"
+            }
+            F::BGRA8UNORM_STORAGE => "typed UAV store support of `DXGI_FORMAT_B8G8R8A8_UNORM`",
+            F::TEXTURE_FORMAT_P010 => "2D texture and sampling support of `DXGI_FORMAT_P010`",
+            F::TILED_TEXTURE => "tiled resources tier 2",
+            _ => return None,
+        })
+    }
 }
@@ -82,6 +82,82 @@ impl super::CommandEncoder {
         self.pass.clear();
     }
 
+    /// Returns the interface of the list used for variable rate shading.
+    fn list5(&self) -> d3d12::ComPtr<super::types::ID3D12GraphicsCommandList5> {
+        unsafe {
+            self.list
+                .as_ref()
+                .unwrap()
+                .cast::<super::types::ID3D12GraphicsCommandList5>()
+        }
+        .into_result()
+        .expect("Variable rate shading requires ID3D12GraphicsCommandList5")
+    }
+
+    /// Transitions the filled size counters of stream output.
+    fn transition_vertex_capture_counters(
+        &mut self,
+        before: d3d12_ty::D3D12_RESOURCE_STATES,
+        after: d3d12_ty::D3D12_RESOURCE_STATES,
+    ) {
+        let mut raw = d3d12_ty::D3D12_RESOURCE_BARRIER {
+            Type: d3d12_ty::D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+            Flags: d3d12_ty::D3D12_RESOURCE_BARRIER_FLAG_NONE,
+            u: unsafe { mem::zeroed() },
+        };
+        unsafe {
+            *raw.u.Transition_mut() = d3d12_ty::D3D12_RESOURCE_TRANSITION_BARRIER {
+                pResource: self.shared.vertex_capture_counters.as_mut_ptr(),
+                Subresource: d3d12_ty::D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES,
+                StateBefore: before,
+                StateAfter: after,
+            };
+            self.list.as_ref().unwrap().ResourceBarrier(1, &raw);
+        }
+    }
+
+    /// Creates the command signature of `execute_indirect`, or a null one on failure.
+    fn create_indirect_signature(
+        &self,
+        layout: &super::PipelineLayout,
+        arguments: &[wgt::IndirectArgument],
+    ) -> d3d12::CommandSignature {
+        let mut uses_root_signature = false;
+        let raw_arguments = arguments
+            .iter()
+            .map(|argument| match *argument {
+                wgt::IndirectArgument::VertexBuffer { slot } => {
+                    d3d12::IndirectArgument::vertex_buffer(slot)
+                }
+                wgt::IndirectArgument::PushConstants { offset, size, .. } => {
+                    let info = layout.shared.root_constant_info.as_ref().unwrap();
+                    uses_root_signature = true;
+                    d3d12::IndirectArgument::constant(
+                        info.root_index,
+                        offset / 4 - info.range.start,
+                        size / 4,
+                    )
+                }
+                wgt::IndirectArgument::Draw => d3d12::IndirectArgument::draw(),
+                wgt::IndirectArgument::DrawIndexed => d3d12::IndirectArgument::draw_indexed(),
+            })
+            .collect::<Vec<_>>();
+        let root_signature = if uses_root_signature {
+            layout.shared.signature.clone()
+        } else {
+            d3d12::RootSignature::null()
+        };
+        self.device
+            .create_command_signature(
+                root_signature,
+                &raw_arguments,
+                wgt::IndirectArgument::stride(arguments),
+                0,
+            )
+            .into_device_result("Command (execute_indirect) signature creation")
+            .unwrap_or_else(|_| d3d12::CommandSignature::null())
+    }
+
     unsafe fn prepare_draw(&mut self, first_vertex: i32, first_instance: u32) {
         while self.pass.dirty_vertex_buffers != 0 {
             let list = self.list.as_ref().unwrap();
@@ -278,6 +354,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         self.list = Some(list);
         self.temp.clear();
         self.pass.clear();
+        self.residency.clear();
         Ok(())
     }
     unsafe fn discard_encoding(&mut self) {
@@ -286,12 +363,16 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                 self.free_lists.push(list);
             }
         }
+        self.residency.clear();
     }
     unsafe fn end_encoding(&mut self) -> Result<super::CommandBuffer, crate::DeviceError> {
         let raw = self.list.take().unwrap();
         raw.close()
             .into_device_result("GraphicsCommandList::close")?;
-        Ok(super::CommandBuffer { raw })
+        Ok(super::CommandBuffer {
+            raw,
+            residency: mem::take(&mut self.residency),
+        })
     }
     unsafe fn reset_all<I: Iterator<Item = super::CommandBuffer>>(&mut self, command_buffers: I) {
         for cmd_buf in command_buffers {
@@ -311,6 +392,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             self.list.as_ref().unwrap().as_ptr()
         );
         for barrier in barriers {
+            self.residency.insert(&barrier.buffer.residency);
             log::trace!(
                 "\t{:p}: usage {:?}..{:?}",
                 barrier.buffer.resource.as_ptr(),
@@ -370,6 +452,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             self.list.as_ref().unwrap().as_ptr()
         );
         for barrier in barriers {
+            self.residency.insert(&barrier.texture.residency);
             log::trace!(
                 "\t{:p}: usage {:?}..{:?}, range {:?}",
                 barrier.texture.resource.as_ptr(),
@@ -467,7 +550,24 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         }
     }
 
+    unsafe fn aliasing_barrier(&mut self) {
+        let mut raw = d3d12_ty::D3D12_RESOURCE_BARRIER {
+            Type: d3d12_ty::D3D12_RESOURCE_BARRIER_TYPE_ALIASING,
+            Flags: d3d12_ty::D3D12_RESOURCE_BARRIER_FLAG_NONE,
+            u: unsafe { mem::zeroed() },
+        };
+        // Null resources stand for any placed resource.
+        unsafe {
+            *raw.u.Aliasing_mut() = d3d12_ty::D3D12_RESOURCE_ALIASING_BARRIER {
+                pResourceBefore: ptr::null_mut(),
+                pResourceAfter: ptr::null_mut(),
+            }
+        };
+        unsafe { self.list.as_ref().unwrap().ResourceBarrier(1, &raw) };
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
+        self.residency.insert(&buffer.residency);
         let list = self.list.as_ref().unwrap();
         let mut offset = range.start;
         while offset < range.end {
@@ -493,6 +593,8 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     ) where
         T: Iterator<Item = crate::BufferCopy>,
     {
+        self.residency.insert(&src.residency);
+        self.residency.insert(&dst.residency);
         let list = self.list.as_ref().unwrap();
         for r in regions {
             unsafe {
@@ -516,6 +618,8 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     ) where
         T: Iterator<Item = crate::TextureCopy>,
     {
+        self.residency.insert(&src.residency);
+        self.residency.insert(&dst.residency);
         let list = self.list.as_ref().unwrap();
         let mut src_location = d3d12_ty::D3D12_TEXTURE_COPY_LOCATION {
             pResource: src.resource.as_mut_ptr(),
@@ -550,6 +654,97 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         }
     }
 
+    unsafe fn resolve_texture<T>(
+        &mut self,
+        src: &super::Texture,
+        _src_usage: crate::TextureUses,
+        dst: &super::Texture,
+        mode: wgt::ResolveMode,
+        regions: T,
+    ) where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        self.residency.insert(&src.residency);
+        self.residency.insert(&dst.residency);
+        let list = self.list.as_ref().unwrap();
+        let list1 = match unsafe { list.cast::<super::types::ID3D12GraphicsCommandList1>() }
+            .into_result()
+        {
+            Ok(list1) => list1,
+            Err(err) => {
+                log::error!("Unable to get ID3D12GraphicsCommandList1 to resolve a texture: {err}");
+                return;
+            }
+        };
+        let raw_format = auxil::dxgi::conv::map_texture_format(src.format);
+        let raw_mode = conv::map_resolve_mode(mode);
+
+        for r in regions {
+            let src_subresource = src.calc_subresource_for_copy(&r.src_base);
+            let dst_subresource = dst.calc_subresource_for_copy(&r.dst_base);
+
+            // Textures are in the copy states here,
+            // but D3D12 has special source/destination states for the resolves.
+            self.temp.barriers.clear();
+            let mut barrier = d3d12_ty::D3D12_RESOURCE_BARRIER {
+                Type: d3d12_ty::D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+                Flags: d3d12_ty::D3D12_RESOURCE_BARRIER_FLAG_NONE,
+                u: unsafe { mem::zeroed() },
+            };
+            unsafe {
+                *barrier.u.Transition_mut() = d3d12_ty::D3D12_RESOURCE_TRANSITION_BARRIER {
+                    pResource: src.resource.as_mut_ptr(),
+                    Subresource: src_subresource,
+                    StateBefore: d3d12_ty::D3D12_RESOURCE_STATE_COPY_SOURCE,
+                    StateAfter: d3d12_ty::D3D12_RESOURCE_STATE_RESOLVE_SOURCE,
+                }
+            };
+            self.temp.barriers.push(barrier);
+            unsafe {
+                *barrier.u.Transition_mut() = d3d12_ty::D3D12_RESOURCE_TRANSITION_BARRIER {
+                    pResource: dst.resource.as_mut_ptr(),
+                    Subresource: dst_subresource,
+                    StateBefore: d3d12_ty::D3D12_RESOURCE_STATE_COPY_DEST,
+                    StateAfter: d3d12_ty::D3D12_RESOURCE_STATE_RESOLVE_DEST,
+                }
+            };
+            self.temp.barriers.push(barrier);
+            unsafe {
+                list.ResourceBarrier(self.temp.barriers.len() as u32, self.temp.barriers.as_ptr())
+            };
+
+            let mut src_rect = d3d12_ty::D3D12_RECT {
+                left: r.src_base.origin.x as i32,
+                top: r.src_base.origin.y as i32,
+                right: (r.src_base.origin.x + r.size.width) as i32,
+                bottom: (r.src_base.origin.y + r.size.height) as i32,
+            };
+            profiling::scope!("ID3D12GraphicsCommandList1::ResolveSubresourceRegion");
+            unsafe {
+                list1.ResolveSubresourceRegion(
+                    dst.resource.as_mut_ptr(),
+                    dst_subresource,
+                    r.dst_base.origin.x,
+                    r.dst_base.origin.y,
+                    src.resource.as_mut_ptr(),
+                    src_subresource,
+                    &mut src_rect,
+                    raw_format,
+                    raw_mode,
+                )
+            };
+
+            // Flip the barriers to reverse, back into the copy states.
+            for barrier in self.temp.barriers.iter_mut() {
+                let transition = unsafe { barrier.u.Transition_mut() };
+                mem::swap(&mut transition.StateBefore, &mut transition.StateAfter);
+            }
+            unsafe {
+                list.ResourceBarrier(self.temp.barriers.len() as u32, self.temp.barriers.as_ptr())
+            };
+        }
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -558,6 +753,8 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     ) where
         T: Iterator<Item = crate::BufferTextureCopy>,
     {
+        self.residency.insert(&src.residency);
+        self.residency.insert(&dst.residency);
         let list = self.list.as_ref().unwrap();
         let mut src_location = d3d12_ty::D3D12_TEXTURE_COPY_LOCATION {
             pResource: src.resource.as_mut_ptr(),
@@ -600,6 +797,8 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     ) where
         T: Iterator<Item = crate::BufferTextureCopy>,
     {
+        self.residency.insert(&src.residency);
+        self.residency.insert(&dst.residency);
         let list = self.list.as_ref().unwrap();
         let mut src_location = d3d12_ty::D3D12_TEXTURE_COPY_LOCATION {
             pResource: src.resource.as_mut_ptr(),
@@ -660,6 +859,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         offset: wgt::BufferAddress,
         _stride: wgt::BufferSize,
     ) {
+        self.residency.insert(&buffer.residency);
         unsafe {
             self.list.as_ref().unwrap().ResolveQueryData(
                 set.raw.as_mut_ptr(),
@@ -692,6 +892,10 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         let mut color_views = [d3d12::CpuDescriptor { ptr: 0 }; crate::MAX_COLOR_ATTACHMENTS];
         for (rtv, cat) in color_views.iter_mut().zip(desc.color_attachments.iter()) {
             if let Some(cat) = cat.as_ref() {
+                self.residency.insert(&cat.target.view.residency);
+                if let Some(ref resolve) = cat.resolve_target {
+                    self.residency.insert(&resolve.view.residency);
+                }
                 *rtv = cat.target.view.handle_rtv.unwrap().raw;
             } else {
                 *rtv = self.null_rtv_handle.raw;
@@ -701,6 +905,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         let ds_view = match desc.depth_stencil_attachment {
             None => ptr::null(),
             Some(ref ds) => {
+                self.residency.insert(&ds.target.view.residency);
                 if ds.target.usage == crate::TextureUses::DEPTH_STENCIL_WRITE {
                     &ds.target.view.handle_dsv_rw.as_ref().unwrap().raw
                 } else {
@@ -782,6 +987,15 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         };
         unsafe { list.RSSetViewports(1, &raw_vp) };
         unsafe { list.RSSetScissorRects(1, &raw_rect) };
+
+        if let Some(ref at) = desc.shading_rate_attachment {
+            self.residency.insert(&at.view.residency);
+            let resource = at.view.target_base.0.as_mut_ptr();
+            unsafe { self.list5().RSSetShadingRateImage(resource) };
+            self.pass.shading_rate_image = true;
+            // Without combiners, the shading rate image would be ignored.
+            unsafe { self.set_shading_rate(wgt::ShadingRate::Rate1x1) };
+        }
     }
 
     unsafe fn end_render_pass(&mut self) {
@@ -858,6 +1072,14 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             }
         }
 
+        if self.pass.shading_rate_dirty {
+            let list5 = self.list5();
+            unsafe {
+                list5.RSSetShadingRate(wgt::ShadingRate::Rate1x1 as u32, ptr::null());
+                list5.RSSetShadingRateImage(ptr::null_mut());
+            }
+        }
+
         self.write_pass_end_timestamp_if_requested();
 
         unsafe { self.end_pass() };
@@ -870,6 +1092,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         group: &super::BindGroup,
         dynamic_offsets: &[wgt::DynamicOffset],
     ) {
+        self.residency.extend(&group.residency);
         log::trace!("Set group[{}]", index);
         let info = &layout.bind_group_infos[index as usize];
         let mut root_index = info.base_root_index as usize;
@@ -990,6 +1213,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         binding: crate::BufferBinding<'a, super::Api>,
         format: wgt::IndexFormat,
     ) {
+        self.residency.insert(&binding.buffer.residency);
         self.list.as_ref().unwrap().set_index_buffer(
             binding.resolve_address(),
             binding.resolve_size() as u32,
@@ -1001,6 +1225,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         index: u32,
         binding: crate::BufferBinding<'a, super::Api>,
     ) {
+        self.residency.insert(&binding.buffer.residency);
         let vb = &mut self.pass.vertex_buffers[index as usize];
         vb.BufferLocation = binding.resolve_address();
         vb.SizeInBytes = binding.resolve_size() as u32;
@@ -1072,6 +1297,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
+        self.residency.insert(&buffer.residency);
         unsafe { self.prepare_draw(0, 0) };
         unsafe {
             self.list.as_ref().unwrap().ExecuteIndirect(
@@ -1090,6 +1316,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
+        self.residency.insert(&buffer.residency);
         unsafe { self.prepare_draw(0, 0) };
         unsafe {
             self.list.as_ref().unwrap().ExecuteIndirect(
@@ -1110,6 +1337,8 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
+        self.residency.insert(&buffer.residency);
+        self.residency.insert(&count_buffer.residency);
         unsafe { self.prepare_draw(0, 0) };
         unsafe {
             self.list.as_ref().unwrap().ExecuteIndirect(
@@ -1130,6 +1359,8 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
+        self.residency.insert(&buffer.residency);
+        self.residency.insert(&count_buffer.residency);
         unsafe { self.prepare_draw(0, 0) };
         unsafe {
             self.list.as_ref().unwrap().ExecuteIndirect(
@@ -1142,6 +1373,167 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             )
         };
     }
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        _group_count_x: u32,
+        _group_count_y: u32,
+        _group_count_z: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _draw_count: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn execute_indirect(
+        &mut self,
+        _pipeline: &super::RenderPipeline,
+        layout: &super::PipelineLayout,
+        arguments: &[wgt::IndirectArgument],
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+        count: Option<(&super::Buffer, wgt::BufferAddress)>,
+        max_count: u32,
+    ) {
+        self.residency.insert(&buffer.residency);
+        if let Some((count_buffer, _)) = count {
+            self.residency.insert(&count_buffer.residency);
+        }
+        let signature = {
+            let mut signatures = layout.indirect_signatures.lock();
+            match signatures.get(arguments) {
+                Some(signature) => signature.clone(),
+                None => {
+                    let signature = self.create_indirect_signature(layout, arguments);
+                    signatures.insert(arguments.to_vec(), signature.clone());
+                    signature
+                }
+            }
+        };
+        if signature.is_null() {
+            return;
+        }
+
+        unsafe { self.prepare_draw(0, 0) };
+        let (count_buffer, count_offset) = match count {
+            Some((buffer, offset)) => (buffer.resource.as_mut_ptr(), offset),
+            None => (ptr::null_mut(), 0),
+        };
+        unsafe {
+            self.list.as_ref().unwrap().ExecuteIndirect(
+                signature.as_mut_ptr(),
+                max_count,
+                buffer.resource.as_mut_ptr(),
+                offset,
+                count_buffer,
+                count_offset,
+            )
+        };
+
+        // The root constants set by the commands are lost, restore ours before the next draw.
+        if let Some(ref info) = layout.shared.root_constant_info {
+            if arguments
+                .iter()
+                .any(|argument| matches!(*argument, wgt::IndirectArgument::PushConstants { .. }))
+            {
+                self.pass.dirty_root_elements |= 1 << info.root_index;
+            }
+        }
+    }
+
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+    ) {
+        self.residency.insert(&buffer.residency);
+        // The `PREDICATION` state is the same as `INDIRECT_ARGUMENT`.
+        unsafe {
+            self.list.as_ref().unwrap().SetPredication(
+                buffer.resource.as_mut_ptr(),
+                offset,
+                d3d12_ty::D3D12_PREDICATION_OP_EQUAL_ZERO,
+            )
+        };
+    }
+    unsafe fn end_conditional_rendering(&mut self) {
+        unsafe {
+            self.list.as_ref().unwrap().SetPredication(
+                ptr::null_mut(),
+                0,
+                d3d12_ty::D3D12_PREDICATION_OP_EQUAL_ZERO,
+            )
+        };
+    }
+
+    unsafe fn begin_vertex_capture(&mut self, buffers: &[crate::BufferBinding<super::Api>]) {
+        // Reset the filled size counters, so vertices are written from the
+        // start of the bound ranges. The counters are implicitly promoted to
+        // `COPY_DEST` from `COMMON`.
+        unsafe {
+            self.list.as_ref().unwrap().CopyBufferRegion(
+                self.shared.vertex_capture_counters.as_mut_ptr(),
+                0,
+                self.shared.zero_buffer.as_mut_ptr(),
+                0,
+                super::VERTEX_CAPTURE_COUNTERS_SIZE,
+            )
+        };
+        self.transition_vertex_capture_counters(
+            d3d12_ty::D3D12_RESOURCE_STATE_COPY_DEST,
+            d3d12_ty::D3D12_RESOURCE_STATE_STREAM_OUT,
+        );
+
+        let counters_address = self.shared.vertex_capture_counters.gpu_virtual_address();
+        let mut views = arrayvec::ArrayVec::<_, { crate::MAX_VERTEX_CAPTURE_BUFFERS }>::new();
+        for (i, binding) in buffers.iter().enumerate() {
+            self.residency.insert(&binding.buffer.residency);
+            views.push(d3d12_ty::D3D12_STREAM_OUTPUT_BUFFER_VIEW {
+                BufferLocation: binding.resolve_address(),
+                SizeInBytes: binding.resolve_size(),
+                BufferFilledSizeLocation: counters_address + 4 * i as u64,
+            });
+        }
+        unsafe {
+            self.list
+                .as_ref()
+                .unwrap()
+                .SOSetTargets(0, views.len() as u32, views.as_ptr())
+        };
+    }
+    unsafe fn end_vertex_capture(&mut self) {
+        unsafe {
+            self.list.as_ref().unwrap().SOSetTargets(
+                0,
+                crate::MAX_VERTEX_CAPTURE_BUFFERS as u32,
+                ptr::null(),
+            )
+        };
+        self.transition_vertex_capture_counters(
+            d3d12_ty::D3D12_RESOURCE_STATE_STREAM_OUT,
+            d3d12_ty::D3D12_RESOURCE_STATE_COMMON,
+        );
+    }
+
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate) {
+        // The shading rate enum matches `D3D12_SHADING_RATE`.
+        let combiners = [
+            super::types::D3D12_SHADING_RATE_COMBINER_PASSTHROUGH,
+            super::types::D3D12_SHADING_RATE_COMBINER_OVERRIDE,
+        ];
+        // Combiners aren't supported with variable rate shading tier 1.
+        let raw_combiners = if self.pass.shading_rate_image {
+            combiners.as_ptr()
+        } else {
+            ptr::null()
+        };
+        unsafe { self.list5().RSSetShadingRate(rate as u32, raw_combiners) };
+        self.pass.shading_rate_dirty = true;
+    }
 
     // compute
 
@@ -1184,6 +1576,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         self.list.as_ref().unwrap().dispatch(count);
     }
     unsafe fn dispatch_indirect(&mut self, buffer: &super::Buffer, offset: wgt::BufferAddress) {
+        self.residency.insert(&buffer.residency);
         self.prepare_dispatch([0; 3]);
         //TODO: update special constants indirectly
         unsafe {
@@ -63,6 +63,44 @@ pub fn map_filter_mode(mode: wgt::FilterMode) -> d3d12_ty::D3D12_FILTER_TYPE {
     }
 }
 
+/// Encodes a mapping like `D3D12_ENCODE_SHADER_4_COMPONENT_MAPPING`.
+pub fn map_component_mapping(mapping: wgt::ComponentMapping) -> u32 {
+    fn map_swizzle(swizzle: wgt::ComponentSwizzle) -> u32 {
+        match swizzle {
+            wgt::ComponentSwizzle::R => {
+                d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FROM_MEMORY_COMPONENT_0
+            }
+            wgt::ComponentSwizzle::G => {
+                d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FROM_MEMORY_COMPONENT_1
+            }
+            wgt::ComponentSwizzle::B => {
+                d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FROM_MEMORY_COMPONENT_2
+            }
+            wgt::ComponentSwizzle::A => {
+                d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FROM_MEMORY_COMPONENT_3
+            }
+            wgt::ComponentSwizzle::Zero => d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FORCE_VALUE_0,
+            wgt::ComponentSwizzle::One => d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FORCE_VALUE_1,
+        }
+    }
+    const ALWAYS_SET_BIT: u32 = 1 << 12;
+    map_swizzle(mapping.r)
+        | map_swizzle(mapping.g) << 3
+        | map_swizzle(mapping.b) << 6
+        | map_swizzle(mapping.a) << 9
+        | ALWAYS_SET_BIT
+}
+
+pub fn map_resolve_mode(mode: wgt::ResolveMode) -> super::types::D3D12_RESOLVE_MODE {
+    use super::types;
+    match mode {
+        wgt::ResolveMode::Average => types::D3D12_RESOLVE_MODE_AVERAGE,
+        wgt::ResolveMode::Min => types::D3D12_RESOLVE_MODE_MIN,
+        wgt::ResolveMode::Max => types::D3D12_RESOLVE_MODE_MAX,
+        wgt::ResolveMode::SampleZero => unreachable!(),
+    }
+}
+
 pub fn map_comparison(func: wgt::CompareFunction) -> d3d12_ty::D3D12_COMPARISON_FUNC {
     use wgt::CompareFunction as Cf;
     match func {
@@ -145,6 +183,9 @@ pub fn map_buffer_usage_to_state(usage: crate::BufferUses) -> d3d12_ty::D3D12_RE
     if usage.intersects(Bu::INDIRECT) {
         state |= d3d12_ty::D3D12_RESOURCE_STATE_INDIRECT_ARGUMENT;
     }
+    if usage.intersects(Bu::VERTEX_CAPTURE) {
+        state |= d3d12_ty::D3D12_RESOURCE_STATE_STREAM_OUT;
+    }
     state
 }
 
@@ -179,6 +220,9 @@ pub fn map_texture_usage_to_state(usage: crate::TextureUses) -> d3d12_ty::D3D12_
     if usage.intersects(Tu::STORAGE_READ | Tu::STORAGE_READ_WRITE) {
         state |= d3d12_ty::D3D12_RESOURCE_STATE_UNORDERED_ACCESS;
     }
+    if usage.intersects(Tu::SHADING_RATE_ATTACHMENT) {
+        state |= super::types::D3D12_RESOURCE_STATE_SHADING_RATE_SOURCE;
+    }
     state
 }
 
@@ -236,6 +236,33 @@ impl CpuPool {
 pub(super) struct CpuHeapInner {
     pub _raw: d3d12::DescriptorHeap,
     pub stage: Vec<d3d12::CpuDescriptor>,
+    /// Offsets and lengths of the ranges of the descriptor table the staged
+    /// descriptors are copied to. Partially bound binding arrays leave the
+    /// slots between them uninitialized.
+    ranges: Vec<(u32, u32)>,
+    /// Number of staged descriptors already covered by `ranges`.
+    ranged: u32,
+    table_len: u32,
+}
+
+impl CpuHeapInner {
+    pub(super) fn clear(&mut self) {
+        self.stage.clear();
+        self.ranges.clear();
+        self.ranged = 0;
+        self.table_len = 0;
+    }
+
+    /// Ends a binding taking `count` slots of the descriptor table, made of
+    /// the descriptors staged since the end of the previous one.
+    pub(super) fn finish_binding(&mut self, count: u32) {
+        let staged = self.stage.len() as u32 - self.ranged;
+        if staged != 0 {
+            self.ranges.push((self.table_len, staged));
+            self.ranged += staged;
+        }
+        self.table_len += count;
+    }
 }
 
 pub(super) struct CpuHeap {
@@ -265,6 +292,9 @@ impl CpuHeap {
             inner: Mutex::new(CpuHeapInner {
                 _raw: raw.clone(),
                 stage: Vec::new(),
+                ranges: Vec::new(),
+                ranged: 0,
+                table_len: 0,
             }),
             start: raw.start_cpu_descriptor(),
             handle_size,
@@ -295,18 +325,23 @@ pub(super) unsafe fn upload(
     dst: &GeneralHeap,
     dummy_copy_counts: &[u32],
 ) -> Result<DualHandle, crate::DeviceError> {
-    let count = src.stage.len() as u32;
-    let index = dst.allocate_slice(count as u64)?;
+    let count = src.table_len as u64;
+    let index = dst.allocate_slice(count)?;
+    let (dst_starts, dst_sizes): (Vec<_>, Vec<_>) = src
+        .ranges
+        .iter()
+        .map(|&(offset, len)| (dst.cpu_descriptor_at(index + offset as u64), len))
+        .unzip();
     unsafe {
         device.CopyDescriptors(
-            1,
-            &dst.cpu_descriptor_at(index),
-            &count,
-            count,
+            dst_starts.len() as u32,
+            dst_starts.as_ptr(),
+            dst_sizes.as_ptr(),
+            src.stage.len() as u32,
             src.stage.as_ptr(),
             dummy_copy_counts.as_ptr(),
             dst.ty as u32,
         )
     };
-    Ok(dst.at(index, count as u64))
+    Ok(dst.at(index, count))
 }
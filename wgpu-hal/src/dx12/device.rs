@@ -30,6 +30,7 @@ impl super::Device {
         limits: &wgt::Limits,
         private_caps: super::PrivateCapabilities,
         library: &Arc<d3d12::D3D12Lib>,
+        residency: Option<Arc<super::residency::Residency>>,
         dxc_container: Option<Arc<shader_compilation::DxcContainer>>,
     ) -> Result<Self, DeviceError> {
         let mem_allocator = if private_caps.suballocation_supported {
@@ -53,6 +54,7 @@ impl super::Device {
         null_comptr_check(&idle_fence)?;
 
         let mut zero_buffer = d3d12::Resource::null();
+        let mut vertex_capture_counters = d3d12::Resource::null();
         unsafe {
             let raw_desc = d3d12_ty::D3D12_RESOURCE_DESC {
                 Dimension: d3d12_ty::D3D12_RESOURCE_DIMENSION_BUFFER,
@@ -97,6 +99,22 @@ impl super::Device {
 
             // Note: without `D3D12_HEAP_FLAG_CREATE_NOT_ZEROED`
             // this resource is zeroed by default.
+
+            raw.CreateCommittedResource(
+                &heap_properties,
+                d3d12_ty::D3D12_HEAP_FLAG_NONE,
+                &d3d12_ty::D3D12_RESOURCE_DESC {
+                    Width: super::VERTEX_CAPTURE_COUNTERS_SIZE,
+                    ..raw_desc
+                },
+                d3d12_ty::D3D12_RESOURCE_STATE_COMMON,
+                ptr::null(),
+                &d3d12_ty::ID3D12Resource::uuidof(),
+                vertex_capture_counters.mut_void(),
+            )
+            .into_device_result("Vertex capture counters creation")?;
+
+            null_comptr_check(&vertex_capture_counters)?;
         };
 
         // maximum number of CBV/SRV/UAV descriptors in heap for Tier 1
@@ -105,6 +123,7 @@ impl super::Device {
 
         let shared = super::DeviceShared {
             zero_buffer,
+            vertex_capture_counters,
             cmd_signatures: super::CommandSignatures {
                 draw: raw
                     .create_command_signature(
@@ -184,6 +203,7 @@ impl super::Device {
             render_doc: Default::default(),
             null_rtv_handle,
             mem_allocator,
+            residency,
             dxc_container,
         })
     }
@@ -308,6 +328,35 @@ impl super::Device {
             mip_level_count,
             sample_count,
             allocation: None,
+            residency: None,
+        }
+    }
+
+    /// Like [`Self::texture_from_raw`], but takes a reference of its own to a
+    /// resource created outside of wgpu.
+    ///
+    /// # Safety
+    ///
+    /// - `resource` must be a valid `ID3D12Resource`, created respecting the
+    ///   other arguments.
+    pub unsafe fn texture_from_raw_resource(
+        resource: *mut d3d12_ty::ID3D12Resource,
+        format: wgt::TextureFormat,
+        dimension: wgt::TextureDimension,
+        size: wgt::Extent3d,
+        mip_level_count: u32,
+        sample_count: u32,
+    ) -> super::Texture {
+        let resource = unsafe { d3d12::ComPtr::from_raw(resource) };
+        unsafe {
+            Self::texture_from_raw(
+                resource,
+                format,
+                dimension,
+                size,
+                mip_level_count,
+                sample_count,
+            )
         }
     }
 
@@ -319,7 +368,200 @@ impl super::Device {
             resource,
             size,
             allocation: None,
+            residency: None,
+            external_size: None,
+        }
+    }
+
+    /// Runs `create`, and if it runs out of memory, evicts idle resources to
+    /// make room for `size` bytes and tries again.
+    fn create_with_eviction<T>(
+        &self,
+        size: u64,
+        mut create: impl FnMut() -> Result<T, DeviceError>,
+    ) -> Result<T, DeviceError> {
+        match create() {
+            Err(DeviceError::OutOfMemory | DeviceError::ResourceCreationFailed)
+                if self
+                    .residency
+                    .as_ref()
+                    .map_or(false, |residency| residency.evict_idle(size)) =>
+            {
+                create()
+            }
+            result => result,
+        }
+    }
+
+    /// Tracks the memory of a new resource of `size` bytes, either its heap
+    /// if it is placed, or the resource itself.
+    fn texture_resource_desc(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> d3d12_ty::D3D12_RESOURCE_DESC {
+        d3d12_ty::D3D12_RESOURCE_DESC {
+            Dimension: conv::map_texture_dimension(desc.dimension),
+            Alignment: 0,
+            Width: desc.size.width as u64,
+            Height: desc.size.height,
+            DepthOrArraySize: desc.size.depth_or_array_layers as u16,
+            MipLevels: desc.mip_level_count as u16,
+            Format: auxil::dxgi::conv::map_texture_format_for_resource(
+                desc.format,
+                desc.usage,
+                !desc.view_formats.is_empty(),
+                self.private_caps.casting_fully_typed_format_supported,
+            ),
+            SampleDesc: dxgitype::DXGI_SAMPLE_DESC {
+                Count: desc.sample_count,
+                Quality: 0,
+            },
+            // Reserved resources need the layout of the standard tile shapes.
+            Layout: if desc.memory_flags.contains(crate::MemoryFlags::TILED) {
+                d3d12_ty::D3D12_TEXTURE_LAYOUT_64KB_UNDEFINED_SWIZZLE
+            } else {
+                d3d12_ty::D3D12_TEXTURE_LAYOUT_UNKNOWN
+            },
+            Flags: conv::map_texture_usage_to_resource_flags(desc.usage),
+        }
+    }
+
+    fn memory_requirements(
+        &self,
+        raw_desc: &d3d12_ty::D3D12_RESOURCE_DESC,
+    ) -> Result<wgt::MemoryRequirements, DeviceError> {
+        let info = unsafe { self.raw.GetResourceAllocationInfo(0, 1, raw_desc) };
+        // An invalid description is reported with the maximum size.
+        if info.SizeInBytes == u64::MAX {
+            return Err(DeviceError::ResourceCreationFailed);
+        }
+        Ok(wgt::MemoryRequirements {
+            size: info.SizeInBytes,
+            alignment: info.Alignment,
+        })
+    }
+
+    unsafe fn create_placed_resource(
+        &self,
+        raw_desc: &d3d12_ty::D3D12_RESOURCE_DESC,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+        label: crate::Label,
+    ) -> Result<d3d12::Resource, DeviceError> {
+        let mut resource = d3d12::Resource::null();
+        let hr = unsafe {
+            self.raw.CreatePlacedResource(
+                heap.heap.as_mut_ptr(),
+                offset,
+                raw_desc,
+                d3d12_ty::D3D12_RESOURCE_STATE_COMMON,
+                ptr::null(),
+                &d3d12_ty::ID3D12Resource::uuidof(),
+                resource.mut_void(),
+            )
+        };
+        hr.into_device_result("Placed resource creation")?;
+        null_comptr_check(&resource)?;
+
+        if let Some(label) = label {
+            let cwstr = conv::map_label(label);
+            unsafe { resource.SetName(cwstr.as_ptr()) };
+        }
+        Ok(resource)
+    }
+
+    fn register_residency(
+        &self,
+        resource: &d3d12::Resource,
+        allocation: Option<&super::suballocation::AllocationWrapper>,
+        size: u64,
+    ) -> Option<Arc<super::residency::Pageable>> {
+        let residency = self.residency.as_ref()?;
+        match allocation.and_then(|allocation| allocation.heap_range()) {
+            Some((heap, end)) => unsafe { residency.register_heap(heap, end) },
+            None => residency.register_resource(resource, size),
+        }
+    }
+}
+
+fn buffer_resource_desc(
+    desc: &crate::BufferDescriptor,
+) -> (wgt::BufferAddress, d3d12_ty::D3D12_RESOURCE_DESC) {
+    let mut size = desc.size;
+    if desc.usage.contains(crate::BufferUses::UNIFORM) {
+        let align_mask = d3d12_ty::D3D12_CONSTANT_BUFFER_DATA_PLACEMENT_ALIGNMENT as u64 - 1;
+        size = ((size - 1) | align_mask) + 1;
+    }
+
+    let raw_desc = d3d12_ty::D3D12_RESOURCE_DESC {
+        Dimension: d3d12_ty::D3D12_RESOURCE_DIMENSION_BUFFER,
+        Alignment: 0,
+        Width: size,
+        Height: 1,
+        DepthOrArraySize: 1,
+        MipLevels: 1,
+        Format: dxgiformat::DXGI_FORMAT_UNKNOWN,
+        SampleDesc: dxgitype::DXGI_SAMPLE_DESC {
+            Count: 1,
+            Quality: 0,
+        },
+        Layout: d3d12_ty::D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+        Flags: conv::map_buffer_usage_to_resource_flags(desc.usage),
+    };
+    (size, raw_desc)
+}
+
+/// Hashes the shaders and state of a pipeline, to find its blob in a [`super::PipelineCache`].
+fn pipeline_cache_key(shaders: &[&super::CompiledShader], state: impl std::hash::Hash) -> u64 {
+    use std::hash::{Hash as _, Hasher as _};
+
+    // `DefaultHasher::new` always uses the same keys, so the hash is stable
+    // between runs of the same build.
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for shader in shaders {
+        shader.bytecode().hash(&mut hasher);
+    }
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl super::PipelineCache {
+    /// Creates a pipeline with `create`, first passing it the blob stored for `key`.
+    ///
+    /// If there is no blob or the driver rejects it, the pipeline is compiled and
+    /// its blob is stored for `key`.
+    fn create_pipeline(
+        &self,
+        key: u64,
+        create: impl Fn(d3d12::CachedPSO) -> d3d12::D3DResult<d3d12::PipelineState>,
+    ) -> d3d12::D3DResult<d3d12::PipelineState> {
+        let blob = self.blobs.lock().get(&key).cloned();
+        if let Some(blob) = blob {
+            let pair = create(d3d12::CachedPSO::from_bytes(&blob));
+            if pair.1.into_result().is_ok() {
+                return pair;
+            }
+            log::info!("Cached pipeline state was rejected by the driver, compiling the pipeline");
+        }
+
+        let pair = create(d3d12::CachedPSO::null());
+        if pair.1.into_result().is_ok() && !pair.0.is_null() {
+            let mut blob = d3d12::Blob::null();
+            let hr = unsafe { pair.0.GetCachedBlob(blob.mut_self()) };
+            match hr.into_result() {
+                Ok(()) => {
+                    let data = unsafe {
+                        std::slice::from_raw_parts(
+                            blob.GetBufferPointer() as *const u8,
+                            blob.GetBufferSize(),
+                        )
+                    };
+                    self.blobs.lock().insert(key, data.to_vec());
+                }
+                Err(err) => log::warn!("Unable to get the cached blob of a pipeline: {}", err),
+            }
         }
+        pair
     }
 }
 
@@ -329,46 +571,34 @@ impl crate::Device<super::Api> for super::Device {
         self.mem_allocator = None;
     }
 
+    unsafe fn destroy_queue(&self, _queue: super::Queue) {}
+
     unsafe fn create_buffer(
         &self,
         desc: &crate::BufferDescriptor,
     ) -> Result<super::Buffer, DeviceError> {
-        let mut resource = d3d12::Resource::null();
-        let mut size = desc.size;
-        if desc.usage.contains(crate::BufferUses::UNIFORM) {
-            let align_mask = d3d12_ty::D3D12_CONSTANT_BUFFER_DATA_PLACEMENT_ALIGNMENT as u64 - 1;
-            size = ((size - 1) | align_mask) + 1;
-        }
-
-        let raw_desc = d3d12_ty::D3D12_RESOURCE_DESC {
-            Dimension: d3d12_ty::D3D12_RESOURCE_DIMENSION_BUFFER,
-            Alignment: 0,
-            Width: size,
-            Height: 1,
-            DepthOrArraySize: 1,
-            MipLevels: 1,
-            Format: dxgiformat::DXGI_FORMAT_UNKNOWN,
-            SampleDesc: dxgitype::DXGI_SAMPLE_DESC {
-                Count: 1,
-                Quality: 0,
-            },
-            Layout: d3d12_ty::D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
-            Flags: conv::map_buffer_usage_to_resource_flags(desc.usage),
-        };
-
-        let (hr, allocation) =
-            super::suballocation::create_buffer_resource(self, desc, raw_desc, &mut resource)?;
+        let (size, raw_desc) = buffer_resource_desc(desc);
+
+        let (resource, allocation) = self.create_with_eviction(size, || {
+            let mut resource = d3d12::Resource::null();
+            let (hr, allocation) =
+                super::suballocation::create_buffer_resource(self, desc, raw_desc, &mut resource)?;
+            hr.into_device_result("Buffer creation")?;
+            Ok((resource, allocation))
+        })?;
 
-        hr.into_device_result("Buffer creation")?;
         if let Some(label) = desc.label {
             let cwstr = conv::map_label(label);
             unsafe { resource.SetName(cwstr.as_ptr()) };
         }
 
+        let residency = self.register_residency(&resource, allocation.as_ref(), size);
         Ok(super::Buffer {
             resource,
             size,
             allocation,
+            residency,
+            external_size: None,
         })
     }
 
@@ -409,6 +639,9 @@ impl crate::Device<super::Api> for super::Device {
 
     unsafe fn flush_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {}
     unsafe fn invalidate_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {}
+    unsafe fn get_buffer_device_address(&self, buffer: &super::Buffer) -> wgt::BufferAddress {
+        buffer.resource.gpu_virtual_address()
+    }
 
     unsafe fn create_texture(
         &self,
@@ -416,37 +649,46 @@ impl crate::Device<super::Api> for super::Device {
     ) -> Result<super::Texture, DeviceError> {
         use super::suballocation::create_texture_resource;
 
-        let mut resource = d3d12::Resource::null();
+        let tiled = desc.memory_flags.contains(crate::MemoryFlags::TILED);
+        let raw_desc = self.texture_resource_desc(desc);
 
-        let raw_desc = d3d12_ty::D3D12_RESOURCE_DESC {
-            Dimension: conv::map_texture_dimension(desc.dimension),
-            Alignment: 0,
-            Width: desc.size.width as u64,
-            Height: desc.size.height,
-            DepthOrArraySize: desc.size.depth_or_array_layers as u16,
-            MipLevels: desc.mip_level_count as u16,
-            Format: auxil::dxgi::conv::map_texture_format_for_resource(
-                desc.format,
-                desc.usage,
-                !desc.view_formats.is_empty(),
-                self.private_caps.casting_fully_typed_format_supported,
-            ),
-            SampleDesc: dxgitype::DXGI_SAMPLE_DESC {
-                Count: desc.sample_count,
-                Quality: 0,
-            },
-            Layout: d3d12_ty::D3D12_TEXTURE_LAYOUT_UNKNOWN,
-            Flags: conv::map_texture_usage_to_resource_flags(desc.usage),
+        let size = super::residency::texture_size(desc);
+        let (resource, allocation) = if tiled {
+            let mut resource = d3d12::Resource::null();
+            let hr = unsafe {
+                self.raw.CreateReservedResource(
+                    &raw_desc,
+                    d3d12_ty::D3D12_RESOURCE_STATE_COMMON,
+                    ptr::null(),
+                    &d3d12_ty::ID3D12Resource::uuidof(),
+                    resource.mut_void(),
+                )
+            };
+            hr.into_device_result("Reserved texture creation")?;
+            null_comptr_check(&resource)?;
+            (resource, None)
+        } else {
+            self.create_with_eviction(size, || {
+                let mut resource = d3d12::Resource::null();
+                let (hr, allocation) =
+                    create_texture_resource(self, desc, raw_desc, &mut resource)?;
+                hr.into_device_result("Texture creation")?;
+                Ok((resource, allocation))
+            })?
         };
 
-        let (hr, allocation) = create_texture_resource(self, desc, raw_desc, &mut resource)?;
-
-        hr.into_device_result("Texture creation")?;
         if let Some(label) = desc.label {
             let cwstr = conv::map_label(label);
             unsafe { resource.SetName(cwstr.as_ptr()) };
         }
 
+        // Reserved resources have no memory of their own, and tile pools are
+        // never evicted.
+        let residency = if tiled {
+            None
+        } else {
+            self.register_residency(&resource, allocation.as_ref(), size)
+        };
         Ok(super::Texture {
             resource,
             format: desc.format,
@@ -455,6 +697,7 @@ impl crate::Device<super::Api> for super::Device {
             mip_level_count: desc.mip_level_count,
             sample_count: desc.sample_count,
             allocation,
+            residency,
         })
     }
 
@@ -569,6 +812,7 @@ impl crate::Device<super::Api> for super::Device {
             } else {
                 None
             },
+            residency: texture.residency.clone(),
         })
     }
     unsafe fn destroy_texture_view(&self, view: super::TextureView) {
@@ -660,6 +904,7 @@ impl crate::Device<super::Api> for super::Device {
             free_lists: Vec::new(),
             pass: super::PassState::new(),
             temp: super::Temp::default(),
+            residency: super::residency::ResidencySet::default(),
             end_of_pass_timer_query: None,
         })
     }
@@ -1075,7 +1320,13 @@ impl crate::Device<super::Api> for super::Device {
                 special_constants_binding,
                 push_constants_target,
                 zero_initialize_workgroup_memory: true,
+                descriptor_heap_binding_arrays: false,
+                readable_names: self
+                    .private_caps
+                    .instance_flags
+                    .contains(wgt::InstanceFlags::DEBUG),
             },
+            indirect_signatures: Mutex::default(),
         })
     }
     unsafe fn destroy_pipeline_layout(&self, _pipeline_layout: super::PipelineLayout) {}
@@ -1090,7 +1341,7 @@ impl crate::Device<super::Api> for super::Device {
             .as_ref()
             .map(|cpu_heap| cpu_heap.inner.lock());
         if let Some(ref mut inner) = cpu_views {
-            inner.stage.clear();
+            inner.clear();
         }
         let mut cpu_samplers = desc
             .layout
@@ -1098,11 +1349,12 @@ impl crate::Device<super::Api> for super::Device {
             .as_ref()
             .map(|cpu_heap| cpu_heap.inner.lock());
         if let Some(ref mut inner) = cpu_samplers {
-            inner.stage.clear();
+            inner.clear();
         }
         let mut dynamic_buffers = Vec::new();
 
         for (layout, entry) in desc.layout.entries.iter().zip(desc.entries.iter()) {
+            let layout_count = layout.count.map_or(1, NonZeroU32::get);
             match layout.ty {
                 wgt::BindingType::Buffer {
                     has_dynamic_offset: true,
@@ -1184,6 +1436,7 @@ impl crate::Device<super::Api> for super::Device {
                         }
                         inner.stage.push(handle);
                     }
+                    cpu_views.as_mut().unwrap().finish_binding(layout_count);
                 }
                 wgt::BindingType::Texture { .. } => {
                     let start = entry.resource_index as usize;
@@ -1192,6 +1445,7 @@ impl crate::Device<super::Api> for super::Device {
                         let handle = data.view.handle_srv.unwrap();
                         cpu_views.as_mut().unwrap().stage.push(handle.raw);
                     }
+                    cpu_views.as_mut().unwrap().finish_binding(layout_count);
                 }
                 wgt::BindingType::StorageTexture { .. } => {
                     let start = entry.resource_index as usize;
@@ -1200,6 +1454,7 @@ impl crate::Device<super::Api> for super::Device {
                         let handle = data.view.handle_uav.unwrap();
                         cpu_views.as_mut().unwrap().stage.push(handle.raw);
                     }
+                    cpu_views.as_mut().unwrap().finish_binding(layout_count);
                 }
                 wgt::BindingType::Sampler { .. } => {
                     let start = entry.resource_index as usize;
@@ -1207,6 +1462,7 @@ impl crate::Device<super::Api> for super::Device {
                     for data in &desc.samplers[start..end] {
                         cpu_samplers.as_mut().unwrap().stage.push(data.handle.raw);
                     }
+                    cpu_samplers.as_mut().unwrap().finish_binding(layout_count);
                 }
                 wgt::BindingType::AccelerationStructure => todo!(),
             }
@@ -1241,10 +1497,24 @@ impl crate::Device<super::Api> for super::Device {
             None => None,
         };
 
+        let mut residency = desc
+            .buffers
+            .iter()
+            .filter_map(|binding| binding.buffer.residency.clone())
+            .chain(
+                desc.textures
+                    .iter()
+                    .filter_map(|binding| binding.view.residency.clone()),
+            )
+            .collect::<Vec<_>>();
+        residency.sort_by_key(Arc::as_ptr);
+        residency.dedup_by_key(|pageable| Arc::as_ptr(pageable));
+
         Ok(super::BindGroup {
             handle_views,
             handle_samplers,
             dynamic_buffers,
+            residency,
         })
     }
     unsafe fn destroy_bind_group(&self, group: super::BindGroup) {
@@ -1319,6 +1589,41 @@ impl crate::Device<super::Api> for super::Device {
             }
         }
 
+        // Captured outputs are written in order of their offsets, with gaps
+        // skipped in steps of up to 4 components.
+        let mut so_entries = Vec::new();
+        let mut so_strides =
+            arrayvec::ArrayVec::<u32, { crate::MAX_VERTEX_CAPTURE_BUFFERS }>::new();
+        for (i, vcb) in desc.vertex_capture.iter().enumerate() {
+            so_strides.push(vcb.array_stride as u32);
+            let mut attributes = vcb.attributes.to_vec();
+            attributes.sort_by_key(|attribute| attribute.offset);
+            let mut offset = 0;
+            for attribute in attributes {
+                while offset < attribute.offset {
+                    let components = ((attribute.offset - offset) / 4).min(4);
+                    so_entries.push(d3d12_ty::D3D12_SO_DECLARATION_ENTRY {
+                        Stream: 0,
+                        SemanticName: ptr::null(),
+                        SemanticIndex: 0,
+                        StartComponent: 0,
+                        ComponentCount: components as u8,
+                        OutputSlot: i as u8,
+                    });
+                    offset += components * 4;
+                }
+                so_entries.push(d3d12_ty::D3D12_SO_DECLARATION_ENTRY {
+                    Stream: 0,
+                    SemanticName: NAGA_LOCATION_SEMANTIC.as_ptr() as *const _,
+                    SemanticIndex: attribute.shader_location,
+                    StartComponent: 0,
+                    ComponentCount: (attribute.format.size() / 4) as u8,
+                    OutputSlot: i as u8,
+                });
+                offset += attribute.format.size();
+            }
+        }
+
         let mut rtv_formats = [dxgiformat::DXGI_FORMAT_UNKNOWN;
             d3d12_ty::D3D12_SIMULTANEOUS_RENDER_TARGET_COUNT as usize];
         for (rtv_format, ct) in rtv_formats.iter_mut().zip(desc.color_targets) {
@@ -1369,10 +1674,18 @@ impl crate::Device<super::Api> for super::Device {
             DS: *d3d12::Shader::null(),
             HS: *d3d12::Shader::null(),
             StreamOutput: d3d12_ty::D3D12_STREAM_OUTPUT_DESC {
-                pSODeclaration: ptr::null(),
-                NumEntries: 0,
-                pBufferStrides: ptr::null(),
-                NumStrides: 0,
+                pSODeclaration: if so_entries.is_empty() {
+                    ptr::null()
+                } else {
+                    so_entries.as_ptr()
+                },
+                NumEntries: so_entries.len() as u32,
+                pBufferStrides: if so_strides.is_empty() {
+                    ptr::null()
+                } else {
+                    so_strides.as_ptr()
+                },
+                NumStrides: so_strides.len() as u32,
                 RasterizedStream: 0,
             },
             BlendState: d3d12_ty::D3D12_BLEND_DESC {
@@ -1424,16 +1737,44 @@ impl crate::Device<super::Api> for super::Device {
             Flags: d3d12_ty::D3D12_PIPELINE_STATE_FLAG_NONE,
         };
 
-        let mut raw = d3d12::PipelineState::null();
-        let hr = {
-            profiling::scope!("ID3D12Device::CreateGraphicsPipelineState");
-            unsafe {
-                self.raw.CreateGraphicsPipelineState(
-                    &raw_desc,
-                    &d3d12_ty::ID3D12PipelineState::uuidof(),
-                    raw.mut_void(),
-                )
+        let create = |cached_pso: d3d12::CachedPSO| {
+            let mut raw_desc = raw_desc;
+            raw_desc.CachedPSO = *cached_pso;
+            let mut raw = d3d12::PipelineState::null();
+            let hr = {
+                profiling::scope!("ID3D12Device::CreateGraphicsPipelineState");
+                unsafe {
+                    self.raw.CreateGraphicsPipelineState(
+                        &raw_desc,
+                        &d3d12_ty::ID3D12PipelineState::uuidof(),
+                        raw.mut_void(),
+                    )
+                }
+            };
+            (raw, hr)
+        };
+        let pair = match desc.cache {
+            Some(cache) => {
+                let mut shaders = vec![&blob_vs];
+                shaders.extend(blob_fs.as_ref());
+                let state = (
+                    &desc.primitive,
+                    &desc.depth_stencil,
+                    &desc.multisample,
+                    desc.color_targets,
+                    desc.vertex_buffers
+                        .iter()
+                        .map(|vb| (vb.array_stride, vb.step_mode, vb.attributes))
+                        .collect::<Vec<_>>(),
+                    desc.vertex_capture
+                        .iter()
+                        .map(|vc| (vc.array_stride, vc.attributes))
+                        .collect::<Vec<_>>(),
+                    desc.multiview,
+                );
+                cache.create_pipeline(pipeline_cache_key(&shaders, state), create)
             }
+            None => create(d3d12::CachedPSO::null()),
         };
 
         unsafe { blob_vs.destroy() };
@@ -1441,7 +1782,8 @@ impl crate::Device<super::Api> for super::Device {
             unsafe { blob_fs.destroy() };
         };
 
-        hr.into_result()
+        let raw = pair
+            .into_result()
             .map_err(|err| crate::PipelineError::Linkage(shader_stages, err.into_owned()))?;
 
         null_comptr_check(&raw)?;
@@ -1458,6 +1800,12 @@ impl crate::Device<super::Api> for super::Device {
             vertex_strides,
         })
     }
+    unsafe fn create_mesh_pipeline(
+        &self,
+        _desc: &crate::MeshPipelineDescriptor<super::Api>,
+    ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        unreachable!()
+    }
     unsafe fn destroy_render_pipeline(&self, _pipeline: super::RenderPipeline) {}
 
     unsafe fn create_compute_pipeline(
@@ -1466,16 +1814,20 @@ impl crate::Device<super::Api> for super::Device {
     ) -> Result<super::ComputePipeline, crate::PipelineError> {
         let blob_cs = self.load_shader(&desc.stage, desc.layout, naga::ShaderStage::Compute)?;
 
-        let pair = {
+        let create = |cached_pso: d3d12::CachedPSO| {
             profiling::scope!("ID3D12Device::CreateComputePipelineState");
             self.raw.create_compute_pipeline_state(
                 &desc.layout.shared.signature,
                 blob_cs.create_native_shader(),
                 0,
-                d3d12::CachedPSO::null(),
+                cached_pso,
                 d3d12::PipelineStateFlags::empty(),
             )
         };
+        let pair = match desc.cache {
+            Some(cache) => cache.create_pipeline(pipeline_cache_key(&[&blob_cs], ()), create),
+            None => create(d3d12::CachedPSO::null()),
+        };
 
         unsafe { blob_cs.destroy() };
 
@@ -1496,6 +1848,23 @@ impl crate::Device<super::Api> for super::Device {
         })
     }
     unsafe fn destroy_compute_pipeline(&self, _pipeline: super::ComputePipeline) {}
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<super::PipelineCache, crate::PipelineCacheError> {
+        match desc.data {
+            Some(data) => {
+                super::PipelineCache::from_data(data).ok_or(crate::PipelineCacheError::Validation)
+            }
+            None => Ok(super::PipelineCache {
+                blobs: Mutex::new(Default::default()),
+            }),
+        }
+    }
+    unsafe fn destroy_pipeline_cache(&self, _cache: super::PipelineCache) {}
+    unsafe fn pipeline_cache_get_data(&self, cache: &super::PipelineCache) -> Option<Vec<u8>> {
+        Some(cache.to_data())
+    }
 
     unsafe fn create_query_set(
         &self,
@@ -1532,6 +1901,348 @@ impl crate::Device<super::Api> for super::Device {
     }
     unsafe fn destroy_query_set(&self, _set: super::QuerySet) {}
 
+    unsafe fn create_tile_pool(
+        &self,
+        desc: &wgt::TilePoolDescriptor<crate::Label>,
+    ) -> Result<super::TilePool, DeviceError> {
+        let heap_desc = d3d12_ty::D3D12_HEAP_DESC {
+            SizeInBytes: desc.tile_count as u64 * wgt::TEXTURE_TILE_SIZE,
+            Properties: d3d12_ty::D3D12_HEAP_PROPERTIES {
+                Type: d3d12_ty::D3D12_HEAP_TYPE_DEFAULT,
+                CPUPageProperty: d3d12_ty::D3D12_CPU_PAGE_PROPERTY_UNKNOWN,
+                MemoryPoolPreference: d3d12_ty::D3D12_MEMORY_POOL_UNKNOWN,
+                CreationNodeMask: 0,
+                VisibleNodeMask: 0,
+            },
+            Alignment: d3d12_ty::D3D12_DEFAULT_RESOURCE_PLACEMENT_ALIGNMENT as u64,
+            // Tiled textures can't be render targets, see `wgt::TextureUsages::TILED`.
+            Flags: d3d12_ty::D3D12_HEAP_FLAG_ALLOW_ONLY_NON_RT_DS_TEXTURES,
+        };
+
+        let mut heap = ComPtr::<d3d12_ty::ID3D12Heap>::null();
+        let hr = unsafe {
+            self.raw
+                .CreateHeap(&heap_desc, &d3d12_ty::ID3D12Heap::uuidof(), heap.mut_void())
+        };
+        hr.into_device_result("Tile pool creation")?;
+        null_comptr_check(&heap)?;
+
+        if let Some(label) = desc.label {
+            let cwstr = conv::map_label(label);
+            unsafe { heap.SetName(cwstr.as_ptr()) };
+        }
+
+        Ok(super::TilePool { heap })
+    }
+    unsafe fn destroy_tile_pool(&self, _pool: super::TilePool) {}
+
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &wgt::MemoryHeapDescriptor<crate::Label>,
+    ) -> Result<super::MemoryHeap, DeviceError> {
+        let (cpu_page_property, device_local) = match desc.memory_type {
+            wgt::MemoryHeapType::DeviceLocal => {
+                (d3d12_ty::D3D12_CPU_PAGE_PROPERTY_NOT_AVAILABLE, true)
+            }
+            wgt::MemoryHeapType::Upload => (d3d12_ty::D3D12_CPU_PAGE_PROPERTY_WRITE_COMBINE, false),
+            wgt::MemoryHeapType::Readback => (d3d12_ty::D3D12_CPU_PAGE_PROPERTY_WRITE_BACK, false),
+        };
+        let heap_desc = d3d12_ty::D3D12_HEAP_DESC {
+            SizeInBytes: desc.size,
+            // Custom heaps let every resource start out in the common state,
+            // like the committed buffers of `create_buffer`.
+            Properties: d3d12_ty::D3D12_HEAP_PROPERTIES {
+                Type: d3d12_ty::D3D12_HEAP_TYPE_CUSTOM,
+                CPUPageProperty: cpu_page_property,
+                MemoryPoolPreference: match self.private_caps.memory_architecture {
+                    super::MemoryArchitecture::NonUnified if device_local => {
+                        d3d12_ty::D3D12_MEMORY_POOL_L1
+                    }
+                    _ => d3d12_ty::D3D12_MEMORY_POOL_L0,
+                },
+                CreationNodeMask: 0,
+                VisibleNodeMask: 0,
+            },
+            // Leaves room for multisampled textures.
+            Alignment: d3d12_ty::D3D12_DEFAULT_MSAA_RESOURCE_PLACEMENT_ALIGNMENT as u64,
+            // Mixing buffers and textures in a heap requires resource heap tier 2,
+            // see `wgt::Features::MEMORY_HEAPS`.
+            Flags: if device_local {
+                d3d12_ty::D3D12_HEAP_FLAG_ALLOW_ALL_BUFFERS_AND_TEXTURES
+            } else {
+                d3d12_ty::D3D12_HEAP_FLAG_ALLOW_ONLY_BUFFERS
+            },
+        };
+
+        let mut heap = ComPtr::<d3d12_ty::ID3D12Heap>::null();
+        let hr = unsafe {
+            self.raw
+                .CreateHeap(&heap_desc, &d3d12_ty::ID3D12Heap::uuidof(), heap.mut_void())
+        };
+        hr.into_device_result("Memory heap creation")?;
+        null_comptr_check(&heap)?;
+
+        if let Some(label) = desc.label {
+            let cwstr = conv::map_label(label);
+            unsafe { heap.SetName(cwstr.as_ptr()) };
+        }
+
+        Ok(super::MemoryHeap { heap })
+    }
+    unsafe fn destroy_memory_heap(&self, _heap: super::MemoryHeap) {}
+
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, DeviceError> {
+        let (_, raw_desc) = buffer_resource_desc(desc);
+        self.memory_requirements(&raw_desc)
+    }
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, DeviceError> {
+        self.memory_requirements(&self.texture_resource_desc(desc))
+    }
+
+    unsafe fn create_placed_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<super::Buffer, DeviceError> {
+        let (size, raw_desc) = buffer_resource_desc(desc);
+        let resource = unsafe { self.create_placed_resource(&raw_desc, heap, offset, desc.label) }?;
+
+        // Memory heaps are never evicted.
+        Ok(super::Buffer {
+            resource,
+            size,
+            allocation: None,
+            residency: None,
+            external_size: None,
+        })
+    }
+    unsafe fn create_placed_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<super::Texture, DeviceError> {
+        let raw_desc = self.texture_resource_desc(desc);
+        let resource = unsafe { self.create_placed_resource(&raw_desc, heap, offset, desc.label) }?;
+
+        Ok(super::Texture {
+            resource,
+            format: desc.format,
+            dimension: desc.dimension,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            allocation: None,
+            residency: None,
+        })
+    }
+
+    unsafe fn import_external_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        source: wgt::ExternalImageSource,
+    ) -> Result<super::Texture, DeviceError> {
+        let handle = match source {
+            wgt::ExternalImageSource::D3D12SharedHandle(handle) => handle,
+            _ => return Err(DeviceError::ResourceCreationFailed),
+        };
+
+        let mut resource = d3d12::Resource::null();
+        let hr = unsafe {
+            self.raw.OpenSharedHandle(
+                handle.cast(),
+                &d3d12_ty::ID3D12Resource::uuidof(),
+                resource.mut_void(),
+            )
+        };
+        hr.into_device_result("Shared handle opening")?;
+        null_comptr_check(&resource)?;
+
+        if let Some(label) = desc.label {
+            let cwstr = conv::map_label(label);
+            unsafe { resource.SetName(cwstr.as_ptr()) };
+        }
+
+        Ok(super::Texture {
+            resource,
+            format: desc.format,
+            dimension: desc.dimension,
+            size: desc.size,
+            mip_level_count: 1,
+            sample_count: 1,
+            allocation: None,
+            residency: None,
+        })
+    }
+
+    unsafe fn create_external_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, DeviceError> {
+        let (size, raw_desc) = buffer_resource_desc(desc);
+        let external_size = self.memory_requirements(&raw_desc)?.size;
+
+        // Only committed resources can be shared.
+        let heap_properties = d3d12_ty::D3D12_HEAP_PROPERTIES {
+            Type: d3d12_ty::D3D12_HEAP_TYPE_DEFAULT,
+            CPUPageProperty: d3d12_ty::D3D12_CPU_PAGE_PROPERTY_UNKNOWN,
+            MemoryPoolPreference: d3d12_ty::D3D12_MEMORY_POOL_UNKNOWN,
+            CreationNodeMask: 0,
+            VisibleNodeMask: 0,
+        };
+        let resource = self.create_with_eviction(size, || {
+            let mut resource = d3d12::Resource::null();
+            unsafe {
+                self.raw.CreateCommittedResource(
+                    &heap_properties,
+                    d3d12_ty::D3D12_HEAP_FLAG_SHARED,
+                    &raw_desc,
+                    d3d12_ty::D3D12_RESOURCE_STATE_COMMON,
+                    ptr::null(),
+                    &d3d12_ty::ID3D12Resource::uuidof(),
+                    resource.mut_void(),
+                )
+            }
+            .into_device_result("Shared buffer creation")?;
+            null_comptr_check(&resource)?;
+            Ok(resource)
+        })?;
+
+        if let Some(label) = desc.label {
+            let cwstr = conv::map_label(label);
+            unsafe { resource.SetName(cwstr.as_ptr()) };
+        }
+
+        let residency = self.register_residency(&resource, None, size);
+        Ok(super::Buffer {
+            resource,
+            size,
+            allocation: None,
+            residency,
+            external_size: Some(external_size),
+        })
+    }
+
+    unsafe fn import_external_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> Result<super::Buffer, DeviceError> {
+        let handle = match memory.handle {
+            wgt::ExternalMemoryHandle::D3D12SharedHandle(handle) => handle,
+            _ => return Err(DeviceError::ResourceCreationFailed),
+        };
+        let (size, _) = buffer_resource_desc(desc);
+        if memory.size < size {
+            return Err(DeviceError::ResourceCreationFailed);
+        }
+
+        let mut resource = d3d12::Resource::null();
+        let hr = unsafe {
+            self.raw.OpenSharedHandle(
+                handle.cast(),
+                &d3d12_ty::ID3D12Resource::uuidof(),
+                resource.mut_void(),
+            )
+        };
+        hr.into_device_result("Shared handle opening")?;
+        null_comptr_check(&resource)?;
+
+        if let Some(label) = desc.label {
+            let cwstr = conv::map_label(label);
+            unsafe { resource.SetName(cwstr.as_ptr()) };
+        }
+
+        // The residency of the memory is managed by the API that allocated it.
+        Ok(super::Buffer {
+            resource,
+            size,
+            allocation: None,
+            residency: None,
+            external_size: Some(memory.size),
+        })
+    }
+
+    unsafe fn export_external_buffer(
+        &self,
+        buffer: &super::Buffer,
+    ) -> Result<wgt::ExternalMemory, DeviceError> {
+        let Some(size) = buffer.external_size else {
+            return Err(DeviceError::ResourceCreationFailed);
+        };
+
+        let mut handle = ptr::null_mut();
+        let hr = unsafe {
+            self.raw.CreateSharedHandle(
+                buffer.resource.as_mut_ptr().cast(),
+                ptr::null(),
+                winapi::um::winnt::GENERIC_ALL,
+                ptr::null(),
+                &mut handle,
+            )
+        };
+        hr.into_device_result("Shared handle creation")?;
+
+        Ok(wgt::ExternalMemory {
+            handle: wgt::ExternalMemoryHandle::D3D12SharedHandle(handle.cast()),
+            size,
+        })
+    }
+
+    unsafe fn create_external_fence(&self) -> Result<super::Fence, DeviceError> {
+        // All fences are created shareable.
+        unsafe { self.create_fence() }
+    }
+
+    unsafe fn import_external_fence(
+        &self,
+        handle: wgt::ExternalFenceHandle,
+    ) -> Result<super::Fence, DeviceError> {
+        let handle = match handle {
+            wgt::ExternalFenceHandle::D3D12SharedHandle(handle) => handle,
+            _ => return Err(DeviceError::ResourceCreationFailed),
+        };
+
+        let mut raw = d3d12::Fence::null();
+        let hr = unsafe {
+            self.raw.OpenSharedHandle(
+                handle.cast(),
+                &d3d12_ty::ID3D12Fence::uuidof(),
+                raw.mut_void(),
+            )
+        };
+        hr.into_device_result("Shared handle opening")?;
+        null_comptr_check(&raw)?;
+
+        Ok(super::Fence { raw })
+    }
+
+    unsafe fn export_external_fence(
+        &self,
+        fence: &super::Fence,
+    ) -> Result<wgt::ExternalFenceHandle, DeviceError> {
+        let mut handle = ptr::null_mut();
+        let hr = unsafe {
+            self.raw.CreateSharedHandle(
+                fence.raw.as_mut_ptr().cast(),
+                ptr::null(),
+                winapi::um::winnt::GENERIC_ALL,
+                ptr::null(),
+                &mut handle,
+            )
+        };
+        hr.into_device_result("Shared handle creation")?;
+
+        Ok(wgt::ExternalFenceHandle::D3D12SharedHandle(handle.cast()))
+    }
+
     unsafe fn create_fence(&self) -> Result<super::Fence, DeviceError> {
         let mut raw = d3d12::Fence::null();
         let hr = unsafe {
@@ -1,5 +1,8 @@
 use parking_lot::RwLock;
-use winapi::shared::{dxgi1_5, minwindef};
+use winapi::{
+    shared::{dxgi1_2, dxgi1_5, minwindef},
+    um::d3d12 as d3d12_ty,
+};
 
 use super::SurfaceTarget;
 use crate::auxil::{self, dxgi::result::HResult as _};
@@ -11,6 +14,39 @@ impl Drop for super::Instance {
     }
 }
 
+impl super::Instance {
+    /// Exposes the adapter of a device created outside of wgpu.
+    ///
+    /// Returns `None` if the device doesn't belong to an adapter of the DXGI
+    /// factory of the instance.
+    ///
+    /// # Safety
+    ///
+    /// - `device` must be a valid `ID3D12Device`.
+    pub unsafe fn expose_adapter_from_raw(
+        &self,
+        device: *mut d3d12_ty::ID3D12Device,
+    ) -> Option<crate::ExposedAdapter<super::Api>> {
+        let device = unsafe { d3d12::ComPtr::from_raw(device) };
+        let luid = unsafe { device.GetAdapterLuid() };
+        let raw = auxil::dxgi::factory::enumerate_adapters(self.factory.clone())
+            .into_iter()
+            .find(|raw| {
+                let mut desc: dxgi1_2::DXGI_ADAPTER_DESC2 = unsafe { mem::zeroed() };
+                unsafe { raw.unwrap_adapter2().GetDesc2(&mut desc) };
+                desc.AdapterLuid.LowPart == luid.LowPart
+                    && desc.AdapterLuid.HighPart == luid.HighPart
+            })?;
+        super::Adapter::expose(
+            raw,
+            Some(device),
+            &self.library,
+            self.flags,
+            self.dxc_container.clone(),
+        )
+    }
+}
+
 impl crate::Instance<super::Api> for super::Instance {
     unsafe fn init(desc: &crate::InstanceDescriptor) -> Result<Self, crate::InstanceError> {
         profiling::scope!("Init DX12 Backend");
@@ -135,7 +171,13 @@ impl crate::Instance<super::Api> for super::Instance {
         adapters
             .into_iter()
             .filter_map(|raw| {
-                super::Adapter::expose(raw, &self.library, self.flags, self.dxc_container.clone())
+                super::Adapter::expose(
+                    raw,
+                    None,
+                    &self.library,
+                    self.flags,
+                    self.dxc_container.clone(),
+                )
             })
             .collect()
     }
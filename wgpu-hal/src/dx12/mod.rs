@@ -13,6 +13,10 @@ and destination states match, and they are for storage sync.
 
 For now, all resources are created with "committed" memory.
 
+When the adapter reports its video memory budget, the heaps and committed
+resources used by each submission are tracked, and the least recently used
+ones are evicted when the budget is exceeded. See the `residency` module.
+
 ## Resource binding
 
 See ['Device::create_pipeline_layout`] documentation for the structure
@@ -31,6 +35,15 @@ we are binding everything in `CommandEncoder::update_root_elements`.
 When the pipeline layout is changed, we reset all bindings.
 Otherwise, we pass a range corresponding only to the current bind group.
 
+## Pipeline caches
+
+D3D12 caches pipelines one by one, with the blob of `GetCachedBlob` passed
+back as `CachedPSO` when creating the same pipeline again. `PipelineCache`
+keeps these blobs keyed by a hash of the shader bytecode and the pipeline
+state. The driver rejects blobs that don't match the pipeline or come from
+another adapter or driver version, in which case the pipeline is compiled
+and its new blob replaces the old one.
+
 !*/
 
 mod adapter;
@@ -39,6 +52,7 @@ mod conv;
 mod descriptor;
 mod device;
 mod instance;
+mod residency;
 mod shader_compilation;
 mod suballocation;
 mod types;
@@ -82,6 +96,9 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type PipelineCache = PipelineCache;
+    type TilePool = TilePool;
+    type MemoryHeap = MemoryHeap;
 
     type AccelerationStructure = AccelerationStructure;
 }
@@ -89,6 +106,9 @@ impl crate::Api for Api {
 // Limited by D3D12's root signature size of 64. Each element takes 1 or 2 entries.
 const MAX_ROOT_ELEMENTS: usize = 64;
 const ZERO_BUFFER_SIZE: wgt::BufferAddress = 256 << 10;
+// One 32-bit filled size counter per stream output buffer.
+const VERTEX_CAPTURE_COUNTERS_SIZE: wgt::BufferAddress =
+    4 * crate::MAX_VERTEX_CAPTURE_BUFFERS as wgt::BufferAddress;
 
 pub struct Instance {
     factory: d3d12::DxgiFactory,
@@ -194,6 +214,7 @@ struct PrivateCapabilities {
     #[allow(unused)] // TODO: Exists until windows-rs is standard, then it can probably be removed?
     heap_create_not_zeroed: bool,
     casting_fully_typed_format_supported: bool,
+    resolve_subresource_region: bool,
     suballocation_supported: bool,
 }
 
@@ -233,6 +254,9 @@ struct CommandSignatures {
 
 struct DeviceShared {
     zero_buffer: d3d12::Resource,
+    /// The filled size counters of stream output, reset at the start of each
+    /// vertex capture. It's in the `COMMON` state outside of them.
+    vertex_capture_counters: d3d12::Resource,
     cmd_signatures: CommandSignatures,
     heap_views: descriptor::GeneralHeap,
     heap_samplers: descriptor::GeneralHeap,
@@ -255,6 +279,7 @@ pub struct Device {
     render_doc: crate::auxil::renderdoc::RenderDoc,
     null_rtv_handle: descriptor::Handle,
     mem_allocator: Option<Mutex<suballocation::GpuAllocatorWrapper>>,
+    residency: Option<Arc<residency::Residency>>,
     dxc_container: Option<Arc<shader_compilation::DxcContainer>>,
 }
 
@@ -264,6 +289,7 @@ unsafe impl Sync for Device {}
 pub struct Queue {
     raw: d3d12::CommandQueue,
     temp_lists: Mutex<Vec<d3d12::CommandList>>,
+    residency: Option<Arc<residency::Residency>>,
 }
 
 unsafe impl Send for Queue {}
@@ -323,6 +349,11 @@ struct PassState {
     vertex_buffers: [d3d12_ty::D3D12_VERTEX_BUFFER_VIEW; crate::MAX_VERTEX_BUFFERS],
     dirty_vertex_buffers: usize,
     kind: PassKind,
+    /// Whether the shading rate or shading rate image were changed, and need to
+    /// be reset at the end of the pass.
+    shading_rate_dirty: bool,
+    /// Whether the pass has a shading rate image, which overrides the shading rate.
+    shading_rate_image: bool,
 }
 
 #[test]
@@ -347,6 +378,8 @@ impl PassState {
             vertex_buffers: [unsafe { mem::zeroed() }; crate::MAX_VERTEX_BUFFERS],
             dirty_vertex_buffers: 0,
             kind: PassKind::Transfer,
+            shading_rate_dirty: false,
+            shading_rate_image: false,
         }
     }
 
@@ -365,6 +398,8 @@ pub struct CommandEncoder {
     free_lists: Vec<d3d12::GraphicsCommandList>,
     pass: PassState,
     temp: Temp,
+    /// Heaps and committed resources used by the commands recorded so far.
+    residency: residency::ResidencySet,
 
     /// If set, the end of the next render/compute pass will write a timestamp at
     /// the given pool & location.
@@ -386,6 +421,7 @@ impl fmt::Debug for CommandEncoder {
 #[derive(Debug)]
 pub struct CommandBuffer {
     raw: d3d12::GraphicsCommandList,
+    residency: residency::ResidencySet,
 }
 
 unsafe impl Send for CommandBuffer {}
@@ -396,6 +432,9 @@ pub struct Buffer {
     resource: d3d12::Resource,
     size: wgt::BufferAddress,
     allocation: Option<suballocation::AllocationWrapper>,
+    residency: Option<Arc<residency::Pageable>>,
+    /// Allocation size of a buffer shared with other APIs.
+    external_size: Option<wgt::BufferAddress>,
 }
 
 unsafe impl Send for Buffer {}
@@ -423,6 +462,7 @@ pub struct Texture {
     mip_level_count: u32,
     sample_count: u32,
     allocation: Option<suballocation::AllocationWrapper>,
+    residency: Option<Arc<residency::Pageable>>,
 }
 
 unsafe impl Send for Texture {}
@@ -462,6 +502,7 @@ pub struct TextureView {
     handle_rtv: Option<descriptor::Handle>,
     handle_dsv_ro: Option<descriptor::Handle>,
     handle_dsv_rw: Option<descriptor::Handle>,
+    residency: Option<Arc<residency::Pageable>>,
 }
 
 unsafe impl Send for TextureView {}
@@ -484,6 +525,22 @@ pub struct QuerySet {
 unsafe impl Send for QuerySet {}
 unsafe impl Sync for QuerySet {}
 
+#[derive(Debug)]
+pub struct TilePool {
+    heap: d3d12::ComPtr<d3d12_ty::ID3D12Heap>,
+}
+
+unsafe impl Send for TilePool {}
+unsafe impl Sync for TilePool {}
+
+#[derive(Debug)]
+pub struct MemoryHeap {
+    heap: d3d12::ComPtr<d3d12_ty::ID3D12Heap>,
+}
+
+unsafe impl Send for MemoryHeap {}
+unsafe impl Sync for MemoryHeap {}
+
 #[derive(Debug)]
 pub struct Fence {
     raw: d3d12::Fence,
@@ -519,6 +576,8 @@ pub struct BindGroup {
     handle_views: Option<descriptor::DualHandle>,
     handle_samplers: Option<descriptor::DualHandle>,
     dynamic_buffers: Vec<d3d12::GpuAddress>,
+    /// Heaps and committed resources of the bound buffers and textures.
+    residency: Vec<Arc<residency::Pageable>>,
 }
 
 bitflags::bitflags! {
@@ -563,8 +622,14 @@ pub struct PipelineLayout {
     // in the root signature. This is required for binding descriptor sets.
     bind_group_infos: ArrayVec<BindGroupInfo, { crate::MAX_BIND_GROUPS }>,
     naga_options: naga::back::hlsl::Options,
+    /// Command signatures used by `execute_indirect`, created on first use.
+    indirect_signatures:
+        Mutex<rustc_hash::FxHashMap<Vec<wgt::IndirectArgument>, d3d12::CommandSignature>>,
 }
 
+unsafe impl Send for PipelineLayout {}
+unsafe impl Sync for PipelineLayout {}
+
 #[derive(Debug)]
 pub struct ShaderModule {
     naga: crate::NagaShader,
@@ -585,6 +650,18 @@ impl CompiledShader {
         }
     }
 
+    fn bytecode(&self) -> &[u8] {
+        match *self {
+            CompiledShader::Dxc(ref shader) => shader,
+            CompiledShader::Fxc(ref shader) => unsafe {
+                std::slice::from_raw_parts(
+                    shader.GetBufferPointer() as *const u8,
+                    shader.GetBufferSize(),
+                )
+            },
+        }
+    }
+
     unsafe fn destroy(self) {}
 }
 
@@ -608,6 +685,85 @@ pub struct ComputePipeline {
 unsafe impl Send for ComputePipeline {}
 unsafe impl Sync for ComputePipeline {}
 
+pub struct PipelineCache {
+    /// Cached pipeline state blobs, keyed by the hash of the shaders and state
+    /// of the pipeline they were created for.
+    blobs: Mutex<rustc_hash::FxHashMap<u64, Vec<u8>>>,
+}
+
+impl fmt::Debug for PipelineCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineCache")
+            .field("blobs", &self.blobs.lock().len())
+            .finish()
+    }
+}
+
+const PIPELINE_CACHE_MAGIC: &[u8; 8] = b"WGPUDXPC";
+const PIPELINE_CACHE_VERSION: u32 = 1;
+
+impl PipelineCache {
+    /// Reads the blobs from `data`, as written by [`Self::to_data`].
+    ///
+    /// Returns `None` if the data is malformed.
+    fn from_data(data: &[u8]) -> Option<Self> {
+        struct Reader<'a>(&'a [u8]);
+
+        impl<'a> Reader<'a> {
+            fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+                if self.0.len() < len {
+                    return None;
+                }
+                let (bytes, rest) = self.0.split_at(len);
+                self.0 = rest;
+                Some(bytes)
+            }
+            fn u32(&mut self) -> Option<u32> {
+                Some(u32::from_le_bytes(self.bytes(4)?.try_into().ok()?))
+            }
+            fn u64(&mut self) -> Option<u64> {
+                Some(u64::from_le_bytes(self.bytes(8)?.try_into().ok()?))
+            }
+        }
+
+        let mut reader = Reader(data);
+        if reader.bytes(PIPELINE_CACHE_MAGIC.len())? != PIPELINE_CACHE_MAGIC
+            || reader.u32()? != PIPELINE_CACHE_VERSION
+        {
+            return None;
+        }
+
+        let count = reader.u32()?;
+        let mut blobs = rustc_hash::FxHashMap::default();
+        for _ in 0..count {
+            let key = reader.u64()?;
+            let len = reader.u32()? as usize;
+            blobs.insert(key, reader.bytes(len)?.to_vec());
+        }
+        if !reader.0.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            blobs: Mutex::new(blobs),
+        })
+    }
+
+    fn to_data(&self) -> Vec<u8> {
+        let blobs = self.blobs.lock();
+        let mut data = Vec::new();
+        data.extend_from_slice(PIPELINE_CACHE_MAGIC);
+        data.extend_from_slice(&PIPELINE_CACHE_VERSION.to_le_bytes());
+        data.extend_from_slice(&(blobs.len() as u32).to_le_bytes());
+        for (key, blob) in blobs.iter() {
+            data.extend_from_slice(&key.to_le_bytes());
+            data.extend_from_slice(&(blob.len() as u32).to_le_bytes());
+            data.extend_from_slice(blob);
+        }
+        data
+    }
+}
+
 #[derive(Debug)]
 pub struct AccelerationStructure {}
 
@@ -868,6 +1024,7 @@ impl crate::Surface<Api> for Surface {
             mip_level_count: 1,
             sample_count: 1,
             allocation: None,
+            residency: None,
         };
         Ok(Some(crate::AcquiredSurfaceTexture {
             texture,
@@ -888,6 +1045,12 @@ impl crate::Queue<Api> for Queue {
         _surface_textures: &[&Texture],
         signal_fence: Option<(&mut Fence, crate::FenceValue)>,
     ) -> Result<(), crate::DeviceError> {
+        if let Some(ref residency) = self.residency {
+            unsafe {
+                residency.begin_submission(command_buffers.iter().map(|cmd_buf| &cmd_buf.residency))
+            }?;
+        }
+
         let mut temp_lists = self.temp_lists.lock();
         temp_lists.clear();
         for cmd_buf in command_buffers {
@@ -899,6 +1062,10 @@ impl crate::Queue<Api> for Queue {
             self.raw.execute_command_lists(&temp_lists);
         }
 
+        if let Some(ref residency) = self.residency {
+            unsafe { residency.end_submission(&self.raw) }?;
+        }
+
         if let Some((fence, value)) = signal_fence {
             self.raw
                 .signal(&fence.raw, value)
@@ -941,6 +1108,109 @@ impl crate::Queue<Api> for Queue {
         unsafe { self.raw.GetTimestampFrequency(&mut frequency) };
         (1_000_000_000.0 / frequency as f64) as f32
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        let mut gpu_timestamp = 0u64;
+        let mut cpu_timestamp = 0u64;
+        let hr = unsafe {
+            self.raw
+                .GetClockCalibration(&mut gpu_timestamp, &mut cpu_timestamp)
+        };
+        if let Err(err) = hr.into_result() {
+            log::error!("GetClockCalibration failed: {err}");
+            return None;
+        }
+        // The CPU timestamp is a value of `QueryPerformanceCounter`, just like the
+        // presentation timestamps of DXGI.
+        Some(wgt::TimestampCalibration {
+            cpu_timestamp: wgt::PresentationTimestamp(
+                auxil::dxgi::time::PresentationTimer::new_dxgi().counter_to_ns(cpu_timestamp),
+            ),
+            gpu_timestamp,
+        })
+    }
+
+    unsafe fn bind_texture_tiles(
+        &self,
+        texture: &Texture,
+        bindings: &[crate::TextureTileBinding<Api>],
+    ) -> Result<(), crate::DeviceError> {
+        for binding in bindings {
+            // A box of tiles can't span subresources, so every layer is a region.
+            let layers = binding.origin.z..binding.origin.z + binding.size.depth_or_array_layers;
+            let coordinates = layers
+                .map(|layer| d3d12_ty::D3D12_TILED_RESOURCE_COORDINATE {
+                    X: binding.origin.x,
+                    Y: binding.origin.y,
+                    Z: 0,
+                    Subresource: texture.calc_subresource(binding.mip_level, layer, 0),
+                })
+                .collect::<Vec<_>>();
+            let region_size = d3d12_ty::D3D12_TILE_REGION_SIZE {
+                NumTiles: binding.size.width * binding.size.height,
+                UseBox: 1,
+                Width: binding.size.width,
+                Height: binding.size.height as u16,
+                Depth: 1,
+            };
+            if coordinates.is_empty() || region_size.NumTiles == 0 {
+                continue;
+            }
+            let region_sizes = vec![region_size; coordinates.len()];
+
+            // All regions are mapped to a single range of tiles of the pool.
+            let tile_count = region_size.NumTiles * coordinates.len() as u32;
+            let (heap, range_flags, first_tile) = match binding.memory {
+                Some((pool, first_tile)) => (
+                    pool.heap.as_mut_ptr(),
+                    d3d12_ty::D3D12_TILE_RANGE_FLAG_NONE,
+                    first_tile,
+                ),
+                None => (
+                    std::ptr::null_mut(),
+                    d3d12_ty::D3D12_TILE_RANGE_FLAG_NULL,
+                    0,
+                ),
+            };
+
+            profiling::scope!("ID3D12CommandQueue::UpdateTileMappings");
+            unsafe {
+                self.raw.UpdateTileMappings(
+                    texture.resource.as_mut_ptr(),
+                    coordinates.len() as u32,
+                    coordinates.as_ptr(),
+                    region_sizes.as_ptr(),
+                    heap,
+                    1,
+                    &range_flags,
+                    &first_tile,
+                    &tile_count,
+                    d3d12_ty::D3D12_TILE_MAPPING_FLAG_NONE,
+                )
+            };
+        }
+        Ok(())
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        self.raw
+            .wait(&fence.raw, value)
+            .into_device_result("Wait on fence")
+    }
+
+    unsafe fn signal_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        self.raw
+            .signal(&fence.raw, value)
+            .into_device_result("Signal fence")
+    }
 }
 
 /// A shorthand for producing a `ResourceCreationFailed` error if a ComPtr is null.
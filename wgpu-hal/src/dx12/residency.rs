@@ -0,0 +1,399 @@
+/*!
+Residency management of heaps and committed resources.
+
+When video memory is overcommitted, D3D12 doesn't fail allocations that
+exceed the budget right away, it lets the OS page memory in and out behind
+our back, or fails with `E_OUTOFMEMORY` once nothing can be paged out anymore.
+To degrade more gracefully, every object holding memory is tracked as a
+[`Pageable`], and every submission records the pageables it uses.
+
+Whenever DXGI notifies us that the video memory budget changed, or an
+allocation runs out of memory, the least recently used pageables that aren't
+used by work in flight get evicted with `ID3D12Device::Evict`. Evicted
+pageables are made resident again before the next submission using them.
+
+Buffers that are only referenced by their GPU address, for example through
+the arguments of `execute_indirect`, aren't tracked by the command encoder
+and have to be used by another command of the submission to stay resident.
+!*/
+
+use crate::auxil::dxgi::result::HResult as _;
+
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use std::{
+    mem,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Weak,
+    },
+};
+use winapi::{
+    shared::{dxgi1_4, winerror},
+    um::{d3d12 as d3d12_ty, winbase},
+    Interface as _,
+};
+
+/// An object holding memory that can be evicted, either a heap shared by
+/// placed resources or a committed resource.
+#[derive(Debug)]
+pub(super) struct Pageable {
+    raw: d3d12::ComPtr<d3d12_ty::ID3D12Pageable>,
+    /// Estimated size of the memory, only used to decide how much to evict.
+    size: AtomicU64,
+    resident: AtomicBool,
+    /// Index of the last submission using this pageable.
+    last_submission: AtomicU64,
+}
+
+/// Pageables used by a command buffer.
+#[derive(Debug, Default)]
+pub(super) struct ResidencySet {
+    pageables: FxHashMap<*const Pageable, Arc<Pageable>>,
+}
+
+impl ResidencySet {
+    pub(super) fn insert(&mut self, pageable: &Option<Arc<Pageable>>) {
+        if let Some(pageable) = pageable {
+            self.pageables
+                .entry(Arc::as_ptr(pageable))
+                .or_insert_with(|| Arc::clone(pageable));
+        }
+    }
+
+    pub(super) fn extend(&mut self, pageables: &[Arc<Pageable>]) {
+        for pageable in pageables {
+            self.pageables
+                .entry(Arc::as_ptr(pageable))
+                .or_insert_with(|| Arc::clone(pageable));
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.pageables.clear();
+    }
+}
+
+struct Inner {
+    /// Index of the last submission, signaled on `fence` once it completes.
+    submission_index: u64,
+    /// Set when DXGI notified a budget change, until usage is within the budget.
+    over_budget: bool,
+    pageables: Vec<Weak<Pageable>>,
+    /// Pageables of the heaps of placed resources, keyed by their heap.
+    heaps: FxHashMap<usize, Weak<Pageable>>,
+}
+
+pub(super) struct Residency {
+    device: d3d12::Device,
+    adapter: d3d12::Adapter3,
+    fence: d3d12::Fence,
+    budget_event: d3d12::Event,
+    budget_cookie: u32,
+    inner: Mutex<Inner>,
+}
+
+unsafe impl Send for Residency {}
+unsafe impl Sync for Residency {}
+
+impl Residency {
+    /// Returns `None` if the adapter can't report its video memory budget.
+    pub(super) fn new(adapter: &d3d12::DxgiAdapter, device: &d3d12::Device) -> Option<Arc<Self>> {
+        let adapter = adapter.as_adapter3()?.clone();
+
+        let mut fence = d3d12::Fence::null();
+        let hr = unsafe {
+            device.CreateFence(
+                0,
+                d3d12_ty::D3D12_FENCE_FLAG_NONE,
+                &d3d12_ty::ID3D12Fence::uuidof(),
+                fence.mut_void(),
+            )
+        };
+        if let Err(err) = hr.into_result() {
+            log::warn!("Residency fence creation failed: {}", err);
+            return None;
+        }
+
+        // Signaled initially, so that the first submission checks the budget.
+        let budget_event = d3d12::Event::create(false, true);
+        let mut budget_cookie = 0;
+        let hr = unsafe {
+            adapter.RegisterVideoMemoryBudgetChangeNotificationEvent(
+                budget_event.0,
+                &mut budget_cookie,
+            )
+        };
+        if let Err(err) = hr.into_result() {
+            log::warn!(
+                "Unable to register for video memory budget changes: {}",
+                err
+            );
+            return None;
+        }
+
+        Some(Arc::new(Self {
+            device: device.clone(),
+            adapter,
+            fence,
+            budget_event,
+            budget_cookie,
+            inner: Mutex::new(Inner {
+                submission_index: 0,
+                over_budget: false,
+                pageables: Vec::new(),
+                heaps: FxHashMap::default(),
+            }),
+        }))
+    }
+
+    fn track(inner: &mut Inner, pageable: &Arc<Pageable>) {
+        if inner.pageables.len() == inner.pageables.capacity() {
+            inner.pageables.retain(|p| p.strong_count() != 0);
+            inner.heaps.retain(|_, p| p.strong_count() != 0);
+        }
+        inner.pageables.push(Arc::downgrade(pageable));
+    }
+
+    /// Tracks a committed resource of `size` bytes.
+    pub(super) fn register_resource(
+        &self,
+        resource: &d3d12::Resource,
+        size: u64,
+    ) -> Option<Arc<Pageable>> {
+        let raw = match unsafe { resource.cast::<d3d12_ty::ID3D12Pageable>() }.into_result() {
+            Ok(raw) => raw,
+            Err(err) => {
+                log::error!("Unable to cast resource to ID3D12Pageable: {}", err);
+                return None;
+            }
+        };
+        let pageable = Arc::new(Pageable {
+            raw,
+            size: AtomicU64::new(size),
+            resident: AtomicBool::new(true),
+            last_submission: AtomicU64::new(0),
+        });
+        Self::track(&mut self.inner.lock(), &pageable);
+        Some(pageable)
+    }
+
+    /// Tracks the heap of a placed resource ending at `end` bytes into it.
+    ///
+    /// # Safety
+    ///
+    /// `heap` must be a valid heap.
+    pub(super) unsafe fn register_heap(
+        &self,
+        heap: *mut d3d12_ty::ID3D12Heap,
+        end: u64,
+    ) -> Option<Arc<Pageable>> {
+        let key = heap as usize;
+        let mut inner = self.inner.lock();
+        if let Some(pageable) = inner.heaps.get(&key).and_then(Weak::upgrade) {
+            pageable.size.fetch_max(end, Ordering::Relaxed);
+            return Some(pageable);
+        }
+
+        // The heap is owned by the allocator, `cast` adds the reference we keep.
+        let heap = mem::ManuallyDrop::new(unsafe { d3d12::ComPtr::from_raw(heap) });
+        let raw = match unsafe { heap.cast::<d3d12_ty::ID3D12Pageable>() }.into_result() {
+            Ok(raw) => raw,
+            Err(err) => {
+                log::error!("Unable to cast heap to ID3D12Pageable: {}", err);
+                return None;
+            }
+        };
+        let pageable = Arc::new(Pageable {
+            raw,
+            size: AtomicU64::new(end),
+            resident: AtomicBool::new(true),
+            last_submission: AtomicU64::new(0),
+        });
+        inner.heaps.insert(key, Arc::downgrade(&pageable));
+        Self::track(&mut inner, &pageable);
+        Some(pageable)
+    }
+
+    /// Evicts the least recently used pageables that aren't used by work in
+    /// flight, until at least `bytes` have been evicted.
+    ///
+    /// Returns `false` if there was nothing to evict.
+    fn evict(&self, inner: &mut Inner, bytes: u64) -> bool {
+        let completed = self.fence.get_value();
+        let mut candidates = inner
+            .pageables
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|p| {
+                p.resident.load(Ordering::Relaxed)
+                    && p.last_submission.load(Ordering::Relaxed) <= completed
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|p| p.last_submission.load(Ordering::Relaxed));
+
+        let mut evicted_bytes = 0;
+        let mut evicted = Vec::new();
+        for pageable in candidates.iter() {
+            if evicted_bytes >= bytes {
+                break;
+            }
+            pageable.resident.store(false, Ordering::Relaxed);
+            evicted_bytes += pageable.size.load(Ordering::Relaxed);
+            evicted.push(pageable.raw.as_mut_ptr());
+        }
+        if evicted.is_empty() {
+            return false;
+        }
+
+        log::debug!(
+            "Evicting {} pageables of {} bytes",
+            evicted.len(),
+            evicted_bytes
+        );
+        let hr = unsafe {
+            profiling::scope!("ID3D12Device::Evict");
+            self.device.Evict(evicted.len() as u32, evicted.as_ptr())
+        };
+        if let Err(err) = hr.into_result() {
+            log::error!("Evict failed: {}", err);
+        }
+        true
+    }
+
+    /// Evicts idle pageables to make room for an allocation of `bytes`.
+    ///
+    /// Returns `false` if there was nothing to evict.
+    pub(super) fn evict_idle(&self, bytes: u64) -> bool {
+        self.evict(&mut self.inner.lock(), bytes)
+    }
+
+    /// Makes the pageables of a submission resident and marks them as used by it.
+    pub(super) unsafe fn begin_submission<'a>(
+        &self,
+        sets: impl Iterator<Item = &'a ResidencySet>,
+    ) -> Result<(), crate::DeviceError> {
+        let mut inner = self.inner.lock();
+        let index = inner.submission_index + 1;
+
+        let mut evicted = Vec::new();
+        let mut evicted_bytes = 0;
+        for set in sets {
+            for pageable in set.pageables.values() {
+                pageable.last_submission.store(index, Ordering::Relaxed);
+                if !pageable.resident.swap(true, Ordering::Relaxed) {
+                    evicted_bytes += pageable.size.load(Ordering::Relaxed);
+                    evicted.push(pageable);
+                }
+            }
+        }
+        inner.submission_index = index;
+        if evicted.is_empty() {
+            return Ok(());
+        }
+
+        let raw = evicted
+            .iter()
+            .map(|p| p.raw.as_mut_ptr())
+            .collect::<Vec<_>>();
+        let make_resident = || unsafe {
+            profiling::scope!("ID3D12Device::MakeResident");
+            self.device.MakeResident(raw.len() as u32, raw.as_ptr())
+        };
+        let mut hr = make_resident();
+        if hr == winerror::E_OUTOFMEMORY && self.evict(&mut inner, evicted_bytes) {
+            hr = make_resident();
+        }
+        hr.into_device_result("MakeResident").map_err(|err| {
+            for pageable in evicted {
+                pageable.resident.store(false, Ordering::Relaxed);
+            }
+            err
+        })
+    }
+
+    /// Signals the completion of the last submission, and trims resident
+    /// memory if a budget change made usage exceed the budget.
+    pub(super) unsafe fn end_submission(
+        &self,
+        queue: &d3d12::CommandQueue,
+    ) -> Result<(), crate::DeviceError> {
+        let mut inner = self.inner.lock();
+        queue
+            .signal(&self.fence, inner.submission_index)
+            .into_device_result("Signal residency fence")?;
+
+        if self.budget_event.wait(0) == winbase::WAIT_OBJECT_0 {
+            inner.over_budget = true;
+        }
+        if !inner.over_budget {
+            return Ok(());
+        }
+
+        let mut info = unsafe { mem::zeroed::<dxgi1_4::DXGI_QUERY_VIDEO_MEMORY_INFO>() };
+        let hr = unsafe {
+            self.adapter.QueryVideoMemoryInfo(
+                0,
+                dxgi1_4::DXGI_MEMORY_SEGMENT_GROUP_LOCAL,
+                &mut info,
+            )
+        };
+        if let Err(err) = hr.into_result() {
+            log::error!("QueryVideoMemoryInfo failed: {}", err);
+            inner.over_budget = false;
+            return Ok(());
+        }
+        if info.CurrentUsage <= info.Budget {
+            inner.over_budget = false;
+            return Ok(());
+        }
+
+        log::info!(
+            "Video memory usage of {} bytes exceeds the budget of {} bytes",
+            info.CurrentUsage,
+            info.Budget
+        );
+        // Usage is queried again after the next submission, which may be
+        // able to evict what is still in flight now.
+        self.evict(&mut inner, info.CurrentUsage - info.Budget);
+        Ok(())
+    }
+}
+
+impl Drop for Residency {
+    fn drop(&mut self) {
+        unsafe {
+            self.adapter
+                .UnregisterVideoMemoryBudgetChangeNotification(self.budget_cookie)
+        };
+    }
+}
+
+/// Estimates the size of the memory backing a texture.
+pub(super) fn texture_size(desc: &crate::TextureDescriptor) -> u64 {
+    let (block_width, block_height) = desc.format.block_dimensions();
+    // Combined depth-stencil formats have no single block size.
+    let block_size = desc.format.block_copy_size(None).unwrap_or(8) as u64;
+    let layers = match desc.dimension {
+        wgt::TextureDimension::D3 => 1,
+        _ => desc.size.depth_or_array_layers as u64,
+    };
+    (0..desc.mip_level_count)
+        .map(|level| {
+            let size = desc
+                .size
+                .mip_level_size(level, desc.dimension)
+                .physical_size(desc.format);
+            let depth = match desc.dimension {
+                wgt::TextureDimension::D3 => size.depth_or_array_layers as u64,
+                _ => 1,
+            };
+            (size.width / block_width) as u64
+                * (size.height / block_height) as u64
+                * depth
+                * block_size
+        })
+        .sum::<u64>()
+        * layers
+        * desc.sample_count as u64
+}
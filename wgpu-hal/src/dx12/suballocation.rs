@@ -44,6 +44,14 @@ mod placed {
         pub(crate) allocation: gpu_allocator::d3d12::Allocation,
     }
 
+    impl AllocationWrapper {
+        /// Returns the heap of the allocation, and where the allocation ends in it.
+        pub(crate) fn heap_range(&self) -> Option<(*mut d3d12_ty::ID3D12Heap, u64)> {
+            let heap = unsafe { self.allocation.heap() }.as_winapi() as *mut _;
+            Some((heap, self.allocation.offset() + self.allocation.size()))
+        }
+    }
+
     pub(crate) fn create_allocator_wrapper(
         raw: &d3d12::Device,
     ) -> Result<Option<Mutex<GpuAllocatorWrapper>>, crate::DeviceError> {
@@ -251,6 +259,13 @@ mod committed {
     #[derive(Debug)]
     pub(crate) struct AllocationWrapper {}
 
+    impl AllocationWrapper {
+        #[allow(unused)]
+        pub(crate) fn heap_range(&self) -> Option<(*mut d3d12_ty::ID3D12Heap, u64)> {
+            None
+        }
+    }
+
     #[allow(unused)]
     pub(crate) fn create_allocator_wrapper(
         _raw: &d3d12::Device,
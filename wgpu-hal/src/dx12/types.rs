@@ -2,6 +2,7 @@
 #![allow(non_snake_case)]
 
 // use here so that the recursive RIDL macro can find the crate
+use winapi::um::d3d12::{ID3D12GraphicsCommandList, ID3D12GraphicsCommandListVtbl};
 use winapi::um::unknwnbase::{IUnknown, IUnknownVtbl};
 use winapi::RIDL;
 
@@ -41,3 +42,152 @@ winapi::STRUCT! {
         BarycentricsSupported: winapi::shared::minwindef::BOOL,
     }
 }
+
+winapi::ENUM! {
+    enum D3D12_RESOLVE_MODE {
+        D3D12_RESOLVE_MODE_DECOMPRESS = 0,
+        D3D12_RESOLVE_MODE_MIN = 1,
+        D3D12_RESOLVE_MODE_MAX = 2,
+        D3D12_RESOLVE_MODE_AVERAGE = 3,
+        // D3D12_RESOLVE_MODE_ENCODE_SAMPLER_FEEDBACK,
+        // D3D12_RESOLVE_MODE_DECODE_SAMPLER_FEEDBACK,
+    }
+}
+
+// Only `ResolveSubresourceRegion` is used, the pointers of the other methods are left untyped.
+RIDL! {#[uuid(0x553103fb, 0x1fe7, 0x4557, 0xbb, 0x38, 0x94, 0x6d, 0x7d, 0x0e, 0x7c, 0xa7)]
+interface ID3D12GraphicsCommandList1(ID3D12GraphicsCommandList1Vtbl):
+    ID3D12GraphicsCommandList(ID3D12GraphicsCommandListVtbl) {
+    fn AtomicCopyBufferUINT(
+        pDstBuffer: *mut winapi::um::d3d12::ID3D12Resource,
+        DstOffset: u64,
+        pSrcBuffer: *mut winapi::um::d3d12::ID3D12Resource,
+        SrcOffset: u64,
+        Dependencies: u32,
+        ppDependentResources: *const *mut winapi::um::d3d12::ID3D12Resource,
+        pDependentSubresourceRanges: *const winapi::ctypes::c_void,
+    ) -> (),
+    fn AtomicCopyBufferUINT64(
+        pDstBuffer: *mut winapi::um::d3d12::ID3D12Resource,
+        DstOffset: u64,
+        pSrcBuffer: *mut winapi::um::d3d12::ID3D12Resource,
+        SrcOffset: u64,
+        Dependencies: u32,
+        ppDependentResources: *const *mut winapi::um::d3d12::ID3D12Resource,
+        pDependentSubresourceRanges: *const winapi::ctypes::c_void,
+    ) -> (),
+    fn OMSetDepthBounds(
+        Min: f32,
+        Max: f32,
+    ) -> (),
+    fn SetSamplePositions(
+        NumSamplesPerPixel: u32,
+        NumPixels: u32,
+        pSamplePositions: *mut winapi::ctypes::c_void,
+    ) -> (),
+    fn ResolveSubresourceRegion(
+        pDstResource: *mut winapi::um::d3d12::ID3D12Resource,
+        DstSubresource: u32,
+        DstX: u32,
+        DstY: u32,
+        pSrcResource: *mut winapi::um::d3d12::ID3D12Resource,
+        SrcSubresource: u32,
+        pSrcRect: *mut winapi::um::d3d12::D3D12_RECT,
+        Format: winapi::shared::dxgiformat::DXGI_FORMAT,
+        ResolveMode: D3D12_RESOLVE_MODE,
+    ) -> (),
+    fn SetViewInstanceMask(
+        Mask: u32,
+    ) -> (),
+}}
+
+winapi::ENUM! {
+    enum D3D12_VARIABLE_SHADING_RATE_TIER {
+        D3D12_VARIABLE_SHADING_RATE_TIER_NOT_SUPPORTED = 0,
+        D3D12_VARIABLE_SHADING_RATE_TIER_1 = 1,
+        D3D12_VARIABLE_SHADING_RATE_TIER_2 = 2,
+    }
+}
+
+winapi::STRUCT! {
+    struct D3D12_FEATURE_DATA_D3D12_OPTIONS6 {
+        AdditionalShadingRatesSupported: winapi::shared::minwindef::BOOL,
+        PerPrimitiveShadingRateSupportedWithViewportIndexing: winapi::shared::minwindef::BOOL,
+        VariableShadingRateTier: D3D12_VARIABLE_SHADING_RATE_TIER,
+        ShadingRateImageTileSize: u32,
+        BackgroundProcessingSupported: winapi::shared::minwindef::BOOL,
+    }
+}
+
+winapi::ENUM! {
+    enum D3D12_SHADING_RATE_COMBINER {
+        D3D12_SHADING_RATE_COMBINER_PASSTHROUGH = 0,
+        D3D12_SHADING_RATE_COMBINER_OVERRIDE = 1,
+        // D3D12_SHADING_RATE_COMBINER_MIN,
+        // D3D12_SHADING_RATE_COMBINER_MAX,
+        // D3D12_SHADING_RATE_COMBINER_SUM,
+    }
+}
+
+pub const D3D12_RESOURCE_STATE_SHADING_RATE_SOURCE: winapi::um::d3d12::D3D12_RESOURCE_STATES =
+    0x1000000;
+
+// Only `RSSetShadingRate` and `RSSetShadingRateImage` are used, the pointers of the methods
+// of `ID3D12GraphicsCommandList2` to `ID3D12GraphicsCommandList4` are left untyped.
+RIDL! {#[uuid(0x55050859, 0x4024, 0x474c, 0x87, 0xf5, 0x64, 0x72, 0xea, 0xee, 0x44, 0xea)]
+interface ID3D12GraphicsCommandList5(ID3D12GraphicsCommandList5Vtbl):
+    ID3D12GraphicsCommandList1(ID3D12GraphicsCommandList1Vtbl) {
+    fn WriteBufferImmediate(
+        Count: u32,
+        pParams: *const winapi::ctypes::c_void,
+        pModes: *const winapi::ctypes::c_void,
+    ) -> (),
+    fn SetProtectedResourceSession(
+        pProtectedResourceSession: *mut winapi::ctypes::c_void,
+    ) -> (),
+    fn BeginRenderPass(
+        NumRenderTargets: u32,
+        pRenderTargets: *const winapi::ctypes::c_void,
+        pDepthStencil: *const winapi::ctypes::c_void,
+        Flags: u32,
+    ) -> (),
+    fn EndRenderPass() -> (),
+    fn InitializeMetaCommand(
+        pMetaCommand: *mut winapi::ctypes::c_void,
+        pInitializationParametersData: *const winapi::ctypes::c_void,
+        InitializationParametersDataSizeInBytes: usize,
+    ) -> (),
+    fn ExecuteMetaCommand(
+        pMetaCommand: *mut winapi::ctypes::c_void,
+        pExecutionParametersData: *const winapi::ctypes::c_void,
+        ExecutionParametersDataSizeInBytes: usize,
+    ) -> (),
+    fn BuildRaytracingAccelerationStructure(
+        pDesc: *const winapi::ctypes::c_void,
+        NumPostbuildInfoDescs: u32,
+        pPostbuildInfoDescs: *const winapi::ctypes::c_void,
+    ) -> (),
+    fn EmitRaytracingAccelerationStructurePostbuildInfo(
+        pDesc: *const winapi::ctypes::c_void,
+        NumSourceAccelerationStructures: u32,
+        pSourceAccelerationStructureData: *const u64,
+    ) -> (),
+    fn CopyRaytracingAccelerationStructure(
+        DestAccelerationStructureData: u64,
+        SourceAccelerationStructureData: u64,
+        Mode: u32,
+    ) -> (),
+    fn SetPipelineState1(
+        pStateObject: *mut winapi::ctypes::c_void,
+    ) -> (),
+    fn DispatchRays(
+        pDesc: *const winapi::ctypes::c_void,
+    ) -> (),
+    fn RSSetShadingRate(
+        baseShadingRate: u32,
+        combiners: *const D3D12_SHADING_RATE_COMBINER,
+    ) -> (),
+    fn RSSetShadingRateImage(
+        shadingRateImage: *mut winapi::um::d3d12::ID3D12Resource,
+    ) -> (),
+}}
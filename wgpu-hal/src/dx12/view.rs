@@ -1,3 +1,4 @@
+use super::conv;
 use crate::auxil;
 use std::mem;
 use winapi::um::d3d12 as d3d12_ty;
@@ -9,6 +10,7 @@ pub(super) struct ViewDescriptor {
     pub aspects: crate::FormatAspects,
     pub rtv_dsv_format: d3d12::Format,
     srv_uav_format: Option<d3d12::Format>,
+    component_mapping: u32,
     multisampled: bool,
     array_layer_base: u32,
     array_layer_count: u32,
@@ -25,6 +27,7 @@ impl crate::TextureViewDescriptor<'_> {
             aspects,
             rtv_dsv_format: auxil::dxgi::conv::map_texture_format(self.format),
             srv_uav_format: auxil::dxgi::conv::map_texture_format_for_srv_uav(self.format, aspects),
+            component_mapping: conv::map_component_mapping(self.swizzle),
             multisampled: texture.sample_count > 1,
             mip_level_base: self.range.base_mip_level,
             mip_level_count: self.range.mip_level_count.unwrap_or(!0),
@@ -48,7 +51,7 @@ impl ViewDescriptor {
         let mut desc = d3d12_ty::D3D12_SHADER_RESOURCE_VIEW_DESC {
             Format: self.srv_uav_format?,
             ViewDimension: 0,
-            Shader4ComponentMapping: D3D12_DEFAULT_SHADER_4_COMPONENT_MAPPING,
+            Shader4ComponentMapping: self.component_mapping,
             u: unsafe { mem::zeroed() },
         };
 
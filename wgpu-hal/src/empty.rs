@@ -37,6 +37,9 @@ impl crate::Api for Api {
     type ShaderModule = Resource;
     type RenderPipeline = Resource;
     type ComputePipeline = Resource;
+    type PipelineCache = Resource;
+    type TilePool = Resource;
+    type MemoryHeap = Resource;
 }
 
 impl crate::Instance<Api> for Context {
@@ -81,6 +84,9 @@ impl crate::Adapter<Api> for Context {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        _compute_only: bool,
+        _queue_priority: wgt::QueuePriority,
+        _additional_queues: u32,
     ) -> DeviceResult<crate::OpenDevice<Api>> {
         Err(crate::DeviceError::Lost)
     }
@@ -98,6 +104,9 @@ impl crate::Adapter<Api> for Context {
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
         wgt::PresentationTimestamp::INVALID_TIMESTAMP
     }
+    fn feature_requirement(&self, _feature: wgt::Features) -> Option<&'static str> {
+        None
+    }
 }
 
 impl crate::Queue<Api> for Context {
@@ -120,10 +129,31 @@ impl crate::Queue<Api> for Context {
     unsafe fn get_timestamp_period(&self) -> f32 {
         1.0
     }
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        None
+    }
+    unsafe fn bind_texture_tiles(
+        &self,
+        texture: &Resource,
+        bindings: &[crate::TextureTileBinding<Api>],
+    ) -> DeviceResult<()> {
+        Ok(())
+    }
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Resource,
+        value: crate::FenceValue,
+    ) -> DeviceResult<()> {
+        Ok(())
+    }
+    unsafe fn signal_fence(&self, fence: &Resource, value: crate::FenceValue) -> DeviceResult<()> {
+        Ok(())
+    }
 }
 
 impl crate::Device<Api> for Context {
     unsafe fn exit(self, queue: Context) {}
+    unsafe fn destroy_queue(&self, queue: Context) {}
     unsafe fn create_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<Resource> {
         Ok(Resource)
     }
@@ -140,6 +170,9 @@ impl crate::Device<Api> for Context {
     }
     unsafe fn flush_mapped_ranges<I>(&self, buffer: &Resource, ranges: I) {}
     unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &Resource, ranges: I) {}
+    unsafe fn get_buffer_device_address(&self, buffer: &Resource) -> wgt::BufferAddress {
+        Default::default()
+    }
 
     unsafe fn create_texture(&self, desc: &crate::TextureDescriptor) -> DeviceResult<Resource> {
         Ok(Resource)
@@ -202,6 +235,12 @@ impl crate::Device<Api> for Context {
     ) -> Result<Resource, crate::PipelineError> {
         Ok(Resource)
     }
+    unsafe fn create_mesh_pipeline(
+        &self,
+        desc: &crate::MeshPipelineDescriptor<Api>,
+    ) -> Result<Resource, crate::PipelineError> {
+        Ok(Resource)
+    }
     unsafe fn destroy_render_pipeline(&self, pipeline: Resource) {}
     unsafe fn create_compute_pipeline(
         &self,
@@ -210,6 +249,13 @@ impl crate::Device<Api> for Context {
         Ok(Resource)
     }
     unsafe fn destroy_compute_pipeline(&self, pipeline: Resource) {}
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<Resource, crate::PipelineCacheError> {
+        Ok(Resource)
+    }
+    unsafe fn destroy_pipeline_cache(&self, cache: Resource) {}
 
     unsafe fn create_query_set(
         &self,
@@ -218,6 +264,100 @@ impl crate::Device<Api> for Context {
         Ok(Resource)
     }
     unsafe fn destroy_query_set(&self, set: Resource) {}
+    unsafe fn create_tile_pool(
+        &self,
+        desc: &wgt::TilePoolDescriptor<crate::Label>,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    unsafe fn destroy_tile_pool(&self, pool: Resource) {}
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &wgt::MemoryHeapDescriptor<crate::Label>,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    unsafe fn destroy_memory_heap(&self, heap: Resource) {}
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> DeviceResult<wgt::MemoryRequirements> {
+        Ok(wgt::MemoryRequirements {
+            size: desc.size,
+            alignment: 1,
+        })
+    }
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<wgt::MemoryRequirements> {
+        Ok(wgt::MemoryRequirements {
+            size: 0,
+            alignment: 1,
+        })
+    }
+    unsafe fn create_placed_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        heap: &Resource,
+        offset: wgt::BufferAddress,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    unsafe fn create_placed_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        heap: &Resource,
+        offset: wgt::BufferAddress,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn import_external_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        source: wgt::ExternalImageSource,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    unsafe fn create_external_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn import_external_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn export_external_buffer(
+        &self,
+        buffer: &Resource,
+    ) -> DeviceResult<wgt::ExternalMemory> {
+        Err(crate::DeviceError::ResourceCreationFailed)
+    }
+    unsafe fn create_external_fence(&self) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn import_external_fence(
+        &self,
+        handle: wgt::ExternalFenceHandle,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn export_external_fence(
+        &self,
+        fence: &Resource,
+    ) -> DeviceResult<wgt::ExternalFenceHandle> {
+        Err(crate::DeviceError::ResourceCreationFailed)
+    }
     unsafe fn create_fence(&self) -> DeviceResult<Resource> {
         Ok(Resource)
     }
@@ -281,6 +421,8 @@ impl crate::CommandEncoder<Api> for Encoder {
     {
     }
 
+    unsafe fn aliasing_barrier(&mut self) {}
+
     unsafe fn clear_buffer(&mut self, buffer: &Resource, range: crate::MemoryRange) {}
 
     unsafe fn copy_buffer_to_buffer<T>(&mut self, src: &Resource, dst: &Resource, regions: T) {}
@@ -306,6 +448,16 @@ impl crate::CommandEncoder<Api> for Encoder {
     ) {
     }
 
+    unsafe fn resolve_texture<T>(
+        &mut self,
+        src: &Resource,
+        src_usage: crate::TextureUses,
+        dst: &Resource,
+        mode: wgt::ResolveMode,
+        regions: T,
+    ) {
+    }
+
     unsafe fn copy_buffer_to_texture<T>(&mut self, src: &Resource, dst: &Resource, regions: T) {}
 
     unsafe fn copy_texture_to_buffer<T>(
@@ -421,6 +573,43 @@ impl crate::CommandEncoder<Api> for Encoder {
         max_count: u32,
     ) {
     }
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) {
+    }
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        buffer: &Resource,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    ) {
+    }
+    unsafe fn execute_indirect(
+        &mut self,
+        pipeline: &Resource,
+        layout: &Resource,
+        arguments: &[wgt::IndirectArgument],
+        buffer: &Resource,
+        offset: wgt::BufferAddress,
+        count: Option<(&Resource, wgt::BufferAddress)>,
+        max_count: u32,
+    ) {
+    }
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &Resource,
+        offset: wgt::BufferAddress,
+    ) {
+    }
+    unsafe fn end_conditional_rendering(&mut self) {}
+
+    unsafe fn begin_vertex_capture(&mut self, buffers: &[crate::BufferBinding<Api>]) {}
+    unsafe fn end_vertex_capture(&mut self) {}
+
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate) {}
 
     // compute
 
@@ -217,6 +217,7 @@ impl super::Adapter {
         log::debug!("Vendor: {}", vendor);
         log::debug!("Renderer: {}", renderer);
         log::debug!("Version: {}", version);
+        let driver_info = format!("{vendor}\n{renderer}\n{version}");
 
         let full_ver = Self::parse_full_version(&version).ok();
         let es_ver = full_ver.map_or_else(|| Self::parse_version(&version).ok(), |_| None);
@@ -435,7 +436,8 @@ impl super::Adapter {
         let mut features = wgt::Features::empty()
             | wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
             | wgt::Features::CLEAR_TEXTURE
-            | wgt::Features::PUSH_CONSTANTS;
+            | wgt::Features::PUSH_CONSTANTS
+            | wgt::Features::VERTEX_CAPTURE;
         features.set(
             wgt::Features::ADDRESS_MODE_CLAMP_TO_BORDER | wgt::Features::ADDRESS_MODE_CLAMP_TO_ZERO,
             extensions.contains("GL_EXT_texture_border_clamp")
@@ -470,6 +472,13 @@ impl super::Adapter {
             supported((3, 1), (4, 2)) || extensions.contains("GL_ARB_shader_image_load_store"),
         );
         features.set(wgt::Features::SHADER_UNUSED_VERTEX_OUTPUT, true);
+        // Program binaries aren't exposed by WebGL.
+        #[cfg(native)]
+        features.set(
+            wgt::Features::PIPELINE_CACHE,
+            (supported((3, 0), (4, 1)) || extensions.contains("GL_ARB_get_program_binary"))
+                && unsafe { gl.get_parameter_i32(glow::NUM_PROGRAM_BINARY_FORMATS) } > 0,
+        );
         if extensions.contains("GL_ARB_timer_query") {
             features.set(wgt::Features::TIMESTAMP_QUERY, true);
             features.set(wgt::Features::TIMESTAMP_QUERY_INSIDE_PASSES, true);
@@ -753,6 +762,9 @@ impl super::Adapter {
             max_compute_workgroups_per_dimension,
             max_buffer_size: i32::MAX as u64,
             max_non_sampler_bindings: std::u32::MAX,
+            max_binding_array_elements_per_shader_stage: 0,
+            max_binding_array_sampler_elements_per_shader_stage: 0,
+            shading_rate_tile_size: 0,
         };
 
         let mut workarounds = super::Workarounds::empty();
@@ -786,6 +798,14 @@ impl super::Adapter {
         #[cfg_attr(target_arch = "wasm32", allow(dropping_references))]
         drop(gl);
 
+        let info = Self::make_info(vendor, renderer);
+        let memory_architecture = match info.device_type {
+            wgt::DeviceType::IntegratedGpu | wgt::DeviceType::Cpu => {
+                wgt::MemoryArchitecture::Unified
+            }
+            _ => wgt::MemoryArchitecture::Discrete,
+        };
+
         Some(crate::ExposedAdapter {
             adapter: super::Adapter {
                 shared: Arc::new(super::AdapterShared {
@@ -796,10 +816,11 @@ impl super::Adapter {
                     shading_language_version,
                     next_shader_id: Default::default(),
                     program_cache: Default::default(),
+                    driver_info,
                     es: es_ver.is_some(),
                 }),
             },
-            info: Self::make_info(vendor, renderer),
+            info,
             features,
             capabilities: crate::Capabilities {
                 limits,
@@ -811,7 +832,10 @@ impl super::Adapter {
                 alignments: crate::Alignments {
                     buffer_copy_offset: wgt::BufferSize::new(4).unwrap(),
                     buffer_copy_pitch: wgt::BufferSize::new(4).unwrap(),
+                    ray_tracing_scratch_buffer_alignment: 0,
                 },
+                max_additional_queues: 0,
+                memory_architecture,
             },
         })
     }
@@ -891,6 +915,9 @@ impl crate::Adapter<super::Api> for super::Adapter {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        _compute_only: bool,
+        _queue_priority: wgt::QueuePriority,
+        _additional_queues: u32,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let gl = &self.shared.context.lock();
         unsafe { gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1) };
@@ -934,6 +961,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 draw_buffer_count: AtomicU8::new(1),
                 current_index_buffer: Mutex::new(None),
             },
+            additional_queues: Vec::new(),
         })
     }
 
@@ -1068,7 +1096,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
             | Tf::Depth32FloatStencil8
             | Tf::Depth24Plus
             | Tf::Depth24PlusStencil8 => depth,
-            Tf::NV12 => empty,
+            Tf::NV12 | Tf::P010 => empty,
             Tf::Rgb9e5Ufloat => filterable,
             Tf::Bc1RgbaUnorm
             | Tf::Bc1RgbaUnormSrgb
@@ -1150,6 +1178,53 @@ impl crate::Adapter<super::Api> for super::Adapter {
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
         wgt::PresentationTimestamp::INVALID_TIMESTAMP
     }
+
+    fn feature_requirement(&self, feature: wgt::Features) -> Option<&'static str> {
+        use wgt::Features as F;
+        Some(match feature {
+            F::ADDRESS_MODE_CLAMP_TO_BORDER | F::ADDRESS_MODE_CLAMP_TO_ZERO => {
+                "the `GL_EXT_texture_border_clamp` or `GL_ARB_texture_border_clamp` extension"
+            }
+            F::DEPTH_CLIP_CONTROL => "the `GL_EXT_depth_clamp` or `GL_ARB_depth_clamp` extension",
+            F::VERTEX_WRITABLE_STORAGE => "storage buffers and textures in vertex shaders",
+            F::MULTIVIEW => "the `OVR_multiview2` extension",
+            F::DUAL_SOURCE_BLENDING => {
+                "the `GL_EXT_blend_func_extended` or `GL_ARB_blend_func_extended` extension"
+            }
+            F::SHADER_PRIMITIVE_INDEX => {
+                "GL or GLES 3.2, or the `OES_geometry_shader` or `GL_ARB_geometry_shader4` extension"
+            }
+            F::SHADER_EARLY_DEPTH_TEST => {
+                "GLES 3.1, GL 4.2, or the `GL_ARB_shader_image_load_store` extension"
+            }
+            F::TIMESTAMP_QUERY | F::TIMESTAMP_QUERY_INSIDE_PASSES => {
+                "the `GL_ARB_timer_query` extension"
+            }
+            F::TEXTURE_COMPRESSION_BC => "the S3TC, RGTC and BPTC texture compression extensions",
+            F::TEXTURE_COMPRESSION_ETC2 => {
+                "GLES, or the `WEBGL_compressed_texture_etc` extension on the web"
+            }
+            F::TEXTURE_COMPRESSION_ASTC => {
+                "the `GL_KHR_texture_compression_astc_ldr` or \
+                 `GL_OES_texture_compression_astc` extension, or the \
+                 `WEBGL_compressed_texture_astc` extension on the web"
+            }
+            F::TEXTURE_COMPRESSION_ASTC_HDR => {
+                "the `GL_KHR_texture_compression_astc_hdr` or \
+                 `GL_OES_texture_compression_astc` extension, or the \
+                 `WEBGL_compressed_texture_astc` extension with the HDR profile on the web"
+            }
+            F::FLOAT32_FILTERABLE => {
+                "the `GL_ARB_color_buffer_float`, `GL_EXT_color_buffer_float` or \
+                 `OES_texture_float_linear` extension"
+            }
+            F::POLYGON_MODE_LINE | F::POLYGON_MODE_POINT => "desktop GL",
+            F::INDIRECT_FIRST_INSTANCE => {
+                "GL 4.2 and the `GL_ARB_shader_draw_parameters` extension"
+            }
+            _ => return None,
+        })
+    }
 }
 
 impl super::AdapterShared {
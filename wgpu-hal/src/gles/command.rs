@@ -317,6 +317,10 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         }
     }
 
+    unsafe fn aliasing_barrier(&mut self) {
+        unreachable!()
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
         self.cmd_buffer.commands.push(C::ClearBuffer {
             dst: buffer.clone(),
@@ -397,6 +401,21 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         }
     }
 
+    unsafe fn resolve_texture<T>(
+        &mut self,
+        _src: &super::Texture,
+        _src_usage: crate::TextureUses,
+        _dst: &super::Texture,
+        _mode: wgt::ResolveMode,
+        _regions: T,
+    ) where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        // `glBlitFramebuffer` can only resolve between identical rectangles,
+        // `MULTISAMPLE_RESOLVE_REGION` is never reported.
+        unimplemented!()
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -1122,6 +1141,73 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     ) {
         unreachable!()
     }
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        _group_count_x: u32,
+        _group_count_y: u32,
+        _group_count_z: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _draw_count: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn execute_indirect(
+        &mut self,
+        _pipeline: &super::RenderPipeline,
+        _layout: &super::PipelineLayout,
+        _arguments: &[wgt::IndirectArgument],
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _count: Option<(&super::Buffer, wgt::BufferAddress)>,
+        _max_count: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+    ) {
+        unreachable!()
+    }
+    unsafe fn end_conditional_rendering(&mut self) {
+        unreachable!()
+    }
+
+    unsafe fn begin_vertex_capture(&mut self, buffers: &[crate::BufferBinding<super::Api>]) {
+        for (slot, binding) in buffers.iter().enumerate() {
+            let size = match binding.size {
+                Some(size) => size.get(),
+                None => binding.buffer.size - binding.offset,
+            };
+            self.cmd_buffer.commands.push(C::BindBuffer {
+                target: glow::TRANSFORM_FEEDBACK_BUFFER,
+                slot: slot as u32,
+                buffer: binding.buffer.raw.unwrap(),
+                offset: binding.offset as i32,
+                size: size as i32,
+            });
+        }
+        // Only list topologies can be captured, whose modes are the same as
+        // the primitive modes of transform feedback.
+        self.cmd_buffer
+            .commands
+            .push(C::BeginTransformFeedback(self.state.topology));
+    }
+
+    unsafe fn end_vertex_capture(&mut self) {
+        self.cmd_buffer.commands.push(C::EndTransformFeedback);
+    }
+
+    unsafe fn set_shading_rate(&mut self, _rate: wgt::ShadingRate) {
+        unreachable!()
+    }
 
     // compute
 
@@ -99,7 +99,12 @@ impl CompilationContext<'_> {
                     assert_eq!(location.index, 0);
                     unsafe { gl.bind_frag_data_location(program, location.location, &name) }
                 }
-                naga::ShaderStage::Compute => {}
+                naga::ShaderStage::Compute
+                | naga::ShaderStage::Task
+                | naga::ShaderStage::Mesh
+                | naga::ShaderStage::Geometry
+                | naga::ShaderStage::TessellationControl
+                | naga::ShaderStage::TessellationEvaluation => {}
             }
         }
 
@@ -107,6 +112,114 @@ impl CompilationContext<'_> {
     }
 }
 
+/// Hashes the GLSL sources of a program, to find its binary in a [`super::PipelineCache`].
+fn program_binary_key(
+    sources: &[(naga::ShaderStage, String, Option<&str>)],
+    transform_feedback: Option<&super::TransformFeedbackDesc>,
+) -> u64 {
+    use std::hash::{Hash as _, Hasher as _};
+
+    // `DefaultHasher::new` always uses the same keys, so the hash is stable
+    // between runs of the same build.
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for (naga_stage, source, _) in sources {
+        naga_stage.hash(&mut hasher);
+        source.hash(&mut hasher);
+    }
+    // The captured varyings are part of the linked program.
+    transform_feedback.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Maps the vertex capture layout to the varyings captured by transform
+/// feedback.
+///
+/// Without `gl_SkipComponents`, which isn't in GLES, the outputs must either
+/// be tightly packed into a single buffer, or each be alone in its buffer.
+fn map_vertex_capture(
+    vertex_capture: &[crate::VertexCaptureBufferLayout],
+) -> Result<Option<super::TransformFeedbackDesc>, crate::PipelineError> {
+    let varying_name = |location| format!("_vs2fs_location{location}");
+    let unsupported = || {
+        crate::PipelineError::Linkage(
+            wgt::ShaderStages::VERTEX,
+            "vertex capture layouts with gaps or multiple outputs per buffer are only \
+             supported with a single, tightly packed buffer"
+                .to_string(),
+        )
+    };
+    match *vertex_capture {
+        [] => Ok(None),
+        [ref vcb] => {
+            let mut attributes = vcb.attributes.to_vec();
+            attributes.sort_by_key(|attribute| attribute.offset);
+            let mut offset = 0;
+            for attribute in attributes.iter() {
+                if attribute.offset != offset {
+                    return Err(unsupported());
+                }
+                offset += attribute.format.size();
+            }
+            if offset != vcb.array_stride {
+                return Err(unsupported());
+            }
+            Ok(Some(super::TransformFeedbackDesc {
+                varyings: attributes
+                    .iter()
+                    .map(|attribute| varying_name(attribute.shader_location))
+                    .collect(),
+                buffer_mode: glow::INTERLEAVED_ATTRIBS,
+            }))
+        }
+        _ => {
+            let mut varyings = Vec::with_capacity(vertex_capture.len());
+            for vcb in vertex_capture {
+                match *vcb.attributes {
+                    [ref attribute]
+                        if attribute.offset == 0 && attribute.format.size() == vcb.array_stride =>
+                    {
+                        varyings.push(varying_name(attribute.shader_location));
+                    }
+                    _ => return Err(unsupported()),
+                }
+            }
+            Ok(Some(super::TransformFeedbackDesc {
+                varyings,
+                buffer_mode: glow::SEPARATE_ATTRIBS,
+            }))
+        }
+    }
+}
+
+impl super::PipelineCache {
+    /// Links `program` from the binary stored for `key`, if any.
+    ///
+    /// Returns `false` if there is no binary or the driver rejected it, in
+    /// which case the program must be linked from its sources.
+    unsafe fn load(&self, gl: &glow::Context, program: glow::Program, key: u64) -> bool {
+        let binaries = self.binaries.lock();
+        let Some(binary) = binaries.get(&key) else {
+            return false;
+        };
+        unsafe { gl.program_binary(program, binary) };
+        let linked_ok = unsafe { gl.get_program_link_status(program) };
+        if !linked_ok {
+            log::info!("Program binary was rejected by the driver, compiling the shaders instead");
+        }
+        linked_ok
+    }
+
+    /// Stores the binary of the linked `program` for `key`.
+    unsafe fn store(&self, gl: &glow::Context, program: glow::Program, key: u64) {
+        match unsafe { gl.get_program_binary(program) } {
+            Some(binary) => {
+                self.binaries.lock().insert(key, binary);
+            }
+            None => log::warn!("Unable to get the binary of program {:?}", program),
+        }
+    }
+}
+
 impl super::Device {
     /// # Safety
     ///
@@ -173,6 +286,11 @@ impl super::Device {
             naga::ShaderStage::Vertex => glow::VERTEX_SHADER,
             naga::ShaderStage::Fragment => glow::FRAGMENT_SHADER,
             naga::ShaderStage::Compute => glow::COMPUTE_SHADER,
+            naga::ShaderStage::Task
+            | naga::ShaderStage::Mesh
+            | naga::ShaderStage::Geometry
+            | naga::ShaderStage::TessellationControl
+            | naga::ShaderStage::TessellationEvaluation => unreachable!(),
         };
 
         let raw = unsafe { gl.create_shader(target) }.unwrap();
@@ -206,13 +324,14 @@ impl super::Device {
         }
     }
 
-    fn create_shader(
+    /// Translates the shader of `stage` to GLSL, binding its interface on `program`.
+    fn create_shader_source(
         gl: &glow::Context,
         naga_stage: naga::ShaderStage,
         stage: &crate::ProgrammableStage<super::Api>,
         context: CompilationContext,
         program: glow::Program,
-    ) -> Result<glow::Shader, crate::PipelineError> {
+    ) -> Result<String, crate::PipelineError> {
         use naga::back::glsl;
         let pipeline_options = glsl::PipelineOptions {
             shader_stage: naga_stage,
@@ -276,9 +395,10 @@ impl super::Device {
             program,
         );
 
-        unsafe { Self::compile_shader(gl, &output, naga_stage, stage.module.label.as_deref()) }
+        Ok(output)
     }
 
+    #[allow(clippy::too_many_arguments)]
     unsafe fn create_pipeline<'a>(
         &self,
         gl: &glow::Context,
@@ -286,6 +406,8 @@ impl super::Device {
         layout: &super::PipelineLayout,
         #[cfg_attr(target_arch = "wasm32", allow(unused))] label: Option<&str>,
         multiview: Option<std::num::NonZeroU32>,
+        cache: Option<&super::PipelineCache>,
+        transform_feedback: Option<super::TransformFeedbackDesc>,
     ) -> Result<Arc<super::PipelineInner>, crate::PipelineError> {
         let mut program_stages = ArrayVec::new();
         let mut group_to_binding_to_slot = Vec::with_capacity(layout.group_infos.len());
@@ -310,6 +432,7 @@ impl super::Device {
             .entry(super::ProgramCacheKey {
                 stages: program_stages,
                 group_to_binding_to_slot: group_to_binding_to_slot.into_boxed_slice(),
+                transform_feedback: transform_feedback.clone(),
             })
             .or_insert_with(|| unsafe {
                 Self::create_program(
@@ -318,6 +441,8 @@ impl super::Device {
                     layout,
                     label,
                     multiview,
+                    cache,
+                    transform_feedback.as_ref(),
                     self.shared.shading_language_version,
                     self.shared.private_caps,
                 )
@@ -328,12 +453,15 @@ impl super::Device {
         Ok(program)
     }
 
+    #[allow(clippy::too_many_arguments)]
     unsafe fn create_program<'a>(
         gl: &glow::Context,
         shaders: ArrayVec<ShaderStage<'a>, { crate::MAX_CONCURRENT_SHADER_STAGES }>,
         layout: &super::PipelineLayout,
         #[cfg_attr(target_arch = "wasm32", allow(unused))] label: Option<&str>,
         multiview: Option<std::num::NonZeroU32>,
+        cache: Option<&super::PipelineCache>,
+        transform_feedback: Option<&super::TransformFeedbackDesc>,
         glsl_version: naga::back::glsl::Version,
         private_caps: PrivateCapabilities,
     ) -> Result<Arc<super::PipelineInner>, crate::PipelineError> {
@@ -354,7 +482,7 @@ impl super::Device {
         let mut push_constant_items = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
         let mut sampler_map = [None; super::MAX_TEXTURE_SLOTS];
         let mut has_stages = wgt::ShaderStages::empty();
-        let mut shaders_to_delete = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+        let mut sources = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
 
         for &(naga_stage, stage) in &shaders {
             has_stages |= map_naga_stage(naga_stage);
@@ -370,32 +498,47 @@ impl super::Device {
                 multiview,
             };
 
-            let shader = Self::create_shader(gl, naga_stage, stage, context, program)?;
-            shaders_to_delete.push(shader);
+            let source = Self::create_shader_source(gl, naga_stage, stage, context, program)?;
+            sources.push((naga_stage, source, stage.module.label.as_deref()));
         }
 
         // Create empty fragment shader if only vertex shader is present
         if has_stages == wgt::ShaderStages::VERTEX {
             let shader_src = format!("#version {glsl_version}\n void main(void) {{}}",);
             log::info!("Only vertex shader is present. Creating an empty fragment shader",);
-            let shader = unsafe {
-                Self::compile_shader(
-                    gl,
-                    &shader_src,
-                    naga::ShaderStage::Fragment,
-                    Some("(wgpu internal) dummy fragment shader"),
-                )
-            }?;
-            shaders_to_delete.push(shader);
+            sources.push((
+                naga::ShaderStage::Fragment,
+                shader_src,
+                Some("(wgpu internal) dummy fragment shader"),
+            ));
         }
 
-        for &shader in shaders_to_delete.iter() {
-            unsafe { gl.attach_shader(program, shader) };
-        }
-        unsafe { gl.link_program(program) };
+        let cache = cache.map(|cache| (cache, program_binary_key(&sources, transform_feedback)));
+        let loaded = match cache {
+            Some((cache, key)) => unsafe { cache.load(gl, program, key) },
+            None => false,
+        };
+
+        if !loaded {
+            let mut shaders_to_delete =
+                ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+            for &(naga_stage, ref source, label) in &sources {
+                let shader = unsafe { Self::compile_shader(gl, source, naga_stage, label) }?;
+                shaders_to_delete.push(shader);
+            }
+
+            for &shader in shaders_to_delete.iter() {
+                unsafe { gl.attach_shader(program, shader) };
+            }
+            if let Some(desc) = transform_feedback {
+                let varyings: Vec<&str> = desc.varyings.iter().map(String::as_str).collect();
+                unsafe { gl.transform_feedback_varyings(program, &varyings, desc.buffer_mode) };
+            }
+            unsafe { gl.link_program(program) };
 
-        for shader in shaders_to_delete {
-            unsafe { gl.delete_shader(shader) };
+            for shader in shaders_to_delete {
+                unsafe { gl.delete_shader(shader) };
+            }
         }
 
         log::debug!("\tLinked program {:?}", program);
@@ -409,6 +552,10 @@ impl super::Device {
             log::warn!("\tLink: {}", msg);
         }
 
+        if let (false, Some((cache, key))) = (loaded, cache) {
+            unsafe { cache.store(gl, program, key) };
+        }
+
         if !private_caps.contains(super::PrivateCapabilities::SHADER_BINDING_LAYOUT) {
             // This remapping is only needed if we aren't able to put the binding layout
             // in the shader. We can't remap storage buffers this way.
@@ -492,6 +639,13 @@ impl crate::Device<super::Api> for super::Device {
         unsafe { gl.delete_buffer(queue.zero_buffer) };
     }
 
+    unsafe fn destroy_queue(&self, queue: super::Queue) {
+        let gl = &self.shared.context.lock();
+        unsafe { gl.delete_framebuffer(queue.draw_fbo) };
+        unsafe { gl.delete_framebuffer(queue.copy_fbo) };
+        unsafe { gl.delete_buffer(queue.zero_buffer) };
+    }
+
     unsafe fn create_buffer(
         &self,
         desc: &crate::BufferDescriptor,
@@ -692,8 +846,28 @@ impl crate::Device<super::Api> for super::Device {
             }
         }
     }
-    unsafe fn invalidate_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {
-        //TODO: do we need to do anything?
+    unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &super::Buffer, ranges: I)
+    where
+        I: Iterator<Item = crate::MemoryRange>,
+    {
+        // Emulated mappings are only read back when mapping the buffer, so
+        // refresh the ranges that the GPU may have written since then.
+        if let (Some(raw), Some(map_read_allocation)) = (buffer.raw, buffer.data.as_ref()) {
+            let gl = &self.shared.context.lock();
+            let mut guard = map_read_allocation.lock().unwrap();
+            unsafe { gl.bind_buffer(buffer.target, Some(raw)) };
+            for range in ranges {
+                let slice = &mut guard.as_mut_slice()[range.start as usize..range.end as usize];
+                unsafe {
+                    self.shared
+                        .get_buffer_sub_data(gl, buffer.target, range.start as i32, slice)
+                };
+            }
+            unsafe { gl.bind_buffer(buffer.target, None) };
+        }
+    }
+    unsafe fn get_buffer_device_address(&self, _buffer: &super::Buffer) -> wgt::BufferAddress {
+        unimplemented!()
     }
 
     unsafe fn create_texture(
@@ -1267,8 +1441,18 @@ impl crate::Device<super::Api> for super::Device {
         if let Some(ref fs) = desc.fragment_stage {
             shaders.push((naga::ShaderStage::Fragment, fs));
         }
-        let inner =
-            unsafe { self.create_pipeline(gl, shaders, desc.layout, desc.label, desc.multiview) }?;
+        let transform_feedback = map_vertex_capture(desc.vertex_capture)?;
+        let inner = unsafe {
+            self.create_pipeline(
+                gl,
+                shaders,
+                desc.layout,
+                desc.label,
+                desc.multiview,
+                desc.cache,
+                transform_feedback,
+            )
+        }?;
 
         let (vertex_buffers, vertex_attributes) = {
             let mut buffers = Vec::new();
@@ -1326,6 +1510,12 @@ impl crate::Device<super::Api> for super::Device {
             alpha_to_coverage_enabled: desc.multisample.alpha_to_coverage_enabled,
         })
     }
+    unsafe fn create_mesh_pipeline(
+        &self,
+        _desc: &crate::MeshPipelineDescriptor<super::Api>,
+    ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        unreachable!()
+    }
     unsafe fn destroy_render_pipeline(&self, pipeline: super::RenderPipeline) {
         let mut program_cache = self.shared.program_cache.lock();
         // If the pipeline only has 2 strong references remaining, they're `pipeline` and `program_cache`
@@ -1349,7 +1539,9 @@ impl crate::Device<super::Api> for super::Device {
         let gl = &self.shared.context.lock();
         let mut shaders = ArrayVec::new();
         shaders.push((naga::ShaderStage::Compute, &desc.stage));
-        let inner = unsafe { self.create_pipeline(gl, shaders, desc.layout, desc.label, None) }?;
+        let inner = unsafe {
+            self.create_pipeline(gl, shaders, desc.layout, desc.label, None, desc.cache, None)
+        }?;
 
         Ok(super::ComputePipeline { inner })
     }
@@ -1368,6 +1560,22 @@ impl crate::Device<super::Api> for super::Device {
             unsafe { gl.delete_program(pipeline.inner.program) };
         }
     }
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<super::PipelineCache, crate::PipelineCacheError> {
+        match desc.data {
+            Some(data) => super::PipelineCache::from_data(data, &self.shared.driver_info)
+                .ok_or(crate::PipelineCacheError::Validation),
+            None => Ok(super::PipelineCache {
+                binaries: Default::default(),
+            }),
+        }
+    }
+    unsafe fn destroy_pipeline_cache(&self, _cache: super::PipelineCache) {}
+    unsafe fn pipeline_cache_get_data(&self, cache: &super::PipelineCache) -> Option<Vec<u8>> {
+        Some(cache.to_data(&self.shared.driver_info))
+    }
 
     #[cfg_attr(target_arch = "wasm32", allow(unused))]
     unsafe fn create_query_set(
@@ -1405,6 +1613,94 @@ impl crate::Device<super::Api> for super::Device {
             unsafe { gl.delete_query(query) };
         }
     }
+    unsafe fn create_tile_pool(
+        &self,
+        _desc: &wgt::TilePoolDescriptor<crate::Label>,
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+    unsafe fn destroy_tile_pool(&self, _pool: ()) {}
+    unsafe fn create_memory_heap(
+        &self,
+        _desc: &wgt::MemoryHeapDescriptor<crate::Label>,
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+    unsafe fn destroy_memory_heap(&self, _heap: ()) {}
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        _desc: &crate::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        unimplemented!()
+    }
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        unimplemented!()
+    }
+    unsafe fn create_placed_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+        _heap: &(),
+        _offset: wgt::BufferAddress,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        unimplemented!()
+    }
+    unsafe fn create_placed_texture(
+        &self,
+        _desc: &crate::TextureDescriptor,
+        _heap: &(),
+        _offset: wgt::BufferAddress,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        unimplemented!()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn import_external_texture(
+        &self,
+        _desc: &crate::TextureDescriptor,
+        _source: wgt::ExternalImageSource,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        unimplemented!()
+    }
+    unsafe fn create_external_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        unimplemented!()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn import_external_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+        _memory: wgt::ExternalMemory,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        unimplemented!()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn export_external_buffer(
+        &self,
+        _buffer: &super::Buffer,
+    ) -> Result<wgt::ExternalMemory, crate::DeviceError> {
+        unimplemented!()
+    }
+    unsafe fn create_external_fence(&self) -> Result<super::Fence, crate::DeviceError> {
+        unimplemented!()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn import_external_fence(
+        &self,
+        _handle: wgt::ExternalFenceHandle,
+    ) -> Result<super::Fence, crate::DeviceError> {
+        unimplemented!()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn export_external_fence(
+        &self,
+        _fence: &super::Fence,
+    ) -> Result<wgt::ExternalFenceHandle, crate::DeviceError> {
+        unimplemented!()
+    }
     unsafe fn create_fence(&self) -> Result<super::Fence, crate::DeviceError> {
         Ok(super::Fence {
             last_completed: 0,
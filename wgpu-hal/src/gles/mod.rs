@@ -79,6 +79,18 @@ we don't bother with that combination.
 - We bind instance buffers with offset emulation.
 - We _do not_ advertise support for `INDIRECT_FIRST_INSTANCE` and cpu-side pretend the `first_instance` is 0 on indirect calls.
 
+## Program binaries
+
+Compiling and linking GLSL is the most expensive part of creating a pipeline,
+so `PipelineCache` keeps the `glGetProgramBinary` blob of every program linked with it,
+keyed by a hash of the generated GLSL sources. A pipeline created with the cache
+first tries `glProgramBinary` with a matching blob, and only compiles the sources
+if there is none or the driver rejects it.
+
+Binaries are only valid for the driver that produced them, so the serialized
+cache data starts with the vendor, renderer and version strings of the context,
+and data with different strings is rejected.
+
 */
 
 ///cbindgen:ignore
@@ -161,6 +173,9 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type PipelineCache = PipelineCache;
+    type TilePool = ();
+    type MemoryHeap = ();
 }
 
 bitflags::bitflags! {
@@ -250,6 +265,8 @@ struct AdapterShared {
     shading_language_version: naga::back::glsl::Version,
     next_shader_id: AtomicU32,
     program_cache: Mutex<ProgramCache>,
+    /// Vendor, renderer and version strings, identifying the driver for program binaries.
+    driver_info: String,
     es: bool,
 }
 
@@ -557,10 +574,18 @@ struct ProgramStage {
     entry_point: String,
 }
 
+/// The vertex shader outputs captured with transform feedback.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct TransformFeedbackDesc {
+    varyings: Vec<String>,
+    buffer_mode: u32,
+}
+
 #[derive(PartialEq, Eq, Hash)]
 struct ProgramCacheKey {
     stages: ArrayVec<ProgramStage, 3>,
     group_to_binding_to_slot: Box<[Box<[u8]>]>,
+    transform_feedback: Option<TransformFeedbackDesc>,
 }
 
 type ProgramCache = FastHashMap<ProgramCacheKey, Result<Arc<PipelineInner>, crate::PipelineError>>;
@@ -593,6 +618,94 @@ unsafe impl Sync for ComputePipeline {}
 #[cfg(send_sync)]
 unsafe impl Send for ComputePipeline {}
 
+pub struct PipelineCache {
+    /// Program binaries, keyed by the hash of the shader sources they were linked from.
+    binaries: Mutex<FastHashMap<u64, glow::ProgramBinary>>,
+}
+
+impl fmt::Debug for PipelineCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineCache")
+            .field("binaries", &self.binaries.lock().len())
+            .finish()
+    }
+}
+
+const PIPELINE_CACHE_MAGIC: &[u8; 8] = b"WGPUGLPC";
+const PIPELINE_CACHE_VERSION: u32 = 1;
+
+impl PipelineCache {
+    /// Reads the binaries from `data`, as written by [`Self::to_data`].
+    ///
+    /// Returns `None` if the data is malformed or comes from another driver.
+    fn from_data(data: &[u8], driver_info: &str) -> Option<Self> {
+        struct Reader<'a>(&'a [u8]);
+
+        impl<'a> Reader<'a> {
+            fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+                if self.0.len() < len {
+                    return None;
+                }
+                let (bytes, rest) = self.0.split_at(len);
+                self.0 = rest;
+                Some(bytes)
+            }
+            fn u32(&mut self) -> Option<u32> {
+                Some(u32::from_le_bytes(self.bytes(4)?.try_into().ok()?))
+            }
+            fn u64(&mut self) -> Option<u64> {
+                Some(u64::from_le_bytes(self.bytes(8)?.try_into().ok()?))
+            }
+        }
+
+        let mut reader = Reader(data);
+        if reader.bytes(PIPELINE_CACHE_MAGIC.len())? != PIPELINE_CACHE_MAGIC
+            || reader.u32()? != PIPELINE_CACHE_VERSION
+        {
+            return None;
+        }
+        let info_len = reader.u32()? as usize;
+        if reader.bytes(info_len)? != driver_info.as_bytes() {
+            log::info!("Pipeline cache data was created by a different driver");
+            return None;
+        }
+
+        let count = reader.u32()?;
+        let mut binaries = FastHashMap::default();
+        for _ in 0..count {
+            let key = reader.u64()?;
+            let format = reader.u32()?;
+            let len = reader.u32()? as usize;
+            let buffer = reader.bytes(len)?.to_vec();
+            binaries.insert(key, glow::ProgramBinary { buffer, format });
+        }
+        if !reader.0.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            binaries: Mutex::new(binaries),
+        })
+    }
+
+    fn to_data(&self, driver_info: &str) -> Vec<u8> {
+        let binaries = self.binaries.lock();
+        let mut data = Vec::new();
+        data.extend_from_slice(PIPELINE_CACHE_MAGIC);
+        data.extend_from_slice(&PIPELINE_CACHE_VERSION.to_le_bytes());
+        data.extend_from_slice(&(driver_info.len() as u32).to_le_bytes());
+        data.extend_from_slice(driver_info.as_bytes());
+        data.extend_from_slice(&(binaries.len() as u32).to_le_bytes());
+        for (key, binary) in binaries.iter() {
+            data.extend_from_slice(&key.to_le_bytes());
+            data.extend_from_slice(&binary.format.to_le_bytes());
+            data.extend_from_slice(&(binary.buffer.len() as u32).to_le_bytes());
+            data.extend_from_slice(&binary.buffer);
+        }
+        data
+    }
+}
+
 #[derive(Debug)]
 pub struct QuerySet {
     queries: Box<[glow::Query]>,
@@ -871,6 +984,8 @@ enum Command {
         offset: i32,
         size: i32,
     },
+    BeginTransformFeedback(u32),
+    EndTransformFeedback,
     BindSampler(u32, Option<glow::Sampler>),
     BindTexture {
         slot: u32,
@@ -972,16 +1087,25 @@ fn gl_debug_message_callback(source: u32, gltype: u32, id: u32, severity: u32, m
         _ => unreachable!(),
     };
 
-    let _ = std::panic::catch_unwind(|| {
-        log::log!(
-            log_severity,
-            "GLES: [{}/{}] ID {} : {}",
-            source_str,
-            type_str,
-            id,
-            message
-        );
-    });
+    let reported = crate::auxil::debug_message::has_callbacks()
+        && crate::auxil::debug_message::report(&wgt::DebugMessage {
+            severity: crate::auxil::debug_message::severity_of_level(log_severity),
+            backend: Some(wgt::Backend::Gl),
+            message: format!("[{}/{}] ID {} : {}", source_str, type_str, id, message),
+            labels: Vec::new(),
+        });
+    if !reported {
+        let _ = std::panic::catch_unwind(|| {
+            log::log!(
+                log_severity,
+                "GLES: [{}/{}] ID {} : {}",
+                source_str,
+                type_str,
+                id,
+                message
+            );
+        });
+    }
 
     if cfg!(debug_assertions) && log_severity == log::Level::Error {
         // Set canary and continue
@@ -1425,6 +1425,15 @@ impl super::Queue {
             } => {
                 unsafe { gl.bind_buffer_range(target, slot, Some(buffer), offset, size) };
             }
+            C::BeginTransformFeedback(primitive_mode) => {
+                unsafe { gl.begin_transform_feedback(primitive_mode) };
+            }
+            C::EndTransformFeedback => {
+                unsafe { gl.end_transform_feedback() };
+                for slot in 0..crate::MAX_VERTEX_CAPTURE_BUFFERS as u32 {
+                    unsafe { gl.bind_buffer_base(glow::TRANSFORM_FEEDBACK_BUFFER, slot, None) };
+                }
+            }
             C::BindSampler(texture_index, sampler) => {
                 unsafe { gl.bind_sampler(texture_index, sampler) };
             }
@@ -1804,6 +1813,35 @@ impl crate::Queue<super::Api> for super::Queue {
     unsafe fn get_timestamp_period(&self) -> f32 {
         1.0
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        None
+    }
+
+    unsafe fn bind_texture_tiles(
+        &self,
+        _texture: &super::Texture,
+        _bindings: &[crate::TextureTileBinding<super::Api>],
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        _fence: &super::Fence,
+        _value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        // There is a single queue, so all work is already ordered.
+        Ok(())
+    }
+
+    unsafe fn signal_fence(
+        &self,
+        _fence: &super::Fence,
+        _value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
 }
 
 #[cfg(send_sync)]
@@ -94,11 +94,13 @@ use thiserror::Error;
 use wgt::WasmNotSendSync;
 
 // - Vertex + Fragment
+// - Task + Mesh + Fragment
 // - Compute
-pub const MAX_CONCURRENT_SHADER_STAGES: usize = 2;
+pub const MAX_CONCURRENT_SHADER_STAGES: usize = 3;
 pub const MAX_ANISOTROPY: u8 = 16;
 pub const MAX_BIND_GROUPS: usize = 8;
 pub const MAX_VERTEX_BUFFERS: usize = 16;
+pub const MAX_VERTEX_CAPTURE_BUFFERS: usize = wgt::MAX_VERTEX_CAPTURE_BUFFERS as usize;
 pub const MAX_COLOR_ATTACHMENTS: usize = 8;
 pub const MAX_MIP_LEVELS: u32 = 16;
 /// Size of a single occlusion/timestamp query, when copied into a buffer, in bytes.
@@ -139,6 +141,14 @@ pub enum PipelineError {
     Device(#[from] DeviceError),
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum PipelineCacheError {
+    #[error("Pipeline cache data is invalid or was created by an incompatible device")]
+    Validation,
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Error)]
 pub enum SurfaceError {
     #[error("Surface is lost")]
@@ -169,8 +179,9 @@ pub struct InstanceError {
 }
 
 impl InstanceError {
-    #[allow(dead_code)] // may be unused on some platforms
-    pub(crate) fn new(message: String) -> Self {
+    /// Creates an error with `message` and no underlying error, for callers
+    /// reporting instance problems of their own.
+    pub fn new(message: String) -> Self {
         Self {
             message,
             source: None,
@@ -212,6 +223,9 @@ pub trait Api: Clone + fmt::Debug + Sized {
     type ShaderModule: fmt::Debug + WasmNotSendSync;
     type RenderPipeline: fmt::Debug + WasmNotSendSync;
     type ComputePipeline: fmt::Debug + WasmNotSendSync;
+    type PipelineCache: fmt::Debug + WasmNotSendSync;
+    type TilePool: fmt::Debug + WasmNotSendSync;
+    type MemoryHeap: fmt::Debug + WasmNotSendSync;
 
     type AccelerationStructure: fmt::Debug + WasmNotSendSync + 'static;
 }
@@ -270,10 +284,22 @@ pub trait Surface<A: Api>: WasmNotSendSync {
 }
 
 pub trait Adapter<A: Api>: WasmNotSendSync {
+    /// Open a device and a queue on this adapter.
+    ///
+    /// If `compute_only` is true, the device will never be used for rendering or
+    /// presentation, and the backend may skip setting those up.
+    ///
+    /// `queue_priority` is a hint, backends that cannot apply it ignore it.
+    ///
+    /// `additional_queues` is the number of queues to open besides the main
+    /// one, at most [`Capabilities::max_additional_queues`].
     unsafe fn open(
         &self,
         features: wgt::Features,
         limits: &wgt::Limits,
+        compute_only: bool,
+        queue_priority: wgt::QueuePriority,
+        additional_queues: u32,
     ) -> Result<OpenDevice<A>, DeviceError>;
 
     /// Return the set of supported capabilities for a texture format.
@@ -291,11 +317,22 @@ pub trait Adapter<A: Api>: WasmNotSendSync {
     ///
     /// [`PresentationTimestamp`]: wgt::PresentationTimestamp
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp;
+
+    /// Describes what the backend requires from the adapter to expose the single
+    /// `feature`, to explain why it isn't available.
+    ///
+    /// `None` means the backend never exposes it. The result for features the
+    /// backend always exposes is unspecified.
+    fn feature_requirement(&self, feature: wgt::Features) -> Option<&'static str>;
 }
 
 pub trait Device<A: Api>: WasmNotSendSync {
     /// Exit connection to this logical device.
     unsafe fn exit(self, queue: A::Queue);
+    /// Destroys one of the [`OpenDevice::additional_queues`].
+    ///
+    /// The queue must be idle.
+    unsafe fn destroy_queue(&self, queue: A::Queue);
     /// Creates a new buffer.
     ///
     /// The initial usage is `BufferUses::empty()`.
@@ -314,6 +351,10 @@ pub trait Device<A: Api>: WasmNotSendSync {
     unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &A::Buffer, ranges: I)
     where
         I: Iterator<Item = MemoryRange>;
+    /// Returns the address of the buffer on the device.
+    ///
+    /// Requires `Features::DEVICE_GENERATED_COMMANDS`.
+    unsafe fn get_buffer_device_address(&self, buffer: &A::Buffer) -> wgt::BufferAddress;
 
     /// Creates a new texture.
     ///
@@ -362,18 +403,140 @@ pub trait Device<A: Api>: WasmNotSendSync {
         &self,
         desc: &RenderPipelineDescriptor<A>,
     ) -> Result<A::RenderPipeline, PipelineError>;
+    /// Create a render pipeline with task and mesh stages instead of a vertex stage.
+    ///
+    /// Requires `Features::EXPERIMENTAL_MESH_SHADER`.
+    unsafe fn create_mesh_pipeline(
+        &self,
+        desc: &MeshPipelineDescriptor<A>,
+    ) -> Result<A::RenderPipeline, PipelineError>;
     unsafe fn destroy_render_pipeline(&self, pipeline: A::RenderPipeline);
     unsafe fn create_compute_pipeline(
         &self,
         desc: &ComputePipelineDescriptor<A>,
     ) -> Result<A::ComputePipeline, PipelineError>;
     unsafe fn destroy_compute_pipeline(&self, pipeline: A::ComputePipeline);
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &PipelineCacheDescriptor<'_>,
+    ) -> Result<A::PipelineCache, PipelineCacheError>;
+    unsafe fn destroy_pipeline_cache(&self, cache: A::PipelineCache);
+    /// Returns the data of the pipelines added to the cache so far, to create
+    /// it again with [`PipelineCacheDescriptor::data`].
+    unsafe fn pipeline_cache_get_data(&self, cache: &A::PipelineCache) -> Option<Vec<u8>> {
+        let _ = cache;
+        None
+    }
 
     unsafe fn create_query_set(
         &self,
         desc: &wgt::QuerySetDescriptor<Label>,
     ) -> Result<A::QuerySet, DeviceError>;
     unsafe fn destroy_query_set(&self, set: A::QuerySet);
+    unsafe fn create_tile_pool(
+        &self,
+        desc: &wgt::TilePoolDescriptor<Label>,
+    ) -> Result<A::TilePool, DeviceError>;
+    unsafe fn destroy_tile_pool(&self, pool: A::TilePool);
+    /// Creates a heap that buffers and textures can be placed in with
+    /// `create_placed_buffer` and `create_placed_texture`.
+    ///
+    /// Requires `Features::MEMORY_HEAPS`.
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &wgt::MemoryHeapDescriptor<Label>,
+    ) -> Result<A::MemoryHeap, DeviceError>;
+    /// The resources placed in `heap` must have been destroyed.
+    unsafe fn destroy_memory_heap(&self, heap: A::MemoryHeap);
+    /// Returns the memory a buffer created from `desc` takes up in a heap.
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        desc: &BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, DeviceError>;
+    /// Returns the memory a texture created from `desc` takes up in a heap.
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        desc: &TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, DeviceError>;
+    /// Creates a buffer in the memory of `heap` starting at `offset`.
+    ///
+    /// `offset` must be aligned and the buffer must fit in the heap, as given by
+    /// `get_buffer_memory_requirements`, and the heap's memory type must
+    /// support the usage of the buffer.
+    unsafe fn create_placed_buffer(
+        &self,
+        desc: &BufferDescriptor,
+        heap: &A::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<A::Buffer, DeviceError>;
+    /// Creates a texture in the memory of `heap` starting at `offset`.
+    ///
+    /// `offset` must be aligned and the texture must fit in the heap, as given
+    /// by `get_texture_memory_requirements`, and the heap must be
+    /// `MemoryHeapType::DeviceLocal`.
+    unsafe fn create_placed_texture(
+        &self,
+        desc: &TextureDescriptor,
+        heap: &A::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<A::Texture, DeviceError>;
+    /// Creates a texture backed by memory allocated outside of wgpu.
+    ///
+    /// Requires `Features::EXTERNAL_TEXTURE_IMPORT`. The texture must be 2D,
+    /// with a single mip level, array layer and sample, and `source` must hold
+    /// an image matching `desc`.
+    ///
+    /// The initial usage for all subresources is `TextureUses::UNINITIALIZED`.
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn import_external_texture(
+        &self,
+        desc: &TextureDescriptor,
+        source: wgt::ExternalImageSource,
+    ) -> Result<A::Texture, DeviceError>;
+    /// Creates a buffer in dedicated memory, whose native handle can be shared
+    /// with other APIs through `export_external_buffer`.
+    ///
+    /// Requires `Features::EXTERNAL_MEMORY`. The buffer can't be mapped.
+    unsafe fn create_external_buffer(
+        &self,
+        desc: &BufferDescriptor,
+    ) -> Result<A::Buffer, DeviceError>;
+    /// Creates a buffer bound to memory allocated by another API.
+    ///
+    /// Requires `Features::EXTERNAL_MEMORY`. The buffer can't be mapped.
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn import_external_buffer(
+        &self,
+        desc: &BufferDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> Result<A::Buffer, DeviceError>;
+    /// Returns the memory of a buffer made by `create_external_buffer` or
+    /// `import_external_buffer`.
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn export_external_buffer(
+        &self,
+        buffer: &A::Buffer,
+    ) -> Result<wgt::ExternalMemory, DeviceError>;
+    /// Creates a fence whose native handle can be shared with other APIs
+    /// through `export_external_fence`.
+    ///
+    /// Requires `Features::EXTERNAL_FENCE`.
+    unsafe fn create_external_fence(&self) -> Result<A::Fence, DeviceError>;
+    /// Creates a fence from the native handle of a fence made by another API.
+    ///
+    /// Requires `Features::EXTERNAL_FENCE`.
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn import_external_fence(
+        &self,
+        handle: wgt::ExternalFenceHandle,
+    ) -> Result<A::Fence, DeviceError>;
+    /// Returns the native handle of a fence made by `create_external_fence`
+    /// or `import_external_fence`.
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe fn export_external_fence(
+        &self,
+        fence: &A::Fence,
+    ) -> Result<wgt::ExternalFenceHandle, DeviceError>;
     unsafe fn create_fence(&self) -> Result<A::Fence, DeviceError>;
     unsafe fn destroy_fence(&self, fence: A::Fence);
     unsafe fn get_fence_value(&self, fence: &A::Fence) -> Result<FenceValue, DeviceError>;
@@ -427,6 +590,45 @@ pub trait Queue<A: Api>: WasmNotSendSync {
         texture: A::SurfaceTexture,
     ) -> Result<(), SurfaceError>;
     unsafe fn get_timestamp_period(&self) -> f32;
+
+    /// Samples the timestamp query clock of this queue and the CPU clock used
+    /// for [`PresentationTimestamp`]s at the same moment.
+    ///
+    /// `None` means the backend can't correlate those clocks.
+    ///
+    /// [`PresentationTimestamp`]: wgt::PresentationTimestamp
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration>;
+
+    /// Maps or unmaps tiles of a texture created with [`MemoryFlags::TILED`].
+    ///
+    /// The bindings take effect after the work submitted so far, and before
+    /// the work submitted afterwards.
+    ///
+    /// Valid usage:
+    /// - the sizes of all mip levels of the texture are multiples of
+    ///   [`wgt::TextureFormat::tile_dimensions`], so there is no packed mip tail.
+    /// - the regions of `bindings` are within the texture, and the ranges of
+    ///   tiles they map to are within their pools.
+    unsafe fn bind_texture_tiles(
+        &self,
+        texture: &A::Texture,
+        bindings: &[TextureTileBinding<A>],
+    ) -> Result<(), DeviceError>;
+
+    /// Makes the work submitted to this queue afterwards wait, on the GPU,
+    /// until `fence` reaches `value`.
+    ///
+    /// This is how work is ordered between the queues of a device: `fence`
+    /// is usually signaled by a submission to another queue.
+    unsafe fn wait_for_fence(&self, fence: &A::Fence, value: FenceValue)
+        -> Result<(), DeviceError>;
+
+    /// Sets `fence` to `value`, on the GPU, once the work submitted to this
+    /// queue so far is done.
+    ///
+    /// `fence` must have been made by `create_external_fence` or
+    /// `import_external_fence`.
+    unsafe fn signal_fence(&self, fence: &A::Fence, value: FenceValue) -> Result<(), DeviceError>;
 }
 
 /// Encoder for commands in command buffers.
@@ -454,6 +656,13 @@ pub trait CommandEncoder<A: Api>: WasmNotSendSync + fmt::Debug {
     where
         T: Iterator<Item = TextureBarrier<'a, A>>;
 
+    /// Makes all previous accesses to placed resources complete before any
+    /// following access, so resources that share the memory of a heap can
+    /// take turns using it.
+    ///
+    /// Requires `Features::MEMORY_HEAPS`.
+    unsafe fn aliasing_barrier(&mut self);
+
     // copy operations
 
     unsafe fn clear_buffer(&mut self, buffer: &A::Buffer, range: MemoryRange);
@@ -489,6 +698,21 @@ pub trait CommandEncoder<A: Api>: WasmNotSendSync + fmt::Debug {
     ) where
         T: Iterator<Item = TextureCopy>;
 
+    /// Resolve a multisampled texture into a single-sampled texture of the same format.
+    /// Works with a single array layer.
+    /// Note: `dst` current usage has to be `TextureUses::COPY_DST`.
+    /// Note: the format must support `TextureFormatCapabilities::MULTISAMPLE_RESOLVE_REGION`,
+    /// and `MULTISAMPLE_RESOLVE_MIN_MAX` for modes other than `ResolveMode::Average`.
+    unsafe fn resolve_texture<T>(
+        &mut self,
+        src: &A::Texture,
+        src_usage: TextureUses,
+        dst: &A::Texture,
+        mode: wgt::ResolveMode,
+        regions: T,
+    ) where
+        T: Iterator<Item = TextureCopy>;
+
     /// Copy from buffer to texture.
     /// Works with a single array layer.
     /// Note: `dst` current usage has to be `TextureUses::COPY_DST`.
@@ -624,6 +848,78 @@ pub trait CommandEncoder<A: Api>: WasmNotSendSync + fmt::Debug {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     );
+    /// Draw with the current mesh pipeline, launching a grid of task shader
+    /// workgroups, or mesh shader workgroups if it has no task stage.
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    );
+    /// Draw `draw_count` times with the current mesh pipeline, reading
+    /// `wgt::DrawMeshTasksIndirectArgs` from `buffer` at `offset`.
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        buffer: &A::Buffer,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    );
+    /// Execute up to `max_count` commands made of `arguments`, written in `buffer` at
+    /// `offset` with a stride of `wgt::IndirectArgument::stride(arguments)`.
+    ///
+    /// If `count` is given, the number of commands is the minimum of `max_count`
+    /// and the `u32` at that offset of the count buffer.
+    ///
+    /// `pipeline` must be the current pipeline, and `layout` its layout. The vertex
+    /// buffers and push constants set by the commands are undefined afterwards.
+    ///
+    /// Requires `Features::DEVICE_GENERATED_COMMANDS`.
+    unsafe fn execute_indirect(
+        &mut self,
+        pipeline: &A::RenderPipeline,
+        layout: &A::PipelineLayout,
+        arguments: &[wgt::IndirectArgument],
+        buffer: &A::Buffer,
+        offset: wgt::BufferAddress,
+        count: Option<(&A::Buffer, wgt::BufferAddress)>,
+        max_count: u32,
+    );
+    /// Skip the following draws if the value at `offset` in `buffer` is zero,
+    /// until `end_conditional_rendering`. The value is a `u64` on DX12, and
+    /// the `u32` at the same offset on Vulkan.
+    ///
+    /// `offset` must be a multiple of 8, and `buffer` must be in the
+    /// `BufferUses::INDIRECT` state. Conditional rendering must be ended in
+    /// the render pass it's begun in.
+    ///
+    /// Requires `Features::CONDITIONAL_RENDERING`.
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &A::Buffer,
+        offset: wgt::BufferAddress,
+    );
+    unsafe fn end_conditional_rendering(&mut self);
+
+    /// Capture the vertex shader outputs of the following draws into
+    /// `buffers`, as laid out by the `vertex_capture` of the current
+    /// pipeline, until `end_vertex_capture`. Vertices are written from the
+    /// start of each binding, with primitives written out as lists.
+    ///
+    /// The buffers must be in the `BufferUses::VERTEX_CAPTURE` state, and
+    /// the pipeline can't be changed while capturing. Only non-indexed,
+    /// direct draws can be captured. Capture must be ended in the render
+    /// pass it's begun in.
+    ///
+    /// Requires `Features::VERTEX_CAPTURE`.
+    unsafe fn begin_vertex_capture(&mut self, buffers: &[BufferBinding<A>]);
+    unsafe fn end_vertex_capture(&mut self);
+
+    /// Set the shading rate of the following draws. The rate is reset to
+    /// `ShadingRate::Rate1x1` at the beginning of every render pass, and is
+    /// overridden by the shading rate attachment of the pass, if any.
+    ///
+    /// Requires `Features::SHADING_RATE`.
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate);
 
     // compute passes
 
@@ -674,6 +970,12 @@ bitflags!(
     pub struct BindGroupLayoutFlags: u32 {
         /// Allows for bind group binding arrays to be shorter than the array in the BGL.
         const PARTIALLY_BOUND = 1 << 0;
+        /// Allows for bind group binding arrays to be update-after-bind, which gives them the
+        /// larger `max_binding_array_*elements_per_shader_stage` limits.
+        ///
+        /// Never set together with buffers with dynamic offsets, and doesn't apply to arrays of
+        /// uniform buffers.
+        const UPDATE_AFTER_BIND = 1 << 1;
     }
 );
 
@@ -718,6 +1020,16 @@ bitflags!(
         const COPY_SRC = 1 << 14;
         /// Format can be copied to.
         const COPY_DST = 1 << 15;
+
+        /// Format can be resolved on a sub-rectangle with
+        /// [`CommandEncoder::resolve_texture`].
+        const MULTISAMPLE_RESOLVE_REGION = 1 << 16;
+        /// Format can be resolved with the min and max modes of
+        /// [`CommandEncoder::resolve_texture`], or of a depth-stencil resolve target.
+        const MULTISAMPLE_RESOLVE_MIN_MAX = 1 << 17;
+        /// Depth format can be resolved with the average mode of a depth-stencil
+        /// resolve target.
+        const MULTISAMPLE_RESOLVE_DEPTH_AVERAGE = 1 << 18;
     }
 );
 
@@ -777,7 +1089,7 @@ impl From<wgt::TextureFormat> for FormatAspects {
             wgt::TextureFormat::Depth32FloatStencil8 | wgt::TextureFormat::Depth24PlusStencil8 => {
                 Self::DEPTH_STENCIL
             }
-            wgt::TextureFormat::NV12 => Self::PLANE_0 | Self::PLANE_1,
+            wgt::TextureFormat::NV12 | wgt::TextureFormat::P010 => Self::PLANE_0 | Self::PLANE_1,
             _ => Self::COLOR,
         }
     }
@@ -788,6 +1100,9 @@ bitflags!(
     pub struct MemoryFlags: u32 {
         const TRANSIENT = 1 << 0;
         const PREFER_COHERENT = 1 << 1;
+        /// The texture gets no memory, its tiles are mapped with
+        /// [`Queue::bind_texture_tiles`] instead.
+        const TILED = 1 << 2;
     }
 );
 
@@ -830,12 +1145,14 @@ bitflags::bitflags! {
         const ACCELERATION_STRUCTURE_SCRATCH = 1 << 11;
         const BOTTOM_LEVEL_ACCELERATION_STRUCTURE_INPUT = 1 << 12;
         const TOP_LEVEL_ACCELERATION_STRUCTURE_INPUT = 1 << 13;
+        /// A buffer written to by vertex capture.
+        const VERTEX_CAPTURE = 1 << 14;
         /// The combination of states that a buffer may be in _at the same time_.
         const INCLUSIVE = Self::MAP_READ.bits() | Self::COPY_SRC.bits() |
             Self::INDEX.bits() | Self::VERTEX.bits() | Self::UNIFORM.bits() |
             Self::STORAGE_READ.bits() | Self::INDIRECT.bits() | Self::BOTTOM_LEVEL_ACCELERATION_STRUCTURE_INPUT.bits() | Self::TOP_LEVEL_ACCELERATION_STRUCTURE_INPUT.bits();
         /// The combination of states that a buffer must exclusively be in.
-        const EXCLUSIVE = Self::MAP_WRITE.bits() | Self::COPY_DST.bits() | Self::STORAGE_READ_WRITE.bits() | Self::ACCELERATION_STRUCTURE_SCRATCH.bits();
+        const EXCLUSIVE = Self::MAP_WRITE.bits() | Self::COPY_DST.bits() | Self::STORAGE_READ_WRITE.bits() | Self::ACCELERATION_STRUCTURE_SCRATCH.bits() | Self::VERTEX_CAPTURE.bits();
         /// The combination of all usages that the are guaranteed to be be ordered by the hardware.
         /// If a usage is ordered, then if the buffer state doesn't change between draw calls, there
         /// are no barriers needed for synchronization.
@@ -867,10 +1184,12 @@ bitflags::bitflags! {
         const STORAGE_READ = 1 << 8;
         /// Read-write or write-only storage buffer usage.
         const STORAGE_READ_WRITE = 1 << 9;
+        /// The shading rate attachment of a renderpass.
+        const SHADING_RATE_ATTACHMENT = 1 << 12;
         /// The combination of states that a texture may be in _at the same time_.
         const INCLUSIVE = Self::COPY_SRC.bits() | Self::RESOURCE.bits() | Self::DEPTH_STENCIL_READ.bits();
         /// The combination of states that a texture must exclusively be in.
-        const EXCLUSIVE = Self::COPY_DST.bits() | Self::COLOR_TARGET.bits() | Self::DEPTH_STENCIL_WRITE.bits() | Self::STORAGE_READ.bits() | Self::STORAGE_READ_WRITE.bits() | Self::PRESENT.bits();
+        const EXCLUSIVE = Self::COPY_DST.bits() | Self::COLOR_TARGET.bits() | Self::DEPTH_STENCIL_WRITE.bits() | Self::STORAGE_READ.bits() | Self::STORAGE_READ_WRITE.bits() | Self::PRESENT.bits() | Self::SHADING_RATE_ATTACHMENT.bits();
         /// The combination of all usages that the are guaranteed to be be ordered by the hardware.
         /// If a usage is ordered, then if the texture state doesn't change between draw calls, there
         /// are no barriers needed for synchronization.
@@ -899,6 +1218,9 @@ pub struct Alignments {
     /// The alignment of the row pitch of the texture data stored in a buffer that is
     /// used in a GPU copy operation.
     pub buffer_copy_pitch: wgt::BufferSize,
+    /// The alignment of the offset, into the scratch buffer, of an acceleration
+    /// structure build. Zero if acceleration structures aren't supported.
+    pub ray_tracing_scratch_buffer_alignment: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -906,6 +1228,9 @@ pub struct Capabilities {
     pub limits: wgt::Limits,
     pub alignments: Alignments,
     pub downlevel: wgt::DownlevelCapabilities,
+    /// The number of queues that can be opened besides the main one.
+    pub max_additional_queues: u32,
+    pub memory_architecture: wgt::MemoryArchitecture,
 }
 
 #[derive(Debug)]
@@ -966,6 +1291,9 @@ pub struct AcquiredSurfaceTexture<A: Api> {
 pub struct OpenDevice<A: Api> {
     pub device: A::Device,
     pub queue: A::Queue,
+    /// Queues that execute independently of `queue`, and support the same
+    /// operations.
+    pub additional_queues: Vec<A::Queue>,
 }
 
 #[derive(Clone, Debug)]
@@ -1017,6 +1345,20 @@ impl TextureDescriptor<'_> {
     }
 }
 
+/// Maps a region of tiles of a texture to consecutive tiles of a tile pool.
+#[derive(Debug)]
+pub struct TextureTileBinding<'a, A: Api> {
+    pub mip_level: u32,
+    /// Origin of the region in tiles, where `z` is the first array layer.
+    pub origin: wgt::Origin3d,
+    /// Size of the region in tiles, where `depth_or_array_layers` is the
+    /// number of array layers.
+    pub size: wgt::Extent3d,
+    /// The pool and the index of the first tile of the pool to map the region
+    /// to, row by row and layer by layer. `None` unmaps the region.
+    pub memory: Option<(&'a A::TilePool, u32)>,
+}
+
 /// TextureView descriptor.
 ///
 /// Valid usage:
@@ -1031,6 +1373,9 @@ pub struct TextureViewDescriptor<'a> {
     pub dimension: wgt::TextureViewDimension,
     pub usage: TextureUses,
     pub range: wgt::ImageSubresourceRange,
+    /// Only [`wgt::ComponentMapping::IDENTITY`] is valid if `usage` contains
+    /// anything but [`TextureUses::RESOURCE`].
+    pub swizzle: wgt::ComponentMapping,
 }
 
 #[derive(Clone, Debug)]
@@ -1218,6 +1563,16 @@ pub struct ComputePipelineDescriptor<'a, A: Api> {
     pub layout: &'a A::PipelineLayout,
     /// The compiled compute stage and its entry point.
     pub stage: ProgrammableStage<'a, A>,
+    /// The cache to look the pipeline up in, and to add it to.
+    pub cache: Option<&'a A::PipelineCache>,
+}
+
+/// Describes a pipeline cache.
+#[derive(Clone, Debug)]
+pub struct PipelineCacheDescriptor<'a> {
+    pub label: Label<'a>,
+    /// Data previously returned by [`Device::pipeline_cache_get_data`].
+    pub data: Option<&'a [u8]>,
 }
 
 /// Describes how the vertex buffer is interpreted.
@@ -1231,6 +1586,15 @@ pub struct VertexBufferLayout<'a> {
     pub attributes: &'a [wgt::VertexAttribute],
 }
 
+/// Describes how the vertex shader outputs are written to a capture buffer.
+#[derive(Clone, Debug)]
+pub struct VertexCaptureBufferLayout<'a> {
+    /// The stride, in bytes, between vertices in this buffer.
+    pub array_stride: wgt::BufferAddress,
+    /// The vertex shader outputs written to this buffer, by location.
+    pub attributes: &'a [wgt::VertexAttribute],
+}
+
 /// Describes a render (graphics) pipeline.
 #[derive(Clone, Debug)]
 pub struct RenderPipelineDescriptor<'a, A: Api> {
@@ -1241,6 +1605,10 @@ pub struct RenderPipelineDescriptor<'a, A: Api> {
     pub vertex_buffers: &'a [VertexBufferLayout<'a>],
     /// The vertex stage for this pipeline.
     pub vertex_stage: ProgrammableStage<'a, A>,
+    /// The buffers vertex shader outputs are captured to, if any.
+    ///
+    /// Requires `Features::VERTEX_CAPTURE` when not empty.
+    pub vertex_capture: &'a [VertexCaptureBufferLayout<'a>],
     /// The properties of the pipeline at the primitive assembly and rasterization level.
     pub primitive: wgt::PrimitiveState,
     /// The effect of draw calls on the depth and stencil aspects of the output target, if any.
@@ -1254,6 +1622,36 @@ pub struct RenderPipelineDescriptor<'a, A: Api> {
     /// If the pipeline will be used with a multiview render pass, this indicates how many array
     /// layers the attachments will have.
     pub multiview: Option<NonZeroU32>,
+    /// The cache to look the pipeline up in, and to add it to.
+    pub cache: Option<&'a A::PipelineCache>,
+}
+
+/// Describes a render pipeline whose geometry is generated by mesh shaders.
+#[derive(Clone, Debug)]
+pub struct MeshPipelineDescriptor<'a, A: Api> {
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    pub layout: &'a A::PipelineLayout,
+    /// The task stage for this pipeline, which launches the mesh shader workgroups.
+    pub task_stage: Option<ProgrammableStage<'a, A>>,
+    /// The mesh stage for this pipeline.
+    pub mesh_stage: ProgrammableStage<'a, A>,
+    /// The properties of the pipeline at the rasterization level. The
+    /// topology is given by the mesh stage and ignored.
+    pub primitive: wgt::PrimitiveState,
+    /// The effect of draw calls on the depth and stencil aspects of the output target, if any.
+    pub depth_stencil: Option<wgt::DepthStencilState>,
+    /// The multi-sampling properties of the pipeline.
+    pub multisample: wgt::MultisampleState,
+    /// The fragment stage for this pipeline.
+    pub fragment_stage: Option<ProgrammableStage<'a, A>>,
+    /// The effect of draw calls on the color aspect of the output target.
+    pub color_targets: &'a [Option<wgt::ColorTargetState>],
+    /// If the pipeline will be used with a multiview render pass, this indicates how many array
+    /// layers the attachments will have.
+    pub multiview: Option<NonZeroU32>,
+    /// The cache to look the pipeline up in, and to add it to.
+    pub cache: Option<&'a A::PipelineCache>,
 }
 
 #[derive(Debug, Clone)]
@@ -1377,6 +1775,12 @@ impl<A: Api> Clone for ColorAttachment<'_, A> {
 #[derive(Clone, Debug)]
 pub struct DepthStencilAttachment<'a, A: Api> {
     pub target: Attachment<'a, A>,
+    /// Single-sampled attachment receiving all aspects of `target`, combined
+    /// with `resolve_mode`, at the end of the pass.
+    ///
+    /// Requires `Features::DEPTH_STENCIL_RESOLVE`.
+    pub resolve_target: Option<Attachment<'a, A>>,
+    pub resolve_mode: wgt::ResolveMode,
     pub depth_ops: AttachmentOps,
     pub stencil_ops: AttachmentOps,
     pub clear_value: (f32, u32),
@@ -1410,6 +1814,11 @@ pub struct RenderPassDescriptor<'a, A: Api> {
     pub multiview: Option<NonZeroU32>,
     pub timestamp_writes: Option<RenderPassTimestampWrites<'a, A>>,
     pub occlusion_query_set: Option<&'a A::QuerySet>,
+    /// R8Uint texture selecting the shading rate of each `Limits::shading_rate_tile_size`
+    /// sized tile of the render targets, in the `TextureUses::SHADING_RATE_ATTACHMENT` state.
+    ///
+    /// Requires `Features::SHADING_RATE_ATTACHMENT`.
+    pub shading_rate_attachment: Option<Attachment<'a, A>>,
 }
 
 #[derive(Debug)]
@@ -16,20 +16,26 @@ impl super::Adapter {
     pub(super) fn new(shared: Arc<super::AdapterShared>) -> Self {
         Self { shared }
     }
-}
 
-impl crate::Adapter<super::Api> for super::Adapter {
-    unsafe fn open(
+    /// Opens a device on a command queue created outside of wgpu.
+    ///
+    /// # Safety
+    ///
+    /// - `queue` must be created from the device of this adapter.
+    /// - `features` must be a subset of the features the adapter was exposed with.
+    pub unsafe fn device_from_raw(
         &self,
+        queue: &metal::CommandQueueRef,
         features: wgt::Features,
-        _limits: &wgt::Limits,
-    ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
-        let queue = self
-            .shared
-            .device
-            .lock()
-            .new_command_queue_with_max_command_buffer_count(MAX_COMMAND_BUFFERS);
+    ) -> crate::OpenDevice<super::Api> {
+        self.open_with_queue(queue.to_owned(), features)
+    }
 
+    fn open_with_queue(
+        &self,
+        queue: metal::CommandQueue,
+        features: wgt::Features,
+    ) -> crate::OpenDevice<super::Api> {
         // Acquiring the meaning of timestamp ticks is hard with Metal!
         // The only thing there is is a method correlating cpu & gpu timestamps (`device.sample_timestamps`).
         // Users are supposed to call this method twice and calculate the difference,
@@ -56,7 +62,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
             1.0
         };
 
-        Ok(crate::OpenDevice {
+        crate::OpenDevice {
             device: super::Device {
                 shared: Arc::clone(&self.shared),
                 features,
@@ -65,7 +71,27 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 raw: Arc::new(Mutex::new(queue)),
                 timestamp_period,
             },
-        })
+            additional_queues: Vec::new(),
+        }
+    }
+}
+
+impl crate::Adapter<super::Api> for super::Adapter {
+    unsafe fn open(
+        &self,
+        features: wgt::Features,
+        _limits: &wgt::Limits,
+        _compute_only: bool,
+        _queue_priority: wgt::QueuePriority,
+        _additional_queues: u32,
+    ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
+        let queue = self
+            .shared
+            .device
+            .lock()
+            .new_command_queue_with_max_command_buffer_count(MAX_COMMAND_BUFFERS);
+
+        Ok(self.open_with_queue(queue, features))
     }
 
     unsafe fn texture_format_capabilities(
@@ -210,6 +236,9 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 if pc.format_depth16unorm {
                     flags |= Tfc::SAMPLED_LINEAR
                 }
+                if pc.depth_stencil_resolve {
+                    flags |= Tfc::MULTISAMPLE_RESOLVE_MIN_MAX
+                }
                 flags
             }
             Tf::Depth32Float | Tf::Depth32FloatStencil8 => {
@@ -218,6 +247,10 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 if pc.format_depth32float_filter {
                     flags |= Tfc::SAMPLED_LINEAR
                 }
+                // Stencil is only resolved from sample zero.
+                if pc.depth_stencil_resolve && format == Tf::Depth32Float {
+                    flags |= Tfc::MULTISAMPLE_RESOLVE_MIN_MAX
+                }
                 flags
             }
             Tf::Depth24Plus | Tf::Depth24PlusStencil8 => {
@@ -232,7 +265,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 }
                 flags
             }
-            Tf::NV12 => return Tfc::empty(),
+            Tf::NV12 | Tf::P010 => return Tfc::empty(),
             Tf::Rgb9e5Ufloat => {
                 if pc.msaa_apple3 {
                     all_caps
@@ -351,6 +384,39 @@ impl crate::Adapter<super::Api> for super::Adapter {
 
         wgt::PresentationTimestamp(timestamp)
     }
+
+    fn feature_requirement(&self, feature: wgt::Features) -> Option<&'static str> {
+        use wgt::Features as F;
+        Some(match feature {
+            F::FLOAT32_FILTERABLE => "filtering support of the 32-bit float formats",
+            F::INDIRECT_FIRST_INSTANCE | F::MULTI_DRAW_INDIRECT => {
+                "indirect draw and dispatch support"
+            }
+            F::TIMESTAMP_QUERY => "counter sampling at stage boundaries",
+            F::TIMESTAMP_QUERY_INSIDE_PASSES => "counter sampling at draw and dispatch boundaries",
+            F::DUAL_SOURCE_BLENDING => "MSL 1.2 and dual-source blending support",
+            F::TEXTURE_COMPRESSION_ASTC => "a GPU family supporting the ASTC formats",
+            F::TEXTURE_COMPRESSION_ASTC_HDR => "a GPU family supporting the ASTC HDR formats",
+            F::TEXTURE_COMPRESSION_BC => "a GPU family supporting the BC formats",
+            F::TEXTURE_COMPRESSION_ETC2 => "a GPU family supporting the EAC and ETC formats",
+            F::DEPTH_CLIP_CONTROL => "depth clip mode support",
+            F::SHADER_PRIMITIVE_INDEX => "primitive ID support",
+            F::TEXTURE_BINDING_ARRAY
+            | F::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
+            | F::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING => {
+                "MSL 2.0 and support of arrays of textures"
+            }
+            F::STORAGE_RESOURCE_BINDING_ARRAY => {
+                "MSL 2.2 and support of arrays of readable and writable textures"
+            }
+            F::ADDRESS_MODE_CLAMP_TO_BORDER => "sampler border color support",
+            F::RG11B10UFLOAT_RENDERABLE => "full support of the `RG11B10Float` format",
+            F::DEPTH_STENCIL_RESOLVE => "a GPU family supporting depth and stencil resolve filters",
+            F::TEXTURE_COMPONENT_SWIZZLE => "macOS 10.15+ or iOS 13+",
+            F::EXTERNAL_FENCE => "macOS 10.14+ or iOS 12+",
+            _ => return None,
+        })
+    }
 }
 
 const RESOURCE_HEAP_SUPPORT: &[MTLFeatureSet] = &[
@@ -464,6 +530,12 @@ const DEPTH_CLIP_MODE: &[MTLFeatureSet] = &[
     MTLFeatureSet::macOS_GPUFamily1_v1,
 ];
 
+const DEPTH_STENCIL_RESOLVE_SUPPORT: &[MTLFeatureSet] = &[
+    MTLFeatureSet::iOS_GPUFamily3_v4,
+    MTLFeatureSet::tvOS_GPUFamily2_v2,
+    MTLFeatureSet::macOS_GPUFamily1_v4,
+];
+
 const OS_NOT_SUPPORT: (usize, usize) = (10000, 0);
 
 impl super::PrivateCapabilities {
@@ -606,6 +678,7 @@ impl super::PrivateCapabilities {
             layered_rendering: Self::supports_any(device, LAYERED_RENDERING_SUPPORT),
             function_specialization: Self::supports_any(device, FUNCTION_SPECIALIZATION_SUPPORT),
             depth_clip_mode: Self::supports_any(device, DEPTH_CLIP_MODE),
+            depth_stencil_resolve: Self::supports_any(device, DEPTH_STENCIL_RESOLVE_SUPPORT),
             texture_cube_array: Self::supports_any(device, TEXTURE_CUBE_ARRAY_SUPPORT),
             supports_float_filtering: os_is_mac
                 || (version.at_least((11, 0), (14, 0), os_is_mac)
@@ -770,6 +843,7 @@ impl super::PrivateCapabilities {
                 ],
             ),
             supports_binary_archives: family_check
+                && version.at_least((11, 0), (14, 0), os_is_mac)
                 && (device.supports_family(MTLGPUFamily::Apple3)
                     || device.supports_family(MTLGPUFamily::Mac1)),
             supports_capture_manager: version.at_least((10, 13), (11, 0), os_is_mac),
@@ -793,6 +867,8 @@ impl super::PrivateCapabilities {
             supports_depth_clip_control: os_is_mac
                 || device.supports_feature_set(MTLFeatureSet::iOS_GPUFamily4_v1),
             supports_preserve_invariance: version.at_least((11, 0), (13, 0), os_is_mac),
+            supports_texture_swizzle: version.at_least((10, 15), (13, 0), os_is_mac),
+            supports_shared_events: version.at_least((10, 14), (12, 0), os_is_mac),
             // Metal 2.2 on mac, 2.3 on iOS.
             supports_shader_primitive_index: version.at_least((10, 15), (14, 0), os_is_mac),
             has_unified_memory: if version.at_least((10, 15), (13, 0), os_is_mac) {
@@ -825,7 +901,8 @@ impl super::PrivateCapabilities {
             | F::TEXTURE_FORMAT_16BIT_NORM
             | F::SHADER_F16
             | F::DEPTH32FLOAT_STENCIL8
-            | F::BGRA8UNORM_STORAGE;
+            | F::BGRA8UNORM_STORAGE
+            | F::EXTERNAL_TEXTURE_IMPORT;
 
         features.set(F::FLOAT32_FILTERABLE, self.supports_float_filtering);
         features.set(
@@ -852,6 +929,9 @@ impl super::PrivateCapabilities {
         features.set(F::TEXTURE_COMPRESSION_ETC2, self.format_eac_etc);
 
         features.set(F::DEPTH_CLIP_CONTROL, self.supports_depth_clip_control);
+        features.set(F::DEPTH_STENCIL_RESOLVE, self.depth_stencil_resolve);
+        features.set(F::TEXTURE_COMPONENT_SWIZZLE, self.supports_texture_swizzle);
+        features.set(F::EXTERNAL_FENCE, self.supports_shared_events);
         features.set(
             F::SHADER_PRIMITIVE_INDEX,
             self.supports_shader_primitive_index,
@@ -881,6 +961,7 @@ impl super::PrivateCapabilities {
 
         features.set(F::RG11B10UFLOAT_RENDERABLE, self.format_rg11b10_all);
         features.set(F::SHADER_UNUSED_VERTEX_OUTPUT, true);
+        features.set(F::PIPELINE_CACHE, self.supports_binary_archives);
 
         features
     }
@@ -948,12 +1029,22 @@ impl super::PrivateCapabilities {
                 max_compute_workgroups_per_dimension: 0xFFFF,
                 max_buffer_size: self.max_buffer_size,
                 max_non_sampler_bindings: std::u32::MAX,
+                max_binding_array_elements_per_shader_stage: 0,
+                max_binding_array_sampler_elements_per_shader_stage: 0,
+                shading_rate_tile_size: 0,
             },
             alignments: crate::Alignments {
                 buffer_copy_offset: wgt::BufferSize::new(self.buffer_alignment).unwrap(),
                 buffer_copy_pitch: wgt::BufferSize::new(4).unwrap(),
+                ray_tracing_scratch_buffer_alignment: 0,
             },
             downlevel,
+            max_additional_queues: 0,
+            memory_architecture: if self.has_unified_memory.unwrap_or(self.low_power) {
+                wgt::MemoryArchitecture::Unified
+            } else {
+                wgt::MemoryArchitecture::Discrete
+            },
         }
     }
 
@@ -1021,7 +1112,7 @@ impl super::PrivateCapabilities {
                     Depth32Float_Stencil8
                 }
             }
-            Tf::NV12 => unreachable!(),
+            Tf::NV12 | Tf::P010 => unreachable!(),
             Tf::Rgb9e5Ufloat => RGB9E5Float,
             Tf::Bc1RgbaUnorm => BC1_RGBA,
             Tf::Bc1RgbaUnormSrgb => BC1_RGBA_sRGB,
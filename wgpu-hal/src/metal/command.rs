@@ -238,6 +238,10 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     {
     }
 
+    unsafe fn aliasing_barrier(&mut self) {
+        unreachable!()
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
         let encoder = self.enter_blit();
         encoder.fill_buffer(&buffer.raw, conv::map_range(&range), 0);
@@ -300,6 +304,21 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         }
     }
 
+    unsafe fn resolve_texture<T>(
+        &mut self,
+        _src: &super::Texture,
+        _src_usage: crate::TextureUses,
+        _dst: &super::Texture,
+        _mode: wgt::ResolveMode,
+        _regions: T,
+    ) where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        // Metal only resolves at the end of render passes,
+        // `MULTISAMPLE_RESOLVE_REGION` is never reported.
+        unimplemented!()
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -526,6 +545,12 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                 if at.target.view.aspects.contains(crate::FormatAspects::DEPTH) {
                     let at_descriptor = descriptor.depth_attachment().unwrap();
                     at_descriptor.set_texture(Some(&at.target.view.raw));
+                    if let Some(ref resolve) = at.resolve_target {
+                        at_descriptor.set_resolve_texture(Some(&resolve.view.raw));
+                        at_descriptor.set_depth_resolve_filter(conv::map_depth_resolve_filter(
+                            at.resolve_mode,
+                        ));
+                    }
 
                     let load_action = if at.depth_ops.contains(crate::AttachmentOps::LOAD) {
                         metal::MTLLoadAction::Load
@@ -533,11 +558,10 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                         at_descriptor.set_clear_depth(at.clear_value.0 as f64);
                         metal::MTLLoadAction::Clear
                     };
-                    let store_action = if at.depth_ops.contains(crate::AttachmentOps::STORE) {
-                        metal::MTLStoreAction::Store
-                    } else {
-                        metal::MTLStoreAction::DontCare
-                    };
+                    let store_action = conv::map_store_action(
+                        at.depth_ops.contains(crate::AttachmentOps::STORE),
+                        at.resolve_target.is_some(),
+                    );
                     at_descriptor.set_load_action(load_action);
                     at_descriptor.set_store_action(store_action);
                 }
@@ -549,6 +573,10 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                 {
                     let at_descriptor = descriptor.stencil_attachment().unwrap();
                     at_descriptor.set_texture(Some(&at.target.view.raw));
+                    // Stencil is always resolved from sample zero, which is the default filter.
+                    if let Some(ref resolve) = at.resolve_target {
+                        at_descriptor.set_resolve_texture(Some(&resolve.view.raw));
+                    }
 
                     let load_action = if at.stencil_ops.contains(crate::AttachmentOps::LOAD) {
                         metal::MTLLoadAction::Load
@@ -556,11 +584,10 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                         at_descriptor.set_clear_stencil(at.clear_value.1);
                         metal::MTLLoadAction::Clear
                     };
-                    let store_action = if at.stencil_ops.contains(crate::AttachmentOps::STORE) {
-                        metal::MTLStoreAction::Store
-                    } else {
-                        metal::MTLStoreAction::DontCare
-                    };
+                    let store_action = conv::map_store_action(
+                        at.stencil_ops.contains(crate::AttachmentOps::STORE),
+                        at.resolve_target.is_some(),
+                    );
                     at_descriptor.set_load_action(load_action);
                     at_descriptor.set_store_action(store_action);
                 }
@@ -1091,6 +1118,57 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     ) {
         //TODO
     }
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        _group_count_x: u32,
+        _group_count_y: u32,
+        _group_count_z: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _draw_count: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn execute_indirect(
+        &mut self,
+        _pipeline: &super::RenderPipeline,
+        _layout: &super::PipelineLayout,
+        _arguments: &[wgt::IndirectArgument],
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _count: Option<(&super::Buffer, wgt::BufferAddress)>,
+        _max_count: u32,
+    ) {
+        // Indirect command buffers can't bind vertex buffers from device addresses.
+        unimplemented!()
+    }
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+    ) {
+        unreachable!()
+    }
+    unsafe fn end_conditional_rendering(&mut self) {
+        unreachable!()
+    }
+
+    unsafe fn begin_vertex_capture(&mut self, _buffers: &[crate::BufferBinding<super::Api>]) {
+        unreachable!()
+    }
+
+    unsafe fn end_vertex_capture(&mut self) {
+        unreachable!()
+    }
+
+    unsafe fn set_shading_rate(&mut self, _rate: wgt::ShadingRate) {
+        unreachable!()
+    }
 
     // compute
 
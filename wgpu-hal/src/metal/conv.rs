@@ -297,6 +297,45 @@ pub fn map_store_action(store: bool, resolve: bool) -> metal::MTLStoreAction {
     }
 }
 
+pub fn map_depth_resolve_filter(mode: wgt::ResolveMode) -> metal::MTLMultisampleDepthResolveFilter {
+    use metal::MTLMultisampleDepthResolveFilter::*;
+    match mode {
+        wgt::ResolveMode::SampleZero => Sample0,
+        wgt::ResolveMode::Min => Min,
+        wgt::ResolveMode::Max => Max,
+        wgt::ResolveMode::Average => unreachable!(),
+    }
+}
+
+/// `MTLTextureSwizzleChannels`, which isn't exposed by `metal`.
+#[repr(C)]
+pub struct TextureSwizzleChannels {
+    red: u8,
+    green: u8,
+    blue: u8,
+    alpha: u8,
+}
+
+pub fn map_component_mapping(mapping: wgt::ComponentMapping) -> TextureSwizzleChannels {
+    // Values of `MTLTextureSwizzle`.
+    fn map_swizzle(swizzle: wgt::ComponentSwizzle) -> u8 {
+        match swizzle {
+            wgt::ComponentSwizzle::Zero => 0,
+            wgt::ComponentSwizzle::One => 1,
+            wgt::ComponentSwizzle::R => 2,
+            wgt::ComponentSwizzle::G => 3,
+            wgt::ComponentSwizzle::B => 4,
+            wgt::ComponentSwizzle::A => 5,
+        }
+    }
+    TextureSwizzleChannels {
+        red: map_swizzle(mapping.r),
+        green: map_swizzle(mapping.g),
+        blue: map_swizzle(mapping.b),
+        alpha: map_swizzle(mapping.a),
+    }
+}
+
 pub fn map_clear_color(color: &wgt::Color) -> metal::MTLClearColor {
     metal::MTLClearColor {
         red: color.r,
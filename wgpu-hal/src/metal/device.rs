@@ -11,6 +11,19 @@ use crate::auxil::map_naga_stage;
 
 type DeviceResult<T> = Result<T, crate::DeviceError>;
 
+/// Returns a unique file to load or serialize a binary archive, which Metal
+/// can only do with files.
+fn binary_archive_path() -> (std::path::PathBuf, metal::URL) {
+    static COUNTER: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "wgpu-pipeline-cache-{}-{}.metallib",
+        std::process::id(),
+        COUNTER.fetch_add(1, atomic::Ordering::Relaxed)
+    ));
+    let url = metal::URL::new_with_string(&format!("file://{}", path.display()));
+    (path, url)
+}
+
 struct CompiledShader {
     library: metal::Library,
     function: metal::Function,
@@ -105,6 +118,8 @@ impl super::Device {
                 binding_array: naga::proc::BoundsCheckPolicy::Unchecked,
             },
             zero_initialize_workgroup_memory: true,
+            argument_buffers: false,
+            readable_names: false,
         };
 
         let pipeline_options = naga::back::msl::PipelineOptions {
@@ -142,7 +157,12 @@ impl super::Device {
             .lock()
             .new_library_with_source(source.as_ref(), &options)
             .map_err(|err| {
-                log::warn!("Naga generated shader:\n{}", source);
+                crate::auxil::debug_message::report_or_log(
+                    wgt::Backend::Metal,
+                    log::Level::Warn,
+                    &format!("Naga generated shader:\n{}", source),
+                    Vec::new(),
+                );
                 crate::PipelineError::Linkage(stage_bit, format!("Metal: {}", err))
             })?;
 
@@ -163,7 +183,12 @@ impl super::Device {
         };
 
         let function = library.get_function(ep_name, None).map_err(|e| {
-            log::error!("get_function: {:?}", e);
+            crate::auxil::debug_message::report_or_log(
+                wgt::Backend::Metal,
+                log::Level::Error,
+                &format!("get_function: {:?}", e),
+                Vec::new(),
+            );
             crate::PipelineError::EntryPoint(naga_stage)
         })?;
 
@@ -276,6 +301,8 @@ impl super::Device {
 impl crate::Device<super::Api> for super::Device {
     unsafe fn exit(self, _queue: super::Queue) {}
 
+    unsafe fn destroy_queue(&self, _queue: super::Queue) {}
+
     unsafe fn create_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<super::Buffer> {
         let map_read = desc.usage.contains(crate::BufferUses::MAP_READ);
         let map_write = desc.usage.contains(crate::BufferUses::MAP_WRITE);
@@ -325,6 +352,9 @@ impl crate::Device<super::Api> for super::Device {
     }
     unsafe fn flush_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {}
     unsafe fn invalidate_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {}
+    unsafe fn get_buffer_device_address(&self, _buffer: &super::Buffer) -> wgt::BufferAddress {
+        unimplemented!()
+    }
 
     unsafe fn create_texture(
         &self,
@@ -390,6 +420,9 @@ impl crate::Device<super::Api> for super::Device {
         texture: &super::Texture,
         desc: &crate::TextureViewDescriptor,
     ) -> DeviceResult<super::TextureView> {
+        use metal::foreign_types::ForeignType as _;
+        use objc::{msg_send, sel, sel_impl};
+
         let raw_type = if texture.raw_type == metal::MTLTextureType::D2Multisample {
             texture.raw_type
         } else {
@@ -409,7 +442,8 @@ impl crate::Device<super::Api> for super::Device {
             desc.range
                 .is_full_resource(desc.format, texture.mip_levels, texture.array_layers);
 
-        let raw = if format_equal && type_equal && range_full_resource {
+        let raw = if format_equal && type_equal && range_full_resource && desc.swizzle.is_identity()
+        {
             // Some images are marked as framebuffer-only, and we can't create aliases of them.
             // Also helps working around Metal bugs with aliased array textures.
             texture.raw.to_owned()
@@ -423,19 +457,34 @@ impl crate::Device<super::Api> for super::Device {
                 .array_layer_count
                 .unwrap_or(texture.array_layers - desc.range.base_array_layer);
 
+            let levels = metal::NSRange {
+                location: desc.range.base_mip_level as _,
+                length: mip_level_count as _,
+            };
+            let slices = metal::NSRange {
+                location: desc.range.base_array_layer as _,
+                length: array_layer_count as _,
+            };
+
             objc::rc::autoreleasepool(|| {
-                let raw = texture.raw.new_texture_view_from_slice(
-                    raw_format,
-                    raw_type,
-                    metal::NSRange {
-                        location: desc.range.base_mip_level as _,
-                        length: mip_level_count as _,
-                    },
-                    metal::NSRange {
-                        location: desc.range.base_array_layer as _,
-                        length: array_layer_count as _,
-                    },
-                );
+                let raw = if desc.swizzle.is_identity() {
+                    texture
+                        .raw
+                        .new_texture_view_from_slice(raw_format, raw_type, levels, slices)
+                } else {
+                    let swizzle = conv::map_component_mapping(desc.swizzle);
+                    unsafe {
+                        let raw: *mut metal::MTLTexture = msg_send![
+                            texture.raw.as_ref(),
+                            newTextureViewWithPixelFormat: raw_format
+                            textureType: raw_type
+                            levels: levels
+                            slices: slices
+                            swizzle: swizzle
+                        ];
+                        metal::Texture::from_ptr(raw)
+                    }
+                };
                 if let Some(label) = desc.label {
                     raw.set_label(label);
                 }
@@ -664,7 +713,12 @@ impl crate::Device<super::Api> for super::Device {
                 || info.counters.textures > self.shared.private_caps.max_textures_per_stage
                 || info.counters.samplers > self.shared.private_caps.max_samplers_per_stage
             {
-                log::error!("Resource limit exceeded: {:?}", info);
+                crate::auxil::debug_message::report_or_log(
+                    wgt::Backend::Metal,
+                    log::Level::Error,
+                    &format!("Resource limit exceeded: {:?}", info),
+                    desc.label.map(str::to_string).into_iter().collect(),
+                );
                 return Err(crate::DeviceError::OutOfMemory);
             }
         }
@@ -686,6 +740,7 @@ impl crate::Device<super::Api> for super::Device {
                 .sizes_buffer
                 .map(|buffer_index| buffer_index as naga::back::msl::Slot),
             resources: info.resources,
+            argument_buffers: Default::default(),
         });
 
         Ok(super::PipelineLayout {
@@ -992,6 +1047,10 @@ impl crate::Device<super::Api> for super::Device {
                 descriptor.set_label(name);
             }
 
+            if let Some(cache) = desc.cache {
+                descriptor.set_binary_archives(&[&*cache.raw]);
+            }
+
             let raw = self
                 .shared
                 .device
@@ -1004,6 +1063,15 @@ impl crate::Device<super::Api> for super::Device {
                     )
                 })?;
 
+            if let Some(cache) = desc.cache {
+                if let Err(e) = cache
+                    .raw
+                    .add_render_pipeline_functions_with_descriptor(&descriptor)
+                {
+                    log::warn!("Unable to add the render pipeline to the cache: {}", e);
+                }
+            }
+
             Ok(super::RenderPipeline {
                 raw,
                 vs_lib,
@@ -1027,6 +1095,12 @@ impl crate::Device<super::Api> for super::Device {
             })
         })
     }
+    unsafe fn create_mesh_pipeline(
+        &self,
+        _desc: &crate::MeshPipelineDescriptor<super::Api>,
+    ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        unreachable!()
+    }
     unsafe fn destroy_render_pipeline(&self, _pipeline: super::RenderPipeline) {}
 
     unsafe fn create_compute_pipeline(
@@ -1061,6 +1135,10 @@ impl crate::Device<super::Api> for super::Device {
                 descriptor.set_label(name);
             }
 
+            if let Some(cache) = desc.cache {
+                descriptor.set_binary_archives(&[&*cache.raw]);
+            }
+
             let raw = self
                 .shared
                 .device
@@ -1073,6 +1151,15 @@ impl crate::Device<super::Api> for super::Device {
                     )
                 })?;
 
+            if let Some(cache) = desc.cache {
+                if let Err(e) = cache
+                    .raw
+                    .add_compute_pipeline_functions_with_descriptor(&descriptor)
+                {
+                    log::warn!("Unable to add the compute pipeline to the cache: {}", e);
+                }
+            }
+
             Ok(super::ComputePipeline {
                 raw,
                 cs_info,
@@ -1084,6 +1171,62 @@ impl crate::Device<super::Api> for super::Device {
     }
     unsafe fn destroy_compute_pipeline(&self, _pipeline: super::ComputePipeline) {}
 
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<super::PipelineCache, crate::PipelineCacheError> {
+        objc::rc::autoreleasepool(|| {
+            let descriptor = metal::BinaryArchiveDescriptor::new();
+            let path = match desc.data {
+                Some(data) => {
+                    let (path, url) = binary_archive_path();
+                    if let Err(e) = std::fs::write(&path, data) {
+                        log::error!("Unable to write the pipeline cache data: {}", e);
+                        return Err(crate::DeviceError::ResourceCreationFailed.into());
+                    }
+                    descriptor.set_url(&url);
+                    Some(path)
+                }
+                None => None,
+            };
+
+            let result = self
+                .shared
+                .device
+                .lock()
+                .new_binary_archive_with_descriptor(&descriptor);
+            if let Some(path) = path {
+                let _ = std::fs::remove_file(path);
+            }
+
+            match result {
+                Ok(raw) => Ok(super::PipelineCache { raw }),
+                Err(e) if desc.data.is_some() => {
+                    log::warn!("Unable to load the pipeline cache data: {}", e);
+                    Err(crate::PipelineCacheError::Validation)
+                }
+                Err(e) => {
+                    log::error!("new_binary_archive_with_descriptor: {}", e);
+                    Err(crate::DeviceError::OutOfMemory.into())
+                }
+            }
+        })
+    }
+    unsafe fn destroy_pipeline_cache(&self, _cache: super::PipelineCache) {}
+    unsafe fn pipeline_cache_get_data(&self, cache: &super::PipelineCache) -> Option<Vec<u8>> {
+        objc::rc::autoreleasepool(|| {
+            let (path, url) = binary_archive_path();
+            if let Err(e) = cache.raw.serialize_to_url(&url) {
+                log::error!("Unable to serialize the pipeline cache: {}", e);
+                return None;
+            }
+            let data = std::fs::read(&path);
+            let _ = std::fs::remove_file(path);
+            data.map_err(|e| log::error!("Unable to read the pipeline cache data: {}", e))
+                .ok()
+        })
+    }
+
     unsafe fn create_query_set(
         &self,
         desc: &wgt::QuerySetDescriptor<crate::Label>,
@@ -1150,15 +1293,171 @@ impl crate::Device<super::Api> for super::Device {
         })
     }
     unsafe fn destroy_query_set(&self, _set: super::QuerySet) {}
+    unsafe fn create_tile_pool(
+        &self,
+        _desc: &wgt::TilePoolDescriptor<crate::Label>,
+    ) -> DeviceResult<super::TilePool> {
+        unimplemented!()
+    }
+    unsafe fn destroy_tile_pool(&self, _pool: super::TilePool) {}
+    unsafe fn create_memory_heap(
+        &self,
+        _desc: &wgt::MemoryHeapDescriptor<crate::Label>,
+    ) -> DeviceResult<super::MemoryHeap> {
+        unimplemented!()
+    }
+    unsafe fn destroy_memory_heap(&self, _heap: super::MemoryHeap) {}
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        _desc: &crate::BufferDescriptor,
+    ) -> DeviceResult<wgt::MemoryRequirements> {
+        unimplemented!()
+    }
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<wgt::MemoryRequirements> {
+        unimplemented!()
+    }
+    unsafe fn create_placed_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+        _heap: &super::MemoryHeap,
+        _offset: wgt::BufferAddress,
+    ) -> DeviceResult<super::Buffer> {
+        unimplemented!()
+    }
+    unsafe fn create_placed_texture(
+        &self,
+        _desc: &crate::TextureDescriptor,
+        _heap: &super::MemoryHeap,
+        _offset: wgt::BufferAddress,
+    ) -> DeviceResult<super::Texture> {
+        unimplemented!()
+    }
+
+    unsafe fn import_external_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        source: wgt::ExternalImageSource,
+    ) -> DeviceResult<super::Texture> {
+        use metal::foreign_types::ForeignType as _;
+        use objc::{msg_send, sel, sel_impl};
+
+        let (surface, plane) = match source {
+            wgt::ExternalImageSource::IoSurface { surface, plane } => (surface, plane),
+            _ => return Err(crate::DeviceError::ResourceCreationFailed),
+        };
+        let mtl_format = self.shared.private_caps.map_format(desc.format);
+
+        objc::rc::autoreleasepool(|| {
+            let descriptor = metal::TextureDescriptor::new();
+            descriptor.set_texture_type(metal::MTLTextureType::D2);
+            descriptor.set_width(desc.size.width as u64);
+            descriptor.set_height(desc.size.height as u64);
+            descriptor.set_pixel_format(mtl_format);
+            descriptor.set_usage(conv::map_texture_usage(desc.format, desc.usage));
+
+            let raw = unsafe {
+                let raw: *mut metal::MTLTexture = msg_send![
+                    self.shared.device.lock().as_ref(),
+                    newTextureWithDescriptor: descriptor.as_ref()
+                    iosurface: surface
+                    plane: plane as u64
+                ];
+                if raw.is_null() {
+                    return Err(crate::DeviceError::ResourceCreationFailed);
+                }
+                metal::Texture::from_ptr(raw)
+            };
+            if let Some(label) = desc.label {
+                raw.set_label(label);
+            }
+
+            Ok(super::Texture {
+                raw,
+                format: desc.format,
+                raw_type: metal::MTLTextureType::D2,
+                mip_levels: 1,
+                array_layers: 1,
+                copy_size: desc.copy_extent(),
+            })
+        })
+    }
+
+    unsafe fn create_external_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+    ) -> DeviceResult<super::Buffer> {
+        unimplemented!()
+    }
+    unsafe fn import_external_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+        _memory: wgt::ExternalMemory,
+    ) -> DeviceResult<super::Buffer> {
+        unimplemented!()
+    }
+    unsafe fn export_external_buffer(
+        &self,
+        _buffer: &super::Buffer,
+    ) -> DeviceResult<wgt::ExternalMemory> {
+        unimplemented!()
+    }
+
+    unsafe fn create_external_fence(&self) -> DeviceResult<super::Fence> {
+        let shared_event = self.shared.device.lock().new_shared_event();
+        Ok(super::Fence {
+            completed_value: Arc::new(atomic::AtomicU64::new(0)),
+            pending_command_buffers: Vec::new(),
+            shared_event: Some(shared_event),
+        })
+    }
+
+    unsafe fn import_external_fence(
+        &self,
+        handle: wgt::ExternalFenceHandle,
+    ) -> DeviceResult<super::Fence> {
+        use metal::foreign_types::ForeignTypeRef as _;
+
+        let raw = match handle {
+            wgt::ExternalFenceHandle::MetalSharedEvent(raw) if !raw.is_null() => raw,
+            _ => return Err(crate::DeviceError::ResourceCreationFailed),
+        };
+        let shared_event = unsafe { metal::SharedEventRef::from_ptr(raw.cast()) }.to_owned();
+        Ok(super::Fence {
+            completed_value: Arc::new(atomic::AtomicU64::new(0)),
+            pending_command_buffers: Vec::new(),
+            shared_event: Some(shared_event),
+        })
+    }
+
+    unsafe fn export_external_fence(
+        &self,
+        fence: &super::Fence,
+    ) -> DeviceResult<wgt::ExternalFenceHandle> {
+        use metal::foreign_types::ForeignType as _;
+
+        match fence.shared_event {
+            Some(ref shared_event) => Ok(wgt::ExternalFenceHandle::MetalSharedEvent(
+                shared_event.as_ptr().cast(),
+            )),
+            None => Err(crate::DeviceError::ResourceCreationFailed),
+        }
+    }
 
     unsafe fn create_fence(&self) -> DeviceResult<super::Fence> {
         Ok(super::Fence {
             completed_value: Arc::new(atomic::AtomicU64::new(0)),
             pending_command_buffers: Vec::new(),
+            shared_event: None,
         })
     }
     unsafe fn destroy_fence(&self, _fence: super::Fence) {}
     unsafe fn get_fence_value(&self, fence: &super::Fence) -> DeviceResult<crate::FenceValue> {
+        if let Some(ref shared_event) = fence.shared_event {
+            return Ok(shared_event.signaled_value());
+        }
         let mut max_value = fence.completed_value.load(atomic::Ordering::Acquire);
         for &(value, ref cmd_buf) in fence.pending_command_buffers.iter() {
             if cmd_buf.status() == metal::MTLCommandBufferStatus::Completed {
@@ -1184,7 +1483,12 @@ impl crate::Device<super::Api> for super::Device {
         {
             Some(&(_, ref cmd_buf)) => cmd_buf,
             None => {
-                log::error!("No active command buffers for fence value {}", wait_value);
+                crate::auxil::debug_message::report_or_log(
+                    wgt::Backend::Metal,
+                    log::Level::Error,
+                    &format!("No active command buffers for fence value {}", wait_value),
+                    Vec::new(),
+                );
                 return Err(crate::DeviceError::Lost);
             }
         };
@@ -66,6 +66,9 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type PipelineCache = PipelineCache;
+    type TilePool = TilePool;
+    type MemoryHeap = MemoryHeap;
 
     type AccelerationStructure = AccelerationStructure;
 }
@@ -78,6 +81,30 @@ impl Instance {
     pub fn create_surface_from_layer(&self, layer: &metal::MetalLayerRef) -> Surface {
         unsafe { Surface::from_layer(layer) }
     }
+
+    /// Exposes a device created outside of wgpu as an adapter.
+    pub fn expose_adapter_from_raw(&self, device: &metal::DeviceRef) -> crate::ExposedAdapter<Api> {
+        Self::expose_adapter(device.to_owned())
+    }
+
+    fn expose_adapter(device: metal::Device) -> crate::ExposedAdapter<Api> {
+        let name = device.name().into();
+        let shared = AdapterShared::new(device);
+        crate::ExposedAdapter {
+            info: wgt::AdapterInfo {
+                name,
+                vendor: 0,
+                device: 0,
+                device_type: shared.private_caps.device_type(),
+                driver: String::new(),
+                driver_info: String::new(),
+                backend: wgt::Backend::Metal,
+            },
+            features: shared.private_caps.features(),
+            capabilities: shared.private_caps.capabilities(),
+            adapter: Adapter::new(Arc::new(shared)),
+        }
+    }
 }
 
 impl crate::Instance<Api> for Instance {
@@ -120,27 +147,8 @@ impl crate::Instance<Api> for Instance {
 
     unsafe fn enumerate_adapters(&self) -> Vec<crate::ExposedAdapter<Api>> {
         let devices = metal::Device::all();
-        let mut adapters: Vec<crate::ExposedAdapter<Api>> = devices
-            .into_iter()
-            .map(|dev| {
-                let name = dev.name().into();
-                let shared = AdapterShared::new(dev);
-                crate::ExposedAdapter {
-                    info: wgt::AdapterInfo {
-                        name,
-                        vendor: 0,
-                        device: 0,
-                        device_type: shared.private_caps.device_type(),
-                        driver: String::new(),
-                        driver_info: String::new(),
-                        backend: wgt::Backend::Metal,
-                    },
-                    features: shared.private_caps.features(),
-                    capabilities: shared.private_caps.capabilities(),
-                    adapter: Adapter::new(Arc::new(shared)),
-                }
-            })
-            .collect();
+        let mut adapters: Vec<crate::ExposedAdapter<Api>> =
+            devices.into_iter().map(Self::expose_adapter).collect();
         adapters.sort_by_key(|ad| {
             (
                 ad.adapter.shared.private_caps.low_power,
@@ -192,6 +200,9 @@ struct PrivateCapabilities {
     layered_rendering: bool,
     function_specialization: bool,
     depth_clip_mode: bool,
+    depth_stencil_resolve: bool,
+    supports_texture_swizzle: bool,
+    supports_shared_events: bool,
     texture_cube_array: bool,
     supports_float_filtering: bool,
     format_depth24_stencil8: bool,
@@ -441,6 +452,62 @@ impl crate::Queue<Api> for Queue {
     unsafe fn get_timestamp_period(&self) -> f32 {
         self.timestamp_period
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        // `MTLDevice::sampleTimestamps` doesn't document the clock of its CPU
+        // timestamps, so they can't be matched to presentation timestamps.
+        None
+    }
+
+    unsafe fn bind_texture_tiles(
+        &self,
+        _texture: &Texture,
+        _bindings: &[crate::TextureTileBinding<Api>],
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        // There is a single queue, so all work is already ordered, except with
+        // the work of other APIs that signal external fences.
+        if let Some(ref shared_event) = fence.shared_event {
+            objc::rc::autoreleasepool(|| {
+                let raw = self
+                    .raw
+                    .lock()
+                    .new_command_buffer_with_unretained_references();
+                raw.set_label("(wgpu internal) Wait");
+                raw.encode_wait_for_event(shared_event, value);
+                raw.commit();
+            });
+        }
+        Ok(())
+    }
+
+    unsafe fn signal_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        let shared_event = fence
+            .shared_event
+            .as_ref()
+            .expect("Fence is not an external fence");
+        objc::rc::autoreleasepool(|| {
+            let raw = self
+                .raw
+                .lock()
+                .new_command_buffer_with_unretained_references();
+            raw.set_label("(wgpu internal) Signal");
+            raw.encode_signal_event(shared_event, value);
+            raw.commit();
+        });
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -533,6 +600,11 @@ impl<T> ops::Index<naga::ShaderStage> for MultiStageData<T> {
             naga::ShaderStage::Vertex => &self.vs,
             naga::ShaderStage::Fragment => &self.fs,
             naga::ShaderStage::Compute => &self.cs,
+            naga::ShaderStage::Task
+            | naga::ShaderStage::Mesh
+            | naga::ShaderStage::Geometry
+            | naga::ShaderStage::TessellationControl
+            | naga::ShaderStage::TessellationEvaluation => unreachable!(),
         }
     }
 }
@@ -732,6 +804,14 @@ pub struct ComputePipeline {
 unsafe impl Send for ComputePipeline {}
 unsafe impl Sync for ComputePipeline {}
 
+#[derive(Debug)]
+pub struct PipelineCache {
+    raw: metal::BinaryArchive,
+}
+
+unsafe impl Send for PipelineCache {}
+unsafe impl Sync for PipelineCache {}
+
 #[derive(Debug, Clone)]
 pub struct QuerySet {
     raw_buffer: metal::Buffer,
@@ -748,6 +828,8 @@ pub struct Fence {
     completed_value: Arc<atomic::AtomicU64>,
     /// The pending fence values have to be ascending.
     pending_command_buffers: Vec<(crate::FenceValue, metal::CommandBuffer)>,
+    /// The event shared with other APIs by external fences, which holds their value.
+    shared_event: Option<metal::SharedEvent>,
 }
 
 unsafe impl Send for Fence {}
@@ -850,3 +932,12 @@ unsafe impl Sync for CommandBuffer {}
 
 #[derive(Debug)]
 pub struct AccelerationStructure;
+
+/// `Features::TILED_TEXTURE` isn't supported yet, it needs sparse heaps and
+/// `MTLResourceStateCommandEncoder`.
+#[derive(Debug)]
+pub struct TilePool;
+
+/// `Features::MEMORY_HEAPS` isn't supported yet, it needs placement heaps.
+#[derive(Debug)]
+pub struct MemoryHeap;
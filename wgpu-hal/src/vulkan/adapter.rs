@@ -1,13 +1,12 @@
 use super::conv;
 
-use ash::{extensions::khr, vk};
+use ash::{
+    extensions::{ext, khr},
+    vk,
+};
 use parking_lot::Mutex;
 
-use std::{
-    collections::BTreeMap,
-    ffi::CStr,
-    sync::{atomic::AtomicIsize, Arc},
-};
+use std::{collections::BTreeMap, ffi::CStr, mem, sync::Arc};
 
 fn depth_stencil_required_flags() -> vk::FormatFeatureFlags {
     vk::FormatFeatureFlags::SAMPLED_IMAGE | vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT
@@ -41,6 +40,11 @@ pub struct PhysicalDeviceFeatures {
     ray_query: Option<vk::PhysicalDeviceRayQueryFeaturesKHR>,
     zero_initialize_workgroup_memory:
         Option<vk::PhysicalDeviceZeroInitializeWorkgroupMemoryFeatures>,
+    device_generated_commands: Option<vk::PhysicalDeviceDeviceGeneratedCommandsFeaturesNV>,
+    mesh_shader: Option<vk::PhysicalDeviceMeshShaderFeaturesEXT>,
+    conditional_rendering: Option<vk::PhysicalDeviceConditionalRenderingFeaturesEXT>,
+    transform_feedback: Option<vk::PhysicalDeviceTransformFeedbackFeaturesEXT>,
+    fragment_shading_rate: Option<vk::PhysicalDeviceFragmentShadingRateFeaturesKHR>,
 }
 
 // This is safe because the structs have `p_next: *mut c_void`, which we null out/never read.
@@ -88,6 +92,21 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self.ray_query {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self.device_generated_commands {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.mesh_shader {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.conditional_rendering {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.transform_feedback {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.fragment_shading_rate {
+            info = info.push_next(feature);
+        }
         info
     }
 
@@ -138,9 +157,9 @@ impl PhysicalDeviceFeatures {
                     requested_features.contains(wgt::Features::INDIRECT_FIRST_INSTANCE),
                 )
                 //.dual_src_blend(requested_features.contains(wgt::Features::DUAL_SRC_BLENDING))
-                .multi_draw_indirect(
-                    requested_features.contains(wgt::Features::MULTI_DRAW_INDIRECT),
-                )
+                .multi_draw_indirect(requested_features.intersects(
+                    wgt::Features::MULTI_DRAW_INDIRECT | wgt::Features::MULTI_DRAW_INDIRECT_COUNT,
+                ))
                 .fill_mode_non_solid(requested_features.intersects(
                     wgt::Features::POLYGON_MODE_LINE | wgt::Features::POLYGON_MODE_POINT,
                 ))
@@ -189,12 +208,17 @@ impl PhysicalDeviceFeatures {
                 //.shader_clip_distance(requested_features.contains(wgt::Features::SHADER_CLIP_DISTANCE))
                 //.shader_cull_distance(requested_features.contains(wgt::Features::SHADER_CULL_DISTANCE))
                 .shader_float64(requested_features.contains(wgt::Features::SHADER_F64))
-                //.shader_int64(requested_features.contains(wgt::Features::SHADER_INT64))
+                .shader_int64(requested_features.contains(wgt::Features::SHADER_INT64))
                 .shader_int16(requested_features.contains(wgt::Features::SHADER_I16))
                 //.shader_resource_residency(requested_features.contains(wgt::Features::SHADER_RESOURCE_RESIDENCY))
                 .geometry_shader(requested_features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX))
                 .depth_clamp(requested_features.contains(wgt::Features::DEPTH_CLIP_CONTROL))
                 .dual_src_blend(requested_features.contains(wgt::Features::DUAL_SOURCE_BLENDING))
+                .sparse_binding(requested_features.contains(wgt::Features::TILED_TEXTURE))
+                .sparse_residency_image2_d(
+                    requested_features.contains(wgt::Features::TILED_TEXTURE),
+                )
+                .sparse_residency_aliased(requested_features.contains(wgt::Features::TILED_TEXTURE))
                 .build(),
             descriptor_indexing: if requested_features.intersects(indexing_features()) {
                 Some(
@@ -212,6 +236,9 @@ impl PhysicalDeviceFeatures {
                             needs_storage_buffer_non_uniform,
                         )
                         .descriptor_binding_partially_bound(needs_partially_bound)
+                        .descriptor_binding_sampled_image_update_after_bind(needs_partially_bound)
+                        .descriptor_binding_storage_image_update_after_bind(needs_partially_bound)
+                        .descriptor_binding_storage_buffer_update_after_bind(needs_partially_bound)
                         .build(),
                 )
             } else {
@@ -351,6 +378,64 @@ impl PhysicalDeviceFeatures {
             } else {
                 None
             },
+            device_generated_commands: if enabled_extensions
+                .contains(&vk::NvDeviceGeneratedCommandsFn::name())
+            {
+                Some(
+                    vk::PhysicalDeviceDeviceGeneratedCommandsFeaturesNV::builder()
+                        .device_generated_commands(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
+            mesh_shader: if enabled_extensions.contains(&vk::ExtMeshShaderFn::name()) {
+                Some(
+                    vk::PhysicalDeviceMeshShaderFeaturesEXT::builder()
+                        .task_shader(true)
+                        .mesh_shader(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
+            conditional_rendering: if enabled_extensions
+                .contains(&vk::ExtConditionalRenderingFn::name())
+            {
+                Some(
+                    vk::PhysicalDeviceConditionalRenderingFeaturesEXT::builder()
+                        .conditional_rendering(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
+            transform_feedback: if enabled_extensions.contains(&vk::ExtTransformFeedbackFn::name())
+            {
+                Some(
+                    vk::PhysicalDeviceTransformFeedbackFeaturesEXT::builder()
+                        .transform_feedback(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
+            fragment_shading_rate: if enabled_extensions
+                .contains(&vk::KhrFragmentShadingRateFn::name())
+            {
+                Some(
+                    vk::PhysicalDeviceFragmentShadingRateFeaturesKHR::builder()
+                        .pipeline_fragment_shading_rate(
+                            requested_features.contains(wgt::Features::SHADING_RATE),
+                        )
+                        .attachment_fragment_shading_rate(
+                            requested_features.contains(wgt::Features::SHADING_RATE_ATTACHMENT),
+                        )
+                        .build(),
+                )
+            } else {
+                None
+            },
         }
     }
 
@@ -365,6 +450,7 @@ impl PhysicalDeviceFeatures {
         let mut features = F::empty()
             | F::SPIRV_SHADER_PASSTHROUGH
             | F::MAPPABLE_PRIMARY_BUFFERS
+            | F::PIPELINE_CACHE
             | F::PUSH_CONSTANTS
             | F::ADDRESS_MODE_CLAMP_TO_BORDER
             | F::ADDRESS_MODE_CLAMP_TO_ZERO
@@ -468,14 +554,17 @@ impl PhysicalDeviceFeatures {
         //if self.core.shader_clip_distance != 0 {
         //if self.core.shader_cull_distance != 0 {
         features.set(F::SHADER_F64, self.core.shader_float64 != 0);
-        //if self.core.shader_int64 != 0 {
+        features.set(F::SHADER_INT64, self.core.shader_int64 != 0);
         features.set(F::SHADER_I16, self.core.shader_int16 != 0);
 
         //if caps.supports_extension(vk::KhrSamplerMirrorClampToEdgeFn::name()) {
         //if caps.supports_extension(vk::ExtSamplerFilterMinmaxFn::name()) {
+        // Without `multiDrawIndirect`, `maxDrawIndirectCount` is 1, which would
+        // cap `max_count` to a single draw.
         features.set(
             F::MULTI_DRAW_INDIRECT_COUNT,
-            caps.supports_extension(vk::KhrDrawIndirectCountFn::name()),
+            caps.supports_extension(vk::KhrDrawIndirectCountFn::name())
+                && self.core.multi_draw_indirect != 0,
         );
         features.set(
             F::CONSERVATIVE_RASTERIZATION,
@@ -516,7 +605,12 @@ impl PhysicalDeviceFeatures {
             ) {
                 features.insert(F::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING);
             }
-            if descriptor_indexing.descriptor_binding_partially_bound != 0 && !intel_windows {
+            if descriptor_indexing.descriptor_binding_partially_bound != 0
+                && descriptor_indexing.descriptor_binding_sampled_image_update_after_bind != 0
+                && descriptor_indexing.descriptor_binding_storage_image_update_after_bind != 0
+                && descriptor_indexing.descriptor_binding_storage_buffer_update_after_bind != 0
+                && !intel_windows
+            {
                 features |= F::PARTIALLY_BOUND_BINDING_ARRAY;
             }
         }
@@ -575,17 +669,127 @@ impl PhysicalDeviceFeatures {
         features.set(F::DEPTH32FLOAT_STENCIL8, texture_d32_s8);
 
         features.set(
-            F::RAY_TRACING_ACCELERATION_STRUCTURE,
+            F::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE,
             caps.supports_extension(vk::KhrDeferredHostOperationsFn::name())
                 && caps.supports_extension(vk::KhrAccelerationStructureFn::name())
                 && caps.supports_extension(vk::KhrBufferDeviceAddressFn::name()),
         );
 
         features.set(
-            F::RAY_QUERY,
+            F::EXPERIMENTAL_RAY_QUERY,
             caps.supports_extension(vk::KhrRayQueryFn::name()),
         );
 
+        features.set(
+            F::DEVICE_GENERATED_COMMANDS,
+            self.device_generated_commands
+                .map_or(false, |f| f.device_generated_commands != 0)
+                && caps.supports_extension(vk::KhrBufferDeviceAddressFn::name()),
+        );
+
+        features.set(
+            F::EXPERIMENTAL_MESH_SHADER,
+            self.mesh_shader
+                .map_or(false, |f| f.task_shader != 0 && f.mesh_shader != 0),
+        );
+
+        features.set(
+            F::CONDITIONAL_RENDERING,
+            self.conditional_rendering
+                .map_or(false, |f| f.conditional_rendering != 0),
+        );
+
+        features.set(
+            F::VERTEX_CAPTURE,
+            self.transform_feedback
+                .map_or(false, |f| f.transform_feedback != 0)
+                && caps.transform_feedback.map_or(false, |p| {
+                    p.max_transform_feedback_buffers >= wgt::MAX_VERTEX_CAPTURE_BUFFERS
+                        && p.max_transform_feedback_buffer_data_stride as u64
+                            >= wgt::MAX_VERTEX_CAPTURE_STRIDE
+                }),
+        );
+
+        // Shading rate attachments are only available through `vkCreateRenderPass2`.
+        let supports_render_pass2 = caps.device_api_version >= vk::API_VERSION_1_2;
+        features.set(
+            F::SHADING_RATE,
+            self.fragment_shading_rate
+                .map_or(false, |f| f.pipeline_fragment_shading_rate != 0)
+                && supports_render_pass2,
+        );
+        features.set(
+            F::SHADING_RATE_ATTACHMENT,
+            self.fragment_shading_rate
+                .map_or(false, |f| f.attachment_fragment_shading_rate != 0)
+                && caps.shading_rate_tile_size() != 0,
+        );
+        features.set(
+            F::DEPTH_STENCIL_RESOLVE,
+            caps.depth_stencil_resolve.is_some(),
+        );
+
+        // Tiles are bound on the queue of the first family, and their
+        // `TEXTURE_TILE_SIZE` matches the standard block shapes.
+        let sparse_binding_queue =
+            unsafe { instance.get_physical_device_queue_family_properties(phd) }
+                .first()
+                .map_or(false, |family| {
+                    family.queue_flags.contains(vk::QueueFlags::SPARSE_BINDING)
+                });
+        let sparse_properties = caps.properties.sparse_properties;
+        features.set(
+            F::TILED_TEXTURE,
+            self.core.sparse_binding != 0
+                && self.core.sparse_residency_image2_d != 0
+                && self.core.sparse_residency_aliased != 0
+                && sparse_properties.residency_standard2_d_block_shape != 0
+                && sparse_properties.residency_non_resident_strict != 0
+                && sparse_binding_queue,
+        );
+
+        // Memory can always be bound to resources at an offset.
+        features.insert(F::MEMORY_HEAPS);
+
+        // `imageViewFormatSwizzle` of `VK_KHR_portability_subset` isn't queried,
+        // so swizzles are only exposed by fully conformant implementations.
+        features.set(
+            F::TEXTURE_COMPONENT_SWIZZLE,
+            !caps.supports_extension(vk::KhrPortabilitySubsetFn::name()),
+        );
+
+        // dma-bufs are imported with an explicit DRM format modifier, whose
+        // extension depends on `VK_KHR_image_format_list` before Vulkan 1.2.
+        features.set(
+            F::EXTERNAL_TEXTURE_IMPORT,
+            cfg!(unix)
+                && caps.device_api_version >= vk::API_VERSION_1_1
+                && (caps.device_api_version >= vk::API_VERSION_1_2
+                    || caps.supports_extension(vk::KhrImageFormatListFn::name()))
+                && caps.supports_extension(vk::KhrExternalMemoryFdFn::name())
+                && caps.supports_extension(vk::ExtExternalMemoryDmaBufFn::name())
+                && caps.supports_extension(vk::ExtImageDrmFormatModifierFn::name()),
+        );
+
+        // External fences are always timeline semaphores, shared as opaque fds.
+        features.set(
+            F::EXTERNAL_FENCE,
+            cfg!(unix)
+                && caps.device_api_version >= vk::API_VERSION_1_1
+                && self
+                    .timeline_semaphore
+                    .map_or(false, |ext| ext.timeline_semaphore != 0)
+                && caps.supports_extension(vk::KhrExternalSemaphoreFdFn::name()),
+        );
+
+        // Buffer memory is shared as opaque fds, the handle type CUDA and HIP import.
+        features.set(
+            F::EXTERNAL_MEMORY,
+            cfg!(unix)
+                && caps.device_api_version >= vk::API_VERSION_1_1
+                && caps.supports_extension(vk::KhrExternalMemoryFdFn::name()),
+        );
+
         let rg11b10ufloat_renderable = supports_format(
             instance,
             phd,
@@ -623,6 +827,21 @@ impl PhysicalDeviceFeatures {
                     .map(|driver| driver.driver_id == vk::DriverId::MOLTENVK)
                     .unwrap_or_default(),
             );
+            features.set(
+                F::TEXTURE_FORMAT_P010,
+                supports_format(
+                    instance,
+                    phd,
+                    vk::Format::G16_B16R16_2PLANE_420_UNORM,
+                    vk::ImageTiling::OPTIMAL,
+                    vk::FormatFeatureFlags::SAMPLED_IMAGE
+                        | vk::FormatFeatureFlags::TRANSFER_SRC
+                        | vk::FormatFeatureFlags::TRANSFER_DST,
+                ) && !caps
+                    .driver
+                    .map(|driver| driver.driver_id == vk::DriverId::MOLTENVK)
+                    .unwrap_or_default(),
+            );
         }
 
         (features, dl_flags)
@@ -647,6 +866,9 @@ pub struct PhysicalDeviceCapabilities {
     descriptor_indexing: Option<vk::PhysicalDeviceDescriptorIndexingPropertiesEXT>,
     acceleration_structure: Option<vk::PhysicalDeviceAccelerationStructurePropertiesKHR>,
     driver: Option<vk::PhysicalDeviceDriverPropertiesKHR>,
+    transform_feedback: Option<vk::PhysicalDeviceTransformFeedbackPropertiesEXT>,
+    fragment_shading_rate: Option<vk::PhysicalDeviceFragmentShadingRatePropertiesKHR>,
+    depth_stencil_resolve: Option<vk::PhysicalDeviceDepthStencilResolveProperties>,
     /// The device API version.
     ///
     /// Which is the version of Vulkan supported for device-level functionality.
@@ -709,7 +931,9 @@ impl PhysicalDeviceCapabilities {
             }
 
             // Require `VK_KHR_sampler_ycbcr_conversion` if the associated feature was requested
-            if requested_features.contains(wgt::Features::TEXTURE_FORMAT_NV12) {
+            if requested_features
+                .intersects(wgt::Features::TEXTURE_FORMAT_NV12 | wgt::Features::TEXTURE_FORMAT_P010)
+            {
                 extensions.push(vk::KhrSamplerYcbcrConversionFn::name());
             }
         }
@@ -774,6 +998,11 @@ impl PhysicalDeviceCapabilities {
             extensions.push(vk::ExtRobustness2Fn::name());
         }
 
+        // Optional `VK_EXT_calibrated_timestamps`
+        if self.supports_extension(vk::ExtCalibratedTimestampsFn::name()) {
+            extensions.push(vk::ExtCalibratedTimestampsFn::name());
+        }
+
         // Require `VK_KHR_draw_indirect_count` if the associated feature was requested
         // Even though Vulkan 1.2 has promoted the extension to core, we must require the extension to avoid
         // large amounts of spaghetti involved with using PhysicalDeviceVulkan12Features.
@@ -782,17 +1011,55 @@ impl PhysicalDeviceCapabilities {
         }
 
         // Require `VK_KHR_deferred_host_operations`, `VK_KHR_acceleration_structure` and `VK_KHR_buffer_device_address` if the feature `RAY_TRACING` was requested
-        if requested_features.contains(wgt::Features::RAY_TRACING_ACCELERATION_STRUCTURE) {
+        if requested_features
+            .contains(wgt::Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE)
+        {
             extensions.push(vk::KhrDeferredHostOperationsFn::name());
             extensions.push(vk::KhrAccelerationStructureFn::name());
             extensions.push(vk::KhrBufferDeviceAddressFn::name());
         }
 
         // Require `VK_KHR_ray_query` if the associated feature was requested
-        if requested_features.contains(wgt::Features::RAY_QUERY) {
+        if requested_features.contains(wgt::Features::EXPERIMENTAL_RAY_QUERY) {
             extensions.push(vk::KhrRayQueryFn::name());
         }
 
+        // Require `VK_NV_device_generated_commands` and `VK_KHR_buffer_device_address` if the associated feature was requested
+        if requested_features.contains(wgt::Features::DEVICE_GENERATED_COMMANDS) {
+            extensions.push(vk::NvDeviceGeneratedCommandsFn::name());
+            if !extensions.contains(&vk::KhrBufferDeviceAddressFn::name()) {
+                extensions.push(vk::KhrBufferDeviceAddressFn::name());
+            }
+        }
+
+        // Require `VK_EXT_mesh_shader` if the associated feature was requested, along with
+        // `VK_KHR_spirv_1_4` and its dependency `VK_KHR_shader_float_controls`, which were
+        // promoted to 1.2
+        if requested_features.contains(wgt::Features::EXPERIMENTAL_MESH_SHADER) {
+            extensions.push(vk::ExtMeshShaderFn::name());
+            if self.device_api_version < vk::API_VERSION_1_2 {
+                extensions.push(vk::KhrSpirv14Fn::name());
+                extensions.push(vk::KhrShaderFloatControlsFn::name());
+            }
+        }
+
+        // Require `VK_EXT_conditional_rendering` if the associated feature was requested
+        if requested_features.contains(wgt::Features::CONDITIONAL_RENDERING) {
+            extensions.push(vk::ExtConditionalRenderingFn::name());
+        }
+
+        // Require `VK_EXT_transform_feedback` if the associated feature was requested
+        if requested_features.contains(wgt::Features::VERTEX_CAPTURE) {
+            extensions.push(vk::ExtTransformFeedbackFn::name());
+        }
+
+        // Require `VK_KHR_fragment_shading_rate` if any of the associated features were requested
+        if requested_features
+            .intersects(wgt::Features::SHADING_RATE | wgt::Features::SHADING_RATE_ATTACHMENT)
+        {
+            extensions.push(vk::KhrFragmentShadingRateFn::name());
+        }
+
         // Require `VK_EXT_conservative_rasterization` if the associated feature was requested
         if requested_features.contains(wgt::Features::CONSERVATIVE_RASTERIZATION) {
             extensions.push(vk::ExtConservativeRasterizationFn::name());
@@ -807,6 +1074,25 @@ impl PhysicalDeviceCapabilities {
             extensions.push(vk::ExtTextureCompressionAstcHdrFn::name());
         }
 
+        // Require `VK_KHR_external_memory_fd` if either feature sharing memory was requested
+        if requested_features
+            .intersects(wgt::Features::EXTERNAL_TEXTURE_IMPORT | wgt::Features::EXTERNAL_MEMORY)
+        {
+            extensions.push(vk::KhrExternalMemoryFdFn::name());
+        }
+
+        // Require `VK_EXT_external_memory_dma_buf` and `VK_EXT_image_drm_format_modifier`
+        // if the associated feature was requested
+        if requested_features.contains(wgt::Features::EXTERNAL_TEXTURE_IMPORT) {
+            extensions.push(vk::ExtExternalMemoryDmaBufFn::name());
+            extensions.push(vk::ExtImageDrmFormatModifierFn::name());
+        }
+
+        // Require `VK_KHR_external_semaphore_fd` if the associated feature was requested
+        if requested_features.contains(wgt::Features::EXTERNAL_FENCE) {
+            extensions.push(vk::KhrExternalSemaphoreFdFn::name());
+        }
+
         extensions
     }
 
@@ -827,6 +1113,41 @@ impl PhysicalDeviceCapabilities {
                 u64::MAX
             };
 
+        // Update-after-bind limits also count the descriptors of the other bind group
+        // layouts, which are validated against the regular limits.
+        let (max_binding_array_elements, max_binding_array_sampler_elements) =
+            match self.descriptor_indexing {
+                Some(ref di) => {
+                    let sampled_images = di
+                        .max_per_stage_descriptor_update_after_bind_sampled_images
+                        .min(di.max_descriptor_set_update_after_bind_sampled_images)
+                        .saturating_sub(limits.max_per_stage_descriptor_sampled_images);
+                    let storage_images = di
+                        .max_per_stage_descriptor_update_after_bind_storage_images
+                        .min(di.max_descriptor_set_update_after_bind_storage_images)
+                        .saturating_sub(limits.max_per_stage_descriptor_storage_images);
+                    let storage_buffers = di
+                        .max_per_stage_descriptor_update_after_bind_storage_buffers
+                        .min(di.max_descriptor_set_update_after_bind_storage_buffers)
+                        .saturating_sub(limits.max_per_stage_descriptor_storage_buffers);
+                    let resources = di
+                        .max_per_stage_update_after_bind_resources
+                        .saturating_sub(limits.max_per_stage_resources);
+                    let samplers = di
+                        .max_per_stage_descriptor_update_after_bind_samplers
+                        .min(di.max_descriptor_set_update_after_bind_samplers)
+                        .saturating_sub(limits.max_per_stage_descriptor_samplers);
+                    (
+                        sampled_images
+                            .min(storage_images)
+                            .min(storage_buffers)
+                            .min(resources),
+                        samplers,
+                    )
+                }
+                None => (0, 0),
+            };
+
         wgt::Limits {
             max_texture_dimension_1d: limits.max_image_dimension1_d,
             max_texture_dimension_2d: limits.max_image_dimension2_d,
@@ -870,7 +1191,30 @@ impl PhysicalDeviceCapabilities {
             max_compute_workgroups_per_dimension,
             max_buffer_size,
             max_non_sampler_bindings: std::u32::MAX,
+            max_binding_array_elements_per_shader_stage: max_binding_array_elements,
+            max_binding_array_sampler_elements_per_shader_stage: max_binding_array_sampler_elements,
+            shading_rate_tile_size: self.shading_rate_tile_size(),
+        }
+    }
+
+    /// Returns the smallest square shading rate attachment texel size, or zero if
+    /// shading rate attachments can't be used.
+    fn shading_rate_tile_size(&self) -> u32 {
+        if self.device_api_version < vk::API_VERSION_1_2 {
+            return 0;
         }
+        self.fragment_shading_rate.map_or(0, |properties| {
+            let min = properties.min_fragment_shading_rate_attachment_texel_size;
+            let max = properties.max_fragment_shading_rate_attachment_texel_size;
+            // Texel sizes are powers of two, so the largest minimum dimension is a valid
+            // size for both dimensions if it doesn't exceed their maximums.
+            let size = min.width.max(min.height);
+            if size <= max.width.min(max.height) {
+                size
+            } else {
+                0
+            }
+        })
     }
 
     fn to_hal_alignments(&self) -> crate::Alignments {
@@ -880,6 +1224,12 @@ impl PhysicalDeviceCapabilities {
                 .unwrap(),
             buffer_copy_pitch: wgt::BufferSize::new(limits.optimal_buffer_copy_row_pitch_alignment)
                 .unwrap(),
+            ray_tracing_scratch_buffer_alignment: self.acceleration_structure.map_or(
+                0,
+                |acceleration_structure| {
+                    acceleration_structure.min_acceleration_structure_scratch_offset_alignment
+                },
+            ),
         }
     }
 }
@@ -911,6 +1261,16 @@ impl super::InstanceShared {
                 let supports_acceleration_structure =
                     capabilities.supports_extension(vk::KhrAccelerationStructureFn::name());
 
+                let supports_transform_feedback =
+                    capabilities.supports_extension(vk::ExtTransformFeedbackFn::name());
+
+                let supports_fragment_shading_rate =
+                    capabilities.supports_extension(vk::KhrFragmentShadingRateFn::name());
+
+                // Depth/stencil resolves are only available through `vkCreateRenderPass2`.
+                let supports_depth_stencil_resolve =
+                    capabilities.device_api_version >= vk::API_VERSION_1_2;
+
                 let mut builder = vk::PhysicalDeviceProperties2KHR::builder();
                 if supports_maintenance3 {
                     capabilities.maintenance_3 =
@@ -932,6 +1292,27 @@ impl super::InstanceShared {
                     builder = builder.push_next(next);
                 }
 
+                if supports_transform_feedback {
+                    let next = capabilities
+                        .transform_feedback
+                        .insert(vk::PhysicalDeviceTransformFeedbackPropertiesEXT::default());
+                    builder = builder.push_next(next);
+                }
+
+                if supports_fragment_shading_rate {
+                    let next = capabilities
+                        .fragment_shading_rate
+                        .insert(vk::PhysicalDeviceFragmentShadingRatePropertiesKHR::default());
+                    builder = builder.push_next(next);
+                }
+
+                if supports_depth_stencil_resolve {
+                    let next = capabilities
+                        .depth_stencil_resolve
+                        .insert(vk::PhysicalDeviceDepthStencilResolveProperties::default());
+                    builder = builder.push_next(next);
+                }
+
                 if supports_driver_properties {
                     let next = capabilities
                         .driver
@@ -1041,6 +1422,40 @@ impl super::InstanceShared {
                     .insert(vk::PhysicalDeviceAccelerationStructureFeaturesKHR::default());
                 builder = builder.push_next(next);
             }
+            if capabilities.supports_extension(vk::NvDeviceGeneratedCommandsFn::name()) {
+                let next = features
+                    .device_generated_commands
+                    .insert(vk::PhysicalDeviceDeviceGeneratedCommandsFeaturesNV::default());
+                builder = builder.push_next(next);
+            }
+            if capabilities.supports_extension(vk::ExtMeshShaderFn::name())
+                && (capabilities.device_api_version >= vk::API_VERSION_1_2
+                    || (capabilities.supports_extension(vk::KhrSpirv14Fn::name())
+                        && capabilities.supports_extension(vk::KhrShaderFloatControlsFn::name())))
+            {
+                let next = features
+                    .mesh_shader
+                    .insert(vk::PhysicalDeviceMeshShaderFeaturesEXT::default());
+                builder = builder.push_next(next);
+            }
+            if capabilities.supports_extension(vk::ExtConditionalRenderingFn::name()) {
+                let next = features
+                    .conditional_rendering
+                    .insert(vk::PhysicalDeviceConditionalRenderingFeaturesEXT::default());
+                builder = builder.push_next(next);
+            }
+            if capabilities.supports_extension(vk::ExtTransformFeedbackFn::name()) {
+                let next = features
+                    .transform_feedback
+                    .insert(vk::PhysicalDeviceTransformFeedbackFeaturesEXT::default());
+                builder = builder.push_next(next);
+            }
+            if capabilities.supports_extension(vk::KhrFragmentShadingRateFn::name()) {
+                let next = features
+                    .fragment_shading_rate
+                    .insert(vk::PhysicalDeviceFragmentShadingRateFeaturesKHR::default());
+                builder = builder.push_next(next);
+            }
 
             // `VK_KHR_zero_initialize_workgroup_memory` is promoted to 1.3
             if capabilities.device_api_version >= vk::API_VERSION_1_3
@@ -1244,6 +1659,7 @@ impl super::Instance {
                 }),
             image_format_list: phd_capabilities.device_api_version >= vk::API_VERSION_1_2
                 || phd_capabilities.supports_extension(vk::KhrImageFormatListFn::name()),
+            shading_rate_tile_size: phd_capabilities.shading_rate_tile_size(),
         };
         let capabilities = crate::Capabilities {
             limits: phd_capabilities.to_wgpu_limits(),
@@ -1253,6 +1669,14 @@ impl super::Instance {
                 limits: wgt::DownlevelLimits {},
                 shader_model: wgt::ShaderModel::Sm5, //TODO?
             },
+            // All queues are taken from the first family.
+            max_additional_queues: queue_families[0].queue_count.saturating_sub(1),
+            memory_architecture: match phd_capabilities.properties.device_type {
+                vk::PhysicalDeviceType::INTEGRATED_GPU | vk::PhysicalDeviceType::CPU => {
+                    wgt::MemoryArchitecture::Unified
+                }
+                _ => wgt::MemoryArchitecture::Discrete,
+            },
         };
 
         let adapter = super::Adapter {
@@ -1269,6 +1693,7 @@ impl super::Instance {
             downlevel_flags,
             private_caps,
             workarounds,
+            memory_allocator_config: Mutex::new(super::MemoryAllocatorConfig::default()),
         };
 
         Some(crate::ExposedAdapter {
@@ -1293,6 +1718,12 @@ impl super::Adapter {
         &self.instance
     }
 
+    /// Sets the settings of the memory allocator of the devices opened from this
+    /// adapter afterwards.
+    pub fn set_memory_allocator_config(&self, config: super::MemoryAllocatorConfig) {
+        *self.memory_allocator_config.lock() = config;
+    }
+
     pub fn required_device_extensions(&self, features: wgt::Features) -> Vec<&'static CStr> {
         let (supported_extensions, unsupported_extensions) = self
             .phd_capabilities
@@ -1325,6 +1756,33 @@ impl super::Adapter {
         )
     }
 
+    /// Opens a device from the raw handle of a `VkDevice` created outside of
+    /// wgpu, which stays owned by the caller.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Self::device_from_raw`].
+    pub unsafe fn device_from_raw_handle(
+        &self,
+        raw_device: vk::Device,
+        enabled_extensions: &[&'static CStr],
+        features: wgt::Features,
+        family_index: u32,
+        queue_index: u32,
+    ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
+        let raw_device = unsafe { ash::Device::load(self.instance.raw.fp_v1_0(), raw_device) };
+        unsafe {
+            self.device_from_raw(
+                raw_device,
+                false,
+                enabled_extensions,
+                features,
+                family_index,
+                queue_index,
+            )
+        }
+    }
+
     /// # Safety
     ///
     /// - `raw_device` must be created from this adapter.
@@ -1392,6 +1850,128 @@ impl super::Adapter {
         } else {
             None
         };
+        let device_generated_commands_fns = if enabled_extensions
+            .contains(&vk::NvDeviceGeneratedCommandsFn::name())
+            && enabled_extensions.contains(&khr::BufferDeviceAddress::name())
+        {
+            Some(super::DeviceGeneratedCommandsFunctions {
+                raw: vk::NvDeviceGeneratedCommandsFn::load(|name| unsafe {
+                    mem::transmute(
+                        self.instance
+                            .raw
+                            .get_device_proc_addr(raw_device.handle(), name.as_ptr()),
+                    )
+                }),
+                buffer_device_address: khr::BufferDeviceAddress::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ),
+            })
+        } else {
+            None
+        };
+
+        let mesh_shading_fn = if enabled_extensions.contains(&ext::MeshShader::name()) {
+            Some(ext::MeshShader::new(&self.instance.raw, &raw_device))
+        } else {
+            None
+        };
+
+        let conditional_rendering_fn =
+            if enabled_extensions.contains(&vk::ExtConditionalRenderingFn::name()) {
+                Some(vk::ExtConditionalRenderingFn::load(|name| unsafe {
+                    mem::transmute(
+                        self.instance
+                            .raw
+                            .get_device_proc_addr(raw_device.handle(), name.as_ptr()),
+                    )
+                }))
+            } else {
+                None
+            };
+
+        let transform_feedback_fn =
+            if enabled_extensions.contains(&vk::ExtTransformFeedbackFn::name()) {
+                Some(vk::ExtTransformFeedbackFn::load(|name| unsafe {
+                    mem::transmute(
+                        self.instance
+                            .raw
+                            .get_device_proc_addr(raw_device.handle(), name.as_ptr()),
+                    )
+                }))
+            } else {
+                None
+            };
+
+        let fragment_shading_rate_fn =
+            if enabled_extensions.contains(&vk::KhrFragmentShadingRateFn::name()) {
+                Some(vk::KhrFragmentShadingRateFn::load(|name| unsafe {
+                    mem::transmute(
+                        self.instance
+                            .raw
+                            .get_device_proc_addr(raw_device.handle(), name.as_ptr()),
+                    )
+                }))
+            } else {
+                None
+            };
+
+        let external_memory_fd_fn = if enabled_extensions.contains(&khr::ExternalMemoryFd::name()) {
+            Some(khr::ExternalMemoryFd::new(&self.instance.raw, &raw_device))
+        } else {
+            None
+        };
+
+        let external_semaphore_fd_fn =
+            if enabled_extensions.contains(&khr::ExternalSemaphoreFd::name()) {
+                Some(khr::ExternalSemaphoreFd::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
+
+        // Presentation timestamps come from `CLOCK_MONOTONIC`, which is only
+        // available on unix.
+        let calibrated_timestamps_fn =
+            if cfg!(unix) && enabled_extensions.contains(&vk::ExtCalibratedTimestampsFn::name()) {
+                let raw = vk::ExtCalibratedTimestampsFn::load(|name| unsafe {
+                    mem::transmute(
+                        self.instance
+                            .entry
+                            .get_instance_proc_addr(self.instance.raw.handle(), name.as_ptr()),
+                    )
+                });
+                let mut count = 0;
+                let mut domains = Vec::new();
+                let result = unsafe {
+                    (raw.get_physical_device_calibrateable_time_domains_ext)(
+                        self.raw,
+                        &mut count,
+                        std::ptr::null_mut(),
+                    )
+                };
+                if result == vk::Result::SUCCESS {
+                    domains.resize(count as usize, vk::TimeDomainEXT::DEVICE);
+                    let result = unsafe {
+                        (raw.get_physical_device_calibrateable_time_domains_ext)(
+                            self.raw,
+                            &mut count,
+                            domains.as_mut_ptr(),
+                        )
+                    };
+                    if result != vk::Result::SUCCESS {
+                        domains.clear();
+                    }
+                    domains.truncate(count as usize);
+                }
+                (domains.contains(&vk::TimeDomainEXT::DEVICE)
+                    && domains.contains(&vk::TimeDomainEXT::CLOCK_MONOTONIC))
+                .then_some(raw)
+            } else {
+                None
+            };
 
         let naga_options = {
             use naga::back::spv;
@@ -1440,10 +2020,18 @@ impl super::Adapter {
                 capabilities.push(spv::Capability::StorageImageWriteWithoutFormat);
             }
 
-            if features.contains(wgt::Features::RAY_QUERY) {
+            if features.contains(wgt::Features::EXPERIMENTAL_RAY_QUERY) {
                 capabilities.push(spv::Capability::RayQueryKHR);
             }
 
+            if features.contains(wgt::Features::EXPERIMENTAL_MESH_SHADER) {
+                capabilities.push(spv::Capability::MeshShadingEXT);
+            }
+
+            if features.contains(wgt::Features::VERTEX_CAPTURE) {
+                capabilities.push(spv::Capability::TransformFeedback);
+            }
+
             let mut flags = spv::WriterFlags::empty();
             flags.set(
                 spv::WriterFlags::DEBUG,
@@ -1490,6 +2078,7 @@ impl super::Adapter {
                 },
                 // We need to build this separately for each invocation, so just default it out here
                 binding_map: BTreeMap::default(),
+                transform_feedback: BTreeMap::default(),
                 debug_info: None,
             }
         };
@@ -1512,34 +2101,30 @@ impl super::Adapter {
                 draw_indirect_count: indirect_count_fn,
                 timeline_semaphore: timeline_semaphore_fn,
                 ray_tracing: ray_tracing_fns,
+                device_generated_commands: device_generated_commands_fns,
+                mesh_shading: mesh_shading_fn,
+                calibrated_timestamps: calibrated_timestamps_fn,
+                conditional_rendering: conditional_rendering_fn,
+                transform_feedback: transform_feedback_fn,
+                fragment_shading_rate: fragment_shading_rate_fn,
+                external_memory_fd: external_memory_fd_fn,
+                external_semaphore_fd: external_semaphore_fd_fn,
             },
             vendor_id: self.phd_capabilities.properties.vendor_id,
+            device_id: self.phd_capabilities.properties.device_id,
+            pipeline_cache_uuid: self.phd_capabilities.properties.pipeline_cache_uuid,
             timestamp_period: self.phd_capabilities.properties.limits.timestamp_period,
             private_caps: self.private_caps.clone(),
             workarounds: self.workarounds,
             render_passes: Mutex::new(Default::default()),
             framebuffers: Mutex::new(Default::default()),
+            device_memory: Mutex::new(Default::default()),
         });
-        let mut relay_semaphores = [vk::Semaphore::null(); 2];
-        for sem in relay_semaphores.iter_mut() {
-            unsafe {
-                *sem = shared
-                    .raw
-                    .create_semaphore(&vk::SemaphoreCreateInfo::builder(), None)?
-            };
-        }
-        let queue = super::Queue {
-            raw: raw_queue,
-            swapchain_fn,
-            device: Arc::clone(&shared),
-            family_index,
-            relay_semaphores,
-            relay_index: AtomicIsize::new(-1),
-        };
+        let queue = unsafe { super::Queue::new(&shared, swapchain_fn, raw_queue) }?;
 
         let mem_allocator = {
             let limits = self.phd_capabilities.properties.limits;
-            let config = gpu_alloc::Config::i_am_prototyping(); //TODO
+            let config = gpu_alloc::Config::from(*self.memory_allocator_config.lock());
             let max_memory_allocation_size =
                 if let Some(maintenance_3) = self.phd_capabilities.maintenance_3 {
                     maintenance_3.max_memory_allocation_size
@@ -1569,7 +2154,11 @@ impl super::Adapter {
                 buffer_device_address: enabled_extensions
                     .contains(&khr::BufferDeviceAddress::name()),
             };
-            gpu_alloc::GpuAllocator::new(config, properties)
+            super::MemoryAllocator {
+                raw: gpu_alloc::GpuAllocator::new(config, properties),
+                allocation_count: 0,
+                allocated_bytes: 0,
+            }
         };
         let desc_allocator = gpu_descriptor::DescriptorAllocator::new(
             if let Some(di) = self.phd_capabilities.descriptor_indexing {
@@ -1589,7 +2178,11 @@ impl super::Adapter {
             render_doc: Default::default(),
         };
 
-        Ok(crate::OpenDevice { device, queue })
+        Ok(crate::OpenDevice {
+            device,
+            queue,
+            additional_queues: Vec::new(),
+        })
     }
 }
 
@@ -1598,46 +2191,107 @@ impl crate::Adapter<super::Api> for super::Adapter {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        compute_only: bool,
+        queue_priority: wgt::QueuePriority,
+        additional_queues: u32,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
-        let enabled_extensions = self.required_device_extensions(features);
-        let mut enabled_phd_features = self.physical_device_features(&enabled_extensions, features);
+        let mut enabled_extensions = self.required_device_extensions(features);
+        if compute_only {
+            // Nothing is ever presented from a compute-only device.
+            enabled_extensions.retain(|&extension| {
+                extension != vk::KhrSwapchainFn::name()
+                    && extension != vk::KhrSwapchainMutableFormatFn::name()
+            });
+        }
+        let global_priority = match queue_priority {
+            wgt::QueuePriority::Low => Some(vk::QueueGlobalPriorityKHR::LOW),
+            wgt::QueuePriority::Normal => None,
+            wgt::QueuePriority::High => Some(vk::QueueGlobalPriorityKHR::HIGH),
+            wgt::QueuePriority::Realtime => Some(vk::QueueGlobalPriorityKHR::REALTIME),
+        }
+        .filter(|_| {
+            self.phd_capabilities
+                .supports_extension(vk::ExtGlobalPriorityFn::name())
+        });
+        if global_priority.is_some() {
+            enabled_extensions.push(vk::ExtGlobalPriorityFn::name());
+        }
 
         let family_index = 0; //TODO
-        let family_info = vk::DeviceQueueCreateInfo::builder()
-            .queue_family_index(family_index)
-            .queue_priorities(&[1.0])
-            .build();
-        let family_infos = [family_info];
+        let queue_priorities = vec![1.0; 1 + additional_queues as usize];
+        let mut raw_device = None;
+        for global_priority in global_priority.into_iter().map(Some).chain([None]) {
+            let mut global_priority_info = global_priority.map(|priority| {
+                vk::DeviceQueueGlobalPriorityCreateInfoKHR::builder().global_priority(priority)
+            });
+            let mut family_info = vk::DeviceQueueCreateInfo::builder()
+                .queue_family_index(family_index)
+                .queue_priorities(&queue_priorities);
+            if let Some(ref mut info) = global_priority_info {
+                family_info = family_info.push_next(info);
+            }
+            let family_infos = [family_info.build()];
 
-        let str_pointers = enabled_extensions
-            .iter()
-            .map(|&s| {
-                // Safe because `enabled_extensions` entries have static lifetime.
-                s.as_ptr()
-            })
-            .collect::<Vec<_>>();
-
-        let pre_info = vk::DeviceCreateInfo::builder()
-            .queue_create_infos(&family_infos)
-            .enabled_extension_names(&str_pointers);
-        let info = enabled_phd_features
-            .add_to_device_create_builder(pre_info)
-            .build();
-        let raw_device = {
-            profiling::scope!("vkCreateDevice");
-            unsafe { self.instance.raw.create_device(self.raw, &info, None)? }
-        };
+            let str_pointers = enabled_extensions
+                .iter()
+                .map(|&s| {
+                    // Safe because `enabled_extensions` entries have static lifetime.
+                    s.as_ptr()
+                })
+                .collect::<Vec<_>>();
+
+            // Adding the features to the builder links them together, so they are
+            // rebuilt for every attempt.
+            let mut enabled_phd_features =
+                self.physical_device_features(&enabled_extensions, features);
+            let pre_info = vk::DeviceCreateInfo::builder()
+                .queue_create_infos(&family_infos)
+                .enabled_extension_names(&str_pointers);
+            let info = enabled_phd_features
+                .add_to_device_create_builder(pre_info)
+                .build();
+            let result = {
+                profiling::scope!("vkCreateDevice");
+                unsafe { self.instance.raw.create_device(self.raw, &info, None) }
+            };
+            match result {
+                Ok(device) => {
+                    raw_device = Some(device);
+                    break;
+                }
+                // Priorities above medium may require privileges we don't have.
+                Err(vk::Result::ERROR_NOT_PERMITTED_KHR) if global_priority.is_some() => {
+                    log::warn!(
+                        "Unable to create a queue with priority {queue_priority:?}, using the default priority"
+                    );
+                    enabled_extensions.retain(|&s| s != vk::ExtGlobalPriorityFn::name());
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+        let raw_device = raw_device.unwrap();
 
-        unsafe {
+        let mut open = unsafe {
             self.device_from_raw(
                 raw_device,
                 true,
                 &enabled_extensions,
                 features,
-                family_info.queue_family_index,
+                family_index,
                 0,
             )
+        }?;
+        for queue_index in 1..=additional_queues {
+            let shared = &open.device.shared;
+            let raw_queue = {
+                profiling::scope!("vkGetDeviceQueue");
+                unsafe { shared.raw.get_device_queue(family_index, queue_index) }
+            };
+            let swapchain_fn = open.queue.swapchain_fn.clone();
+            let queue = unsafe { super::Queue::new(shared, swapchain_fn, raw_queue) }?;
+            open.additional_queues.push(queue);
         }
+        Ok(open)
     }
 
     unsafe fn texture_format_capabilities(
@@ -1697,6 +2351,13 @@ impl crate::Adapter<super::Api> for super::Adapter {
         );
         // Vulkan is very permissive about MSAA
         flags.set(Tfc::MULTISAMPLE_RESOLVE, !format.is_compressed());
+        // `vkCmdResolveImage` needs a color attachment format, and never does min/max.
+        flags.set(
+            Tfc::MULTISAMPLE_RESOLVE_REGION,
+            !format.is_compressed()
+                && !format.is_depth_stencil_format()
+                && features.contains(vk::FormatFeatureFlags::COLOR_ATTACHMENT),
+        );
 
         // get the supported sample counts
         let format_aspect = crate::FormatAspects::from(format);
@@ -1732,6 +2393,29 @@ impl crate::Adapter<super::Api> for super::Adapter {
             }
         };
 
+        // Sample zero resolves are always supported, the other modes depend on the aspects.
+        if let Some(resolve) = self.phd_capabilities.depth_stencil_resolve {
+            if format.is_depth_stencil_format() {
+                let mut modes = vk::ResolveModeFlags::AVERAGE
+                    | vk::ResolveModeFlags::MIN
+                    | vk::ResolveModeFlags::MAX;
+                if format_aspect.contains(crate::FormatAspects::DEPTH) {
+                    modes &= resolve.supported_depth_resolve_modes;
+                }
+                if format_aspect.contains(crate::FormatAspects::STENCIL) {
+                    modes &= resolve.supported_stencil_resolve_modes;
+                }
+                flags.set(
+                    Tfc::MULTISAMPLE_RESOLVE_MIN_MAX,
+                    modes.contains(vk::ResolveModeFlags::MIN | vk::ResolveModeFlags::MAX),
+                );
+                flags.set(
+                    Tfc::MULTISAMPLE_RESOLVE_DEPTH_AVERAGE,
+                    modes.contains(vk::ResolveModeFlags::AVERAGE),
+                );
+            }
+        }
+
         flags.set(
             Tfc::MULTISAMPLE_X2,
             sample_flags.contains(vk::SampleCountFlags::TYPE_2),
@@ -1887,6 +2571,132 @@ impl crate::Adapter<super::Api> for super::Adapter {
             wgt::PresentationTimestamp::INVALID_TIMESTAMP
         }
     }
+
+    fn feature_requirement(&self, feature: wgt::Features) -> Option<&'static str> {
+        use wgt::Features as F;
+        Some(match feature {
+            F::INDIRECT_FIRST_INSTANCE => "the `drawIndirectFirstInstance` device feature",
+            F::MULTI_DRAW_INDIRECT => "the `multiDrawIndirect` device feature",
+            F::POLYGON_MODE_LINE | F::POLYGON_MODE_POINT => "the `fillModeNonSolid` device feature",
+            F::TEXTURE_COMPRESSION_ETC2 => "the `textureCompressionETC2` device feature",
+            F::TEXTURE_COMPRESSION_ASTC => "the `textureCompressionASTC_LDR` device feature",
+            F::TEXTURE_COMPRESSION_BC => "the `textureCompressionBC` device feature",
+            F::PIPELINE_STATISTICS_QUERY => "the `pipelineStatisticsQuery` device feature",
+            F::VERTEX_WRITABLE_STORAGE => "the `vertexPipelineStoresAndAtomics` device feature",
+            F::BUFFER_BINDING_ARRAY => {
+                "the `shaderUniformBufferArrayDynamicIndexing` device feature"
+            }
+            F::TEXTURE_BINDING_ARRAY => {
+                "the `shaderSampledImageArrayDynamicIndexing` device feature"
+            }
+            F::STORAGE_RESOURCE_BINDING_ARRAY => {
+                "the `shaderStorageBufferArrayDynamicIndexing` and \
+                 `shaderStorageImageArrayDynamicIndexing` device features"
+            }
+            F::SHADER_PRIMITIVE_INDEX => "the `geometryShader` device feature",
+            F::SHADER_F64 => "the `shaderFloat64` device feature",
+            F::SHADER_INT64 => "the `shaderInt64` device feature",
+            F::SHADER_I16 => "the `shaderInt16` device feature",
+            F::MULTI_DRAW_INDIRECT_COUNT => {
+                "the `VK_KHR_draw_indirect_count` extension and the `multiDrawIndirect` device feature"
+            }
+            F::CONSERVATIVE_RASTERIZATION => "the `VK_EXT_conservative_rasterization` extension",
+            F::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING => {
+                "the `shaderSampledImageArrayNonUniformIndexing` and \
+                 `shaderStorageBufferArrayNonUniformIndexing` features of \
+                 `VK_EXT_descriptor_indexing`"
+            }
+            F::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING => {
+                "the `shaderUniformBufferArrayNonUniformIndexing` and \
+                 `shaderStorageImageArrayNonUniformIndexing` features of \
+                 `VK_EXT_descriptor_indexing`"
+            }
+            F::PARTIALLY_BOUND_BINDING_ARRAY => {
+                "the `descriptorBindingPartiallyBound` and the sampled image, storage image and \
+                 storage buffer `descriptorBinding*UpdateAfterBind` features of \
+                 `VK_EXT_descriptor_indexing`, which are not used with Intel drivers on Windows"
+            }
+            F::DEPTH_CLIP_CONTROL => "the `depthClamp` device feature",
+            F::DUAL_SOURCE_BLENDING => "the `dualSrcBlend` device feature",
+            F::MULTIVIEW => "the `multiview` feature of `VK_KHR_multiview`",
+            F::TEXTURE_FORMAT_16BIT_NORM => {
+                "sampling, storage and color attachment support of the 16-bit normalized formats"
+            }
+            F::TEXTURE_COMPRESSION_ASTC_HDR => {
+                "the `textureCompressionASTC_HDR` feature of \
+                 `VK_EXT_texture_compression_astc_hdr`"
+            }
+            F::SHADER_F16 => {
+                "the `shaderFloat16` feature of `VK_KHR_shader_float16_int8`, and the \
+                 `storageBuffer16BitAccess` and `uniformAndStorageBuffer16BitAccess` \
+                 features of `VK_KHR_16bit_storage`"
+            }
+            F::DEPTH32FLOAT_STENCIL8 => {
+                "depth-stencil attachment support of the `D32_SFLOAT_S8_UINT` format"
+            }
+            F::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE => {
+                "the `VK_KHR_deferred_host_operations`, `VK_KHR_acceleration_structure` and \
+                 `VK_KHR_buffer_device_address` extensions"
+            }
+            F::EXPERIMENTAL_RAY_QUERY => "the `VK_KHR_ray_query` extension",
+            F::DEVICE_GENERATED_COMMANDS => {
+                "the `deviceGeneratedCommands` feature of `VK_NV_device_generated_commands`, \
+                 and the `VK_KHR_buffer_device_address` extension"
+            }
+            F::EXPERIMENTAL_MESH_SHADER => {
+                "the `taskShader` and `meshShader` features of `VK_EXT_mesh_shader`, which \
+                 requires `VK_KHR_spirv_1_4` before Vulkan 1.2"
+            }
+            F::CONDITIONAL_RENDERING => {
+                "the `conditionalRendering` feature of `VK_EXT_conditional_rendering`"
+            }
+            F::VERTEX_CAPTURE => {
+                "the `transformFeedback` feature of `VK_EXT_transform_feedback`, with at least \
+                 4 buffers and a buffer data stride of at least 256 bytes"
+            }
+            F::SHADING_RATE => {
+                "the `pipelineFragmentShadingRate` feature of `VK_KHR_fragment_shading_rate`, \
+                 and Vulkan 1.2"
+            }
+            F::SHADING_RATE_ATTACHMENT => {
+                "the `attachmentFragmentShadingRate` feature of `VK_KHR_fragment_shading_rate`, \
+                 with square attachment texels, and Vulkan 1.2"
+            }
+            F::DEPTH_STENCIL_RESOLVE => "Vulkan 1.2",
+            F::TEXTURE_COMPONENT_SWIZZLE => "an implementation without `VK_KHR_portability_subset`",
+            F::EXTERNAL_TEXTURE_IMPORT => {
+                "Vulkan 1.1, and the `VK_KHR_external_memory_fd`, \
+                 `VK_EXT_external_memory_dma_buf` and `VK_EXT_image_drm_format_modifier` \
+                 extensions on a Unix platform"
+            }
+            F::EXTERNAL_FENCE => {
+                "Vulkan 1.1, timeline semaphores, and the `VK_KHR_external_semaphore_fd` \
+                 extension on a Unix platform"
+            }
+            F::EXTERNAL_MEMORY => {
+                "Vulkan 1.1 and the `VK_KHR_external_memory_fd` extension on a Unix platform"
+            }
+            F::TILED_TEXTURE => {
+                "the `sparseBinding`, `sparseResidencyImage2D` and `sparseResidencyAliased` \
+                 device features, the standard 2D block shapes, strict non-resident reads, and \
+                 sparse binding support of the first queue family"
+            }
+            F::RG11B10UFLOAT_RENDERABLE => {
+                "color attachment and blending support of the `B10G11R11_UFLOAT_PACK32` format"
+            }
+            F::BGRA8UNORM_STORAGE => "storage image support of the `B8G8R8A8_UNORM` format",
+            F::FLOAT32_FILTERABLE => "linear filtering support of the 32-bit float formats",
+            F::TEXTURE_FORMAT_NV12 => {
+                "the `VK_KHR_sampler_ycbcr_conversion` extension and support of the \
+                 `G8_B8R8_2PLANE_420_UNORM` format, which is not used with MoltenVK"
+            }
+            F::TEXTURE_FORMAT_P010 => {
+                "the `VK_KHR_sampler_ycbcr_conversion` extension and support of the \
+                 `G16_B16R16_2PLANE_420_UNORM` format, which is not used with MoltenVK"
+            }
+            _ => return None,
+        })
+    }
 }
 
 fn is_format_16bit_norm_supported(instance: &ash::Instance, phd: vk::PhysicalDevice) -> bool {
@@ -3,7 +3,7 @@ use super::conv;
 use arrayvec::ArrayVec;
 use ash::{extensions::ext, vk};
 
-use std::{mem, ops::Range, slice};
+use std::{mem, ops::Range, ptr, slice};
 
 const ALLOCATION_GRANULARITY: u32 = 16;
 const DST_IMAGE_LAYOUT: vk::ImageLayout = vk::ImageLayout::TRANSFER_DST_OPTIMAL;
@@ -60,6 +60,18 @@ impl super::CommandEncoder {
     }
 }
 
+impl super::CommandEncoder {
+    /// Frees the buffers used by `execute_indirect`, once the GPU is done with them.
+    pub(super) unsafe fn free_preprocess_buffers(&mut self) {
+        for (raw, memory) in self.preprocess_buffers.drain(..) {
+            unsafe {
+                self.device.raw.destroy_buffer(raw, None);
+                self.device.raw.free_memory(memory, None);
+            }
+        }
+    }
+}
+
 impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     unsafe fn begin_encoding(&mut self, label: crate::Label) -> Result<(), crate::DeviceError> {
         if self.free.is_empty() {
@@ -111,6 +123,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         I: Iterator<Item = super::CommandBuffer>,
     {
         self.temp.clear();
+        unsafe { self.free_preprocess_buffers() };
         self.free
             .extend(cmd_bufs.into_iter().map(|cmd_buf| cmd_buf.raw));
         self.free.append(&mut self.discarded);
@@ -130,11 +143,21 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         let mut dst_stages = vk::PipelineStageFlags::BOTTOM_OF_PIPE;
         let vk_barriers = &mut self.temp.buffer_barriers;
         vk_barriers.clear();
+        let conditional_rendering = self.device.extension_fns.conditional_rendering.is_some();
+        let map_usage = |usage: crate::BufferUses| {
+            let (mut stages, mut access) = conv::map_buffer_usage_to_barrier(usage);
+            // Indirect buffers may also hold the condition of conditional rendering.
+            if conditional_rendering && usage.contains(crate::BufferUses::INDIRECT) {
+                stages |= vk::PipelineStageFlags::CONDITIONAL_RENDERING_EXT;
+                access |= vk::AccessFlags::CONDITIONAL_RENDERING_READ_EXT;
+            }
+            (stages, access)
+        };
 
         for bar in barriers {
-            let (src_stage, src_access) = conv::map_buffer_usage_to_barrier(bar.usage.start);
+            let (src_stage, src_access) = map_usage(bar.usage.start);
             src_stages |= src_stage;
-            let (dst_stage, dst_access) = conv::map_buffer_usage_to_barrier(bar.usage.end);
+            let (dst_stage, dst_access) = map_usage(bar.usage.end);
             dst_stages |= dst_stage;
 
             vk_barriers.push(
@@ -211,6 +234,23 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         }
     }
 
+    unsafe fn aliasing_barrier(&mut self) {
+        unsafe {
+            self.device.raw.cmd_pipeline_barrier(
+                self.active,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::DependencyFlags::empty(),
+                &[vk::MemoryBarrier::builder()
+                    .src_access_mask(vk::AccessFlags::MEMORY_WRITE)
+                    .dst_access_mask(vk::AccessFlags::MEMORY_READ | vk::AccessFlags::MEMORY_WRITE)
+                    .build()],
+                &[],
+                &[],
+            )
+        };
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
         let range_size = range.end - range.start;
         if self.device.workarounds.contains(
@@ -315,6 +355,48 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         };
     }
 
+    unsafe fn resolve_texture<T>(
+        &mut self,
+        src: &super::Texture,
+        src_usage: crate::TextureUses,
+        dst: &super::Texture,
+        mode: wgt::ResolveMode,
+        regions: T,
+    ) where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        // `vkCmdResolveImage` always averages the samples.
+        debug_assert_eq!(mode, wgt::ResolveMode::Average);
+        let src_layout = conv::derive_image_layout(src_usage, src.format);
+
+        let vk_regions_iter = regions.map(|r| {
+            let (src_subresource, src_offset) = conv::map_subresource_layers(&r.src_base);
+            let (dst_subresource, dst_offset) = conv::map_subresource_layers(&r.dst_base);
+            let extent = r
+                .size
+                .min(&r.src_base.max_copy_size(&src.copy_size))
+                .min(&r.dst_base.max_copy_size(&dst.copy_size));
+            vk::ImageResolve {
+                src_subresource,
+                src_offset,
+                dst_subresource,
+                dst_offset,
+                extent: conv::map_copy_extent(&extent),
+            }
+        });
+
+        unsafe {
+            self.device.raw.cmd_resolve_image(
+                self.active,
+                src.raw,
+                src_layout,
+                dst.raw,
+                DST_IMAGE_LAYOUT,
+                &smallvec::SmallVec::<[vk::ImageResolve; 32]>::from_iter(vk_regions_iter),
+            )
+        };
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -512,7 +594,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                                 .first_vertex(triangles.first_vertex);
                         } else {
                             range = range
-                                .primitive_count(triangles.vertex_count)
+                                .primitive_count(triangles.vertex_count / 3)
                                 .first_vertex(triangles.first_vertex);
                         }
 
@@ -662,6 +744,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             attachments: ArrayVec::default(),
             extent: desc.extent,
             sample_count: desc.sample_count,
+            shading_rate_extent: None,
         };
         let caps = &self.device.private_caps;
 
@@ -706,18 +789,51 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                 },
             });
             vk_image_views.push(ds.target.view.raw);
+            let resolve = ds.resolve_target.as_ref().map(|target| {
+                let aspects = crate::FormatAspects::from(target.view.attachment.view_format);
+                let mode = conv::map_resolve_mode(ds.resolve_mode);
+                super::DepthStencilResolveKey {
+                    base: target.make_attachment_key(crate::AttachmentOps::STORE, caps),
+                    depth_mode: if aspects.contains(crate::FormatAspects::DEPTH) {
+                        mode
+                    } else {
+                        vk::ResolveModeFlags::NONE
+                    },
+                    stencil_mode: if aspects.contains(crate::FormatAspects::STENCIL) {
+                        mode
+                    } else {
+                        vk::ResolveModeFlags::NONE
+                    },
+                }
+            });
             rp_key.depth_stencil = Some(super::DepthStencilAttachmentKey {
                 base: ds.target.make_attachment_key(ds.depth_ops, caps),
                 stencil_ops: ds.stencil_ops,
+                resolve,
             });
             fb_key.attachments.push(ds.target.view.attachment.clone());
+            if let Some(ref at) = ds.resolve_target {
+                vk_clear_values.push(unsafe { mem::zeroed() });
+                vk_image_views.push(at.view.raw);
+                fb_key.attachments.push(at.view.attachment.clone());
+            }
 
             // Assert this attachment is valid for the detected multiview, as a sanity check
             // The driver crash for this is really bad on AMD, so the check is worth it
             if let Some(multiview) = desc.multiview {
                 assert_eq!(ds.target.view.layers, multiview);
+                if let Some(ref resolve_target) = ds.resolve_target {
+                    assert_eq!(resolve_target.view.layers, multiview);
+                }
             }
         }
+        if let Some(ref at) = desc.shading_rate_attachment {
+            vk_clear_values.push(unsafe { mem::zeroed() });
+            vk_image_views.push(at.view.raw);
+            rp_key.shading_rate = true;
+            fb_key.attachments.push(at.view.attachment.clone());
+            fb_key.shading_rate_extent = Some(at.view.extent);
+        }
         rp_key.sample_count = fb_key.sample_count;
         rp_key.multiview = desc.multiview;
 
@@ -797,6 +913,17 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         };
 
         self.bind_point = vk::PipelineBindPoint::GRAPHICS;
+
+        // All pipelines take their shading rate from the command buffer once the
+        // extension is enabled, so it has to be reset for every pass.
+        if self.device.extension_fns.fragment_shading_rate.is_some() {
+            self.shading_rate_combiner_op = if desc.shading_rate_attachment.is_some() {
+                vk::FragmentShadingRateCombinerOpKHR::REPLACE
+            } else {
+                vk::FragmentShadingRateCombinerOpKHR::KEEP
+            };
+            unsafe { self.set_shading_rate(wgt::ShadingRate::Rate1x1) };
+        }
     }
     unsafe fn end_render_pass(&mut self) {
         unsafe {
@@ -1077,6 +1204,213 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             None => panic!("Feature `DRAW_INDIRECT_COUNT` not enabled"),
         }
     }
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) {
+        match self.device.extension_fns.mesh_shading {
+            Some(ref t) => {
+                unsafe {
+                    t.cmd_draw_mesh_tasks(self.active, group_count_x, group_count_y, group_count_z)
+                };
+            }
+            None => panic!("Feature `EXPERIMENTAL_MESH_SHADER` not enabled"),
+        }
+    }
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    ) {
+        match self.device.extension_fns.mesh_shading {
+            Some(ref t) => {
+                unsafe {
+                    t.cmd_draw_mesh_tasks_indirect(
+                        self.active,
+                        buffer.raw,
+                        offset,
+                        draw_count,
+                        mem::size_of::<wgt::DrawMeshTasksIndirectArgs>() as u32,
+                    )
+                };
+            }
+            None => panic!("Feature `EXPERIMENTAL_MESH_SHADER` not enabled"),
+        }
+    }
+    unsafe fn execute_indirect(
+        &mut self,
+        pipeline: &super::RenderPipeline,
+        layout: &super::PipelineLayout,
+        arguments: &[wgt::IndirectArgument],
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+        count: Option<(&super::Buffer, wgt::BufferAddress)>,
+        max_count: u32,
+    ) {
+        let fns = self
+            .device
+            .extension_fns
+            .device_generated_commands
+            .as_ref()
+            .expect("Feature `DEVICE_GENERATED_COMMANDS` not enabled");
+
+        let indirect_layout = {
+            let mut layouts = layout.indirect_commands_layouts.lock();
+            match layouts.get(arguments) {
+                Some(&raw) => raw,
+                None => {
+                    match unsafe {
+                        self.device
+                            .create_indirect_commands_layout(layout, arguments)
+                    } {
+                        Ok(raw) => {
+                            layouts.insert(arguments.to_vec(), raw);
+                            raw
+                        }
+                        Err(err) => {
+                            log::error!("Indirect commands layout creation failed: {err}");
+                            return;
+                        }
+                    }
+                }
+            }
+        };
+
+        let requirements_info = vk::GeneratedCommandsMemoryRequirementsInfoNV::builder()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .pipeline(pipeline.raw)
+            .indirect_commands_layout(indirect_layout)
+            .max_sequences_count(max_count);
+        let mut requirements = vk::MemoryRequirements2::default();
+        unsafe {
+            (fns.raw.get_generated_commands_memory_requirements_nv)(
+                self.device.raw.handle(),
+                &*requirements_info,
+                &mut requirements,
+            )
+        };
+        let requirements = requirements.memory_requirements;
+        let preprocess_buffer = match unsafe { self.device.create_preprocess_buffer(requirements) }
+        {
+            Ok(preprocess) => {
+                self.preprocess_buffers.push(preprocess);
+                preprocess.0
+            }
+            Err(err) => {
+                log::error!("Preprocess buffer creation failed: {err}");
+                return;
+            }
+        };
+
+        let streams = [vk::IndirectCommandsStreamNV {
+            buffer: buffer.raw,
+            offset,
+        }];
+        let mut vk_info = vk::GeneratedCommandsInfoNV::builder()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .pipeline(pipeline.raw)
+            .indirect_commands_layout(indirect_layout)
+            .streams(&streams)
+            .sequences_count(max_count)
+            .preprocess_buffer(preprocess_buffer)
+            .preprocess_size(requirements.size.max(4));
+        if let Some((count_buffer, count_offset)) = count {
+            vk_info = vk_info
+                .sequences_count_buffer(count_buffer.raw)
+                .sequences_count_offset(count_offset);
+        }
+        unsafe { (fns.raw.cmd_execute_generated_commands_nv)(self.active, vk::FALSE, &*vk_info) };
+    }
+
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+    ) {
+        let fns = self
+            .device
+            .extension_fns
+            .conditional_rendering
+            .as_ref()
+            .expect("Feature `CONDITIONAL_RENDERING` not enabled");
+        let vk_info = vk::ConditionalRenderingBeginInfoEXT::builder()
+            .buffer(buffer.raw)
+            .offset(offset);
+        unsafe { (fns.cmd_begin_conditional_rendering_ext)(self.active, &*vk_info) };
+    }
+    unsafe fn end_conditional_rendering(&mut self) {
+        let fns = self
+            .device
+            .extension_fns
+            .conditional_rendering
+            .as_ref()
+            .expect("Feature `CONDITIONAL_RENDERING` not enabled");
+        unsafe { (fns.cmd_end_conditional_rendering_ext)(self.active) };
+    }
+
+    unsafe fn begin_vertex_capture(&mut self, buffers: &[crate::BufferBinding<super::Api>]) {
+        let fns = self
+            .device
+            .extension_fns
+            .transform_feedback
+            .as_ref()
+            .expect("Feature `VERTEX_CAPTURE` not enabled");
+        let mut vk_buffers = ArrayVec::<_, { crate::MAX_VERTEX_CAPTURE_BUFFERS }>::new();
+        let mut vk_offsets = ArrayVec::<_, { crate::MAX_VERTEX_CAPTURE_BUFFERS }>::new();
+        let mut vk_sizes = ArrayVec::<_, { crate::MAX_VERTEX_CAPTURE_BUFFERS }>::new();
+        for binding in buffers {
+            vk_buffers.push(binding.buffer.raw);
+            vk_offsets.push(binding.offset);
+            vk_sizes.push(binding.size.map_or(vk::WHOLE_SIZE, |size| size.get()));
+        }
+        unsafe {
+            (fns.cmd_bind_transform_feedback_buffers_ext)(
+                self.active,
+                0,
+                vk_buffers.len() as u32,
+                vk_buffers.as_ptr(),
+                vk_offsets.as_ptr(),
+                vk_sizes.as_ptr(),
+            );
+            // Without counter buffers, vertices are written from the start of
+            // the bound ranges.
+            (fns.cmd_begin_transform_feedback_ext)(self.active, 0, 0, ptr::null(), ptr::null());
+        }
+    }
+    unsafe fn end_vertex_capture(&mut self) {
+        let fns = self
+            .device
+            .extension_fns
+            .transform_feedback
+            .as_ref()
+            .expect("Feature `VERTEX_CAPTURE` not enabled");
+        unsafe {
+            (fns.cmd_end_transform_feedback_ext)(self.active, 0, 0, ptr::null(), ptr::null())
+        };
+    }
+
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate) {
+        let fns = self
+            .device
+            .extension_fns
+            .fragment_shading_rate
+            .as_ref()
+            .expect("Feature `SHADING_RATE` not enabled");
+        let combiner_ops = [
+            vk::FragmentShadingRateCombinerOpKHR::KEEP,
+            self.shading_rate_combiner_op,
+        ];
+        unsafe {
+            (fns.cmd_set_fragment_shading_rate_khr)(
+                self.active,
+                &conv::map_shading_rate(rate),
+                &combiner_ops,
+            )
+        };
+    }
 
     // compute
 
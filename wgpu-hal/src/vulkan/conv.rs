@@ -75,6 +75,7 @@ impl super::PrivateCapabilities {
             }
             Tf::Depth16Unorm => F::D16_UNORM,
             Tf::NV12 => F::G8_B8R8_2PLANE_420_UNORM,
+            Tf::P010 => F::G16_B16R16_2PLANE_420_UNORM,
             Tf::Rgb9e5Ufloat => F::E5B9G9R9_UFLOAT_PACK32,
             Tf::Bc1RgbaUnorm => F::BC1_RGBA_UNORM_BLOCK,
             Tf::Bc1RgbaUnormSrgb => F::BC1_RGBA_SRGB_BLOCK,
@@ -232,6 +233,9 @@ pub fn derive_image_layout(
         crate::TextureUses::DEPTH_STENCIL_WRITE => {
             vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL
         }
+        crate::TextureUses::SHADING_RATE_ATTACHMENT => {
+            vk::ImageLayout::FRAGMENT_SHADING_RATE_ATTACHMENT_OPTIMAL_KHR
+        }
         _ => {
             if usage == crate::TextureUses::PRESENT {
                 vk::ImageLayout::PRESENT_SRC_KHR
@@ -266,6 +270,9 @@ pub fn map_texture_usage(usage: crate::TextureUses) -> vk::ImageUsageFlags {
     if usage.intersects(crate::TextureUses::STORAGE_READ | crate::TextureUses::STORAGE_READ_WRITE) {
         flags |= vk::ImageUsageFlags::STORAGE;
     }
+    if usage.contains(crate::TextureUses::SHADING_RATE_ATTACHMENT) {
+        flags |= vk::ImageUsageFlags::FRAGMENT_SHADING_RATE_ATTACHMENT_KHR;
+    }
     flags
 }
 
@@ -313,6 +320,10 @@ pub fn map_texture_usage_to_barrier(
         stages |= shader_stages;
         access |= vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE;
     }
+    if usage.contains(crate::TextureUses::SHADING_RATE_ATTACHMENT) {
+        stages |= vk::PipelineStageFlags::FRAGMENT_SHADING_RATE_ATTACHMENT_KHR;
+        access |= vk::AccessFlags::FRAGMENT_SHADING_RATE_ATTACHMENT_READ_KHR;
+    }
 
     if usage == crate::TextureUses::UNINITIALIZED || usage == crate::TextureUses::PRESENT {
         (
@@ -344,6 +355,9 @@ pub fn map_vk_image_usage(usage: vk::ImageUsageFlags) -> crate::TextureUses {
     if usage.contains(vk::ImageUsageFlags::STORAGE) {
         bits |= crate::TextureUses::STORAGE_READ | crate::TextureUses::STORAGE_READ_WRITE;
     }
+    if usage.contains(vk::ImageUsageFlags::FRAGMENT_SHADING_RATE_ATTACHMENT_KHR) {
+        bits |= crate::TextureUses::SHADING_RATE_ATTACHMENT;
+    }
     bits
 }
 
@@ -362,6 +376,40 @@ pub fn map_index_format(index_format: wgt::IndexFormat) -> vk::IndexType {
     }
 }
 
+fn map_component_swizzle(swizzle: wgt::ComponentSwizzle) -> vk::ComponentSwizzle {
+    match swizzle {
+        wgt::ComponentSwizzle::Zero => vk::ComponentSwizzle::ZERO,
+        wgt::ComponentSwizzle::One => vk::ComponentSwizzle::ONE,
+        wgt::ComponentSwizzle::R => vk::ComponentSwizzle::R,
+        wgt::ComponentSwizzle::G => vk::ComponentSwizzle::G,
+        wgt::ComponentSwizzle::B => vk::ComponentSwizzle::B,
+        wgt::ComponentSwizzle::A => vk::ComponentSwizzle::A,
+    }
+}
+
+pub fn map_component_mapping(mapping: wgt::ComponentMapping) -> vk::ComponentMapping {
+    vk::ComponentMapping {
+        r: map_component_swizzle(mapping.r),
+        g: map_component_swizzle(mapping.g),
+        b: map_component_swizzle(mapping.b),
+        a: map_component_swizzle(mapping.a),
+    }
+}
+
+pub fn map_resolve_mode(mode: wgt::ResolveMode) -> vk::ResolveModeFlags {
+    match mode {
+        wgt::ResolveMode::Average => vk::ResolveModeFlags::AVERAGE,
+        wgt::ResolveMode::Min => vk::ResolveModeFlags::MIN,
+        wgt::ResolveMode::Max => vk::ResolveModeFlags::MAX,
+        wgt::ResolveMode::SampleZero => vk::ResolveModeFlags::SAMPLE_ZERO,
+    }
+}
+
+pub fn map_shading_rate(rate: wgt::ShadingRate) -> vk::Extent2D {
+    let (width, height) = rate.block_size();
+    vk::Extent2D { width, height }
+}
+
 pub fn map_vertex_format(vertex_format: wgt::VertexFormat) -> vk::Format {
     use wgt::VertexFormat as Vf;
     match vertex_format {
@@ -528,6 +576,9 @@ pub fn map_buffer_usage(usage: crate::BufferUses) -> vk::BufferUsageFlags {
         flags |= vk::BufferUsageFlags::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR
             | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
     }
+    if usage.contains(crate::BufferUses::VERTEX_CAPTURE) {
+        flags |= vk::BufferUsageFlags::TRANSFORM_FEEDBACK_BUFFER_EXT;
+    }
     flags
 }
 
@@ -589,6 +640,10 @@ pub fn map_buffer_usage_to_barrier(
         access |= vk::AccessFlags::ACCELERATION_STRUCTURE_READ_KHR
             | vk::AccessFlags::ACCELERATION_STRUCTURE_WRITE_KHR;
     }
+    if usage.contains(crate::BufferUses::VERTEX_CAPTURE) {
+        stages |= vk::PipelineStageFlags::TRANSFORM_FEEDBACK_EXT;
+        access |= vk::AccessFlags::TRANSFORM_FEEDBACK_WRITE_EXT;
+    }
 
     (stages, access)
 }
@@ -717,6 +772,12 @@ pub fn map_shader_stage(stage: wgt::ShaderStages) -> vk::ShaderStageFlags {
     if stage.contains(wgt::ShaderStages::COMPUTE) {
         flags |= vk::ShaderStageFlags::COMPUTE;
     }
+    if stage.contains(wgt::ShaderStages::TASK) {
+        flags |= vk::ShaderStageFlags::TASK_EXT;
+    }
+    if stage.contains(wgt::ShaderStages::MESH) {
+        flags |= vk::ShaderStageFlags::MESH_EXT;
+    }
     flags
 }
 
@@ -14,6 +14,21 @@ use std::{
 };
 
 impl super::DeviceShared {
+    /// Checks that `data` starts with a `VK_PIPELINE_CACHE_HEADER_VERSION_ONE` header
+    /// written by this device, since not all drivers reject other data themselves.
+    fn pipeline_cache_header_matches(&self, data: &[u8]) -> bool {
+        const HEADER_SIZE: usize = 16 + vk::UUID_SIZE;
+        if data.len() < HEADER_SIZE {
+            return false;
+        }
+        let word = |i: usize| u32::from_ne_bytes(data[i * 4..i * 4 + 4].try_into().unwrap());
+        word(0) as usize >= HEADER_SIZE
+            && word(1) == vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+            && word(2) == self.vendor_id
+            && word(3) == self.device_id
+            && data[16..HEADER_SIZE] == self.pipeline_cache_uuid
+    }
+
     pub(super) unsafe fn set_object_name(
         &self,
         object_type: vk::ObjectType,
@@ -73,6 +88,7 @@ impl super::DeviceShared {
                 let mut color_refs = Vec::with_capacity(e.key().colors.len());
                 let mut resolve_refs = Vec::with_capacity(color_refs.capacity());
                 let mut ds_ref = None;
+                let mut ds_resolve = None;
                 let samples = vk::SampleCountFlags::from_raw(e.key().sample_count);
                 let unused = vk::AttachmentReference {
                     attachment: vk::ATTACHMENT_UNUSED,
@@ -143,6 +159,45 @@ impl super::DeviceShared {
                         .final_layout(ds.base.layout)
                         .build();
                     vk_attachments.push(vk_attachment);
+
+                    if let Some(ref rat) = ds.resolve {
+                        let (load_op, store_op) = conv::map_attachment_ops(rat.base.ops);
+                        let vk_attachment = vk::AttachmentDescription::builder()
+                            .format(rat.base.format)
+                            .samples(vk::SampleCountFlags::TYPE_1)
+                            .load_op(load_op)
+                            .store_op(store_op)
+                            .stencil_load_op(load_op)
+                            .stencil_store_op(store_op)
+                            .initial_layout(rat.base.layout)
+                            .final_layout(rat.base.layout)
+                            .build();
+                        vk_attachments.push(vk_attachment);
+
+                        ds_resolve = Some(DepthStencilResolve {
+                            reference: vk::AttachmentReference {
+                                attachment: vk_attachments.len() as u32 - 1,
+                                layout: rat.base.layout,
+                            },
+                            depth_mode: rat.depth_mode,
+                            stencil_mode: rat.stencil_mode,
+                        });
+                    }
+                }
+
+                if e.key().shading_rate || ds_resolve.is_some() {
+                    let raw = unsafe {
+                        self.make_render_pass2(
+                            &vk_attachments,
+                            &color_refs,
+                            &resolve_refs,
+                            ds_ref.as_ref(),
+                            ds_resolve.as_ref(),
+                            e.key().shading_rate,
+                            e.key().multiview,
+                        )?
+                    };
+                    return Ok(*e.insert(raw));
                 }
 
                 let vk_subpasses = [{
@@ -196,6 +251,121 @@ impl super::DeviceShared {
         })
     }
 
+    /// Creates a render pass with the structures of `vkCreateRenderPass2`.
+    ///
+    /// Shading rate attachments and depth/stencil resolves can only be described
+    /// this way. If `shading_rate` is set, a shading rate attachment is added
+    /// after the given attachments.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn make_render_pass2(
+        &self,
+        attachments: &[vk::AttachmentDescription],
+        color_refs: &[vk::AttachmentReference],
+        resolve_refs: &[vk::AttachmentReference],
+        ds_ref: Option<&vk::AttachmentReference>,
+        ds_resolve: Option<&DepthStencilResolve>,
+        shading_rate: bool,
+        multiview: Option<NonZeroU32>,
+    ) -> Result<vk::RenderPass, crate::DeviceError> {
+        let layout = vk::ImageLayout::FRAGMENT_SHADING_RATE_ATTACHMENT_OPTIMAL_KHR;
+        let vk_attachments = attachments
+            .iter()
+            .map(|at| {
+                vk::AttachmentDescription2::builder()
+                    .format(at.format)
+                    .samples(at.samples)
+                    .load_op(at.load_op)
+                    .store_op(at.store_op)
+                    .stencil_load_op(at.stencil_load_op)
+                    .stencil_store_op(at.stencil_store_op)
+                    .initial_layout(at.initial_layout)
+                    .final_layout(at.final_layout)
+                    .build()
+            })
+            .chain(shading_rate.then(|| {
+                vk::AttachmentDescription2::builder()
+                    .format(vk::Format::R8_UINT)
+                    .samples(vk::SampleCountFlags::TYPE_1)
+                    .load_op(vk::AttachmentLoadOp::LOAD)
+                    .store_op(vk::AttachmentStoreOp::STORE)
+                    .initial_layout(layout)
+                    .final_layout(layout)
+                    .build()
+            }))
+            .collect::<Vec<_>>();
+        let map_ref = |reference: &vk::AttachmentReference| {
+            vk::AttachmentReference2::builder()
+                .attachment(reference.attachment)
+                .layout(reference.layout)
+                .build()
+        };
+        let color_refs = color_refs.iter().map(map_ref).collect::<Vec<_>>();
+        let resolve_refs = resolve_refs.iter().map(map_ref).collect::<Vec<_>>();
+        let ds_ref = ds_ref.map(map_ref);
+        let ds_resolve_ref = ds_resolve.map(|resolve| map_ref(&resolve.reference));
+        let mut ds_resolve_info =
+            ds_resolve
+                .zip(ds_resolve_ref.as_ref())
+                .map(|(resolve, reference)| {
+                    vk::SubpassDescriptionDepthStencilResolve::builder()
+                        .depth_stencil_resolve_attachment(reference)
+                        .depth_resolve_mode(resolve.depth_mode)
+                        .stencil_resolve_mode(resolve.stencil_mode)
+                        .build()
+                });
+        let shading_rate_ref = vk::AttachmentReference2::builder()
+            .attachment(attachments.len() as u32)
+            .layout(layout)
+            .build();
+        let tile_size = self.private_caps.shading_rate_tile_size;
+        let mut shading_rate_info = vk::FragmentShadingRateAttachmentInfoKHR::builder()
+            .fragment_shading_rate_attachment(&shading_rate_ref)
+            .shading_rate_attachment_texel_size(vk::Extent2D {
+                width: tile_size,
+                height: tile_size,
+            })
+            .build();
+
+        // See `make_render_pass` for the multiview masks.
+        let mask = multiview.map_or(0, |multiview| (1 << multiview.get()) - 1);
+        let correlation_masks = [mask];
+        let vk_subpasses = [{
+            let mut vk_subpass = vk::SubpassDescription2::builder()
+                .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+                .view_mask(mask)
+                .color_attachments(&color_refs)
+                .resolve_attachments(&resolve_refs);
+            if shading_rate {
+                vk_subpass = vk_subpass.push_next(&mut shading_rate_info);
+            }
+            if let Some(ref mut info) = ds_resolve_info {
+                vk_subpass = vk_subpass.push_next(info);
+            }
+
+            if self
+                .workarounds
+                .contains(super::Workarounds::EMPTY_RESOLVE_ATTACHMENT_LISTS)
+                && resolve_refs.is_empty()
+            {
+                vk_subpass.p_resolve_attachments = ptr::null();
+            }
+
+            if let Some(ref reference) = ds_ref {
+                vk_subpass = vk_subpass.depth_stencil_attachment(reference)
+            }
+            vk_subpass.build()
+        }];
+
+        let mut vk_info = vk::RenderPassCreateInfo2::builder()
+            .attachments(&vk_attachments)
+            .subpasses(&vk_subpasses);
+        if multiview.is_some() {
+            vk_info = vk_info.correlated_view_masks(&correlation_masks);
+        }
+
+        Ok(unsafe { self.raw.create_render_pass2(&vk_info, None)? })
+    }
+
     pub fn make_framebuffer(
         &self,
         key: super::FramebufferKey,
@@ -232,10 +402,17 @@ impl super::DeviceShared {
                     .map(|(i, at)| {
                         let mut info = vk::FramebufferAttachmentImageInfo::builder()
                             .usage(conv::map_texture_usage(at.view_usage))
-                            .flags(at.raw_image_flags)
-                            .width(e.key().extent.width)
-                            .height(e.key().extent.height)
-                            .layer_count(e.key().extent.depth_or_array_layers);
+                            .flags(at.raw_image_flags);
+                        info = match e.key().shading_rate_extent {
+                            Some(extent) if i == e.key().attachments.len() - 1 => info
+                                .width(extent.width)
+                                .height(extent.height)
+                                .layer_count(1),
+                            _ => info
+                                .width(e.key().extent.width)
+                                .height(e.key().extent.height)
+                                .layer_count(e.key().extent.depth_or_array_layers),
+                        };
                         // https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkRenderPassBeginInfo.html#VUID-VkRenderPassBeginInfo-framebuffer-03214
                         if vk_view_formats_list[i].is_empty() {
                             info = info.view_formats(&vk_view_formats[i..i + 1]);
@@ -281,17 +458,136 @@ impl super::DeviceShared {
         buffer: &'a super::Buffer,
         ranges: I,
     ) -> Option<impl 'a + Iterator<Item = vk::MappedMemoryRange>> {
-        let block = buffer.block.as_ref()?.lock();
+        let (memory, offset) = match (buffer.block.as_ref(), buffer.placement.as_ref()) {
+            (Some(block), _) => {
+                let block = block.lock();
+                (*block.memory(), block.offset())
+            }
+            (None, Some(placement)) => (placement.memory, placement.offset),
+            (None, None) => return None,
+        };
         let mask = self.private_caps.non_coherent_map_mask;
         Some(ranges.map(move |range| {
             vk::MappedMemoryRange::builder()
-                .memory(*block.memory())
-                .offset((block.offset() + range.start) & !mask)
+                .memory(memory)
+                .offset((offset + range.start) & !mask)
                 .size((range.end - range.start + mask) & !mask)
                 .build()
         }))
     }
 
+    /// Creates the indirect commands layout used by `execute_indirect` with `arguments`.
+    pub(super) unsafe fn create_indirect_commands_layout(
+        &self,
+        layout: &super::PipelineLayout,
+        arguments: &[wgt::IndirectArgument],
+    ) -> Result<vk::IndirectCommandsLayoutNV, crate::DeviceError> {
+        let fns = self
+            .extension_fns
+            .device_generated_commands
+            .as_ref()
+            .unwrap();
+        let mut offset = 0;
+        let tokens = arguments
+            .iter()
+            .map(|argument| {
+                let token = vk::IndirectCommandsLayoutTokenNV::builder().offset(offset);
+                let token = match *argument {
+                    wgt::IndirectArgument::VertexBuffer { slot } => token
+                        .token_type(vk::IndirectCommandsTokenTypeNV::VERTEX_BUFFER)
+                        .vertex_binding_unit(slot),
+                    wgt::IndirectArgument::PushConstants {
+                        stages,
+                        offset: constant_offset,
+                        size,
+                    } => token
+                        .token_type(vk::IndirectCommandsTokenTypeNV::PUSH_CONSTANT)
+                        .pushconstant_pipeline_layout(layout.raw)
+                        .pushconstant_shader_stage_flags(conv::map_shader_stage(stages))
+                        .pushconstant_offset(constant_offset)
+                        .pushconstant_size(size),
+                    wgt::IndirectArgument::Draw => {
+                        token.token_type(vk::IndirectCommandsTokenTypeNV::DRAW)
+                    }
+                    wgt::IndirectArgument::DrawIndexed => {
+                        token.token_type(vk::IndirectCommandsTokenTypeNV::DRAW_INDEXED)
+                    }
+                };
+                offset += argument.size();
+                token.build()
+            })
+            .collect::<Vec<_>>();
+        let strides = [wgt::IndirectArgument::stride(arguments)];
+        let vk_info = vk::IndirectCommandsLayoutCreateInfoNV::builder()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .tokens(&tokens)
+            .stream_strides(&strides);
+
+        let mut raw = vk::IndirectCommandsLayoutNV::null();
+        unsafe {
+            (fns.raw.create_indirect_commands_layout_nv)(
+                self.raw.handle(),
+                &*vk_info,
+                ptr::null(),
+                &mut raw,
+            )
+        }
+        .result()?;
+        Ok(raw)
+    }
+
+    /// Creates a device local buffer for the preprocessing of generated commands.
+    ///
+    /// It's owned by the command encoder, and freed with its command buffers.
+    pub(super) unsafe fn create_preprocess_buffer(
+        &self,
+        requirements: vk::MemoryRequirements,
+    ) -> Result<(vk::Buffer, vk::DeviceMemory), crate::DeviceError> {
+        let vk_info = vk::BufferCreateInfo::builder()
+            .size(requirements.size.max(4))
+            .usage(vk::BufferUsageFlags::INDIRECT_BUFFER)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+        let raw = unsafe { self.raw.create_buffer(&vk_info, None)? };
+
+        let buffer_requirements = unsafe { self.raw.get_buffer_memory_requirements(raw) };
+        let memory_type_bits = requirements.memory_type_bits & buffer_requirements.memory_type_bits;
+        let properties = unsafe {
+            self.instance
+                .raw
+                .get_physical_device_memory_properties(self.physical_device)
+        };
+        let memory_type = (0..properties.memory_type_count)
+            .filter(|&index| memory_type_bits & (1 << index) != 0)
+            .min_by_key(|&index| {
+                !properties.memory_types[index as usize]
+                    .property_flags
+                    .contains(vk::MemoryPropertyFlags::DEVICE_LOCAL)
+            });
+        let Some(memory_type) = memory_type else {
+            unsafe { self.raw.destroy_buffer(raw, None) };
+            return Err(crate::DeviceError::OutOfMemory);
+        };
+
+        let memory_info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(buffer_requirements.size)
+            .memory_type_index(memory_type);
+        let memory = match unsafe { self.raw.allocate_memory(&memory_info, None) } {
+            Ok(memory) => memory,
+            Err(err) => {
+                unsafe { self.raw.destroy_buffer(raw, None) };
+                return Err(err.into());
+            }
+        };
+        if let Err(err) = unsafe { self.raw.bind_buffer_memory(raw, memory, 0) } {
+            unsafe {
+                self.raw.destroy_buffer(raw, None);
+                self.raw.free_memory(memory, None);
+            }
+            return Err(err.into());
+        }
+        Ok((raw, memory))
+    }
+
     unsafe fn free_resources(&self) {
         for &raw in self.render_passes.lock().values() {
             unsafe { self.raw.destroy_render_pass(raw, None) };
@@ -325,7 +621,10 @@ impl gpu_alloc::MemoryDevice<vk::DeviceMemory> for super::DeviceShared {
         }
 
         match unsafe { self.raw.allocate_memory(&info, None) } {
-            Ok(memory) => Ok(memory),
+            Ok(memory) => {
+                self.device_memory.lock().insert(memory, size);
+                Ok(memory)
+            }
             Err(vk::Result::ERROR_OUT_OF_DEVICE_MEMORY) => {
                 Err(gpu_alloc::OutOfMemory::OutOfDeviceMemory)
             }
@@ -338,6 +637,7 @@ impl gpu_alloc::MemoryDevice<vk::DeviceMemory> for super::DeviceShared {
     }
 
     unsafe fn deallocate_memory(&self, memory: vk::DeviceMemory) {
+        self.device_memory.lock().remove(&memory);
         unsafe { self.raw.free_memory(memory, None) };
     }
 
@@ -385,6 +685,29 @@ impl gpu_alloc::MemoryDevice<vk::DeviceMemory> for super::DeviceShared {
     }
 }
 
+impl super::MemoryAllocator {
+    unsafe fn alloc(
+        &mut self,
+        device: &super::DeviceShared,
+        request: gpu_alloc::Request,
+    ) -> Result<gpu_alloc::MemoryBlock<vk::DeviceMemory>, gpu_alloc::AllocationError> {
+        let block = unsafe { self.raw.alloc(device, request) }?;
+        self.allocation_count += 1;
+        self.allocated_bytes += block.size();
+        Ok(block)
+    }
+
+    unsafe fn dealloc(
+        &mut self,
+        device: &super::DeviceShared,
+        block: gpu_alloc::MemoryBlock<vk::DeviceMemory>,
+    ) {
+        self.allocation_count -= 1;
+        self.allocated_bytes -= block.size();
+        unsafe { self.raw.dealloc(device, block) };
+    }
+}
+
 impl
     gpu_descriptor::DescriptorDevice<vk::DescriptorSetLayout, vk::DescriptorPool, vk::DescriptorSet>
     for super::DeviceShared
@@ -530,6 +853,13 @@ impl
     }
 }
 
+/// A depth/stencil resolve attachment of a subpass.
+struct DepthStencilResolve {
+    reference: vk::AttachmentReference,
+    depth_mode: vk::ResolveModeFlags,
+    stencil_mode: vk::ResolveModeFlags,
+}
+
 struct CompiledStage {
     create_info: vk::PipelineShaderStageCreateInfo,
     _entry_point: CString,
@@ -684,6 +1014,7 @@ impl super::Device {
             raw: vk_image,
             drop_guard,
             block: None,
+            external_memory: None,
             usage: desc.usage,
             format: desc.format,
             raw_flags: vk::ImageCreateFlags::empty(),
@@ -700,7 +1031,381 @@ impl super::Device {
         super::Buffer {
             raw: vk_buffer,
             block: None,
+            placement: None,
+            external_memory: None,
+        }
+    }
+
+    unsafe fn create_raw_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        external_handle_types: vk::ExternalMemoryHandleTypeFlags,
+    ) -> Result<(vk::Buffer, vk::BufferUsageFlags), crate::DeviceError> {
+        let mut vk_usage = conv::map_buffer_usage(desc.usage);
+        // Vertex buffers may be bound from their address by generated commands.
+        if self
+            .shared
+            .extension_fns
+            .device_generated_commands
+            .is_some()
+            && desc.usage.contains(crate::BufferUses::VERTEX)
+        {
+            vk_usage |= vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
+        }
+        // Conditional rendering reads its condition from indirect buffers.
+        if self.shared.extension_fns.conditional_rendering.is_some()
+            && desc.usage.contains(crate::BufferUses::INDIRECT)
+        {
+            vk_usage |= vk::BufferUsageFlags::CONDITIONAL_RENDERING_EXT;
+        }
+        let mut external_info =
+            vk::ExternalMemoryBufferCreateInfo::builder().handle_types(external_handle_types);
+        let mut vk_info = vk::BufferCreateInfo::builder()
+            .size(desc.size)
+            .usage(vk_usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+        if !external_handle_types.is_empty() {
+            vk_info = vk_info.push_next(&mut external_info);
+        }
+
+        let raw = unsafe { self.shared.raw.create_buffer(&vk_info, None)? };
+        Ok((raw, vk_usage))
+    }
+
+    /// Creates an image for `desc`, laid out as `dma_buf` if one is given.
+    unsafe fn create_raw_image(
+        &self,
+        desc: &crate::TextureDescriptor,
+        dma_buf: Option<&wgt::DmaBufImage>,
+    ) -> Result<(vk::Image, vk::ImageCreateFlags, Vec<wgt::TextureFormat>), crate::DeviceError>
+    {
+        let copy_size = desc.copy_extent();
+
+        let mut raw_flags = vk::ImageCreateFlags::empty();
+        if desc.is_cube_compatible() {
+            raw_flags |= vk::ImageCreateFlags::CUBE_COMPATIBLE;
+        }
+
+        let original_format = self.shared.private_caps.map_texture_format(desc.format);
+        let mut vk_view_formats = vec![];
+        let mut wgt_view_formats = vec![];
+        if !desc.view_formats.is_empty() {
+            raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
+            wgt_view_formats = desc.view_formats.clone();
+            wgt_view_formats.push(desc.format);
+
+            if self.shared.private_caps.image_format_list {
+                vk_view_formats = desc
+                    .view_formats
+                    .iter()
+                    .map(|f| self.shared.private_caps.map_texture_format(*f))
+                    .collect();
+                vk_view_formats.push(original_format)
+            }
+        }
+        if desc.format.is_multi_planar_format() {
+            raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
+        }
+        if desc.memory_flags.contains(crate::MemoryFlags::TILED) {
+            raw_flags |= vk::ImageCreateFlags::SPARSE_BINDING
+                | vk::ImageCreateFlags::SPARSE_RESIDENCY
+                | vk::ImageCreateFlags::SPARSE_ALIASED;
+        }
+
+        let mut vk_info = vk::ImageCreateInfo::builder()
+            .flags(raw_flags)
+            .image_type(conv::map_texture_dimension(desc.dimension))
+            .format(original_format)
+            .extent(conv::map_copy_extent(&copy_size))
+            .mip_levels(desc.mip_level_count)
+            .array_layers(desc.array_layer_count())
+            .samples(vk::SampleCountFlags::from_raw(desc.sample_count))
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(conv::map_texture_usage(desc.usage))
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED);
+
+        let mut format_list_info = vk::ImageFormatListCreateInfo::builder();
+        if !vk_view_formats.is_empty() {
+            format_list_info = format_list_info.view_formats(&vk_view_formats);
+            vk_info = vk_info.push_next(&mut format_list_info);
+        }
+
+        let plane_layouts;
+        let mut external_memory_info;
+        let mut drm_modifier_info;
+        if let Some(dma_buf) = dma_buf {
+            plane_layouts = dma_buf
+                .planes
+                .iter()
+                .map(|plane| vk::SubresourceLayout {
+                    offset: plane.offset,
+                    row_pitch: plane.stride,
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+            external_memory_info = vk::ExternalMemoryImageCreateInfo::builder()
+                .handle_types(vk::ExternalMemoryHandleTypeFlags::DMA_BUF_EXT);
+            drm_modifier_info = vk::ImageDrmFormatModifierExplicitCreateInfoEXT::builder()
+                .drm_format_modifier(dma_buf.modifier)
+                .plane_layouts(&plane_layouts);
+            vk_info = vk_info
+                .tiling(vk::ImageTiling::DRM_FORMAT_MODIFIER_EXT)
+                .push_next(&mut external_memory_info)
+                .push_next(&mut drm_modifier_info);
+        }
+
+        let raw = unsafe { self.shared.raw.create_image(&vk_info, None)? };
+        Ok((raw, raw_flags, wgt_view_formats))
+    }
+
+    /// Creates a texture bound to the memory of `dma_buf`.
+    #[cfg(unix)]
+    unsafe fn import_dma_buf(
+        &self,
+        desc: &crate::TextureDescriptor,
+        dma_buf: &wgt::DmaBufImage,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        use std::os::fd::{AsRawFd as _, BorrowedFd, IntoRawFd as _};
+
+        let external_memory_fd = self
+            .shared
+            .extension_fns
+            .external_memory_fd
+            .as_ref()
+            .expect("Feature `EXTERNAL_TEXTURE_IMPORT` not enabled");
+
+        // A successful import takes ownership of the fd, so import a duplicate
+        // to leave the caller's one open.
+        let fd = unsafe { BorrowedFd::borrow_raw(dma_buf.fd) }
+            .try_clone_to_owned()
+            .map_err(|_| crate::DeviceError::ResourceCreationFailed)?;
+        let fd_properties = unsafe {
+            external_memory_fd.get_memory_fd_properties(
+                vk::ExternalMemoryHandleTypeFlags::DMA_BUF_EXT,
+                fd.as_raw_fd(),
+            )
+        }
+        .map_err(|_| crate::DeviceError::ResourceCreationFailed)?;
+
+        let (raw, raw_flags, wgt_view_formats) =
+            unsafe { self.create_raw_image(desc, Some(dma_buf)) }?;
+        let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
+        let memory_types =
+            req.memory_type_bits & fd_properties.memory_type_bits & self.valid_ash_memory_types;
+        if memory_types == 0 {
+            unsafe { self.shared.raw.destroy_image(raw, None) };
+            return Err(crate::DeviceError::ResourceCreationFailed);
+        }
+
+        let mut import_info = vk::ImportMemoryFdInfoKHR::builder()
+            .handle_type(vk::ExternalMemoryHandleTypeFlags::DMA_BUF_EXT)
+            .fd(fd.as_raw_fd());
+        let mut dedicated_info = vk::MemoryDedicatedAllocateInfo::builder().image(raw);
+        let alloc_info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(req.size)
+            .memory_type_index(memory_types.trailing_zeros())
+            .push_next(&mut import_info)
+            .push_next(&mut dedicated_info);
+        let memory = match unsafe { self.shared.raw.allocate_memory(&alloc_info, None) } {
+            Ok(memory) => memory,
+            Err(err) => {
+                unsafe { self.shared.raw.destroy_image(raw, None) };
+                return Err(err.into());
+            }
+        };
+        let _ = fd.into_raw_fd();
+
+        if let Err(err) = unsafe { self.shared.raw.bind_image_memory(raw, memory, 0) } {
+            unsafe {
+                self.shared.raw.destroy_image(raw, None);
+                self.shared.raw.free_memory(memory, None);
+            }
+            return Err(err.into());
+        }
+
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::IMAGE, raw, label)
+            };
+        }
+
+        Ok(super::Texture {
+            raw,
+            drop_guard: None,
+            block: None,
+            external_memory: Some(memory),
+            usage: desc.usage,
+            format: desc.format,
+            raw_flags,
+            copy_size: desc.copy_extent(),
+            view_formats: wgt_view_formats,
+        })
+    }
+
+    /// Binds `raw` to dedicated memory, allocated with `external_info` to export
+    /// or import it. `raw` is destroyed if that fails.
+    unsafe fn bind_external_buffer_memory<T: vk::ExtendsMemoryAllocateInfo>(
+        &self,
+        desc: &crate::BufferDescriptor,
+        raw: vk::Buffer,
+        vk_usage: vk::BufferUsageFlags,
+        size: u64,
+        memory_type_bits: u32,
+        external_info: &mut T,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let properties = unsafe {
+            self.shared
+                .instance
+                .raw
+                .get_physical_device_memory_properties(self.shared.physical_device)
+        };
+        let memory_type_bits = memory_type_bits & self.valid_ash_memory_types;
+        let memory_type = (0..properties.memory_type_count)
+            .filter(|&index| memory_type_bits & (1 << index) != 0)
+            .min_by_key(|&index| {
+                !properties.memory_types[index as usize]
+                    .property_flags
+                    .contains(vk::MemoryPropertyFlags::DEVICE_LOCAL)
+            });
+        let Some(memory_type) = memory_type else {
+            unsafe { self.shared.raw.destroy_buffer(raw, None) };
+            return Err(crate::DeviceError::ResourceCreationFailed);
+        };
+
+        let mut dedicated_info = vk::MemoryDedicatedAllocateInfo::builder().buffer(raw);
+        let mut flags_info =
+            vk::MemoryAllocateFlagsInfo::builder().flags(vk::MemoryAllocateFlags::DEVICE_ADDRESS);
+        let mut alloc_info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(size)
+            .memory_type_index(memory_type)
+            .push_next(external_info)
+            .push_next(&mut dedicated_info);
+        if vk_usage.contains(vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS) {
+            alloc_info = alloc_info.push_next(&mut flags_info);
+        }
+        let memory = match unsafe { self.shared.raw.allocate_memory(&alloc_info, None) } {
+            Ok(memory) => memory,
+            Err(err) => {
+                unsafe { self.shared.raw.destroy_buffer(raw, None) };
+                return Err(err.into());
+            }
+        };
+
+        if let Err(err) = unsafe { self.shared.raw.bind_buffer_memory(raw, memory, 0) } {
+            unsafe {
+                self.shared.raw.destroy_buffer(raw, None);
+                self.shared.raw.free_memory(memory, None);
+            }
+            return Err(err.into());
+        }
+
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::BUFFER, raw, label)
+            };
+        }
+
+        Ok(super::Buffer {
+            raw,
+            block: None,
+            placement: None,
+            external_memory: Some(super::ExternalBufferMemory { memory, size }),
+        })
+    }
+
+    /// Creates a buffer bound to the memory behind `fd`, exported with `size`.
+    #[cfg(unix)]
+    unsafe fn import_buffer_fd(
+        &self,
+        desc: &crate::BufferDescriptor,
+        fd: i32,
+        size: u64,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        use std::os::fd::{AsRawFd as _, BorrowedFd, IntoRawFd as _};
+
+        let handle_type = vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD;
+        // The fd is only consumed by a successful import, so the duplicate is
+        // closed on failure and the caller's one is left open either way.
+        let fd = unsafe { BorrowedFd::borrow_raw(fd) }
+            .try_clone_to_owned()
+            .map_err(|_| crate::DeviceError::ResourceCreationFailed)?;
+
+        let (raw, vk_usage) = unsafe { self.create_raw_buffer(desc, handle_type) }?;
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+        if size < req.size {
+            unsafe { self.shared.raw.destroy_buffer(raw, None) };
+            return Err(crate::DeviceError::ResourceCreationFailed);
+        }
+
+        let mut import_info = vk::ImportMemoryFdInfoKHR::builder()
+            .handle_type(handle_type)
+            .fd(fd.as_raw_fd());
+        let buffer = unsafe {
+            self.bind_external_buffer_memory(
+                desc,
+                raw,
+                vk_usage,
+                size,
+                req.memory_type_bits,
+                &mut import_info,
+            )
+        }?;
+        let _ = fd.into_raw_fd();
+        Ok(buffer)
+    }
+
+    /// Creates a timeline semaphore sharing the payload of the semaphore behind `fd`.
+    #[cfg(unix)]
+    unsafe fn import_semaphore_fd(&self, fd: i32) -> Result<super::Fence, crate::DeviceError> {
+        use std::os::fd::{AsRawFd as _, BorrowedFd, IntoRawFd as _};
+
+        let external_semaphore_fd = self
+            .shared
+            .extension_fns
+            .external_semaphore_fd
+            .as_ref()
+            .expect("Feature `EXTERNAL_FENCE` not enabled");
+
+        // As with dma-bufs, import a duplicate to leave the caller's fd open.
+        let fd = unsafe { BorrowedFd::borrow_raw(fd) }
+            .try_clone_to_owned()
+            .map_err(|_| crate::DeviceError::ResourceCreationFailed)?;
+
+        let mut sem_type_info =
+            vk::SemaphoreTypeCreateInfo::builder().semaphore_type(vk::SemaphoreType::TIMELINE);
+        let vk_info = vk::SemaphoreCreateInfo::builder().push_next(&mut sem_type_info);
+        let raw = unsafe { self.shared.raw.create_semaphore(&vk_info, None) }?;
+
+        let import_info = vk::ImportSemaphoreFdInfoKHR::builder()
+            .semaphore(raw)
+            .handle_type(vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD)
+            .fd(fd.as_raw_fd());
+        if let Err(err) = unsafe { external_semaphore_fd.import_semaphore_fd(&import_info) } {
+            unsafe { self.shared.raw.destroy_semaphore(raw, None) };
+            return Err(err.into());
+        }
+        let _ = fd.into_raw_fd();
+
+        Ok(super::Fence::TimelineSemaphore(raw))
+    }
+
+    /// Checks that memory with `req` can be bound at `offset` of `heap`, and
+    /// returns the offset in the memory of the heap.
+    fn heap_memory_offset(
+        heap: &super::MemoryHeap,
+        req: vk::MemoryRequirements,
+        offset: wgt::BufferAddress,
+    ) -> Result<u64, crate::DeviceError> {
+        let memory_offset = heap.block.offset() + offset;
+        if req.memory_type_bits & (1 << heap.block.memory_type()) == 0
+            || memory_offset % req.alignment != 0
+        {
+            return Err(crate::DeviceError::ResourceCreationFailed);
         }
+        Ok(memory_offset)
     }
 
     fn create_shader_module_impl(
@@ -723,6 +1428,7 @@ impl super::Device {
         stage: &crate::ProgrammableStage<super::Api>,
         naga_stage: naga::ShaderStage,
         binding_map: &naga::back::spv::BindingMap,
+        transform_feedback: &naga::back::spv::TransformFeedbackMap,
     ) -> Result<CompiledStage, crate::PipelineError> {
         let stage_flags = crate::auxil::map_naga_stage(naga_stage);
         let vk_module = match *stage.module {
@@ -735,9 +1441,16 @@ impl super::Device {
                     entry_point: stage.entry_point.to_string(),
                     shader_stage: naga_stage,
                 };
+                // Mesh shading requires SPIR-V 1.4.
+                let needs_spirv_1_4 = matches!(
+                    naga_stage,
+                    naga::ShaderStage::Task | naga::ShaderStage::Mesh
+                ) && self.naga_options.lang_version < (1, 4);
                 let needs_temp_options = !runtime_checks
                     || !binding_map.is_empty()
-                    || naga_shader.debug_source.is_some();
+                    || !transform_feedback.is_empty()
+                    || naga_shader.debug_source.is_some()
+                    || needs_spirv_1_4;
                 let mut temp_options;
                 let options = if needs_temp_options {
                     temp_options = self.naga_options.clone();
@@ -753,6 +1466,12 @@ impl super::Device {
                     if !binding_map.is_empty() {
                         temp_options.binding_map = binding_map.clone();
                     }
+                    if !transform_feedback.is_empty() {
+                        temp_options.transform_feedback = transform_feedback.clone();
+                    }
+                    if needs_spirv_1_4 {
+                        temp_options.lang_version = (1, 4);
+                    }
 
                     if let Some(ref debug) = naga_shader.debug_source {
                         temp_options.debug_info = Some(naga::back::spv::DebugInfo {
@@ -796,18 +1515,298 @@ impl super::Device {
         })
     }
 
-    /// Returns the queue family index of the device's internal queue.
+    /// Creates a graphics pipeline from `shader_stages`, which are either a
+    /// vertex stage or task and mesh stages, optionally followed by a fragment
+    /// stage.
     ///
-    /// This is useful for constructing memory barriers needed for queue family ownership transfer when
-    /// external memory is involved (from/to `VK_QUEUE_FAMILY_EXTERNAL_KHR` and `VK_QUEUE_FAMILY_FOREIGN_EXT`
-    /// for example).
-    pub fn queue_family_index(&self) -> u32 {
-        self.shared.family_index
-    }
-
-    pub fn queue_index(&self) -> u32 {
-        self.shared.queue_index
-    }
+    /// The vertex input and input assembly states are ignored by pipelines
+    /// with a mesh stage.
+    #[allow(clippy::too_many_arguments)]
+    fn create_graphics_pipeline(
+        &self,
+        label: crate::Label,
+        layout: &super::PipelineLayout,
+        shader_stages: &[(&crate::ProgrammableStage<super::Api>, naga::ShaderStage)],
+        vertex_buffers: &[crate::VertexBufferLayout],
+        vertex_capture: &[crate::VertexCaptureBufferLayout],
+        primitive: &wgt::PrimitiveState,
+        depth_stencil: Option<&wgt::DepthStencilState>,
+        multisample: &wgt::MultisampleState,
+        color_targets: &[Option<wgt::ColorTargetState>],
+        multiview: Option<NonZeroU32>,
+        cache: Option<&super::PipelineCache>,
+    ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        let mut dynamic_states = vec![
+            vk::DynamicState::VIEWPORT,
+            vk::DynamicState::SCISSOR,
+            vk::DynamicState::BLEND_CONSTANTS,
+            vk::DynamicState::STENCIL_REFERENCE,
+        ];
+        // The shading rate is set at the beginning of every render pass, so all pipelines
+        // need to take it from the command buffer once the extension is enabled.
+        if self.shared.extension_fns.fragment_shading_rate.is_some() {
+            dynamic_states.push(vk::DynamicState::FRAGMENT_SHADING_RATE_KHR);
+        }
+        let mut compatible_rp_key = super::RenderPassKey {
+            sample_count: multisample.count,
+            multiview,
+            ..Default::default()
+        };
+        let mut stages = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+        let mut vk_vertex_buffers = Vec::with_capacity(vertex_buffers.len());
+        let mut vertex_attributes = Vec::new();
+
+        for (i, vb) in vertex_buffers.iter().enumerate() {
+            vk_vertex_buffers.push(vk::VertexInputBindingDescription {
+                binding: i as u32,
+                stride: vb.array_stride as u32,
+                input_rate: match vb.step_mode {
+                    wgt::VertexStepMode::Vertex => vk::VertexInputRate::VERTEX,
+                    wgt::VertexStepMode::Instance => vk::VertexInputRate::INSTANCE,
+                },
+            });
+            for at in vb.attributes {
+                vertex_attributes.push(vk::VertexInputAttributeDescription {
+                    location: at.shader_location,
+                    binding: i as u32,
+                    format: conv::map_vertex_format(at.format),
+                    offset: at.offset as u32,
+                });
+            }
+        }
+
+        let vk_vertex_input = vk::PipelineVertexInputStateCreateInfo::builder()
+            .vertex_binding_descriptions(&vk_vertex_buffers)
+            .vertex_attribute_descriptions(&vertex_attributes)
+            .build();
+
+        let vk_input_assembly = vk::PipelineInputAssemblyStateCreateInfo::builder()
+            .topology(conv::map_topology(primitive.topology))
+            .primitive_restart_enable(primitive.strip_index_format.is_some())
+            .build();
+
+        // The captured vertex shader outputs are decorated in the shader.
+        let mut transform_feedback = naga::back::spv::TransformFeedbackMap::new();
+        for (i, vcb) in vertex_capture.iter().enumerate() {
+            for at in vcb.attributes {
+                transform_feedback.insert(
+                    at.shader_location,
+                    naga::back::spv::TransformFeedbackTarget {
+                        buffer: i as u32,
+                        stride: vcb.array_stride as u32,
+                        offset: at.offset as u32,
+                    },
+                );
+            }
+        }
+        let no_transform_feedback = naga::back::spv::TransformFeedbackMap::new();
+
+        let mut compiled_stages = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+        for &(stage, naga_stage) in shader_stages {
+            let compiled = self.compile_stage(
+                stage,
+                naga_stage,
+                &layout.binding_arrays,
+                if naga_stage == naga::ShaderStage::Vertex {
+                    &transform_feedback
+                } else {
+                    &no_transform_feedback
+                },
+            )?;
+            stages.push(compiled.create_info);
+            compiled_stages.push(compiled);
+        }
+
+        let mut vk_rasterization = vk::PipelineRasterizationStateCreateInfo::builder()
+            .polygon_mode(conv::map_polygon_mode(primitive.polygon_mode))
+            .front_face(conv::map_front_face(primitive.front_face))
+            .line_width(1.0)
+            .depth_clamp_enable(primitive.unclipped_depth);
+        if let Some(face) = primitive.cull_mode {
+            vk_rasterization = vk_rasterization.cull_mode(conv::map_cull_face(face))
+        }
+        let mut vk_rasterization_conservative_state =
+            vk::PipelineRasterizationConservativeStateCreateInfoEXT::builder()
+                .conservative_rasterization_mode(vk::ConservativeRasterizationModeEXT::OVERESTIMATE)
+                .build();
+        if primitive.conservative {
+            vk_rasterization = vk_rasterization.push_next(&mut vk_rasterization_conservative_state);
+        }
+
+        let mut vk_depth_stencil = vk::PipelineDepthStencilStateCreateInfo::builder();
+        if let Some(ds) = depth_stencil {
+            let vk_format = self.shared.private_caps.map_texture_format(ds.format);
+            let vk_layout = if ds.is_read_only(primitive.cull_mode) {
+                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL
+            } else {
+                vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL
+            };
+            compatible_rp_key.depth_stencil = Some(super::DepthStencilAttachmentKey {
+                base: super::AttachmentKey::compatible(vk_format, vk_layout),
+                stencil_ops: crate::AttachmentOps::all(),
+                resolve: None,
+            });
+
+            if ds.is_depth_enabled() {
+                vk_depth_stencil = vk_depth_stencil
+                    .depth_test_enable(true)
+                    .depth_write_enable(ds.depth_write_enabled)
+                    .depth_compare_op(conv::map_comparison(ds.depth_compare));
+            }
+            if ds.stencil.is_enabled() {
+                let s = &ds.stencil;
+                let front = conv::map_stencil_face(&s.front, s.read_mask, s.write_mask);
+                let back = conv::map_stencil_face(&s.back, s.read_mask, s.write_mask);
+                vk_depth_stencil = vk_depth_stencil
+                    .stencil_test_enable(true)
+                    .front(front)
+                    .back(back);
+            }
+
+            if ds.bias.is_enabled() {
+                vk_rasterization = vk_rasterization
+                    .depth_bias_enable(true)
+                    .depth_bias_constant_factor(ds.bias.constant as f32)
+                    .depth_bias_clamp(ds.bias.clamp)
+                    .depth_bias_slope_factor(ds.bias.slope_scale);
+            }
+        }
+
+        let vk_viewport = vk::PipelineViewportStateCreateInfo::builder()
+            .flags(vk::PipelineViewportStateCreateFlags::empty())
+            .scissor_count(1)
+            .viewport_count(1)
+            .build();
+
+        let vk_sample_mask = [multisample.mask as u32, (multisample.mask >> 32) as u32];
+        let vk_multisample = vk::PipelineMultisampleStateCreateInfo::builder()
+            .rasterization_samples(vk::SampleCountFlags::from_raw(multisample.count))
+            .alpha_to_coverage_enable(multisample.alpha_to_coverage_enabled)
+            .sample_mask(&vk_sample_mask)
+            .build();
+
+        let mut vk_attachments = Vec::with_capacity(color_targets.len());
+        for cat in color_targets {
+            let (key, attarchment) = if let Some(cat) = cat.as_ref() {
+                let mut vk_attachment = vk::PipelineColorBlendAttachmentState::builder()
+                    .color_write_mask(vk::ColorComponentFlags::from_raw(cat.write_mask.bits()));
+                if let Some(ref blend) = cat.blend {
+                    let (color_op, color_src, color_dst) = conv::map_blend_component(&blend.color);
+                    let (alpha_op, alpha_src, alpha_dst) = conv::map_blend_component(&blend.alpha);
+                    vk_attachment = vk_attachment
+                        .blend_enable(true)
+                        .color_blend_op(color_op)
+                        .src_color_blend_factor(color_src)
+                        .dst_color_blend_factor(color_dst)
+                        .alpha_blend_op(alpha_op)
+                        .src_alpha_blend_factor(alpha_src)
+                        .dst_alpha_blend_factor(alpha_dst);
+                }
+
+                let vk_format = self.shared.private_caps.map_texture_format(cat.format);
+                (
+                    Some(super::ColorAttachmentKey {
+                        base: super::AttachmentKey::compatible(
+                            vk_format,
+                            vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+                        ),
+                        resolve: None,
+                    }),
+                    vk_attachment.build(),
+                )
+            } else {
+                (None, vk::PipelineColorBlendAttachmentState::default())
+            };
+
+            compatible_rp_key.colors.push(key);
+            vk_attachments.push(attarchment);
+        }
+
+        let vk_color_blend = vk::PipelineColorBlendStateCreateInfo::builder()
+            .attachments(&vk_attachments)
+            .build();
+
+        let vk_dynamic_state = vk::PipelineDynamicStateCreateInfo::builder()
+            .dynamic_states(&dynamic_states)
+            .build();
+
+        let raw_pass = self
+            .shared
+            .make_render_pass(compatible_rp_key)
+            .map_err(crate::DeviceError::from)?;
+
+        // Pipelines may be used by generated commands.
+        let vk_flags = if self
+            .shared
+            .extension_fns
+            .device_generated_commands
+            .is_some()
+        {
+            vk::PipelineCreateFlags::INDIRECT_BINDABLE_NV
+        } else {
+            vk::PipelineCreateFlags::empty()
+        };
+
+        let vk_infos = [{
+            vk::GraphicsPipelineCreateInfo::builder()
+                .flags(vk_flags)
+                .layout(layout.raw)
+                .stages(&stages)
+                .vertex_input_state(&vk_vertex_input)
+                .input_assembly_state(&vk_input_assembly)
+                .rasterization_state(&vk_rasterization)
+                .viewport_state(&vk_viewport)
+                .multisample_state(&vk_multisample)
+                .depth_stencil_state(&vk_depth_stencil)
+                .color_blend_state(&vk_color_blend)
+                .dynamic_state(&vk_dynamic_state)
+                .render_pass(raw_pass)
+                .build()
+        }];
+
+        let mut raw_vec = {
+            profiling::scope!("vkCreateGraphicsPipelines");
+            unsafe {
+                self.shared
+                    .raw
+                    .create_graphics_pipelines(
+                        cache.map_or(vk::PipelineCache::null(), |cache| cache.raw),
+                        &vk_infos,
+                        None,
+                    )
+                    .map_err(|(_, e)| crate::DeviceError::from(e))
+            }?
+        };
+
+        let raw = raw_vec.pop().unwrap();
+        if let Some(label) = label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::PIPELINE, raw, label)
+            };
+        }
+
+        for compiled in compiled_stages {
+            if let Some(raw_module) = compiled.temp_raw_module {
+                unsafe { self.shared.raw.destroy_shader_module(raw_module, None) };
+            }
+        }
+
+        Ok(super::RenderPipeline { raw })
+    }
+
+    /// Returns the queue family index of the device's internal queue.
+    ///
+    /// This is useful for constructing memory barriers needed for queue family ownership transfer when
+    /// external memory is involved (from/to `VK_QUEUE_FAMILY_EXTERNAL_KHR` and `VK_QUEUE_FAMILY_FOREIGN_EXT`
+    /// for example).
+    pub fn queue_family_index(&self) -> u32 {
+        self.shared.family_index
+    }
+
+    pub fn queue_index(&self) -> u32 {
+        self.shared.queue_index
+    }
 
     pub fn raw_device(&self) -> &ash::Device {
         &self.shared.raw
@@ -828,11 +1827,23 @@ impl super::Device {
     pub fn shared_instance(&self) -> &super::InstanceShared {
         &self.shared.instance
     }
+
+    /// Returns the current statistics of the memory allocator.
+    pub fn memory_allocator_stats(&self) -> super::MemoryAllocatorStats {
+        let mem_allocator = self.mem_allocator.lock();
+        let device_memory = self.shared.device_memory.lock();
+        super::MemoryAllocatorStats {
+            allocation_count: mem_allocator.allocation_count,
+            allocated_bytes: mem_allocator.allocated_bytes,
+            device_memory_count: device_memory.len() as u64,
+            device_memory_bytes: device_memory.values().sum(),
+        }
+    }
 }
 
 impl crate::Device<super::Api> for super::Device {
     unsafe fn exit(self, queue: super::Queue) {
-        unsafe { self.mem_allocator.into_inner().cleanup(&*self.shared) };
+        unsafe { self.mem_allocator.into_inner().raw.cleanup(&*self.shared) };
         unsafe { self.desc_allocator.into_inner().cleanup(&*self.shared) };
         for &sem in queue.relay_semaphores.iter() {
             unsafe { self.shared.raw.destroy_semaphore(sem, None) };
@@ -840,16 +1851,18 @@ impl crate::Device<super::Api> for super::Device {
         unsafe { self.shared.free_resources() };
     }
 
+    unsafe fn destroy_queue(&self, queue: super::Queue) {
+        for &sem in queue.relay_semaphores.iter() {
+            unsafe { self.shared.raw.destroy_semaphore(sem, None) };
+        }
+    }
+
     unsafe fn create_buffer(
         &self,
         desc: &crate::BufferDescriptor,
     ) -> Result<super::Buffer, crate::DeviceError> {
-        let vk_info = vk::BufferCreateInfo::builder()
-            .size(desc.size)
-            .usage(conv::map_buffer_usage(desc.usage))
-            .sharing_mode(vk::SharingMode::EXCLUSIVE);
-
-        let raw = unsafe { self.shared.raw.create_buffer(&vk_info, None)? };
+        let (raw, vk_usage) =
+            unsafe { self.create_raw_buffer(desc, vk::ExternalMemoryHandleTypeFlags::empty()) }?;
         let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
 
         let mut alloc_usage = if desc
@@ -874,6 +1887,10 @@ impl crate::Device<super::Api> for super::Device {
             gpu_alloc::UsageFlags::TRANSIENT,
             desc.memory_flags.contains(crate::MemoryFlags::TRANSIENT),
         );
+        alloc_usage.set(
+            gpu_alloc::UsageFlags::DEVICE_ADDRESS,
+            vk_usage.contains(vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS),
+        );
 
         let alignment_mask = if desc.usage.intersects(
             crate::BufferUses::TOP_LEVEL_ACCELERATION_STRUCTURE_INPUT
@@ -912,6 +1929,8 @@ impl crate::Device<super::Api> for super::Device {
         Ok(super::Buffer {
             raw,
             block: Some(Mutex::new(block)),
+            placement: None,
+            external_memory: None,
         })
     }
     unsafe fn destroy_buffer(&self, buffer: super::Buffer) {
@@ -923,6 +1942,9 @@ impl crate::Device<super::Api> for super::Device {
                     .dealloc(&*self.shared, block.into_inner())
             };
         }
+        if let Some(external_memory) = buffer.external_memory {
+            unsafe { self.shared.raw.free_memory(external_memory.memory, None) };
+        }
     }
 
     unsafe fn map_buffer(
@@ -938,6 +1960,16 @@ impl crate::Device<super::Api> for super::Device {
                 .props()
                 .contains(gpu_alloc::MemoryPropertyFlags::HOST_COHERENT);
             Ok(crate::BufferMapping { ptr, is_coherent })
+        } else if let Some(mapping) = buffer
+            .placement
+            .as_ref()
+            .and_then(|placement| placement.mapping.as_ref())
+        {
+            let ptr = unsafe { mapping.ptr.as_ptr().add(range.start as usize) };
+            Ok(crate::BufferMapping {
+                ptr: ptr::NonNull::new(ptr).unwrap(),
+                is_coherent: mapping.is_coherent,
+            })
         } else {
             Err(crate::DeviceError::OutOfMemory)
         }
@@ -946,6 +1978,9 @@ impl crate::Device<super::Api> for super::Device {
         if let Some(ref block) = buffer.block {
             unsafe { block.lock().unmap(&*self.shared) };
             Ok(())
+        } else if buffer.placement.is_some() {
+            // The heap stays mapped.
+            Ok(())
         } else {
             Err(crate::DeviceError::OutOfMemory)
         }
@@ -981,90 +2016,67 @@ impl crate::Device<super::Api> for super::Device {
             .unwrap();
         }
     }
+    unsafe fn get_buffer_device_address(&self, buffer: &super::Buffer) -> wgt::BufferAddress {
+        let functions = self
+            .shared
+            .extension_fns
+            .device_generated_commands
+            .as_ref()
+            .expect("Feature `DEVICE_GENERATED_COMMANDS` not enabled");
+
+        unsafe {
+            functions.buffer_device_address.get_buffer_device_address(
+                &vk::BufferDeviceAddressInfo::builder().buffer(buffer.raw),
+            )
+        }
+    }
 
     unsafe fn create_texture(
         &self,
         desc: &crate::TextureDescriptor,
     ) -> Result<super::Texture, crate::DeviceError> {
         let copy_size = desc.copy_extent();
+        let tiled = desc.memory_flags.contains(crate::MemoryFlags::TILED);
+        let (raw, raw_flags, wgt_view_formats) = unsafe { self.create_raw_image(desc, None) }?;
 
-        let mut raw_flags = vk::ImageCreateFlags::empty();
-        if desc.is_cube_compatible() {
-            raw_flags |= vk::ImageCreateFlags::CUBE_COMPATIBLE;
-        }
-
-        let original_format = self.shared.private_caps.map_texture_format(desc.format);
-        let mut vk_view_formats = vec![];
-        let mut wgt_view_formats = vec![];
-        if !desc.view_formats.is_empty() {
-            raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
-            wgt_view_formats = desc.view_formats.clone();
-            wgt_view_formats.push(desc.format);
-
-            if self.shared.private_caps.image_format_list {
-                vk_view_formats = desc
-                    .view_formats
-                    .iter()
-                    .map(|f| self.shared.private_caps.map_texture_format(*f))
-                    .collect();
-                vk_view_formats.push(original_format)
-            }
-        }
-        if desc.format.is_multi_planar_format() {
-            raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
-        }
-
-        let mut vk_info = vk::ImageCreateInfo::builder()
-            .flags(raw_flags)
-            .image_type(conv::map_texture_dimension(desc.dimension))
-            .format(original_format)
-            .extent(conv::map_copy_extent(&copy_size))
-            .mip_levels(desc.mip_level_count)
-            .array_layers(desc.array_layer_count())
-            .samples(vk::SampleCountFlags::from_raw(desc.sample_count))
-            .tiling(vk::ImageTiling::OPTIMAL)
-            .usage(conv::map_texture_usage(desc.usage))
-            .sharing_mode(vk::SharingMode::EXCLUSIVE)
-            .initial_layout(vk::ImageLayout::UNDEFINED);
-
-        let mut format_list_info = vk::ImageFormatListCreateInfo::builder();
-        if !vk_view_formats.is_empty() {
-            format_list_info = format_list_info.view_formats(&vk_view_formats);
-            vk_info = vk_info.push_next(&mut format_list_info);
-        }
-
-        let raw = unsafe { self.shared.raw.create_image(&vk_info, None)? };
-        let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
-
-        let block = unsafe {
-            self.mem_allocator.lock().alloc(
-                &*self.shared,
-                gpu_alloc::Request {
-                    size: req.size,
-                    align_mask: req.alignment - 1,
-                    usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
-                    memory_types: req.memory_type_bits & self.valid_ash_memory_types,
-                },
-            )?
-        };
-
-        unsafe {
-            self.shared
-                .raw
-                .bind_image_memory(raw, *block.memory(), block.offset())?
-        };
-
-        if let Some(label) = desc.label {
-            unsafe {
-                self.shared
-                    .set_object_name(vk::ObjectType::IMAGE, raw, label)
-            };
+        // The tiles of sparse images are bound to tile pools instead.
+        let block = if tiled {
+            None
+        } else {
+            let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
+
+            let block = unsafe {
+                self.mem_allocator.lock().alloc(
+                    &*self.shared,
+                    gpu_alloc::Request {
+                        size: req.size,
+                        align_mask: req.alignment - 1,
+                        usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
+                        memory_types: req.memory_type_bits & self.valid_ash_memory_types,
+                    },
+                )?
+            };
+
+            unsafe {
+                self.shared
+                    .raw
+                    .bind_image_memory(raw, *block.memory(), block.offset())?
+            };
+            Some(block)
+        };
+
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::IMAGE, raw, label)
+            };
         }
 
         Ok(super::Texture {
             raw,
             drop_guard: None,
-            block: Some(block),
+            block,
+            external_memory: None,
             usage: desc.usage,
             format: desc.format,
             raw_flags,
@@ -1079,6 +2091,9 @@ impl crate::Device<super::Api> for super::Device {
         if let Some(block) = texture.block {
             unsafe { self.mem_allocator.lock().dealloc(&*self.shared, block) };
         }
+        if let Some(memory) = texture.external_memory {
+            unsafe { self.shared.raw.free_memory(memory, None) };
+        }
     }
 
     unsafe fn create_texture_view(
@@ -1092,6 +2107,7 @@ impl crate::Device<super::Api> for super::Device {
             .image(texture.raw)
             .view_type(conv::map_view_dimension(desc.dimension))
             .format(self.shared.private_caps.map_texture_format(desc.format))
+            .components(conv::map_component_mapping(desc.swizzle))
             .subresource_range(subresource_range);
         let layers =
             NonZeroU32::new(subresource_range.layer_count).expect("Unexpected zero layer count");
@@ -1135,6 +2151,10 @@ impl crate::Device<super::Api> for super::Device {
         Ok(super::TextureView {
             raw,
             layers,
+            extent: vk::Extent2D {
+                width: (texture.copy_size.width >> desc.range.base_mip_level).max(1),
+                height: (texture.copy_size.height >> desc.range.base_mip_level).max(1),
+            },
             attachment,
         })
     }
@@ -1217,12 +2237,15 @@ impl crate::Device<super::Api> for super::Device {
             temp: super::Temp::default(),
             free: Vec::new(),
             discarded: Vec::new(),
+            preprocess_buffers: Vec::new(),
             rpass_debug_marker_active: false,
             end_of_pass_timer_query: None,
+            shading_rate_combiner_op: vk::FragmentShadingRateCombinerOpKHR::KEEP,
         })
     }
-    unsafe fn destroy_command_encoder(&self, cmd_encoder: super::CommandEncoder) {
+    unsafe fn destroy_command_encoder(&self, mut cmd_encoder: super::CommandEncoder) {
         unsafe {
+            cmd_encoder.free_preprocess_buffers();
             // `vkDestroyCommandPool` also frees any command buffers allocated
             // from that pool, so there's no need to explicitly call
             // `vkFreeCommandBuffers` on `cmd_encoder`'s `free` and `discarded`
@@ -1314,8 +2337,11 @@ impl crate::Device<super::Api> for super::Device {
         let partially_bound = desc
             .flags
             .contains(crate::BindGroupLayoutFlags::PARTIALLY_BOUND);
+        let update_after_bind = desc
+            .flags
+            .contains(crate::BindGroupLayoutFlags::UPDATE_AFTER_BIND);
 
-        let vk_info = if partially_bound {
+        let vk_info = if partially_bound || update_after_bind {
             binding_flag_vec = desc
                 .entries
                 .iter()
@@ -1326,6 +2352,21 @@ impl crate::Device<super::Api> for super::Device {
                         flags |= vk::DescriptorBindingFlags::PARTIALLY_BOUND;
                     }
 
+                    // Only the update-after-bind features of these descriptor types are
+                    // required by `PARTIALLY_BOUND_BINDING_ARRAY`.
+                    let update_after_bind_type = match entry.ty {
+                        wgt::BindingType::Buffer { ty, .. } => {
+                            ty != wgt::BufferBindingType::Uniform
+                        }
+                        wgt::BindingType::Sampler { .. }
+                        | wgt::BindingType::Texture { .. }
+                        | wgt::BindingType::StorageTexture { .. } => true,
+                        wgt::BindingType::AccelerationStructure => false,
+                    };
+                    if update_after_bind && entry.count.is_some() && update_after_bind_type {
+                        flags |= vk::DescriptorBindingFlags::UPDATE_AFTER_BIND;
+                    }
+
                     flags
                 })
                 .collect::<Vec<_>>();
@@ -1333,6 +2374,11 @@ impl crate::Device<super::Api> for super::Device {
             binding_flag_info = vk::DescriptorSetLayoutBindingFlagsCreateInfo::builder()
                 .binding_flags(&binding_flag_vec);
 
+            let vk_info = if update_after_bind {
+                vk_info.flags(vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL)
+            } else {
+                vk_info
+            };
             vk_info.push_next(&mut binding_flag_info)
         } else {
             vk_info
@@ -1356,6 +2402,7 @@ impl crate::Device<super::Api> for super::Device {
             desc_count,
             types: types.into_boxed_slice(),
             binding_arrays,
+            update_after_bind,
         })
     }
     unsafe fn destroy_bind_group_layout(&self, bg_layout: super::BindGroupLayout) {
@@ -1421,9 +2468,21 @@ impl crate::Device<super::Api> for super::Device {
         Ok(super::PipelineLayout {
             raw,
             binding_arrays,
+            indirect_commands_layouts: Mutex::default(),
         })
     }
     unsafe fn destroy_pipeline_layout(&self, pipeline_layout: super::PipelineLayout) {
+        if let Some(ref fns) = self.shared.extension_fns.device_generated_commands {
+            for (_, raw) in pipeline_layout.indirect_commands_layouts.into_inner() {
+                unsafe {
+                    (fns.raw.destroy_indirect_commands_layout_nv)(
+                        self.shared.raw.handle(),
+                        raw,
+                        ptr::null(),
+                    )
+                };
+            }
+        }
         unsafe {
             self.shared
                 .raw
@@ -1439,7 +2498,11 @@ impl crate::Device<super::Api> for super::Device {
             self.desc_allocator.lock().allocate(
                 &*self.shared,
                 &desc.layout.raw,
-                gpu_descriptor::DescriptorSetLayoutCreateFlags::empty(),
+                if desc.layout.update_after_bind {
+                    gpu_descriptor::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND
+                } else {
+                    gpu_descriptor::DescriptorSetLayoutCreateFlags::empty()
+                },
                 &desc.layout.desc_count,
                 1,
             )?
@@ -1646,235 +2709,50 @@ impl crate::Device<super::Api> for super::Device {
         &self,
         desc: &crate::RenderPipelineDescriptor<super::Api>,
     ) -> Result<super::RenderPipeline, crate::PipelineError> {
-        let dynamic_states = [
-            vk::DynamicState::VIEWPORT,
-            vk::DynamicState::SCISSOR,
-            vk::DynamicState::BLEND_CONSTANTS,
-            vk::DynamicState::STENCIL_REFERENCE,
-        ];
-        let mut compatible_rp_key = super::RenderPassKey {
-            sample_count: desc.multisample.count,
-            multiview: desc.multiview,
-            ..Default::default()
-        };
-        let mut stages = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
-        let mut vertex_buffers = Vec::with_capacity(desc.vertex_buffers.len());
-        let mut vertex_attributes = Vec::new();
-
-        for (i, vb) in desc.vertex_buffers.iter().enumerate() {
-            vertex_buffers.push(vk::VertexInputBindingDescription {
-                binding: i as u32,
-                stride: vb.array_stride as u32,
-                input_rate: match vb.step_mode {
-                    wgt::VertexStepMode::Vertex => vk::VertexInputRate::VERTEX,
-                    wgt::VertexStepMode::Instance => vk::VertexInputRate::INSTANCE,
-                },
-            });
-            for at in vb.attributes {
-                vertex_attributes.push(vk::VertexInputAttributeDescription {
-                    location: at.shader_location,
-                    binding: i as u32,
-                    format: conv::map_vertex_format(at.format),
-                    offset: at.offset as u32,
-                });
-            }
-        }
-
-        let vk_vertex_input = vk::PipelineVertexInputStateCreateInfo::builder()
-            .vertex_binding_descriptions(&vertex_buffers)
-            .vertex_attribute_descriptions(&vertex_attributes)
-            .build();
-
-        let vk_input_assembly = vk::PipelineInputAssemblyStateCreateInfo::builder()
-            .topology(conv::map_topology(desc.primitive.topology))
-            .primitive_restart_enable(desc.primitive.strip_index_format.is_some())
-            .build();
-
-        let compiled_vs = self.compile_stage(
-            &desc.vertex_stage,
-            naga::ShaderStage::Vertex,
-            &desc.layout.binding_arrays,
-        )?;
-        stages.push(compiled_vs.create_info);
-        let compiled_fs = match desc.fragment_stage {
-            Some(ref stage) => {
-                let compiled = self.compile_stage(
-                    stage,
-                    naga::ShaderStage::Fragment,
-                    &desc.layout.binding_arrays,
-                )?;
-                stages.push(compiled.create_info);
-                Some(compiled)
-            }
-            None => None,
-        };
-
-        let mut vk_rasterization = vk::PipelineRasterizationStateCreateInfo::builder()
-            .polygon_mode(conv::map_polygon_mode(desc.primitive.polygon_mode))
-            .front_face(conv::map_front_face(desc.primitive.front_face))
-            .line_width(1.0)
-            .depth_clamp_enable(desc.primitive.unclipped_depth);
-        if let Some(face) = desc.primitive.cull_mode {
-            vk_rasterization = vk_rasterization.cull_mode(conv::map_cull_face(face))
-        }
-        let mut vk_rasterization_conservative_state =
-            vk::PipelineRasterizationConservativeStateCreateInfoEXT::builder()
-                .conservative_rasterization_mode(vk::ConservativeRasterizationModeEXT::OVERESTIMATE)
-                .build();
-        if desc.primitive.conservative {
-            vk_rasterization = vk_rasterization.push_next(&mut vk_rasterization_conservative_state);
-        }
-
-        let mut vk_depth_stencil = vk::PipelineDepthStencilStateCreateInfo::builder();
-        if let Some(ref ds) = desc.depth_stencil {
-            let vk_format = self.shared.private_caps.map_texture_format(ds.format);
-            let vk_layout = if ds.is_read_only(desc.primitive.cull_mode) {
-                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL
-            } else {
-                vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL
-            };
-            compatible_rp_key.depth_stencil = Some(super::DepthStencilAttachmentKey {
-                base: super::AttachmentKey::compatible(vk_format, vk_layout),
-                stencil_ops: crate::AttachmentOps::all(),
-            });
-
-            if ds.is_depth_enabled() {
-                vk_depth_stencil = vk_depth_stencil
-                    .depth_test_enable(true)
-                    .depth_write_enable(ds.depth_write_enabled)
-                    .depth_compare_op(conv::map_comparison(ds.depth_compare));
-            }
-            if ds.stencil.is_enabled() {
-                let s = &ds.stencil;
-                let front = conv::map_stencil_face(&s.front, s.read_mask, s.write_mask);
-                let back = conv::map_stencil_face(&s.back, s.read_mask, s.write_mask);
-                vk_depth_stencil = vk_depth_stencil
-                    .stencil_test_enable(true)
-                    .front(front)
-                    .back(back);
-            }
-
-            if ds.bias.is_enabled() {
-                vk_rasterization = vk_rasterization
-                    .depth_bias_enable(true)
-                    .depth_bias_constant_factor(ds.bias.constant as f32)
-                    .depth_bias_clamp(ds.bias.clamp)
-                    .depth_bias_slope_factor(ds.bias.slope_scale);
-            }
-        }
-
-        let vk_viewport = vk::PipelineViewportStateCreateInfo::builder()
-            .flags(vk::PipelineViewportStateCreateFlags::empty())
-            .scissor_count(1)
-            .viewport_count(1)
-            .build();
-
-        let vk_sample_mask = [
-            desc.multisample.mask as u32,
-            (desc.multisample.mask >> 32) as u32,
-        ];
-        let vk_multisample = vk::PipelineMultisampleStateCreateInfo::builder()
-            .rasterization_samples(vk::SampleCountFlags::from_raw(desc.multisample.count))
-            .alpha_to_coverage_enable(desc.multisample.alpha_to_coverage_enabled)
-            .sample_mask(&vk_sample_mask)
-            .build();
-
-        let mut vk_attachments = Vec::with_capacity(desc.color_targets.len());
-        for cat in desc.color_targets {
-            let (key, attarchment) = if let Some(cat) = cat.as_ref() {
-                let mut vk_attachment = vk::PipelineColorBlendAttachmentState::builder()
-                    .color_write_mask(vk::ColorComponentFlags::from_raw(cat.write_mask.bits()));
-                if let Some(ref blend) = cat.blend {
-                    let (color_op, color_src, color_dst) = conv::map_blend_component(&blend.color);
-                    let (alpha_op, alpha_src, alpha_dst) = conv::map_blend_component(&blend.alpha);
-                    vk_attachment = vk_attachment
-                        .blend_enable(true)
-                        .color_blend_op(color_op)
-                        .src_color_blend_factor(color_src)
-                        .dst_color_blend_factor(color_dst)
-                        .alpha_blend_op(alpha_op)
-                        .src_alpha_blend_factor(alpha_src)
-                        .dst_alpha_blend_factor(alpha_dst);
-                }
-
-                let vk_format = self.shared.private_caps.map_texture_format(cat.format);
-                (
-                    Some(super::ColorAttachmentKey {
-                        base: super::AttachmentKey::compatible(
-                            vk_format,
-                            vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
-                        ),
-                        resolve: None,
-                    }),
-                    vk_attachment.build(),
-                )
-            } else {
-                (None, vk::PipelineColorBlendAttachmentState::default())
-            };
-
-            compatible_rp_key.colors.push(key);
-            vk_attachments.push(attarchment);
-        }
-
-        let vk_color_blend = vk::PipelineColorBlendStateCreateInfo::builder()
-            .attachments(&vk_attachments)
-            .build();
-
-        let vk_dynamic_state = vk::PipelineDynamicStateCreateInfo::builder()
-            .dynamic_states(&dynamic_states)
-            .build();
-
-        let raw_pass = self
-            .shared
-            .make_render_pass(compatible_rp_key)
-            .map_err(crate::DeviceError::from)?;
-
-        let vk_infos = [{
-            vk::GraphicsPipelineCreateInfo::builder()
-                .layout(desc.layout.raw)
-                .stages(&stages)
-                .vertex_input_state(&vk_vertex_input)
-                .input_assembly_state(&vk_input_assembly)
-                .rasterization_state(&vk_rasterization)
-                .viewport_state(&vk_viewport)
-                .multisample_state(&vk_multisample)
-                .depth_stencil_state(&vk_depth_stencil)
-                .color_blend_state(&vk_color_blend)
-                .dynamic_state(&vk_dynamic_state)
-                .render_pass(raw_pass)
-                .build()
-        }];
-
-        let mut raw_vec = {
-            profiling::scope!("vkCreateGraphicsPipelines");
-            unsafe {
-                self.shared
-                    .raw
-                    .create_graphics_pipelines(vk::PipelineCache::null(), &vk_infos, None)
-                    .map_err(|(_, e)| crate::DeviceError::from(e))
-            }?
-        };
-
-        let raw = raw_vec.pop().unwrap();
-        if let Some(label) = desc.label {
-            unsafe {
-                self.shared
-                    .set_object_name(vk::ObjectType::PIPELINE, raw, label)
-            };
-        }
-
-        if let Some(raw_module) = compiled_vs.temp_raw_module {
-            unsafe { self.shared.raw.destroy_shader_module(raw_module, None) };
-        }
-        if let Some(CompiledStage {
-            temp_raw_module: Some(raw_module),
-            ..
-        }) = compiled_fs
-        {
-            unsafe { self.shared.raw.destroy_shader_module(raw_module, None) };
-        }
-
-        Ok(super::RenderPipeline { raw })
+        let mut shader_stages = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+        shader_stages.push((&desc.vertex_stage, naga::ShaderStage::Vertex));
+        if let Some(ref stage) = desc.fragment_stage {
+            shader_stages.push((stage, naga::ShaderStage::Fragment));
+        }
+        self.create_graphics_pipeline(
+            desc.label,
+            desc.layout,
+            &shader_stages,
+            desc.vertex_buffers,
+            desc.vertex_capture,
+            &desc.primitive,
+            desc.depth_stencil.as_ref(),
+            &desc.multisample,
+            desc.color_targets,
+            desc.multiview,
+            desc.cache,
+        )
+    }
+    unsafe fn create_mesh_pipeline(
+        &self,
+        desc: &crate::MeshPipelineDescriptor<super::Api>,
+    ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        let mut shader_stages = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+        if let Some(ref stage) = desc.task_stage {
+            shader_stages.push((stage, naga::ShaderStage::Task));
+        }
+        shader_stages.push((&desc.mesh_stage, naga::ShaderStage::Mesh));
+        if let Some(ref stage) = desc.fragment_stage {
+            shader_stages.push((stage, naga::ShaderStage::Fragment));
+        }
+        self.create_graphics_pipeline(
+            desc.label,
+            desc.layout,
+            &shader_stages,
+            &[],
+            &[],
+            &desc.primitive,
+            desc.depth_stencil.as_ref(),
+            &desc.multisample,
+            desc.color_targets,
+            desc.multiview,
+            desc.cache,
+        )
     }
     unsafe fn destroy_render_pipeline(&self, pipeline: super::RenderPipeline) {
         unsafe { self.shared.raw.destroy_pipeline(pipeline.raw, None) };
@@ -1888,6 +2766,7 @@ impl crate::Device<super::Api> for super::Device {
             &desc.stage,
             naga::ShaderStage::Compute,
             &desc.layout.binding_arrays,
+            &naga::back::spv::TransformFeedbackMap::new(),
         )?;
 
         let vk_infos = [{
@@ -1902,7 +2781,12 @@ impl crate::Device<super::Api> for super::Device {
             unsafe {
                 self.shared
                     .raw
-                    .create_compute_pipelines(vk::PipelineCache::null(), &vk_infos, None)
+                    .create_compute_pipelines(
+                        desc.cache
+                            .map_or(vk::PipelineCache::null(), |cache| cache.raw),
+                        &vk_infos,
+                        None,
+                    )
                     .map_err(|(_, e)| crate::DeviceError::from(e))
             }?
         };
@@ -1924,6 +2808,35 @@ impl crate::Device<super::Api> for super::Device {
     unsafe fn destroy_compute_pipeline(&self, pipeline: super::ComputePipeline) {
         unsafe { self.shared.raw.destroy_pipeline(pipeline.raw, None) };
     }
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<super::PipelineCache, crate::PipelineCacheError> {
+        let mut vk_info = vk::PipelineCacheCreateInfo::builder();
+        if let Some(data) = desc.data {
+            if !self.shared.pipeline_cache_header_matches(data) {
+                return Err(crate::PipelineCacheError::Validation);
+            }
+            vk_info = vk_info.initial_data(data);
+        }
+
+        let raw = unsafe { self.shared.raw.create_pipeline_cache(&vk_info, None) }
+            .map_err(crate::DeviceError::from)?;
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::PIPELINE_CACHE, raw, label)
+            };
+        }
+
+        Ok(super::PipelineCache { raw })
+    }
+    unsafe fn destroy_pipeline_cache(&self, cache: super::PipelineCache) {
+        unsafe { self.shared.raw.destroy_pipeline_cache(cache.raw, None) };
+    }
+    unsafe fn pipeline_cache_get_data(&self, cache: &super::PipelineCache) -> Option<Vec<u8>> {
+        unsafe { self.shared.raw.get_pipeline_cache_data(cache.raw) }.ok()
+    }
 
     unsafe fn create_query_set(
         &self,
@@ -1963,6 +2876,313 @@ impl crate::Device<super::Api> for super::Device {
     unsafe fn destroy_query_set(&self, set: super::QuerySet) {
         unsafe { self.shared.raw.destroy_query_pool(set.raw, None) };
     }
+    unsafe fn create_tile_pool(
+        &self,
+        desc: &wgt::TilePoolDescriptor<crate::Label>,
+    ) -> Result<super::TilePool, crate::DeviceError> {
+        // Sparse images with the standard block shapes take device local memory
+        // aligned to their 64 KiB blocks, which is what `FAST_DEVICE_ACCESS` picks.
+        let block = unsafe {
+            self.mem_allocator.lock().alloc(
+                &*self.shared,
+                gpu_alloc::Request {
+                    size: desc.tile_count as u64 * wgt::TEXTURE_TILE_SIZE,
+                    align_mask: wgt::TEXTURE_TILE_SIZE - 1,
+                    usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
+                    memory_types: self.valid_ash_memory_types,
+                },
+            )?
+        };
+
+        Ok(super::TilePool { block })
+    }
+    unsafe fn destroy_tile_pool(&self, pool: super::TilePool) {
+        unsafe { self.mem_allocator.lock().dealloc(&*self.shared, pool.block) };
+    }
+
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &wgt::MemoryHeapDescriptor<crate::Label>,
+    ) -> Result<super::MemoryHeap, crate::DeviceError> {
+        let mut usage = match desc.memory_type {
+            wgt::MemoryHeapType::DeviceLocal => gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
+            wgt::MemoryHeapType::Upload => {
+                gpu_alloc::UsageFlags::HOST_ACCESS | gpu_alloc::UsageFlags::UPLOAD
+            }
+            wgt::MemoryHeapType::Readback => {
+                gpu_alloc::UsageFlags::HOST_ACCESS | gpu_alloc::UsageFlags::DOWNLOAD
+            }
+        };
+        // Placed vertex buffers may need their address, see `create_buffer`.
+        usage.set(
+            gpu_alloc::UsageFlags::DEVICE_ADDRESS,
+            self.shared
+                .extension_fns
+                .device_generated_commands
+                .is_some(),
+        );
+
+        // Aligning the heap to 64 KiB keeps the aligned offsets in it aligned
+        // in its memory too, as resources don't need more.
+        let mut block = unsafe {
+            self.mem_allocator.lock().alloc(
+                &*self.shared,
+                gpu_alloc::Request {
+                    size: desc.size,
+                    align_mask: (64 << 10) - 1,
+                    usage,
+                    memory_types: self.valid_ash_memory_types,
+                },
+            )?
+        };
+
+        let mapping = if usage.contains(gpu_alloc::UsageFlags::HOST_ACCESS) {
+            let ptr = unsafe { block.map(&*self.shared, 0, desc.size as usize)? };
+            let is_coherent = block
+                .props()
+                .contains(gpu_alloc::MemoryPropertyFlags::HOST_COHERENT);
+            Some(crate::BufferMapping { ptr, is_coherent })
+        } else {
+            None
+        };
+
+        Ok(super::MemoryHeap { block, mapping })
+    }
+    unsafe fn destroy_memory_heap(&self, mut heap: super::MemoryHeap) {
+        if heap.mapping.is_some() {
+            unsafe { heap.block.unmap(&*self.shared) };
+        }
+        unsafe { self.mem_allocator.lock().dealloc(&*self.shared, heap.block) };
+    }
+
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        let (raw, _) =
+            unsafe { self.create_raw_buffer(desc, vk::ExternalMemoryHandleTypeFlags::empty()) }?;
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+        unsafe { self.shared.raw.destroy_buffer(raw, None) };
+        Ok(wgt::MemoryRequirements {
+            size: req.size,
+            alignment: req.alignment,
+        })
+    }
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        let (raw, _, _) = unsafe { self.create_raw_image(desc, None) }?;
+        let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
+        unsafe { self.shared.raw.destroy_image(raw, None) };
+        Ok(wgt::MemoryRequirements {
+            size: req.size,
+            alignment: req.alignment,
+        })
+    }
+
+    unsafe fn create_placed_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let (raw, _) =
+            unsafe { self.create_raw_buffer(desc, vk::ExternalMemoryHandleTypeFlags::empty()) }?;
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+        let memory_offset = match Self::heap_memory_offset(heap, req, offset) {
+            Ok(memory_offset) => memory_offset,
+            Err(err) => {
+                unsafe { self.shared.raw.destroy_buffer(raw, None) };
+                return Err(err);
+            }
+        };
+
+        unsafe {
+            self.shared
+                .raw
+                .bind_buffer_memory(raw, *heap.block.memory(), memory_offset)?
+        };
+
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::BUFFER, raw, label)
+            };
+        }
+
+        let mapping = heap.mapping.as_ref().map(|mapping| crate::BufferMapping {
+            ptr: ptr::NonNull::new(unsafe { mapping.ptr.as_ptr().add(offset as usize) }).unwrap(),
+            is_coherent: mapping.is_coherent,
+        });
+        Ok(super::Buffer {
+            raw,
+            block: None,
+            placement: Some(super::BufferPlacement {
+                memory: *heap.block.memory(),
+                offset: memory_offset,
+                mapping,
+            }),
+            external_memory: None,
+        })
+    }
+    unsafe fn create_placed_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let (raw, raw_flags, wgt_view_formats) = unsafe { self.create_raw_image(desc, None) }?;
+        let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
+        let memory_offset = match Self::heap_memory_offset(heap, req, offset) {
+            Ok(memory_offset) => memory_offset,
+            Err(err) => {
+                unsafe { self.shared.raw.destroy_image(raw, None) };
+                return Err(err);
+            }
+        };
+
+        unsafe {
+            self.shared
+                .raw
+                .bind_image_memory(raw, *heap.block.memory(), memory_offset)?
+        };
+
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::IMAGE, raw, label)
+            };
+        }
+
+        // The heap owns the memory, so there is no block to free.
+        Ok(super::Texture {
+            raw,
+            drop_guard: None,
+            block: None,
+            external_memory: None,
+            usage: desc.usage,
+            format: desc.format,
+            raw_flags,
+            copy_size: desc.copy_extent(),
+            view_formats: wgt_view_formats,
+        })
+    }
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    unsafe fn import_external_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        source: wgt::ExternalImageSource,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        match source {
+            #[cfg(unix)]
+            wgt::ExternalImageSource::DmaBuf(dma_buf) => unsafe {
+                self.import_dma_buf(desc, &dma_buf)
+            },
+            _ => Err(crate::DeviceError::ResourceCreationFailed),
+        }
+    }
+
+    unsafe fn create_external_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let handle_type = vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD;
+        let (raw, vk_usage) = unsafe { self.create_raw_buffer(desc, handle_type) }?;
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+        let mut export_info = vk::ExportMemoryAllocateInfo::builder().handle_types(handle_type);
+        unsafe {
+            self.bind_external_buffer_memory(
+                desc,
+                raw,
+                vk_usage,
+                req.size,
+                req.memory_type_bits,
+                &mut export_info,
+            )
+        }
+    }
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    unsafe fn import_external_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        match memory.handle {
+            #[cfg(unix)]
+            wgt::ExternalMemoryHandle::OpaqueFd(fd) => unsafe {
+                self.import_buffer_fd(desc, fd, memory.size)
+            },
+            _ => Err(crate::DeviceError::ResourceCreationFailed),
+        }
+    }
+    unsafe fn export_external_buffer(
+        &self,
+        buffer: &super::Buffer,
+    ) -> Result<wgt::ExternalMemory, crate::DeviceError> {
+        let external_memory_fd = self
+            .shared
+            .extension_fns
+            .external_memory_fd
+            .as_ref()
+            .expect("Feature `EXTERNAL_MEMORY` not enabled");
+        let Some(ref external_memory) = buffer.external_memory else {
+            return Err(crate::DeviceError::ResourceCreationFailed);
+        };
+        let vk_info = vk::MemoryGetFdInfoKHR::builder()
+            .memory(external_memory.memory)
+            .handle_type(vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD);
+        let fd = unsafe { external_memory_fd.get_memory_fd(&vk_info) }?;
+        Ok(wgt::ExternalMemory {
+            handle: wgt::ExternalMemoryHandle::OpaqueFd(fd),
+            size: external_memory.size,
+        })
+    }
+
+    unsafe fn create_external_fence(&self) -> Result<super::Fence, crate::DeviceError> {
+        let mut export_info = vk::ExportSemaphoreCreateInfo::builder()
+            .handle_types(vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD);
+        let mut sem_type_info =
+            vk::SemaphoreTypeCreateInfo::builder().semaphore_type(vk::SemaphoreType::TIMELINE);
+        let vk_info = vk::SemaphoreCreateInfo::builder()
+            .push_next(&mut sem_type_info)
+            .push_next(&mut export_info);
+        let raw = unsafe { self.shared.raw.create_semaphore(&vk_info, None) }?;
+        Ok(super::Fence::TimelineSemaphore(raw))
+    }
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    unsafe fn import_external_fence(
+        &self,
+        handle: wgt::ExternalFenceHandle,
+    ) -> Result<super::Fence, crate::DeviceError> {
+        match handle {
+            #[cfg(unix)]
+            wgt::ExternalFenceHandle::OpaqueFd(fd) => unsafe { self.import_semaphore_fd(fd) },
+            _ => Err(crate::DeviceError::ResourceCreationFailed),
+        }
+    }
+    unsafe fn export_external_fence(
+        &self,
+        fence: &super::Fence,
+    ) -> Result<wgt::ExternalFenceHandle, crate::DeviceError> {
+        let external_semaphore_fd = self
+            .shared
+            .extension_fns
+            .external_semaphore_fd
+            .as_ref()
+            .expect("Feature `EXTERNAL_FENCE` not enabled");
+        let raw = match *fence {
+            super::Fence::TimelineSemaphore(raw) => raw,
+            super::Fence::FencePool { .. } => {
+                return Err(crate::DeviceError::ResourceCreationFailed)
+            }
+        };
+        let vk_info = vk::SemaphoreGetFdInfoKHR::builder()
+            .semaphore(raw)
+            .handle_type(vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD);
+        let fd = unsafe { external_semaphore_fd.get_semaphore_fd(&vk_info) }?;
+        Ok(wgt::ExternalFenceHandle::OpaqueFd(fd))
+    }
 
     unsafe fn create_fence(&self) -> Result<super::Fence, crate::DeviceError> {
         Ok(if self.shared.private_caps.timeline_semaphores {
@@ -2141,7 +3361,7 @@ impl crate::Device<super::Api> for super::Device {
                             triangle_data.index_type(conv::map_index_format(indices.format));
                         indices.count / 3
                     } else {
-                        triangles.vertex_count
+                        triangles.vertex_count / 3
                     };
 
                     let geometry = vk::AccelerationStructureGeometryKHR::builder()
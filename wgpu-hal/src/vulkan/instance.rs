@@ -82,6 +82,60 @@ unsafe extern "system" fn debug_utils_messenger_callback(
         unsafe { CStr::from_ptr(cd.p_message) }.to_string_lossy()
     };
 
+    if cfg!(debug_assertions) && level == log::Level::Error {
+        // Set canary and continue
+        crate::VALIDATION_CANARY.add(message.to_string());
+    }
+
+    if crate::auxil::debug_message::has_callbacks() {
+        let label_names = |labels: &[vk::DebugUtilsLabelEXT]| {
+            labels
+                .iter()
+                .flat_map(|dul_obj| {
+                    unsafe { dul_obj.p_label_name.as_ref() }.map(|lbl| {
+                        unsafe { CStr::from_ptr(lbl) }
+                            .to_string_lossy()
+                            .into_owned()
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut labels = Vec::new();
+        if cd.queue_label_count != 0 {
+            labels.extend(label_names(unsafe {
+                slice::from_raw_parts(cd.p_queue_labels, cd.queue_label_count as usize)
+            }));
+        }
+        if cd.cmd_buf_label_count != 0 {
+            labels.extend(label_names(unsafe {
+                slice::from_raw_parts(cd.p_cmd_buf_labels, cd.cmd_buf_label_count as usize)
+            }));
+        }
+        if cd.object_count != 0 {
+            let objects = unsafe { slice::from_raw_parts(cd.p_objects, cd.object_count as usize) };
+            labels.extend(objects.iter().flat_map(|obj_info| {
+                unsafe { obj_info.p_object_name.as_ref() }.map(|name| {
+                    unsafe { CStr::from_ptr(name) }
+                        .to_string_lossy()
+                        .into_owned()
+                })
+            }));
+        }
+
+        let reported = crate::auxil::debug_message::report(&wgt::DebugMessage {
+            severity: crate::auxil::debug_message::severity_of_level(level),
+            backend: Some(wgt::Backend::Vulkan),
+            message: format!(
+                "{:?} [{} (0x{:x})]\n\t{}",
+                message_type, message_id_name, cd.message_id_number, message,
+            ),
+            labels,
+        });
+        if reported {
+            return vk::FALSE;
+        }
+    }
+
     let _ = std::panic::catch_unwind(|| {
         log::log!(
             level,
@@ -146,11 +200,6 @@ unsafe extern "system" fn debug_utils_messenger_callback(
         });
     }
 
-    if cfg!(debug_assertions) && level == log::Level::Error {
-        // Set canary and continue
-        crate::VALIDATION_CANARY.add(message.to_string());
-    }
-
     vk::FALSE
 }
 
@@ -258,6 +307,12 @@ impl super::Instance {
             extensions.push(khr::XcbSurface::name());
             // VK_KHR_wayland_surface
             extensions.push(khr::WaylandSurface::name());
+            // VK_KHR_display
+            extensions.push(khr::Display::name());
+            // VK_EXT_direct_mode_display
+            extensions.push(vk::ExtDirectModeDisplayFn::name());
+            // VK_EXT_acquire_drm_display
+            extensions.push(vk::ExtAcquireDrmDisplayFn::name());
         }
         if cfg!(target_os = "android") {
             // VK_KHR_android_surface
@@ -514,6 +569,154 @@ impl super::Instance {
         Ok(self.create_surface_from_vk_surface_khr(surface))
     }
 
+    /// Creates a surface presenting to `plane` of the display behind `connector_id`,
+    /// set to the mode of `width` by `height` pixels refreshing at `refresh_rate`
+    /// millihertz.
+    ///
+    /// `fd` is an open DRM device, or a lease of one of its connectors, that is
+    /// DRM master. The display is acquired from it, and stays acquired until the
+    /// instance is destroyed.
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must be a valid DRM file descriptor, and stay open as long as the
+    ///   surface exists.
+    #[cfg(all(
+        unix,
+        not(any(target_os = "android", target_os = "ios", target_os = "macos"))
+    ))]
+    pub unsafe fn create_surface_from_drm(
+        &self,
+        fd: i32,
+        plane: u32,
+        connector_id: u32,
+        width: u32,
+        height: u32,
+        refresh_rate: u32,
+    ) -> Result<super::Surface, crate::InstanceError> {
+        for name in [khr::Display::name(), vk::ExtAcquireDrmDisplayFn::name()] {
+            if !self.shared.extensions.contains(&name) {
+                return Err(crate::InstanceError::new(format!(
+                    "Vulkan driver does not support {}",
+                    name.to_string_lossy()
+                )));
+            }
+        }
+
+        // The physical device is the one whose primary node is the device `fd` opened.
+        let drm_stat = {
+            let mut stat = std::mem::MaybeUninit::<libc::stat>::uninit();
+            if unsafe { libc::fstat(fd, stat.as_mut_ptr()) } != 0 {
+                return Err(crate::InstanceError::with_source(
+                    String::from("fstat() failed on the DRM file descriptor"),
+                    std::io::Error::last_os_error(),
+                ));
+            }
+            unsafe { stat.assume_init() }
+        };
+        let raw_devices = unsafe { self.shared.raw.enumerate_physical_devices() }.map_err(|e| {
+            crate::InstanceError::with_source(
+                String::from("enumerate_physical_devices() failed"),
+                e,
+            )
+        })?;
+        let physical_device = raw_devices
+            .into_iter()
+            .find(|&device| {
+                let supports_drm_properties = unsafe {
+                    self.shared
+                        .raw
+                        .enumerate_device_extension_properties(device)
+                }
+                .unwrap_or_default()
+                .iter()
+                .any(|ext| {
+                    crate::auxil::cstr_from_bytes_until_nul(&ext.extension_name)
+                        == Some(vk::ExtPhysicalDeviceDrmFn::name())
+                });
+                if !supports_drm_properties {
+                    return false;
+                }
+
+                let mut drm_properties = vk::PhysicalDeviceDrmPropertiesEXT::default();
+                let mut properties2 =
+                    vk::PhysicalDeviceProperties2KHR::builder().push_next(&mut drm_properties);
+                unsafe {
+                    self.shared
+                        .raw
+                        .get_physical_device_properties2(device, &mut properties2)
+                };
+                drm_properties.has_primary != vk::FALSE
+                    && libc::makedev(
+                        drm_properties.primary_major as _,
+                        drm_properties.primary_minor as _,
+                    ) == drm_stat.st_rdev
+            })
+            .ok_or_else(|| {
+                crate::InstanceError::new(String::from(
+                    "No physical device corresponds to the DRM file descriptor",
+                ))
+            })?;
+
+        let acquire_drm_display = ext::AcquireDrmDisplay::new(&self.shared.entry, &self.shared.raw);
+        let display =
+            unsafe { acquire_drm_display.get_drm_display(physical_device, fd, connector_id) }
+                .map_err(|e| {
+                    crate::InstanceError::with_source(
+                        String::from("vkGetDrmDisplayEXT() failed"),
+                        e,
+                    )
+                })?;
+        // Acquiring the display lets Vulkan do the modesetting.
+        unsafe { acquire_drm_display.acquire_drm_display(physical_device, fd, display) }.map_err(
+            |e| {
+                crate::InstanceError::with_source(
+                    String::from("vkAcquireDrmDisplayEXT() failed"),
+                    e,
+                )
+            },
+        )?;
+
+        let display_loader = khr::Display::new(&self.shared.entry, &self.shared.raw);
+        let modes = unsafe { display_loader.get_display_mode_properties(physical_device, display) }
+            .map_err(|e| {
+                crate::InstanceError::with_source(
+                    String::from("vkGetDisplayModePropertiesKHR() failed"),
+                    e,
+                )
+            })?;
+        let mode = modes
+            .iter()
+            .find(|mode| {
+                let parameters = mode.parameters;
+                parameters.visible_region.width == width
+                    && parameters.visible_region.height == height
+                    && parameters.refresh_rate == refresh_rate
+            })
+            .ok_or_else(|| {
+                crate::InstanceError::new(format!(
+                    "The display has no {width}x{height} mode at {refresh_rate} mHz"
+                ))
+            })?;
+
+        let surface = {
+            let info = vk::DisplaySurfaceCreateInfoKHR::builder()
+                .display_mode(mode.display_mode)
+                .plane_index(plane)
+                .transform(vk::SurfaceTransformFlagsKHR::IDENTITY)
+                .alpha_mode(vk::DisplayPlaneAlphaFlagsKHR::OPAQUE)
+                .image_extent(mode.parameters.visible_region);
+            unsafe { display_loader.create_display_plane_surface(&info, None) }.map_err(|e| {
+                crate::InstanceError::with_source(
+                    String::from("vkCreateDisplayPlaneSurfaceKHR() failed"),
+                    e,
+                )
+            })?
+        };
+
+        Ok(self.create_surface_from_vk_surface_khr(surface))
+    }
+
     #[cfg(metal)]
     fn create_surface_from_view(
         &self,
@@ -989,6 +1192,7 @@ impl crate::Surface<super::Api> for super::Surface {
                 raw: sc.images[index as usize],
                 drop_guard: None,
                 block: None,
+                external_memory: None,
                 usage: sc.config.usage,
                 format: sc.config.format,
                 raw_flags,
@@ -50,7 +50,7 @@ use ash::{
 use parking_lot::{Mutex, RwLock};
 
 const MILLIS_TO_NANOS: u64 = 1_000_000;
-const MAX_TOTAL_ATTACHMENTS: usize = crate::MAX_COLOR_ATTACHMENTS * 2 + 1;
+const MAX_TOTAL_ATTACHMENTS: usize = crate::MAX_COLOR_ATTACHMENTS * 2 + 3;
 
 #[derive(Clone, Debug)]
 pub struct Api;
@@ -80,6 +80,9 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type PipelineCache = PipelineCache;
+    type TilePool = TilePool;
+    type MemoryHeap = MemoryHeap;
 }
 
 struct DebugUtils {
@@ -186,6 +189,7 @@ pub struct Adapter {
     downlevel_flags: wgt::DownlevelFlags,
     private_caps: PrivateCapabilities,
     workarounds: Workarounds,
+    memory_allocator_config: Mutex<MemoryAllocatorConfig>,
 }
 
 // TODO there's no reason why this can't be unified--the function pointers should all be the same--it's not clear how to do this with `ash`.
@@ -200,6 +204,14 @@ struct DeviceExtensionFunctions {
     draw_indirect_count: Option<khr::DrawIndirectCount>,
     timeline_semaphore: Option<ExtensionFn<khr::TimelineSemaphore>>,
     ray_tracing: Option<RayTracingDeviceExtensionFunctions>,
+    device_generated_commands: Option<DeviceGeneratedCommandsFunctions>,
+    mesh_shading: Option<ext::MeshShader>,
+    calibrated_timestamps: Option<vk::ExtCalibratedTimestampsFn>,
+    conditional_rendering: Option<vk::ExtConditionalRenderingFn>,
+    transform_feedback: Option<vk::ExtTransformFeedbackFn>,
+    fragment_shading_rate: Option<vk::KhrFragmentShadingRateFn>,
+    external_memory_fd: Option<khr::ExternalMemoryFd>,
+    external_semaphore_fd: Option<khr::ExternalSemaphoreFd>,
 }
 
 struct RayTracingDeviceExtensionFunctions {
@@ -207,6 +219,11 @@ struct RayTracingDeviceExtensionFunctions {
     buffer_device_address: khr::BufferDeviceAddress,
 }
 
+struct DeviceGeneratedCommandsFunctions {
+    raw: vk::NvDeviceGeneratedCommandsFn,
+    buffer_device_address: khr::BufferDeviceAddress,
+}
+
 /// Set of internal capabilities, which don't show up in the exposed
 /// device geometry, but affect the code paths taken internally.
 #[derive(Clone, Debug)]
@@ -230,6 +247,9 @@ struct PrivateCapabilities {
     robust_image_access2: bool,
     zero_initialize_workgroup_memory: bool,
     image_format_list: bool,
+    /// Width and height of the render target area covered by a shading rate attachment
+    /// texel, or zero if shading rate attachments aren't supported.
+    shading_rate_tile_size: u32,
 }
 
 bitflags::bitflags!(
@@ -294,6 +314,14 @@ struct ColorAttachmentKey {
 struct DepthStencilAttachmentKey {
     base: AttachmentKey,
     stencil_ops: crate::AttachmentOps,
+    resolve: Option<DepthStencilResolveKey>,
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+struct DepthStencilResolveKey {
+    base: AttachmentKey,
+    depth_mode: vk::ResolveModeFlags,
+    stencil_mode: vk::ResolveModeFlags,
 }
 
 #[derive(Clone, Eq, Default, Hash, PartialEq)]
@@ -302,6 +330,8 @@ struct RenderPassKey {
     depth_stencil: Option<DepthStencilAttachmentKey>,
     sample_count: u32,
     multiview: Option<NonZeroU32>,
+    /// Whether the last attachment is a shading rate attachment.
+    shading_rate: bool,
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -319,6 +349,8 @@ struct FramebufferKey {
     attachments: ArrayVec<FramebufferAttachment, { MAX_TOTAL_ATTACHMENTS }>,
     extent: wgt::Extent3d,
     sample_count: u32,
+    /// Size of the shading rate attachment, if any, which is the last attachment.
+    shading_rate_extent: Option<vk::Extent2D>,
 }
 
 struct DeviceShared {
@@ -332,16 +364,101 @@ struct DeviceShared {
     enabled_extensions: Vec<&'static CStr>,
     extension_fns: DeviceExtensionFunctions,
     vendor_id: u32,
+    device_id: u32,
+    /// Identifies the pipeline cache data compatible with the device.
+    pipeline_cache_uuid: [u8; vk::UUID_SIZE],
     timestamp_period: f32,
     private_caps: PrivateCapabilities,
     workarounds: Workarounds,
     render_passes: Mutex<rustc_hash::FxHashMap<RenderPassKey, vk::RenderPass>>,
     framebuffers: Mutex<rustc_hash::FxHashMap<FramebufferKey, vk::Framebuffer>>,
+    /// Sizes of the device memory objects allocated for the memory allocator.
+    device_memory: Mutex<rustc_hash::FxHashMap<vk::DeviceMemory, u64>>,
+}
+
+/// Settings of the memory allocator of a [`Device`].
+///
+/// Resources are suballocated from larger blocks of device memory, except for
+/// large requests that get a memory allocation of their own. The defaults suit
+/// most applications, very large scenes may prefer larger blocks.
+///
+/// See [`Adapter::set_memory_allocator_config`]. Resources in memory coming from
+/// another allocator can be used through [`Device::buffer_from_raw`] and
+/// [`Device::texture_from_raw`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MemoryAllocatorConfig {
+    /// Requests of at least this many bytes get a dedicated memory allocation.
+    pub dedicated_threshold: u64,
+    /// Requests of at least this many bytes get a dedicated memory allocation
+    /// when the driver prefers it.
+    pub preferred_dedicated_threshold: u64,
+    /// Requests of at least this many bytes get a dedicated memory allocation
+    /// when they are transient.
+    pub transient_dedicated_threshold: u64,
+    /// Size of the first block of host visible memory.
+    pub starting_free_list_chunk: u64,
+    /// Size that the blocks of host visible memory grow up to.
+    pub final_free_list_chunk: u64,
+    /// Smallest size that blocks of device local memory are split into.
+    pub minimal_buddy_size: u64,
+    /// Size of the first block of device local memory.
+    pub initial_buddy_dedicated_size: u64,
+}
+
+impl Default for MemoryAllocatorConfig {
+    fn default() -> Self {
+        let config = gpu_alloc::Config::i_am_prototyping();
+        Self {
+            dedicated_threshold: config.dedicated_threshold,
+            preferred_dedicated_threshold: config.preferred_dedicated_threshold,
+            transient_dedicated_threshold: config.transient_dedicated_threshold,
+            starting_free_list_chunk: config.starting_free_list_chunk,
+            final_free_list_chunk: config.final_free_list_chunk,
+            minimal_buddy_size: config.minimal_buddy_size,
+            initial_buddy_dedicated_size: config.initial_buddy_dedicated_size,
+        }
+    }
+}
+
+impl From<MemoryAllocatorConfig> for gpu_alloc::Config {
+    fn from(config: MemoryAllocatorConfig) -> Self {
+        Self {
+            dedicated_threshold: config.dedicated_threshold,
+            preferred_dedicated_threshold: config.preferred_dedicated_threshold,
+            transient_dedicated_threshold: config.transient_dedicated_threshold,
+            starting_free_list_chunk: config.starting_free_list_chunk,
+            final_free_list_chunk: config.final_free_list_chunk,
+            minimal_buddy_size: config.minimal_buddy_size,
+            initial_buddy_dedicated_size: config.initial_buddy_dedicated_size,
+        }
+    }
+}
+
+/// Statistics of the memory allocator of a [`Device`], see
+/// [`Device::memory_allocator_stats`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryAllocatorStats {
+    /// Number of live suballocations.
+    pub allocation_count: u64,
+    /// Total size of the live suballocations in bytes.
+    pub allocated_bytes: u64,
+    /// Number of device memory objects held by the allocator.
+    pub device_memory_count: u64,
+    /// Total size of the device memory objects in bytes, including the space
+    /// that isn't suballocated.
+    pub device_memory_bytes: u64,
+}
+
+/// A [`gpu_alloc::GpuAllocator`] keeping count of its suballocations.
+struct MemoryAllocator {
+    raw: gpu_alloc::GpuAllocator<vk::DeviceMemory>,
+    allocation_count: u64,
+    allocated_bytes: u64,
 }
 
 pub struct Device {
     shared: Arc<DeviceShared>,
-    mem_allocator: Mutex<gpu_alloc::GpuAllocator<vk::DeviceMemory>>,
+    mem_allocator: Mutex<MemoryAllocator>,
     desc_allocator:
         Mutex<gpu_descriptor::DescriptorAllocator<vk::DescriptorPool, vk::DescriptorSet>>,
     valid_ash_memory_types: u32,
@@ -362,14 +479,66 @@ pub struct Queue {
     /// [Intel hangs in `anv_queue_finish`](https://gitlab.freedesktop.org/mesa/mesa/-/issues/5508).
     relay_semaphores: [vk::Semaphore; 2],
     relay_index: AtomicIsize,
+    /// Timeline semaphores, and their values, that the next submission waits on.
+    pending_waits: Mutex<Vec<(vk::Semaphore, crate::FenceValue)>>,
+}
+
+impl Queue {
+    unsafe fn new(
+        device: &Arc<DeviceShared>,
+        swapchain_fn: khr::Swapchain,
+        raw: vk::Queue,
+    ) -> Result<Self, crate::DeviceError> {
+        let mut relay_semaphores = [vk::Semaphore::null(); 2];
+        for sem in relay_semaphores.iter_mut() {
+            unsafe {
+                *sem = device
+                    .raw
+                    .create_semaphore(&vk::SemaphoreCreateInfo::builder(), None)?
+            };
+        }
+        Ok(Self {
+            raw,
+            swapchain_fn,
+            device: Arc::clone(device),
+            family_index: device.family_index,
+            relay_semaphores,
+            relay_index: AtomicIsize::new(-1),
+            pending_waits: Mutex::new(Vec::new()),
+        })
+    }
 }
 
 #[derive(Debug)]
 pub struct Buffer {
     raw: vk::Buffer,
     block: Option<Mutex<gpu_alloc::MemoryBlock<vk::DeviceMemory>>>,
+    /// The memory of a buffer placed in a [`MemoryHeap`], which owns it.
+    placement: Option<BufferPlacement>,
+    /// Dedicated memory shared with other APIs, which is freed together with
+    /// the buffer.
+    external_memory: Option<ExternalBufferMemory>,
+}
+
+#[derive(Debug)]
+struct ExternalBufferMemory {
+    memory: vk::DeviceMemory,
+    /// Size of `memory`, which other APIs need to import it.
+    size: u64,
 }
 
+#[derive(Debug)]
+struct BufferPlacement {
+    memory: vk::DeviceMemory,
+    /// Offset of the buffer in `memory`.
+    offset: u64,
+    /// The start of the buffer in the mapping of a host visible heap.
+    mapping: Option<crate::BufferMapping>,
+}
+
+unsafe impl Send for BufferPlacement {}
+unsafe impl Sync for BufferPlacement {}
+
 #[derive(Debug)]
 pub struct AccelerationStructure {
     raw: vk::AccelerationStructureKHR,
@@ -382,6 +551,8 @@ pub struct Texture {
     raw: vk::Image,
     drop_guard: Option<crate::DropGuard>,
     block: Option<gpu_alloc::MemoryBlock<vk::DeviceMemory>>,
+    /// Imported memory, which is freed together with the texture.
+    external_memory: Option<vk::DeviceMemory>,
     usage: crate::TextureUses,
     format: wgt::TextureFormat,
     raw_flags: vk::ImageCreateFlags,
@@ -402,6 +573,8 @@ impl Texture {
 pub struct TextureView {
     raw: vk::ImageView,
     layers: NonZeroU32,
+    /// Size of the base mip level of the view.
+    extent: vk::Extent2D,
     attachment: FramebufferAttachment,
 }
 
@@ -417,12 +590,17 @@ pub struct BindGroupLayout {
     types: Box<[(vk::DescriptorType, u32)]>,
     /// Map of binding index to size,
     binding_arrays: Vec<(u32, NonZeroU32)>,
+    /// Whether sets of this layout need to be allocated from update-after-bind pools.
+    update_after_bind: bool,
 }
 
 #[derive(Debug)]
 pub struct PipelineLayout {
     raw: vk::PipelineLayout,
     binding_arrays: naga::back::spv::BindingMap,
+    /// Layouts used by `execute_indirect`, created on first use.
+    indirect_commands_layouts:
+        Mutex<rustc_hash::FxHashMap<Vec<wgt::IndirectArgument>, vk::IndirectCommandsLayoutNV>>,
 }
 
 #[derive(Debug)]
@@ -464,6 +642,8 @@ pub struct CommandEncoder {
     temp: Temp,
     free: Vec<vk::CommandBuffer>,
     discarded: Vec<vk::CommandBuffer>,
+    /// Buffers used to preprocess generated commands, freed with the command buffers.
+    preprocess_buffers: Vec<(vk::Buffer, vk::DeviceMemory)>,
     /// If this is true, the active renderpass enabled a debug span,
     /// and needs to be disabled on renderpass close.
     rpass_debug_marker_active: bool,
@@ -471,6 +651,10 @@ pub struct CommandEncoder {
     /// If set, the end of the next render/compute pass will write a timestamp at
     /// the given pool & location.
     end_of_pass_timer_query: Option<(vk::QueryPool, u32)>,
+
+    /// How the shading rate of the active render pass is combined with the rate
+    /// of its shading rate attachment.
+    shading_rate_combiner_op: vk::FragmentShadingRateCombinerOpKHR,
 }
 
 impl fmt::Debug for CommandEncoder {
@@ -506,11 +690,32 @@ pub struct ComputePipeline {
     raw: vk::Pipeline,
 }
 
+#[derive(Debug)]
+pub struct PipelineCache {
+    raw: vk::PipelineCache,
+}
+
 #[derive(Debug)]
 pub struct QuerySet {
     raw: vk::QueryPool,
 }
 
+#[derive(Debug)]
+pub struct TilePool {
+    block: gpu_alloc::MemoryBlock<vk::DeviceMemory>,
+}
+
+#[derive(Debug)]
+pub struct MemoryHeap {
+    block: gpu_alloc::MemoryBlock<vk::DeviceMemory>,
+    /// Host visible heaps stay mapped for as long as they exist, so the
+    /// buffers placed in them can be mapped at the same time.
+    mapping: Option<crate::BufferMapping>,
+}
+
+unsafe impl Send for MemoryHeap {}
+unsafe impl Sync for MemoryHeap {}
+
 #[derive(Debug)]
 pub enum Fence {
     TimelineSemaphore(vk::Semaphore),
@@ -620,6 +825,18 @@ impl crate::Queue<Api> for Queue {
         self.relay_index
             .store(sem_index as isize, Ordering::Relaxed);
 
+        let mut wait_values = Vec::new();
+        let pending_waits = std::mem::take(&mut *self.pending_waits.lock());
+        if !pending_waits.is_empty() {
+            // The values of the binary semaphores are ignored.
+            wait_values.resize(wait_semaphores.len(), 0);
+            for (raw, value) in pending_waits {
+                wait_stage_masks.push(vk::PipelineStageFlags::ALL_COMMANDS);
+                wait_semaphores.push(raw);
+                wait_values.push(value);
+            }
+        }
+
         if let Some((fence, value)) = signal_fence {
             fence.maintain(&self.device.raw)?;
             match *fence {
@@ -660,9 +877,10 @@ impl crate::Queue<Api> for Queue {
 
         let mut vk_timeline_info;
 
-        if !signal_values.is_empty() {
-            vk_timeline_info =
-                vk::TimelineSemaphoreSubmitInfo::builder().signal_semaphore_values(&signal_values);
+        if !signal_values.is_empty() || !wait_values.is_empty() {
+            vk_timeline_info = vk::TimelineSemaphoreSubmitInfo::builder()
+                .wait_semaphore_values(&wait_values)
+                .signal_semaphore_values(&signal_values);
             vk_info = vk_info.push_next(&mut vk_timeline_info);
         }
 
@@ -720,6 +938,163 @@ impl crate::Queue<Api> for Queue {
     unsafe fn get_timestamp_period(&self) -> f32 {
         self.device.timestamp_period
     }
+
+    unsafe fn bind_texture_tiles(
+        &self,
+        texture: &Texture,
+        bindings: &[crate::TextureTileBinding<Api>],
+    ) -> Result<(), crate::DeviceError> {
+        let (tile_width, tile_height) = texture.format.tile_dimensions().unwrap();
+
+        let mut binds = Vec::new();
+        for binding in bindings {
+            let mut pool_tile = 0;
+            for array_layer in
+                binding.origin.z..binding.origin.z + binding.size.depth_or_array_layers
+            {
+                for y in binding.origin.y..binding.origin.y + binding.size.height {
+                    for x in binding.origin.x..binding.origin.x + binding.size.width {
+                        let (memory, memory_offset) = match binding.memory {
+                            Some((pool, first_tile)) => (
+                                *pool.block.memory(),
+                                pool.block.offset()
+                                    + (first_tile + pool_tile) as u64 * wgt::TEXTURE_TILE_SIZE,
+                            ),
+                            None => (vk::DeviceMemory::null(), 0),
+                        };
+                        pool_tile += 1;
+                        binds.push(
+                            vk::SparseImageMemoryBind::builder()
+                                .subresource(vk::ImageSubresource {
+                                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                                    mip_level: binding.mip_level,
+                                    array_layer,
+                                })
+                                .offset(vk::Offset3D {
+                                    x: (x * tile_width) as i32,
+                                    y: (y * tile_height) as i32,
+                                    z: 0,
+                                })
+                                .extent(vk::Extent3D {
+                                    width: tile_width,
+                                    height: tile_height,
+                                    depth: 1,
+                                })
+                                .memory(memory)
+                                .memory_offset(memory_offset)
+                                .build(),
+                        );
+                    }
+                }
+            }
+        }
+        if binds.is_empty() {
+            return Ok(());
+        }
+
+        // Chain the binding with the submissions through the relay semaphores,
+        // the same way `submit` does.
+        let mut wait_semaphores = ArrayVec::<_, 1>::new();
+        let old_index = self.relay_index.load(Ordering::Relaxed);
+        let sem_index = if old_index >= 0 {
+            wait_semaphores.push(self.relay_semaphores[old_index as usize]);
+            (old_index as usize + 1) % self.relay_semaphores.len()
+        } else {
+            0
+        };
+        let signal_semaphores = [self.relay_semaphores[sem_index]];
+        self.relay_index
+            .store(sem_index as isize, Ordering::Relaxed);
+
+        let image_binds = [vk::SparseImageMemoryBindInfo::builder()
+            .image(texture.raw)
+            .binds(&binds)
+            .build()];
+        let vk_info = vk::BindSparseInfo::builder()
+            .wait_semaphores(&wait_semaphores)
+            .image_binds(&image_binds)
+            .signal_semaphores(&signal_semaphores);
+
+        profiling::scope!("vkQueueBindSparse");
+        unsafe {
+            self.device
+                .raw
+                .queue_bind_sparse(self.raw, &[vk_info.build()], vk::Fence::null())?
+        };
+        Ok(())
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        match *fence {
+            Fence::TimelineSemaphore(raw) => {
+                self.pending_waits.lock().push((raw, value));
+            }
+            // Binary fences can't be waited on by the GPU, so wait on the CPU.
+            Fence::FencePool {
+                last_completed,
+                ref active,
+                free: _,
+            } => {
+                if value > last_completed {
+                    if let Some(&(_, raw)) = active.iter().find(|&&(v, _)| v >= value) {
+                        unsafe { self.device.raw.wait_for_fences(&[raw], true, !0)? };
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    unsafe fn signal_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        match *fence {
+            // An empty submission signals the semaphore once all prior work is done.
+            Fence::TimelineSemaphore(raw) => {
+                let mut fence = Fence::TimelineSemaphore(raw);
+                unsafe { self.submit(&[], &[], Some((&mut fence, value))) }
+            }
+            Fence::FencePool { .. } => unreachable!("Fence is not an external fence"),
+        }
+    }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        let fns = self.device.extension_fns.calibrated_timestamps.as_ref()?;
+        let infos = [
+            vk::CalibratedTimestampInfoEXT::builder()
+                .time_domain(vk::TimeDomainEXT::DEVICE)
+                .build(),
+            vk::CalibratedTimestampInfoEXT::builder()
+                .time_domain(vk::TimeDomainEXT::CLOCK_MONOTONIC)
+                .build(),
+        ];
+        let mut timestamps = [0u64; 2];
+        let mut max_deviation = 0;
+        let result = unsafe {
+            (fns.get_calibrated_timestamps_ext)(
+                self.device.raw.handle(),
+                infos.len() as u32,
+                infos.as_ptr(),
+                timestamps.as_mut_ptr(),
+                &mut max_deviation,
+            )
+        };
+        if result != vk::Result::SUCCESS {
+            log::error!("vkGetCalibratedTimestampsEXT failed: {result}");
+            return None;
+        }
+        // `CLOCK_MONOTONIC` timestamps are in nanoseconds.
+        Some(wgt::TimestampCalibration {
+            cpu_timestamp: wgt::PresentationTimestamp(timestamps[1] as u128),
+            gpu_timestamp: timestamps[0],
+        })
+    }
 }
 
 impl From<vk::Result> for crate::DeviceError {
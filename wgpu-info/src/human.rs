@@ -154,6 +154,9 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
         max_compute_workgroup_size_z,
         max_compute_workgroups_per_dimension,
         max_non_sampler_bindings,
+        max_binding_array_elements_per_shader_stage,
+        max_binding_array_sampler_elements_per_shader_stage,
+        shading_rate_tile_size,
     } = limits;
     writeln!(output, "\t\t                        Max Texture Dimension 1d: {max_texture_dimension_1d}")?;
     writeln!(output, "\t\t                        Max Texture Dimension 2d: {max_texture_dimension_2d}")?;
@@ -184,6 +187,9 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
     writeln!(output, "\t\t                    Max Compute Workgroup Size Y: {max_compute_workgroup_size_y}")?;
     writeln!(output, "\t\t                    Max Compute Workgroup Size Z: {max_compute_workgroup_size_z}")?;
     writeln!(output, "\t\t            Max Compute Workgroups Per Dimension: {max_compute_workgroups_per_dimension}")?;
+    writeln!(output, "\t\t     Max Binding Array Elements Per Shader Stage: {max_binding_array_elements_per_shader_stage}")?;
+    writeln!(output, "\t\t     Max Binding Array Samplers Per Shader Stage: {max_binding_array_sampler_elements_per_shader_stage}")?;
+    writeln!(output, "\t\t                          Shading Rate Tile Size: {shading_rate_tile_size}")?;
 
     // This one reflects more of a wgpu implementation limitations than a hardware limit
     // so don't show it here.
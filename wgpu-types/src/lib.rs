@@ -82,12 +82,18 @@ pub const COPY_BUFFER_ALIGNMENT: BufferAddress = 4;
 pub const MAP_ALIGNMENT: BufferAddress = 8;
 /// Vertex buffer strides have to be aligned to this number.
 pub const VERTEX_STRIDE_ALIGNMENT: BufferAddress = 4;
+/// Maximum number of buffers the vertex outputs of a pipeline are captured to.
+pub const MAX_VERTEX_CAPTURE_BUFFERS: u32 = 4;
+/// Maximum stride of a buffer the vertex outputs of a pipeline are captured to.
+pub const MAX_VERTEX_CAPTURE_STRIDE: BufferAddress = 256;
 /// Alignment all push constants need
 pub const PUSH_CONSTANT_ALIGNMENT: u32 = 4;
 /// Maximum queries in a query set
 pub const QUERY_SET_MAX_QUERIES: u32 = 8192;
 /// Size of a single piece of query data.
 pub const QUERY_SIZE: u32 = 8;
+/// Size in bytes of a tile of a [`TextureUsages::TILED`] texture, and of a tile of a tile pool.
+pub const TEXTURE_TILE_SIZE: BufferAddress = 64 << 10;
 
 /// Backends supported by wgpu.
 #[repr(u8)]
@@ -237,7 +243,7 @@ bitflags::bitflags! {
     #[repr(transparent)]
     #[derive(Default)]
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-    pub struct Features: u64 {
+    pub struct Features: u128 {
         //
         // ---- Start numbering at 1 << 0 ----
         //
@@ -305,7 +311,8 @@ bitflags::bitflags! {
 
         /// Allows shaders to acquire the FP16 ability
         ///
-        /// Note: this is not supported in `naga` yet，only through `spirv-passthrough` right now.
+        /// Note: `naga` only supports it for GLSL shaders (`float16_t`) right now, for other shaders
+        /// only through `spirv-passthrough`.
         ///
         /// Supported Platforms:
         /// - Vulkan
@@ -505,7 +512,8 @@ bitflags::bitflags! {
         ///
         /// This is only beneficial on systems that share memory between CPU and GPU. If enabled
         /// on a system that doesn't, this can severely hinder performance. Only use if you understand
-        /// the consequences.
+        /// the consequences. `Device::memory_architecture` reports whether the device shares
+        /// memory with the CPU.
         ///
         /// Supported platforms:
         /// - Vulkan
@@ -624,6 +632,18 @@ bitflags::bitflags! {
         const UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING = 1 << 39;
         /// Allows the user to create bind groups containing arrays with less bindings than the BindGroupLayout.
         ///
+        /// Binding arrays of bind group layouts without buffers with dynamic offsets are then
+        /// update-after-bind, so that they can be much larger: their elements count towards
+        /// [`Limits::max_binding_array_elements_per_shader_stage`] and
+        /// [`Limits::max_binding_array_sampler_elements_per_shader_stage`] instead of the
+        /// limits of their binding type. Arrays of uniform buffers keep counting towards
+        /// [`Limits::max_uniform_buffers_per_shader_stage`].
+        ///
+        /// Supported platforms:
+        /// - DX12 (with resource binding tier 3)
+        /// - Vulkan 1.2+ (or VK_EXT_descriptor_indexing)'s descriptorBindingPartiallyBound feature,
+        ///   along with the update-after-bind features of sampled images, storage images and storage buffers
+        ///
         /// This is a native only feature.
         const PARTIALLY_BOUND_BINDING_ARRAY = 1 << 40;
         /// Allows the user to call [`RenderPass::multi_draw_indirect`] and [`RenderPass::multi_draw_indexed_indirect`].
@@ -785,25 +805,49 @@ bitflags::bitflags! {
         ///
         /// This is a native only feature.
         const TEXTURE_FORMAT_NV12 = 1 << 55;
-        /// Allows for the creation of ray-tracing acceleration structures.
+        /// Allows for the creation of ray-tracing acceleration structures, with
+        /// `Device::create_blas` and `Device::create_tlas`, and building them with
+        /// `CommandEncoder::build_acceleration_structures_unsafe_tlas`.
+        ///
+        /// This feature is experimental, its API may change in incompatible ways.
         ///
         /// Supported platforms:
         /// - Vulkan
         ///
         /// This is a native-only feature.
-        const RAY_TRACING_ACCELERATION_STRUCTURE = 1 << 56;
-
-        // 57 available
+        const EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE = 1 << 56;
 
         // Shader:
 
-        /// Allows for the creation of ray-tracing queries within shaders.
+        /// Allows shaders to use `debugPrintf` statements.
+        ///
+        /// Messages are written into a buffer owned by the device, read back once
+        /// the submission that produced them completes, and passed to the callback
+        /// set with `Device::set_debug_printf_callback`.
+        ///
+        /// The device reserves the bind group right after the last one allowed by
+        /// [`Limits::max_bind_groups`] for the capture buffer, so the requested
+        /// limit must be lower than what the adapter supports.
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - DX12
+        /// - Metal
+        /// - OpenGL (with compute shaders)
+        ///
+        /// This is a native only feature.
+        const SHADER_DEBUG_PRINTF = 1 << 57;
+
+        /// Allows for the creation of ray-tracing queries within shaders, and binding
+        /// top level acceleration structures with [`BindingType::AccelerationStructure`].
+        ///
+        /// This feature is experimental, its API may change in incompatible ways.
         ///
         /// Supported platforms:
         /// - Vulkan
         ///
         /// This is a native-only feature.
-        const RAY_QUERY = 1 << 58;
+        const EXPERIMENTAL_RAY_QUERY = 1 << 58;
         /// Enables 64-bit floating point types in SPIR-V shaders.
         ///
         /// Note: even when supported by GPU hardware, 64-bit floating point operations are
@@ -853,6 +897,215 @@ bitflags::bitflags! {
         /// - Vulkan (with dualSrcBlend)
         /// - DX12
         const DUAL_SOURCE_BLENDING = 1 << 63;
+
+        /// Allows the GPU to write commands that bind vertex buffers and set push
+        /// constants before each draw, executed with `RenderPass::execute_indirect`.
+        ///
+        /// Also allows getting the address of buffers with `Buffer::device_address`,
+        /// to write vertex buffer arguments.
+        ///
+        /// Supported platforms:
+        /// - DX12
+        /// - Vulkan (with VK_NV_device_generated_commands)
+        ///
+        /// This is a native only feature.
+        const DEVICE_GENERATED_COMMANDS = 1 << 64;
+        /// Allows creating pipeline caches with `Device::create_pipeline_cache`,
+        /// whose data can be stored to make creating the same pipelines faster
+        /// in later runs.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with `VkPipelineCache`)
+        /// - DX12 (with cached pipeline state objects)
+        /// - Metal (macOS 11+, iOS 14+, with binary archives)
+        /// - OpenGL (GLES 3.0+, GL 4.1+ or GL_ARB_get_program_binary, with program binaries)
+        ///
+        /// This is a native only feature.
+        const PIPELINE_CACHE = 1 << 65;
+        /// Enables 64-bit integer types in shaders.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan
+        ///
+        /// This is a native only feature.
+        const SHADER_INT64 = 1 << 66;
+        /// Allows creating mesh pipelines with `Device::create_mesh_pipeline`,
+        /// whose geometry is generated by task and mesh shaders, and drawing
+        /// with them with `RenderPass::draw_mesh_tasks`.
+        ///
+        /// This is currently experimental, and the shaders can only be
+        /// written in WGSL.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with VK_EXT_mesh_shader)
+        ///
+        /// This is a native only feature.
+        const EXPERIMENTAL_MESH_SHADER = 1 << 67;
+        /// Allows `RenderPass::begin_conditional_rendering` to skip draws based
+        /// on a value in a buffer, such as a resolved occlusion query, without
+        /// reading it back on the CPU.
+        ///
+        /// Without this feature, conditional rendering is still validated, but
+        /// no draws are skipped.
+        ///
+        /// Supported platforms:
+        /// - DX12
+        /// - Vulkan (with VK_EXT_conditional_rendering)
+        ///
+        /// This is a native only feature.
+        const CONDITIONAL_RENDERING = 1 << 68;
+        /// Allows render pipelines to capture the outputs of their vertex
+        /// shader into buffers, with [`VertexState::capture_buffers`] and
+        /// `RenderPass::begin_vertex_capture`. Also allows the creation of
+        /// buffers with [`BufferUsages::VERTEX_CAPTURE`].
+        ///
+        /// This is known as transform feedback or stream output.
+        ///
+        /// Supported platforms:
+        /// - DX12
+        /// - Vulkan (with VK_EXT_transform_feedback)
+        /// - OpenGL (interleaving is limited to a single buffer)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`VertexState::capture_buffers`]: ../wgpu/struct.VertexState.html#structfield.capture_buffers
+        const VERTEX_CAPTURE = 1 << 69;
+        /// Allows the fragment shading rate of draws to be lowered with
+        /// [`RenderPass::set_shading_rate`], so that a single fragment shader invocation
+        /// covers a block of up to 4x4 pixels.
+        ///
+        /// Supported platforms:
+        /// - DX12 (with variable rate shading tier 1)
+        /// - Vulkan (with VK_KHR_fragment_shading_rate and `pipelineFragmentShadingRate`)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderPass::set_shading_rate`]: ../wgpu/struct.RenderPass.html#method.set_shading_rate
+        const SHADING_RATE = 1 << 70;
+        /// Allows a render pass to be given a [`TextureFormat::R8Uint`] texture with
+        /// [`TextureUsages::SHADING_RATE_ATTACHMENT`] whose texels select the [`ShadingRate`]
+        /// of each [`Limits::shading_rate_tile_size`] sized tile of the render targets.
+        ///
+        /// Rates coming from the attachment override the rate set with
+        /// [`RenderPass::set_shading_rate`].
+        ///
+        /// Supported platforms:
+        /// - DX12 (with variable rate shading tier 2)
+        /// - Vulkan (with VK_KHR_fragment_shading_rate and `attachmentFragmentShadingRate`)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderPass::set_shading_rate`]: ../wgpu/struct.RenderPass.html#method.set_shading_rate
+        const SHADING_RATE_ATTACHMENT = 1 << 71;
+        /// Allows creating 2D textures with [`TextureUsages::TILED`], which have no memory
+        /// of their own. Instead, their tiles are mapped to tiles of a tile pool with
+        /// [`Queue::bind_texture_pages`], for virtual texturing.
+        ///
+        /// Reading from a tile that isn't mapped returns zero, and writes to it are discarded.
+        ///
+        /// Supported platforms:
+        /// - DX12 (with tiled resources tier 2)
+        /// - Vulkan (with `sparseResidencyImage2D`, `sparseResidencyAliased`, standard
+        ///   block shapes and strict non-resident reads)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`Queue::bind_texture_pages`]: ../wgpu/struct.Queue.html#method.bind_texture_pages
+        const TILED_TEXTURE = 1 << 72;
+        /// Allows creating memory heaps with [`Device::create_memory_heap`], and placing
+        /// buffers and textures at offsets of them with [`Device::create_buffer_in_heap`]
+        /// and [`Device::create_texture_in_heap`]. Resources may overlap in a heap, and
+        /// [`CommandEncoder::aliasing_barrier`] orders the uses of resources sharing memory.
+        ///
+        /// Supported platforms:
+        /// - DX12 (with resource heap tier 2)
+        /// - Vulkan
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`Device::create_memory_heap`]: ../wgpu/struct.Device.html#method.create_memory_heap
+        /// [`Device::create_buffer_in_heap`]: ../wgpu/struct.Device.html#method.create_buffer_in_heap
+        /// [`Device::create_texture_in_heap`]: ../wgpu/struct.Device.html#method.create_texture_in_heap
+        /// [`CommandEncoder::aliasing_barrier`]: ../wgpu/struct.CommandEncoder.html#method.aliasing_barrier
+        const MEMORY_HEAPS = 1 << 73;
+        /// Allows the depth-stencil attachment of a render pass to be given a resolve target,
+        /// receiving the samples of the attachment combined with a [`ResolveMode`].
+        ///
+        /// [`ResolveMode::SampleZero`] is supported by all depth-stencil formats. Other modes
+        /// depend on the format, see [`TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_DEPTH_AVERAGE`]
+        /// and [`TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_MIN_MAX`].
+        ///
+        /// Supported platforms:
+        /// - Vulkan 1.2+
+        /// - Metal (macOS 10.14+, iOS 12+ on Apple3+ GPUs)
+        ///
+        /// This is a native only feature.
+        const DEPTH_STENCIL_RESOLVE = 1 << 74;
+        /// Allows texture views to be created with a [`ComponentMapping`] other than
+        /// [`ComponentMapping::IDENTITY`], rearranging the components read through the view.
+        ///
+        /// Such views can only be sampled, not used as storage textures or render attachments.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (except for portability subset implementations)
+        /// - DX12
+        /// - Metal (macOS 10.15+, iOS 13+)
+        ///
+        /// This is a native only feature.
+        const TEXTURE_COMPONENT_SWIZZLE = 1 << 75;
+        /// Allows for creation of textures of format [`TextureFormat::P010`]
+        ///
+        /// Supported platforms:
+        /// - DX12
+        /// - Vulkan
+        ///
+        /// This is a native only feature.
+        const TEXTURE_FORMAT_P010 = 1 << 76;
+        /// Allows importing textures whose memory was allocated outside of wgpu, see
+        /// [`ExternalImageSource`].
+        ///
+        /// Imported textures must be 2D, with a single mip level, array layer and sample.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with VK_EXT_external_memory_dma_buf and VK_EXT_image_drm_format_modifier)
+        /// - DX12
+        /// - Metal
+        ///
+        /// This is a native only feature.
+        const EXTERNAL_TEXTURE_IMPORT = 1 << 77;
+        /// Allows creating fences that are shared with other APIs, like Vulkan, CUDA or
+        /// media decoders, to order work between them and wgpu queues. See
+        /// [`ExternalFenceHandle`].
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with timeline semaphores and VK_KHR_external_semaphore_fd)
+        /// - DX12
+        /// - Metal (macOS 10.14+, iOS 12+)
+        ///
+        /// This is a native only feature.
+        const EXTERNAL_FENCE = 1 << 78;
+        /// Allows sharing the memory of buffers with other APIs, like CUDA or ROCm,
+        /// so compute work can exchange data with them without copying it through
+        /// the CPU. See [`ExternalMemory`].
+        ///
+        /// Buffers sharing memory can't be mappable.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with VK_KHR_external_memory_fd)
+        /// - DX12
+        ///
+        /// This is a native only feature.
+        const EXTERNAL_MEMORY = 1 << 79;
+        /// Allows render bundles to begin and end occlusion queries, see
+        /// [`RenderBundleEncoder::begin_occlusion_query`].
+        ///
+        /// Supported platforms:
+        /// - All native platforms, as wgpu-core records render bundles itself
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderBundleEncoder::begin_occlusion_query`]: ../wgpu/struct.RenderBundleEncoder.html#method.begin_occlusion_query
+        const RENDER_BUNDLE_OCCLUSION_QUERY = 1 << 80;
     }
 }
 
@@ -861,12 +1114,58 @@ impl_bitflags!(Features);
 impl Features {
     /// Mask of all features which are part of the upstream WebGPU standard.
     pub const fn all_webgpu_mask() -> Self {
-        Self::from_bits_truncate(0x0000_0000_0000_FFFF)
+        Self::from_bits_truncate(0xFFFF)
     }
 
     /// Mask of all features that are only available when targeting native (not web).
     pub const fn all_native_mask() -> Self {
-        Self::from_bits_truncate(0xFFFF_FFFF_FFFF_0000)
+        Self::from_bits_truncate(!0xFFFF)
+    }
+}
+
+/// Explains whether a feature is available on an adapter, and if not, why.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FeatureSupport {
+    /// The adapter supports the feature.
+    Available,
+    /// The backend of the adapter never supports the feature.
+    NotImplemented {
+        /// Backend of the adapter.
+        backend: Backend,
+    },
+    /// The backend supports the feature, but the adapter doesn't provide what
+    /// the backend requires for it.
+    MissingRequirement {
+        /// Backend of the adapter.
+        backend: Backend,
+        /// Description of what the backend requires, like an extension or a
+        /// device capability.
+        requirement: &'static str,
+    },
+}
+
+impl FeatureSupport {
+    /// Returns true if the result is [`Self::Available`].
+    pub fn is_available(&self) -> bool {
+        matches!(self, Self::Available)
+    }
+}
+
+impl std::fmt::Display for FeatureSupport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Available => write!(f, "available"),
+            Self::NotImplemented { backend } => {
+                write!(f, "not implemented by the {backend:?} backend")
+            }
+            Self::MissingRequirement {
+                backend,
+                requirement,
+            } => write!(
+                f,
+                "the {backend:?} backend requires {requirement}, which the adapter lacks"
+            ),
+        }
     }
 }
 
@@ -895,6 +1194,12 @@ bitflags::bitflags! {
         /// This mainly applies to a Vulkan driver's compliance version. If the major compliance version
         /// is `0`, then the driver is ignored. This flag allows that driver to be enabled for testing.
         const ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER = 1 << 3;
+        /// Poll every device from a background thread while it has work in flight.
+        ///
+        /// With this flag, buffer mapping and work done callbacks are called without
+        /// polling the device. This has no effect on the WebGPU backend, which always
+        /// calls callbacks on its own.
+        const AUTOMATIC_POLLING = 1 << 4;
     }
 }
 
@@ -950,6 +1255,9 @@ impl InstanceFlags {
         if let Some(bit) = env("WGPU_ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER") {
             self.set(Self::ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER, bit);
         }
+        if let Some(bit) = env("WGPU_AUTOMATIC_POLLING") {
+            self.set(Self::AUTOMATIC_POLLING, bit);
+        }
 
         self
     }
@@ -1092,6 +1400,26 @@ pub struct Limits {
     /// This limit only affects the d3d12 backend. Using a large number will allow the device
     /// to create many bind groups at the cost of a large up-front allocation at device creation.
     pub max_non_sampler_bindings: u32,
+
+    /// Amount of individual resources within update-after-bind binding arrays that can be
+    /// accessed in a single shader stage, for all binding types but samplers and uniform
+    /// buffers. Defaults to 0. Higher is "better".
+    ///
+    /// Binding arrays are only update-after-bind when [`Features::PARTIALLY_BOUND_BINDING_ARRAY`]
+    /// is enabled.
+    pub max_binding_array_elements_per_shader_stage: u32,
+    /// Amount of individual samplers within update-after-bind binding arrays that can be
+    /// accessed in a single shader stage. Defaults to 0. Higher is "better".
+    ///
+    /// Binding arrays are only update-after-bind when [`Features::PARTIALLY_BOUND_BINDING_ARRAY`]
+    /// is enabled.
+    pub max_binding_array_sampler_elements_per_shader_stage: u32,
+    /// Width and height in pixels of the render target area covered by a single texel of a
+    /// shading rate attachment. Zero if [`Features::SHADING_RATE_ATTACHMENT`] is not supported.
+    ///
+    /// This is a property of the adapter rather than a limit that can be requested: devices
+    /// always use the tile size of their adapter, whatever value is requested.
+    pub shading_rate_tile_size: u32,
 }
 
 impl Default for Limits {
@@ -1127,6 +1455,9 @@ impl Default for Limits {
             max_compute_workgroups_per_dimension: 65535,
             max_push_constant_size: 0,
             max_non_sampler_bindings: 1_000_000,
+            max_binding_array_elements_per_shader_stage: 0,
+            max_binding_array_sampler_elements_per_shader_stage: 0,
+            shading_rate_tile_size: 0,
         }
     }
 }
@@ -1168,6 +1499,9 @@ impl Limits {
     ///     max_compute_workgroups_per_dimension: 65535,
     ///     max_buffer_size: 256 << 20, // (256 MiB)
     ///     max_non_sampler_bindings: 1_000_000,
+    ///     max_binding_array_elements_per_shader_stage: 0,
+    ///     max_binding_array_sampler_elements_per_shader_stage: 0,
+    ///     shading_rate_tile_size: 0,
     /// });
     /// ```
     pub fn downlevel_defaults() -> Self {
@@ -1202,6 +1536,9 @@ impl Limits {
             max_compute_workgroups_per_dimension: 65535,
             max_buffer_size: 256 << 20,
             max_non_sampler_bindings: 1_000_000,
+            max_binding_array_elements_per_shader_stage: 0,
+            max_binding_array_sampler_elements_per_shader_stage: 0,
+            shading_rate_tile_size: 0,
         }
     }
 
@@ -1242,6 +1579,9 @@ impl Limits {
     ///     max_compute_workgroups_per_dimension: 0, // +
     ///     max_buffer_size: 256 << 20, // (256 MiB),
     ///     max_non_sampler_bindings: 1_000_000,
+    ///     max_binding_array_elements_per_shader_stage: 0,
+    ///     max_binding_array_sampler_elements_per_shader_stage: 0,
+    ///     shading_rate_tile_size: 0,
     /// });
     /// ```
     pub fn downlevel_webgl2_defaults() -> Self {
@@ -1362,9 +1702,150 @@ impl Limits {
         compare!(max_compute_workgroups_per_dimension, Less);
         compare!(max_buffer_size, Less);
         compare!(max_non_sampler_bindings, Less);
+        compare!(max_binding_array_elements_per_shader_stage, Less);
+        compare!(max_binding_array_sampler_elements_per_shader_stage, Less);
+        // `shading_rate_tile_size` is reported by the adapter and can't be requested.
+    }
+
+    /// Returns the `requested` limits, clamped to the `adapter` limits.
+    ///
+    /// The returned limits can always be used to request a device from the adapter.
+    /// Every limit that couldn't be granted as requested is listed in
+    /// [`NegotiatedLimits::clamped`], so that applications can degrade gracefully
+    /// and report exactly what the adapter couldn't provide.
+    pub fn negotiate(requested: &Self, adapter: &Self) -> NegotiatedLimits {
+        let mut limits = requested.clone();
+        let mut clamped = Vec::new();
+
+        macro_rules! clamp {
+            ($name:ident, $clamp:ident) => {
+                let granted = requested.$name.$clamp(adapter.$name);
+                if granted != requested.$name {
+                    clamped.push(ClampedLimit {
+                        name: stringify!($name),
+                        requested: requested.$name as u64,
+                        granted: granted as u64,
+                    });
+                    limits.$name = granted;
+                }
+            };
+        }
+
+        clamp!(max_texture_dimension_1d, min);
+        clamp!(max_texture_dimension_2d, min);
+        clamp!(max_texture_dimension_3d, min);
+        clamp!(max_texture_array_layers, min);
+        clamp!(max_bind_groups, min);
+        clamp!(max_bindings_per_bind_group, min);
+        clamp!(max_dynamic_uniform_buffers_per_pipeline_layout, min);
+        clamp!(max_dynamic_storage_buffers_per_pipeline_layout, min);
+        clamp!(max_sampled_textures_per_shader_stage, min);
+        clamp!(max_samplers_per_shader_stage, min);
+        clamp!(max_storage_buffers_per_shader_stage, min);
+        clamp!(max_storage_textures_per_shader_stage, min);
+        clamp!(max_uniform_buffers_per_shader_stage, min);
+        clamp!(max_uniform_buffer_binding_size, min);
+        clamp!(max_storage_buffer_binding_size, min);
+        clamp!(max_vertex_buffers, min);
+        clamp!(max_buffer_size, min);
+        clamp!(max_vertex_attributes, min);
+        clamp!(max_vertex_buffer_array_stride, min);
+        // Alignments are better when smaller, so they are clamped the other way.
+        clamp!(min_uniform_buffer_offset_alignment, max);
+        clamp!(min_storage_buffer_offset_alignment, max);
+        clamp!(max_inter_stage_shader_components, min);
+        clamp!(max_compute_workgroup_storage_size, min);
+        clamp!(max_compute_invocations_per_workgroup, min);
+        clamp!(max_compute_workgroup_size_x, min);
+        clamp!(max_compute_workgroup_size_y, min);
+        clamp!(max_compute_workgroup_size_z, min);
+        clamp!(max_compute_workgroups_per_dimension, min);
+        clamp!(max_push_constant_size, min);
+        clamp!(max_non_sampler_bindings, min);
+        clamp!(max_binding_array_elements_per_shader_stage, min);
+        clamp!(max_binding_array_sampler_elements_per_shader_stage, min);
+        limits.shading_rate_tile_size = adapter.shading_rate_tile_size;
+
+        NegotiatedLimits { limits, clamped }
+    }
+}
+
+/// Limits returned by [`Limits::negotiate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegotiatedLimits {
+    /// The requested limits, clamped to the adapter limits.
+    pub limits: Limits,
+    /// The limits that couldn't be granted as requested, in declaration order.
+    pub clamped: Vec<ClampedLimit>,
+}
+
+impl NegotiatedLimits {
+    /// Returns true if every limit was granted as requested.
+    pub fn is_exact(&self) -> bool {
+        self.clamped.is_empty()
+    }
+}
+
+/// A limit that [`Limits::negotiate`] couldn't grant as requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClampedLimit {
+    /// Name of the field of [`Limits`].
+    pub name: &'static str,
+    /// Requested value of the limit.
+    pub requested: u64,
+    /// Value the adapter supports, which was granted instead.
+    pub granted: u64,
+}
+
+impl ClampedLimit {
+    /// Returns by how much the limit was reduced.
+    ///
+    /// For the alignment limits, this is by how much the alignment was increased.
+    pub fn reduction(&self) -> u64 {
+        self.requested.abs_diff(self.granted)
     }
 }
 
+#[test]
+fn negotiate_limits() {
+    let adapter = Limits {
+        max_texture_dimension_2d: 4096,
+        min_uniform_buffer_offset_alignment: 256,
+        ..Limits::default()
+    };
+    let requested = Limits {
+        max_texture_dimension_2d: 16384,
+        min_uniform_buffer_offset_alignment: 64,
+        max_bind_groups: 2,
+        ..Limits::default()
+    };
+
+    let negotiated = Limits::negotiate(&requested, &adapter);
+    assert!(!negotiated.is_exact());
+    assert!(negotiated.limits.check_limits(&adapter));
+    assert_eq!(negotiated.limits.max_texture_dimension_2d, 4096);
+    assert_eq!(negotiated.limits.min_uniform_buffer_offset_alignment, 256);
+    assert_eq!(negotiated.limits.max_bind_groups, 2);
+    assert_eq!(
+        negotiated.clamped,
+        [
+            ClampedLimit {
+                name: "max_texture_dimension_2d",
+                requested: 16384,
+                granted: 4096,
+            },
+            ClampedLimit {
+                name: "min_uniform_buffer_offset_alignment",
+                requested: 64,
+                granted: 256,
+            },
+        ]
+    );
+    assert_eq!(negotiated.clamped[0].reduction(), 12288);
+
+    assert!(Limits::negotiate(&adapter, &adapter).is_exact());
+}
+
 /// Represents the sets of additional limits on an adapter,
 /// which take place when running on downlevel backends.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -1617,6 +2098,24 @@ pub enum DeviceType {
     Cpu,
 }
 
+/// How the memory of a device is organized, see [`Device::memory_architecture`].
+///
+/// [`Device::memory_architecture`]: ../wgpu/struct.Device.html#method.memory_architecture
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MemoryArchitecture {
+    /// The GPU has separate memory, and buffers the CPU can map are slow for the GPU to
+    /// access. Data should be uploaded through staging buffers.
+    ///
+    /// This is also reported when the backend doesn't know the memory architecture.
+    Discrete,
+    /// The CPU and GPU share memory, so buffers the CPU can map are as fast for the GPU
+    /// to access as any other. With [`Features::MAPPABLE_PRIMARY_BUFFERS`], data can be
+    /// written to such buffers directly instead of through staging copies.
+    Unified,
+}
+
 //TODO: convert `vendor` and `device` to `u32`
 
 /// Information about an adapter.
@@ -1658,6 +2157,34 @@ pub struct AdapterInfo {
     pub backend: Backend,
 }
 
+/// Scheduling priority of the queue of a device, relative to the queues of other
+/// devices and processes.
+///
+/// Raising the priority lets latency sensitive work, such as VR compositors, win
+/// scheduling against background work. It maps to the queue global priority on
+/// Vulkan (if `VK_EXT_global_priority` is supported) and to the command queue
+/// priority on DX12. Other backends ignore it.
+///
+/// Priorities above [`QueuePriority::Normal`] may require elevated privileges. If
+/// they are not granted, the device is created with the default priority instead.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "trace", derive(Serialize))]
+#[cfg_attr(feature = "replay", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum QueuePriority {
+    /// Background work that should yield to other work.
+    Low = 0,
+    #[default]
+    /// The default priority.
+    Normal = 1,
+    /// Latency sensitive work.
+    High = 2,
+    /// Work that must run as soon as it is submitted, preempting other work where
+    /// the platform allows it.
+    Realtime = 3,
+}
+
 /// Describes a [`Device`](../wgpu/struct.Device.html).
 ///
 /// Corresponds to [WebGPU `GPUDeviceDescriptor`](
@@ -1681,6 +2208,43 @@ pub struct DeviceDescriptor<L> {
     /// Exactly the specified limits, and no better or worse,
     /// will be allowed in validation of API calls on the resulting device.
     pub required_limits: Limits,
+    /// Requests a device that only runs compute and transfer work.
+    ///
+    /// Such a device skips the presentation machinery of the backend, such as the
+    /// swapchain extension on Vulkan, and only validates the limits that apply to
+    /// compute and transfer work. Render pipelines, render passes, render bundles
+    /// and surfaces cannot be used with it.
+    ///
+    /// This is ignored on the WebGPU backend.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compute_only: bool,
+    /// Scheduling priority of the queue of the device.
+    ///
+    /// This is ignored on the WebGPU backend.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub queue_priority: QueuePriority,
+    /// Number of queues to create besides the main queue of the device.
+    ///
+    /// Work submitted to different queues may run concurrently, for example
+    /// compute work alongside rendering, or uploads in the background. The
+    /// additional queues support the same operations as the main queue and
+    /// have the same priority.
+    ///
+    /// The request will fail if this is more than the adapter's maximum.
+    /// This must be 0 on the WebGPU backend.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub additional_queues: u32,
+    /// Reuses bind groups instead of creating identical ones.
+    ///
+    /// When enabled, creating a bind group with the same layout and resources as
+    /// a bind group that is still alive returns a new handle to the existing
+    /// bind group, without allocating new descriptors. A cached bind group is
+    /// released once all its handles are dropped, and is never reused once one
+    /// of its resources has been destroyed.
+    ///
+    /// This is ignored on the WebGPU backend.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cache_bind_groups: bool,
 }
 
 impl<L> DeviceDescriptor<L> {
@@ -1690,6 +2254,10 @@ impl<L> DeviceDescriptor<L> {
             label: fun(&self.label),
             required_features: self.required_features,
             required_limits: self.required_limits.clone(),
+            compute_only: self.compute_only,
+            queue_priority: self.queue_priority,
+            additional_queues: self.additional_queues,
+            cache_bind_groups: self.cache_bind_groups,
         }
     }
 }
@@ -1716,6 +2284,10 @@ bitflags::bitflags! {
         const COMPUTE = 1 << 2;
         /// Binding is visible from the vertex and fragment shaders of a render pipeline.
         const VERTEX_FRAGMENT = Self::VERTEX.bits() | Self::FRAGMENT.bits();
+        /// Binding is visible from the task shader of a mesh pipeline.
+        const TASK = 1 << 3;
+        /// Binding is visible from the mesh shader of a mesh pipeline.
+        const MESH = 1 << 4;
     }
 }
 
@@ -2176,6 +2748,17 @@ bitflags::bitflags! {
         const STORAGE_READ_WRITE = 1 << 6;
         /// If not present, the texture can't be blended into the render target.
         const BLENDABLE = 1 << 7;
+        /// Allows a multisampled texture of this format to be resolved into a texture
+        /// of the same format with `CommandEncoder::resolve_texture`, using
+        /// [`ResolveMode::Average`] on any sub-rectangle.
+        const MULTISAMPLE_RESOLVE_REGION = 1 << 8;
+        /// Allows [`ResolveMode::Min`] and [`ResolveMode::Max`] to be used when resolving
+        /// a texture of this format with `CommandEncoder::resolve_texture`, or into the
+        /// resolve target of a depth-stencil attachment.
+        const MULTISAMPLE_RESOLVE_MIN_MAX = 1 << 9;
+        /// Allows [`ResolveMode::Average`] to be used when resolving a depth-stencil attachment
+        /// of this format into its resolve target. Never supported by formats with a stencil aspect.
+        const MULTISAMPLE_RESOLVE_DEPTH_AVERAGE = 1 << 10;
     }
 }
 
@@ -2424,6 +3007,23 @@ pub enum TextureFormat {
     ///
     /// [`Features::TEXTURE_FORMAT_NV12`] must be enabled to use this texture format.
     NV12,
+    /// YUV 4:2:0 chroma subsampled format with 10 bits per channel.
+    ///
+    /// Contains two planes:
+    /// - 0: Single 16 bit channel luminance.
+    /// - 1: Dual 16 bit channel chrominance at half width and half height.
+    ///
+    /// The values are stored in the 10 most significant bits of each channel, the remaining bits
+    /// are zero.
+    ///
+    /// Valid view formats for luminance are [`TextureFormat::R16Unorm`].
+    ///
+    /// Valid view formats for chrominance are [`TextureFormat::Rg16Unorm`].
+    ///
+    /// Width and height must be even.
+    ///
+    /// [`Features::TEXTURE_FORMAT_P010`] must be enabled to use this texture format.
+    P010,
 
     // Compressed textures usable with `TEXTURE_COMPRESSION_BC` feature.
     /// 4x4 block compressed texture. 8 bytes per block (4 bit/px). 4 color + alpha pallet. 5 bit R + 6 bit G + 5 bit B + 1 bit alpha.
@@ -2655,6 +3255,7 @@ impl<'de> Deserialize<'de> for TextureFormat {
                     "depth24plus" => TextureFormat::Depth24Plus,
                     "depth24plus-stencil8" => TextureFormat::Depth24PlusStencil8,
                     "nv12" => TextureFormat::NV12,
+                    "p010" => TextureFormat::P010,
                     "rgb9e5ufloat" => TextureFormat::Rgb9e5Ufloat,
                     "bc1-rgba-unorm" => TextureFormat::Bc1RgbaUnorm,
                     "bc1-rgba-unorm-srgb" => TextureFormat::Bc1RgbaUnormSrgb,
@@ -2783,6 +3384,7 @@ impl Serialize for TextureFormat {
             TextureFormat::Depth24Plus => "depth24plus",
             TextureFormat::Depth24PlusStencil8 => "depth24plus-stencil8",
             TextureFormat::NV12 => "nv12",
+            TextureFormat::P010 => "p010",
             TextureFormat::Rgb9e5Ufloat => "rgb9e5ufloat",
             TextureFormat::Bc1RgbaUnorm => "bc1-rgba-unorm",
             TextureFormat::Bc1RgbaUnormSrgb => "bc1-rgba-unorm-srgb",
@@ -2871,6 +3473,8 @@ impl TextureFormat {
             (Self::Depth32FloatStencil8, TextureAspect::DepthOnly) => Some(Self::Depth32Float),
             (Self::NV12, TextureAspect::Plane0) => Some(Self::R8Unorm),
             (Self::NV12, TextureAspect::Plane1) => Some(Self::Rg8Unorm),
+            (Self::P010, TextureAspect::Plane0) => Some(Self::R16Unorm),
+            (Self::P010, TextureAspect::Plane1) => Some(Self::Rg16Unorm),
             // views to multi-planar formats must specify the plane
             (format, TextureAspect::All) if !format.is_multi_planar_format() => Some(format),
             _ => None,
@@ -2920,7 +3524,7 @@ impl TextureFormat {
     /// Returns the number of planes a multi-planar format has.
     pub fn planes(&self) -> Option<u32> {
         match *self {
-            Self::NV12 => Some(2),
+            Self::NV12 | Self::P010 => Some(2),
             _ => None,
         }
     }
@@ -2953,7 +3557,7 @@ impl TextureFormat {
     /// Returns the size multiple requirement for a texture using this format.
     pub fn size_multiple_requirement(&self) -> (u32, u32) {
         match *self {
-            Self::NV12 => (2, 2),
+            Self::NV12 | Self::P010 => (2, 2),
             _ => self.block_dimensions(),
         }
     }
@@ -3012,7 +3616,8 @@ impl TextureFormat {
             | Self::Depth24PlusStencil8
             | Self::Depth32Float
             | Self::Depth32FloatStencil8
-            | Self::NV12 => (1, 1),
+            | Self::NV12
+            | Self::P010 => (1, 1),
 
             Self::Bc1RgbaUnorm
             | Self::Bc1RgbaUnormSrgb
@@ -3064,6 +3669,32 @@ impl TextureFormat {
         self.block_dimensions() != (1, 1)
     }
 
+    /// Returns the dimensions in texels of the tiles of a [`TextureUsages::TILED`]
+    /// texture of this format, each of which takes [`TEXTURE_TILE_SIZE`] bytes.
+    ///
+    /// Returns `None` for formats that can't be tiled, which are the depth-stencil,
+    /// multi-planar and non-BC compressed formats.
+    pub fn tile_dimensions(&self) -> Option<(u32, u32)> {
+        if self.is_depth_stencil_format()
+            || self.is_multi_planar_format()
+            || (self.is_compressed()
+                && self.required_features() != Features::TEXTURE_COMPRESSION_BC)
+        {
+            return None;
+        }
+        // These are the standard tile shapes of both D3D12 and Vulkan.
+        let (width, height) = match self.block_copy_size(None)? {
+            1 => (256, 256),
+            2 => (256, 128),
+            4 => (128, 128),
+            8 => (128, 64),
+            16 => (64, 64),
+            _ => return None,
+        };
+        let (block_width, block_height) = self.block_dimensions();
+        Some((width * block_width, height * block_height))
+    }
+
     /// Returns the required features (if any) in order to use the texture.
     pub fn required_features(&self) -> Features {
         match *self {
@@ -3113,6 +3744,7 @@ impl TextureFormat {
             Self::Depth32FloatStencil8 => Features::DEPTH32FLOAT_STENCIL8,
 
             Self::NV12 => Features::TEXTURE_FORMAT_NV12,
+            Self::P010 => Features::TEXTURE_FORMAT_P010,
 
             Self::R16Unorm
             | Self::R16Snorm
@@ -3169,7 +3801,7 @@ impl TextureFormat {
         let attachment = basic | TextureUsages::RENDER_ATTACHMENT;
         let storage = basic | TextureUsages::STORAGE_BINDING;
         let binding = TextureUsages::TEXTURE_BINDING;
-        let all_flags = TextureUsages::all();
+        let all_flags = attachment | TextureUsages::STORAGE_BINDING;
         let rg11b10f = if device_features.contains(Features::RG11B10UFLOAT_RENDERABLE) {
             attachment
         } else {
@@ -3180,15 +3812,20 @@ impl TextureFormat {
         } else {
             attachment
         };
+        let r8uint = if device_features.contains(Features::SHADING_RATE_ATTACHMENT) {
+            attachment | TextureUsages::SHADING_RATE_ATTACHMENT
+        } else {
+            attachment
+        };
 
         #[rustfmt::skip] // lets make a nice table
         let (
             mut flags,
-            allowed_usages,
+            mut allowed_usages,
         ) = match *self {
             Self::R8Unorm =>              (msaa_resolve, attachment),
             Self::R8Snorm =>              (        noaa,      basic),
-            Self::R8Uint =>               (        msaa, attachment),
+            Self::R8Uint =>               (        msaa,     r8uint),
             Self::R8Sint =>               (        msaa, attachment),
             Self::R16Uint =>              (        msaa, attachment),
             Self::R16Sint =>              (        msaa, attachment),
@@ -3230,8 +3867,9 @@ impl TextureFormat {
             Self::Depth32Float =>         (        msaa, attachment),
             Self::Depth32FloatStencil8 => (        msaa, attachment),
 
-            // We only support sampling nv12 textures until we implement transfer plane data.
+            // We only support sampling planar textures until we implement transfer plane data.
             Self::NV12 =>                 (        noaa,    binding),
+            Self::P010 =>                 (        noaa,    binding),
 
             Self::R16Unorm =>             (        msaa,    storage),
             Self::R16Snorm =>             (        msaa,    storage),
@@ -3282,6 +3920,11 @@ impl TextureFormat {
         flags.set(TextureFormatFeatureFlags::FILTERABLE, is_filterable);
         flags.set(TextureFormatFeatureFlags::BLENDABLE, is_blendable);
 
+        allowed_usages.set(
+            TextureUsages::TILED,
+            device_features.contains(Features::TILED_TEXTURE) && self.tile_dimensions().is_some(),
+        );
+
         TextureFormatFeatures {
             allowed_usages,
             flags,
@@ -3355,7 +3998,7 @@ impl TextureFormat {
                 _ => None,
             },
 
-            Self::NV12 => match aspect {
+            Self::NV12 | Self::P010 => match aspect {
                 Some(TextureAspect::Plane0) | Some(TextureAspect::Plane1) => {
                     Some(unfilterable_float)
                 }
@@ -3486,6 +4129,12 @@ impl TextureFormat {
                 _ => None,
             },
 
+            Self::P010 => match aspect {
+                Some(TextureAspect::Plane0) => Some(2),
+                Some(TextureAspect::Plane1) => Some(4),
+                _ => None,
+            },
+
             Self::Bc1RgbaUnorm | Self::Bc1RgbaUnormSrgb | Self::Bc4RUnorm | Self::Bc4RSnorm => {
                 Some(8)
             }
@@ -3577,7 +4226,7 @@ impl TextureFormat {
                 _ => 2,
             },
 
-            Self::NV12 => match aspect {
+            Self::NV12 | Self::P010 => match aspect {
                 TextureAspect::Plane0 => 1,
                 TextureAspect::Plane1 => 2,
                 _ => 3,
@@ -4358,6 +5007,36 @@ impl MaintainResult {
     }
 }
 
+/// Result of waiting for a submission with a timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PollStatus {
+    /// The submission is done, and the callbacks that were waiting for it were called.
+    Complete,
+    /// The timeout elapsed before the submission was done.
+    TimedOut,
+    /// The device was lost, the submission will never be done.
+    DeviceLost,
+}
+
+impl PollStatus {
+    /// Returns true if the result is [`Self::Complete`].
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Self::Complete)
+    }
+}
+
+/// A submission that was still running after the threshold given to the device's watchdog.
+///
+/// Reported at most once per submission, while it is still running.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmissionTimeout {
+    /// Time elapsed since the submission was made.
+    pub elapsed: std::time::Duration,
+    /// Labels of the command buffers and passes of the submission, and of the
+    /// debug groups and markers recorded outside of passes, in recording order.
+    pub debug_markers: Vec<String>,
+}
+
 /// State of the stencil operation (fixed-pipeline stage).
 ///
 /// For use in [`DepthStencilState`].
@@ -4514,6 +5193,42 @@ pub enum IndexFormat {
     Uint32 = 1,
 }
 
+/// Size of the block of pixels covered by a single fragment shader invocation.
+///
+/// The discriminant of each rate is the value a texel of a shading rate attachment holds
+/// to select it: the base 2 logarithm of the width in the upper two bits and of the height
+/// in the lower two bits.
+///
+/// Used with [`Features::SHADING_RATE`] and [`Features::SHADING_RATE_ATTACHMENT`].
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ShadingRate {
+    /// A fragment for every pixel.
+    #[default]
+    Rate1x1 = 0b0000,
+    /// A fragment for every pixel horizontally and every second pixel vertically.
+    Rate1x2 = 0b0001,
+    /// A fragment for every second pixel horizontally and every pixel vertically.
+    Rate2x1 = 0b0100,
+    /// A fragment per 2x2 block of pixels.
+    Rate2x2 = 0b0101,
+    /// A fragment per block of 2 pixels horizontally and 4 pixels vertically.
+    Rate2x4 = 0b0110,
+    /// A fragment per block of 4 pixels horizontally and 2 pixels vertically.
+    Rate4x2 = 0b1001,
+    /// A fragment per 4x4 block of pixels.
+    Rate4x4 = 0b1010,
+}
+
+impl ShadingRate {
+    /// Returns the width and height in pixels of the blocks covered by a fragment.
+    pub fn block_size(&self) -> (u32, u32) {
+        let bits = *self as u32;
+        (1 << (bits >> 2), 1 << (bits & 0b11))
+    }
+}
+
 /// Operation to perform on the stencil value.
 ///
 /// Corresponds to [WebGPU `GPUStencilOperation`](
@@ -4889,6 +5604,19 @@ bitflags::bitflags! {
         const INDIRECT = 1 << 8;
         /// Allow a buffer to be the destination buffer for a [`CommandEncoder::resolve_query_set`] operation.
         const QUERY_RESOLVE = 1 << 9;
+        /// Allow a buffer to hold the vertices, indices or transform of a bottom level
+        /// acceleration structure build.
+        ///
+        /// Requires [`Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE`].
+        const BLAS_INPUT = 1 << 10;
+        /// Allow a buffer to hold the instances of a top level acceleration structure build.
+        ///
+        /// Requires [`Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE`].
+        const TLAS_INPUT = 1 << 11;
+        /// Allow a buffer to be written to by `RenderPass::begin_vertex_capture`.
+        ///
+        /// Requires [`Features::VERTEX_CAPTURE`].
+        const VERTEX_CAPTURE = 1 << 12;
     }
 }
 
@@ -5092,6 +5820,15 @@ bitflags::bitflags! {
         const STORAGE_BINDING = 1 << 3;
         /// Allows a texture to be an output attachment of a render pass.
         const RENDER_ATTACHMENT = 1 << 4;
+        /// Allows a texture to be the shading rate attachment of a render pass.
+        ///
+        /// Requires [`Features::SHADING_RATE_ATTACHMENT`].
+        const SHADING_RATE_ATTACHMENT = 1 << 5;
+        /// Creates the texture without memory, so that its tiles can be mapped to tiles of
+        /// a tile pool. It can't be combined with [`TextureUsages::RENDER_ATTACHMENT`].
+        ///
+        /// Requires [`Features::TILED_TEXTURE`].
+        const TILED = 1 << 6;
     }
 }
 
@@ -5256,6 +5993,20 @@ impl PresentationTimestamp {
     }
 }
 
+/// A CPU timestamp and a GPU timestamp sampled at the same moment.
+///
+/// This allows placing the results of timestamp queries on the CPU timeline,
+/// for instance to align GPU spans with CPU traces in a profiler.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TimestampCalibration {
+    /// The CPU timestamp, from the same clock as [`PresentationTimestamp`].
+    pub cpu_timestamp: PresentationTimestamp,
+    /// The GPU timestamp, in the ticks of timestamp queries.
+    ///
+    /// Must be multiplied by the timestamp period of the queue to get nanoseconds.
+    pub gpu_timestamp: u64,
+}
+
 /// RGBA double precision color.
 ///
 /// This is not to be used as a generic color type, only for specific wgpu interfaces.
@@ -5770,6 +6521,70 @@ pub enum TextureAspect {
     Plane2,
 }
 
+/// Source of a component of the texels read through a texture view.
+///
+/// For use in [`ComponentMapping`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ComponentSwizzle {
+    /// The component is always 0.
+    Zero,
+    /// The component is always 1.
+    One,
+    /// The red component of the texture.
+    R,
+    /// The green component of the texture.
+    G,
+    /// The blue component of the texture.
+    B,
+    /// The alpha component of the texture.
+    A,
+}
+
+/// Sources of the components of the texels read through a texture view.
+///
+/// For example, a single channel texture can be read as grayscale with
+/// `ComponentMapping { r: R, g: R, b: R, a: One }`.
+///
+/// Mappings other than [`ComponentMapping::IDENTITY`] require
+/// [`Features::TEXTURE_COMPONENT_SWIZZLE`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ComponentMapping {
+    /// Source of the red component.
+    pub r: ComponentSwizzle,
+    /// Source of the green component.
+    pub g: ComponentSwizzle,
+    /// Source of the blue component.
+    pub b: ComponentSwizzle,
+    /// Source of the alpha component.
+    pub a: ComponentSwizzle,
+}
+
+impl ComponentMapping {
+    /// Reads every component from the same component of the texture.
+    pub const IDENTITY: Self = Self {
+        r: ComponentSwizzle::R,
+        g: ComponentSwizzle::G,
+        b: ComponentSwizzle::B,
+        a: ComponentSwizzle::A,
+    };
+
+    /// Returns true if this is [`ComponentMapping::IDENTITY`].
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+}
+
+impl Default for ComponentMapping {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
 /// How edges should be handled in texture addressing.
 ///
 /// Corresponds to [WebGPU `GPUAddressMode`](
@@ -5825,6 +6640,34 @@ pub enum FilterMode {
     Linear = 1,
 }
 
+/// How the samples of a multisampled texture are combined when it is resolved
+/// with [`CommandEncoder::resolve_texture`](../wgpu/struct.CommandEncoder.html#method.resolve_texture),
+/// or into the resolve target of a depth-stencil attachment.
+///
+/// [`ResolveMode::Min`] and [`ResolveMode::Max`] require the format to support
+/// [`TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_MIN_MAX`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ResolveMode {
+    /// The average of the samples, as done by render pass resolve targets.
+    #[default]
+    Average = 0,
+    /// The smallest sample of every component.
+    ///
+    /// This is useful to build a depth pyramid out of a reversed-Z depth
+    /// buffer stored in a color texture.
+    Min = 1,
+    /// The largest sample of every component.
+    Max = 2,
+    /// The first sample, without combining it with the others.
+    ///
+    /// Only supported by depth-stencil resolve targets, see
+    /// [`Features::DEPTH_STENCIL_RESOLVE`].
+    SampleZero = 3,
+}
+
 /// A range of push constant memory to pass to a shader stage.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
@@ -6523,6 +7366,113 @@ unsafe impl Send for ExternalImageSource {}
 ))]
 unsafe impl Sync for ExternalImageSource {}
 
+/// Native image memory to import as a texture.
+///
+/// Used with `Device::import_external_texture`, which requires
+/// [`Features::EXTERNAL_TEXTURE_IMPORT`]. Each backend only accepts the sources
+/// of its own platform.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub enum ExternalImageSource {
+    /// A Linux dma-buf, imported by Vulkan.
+    DmaBuf(DmaBufImage),
+    /// An `IOSurfaceRef`, imported by Metal.
+    IoSurface {
+        /// The `IOSurfaceRef` to import.
+        surface: *mut std::ffi::c_void,
+        /// The plane of the surface to import.
+        plane: u32,
+    },
+    /// A shared NT `HANDLE` to an `ID3D12Resource`, imported by DX12.
+    ///
+    /// The handle is not closed by wgpu.
+    D3D12SharedHandle(*mut std::ffi::c_void),
+}
+
+/// A Linux dma-buf and the layout of its planes.
+///
+/// wgpu imports a duplicate of `fd`, so the caller keeps ownership of it.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub struct DmaBufImage {
+    /// File descriptor of the dma-buf.
+    pub fd: i32,
+    /// DRM format modifier describing the tiling of the buffer.
+    pub modifier: u64,
+    /// Layout of each memory plane of the buffer, as many as the modifier requires.
+    pub planes: Vec<DmaBufPlane>,
+}
+
+/// Layout of a single memory plane of a [`DmaBufImage`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DmaBufPlane {
+    /// Offset of the plane from the start of the buffer, in bytes.
+    pub offset: u64,
+    /// Distance between the starts of consecutive rows, in bytes.
+    pub stride: u64,
+}
+
+/// Native handle of a fence shared with another API.
+///
+/// Used with [`Features::EXTERNAL_FENCE`]. Each backend only accepts the handles
+/// of its own platform.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug)]
+pub enum ExternalFenceHandle {
+    /// An opaque file descriptor of a Vulkan timeline semaphore, as used by
+    /// `VK_KHR_external_semaphore_fd` and CUDA.
+    ///
+    /// wgpu imports a duplicate of the descriptor, and exported descriptors are
+    /// owned by the caller.
+    OpaqueFd(i32),
+    /// A shared NT `HANDLE` to an `ID3D12Fence`.
+    ///
+    /// Imported handles are not closed by wgpu, and exported handles are owned by
+    /// the caller.
+    D3D12SharedHandle(*mut std::ffi::c_void),
+    /// An `id<MTLSharedEvent>`.
+    ///
+    /// wgpu retains imported events, and exported events must be retained by the
+    /// caller to outlive the fence.
+    MetalSharedEvent(*mut std::ffi::c_void),
+}
+
+/// Memory of a buffer shared with another API.
+///
+/// Used with [`Features::EXTERNAL_MEMORY`]. Each backend only accepts the handles
+/// of its own platform.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug)]
+pub struct ExternalMemory {
+    /// Native handle of the memory.
+    pub handle: ExternalMemoryHandle,
+    /// Size of the memory allocation in bytes, which may be larger than the buffer.
+    ///
+    /// CUDA and HIP need it to import the memory, and Vulkan needs the size the
+    /// memory was exported with.
+    pub size: u64,
+}
+
+/// Native handle of memory shared with another API.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug)]
+pub enum ExternalMemoryHandle {
+    /// An opaque file descriptor of Vulkan device memory, as used by
+    /// `VK_KHR_external_memory_fd`, `cudaExternalMemoryHandleTypeOpaqueFd` and
+    /// `hipExternalMemoryHandleTypeOpaqueFd`.
+    ///
+    /// wgpu imports a duplicate of the descriptor, and exported descriptors are
+    /// owned by the caller.
+    OpaqueFd(i32),
+    /// A shared NT `HANDLE` to a committed `ID3D12Resource`, as used by
+    /// `cudaExternalMemoryHandleTypeD3D12Resource`.
+    ///
+    /// Imported handles are not closed by wgpu, and exported handles are owned by
+    /// the caller.
+    D3D12SharedHandle(*mut std::ffi::c_void),
+}
+
 /// Color spaces supported on the web.
 ///
 /// Corresponds to [HTML Canvas `PredefinedColorSpace`](
@@ -6724,6 +7674,118 @@ impl<L> QuerySetDescriptor<L> {
     }
 }
 
+/// Describes how to create a tile pool, the memory that the tiles of
+/// [`TextureUsages::TILED`] textures are mapped to.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct TilePoolDescriptor<L> {
+    /// Debug label for the tile pool.
+    pub label: L,
+    /// Number of tiles of [`TEXTURE_TILE_SIZE`] bytes in the pool. Must not be zero.
+    pub tile_count: u32,
+}
+
+impl<L> TilePoolDescriptor<L> {
+    /// Takes a closure and maps the label of the tile pool descriptor into another.
+    pub fn map_label<'a, K>(&'a self, fun: impl FnOnce(&'a L) -> K) -> TilePoolDescriptor<K> {
+        TilePoolDescriptor {
+            label: fun(&self.label),
+            tile_count: self.tile_count,
+        }
+    }
+}
+
+/// Kind of memory a memory heap is made of, which decides how the resources placed in
+/// it can be used.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub enum MemoryHeapType {
+    /// Memory that is fast to access from the GPU, and can't be mapped.
+    ///
+    /// Holds textures, and buffers without [`BufferUsages::MAP_READ`] or
+    /// [`BufferUsages::MAP_WRITE`].
+    DeviceLocal,
+    /// Memory that the CPU writes and the GPU reads.
+    ///
+    /// Holds buffers with at most [`BufferUsages::MAP_WRITE`] and [`BufferUsages::COPY_SRC`].
+    Upload,
+    /// Memory that the GPU writes and the CPU reads.
+    ///
+    /// Holds buffers with at most [`BufferUsages::MAP_READ`] and [`BufferUsages::COPY_DST`].
+    Readback,
+}
+
+impl MemoryHeapType {
+    /// Returns whether buffers with `usage` can be placed in heaps of this type.
+    pub fn supports_buffer_usage(self, usage: BufferUsages) -> bool {
+        match self {
+            Self::DeviceLocal => {
+                !usage.intersects(BufferUsages::MAP_READ | BufferUsages::MAP_WRITE)
+            }
+            Self::Upload => (BufferUsages::MAP_WRITE | BufferUsages::COPY_SRC).contains(usage),
+            Self::Readback => (BufferUsages::MAP_READ | BufferUsages::COPY_DST).contains(usage),
+        }
+    }
+
+    /// Returns whether textures can be placed in heaps of this type.
+    pub fn supports_textures(self) -> bool {
+        self == Self::DeviceLocal
+    }
+}
+
+/// Describes how to create a memory heap, which buffers and textures can be placed in.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct MemoryHeapDescriptor<L> {
+    /// Debug label for the memory heap.
+    pub label: L,
+    /// Size of the heap in bytes. Must not be zero.
+    pub size: BufferAddress,
+    /// Kind of memory the heap is made of.
+    pub memory_type: MemoryHeapType,
+}
+
+impl<L> MemoryHeapDescriptor<L> {
+    /// Takes a closure and maps the label of the memory heap descriptor into another.
+    pub fn map_label<'a, K>(&'a self, fun: impl FnOnce(&'a L) -> K) -> MemoryHeapDescriptor<K> {
+        MemoryHeapDescriptor {
+            label: fun(&self.label),
+            size: self.size,
+            memory_type: self.memory_type,
+        }
+    }
+}
+
+/// Describes how to create an external fence, a GPU timeline shared with other APIs.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct ExternalFenceDescriptor<L> {
+    /// Debug label for the external fence.
+    pub label: L,
+}
+
+impl<L> ExternalFenceDescriptor<L> {
+    /// Takes a closure and maps the label of the external fence descriptor into another.
+    pub fn map_label<'a, K>(&'a self, fun: impl FnOnce(&'a L) -> K) -> ExternalFenceDescriptor<K> {
+        ExternalFenceDescriptor {
+            label: fun(&self.label),
+        }
+    }
+}
+
+/// The memory a resource takes up when it's placed in a memory heap.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryRequirements {
+    /// Number of bytes of the heap the resource covers.
+    pub size: BufferAddress,
+    /// The offset of the resource in the heap must be a multiple of this.
+    pub alignment: BufferAddress,
+}
+
 /// Type of query contained in a QuerySet.
 ///
 /// Corresponds to [WebGPU `GPUQueryType`](
@@ -6850,6 +7912,88 @@ impl DrawIndexedIndirectArgs {
     }
 }
 
+/// Argument buffer layout for the vertex buffers bound by [`IndirectArgument::VertexBuffer`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct VertexBufferIndirectArgs {
+    /// Device address of the start of the vertex data, see `Buffer::device_address`.
+    pub address: BufferAddress,
+    /// The size of the vertex data in bytes.
+    pub size: u32,
+    /// The stride of the vertex buffer, which must match the `array_stride` of
+    /// the pipeline.
+    pub stride: u32,
+}
+
+impl VertexBufferIndirectArgs {
+    /// Returns the bytes representation of the struct, ready to be written in a buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            std::mem::transmute(std::slice::from_raw_parts(
+                self as *const _ as *const u8,
+                std::mem::size_of::<Self>(),
+            ))
+        }
+    }
+}
+
+/// Argument of the commands written by the GPU for `RenderPass::execute_indirect`.
+///
+/// Each command is made of its arguments, tightly packed in order, and must end
+/// with a single [`IndirectArgument::Draw`] or [`IndirectArgument::DrawIndexed`].
+/// [`IndirectArgument::VertexBuffer`] arguments must start at a multiple of 8 bytes.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum IndirectArgument {
+    /// Binds a vertex buffer to `slot`, reading a [`VertexBufferIndirectArgs`].
+    VertexBuffer {
+        /// Vertex buffer slot.
+        slot: u32,
+    },
+    /// Sets push constants, reading `size` bytes.
+    PushConstants {
+        /// Stages of the push constant range.
+        stages: ShaderStages,
+        /// Byte offset within the push constant storage, a multiple of 4.
+        offset: u32,
+        /// Number of bytes to write, a multiple of 4.
+        size: u32,
+    },
+    /// Draws, reading a [`DrawIndirectArgs`].
+    Draw,
+    /// Draws indexed primitives, reading a [`DrawIndexedIndirectArgs`].
+    DrawIndexed,
+}
+
+impl IndirectArgument {
+    /// Returns the number of bytes read for the argument.
+    pub const fn size(&self) -> u32 {
+        match *self {
+            Self::VertexBuffer { .. } => std::mem::size_of::<VertexBufferIndirectArgs>() as u32,
+            Self::PushConstants { size, .. } => size,
+            Self::Draw => std::mem::size_of::<DrawIndirectArgs>() as u32,
+            Self::DrawIndexed => std::mem::size_of::<DrawIndexedIndirectArgs>() as u32,
+        }
+    }
+
+    /// Returns the distance in bytes between commands made of `arguments`.
+    ///
+    /// This is their total size, rounded up to a multiple of 8 if they bind vertex
+    /// buffers.
+    pub fn stride(arguments: &[Self]) -> u32 {
+        let size: u32 = arguments.iter().map(Self::size).sum();
+        if arguments
+            .iter()
+            .any(|argument| matches!(*argument, Self::VertexBuffer { .. }))
+        {
+            (size + 7) & !7
+        } else {
+            size
+        }
+    }
+}
+
 /// Argument buffer layout for dispatch_indirect commands.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Default)]
@@ -6874,6 +8018,31 @@ impl DispatchIndirectArgs {
     }
 }
 
+/// Argument buffer layout for draw_mesh_tasks_indirect commands.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DrawMeshTasksIndirectArgs {
+    /// The number of task shader work groups in X dimension, or mesh shader
+    /// work groups if the pipeline has no task stage.
+    pub group_count_x: u32,
+    /// The number of work groups in Y dimension.
+    pub group_count_y: u32,
+    /// The number of work groups in Z dimension.
+    pub group_count_z: u32,
+}
+
+impl DrawMeshTasksIndirectArgs {
+    /// Returns the bytes representation of the struct, ready to be written into a buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            std::mem::transmute(std::slice::from_raw_parts(
+                self as *const _ as *const u8,
+                std::mem::size_of::<Self>(),
+            ))
+        }
+    }
+}
+
 /// Describes how shader bound checks should be performed.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
@@ -6986,6 +8155,33 @@ impl Default for InstanceDescriptor {
     }
 }
 
+/// Severity of a [`DebugMessage`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DebugMessageSeverity {
+    /// Diagnostic information, only useful when debugging wgpu or the backend.
+    Verbose,
+    /// Informational message.
+    Info,
+    /// Possible misuse of the API, or a performance issue.
+    Warning,
+    /// Invalid use of the API, or a failure of the backend.
+    Error,
+}
+
+/// A message reported by a backend's validation layers or debug runtime, or by wgpu itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugMessage {
+    /// How serious the message is.
+    pub severity: DebugMessageSeverity,
+    /// Backend that reported the message, or `None` for messages of wgpu itself.
+    pub backend: Option<Backend>,
+    /// Text of the message.
+    pub message: String,
+    /// Labels of the objects, queues and command buffers the message is about, if the
+    /// backend reports them.
+    pub labels: Vec<String>,
+}
+
 bitflags::bitflags!(
     /// Flags for acceleration structures
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -7016,6 +8212,98 @@ bitflags::bitflags!(
 );
 impl_bitflags!(AccelerationStructureGeometryFlags);
 
+/// Describes how to create a bottom level acceleration structure (BLAS), which
+/// holds triangle geometry.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct CreateBlasDescriptor<L> {
+    /// Debug label for the acceleration structure.
+    pub label: L,
+    /// Flags for the acceleration structure.
+    pub flags: AccelerationStructureFlags,
+}
+
+impl<L> CreateBlasDescriptor<L> {
+    /// Takes a closure and maps the label of the descriptor into another.
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> CreateBlasDescriptor<K> {
+        CreateBlasDescriptor {
+            label: fun(&self.label),
+            flags: self.flags,
+        }
+    }
+}
+
+/// Describes how to create a top level acceleration structure (TLAS), which
+/// holds instances of bottom level acceleration structures.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct CreateTlasDescriptor<L> {
+    /// Debug label for the acceleration structure.
+    pub label: L,
+    /// The maximum number of instances the acceleration structure can be built with.
+    pub max_instances: u32,
+    /// Flags for the acceleration structure.
+    pub flags: AccelerationStructureFlags,
+}
+
+impl<L> CreateTlasDescriptor<L> {
+    /// Takes a closure and maps the label of the descriptor into another.
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> CreateTlasDescriptor<K> {
+        CreateTlasDescriptor {
+            label: fun(&self.label),
+            max_instances: self.max_instances,
+            flags: self.flags,
+        }
+    }
+}
+
+/// The size of a triangle geometry of a bottom level acceleration structure.
+///
+/// The geometries a BLAS is built with must have the same formats and flags as
+/// the ones it was created with, and at most as many vertices and indices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct BlasTriangleGeometrySizeDescriptor {
+    /// Format of the vertex positions. Must be one of [`VertexFormat::Float32x2`],
+    /// [`VertexFormat::Float32x3`], [`VertexFormat::Float16x2`], [`VertexFormat::Float16x4`],
+    /// [`VertexFormat::Snorm16x2`] or [`VertexFormat::Snorm16x4`].
+    pub vertex_format: VertexFormat,
+    /// Number of vertices. Without indices, this must be a multiple of 3.
+    pub vertex_count: u32,
+    /// Format of the indices, if the triangles are indexed.
+    pub index_format: Option<IndexFormat>,
+    /// Number of indices, if the triangles are indexed. Must be a multiple of 3.
+    pub index_count: Option<u32>,
+    /// Flags for the geometry.
+    pub flags: AccelerationStructureGeometryFlags,
+}
+
+/// The sizes of the geometries of a bottom level acceleration structure.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub enum BlasGeometrySizeDescriptors {
+    /// Triangle geometries.
+    Triangles {
+        /// The size of each geometry.
+        descriptors: Vec<BlasTriangleGeometrySizeDescriptor>,
+    },
+}
+
+/// The size in bytes of an instance in the instance buffer of a top level
+/// acceleration structure build.
+///
+/// Each instance is laid out like Vulkan's `VkAccelerationStructureInstanceKHR`:
+/// - a row-major 3x4 transform matrix, as 12 `f32`,
+/// - a `u32` holding the custom index in its 24 low bits and the visibility mask in its 8 high bits,
+/// - a `u32` holding the shader binding table offset in its 24 low bits and
+///   `VkGeometryInstanceFlagsKHR` in its 8 high bits,
+/// - the `u64` handle of the bottom level acceleration structure it instantiates.
+pub const TLAS_INSTANCE_SIZE: BufferAddress = 64;
+
 pub use send_sync::*;
 
 #[doc(hidden)]
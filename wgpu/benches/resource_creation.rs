@@ -0,0 +1,107 @@
+//! Measures how resource creation scales when several threads create
+//! resources on the same device at once.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use criterion::*;
+use std::thread;
+
+const RESOURCES_PER_ITERATION: usize = 1024;
+const THREAD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+
+fn request_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::default();
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::HighPerformance,
+        force_fallback_adapter: false,
+        compatible_surface: None,
+    }))?;
+    pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None)).ok()
+}
+
+/// Creates [`RESOURCES_PER_ITERATION`] resources with `create`, split evenly
+/// over `threads` threads.
+fn create_in_parallel<T>(threads: usize, create: impl Fn() -> T + Sync) {
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..RESOURCES_PER_ITERATION / threads {
+                    black_box(create());
+                }
+            });
+        }
+    });
+}
+
+fn resource_creation(c: &mut Criterion) {
+    let Some((device, _queue)) = request_device() else {
+        eprintln!("No adapter available, skipping the resource creation benchmarks");
+        return;
+    };
+
+    let buffer_desc = wgpu::BufferDescriptor {
+        label: None,
+        size: 256,
+        usage: wgpu::BufferUsages::UNIFORM,
+        mapped_at_creation: false,
+    };
+
+    let mut group = c.benchmark_group("create_buffer");
+    group.throughput(Throughput::Elements(RESOURCES_PER_ITERATION as u64));
+    for threads in THREAD_COUNTS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    create_in_parallel(threads, || device.create_buffer(&buffer_desc));
+                    device.poll(wgpu::Maintain::Poll);
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::VERTEX,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    });
+    let buffer = device.create_buffer(&buffer_desc);
+
+    let mut group = c.benchmark_group("create_bind_group");
+    group.throughput(Throughput::Elements(RESOURCES_PER_ITERATION as u64));
+    for threads in THREAD_COUNTS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    create_in_parallel(threads, || {
+                        device.create_bind_group(&wgpu::BindGroupDescriptor {
+                            label: None,
+                            layout: &layout,
+                            entries: &[wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: buffer.as_entire_binding(),
+                            }],
+                        })
+                    });
+                    device.poll(wgpu::Maintain::Poll);
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, resource_creation);
+criterion_main!(benches);
@@ -11,6 +11,11 @@ fn main() {
         ) },
         dx12: { all(target_os = "windows", feature = "dx12") },
         metal: { all(any(target_os = "ios", target_os = "macos"), feature = "metal") },
+        vulkan: { all(
+            not(target_arch = "wasm32"),
+            feature = "vulkan",
+            any(not(any(target_os = "ios", target_os = "macos")), feature = "vulkan-portability")
+        ) },
         // This alias is _only_ if _we_ need naga in the wrapper. wgpu-core provides
         // its own re-export of naga, which can be used in other situations
         naga: { any(feature = "naga-ir", feature = "spirv", feature = "glsl") },
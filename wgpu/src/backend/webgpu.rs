@@ -97,6 +97,11 @@ impl crate::Error {
             }
         } else if js_error.has_type::<web_sys::GpuOutOfMemoryError>() {
             crate::Error::OutOfMemory { source }
+        } else if let Some(js_error) = js_error.dyn_ref::<web_sys::GpuInternalError>() {
+            crate::Error::Internal {
+                source,
+                description: js_error.message(),
+            }
         } else {
             panic!("Unexpected error");
         }
@@ -733,6 +738,11 @@ fn map_wgt_limits(limits: web_sys::GpuSupportedLimits) -> wgt::Limits {
         // The following are not part of WebGPU
         max_push_constant_size: wgt::Limits::default().max_push_constant_size,
         max_non_sampler_bindings: wgt::Limits::default().max_non_sampler_bindings,
+        max_binding_array_elements_per_shader_stage: wgt::Limits::default()
+            .max_binding_array_elements_per_shader_stage,
+        max_binding_array_sampler_elements_per_shader_stage: wgt::Limits::default()
+            .max_binding_array_sampler_elements_per_shader_stage,
+        shading_rate_tile_size: wgt::Limits::default().shading_rate_tile_size,
     }
 }
 
@@ -791,6 +801,124 @@ fn map_js_sys_limits(limits: &wgt::Limits) -> js_sys::Object {
     object
 }
 
+fn map_render_pipeline_descriptor(
+    desc: &crate::RenderPipelineDescriptor<'_>,
+) -> web_sys::GpuRenderPipelineDescriptor {
+    let module: &<ContextWebGpu as crate::Context>::ShaderModuleData =
+        downcast_ref(desc.vertex.module.data.as_ref());
+    let mut mapped_vertex_state = web_sys::GpuVertexState::new(desc.vertex.entry_point, &module.0);
+
+    let buffers = desc
+        .vertex
+        .buffers
+        .iter()
+        .map(|vbuf| {
+            let mapped_attributes = vbuf
+                .attributes
+                .iter()
+                .map(|attr| {
+                    web_sys::GpuVertexAttribute::new(
+                        map_vertex_format(attr.format),
+                        attr.offset as f64,
+                        attr.shader_location,
+                    )
+                })
+                .collect::<js_sys::Array>();
+
+            let mut mapped_vbuf =
+                web_sys::GpuVertexBufferLayout::new(vbuf.array_stride as f64, &mapped_attributes);
+            mapped_vbuf.step_mode(map_vertex_step_mode(vbuf.step_mode));
+            mapped_vbuf
+        })
+        .collect::<js_sys::Array>();
+
+    mapped_vertex_state.buffers(&buffers);
+
+    let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
+    let mut mapped_desc = web_sys::GpuRenderPipelineDescriptor::new(
+        &match desc.layout {
+            Some(layout) => {
+                let layout: &<ContextWebGpu as crate::Context>::PipelineLayoutData =
+                    downcast_ref(layout.data.as_ref());
+                JsValue::from(&layout.0)
+            }
+            None => auto_layout,
+        },
+        &mapped_vertex_state,
+    );
+
+    if let Some(label) = desc.label {
+        mapped_desc.label(label);
+    }
+
+    if let Some(ref depth_stencil) = desc.depth_stencil {
+        mapped_desc.depth_stencil(&map_depth_stencil_state(depth_stencil));
+    }
+
+    if let Some(ref frag) = desc.fragment {
+        let targets = frag
+            .targets
+            .iter()
+            .map(|target| match target {
+                Some(target) => {
+                    let mapped_format = map_texture_format(target.format);
+                    let mut mapped_color_state = web_sys::GpuColorTargetState::new(mapped_format);
+                    if let Some(ref bs) = target.blend {
+                        let alpha = map_blend_component(&bs.alpha);
+                        let color = map_blend_component(&bs.color);
+                        let mapped_blend_state = web_sys::GpuBlendState::new(&alpha, &color);
+                        mapped_color_state.blend(&mapped_blend_state);
+                    }
+                    mapped_color_state.write_mask(target.write_mask.bits());
+                    wasm_bindgen::JsValue::from(mapped_color_state)
+                }
+                None => wasm_bindgen::JsValue::null(),
+            })
+            .collect::<js_sys::Array>();
+        let module: &<ContextWebGpu as crate::Context>::ShaderModuleData =
+            downcast_ref(frag.module.data.as_ref());
+        let mapped_fragment_desc =
+            web_sys::GpuFragmentState::new(frag.entry_point, &module.0, &targets);
+        mapped_desc.fragment(&mapped_fragment_desc);
+    }
+
+    let mut mapped_multisample = web_sys::GpuMultisampleState::new();
+    mapped_multisample.count(desc.multisample.count);
+    mapped_multisample.mask(desc.multisample.mask as u32);
+    mapped_multisample.alpha_to_coverage_enabled(desc.multisample.alpha_to_coverage_enabled);
+    mapped_desc.multisample(&mapped_multisample);
+
+    let mapped_primitive = map_primitive_state(&desc.primitive);
+    mapped_desc.primitive(&mapped_primitive);
+
+    mapped_desc
+}
+
+fn map_compute_pipeline_descriptor(
+    desc: &crate::ComputePipelineDescriptor<'_>,
+) -> web_sys::GpuComputePipelineDescriptor {
+    let shader_module: &<ContextWebGpu as crate::Context>::ShaderModuleData =
+        downcast_ref(desc.module.data.as_ref());
+    let mapped_compute_stage =
+        web_sys::GpuProgrammableStage::new(desc.entry_point, &shader_module.0);
+    let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
+    let mut mapped_desc = web_sys::GpuComputePipelineDescriptor::new(
+        &match desc.layout {
+            Some(layout) => {
+                let layout: &<ContextWebGpu as crate::Context>::PipelineLayoutData =
+                    downcast_ref(layout.data.as_ref());
+                JsValue::from(&layout.0)
+            }
+            None => auto_layout,
+        },
+        &mapped_compute_stage,
+    );
+    if let Some(label) = desc.label {
+        mapped_desc.label(label);
+    }
+    mapped_desc
+}
+
 type JsFutureResult = Result<wasm_bindgen::JsValue, wasm_bindgen::JsValue>;
 
 fn future_request_adapter(
@@ -838,6 +966,52 @@ fn future_pop_error_scope(result: JsFutureResult) -> Option<crate::Error> {
     }
 }
 
+fn future_device_lost(result: JsFutureResult) -> (wgt::DeviceLostReason, String) {
+    let Ok(js_value) = result else {
+        return (wgt::DeviceLostReason::Unknown, String::new());
+    };
+    let info = web_sys::GpuDeviceLostInfo::from(js_value);
+    let reason = match info.reason() {
+        web_sys::GpuDeviceLostReason::Destroyed => wgt::DeviceLostReason::Destroyed,
+        _ => wgt::DeviceLostReason::Unknown,
+    };
+    (reason, info.message())
+}
+
+fn future_compilation_info(result: JsFutureResult) -> crate::CompilationInfo {
+    let Ok(js_value) = result else {
+        return crate::CompilationInfo::default();
+    };
+    let compilation_info = web_sys::GpuCompilationInfo::from(js_value);
+    let messages = compilation_info
+        .messages()
+        .iter()
+        .map(|js_message| {
+            let message = web_sys::GpuCompilationMessage::from(js_message);
+            crate::CompilationMessage {
+                message: message.message(),
+                message_type: match message.type_() {
+                    web_sys::GpuCompilationMessageType::Error => {
+                        crate::CompilationMessageType::Error
+                    }
+                    web_sys::GpuCompilationMessageType::Warning => {
+                        crate::CompilationMessageType::Warning
+                    }
+                    _ => crate::CompilationMessageType::Info,
+                },
+                // A line number of 0 means that the message doesn't refer to the source.
+                location: (message.line_num() != 0.0).then(|| crate::SourceLocation {
+                    line_number: message.line_num() as u32,
+                    line_position: message.line_pos() as u32,
+                    offset: message.offset() as u32,
+                    length: message.length() as u32,
+                }),
+            }
+        })
+        .collect();
+    crate::CompilationInfo { messages }
+}
+
 /// Calls `callback(success_value)` when the promise completes successfully, calls `callback(failure_value)`
 /// when the promise completes unsuccessfully.
 fn register_then_closures<F, T>(promise: &Promise, callback: F, success_value: T, failure_value: T)
@@ -1011,12 +1185,24 @@ impl crate::context::Context for ContextWebGpu {
     type TextureData = Sendable<web_sys::GpuTexture>;
     type QuerySetId = Identified<web_sys::GpuQuerySet>;
     type QuerySetData = Sendable<web_sys::GpuQuerySet>;
+    type TilePoolId = Unused;
+    type TilePoolData = ();
+    type MemoryHeapId = Unused;
+    type MemoryHeapData = ();
+    type ExternalFenceId = Unused;
+    type ExternalFenceData = ();
+    type BlasId = Unused;
+    type BlasData = ();
+    type TlasId = Unused;
+    type TlasData = ();
     type PipelineLayoutId = Identified<web_sys::GpuPipelineLayout>;
     type PipelineLayoutData = Sendable<web_sys::GpuPipelineLayout>;
     type RenderPipelineId = Identified<web_sys::GpuRenderPipeline>;
     type RenderPipelineData = Sendable<web_sys::GpuRenderPipeline>;
     type ComputePipelineId = Identified<web_sys::GpuComputePipeline>;
     type ComputePipelineData = Sendable<web_sys::GpuComputePipeline>;
+    type PipelineCacheId = Unused;
+    type PipelineCacheData = ();
     type CommandEncoderId = Identified<web_sys::GpuCommandEncoder>;
     type CommandEncoderData = Sendable<web_sys::GpuCommandEncoder>;
     type ComputePassId = Identified<web_sys::GpuComputePassEncoder>;
@@ -1056,6 +1242,22 @@ impl crate::context::Context for ContextWebGpu {
     >;
     type PopErrorScopeFuture =
         MakeSendFuture<wasm_bindgen_futures::JsFuture, fn(JsFutureResult) -> Option<crate::Error>>;
+    type CompilationInfoFuture = MakeSendFuture<
+        wasm_bindgen_futures::JsFuture,
+        fn(JsFutureResult) -> crate::CompilationInfo,
+    >;
+    type DeviceLostFuture = MakeSendFuture<
+        wasm_bindgen_futures::JsFuture,
+        fn(JsFutureResult) -> (wgt::DeviceLostReason, String),
+    >;
+    type CreateRenderPipelineFuture = MakeSendFuture<
+        wasm_bindgen_futures::JsFuture,
+        Box<dyn Fn(JsFutureResult) -> (Self::RenderPipelineId, Self::RenderPipelineData)>,
+    >;
+    type CreateComputePipelineFuture = MakeSendFuture<
+        wasm_bindgen_futures::JsFuture,
+        Box<dyn Fn(JsFutureResult) -> (Self::ComputePipelineId, Self::ComputePipelineData)>,
+    >;
 
     fn init(_instance_desc: wgt::InstanceDescriptor) -> Self {
         let Some(gpu) = get_browser_gpu_property() else {
@@ -1151,6 +1353,9 @@ impl crate::context::Context for ContextWebGpu {
         if trace_dir.is_some() {
             //Error: Tracing isn't supported on the Web target
         }
+        if desc.additional_queues > 0 {
+            panic!("Additional queues are not supported on the WebGPU backend")
+        }
 
         let mut mapped_desc = web_sys::GpuDeviceDescriptor::new();
 
@@ -1195,6 +1400,18 @@ impl crate::context::Context for ContextWebGpu {
         true
     }
 
+    fn instance_set_debug_callback(&self, _callback: Option<crate::context::DebugCallback>) {
+        // The browser reports its messages to the developer console.
+    }
+
+    fn begin_error_capture(&self) {
+        // The browser reports errors asynchronously, so they can't be captured.
+    }
+
+    fn end_error_capture(&self) -> Option<crate::Error> {
+        None
+    }
+
     fn adapter_is_surface_supported(
         &self,
         _adapter: &Self::AdapterId,
@@ -1213,6 +1430,27 @@ impl crate::context::Context for ContextWebGpu {
         map_wgt_features(adapter_data.0.features())
     }
 
+    fn adapter_explain_feature(
+        &self,
+        _adapter: &Self::AdapterId,
+        adapter_data: &Self::AdapterData,
+        feature: wgt::Features,
+    ) -> wgt::FeatureSupport {
+        let missing = feature - map_wgt_features(adapter_data.0.features());
+        if missing.is_empty() {
+            wgt::FeatureSupport::Available
+        } else if missing.intersects(wgt::Features::all_native_mask()) {
+            wgt::FeatureSupport::NotImplemented {
+                backend: wgt::Backend::BrowserWebGpu,
+            }
+        } else {
+            wgt::FeatureSupport::MissingRequirement {
+                backend: wgt::Backend::BrowserWebGpu,
+                requirement: "support by the browser and the adapter",
+            }
+        }
+    }
+
     fn adapter_limits(
         &self,
         _adapter: &Self::AdapterId,
@@ -1264,6 +1502,14 @@ impl crate::context::Context for ContextWebGpu {
         wgt::PresentationTimestamp::INVALID_TIMESTAMP
     }
 
+    fn adapter_max_additional_queues(
+        &self,
+        _adapter: &Self::AdapterId,
+        _adapter_data: &Self::AdapterData,
+    ) -> u32 {
+        0
+    }
+
     fn surface_get_capabilities(
         &self,
         _surface: &Self::SurfaceId,
@@ -1395,6 +1641,15 @@ impl crate::context::Context for ContextWebGpu {
         wgt::DownlevelCapabilities::default()
     }
 
+    fn device_memory_architecture(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+    ) -> wgt::MemoryArchitecture {
+        // WebGPU doesn't expose the memory architecture.
+        wgt::MemoryArchitecture::Discrete
+    }
+
     #[cfg_attr(
         not(any(
             feature = "spirv",
@@ -1447,6 +1702,8 @@ impl crate::context::Context for ContextWebGpu {
                 let options = front::glsl::Options {
                     stage,
                     defines: defines.clone(),
+                    include_resolver: None,
+                    entry_point: None,
                 };
                 let mut parser = front::glsl::Frontend::default();
                 let glsl_module = parser.parse(&options, shader).unwrap();
@@ -1499,6 +1756,18 @@ impl crate::context::Context for ContextWebGpu {
         unreachable!("SPIRV_SHADER_PASSTHROUGH is not enabled for this backend")
     }
 
+    fn shader_get_compilation_info(
+        &self,
+        _shader: &Self::ShaderModuleId,
+        shader_data: &Self::ShaderModuleData,
+    ) -> Self::CompilationInfoFuture {
+        let compilation_info_promise = shader_data.0.get_compilation_info();
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(compilation_info_promise),
+            future_compilation_info,
+        )
+    }
+
     fn device_create_bind_group_layout(
         &self,
         _device: &Self::DeviceId,
@@ -1644,6 +1913,9 @@ impl crate::context::Context for ContextWebGpu {
                     crate::BindingResource::TextureViewArray(..) => {
                         panic!("Web backend does not support BINDING_INDEXING extension")
                     }
+                    crate::BindingResource::AccelerationStructure(..) => {
+                        panic!("Web backend does not support ray tracing")
+                    }
                 };
 
                 web_sys::GpuBindGroupEntry::new(binding.binding, &mapped_resource)
@@ -1687,98 +1959,37 @@ impl crate::context::Context for ContextWebGpu {
         device_data: &Self::DeviceData,
         desc: &crate::RenderPipelineDescriptor<'_>,
     ) -> (Self::RenderPipelineId, Self::RenderPipelineData) {
-        let module: &<ContextWebGpu as crate::Context>::ShaderModuleData =
-            downcast_ref(desc.vertex.module.data.as_ref());
-        let mut mapped_vertex_state =
-            web_sys::GpuVertexState::new(desc.vertex.entry_point, &module.0);
-
-        let buffers = desc
-            .vertex
-            .buffers
-            .iter()
-            .map(|vbuf| {
-                let mapped_attributes = vbuf
-                    .attributes
-                    .iter()
-                    .map(|attr| {
-                        web_sys::GpuVertexAttribute::new(
-                            map_vertex_format(attr.format),
-                            attr.offset as f64,
-                            attr.shader_location,
-                        )
-                    })
-                    .collect::<js_sys::Array>();
-
-                let mut mapped_vbuf = web_sys::GpuVertexBufferLayout::new(
-                    vbuf.array_stride as f64,
-                    &mapped_attributes,
-                );
-                mapped_vbuf.step_mode(map_vertex_step_mode(vbuf.step_mode));
-                mapped_vbuf
-            })
-            .collect::<js_sys::Array>();
-
-        mapped_vertex_state.buffers(&buffers);
-
-        let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
-        let mut mapped_desc = web_sys::GpuRenderPipelineDescriptor::new(
-            &match desc.layout {
-                Some(layout) => {
-                    let layout: &<ContextWebGpu as crate::Context>::PipelineLayoutData =
-                        downcast_ref(layout.data.as_ref());
-                    JsValue::from(&layout.0)
-                }
-                None => auto_layout,
-            },
-            &mapped_vertex_state,
-        );
-
-        if let Some(label) = desc.label {
-            mapped_desc.label(label);
-        }
-
-        if let Some(ref depth_stencil) = desc.depth_stencil {
-            mapped_desc.depth_stencil(&map_depth_stencil_state(depth_stencil));
-        }
-
-        if let Some(ref frag) = desc.fragment {
-            let targets = frag
-                .targets
-                .iter()
-                .map(|target| match target {
-                    Some(target) => {
-                        let mapped_format = map_texture_format(target.format);
-                        let mut mapped_color_state =
-                            web_sys::GpuColorTargetState::new(mapped_format);
-                        if let Some(ref bs) = target.blend {
-                            let alpha = map_blend_component(&bs.alpha);
-                            let color = map_blend_component(&bs.color);
-                            let mapped_blend_state = web_sys::GpuBlendState::new(&alpha, &color);
-                            mapped_color_state.blend(&mapped_blend_state);
-                        }
-                        mapped_color_state.write_mask(target.write_mask.bits());
-                        wasm_bindgen::JsValue::from(mapped_color_state)
-                    }
-                    None => wasm_bindgen::JsValue::null(),
-                })
-                .collect::<js_sys::Array>();
-            let module: &<ContextWebGpu as crate::Context>::ShaderModuleData =
-                downcast_ref(frag.module.data.as_ref());
-            let mapped_fragment_desc =
-                web_sys::GpuFragmentState::new(frag.entry_point, &module.0, &targets);
-            mapped_desc.fragment(&mapped_fragment_desc);
-        }
-
-        let mut mapped_multisample = web_sys::GpuMultisampleState::new();
-        mapped_multisample.count(desc.multisample.count);
-        mapped_multisample.mask(desc.multisample.mask as u32);
-        mapped_multisample.alpha_to_coverage_enabled(desc.multisample.alpha_to_coverage_enabled);
-        mapped_desc.multisample(&mapped_multisample);
+        let mapped_desc = map_render_pipeline_descriptor(desc);
+        create_identified(device_data.0.create_render_pipeline(&mapped_desc))
+    }
 
-        let mapped_primitive = map_primitive_state(&desc.primitive);
-        mapped_desc.primitive(&mapped_primitive);
+    fn device_create_render_pipeline_async(
+        &self,
+        _device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::RenderPipelineDescriptor<'_>,
+    ) -> Self::CreateRenderPipelineFuture {
+        let mapped_desc = map_render_pipeline_descriptor(desc);
+        let promise = device_data.0.create_render_pipeline_async(&mapped_desc);
+        let device = device_data.0.clone();
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(promise),
+            Box::new(move |result| match result {
+                Ok(pipeline) => create_identified(web_sys::GpuRenderPipeline::from(pipeline)),
+                // A rejected promise doesn't reach the error scopes, so create the pipeline
+                // again to report the error the way `create_render_pipeline` does.
+                Err(_) => create_identified(device.create_render_pipeline(&mapped_desc)),
+            }),
+        )
+    }
 
-        create_identified(device_data.0.create_render_pipeline(&mapped_desc))
+    fn device_create_mesh_pipeline(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::MeshPipelineDescriptor<'_>,
+    ) -> (Self::RenderPipelineId, Self::RenderPipelineData) {
+        panic!("Web backend does not support mesh shaders")
     }
 
     fn device_create_compute_pipeline(
@@ -1787,28 +1998,38 @@ impl crate::context::Context for ContextWebGpu {
         device_data: &Self::DeviceData,
         desc: &crate::ComputePipelineDescriptor<'_>,
     ) -> (Self::ComputePipelineId, Self::ComputePipelineData) {
-        let shader_module: &<ContextWebGpu as crate::Context>::ShaderModuleData =
-            downcast_ref(desc.module.data.as_ref());
-        let mapped_compute_stage =
-            web_sys::GpuProgrammableStage::new(desc.entry_point, &shader_module.0);
-        let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
-        let mut mapped_desc = web_sys::GpuComputePipelineDescriptor::new(
-            &match desc.layout {
-                Some(layout) => {
-                    let layout: &<ContextWebGpu as crate::Context>::PipelineLayoutData =
-                        downcast_ref(layout.data.as_ref());
-                    JsValue::from(&layout.0)
-                }
-                None => auto_layout,
-            },
-            &mapped_compute_stage,
-        );
-        if let Some(label) = desc.label {
-            mapped_desc.label(label);
-        }
+        let mapped_desc = map_compute_pipeline_descriptor(desc);
         create_identified(device_data.0.create_compute_pipeline(&mapped_desc))
     }
 
+    fn device_create_compute_pipeline_async(
+        &self,
+        _device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::ComputePipelineDescriptor<'_>,
+    ) -> Self::CreateComputePipelineFuture {
+        let mapped_desc = map_compute_pipeline_descriptor(desc);
+        let promise = device_data.0.create_compute_pipeline_async(&mapped_desc);
+        let device = device_data.0.clone();
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(promise),
+            Box::new(move |result| match result {
+                Ok(pipeline) => create_identified(web_sys::GpuComputePipeline::from(pipeline)),
+                // A rejected promise doesn't reach the error scopes, so create the pipeline
+                // again to report the error the way `create_compute_pipeline` does.
+                Err(_) => create_identified(device.create_compute_pipeline(&mapped_desc)),
+            }),
+        )
+    }
+
+    unsafe fn device_create_pipeline_cache(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> (Self::PipelineCacheId, Self::PipelineCacheData) {
+        (Unused, ())
+    }
     fn device_create_buffer(
         &self,
         _device: &Self::DeviceId,
@@ -1897,6 +2118,103 @@ impl crate::context::Context for ContextWebGpu {
         create_identified(device_data.0.create_query_set(&mapped_desc))
     }
 
+    fn device_create_tile_pool(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::TilePoolDescriptor<'_>,
+    ) -> (Self::TilePoolId, Self::TilePoolData) {
+        panic!("TILED_TEXTURE feature must be enabled to call create_tile_pool")
+    }
+
+    fn device_create_memory_heap(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::MemoryHeapDescriptor<'_>,
+    ) -> (Self::MemoryHeapId, Self::MemoryHeapData) {
+        panic!("MEMORY_HEAPS feature must be enabled to call create_memory_heap")
+    }
+
+    fn device_create_buffer_in_heap(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::BufferDescriptor<'_>,
+        _heap: &Self::MemoryHeapId,
+        _heap_data: &Self::MemoryHeapData,
+        _offset: wgt::BufferAddress,
+    ) -> (Self::BufferId, Self::BufferData) {
+        panic!("MEMORY_HEAPS feature must be enabled to call create_buffer_in_heap")
+    }
+
+    fn device_create_texture_in_heap(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::TextureDescriptor<'_>,
+        _heap: &Self::MemoryHeapId,
+        _heap_data: &Self::MemoryHeapData,
+        _offset: wgt::BufferAddress,
+    ) -> (Self::TextureId, Self::TextureData) {
+        panic!("MEMORY_HEAPS feature must be enabled to call create_texture_in_heap")
+    }
+
+    fn device_create_external_fence(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::ExternalFenceDescriptor<'_>,
+    ) -> (Self::ExternalFenceId, Self::ExternalFenceData) {
+        panic!("EXTERNAL_FENCE feature must be enabled to call create_external_fence")
+    }
+
+    fn device_create_external_buffer(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::BufferDescriptor<'_>,
+    ) -> (Self::BufferId, Self::BufferData) {
+        panic!("EXTERNAL_MEMORY feature must be enabled to call create_external_buffer")
+    }
+
+    fn device_buffer_memory_requirements(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::BufferDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        panic!("MEMORY_HEAPS feature must be enabled to call buffer_memory_requirements")
+    }
+
+    fn device_texture_memory_requirements(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::TextureDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        panic!("MEMORY_HEAPS feature must be enabled to call texture_memory_requirements")
+    }
+
+    fn device_create_blas(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::CreateBlasDescriptor<'_>,
+        _sizes: wgt::BlasGeometrySizeDescriptors,
+    ) -> (Self::BlasId, Option<u64>, Self::BlasData) {
+        panic!("Web backend does not support ray tracing")
+    }
+
+    fn device_create_tlas(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::CreateTlasDescriptor<'_>,
+    ) -> (Self::TlasId, Self::TlasData) {
+        panic!("Web backend does not support ray tracing")
+    }
+
     fn device_create_command_encoder(
         &self,
         _device: &Self::DeviceId,
@@ -1960,6 +2278,14 @@ impl crate::context::Context for ContextWebGpu {
         // with a callback.
     }
 
+    fn device_mark_lost_by_driver(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+    ) {
+        // Browsers don't expose the errors of their drivers.
+    }
+
     fn queue_drop(&self, _queue: &Self::QueueId, _queue_data: &Self::QueueData) {
         // Queue is dropped automatically
     }
@@ -1973,6 +2299,53 @@ impl crate::context::Context for ContextWebGpu {
         unimplemented!();
     }
 
+    fn device_lost(
+        &self,
+        _device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+    ) -> Self::DeviceLostFuture {
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(device_data.0.lost()),
+            future_device_lost,
+        )
+    }
+
+    fn device_set_debug_printf_callback(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _debug_printf_callback: crate::context::DebugPrintfCallback,
+    ) {
+        // SHADER_DEBUG_PRINTF is never supported on WebGPU.
+    }
+
+    fn device_set_watchdog(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _watchdog: Option<(std::time::Duration, crate::context::WatchdogCallback)>,
+    ) {
+        log::warn!("Device watchdogs are not supported on WebGPU");
+    }
+
+    fn device_set_automatic_polling(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _enabled: bool,
+    ) {
+        // Device is polled automatically
+    }
+
+    fn device_set_command_disassembly(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _enabled: bool,
+    ) {
+        log::warn!("Command buffer disassembly is not supported on WebGPU");
+    }
+
     fn device_poll(
         &self,
         _device: &Self::DeviceId,
@@ -1983,6 +2356,25 @@ impl crate::context::Context for ContextWebGpu {
         crate::MaintainResult::SubmissionQueueEmpty
     }
 
+    fn device_poll_until(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _submission_index: &Self::SubmissionIndexData,
+        _timeout: std::time::Duration,
+    ) -> wgt::PollStatus {
+        // Device is polled automatically
+        wgt::PollStatus::Complete
+    }
+
+    fn device_additional_queues(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+    ) -> Vec<(Self::QueueId, Self::QueueData)> {
+        Vec::new()
+    }
+
     fn device_on_uncaptured_error(
         &self,
         _device: &Self::DeviceId,
@@ -2009,6 +2401,7 @@ impl crate::context::Context for ContextWebGpu {
         device_data.0.push_error_scope(match filter {
             crate::ErrorFilter::OutOfMemory => web_sys::GpuErrorFilter::OutOfMemory,
             crate::ErrorFilter::Validation => web_sys::GpuErrorFilter::Validation,
+            crate::ErrorFilter::Internal => web_sys::GpuErrorFilter::Internal,
         });
     }
 
@@ -2062,6 +2455,25 @@ impl crate::context::Context for ContextWebGpu {
         buffer_data.0.mapping.borrow_mut().mapped_buffer = None;
     }
 
+    fn buffer_unmap_range(
+        &self,
+        _buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        _range: Range<wgt::BufferAddress>,
+    ) {
+        // WebGPU maps a single range at a time, which is the one unmapped.
+        buffer_data.0.buffer.unmap();
+        buffer_data.0.mapping.borrow_mut().mapped_buffer = None;
+    }
+
+    fn buffer_device_address(
+        &self,
+        _buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+    ) -> wgt::BufferAddress {
+        panic!("DEVICE_GENERATED_COMMANDS feature must be enabled to call device_address")
+    }
+
     fn texture_create_view(
         &self,
         _texture: &Self::TextureId,
@@ -2130,6 +2542,34 @@ impl crate::context::Context for ContextWebGpu {
         // Dropped automatically
     }
 
+    fn tile_pool_drop(&self, _tile_pool: &Self::TilePoolId, _tile_pool_data: &Self::TilePoolData) {
+        // Can't be created
+    }
+
+    fn memory_heap_drop(
+        &self,
+        _memory_heap: &Self::MemoryHeapId,
+        _memory_heap_data: &Self::MemoryHeapData,
+    ) {
+        // Can't be created
+    }
+
+    fn external_fence_drop(
+        &self,
+        _external_fence: &Self::ExternalFenceId,
+        _external_fence_data: &Self::ExternalFenceData,
+    ) {
+        // Can't be created
+    }
+
+    fn blas_drop(&self, _blas: &Self::BlasId, _blas_data: &Self::BlasData) {
+        // Can't be created
+    }
+
+    fn tlas_drop(&self, _tlas: &Self::TlasId, _tlas_data: &Self::TlasData) {
+        // Can't be created
+    }
+
     fn bind_group_drop(
         &self,
         _bind_group: &Self::BindGroupId,
@@ -2178,6 +2618,14 @@ impl crate::context::Context for ContextWebGpu {
         // Dropped automatically
     }
 
+    fn command_buffer_disassembly(
+        &self,
+        _command_buffer: &Self::CommandBufferId,
+        _command_buffer_data: &Self::CommandBufferData,
+    ) -> Option<String> {
+        None
+    }
+
     fn render_bundle_drop(
         &self,
         _render_bundle: &Self::RenderBundleId,
@@ -2202,6 +2650,22 @@ impl crate::context::Context for ContextWebGpu {
         // Dropped automatically
     }
 
+    fn pipeline_cache_drop(
+        &self,
+        _cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) {
+        // Dropped automatically
+    }
+
+    fn pipeline_cache_get_data(
+        &self,
+        _cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) -> Option<Vec<u8>> {
+        None
+    }
+
     fn compute_pipeline_get_bind_group_layout(
         &self,
         _pipeline: &Self::ComputePipelineId,
@@ -2220,6 +2684,15 @@ impl crate::context::Context for ContextWebGpu {
         create_identified(pipeline_data.0.get_bind_group_layout(index))
     }
 
+    fn bind_group_layout_entries(
+        &self,
+        _bind_group_layout: &Self::BindGroupLayoutId,
+        _bind_group_layout_data: &Self::BindGroupLayoutData,
+    ) -> Vec<wgt::BindGroupLayoutEntry> {
+        // The browser doesn't expose the entries of a layout.
+        Vec::new()
+    }
+
     fn command_encoder_copy_buffer_to_buffer(
         &self,
         _encoder: &Self::CommandEncoderId,
@@ -2294,6 +2767,18 @@ impl crate::context::Context for ContextWebGpu {
             )
     }
 
+    fn command_encoder_resolve_texture(
+        &self,
+        _encoder: &Self::CommandEncoderId,
+        _encoder_data: &Self::CommandEncoderData,
+        _source: crate::ImageCopyTexture<'_>,
+        _destination: crate::ImageCopyTexture<'_>,
+        _copy_size: wgt::Extent3d,
+        _mode: wgt::ResolveMode,
+    ) {
+        panic!("TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES feature must be enabled to call resolve_texture")
+    }
+
     fn command_encoder_begin_compute_pass(
         &self,
         _encoder: &Self::CommandEncoderId,
@@ -2492,6 +2977,14 @@ impl crate::context::Context for ContextWebGpu {
         // encoder.pop_debug_group();
     }
 
+    fn command_encoder_aliasing_barrier(
+        &self,
+        _encoder: &Self::CommandEncoderId,
+        _encoder_data: &Self::CommandEncoderData,
+    ) {
+        panic!("MEMORY_HEAPS feature must be enabled to call aliasing_barrier")
+    }
+
     fn command_encoder_write_timestamp(
         &self,
         _encoder: &Self::CommandEncoderId,
@@ -2526,6 +3019,16 @@ impl crate::context::Context for ContextWebGpu {
         );
     }
 
+    fn command_encoder_build_acceleration_structures_unsafe_tlas(
+        &self,
+        _encoder: &Self::CommandEncoderId,
+        _encoder_data: &Self::CommandEncoderData,
+        _blas: &[crate::BlasBuildEntry<'_>],
+        _tlas: &[crate::TlasBuildEntry<'_>],
+    ) {
+        panic!("Web backend does not support ray tracing")
+    }
+
     fn render_bundle_encoder_finish(
         &self,
         _encoder: Self::RenderBundleEncoderId,
@@ -2643,6 +3146,41 @@ impl crate::context::Context for ContextWebGpu {
         )
     }
 
+    fn queue_write_buffer_regions(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        regions: &[(wgt::BufferAddress, &[u8])],
+    ) {
+        // WebGPU has no batched write, so every region is written on its own.
+        for &(offset, data) in regions {
+            self.queue_write_buffer(queue, queue_data, buffer, buffer_data, offset, data);
+        }
+    }
+
+    fn queue_write_staging_buffer_regions(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        regions: &[(wgt::BufferAddress, wgt::BufferSize)],
+        staging_buffer: &dyn QueueWriteBuffer,
+    ) {
+        let mut staging_buffer = staging_buffer
+            .as_any()
+            .downcast_ref::<WebQueueWriteBuffer>()
+            .unwrap()
+            .slice();
+        for &(offset, size) in regions {
+            let (data, rest) = staging_buffer.split_at(size.get() as usize);
+            self.queue_write_buffer(queue, queue_data, buffer, buffer_data, offset, data);
+            staging_buffer = rest;
+        }
+    }
+
     fn queue_write_texture(
         &self,
         _queue: &Self::QueueId,
@@ -2720,6 +3258,55 @@ impl crate::context::Context for ContextWebGpu {
         1.0
     }
 
+    fn queue_get_timestamp_calibration(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+    ) -> Option<wgt::TimestampCalibration> {
+        None
+    }
+
+    fn queue_bind_texture_pages(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        _texture: &crate::Texture,
+        _bindings: &[crate::TextureTileBinding<'_>],
+    ) {
+        panic!("TILED_TEXTURE feature must be enabled to call bind_texture_pages")
+    }
+
+    fn queue_wait_for_submission(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        _submission_index: &Self::SubmissionIndexData,
+    ) {
+        // There is a single queue, so all work is already ordered.
+    }
+
+    fn queue_wait_for_external_fence(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        _external_fence: &Self::ExternalFenceId,
+        _external_fence_data: &Self::ExternalFenceData,
+        _value: u64,
+    ) {
+        panic!("EXTERNAL_FENCE feature must be enabled to call wait_for_external_fence")
+    }
+
+    fn queue_signal_external_fence(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        _external_fence: &Self::ExternalFenceId,
+        _external_fence_data: &Self::ExternalFenceData,
+        _value: u64,
+    ) {
+        panic!("EXTERNAL_FENCE feature must be enabled to call signal_external_fence")
+    }
+
     fn queue_on_submitted_work_done(
         &self,
         _queue: &Self::QueueId,
@@ -3085,6 +3672,25 @@ impl crate::context::Context for ContextWebGpu {
         panic!("MULTI_DRAW_INDIRECT_COUNT feature must be enabled to call multi_draw_indexed_indirect_count")
     }
 
+    fn render_bundle_encoder_begin_occlusion_query(
+        &self,
+        _encoder: &mut Self::RenderBundleEncoderId,
+        _encoder_data: &mut Self::RenderBundleEncoderData,
+        _query_index: u32,
+    ) {
+        panic!(
+            "RENDER_BUNDLE_OCCLUSION_QUERY feature must be enabled to call begin_occlusion_query"
+        )
+    }
+
+    fn render_bundle_encoder_end_occlusion_query(
+        &self,
+        _encoder: &mut Self::RenderBundleEncoderId,
+        _encoder_data: &mut Self::RenderBundleEncoderData,
+    ) {
+        panic!("RENDER_BUNDLE_OCCLUSION_QUERY feature must be enabled to call end_occlusion_query")
+    }
+
     fn render_pass_set_pipeline(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -3119,6 +3725,16 @@ impl crate::context::Context for ContextWebGpu {
         }
     }
 
+    fn render_pass_set_bind_group_immediate(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _index: u32,
+        _entries: &[crate::BindGroupEntry<'_>],
+    ) {
+        panic!("Immediate bind groups are not supported on the WebGPU backend")
+    }
+
     fn render_pass_set_index_buffer(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -3250,6 +3866,28 @@ impl crate::context::Context for ContextWebGpu {
             .draw_indexed_indirect_with_f64(&indirect_buffer_data.0.buffer, indirect_offset as f64);
     }
 
+    fn render_pass_draw_mesh_tasks(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _group_count_x: u32,
+        _group_count_y: u32,
+        _group_count_z: u32,
+    ) {
+        panic!("Web backend does not support mesh shaders")
+    }
+
+    fn render_pass_draw_mesh_tasks_indirect(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _indirect_buffer: &Self::BufferId,
+        _indirect_buffer_data: &Self::BufferData,
+        _indirect_offset: wgt::BufferAddress,
+    ) {
+        panic!("Web backend does not support mesh shaders")
+    }
+
     fn render_pass_multi_draw_indirect(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -3306,6 +3944,35 @@ impl crate::context::Context for ContextWebGpu {
         panic!("MULTI_DRAW_INDIRECT_COUNT feature must be enabled to call multi_draw_indexed_indirect_count")
     }
 
+    fn render_pass_execute_indirect(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _arguments: &[wgt::IndirectArgument],
+        _indirect_buffer: &Self::BufferId,
+        _indirect_buffer_data: &Self::BufferData,
+        _indirect_offset: wgt::BufferAddress,
+        _max_count: u32,
+    ) {
+        panic!("DEVICE_GENERATED_COMMANDS feature must be enabled to call execute_indirect")
+    }
+
+    fn render_pass_execute_indirect_count(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _arguments: &[wgt::IndirectArgument],
+        _indirect_buffer: &Self::BufferId,
+        _indirect_buffer_data: &Self::BufferData,
+        _indirect_offset: wgt::BufferAddress,
+        _count_buffer: &Self::BufferId,
+        _count_buffer_data: &Self::BufferData,
+        _count_buffer_offset: wgt::BufferAddress,
+        _max_count: u32,
+    ) {
+        panic!("DEVICE_GENERATED_COMMANDS feature must be enabled to call execute_indirect_count")
+    }
+
     fn render_pass_set_blend_constant(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -3354,6 +4021,15 @@ impl crate::context::Context for ContextWebGpu {
         pass_data.0.set_stencil_reference(reference);
     }
 
+    fn render_pass_set_shading_rate(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _rate: wgt::ShadingRate,
+    ) {
+        panic!("SHADING_RATE feature must be enabled to call render_pass_set_shading_rate")
+    }
+
     fn render_pass_insert_debug_marker(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -3383,6 +4059,22 @@ impl crate::context::Context for ContextWebGpu {
         // self.0.pop_debug_group();
     }
 
+    fn render_pass_resume(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+    ) {
+        panic!("Resuming render passes is not supported on the WebGPU backend");
+    }
+
+    fn render_pass_suspend(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+    ) {
+        panic!("Suspending render passes is not supported on the WebGPU backend");
+    }
+
     fn render_pass_write_timestamp(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -3430,6 +4122,53 @@ impl crate::context::Context for ContextWebGpu {
         // Not available in gecko yet
     }
 
+    fn render_pass_begin_conditional_rendering(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+        _offset: wgt::BufferAddress,
+    ) {
+        // WebGPU has no conditional rendering, nothing is skipped.
+    }
+
+    fn render_pass_end_conditional_rendering(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+    ) {
+    }
+
+    fn render_pass_set_vertex_capture_buffer(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _slot: u32,
+        _buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+        _offset: wgt::BufferAddress,
+        _size: Option<wgt::BufferSize>,
+    ) {
+        panic!("VERTEX_CAPTURE feature must be enabled to call begin_capture")
+    }
+
+    fn render_pass_begin_vertex_capture(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+    ) {
+        panic!("VERTEX_CAPTURE feature must be enabled to call begin_capture")
+    }
+
+    fn render_pass_end_vertex_capture(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+    ) {
+        panic!("VERTEX_CAPTURE feature must be enabled to call end_capture")
+    }
+
     fn render_pass_execute_bundles(
         &self,
         _pass: &mut Self::RenderPassId,
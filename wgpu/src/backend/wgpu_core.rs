@@ -1,12 +1,13 @@
 use crate::{
     context::{ObjectId, Unused},
-    AdapterInfo, BindGroupDescriptor, BindGroupLayoutDescriptor, BindingResource, BufferBinding,
-    BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor, ComputePipelineDescriptor,
-    DownlevelCapabilities, Features, Label, Limits, LoadOp, MapMode, Operations,
-    PipelineLayoutDescriptor, RenderBundleEncoderDescriptor, RenderPipelineDescriptor,
-    SamplerDescriptor, ShaderModuleDescriptor, ShaderModuleDescriptorSpirV, ShaderSource, StoreOp,
-    SurfaceStatus, SurfaceTargetUnsafe, TextureDescriptor, TextureViewDescriptor,
-    UncapturedErrorHandler,
+    AdapterInfo, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, BindingResource,
+    BufferBinding, BufferDescriptor, CommandEncoderDescriptor, CompilationInfo, CompilationMessage,
+    CompilationMessageType, ComputePassDescriptor, ComputePipelineDescriptor,
+    DownlevelCapabilities, Features, Label, Limits, LoadOp, MapMode, MeshPipelineDescriptor,
+    Operations, PipelineCacheDescriptor, PipelineLayoutDescriptor, RenderBundleEncoderDescriptor,
+    RenderPipelineDescriptor, SamplerDescriptor, ShaderModuleDescriptor,
+    ShaderModuleDescriptorSpirV, ShaderSource, StoreOp, SurfaceStatus, SurfaceTargetUnsafe,
+    TextureDescriptor, TextureViewDescriptor, UncapturedErrorHandler,
 };
 
 use arrayvec::ArrayVec;
@@ -15,21 +16,24 @@ use smallvec::SmallVec;
 use std::{
     any::Any,
     borrow::Cow::{Borrowed, Owned},
+    cell::RefCell,
     error::Error,
     fmt,
-    future::{ready, Ready},
+    future::{ready, Future, Ready},
     ops::Range,
+    pin::Pin,
     slice,
     sync::Arc,
+    task::{self, Poll, Waker},
 };
 use wgc::command::{bundle_ffi::*, compute_ffi::*, render_ffi::*};
 use wgc::device::DeviceLostClosure;
 use wgc::id::TypedId;
-use wgt::WasmNotSendSync;
+use wgt::{WasmNotSend, WasmNotSendSync};
 
 const LABEL: &str = "label";
 
-pub struct ContextWgpuCore(wgc::global::Global<wgc::identity::IdentityManagerFactory>);
+pub struct ContextWgpuCore(Arc<wgc::global::Global<wgc::identity::IdentityManagerFactory>>);
 
 impl Drop for ContextWgpuCore {
     fn drop(&mut self) {
@@ -47,13 +51,13 @@ impl fmt::Debug for ContextWgpuCore {
 
 impl ContextWgpuCore {
     pub unsafe fn from_hal_instance<A: wgc::hal_api::HalApi>(hal_instance: A::Instance) -> Self {
-        Self(unsafe {
+        Self(Arc::new(unsafe {
             wgc::global::Global::from_hal_instance::<A>(
                 "wgpu",
                 wgc::identity::IdentityManagerFactory,
                 hal_instance,
             )
-        })
+        }))
     }
 
     /// # Safety
@@ -64,9 +68,9 @@ impl ContextWgpuCore {
     }
 
     pub unsafe fn from_core_instance(core_instance: wgc::instance::Instance) -> Self {
-        Self(unsafe {
+        Self(Arc::new(unsafe {
             wgc::global::Global::from_instance(wgc::identity::IdentityManagerFactory, core_instance)
-        })
+        }))
     }
 
     pub(crate) fn global(&self) -> &wgc::global::Global<wgc::identity::IdentityManagerFactory> {
@@ -120,11 +124,15 @@ impl ContextWgpuCore {
         if let Some(err) = error {
             self.handle_error_fatal(err, "Adapter::create_device_from_hal");
         }
+        let (lost, device_lost_closure) = device_lost_state();
+        self.0
+            .device_set_device_lost_closure::<A>(device_id, device_lost_closure);
         let error_sink = Arc::new(Mutex::new(ErrorSinkRaw::new()));
         let device = Device {
             id: device_id,
             error_sink: error_sink.clone(),
             features: desc.required_features,
+            lost,
         };
         let queue = Queue {
             id: queue_id,
@@ -159,6 +167,60 @@ impl ContextWgpuCore {
         }
     }
 
+    #[cfg(vulkan)]
+    pub unsafe fn create_texture_from_vulkan_image(
+        &self,
+        image: u64,
+        device: &Device,
+        desc: &TextureDescriptor<'_>,
+    ) -> Texture {
+        let descriptor = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = unsafe {
+            self.0
+                .device_create_texture_from_vulkan_image(device.id, image, &descriptor, ())
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Texture::from_raw_vulkan_image",
+            );
+        }
+        Texture {
+            id,
+            error_sink: Arc::clone(&device.error_sink),
+        }
+    }
+
+    #[cfg(dx12)]
+    pub unsafe fn create_texture_from_d3d12_resource(
+        &self,
+        resource: *mut std::ffi::c_void,
+        device: &Device,
+        desc: &TextureDescriptor<'_>,
+    ) -> Texture {
+        let descriptor = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = unsafe {
+            self.0
+                .device_create_texture_from_d3d12_resource(device.id, resource, &descriptor, ())
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Texture::from_raw_d3d12_resource",
+            );
+        }
+        Texture {
+            id,
+            error_sink: Arc::clone(&device.error_sink),
+        }
+    }
+
     pub unsafe fn create_buffer_from_hal<A: wgc::hal_api::HalApi>(
         &self,
         hal_buffer: A::Buffer,
@@ -248,12 +310,19 @@ impl ContextWgpuCore {
         let mut sink = sink_mutex.lock();
         let mut source_opt: Option<&(dyn Error + 'static)> = Some(&error);
         while let Some(source) = source_opt {
-            if let Some(wgc::device::DeviceError::OutOfMemory) =
-                source.downcast_ref::<wgc::device::DeviceError>()
-            {
-                return sink.handle_error(crate::Error::OutOfMemory {
-                    source: Box::new(error),
-                });
+            match error_filter(source) {
+                Some(crate::ErrorFilter::OutOfMemory) => {
+                    return sink.handle_error(crate::Error::OutOfMemory {
+                        source: Box::new(error),
+                    });
+                }
+                Some(crate::ErrorFilter::Internal) => {
+                    return sink.handle_error(crate::Error::Internal {
+                        description: self.format_error(&error),
+                        source: Box::new(error),
+                    });
+                }
+                Some(crate::ErrorFilter::Validation) | None => {}
             }
             source_opt = source.source();
         }
@@ -301,6 +370,152 @@ impl ContextWgpuCore {
 
         format!("Validation Error\n\nCaused by:\n{}", err_descs.join(""))
     }
+
+    fn create_render_pipeline(
+        &self,
+        device: &wgc::id::DeviceId,
+        error_sink: &ErrorSink,
+        descriptor: &wgc::pipeline::RenderPipelineDescriptor<'_>,
+        string: &'static str,
+    ) -> wgc::id::RenderPipelineId {
+        let implicit_pipeline_ids = match descriptor.layout {
+            Some(_) => None,
+            None => Some(wgc::device::ImplicitPipelineIds {
+                root_id: (),
+                group_ids: &[(); wgc::MAX_BIND_GROUPS],
+            }),
+        };
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_render_pipeline(
+            *device,
+            descriptor,
+            (),
+            implicit_pipeline_ids
+        ));
+        if let Some(cause) = error {
+            if let wgc::pipeline::CreateRenderPipelineError::Internal { stage, ref error } = cause {
+                log::error!("Shader translation error for stage {:?}: {}", stage, error);
+                log::error!("Please report it to https://github.com/gfx-rs/wgpu");
+            }
+            self.handle_error(
+                error_sink,
+                cause,
+                LABEL,
+                descriptor.label.as_deref(),
+                string,
+            );
+        }
+        id
+    }
+
+    fn create_compute_pipeline(
+        &self,
+        device: &wgc::id::DeviceId,
+        error_sink: &ErrorSink,
+        descriptor: &wgc::pipeline::ComputePipelineDescriptor<'_>,
+        string: &'static str,
+    ) -> wgc::id::ComputePipelineId {
+        let implicit_pipeline_ids = match descriptor.layout {
+            Some(_) => None,
+            None => Some(wgc::device::ImplicitPipelineIds {
+                root_id: (),
+                group_ids: &[(); wgc::MAX_BIND_GROUPS],
+            }),
+        };
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_compute_pipeline(
+            *device,
+            descriptor,
+            (),
+            implicit_pipeline_ids
+        ));
+        if let Some(cause) = error {
+            if let wgc::pipeline::CreateComputePipelineError::Internal(ref error) = cause {
+                log::error!(
+                    "Shader translation error for stage {:?}: {}",
+                    wgt::ShaderStages::COMPUTE,
+                    error
+                );
+                log::error!("Please report it to https://github.com/gfx-rs/wgpu");
+            }
+            self.handle_error(
+                error_sink,
+                cause,
+                LABEL,
+                descriptor.label.as_deref(),
+                string,
+            );
+        }
+        id
+    }
+}
+
+/// Maps `desc` to a descriptor that doesn't borrow from it, so that it can be moved to a
+/// pipeline worker thread.
+fn map_render_pipeline_descriptor_owned(
+    desc: &RenderPipelineDescriptor<'_>,
+) -> wgc::pipeline::RenderPipelineDescriptor<'static> {
+    use wgc::pipeline as pipe;
+
+    pipe::RenderPipelineDescriptor {
+        label: desc.label.map(|label| Owned(label.to_owned())),
+        layout: desc.layout.map(|l| l.id.into()),
+        vertex: pipe::VertexState {
+            stage: pipe::ProgrammableStageDescriptor {
+                module: desc.vertex.module.id.into(),
+                entry_point: Owned(desc.vertex.entry_point.to_owned()),
+            },
+            buffers: Owned(
+                desc.vertex
+                    .buffers
+                    .iter()
+                    .map(|vbuf| pipe::VertexBufferLayout {
+                        array_stride: vbuf.array_stride,
+                        step_mode: vbuf.step_mode,
+                        attributes: Owned(vbuf.attributes.to_vec()),
+                    })
+                    .collect(),
+            ),
+            capture_buffers: Owned(
+                desc.vertex
+                    .capture_buffers
+                    .iter()
+                    .map(|cbuf| pipe::VertexCaptureBufferLayout {
+                        array_stride: cbuf.array_stride,
+                        attributes: Owned(cbuf.attributes.to_vec()),
+                    })
+                    .collect(),
+            ),
+        },
+        primitive: desc.primitive,
+        depth_stencil: desc.depth_stencil.clone(),
+        multisample: desc.multisample,
+        fragment: desc.fragment.as_ref().map(|frag| pipe::FragmentState {
+            stage: pipe::ProgrammableStageDescriptor {
+                module: frag.module.id.into(),
+                entry_point: Owned(frag.entry_point.to_owned()),
+            },
+            targets: Owned(frag.targets.to_vec()),
+        }),
+        multiview: desc.multiview,
+        cache: desc.cache.map(|c| c.id.into()),
+    }
+}
+
+/// Maps `desc` to a descriptor that doesn't borrow from it, so that it can be moved to a
+/// pipeline worker thread.
+fn map_compute_pipeline_descriptor_owned(
+    desc: &ComputePipelineDescriptor<'_>,
+) -> wgc::pipeline::ComputePipelineDescriptor<'static> {
+    use wgc::pipeline as pipe;
+
+    pipe::ComputePipelineDescriptor {
+        label: desc.label.map(|label| Owned(label.to_owned())),
+        layout: desc.layout.map(|l| l.id.into()),
+        stage: pipe::ProgrammableStageDescriptor {
+            module: desc.module.id.into(),
+            entry_point: Owned(desc.entry_point.to_owned()),
+        },
+        cache: desc.cache.map(|c| c.id.into()),
+    }
 }
 
 fn map_buffer_copy_view(view: crate::ImageCopyBuffer<'_>) -> wgc::command::ImageCopyBuffer {
@@ -336,6 +551,41 @@ fn map_texture_tagged_copy_view(
     }
 }
 
+fn map_bind_group_entry_owned(
+    entry: &BindGroupEntry<'_>,
+) -> wgc::binding_model::BindGroupEntry<'static> {
+    use wgc::binding_model as bm;
+
+    let map_buffer_binding = |binding: &BufferBinding<'_>| bm::BufferBinding {
+        buffer_id: binding.buffer.id.into(),
+        offset: binding.offset,
+        size: binding.size,
+    };
+
+    bm::BindGroupEntry {
+        binding: entry.binding,
+        resource: match entry.resource {
+            BindingResource::Buffer(ref binding) => {
+                bm::BindingResource::Buffer(map_buffer_binding(binding))
+            }
+            BindingResource::BufferArray(array) => bm::BindingResource::BufferArray(Owned(
+                array.iter().map(map_buffer_binding).collect(),
+            )),
+            BindingResource::Sampler(sampler) => bm::BindingResource::Sampler(sampler.id.into()),
+            BindingResource::SamplerArray(array) => bm::BindingResource::SamplerArray(Owned(
+                array.iter().map(|sampler| sampler.id.into()).collect(),
+            )),
+            BindingResource::TextureView(view) => bm::BindingResource::TextureView(view.id.into()),
+            BindingResource::TextureViewArray(array) => bm::BindingResource::TextureViewArray(
+                Owned(array.iter().map(|view| view.id.into()).collect()),
+            ),
+            BindingResource::AccelerationStructure(tlas) => {
+                bm::BindingResource::AccelerationStructure(tlas.id.into())
+            }
+        },
+    }
+}
+
 fn map_store_op(op: StoreOp) -> wgc::command::StoreOp {
     match op {
         StoreOp::Store => wgc::command::StoreOp::Store,
@@ -395,6 +645,7 @@ pub struct Device {
     id: wgc::id::DeviceId,
     error_sink: ErrorSink,
     features: Features,
+    lost: Arc<Mutex<DeviceLostState>>,
 }
 
 impl Device {
@@ -405,6 +656,67 @@ impl Device {
     }
 }
 
+/// What [`crate::Device::lost`] resolves to, once wgpu-core calls the device
+/// lost closure of the device.
+#[derive(Default)]
+struct DeviceLostState {
+    info: Option<(wgt::DeviceLostReason, String)>,
+    wakers: Vec<Waker>,
+    callback: Option<crate::context::DeviceLostCallback>,
+}
+
+impl fmt::Debug for DeviceLostState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceLostState")
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creates the state of a new device, along with the device lost closure to
+/// register on it.
+fn device_lost_state() -> (Arc<Mutex<DeviceLostState>>, DeviceLostClosure) {
+    let lost = Arc::new(Mutex::new(DeviceLostState::default()));
+    let shared = lost.clone();
+    let closure = DeviceLostClosure::from_rust(Box::new(move |reason, message| {
+        let mut lost = shared.lock();
+        lost.info = Some((reason, message.clone()));
+        let wakers = std::mem::take(&mut lost.wakers);
+        let callback = lost.callback.take();
+        // It's important to not hold the lock while calling the callback.
+        drop(lost);
+        for waker in wakers {
+            waker.wake();
+        }
+        if let Some(callback) = callback {
+            callback(reason, message);
+        }
+    }));
+    (lost, closure)
+}
+
+#[derive(Debug)]
+pub struct DeviceLostFuture {
+    lost: Arc<Mutex<DeviceLostState>>,
+}
+
+impl Future for DeviceLostFuture {
+    type Output = (wgt::DeviceLostReason, String);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut lost = self.lost.lock();
+        match lost.info {
+            Some((reason, ref message)) => Poll::Ready((reason, message.clone())),
+            None => {
+                if !lost.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    lost.wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Buffer {
     error_sink: ErrorSink,
@@ -452,7 +764,7 @@ impl crate::Context for ContextWgpuCore {
     type QueueId = wgc::id::QueueId;
     type QueueData = Queue;
     type ShaderModuleId = wgc::id::ShaderModuleId;
-    type ShaderModuleData = ();
+    type ShaderModuleData = ShaderModule;
     type BindGroupLayoutId = wgc::id::BindGroupLayoutId;
     type BindGroupLayoutData = ();
     type BindGroupId = wgc::id::BindGroupId;
@@ -467,12 +779,24 @@ impl crate::Context for ContextWgpuCore {
     type TextureData = Texture;
     type QuerySetId = wgc::id::QuerySetId;
     type QuerySetData = ();
+    type TilePoolId = wgc::id::TilePoolId;
+    type TilePoolData = ();
+    type MemoryHeapId = wgc::id::MemoryHeapId;
+    type MemoryHeapData = ();
+    type ExternalFenceId = wgc::id::ExternalFenceId;
+    type ExternalFenceData = ();
+    type BlasId = wgc::id::BlasId;
+    type BlasData = ();
+    type TlasId = wgc::id::TlasId;
+    type TlasData = ();
     type PipelineLayoutId = wgc::id::PipelineLayoutId;
     type PipelineLayoutData = ();
     type RenderPipelineId = wgc::id::RenderPipelineId;
     type RenderPipelineData = ();
     type ComputePipelineId = wgc::id::ComputePipelineId;
     type ComputePipelineData = ();
+    type PipelineCacheId = wgc::id::PipelineCacheId;
+    type PipelineCacheData = ();
     type CommandEncoderId = wgc::id::CommandEncoderId;
     type CommandEncoderData = CommandEncoder;
     type ComputePassId = Unused;
@@ -508,13 +832,17 @@ impl crate::Context for ContextWgpuCore {
     >;
 
     type PopErrorScopeFuture = Ready<Option<crate::Error>>;
+    type CompilationInfoFuture = Ready<CompilationInfo>;
+    type DeviceLostFuture = DeviceLostFuture;
+    type CreateRenderPipelineFuture = PipelineFuture<(Self::RenderPipelineId, ())>;
+    type CreateComputePipelineFuture = PipelineFuture<(Self::ComputePipelineId, ())>;
 
     fn init(instance_desc: wgt::InstanceDescriptor) -> Self {
-        Self(wgc::global::Global::new(
+        Self(Arc::new(wgc::global::Global::new(
             "wgpu",
             wgc::identity::IdentityManagerFactory,
             instance_desc,
-        ))
+        )))
     }
 
     unsafe fn instance_create_surface(
@@ -551,6 +879,30 @@ impl crate::Context for ContextWgpuCore {
                 self.0
                     .instance_create_surface_from_swap_chain_panel(swap_chain_panel, ())
             },
+
+            #[cfg(all(
+                vulkan,
+                unix,
+                not(any(target_os = "android", target_os = "ios", target_os = "macos"))
+            ))]
+            SurfaceTargetUnsafe::Drm {
+                fd,
+                plane,
+                connector_id,
+                width,
+                height,
+                refresh_rate,
+            } => unsafe {
+                self.0.instance_create_surface_from_drm(
+                    fd,
+                    plane,
+                    connector_id,
+                    width,
+                    height,
+                    refresh_rate,
+                    (),
+                )?
+            },
         };
 
         Ok((
@@ -594,11 +946,14 @@ impl crate::Context for ContextWgpuCore {
         if let Some(err) = error {
             return ready(Err(err.into()));
         }
+        let (lost, device_lost_closure) = device_lost_state();
+        wgc::gfx_select!(device_id => self.0.device_set_device_lost_closure(device_id, device_lost_closure));
         let error_sink = Arc::new(Mutex::new(ErrorSinkRaw::new()));
         let device = Device {
             id: device_id,
             error_sink: error_sink.clone(),
             features: desc.required_features,
+            lost,
         };
         let queue = Queue {
             id: queue_id,
@@ -614,6 +969,18 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn instance_set_debug_callback(&self, callback: Option<crate::context::DebugCallback>) {
+        self.0.instance_set_debug_callback(callback)
+    }
+
+    fn begin_error_capture(&self) {
+        CAPTURED_ERRORS.with(|captured| captured.borrow_mut().push(None));
+    }
+
+    fn end_error_capture(&self) -> Option<crate::Error> {
+        CAPTURED_ERRORS.with(|captured| captured.borrow_mut().pop().flatten())
+    }
+
     fn adapter_is_surface_supported(
         &self,
         adapter: &Self::AdapterId,
@@ -638,6 +1005,18 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn adapter_explain_feature(
+        &self,
+        adapter: &Self::AdapterId,
+        _adapter_data: &Self::AdapterData,
+        feature: wgt::Features,
+    ) -> wgt::FeatureSupport {
+        match wgc::gfx_select!(*adapter => self.0.adapter_explain_feature(*adapter, feature)) {
+            Ok(support) => support,
+            Err(err) => self.handle_error_fatal(err, "Adapter::explain_feature"),
+        }
+    }
+
     fn adapter_limits(
         &self,
         adapter: &Self::AdapterId,
@@ -695,6 +1074,17 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn adapter_max_additional_queues(
+        &self,
+        adapter: &Self::AdapterId,
+        _adapter_data: &Self::AdapterData,
+    ) -> u32 {
+        match wgc::gfx_select!(*adapter => self.0.adapter_max_additional_queues(*adapter)) {
+            Ok(max) => max,
+            Err(err) => self.handle_error_fatal(err, "Adapter::max_additional_queues"),
+        }
+    }
+
     fn surface_get_capabilities(
         &self,
         surface: &Self::SurfaceId,
@@ -815,6 +1205,17 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn device_memory_architecture(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+    ) -> wgt::MemoryArchitecture {
+        match wgc::gfx_select!(device => self.0.device_memory_architecture(*device)) {
+            Ok(architecture) => architecture,
+            Err(err) => self.handle_error_fatal(err, "Device::memory_architecture"),
+        }
+    }
+
     #[cfg_attr(
         not(any(
             feature = "spirv",
@@ -852,7 +1253,12 @@ impl crate::Context for ContextWgpuCore {
                 stage,
                 defines,
             } => {
-                let options = naga::front::glsl::Options { stage, defines };
+                let options = naga::front::glsl::Options {
+                    stage,
+                    defines,
+                    include_resolver: None,
+                    entry_point: None,
+                };
                 wgc::pipeline::ShaderModuleSource::Glsl(Borrowed(shader), options)
             }
             #[cfg(feature = "wgsl")]
@@ -864,16 +1270,21 @@ impl crate::Context for ContextWgpuCore {
         let (id, error) = wgc::gfx_select!(
             device => self.0.device_create_shader_module(*device, &descriptor, source, ())
         );
-        if let Some(cause) = error {
-            self.handle_error(
-                &device_data.error_sink,
-                cause,
-                LABEL,
-                desc.label,
-                "Device::create_shader_module",
-            );
-        }
-        (id, ())
+        let compilation_info = match error {
+            Some(cause) => {
+                let compilation_info = CompilationInfo::from(&cause);
+                self.handle_error(
+                    &device_data.error_sink,
+                    cause,
+                    LABEL,
+                    desc.label,
+                    "Device::create_shader_module",
+                );
+                compilation_info
+            }
+            None => CompilationInfo::default(),
+        };
+        (id, ShaderModule { compilation_info })
     }
 
     unsafe fn device_create_shader_module_spirv(
@@ -891,16 +1302,29 @@ impl crate::Context for ContextWgpuCore {
         let (id, error) = wgc::gfx_select!(
             device => self.0.device_create_shader_module_spirv(*device, &descriptor, Borrowed(&desc.source), ())
         );
-        if let Some(cause) = error {
-            self.handle_error(
-                &device_data.error_sink,
-                cause,
-                LABEL,
-                desc.label,
-                "Device::create_shader_module_spirv",
-            );
-        }
-        (id, ())
+        let compilation_info = match error {
+            Some(cause) => {
+                let compilation_info = CompilationInfo::from(&cause);
+                self.handle_error(
+                    &device_data.error_sink,
+                    cause,
+                    LABEL,
+                    desc.label,
+                    "Device::create_shader_module_spirv",
+                );
+                compilation_info
+            }
+            None => CompilationInfo::default(),
+        };
+        (id, ShaderModule { compilation_info })
+    }
+
+    fn shader_get_compilation_info(
+        &self,
+        _shader: &Self::ShaderModuleId,
+        shader_data: &Self::ShaderModuleData,
+    ) -> Self::CompilationInfoFuture {
+        ready(shader_data.compilation_info.clone())
     }
 
     fn device_create_bind_group_layout(
@@ -1016,6 +1440,9 @@ impl crate::Context for ContextWgpuCore {
                             &remaining_arrayed_texture_views[array.len()..];
                         bm::BindingResource::TextureViewArray(Owned(views))
                     }
+                    BindingResource::AccelerationStructure(tlas) => {
+                        bm::BindingResource::AccelerationStructure(tlas.id.into())
+                    }
                 },
             })
             .collect::<Vec<_>>();
@@ -1101,14 +1528,16 @@ impl crate::Context for ContextWgpuCore {
                 attributes: Borrowed(vbuf.attributes),
             })
             .collect();
+        let capture_buffers: ArrayVec<_, { wgt::MAX_VERTEX_CAPTURE_BUFFERS as usize }> = desc
+            .vertex
+            .capture_buffers
+            .iter()
+            .map(|cbuf| pipe::VertexCaptureBufferLayout {
+                array_stride: cbuf.array_stride,
+                attributes: Borrowed(cbuf.attributes),
+            })
+            .collect();
 
-        let implicit_pipeline_ids = match desc.layout {
-            Some(_) => None,
-            None => Some(wgc::device::ImplicitPipelineIds {
-                root_id: (),
-                group_ids: &[(); wgc::MAX_BIND_GROUPS],
-            }),
-        };
         let descriptor = pipe::RenderPipelineDescriptor {
             label: desc.label.map(Borrowed),
             layout: desc.layout.map(|l| l.id.into()),
@@ -1118,6 +1547,7 @@ impl crate::Context for ContextWgpuCore {
                     entry_point: Borrowed(desc.vertex.entry_point),
                 },
                 buffers: Borrowed(&vertex_buffers),
+                capture_buffers: Borrowed(&capture_buffers),
             },
             primitive: desc.primitive,
             depth_stencil: desc.depth_stencil.clone(),
@@ -1130,35 +1560,48 @@ impl crate::Context for ContextWgpuCore {
                 targets: Borrowed(frag.targets),
             }),
             multiview: desc.multiview,
+            cache: desc.cache.map(|c| c.id.into()),
         };
 
-        let (id, error) = wgc::gfx_select!(device => self.0.device_create_render_pipeline(
-            *device,
+        let id = self.create_render_pipeline(
+            device,
+            &device_data.error_sink,
             &descriptor,
-            (),
-            implicit_pipeline_ids
-        ));
-        if let Some(cause) = error {
-            if let wgc::pipeline::CreateRenderPipelineError::Internal { stage, ref error } = cause {
-                log::error!("Shader translation error for stage {:?}: {}", stage, error);
-                log::error!("Please report it to https://github.com/gfx-rs/wgpu");
-            }
-            self.handle_error(
-                &device_data.error_sink,
-                cause,
-                LABEL,
-                desc.label,
-                "Device::create_render_pipeline",
-            );
-        }
+            "Device::create_render_pipeline",
+        );
         (id, ())
     }
-    fn device_create_compute_pipeline(
+    fn device_create_render_pipeline_async(
         &self,
         device: &Self::DeviceId,
         device_data: &Self::DeviceData,
-        desc: &ComputePipelineDescriptor<'_>,
-    ) -> (Self::ComputePipelineId, Self::ComputePipelineData) {
+        desc: &RenderPipelineDescriptor<'_>,
+    ) -> Self::CreateRenderPipelineFuture {
+        let context = ContextWgpuCore(Arc::clone(&self.0));
+        let device = *device;
+        let error_sink = Arc::clone(&device_data.error_sink);
+        let descriptor = map_render_pipeline_descriptor_owned(desc);
+        PipelineFuture::spawn(
+            self,
+            &device_data.error_sink,
+            move || {
+                let id = context.create_render_pipeline(
+                    &device,
+                    &error_sink,
+                    &descriptor,
+                    "Device::create_render_pipeline_async",
+                );
+                (id, ())
+            },
+            |context, (id, ())| wgc::gfx_select!(id => context.0.render_pipeline_drop(id)),
+        )
+    }
+    fn device_create_mesh_pipeline(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &MeshPipelineDescriptor<'_>,
+    ) -> (Self::RenderPipelineId, Self::RenderPipelineData) {
         use wgc::pipeline as pipe;
 
         let implicit_pipeline_ids = match desc.layout {
@@ -1168,28 +1611,43 @@ impl crate::Context for ContextWgpuCore {
                 group_ids: &[(); wgc::MAX_BIND_GROUPS],
             }),
         };
-        let descriptor = pipe::ComputePipelineDescriptor {
+        let descriptor = pipe::MeshPipelineDescriptor {
             label: desc.label.map(Borrowed),
             layout: desc.layout.map(|l| l.id.into()),
-            stage: pipe::ProgrammableStageDescriptor {
-                module: desc.module.id.into(),
-                entry_point: Borrowed(desc.entry_point),
+            task: desc
+                .task
+                .as_ref()
+                .map(|task| pipe::ProgrammableStageDescriptor {
+                    module: task.module.id.into(),
+                    entry_point: Borrowed(task.entry_point),
+                }),
+            mesh: pipe::ProgrammableStageDescriptor {
+                module: desc.mesh.module.id.into(),
+                entry_point: Borrowed(desc.mesh.entry_point),
             },
-        };
-
-        let (id, error) = wgc::gfx_select!(device => self.0.device_create_compute_pipeline(
-            *device,
-            &descriptor,
-            (),
-            implicit_pipeline_ids
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.clone(),
+            multisample: desc.multisample,
+            fragment: desc.fragment.as_ref().map(|frag| pipe::FragmentState {
+                stage: pipe::ProgrammableStageDescriptor {
+                    module: frag.module.id.into(),
+                    entry_point: Borrowed(frag.entry_point),
+                },
+                targets: Borrowed(frag.targets),
+            }),
+            multiview: desc.multiview,
+            cache: desc.cache.map(|c| c.id.into()),
+        };
+
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_mesh_pipeline(
+            *device,
+            &descriptor,
+            (),
+            implicit_pipeline_ids
         ));
         if let Some(cause) = error {
-            if let wgc::pipeline::CreateComputePipelineError::Internal(ref error) = cause {
-                log::error!(
-                    "Shader translation error for stage {:?}: {}",
-                    wgt::ShaderStages::COMPUTE,
-                    error
-                );
+            if let wgc::pipeline::CreateRenderPipelineError::Internal { stage, ref error } = cause {
+                log::error!("Shader translation error for stage {:?}: {}", stage, error);
                 log::error!("Please report it to https://github.com/gfx-rs/wgpu");
             }
             self.handle_error(
@@ -1197,7 +1655,87 @@ impl crate::Context for ContextWgpuCore {
                 cause,
                 LABEL,
                 desc.label,
-                "Device::create_compute_pipeline",
+                "Device::create_mesh_pipeline",
+            );
+        }
+        (id, ())
+    }
+    fn device_create_compute_pipeline(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ComputePipelineDescriptor<'_>,
+    ) -> (Self::ComputePipelineId, Self::ComputePipelineData) {
+        use wgc::pipeline as pipe;
+
+        let descriptor = pipe::ComputePipelineDescriptor {
+            label: desc.label.map(Borrowed),
+            layout: desc.layout.map(|l| l.id.into()),
+            stage: pipe::ProgrammableStageDescriptor {
+                module: desc.module.id.into(),
+                entry_point: Borrowed(desc.entry_point),
+            },
+            cache: desc.cache.map(|c| c.id.into()),
+        };
+
+        let id = self.create_compute_pipeline(
+            device,
+            &device_data.error_sink,
+            &descriptor,
+            "Device::create_compute_pipeline",
+        );
+        (id, ())
+    }
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ComputePipelineDescriptor<'_>,
+    ) -> Self::CreateComputePipelineFuture {
+        let context = ContextWgpuCore(Arc::clone(&self.0));
+        let device = *device;
+        let error_sink = Arc::clone(&device_data.error_sink);
+        let descriptor = map_compute_pipeline_descriptor_owned(desc);
+        PipelineFuture::spawn(
+            self,
+            &device_data.error_sink,
+            move || {
+                let id = context.create_compute_pipeline(
+                    &device,
+                    &error_sink,
+                    &descriptor,
+                    "Device::create_compute_pipeline_async",
+                );
+                (id, ())
+            },
+            |context, (id, ())| wgc::gfx_select!(id => context.0.compute_pipeline_drop(id)),
+        )
+    }
+    unsafe fn device_create_pipeline_cache(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &PipelineCacheDescriptor<'_>,
+    ) -> (Self::PipelineCacheId, Self::PipelineCacheData) {
+        use wgc::pipeline as pipe;
+
+        let descriptor = pipe::PipelineCacheDescriptor {
+            label: desc.label.map(Borrowed),
+            data: desc.data.map(Borrowed),
+            fallback: desc.fallback,
+        };
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_pipeline_cache(
+            *device,
+            &descriptor,
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_pipeline_cache",
             );
         }
         (id, ())
@@ -1313,6 +1851,363 @@ impl crate::Context for ContextWgpuCore {
         }
         (id, ())
     }
+    fn device_create_tile_pool(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::TilePoolDescriptor<'_>,
+    ) -> (Self::TilePoolId, Self::TilePoolData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_tile_pool(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_tile_pool",
+            );
+        }
+        (id, ())
+    }
+    fn device_create_memory_heap(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::MemoryHeapDescriptor<'_>,
+    ) -> (Self::MemoryHeapId, Self::MemoryHeapData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_memory_heap(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_memory_heap",
+            );
+        }
+        (id, ())
+    }
+    fn device_create_buffer_in_heap(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::BufferDescriptor<'_>,
+        heap: &Self::MemoryHeapId,
+        _heap_data: &Self::MemoryHeapData,
+        offset: wgt::BufferAddress,
+    ) -> (Self::BufferId, Self::BufferData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_buffer_in_heap(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            *heap,
+            offset,
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_buffer_in_heap",
+            );
+        }
+        (
+            id,
+            Buffer {
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
+    fn device_create_texture_in_heap(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+        heap: &Self::MemoryHeapId,
+        _heap_data: &Self::MemoryHeapData,
+        offset: wgt::BufferAddress,
+    ) -> (Self::TextureId, Self::TextureData) {
+        let wgt_desc = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_texture_in_heap(
+            *device,
+            &wgt_desc,
+            *heap,
+            offset,
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_texture_in_heap",
+            );
+        }
+        (
+            id,
+            Texture {
+                id,
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
+    #[cfg(native)]
+    unsafe fn device_import_external_texture(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+        source: wgt::ExternalImageSource,
+    ) -> (Self::TextureId, Self::TextureData) {
+        let wgt_desc = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = wgc::gfx_select!(device => self.0.device_import_external_texture(
+            *device,
+            &wgt_desc,
+            source,
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::import_external_texture",
+            );
+        }
+        (
+            id,
+            Texture {
+                id,
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
+    fn device_create_external_fence(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::ExternalFenceDescriptor<'_>,
+    ) -> (Self::ExternalFenceId, Self::ExternalFenceData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_external_fence(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_external_fence",
+            );
+        }
+        (id, ())
+    }
+    #[cfg(native)]
+    unsafe fn device_import_external_fence(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::ExternalFenceDescriptor<'_>,
+        handle: wgt::ExternalFenceHandle,
+    ) -> (Self::ExternalFenceId, Self::ExternalFenceData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_import_external_fence(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            handle,
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::import_external_fence",
+            );
+        }
+        (id, ())
+    }
+    #[cfg(native)]
+    fn external_fence_export(
+        &self,
+        external_fence: &Self::ExternalFenceId,
+        _external_fence_data: &Self::ExternalFenceData,
+    ) -> wgt::ExternalFenceHandle {
+        match wgc::gfx_select!(*external_fence => self.0.external_fence_export(*external_fence)) {
+            Ok(handle) => handle,
+            Err(err) => self.handle_error_fatal(err, "ExternalFence::export_handle"),
+        }
+    }
+    fn device_create_external_buffer(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::BufferDescriptor<'_>,
+    ) -> (Self::BufferId, Self::BufferData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_external_buffer(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_external_buffer",
+            );
+        }
+        (
+            id,
+            Buffer {
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
+    #[cfg(native)]
+    unsafe fn device_import_external_buffer(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::BufferDescriptor<'_>,
+        memory: wgt::ExternalMemory,
+    ) -> (Self::BufferId, Self::BufferData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_import_external_buffer(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            memory,
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::import_external_buffer",
+            );
+        }
+        (
+            id,
+            Buffer {
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
+    #[cfg(native)]
+    fn buffer_export_external_memory(
+        &self,
+        buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+    ) -> wgt::ExternalMemory {
+        match wgc::gfx_select!(*buffer => self.0.buffer_export_external_memory(*buffer)) {
+            Ok(memory) => memory,
+            Err(err) => self.handle_error_fatal(err, "Buffer::export_external_memory"),
+        }
+    }
+    fn device_buffer_memory_requirements(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::BufferDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        match wgc::gfx_select!(device => self.0.device_buffer_memory_requirements(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed))
+        )) {
+            Ok(requirements) => requirements,
+            Err(cause) => {
+                self.handle_error_nolabel(
+                    &device_data.error_sink,
+                    cause,
+                    "Device::buffer_memory_requirements",
+                );
+                wgt::MemoryRequirements::default()
+            }
+        }
+    }
+    fn device_texture_memory_requirements(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        let wgt_desc = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        match wgc::gfx_select!(device => self.0.device_texture_memory_requirements(
+            *device,
+            &wgt_desc
+        )) {
+            Ok(requirements) => requirements,
+            Err(cause) => {
+                self.handle_error_nolabel(
+                    &device_data.error_sink,
+                    cause,
+                    "Device::texture_memory_requirements",
+                );
+                wgt::MemoryRequirements::default()
+            }
+        }
+    }
+    fn device_create_blas(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::CreateBlasDescriptor<'_>,
+        sizes: wgt::BlasGeometrySizeDescriptors,
+    ) -> (Self::BlasId, Option<u64>, Self::BlasData) {
+        let (id, handle, error) = wgc::gfx_select!(device => self.0.device_create_blas(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            sizes,
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_blas",
+            );
+        }
+        (id, handle, ())
+    }
+    fn device_create_tlas(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::CreateTlasDescriptor<'_>,
+    ) -> (Self::TlasId, Self::TlasData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_tlas(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_tlas",
+            );
+        }
+        (id, ())
+    }
     fn device_create_command_encoder(
         &self,
         device: &Self::DeviceId,
@@ -1359,29 +2254,91 @@ impl crate::Context for ContextWgpuCore {
             Err(e) => panic!("Error in Device::create_render_bundle_encoder: {e}"),
         }
     }
-    #[cfg_attr(not(any(native, Emscripten)), allow(unused))]
-    fn device_drop(&self, device: &Self::DeviceId, _device_data: &Self::DeviceData) {
-        #[cfg(any(native, Emscripten))]
+    #[cfg_attr(not(any(native, Emscripten)), allow(unused))]
+    fn device_drop(&self, device: &Self::DeviceId, _device_data: &Self::DeviceData) {
+        #[cfg(any(native, Emscripten))]
+        {
+            match wgc::gfx_select!(device => self.0.device_poll(*device, wgt::Maintain::wait())) {
+                Ok(_) => {}
+                Err(err) => self.handle_error_fatal(err, "Device::drop"),
+            }
+            wgc::gfx_select!(device => self.0.device_drop(*device));
+        }
+    }
+    #[cfg_attr(target_arch = "wasm32", allow(unused))]
+    fn queue_drop(&self, queue: &Self::QueueId, _device_data: &Self::QueueData) {
+        wgc::gfx_select!(queue => self.0.queue_drop(*queue));
+    }
+    fn device_set_device_lost_callback(
+        &self,
+        _device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        device_lost_callback: crate::context::DeviceLostCallback,
+    ) {
+        let mut lost = device_data.lost.lock();
+        match lost.info {
+            // The device is already lost, so the callback is called right away.
+            Some((reason, ref message)) => {
+                let message = message.clone();
+                drop(lost);
+                device_lost_callback(reason, message);
+            }
+            None => lost.callback = Some(device_lost_callback),
+        }
+    }
+    fn device_lost(
+        &self,
+        _device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+    ) -> Self::DeviceLostFuture {
+        DeviceLostFuture {
+            lost: device_data.lost.clone(),
+        }
+    }
+    fn device_set_debug_printf_callback(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        debug_printf_callback: crate::context::DebugPrintfCallback,
+    ) {
+        let callback = Arc::from(debug_printf_callback);
+        wgc::gfx_select!(device => self.0.device_set_debug_printf_callback(*device, Some(callback)));
+    }
+    fn device_set_watchdog(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        watchdog: Option<(std::time::Duration, crate::context::WatchdogCallback)>,
+    ) {
+        if let Err(cause) =
+            wgc::gfx_select!(device => self.0.device_set_watchdog(*device, watchdog))
+        {
+            self.handle_error_fatal(cause, "Device::set_watchdog");
+        }
+    }
+    fn device_set_automatic_polling(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        enabled: bool,
+    ) {
+        if let Err(cause) =
+            wgc::gfx_select!(device => self.0.device_set_automatic_polling(*device, enabled))
         {
-            match wgc::gfx_select!(device => self.0.device_poll(*device, wgt::Maintain::wait())) {
-                Ok(_) => {}
-                Err(err) => self.handle_error_fatal(err, "Device::drop"),
-            }
-            wgc::gfx_select!(device => self.0.device_drop(*device));
+            self.handle_error_fatal(cause, "Device::set_automatic_polling");
         }
     }
-    #[cfg_attr(target_arch = "wasm32", allow(unused))]
-    fn queue_drop(&self, queue: &Self::QueueId, _device_data: &Self::QueueData) {
-        wgc::gfx_select!(queue => self.0.queue_drop(*queue));
-    }
-    fn device_set_device_lost_callback(
+    fn device_set_command_disassembly(
         &self,
         device: &Self::DeviceId,
         _device_data: &Self::DeviceData,
-        device_lost_callback: crate::context::DeviceLostCallback,
+        enabled: bool,
     ) {
-        let device_lost_closure = DeviceLostClosure::from_rust(device_lost_callback);
-        wgc::gfx_select!(device => self.0.device_set_device_lost_closure(*device, device_lost_closure));
+        if let Err(cause) =
+            wgc::gfx_select!(device => self.0.device_set_command_disassembly(*device, enabled))
+        {
+            self.handle_error_fatal(cause, "Device::set_command_disassembly");
+        }
     }
     fn device_destroy(&self, device: &Self::DeviceId, _device_data: &Self::DeviceData) {
         wgc::gfx_select!(device => self.0.device_destroy(*device));
@@ -1396,6 +2353,9 @@ impl crate::Context for ContextWgpuCore {
         // destroyed (which this is not) are "unknown".
         wgc::gfx_select!(device => self.0.device_mark_lost(*device, message));
     }
+    fn device_mark_lost_by_driver(&self, device: &Self::DeviceId, _device_data: &Self::DeviceData) {
+        wgc::gfx_select!(device => self.0.device_mark_lost_by_driver(*device));
+    }
     fn device_poll(
         &self,
         device: &Self::DeviceId,
@@ -1414,6 +2374,41 @@ impl crate::Context for ContextWgpuCore {
             Err(err) => self.handle_error_fatal(err, "Device::poll"),
         }
     }
+    fn device_poll_until(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        submission_index: &Self::SubmissionIndexData,
+        timeout: std::time::Duration,
+    ) -> wgt::PollStatus {
+        match wgc::gfx_select!(device => self.0.device_poll_until(
+            *device,
+            *submission_index,
+            timeout
+        )) {
+            Ok(status) => status,
+            Err(err) => self.handle_error_fatal(err, "Device::poll_until"),
+        }
+    }
+    fn device_additional_queues(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+    ) -> Vec<(Self::QueueId, Self::QueueData)> {
+        match wgc::gfx_select!(device => self.0.device_additional_queues(*device)) {
+            Ok(ids) => ids
+                .into_iter()
+                .map(|id| {
+                    let queue = Queue {
+                        id,
+                        error_sink: device_data.error_sink.clone(),
+                    };
+                    (id, queue)
+                })
+                .collect(),
+            Err(err) => self.handle_error_fatal(err, "Device::additional_queues"),
+        }
+    }
     fn device_on_uncaptured_error(
         &self,
         _device: &Self::DeviceId,
@@ -1430,10 +2425,7 @@ impl crate::Context for ContextWgpuCore {
         filter: crate::ErrorFilter,
     ) {
         let mut error_sink = device_data.error_sink.lock();
-        error_sink.scopes.push(ErrorScope {
-            error: None,
-            filter,
-        });
+        error_sink.push_scope(filter);
     }
     fn device_pop_error_scope(
         &self,
@@ -1441,7 +2433,10 @@ impl crate::Context for ContextWgpuCore {
         device_data: &Self::DeviceData,
     ) -> Self::PopErrorScopeFuture {
         let mut error_sink = device_data.error_sink.lock();
-        let scope = error_sink.scopes.pop().unwrap();
+        let Some(scope) = error_sink.scopes.pop() else {
+            log::warn!("Device::pop_error_scope called without a matching push_error_scope");
+            return ready(None);
+        };
         ready(scope.error)
     }
 
@@ -1502,6 +2497,38 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn buffer_unmap_range(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        range: Range<wgt::BufferAddress>,
+    ) {
+        match wgc::gfx_select!(buffer => self.0.buffer_unmap_range(
+            *buffer,
+            range.start,
+            Some(range.end - range.start)
+        )) {
+            Ok(()) => (),
+            Err(cause) => {
+                self.handle_error_nolabel(&buffer_data.error_sink, cause, "Buffer::unmap_range")
+            }
+        }
+    }
+
+    fn buffer_device_address(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+    ) -> wgt::BufferAddress {
+        match wgc::gfx_select!(buffer => self.0.buffer_device_address(*buffer)) {
+            Ok(address) => address,
+            Err(cause) => {
+                self.handle_error_nolabel(&buffer_data.error_sink, cause, "Buffer::device_address");
+                0
+            }
+        }
+    }
+
     fn texture_create_view(
         &self,
         texture: &Self::TextureId,
@@ -1519,6 +2546,7 @@ impl crate::Context for ContextWgpuCore {
                 base_array_layer: desc.base_array_layer,
                 array_layer_count: desc.array_layer_count,
             },
+            swizzle: desc.swizzle,
         };
         let (id, error) = wgc::gfx_select!(
             texture => self.0.texture_create_view(*texture, &descriptor, ())
@@ -1577,6 +2605,34 @@ impl crate::Context for ContextWgpuCore {
         wgc::gfx_select!(*query_set => self.0.query_set_drop(*query_set))
     }
 
+    fn tile_pool_drop(&self, tile_pool: &Self::TilePoolId, _tile_pool_data: &Self::TilePoolData) {
+        wgc::gfx_select!(*tile_pool => self.0.tile_pool_drop(*tile_pool))
+    }
+
+    fn memory_heap_drop(
+        &self,
+        memory_heap: &Self::MemoryHeapId,
+        _memory_heap_data: &Self::MemoryHeapData,
+    ) {
+        wgc::gfx_select!(*memory_heap => self.0.memory_heap_drop(*memory_heap))
+    }
+
+    fn external_fence_drop(
+        &self,
+        external_fence: &Self::ExternalFenceId,
+        _external_fence_data: &Self::ExternalFenceData,
+    ) {
+        wgc::gfx_select!(*external_fence => self.0.external_fence_drop(*external_fence))
+    }
+
+    fn blas_drop(&self, blas: &Self::BlasId, _blas_data: &Self::BlasData) {
+        wgc::gfx_select!(*blas => self.0.blas_drop(*blas))
+    }
+
+    fn tlas_drop(&self, tlas: &Self::TlasId, _tlas_data: &Self::TlasData) {
+        wgc::gfx_select!(*tlas => self.0.tlas_drop(*tlas))
+    }
+
     fn bind_group_drop(
         &self,
         bind_group: &Self::BindGroupId,
@@ -1625,6 +2681,14 @@ impl crate::Context for ContextWgpuCore {
         wgc::gfx_select!(*command_buffer => self.0.command_buffer_drop(*command_buffer))
     }
 
+    fn command_buffer_disassembly(
+        &self,
+        command_buffer: &Self::CommandBufferId,
+        _command_buffer_data: &Self::CommandBufferData,
+    ) -> Option<String> {
+        wgc::gfx_select!(*command_buffer => self.0.command_buffer_disassembly(*command_buffer))
+    }
+
     fn render_bundle_drop(
         &self,
         render_bundle: &Self::RenderBundleId,
@@ -1649,6 +2713,22 @@ impl crate::Context for ContextWgpuCore {
         wgc::gfx_select!(*pipeline => self.0.render_pipeline_drop(*pipeline))
     }
 
+    fn pipeline_cache_drop(
+        &self,
+        cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) {
+        wgc::gfx_select!(*cache => self.0.pipeline_cache_drop(*cache))
+    }
+
+    fn pipeline_cache_get_data(
+        &self,
+        cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) -> Option<Vec<u8>> {
+        wgc::gfx_select!(*cache => self.0.pipeline_cache_get_data(*cache))
+    }
+
     fn compute_pipeline_get_bind_group_layout(
         &self,
         pipeline: &Self::ComputePipelineId,
@@ -1675,6 +2755,16 @@ impl crate::Context for ContextWgpuCore {
         (id, ())
     }
 
+    fn bind_group_layout_entries(
+        &self,
+        bind_group_layout: &Self::BindGroupLayoutId,
+        _bind_group_layout_data: &Self::BindGroupLayoutData,
+    ) -> Vec<wgt::BindGroupLayoutEntry> {
+        // An invalid layout has no entries to report.
+        wgc::gfx_select!(*bind_group_layout => self.0.bind_group_layout_entries(*bind_group_layout))
+            .unwrap_or_default()
+    }
+
     fn command_encoder_copy_buffer_to_buffer(
         &self,
         encoder: &Self::CommandEncoderId,
@@ -1769,6 +2859,30 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn command_encoder_resolve_texture(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+        source: crate::ImageCopyTexture<'_>,
+        destination: crate::ImageCopyTexture<'_>,
+        copy_size: wgt::Extent3d,
+        mode: wgt::ResolveMode,
+    ) {
+        if let Err(cause) = wgc::gfx_select!(encoder => self.0.command_encoder_resolve_texture(
+            *encoder,
+            &map_texture_copy_view(source),
+            &map_texture_copy_view(destination),
+            &copy_size,
+            mode
+        )) {
+            self.handle_error_nolabel(
+                &encoder_data.error_sink,
+                cause,
+                "CommandEncoder::resolve_texture",
+            );
+        }
+    }
+
     fn command_encoder_begin_compute_pass(
         &self,
         encoder: &Self::CommandEncoderId,
@@ -1849,6 +2963,12 @@ impl crate::Context for ContextWgpuCore {
                 view: dsa.view.id.into(),
                 depth: map_pass_channel(dsa.depth_ops.as_ref()),
                 stencil: map_pass_channel(dsa.stencil_ops.as_ref()),
+                resolve: dsa.resolve.as_ref().map(|resolve| {
+                    wgc::command::RenderPassDepthStencilResolve {
+                        view: resolve.view.id.into(),
+                        mode: resolve.mode,
+                    }
+                }),
             }
         });
 
@@ -1861,6 +2981,19 @@ impl crate::Context for ContextWgpuCore {
                     end_of_pass_write_index: tw.end_of_pass_write_index,
                 });
 
+        let pipeline_statistics_query = desc.pipeline_statistics_query.as_ref().map(|psq| {
+            wgc::command::RenderPassPipelineStatisticsQuery {
+                query_set: psq.query_set.id.into(),
+                query_index: psq.query_index,
+            }
+        });
+
+        let shading_rate_attachment = desc.shading_rate_attachment.as_ref().map(|sra| {
+            wgc::command::RenderPassShadingRateAttachment {
+                view: sra.view.id.into(),
+            }
+        });
+
         (
             Unused,
             wgc::command::RenderPass::new(
@@ -1873,6 +3006,8 @@ impl crate::Context for ContextWgpuCore {
                     occlusion_query_set: desc
                         .occlusion_query_set
                         .map(|query_set| query_set.id.into()),
+                    pipeline_statistics_query: pipeline_statistics_query.as_ref(),
+                    shading_rate_attachment: shading_rate_attachment.as_ref(),
                 },
             ),
         )
@@ -2005,6 +3140,22 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn command_encoder_aliasing_barrier(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+    ) {
+        if let Err(cause) =
+            wgc::gfx_select!(encoder => self.0.command_encoder_aliasing_barrier(*encoder))
+        {
+            self.handle_error_nolabel(
+                &encoder_data.error_sink,
+                cause,
+                "CommandEncoder::aliasing_barrier",
+            );
+        }
+    }
+
     fn command_encoder_write_timestamp(
         &self,
         encoder: &Self::CommandEncoderId,
@@ -2054,6 +3205,61 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn command_encoder_build_acceleration_structures_unsafe_tlas(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+        blas: &[crate::BlasBuildEntry<'_>],
+        tlas: &[crate::TlasBuildEntry<'_>],
+    ) {
+        let blas = blas
+            .iter()
+            .map(|entry| {
+                let crate::BlasGeometries::TriangleGeometries(ref geometries) = entry.geometry;
+                wgc::ray_tracing::BlasBuildEntry {
+                    blas_id: entry.blas.id.into(),
+                    geometries: wgc::ray_tracing::BlasGeometries::TriangleGeometries(
+                        geometries
+                            .iter()
+                            .map(|geometry| wgc::ray_tracing::BlasTriangleGeometry {
+                                size: geometry.size.clone(),
+                                vertex_buffer: geometry.vertex_buffer.id.into(),
+                                first_vertex: geometry.first_vertex,
+                                vertex_stride: geometry.vertex_stride,
+                                index_buffer: geometry.index_buffer.map(|buffer| buffer.id.into()),
+                                index_buffer_offset: geometry.index_buffer_offset,
+                                transform_buffer: geometry
+                                    .transform_buffer
+                                    .map(|buffer| buffer.id.into()),
+                                transform_buffer_offset: geometry.transform_buffer_offset,
+                            })
+                            .collect(),
+                    ),
+                }
+            })
+            .collect::<Vec<_>>();
+        let tlas = tlas
+            .iter()
+            .map(|entry| wgc::ray_tracing::TlasBuildEntry {
+                tlas_id: entry.tlas.id.into(),
+                instance_buffer_id: entry.instance_buffer.id.into(),
+                instance_count: entry.instance_count,
+            })
+            .collect::<Vec<_>>();
+
+        if let Err(cause) = wgc::gfx_select!(encoder => self.0.command_encoder_build_acceleration_structures_unsafe_tlas(
+            *encoder,
+            &blas,
+            &tlas
+        )) {
+            self.handle_error_nolabel(
+                &encoder_data.error_sink,
+                cause,
+                "CommandEncoder::build_acceleration_structures_unsafe_tlas",
+            );
+        }
+    }
+
     fn render_bundle_encoder_finish(
         &self,
         _encoder: Self::RenderBundleEncoderId,
@@ -2156,6 +3362,57 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn queue_write_buffer_regions(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+        regions: &[(wgt::BufferAddress, &[u8])],
+    ) {
+        match wgc::gfx_select!(
+            *queue => self.0.queue_write_buffer_regions(*queue, *buffer, regions)
+        ) {
+            Ok(()) => (),
+            Err(err) => self.handle_error_nolabel(
+                &queue_data.error_sink,
+                err,
+                "Queue::write_buffer_regions",
+            ),
+        }
+    }
+
+    fn queue_write_staging_buffer_regions(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+        regions: &[(wgt::BufferAddress, wgt::BufferSize)],
+        staging_buffer: &dyn crate::context::QueueWriteBuffer,
+    ) {
+        let staging_buffer = staging_buffer
+            .as_any()
+            .downcast_ref::<QueueWriteBuffer>()
+            .unwrap();
+        let regions = regions
+            .iter()
+            .map(|&(offset, size)| (offset, size.get()))
+            .collect::<Vec<_>>();
+        match wgc::gfx_select!(
+            *queue => self.0.queue_write_staging_buffer_regions(*queue, *buffer, &regions, staging_buffer.buffer_id)
+        ) {
+            Ok(()) => (),
+            Err(err) => {
+                self.handle_error_nolabel(
+                    &queue_data.error_sink,
+                    err,
+                    "Queue::write_buffer_regions_with",
+                );
+            }
+        }
+    }
+
     fn queue_write_texture(
         &self,
         queue: &Self::QueueId,
@@ -2222,19 +3479,126 @@ impl crate::Context for ContextWgpuCore {
         (Unused, index)
     }
 
-    fn queue_get_timestamp_period(
+    fn queue_get_timestamp_period(
+        &self,
+        queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+    ) -> f32 {
+        let res = wgc::gfx_select!(queue => self.0.queue_get_timestamp_period(
+            *queue
+        ));
+        match res {
+            Ok(v) => v,
+            Err(cause) => {
+                self.handle_error_fatal(cause, "Queue::get_timestamp_period");
+            }
+        }
+    }
+
+    fn queue_get_timestamp_calibration(
+        &self,
+        queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+    ) -> Option<wgt::TimestampCalibration> {
+        let res = wgc::gfx_select!(queue => self.0.queue_get_timestamp_calibration(
+            *queue
+        ));
+        match res {
+            Ok(v) => v,
+            Err(cause) => {
+                self.handle_error_fatal(cause, "Queue::get_timestamp_calibration");
+            }
+        }
+    }
+
+    fn queue_bind_texture_pages(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        texture: &crate::Texture,
+        bindings: &[crate::TextureTileBinding<'_>],
+    ) {
+        let bindings = bindings
+            .iter()
+            .map(|binding| wgc::device::queue::TextureTileBinding {
+                mip_level: binding.mip_level,
+                origin: binding.origin,
+                size: binding.size,
+                memory: binding
+                    .memory
+                    .map(|(pool, first_tile)| (pool.id.into(), first_tile)),
+            })
+            .collect::<Vec<_>>();
+        match wgc::gfx_select!(*queue => self.0.queue_bind_texture_pages(
+            *queue,
+            texture.id.into(),
+            &bindings
+        )) {
+            Ok(()) => (),
+            Err(err) => {
+                self.handle_error_nolabel(&queue_data.error_sink, err, "Queue::bind_texture_pages")
+            }
+        }
+    }
+
+    fn queue_wait_for_submission(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        submission_index: &Self::SubmissionIndexData,
+    ) {
+        match wgc::gfx_select!(*queue => self.0.queue_wait_for_submission(
+            *queue,
+            *submission_index
+        )) {
+            Ok(()) => (),
+            Err(err) => {
+                self.handle_error_nolabel(&queue_data.error_sink, err, "Queue::wait_for_submission")
+            }
+        }
+    }
+
+    fn queue_wait_for_external_fence(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        external_fence: &Self::ExternalFenceId,
+        _external_fence_data: &Self::ExternalFenceData,
+        value: u64,
+    ) {
+        match wgc::gfx_select!(*queue => self.0.queue_wait_for_external_fence(
+            *queue,
+            *external_fence,
+            value
+        )) {
+            Ok(()) => (),
+            Err(err) => self.handle_error_nolabel(
+                &queue_data.error_sink,
+                err,
+                "Queue::wait_for_external_fence",
+            ),
+        }
+    }
+
+    fn queue_signal_external_fence(
         &self,
         queue: &Self::QueueId,
-        _queue_data: &Self::QueueData,
-    ) -> f32 {
-        let res = wgc::gfx_select!(queue => self.0.queue_get_timestamp_period(
-            *queue
-        ));
-        match res {
-            Ok(v) => v,
-            Err(cause) => {
-                self.handle_error_fatal(cause, "Queue::get_timestamp_period");
-            }
+        queue_data: &Self::QueueData,
+        external_fence: &Self::ExternalFenceId,
+        _external_fence_data: &Self::ExternalFenceData,
+        value: u64,
+    ) {
+        match wgc::gfx_select!(*queue => self.0.queue_signal_external_fence(
+            *queue,
+            *external_fence,
+            value
+        )) {
+            Ok(()) => (),
+            Err(err) => self.handle_error_nolabel(
+                &queue_data.error_sink,
+                err,
+                "Queue::signal_external_fence",
+            ),
         }
     }
 
@@ -2525,55 +3889,96 @@ impl crate::Context for ContextWgpuCore {
     fn render_bundle_encoder_multi_draw_indirect(
         &self,
         _encoder: &mut Self::RenderBundleEncoderId,
-        _encoder_data: &mut Self::RenderBundleEncoderData,
-        _indirect_buffer: &Self::BufferId,
+        encoder_data: &mut Self::RenderBundleEncoderData,
+        indirect_buffer: &Self::BufferId,
         _indirect_buffer_data: &Self::BufferData,
-        _indirect_offset: wgt::BufferAddress,
-        _count: u32,
+        indirect_offset: wgt::BufferAddress,
+        count: u32,
     ) {
-        unimplemented!()
+        wgpu_render_bundle_multi_draw_indirect(
+            encoder_data,
+            *indirect_buffer,
+            indirect_offset,
+            count,
+        )
     }
 
     fn render_bundle_encoder_multi_draw_indexed_indirect(
         &self,
         _encoder: &mut Self::RenderBundleEncoderId,
-        _encoder_data: &mut Self::RenderBundleEncoderData,
-        _indirect_buffer: &Self::BufferId,
+        encoder_data: &mut Self::RenderBundleEncoderData,
+        indirect_buffer: &Self::BufferId,
         _indirect_buffer_data: &Self::BufferData,
-        _indirect_offset: wgt::BufferAddress,
-        _count: u32,
+        indirect_offset: wgt::BufferAddress,
+        count: u32,
     ) {
-        unimplemented!()
+        wgpu_render_bundle_multi_draw_indexed_indirect(
+            encoder_data,
+            *indirect_buffer,
+            indirect_offset,
+            count,
+        )
     }
 
     fn render_bundle_encoder_multi_draw_indirect_count(
         &self,
         _encoder: &mut Self::RenderBundleEncoderId,
-        _encoder_data: &mut Self::RenderBundleEncoderData,
-        _indirect_buffer: &Self::BufferId,
+        encoder_data: &mut Self::RenderBundleEncoderData,
+        indirect_buffer: &Self::BufferId,
         _indirect_buffer_data: &Self::BufferData,
-        _indirect_offset: wgt::BufferAddress,
-        _count_buffer: &Self::BufferId,
+        indirect_offset: wgt::BufferAddress,
+        count_buffer: &Self::BufferId,
         _count_buffer_data: &Self::BufferData,
-        _count_buffer_offset: wgt::BufferAddress,
-        _max_count: u32,
+        count_buffer_offset: wgt::BufferAddress,
+        max_count: u32,
     ) {
-        unimplemented!()
+        wgpu_render_bundle_multi_draw_indirect_count(
+            encoder_data,
+            *indirect_buffer,
+            indirect_offset,
+            *count_buffer,
+            count_buffer_offset,
+            max_count,
+        )
     }
 
     fn render_bundle_encoder_multi_draw_indexed_indirect_count(
         &self,
         _encoder: &mut Self::RenderBundleEncoderId,
-        _encoder_data: &mut Self::RenderBundleEncoderData,
-        _indirect_buffer: &Self::BufferId,
+        encoder_data: &mut Self::RenderBundleEncoderData,
+        indirect_buffer: &Self::BufferId,
         _indirect_buffer_data: &Self::BufferData,
-        _indirect_offset: wgt::BufferAddress,
-        _count_buffer: &Self::BufferId,
+        indirect_offset: wgt::BufferAddress,
+        count_buffer: &Self::BufferId,
         _count_buffer_data: &Self::BufferData,
-        _count_buffer_offset: wgt::BufferAddress,
-        _max_count: u32,
+        count_buffer_offset: wgt::BufferAddress,
+        max_count: u32,
+    ) {
+        wgpu_render_bundle_multi_draw_indexed_indirect_count(
+            encoder_data,
+            *indirect_buffer,
+            indirect_offset,
+            *count_buffer,
+            count_buffer_offset,
+            max_count,
+        )
+    }
+
+    fn render_bundle_encoder_begin_occlusion_query(
+        &self,
+        _encoder: &mut Self::RenderBundleEncoderId,
+        encoder_data: &mut Self::RenderBundleEncoderData,
+        query_index: u32,
+    ) {
+        wgpu_render_bundle_begin_occlusion_query(encoder_data, query_index)
+    }
+
+    fn render_bundle_encoder_end_occlusion_query(
+        &self,
+        _encoder: &mut Self::RenderBundleEncoderId,
+        encoder_data: &mut Self::RenderBundleEncoderData,
     ) {
-        unimplemented!()
+        wgpu_render_bundle_end_occlusion_query(encoder_data)
     }
 
     fn render_pass_set_pipeline(
@@ -2606,6 +4011,20 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn render_pass_set_bind_group_immediate(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        index: u32,
+        entries: &[BindGroupEntry<'_>],
+    ) {
+        let entries = entries
+            .iter()
+            .map(map_bind_group_entry_owned)
+            .collect::<Vec<_>>();
+        wgpu_render_pass_set_bind_group_immediate(pass_data, index, &entries)
+    }
+
     fn render_pass_set_index_buffer(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -2707,6 +4126,28 @@ impl crate::Context for ContextWgpuCore {
         wgpu_render_pass_draw_indexed_indirect(pass_data, *indirect_buffer, indirect_offset)
     }
 
+    fn render_pass_draw_mesh_tasks(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) {
+        wgpu_render_pass_draw_mesh_tasks(pass_data, group_count_x, group_count_y, group_count_z)
+    }
+
+    fn render_pass_draw_mesh_tasks_indirect(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        indirect_buffer: &Self::BufferId,
+        _indirect_buffer_data: &Self::BufferData,
+        indirect_offset: wgt::BufferAddress,
+    ) {
+        wgpu_render_pass_draw_mesh_tasks_indirect(pass_data, *indirect_buffer, indirect_offset)
+    }
+
     fn render_pass_multi_draw_indirect(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -2780,6 +4221,55 @@ impl crate::Context for ContextWgpuCore {
         )
     }
 
+    fn render_pass_execute_indirect(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        arguments: &[wgt::IndirectArgument],
+        indirect_buffer: &Self::BufferId,
+        _indirect_buffer_data: &Self::BufferData,
+        indirect_offset: wgt::BufferAddress,
+        max_count: u32,
+    ) {
+        unsafe {
+            wgpu_render_pass_execute_indirect(
+                pass_data,
+                arguments.as_ptr(),
+                arguments.len(),
+                *indirect_buffer,
+                indirect_offset,
+                max_count,
+            )
+        }
+    }
+
+    fn render_pass_execute_indirect_count(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        arguments: &[wgt::IndirectArgument],
+        indirect_buffer: &Self::BufferId,
+        _indirect_buffer_data: &Self::BufferData,
+        indirect_offset: wgt::BufferAddress,
+        count_buffer: &Self::BufferId,
+        _count_buffer_data: &Self::BufferData,
+        count_buffer_offset: wgt::BufferAddress,
+        max_count: u32,
+    ) {
+        unsafe {
+            wgpu_render_pass_execute_indirect_count(
+                pass_data,
+                arguments.as_ptr(),
+                arguments.len(),
+                *indirect_buffer,
+                indirect_offset,
+                *count_buffer,
+                count_buffer_offset,
+                max_count,
+            )
+        }
+    }
+
     fn render_pass_set_blend_constant(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -2824,6 +4314,15 @@ impl crate::Context for ContextWgpuCore {
         wgpu_render_pass_set_stencil_reference(pass_data, reference)
     }
 
+    fn render_pass_set_shading_rate(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        rate: wgt::ShadingRate,
+    ) {
+        wgpu_render_pass_set_shading_rate(pass_data, rate)
+    }
+
     fn render_pass_insert_debug_marker(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -2856,6 +4355,22 @@ impl crate::Context for ContextWgpuCore {
         wgpu_render_pass_pop_debug_group(pass_data);
     }
 
+    fn render_pass_resume(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    ) {
+        pass_data.resume();
+    }
+
+    fn render_pass_suspend(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    ) {
+        pass_data.suspend();
+    }
+
     fn render_pass_write_timestamp(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -2903,6 +4418,54 @@ impl crate::Context for ContextWgpuCore {
         wgpu_render_pass_end_pipeline_statistics_query(pass_data)
     }
 
+    fn render_pass_begin_conditional_rendering(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+        offset: wgt::BufferAddress,
+    ) {
+        wgpu_render_pass_begin_conditional_rendering(pass_data, *buffer, offset)
+    }
+
+    fn render_pass_end_conditional_rendering(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    ) {
+        wgpu_render_pass_end_conditional_rendering(pass_data)
+    }
+
+    fn render_pass_set_vertex_capture_buffer(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        slot: u32,
+        buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+        offset: wgt::BufferAddress,
+        size: Option<wgt::BufferSize>,
+    ) {
+        wgpu_render_pass_set_vertex_capture_buffer(pass_data, slot, *buffer, offset, size)
+    }
+
+    fn render_pass_begin_vertex_capture(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    ) {
+        wgpu_render_pass_begin_vertex_capture(pass_data)
+    }
+
+    fn render_pass_end_vertex_capture(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    ) {
+        wgpu_render_pass_end_vertex_capture(pass_data)
+    }
+
     fn render_pass_execute_bundles(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -2941,20 +4504,217 @@ where
     }
 }
 
+#[derive(Debug)]
+pub struct ShaderModule {
+    compilation_info: CompilationInfo,
+}
+
+impl From<&wgc::pipeline::CreateShaderModuleError> for CompilationInfo {
+    fn from(error: &wgc::pipeline::CreateShaderModuleError) -> Self {
+        use wgc::pipeline::CreateShaderModuleError as E;
+
+        let error_message =
+            |message: String, location: Option<naga::SourceLocation>| CompilationMessage {
+                message,
+                message_type: CompilationMessageType::Error,
+                location: location.map(|location| crate::SourceLocation {
+                    line_number: location.line_number,
+                    line_position: location.line_position,
+                    offset: location.offset,
+                    length: location.length,
+                }),
+            };
+        let messages = match *error {
+            #[cfg(feature = "wgsl")]
+            E::Parsing(ref err) => vec![error_message(
+                err.inner.to_string(),
+                err.inner.location(&err.source),
+            )],
+            #[cfg(feature = "glsl")]
+            E::ParsingGlsl(ref err) => err
+                .inner
+                .errors
+                .iter()
+                .map(|e| {
+                    let location = e.meta.is_defined().then(|| e.meta.location(&err.source));
+                    error_message(e.kind.to_string(), location)
+                })
+                .collect(),
+            E::Validation(ref err) => vec![error_message(
+                err.inner.to_string(),
+                err.inner.location(&err.source),
+            )],
+            // Device errors are not caused by the shader, and only reach the error sink.
+            E::Device(_) => Vec::new(),
+            _ => vec![error_message(error.to_string(), None)],
+        };
+        Self { messages }
+    }
+}
+
 #[derive(Debug)]
 pub struct SurfaceOutputDetail {
     surface_id: wgc::id::SurfaceId,
 }
 
+/// Runs `job` on one of the threads compiling the pipelines of
+/// `device_create_*_pipeline_async`, which are started on first use.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_pipeline_job(job: impl FnOnce() + Send + 'static) {
+    type Job = Box<dyn FnOnce() + Send>;
+    static JOBS: std::sync::OnceLock<Mutex<std::sync::mpsc::Sender<Job>>> =
+        std::sync::OnceLock::new();
+
+    let jobs = JOBS.get_or_init(|| {
+        let (sender, receiver) = std::sync::mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get().min(4));
+        for index in 0..workers {
+            let receiver = Arc::clone(&receiver);
+            std::thread::Builder::new()
+                .name(format!("wgpu pipeline worker {index}"))
+                .spawn(move || loop {
+                    // The receiver is only locked while waiting for a job, not while running it.
+                    let Ok(job) = receiver.lock().recv() else {
+                        break;
+                    };
+                    job();
+                })
+                .expect("Failed to spawn a pipeline worker thread");
+        }
+        Mutex::new(sender)
+    });
+    jobs.lock()
+        .send(Box::new(job))
+        .expect("Pipeline worker threads have exited");
+}
+
+/// There are no threads to compile pipelines on, so `job` runs right away.
+#[cfg(target_arch = "wasm32")]
+fn run_pipeline_job(job: impl FnOnce() + 'static) {
+    job()
+}
+
+struct PipelineJobState<T> {
+    pipeline: Option<std::thread::Result<T>>,
+    finished: bool,
+    abandoned: bool,
+    waker: Option<Waker>,
+}
+
+/// Future of a pipeline compiled by `run_pipeline_job`.
+///
+/// Errors are reported to the error scopes that were open when the future was created, not
+/// to the ones open when the compilation finishes. Dropping the future does not wait for the
+/// job: if it has not started, it is skipped, and otherwise its pipeline is destroyed and its
+/// errors are discarded once it finishes.
+pub struct PipelineFuture<T> {
+    context: ContextWgpuCore,
+    state: Arc<Mutex<PipelineJobState<T>>>,
+    destroy: fn(&ContextWgpuCore, T),
+}
+
+impl<T: WasmNotSend + 'static> PipelineFuture<T> {
+    fn spawn(
+        context: &ContextWgpuCore,
+        error_sink: &ErrorSink,
+        create: impl FnOnce() -> T + WasmNotSend + 'static,
+        destroy: fn(&ContextWgpuCore, T),
+    ) -> Self {
+        let state = Arc::new(Mutex::new(PipelineJobState {
+            pipeline: None,
+            finished: false,
+            abandoned: false,
+            waker: None,
+        }));
+        let shared = Arc::clone(&state);
+        let job_context = ContextWgpuCore(Arc::clone(&context.0));
+        let error_sink = Arc::clone(error_sink);
+        let scope_limit = error_sink.lock().scope_limit();
+        run_pipeline_job(move || {
+            if shared.lock().abandoned {
+                return;
+            }
+            CAPTURED_ERRORS.with(|captured| captured.borrow_mut().push(None));
+            // A panic is passed on to whoever polls the future.
+            let pipeline = std::panic::catch_unwind(std::panic::AssertUnwindSafe(create));
+            let error = CAPTURED_ERRORS.with(|captured| captured.borrow_mut().pop().flatten());
+
+            // The error is reported before the pipeline is handed out, so that it is in its
+            // scope by the time the future resolves.
+            if let Some(error) = error {
+                if !shared.lock().abandoned {
+                    error_sink.lock().report_error(error, scope_limit);
+                }
+            }
+            let mut state = shared.lock();
+            if state.abandoned {
+                drop(state);
+                if let Ok(pipeline) = pipeline {
+                    destroy(&job_context, pipeline);
+                }
+                return;
+            }
+            state.pipeline = Some(pipeline);
+            state.finished = true;
+            let waker = state.waker.take();
+            drop(state);
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+        Self {
+            context: ContextWgpuCore(Arc::clone(&context.0)),
+            state,
+            destroy,
+        }
+    }
+}
+
+impl<T> Future for PipelineFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<T> {
+        let mut state = self.state.lock();
+        match state.pipeline.take() {
+            Some(Ok(pipeline)) => Poll::Ready(pipeline),
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for PipelineFuture<T> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock();
+        if !state.finished {
+            // The job cleans up after itself.
+            state.abandoned = true;
+            return;
+        }
+        let pipeline = state.pipeline.take();
+        drop(state);
+        if let Some(Ok(pipeline)) = pipeline {
+            (self.destroy)(&self.context, pipeline);
+        }
+    }
+}
+
 type ErrorSink = Arc<Mutex<ErrorSinkRaw>>;
 
 struct ErrorScope {
+    /// Increases with every pushed scope, so that the scopes on the stack are in order.
+    id: u64,
     error: Option<crate::Error>,
     filter: crate::ErrorFilter,
 }
 
 struct ErrorSinkRaw {
     scopes: Vec<ErrorScope>,
+    next_scope_id: u64,
     uncaptured_handler: Box<dyn crate::UncapturedErrorHandler>,
 }
 
@@ -2962,20 +4722,45 @@ impl ErrorSinkRaw {
     fn new() -> ErrorSinkRaw {
         ErrorSinkRaw {
             scopes: Vec::new(),
+            next_scope_id: 0,
             uncaptured_handler: Box::from(default_error_handler),
         }
     }
 
+    fn push_scope(&mut self, filter: crate::ErrorFilter) {
+        self.scopes.push(ErrorScope {
+            id: self.next_scope_id,
+            error: None,
+            filter,
+        });
+        self.next_scope_id += 1;
+    }
+
+    /// Returns a limit for `report_error` that excludes the scopes pushed after this call.
+    fn scope_limit(&self) -> u64 {
+        self.next_scope_id
+    }
+
     fn handle_error(&mut self, err: crate::Error) {
+        let Some(err) = capture_error(err) else {
+            return;
+        };
+        self.report_error(err, self.scope_limit());
+    }
+
+    /// Reports `err` to the innermost scope with a matching filter among those with an id
+    /// below `scope_limit`, or to the uncaptured error handler.
+    fn report_error(&mut self, err: crate::Error, scope_limit: u64) {
         let filter = match err {
             crate::Error::OutOfMemory { .. } => crate::ErrorFilter::OutOfMemory,
             crate::Error::Validation { .. } => crate::ErrorFilter::Validation,
+            crate::Error::Internal { .. } => crate::ErrorFilter::Internal,
         };
         match self
             .scopes
             .iter_mut()
             .rev()
-            .find(|scope| scope.filter == filter)
+            .find(|scope| scope.id < scope_limit && scope.filter == filter)
         {
             Some(scope) => {
                 if scope.error.is_none() {
@@ -2995,6 +4780,48 @@ impl fmt::Debug for ErrorSinkRaw {
     }
 }
 
+thread_local! {
+    /// Errors captured on this thread by `Device::try_create_*`, innermost capture last.
+    static CAPTURED_ERRORS: RefCell<Vec<Option<crate::Error>>> = RefCell::new(Vec::new());
+}
+
+/// Keeps `err` if errors are being captured on this thread, or returns it to be reported.
+fn capture_error(err: crate::Error) -> Option<crate::Error> {
+    CAPTURED_ERRORS.with(|captured| match captured.borrow_mut().last_mut() {
+        Some(slot) => {
+            // Only the first error is kept, like error scopes do.
+            if slot.is_none() {
+                *slot = Some(err);
+            }
+            None
+        }
+        None => Some(err),
+    })
+}
+
+/// Returns the filter for errors caused by `source`, if it is not a validation error.
+fn error_filter(source: &(dyn Error + 'static)) -> Option<crate::ErrorFilter> {
+    use wgc::{device::DeviceError, pipeline};
+
+    match source.downcast_ref::<DeviceError>() {
+        Some(DeviceError::OutOfMemory) => return Some(crate::ErrorFilter::OutOfMemory),
+        Some(DeviceError::ResourceCreationFailed) => return Some(crate::ErrorFilter::Internal),
+        _ => {}
+    }
+    // The backend failed to compile a shader that passed validation.
+    let internal = matches!(
+        source.downcast_ref::<pipeline::CreateShaderModuleError>(),
+        Some(pipeline::CreateShaderModuleError::Generation)
+    ) || matches!(
+        source.downcast_ref::<pipeline::CreateRenderPipelineError>(),
+        Some(pipeline::CreateRenderPipelineError::Internal { .. })
+    ) || matches!(
+        source.downcast_ref::<pipeline::CreateComputePipelineError>(),
+        Some(pipeline::CreateComputePipelineError::Internal(_))
+    );
+    internal.then_some(crate::ErrorFilter::Internal)
+}
+
 fn default_error_handler(err: crate::Error) {
     log::error!("Handling wgpu errors as fatal by default");
     panic!("wgpu error: {err}\n");
@@ -1,21 +1,26 @@
 use std::{any::Any, fmt::Debug, future::Future, num::NonZeroU64, ops::Range, pin::Pin, sync::Arc};
 
 use wgt::{
-    strict_assert, strict_assert_eq, AdapterInfo, BufferAddress, BufferSize, Color,
-    DeviceLostReason, DownlevelCapabilities, DynamicOffset, Extent3d, Features, ImageDataLayout,
-    ImageSubresourceRange, IndexFormat, Limits, ShaderStages, SurfaceStatus, TextureFormat,
-    TextureFormatFeatures, WasmNotSend, WasmNotSendSync,
+    strict_assert, strict_assert_eq, AdapterInfo, BufferAddress, BufferSize, Color, DebugMessage,
+    DeviceLostReason, DownlevelCapabilities, DynamicOffset, Extent3d, FeatureSupport, Features,
+    ImageDataLayout, ImageSubresourceRange, IndexFormat, IndirectArgument, Limits, PollStatus,
+    ShaderStages, SurfaceStatus, TextureFormat, TextureFormatFeatures, WasmNotSend,
+    WasmNotSendSync,
 };
 
 use crate::{
-    AnyWasmNotSendSync, BindGroupDescriptor, BindGroupLayoutDescriptor, Buffer, BufferAsyncError,
-    BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor, ComputePipelineDescriptor,
-    DeviceDescriptor, Error, ErrorFilter, ImageCopyBuffer, ImageCopyTexture, Maintain,
-    MaintainResult, MapMode, PipelineLayoutDescriptor, QuerySetDescriptor, RenderBundleDescriptor,
+    AnyWasmNotSendSync, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BlasBuildEntry, Buffer, BufferAsyncError, BufferDescriptor,
+    CommandEncoderDescriptor, CompilationInfo, ComputePassDescriptor, ComputePipelineDescriptor,
+    CreateBlasDescriptor, CreateTlasDescriptor, DeviceDescriptor, Error, ErrorFilter,
+    ExternalFenceDescriptor, ImageCopyBuffer, ImageCopyTexture, Maintain, MaintainResult, MapMode,
+    MemoryHeapDescriptor, MeshPipelineDescriptor, PipelineCacheDescriptor,
+    PipelineLayoutDescriptor, QuerySetDescriptor, RenderBundleDescriptor,
     RenderBundleEncoderDescriptor, RenderPassDescriptor, RenderPipelineDescriptor,
-    RequestAdapterOptions, RequestDeviceError, SamplerDescriptor, ShaderModuleDescriptor,
-    ShaderModuleDescriptorSpirV, SurfaceTargetUnsafe, Texture, TextureDescriptor,
-    TextureViewDescriptor, UncapturedErrorHandler,
+    RequestAdapterOptions, RequestDeviceError, ResolveMode, SamplerDescriptor,
+    ShaderModuleDescriptor, ShaderModuleDescriptorSpirV, SurfaceTargetUnsafe, Texture,
+    TextureDescriptor, TextureTileBinding, TextureViewDescriptor, TilePoolDescriptor,
+    TlasBuildEntry, UncapturedErrorHandler,
 };
 
 /// Meta trait for an id tracked by a context.
@@ -53,12 +58,24 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
     type TextureData: ContextData;
     type QuerySetId: ContextId + WasmNotSendSync;
     type QuerySetData: ContextData;
+    type TilePoolId: ContextId + WasmNotSendSync;
+    type TilePoolData: ContextData;
+    type MemoryHeapId: ContextId + WasmNotSendSync;
+    type MemoryHeapData: ContextData;
+    type ExternalFenceId: ContextId + WasmNotSendSync;
+    type ExternalFenceData: ContextData;
+    type BlasId: ContextId + WasmNotSendSync;
+    type BlasData: ContextData;
+    type TlasId: ContextId + WasmNotSendSync;
+    type TlasData: ContextData;
     type PipelineLayoutId: ContextId + WasmNotSendSync;
     type PipelineLayoutData: ContextData;
     type RenderPipelineId: ContextId + WasmNotSendSync;
     type RenderPipelineData: ContextData;
     type ComputePipelineId: ContextId + WasmNotSendSync;
     type ComputePipelineData: ContextData;
+    type PipelineCacheId: ContextId + WasmNotSendSync;
+    type PipelineCacheData: ContextData;
     type CommandEncoderId: ContextId + WasmNotSendSync;
     type CommandEncoderData: ContextData;
     type ComputePassId: ContextId;
@@ -94,6 +111,14 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         > + WasmNotSend
         + 'static;
     type PopErrorScopeFuture: Future<Output = Option<Error>> + WasmNotSend + 'static;
+    type DeviceLostFuture: Future<Output = (DeviceLostReason, String)> + WasmNotSend + 'static;
+    type CompilationInfoFuture: Future<Output = CompilationInfo> + WasmNotSend + 'static;
+    type CreateRenderPipelineFuture: Future<Output = (Self::RenderPipelineId, Self::RenderPipelineData)>
+        + WasmNotSend
+        + 'static;
+    type CreateComputePipelineFuture: Future<Output = (Self::ComputePipelineId, Self::ComputePipelineData)>
+        + WasmNotSend
+        + 'static;
 
     fn init(instance_desc: wgt::InstanceDescriptor) -> Self;
     unsafe fn instance_create_surface(
@@ -112,6 +137,11 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         trace_dir: Option<&std::path::Path>,
     ) -> Self::RequestDeviceFuture;
     fn instance_poll_all_devices(&self, force_wait: bool) -> bool;
+    fn instance_set_debug_callback(&self, callback: Option<DebugCallback>);
+    /// Starts capturing the errors raised on this thread, instead of reporting them.
+    fn begin_error_capture(&self);
+    /// Stops the innermost capture started with `begin_error_capture`, returning its first error.
+    fn end_error_capture(&self) -> Option<Error>;
     fn adapter_is_surface_supported(
         &self,
         adapter: &Self::AdapterId,
@@ -124,6 +154,12 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         adapter: &Self::AdapterId,
         adapter_data: &Self::AdapterData,
     ) -> Features;
+    fn adapter_explain_feature(
+        &self,
+        adapter: &Self::AdapterId,
+        adapter_data: &Self::AdapterData,
+        feature: Features,
+    ) -> FeatureSupport;
     fn adapter_limits(&self, adapter: &Self::AdapterId, adapter_data: &Self::AdapterData)
         -> Limits;
     fn adapter_downlevel_capabilities(
@@ -147,6 +183,11 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         adapter: &Self::AdapterId,
         adapter_data: &Self::AdapterData,
     ) -> wgt::PresentationTimestamp;
+    fn adapter_max_additional_queues(
+        &self,
+        adapter: &Self::AdapterId,
+        adapter_data: &Self::AdapterData,
+    ) -> u32;
 
     fn surface_get_capabilities(
         &self,
@@ -188,6 +229,11 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device: &Self::DeviceId,
         device_data: &Self::DeviceData,
     ) -> DownlevelCapabilities;
+    fn device_memory_architecture(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+    ) -> wgt::MemoryArchitecture;
     fn device_create_shader_module(
         &self,
         device: &Self::DeviceId,
@@ -201,6 +247,11 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         desc: &ShaderModuleDescriptorSpirV<'_>,
     ) -> (Self::ShaderModuleId, Self::ShaderModuleData);
+    fn shader_get_compilation_info(
+        &self,
+        shader: &Self::ShaderModuleId,
+        shader_data: &Self::ShaderModuleData,
+    ) -> Self::CompilationInfoFuture;
     fn device_create_bind_group_layout(
         &self,
         device: &Self::DeviceId,
@@ -225,12 +276,36 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         desc: &RenderPipelineDescriptor<'_>,
     ) -> (Self::RenderPipelineId, Self::RenderPipelineData);
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &RenderPipelineDescriptor<'_>,
+    ) -> Self::CreateRenderPipelineFuture;
+    fn device_create_mesh_pipeline(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &MeshPipelineDescriptor<'_>,
+    ) -> (Self::RenderPipelineId, Self::RenderPipelineData);
     fn device_create_compute_pipeline(
         &self,
         device: &Self::DeviceId,
         device_data: &Self::DeviceData,
         desc: &ComputePipelineDescriptor<'_>,
     ) -> (Self::ComputePipelineId, Self::ComputePipelineData);
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ComputePipelineDescriptor<'_>,
+    ) -> Self::CreateComputePipelineFuture;
+    unsafe fn device_create_pipeline_cache(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &PipelineCacheDescriptor<'_>,
+    ) -> (Self::PipelineCacheId, Self::PipelineCacheData);
     fn device_create_buffer(
         &self,
         device: &Self::DeviceId,
@@ -255,6 +330,109 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         desc: &QuerySetDescriptor<'_>,
     ) -> (Self::QuerySetId, Self::QuerySetData);
+    fn device_create_tile_pool(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TilePoolDescriptor<'_>,
+    ) -> (Self::TilePoolId, Self::TilePoolData);
+    fn device_create_memory_heap(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &MemoryHeapDescriptor<'_>,
+    ) -> (Self::MemoryHeapId, Self::MemoryHeapData);
+    fn device_create_buffer_in_heap(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &BufferDescriptor<'_>,
+        heap: &Self::MemoryHeapId,
+        heap_data: &Self::MemoryHeapData,
+        offset: BufferAddress,
+    ) -> (Self::BufferId, Self::BufferData);
+    fn device_create_texture_in_heap(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+        heap: &Self::MemoryHeapId,
+        heap_data: &Self::MemoryHeapData,
+        offset: BufferAddress,
+    ) -> (Self::TextureId, Self::TextureData);
+    #[cfg(native)]
+    unsafe fn device_import_external_texture(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+        source: wgt::ExternalImageSource,
+    ) -> (Self::TextureId, Self::TextureData);
+    fn device_create_external_fence(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ExternalFenceDescriptor<'_>,
+    ) -> (Self::ExternalFenceId, Self::ExternalFenceData);
+    #[cfg(native)]
+    unsafe fn device_import_external_fence(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ExternalFenceDescriptor<'_>,
+        handle: wgt::ExternalFenceHandle,
+    ) -> (Self::ExternalFenceId, Self::ExternalFenceData);
+    #[cfg(native)]
+    fn external_fence_export(
+        &self,
+        external_fence: &Self::ExternalFenceId,
+        external_fence_data: &Self::ExternalFenceData,
+    ) -> wgt::ExternalFenceHandle;
+    fn device_create_external_buffer(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &BufferDescriptor<'_>,
+    ) -> (Self::BufferId, Self::BufferData);
+    #[cfg(native)]
+    unsafe fn device_import_external_buffer(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &BufferDescriptor<'_>,
+        memory: wgt::ExternalMemory,
+    ) -> (Self::BufferId, Self::BufferData);
+    #[cfg(native)]
+    fn buffer_export_external_memory(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+    ) -> wgt::ExternalMemory;
+    fn device_buffer_memory_requirements(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &BufferDescriptor<'_>,
+    ) -> wgt::MemoryRequirements;
+    fn device_texture_memory_requirements(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+    ) -> wgt::MemoryRequirements;
+    fn device_create_blas(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &CreateBlasDescriptor<'_>,
+        sizes: wgt::BlasGeometrySizeDescriptors,
+    ) -> (Self::BlasId, Option<u64>, Self::BlasData);
+    fn device_create_tlas(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &CreateTlasDescriptor<'_>,
+    ) -> (Self::TlasId, Self::TlasData);
     fn device_create_command_encoder(
         &self,
         device: &Self::DeviceId,
@@ -274,6 +452,35 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         device_lost_callback: DeviceLostCallback,
     );
+    fn device_lost(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+    ) -> Self::DeviceLostFuture;
+    fn device_set_debug_printf_callback(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        debug_printf_callback: DebugPrintfCallback,
+    );
+    fn device_set_watchdog(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        watchdog: Option<(std::time::Duration, WatchdogCallback)>,
+    );
+    fn device_set_automatic_polling(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        enabled: bool,
+    );
+    fn device_set_command_disassembly(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        enabled: bool,
+    );
     fn device_destroy(&self, device: &Self::DeviceId, device_data: &Self::DeviceData);
     fn device_mark_lost(
         &self,
@@ -281,6 +488,7 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         message: &str,
     );
+    fn device_mark_lost_by_driver(&self, device: &Self::DeviceId, device_data: &Self::DeviceData);
     fn queue_drop(&self, queue: &Self::QueueId, queue_data: &Self::QueueData);
     fn device_poll(
         &self,
@@ -288,6 +496,18 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         maintain: Maintain,
     ) -> MaintainResult;
+    fn device_poll_until(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        submission_index: &Self::SubmissionIndexData,
+        timeout: std::time::Duration,
+    ) -> PollStatus;
+    fn device_additional_queues(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+    ) -> Vec<(Self::QueueId, Self::QueueData)>;
     fn device_on_uncaptured_error(
         &self,
         device: &Self::DeviceId,
@@ -321,6 +541,17 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         sub_range: Range<BufferAddress>,
     ) -> Box<dyn BufferMappedRange>;
     fn buffer_unmap(&self, buffer: &Self::BufferId, buffer_data: &Self::BufferData);
+    fn buffer_unmap_range(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        range: Range<BufferAddress>,
+    );
+    fn buffer_device_address(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+    ) -> BufferAddress;
     fn texture_create_view(
         &self,
         texture: &Self::TextureId,
@@ -341,6 +572,19 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
     );
     fn sampler_drop(&self, sampler: &Self::SamplerId, sampler_data: &Self::SamplerData);
     fn query_set_drop(&self, query_set: &Self::QuerySetId, query_set_data: &Self::QuerySetData);
+    fn tile_pool_drop(&self, tile_pool: &Self::TilePoolId, tile_pool_data: &Self::TilePoolData);
+    fn memory_heap_drop(
+        &self,
+        memory_heap: &Self::MemoryHeapId,
+        memory_heap_data: &Self::MemoryHeapData,
+    );
+    fn external_fence_drop(
+        &self,
+        external_fence: &Self::ExternalFenceId,
+        external_fence_data: &Self::ExternalFenceData,
+    );
+    fn blas_drop(&self, blas: &Self::BlasId, blas_data: &Self::BlasData);
+    fn tlas_drop(&self, tlas: &Self::TlasId, tlas_data: &Self::TlasData);
     fn bind_group_drop(
         &self,
         bind_group: &Self::BindGroupId,
@@ -371,6 +615,11 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         command_buffer: &Self::CommandBufferId,
         command_buffer_data: &Self::CommandBufferData,
     );
+    fn command_buffer_disassembly(
+        &self,
+        command_buffer: &Self::CommandBufferId,
+        command_buffer_data: &Self::CommandBufferData,
+    ) -> Option<String>;
     fn render_bundle_drop(
         &self,
         render_bundle: &Self::RenderBundleId,
@@ -386,6 +635,16 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         pipeline: &Self::RenderPipelineId,
         pipeline_data: &Self::RenderPipelineData,
     );
+    fn pipeline_cache_drop(
+        &self,
+        cache: &Self::PipelineCacheId,
+        cache_data: &Self::PipelineCacheData,
+    );
+    fn pipeline_cache_get_data(
+        &self,
+        cache: &Self::PipelineCacheId,
+        cache_data: &Self::PipelineCacheData,
+    ) -> Option<Vec<u8>>;
 
     fn compute_pipeline_get_bind_group_layout(
         &self,
@@ -399,6 +658,11 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         pipeline_data: &Self::RenderPipelineData,
         index: u32,
     ) -> (Self::BindGroupLayoutId, Self::BindGroupLayoutData);
+    fn bind_group_layout_entries(
+        &self,
+        bind_group_layout: &Self::BindGroupLayoutId,
+        bind_group_layout_data: &Self::BindGroupLayoutData,
+    ) -> Vec<BindGroupLayoutEntry>;
 
     #[allow(clippy::too_many_arguments)]
     fn command_encoder_copy_buffer_to_buffer(
@@ -437,6 +701,15 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         destination: ImageCopyTexture<'_>,
         copy_size: Extent3d,
     );
+    fn command_encoder_resolve_texture(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+        source: ImageCopyTexture<'_>,
+        destination: ImageCopyTexture<'_>,
+        copy_size: Extent3d,
+        mode: ResolveMode,
+    );
 
     fn command_encoder_begin_compute_pass(
         &self,
@@ -503,6 +776,11 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         encoder: &Self::CommandEncoderId,
         encoder_data: &Self::CommandEncoderData,
     );
+    fn command_encoder_aliasing_barrier(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+    );
 
     fn command_encoder_write_timestamp(
         &self,
@@ -525,6 +803,13 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         destination_data: &Self::BufferData,
         destination_offset: BufferAddress,
     );
+    fn command_encoder_build_acceleration_structures_unsafe_tlas(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+        blas: &[BlasBuildEntry<'_>],
+        tlas: &[TlasBuildEntry<'_>],
+    );
 
     fn render_bundle_encoder_finish(
         &self,
@@ -565,6 +850,23 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         offset: BufferAddress,
         staging_buffer: &dyn QueueWriteBuffer,
     );
+    fn queue_write_buffer_regions(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        regions: &[(BufferAddress, &[u8])],
+    );
+    fn queue_write_staging_buffer_regions(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        regions: &[(BufferAddress, BufferSize)],
+        staging_buffer: &dyn QueueWriteBuffer,
+    );
     fn queue_write_texture(
         &self,
         queue: &Self::QueueId,
@@ -594,6 +896,40 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         queue: &Self::QueueId,
         queue_data: &Self::QueueData,
     ) -> f32;
+    fn queue_get_timestamp_calibration(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+    ) -> Option<wgt::TimestampCalibration>;
+    fn queue_bind_texture_pages(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        texture: &Texture,
+        bindings: &[TextureTileBinding<'_>],
+    );
+    fn queue_wait_for_submission(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        submission_index: &Self::SubmissionIndexData,
+    );
+    fn queue_wait_for_external_fence(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        external_fence: &Self::ExternalFenceId,
+        external_fence_data: &Self::ExternalFenceData,
+        value: u64,
+    );
+    fn queue_signal_external_fence(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        external_fence: &Self::ExternalFenceId,
+        external_fence_data: &Self::ExternalFenceData,
+        value: u64,
+    );
     fn queue_on_submitted_work_done(
         &self,
         queue: &Self::QueueId,
@@ -803,6 +1139,17 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         count_buffer_offset: BufferAddress,
         max_count: u32,
     );
+    fn render_bundle_encoder_begin_occlusion_query(
+        &self,
+        encoder: &mut Self::RenderBundleEncoderId,
+        encoder_data: &mut Self::RenderBundleEncoderData,
+        query_index: u32,
+    );
+    fn render_bundle_encoder_end_occlusion_query(
+        &self,
+        encoder: &mut Self::RenderBundleEncoderId,
+        encoder_data: &mut Self::RenderBundleEncoderData,
+    );
 
     fn render_pass_set_pipeline(
         &self,
@@ -820,6 +1167,13 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         bind_group_data: &Self::BindGroupData,
         offsets: &[DynamicOffset],
     );
+    fn render_pass_set_bind_group_immediate(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        index: u32,
+        entries: &[BindGroupEntry<'_>],
+    );
     #[allow(clippy::too_many_arguments)]
     fn render_pass_set_index_buffer(
         &self,
@@ -881,6 +1235,22 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         indirect_buffer_data: &Self::BufferData,
         indirect_offset: BufferAddress,
     );
+    fn render_pass_draw_mesh_tasks(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    );
+    fn render_pass_draw_mesh_tasks_indirect(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        indirect_buffer: &Self::BufferId,
+        indirect_buffer_data: &Self::BufferData,
+        indirect_offset: BufferAddress,
+    );
     fn render_pass_multi_draw_indirect(
         &self,
         pass: &mut Self::RenderPassId,
@@ -925,6 +1295,31 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         count_buffer_offset: BufferAddress,
         max_count: u32,
     );
+    #[allow(clippy::too_many_arguments)]
+    fn render_pass_execute_indirect(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        arguments: &[IndirectArgument],
+        indirect_buffer: &Self::BufferId,
+        indirect_buffer_data: &Self::BufferData,
+        indirect_offset: BufferAddress,
+        max_count: u32,
+    );
+    #[allow(clippy::too_many_arguments)]
+    fn render_pass_execute_indirect_count(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        arguments: &[IndirectArgument],
+        indirect_buffer: &Self::BufferId,
+        indirect_buffer_data: &Self::BufferData,
+        indirect_offset: BufferAddress,
+        count_buffer: &Self::BufferId,
+        count_buffer_data: &Self::BufferData,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    );
     fn render_pass_set_blend_constant(
         &self,
         pass: &mut Self::RenderPassId,
@@ -958,6 +1353,12 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         pass_data: &mut Self::RenderPassData,
         reference: u32,
     );
+    fn render_pass_set_shading_rate(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        rate: wgt::ShadingRate,
+    );
     fn render_pass_insert_debug_marker(
         &self,
         pass: &mut Self::RenderPassId,
@@ -975,6 +1376,16 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         pass: &mut Self::RenderPassId,
         pass_data: &mut Self::RenderPassData,
     );
+    fn render_pass_resume(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    );
+    fn render_pass_suspend(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    );
     fn render_pass_write_timestamp(
         &self,
         pass: &mut Self::RenderPassId,
@@ -1007,25 +1418,59 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         pass: &mut Self::RenderPassId,
         pass_data: &mut Self::RenderPassData,
     );
-    fn render_pass_execute_bundles(
+    fn render_pass_begin_conditional_rendering(
         &self,
         pass: &mut Self::RenderPassId,
         pass_data: &mut Self::RenderPassData,
-        render_bundles: &mut dyn Iterator<Item = (Self::RenderBundleId, &Self::RenderBundleData)>,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        offset: BufferAddress,
     );
-}
-
-/// Object id.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct ObjectId {
-    /// ID that is unique at any given time
-    id: Option<NonZeroU64>,
-    /// ID that is unique at all times
-    global_id: Option<NonZeroU64>,
-}
-
-impl ObjectId {
-    pub(crate) const UNUSED: Self = ObjectId {
+    fn render_pass_end_conditional_rendering(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    );
+    #[allow(clippy::too_many_arguments)]
+    fn render_pass_set_vertex_capture_buffer(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        slot: u32,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    );
+    fn render_pass_begin_vertex_capture(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    );
+    fn render_pass_end_vertex_capture(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+    );
+    fn render_pass_execute_bundles(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        render_bundles: &mut dyn Iterator<Item = (Self::RenderBundleId, &Self::RenderBundleData)>,
+    );
+}
+
+/// Object id.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ObjectId {
+    /// ID that is unique at any given time
+    id: Option<NonZeroU64>,
+    /// ID that is unique at all times
+    global_id: Option<NonZeroU64>,
+}
+
+impl ObjectId {
+    pub(crate) const UNUSED: Self = ObjectId {
         id: None,
         global_id: None,
     };
@@ -1109,6 +1554,22 @@ pub(crate) type AdapterRequestDeviceFuture =
 pub(crate) type AdapterRequestDeviceFuture =
     Box<dyn Future<Output = Result<DeviceRequest, RequestDeviceError>>>;
 
+#[cfg(send_sync)]
+pub(crate) type DeviceLostFuture = Box<dyn Future<Output = (DeviceLostReason, String)> + Send>;
+#[cfg(not(send_sync))]
+pub(crate) type DeviceLostFuture = Box<dyn Future<Output = (DeviceLostReason, String)>>;
+
+#[cfg(send_sync)]
+pub(crate) type ShaderCompilationInfoFuture = Box<dyn Future<Output = CompilationInfo> + Send>;
+#[cfg(not(send_sync))]
+pub(crate) type ShaderCompilationInfoFuture = Box<dyn Future<Output = CompilationInfo>>;
+
+#[cfg(send_sync)]
+pub(crate) type DeviceCreatePipelineFuture =
+    Box<dyn Future<Output = (ObjectId, Box<crate::Data>)> + Send>;
+#[cfg(not(send_sync))]
+pub(crate) type DeviceCreatePipelineFuture = Box<dyn Future<Output = (ObjectId, Box<crate::Data>)>>;
+
 #[cfg(send_sync)]
 pub type InstanceRequestAdapterFuture =
     Box<dyn Future<Output = Option<(ObjectId, Box<crate::Data>)>> + Send>;
@@ -1129,6 +1590,13 @@ pub type SubmittedWorkDoneCallback = Box<dyn FnOnce() + 'static>;
 pub type DeviceLostCallback = Box<dyn Fn(DeviceLostReason, String) + Send + 'static>;
 #[cfg(not(send_sync))]
 pub type DeviceLostCallback = Box<dyn Fn(DeviceLostReason, String) + 'static>;
+// wgpu-hal reports debug messages from any thread on every platform.
+pub type DebugCallback = Arc<dyn Fn(&DebugMessage) + Send + Sync + 'static>;
+pub type WatchdogCallback = Box<dyn Fn(crate::SubmissionTimeout) + Send + 'static>;
+#[cfg(send_sync)]
+pub type DebugPrintfCallback = Box<dyn Fn(&str) + Send + Sync + 'static>;
+#[cfg(not(send_sync))]
+pub type DebugPrintfCallback = Box<dyn Fn(&str) + 'static>;
 
 /// An object safe variant of [`Context`] implemented by all types that implement [`Context`].
 pub(crate) trait DynContext: Debug + WasmNotSendSync {
@@ -1152,6 +1620,9 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
     ) -> Pin<AdapterRequestDeviceFuture>;
 
     fn instance_poll_all_devices(&self, force_wait: bool) -> bool;
+    fn instance_set_debug_callback(&self, callback: Option<DebugCallback>);
+    fn begin_error_capture(&self);
+    fn end_error_capture(&self) -> Option<Error>;
     fn adapter_is_surface_supported(
         &self,
         adapter: &ObjectId,
@@ -1160,6 +1631,12 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         surface_data: &crate::Data,
     ) -> bool;
     fn adapter_features(&self, adapter: &ObjectId, adapter_data: &crate::Data) -> Features;
+    fn adapter_explain_feature(
+        &self,
+        adapter: &ObjectId,
+        adapter_data: &crate::Data,
+        feature: Features,
+    ) -> FeatureSupport;
     fn adapter_limits(&self, adapter: &ObjectId, adapter_data: &crate::Data) -> Limits;
     fn adapter_downlevel_capabilities(
         &self,
@@ -1178,6 +1655,7 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         adapter: &ObjectId,
         adapter_data: &crate::Data,
     ) -> wgt::PresentationTimestamp;
+    fn adapter_max_additional_queues(&self, adapter: &ObjectId, adapter_data: &crate::Data) -> u32;
 
     fn surface_get_capabilities(
         &self,
@@ -1214,6 +1692,11 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         device: &ObjectId,
         device_data: &crate::Data,
     ) -> DownlevelCapabilities;
+    fn device_memory_architecture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+    ) -> wgt::MemoryArchitecture;
     fn device_create_shader_module(
         &self,
         device: &ObjectId,
@@ -1227,6 +1710,11 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         device_data: &crate::Data,
         desc: &ShaderModuleDescriptorSpirV<'_>,
     ) -> (ObjectId, Box<crate::Data>);
+    fn shader_get_compilation_info(
+        &self,
+        shader: &ObjectId,
+        shader_data: &crate::Data,
+    ) -> Pin<ShaderCompilationInfoFuture>;
     fn device_create_bind_group_layout(
         &self,
         device: &ObjectId,
@@ -1251,12 +1739,36 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         device_data: &crate::Data,
         desc: &RenderPipelineDescriptor<'_>,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &RenderPipelineDescriptor<'_>,
+    ) -> Pin<DeviceCreatePipelineFuture>;
+    fn device_create_mesh_pipeline(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &MeshPipelineDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
     fn device_create_compute_pipeline(
         &self,
         device: &ObjectId,
         device_data: &crate::Data,
         desc: &ComputePipelineDescriptor<'_>,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ComputePipelineDescriptor<'_>,
+    ) -> Pin<DeviceCreatePipelineFuture>;
+    unsafe fn device_create_pipeline_cache(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &PipelineCacheDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
     fn device_create_buffer(
         &self,
         device: &ObjectId,
@@ -1281,6 +1793,109 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         device_data: &crate::Data,
         desc: &QuerySetDescriptor<'_>,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_tile_pool(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TilePoolDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_memory_heap(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &MemoryHeapDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_buffer_in_heap(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+        heap: &ObjectId,
+        heap_data: &crate::Data,
+        offset: BufferAddress,
+    ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_texture_in_heap(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+        heap: &ObjectId,
+        heap_data: &crate::Data,
+        offset: BufferAddress,
+    ) -> (ObjectId, Box<crate::Data>);
+    #[cfg(native)]
+    unsafe fn device_import_external_texture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+        source: wgt::ExternalImageSource,
+    ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_external_fence(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ExternalFenceDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
+    #[cfg(native)]
+    unsafe fn device_import_external_fence(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ExternalFenceDescriptor<'_>,
+        handle: wgt::ExternalFenceHandle,
+    ) -> (ObjectId, Box<crate::Data>);
+    #[cfg(native)]
+    fn external_fence_export(
+        &self,
+        external_fence: &ObjectId,
+        external_fence_data: &crate::Data,
+    ) -> wgt::ExternalFenceHandle;
+    fn device_create_external_buffer(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
+    #[cfg(native)]
+    unsafe fn device_import_external_buffer(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+        memory: wgt::ExternalMemory,
+    ) -> (ObjectId, Box<crate::Data>);
+    #[cfg(native)]
+    fn buffer_export_external_memory(
+        &self,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+    ) -> wgt::ExternalMemory;
+    fn device_buffer_memory_requirements(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+    ) -> wgt::MemoryRequirements;
+    fn device_texture_memory_requirements(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+    ) -> wgt::MemoryRequirements;
+    fn device_create_blas(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &CreateBlasDescriptor<'_>,
+        sizes: wgt::BlasGeometrySizeDescriptors,
+    ) -> (ObjectId, Option<u64>, Box<crate::Data>);
+    fn device_create_tlas(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &CreateTlasDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
     fn device_create_command_encoder(
         &self,
         device: &ObjectId,
@@ -1300,8 +1915,34 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         device_data: &crate::Data,
         device_lost_callback: DeviceLostCallback,
     );
+    fn device_lost(&self, device: &ObjectId, device_data: &crate::Data) -> Pin<DeviceLostFuture>;
+    fn device_set_debug_printf_callback(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        debug_printf_callback: DebugPrintfCallback,
+    );
+    fn device_set_watchdog(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        watchdog: Option<(std::time::Duration, WatchdogCallback)>,
+    );
+    fn device_set_automatic_polling(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        enabled: bool,
+    );
+    fn device_set_command_disassembly(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        enabled: bool,
+    );
     fn device_destroy(&self, device: &ObjectId, device_data: &crate::Data);
     fn device_mark_lost(&self, device: &ObjectId, device_data: &crate::Data, message: &str);
+    fn device_mark_lost_by_driver(&self, device: &ObjectId, device_data: &crate::Data);
     fn queue_drop(&self, queue: &ObjectId, queue_data: &crate::Data);
     fn device_poll(
         &self,
@@ -1309,6 +1950,18 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         device_data: &crate::Data,
         maintain: Maintain,
     ) -> MaintainResult;
+    fn device_poll_until(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        submission_index: &crate::Data,
+        timeout: std::time::Duration,
+    ) -> PollStatus;
+    fn device_additional_queues(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+    ) -> Vec<(ObjectId, Box<crate::Data>)>;
     fn device_on_uncaptured_error(
         &self,
         device: &ObjectId,
@@ -1341,6 +1994,13 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         sub_range: Range<BufferAddress>,
     ) -> Box<dyn BufferMappedRange>;
     fn buffer_unmap(&self, buffer: &ObjectId, buffer_data: &crate::Data);
+    fn buffer_unmap_range(
+        &self,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        range: Range<BufferAddress>,
+    );
+    fn buffer_device_address(&self, buffer: &ObjectId, buffer_data: &crate::Data) -> BufferAddress;
     fn texture_create_view(
         &self,
         texture: &ObjectId,
@@ -1357,6 +2017,11 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
     fn texture_view_drop(&self, texture_view: &ObjectId, texture_view_data: &crate::Data);
     fn sampler_drop(&self, sampler: &ObjectId, sampler_data: &crate::Data);
     fn query_set_drop(&self, query_set: &ObjectId, query_set_data: &crate::Data);
+    fn tile_pool_drop(&self, tile_pool: &ObjectId, tile_pool_data: &crate::Data);
+    fn memory_heap_drop(&self, memory_heap: &ObjectId, memory_heap_data: &crate::Data);
+    fn external_fence_drop(&self, external_fence: &ObjectId, external_fence_data: &crate::Data);
+    fn blas_drop(&self, blas: &ObjectId, blas_data: &crate::Data);
+    fn tlas_drop(&self, tlas: &ObjectId, tlas_data: &crate::Data);
     fn bind_group_drop(&self, bind_group: &ObjectId, bind_group_data: &crate::Data);
     fn bind_group_layout_drop(
         &self,
@@ -1367,9 +2032,20 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
     fn shader_module_drop(&self, shader_module: &ObjectId, shader_module_data: &crate::Data);
     fn command_encoder_drop(&self, command_encoder: &ObjectId, command_encoder_data: &crate::Data);
     fn command_buffer_drop(&self, command_buffer: &ObjectId, command_buffer_data: &crate::Data);
+    fn command_buffer_disassembly(
+        &self,
+        command_buffer: &ObjectId,
+        command_buffer_data: &crate::Data,
+    ) -> Option<String>;
     fn render_bundle_drop(&self, render_bundle: &ObjectId, render_bundle_data: &crate::Data);
     fn compute_pipeline_drop(&self, pipeline: &ObjectId, pipeline_data: &crate::Data);
     fn render_pipeline_drop(&self, pipeline: &ObjectId, pipeline_data: &crate::Data);
+    fn pipeline_cache_drop(&self, cache: &ObjectId, cache_data: &crate::Data);
+    fn pipeline_cache_get_data(
+        &self,
+        cache: &ObjectId,
+        cache_data: &crate::Data,
+    ) -> Option<Vec<u8>>;
 
     fn compute_pipeline_get_bind_group_layout(
         &self,
@@ -1383,6 +2059,11 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         pipeline_data: &crate::Data,
         index: u32,
     ) -> (ObjectId, Box<crate::Data>);
+    fn bind_group_layout_entries(
+        &self,
+        bind_group_layout: &ObjectId,
+        bind_group_layout_data: &crate::Data,
+    ) -> Vec<BindGroupLayoutEntry>;
 
     #[allow(clippy::too_many_arguments)]
     fn command_encoder_copy_buffer_to_buffer(
@@ -1421,6 +2102,15 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         destination: ImageCopyTexture<'_>,
         copy_size: Extent3d,
     );
+    fn command_encoder_resolve_texture(
+        &self,
+        encoder: &ObjectId,
+        encoder_data: &crate::Data,
+        source: ImageCopyTexture<'_>,
+        destination: ImageCopyTexture<'_>,
+        copy_size: Extent3d,
+        mode: ResolveMode,
+    );
 
     fn command_encoder_begin_compute_pass(
         &self,
@@ -1483,6 +2173,7 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         label: &str,
     );
     fn command_encoder_pop_debug_group(&self, encoder: &ObjectId, encoder_data: &crate::Data);
+    fn command_encoder_aliasing_barrier(&self, encoder: &ObjectId, encoder_data: &crate::Data);
 
     fn command_encoder_write_timestamp(
         &self,
@@ -1505,6 +2196,13 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         destination_data: &crate::Data,
         destination_offset: BufferAddress,
     );
+    fn command_encoder_build_acceleration_structures_unsafe_tlas(
+        &self,
+        encoder: &ObjectId,
+        encoder_data: &crate::Data,
+        blas: &[BlasBuildEntry<'_>],
+        tlas: &[TlasBuildEntry<'_>],
+    );
 
     fn render_bundle_encoder_finish(
         &self,
@@ -1545,6 +2243,23 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         offset: BufferAddress,
         staging_buffer: &dyn QueueWriteBuffer,
     );
+    fn queue_write_buffer_regions(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        regions: &[(BufferAddress, &[u8])],
+    );
+    fn queue_write_staging_buffer_regions(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        regions: &[(BufferAddress, BufferSize)],
+        staging_buffer: &dyn QueueWriteBuffer,
+    );
     fn queue_write_texture(
         &self,
         queue: &ObjectId,
@@ -1570,6 +2285,40 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         command_buffers: &mut dyn Iterator<Item = (ObjectId, Box<crate::Data>)>,
     ) -> (ObjectId, Arc<crate::Data>);
     fn queue_get_timestamp_period(&self, queue: &ObjectId, queue_data: &crate::Data) -> f32;
+    fn queue_get_timestamp_calibration(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+    ) -> Option<wgt::TimestampCalibration>;
+    fn queue_bind_texture_pages(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        texture: &Texture,
+        bindings: &[TextureTileBinding<'_>],
+    );
+    fn queue_wait_for_submission(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        submission_index: &crate::Data,
+    );
+    fn queue_wait_for_external_fence(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        external_fence: &ObjectId,
+        external_fence_data: &crate::Data,
+        value: u64,
+    );
+    fn queue_signal_external_fence(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        external_fence: &ObjectId,
+        external_fence_data: &crate::Data,
+        value: u64,
+    );
     fn queue_on_submitted_work_done(
         &self,
         queue: &ObjectId,
@@ -1775,6 +2524,17 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         count_buffer_offset: BufferAddress,
         max_count: u32,
     );
+    fn render_bundle_encoder_begin_occlusion_query(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        query_index: u32,
+    );
+    fn render_bundle_encoder_end_occlusion_query(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+    );
 
     fn render_pass_set_pipeline(
         &self,
@@ -1792,6 +2552,13 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         bind_group_data: &crate::Data,
         offsets: &[DynamicOffset],
     );
+    fn render_pass_set_bind_group_immediate(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        index: u32,
+        entries: &[BindGroupEntry<'_>],
+    );
     #[allow(clippy::too_many_arguments)]
     fn render_pass_set_index_buffer(
         &self,
@@ -1853,6 +2620,22 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         indirect_buffer_data: &crate::Data,
         indirect_offset: BufferAddress,
     );
+    fn render_pass_draw_mesh_tasks(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    );
+    fn render_pass_draw_mesh_tasks_indirect(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        indirect_buffer: &ObjectId,
+        indirect_buffer_data: &crate::Data,
+        indirect_offset: BufferAddress,
+    );
     fn render_pass_multi_draw_indirect(
         &self,
         pass: &mut ObjectId,
@@ -1897,23 +2680,48 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         count_buffer_offset: BufferAddress,
         max_count: u32,
     );
-    fn render_pass_set_blend_constant(
-        &self,
-        pass: &mut ObjectId,
-        pass_data: &mut crate::Data,
-        color: Color,
-    );
-    fn render_pass_set_scissor_rect(
+    #[allow(clippy::too_many_arguments)]
+    fn render_pass_execute_indirect(
         &self,
         pass: &mut ObjectId,
         pass_data: &mut crate::Data,
-        x: u32,
-        y: u32,
-        width: u32,
-        height: u32,
+        arguments: &[IndirectArgument],
+        indirect_buffer: &ObjectId,
+        indirect_buffer_data: &crate::Data,
+        indirect_offset: BufferAddress,
+        max_count: u32,
     );
     #[allow(clippy::too_many_arguments)]
-    fn render_pass_set_viewport(
+    fn render_pass_execute_indirect_count(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        arguments: &[IndirectArgument],
+        indirect_buffer: &ObjectId,
+        indirect_buffer_data: &crate::Data,
+        indirect_offset: BufferAddress,
+        count_buffer: &ObjectId,
+        count_buffer_data: &crate::Data,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    );
+    fn render_pass_set_blend_constant(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        color: Color,
+    );
+    fn render_pass_set_scissor_rect(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    );
+    #[allow(clippy::too_many_arguments)]
+    fn render_pass_set_viewport(
         &self,
         pass: &mut ObjectId,
         pass_data: &mut crate::Data,
@@ -1930,6 +2738,12 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         pass_data: &mut crate::Data,
         reference: u32,
     );
+    fn render_pass_set_shading_rate(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        rate: wgt::ShadingRate,
+    );
     fn render_pass_insert_debug_marker(
         &self,
         pass: &mut ObjectId,
@@ -1943,6 +2757,8 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         group_label: &str,
     );
     fn render_pass_pop_debug_group(&self, pass: &mut ObjectId, pass_data: &mut crate::Data);
+    fn render_pass_resume(&self, pass: &mut ObjectId, pass_data: &mut crate::Data);
+    fn render_pass_suspend(&self, pass: &mut ObjectId, pass_data: &mut crate::Data);
     fn render_pass_write_timestamp(
         &self,
         pass: &mut ObjectId,
@@ -1971,6 +2787,32 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         pass: &mut ObjectId,
         pass_data: &mut crate::Data,
     );
+    fn render_pass_begin_conditional_rendering(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        offset: BufferAddress,
+    );
+    fn render_pass_end_conditional_rendering(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+    );
+    #[allow(clippy::too_many_arguments)]
+    fn render_pass_set_vertex_capture_buffer(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        slot: u32,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    );
+    fn render_pass_begin_vertex_capture(&self, pass: &mut ObjectId, pass_data: &mut crate::Data);
+    fn render_pass_end_vertex_capture(&self, pass: &mut ObjectId, pass_data: &mut crate::Data);
     fn render_pass_execute_bundles(
         &self,
         pass: &mut ObjectId,
@@ -2033,6 +2875,18 @@ where
         Context::instance_poll_all_devices(self, force_wait)
     }
 
+    fn instance_set_debug_callback(&self, callback: Option<DebugCallback>) {
+        Context::instance_set_debug_callback(self, callback)
+    }
+
+    fn begin_error_capture(&self) {
+        Context::begin_error_capture(self)
+    }
+
+    fn end_error_capture(&self) -> Option<Error> {
+        Context::end_error_capture(self)
+    }
+
     fn adapter_is_surface_supported(
         &self,
         adapter: &ObjectId,
@@ -2053,6 +2907,17 @@ where
         Context::adapter_features(self, &adapter, adapter_data)
     }
 
+    fn adapter_explain_feature(
+        &self,
+        adapter: &ObjectId,
+        adapter_data: &crate::Data,
+        feature: Features,
+    ) -> FeatureSupport {
+        let adapter = <T::AdapterId>::from(*adapter);
+        let adapter_data = downcast_ref(adapter_data);
+        Context::adapter_explain_feature(self, &adapter, adapter_data, feature)
+    }
+
     fn adapter_limits(&self, adapter: &ObjectId, adapter_data: &crate::Data) -> Limits {
         let adapter = <T::AdapterId>::from(*adapter);
         let adapter_data = downcast_ref(adapter_data);
@@ -2095,6 +2960,12 @@ where
         Context::adapter_get_presentation_timestamp(self, &adapter, adapter_data)
     }
 
+    fn adapter_max_additional_queues(&self, adapter: &ObjectId, adapter_data: &crate::Data) -> u32 {
+        let adapter = <T::AdapterId>::from(*adapter);
+        let adapter_data = downcast_ref(adapter_data);
+        Context::adapter_max_additional_queues(self, &adapter, adapter_data)
+    }
+
     fn surface_get_capabilities(
         &self,
         surface: &ObjectId,
@@ -2179,6 +3050,16 @@ where
         Context::device_downlevel_properties(self, &device, device_data)
     }
 
+    fn device_memory_architecture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+    ) -> wgt::MemoryArchitecture {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_memory_architecture(self, &device, device_data)
+    }
+
     fn device_create_shader_module(
         &self,
         device: &ObjectId,
@@ -2211,6 +3092,17 @@ where
         (shader_module.into(), Box::new(data) as _)
     }
 
+    fn shader_get_compilation_info(
+        &self,
+        shader: &ObjectId,
+        shader_data: &crate::Data,
+    ) -> Pin<ShaderCompilationInfoFuture> {
+        let shader = <T::ShaderModuleId>::from(*shader);
+        let shader_data = downcast_ref(shader_data);
+        let future = Context::shader_get_compilation_info(self, &shader, shader_data);
+        Box::pin(future)
+    }
+
     fn device_create_bind_group_layout(
         &self,
         device: &ObjectId,
@@ -2263,6 +3155,35 @@ where
         (render_pipeline.into(), Box::new(data) as _)
     }
 
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &RenderPipelineDescriptor<'_>,
+    ) -> Pin<DeviceCreatePipelineFuture> {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let future = Context::device_create_render_pipeline_async(self, &device, device_data, desc);
+
+        Box::pin(async move {
+            let (render_pipeline, data) = future.await;
+            (render_pipeline.into(), Box::new(data) as _)
+        })
+    }
+
+    fn device_create_mesh_pipeline(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &MeshPipelineDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (render_pipeline, data) =
+            Context::device_create_mesh_pipeline(self, &device, device_data, desc);
+        (render_pipeline.into(), Box::new(data) as _)
+    }
+
     fn device_create_compute_pipeline(
         &self,
         device: &ObjectId,
@@ -2276,6 +3197,36 @@ where
         (compute_pipeline.into(), Box::new(data) as _)
     }
 
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ComputePipelineDescriptor<'_>,
+    ) -> Pin<DeviceCreatePipelineFuture> {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let future =
+            Context::device_create_compute_pipeline_async(self, &device, device_data, desc);
+
+        Box::pin(async move {
+            let (compute_pipeline, data) = future.await;
+            (compute_pipeline.into(), Box::new(data) as _)
+        })
+    }
+
+    unsafe fn device_create_pipeline_cache(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &PipelineCacheDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (pipeline_cache, data) =
+            unsafe { Context::device_create_pipeline_cache(self, &device, device_data, desc) };
+        (pipeline_cache.into(), Box::new(data) as _)
+    }
+
     fn device_create_buffer(
         &self,
         device: &ObjectId,
@@ -2324,6 +3275,225 @@ where
         (query_set.into(), Box::new(data) as _)
     }
 
+    fn device_create_tile_pool(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TilePoolDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (tile_pool, data) = Context::device_create_tile_pool(self, &device, device_data, desc);
+        (tile_pool.into(), Box::new(data) as _)
+    }
+
+    fn device_create_memory_heap(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &MemoryHeapDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (memory_heap, data) =
+            Context::device_create_memory_heap(self, &device, device_data, desc);
+        (memory_heap.into(), Box::new(data) as _)
+    }
+
+    fn device_create_buffer_in_heap(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+        heap: &ObjectId,
+        heap_data: &crate::Data,
+        offset: BufferAddress,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let heap = <T::MemoryHeapId>::from(*heap);
+        let heap_data = downcast_ref(heap_data);
+        let (buffer, data) = Context::device_create_buffer_in_heap(
+            self,
+            &device,
+            device_data,
+            desc,
+            &heap,
+            heap_data,
+            offset,
+        );
+        (buffer.into(), Box::new(data) as _)
+    }
+
+    fn device_create_texture_in_heap(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+        heap: &ObjectId,
+        heap_data: &crate::Data,
+        offset: BufferAddress,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let heap = <T::MemoryHeapId>::from(*heap);
+        let heap_data = downcast_ref(heap_data);
+        let (texture, data) = Context::device_create_texture_in_heap(
+            self,
+            &device,
+            device_data,
+            desc,
+            &heap,
+            heap_data,
+            offset,
+        );
+        (texture.into(), Box::new(data) as _)
+    }
+
+    #[cfg(native)]
+    unsafe fn device_import_external_texture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+        source: wgt::ExternalImageSource,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (texture, data) = unsafe {
+            Context::device_import_external_texture(self, &device, device_data, desc, source)
+        };
+        (texture.into(), Box::new(data) as _)
+    }
+
+    fn device_create_external_fence(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ExternalFenceDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (external_fence, data) =
+            Context::device_create_external_fence(self, &device, device_data, desc);
+        (external_fence.into(), Box::new(data) as _)
+    }
+
+    #[cfg(native)]
+    unsafe fn device_import_external_fence(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ExternalFenceDescriptor<'_>,
+        handle: wgt::ExternalFenceHandle,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (external_fence, data) = unsafe {
+            Context::device_import_external_fence(self, &device, device_data, desc, handle)
+        };
+        (external_fence.into(), Box::new(data) as _)
+    }
+
+    #[cfg(native)]
+    fn external_fence_export(
+        &self,
+        external_fence: &ObjectId,
+        external_fence_data: &crate::Data,
+    ) -> wgt::ExternalFenceHandle {
+        let external_fence = <T::ExternalFenceId>::from(*external_fence);
+        let external_fence_data = downcast_ref(external_fence_data);
+        Context::external_fence_export(self, &external_fence, external_fence_data)
+    }
+
+    fn device_create_external_buffer(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (buffer, data) =
+            Context::device_create_external_buffer(self, &device, device_data, desc);
+        (buffer.into(), Box::new(data) as _)
+    }
+
+    #[cfg(native)]
+    unsafe fn device_import_external_buffer(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+        memory: wgt::ExternalMemory,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (buffer, data) = unsafe {
+            Context::device_import_external_buffer(self, &device, device_data, desc, memory)
+        };
+        (buffer.into(), Box::new(data) as _)
+    }
+
+    #[cfg(native)]
+    fn buffer_export_external_memory(
+        &self,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+    ) -> wgt::ExternalMemory {
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::buffer_export_external_memory(self, &buffer, buffer_data)
+    }
+
+    fn device_buffer_memory_requirements(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_buffer_memory_requirements(self, &device, device_data, desc)
+    }
+
+    fn device_texture_memory_requirements(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_texture_memory_requirements(self, &device, device_data, desc)
+    }
+
+    fn device_create_blas(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &CreateBlasDescriptor<'_>,
+        sizes: wgt::BlasGeometrySizeDescriptors,
+    ) -> (ObjectId, Option<u64>, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (blas, handle, data) =
+            Context::device_create_blas(self, &device, device_data, desc, sizes);
+        (blas.into(), handle, Box::new(data) as _)
+    }
+
+    fn device_create_tlas(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &CreateTlasDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (tlas, data) = Context::device_create_tlas(self, &device, device_data, desc);
+        (tlas.into(), Box::new(data) as _)
+    }
+
     fn device_create_command_encoder(
         &self,
         device: &ObjectId,
@@ -2367,6 +3537,57 @@ where
         Context::device_set_device_lost_callback(self, &device, device_data, device_lost_callback)
     }
 
+    fn device_lost(&self, device: &ObjectId, device_data: &crate::Data) -> Pin<DeviceLostFuture> {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let future = Context::device_lost(self, &device, device_data);
+        Box::pin(future)
+    }
+
+    fn device_set_debug_printf_callback(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        debug_printf_callback: DebugPrintfCallback,
+    ) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_set_debug_printf_callback(self, &device, device_data, debug_printf_callback)
+    }
+
+    fn device_set_watchdog(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        watchdog: Option<(std::time::Duration, WatchdogCallback)>,
+    ) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_set_watchdog(self, &device, device_data, watchdog)
+    }
+
+    fn device_set_automatic_polling(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        enabled: bool,
+    ) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_set_automatic_polling(self, &device, device_data, enabled)
+    }
+
+    fn device_set_command_disassembly(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        enabled: bool,
+    ) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_set_command_disassembly(self, &device, device_data, enabled)
+    }
+
     fn device_destroy(&self, device: &ObjectId, device_data: &crate::Data) {
         let device = <T::DeviceId>::from(*device);
         let device_data = downcast_ref(device_data);
@@ -2379,6 +3600,12 @@ where
         Context::device_mark_lost(self, &device, device_data, message)
     }
 
+    fn device_mark_lost_by_driver(&self, device: &ObjectId, device_data: &crate::Data) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_mark_lost_by_driver(self, &device, device_data)
+    }
+
     fn queue_drop(&self, queue: &ObjectId, queue_data: &crate::Data) {
         let queue = <T::QueueId>::from(*queue);
         let queue_data = downcast_ref(queue_data);
@@ -2396,6 +3623,32 @@ where
         Context::device_poll(self, &device, device_data, maintain)
     }
 
+    fn device_poll_until(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        submission_index: &crate::Data,
+        timeout: std::time::Duration,
+    ) -> PollStatus {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let submission_index = downcast_ref(submission_index);
+        Context::device_poll_until(self, &device, device_data, submission_index, timeout)
+    }
+
+    fn device_additional_queues(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+    ) -> Vec<(ObjectId, Box<crate::Data>)> {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_additional_queues(self, &device, device_data)
+            .into_iter()
+            .map(|(queue, queue_data)| (queue.into(), Box::new(queue_data) as _))
+            .collect()
+    }
+
     fn device_on_uncaptured_error(
         &self,
         device: &ObjectId,
@@ -2458,6 +3711,23 @@ where
         Context::buffer_unmap(self, &buffer, buffer_data)
     }
 
+    fn buffer_unmap_range(
+        &self,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        range: Range<BufferAddress>,
+    ) {
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::buffer_unmap_range(self, &buffer, buffer_data, range)
+    }
+
+    fn buffer_device_address(&self, buffer: &ObjectId, buffer_data: &crate::Data) -> BufferAddress {
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::buffer_device_address(self, &buffer, buffer_data)
+    }
+
     fn texture_create_view(
         &self,
         texture: &ObjectId,
@@ -2524,6 +3794,36 @@ where
         Context::query_set_drop(self, &query_set, query_set_data)
     }
 
+    fn tile_pool_drop(&self, tile_pool: &ObjectId, tile_pool_data: &crate::Data) {
+        let tile_pool = <T::TilePoolId>::from(*tile_pool);
+        let tile_pool_data = downcast_ref(tile_pool_data);
+        Context::tile_pool_drop(self, &tile_pool, tile_pool_data)
+    }
+
+    fn memory_heap_drop(&self, memory_heap: &ObjectId, memory_heap_data: &crate::Data) {
+        let memory_heap = <T::MemoryHeapId>::from(*memory_heap);
+        let memory_heap_data = downcast_ref(memory_heap_data);
+        Context::memory_heap_drop(self, &memory_heap, memory_heap_data)
+    }
+
+    fn external_fence_drop(&self, external_fence: &ObjectId, external_fence_data: &crate::Data) {
+        let external_fence = <T::ExternalFenceId>::from(*external_fence);
+        let external_fence_data = downcast_ref(external_fence_data);
+        Context::external_fence_drop(self, &external_fence, external_fence_data)
+    }
+
+    fn blas_drop(&self, blas: &ObjectId, blas_data: &crate::Data) {
+        let blas = <T::BlasId>::from(*blas);
+        let blas_data = downcast_ref(blas_data);
+        Context::blas_drop(self, &blas, blas_data)
+    }
+
+    fn tlas_drop(&self, tlas: &ObjectId, tlas_data: &crate::Data) {
+        let tlas = <T::TlasId>::from(*tlas);
+        let tlas_data = downcast_ref(tlas_data);
+        Context::tlas_drop(self, &tlas, tlas_data)
+    }
+
     fn bind_group_drop(&self, bind_group: &ObjectId, bind_group_data: &crate::Data) {
         let bind_group = <T::BindGroupId>::from(*bind_group);
         let bind_group_data = downcast_ref(bind_group_data);
@@ -2564,6 +3864,16 @@ where
         Context::command_buffer_drop(self, &command_buffer, command_buffer_data)
     }
 
+    fn command_buffer_disassembly(
+        &self,
+        command_buffer: &ObjectId,
+        command_buffer_data: &crate::Data,
+    ) -> Option<String> {
+        let command_buffer = <T::CommandBufferId>::from(*command_buffer);
+        let command_buffer_data = downcast_ref(command_buffer_data);
+        Context::command_buffer_disassembly(self, &command_buffer, command_buffer_data)
+    }
+
     fn render_bundle_drop(&self, render_bundle: &ObjectId, render_bundle_data: &crate::Data) {
         let render_bundle = <T::RenderBundleId>::from(*render_bundle);
         let render_bundle_data = downcast_ref(render_bundle_data);
@@ -2582,6 +3892,22 @@ where
         Context::render_pipeline_drop(self, &pipeline, pipeline_data)
     }
 
+    fn pipeline_cache_drop(&self, cache: &ObjectId, cache_data: &crate::Data) {
+        let cache = <T::PipelineCacheId>::from(*cache);
+        let cache_data = downcast_ref(cache_data);
+        Context::pipeline_cache_drop(self, &cache, cache_data)
+    }
+
+    fn pipeline_cache_get_data(
+        &self,
+        cache: &ObjectId,
+        cache_data: &crate::Data,
+    ) -> Option<Vec<u8>> {
+        let cache = <T::PipelineCacheId>::from(*cache);
+        let cache_data = downcast_ref(cache_data);
+        Context::pipeline_cache_get_data(self, &cache, cache_data)
+    }
+
     fn compute_pipeline_get_bind_group_layout(
         &self,
         pipeline: &ObjectId,
@@ -2608,6 +3934,16 @@ where
         (bind_group_layout.into(), Box::new(data) as _)
     }
 
+    fn bind_group_layout_entries(
+        &self,
+        bind_group_layout: &ObjectId,
+        bind_group_layout_data: &crate::Data,
+    ) -> Vec<BindGroupLayoutEntry> {
+        let bind_group_layout = <T::BindGroupLayoutId>::from(*bind_group_layout);
+        let bind_group_layout_data = downcast_ref(bind_group_layout_data);
+        Context::bind_group_layout_entries(self, &bind_group_layout, bind_group_layout_data)
+    }
+
     fn command_encoder_copy_buffer_to_buffer(
         &self,
         encoder: &ObjectId,
@@ -2700,6 +4036,28 @@ where
         )
     }
 
+    fn command_encoder_resolve_texture(
+        &self,
+        encoder: &ObjectId,
+        encoder_data: &crate::Data,
+        source: ImageCopyTexture<'_>,
+        destination: ImageCopyTexture<'_>,
+        copy_size: Extent3d,
+        mode: ResolveMode,
+    ) {
+        let encoder = <T::CommandEncoderId>::from(*encoder);
+        let encoder_data = downcast_ref(encoder_data);
+        Context::command_encoder_resolve_texture(
+            self,
+            &encoder,
+            encoder_data,
+            source,
+            destination,
+            copy_size,
+            mode,
+        )
+    }
+
     fn command_encoder_begin_compute_pass(
         &self,
         encoder: &ObjectId,
@@ -2829,6 +4187,12 @@ where
         Context::command_encoder_pop_debug_group(self, &encoder, encoder_data)
     }
 
+    fn command_encoder_aliasing_barrier(&self, encoder: &ObjectId, encoder_data: &crate::Data) {
+        let encoder = <T::CommandEncoderId>::from(*encoder);
+        let encoder_data = downcast_ref(encoder_data);
+        Context::command_encoder_aliasing_barrier(self, &encoder, encoder_data)
+    }
+
     fn command_encoder_write_timestamp(
         &self,
         encoder: &ObjectId,
@@ -2883,6 +4247,24 @@ where
         )
     }
 
+    fn command_encoder_build_acceleration_structures_unsafe_tlas(
+        &self,
+        encoder: &ObjectId,
+        encoder_data: &crate::Data,
+        blas: &[BlasBuildEntry<'_>],
+        tlas: &[TlasBuildEntry<'_>],
+    ) {
+        let encoder = <T::CommandEncoderId>::from(*encoder);
+        let encoder_data = downcast_ref(encoder_data);
+        Context::command_encoder_build_acceleration_structures_unsafe_tlas(
+            self,
+            &encoder,
+            encoder_data,
+            blas,
+            tlas,
+        )
+    }
+
     fn render_bundle_encoder_finish(
         &self,
         encoder: ObjectId,
@@ -2924,48 +4306,87 @@ where
         let queue_data = downcast_ref(queue_data);
         let buffer = <T::BufferId>::from(*buffer);
         let buffer_data = downcast_ref(buffer_data);
-        Context::queue_validate_write_buffer(
+        Context::queue_validate_write_buffer(
+            self,
+            &queue,
+            queue_data,
+            &buffer,
+            buffer_data,
+            offset,
+            size,
+        )
+    }
+
+    fn queue_create_staging_buffer(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        size: BufferSize,
+    ) -> Option<Box<dyn QueueWriteBuffer>> {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        Context::queue_create_staging_buffer(self, &queue, queue_data, size)
+    }
+
+    fn queue_write_staging_buffer(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        offset: BufferAddress,
+        staging_buffer: &dyn QueueWriteBuffer,
+    ) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::queue_write_staging_buffer(
             self,
             &queue,
             queue_data,
             &buffer,
             buffer_data,
             offset,
-            size,
+            staging_buffer,
         )
     }
 
-    fn queue_create_staging_buffer(
+    fn queue_write_buffer_regions(
         &self,
         queue: &ObjectId,
         queue_data: &crate::Data,
-        size: BufferSize,
-    ) -> Option<Box<dyn QueueWriteBuffer>> {
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        regions: &[(BufferAddress, &[u8])],
+    ) {
         let queue = <T::QueueId>::from(*queue);
         let queue_data = downcast_ref(queue_data);
-        Context::queue_create_staging_buffer(self, &queue, queue_data, size)
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::queue_write_buffer_regions(self, &queue, queue_data, &buffer, buffer_data, regions)
     }
 
-    fn queue_write_staging_buffer(
+    fn queue_write_staging_buffer_regions(
         &self,
         queue: &ObjectId,
         queue_data: &crate::Data,
         buffer: &ObjectId,
         buffer_data: &crate::Data,
-        offset: BufferAddress,
+        regions: &[(BufferAddress, BufferSize)],
         staging_buffer: &dyn QueueWriteBuffer,
     ) {
         let queue = <T::QueueId>::from(*queue);
         let queue_data = downcast_ref(queue_data);
         let buffer = <T::BufferId>::from(*buffer);
         let buffer_data = downcast_ref(buffer_data);
-        Context::queue_write_staging_buffer(
+        Context::queue_write_staging_buffer_regions(
             self,
             &queue,
             queue_data,
             &buffer,
             buffer_data,
-            offset,
+            regions,
             staging_buffer,
         )
     }
@@ -3021,6 +4442,84 @@ where
         Context::queue_get_timestamp_period(self, &queue, queue_data)
     }
 
+    fn queue_get_timestamp_calibration(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+    ) -> Option<wgt::TimestampCalibration> {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        Context::queue_get_timestamp_calibration(self, &queue, queue_data)
+    }
+
+    fn queue_bind_texture_pages(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        texture: &Texture,
+        bindings: &[TextureTileBinding<'_>],
+    ) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        Context::queue_bind_texture_pages(self, &queue, queue_data, texture, bindings)
+    }
+
+    fn queue_wait_for_submission(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        submission_index: &crate::Data,
+    ) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        let submission_index = downcast_ref(submission_index);
+        Context::queue_wait_for_submission(self, &queue, queue_data, submission_index)
+    }
+
+    fn queue_wait_for_external_fence(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        external_fence: &ObjectId,
+        external_fence_data: &crate::Data,
+        value: u64,
+    ) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        let external_fence = <T::ExternalFenceId>::from(*external_fence);
+        let external_fence_data = downcast_ref(external_fence_data);
+        Context::queue_wait_for_external_fence(
+            self,
+            &queue,
+            queue_data,
+            &external_fence,
+            external_fence_data,
+            value,
+        )
+    }
+
+    fn queue_signal_external_fence(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        external_fence: &ObjectId,
+        external_fence_data: &crate::Data,
+        value: u64,
+    ) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        let external_fence = <T::ExternalFenceId>::from(*external_fence);
+        let external_fence_data = downcast_ref(external_fence_data);
+        Context::queue_signal_external_fence(
+            self,
+            &queue,
+            queue_data,
+            &external_fence,
+            external_fence_data,
+            value,
+        )
+    }
+
     fn queue_on_submitted_work_done(
         &self,
         queue: &ObjectId,
@@ -3515,6 +5014,32 @@ where
         )
     }
 
+    fn render_bundle_encoder_begin_occlusion_query(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        query_index: u32,
+    ) {
+        let mut encoder = <T::RenderBundleEncoderId>::from(*encoder);
+        let encoder_data = downcast_mut::<T::RenderBundleEncoderData>(encoder_data);
+        Context::render_bundle_encoder_begin_occlusion_query(
+            self,
+            &mut encoder,
+            encoder_data,
+            query_index,
+        )
+    }
+
+    fn render_bundle_encoder_end_occlusion_query(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+    ) {
+        let mut encoder = <T::RenderBundleEncoderId>::from(*encoder);
+        let encoder_data = downcast_mut::<T::RenderBundleEncoderData>(encoder_data);
+        Context::render_bundle_encoder_end_occlusion_query(self, &mut encoder, encoder_data)
+    }
+
     fn render_pass_set_pipeline(
         &self,
         pass: &mut ObjectId,
@@ -3553,6 +5078,18 @@ where
         )
     }
 
+    fn render_pass_set_bind_group_immediate(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        index: u32,
+        entries: &[BindGroupEntry<'_>],
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_bind_group_immediate(self, &mut pass, pass_data, index, entries)
+    }
+
     fn render_pass_set_index_buffer(
         &self,
         pass: &mut ObjectId,
@@ -3694,6 +5231,48 @@ where
         )
     }
 
+    fn render_pass_draw_mesh_tasks(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_draw_mesh_tasks(
+            self,
+            &mut pass,
+            pass_data,
+            group_count_x,
+            group_count_y,
+            group_count_z,
+        )
+    }
+
+    fn render_pass_draw_mesh_tasks_indirect(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        indirect_buffer: &ObjectId,
+        indirect_buffer_data: &crate::Data,
+        indirect_offset: BufferAddress,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        let indirect_buffer = <T::BufferId>::from(*indirect_buffer);
+        let indirect_buffer_data = downcast_ref(indirect_buffer_data);
+        Context::render_pass_draw_mesh_tasks_indirect(
+            self,
+            &mut pass,
+            pass_data,
+            &indirect_buffer,
+            indirect_buffer_data,
+            indirect_offset,
+        )
+    }
+
     fn render_pass_multi_draw_indirect(
         &self,
         pass: &mut ObjectId,
@@ -3806,6 +5385,66 @@ where
         )
     }
 
+    fn render_pass_execute_indirect(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        arguments: &[IndirectArgument],
+        indirect_buffer: &ObjectId,
+        indirect_buffer_data: &crate::Data,
+        indirect_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        let indirect_buffer = <T::BufferId>::from(*indirect_buffer);
+        let indirect_buffer_data = downcast_ref(indirect_buffer_data);
+        Context::render_pass_execute_indirect(
+            self,
+            &mut pass,
+            pass_data,
+            arguments,
+            &indirect_buffer,
+            indirect_buffer_data,
+            indirect_offset,
+            max_count,
+        )
+    }
+
+    fn render_pass_execute_indirect_count(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        arguments: &[IndirectArgument],
+        indirect_buffer: &ObjectId,
+        indirect_buffer_data: &crate::Data,
+        indirect_offset: BufferAddress,
+        count_buffer: &ObjectId,
+        count_buffer_data: &crate::Data,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        let indirect_buffer = <T::BufferId>::from(*indirect_buffer);
+        let indirect_buffer_data = downcast_ref(indirect_buffer_data);
+        let count_buffer = <T::BufferId>::from(*count_buffer);
+        let count_buffer_data = downcast_ref(count_buffer_data);
+        Context::render_pass_execute_indirect_count(
+            self,
+            &mut pass,
+            pass_data,
+            arguments,
+            &indirect_buffer,
+            indirect_buffer_data,
+            indirect_offset,
+            &count_buffer,
+            count_buffer_data,
+            count_buffer_offset,
+            max_count,
+        )
+    }
+
     fn render_pass_set_blend_constant(
         &self,
         pass: &mut ObjectId,
@@ -3860,6 +5499,17 @@ where
         Context::render_pass_set_stencil_reference(self, &mut pass, pass_data, reference)
     }
 
+    fn render_pass_set_shading_rate(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        rate: wgt::ShadingRate,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_shading_rate(self, &mut pass, pass_data, rate)
+    }
+
     fn render_pass_insert_debug_marker(
         &self,
         pass: &mut ObjectId,
@@ -3888,6 +5538,18 @@ where
         Context::render_pass_pop_debug_group(self, &mut pass, pass_data)
     }
 
+    fn render_pass_resume(&self, pass: &mut ObjectId, pass_data: &mut crate::Data) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_resume(self, &mut pass, pass_data)
+    }
+
+    fn render_pass_suspend(&self, pass: &mut ObjectId, pass_data: &mut crate::Data) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_suspend(self, &mut pass, pass_data)
+    }
+
     fn render_pass_write_timestamp(
         &self,
         pass: &mut ObjectId,
@@ -3959,6 +5621,76 @@ where
         Context::render_pass_end_pipeline_statistics_query(self, &mut pass, pass_data)
     }
 
+    fn render_pass_begin_conditional_rendering(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        offset: BufferAddress,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::render_pass_begin_conditional_rendering(
+            self,
+            &mut pass,
+            pass_data,
+            &buffer,
+            buffer_data,
+            offset,
+        )
+    }
+
+    fn render_pass_end_conditional_rendering(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_end_conditional_rendering(self, &mut pass, pass_data)
+    }
+
+    fn render_pass_set_vertex_capture_buffer(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        slot: u32,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::render_pass_set_vertex_capture_buffer(
+            self,
+            &mut pass,
+            pass_data,
+            slot,
+            &buffer,
+            buffer_data,
+            offset,
+            size,
+        )
+    }
+
+    fn render_pass_begin_vertex_capture(&self, pass: &mut ObjectId, pass_data: &mut crate::Data) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_begin_vertex_capture(self, &mut pass, pass_data)
+    }
+
+    fn render_pass_end_vertex_capture(&self, pass: &mut ObjectId, pass_data: &mut crate::Data) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_end_vertex_capture(self, &mut pass, pass_data)
+    }
+
     fn render_pass_execute_bundles(
         &self,
         pass: &mut ObjectId,
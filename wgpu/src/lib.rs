@@ -7,12 +7,15 @@
 //!
 //! ### Backends
 //!
-//! ⚠️ WIP: Not all backends can be manually configured today. On Windows & Linux the **Vulkan & GLES
-//! backends are always enabled**. See [#3514](https://github.com/gfx-rs/wgpu/issues/3514) for more
-//! details.
+//! Every backend can be disabled to reduce the binary size. To only build the backends you need,
+//! disable the default features and enable them one by one.
 //!
 //! - **`dx12`** _(enabled by default)_ ---   Enables the DX12 backend on Windows.
 //! - **`metal`** _(enabled by default)_ --- Enables the Metal backend on macOS & iOS.
+//! - **`vulkan`** _(enabled by default)_ --- Enables the Vulkan backend on Windows, Linux &
+//!   Android. To enable Vulkan on macOS & iOS, use the `vulkan-portability` feature.
+//! - **`gles`** _(enabled by default)_ --- Enables the GLES backend on Windows (via WGL), Linux &
+//!   Android, and on Emscripten. To enable GLES on macOS, use the `angle` feature.
 //! - **`webgpu`** _(enabled by default)_ --- Enables the WebGPU backend on Wasm. Disabled when targeting `emscripten`.
 //! - **`angle`** --- Enables the GLES backend via [ANGLE](https://github.com/google/angle) on macOS.
 //! - **`vulkan-portability`** --- Enables the Vulkan backend on macOS & iOS.
@@ -30,6 +33,15 @@
 //! - **`glsl`** --- Enable accepting GLSL shaders as input.
 //! - **`naga-ir`** --- Enable accepting Naga IR shaders as input.
 //!
+//! ### Reducing the binary size
+//!
+//! The backends and shader frontends make up most of the size of a release build. A minimal build
+//! only enables the backend of its platform and a single shader frontend, for instance
+//! `default-features = false, features = ["vulkan", "wgsl"]` on Linux, or
+//! `default-features = false, features = ["webgpu", "wgsl"]` on the web. The `release-minimal`
+//! cargo profile of the wgpu workspace additionally optimizes for size, and can serve as a
+//! template for the profile of an application.
+//!
 //! ### Logging & Tracing
 //!
 //! The following features do not have any effect on the WebGPU backend.
@@ -42,6 +54,13 @@
 //!   feature. To replay a trace file use the [wgpu
 //!   player](https://github.com/gfx-rs/wgpu/tree/trunk/player).
 //!
+//! ### Texture file loading
+//!
+//! - **`ktx2`** --- Enable loading KTX2 texture files with [`util::parse_ktx2`].
+//! - **`dds`** --- Enable loading DDS texture files with [`util::parse_dds`].
+//! - **`transcode`** --- Enable transcoding compressed textures on the CPU with
+//!   [`util::transcode_texture_data`].
+//!
 //! ### Other
 //!
 //! - **`fragile-send-sync-non-atomic-wasm`** --- Implement `Send` and `Sync` on Wasm, but only if
@@ -91,24 +110,29 @@ use parking_lot::Mutex;
 
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 pub use wgt::{
-    AdapterInfo, AddressMode, AstcBlock, AstcChannel, Backend, Backends, BindGroupLayoutEntry,
-    BindingType, BlendComponent, BlendFactor, BlendOperation, BlendState, BufferAddress,
-    BufferBindingType, BufferSize, BufferUsages, Color, ColorTargetState, ColorWrites,
-    CommandBufferDescriptor, CompareFunction, CompositeAlphaMode, DepthBiasState,
-    DepthStencilState, DeviceLostReason, DeviceType, DownlevelCapabilities, DownlevelFlags,
-    Dx12Compiler, DynamicOffset, Extent3d, Face, Features, FilterMode, FrontFace,
-    Gles3MinorVersion, ImageDataLayout, ImageSubresourceRange, IndexFormat, InstanceDescriptor,
-    InstanceFlags, Limits, MaintainResult, MultisampleState, Origin2d, Origin3d,
-    PipelineStatisticsTypes, PolygonMode, PowerPreference, PredefinedColorSpace, PresentMode,
-    PresentationTimestamp, PrimitiveState, PrimitiveTopology, PushConstantRange, QueryType,
-    RenderBundleDepthStencil, SamplerBindingType, SamplerBorderColor, ShaderLocation, ShaderModel,
-    ShaderStages, StencilFaceState, StencilOperation, StencilState, StorageTextureAccess,
+    AccelerationStructureFlags, AccelerationStructureGeometryFlags, AdapterInfo, AddressMode,
+    AstcBlock, AstcChannel, Backend, Backends, BindGroupLayoutEntry, BindingType,
+    BlasGeometrySizeDescriptors, BlasTriangleGeometrySizeDescriptor, BlendComponent, BlendFactor,
+    BlendOperation, BlendState, BufferAddress, BufferBindingType, BufferSize, BufferUsages,
+    ClampedLimit, Color, ColorTargetState, ColorWrites, CommandBufferDescriptor, CompareFunction,
+    ComponentMapping, ComponentSwizzle, CompositeAlphaMode, DebugMessage, DebugMessageSeverity,
+    DepthBiasState, DepthStencilState, DeviceLostReason, DeviceType, DownlevelCapabilities,
+    DownlevelFlags, Dx12Compiler, DynamicOffset, Extent3d, Face, FeatureSupport, Features,
+    FilterMode, FrontFace, Gles3MinorVersion, ImageDataLayout, ImageSubresourceRange, IndexFormat,
+    IndirectArgument, InstanceDescriptor, InstanceFlags, Limits, MaintainResult,
+    MemoryArchitecture, MemoryHeapType, MemoryRequirements, MultisampleState, NegotiatedLimits,
+    Origin2d, Origin3d, PipelineStatisticsTypes, PollStatus, PolygonMode, PowerPreference,
+    PredefinedColorSpace, PresentMode, PresentationTimestamp, PrimitiveState, PrimitiveTopology,
+    PushConstantRange, QueryType, QueuePriority, RenderBundleDepthStencil, ResolveMode,
+    SamplerBindingType, SamplerBorderColor, ShaderLocation, ShaderModel, ShaderStages, ShadingRate,
+    StencilFaceState, StencilOperation, StencilState, StorageTextureAccess, SubmissionTimeout,
     SurfaceCapabilities, SurfaceStatus, TextureAspect, TextureDimension, TextureFormat,
     TextureFormatFeatureFlags, TextureFormatFeatures, TextureSampleType, TextureUsages,
-    TextureViewDimension, VertexAttribute, VertexFormat, VertexStepMode, WasmNotSend,
-    WasmNotSendSync, WasmNotSync, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT,
-    MAP_ALIGNMENT, PUSH_CONSTANT_ALIGNMENT, QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES,
-    QUERY_SIZE, VERTEX_STRIDE_ALIGNMENT,
+    TextureViewDimension, TimestampCalibration, VertexAttribute, VertexFormat, VertexStepMode,
+    WasmNotSend, WasmNotSendSync, WasmNotSync, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT,
+    MAP_ALIGNMENT, MAX_VERTEX_CAPTURE_BUFFERS, MAX_VERTEX_CAPTURE_STRIDE, PUSH_CONSTANT_ALIGNMENT,
+    QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES, QUERY_SIZE, TEXTURE_TILE_SIZE,
+    TLAS_INSTANCE_SIZE, VERTEX_STRIDE_ALIGNMENT,
 };
 
 /// Re-export of our `wgpu-core` dependency.
@@ -155,6 +179,18 @@ pub use web_sys;
 #[cfg(any(webgpu, webgl))]
 pub use wgt::{ExternalImageSource, ImageCopyExternalImage};
 
+// Native images that textures can be imported from.
+#[cfg(native)]
+pub use wgt::{DmaBufImage, DmaBufPlane, ExternalImageSource};
+
+// Native fences that other APIs share with wgpu.
+#[cfg(native)]
+pub use wgt::ExternalFenceHandle;
+
+// Native memory that other APIs share buffers with.
+#[cfg(native)]
+pub use wgt::{ExternalMemory, ExternalMemoryHandle};
+
 /// Filter for error scopes.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd)]
 pub enum ErrorFilter {
@@ -162,6 +198,8 @@ pub enum ErrorFilter {
     OutOfMemory,
     /// Catch only validation errors.
     Validation,
+    /// Catch only internal errors.
+    Internal,
 }
 static_assertions::assert_impl_all!(ErrorFilter: Send, Sync);
 
@@ -239,12 +277,56 @@ pub struct SubmissionIndex(ObjectId, Arc<crate::Data>);
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(SubmissionIndex: Send, Sync);
 
+/// Future resolving once the GPU finished a submission, returned by [`Queue::submit_async`].
+///
+/// This type is unique to the Rust API of `wgpu`.
+/// There is no analogue in the WebGPU specification.
+#[derive(Debug)]
+pub struct SubmissionFuture {
+    index: SubmissionIndex,
+    state: Arc<Mutex<SubmissionState>>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(SubmissionFuture: Send, Sync);
+
+#[derive(Debug, Default)]
+struct SubmissionState {
+    done: bool,
+    waker: Option<std::task::Waker>,
+}
+
+impl SubmissionFuture {
+    /// Returns the index of the submission this future waits for.
+    pub fn submission_index(&self) -> &SubmissionIndex {
+        &self.index
+    }
+}
+
+impl Future for SubmissionFuture {
+    type Output = SubmissionIndex;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let mut state = self.state.lock();
+        if state.done {
+            std::task::Poll::Ready(self.index.clone())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            std::task::Poll::Pending
+        }
+    }
+}
+
 /// The main purpose of this struct is to resolve mapped ranges (convert sizes
 /// to end points), and to ensure that the sub-ranges don't intersect.
 #[derive(Debug)]
 struct MapContext {
     total_size: BufferAddress,
-    initial_range: Range<BufferAddress>,
+    /// Ranges mapped at creation or with [`BufferSlice::map_async`], which
+    /// don't intersect.
+    mapped_ranges: Vec<Range<BufferAddress>>,
     sub_ranges: Vec<Range<BufferAddress>>,
 }
 
@@ -252,13 +334,13 @@ impl MapContext {
     fn new(total_size: BufferAddress) -> Self {
         Self {
             total_size,
-            initial_range: 0..0,
+            mapped_ranges: Vec::new(),
             sub_ranges: Vec::new(),
         }
     }
 
     fn reset(&mut self) {
-        self.initial_range = 0..0;
+        self.mapped_ranges.clear();
 
         assert!(
             self.sub_ranges.is_empty(),
@@ -266,12 +348,48 @@ impl MapContext {
         );
     }
 
-    fn add(&mut self, offset: BufferAddress, size: Option<BufferSize>) -> BufferAddress {
-        let end = match size {
+    fn is_mapped(&self, range: &Range<BufferAddress>) -> bool {
+        self.mapped_ranges
+            .iter()
+            .any(|mapped| range.start < mapped.end && mapped.start < range.end)
+    }
+
+    /// Forgets the mapped `range`, which must not have accessible views.
+    fn unmap(&mut self, range: Range<BufferAddress>) {
+        let index = self
+            .mapped_ranges
+            .iter()
+            .position(|r| *r == range)
+            .unwrap_or_else(|| panic!("Buffer range {range:?} wasn't mapped on its own"));
+        for sub in self.sub_ranges.iter() {
+            assert!(
+                sub.end <= range.start || sub.start >= range.end,
+                "You cannot unmap a buffer range that still has accessible mapped views"
+            );
+        }
+        self.mapped_ranges.swap_remove(index);
+    }
+
+    /// Resolves the end of a sub-range, which defaults to the end of the
+    /// mapped range it starts in.
+    fn resolve_end(&self, offset: BufferAddress, size: Option<BufferSize>) -> BufferAddress {
+        match size {
             Some(s) => offset + s.get(),
-            None => self.initial_range.end,
-        };
-        assert!(self.initial_range.start <= offset && end <= self.initial_range.end);
+            None => self
+                .mapped_ranges
+                .iter()
+                .find(|r| r.start <= offset && offset < r.end)
+                .or_else(|| self.mapped_ranges.iter().find(|r| r.end == offset))
+                .map_or(offset, |r| r.end),
+        }
+    }
+
+    fn add(&mut self, offset: BufferAddress, size: Option<BufferSize>) -> BufferAddress {
+        let end = self.resolve_end(offset, size);
+        assert!(self
+            .mapped_ranges
+            .iter()
+            .any(|r| r.start <= offset && end <= r.end));
         for sub in self.sub_ranges.iter() {
             assert!(
                 end <= sub.start || offset >= sub.end,
@@ -283,10 +401,7 @@ impl MapContext {
     }
 
     fn remove(&mut self, offset: BufferAddress, size: Option<BufferSize>) {
-        let end = match size {
-            Some(s) => offset + s.get(),
-            None => self.initial_range.end,
-        };
+        let end = self.resolve_end(offset, size);
 
         let index = self
             .sub_ranges
@@ -345,6 +460,7 @@ pub struct Texture {
     data: Box<Data>,
     owned: bool,
     descriptor: TextureDescriptor<'static>,
+    view_formats: Vec<TextureFormat>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(Texture: Send, Sync);
@@ -360,6 +476,8 @@ pub struct TextureView {
     context: Arc<C>,
     id: ObjectId,
     data: Box<Data>,
+    texture: Id<Texture>,
+    descriptor: TextureViewDescriptor<'static>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(TextureView: Send, Sync);
@@ -576,6 +694,42 @@ pub enum SurfaceTargetUnsafe {
     /// - visual must be a valid SwapChainPanel to create a surface upon.
     #[cfg(dx12)]
     SwapChainPanel(*mut std::ffi::c_void),
+
+    /// Surface presenting directly to a display through DRM/KMS, for kiosks and
+    /// embedded devices running without a window system.
+    ///
+    /// `fd` is an open DRM device that is DRM master, or a DRM lease of the
+    /// connector, such as one granted by a compositor through
+    /// `wp_drm_lease_device_v1`. The display behind `connector_id` is set to the
+    /// mode of `width` by `height` pixels refreshing at `refresh_rate` millihertz,
+    /// and the surface is shown on its `plane`.
+    ///
+    /// Only the Vulkan backend supports it, through `VK_EXT_acquire_drm_display`.
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must be a valid DRM file descriptor, and stay open until after the
+    ///   returned [`Surface`] is dropped.
+    /// - The display can't be used by anything else while the instance exists.
+    #[cfg(all(
+        vulkan,
+        unix,
+        not(any(target_os = "android", target_os = "ios", target_os = "macos"))
+    ))]
+    Drm {
+        /// DRM file descriptor of the device or lease.
+        fd: i32,
+        /// Index of the display plane to present to.
+        plane: u32,
+        /// DRM id of the connector of the display.
+        connector_id: u32,
+        /// Width of the display mode in pixels.
+        width: u32,
+        /// Height of the display mode in pixels.
+        height: u32,
+        /// Refresh rate of the display mode in millihertz.
+        refresh_rate: u32,
+    },
 }
 
 impl SurfaceTargetUnsafe {
@@ -612,6 +766,7 @@ pub struct BindGroupLayout {
     context: Arc<C>,
     id: ObjectId,
     data: Box<Data>,
+    entries: Vec<BindGroupLayoutEntry>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(BindGroupLayout: Send, Sync);
@@ -676,6 +831,69 @@ impl Drop for ShaderModule {
     }
 }
 
+impl ShaderModule {
+    /// Get the compilation info for the shader module.
+    ///
+    /// On native backends this holds the errors found by naga while parsing and validating
+    /// the shader, which are also reported to the device's error handling as usual.
+    pub fn get_compilation_info(&self) -> impl Future<Output = CompilationInfo> + WasmNotSend {
+        self.context
+            .shader_get_compilation_info(&self.id, self.data.as_ref())
+    }
+}
+
+/// Compilation information for a shader module.
+///
+/// Corresponds to [WebGPU `GPUCompilationInfo`](https://gpuweb.github.io/gpuweb/#gpucompilationinfo).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompilationInfo {
+    /// The messages from the shader compilation process.
+    pub messages: Vec<CompilationMessage>,
+}
+
+/// A single message from the shader compilation process.
+///
+/// Corresponds to [WebGPU `GPUCompilationMessage`](https://gpuweb.github.io/gpuweb/#gpucompilationmessage).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompilationMessage {
+    /// The text of the message.
+    pub message: String,
+    /// The type of the message.
+    pub message_type: CompilationMessageType,
+    /// Where in the source code the message applies, if known.
+    pub location: Option<SourceLocation>,
+}
+
+/// The type of a compilation message.
+///
+/// Corresponds to [WebGPU `GPUCompilationMessageType`](https://gpuweb.github.io/gpuweb/#enumdef-gpucompilationmessagetype).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CompilationMessageType {
+    /// An error message.
+    Error,
+    /// A warning message.
+    Warning,
+    /// An informational message.
+    Info,
+}
+
+/// A span of the source code of a shader module.
+///
+/// On native backends columns, offsets and lengths are counted as by naga: the column in
+/// characters, the offset and length in bytes. On WebGPU they are all in UTF-16 code units, as
+/// reported by the browser.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SourceLocation {
+    /// 1-based line number.
+    pub line_number: u32,
+    /// 1-based column of the start of the span.
+    pub line_position: u32,
+    /// 0-based offset of the start of the span.
+    pub offset: u32,
+    /// Length of the span.
+    pub length: u32,
+}
+
 /// Source of a shader module.
 ///
 /// The source will be parsed and validated.
@@ -801,7 +1019,13 @@ impl RenderPipeline {
         let (id, data) =
             self.context
                 .render_pipeline_get_bind_group_layout(&self.id, self.data.as_ref(), index);
-        BindGroupLayout { context, id, data }
+        let entries = context.bind_group_layout_entries(&id, data.as_ref());
+        BindGroupLayout {
+            context,
+            id,
+            data,
+            entries,
+        }
     }
 }
 
@@ -838,7 +1062,54 @@ impl ComputePipeline {
             self.data.as_ref(),
             index,
         );
-        BindGroupLayout { context, id, data }
+        let entries = context.bind_group_layout_entries(&id, data.as_ref());
+        BindGroupLayout {
+            context,
+            id,
+            data,
+            entries,
+        }
+    }
+}
+
+/// Handle to a pipeline cache, which is used to accelerate
+/// creating [`RenderPipeline`]s and [`ComputePipeline`]s
+/// in subsequent executions.
+///
+/// Pipelines created with the cache add their compiled code to it, which can
+/// be retrieved with [`PipelineCache::get_data`] and stored on disk. Passing
+/// that data to [`Device::create_pipeline_cache`] in a later run allows the
+/// backend to skip most of the compilation of the same pipelines.
+///
+/// This type is unique to the Rust API of `wgpu`, and requires
+/// [`Features::PIPELINE_CACHE`].
+#[derive(Debug)]
+pub struct PipelineCache {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(PipelineCache: Send, Sync);
+
+impl Drop for PipelineCache {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context
+                .pipeline_cache_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
+impl PipelineCache {
+    /// Get the data associated with this pipeline cache.
+    ///
+    /// The returned data is specific to the adapter and driver it was created
+    /// on, and should only be passed to [`Device::create_pipeline_cache`] on the
+    /// same system. Returns `None` if the backend has no data to provide.
+    pub fn get_data(&self) -> Option<Vec<u8>> {
+        self.context
+            .pipeline_cache_get_data(&self.id, self.data.as_ref())
     }
 }
 
@@ -869,6 +1140,27 @@ impl Drop for CommandBuffer {
     }
 }
 
+impl CommandBuffer {
+    /// Returns a readable listing of the commands of this command buffer.
+    ///
+    /// The listing shows the passes with their attachments, the labels of the pipelines,
+    /// bind groups and other resources in use, the parameters of draws and dispatches, and
+    /// the number of barriers inserted by wgpu. Comparing the listings of a working and a
+    /// broken frame can locate a problem without taking a full GPU capture.
+    ///
+    /// Returns `None` unless [`Device::set_command_disassembly`] was enabled when the
+    /// [`CommandEncoder`] was created. The format of the listing is meant for people
+    /// and may change between releases. Command buffers are not disassembled on WebGPU.
+    pub fn disassembly(&self) -> Option<String> {
+        let id = self.id.as_ref()?;
+        DynContext::command_buffer_disassembly(
+            &*self.context,
+            id,
+            self.data.as_ref().unwrap().as_ref(),
+        )
+    }
+}
+
 /// Encodes a series of GPU operations.
 ///
 /// A command encoder can record [`RenderPass`]es, [`ComputePass`]es,
@@ -985,6 +1277,38 @@ impl Drop for RenderBundle {
     }
 }
 
+/// In-progress recording of a render pass split into slices that can be recorded in parallel.
+///
+/// It can be created with [`CommandEncoder::begin_render_pass_parallel`]. Each slice is a
+/// [`RenderPassSliceEncoder`] which can be sent to another thread and recorded independently.
+/// When the pass is finished with [`ParallelRenderPass::finish`], the slices are stitched
+/// together in order into the render pass. Dropping it without finishing ends the render pass
+/// without executing any of the slices.
+#[derive(Debug)]
+pub struct ParallelRenderPass<'a> {
+    pass: RenderPass<'a>,
+    slices: Vec<RenderPassSliceEncoder<'a>>,
+}
+
+/// Records one slice of a [`ParallelRenderPass`].
+///
+/// It dereferences to a [`RenderBundleEncoder`] and supports the same commands. Unlike a
+/// [`RenderBundleEncoder`], it can be sent to other threads, so that the slices of a pass can be
+/// recorded concurrently.
+///
+/// Like a render bundle, each slice starts with empty render state and does not inherit the state
+/// set by the slices before it.
+#[derive(Debug)]
+pub struct RenderPassSliceEncoder<'a> {
+    inner: RenderBundleEncoder<'a>,
+}
+// SAFETY: The recording only lives in `inner`'s context data, which is `Send` when `send_sync` is
+// enabled. `RenderBundleEncoder` opts out of `Send` through a marker only.
+#[cfg(send_sync)]
+unsafe impl Send for RenderPassSliceEncoder<'_> {}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(RenderPassSliceEncoder<'_>: Send);
+
 /// Handle to a query set.
 ///
 /// It can be created with [`Device::create_query_set`].
@@ -1008,6 +1332,150 @@ impl Drop for QuerySet {
     }
 }
 
+/// Handle to a pool of memory tiles that can back [`TextureUsages::TILED`] textures.
+///
+/// It can be created with [`Device::create_tile_pool`], and its tiles are bound to
+/// textures with [`Queue::bind_texture_pages`].
+///
+/// Every tile is [`TEXTURE_TILE_SIZE`] bytes. Textures keep the pools they've been
+/// bound to alive, so a pool can be dropped while it's still bound.
+#[derive(Debug)]
+pub struct TilePool {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(TilePool: Send, Sync);
+
+impl Drop for TilePool {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context.tile_pool_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
+/// Handle to a block of memory that buffers and textures can be placed in.
+///
+/// It can be created with [`Device::create_memory_heap`], and resources are placed
+/// in it with [`Device::create_buffer_in_heap`] and [`Device::create_texture_in_heap`].
+///
+/// Resources placed in overlapping ranges of a heap alias each other: the contents
+/// of a resource are undefined once another one has used the memory it shares with
+/// it, and [`CommandEncoder::aliasing_barrier`] must be recorded between their uses.
+/// Placed resources keep their heap alive, so it can be dropped while in use.
+#[derive(Debug)]
+pub struct MemoryHeap {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(MemoryHeap: Send, Sync);
+
+impl Drop for MemoryHeap {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context.memory_heap_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
+/// Handle to a fence whose timeline is shared with other APIs, such as Vulkan, CUDA
+/// or media decoders.
+///
+/// It can be created with [`Device::create_external_fence`] and exported with
+/// [`ExternalFence::export_handle`], or imported with [`Device::import_external_fence`].
+/// Queues signal it with [`Queue::submit_with_external_signals`] and wait on it with
+/// [`Queue::wait_for_external_fence`].
+///
+/// The fence must not be dropped while submitted work still signals or waits on it.
+#[derive(Debug)]
+pub struct ExternalFence {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(ExternalFence: Send, Sync);
+
+impl ExternalFence {
+    /// Returns a native handle that other APIs can import the fence with.
+    ///
+    /// Opaque fds are owned by the caller, and must be closed once they've been
+    /// imported. D3D12 shared handles must be closed too, while Metal shared events
+    /// are borrowed from the fence.
+    #[cfg(native)]
+    pub fn export_handle(&self) -> ExternalFenceHandle {
+        DynContext::external_fence_export(&*self.context, &self.id, self.data.as_ref())
+    }
+}
+
+impl Drop for ExternalFence {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context
+                .external_fence_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
+/// Handle to a bottom level acceleration structure, holding triangle geometry.
+///
+/// It can be created with [`Device::create_blas`], and built with
+/// [`CommandEncoder::build_acceleration_structures_unsafe_tlas`].
+#[derive(Debug)]
+pub struct Blas {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+    handle: Option<u64>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(Blas: Send, Sync);
+
+impl Blas {
+    /// Returns the handle that the instances of a top level acceleration
+    /// structure reference this acceleration structure by, or `None` if it
+    /// couldn't be created.
+    pub fn handle(&self) -> Option<u64> {
+        self.handle
+    }
+}
+
+impl Drop for Blas {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context.blas_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
+/// Handle to a top level acceleration structure, holding instances of bottom
+/// level acceleration structures.
+///
+/// It can be created with [`Device::create_tlas`], built with
+/// [`CommandEncoder::build_acceleration_structures_unsafe_tlas`], and bound
+/// with [`BindingResource::AccelerationStructure`] to be traversed by ray
+/// queries.
+#[derive(Debug)]
+pub struct Tlas {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(Tlas: Send, Sync);
+
+impl Drop for Tlas {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context.tlas_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
 /// Handle to a command queue on a device.
 ///
 /// A `Queue` executes recorded [`CommandBuffer`] objects and provides convenience methods
@@ -1074,6 +1542,13 @@ pub enum BindingResource<'a> {
     /// Corresponds to [`wgt::BindingType::Texture`] and [`wgt::BindingType::StorageTexture`] with
     /// [`BindGroupLayoutEntry::count`] set to Some.
     TextureViewArray(&'a [&'a TextureView]),
+    /// Binding is a top level acceleration structure.
+    ///
+    /// [`Features::EXPERIMENTAL_RAY_QUERY`] must be supported to use this feature.
+    ///
+    /// Corresponds to [`wgt::BindingType::AccelerationStructure`] with
+    /// [`BindGroupLayoutEntry::count`] set to None.
+    AccelerationStructure(&'a Tlas),
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(BindingResource<'_>: Send, Sync);
@@ -1207,6 +1682,41 @@ pub struct RenderPassTimestampWrites<'a> {
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(RenderPassTimestampWrites<'_>: Send, Sync);
 
+/// Describes a pipeline statistics query covering a whole render pass.
+///
+/// For use with [`RenderPassDescriptor`]. The query is begun when the pass
+/// begins and ended when it ends, so [`RenderPass::begin_pipeline_statistics_query`]
+/// can't be used in the same pass.
+///
+/// Requires [`Features::PIPELINE_STATISTICS_QUERY`] to be enabled.
+#[derive(Clone, Debug)]
+pub struct RenderPassPipelineStatisticsQuery<'a> {
+    /// The query set to write to, which must be of type [`QueryType::PipelineStatistics`].
+    pub query_set: &'a QuerySet,
+    /// The index of the query set at which the statistics of this pass are written.
+    pub query_index: u32,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(RenderPassPipelineStatisticsQuery<'_>: Send, Sync);
+
+/// Describes the shading rate attachment of a render pass.
+///
+/// For use with [`RenderPassDescriptor`]. Each texel of the attachment holds a
+/// [`ShadingRate`] for one tile of [`Limits::shading_rate_tile_size`] by
+/// [`Limits::shading_rate_tile_size`] pixels of the render targets. It overrides
+/// the rate set with [`RenderPass::set_shading_rate`].
+///
+/// Requires [`Features::SHADING_RATE_ATTACHMENT`] to be enabled.
+#[derive(Clone, Debug)]
+pub struct RenderPassShadingRateAttachment<'tex> {
+    /// The view to use as the attachment. It must be a single-sampled 2D
+    /// [`TextureFormat::R8Uint`] view of a single mip level and array layer, of a
+    /// texture created with [`TextureUsages::SHADING_RATE_ATTACHMENT`].
+    pub view: &'tex TextureView,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(RenderPassShadingRateAttachment<'_>: Send, Sync);
+
 /// Describes a color attachment to a [`RenderPass`].
 ///
 /// For use with [`RenderPassDescriptor`].
@@ -1241,10 +1751,33 @@ pub struct RenderPassDepthStencilAttachment<'tex> {
     pub depth_ops: Option<Operations<f32>>,
     /// What operations will be performed on the stencil part of the attachment.
     pub stencil_ops: Option<Operations<u32>>,
+    /// The view that will receive the resolved depth and stencil if multisampling is used.
+    ///
+    /// Requires [`Features::DEPTH_STENCIL_RESOLVE`] to be enabled.
+    pub resolve: Option<RenderPassDepthStencilResolve<'tex>>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(RenderPassDepthStencilAttachment<'_>: Send, Sync);
 
+/// Describes the resolve target of a [`RenderPassDepthStencilAttachment`].
+///
+/// The resolve happens at the end of the render pass, even if the attachment
+/// itself is discarded.
+#[derive(Clone, Debug)]
+pub struct RenderPassDepthStencilResolve<'tex> {
+    /// The single-sampled view to resolve into. It must have the same format and
+    /// size as the attachment.
+    pub view: &'tex TextureView,
+    /// How the samples of each aspect are combined.
+    ///
+    /// [`ResolveMode::SampleZero`] is supported by all formats, the other modes require
+    /// [`TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_DEPTH_AVERAGE`] or
+    /// [`TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_MIN_MAX`].
+    pub mode: ResolveMode,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(RenderPassDepthStencilResolve<'_>: Send, Sync);
+
 // The underlying types are also exported so that documentation shows up for them
 
 /// Object debugging label.
@@ -1307,15 +1840,114 @@ static_assertions::assert_impl_all!(TextureDescriptor<'_>: Send, Sync);
 /// https://gpuweb.github.io/gpuweb/#dictdef-gpuquerysetdescriptor).
 pub type QuerySetDescriptor<'a> = wgt::QuerySetDescriptor<Label<'a>>;
 static_assertions::assert_impl_all!(QuerySetDescriptor<'_>: Send, Sync);
-pub use wgt::Maintain as MaintainBase;
-/// Passed to [`Device::poll`] to control how and if it should block.
-pub type Maintain = wgt::Maintain<SubmissionIndex>;
+
+/// Describes a [`TilePool`].
+///
+/// For use with [`Device::create_tile_pool`].
+pub type TilePoolDescriptor<'a> = wgt::TilePoolDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(TilePoolDescriptor<'_>: Send, Sync);
+
+/// Describes a [`MemoryHeap`].
+///
+/// For use with [`Device::create_memory_heap`].
+pub type MemoryHeapDescriptor<'a> = wgt::MemoryHeapDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(MemoryHeapDescriptor<'_>: Send, Sync);
+
+/// Describes an [`ExternalFence`].
+///
+/// For use with [`Device::create_external_fence`] and [`Device::import_external_fence`].
+pub type ExternalFenceDescriptor<'a> = wgt::ExternalFenceDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(ExternalFenceDescriptor<'_>: Send, Sync);
+
+/// Describes a [`Blas`].
+///
+/// For use with [`Device::create_blas`].
+pub type CreateBlasDescriptor<'a> = wgt::CreateBlasDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(CreateBlasDescriptor<'_>: Send, Sync);
+
+/// Describes a [`Tlas`].
+///
+/// For use with [`Device::create_tlas`].
+pub type CreateTlasDescriptor<'a> = wgt::CreateTlasDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(CreateTlasDescriptor<'_>: Send, Sync);
+
+/// A triangle geometry a [`Blas`] is built with.
+///
+/// Vertex positions are read from `vertex_buffer`, starting at vertex
+/// `first_vertex`, with `vertex_stride` bytes between consecutive vertices.
+/// All the buffers must have the [`BufferUsages::BLAS_INPUT`] usage.
+#[derive(Clone, Debug)]
+pub struct BlasTriangleGeometry<'a> {
+    /// The size of the geometry, which must fit the size at the same index
+    /// that the acceleration structure was created with.
+    pub size: &'a BlasTriangleGeometrySizeDescriptor,
+    /// The buffer holding the positions of the vertices.
+    pub vertex_buffer: &'a Buffer,
+    /// The index of the first vertex in `vertex_buffer`.
+    pub first_vertex: u32,
+    /// The distance in bytes between consecutive vertices.
+    pub vertex_stride: BufferAddress,
+    /// The buffer holding the indices, if the size has an index format.
+    pub index_buffer: Option<&'a Buffer>,
+    /// Offset in bytes into `index_buffer`.
+    pub index_buffer_offset: Option<BufferAddress>,
+    /// A buffer holding a row-major 3x4 matrix of `f32`s the vertices are
+    /// transformed by.
+    pub transform_buffer: Option<&'a Buffer>,
+    /// Offset in bytes into `transform_buffer`, which must be a multiple of 16.
+    pub transform_buffer_offset: Option<BufferAddress>,
+}
 #[cfg(send_sync)]
-static_assertions::assert_impl_all!(Maintain: Send, Sync);
+static_assertions::assert_impl_all!(BlasTriangleGeometry<'_>: Send, Sync);
 
-/// Describes a [`TextureView`].
+/// The geometries a [`Blas`] is built with.
+#[derive(Clone, Debug)]
+pub enum BlasGeometries<'a> {
+    /// Triangle geometries.
+    TriangleGeometries(Vec<BlasTriangleGeometry<'a>>),
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(BlasGeometries<'_>: Send, Sync);
+
+/// A build of a [`Blas`].
 ///
-/// For use with [`Texture::create_view`].
+/// For use with [`CommandEncoder::build_acceleration_structures_unsafe_tlas`].
+#[derive(Clone, Debug)]
+pub struct BlasBuildEntry<'a> {
+    /// The acceleration structure to build.
+    pub blas: &'a Blas,
+    /// The geometries to build it with.
+    pub geometry: BlasGeometries<'a>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(BlasBuildEntry<'_>: Send, Sync);
+
+/// A build of a [`Tlas`].
+///
+/// For use with [`CommandEncoder::build_acceleration_structures_unsafe_tlas`].
+#[derive(Clone, Debug)]
+pub struct TlasBuildEntry<'a> {
+    /// The acceleration structure to build.
+    pub tlas: &'a Tlas,
+    /// The buffer holding the instances, one every [`TLAS_INSTANCE_SIZE`]
+    /// bytes from its start. It must have the [`BufferUsages::TLAS_INPUT`]
+    /// usage.
+    pub instance_buffer: &'a Buffer,
+    /// The number of instances to read from `instance_buffer`, which must not
+    /// be more than the acceleration structure was created with.
+    pub instance_count: u32,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(TlasBuildEntry<'_>: Send, Sync);
+pub use wgt::Maintain as MaintainBase;
+/// Passed to [`Device::poll`] to control how and if it should block.
+pub type Maintain = wgt::Maintain<SubmissionIndex>;
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(Maintain: Send, Sync);
+
+/// Describes a [`TextureView`].
+///
+/// For use with [`Texture::create_view`].
 ///
 /// Corresponds to [WebGPU `GPUTextureViewDescriptor`](
 /// https://gpuweb.github.io/gpuweb/#dictdef-gputextureviewdescriptor).
@@ -1343,6 +1975,11 @@ pub struct TextureViewDescriptor<'a> {
     /// If `Some(count)`, `base_array_layer + count` must be less or equal to the underlying array count.
     /// If `None`, considered to include the rest of the array layers, but at least 1 in total.
     pub array_layer_count: Option<u32>,
+    /// Sources of the components read through the view.
+    ///
+    /// Anything but [`ComponentMapping::IDENTITY`] requires [`Features::TEXTURE_COMPONENT_SWIZZLE`],
+    /// and prevents the view from being used as a storage texture or render attachment.
+    pub swizzle: ComponentMapping,
 }
 static_assertions::assert_impl_all!(TextureViewDescriptor<'_>: Send, Sync);
 
@@ -1480,6 +2117,14 @@ pub struct RenderPassDescriptor<'tex, 'desc> {
     pub timestamp_writes: Option<RenderPassTimestampWrites<'desc>>,
     /// Defines where the occlusion query results will be stored for this pass.
     pub occlusion_query_set: Option<&'tex QuerySet>,
+    /// Defines where the pipeline statistics of this whole pass will be written, if any.
+    ///
+    /// Requires [`Features::PIPELINE_STATISTICS_QUERY`] to be enabled.
+    pub pipeline_statistics_query: Option<RenderPassPipelineStatisticsQuery<'desc>>,
+    /// The attachment controlling the shading rate of each tile of this pass, if any.
+    ///
+    /// Requires [`Features::SHADING_RATE_ATTACHMENT`] to be enabled.
+    pub shading_rate_attachment: Option<RenderPassShadingRateAttachment<'tex>>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(RenderPassDescriptor<'_, '_>: Send, Sync);
@@ -1501,6 +2146,23 @@ pub struct VertexBufferLayout<'a> {
 }
 static_assertions::assert_impl_all!(VertexBufferLayout<'_>: Send, Sync);
 
+/// Describes how the outputs of the vertex stage are written to a capture buffer.
+///
+/// For use in [`VertexState`].
+///
+/// Each attribute names the vertex stage output at its `shader_location`, which must have
+/// exactly the type of its `format`. Only formats made of 32-bit components are supported.
+/// Attributes may not overlap, and each output may only be captured once.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct VertexCaptureBufferLayout<'a> {
+    /// The stride, in bytes, between captured vertices in this buffer. Must be a non-zero
+    /// multiple of 4, no larger than [`MAX_VERTEX_CAPTURE_STRIDE`].
+    pub array_stride: BufferAddress,
+    /// The vertex stage outputs which make up a single captured vertex.
+    pub attributes: &'a [VertexAttribute],
+}
+static_assertions::assert_impl_all!(VertexCaptureBufferLayout<'_>: Send, Sync);
+
 /// Describes the vertex processing in a render pipeline.
 ///
 /// For use in [`RenderPipelineDescriptor`].
@@ -1516,6 +2178,11 @@ pub struct VertexState<'a> {
     pub entry_point: &'a str,
     /// The format of any vertex buffers used with this pipeline.
     pub buffers: &'a [VertexBufferLayout<'a>],
+    /// The buffers the outputs of the vertex stage are written to, while vertex capture is
+    /// active on the render pass. See [`RenderPass::begin_capture`].
+    ///
+    /// Requires [`Features::VERTEX_CAPTURE`] if not empty.
+    pub capture_buffers: &'a [VertexCaptureBufferLayout<'a>],
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(VertexState<'_>: Send, Sync);
@@ -1564,10 +2231,72 @@ pub struct RenderPipelineDescriptor<'a> {
     /// If the pipeline will be used with a multiview render pass, this indicates how many array
     /// layers the attachments will have.
     pub multiview: Option<NonZeroU32>,
+    /// The pipeline cache to use when creating this pipeline.
+    pub cache: Option<&'a PipelineCache>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(RenderPipelineDescriptor<'_>: Send, Sync);
 
+/// Describes the task stage of a mesh pipeline.
+///
+/// For use in [`MeshPipelineDescriptor`].
+#[derive(Clone, Debug)]
+pub struct TaskState<'a> {
+    /// The compiled shader module for this stage.
+    pub module: &'a ShaderModule,
+    /// The name of the entry point in the compiled shader. There must be a function with this name
+    /// in the shader.
+    pub entry_point: &'a str,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(TaskState<'_>: Send, Sync);
+
+/// Describes the mesh stage of a mesh pipeline.
+///
+/// For use in [`MeshPipelineDescriptor`].
+#[derive(Clone, Debug)]
+pub struct MeshState<'a> {
+    /// The compiled shader module for this stage.
+    pub module: &'a ShaderModule,
+    /// The name of the entry point in the compiled shader. There must be a function with this name
+    /// in the shader.
+    pub entry_point: &'a str,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(MeshState<'_>: Send, Sync);
+
+/// Describes a render pipeline whose primitives are generated by mesh shaders.
+///
+/// For use with [`Device::create_mesh_pipeline`].
+#[derive(Clone, Debug)]
+pub struct MeshPipelineDescriptor<'a> {
+    /// Debug label of the pipeline. This will show up in graphics debuggers for easy identification.
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    pub layout: Option<&'a PipelineLayout>,
+    /// The compiled task stage and its entry point, if any. Without a task
+    /// stage, draw calls launch the mesh shader workgroups directly.
+    pub task: Option<TaskState<'a>>,
+    /// The compiled mesh stage and its entry point.
+    pub mesh: MeshState<'a>,
+    /// The properties of the pipeline at the rasterization level. The topology is given by the
+    /// mesh shader and ignored.
+    pub primitive: PrimitiveState,
+    /// The effect of draw calls on the depth and stencil aspects of the output target, if any.
+    pub depth_stencil: Option<DepthStencilState>,
+    /// The multi-sampling properties of the pipeline.
+    pub multisample: MultisampleState,
+    /// The compiled fragment stage, its entry point, and the color targets.
+    pub fragment: Option<FragmentState<'a>>,
+    /// If the pipeline will be used with a multiview render pass, this indicates how many array
+    /// layers the attachments will have.
+    pub multiview: Option<NonZeroU32>,
+    /// The pipeline cache to use when creating this pipeline.
+    pub cache: Option<&'a PipelineCache>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(MeshPipelineDescriptor<'_>: Send, Sync);
+
 /// Describes the timestamp writes of a compute pass.
 ///
 /// For use with [`ComputePassDescriptor`].
@@ -1622,10 +2351,37 @@ pub struct ComputePipelineDescriptor<'a> {
     /// The name of the entry point in the compiled shader. There must be a function with this name
     /// and no return value in the shader.
     pub entry_point: &'a str,
+    /// The pipeline cache to use when creating this pipeline.
+    pub cache: Option<&'a PipelineCache>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(ComputePipelineDescriptor<'_>: Send, Sync);
 
+/// Describes a pipeline cache, which allows reusing compilation work
+/// between program runs.
+///
+/// For use with [`Device::create_pipeline_cache`].
+///
+/// This type is unique to the Rust API of `wgpu`.
+#[derive(Clone, Debug)]
+pub struct PipelineCacheDescriptor<'a> {
+    /// Debug label of the pipeline cache. This might show up in some logs from `wgpu`.
+    pub label: Label<'a>,
+    /// The data used to initialise the cache, as returned by
+    /// [`PipelineCache::get_data`] on the same adapter and driver.
+    ///
+    /// There is no need to validate this data before passing it in.
+    pub data: Option<&'a [u8]>,
+    /// Whether to create an empty cache if `data` is rejected by the backend,
+    /// instead of producing an error.
+    ///
+    /// This is recommended for data loaded from disk, which may have been
+    /// written by a different driver version.
+    pub fallback: bool,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(PipelineCacheDescriptor<'_>: Send, Sync);
+
 pub use wgt::ImageCopyBuffer as ImageCopyBufferBase;
 /// View of a buffer which can be used to copy to/from a texture.
 ///
@@ -1654,6 +2410,28 @@ pub type ImageCopyTextureTagged<'a> = ImageCopyTextureTaggedBase<&'a Texture>;
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(ImageCopyTexture<'_>: Send, Sync);
 
+/// Binds a region of a [`TextureUsages::TILED`] texture to tiles of a [`TilePool`].
+///
+/// For use with [`Queue::bind_texture_pages`].
+///
+/// The region is measured in tiles of [`TextureFormat::tile_dimensions`] texels, so a
+/// `size` of 1x1x1 covers a single tile.
+#[derive(Clone, Debug)]
+pub struct TextureTileBinding<'a> {
+    /// The mip level of the texture to bind.
+    pub mip_level: u32,
+    /// The first tile of the region, in tiles. `z` is the first array layer.
+    pub origin: Origin3d,
+    /// The size of the region, in tiles. `depth_or_array_layers` is the number of
+    /// array layers.
+    pub size: Extent3d,
+    /// The pool and the index of its first tile to bind the region to, in row-major
+    /// order per layer, or `None` to unbind the region.
+    pub memory: Option<(&'a TilePool, u32)>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(TextureTileBinding<'_>: Send, Sync);
+
 /// Describes a [`BindGroupLayout`].
 ///
 /// For use with [`Device::create_bind_group_layout`].
@@ -1759,13 +2537,9 @@ impl Instance {
     ///
     /// Which feature makes this method return true depends on the target platform:
     /// * MacOS/iOS: `metal`, `vulkan-portability` or `angle`
-    /// * Wasm32: `webgpu`, `webgl` or Emscripten target.
-    /// * All other: Always returns true
-    ///
-    /// TODO: Right now it's otherwise not possible yet to opt-out of all features on most platforms.
-    /// See <https://github.com/gfx-rs/wgpu/issues/3514>
-    /// * Windows: always enables Vulkan and GLES with no way to opt out
-    /// * Linux: always enables Vulkan and GLES with no way to opt out
+    /// * Wasm32: `webgpu`, `webgl` or Emscripten target with `gles`.
+    /// * Windows: `dx12`, `vulkan` or `gles`
+    /// * All other: `vulkan` or `gles`
     pub const fn any_backend_feature_enabled() -> bool {
         // Method intentionally kept verbose to keep it a bit easier to follow!
 
@@ -1777,9 +2551,13 @@ impl Instance {
                 || cfg!(feature = "angle")
         // On the web, either WebGPU or WebGL must be enabled.
         } else if cfg!(target_arch = "wasm32") {
-            cfg!(feature = "webgpu") || cfg!(feature = "webgl") || cfg!(target_os = "emscripten")
+            cfg!(feature = "webgpu")
+                || cfg!(feature = "webgl")
+                || (cfg!(target_os = "emscripten") && cfg!(feature = "gles"))
         } else {
-            true
+            cfg!(feature = "vulkan")
+                || cfg!(feature = "gles")
+                || (cfg!(windows) && cfg!(feature = "dx12"))
         }
     }
 
@@ -2079,6 +2857,30 @@ impl Instance {
         self.context.instance_poll_all_devices(force_wait)
     }
 
+    /// Reports debug messages to `callback` instead of logging them.
+    ///
+    /// The callback receives the messages of the Vulkan validation layers, the D3D12 debug
+    /// layer, the Metal backend's diagnostics, the GL debug output, and wgpu's own warnings
+    /// and errors. Each message has a severity and, when the backend reports them, the
+    /// labels of the objects it is about. The validation layers only report messages when
+    /// they are enabled with [`InstanceFlags::VALIDATION`]. wgpu's API and resource logs are
+    /// still logged, as are messages caused by the callback itself.
+    ///
+    /// Setting a callback replaces the previous one. The D3D12 and GL debug output is
+    /// process-wide, so the callback may also receive messages about other instances.
+    ///
+    /// On the web, this is a no-op. The browser reports its messages to the developer console.
+    pub fn set_debug_callback(&self, callback: impl Fn(&DebugMessage) + Send + Sync + 'static) {
+        self.context
+            .instance_set_debug_callback(Some(Arc::new(callback)));
+    }
+
+    /// Removes the callback set with [`Instance::set_debug_callback`], logging debug messages
+    /// again.
+    pub fn remove_debug_callback(&self) {
+        self.context.instance_set_debug_callback(None);
+    }
+
     /// Generates memory report.
     ///
     /// Returns `None` if the feature is not supported by the backend
@@ -2096,6 +2898,8 @@ impl Adapter {
     /// Requests a connection to a physical device, creating a logical device.
     ///
     /// Returns the [`Device`] together with a [`Queue`] that executes command buffers.
+    /// The queues requested with [`DeviceDescriptor::additional_queues`] are dropped,
+    /// use [`Adapter::request_device_with_queues`] to get them.
     ///
     /// # Arguments
     ///
@@ -2114,6 +2918,29 @@ impl Adapter {
         desc: &DeviceDescriptor<'_>,
         trace_path: Option<&std::path::Path>,
     ) -> impl Future<Output = Result<(Device, Queue), RequestDeviceError>> + WasmNotSend {
+        let request = self.request_device_with_queues(desc, trace_path);
+        async move {
+            request
+                .await
+                .map(|(device, mut queues)| (device, queues.remove(0)))
+        }
+    }
+
+    /// Requests a connection to a physical device, creating a logical device with
+    /// several queues.
+    ///
+    /// Returns the [`Device`] together with its main [`Queue`], followed by the
+    /// [`DeviceDescriptor::additional_queues`]. Work submitted to different queues may
+    /// run concurrently, for example compute work alongside rendering. A submission
+    /// waits for the work of the other queues that uses the same resources, and
+    /// [`Queue::wait_for_submission`] orders any other work.
+    ///
+    /// The arguments and panics are the same as for [`Adapter::request_device`].
+    pub fn request_device_with_queues(
+        &self,
+        desc: &DeviceDescriptor<'_>,
+        trace_path: Option<&std::path::Path>,
+    ) -> impl Future<Output = Result<(Device, Vec<Queue>), RequestDeviceError>> + WasmNotSend {
         let context = Arc::clone(&self.context);
         let device = DynContext::adapter_request_device(
             &*self.context,
@@ -2130,18 +2957,25 @@ impl Adapter {
                      queue_id,
                      queue_data,
                  }| {
-                    (
-                        Device {
+                    let device = Device {
+                        context: Arc::clone(&context),
+                        id: device_id,
+                        data: device_data,
+                    };
+                    let additional_queues = DynContext::device_additional_queues(
+                        &*context,
+                        &device.id,
+                        device.data.as_ref(),
+                    );
+                    let queues = std::iter::once((queue_id, queue_data))
+                        .chain(additional_queues)
+                        .map(|(id, data)| Queue {
                             context: Arc::clone(&context),
-                            id: device_id,
-                            data: device_data,
-                        },
-                        Queue {
-                            context,
-                            id: queue_id,
-                            data: queue_data,
-                        },
-                    )
+                            id,
+                            data,
+                        })
+                        .collect();
+                    (device, queues)
                 },
             )
         }
@@ -2237,11 +3071,27 @@ impl Adapter {
         DynContext::adapter_features(&*self.context, &self.id, self.data.as_ref())
     }
 
+    /// Explains whether `feature` is available on this adapter, and if not, why: the backend
+    /// may not implement it at all, or the adapter may lack what the backend requires for it,
+    /// like a driver extension.
+    ///
+    /// If `feature` contains several features, the first unavailable one is explained.
+    pub fn explain_feature(&self, feature: Features) -> FeatureSupport {
+        DynContext::adapter_explain_feature(&*self.context, &self.id, self.data.as_ref(), feature)
+    }
+
     /// The best limits which can be used to create devices on this adapter.
     pub fn limits(&self) -> Limits {
         DynContext::adapter_limits(&*self.context, &self.id, self.data.as_ref())
     }
 
+    /// The maximum of [`DeviceDescriptor::additional_queues`] for devices of this adapter.
+    ///
+    /// Zero if the backend only supports a single queue per device.
+    pub fn max_additional_queues(&self) -> u32 {
+        DynContext::adapter_max_additional_queues(&*self.context, &self.id, self.data.as_ref())
+    }
+
     /// Get info about the adapter itself.
     pub fn get_info(&self) -> AdapterInfo {
         DynContext::adapter_get_info(&*self.context, &self.id, self.data.as_ref())
@@ -2305,6 +3155,29 @@ impl Device {
         DynContext::device_poll(&*self.context, &self.id, self.data.as_ref(), maintain)
     }
 
+    /// Block until the submission `submission_index` is done, or `timeout` elapsed.
+    ///
+    /// Then checks for resource cleanups and mapping callbacks like [`Device::poll`].
+    /// Unlike [`Maintain::WaitForSubmissionIndex`], the returned [`PollStatus`] tells
+    /// whether the submission is done, the wait timed out, or the device was lost,
+    /// so slow GPUs can be handled gracefully.
+    ///
+    /// When running on WebGPU, this is a no-op that returns [`PollStatus::Complete`].
+    /// `Device`s are automatically polled.
+    pub fn poll_until(
+        &self,
+        submission_index: &SubmissionIndex,
+        timeout: std::time::Duration,
+    ) -> PollStatus {
+        DynContext::device_poll_until(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            submission_index.1.as_ref(),
+            timeout,
+        )
+    }
+
     /// The features which can be used on this device.
     ///
     /// No additional features can be used, even if the underlying adapter can support them.
@@ -2319,6 +3192,15 @@ impl Device {
         DynContext::device_limits(&*self.context, &self.id, self.data.as_ref())
     }
 
+    /// How the memory of this device is organized.
+    ///
+    /// On [`MemoryArchitecture::Unified`] devices, buffers created with
+    /// [`Features::MAPPABLE_PRIMARY_BUFFERS`] can be written and read by the CPU directly,
+    /// without copies through staging buffers.
+    pub fn memory_architecture(&self) -> MemoryArchitecture {
+        DynContext::device_memory_architecture(&*self.context, &self.id, self.data.as_ref())
+    }
+
     /// Creates a shader module from either SPIR-V or WGSL source code.
     pub fn create_shader_module(&self, desc: ShaderModuleDescriptor<'_>) -> ShaderModule {
         let (id, data) = DynContext::device_create_shader_module(
@@ -2451,10 +3333,13 @@ impl Device {
             self.data.as_ref(),
             desc,
         );
+        let mut entries = desc.entries.to_vec();
+        entries.sort_by_key(|entry| entry.binding);
         BindGroupLayout {
             context: Arc::clone(&self.context),
             id,
             data,
+            entries,
         }
     }
 
@@ -2488,6 +3373,53 @@ impl Device {
         }
     }
 
+    /// Creates a [`RenderPipeline`] without blocking the calling thread, like
+    /// `createRenderPipelineAsync` in WebGPU.
+    ///
+    /// On native backends the pipeline is compiled on a pool of background threads. Errors
+    /// are reported the same way as for [`Device::create_render_pipeline`], to the error
+    /// scopes that were open when this was called.
+    ///
+    /// The future borrows the shader modules and layout referenced by `desc`. Dropping it
+    /// before it resolves cancels the compilation without waiting for it, and discards its
+    /// errors.
+    pub fn create_render_pipeline_async<'a>(
+        &self,
+        desc: &RenderPipelineDescriptor<'a>,
+    ) -> impl Future<Output = RenderPipeline> + WasmNotSend + 'a {
+        let context = Arc::clone(&self.context);
+        let future = DynContext::device_create_render_pipeline_async(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        let borrow = PhantomData::<&'a ()>;
+        async move {
+            let _borrow = borrow;
+            let (id, data) = future.await;
+            RenderPipeline { context, id, data }
+        }
+    }
+
+    /// Creates a [`RenderPipeline`] whose primitives are generated by mesh shaders, to be drawn
+    /// with [`RenderPass::draw_mesh_tasks`].
+    ///
+    /// Requires [`Features::EXPERIMENTAL_MESH_SHADER`].
+    pub fn create_mesh_pipeline(&self, desc: &MeshPipelineDescriptor<'_>) -> RenderPipeline {
+        let (id, data) = DynContext::device_create_mesh_pipeline(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        RenderPipeline {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
     /// Creates a [`ComputePipeline`].
     pub fn create_compute_pipeline(&self, desc: &ComputePipelineDescriptor<'_>) -> ComputePipeline {
         let (id, data) = DynContext::device_create_compute_pipeline(
@@ -2503,11 +3435,67 @@ impl Device {
         }
     }
 
+    /// Creates a [`ComputePipeline`] without blocking the calling thread, like
+    /// `createComputePipelineAsync` in WebGPU.
+    ///
+    /// See [`Device::create_render_pipeline_async`] for how the pipeline is compiled.
+    pub fn create_compute_pipeline_async<'a>(
+        &self,
+        desc: &ComputePipelineDescriptor<'a>,
+    ) -> impl Future<Output = ComputePipeline> + WasmNotSend + 'a {
+        let context = Arc::clone(&self.context);
+        let future = DynContext::device_create_compute_pipeline_async(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        let borrow = PhantomData::<&'a ()>;
+        async move {
+            let _borrow = borrow;
+            let (id, data) = future.await;
+            ComputePipeline { context, id, data }
+        }
+    }
+
+    /// Creates a [`PipelineCache`] with initial data.
+    ///
+    /// This can be passed to [`Device::create_compute_pipeline`]
+    /// and [`Device::create_render_pipeline`] to either accelerate these
+    /// or add the compiled pipelines to the cache.
+    ///
+    /// Requires [`Features::PIPELINE_CACHE`].
+    ///
+    /// # Safety
+    ///
+    /// If the `data` field of `desc` is set, it must have previously been returned
+    /// from a call to [`PipelineCache::get_data`] on an adapter with the same
+    /// [`AdapterInfo`]. The data may still be rejected by the backend, for example
+    /// after a driver update, which is handled according to `desc.fallback`.
+    pub unsafe fn create_pipeline_cache(
+        &self,
+        desc: &PipelineCacheDescriptor<'_>,
+    ) -> PipelineCache {
+        let (id, data) = unsafe {
+            DynContext::device_create_pipeline_cache(
+                &*self.context,
+                &self.id,
+                self.data.as_ref(),
+                desc,
+            )
+        };
+        PipelineCache {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
     /// Creates a [`Buffer`].
     pub fn create_buffer(&self, desc: &BufferDescriptor<'_>) -> Buffer {
         let mut map_context = MapContext::new(desc.size);
         if desc.mapped_at_creation {
-            map_context.initial_range = 0..desc.size;
+            map_context.mapped_ranges.push(0..desc.size);
         }
 
         let (id, data) =
@@ -2539,6 +3527,7 @@ impl Device {
                 view_formats: &[],
                 ..desc.clone()
             },
+            view_formats: desc.view_formats.to_vec(),
         }
     }
 
@@ -2578,6 +3567,7 @@ impl Device {
                 view_formats: &[],
                 ..desc.clone()
             },
+            view_formats: desc.view_formats.to_vec(),
         }
     }
 
@@ -2596,7 +3586,7 @@ impl Device {
     ) -> Buffer {
         let mut map_context = MapContext::new(desc.size);
         if desc.mapped_at_creation {
-            map_context.initial_range = 0..desc.size;
+            map_context.mapped_ranges.push(0..desc.size);
         }
 
         let (id, buffer) = unsafe {
@@ -2647,59 +3637,501 @@ impl Device {
         }
     }
 
-    /// Set a callback for errors that are not handled in error scopes.
-    pub fn on_uncaptured_error(&self, handler: Box<dyn UncapturedErrorHandler>) {
-        self.context
-            .device_on_uncaptured_error(&self.id, self.data.as_ref(), handler);
+    /// Creates a new [`TilePool`].
+    ///
+    /// [`Features::TILED_TEXTURE`] must be enabled on the device.
+    pub fn create_tile_pool(&self, desc: &TilePoolDescriptor<'_>) -> TilePool {
+        let (id, data) =
+            DynContext::device_create_tile_pool(&*self.context, &self.id, self.data.as_ref(), desc);
+        TilePool {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
     }
 
-    /// Push an error scope.
-    pub fn push_error_scope(&self, filter: ErrorFilter) {
-        self.context
-            .device_push_error_scope(&self.id, self.data.as_ref(), filter);
+    /// Creates a new [`MemoryHeap`].
+    ///
+    /// [`Features::MEMORY_HEAPS`] must be enabled on the device.
+    pub fn create_memory_heap(&self, desc: &MemoryHeapDescriptor<'_>) -> MemoryHeap {
+        let (id, data) = DynContext::device_create_memory_heap(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        MemoryHeap {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
     }
 
-    /// Pop an error scope.
-    pub fn pop_error_scope(&self) -> impl Future<Output = Option<Error>> + WasmNotSend {
-        self.context
-            .device_pop_error_scope(&self.id, self.data.as_ref())
-    }
+    /// Creates a [`Buffer`] at `offset` bytes into `heap`.
+    ///
+    /// `offset` must be a multiple of the alignment returned by
+    /// [`Device::buffer_memory_requirements`] for `desc`, and the buffer must fit
+    /// in the heap. Its usages must be allowed by the [`MemoryHeapType`] of the heap.
+    ///
+    /// The buffer is zero-initialized on first use like any other buffer, but
+    /// its contents are undefined once a resource aliasing it has used the memory;
+    /// see [`MemoryHeap`].
+    pub fn create_buffer_in_heap(
+        &self,
+        desc: &BufferDescriptor<'_>,
+        heap: &MemoryHeap,
+        offset: BufferAddress,
+    ) -> Buffer {
+        let mut map_context = MapContext::new(desc.size);
+        if desc.mapped_at_creation {
+            map_context.mapped_ranges.push(0..desc.size);
+        }
 
-    /// Starts frame capture.
-    pub fn start_capture(&self) {
-        DynContext::device_start_capture(&*self.context, &self.id, self.data.as_ref())
-    }
+        let (id, data) = DynContext::device_create_buffer_in_heap(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+            &heap.id,
+            heap.data.as_ref(),
+            offset,
+        );
 
-    /// Stops frame capture.
-    pub fn stop_capture(&self) {
-        DynContext::device_stop_capture(&*self.context, &self.id, self.data.as_ref())
+        Buffer {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            map_context: Mutex::new(map_context),
+            size: desc.size,
+            usage: desc.usage,
+        }
     }
 
-    /// Apply a callback to this `Device`'s underlying backend device.
-    ///
-    /// If this `Device` is implemented by the backend API given by `A` (Vulkan,
-    /// Dx12, etc.), then apply `hal_device_callback` to `Some(&device)`, where
-    /// `device` is the underlying backend device type, [`A::Device`].
-    ///
-    /// If this `Device` uses a different backend, apply `hal_device_callback`
-    /// to `None`.
-    ///
-    /// The device is locked for reading while `hal_device_callback` runs. If
-    /// the callback attempts to perform any `wgpu` operations that require
-    /// write access to the device (destroying a buffer, say), deadlock will
-    /// occur. The locks are automatically released when the callback returns.
-    ///
-    /// # Safety
+    /// Creates a [`Texture`] at `offset` bytes into `heap`.
     ///
-    /// - The raw handle passed to the callback must not be manually destroyed.
+    /// `offset` must be a multiple of the alignment returned by
+    /// [`Device::texture_memory_requirements`] for `desc`, and the texture must
+    /// fit in the heap, which must be [`MemoryHeapType::DeviceLocal`].
     ///
-    /// [`A::Device`]: hal::Api::Device
-    #[cfg(wgpu_core)]
-    pub unsafe fn as_hal<A: wgc::hal_api::HalApi, F: FnOnce(Option<&A::Device>) -> R, R>(
+    /// The texture is zero-initialized on first use like any other texture, but
+    /// its contents are undefined once a resource aliasing it has used the memory;
+    /// see [`MemoryHeap`].
+    pub fn create_texture_in_heap(
         &self,
-        hal_device_callback: F,
-    ) -> Option<R> {
-        self.context
+        desc: &TextureDescriptor<'_>,
+        heap: &MemoryHeap,
+        offset: BufferAddress,
+    ) -> Texture {
+        let (id, data) = DynContext::device_create_texture_in_heap(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+            &heap.id,
+            heap.data.as_ref(),
+            offset,
+        );
+        Texture {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            view_formats: desc.view_formats.to_vec(),
+        }
+    }
+
+    /// Creates a [`Texture`] backed by memory allocated outside of wgpu.
+    ///
+    /// The texture must be 2D, with a single mip level, array layer and sample.
+    /// Its contents are those of the external image, so it isn't zero-initialized.
+    ///
+    /// [`Features::EXTERNAL_TEXTURE_IMPORT`] must be enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - `source` must hold a valid image of the size and format of `desc`, which
+    ///   must outlive the texture.
+    /// - The image must not be written outside of wgpu while the texture is in use.
+    #[cfg(native)]
+    pub unsafe fn import_external_texture(
+        &self,
+        desc: &TextureDescriptor<'_>,
+        source: ExternalImageSource,
+    ) -> Texture {
+        let (id, data) = unsafe {
+            DynContext::device_import_external_texture(
+                &*self.context,
+                &self.id,
+                self.data.as_ref(),
+                desc,
+                source,
+            )
+        };
+        Texture {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            view_formats: desc.view_formats.to_vec(),
+        }
+    }
+
+    /// Creates a new [`ExternalFence`], whose value starts at zero.
+    ///
+    /// [`Features::EXTERNAL_FENCE`] must be enabled on the device.
+    pub fn create_external_fence(&self, desc: &ExternalFenceDescriptor<'_>) -> ExternalFence {
+        let (id, data) = DynContext::device_create_external_fence(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        ExternalFence {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
+    /// Creates an [`ExternalFence`] sharing the timeline of a fence created outside
+    /// of wgpu.
+    ///
+    /// The handle must be of the kind the backend of the device uses: an opaque fd of a
+    /// timeline semaphore on Vulkan, a shared handle of a fence on DX12, or a shared
+    /// event on Metal. Opaque fds and shared handles are left owned by the caller.
+    ///
+    /// [`Features::EXTERNAL_FENCE`] must be enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must refer to a valid fence of the device's backend, created on the
+    ///   same physical device.
+    /// - The fence's value must only ever increase.
+    #[cfg(native)]
+    pub unsafe fn import_external_fence(
+        &self,
+        desc: &ExternalFenceDescriptor<'_>,
+        handle: ExternalFenceHandle,
+    ) -> ExternalFence {
+        let (id, data) = unsafe {
+            DynContext::device_import_external_fence(
+                &*self.context,
+                &self.id,
+                self.data.as_ref(),
+                desc,
+                handle,
+            )
+        };
+        ExternalFence {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
+    /// Creates a [`Buffer`] whose memory can be shared with other APIs through
+    /// [`Buffer::export_external_memory`].
+    ///
+    /// The buffer can't be mapped, and is zero-initialized on first use by wgpu.
+    /// Work done on it by other APIs must be synchronized with the queue, for
+    /// example with an [`ExternalFence`].
+    ///
+    /// [`Features::EXTERNAL_MEMORY`] must be enabled on the device.
+    pub fn create_external_buffer(&self, desc: &BufferDescriptor<'_>) -> Buffer {
+        let (id, data) = DynContext::device_create_external_buffer(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        Buffer {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            map_context: Mutex::new(MapContext::new(desc.size)),
+            size: desc.size,
+            usage: desc.usage,
+        }
+    }
+
+    /// Creates a [`Buffer`] bound to memory allocated outside of wgpu, such as a
+    /// CUDA or HIP allocation exported as an opaque fd on Vulkan, or a committed
+    /// resource shared with a D3D12 shared handle.
+    ///
+    /// The buffer can't be mapped, and its contents are the ones of the memory.
+    /// Opaque fds and shared handles are left owned by the caller.
+    ///
+    /// [`Features::EXTERNAL_MEMORY`] must be enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - `memory` must refer to valid memory of the kind the device's backend shares,
+    ///   allocated on the same physical device and at least as large as `desc.size`.
+    /// - Work done on the memory by other APIs must be synchronized with the queue.
+    #[cfg(native)]
+    pub unsafe fn import_external_buffer(
+        &self,
+        desc: &BufferDescriptor<'_>,
+        memory: ExternalMemory,
+    ) -> Buffer {
+        let (id, data) = unsafe {
+            DynContext::device_import_external_buffer(
+                &*self.context,
+                &self.id,
+                self.data.as_ref(),
+                desc,
+                memory,
+            )
+        };
+        Buffer {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            map_context: Mutex::new(MapContext::new(desc.size)),
+            size: desc.size,
+            usage: desc.usage,
+        }
+    }
+
+    /// Returns the size and alignment of the memory a buffer created from `desc`
+    /// with [`Device::create_buffer_in_heap`] takes up.
+    ///
+    /// [`Features::MEMORY_HEAPS`] must be enabled on the device.
+    pub fn buffer_memory_requirements(&self, desc: &BufferDescriptor<'_>) -> MemoryRequirements {
+        DynContext::device_buffer_memory_requirements(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        )
+    }
+
+    /// Returns the size and alignment of the memory a texture created from `desc`
+    /// with [`Device::create_texture_in_heap`] takes up.
+    ///
+    /// [`Features::MEMORY_HEAPS`] must be enabled on the device.
+    pub fn texture_memory_requirements(&self, desc: &TextureDescriptor<'_>) -> MemoryRequirements {
+        DynContext::device_texture_memory_requirements(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        )
+    }
+
+    /// Creates a new [`Blas`], which can be built with geometries fitting
+    /// `sizes`.
+    ///
+    /// [`Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE`] must be
+    /// enabled on the device.
+    pub fn create_blas(
+        &self,
+        desc: &CreateBlasDescriptor<'_>,
+        sizes: BlasGeometrySizeDescriptors,
+    ) -> Blas {
+        let (id, handle, data) = DynContext::device_create_blas(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+            sizes,
+        );
+        Blas {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            handle,
+        }
+    }
+
+    /// Creates a new [`Tlas`], which can be built with up to
+    /// `desc.max_instances` instances.
+    ///
+    /// [`Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE`] must be
+    /// enabled on the device.
+    pub fn create_tlas(&self, desc: &CreateTlasDescriptor<'_>) -> Tlas {
+        let (id, data) =
+            DynContext::device_create_tlas(&*self.context, &self.id, self.data.as_ref(), desc);
+        Tlas {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
+    /// Creates a [`ShaderModule`] like [`Device::create_shader_module`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_shader_module(
+        &self,
+        desc: ShaderModuleDescriptor<'_>,
+    ) -> Result<ShaderModule, CreateError> {
+        try_create(&*self.context, || self.create_shader_module(desc))
+    }
+
+    /// Creates a [`BindGroup`] like [`Device::create_bind_group`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_bind_group(
+        &self,
+        desc: &BindGroupDescriptor<'_>,
+    ) -> Result<BindGroup, CreateError> {
+        try_create(&*self.context, || self.create_bind_group(desc))
+    }
+
+    /// Creates a [`BindGroupLayout`] like [`Device::create_bind_group_layout`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_bind_group_layout(
+        &self,
+        desc: &BindGroupLayoutDescriptor<'_>,
+    ) -> Result<BindGroupLayout, CreateError> {
+        try_create(&*self.context, || self.create_bind_group_layout(desc))
+    }
+
+    /// Creates a [`PipelineLayout`] like [`Device::create_pipeline_layout`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_pipeline_layout(
+        &self,
+        desc: &PipelineLayoutDescriptor<'_>,
+    ) -> Result<PipelineLayout, CreateError> {
+        try_create(&*self.context, || self.create_pipeline_layout(desc))
+    }
+
+    /// Creates a [`RenderPipeline`] like [`Device::create_render_pipeline`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_render_pipeline(
+        &self,
+        desc: &RenderPipelineDescriptor<'_>,
+    ) -> Result<RenderPipeline, CreateError> {
+        try_create(&*self.context, || self.create_render_pipeline(desc))
+    }
+
+    /// Creates a [`ComputePipeline`] like [`Device::create_compute_pipeline`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_compute_pipeline(
+        &self,
+        desc: &ComputePipelineDescriptor<'_>,
+    ) -> Result<ComputePipeline, CreateError> {
+        try_create(&*self.context, || self.create_compute_pipeline(desc))
+    }
+
+    /// Creates a [`Buffer`] like [`Device::create_buffer`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_buffer(&self, desc: &BufferDescriptor<'_>) -> Result<Buffer, CreateError> {
+        try_create(&*self.context, || self.create_buffer(desc))
+    }
+
+    /// Creates a [`Texture`] like [`Device::create_texture`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_texture(&self, desc: &TextureDescriptor<'_>) -> Result<Texture, CreateError> {
+        try_create(&*self.context, || self.create_texture(desc))
+    }
+
+    /// Creates a [`Sampler`] like [`Device::create_sampler`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_sampler(&self, desc: &SamplerDescriptor<'_>) -> Result<Sampler, CreateError> {
+        try_create(&*self.context, || self.create_sampler(desc))
+    }
+
+    /// Creates a [`QuerySet`] like [`Device::create_query_set`], but returns the error instead of
+    /// reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_query_set(
+        &self,
+        desc: &QuerySetDescriptor<'_>,
+    ) -> Result<QuerySet, CreateError> {
+        try_create(&*self.context, || self.create_query_set(desc))
+    }
+
+    /// Set a callback for errors that are not handled in error scopes.
+    ///
+    /// On native backends, uncaptured errors panic until a callback is set.
+    pub fn on_uncaptured_error(&self, handler: Box<dyn UncapturedErrorHandler>) {
+        self.context
+            .device_on_uncaptured_error(&self.id, self.data.as_ref(), handler);
+    }
+
+    /// Push an error scope.
+    ///
+    /// Errors matching `filter` are caught by the innermost scope with that filter, instead of
+    /// reaching the scopes below it or the uncaptured error handler. A scope only keeps the
+    /// first error it catches.
+    pub fn push_error_scope(&self, filter: ErrorFilter) {
+        self.context
+            .device_push_error_scope(&self.id, self.data.as_ref(), filter);
+    }
+
+    /// Pop an error scope.
+    ///
+    /// Resolves to the first error caught by the innermost error scope, if any. If no error scope
+    /// was pushed, this resolves to `None`, like the rejected promise of WebGPU does.
+    pub fn pop_error_scope(&self) -> impl Future<Output = Option<Error>> + WasmNotSend {
+        self.context
+            .device_pop_error_scope(&self.id, self.data.as_ref())
+    }
+
+    /// Starts frame capture.
+    pub fn start_capture(&self) {
+        DynContext::device_start_capture(&*self.context, &self.id, self.data.as_ref())
+    }
+
+    /// Stops frame capture.
+    pub fn stop_capture(&self) {
+        DynContext::device_stop_capture(&*self.context, &self.id, self.data.as_ref())
+    }
+
+    /// Apply a callback to this `Device`'s underlying backend device.
+    ///
+    /// If this `Device` is implemented by the backend API given by `A` (Vulkan,
+    /// Dx12, etc.), then apply `hal_device_callback` to `Some(&device)`, where
+    /// `device` is the underlying backend device type, [`A::Device`].
+    ///
+    /// If this `Device` uses a different backend, apply `hal_device_callback`
+    /// to `None`.
+    ///
+    /// The device is locked for reading while `hal_device_callback` runs. If
+    /// the callback attempts to perform any `wgpu` operations that require
+    /// write access to the device (destroying a buffer, say), deadlock will
+    /// occur. The locks are automatically released when the callback returns.
+    ///
+    /// # Safety
+    ///
+    /// - The raw handle passed to the callback must not be manually destroyed.
+    ///
+    /// [`A::Device`]: hal::Api::Device
+    #[cfg(wgpu_core)]
+    pub unsafe fn as_hal<A: wgc::hal_api::HalApi, F: FnOnce(Option<&A::Device>) -> R, R>(
+        &self,
+        hal_device_callback: F,
+    ) -> Option<R> {
+        self.context
             .as_any()
             .downcast_ref::<crate::backend::ContextWgpuCore>()
             .map(|ctx| unsafe {
@@ -2710,6 +4142,146 @@ impl Device {
             })
     }
 
+    /// Creates a [`Device`] and [`Queue`] on a `VkDevice` created outside of wgpu,
+    /// together with the [`Adapter`] of its `VkPhysicalDevice`.
+    ///
+    /// This lets applications embedded in native engines or OpenXR runtimes drive
+    /// wgpu on a device they created. The device stays owned by the caller.
+    ///
+    /// # Safety
+    ///
+    /// - `instance` must use the Vulkan `VkInstance` that `physical_device` was
+    ///   enumerated from, for example by wrapping it with
+    ///   [`hal::vulkan::Instance::from_raw`] and [`Instance::from_hal`].
+    /// - `device` must be created from `physical_device` with a queue of
+    ///   `queue_family_index` at `queue_index`, and the extensions and features that
+    ///   [`hal::vulkan::Adapter::required_device_extensions`] and
+    ///   [`hal::vulkan::Adapter::physical_device_features`] return for
+    ///   `desc.required_features`.
+    /// - `enabled_extensions` must list the extensions `device` was created with.
+    /// - `device` must outlive the returned objects.
+    #[cfg(vulkan)]
+    pub unsafe fn from_raw_vulkan(
+        instance: &Instance,
+        physical_device: *mut std::ffi::c_void,
+        device: *mut std::ffi::c_void,
+        enabled_extensions: &[&'static std::ffi::CStr],
+        queue_family_index: u32,
+        queue_index: u32,
+        desc: &DeviceDescriptor<'_>,
+    ) -> Result<(Adapter, Device, Queue), RequestDeviceError> {
+        let hal_instance = unsafe { instance.as_hal::<hal::api::Vulkan>() }
+            .ok_or(wgc::instance::RequestDeviceError::InvalidAdapter)?;
+        // we don't want to link to ash for this
+        let exposed = hal_instance
+            .expose_adapter(unsafe { std::mem::transmute(physical_device) })
+            .ok_or(wgc::instance::RequestDeviceError::InvalidAdapter)?;
+        if !exposed.features.contains(desc.required_features) {
+            return Err(wgc::instance::RequestDeviceError::UnsupportedFeature(
+                desc.required_features - exposed.features,
+            )
+            .into());
+        }
+        let hal_device = unsafe {
+            exposed.adapter.device_from_raw_handle(
+                std::mem::transmute(device),
+                enabled_extensions,
+                desc.required_features,
+                queue_family_index,
+                queue_index,
+            )
+        }
+        .map_err(wgc::instance::RequestDeviceError::from)?;
+        unsafe { Self::from_raw_hal(instance, exposed, hal_device, desc) }
+    }
+
+    /// Creates a [`Device`] and [`Queue`] on an `ID3D12Device` and direct
+    /// `ID3D12CommandQueue` created outside of wgpu, together with the [`Adapter`]
+    /// of the device.
+    ///
+    /// wgpu holds references of its own to the device and queue.
+    ///
+    /// # Safety
+    ///
+    /// - `instance` must have a DX12 backend.
+    /// - `device` must be a valid `ID3D12Device`, and `queue` a valid direct command
+    ///   queue created from it.
+    #[cfg(dx12)]
+    pub unsafe fn from_raw_dx12(
+        instance: &Instance,
+        device: *mut std::ffi::c_void,
+        queue: *mut std::ffi::c_void,
+        desc: &DeviceDescriptor<'_>,
+    ) -> Result<(Adapter, Device, Queue), RequestDeviceError> {
+        let hal_instance = unsafe { instance.as_hal::<hal::api::Dx12>() }
+            .ok_or(wgc::instance::RequestDeviceError::InvalidAdapter)?;
+        let exposed = unsafe { hal_instance.expose_adapter_from_raw(device.cast()) }
+            .ok_or(wgc::instance::RequestDeviceError::InvalidAdapter)?;
+        if !exposed.features.contains(desc.required_features) {
+            return Err(wgc::instance::RequestDeviceError::UnsupportedFeature(
+                desc.required_features - exposed.features,
+            )
+            .into());
+        }
+        let hal_device = unsafe {
+            exposed
+                .adapter
+                .device_from_raw(queue.cast(), &desc.required_limits)
+        }
+        .map_err(wgc::instance::RequestDeviceError::from)?;
+        unsafe { Self::from_raw_hal(instance, exposed, hal_device, desc) }
+    }
+
+    /// Creates a [`Device`] and [`Queue`] on an `MTLDevice` and `MTLCommandQueue`
+    /// created outside of wgpu, together with the [`Adapter`] of the device.
+    ///
+    /// The device and queue are retained by wgpu, so the caller's references stay
+    /// valid.
+    ///
+    /// # Safety
+    ///
+    /// - `instance` must have a Metal backend.
+    /// - `device` must be a valid `MTLDevice`, and `queue` a valid `MTLCommandQueue`
+    ///   created from it.
+    #[cfg(metal)]
+    pub unsafe fn from_raw_metal(
+        instance: &Instance,
+        device: *mut std::ffi::c_void,
+        queue: *mut std::ffi::c_void,
+        desc: &DeviceDescriptor<'_>,
+    ) -> Result<(Adapter, Device, Queue), RequestDeviceError> {
+        let hal_instance = unsafe { instance.as_hal::<hal::api::Metal>() }
+            .ok_or(wgc::instance::RequestDeviceError::InvalidAdapter)?;
+        // we don't want to link to metal-rs for this
+        #[allow(clippy::transmute_ptr_to_ref)]
+        let exposed = hal_instance.expose_adapter_from_raw(unsafe { std::mem::transmute(device) });
+        if !exposed.features.contains(desc.required_features) {
+            return Err(wgc::instance::RequestDeviceError::UnsupportedFeature(
+                desc.required_features - exposed.features,
+            )
+            .into());
+        }
+        #[allow(clippy::transmute_ptr_to_ref)]
+        let hal_device = unsafe {
+            exposed
+                .adapter
+                .device_from_raw(std::mem::transmute(queue), desc.required_features)
+        };
+        unsafe { Self::from_raw_hal(instance, exposed, hal_device, desc) }
+    }
+
+    #[cfg(any(vulkan, dx12, metal))]
+    unsafe fn from_raw_hal<A: wgc::hal_api::HalApi>(
+        instance: &Instance,
+        hal_adapter: hal::ExposedAdapter<A>,
+        hal_device: hal::OpenDevice<A>,
+        desc: &DeviceDescriptor<'_>,
+    ) -> Result<(Adapter, Device, Queue), RequestDeviceError> {
+        let adapter = unsafe { instance.create_adapter_from_hal(hal_adapter) };
+        let (device, queue) = unsafe { adapter.create_device_from_hal(hal_device, desc, None) }?;
+        Ok((adapter, device, queue))
+    }
+
     /// Destroy this device.
     pub fn destroy(&self) {
         DynContext::device_destroy(&*self.context, &self.id, self.data.as_ref())
@@ -2727,6 +4299,115 @@ impl Device {
             Box::new(callback),
         )
     }
+
+    /// Returns a future resolving once this device is lost, with the reason and
+    /// a message describing why.
+    ///
+    /// All the resources of a lost device are invalid, and the work submitted to
+    /// it is dropped without errors. To recover, request a new device from the
+    /// same [`Adapter`] and recreate the resources with it.
+    pub fn lost(&self) -> impl Future<Output = (DeviceLostReason, String)> + WasmNotSend {
+        DynContext::device_lost(&*self.context, &self.id, self.data.as_ref())
+    }
+
+    /// Loses this device with [`DeviceLostReason::Unknown`] and `message`, like
+    /// a driver losing it would.
+    ///
+    /// This is meant to test how an application recovers from device loss. It
+    /// does nothing on the WebGPU backend.
+    pub fn mark_lost(&self, message: &str) {
+        DynContext::device_mark_lost(&*self.context, &self.id, self.data.as_ref(), message)
+    }
+
+    /// Loses this device as if the driver had reported it lost while it was
+    /// being used, for example with `VK_ERROR_DEVICE_LOST`.
+    ///
+    /// Unlike [`Device::mark_lost`], this goes through the handling of driver
+    /// errors: the in-flight submissions are dropped, pending mappings fail, and
+    /// the device lost callback is called by the next poll. It does nothing on
+    /// the WebGPU backend.
+    pub fn mark_lost_by_driver(&self) {
+        DynContext::device_mark_lost_by_driver(&*self.context, &self.id, self.data.as_ref())
+    }
+
+    /// Start a watchdog reporting submissions which are still running after `threshold`.
+    ///
+    /// `callback` is called from a background thread, at most once per submission,
+    /// while the submission is still running. Choosing a threshold below the time after
+    /// which the operating system resets the GPU (two seconds by default on Windows)
+    /// allows diagnosing shaders that never terminate before the device is lost.
+    ///
+    /// The reports carry the labels of the command buffers and passes of the submission,
+    /// and of the debug groups and markers recorded outside of passes. Replaces any
+    /// previous watchdog of this device.
+    ///
+    /// Watchdogs are not supported on WebGPU, or on WebAssembly without threads.
+    pub fn enable_watchdog(
+        &self,
+        threshold: std::time::Duration,
+        callback: impl Fn(SubmissionTimeout) + Send + 'static,
+    ) {
+        DynContext::device_set_watchdog(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            Some((threshold, Box::new(callback))),
+        )
+    }
+
+    /// Stop the watchdog started with [`Device::enable_watchdog`].
+    pub fn disable_watchdog(&self) {
+        DynContext::device_set_watchdog(&*self.context, &self.id, self.data.as_ref(), None)
+    }
+
+    /// Start or stop polling this device from a background thread.
+    ///
+    /// While enabled, the device is polled whenever it has work in flight, so the
+    /// callbacks of [`BufferSlice::map_async`] and [`Queue::on_submitted_work_done`]
+    /// are called without calling [`Device::poll`]. The callbacks are then called
+    /// from the background thread.
+    ///
+    /// This is enabled for every device of an instance created with
+    /// [`InstanceFlags::AUTOMATIC_POLLING`]. It has no effect on WebGPU, which always
+    /// calls callbacks on its own, and is not supported on WebAssembly without threads.
+    pub fn set_automatic_polling(&self, enabled: bool) {
+        DynContext::device_set_automatic_polling(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            enabled,
+        )
+    }
+
+    /// Start or stop writing a listing of the commands of new command encoders.
+    ///
+    /// Command buffers finished from encoders created while this is enabled can be
+    /// disassembled with [`CommandBuffer::disassembly`]. Recording the listing is
+    /// slow, and only meant for debugging. Not supported on WebGPU.
+    pub fn set_command_disassembly(&self, enabled: bool) {
+        DynContext::device_set_command_disassembly(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            enabled,
+        )
+    }
+
+    /// Set the callback receiving the messages of `debugPrintf` statements in shaders.
+    ///
+    /// Messages of a submission are delivered once the submission is done, during
+    /// [`Device::poll`] or [`Queue::submit`]. Without a callback, messages are
+    /// logged at the info level.
+    ///
+    /// Only has an effect if [`Features::SHADER_DEBUG_PRINTF`] is enabled.
+    pub fn set_debug_printf_callback(&self, callback: impl Fn(&str) + WasmNotSendSync + 'static) {
+        DynContext::device_set_debug_printf_callback(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            Box::new(callback),
+        )
+    }
 }
 
 impl Drop for Device {
@@ -3002,6 +4683,9 @@ impl Buffer {
     }
 
     /// Flushes any pending write operations and unmaps the buffer from host memory.
+    ///
+    /// All the mapped slices of the buffer are unmapped, see [`BufferSlice::unmap`]
+    /// for unmapping a single one.
     pub fn unmap(&self) {
         self.map_context.lock().reset();
         DynContext::buffer_unmap(&*self.context, &self.id, self.data.as_ref());
@@ -3012,6 +4696,27 @@ impl Buffer {
         DynContext::buffer_destroy(&*self.context, &self.id, self.data.as_ref());
     }
 
+    /// Returns the device address of the start of this buffer, for binding it as a vertex
+    /// buffer through [`VertexBufferIndirectArgs`](crate::util::VertexBufferIndirectArgs).
+    ///
+    /// The buffer must have been created with [`BufferUsages::VERTEX`].
+    /// [`Features::DEVICE_GENERATED_COMMANDS`] must be enabled on the device in order to call
+    /// this function.
+    pub fn device_address(&self) -> BufferAddress {
+        DynContext::buffer_device_address(&*self.context, &self.id, self.data.as_ref())
+    }
+
+    /// Returns the memory of a buffer made by [`Device::create_external_buffer`] or
+    /// [`Device::import_external_buffer`], for importing it into another API such
+    /// as CUDA or HIP.
+    ///
+    /// Opaque fds and D3D12 shared handles are owned by the caller, and must be
+    /// closed once they've been imported.
+    #[cfg(native)]
+    pub fn export_external_memory(&self) -> ExternalMemory {
+        DynContext::buffer_export_external_memory(&*self.context, &self.id, self.data.as_ref())
+    }
+
     /// Returns the length of the buffer allocation in bytes.
     ///
     /// This is always equal to the `size` that was specified when creating the buffer.
@@ -3037,23 +4742,27 @@ impl<'a> BufferSlice<'a> {
     /// has completed. There are no restrictions on the code you can run in the callback, however on native the
     /// call to the function will not complete until the callback returns, so prefer keeping callbacks short
     /// and used to set flags, send messages, etc.
+    ///
+    /// Disjoint slices of a buffer can be mapped at the same time with the same [`MapMode`], and are
+    /// unmapped independently with [`BufferSlice::unmap`]. While some slices are mapped, copies can
+    /// still use the other parts of the buffer, which allows reading back a ring buffer one slice at
+    /// a time. On WebGPU, only one slice of a buffer can be mapped at a time.
     pub fn map_async(
         &self,
         mode: MapMode,
         callback: impl FnOnce(Result<(), BufferAsyncError>) + WasmNotSend + 'static,
     ) {
         let mut mc = self.buffer.map_context.lock();
-        assert_eq!(
-            mc.initial_range,
-            0..0,
-            "Buffer {:?} is already mapped",
-            self.buffer.id
-        );
         let end = match self.size {
             Some(s) => self.offset + s.get(),
             None => mc.total_size,
         };
-        mc.initial_range = self.offset..end;
+        assert!(
+            !mc.is_mapped(&(self.offset..end)),
+            "Buffer {:?} is already mapped",
+            self.buffer.id
+        );
+        mc.mapped_ranges.push(self.offset..end);
 
         DynContext::buffer_map_async(
             &*self.buffer.context,
@@ -3065,6 +4774,27 @@ impl<'a> BufferSlice<'a> {
         )
     }
 
+    /// Flushes any pending write operations and unmaps this slice from host memory, leaving the other
+    /// mapped slices of the buffer accessible.
+    ///
+    /// The slice must be the same as the one passed to [`BufferSlice::map_async`], and none of its mapped
+    /// views can still be alive. If the mapping is still pending, its callback is called with an error.
+    ///
+    /// On WebGPU, only one slice of a buffer can be mapped at a time, and this unmaps the whole buffer.
+    pub fn unmap(&self) {
+        let end = match self.size {
+            Some(s) => self.offset + s.get(),
+            None => self.buffer.size,
+        };
+        self.buffer.map_context.lock().unmap(self.offset..end);
+        DynContext::buffer_unmap_range(
+            &*self.buffer.context,
+            &self.buffer.id,
+            self.buffer.data.as_ref(),
+            self.offset..end,
+        );
+    }
+
     /// Synchronously and immediately map a buffer for reading. If the buffer is not immediately mappable
     /// through [`BufferDescriptor::mapped_at_creation`] or [`BufferSlice::map_async`], will panic.
     pub fn get_mapped_range(&self) -> BufferView<'a> {
@@ -3150,6 +4880,119 @@ impl Texture {
         }
     }
 
+    /// Creates a [`Texture`] around a `VkImage` owned by another API, so it can be
+    /// rendered to in place. This is meant for the swapchain images of OpenXR
+    /// runtimes, whose array layers hold the views of each eye and can be rendered
+    /// to through [`TextureViewDimension::D2`] views of single layers, or a
+    /// [`TextureViewDimension::D2Array`] view for multiview.
+    ///
+    /// The image is expected in the `COLOR_ATTACHMENT_OPTIMAL` layout, or
+    /// `DEPTH_STENCIL_ATTACHMENT_OPTIMAL` for depth formats, as OpenXR hands it
+    /// out between `xrWaitSwapchainImage` and `xrReleaseSwapchainImage`. Every
+    /// submission using the texture leaves it in that layout again. The image is
+    /// neither cleared nor destroyed by wgpu.
+    ///
+    /// `desc.usage` must contain [`TextureUsages::RENDER_ATTACHMENT`].
+    ///
+    /// # Safety
+    ///
+    /// - `image` must be a `VkImage` created from the `VkDevice` of `device`,
+    ///   respecting `desc`, and outlive the texture.
+    /// - The image must be in the layout above whenever work using the texture is
+    ///   submitted, and must not be used by other APIs until that work is done.
+    #[cfg(vulkan)]
+    pub unsafe fn from_raw_vulkan_image(
+        device: &Device,
+        image: u64,
+        desc: &TextureDescriptor<'_>,
+    ) -> Texture {
+        let texture = unsafe {
+            device
+                .context
+                .as_any()
+                .downcast_ref::<crate::backend::ContextWgpuCore>()
+                // Vulkan devices can only be created by wgpu-core.
+                .unwrap()
+                .create_texture_from_vulkan_image(
+                    image,
+                    device.data.as_ref().downcast_ref().unwrap(),
+                    desc,
+                )
+        };
+        Texture {
+            context: Arc::clone(&device.context),
+            id: ObjectId::from(texture.id()),
+            data: Box::new(texture),
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            view_formats: desc.view_formats.to_vec(),
+        }
+    }
+
+    /// Creates a [`Texture`] around an `ID3D12Resource` owned by another API, like
+    /// [`Texture::from_raw_vulkan_image`] does for Vulkan images.
+    ///
+    /// The resource is expected in the `D3D12_RESOURCE_STATE_RENDER_TARGET` state,
+    /// or `D3D12_RESOURCE_STATE_DEPTH_WRITE` for depth formats, which is how OpenXR
+    /// runtimes hand out their swapchain images, and every submission using the
+    /// texture leaves it there. wgpu holds a reference of its own to the resource.
+    ///
+    /// `desc.usage` must contain [`TextureUsages::RENDER_ATTACHMENT`].
+    ///
+    /// # Safety
+    ///
+    /// - `resource` must be an `ID3D12Resource` created from the `ID3D12Device` of
+    ///   `device`, respecting `desc`.
+    /// - The resource must be in the state above whenever work using the texture
+    ///   is submitted, and must not be used by other APIs until that work is done.
+    #[cfg(dx12)]
+    pub unsafe fn from_raw_d3d12_resource(
+        device: &Device,
+        resource: *mut std::ffi::c_void,
+        desc: &TextureDescriptor<'_>,
+    ) -> Texture {
+        let texture = unsafe {
+            device
+                .context
+                .as_any()
+                .downcast_ref::<crate::backend::ContextWgpuCore>()
+                // D3D12 devices can only be created by wgpu-core.
+                .unwrap()
+                .create_texture_from_d3d12_resource(
+                    resource,
+                    device.data.as_ref().downcast_ref().unwrap(),
+                    desc,
+                )
+        };
+        Texture {
+            context: Arc::clone(&device.context),
+            id: ObjectId::from(texture.id()),
+            data: Box::new(texture),
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            view_formats: desc.view_formats.to_vec(),
+        }
+    }
+
+    /// Creates a view of this texture like [`Texture::create_view`], but returns the error
+    /// instead of reporting it.
+    ///
+    /// See [`CreateError`].
+    pub fn try_create_view(
+        &self,
+        desc: &TextureViewDescriptor<'_>,
+    ) -> Result<TextureView, CreateError> {
+        try_create(&*self.context, || self.create_view(desc))
+    }
+
     /// Creates a view of this texture.
     pub fn create_view(&self, desc: &TextureViewDescriptor<'_>) -> TextureView {
         let (id, data) =
@@ -3158,6 +5001,60 @@ impl Texture {
             context: Arc::clone(&self.context),
             id,
             data,
+            texture: self.global_id(),
+            descriptor: self.resolve_view_descriptor(desc),
+        }
+    }
+
+    /// Resolves the defaults of `desc`, the same way as when creating the view.
+    ///
+    /// See <https://gpuweb.github.io/gpuweb/#abstract-opdef-resolving-gputextureviewdescriptor-defaults>.
+    fn resolve_view_descriptor(
+        &self,
+        desc: &TextureViewDescriptor<'_>,
+    ) -> TextureViewDescriptor<'static> {
+        let texture_desc = &self.descriptor;
+        let format = desc.format.unwrap_or_else(|| {
+            texture_desc
+                .format
+                .aspect_specific_format(desc.aspect)
+                .unwrap_or(texture_desc.format)
+        });
+        let dimension = desc
+            .dimension
+            .unwrap_or_else(|| match texture_desc.dimension {
+                TextureDimension::D1 => TextureViewDimension::D1,
+                TextureDimension::D2 => {
+                    if texture_desc.array_layer_count() == 1 {
+                        TextureViewDimension::D2
+                    } else {
+                        TextureViewDimension::D2Array
+                    }
+                }
+                TextureDimension::D3 => TextureViewDimension::D3,
+            });
+        let mip_level_count = desc.mip_level_count.unwrap_or_else(|| {
+            texture_desc
+                .mip_level_count
+                .saturating_sub(desc.base_mip_level)
+        });
+        let array_layer_count = desc.array_layer_count.unwrap_or_else(|| match dimension {
+            TextureViewDimension::D1 | TextureViewDimension::D2 | TextureViewDimension::D3 => 1,
+            TextureViewDimension::Cube => 6,
+            TextureViewDimension::D2Array | TextureViewDimension::CubeArray => texture_desc
+                .array_layer_count()
+                .saturating_sub(desc.base_array_layer),
+        });
+        TextureViewDescriptor {
+            label: None,
+            format: Some(format),
+            dimension: Some(dimension),
+            aspect: desc.aspect,
+            base_mip_level: desc.base_mip_level,
+            mip_level_count: Some(mip_level_count),
+            base_array_layer: desc.base_array_layer,
+            array_layer_count: Some(array_layer_count),
+            swizzle: desc.swizzle,
         }
     }
 
@@ -3238,6 +5135,24 @@ impl Texture {
     pub fn usage(&self) -> TextureUsages {
         self.descriptor.usage
     }
+
+    /// Returns the formats that views of this `Texture` can have besides its own format.
+    ///
+    /// This is always equal to the `view_formats` that were specified when creating the texture.
+    pub fn view_formats(&self) -> &[TextureFormat] {
+        &self.view_formats
+    }
+
+    /// Returns the descriptor of this `Texture`.
+    ///
+    /// This is always equal to the descriptor that was specified when creating the texture,
+    /// except for the label which isn't kept.
+    pub fn descriptor(&self) -> TextureDescriptor<'_> {
+        TextureDescriptor {
+            view_formats: &self.view_formats,
+            ..self.descriptor.clone()
+        }
+    }
 }
 
 impl Drop for Texture {
@@ -3292,6 +5207,49 @@ impl CommandEncoder {
         }
     }
 
+    /// Begins recording of a render pass resuming one ended with [`RenderPass::suspend`].
+    ///
+    /// The suspended pass must be the last command of the command buffer submitted right before
+    /// this one, in the same [`Queue::submit`] call, and this pass must be the first command of
+    /// this encoder. `desc` must have the same attachments and resolve targets as the suspended
+    /// pass. The attachments are always loaded, ignoring their [`LoadOp`]s.
+    ///
+    /// Not supported on WebGPU.
+    pub fn resume_render_pass<'pass>(
+        &'pass mut self,
+        desc: &RenderPassDescriptor<'pass, '_>,
+    ) -> RenderPass<'pass> {
+        let mut pass = self.begin_render_pass(desc);
+        DynContext::render_pass_resume(&*pass.parent.context, &mut pass.id, pass.data.as_mut());
+        pass
+    }
+
+    /// Begins recording of a render pass whose commands are recorded in parallel.
+    ///
+    /// This function returns a [`ParallelRenderPass`] with `slice_count` slices. Each slice is a
+    /// [`RenderPassSliceEncoder`] that may be recorded on a different thread. `slice_desc`
+    /// describes the attachments of the pass and must match `desc`, as for a
+    /// [`RenderBundleEncoder`] executed in it.
+    ///
+    /// The commands of all slices are executed in slice order once the pass is finished.
+    pub fn begin_render_pass_parallel<'pass>(
+        &'pass mut self,
+        device: &'pass Device,
+        desc: &RenderPassDescriptor<'pass, '_>,
+        slice_desc: &RenderBundleEncoderDescriptor<'_>,
+        slice_count: usize,
+    ) -> ParallelRenderPass<'pass> {
+        let slices = (0..slice_count)
+            .map(|_| RenderPassSliceEncoder {
+                inner: device.create_render_bundle_encoder(slice_desc),
+            })
+            .collect();
+        ParallelRenderPass {
+            pass: self.begin_render_pass(desc),
+            slices,
+        }
+    }
+
     /// Begins recording of a compute pass.
     ///
     /// This function returns a [`ComputePass`] object which records a single compute pass.
@@ -3396,6 +5354,38 @@ impl CommandEncoder {
         );
     }
 
+    /// Resolve a region of a multisampled texture into a single-sampled texture.
+    ///
+    /// Unlike the `resolve_target` of a render pass attachment, only `copy_size`
+    /// texels starting at the source origin are resolved, and the samples may be
+    /// combined with `mode`. Needs [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`].
+    ///
+    /// # Panics
+    ///
+    /// - Source is not multisampled, or destination is multisampled
+    /// - Textures don't have the same format
+    /// - The format doesn't support [`TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_REGION`],
+    ///   or [`TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE_MIN_MAX`] for modes other
+    ///   than [`ResolveMode::Average`]
+    /// - Resolve would overrun either texture
+    pub fn resolve_texture(
+        &mut self,
+        source: ImageCopyTexture<'_>,
+        destination: ImageCopyTexture<'_>,
+        copy_size: Extent3d,
+        mode: ResolveMode,
+    ) {
+        DynContext::command_encoder_resolve_texture(
+            &*self.context,
+            self.id.as_ref().unwrap(),
+            self.data.as_ref(),
+            source,
+            destination,
+            copy_size,
+            mode,
+        );
+    }
+
     /// Clears texture to zero.
     ///
     /// Note that unlike with clear_buffer, `COPY_DST` usage is not required.
@@ -3464,6 +5454,17 @@ impl CommandEncoder {
         DynContext::command_encoder_pop_debug_group(&*self.context, id, self.data.as_ref());
     }
 
+    /// Makes the memory of aliasing resources in a [`MemoryHeap`] available to the
+    /// resource used next.
+    ///
+    /// This must be recorded after the last use of a placed resource and before the
+    /// first use of another one sharing its memory. [`Features::MEMORY_HEAPS`] must
+    /// be enabled on the device.
+    pub fn aliasing_barrier(&mut self) {
+        let id = self.id.as_ref().unwrap();
+        DynContext::command_encoder_aliasing_barrier(&*self.context, id, self.data.as_ref());
+    }
+
     /// Resolves a query set, writing the results into the supplied destination buffer.
     ///
     /// Occlusion and timestamp queries are 8 bytes each (see [`crate::QUERY_SIZE`]). For pipeline statistics queries,
@@ -3490,6 +5491,37 @@ impl CommandEncoder {
     }
 }
 
+/// [`Features::EXPERIMENTAL_RAY_TRACING_ACCELERATION_STRUCTURE`] must be enabled on the device in order to call these functions.
+impl CommandEncoder {
+    /// Builds the bottom level acceleration structures of `blas`, and then the
+    /// top level acceleration structures of `tlas`.
+    ///
+    /// Each acceleration structure can only be built once per call, and the
+    /// top level acceleration structures can instantiate the bottom level ones
+    /// built by the same call.
+    ///
+    /// # Safety
+    ///
+    /// The instances of the top level acceleration structures aren't
+    /// validated. Each instance must reference a bottom level acceleration
+    /// structure by its [`Blas::handle`], and that acceleration structure must
+    /// have been built, and must stay alive, for as long as the top level
+    /// acceleration structure is used.
+    pub unsafe fn build_acceleration_structures_unsafe_tlas(
+        &mut self,
+        blas: &[BlasBuildEntry<'_>],
+        tlas: &[TlasBuildEntry<'_>],
+    ) {
+        DynContext::command_encoder_build_acceleration_structures_unsafe_tlas(
+            &*self.context,
+            self.id.as_ref().unwrap(),
+            self.data.as_ref(),
+            blas,
+            tlas,
+        )
+    }
+}
+
 /// [`Features::TIMESTAMP_QUERY`] must be enabled on the device in order to call these functions.
 impl CommandEncoder {
     /// Issue a timestamp command at this point in the queue.
@@ -3538,6 +5570,28 @@ impl<'a> RenderPass<'a> {
         )
     }
 
+    /// Binds `entries` at the given bind group index without creating a [`BindGroup`].
+    ///
+    /// The entries are validated against the bind group layout that the active pipeline
+    /// has at `index`, so a pipeline must be set first, and they stay bound until another
+    /// bind group is set at `index`. Dynamic offsets are not supported.
+    ///
+    /// This is meant for small bindings that change with every draw, where creating and
+    /// dropping a bind group each time would dominate. wgpu still creates a transient bind
+    /// group internally, but it is never registered or tracked on its own, and its resources
+    /// are kept alive by the command buffer until it has executed.
+    ///
+    /// This is a native only API; it panics on the WebGPU backend.
+    pub fn set_bind_group_immediate(&mut self, index: u32, entries: &[BindGroupEntry<'a>]) {
+        DynContext::render_pass_set_bind_group_immediate(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            index,
+            entries,
+        )
+    }
+
     /// Sets the active render pipeline.
     ///
     /// Subsequent draw calls will exhibit the behavior defined by `pipeline`.
@@ -3659,6 +5713,23 @@ impl<'a> RenderPass<'a> {
         );
     }
 
+    /// Sets the shading rate for subsequent draw calls.
+    ///
+    /// Each fragment shader invocation then covers a block of pixels of this
+    /// size. The rate is reset to [`ShadingRate::Rate1x1`] at the beginning of
+    /// each pass, and a [`RenderPassDescriptor::shading_rate_attachment`] takes
+    /// precedence over it.
+    ///
+    /// Requires [`Features::SHADING_RATE`] to be enabled.
+    pub fn set_shading_rate(&mut self, rate: ShadingRate) {
+        DynContext::render_pass_set_shading_rate(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            rate,
+        );
+    }
+
     /// Inserts debug marker.
     pub fn insert_debug_marker(&mut self, label: &str) {
         DynContext::render_pass_insert_debug_marker(
@@ -3688,6 +5759,22 @@ impl<'a> RenderPass<'a> {
         );
     }
 
+    /// Ends the pass, suspending it to be resumed by [`CommandEncoder::resume_render_pass`].
+    ///
+    /// The render pass continues in the first command of the command buffer submitted right
+    /// after the one of this pass, in the same [`Queue::submit`] call. This lets a single render
+    /// pass be recorded by several encoders. No other commands can be recorded to the encoder
+    /// after this pass.
+    ///
+    /// The contents of the attachments are kept for the resuming pass regardless of their
+    /// [`StoreOp`]s, and the resolve targets are only written at the end of the last part of the
+    /// render pass.
+    ///
+    /// Not supported on WebGPU.
+    pub fn suspend(mut self) {
+        DynContext::render_pass_suspend(&*self.parent.context, &mut self.id, self.data.as_mut());
+    }
+
     /// Draws primitives from the active vertex buffer(s).
     ///
     /// The active vertex buffer(s) can be set with [`RenderPass::set_vertex_buffer`].
@@ -3891,6 +5978,7 @@ impl<'a> RenderPass<'a> {
     ///
     /// The indirect buffer must be long enough to account for `max_count` draws, however only `count`
     /// draws will be read. If `count` is greater than `max_count`, `max_count` will be used.
+    /// `indirect_offset` and `count_offset` must be multiples of 4.
     ///
     /// The active vertex buffers can be set with [`RenderPass::set_vertex_buffer`].
     ///
@@ -3935,6 +6023,7 @@ impl<'a> RenderPass<'a> {
     ///
     /// The indirect buffer must be long enough to account for `max_count` draws, however only `count`
     /// draws will be read. If `count` is greater than `max_count`, `max_count` will be used.
+    /// `indirect_offset` and `count_offset` must be multiples of 4.
     ///
     /// The active index buffer can be set with [`RenderPass::set_index_buffer`], while the active
     /// vertex buffers can be set with [`RenderPass::set_vertex_buffer`].
@@ -3978,6 +6067,78 @@ impl<'a> RenderPass<'a> {
     }
 }
 
+/// [`Features::DEVICE_GENERATED_COMMANDS`] must be enabled on the device in order to call these functions.
+impl<'a> RenderPass<'a> {
+    /// Executes up to `max_count` commands written in `indirect_buffer`, each of which
+    /// may bind vertex buffers and set push constants before drawing.
+    ///
+    /// Each command is laid out as described by `arguments`, and commands are
+    /// [`IndirectArgument::stride`] bytes apart, starting at `indirect_offset`.
+    /// The arguments must end with a single [`IndirectArgument::Draw`] or
+    /// [`IndirectArgument::DrawIndexed`], and the draws use the current render state for
+    /// everything that the commands don't set.
+    ///
+    /// The vertex buffers and push constants set by the commands are undefined after
+    /// the call, and must be set again before drawing with them.
+    ///
+    /// # Safety
+    ///
+    /// The addresses written for [`IndirectArgument::VertexBuffer`] arguments must be within
+    /// buffers returned by [`Buffer::device_address`], that are still alive when the commands
+    /// execute and that are bound with [`RenderPass::set_vertex_buffer`] earlier in the pass,
+    /// so that their usage is tracked.
+    pub unsafe fn execute_indirect(
+        &mut self,
+        arguments: &[IndirectArgument],
+        indirect_buffer: &'a Buffer,
+        indirect_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        DynContext::render_pass_execute_indirect(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            arguments,
+            &indirect_buffer.id,
+            indirect_buffer.data.as_ref(),
+            indirect_offset,
+            max_count,
+        );
+    }
+
+    /// Executes commands like [`RenderPass::execute_indirect`], reading the number of
+    /// commands to execute from a `u32` in `count_buffer`.
+    ///
+    /// If the count is greater than `max_count`, `max_count` is used.
+    ///
+    /// # Safety
+    ///
+    /// See [`RenderPass::execute_indirect`].
+    pub unsafe fn execute_indirect_count(
+        &mut self,
+        arguments: &[IndirectArgument],
+        indirect_buffer: &'a Buffer,
+        indirect_offset: BufferAddress,
+        count_buffer: &'a Buffer,
+        count_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        DynContext::render_pass_execute_indirect_count(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            arguments,
+            &indirect_buffer.id,
+            indirect_buffer.data.as_ref(),
+            indirect_offset,
+            &count_buffer.id,
+            count_buffer.data.as_ref(),
+            count_offset,
+            max_count,
+        );
+    }
+}
+
 /// [`Features::PUSH_CONSTANTS`] must be enabled on the device in order to call these functions.
 impl<'a> RenderPass<'a> {
     /// Set push constant data for subsequent draw calls.
@@ -4079,7 +6240,11 @@ impl<'a> RenderPass<'a> {
 /// [`Features::PIPELINE_STATISTICS_QUERY`] must be enabled on the device in order to call these functions.
 impl<'a> RenderPass<'a> {
     /// Start a pipeline statistics query on this render pass. It can be ended with
-    /// `end_pipeline_statistics_query`. Pipeline statistics queries may not be nested.
+    /// `end_pipeline_statistics_query`. Pipeline statistics queries may not be nested,
+    /// nor be used in a pass with a [`RenderPassDescriptor::pipeline_statistics_query`].
+    ///
+    /// An occlusion query may be active at the same time. Both must be ended before
+    /// the end of the pass.
     pub fn begin_pipeline_statistics_query(&mut self, query_set: &QuerySet, query_index: u32) {
         DynContext::render_pass_begin_pipeline_statistics_query(
             &*self.parent.context,
@@ -4102,6 +6267,123 @@ impl<'a> RenderPass<'a> {
     }
 }
 
+/// [`Features::EXPERIMENTAL_MESH_SHADER`] must be enabled on the device in order to call these functions.
+impl<'a> RenderPass<'a> {
+    /// Draws primitives with the active mesh pipeline, launching
+    /// `group_count_x * group_count_y * group_count_z` task shader workgroups, or mesh shader
+    /// workgroups if the pipeline has no task stage.
+    ///
+    /// The active pipeline must have been created with [`Device::create_mesh_pipeline`].
+    pub fn draw_mesh_tasks(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) {
+        DynContext::render_pass_draw_mesh_tasks(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            group_count_x,
+            group_count_y,
+            group_count_z,
+        );
+    }
+
+    /// Draws primitives with the active mesh pipeline, based on the contents of the
+    /// `indirect_buffer`.
+    ///
+    /// This is like calling [`RenderPass::draw_mesh_tasks`] but the contents of the call are
+    /// specified in the `indirect_buffer`. The structure expected in `indirect_buffer` must
+    /// conform to [`DrawMeshTasksIndirectArgs`](crate::util::DrawMeshTasksIndirectArgs).
+    pub fn draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: &'a Buffer,
+        indirect_offset: BufferAddress,
+    ) {
+        DynContext::render_pass_draw_mesh_tasks_indirect(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            &indirect_buffer.id,
+            indirect_buffer.data.as_ref(),
+            indirect_offset,
+        );
+    }
+}
+
+impl<'a> RenderPass<'a> {
+    /// Start conditional rendering on this render pass. Until it's ended with
+    /// `end_conditional_rendering`, draws are skipped if the `u64` at `offset` in
+    /// `buffer` is zero, as it is when an occlusion query resolved there passed no
+    /// samples. The value isn't read back on the CPU.
+    ///
+    /// `buffer` must have the [`BufferUsages::INDIRECT`] usage, and `offset` must be
+    /// a multiple of 8. Conditional rendering may not be nested, and must be ended
+    /// before the end of the pass.
+    ///
+    /// Draws are only skipped if [`Features::CONDITIONAL_RENDERING`] is enabled on the
+    /// device. Without it, the draws always happen, so this can be used as an
+    /// optimization regardless of the backend. On Vulkan, only the low 32 bits of the
+    /// value are checked.
+    pub fn begin_conditional_rendering(&mut self, buffer: &'a Buffer, offset: BufferAddress) {
+        DynContext::render_pass_begin_conditional_rendering(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            &buffer.id,
+            buffer.data.as_ref(),
+            offset,
+        );
+    }
+
+    /// End conditional rendering on this render pass. It can be started with
+    /// `begin_conditional_rendering`.
+    pub fn end_conditional_rendering(&mut self) {
+        DynContext::render_pass_end_conditional_rendering(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+        );
+    }
+}
+
+/// [`Features::VERTEX_CAPTURE`] must be enabled on the device in order to call these functions.
+impl<'a> RenderPass<'a> {
+    /// Start capturing the outputs of the vertex stage into `buffers`, as described by the
+    /// [`VertexState::capture_buffers`] of the current pipeline. `buffers[i]` is written with
+    /// the layout of `capture_buffers[i]`, starting from the beginning of the slice, and must
+    /// have the [`BufferUsages::VERTEX_CAPTURE`] usage and an offset that is a multiple of 4.
+    ///
+    /// Vertices are written in the order they are drawn, whole primitives at a time. While the
+    /// capture is active, the pipeline can't be changed and only [`RenderPass::draw`] may be
+    /// used to draw, and the draws must not write more vertices than the buffers can hold.
+    /// The capture must be ended with [`RenderPass::end_capture`] before the end of the pass.
+    pub fn begin_capture(&mut self, buffers: &[BufferSlice<'a>]) {
+        for (slot, buffer_slice) in buffers.iter().enumerate() {
+            DynContext::render_pass_set_vertex_capture_buffer(
+                &*self.parent.context,
+                &mut self.id,
+                self.data.as_mut(),
+                slot as u32,
+                &buffer_slice.buffer.id,
+                buffer_slice.buffer.data.as_ref(),
+                buffer_slice.offset,
+                buffer_slice.size,
+            );
+        }
+        DynContext::render_pass_begin_vertex_capture(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+        );
+    }
+
+    /// End the vertex capture started with [`RenderPass::begin_capture`].
+    pub fn end_capture(&mut self) {
+        DynContext::render_pass_end_vertex_capture(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+        );
+    }
+}
+
 impl<'a> Drop for RenderPass<'a> {
     fn drop(&mut self) {
         if !thread::panicking() {
@@ -4294,6 +6576,53 @@ impl<'a> Drop for ComputePass<'a> {
     }
 }
 
+impl<'a> ParallelRenderPass<'a> {
+    /// Returns the slice encoders of this pass, in execution order.
+    ///
+    /// The slices can be handed out to different threads, for example with [`std::thread::scope`].
+    pub fn slices_mut(&mut self) -> &mut [RenderPassSliceEncoder<'a>] {
+        &mut self.slices
+    }
+
+    /// Stitches the recorded slices together in order and ends the render pass.
+    pub fn finish(self) {
+        let Self { mut pass, slices } = self;
+        let bundles = slices
+            .into_iter()
+            .map(|slice| {
+                slice.inner.finish(&RenderBundleDescriptor {
+                    label: Some("Render pass slice"),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut bundle_ids = bundles.iter().map(|rb| (&rb.id, rb.data.as_ref()));
+        DynContext::render_pass_execute_bundles(
+            &*pass.parent.context,
+            &mut pass.id,
+            pass.data.as_mut(),
+            &mut bundle_ids,
+        );
+        // The pass must end before the slices' bundles are released.
+        drop(pass);
+        drop(bundles);
+    }
+}
+
+impl<'a> Deref for RenderPassSliceEncoder<'a> {
+    type Target = RenderBundleEncoder<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<'a> DerefMut for RenderPassSliceEncoder<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
 impl<'a> RenderBundleEncoder<'a> {
     /// Finishes recording and returns a [`RenderBundle`] that can be executed in other render passes.
     pub fn finish(self, desc: &RenderBundleDescriptor<'_>) -> RenderBundle {
@@ -4434,47 +6763,193 @@ impl<'a> RenderBundleEncoder<'a> {
             &*self.parent.context,
             &mut self.id,
             self.data.as_mut(),
-            indices,
-            base_vertex,
-            instances,
+            indices,
+            base_vertex,
+            instances,
+        );
+    }
+
+    /// Draws primitives from the active vertex buffer(s) based on the contents of the `indirect_buffer`.
+    ///
+    /// The active vertex buffers can be set with [`RenderBundleEncoder::set_vertex_buffer`].
+    ///
+    /// The structure expected in `indirect_buffer` must conform to [`DrawIndirectArgs`](crate::util::DrawIndirectArgs).
+    pub fn draw_indirect(&mut self, indirect_buffer: &'a Buffer, indirect_offset: BufferAddress) {
+        DynContext::render_bundle_encoder_draw_indirect(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            &indirect_buffer.id,
+            indirect_buffer.data.as_ref(),
+            indirect_offset,
+        );
+    }
+
+    /// Draws indexed primitives using the active index buffer and the active vertex buffers,
+    /// based on the contents of the `indirect_buffer`.
+    ///
+    /// The active index buffer can be set with [`RenderBundleEncoder::set_index_buffer`], while the active
+    /// vertex buffers can be set with [`RenderBundleEncoder::set_vertex_buffer`].
+    ///
+    /// The structure expected in `indirect_buffer` must conform to [`DrawIndexedIndirectArgs`](crate::util::DrawIndexedIndirectArgs).
+    pub fn draw_indexed_indirect(
+        &mut self,
+        indirect_buffer: &'a Buffer,
+        indirect_offset: BufferAddress,
+    ) {
+        DynContext::render_bundle_encoder_draw_indexed_indirect(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            &indirect_buffer.id,
+            indirect_buffer.data.as_ref(),
+            indirect_offset,
+        );
+    }
+}
+
+impl<'a> RenderBundleEncoder<'a> {
+    /// Start an occlusion query in this render bundle. It can be ended with
+    /// `end_occlusion_query`, and must be ended before the bundle is finished.
+    /// Occlusion queries may not be nested.
+    ///
+    /// The query is written to the occlusion query set of the render pass that executes
+    /// the bundle, which must not have an occlusion query active at that point.
+    ///
+    /// [`Features::RENDER_BUNDLE_OCCLUSION_QUERY`] must be enabled on the device.
+    pub fn begin_occlusion_query(&mut self, query_index: u32) {
+        DynContext::render_bundle_encoder_begin_occlusion_query(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            query_index,
+        );
+    }
+
+    /// End the occlusion query in this render bundle. It can be started with
+    /// `begin_occlusion_query`. Occlusion queries may not be nested.
+    ///
+    /// [`Features::RENDER_BUNDLE_OCCLUSION_QUERY`] must be enabled on the device.
+    pub fn end_occlusion_query(&mut self) {
+        DynContext::render_bundle_encoder_end_occlusion_query(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+        );
+    }
+}
+
+/// [`Features::MULTI_DRAW_INDIRECT`] must be enabled on the device in order to call these functions.
+impl<'a> RenderBundleEncoder<'a> {
+    /// Dispatches multiple draw calls from the active vertex buffer(s) based on the contents of the `indirect_buffer`.
+    /// `count` draw calls are issued.
+    ///
+    /// The active vertex buffers can be set with [`RenderBundleEncoder::set_vertex_buffer`].
+    ///
+    /// The structure expected in `indirect_buffer` must conform to [`DrawIndirectArgs`](crate::util::DrawIndirectArgs).
+    /// These draw structures are expected to be tightly packed.
+    pub fn multi_draw_indirect(
+        &mut self,
+        indirect_buffer: &'a Buffer,
+        indirect_offset: BufferAddress,
+        count: u32,
+    ) {
+        DynContext::render_bundle_encoder_multi_draw_indirect(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            &indirect_buffer.id,
+            indirect_buffer.data.as_ref(),
+            indirect_offset,
+            count,
+        );
+    }
+
+    /// Dispatches multiple draw calls from the active index buffer and the active vertex buffers,
+    /// based on the contents of the `indirect_buffer`. `count` draw calls are issued.
+    ///
+    /// The active index buffer can be set with [`RenderBundleEncoder::set_index_buffer`], while the active
+    /// vertex buffers can be set with [`RenderBundleEncoder::set_vertex_buffer`].
+    ///
+    /// The structure expected in `indirect_buffer` must conform to [`DrawIndexedIndirectArgs`](crate::util::DrawIndexedIndirectArgs).
+    /// These draw structures are expected to be tightly packed.
+    pub fn multi_draw_indexed_indirect(
+        &mut self,
+        indirect_buffer: &'a Buffer,
+        indirect_offset: BufferAddress,
+        count: u32,
+    ) {
+        DynContext::render_bundle_encoder_multi_draw_indexed_indirect(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            &indirect_buffer.id,
+            indirect_buffer.data.as_ref(),
+            indirect_offset,
+            count,
         );
     }
+}
 
-    /// Draws primitives from the active vertex buffer(s) based on the contents of the `indirect_buffer`.
+/// [`Features::MULTI_DRAW_INDIRECT_COUNT`] must be enabled on the device in order to call these functions.
+impl<'a> RenderBundleEncoder<'a> {
+    /// Dispatches multiple draw calls from the active vertex buffer(s) based on the contents of the `indirect_buffer`.
+    /// The count buffer is read to determine how many draws to issue.
     ///
-    /// The active vertex buffers can be set with [`RenderBundleEncoder::set_vertex_buffer`].
+    /// The indirect buffer must be long enough to account for `max_count` draws, however only `count`
+    /// draws will be read. If `count` is greater than `max_count`, `max_count` will be used.
+    /// `indirect_offset` and `count_offset` must be multiples of 4.
     ///
-    /// The structure expected in `indirect_buffer` must conform to [`DrawIndirectArgs`](crate::util::DrawIndirectArgs).
-    pub fn draw_indirect(&mut self, indirect_buffer: &'a Buffer, indirect_offset: BufferAddress) {
-        DynContext::render_bundle_encoder_draw_indirect(
+    /// See [`RenderPass::multi_draw_indirect_count`] for the expected buffer layouts.
+    pub fn multi_draw_indirect_count(
+        &mut self,
+        indirect_buffer: &'a Buffer,
+        indirect_offset: BufferAddress,
+        count_buffer: &'a Buffer,
+        count_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        DynContext::render_bundle_encoder_multi_draw_indirect_count(
             &*self.parent.context,
             &mut self.id,
             self.data.as_mut(),
             &indirect_buffer.id,
             indirect_buffer.data.as_ref(),
             indirect_offset,
+            &count_buffer.id,
+            count_buffer.data.as_ref(),
+            count_offset,
+            max_count,
         );
     }
 
-    /// Draws indexed primitives using the active index buffer and the active vertex buffers,
-    /// based on the contents of the `indirect_buffer`.
+    /// Dispatches multiple draw calls from the active index buffer and the active vertex buffers,
+    /// based on the contents of the `indirect_buffer`. The count buffer is read to determine how many draws to issue.
     ///
-    /// The active index buffer can be set with [`RenderBundleEncoder::set_index_buffer`], while the active
-    /// vertex buffers can be set with [`RenderBundleEncoder::set_vertex_buffer`].
+    /// The indirect buffer must be long enough to account for `max_count` draws, however only `count`
+    /// draws will be read. If `count` is greater than `max_count`, `max_count` will be used.
+    /// `indirect_offset` and `count_offset` must be multiples of 4.
     ///
-    /// The structure expected in `indirect_buffer` must conform to [`DrawIndexedIndirectArgs`](crate::util::DrawIndexedIndirectArgs).
-    pub fn draw_indexed_indirect(
+    /// See [`RenderPass::multi_draw_indexed_indirect_count`] for the expected buffer layouts.
+    pub fn multi_draw_indexed_indirect_count(
         &mut self,
         indirect_buffer: &'a Buffer,
         indirect_offset: BufferAddress,
+        count_buffer: &'a Buffer,
+        count_offset: BufferAddress,
+        max_count: u32,
     ) {
-        DynContext::render_bundle_encoder_draw_indexed_indirect(
+        DynContext::render_bundle_encoder_multi_draw_indexed_indirect_count(
             &*self.parent.context,
             &mut self.id,
             self.data.as_mut(),
             &indirect_buffer.id,
             indirect_buffer.data.as_ref(),
             indirect_offset,
+            &count_buffer.id,
+            count_buffer.data.as_ref(),
+            count_offset,
+            max_count,
         );
     }
 }
@@ -4570,6 +7045,77 @@ impl<'a> Drop for QueueWriteBufferView<'a> {
     }
 }
 
+/// Write operation to several regions of a buffer, created with
+/// [`Queue::write_buffer_regions_with`].
+///
+/// The data of all regions is packed in one staging buffer, in the order the
+/// regions were given. Use [`region_mut`](Self::region_mut) to access the data
+/// of a single region.
+pub struct QueueWriteBufferRegionsView<'a> {
+    queue: &'a Queue,
+    buffer: &'a Buffer,
+    regions: Vec<(BufferAddress, BufferSize)>,
+    inner: Box<dyn context::QueueWriteBuffer>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(QueueWriteBufferRegionsView<'_>: Send, Sync);
+
+impl QueueWriteBufferRegionsView<'_> {
+    /// Returns the number of regions written by this view.
+    pub fn region_count(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Returns the data of the region `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`region_count`](Self::region_count).
+    pub fn region_mut(&mut self, index: usize) -> &mut [u8] {
+        let start = self.regions[..index]
+            .iter()
+            .map(|&(_, size)| size.get() as usize)
+            .sum::<usize>();
+        let end = start + self.regions[index].1.get() as usize;
+        &mut self.inner.slice_mut()[start..end]
+    }
+}
+
+impl Deref for QueueWriteBufferRegionsView<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        log::warn!("Reading from a QueueWriteBufferRegionsView won't yield the contents of the buffer and may be slow.");
+        self.inner.slice()
+    }
+}
+
+impl DerefMut for QueueWriteBufferRegionsView<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner.slice_mut()
+    }
+}
+
+impl<'a> AsMut<[u8]> for QueueWriteBufferRegionsView<'a> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.inner.slice_mut()
+    }
+}
+
+impl<'a> Drop for QueueWriteBufferRegionsView<'a> {
+    fn drop(&mut self) {
+        DynContext::queue_write_staging_buffer_regions(
+            &*self.queue.context,
+            &self.queue.id,
+            self.queue.data.as_ref(),
+            &self.buffer.id,
+            self.buffer.data.as_ref(),
+            &self.regions,
+            &*self.inner,
+        );
+    }
+}
+
 impl Queue {
     /// Schedule a data write into `buffer` starting at `offset`.
     ///
@@ -4631,6 +7177,71 @@ impl Queue {
         })
     }
 
+    /// Schedule data writes into several regions of `buffer`.
+    ///
+    /// Every region is a pair of an offset into `buffer` and the data to write
+    /// there. All regions are staged together and copied in a single pass, which
+    /// is cheaper than calling [`Queue::write_buffer`] once per region.
+    ///
+    /// Like [`Queue::write_buffer`], the writes are enqueued to happen at the
+    /// start of the next `submit()` call.
+    ///
+    /// This method fails if any region overruns the size of `buffer`.
+    pub fn write_buffer_regions(&self, buffer: &Buffer, regions: &[(BufferAddress, &[u8])]) {
+        DynContext::queue_write_buffer_regions(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            &buffer.id,
+            buffer.data.as_ref(),
+            regions,
+        )
+    }
+
+    /// Schedule data writes into several regions of `buffer` via the returned
+    /// [`QueueWriteBufferRegionsView`].
+    ///
+    /// Every region is a pair of an offset into `buffer` and the size of the
+    /// data to write there. The data of all regions is packed in the view in the
+    /// order of `regions`.
+    ///
+    /// Reading from the view is slow and will not yield the actual contents of the buffer.
+    ///
+    /// This method fails if any region overruns the size of `buffer`, or if
+    /// `regions` is empty.
+    #[must_use]
+    pub fn write_buffer_regions_with<'a>(
+        &'a self,
+        buffer: &'a Buffer,
+        regions: &[(BufferAddress, BufferSize)],
+    ) -> Option<QueueWriteBufferRegionsView<'a>> {
+        profiling::scope!("Queue::write_buffer_regions_with");
+        for &(offset, size) in regions {
+            DynContext::queue_validate_write_buffer(
+                &*self.context,
+                &self.id,
+                self.data.as_ref(),
+                &buffer.id,
+                buffer.data.as_ref(),
+                offset,
+                size,
+            )?;
+        }
+        let total_size = BufferSize::new(regions.iter().map(|&(_, size)| size.get()).sum())?;
+        let staging_buffer = DynContext::queue_create_staging_buffer(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            total_size,
+        )?;
+        Some(QueueWriteBufferRegionsView {
+            queue: self,
+            buffer,
+            regions: regions.to_vec(),
+            inner: staging_buffer,
+        })
+    }
+
     /// Schedule a write of some data into a texture.
     ///
     /// * `data` contains the texels to be written, which must be in
@@ -4703,6 +7314,73 @@ impl Queue {
         SubmissionIndex(raw, data)
     }
 
+    /// Submits a series of finished command buffers for execution, returning a future
+    /// that resolves to the index of the submission once the GPU finished it.
+    ///
+    /// The future works with any async runtime. It is resolved by the same mechanism
+    /// as [`Queue::on_submitted_work_done`], so on native the device must still be
+    /// polled, for example by enabling [`Device::set_automatic_polling`]. On WebGPU,
+    /// it resolves on its own.
+    pub fn submit_async<I: IntoIterator<Item = CommandBuffer>>(
+        &self,
+        command_buffers: I,
+    ) -> SubmissionFuture {
+        let index = self.submit(command_buffers);
+        let state = Arc::new(Mutex::new(SubmissionState::default()));
+        let callback_state = state.clone();
+        self.on_submitted_work_done(move || {
+            let waker = {
+                let mut state = callback_state.lock();
+                state.done = true;
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+        SubmissionFuture { index, state }
+    }
+
+    /// Submits a series of finished command buffers for execution, and sets each
+    /// [`ExternalFence`] of `signals` to its value once they're done.
+    ///
+    /// The values of a fence must increase from one signal to the next.
+    ///
+    /// [`Features::EXTERNAL_FENCE`] must be enabled on the device.
+    pub fn submit_with_external_signals<I: IntoIterator<Item = CommandBuffer>>(
+        &self,
+        command_buffers: I,
+        signals: &[(&ExternalFence, u64)],
+    ) -> SubmissionIndex {
+        let index = self.submit(command_buffers);
+        for &(fence, value) in signals {
+            DynContext::queue_signal_external_fence(
+                &*self.context,
+                &self.id,
+                self.data.as_ref(),
+                &fence.id,
+                fence.data.as_ref(),
+                value,
+            );
+        }
+        index
+    }
+
+    /// Makes the work submitted to this queue afterwards wait until `fence` reaches
+    /// `value`, without blocking the CPU.
+    ///
+    /// [`Features::EXTERNAL_FENCE`] must be enabled on the device.
+    pub fn wait_for_external_fence(&self, fence: &ExternalFence, value: u64) {
+        DynContext::queue_wait_for_external_fence(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            &fence.id,
+            fence.data.as_ref(),
+            value,
+        )
+    }
+
     /// Gets the amount of nanoseconds each tick of a timestamp query represents.
     ///
     /// Returns zero if timestamp queries are unsupported.
@@ -4713,6 +7391,69 @@ impl Queue {
         DynContext::queue_get_timestamp_period(&*self.context, &self.id, self.data.as_ref())
     }
 
+    /// Samples a GPU timestamp, in the ticks of timestamp queries on this queue, together
+    /// with a CPU timestamp from the clock of [`Adapter::get_presentation_timestamp`].
+    ///
+    /// Profilers can use this to place timestamp query results on the CPU timeline:
+    ///
+    /// ```no_run
+    /// # let queue: wgpu::Queue = return;
+    /// # let gpu_ticks = 0u64;
+    /// if let Some(calibration) = queue.get_timestamp_calibration() {
+    ///     let period = queue.get_timestamp_period() as f64;
+    ///     let offset = (gpu_ticks as f64 - calibration.gpu_timestamp as f64) * period;
+    ///     let cpu_ns = calibration.cpu_timestamp.0 as f64 + offset;
+    /// }
+    /// ```
+    ///
+    /// The clocks may drift apart, so long running applications should sample them
+    /// regularly.
+    ///
+    /// Returns `None` if the clocks can't be correlated, which is always the case on
+    /// Metal, GLES and WebGPU. Vulkan requires `VK_EXT_calibrated_timestamps` and is only
+    /// supported on unix.
+    ///
+    /// This is a queue method, rather than an adapter one, because GPU timestamps are
+    /// specific to the queue they're taken on.
+    pub fn get_timestamp_calibration(&self) -> Option<TimestampCalibration> {
+        DynContext::queue_get_timestamp_calibration(&*self.context, &self.id, self.data.as_ref())
+    }
+
+    /// Binds regions of a [`TextureUsages::TILED`] texture to tiles of [`TilePool`]s,
+    /// or unbinds them.
+    ///
+    /// [`Features::TILED_TEXTURE`] must be enabled on the device.
+    ///
+    /// Bindings take effect on the queue immediately, so writes that are queued with
+    /// [`Queue::write_texture`] but not yet submitted see the new bindings. Tiles are
+    /// zeroed the first time they are bound, and reading a region that isn't bound
+    /// returns zero.
+    pub fn bind_texture_pages(&self, texture: &Texture, bindings: &[TextureTileBinding<'_>]) {
+        DynContext::queue_bind_texture_pages(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            texture,
+            bindings,
+        )
+    }
+
+    /// Makes the work submitted to this queue afterwards wait until the submission
+    /// `submission_index` is done, without blocking the CPU.
+    ///
+    /// `submission_index` may come from any queue of the device, see
+    /// [`Adapter::request_device_with_queues`]. Submissions already wait for the work of
+    /// other queues that uses the same resources, so this only needs to be called to
+    /// order work that doesn't.
+    pub fn wait_for_submission(&self, submission_index: &SubmissionIndex) {
+        DynContext::queue_wait_for_submission(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            submission_index.1.as_ref(),
+        )
+    }
+
     /// Registers a callback when the previous call to submit finishes running on the gpu. This callback
     /// being called implies that all mapped buffer callbacks which were registered before this call will
     /// have been called.
@@ -4876,6 +7617,7 @@ impl Surface<'_> {
                     data,
                     owned: false,
                     descriptor,
+                    view_formats: config.view_formats.clone(),
                 },
                 suboptimal,
                 presented: false,
@@ -5002,6 +7744,16 @@ impl BindGroupLayout {
     pub fn global_id(&self) -> Id<Self> {
         Id(self.id.global_id(), PhantomData)
     }
+
+    /// Returns the entries of this `BindGroupLayout`, sorted by binding.
+    ///
+    /// For a layout created with [`Device::create_bind_group_layout`], these are the entries
+    /// specified when creating it. For a layout returned by `get_bind_group_layout` on a
+    /// pipeline, these are the entries derived from the shaders. They are empty on WebGPU,
+    /// which doesn't expose the entries of derived layouts.
+    pub fn entries(&self) -> &[BindGroupLayoutEntry] {
+        &self.entries
+    }
 }
 
 impl BindGroup {
@@ -5022,6 +7774,31 @@ impl TextureView {
     pub fn global_id(&self) -> Id<Self> {
         Id(self.id.global_id(), PhantomData)
     }
+
+    /// Returns the identifier of the [`Texture`] this view was created from.
+    ///
+    /// This is equal to the [`Texture::global_id`] of the texture.
+    pub fn texture(&self) -> Id<Texture> {
+        self.texture
+    }
+
+    /// Returns the descriptor of this `TextureView`.
+    ///
+    /// This is equal to the descriptor that was specified when creating the view, with the
+    /// defaults resolved to the values the view actually uses. The label isn't kept.
+    pub fn descriptor(&self) -> &TextureViewDescriptor<'static> {
+        &self.descriptor
+    }
+
+    /// Returns the format of this `TextureView`.
+    pub fn format(&self) -> TextureFormat {
+        self.descriptor.format.unwrap()
+    }
+
+    /// Returns the dimension of this `TextureView`.
+    pub fn dimension(&self) -> TextureViewDimension {
+        self.descriptor.dimension.unwrap()
+    }
 }
 
 impl Sampler {
@@ -5064,6 +7841,26 @@ impl QuerySet {
     }
 }
 
+impl Blas {
+    /// Returns a globally-unique identifier for this `Blas`.
+    ///
+    /// Calling this method multiple times on the same object will always return the same value.
+    /// The returned value is guaranteed to be different for all resources created from the same `Instance`.
+    pub fn global_id(&self) -> Id<Self> {
+        Id(self.id.global_id(), PhantomData)
+    }
+}
+
+impl Tlas {
+    /// Returns a globally-unique identifier for this `Tlas`.
+    ///
+    /// Calling this method multiple times on the same object will always return the same value.
+    /// The returned value is guaranteed to be different for all resources created from the same `Instance`.
+    pub fn global_id(&self) -> Id<Self> {
+        Id(self.id.global_id(), PhantomData)
+    }
+}
+
 impl PipelineLayout {
     /// Returns a globally-unique identifier for this `PipelineLayout`.
     ///
@@ -5145,6 +7942,20 @@ pub enum Error {
         /// Description of the validation error.
         description: String,
     },
+    /// Internal error, signifying that the implementation failed an operation although its
+    /// inputs were valid, for example because a shader could not be compiled for the backend
+    Internal {
+        /// Lower level source of the error.
+        #[cfg(send_sync)]
+        #[cfg_attr(docsrs, doc(cfg(all())))]
+        source: Box<dyn error::Error + Send + 'static>,
+        /// Lower level source of the error.
+        #[cfg(not(send_sync))]
+        #[cfg_attr(docsrs, doc(cfg(all())))]
+        source: Box<dyn error::Error + 'static>,
+        /// Description of the internal error.
+        description: String,
+    },
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(Error: Send);
@@ -5154,6 +7965,7 @@ impl error::Error for Error {
         match self {
             Error::OutOfMemory { source } => Some(source.as_ref()),
             Error::Validation { source, .. } => Some(source.as_ref()),
+            Error::Internal { source, .. } => Some(source.as_ref()),
         }
     }
 }
@@ -5163,8 +7975,83 @@ impl fmt::Display for Error {
         match self {
             Error::OutOfMemory { .. } => f.write_str("Out of Memory"),
             Error::Validation { description, .. } => f.write_str(description),
+            Error::Internal { description, .. } => f.write_str(description),
+        }
+    }
+}
+
+/// Error returned by the `try_create_*` methods, like [`Device::try_create_buffer`].
+///
+/// These methods return the error raised while creating the object, instead of reporting it
+/// to the innermost error scope or to the uncaptured error handler, so that invalid descriptors
+/// never abort the application.
+///
+/// On WebGPU, errors are raised asynchronously by the browser, so they are still reported
+/// as usual and these methods always succeed.
+#[derive(Debug)]
+pub struct CreateError {
+    error: Error,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(CreateError: Send);
+
+impl CreateError {
+    /// Returns the error that was raised.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns the error that was raised.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+}
+
+impl fmt::Display for CreateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl error::Error for CreateError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        error::Error::source(&self.error)
+    }
+}
+
+impl From<CreateError> for Error {
+    fn from(error: CreateError) -> Self {
+        error.error
+    }
+}
+
+/// Calls `create`, capturing the first error it raises on this thread.
+fn try_create<T>(context: &C, create: impl FnOnce() -> T) -> Result<T, CreateError> {
+    /// Ends the capture even if `create` panics.
+    struct Capture<'a> {
+        context: &'a C,
+        ended: bool,
+    }
+    impl Drop for Capture<'_> {
+        fn drop(&mut self) {
+            if !self.ended {
+                self.context.end_error_capture();
+            }
         }
     }
+
+    context.begin_error_capture();
+    let mut capture = Capture {
+        context,
+        ended: false,
+    };
+    let object = create();
+    capture.ended = true;
+    match context.end_error_capture() {
+        // The invalid object is dropped, like any other.
+        Some(error) => Err(CreateError { error }),
+        None => Ok(object),
+    }
 }
 
 use send_sync::*;
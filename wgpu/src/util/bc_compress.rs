@@ -0,0 +1,277 @@
+use crate::{
+    util::{align_to, BufferInitDescriptor, DeviceExt},
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, BufferBindingType, BufferDescriptor, BufferUsages,
+    CommandEncoder, ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor, Device,
+    Extent3d, ImageCopyBuffer, ImageCopyTexture, ImageDataLayout, Origin3d,
+    PipelineLayoutDescriptor, ShaderModuleDescriptor, ShaderSource, ShaderStages, Texture,
+    TextureAspect, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+    TextureViewDescriptor, TextureViewDimension, COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+const WORKGROUP_SIZE: u32 = 8;
+
+/// Compresses uncompressed color textures into BC1, BC3 or BC7 textures on the GPU.
+///
+/// This is meant for content that is only available uncompressed at runtime,
+/// like user generated images, but that should still be kept compressed in
+/// video memory. The encoders are tuned for speed, so offline compressors will
+/// produce better results for assets known at build time.
+///
+/// The device must have [`Features::TEXTURE_COMPRESSION_BC`] enabled to create
+/// the target textures.
+///
+/// [`Features::TEXTURE_COMPRESSION_BC`]: crate::Features::TEXTURE_COMPRESSION_BC
+#[derive(Debug)]
+pub struct BcCompressor {
+    bind_group_layout: BindGroupLayout,
+    bc1: ComputePipeline,
+    bc3: ComputePipeline,
+    bc7: ComputePipeline,
+}
+
+struct Params {
+    size: [u32; 2],
+    blocks: [u32; 2],
+    row_pitch: u32,
+    encode_srgb: u32,
+}
+
+impl Params {
+    fn as_bytes(&self) -> [u8; 24] {
+        let words = [
+            self.size[0],
+            self.size[1],
+            self.blocks[0],
+            self.blocks[1],
+            self.row_pitch,
+            self.encode_srgb,
+        ];
+        let mut bytes = [0; 24];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+impl BcCompressor {
+    /// Creates the compute pipelines used for compression.
+    pub fn new(device: &Device) -> Self {
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("(wgpu internal) BcCompressor shader"),
+            source: ShaderSource::Wgsl(include_str!("bc_compress.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("(wgpu internal) BcCompressor bind group layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("(wgpu internal) BcCompressor pipeline layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = |entry_point| {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some("(wgpu internal) BcCompressor pipeline"),
+                layout: Some(&layout),
+                module: &module,
+                entry_point,
+                cache: None,
+            })
+        };
+
+        Self {
+            bc1: pipeline("encode_bc1"),
+            bc3: pipeline("encode_bc3"),
+            bc7: pipeline("encode_bc7"),
+            bind_group_layout,
+        }
+    }
+
+    /// Returns whether `format` can be produced by [`BcCompressor::compress`].
+    pub fn supports_format(format: TextureFormat) -> bool {
+        matches!(
+            format,
+            TextureFormat::Bc1RgbaUnorm
+                | TextureFormat::Bc1RgbaUnormSrgb
+                | TextureFormat::Bc3RgbaUnorm
+                | TextureFormat::Bc3RgbaUnormSrgb
+                | TextureFormat::Bc7RgbaUnorm
+                | TextureFormat::Bc7RgbaUnormSrgb
+        )
+    }
+
+    /// Records commands compressing every mip level and array layer of `source` into `target`.
+    ///
+    /// Both textures must be 2D and have the same size and array layer count. Only the mip levels
+    /// present in both textures are written. `source` must have been created with
+    /// [`TextureUsages::TEXTURE_BINDING`] and have a float sample type, while `target` must have
+    /// been created with [`TextureUsages::COPY_DST`] and a format accepted by
+    /// [`BcCompressor::supports_format`].
+    ///
+    /// If both formats are sRGB, the source is decoded on load and re-encoded into the target, so
+    /// the stored values round trip. Otherwise, texel values are compressed as they are read.
+    ///
+    /// # Panics
+    ///
+    /// - Any of the requirements above is not met.
+    pub fn compress(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        source: &Texture,
+        target: &Texture,
+    ) {
+        let format = target.format();
+        let (pipeline, block_size) = match format {
+            TextureFormat::Bc1RgbaUnorm | TextureFormat::Bc1RgbaUnormSrgb => (&self.bc1, 8),
+            TextureFormat::Bc3RgbaUnorm | TextureFormat::Bc3RgbaUnormSrgb => (&self.bc3, 16),
+            TextureFormat::Bc7RgbaUnorm | TextureFormat::Bc7RgbaUnormSrgb => (&self.bc7, 16),
+            _ => panic!("BcCompressor cannot encode into {format:?}"),
+        };
+        assert_eq!(source.dimension(), TextureDimension::D2);
+        assert_eq!(target.dimension(), TextureDimension::D2);
+        assert_eq!(
+            source.size(),
+            target.size(),
+            "source and target texture sizes differ"
+        );
+        assert!(source.usage().contains(TextureUsages::TEXTURE_BINDING));
+        assert!(target.usage().contains(TextureUsages::COPY_DST));
+
+        let encode_srgb = (source.format().is_srgb() && format.is_srgb()) as u32;
+        let mip_level_count = source.mip_level_count().min(target.mip_level_count());
+
+        for mip_level in 0..mip_level_count {
+            let size = source
+                .size()
+                .mip_level_size(mip_level, TextureDimension::D2);
+            let blocks_x = (size.width + 3) / 4;
+            let blocks_y = (size.height + 3) / 4;
+            let bytes_per_row = align_to(blocks_x * block_size, COPY_BYTES_PER_ROW_ALIGNMENT);
+
+            let params = Params {
+                size: [size.width, size.height],
+                blocks: [blocks_x, blocks_y],
+                row_pitch: bytes_per_row / 4,
+                encode_srgb,
+            };
+            let params = device.create_buffer_init(&BufferInitDescriptor {
+                label: Some("(wgpu internal) BcCompressor params"),
+                contents: &params.as_bytes(),
+                usage: BufferUsages::UNIFORM,
+            });
+            let blocks = device.create_buffer(&BufferDescriptor {
+                label: Some("(wgpu internal) BcCompressor blocks"),
+                size: (bytes_per_row * blocks_y) as u64,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            });
+
+            for layer in 0..size.depth_or_array_layers {
+                let view = source.create_view(&TextureViewDescriptor {
+                    label: Some("(wgpu internal) BcCompressor source view"),
+                    dimension: Some(TextureViewDimension::D2),
+                    base_mip_level: mip_level,
+                    mip_level_count: Some(1),
+                    base_array_layer: layer,
+                    array_layer_count: Some(1),
+                    ..Default::default()
+                });
+                let bind_group = device.create_bind_group(&BindGroupDescriptor {
+                    label: Some("(wgpu internal) BcCompressor bind group"),
+                    layout: &self.bind_group_layout,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: crate::BindingResource::TextureView(&view),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: blocks.as_entire_binding(),
+                        },
+                        BindGroupEntry {
+                            binding: 2,
+                            resource: params.as_entire_binding(),
+                        },
+                    ],
+                });
+
+                {
+                    let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("(wgpu internal) BcCompressor pass"),
+                        timestamp_writes: None,
+                    });
+                    pass.set_pipeline(pipeline);
+                    pass.set_bind_group(0, &bind_group, &[]);
+                    pass.dispatch_workgroups(
+                        (blocks_x + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE,
+                        (blocks_y + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE,
+                        1,
+                    );
+                }
+
+                encoder.copy_buffer_to_texture(
+                    ImageCopyBuffer {
+                        buffer: &blocks,
+                        layout: ImageDataLayout {
+                            offset: 0,
+                            bytes_per_row: Some(bytes_per_row),
+                            rows_per_image: Some(blocks_y),
+                        },
+                    },
+                    ImageCopyTexture {
+                        texture: target,
+                        mip_level,
+                        origin: Origin3d {
+                            x: 0,
+                            y: 0,
+                            z: layer,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    Extent3d {
+                        width: blocks_x * 4,
+                        height: blocks_y * 4,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
+        }
+    }
+}
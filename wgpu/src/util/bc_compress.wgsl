@@ -0,0 +1,270 @@
+// Block compression of RGBA textures, one invocation per 4x4 block.
+//
+// The encoders favour speed over quality: endpoints are taken from the
+// (slightly inset) bounding box of the block's colours, and every texel is
+// projected onto the line between them. This is comparable to the "fast"
+// presets of offline compressors.
+
+struct Params {
+    // Size of the source mip level, in texels.
+    size: vec2<u32>,
+    // Number of blocks in each dimension.
+    blocks: vec2<u32>,
+    // Distance between two rows of blocks in the output, in `u32`s.
+    row_pitch: u32,
+    // Non-zero if loaded values are linear and must be re-encoded as sRGB.
+    encode_srgb: u32,
+}
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var<storage, read_write> output: array<u32>;
+@group(0) @binding(2)
+var<uniform> params: Params;
+
+var<private> texels: array<vec4<f32>, 16>;
+
+fn linear_to_srgb(linear: vec3<f32>) -> vec3<f32> {
+    let low = linear * 12.92;
+    let high = 1.055 * pow(linear, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, linear <= vec3<f32>(0.0031308));
+}
+
+// Loads the block into `texels`, clamping to the edge of partial blocks.
+fn load_block(block: vec2<u32>) {
+    let max_coord = params.size - vec2<u32>(1u);
+    for (var i = 0u; i < 16u; i++) {
+        let coord = min(block * 4u + vec2<u32>(i % 4u, i / 4u), max_coord);
+        var texel = clamp(textureLoad(source, coord, 0), vec4<f32>(0.0), vec4<f32>(1.0));
+        if params.encode_srgb != 0u {
+            texel = vec4<f32>(linear_to_srgb(texel.rgb), texel.a);
+        }
+        texels[i] = texel;
+    }
+}
+
+// Returns the bounding box of the block, shrunk by 1/16th of its extent on
+// each side to reduce the error of the interpolated colours.
+fn inset_bounds() -> array<vec4<f32>, 2> {
+    var lo = texels[0];
+    var hi = texels[0];
+    for (var i = 1u; i < 16u; i++) {
+        lo = min(lo, texels[i]);
+        hi = max(hi, texels[i]);
+    }
+    let inset = (hi - lo) / 16.0;
+    return array<vec4<f32>, 2>(lo + inset, hi - inset);
+}
+
+fn to_565(color: vec3<f32>) -> u32 {
+    let q = vec3<u32>(round(color * vec3<f32>(31.0, 63.0, 31.0)));
+    return (q.r << 11u) | (q.g << 5u) | q.b;
+}
+
+fn from_565(color: u32) -> vec3<f32> {
+    let q = vec3<u32>((color >> 11u) & 31u, (color >> 5u) & 63u, color & 31u);
+    return vec3<f32>(q) / vec3<f32>(31.0, 63.0, 31.0);
+}
+
+// Position of `color` along `lo..hi`, in `0.0..=1.0`.
+fn project(color: vec3<f32>, lo: vec3<f32>, hi: vec3<f32>) -> f32 {
+    let axis = hi - lo;
+    let len = dot(axis, axis);
+    if len == 0.0 {
+        return 0.0;
+    }
+    return clamp(dot(color - lo, axis) / len, 0.0, 1.0);
+}
+
+// Encodes the colour part of a BC1/BC2/BC3 block, always in four colour mode.
+fn encode_color_block() -> vec2<u32> {
+    let bounds = inset_bounds();
+    var c0 = to_565(bounds[1].rgb);
+    var c1 = to_565(bounds[0].rgb);
+    if c0 < c1 {
+        let tmp = c0;
+        c0 = c1;
+        c1 = tmp;
+    }
+    if c0 == c1 {
+        return vec2<u32>(c0 | (c1 << 16u), 0u);
+    }
+
+    let hi = from_565(c0);
+    let lo = from_565(c1);
+    // Palette order is c0, c1, 2/3 c0 + 1/3 c1, 1/3 c0 + 2/3 c1.
+    var remap = array<u32, 4>(1u, 3u, 2u, 0u);
+    var indices = 0u;
+    for (var i = 0u; i < 16u; i++) {
+        let level = u32(round(project(texels[i].rgb, lo, hi) * 3.0));
+        indices |= remap[level] << (2u * i);
+    }
+    return vec2<u32>(c0 | (c1 << 16u), indices);
+}
+
+// Encodes the alpha part of a BC3 block, in eight alpha mode.
+fn encode_alpha_block() -> vec2<u32> {
+    var lo = texels[0].a;
+    var hi = texels[0].a;
+    for (var i = 1u; i < 16u; i++) {
+        lo = min(lo, texels[i].a);
+        hi = max(hi, texels[i].a);
+    }
+    let a0 = u32(round(hi * 255.0));
+    let a1 = u32(round(lo * 255.0));
+    if a0 == a1 {
+        return vec2<u32>(a0 | (a1 << 8u), 0u);
+    }
+
+    let qhi = f32(a0) / 255.0;
+    let qlo = f32(a1) / 255.0;
+    // Palette order is a0, a1, then six interpolated values from a0 to a1.
+    var bits = array<u32, 2>(0u, 0u);
+    for (var i = 0u; i < 16u; i++) {
+        let t = clamp((texels[i].a - qlo) / (qhi - qlo), 0.0, 1.0);
+        let level = u32(round(t * 7.0));
+        var index = 8u - level;
+        if level == 7u {
+            index = 0u;
+        } else if level == 0u {
+            index = 1u;
+        }
+        let offset = 3u * i;
+        bits[offset / 32u] |= index << (offset % 32u);
+        if offset % 32u > 29u {
+            bits[offset / 32u + 1u] |= index >> (32u - offset % 32u);
+        }
+    }
+    return vec2<u32>(
+        a0 | (a1 << 8u) | (bits[0] << 16u),
+        (bits[0] >> 16u) | (bits[1] << 16u),
+    );
+}
+
+fn block_offset(block: vec2<u32>, words_per_block: u32) -> u32 {
+    return block.y * params.row_pitch + block.x * words_per_block;
+}
+
+@compute @workgroup_size(8, 8)
+fn encode_bc1(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= params.blocks) {
+        return;
+    }
+    load_block(id.xy);
+    let color = encode_color_block();
+    let offset = block_offset(id.xy, 2u);
+    output[offset] = color.x;
+    output[offset + 1u] = color.y;
+}
+
+@compute @workgroup_size(8, 8)
+fn encode_bc3(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= params.blocks) {
+        return;
+    }
+    load_block(id.xy);
+    let alpha = encode_alpha_block();
+    let color = encode_color_block();
+    let offset = block_offset(id.xy, 4u);
+    output[offset] = alpha.x;
+    output[offset + 1u] = alpha.y;
+    output[offset + 2u] = color.x;
+    output[offset + 3u] = color.y;
+}
+
+var<private> bc7_bits: array<u32, 4>;
+var<private> bc7_cursor: u32;
+
+fn bc7_put(value: u32, count: u32) {
+    let word = bc7_cursor / 32u;
+    let shift = bc7_cursor % 32u;
+    bc7_bits[word] |= value << shift;
+    if shift + count > 32u {
+        bc7_bits[word + 1u] |= value >> (32u - shift);
+    }
+    bc7_cursor += count;
+}
+
+// Quantizes an 8 bit endpoint to 7 bits plus a shared p-bit, picking the
+// p-bit that minimizes the error over all four channels.
+fn bc7_quantize(endpoint: vec4<f32>) -> vec4<u32> {
+    let value = endpoint * 255.0;
+    var best = vec4<u32>(0u);
+    var best_p = 0u;
+    var best_error = 1e9;
+    for (var p = 0u; p < 2u; p++) {
+        let q = vec4<u32>(clamp(round((value - f32(p)) / 2.0), vec4<f32>(0.0), vec4<f32>(127.0)));
+        let diff = vec4<f32>((q << vec4<u32>(1u)) | vec4<u32>(p)) - value;
+        let error = dot(diff, diff);
+        if error < best_error {
+            best_error = error;
+            best = q;
+            best_p = p;
+        }
+    }
+    return vec4<u32>(best.xyz, best.w | (best_p << 7u));
+}
+
+fn bc7_dequantize(q: vec4<u32>) -> vec4<f32> {
+    let p = q.w >> 7u;
+    let channels = vec4<u32>(q.xyz, q.w & 127u);
+    return vec4<f32>((channels << vec4<u32>(1u)) | vec4<u32>(p)) / 255.0;
+}
+
+// Encodes a BC7 block in mode 6: one subset, RGBA endpoints with p-bits and
+// 4 bit indices.
+@compute @workgroup_size(8, 8)
+fn encode_bc7(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= params.blocks) {
+        return;
+    }
+    load_block(id.xy);
+
+    let bounds = inset_bounds();
+    var e0 = bc7_quantize(bounds[0]);
+    var e1 = bc7_quantize(bounds[1]);
+    let lo = bc7_dequantize(e0);
+    let hi = bc7_dequantize(e1);
+
+    let axis = hi - lo;
+    let len = dot(axis, axis);
+    var indices: array<u32, 16>;
+    for (var i = 0u; i < 16u; i++) {
+        var t = 0.0;
+        if len > 0.0 {
+            t = clamp(dot(texels[i] - lo, axis) / len, 0.0, 1.0);
+        }
+        indices[i] = u32(round(t * 15.0));
+    }
+
+    // The most significant index bit of the first texel is implied to be
+    // zero, so flip the endpoints if it would be set.
+    if indices[0] >= 8u {
+        let tmp = e0;
+        e0 = e1;
+        e1 = tmp;
+        for (var i = 0u; i < 16u; i++) {
+            indices[i] = 15u - indices[i];
+        }
+    }
+
+    bc7_bits = array<u32, 4>(0u, 0u, 0u, 0u);
+    bc7_cursor = 0u;
+    bc7_put(1u << 6u, 7u);
+    for (var channel = 0u; channel < 4u; channel++) {
+        bc7_put(e0[channel] & 127u, 7u);
+        bc7_put(e1[channel] & 127u, 7u);
+    }
+    bc7_put(e0.w >> 7u, 1u);
+    bc7_put(e1.w >> 7u, 1u);
+    bc7_put(indices[0], 3u);
+    for (var i = 1u; i < 16u; i++) {
+        bc7_put(indices[i], 4u);
+    }
+
+    let offset = block_offset(id.xy, 4u);
+    for (var i = 0u; i < 4u; i++) {
+        output[offset + i] = bc7_bits[i];
+    }
+}
@@ -14,16 +14,16 @@ struct Chunk {
 /// `Sync` wrapper that works by providing only exclusive access.
 ///
 /// See https://doc.rust-lang.org/nightly/std/sync/struct.Exclusive.html
-struct Exclusive<T>(T);
+pub(super) struct Exclusive<T>(T);
 
 unsafe impl<T> Sync for Exclusive<T> {}
 
 impl<T> Exclusive<T> {
-    fn new(value: T) -> Self {
+    pub(super) fn new(value: T) -> Self {
         Self(value)
     }
 
-    fn get_mut(&mut self) -> &mut T {
+    pub(super) fn get_mut(&mut self) -> &mut T {
         &mut self.0
     }
 }
@@ -0,0 +1,459 @@
+use std::borrow::Cow;
+
+use super::texture_file::{Reader, TextureFile, TextureFileError};
+use crate::{util::TextureDataOrder, Extent3d, TextureDimension, TextureFormat};
+
+const DDS_MAGIC: [u8; 4] = *b"DDS ";
+const HEADER_SIZE: u32 = 124;
+const DX10_HEADER_SIZE: usize = 20;
+
+const DDSD_MIPMAPCOUNT: u32 = 0x2_0000;
+const DDSD_DEPTH: u32 = 0x80_0000;
+
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDPF_LUMINANCE: u32 = 0x2_0000;
+
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_CUBEMAP_ALLFACES: u32 = 0xFC00;
+const DDSCAPS2_VOLUME: u32 = 0x20_0000;
+
+const D3D10_RESOURCE_DIMENSION_TEXTURE1D: u32 = 2;
+const D3D10_RESOURCE_DIMENSION_TEXTURE2D: u32 = 3;
+const D3D10_RESOURCE_DIMENSION_TEXTURE3D: u32 = 4;
+const D3D10_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+
+/// Parses a DDS file, including files with the `DX10` extended header.
+///
+/// Legacy cube maps must contain all six faces.
+pub fn parse_dds(data: &[u8]) -> Result<TextureFile<'_>, TextureFileError> {
+    let reader = Reader::new(data);
+    if reader.bytes(0, DDS_MAGIC.len())? != DDS_MAGIC {
+        return Err(TextureFileError::InvalidMagic);
+    }
+    if reader.u32(4)? != HEADER_SIZE {
+        return Err(TextureFileError::InvalidHeader("header size must be 124"));
+    }
+
+    let flags = reader.u32(8)?;
+    let height = reader.u32(12)?;
+    let width = reader.u32(16)?;
+    let depth = reader.u32(24)?;
+    let mip_map_count = reader.u32(28)?;
+    let pf_flags = reader.u32(80)?;
+    let four_cc = reader.bytes(84, 4)?;
+    let caps2 = reader.u32(112)?;
+
+    if width == 0 || height == 0 {
+        return Err(TextureFileError::InvalidHeader("image size is zero"));
+    }
+
+    let mip_level_count = if flags & DDSD_MIPMAPCOUNT != 0 {
+        mip_map_count.max(1)
+    } else {
+        1
+    };
+
+    let mut data_offset = 4 + HEADER_SIZE as usize;
+    let (format, dimension, layers, is_cube) = if pf_flags & DDPF_FOURCC != 0 && four_cc == b"DX10"
+    {
+        let dxgi_format = reader.u32(data_offset)?;
+        let resource_dimension = reader.u32(data_offset + 4)?;
+        let misc_flag = reader.u32(data_offset + 8)?;
+        let array_size = reader.u32(data_offset + 12)?.max(1);
+        data_offset += DX10_HEADER_SIZE;
+
+        let format = dxgi_format_to_texture_format(dxgi_format).ok_or_else(|| {
+            TextureFileError::UnsupportedFormat(format!("DXGI_FORMAT({dxgi_format})"))
+        })?;
+        let dimension = match resource_dimension {
+            D3D10_RESOURCE_DIMENSION_TEXTURE1D => TextureDimension::D1,
+            D3D10_RESOURCE_DIMENSION_TEXTURE2D => TextureDimension::D2,
+            D3D10_RESOURCE_DIMENSION_TEXTURE3D => TextureDimension::D3,
+            _ => {
+                return Err(TextureFileError::InvalidHeader(
+                    "unknown resource dimension",
+                ))
+            }
+        };
+        let is_cube = misc_flag & D3D10_RESOURCE_MISC_TEXTURECUBE != 0;
+        let layers = if is_cube {
+            array_size
+                .checked_mul(6)
+                .ok_or(TextureFileError::InvalidHeader("array size is too large"))?
+        } else {
+            array_size
+        };
+        (format, dimension, layers, is_cube)
+    } else {
+        let format = legacy_pixel_format(&reader, pf_flags, four_cc)?;
+        if caps2 & DDSCAPS2_CUBEMAP != 0 {
+            if caps2 & DDSCAPS2_CUBEMAP_ALLFACES != DDSCAPS2_CUBEMAP_ALLFACES {
+                return Err(TextureFileError::InvalidHeader(
+                    "cube maps must contain all six faces",
+                ));
+            }
+            (format, TextureDimension::D2, 6, true)
+        } else if caps2 & DDSCAPS2_VOLUME != 0 && flags & DDSD_DEPTH != 0 {
+            (format, TextureDimension::D3, 1, false)
+        } else {
+            (format, TextureDimension::D2, 1, false)
+        }
+    };
+
+    if is_cube && width != height {
+        return Err(TextureFileError::InvalidHeader(
+            "cube map faces must be square",
+        ));
+    }
+
+    let size = Extent3d {
+        width,
+        height: match dimension {
+            TextureDimension::D1 => 1,
+            _ => height,
+        },
+        depth_or_array_layers: match dimension {
+            TextureDimension::D3 => depth.max(1),
+            _ => layers,
+        },
+    };
+
+    let file = TextureFile {
+        size,
+        mip_level_count,
+        dimension,
+        format,
+        is_cube,
+        // Each layer (or cube face) stores its full mip chain before the next one.
+        order: TextureDataOrder::LayerMajor,
+        data: Cow::Borrowed(
+            data.get(data_offset..)
+                .ok_or(TextureFileError::UnexpectedEof)?,
+        ),
+    };
+    file.validate_data_size()?;
+    Ok(file)
+}
+
+/// Determines the format of a file without the `DX10` header.
+fn legacy_pixel_format(
+    reader: &Reader<'_>,
+    pf_flags: u32,
+    four_cc: &[u8],
+) -> Result<TextureFormat, TextureFileError> {
+    use TextureFormat as Tf;
+
+    if pf_flags & DDPF_FOURCC != 0 {
+        let format = match four_cc {
+            b"DXT1" => Some(Tf::Bc1RgbaUnorm),
+            b"DXT2" | b"DXT3" => Some(Tf::Bc2RgbaUnorm),
+            b"DXT4" | b"DXT5" => Some(Tf::Bc3RgbaUnorm),
+            b"ATI1" | b"BC4U" => Some(Tf::Bc4RUnorm),
+            b"BC4S" => Some(Tf::Bc4RSnorm),
+            b"ATI2" | b"BC5U" => Some(Tf::Bc5RgUnorm),
+            b"BC5S" => Some(Tf::Bc5RgSnorm),
+            // Some writers store a `D3DFORMAT` value in place of a FourCC.
+            _ => match u32::from_le_bytes([four_cc[0], four_cc[1], four_cc[2], four_cc[3]]) {
+                36 => Some(Tf::Rgba16Unorm),
+                110 => Some(Tf::Rgba16Snorm),
+                111 => Some(Tf::R16Float),
+                112 => Some(Tf::Rg16Float),
+                113 => Some(Tf::Rgba16Float),
+                114 => Some(Tf::R32Float),
+                115 => Some(Tf::Rg32Float),
+                116 => Some(Tf::Rgba32Float),
+                _ => None,
+            },
+        };
+        return format.ok_or_else(|| {
+            TextureFileError::UnsupportedFormat(format!(
+                "FourCC {:?}",
+                String::from_utf8_lossy(four_cc)
+            ))
+        });
+    }
+
+    let bit_count = reader.u32(88)?;
+    let masks = [
+        reader.u32(92)?,
+        reader.u32(96)?,
+        reader.u32(100)?,
+        if pf_flags & DDPF_ALPHAPIXELS != 0 {
+            reader.u32(104)?
+        } else {
+            0
+        },
+    ];
+
+    let format = if pf_flags & DDPF_RGB != 0 {
+        match (bit_count, masks) {
+            (32, [0xFF, 0xFF00, 0xFF_0000, 0xFF00_0000]) => Some(Tf::Rgba8Unorm),
+            (32, [0xFF_0000, 0xFF00, 0xFF, 0xFF00_0000]) => Some(Tf::Bgra8Unorm),
+            (32, [0x3FF, 0xF_FC00, 0x3FF0_0000, 0xC000_0000]) => Some(Tf::Rgb10a2Unorm),
+            (32, [0xFFFF, 0xFFFF_0000, 0, 0]) => Some(Tf::Rg16Unorm),
+            (16, [0xFF, 0xFF00, 0, 0]) => Some(Tf::Rg8Unorm),
+            _ => None,
+        }
+    } else if pf_flags & DDPF_LUMINANCE != 0 {
+        match (bit_count, masks[0]) {
+            (8, 0xFF) => Some(Tf::R8Unorm),
+            (16, 0xFFFF) => Some(Tf::R16Unorm),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    format.ok_or_else(|| {
+        TextureFileError::UnsupportedFormat(format!(
+            "{bit_count} bit pixel format with masks {masks:#x?}"
+        ))
+    })
+}
+
+/// Maps a `DXGI_FORMAT` value, as stored in the `DX10` header, to a [`TextureFormat`].
+fn dxgi_format_to_texture_format(dxgi_format: u32) -> Option<TextureFormat> {
+    use TextureFormat as Tf;
+
+    Some(match dxgi_format {
+        2 => Tf::Rgba32Float,
+        3 => Tf::Rgba32Uint,
+        4 => Tf::Rgba32Sint,
+        10 => Tf::Rgba16Float,
+        11 => Tf::Rgba16Unorm,
+        12 => Tf::Rgba16Uint,
+        13 => Tf::Rgba16Snorm,
+        14 => Tf::Rgba16Sint,
+        16 => Tf::Rg32Float,
+        17 => Tf::Rg32Uint,
+        18 => Tf::Rg32Sint,
+        20 => Tf::Depth32FloatStencil8,
+        24 => Tf::Rgb10a2Unorm,
+        25 => Tf::Rgb10a2Uint,
+        26 => Tf::Rg11b10Float,
+        28 => Tf::Rgba8Unorm,
+        29 => Tf::Rgba8UnormSrgb,
+        30 => Tf::Rgba8Uint,
+        31 => Tf::Rgba8Snorm,
+        32 => Tf::Rgba8Sint,
+        34 => Tf::Rg16Float,
+        35 => Tf::Rg16Unorm,
+        36 => Tf::Rg16Uint,
+        37 => Tf::Rg16Snorm,
+        38 => Tf::Rg16Sint,
+        40 => Tf::Depth32Float,
+        41 => Tf::R32Float,
+        42 => Tf::R32Uint,
+        43 => Tf::R32Sint,
+        49 => Tf::Rg8Unorm,
+        50 => Tf::Rg8Uint,
+        51 => Tf::Rg8Snorm,
+        52 => Tf::Rg8Sint,
+        54 => Tf::R16Float,
+        55 => Tf::Depth16Unorm,
+        56 => Tf::R16Unorm,
+        57 => Tf::R16Uint,
+        58 => Tf::R16Snorm,
+        59 => Tf::R16Sint,
+        61 => Tf::R8Unorm,
+        62 => Tf::R8Uint,
+        63 => Tf::R8Snorm,
+        64 => Tf::R8Sint,
+        67 => Tf::Rgb9e5Ufloat,
+        71 => Tf::Bc1RgbaUnorm,
+        72 => Tf::Bc1RgbaUnormSrgb,
+        74 => Tf::Bc2RgbaUnorm,
+        75 => Tf::Bc2RgbaUnormSrgb,
+        77 => Tf::Bc3RgbaUnorm,
+        78 => Tf::Bc3RgbaUnormSrgb,
+        80 => Tf::Bc4RUnorm,
+        81 => Tf::Bc4RSnorm,
+        83 => Tf::Bc5RgUnorm,
+        84 => Tf::Bc5RgSnorm,
+        87 => Tf::Bgra8Unorm,
+        91 => Tf::Bgra8UnormSrgb,
+        95 => Tf::Bc6hRgbUfloat,
+        96 => Tf::Bc6hRgbFloat,
+        98 => Tf::Bc7RgbaUnorm,
+        99 => Tf::Bc7RgbaUnormSrgb,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RGBA_MASKS: [u32; 4] = [0xFF, 0xFF00, 0xFF_0000, 0xFF00_0000];
+    const BGRA_MASKS: [u32; 4] = [0xFF_0000, 0xFF00, 0xFF, 0xFF00_0000];
+
+    struct Header {
+        flags: u32,
+        width: u32,
+        height: u32,
+        mip_map_count: u32,
+        pf_flags: u32,
+        four_cc: [u8; 4],
+        bit_count: u32,
+        masks: [u32; 4],
+        caps2: u32,
+    }
+
+    impl Header {
+        fn four_cc(four_cc: &[u8; 4], width: u32, height: u32) -> Self {
+            Self {
+                flags: 0,
+                width,
+                height,
+                mip_map_count: 0,
+                pf_flags: DDPF_FOURCC,
+                four_cc: *four_cc,
+                bit_count: 0,
+                masks: [0; 4],
+                caps2: 0,
+            }
+        }
+
+        fn rgb(masks: [u32; 4], width: u32, height: u32) -> Self {
+            Self {
+                pf_flags: DDPF_RGB | DDPF_ALPHAPIXELS,
+                four_cc: [0; 4],
+                bit_count: 32,
+                masks,
+                ..Self::four_cc(&[0; 4], width, height)
+            }
+        }
+
+        fn write(&self, data: &[u8]) -> Vec<u8> {
+            let mut file = DDS_MAGIC.to_vec();
+            for value in [
+                HEADER_SIZE,
+                self.flags,
+                self.height,
+                self.width,
+                0,
+                0,
+                self.mip_map_count,
+            ] {
+                file.extend_from_slice(&value.to_le_bytes());
+            }
+            file.extend_from_slice(&[0; 44]);
+            for value in [32, self.pf_flags] {
+                file.extend_from_slice(&value.to_le_bytes());
+            }
+            file.extend_from_slice(&self.four_cc);
+            file.extend_from_slice(&self.bit_count.to_le_bytes());
+            for mask in self.masks {
+                file.extend_from_slice(&mask.to_le_bytes());
+            }
+            for value in [0, self.caps2, 0, 0, 0] {
+                file.extend_from_slice(&value.to_le_bytes());
+            }
+            file.extend_from_slice(data);
+            file
+        }
+    }
+
+    #[test]
+    fn parse_dx10_array() {
+        let mut file = Header::four_cc(b"DX10", 2, 2).write(&[]);
+        for value in [28, D3D10_RESOURCE_DIMENSION_TEXTURE2D, 0, 2, 0] {
+            file.extend_from_slice(&value.to_le_bytes());
+        }
+        file.extend_from_slice(&[7; 32]);
+
+        let texture = parse_dds(&file).unwrap();
+        assert_eq!(texture.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(texture.dimension, TextureDimension::D2);
+        assert_eq!(
+            texture.size,
+            Extent3d {
+                width: 2,
+                height: 2,
+                depth_or_array_layers: 2
+            }
+        );
+        assert!(!texture.is_cube);
+        assert_eq!(texture.order, TextureDataOrder::LayerMajor);
+        assert_eq!(&texture.data[..], &[7; 32]);
+    }
+
+    #[test]
+    fn parse_dxt1_mip_chain() {
+        let header = Header {
+            flags: DDSD_MIPMAPCOUNT,
+            mip_map_count: 2,
+            ..Header::four_cc(b"DXT1", 8, 8)
+        };
+        // 2x2 blocks of 8 bytes, then a single block.
+        let texture = parse_dds(&header.write(&[1; 40])).unwrap();
+        assert_eq!(texture.format, TextureFormat::Bc1RgbaUnorm);
+        assert_eq!(texture.mip_level_count, 2);
+        assert_eq!(texture.data.len(), 40);
+    }
+
+    #[test]
+    fn parse_dxt5() {
+        let file = Header::four_cc(b"DXT5", 4, 4).write(&[1; 16]);
+        let texture = parse_dds(&file).unwrap();
+        assert_eq!(texture.format, TextureFormat::Bc3RgbaUnorm);
+        assert_eq!(texture.mip_level_count, 1);
+        assert_eq!(texture.data.len(), 16);
+    }
+
+    #[test]
+    fn parse_rgb_masks() {
+        let file = Header::rgb(BGRA_MASKS, 1, 1).write(&[1, 2, 3, 4]);
+        let texture = parse_dds(&file).unwrap();
+        assert_eq!(texture.format, TextureFormat::Bgra8Unorm);
+        assert_eq!(&texture.data[..], &[1, 2, 3, 4]);
+
+        let unsupported = Header {
+            bit_count: 24,
+            ..Header::rgb(RGBA_MASKS, 1, 1)
+        };
+        assert!(matches!(
+            parse_dds(&unsupported.write(&[0; 3])),
+            Err(TextureFileError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn parse_cube_map() {
+        let header = Header {
+            caps2: DDSCAPS2_CUBEMAP | DDSCAPS2_CUBEMAP_ALLFACES,
+            ..Header::rgb(RGBA_MASKS, 1, 1)
+        };
+        let texture = parse_dds(&header.write(&[1; 24])).unwrap();
+        assert_eq!(texture.format, TextureFormat::Rgba8Unorm);
+        assert!(texture.is_cube);
+        assert_eq!(texture.size.depth_or_array_layers, 6);
+
+        let missing_faces = Header {
+            caps2: DDSCAPS2_CUBEMAP,
+            ..Header::rgb(RGBA_MASKS, 1, 1)
+        };
+        assert_eq!(
+            parse_dds(&missing_faces.write(&[1; 24])).unwrap_err(),
+            TextureFileError::InvalidHeader("cube maps must contain all six faces")
+        );
+    }
+
+    #[test]
+    fn reject_truncated() {
+        let file = Header::four_cc(b"DXT1", 4, 4).write(&[1; 7]);
+        assert_eq!(
+            parse_dds(&file).unwrap_err(),
+            TextureFileError::DataSizeMismatch {
+                expected: 8,
+                actual: 7
+            }
+        );
+        assert_eq!(
+            parse_dds(&file[..100]).unwrap_err(),
+            TextureFileError::UnexpectedEof
+        );
+    }
+}
@@ -58,6 +58,22 @@ pub trait DeviceExt {
         order: TextureDataOrder,
         data: &[u8],
     ) -> crate::Texture;
+
+    /// Upload an entire block compressed texture, transcoding it on the CPU if the device does
+    /// not support its format.
+    ///
+    /// `data` is laid out the same way as for [`DeviceExt::create_texture_with_data`]. If the
+    /// features needed by `desc.format` are not enabled, the texture is created with the format
+    /// chosen by [`transcode_fallback_format`](super::transcode_fallback_format) instead, which
+    /// can be queried with [`Texture::format`](crate::Texture::format).
+    #[cfg(feature = "transcode")]
+    fn create_texture_with_compressed_data(
+        &self,
+        queue: &crate::Queue,
+        desc: &crate::TextureDescriptor<'_>,
+        order: TextureDataOrder,
+        data: &[u8],
+    ) -> Result<crate::Texture, super::TranscodeError>;
 }
 
 impl DeviceExt for crate::Device {
@@ -186,4 +202,81 @@ impl DeviceExt for crate::Device {
 
         texture
     }
+
+    #[cfg(feature = "transcode")]
+    fn create_texture_with_compressed_data(
+        &self,
+        queue: &crate::Queue,
+        desc: &crate::TextureDescriptor<'_>,
+        order: TextureDataOrder,
+        data: &[u8],
+    ) -> Result<crate::Texture, super::TranscodeError> {
+        if self.features().contains(desc.format.required_features()) {
+            return Ok(self.create_texture_with_data(queue, desc, order, data));
+        }
+        let format = super::transcode_fallback_format(desc.format, desc.size, self.features())
+            .ok_or(super::TranscodeError::UnsupportedSource(desc.format))?;
+
+        let block_size = desc.format.block_copy_size(None).unwrap() as usize;
+        let (block_width, block_height) = desc.format.block_dimensions();
+        let (outer_iteration, inner_iteration) = match order {
+            TextureDataOrder::LayerMajor => (desc.array_layer_count(), desc.mip_level_count),
+            TextureDataOrder::MipMajor => (desc.mip_level_count, desc.array_layer_count()),
+        };
+
+        // Transcode each subresource separately, keeping the order and tight packing expected
+        // by `create_texture_with_data`.
+        let mut transcoded = Vec::new();
+        let mut binary_offset = 0;
+        for (outer, inner) in (0..outer_iteration)
+            .flat_map(|outer| (0..inner_iteration).map(move |inner| (outer, inner)))
+        {
+            let mip = match order {
+                TextureDataOrder::LayerMajor => inner,
+                TextureDataOrder::MipMajor => outer,
+            };
+            let mut mip_size = desc.mip_level_size(mip).unwrap();
+            if desc.dimension != wgt::TextureDimension::D3 {
+                mip_size.depth_or_array_layers = 1;
+            }
+            let mip_physical = mip_size.physical_size(desc.format);
+            let data_size = (mip_physical.width / block_width) as usize
+                * (mip_physical.height / block_height) as usize
+                * mip_size.depth_or_array_layers as usize
+                * block_size;
+
+            let end_offset = binary_offset + data_size;
+            let chunk = data.get(binary_offset..end_offset).ok_or(
+                super::TranscodeError::DataSizeMismatch {
+                    expected: end_offset,
+                    actual: data.len(),
+                },
+            )?;
+            transcoded.extend(super::transcode_texture_data(
+                desc.format,
+                format,
+                mip_size,
+                chunk,
+            )?);
+            binary_offset = end_offset;
+        }
+
+        let view_formats: Vec<_> = desc
+            .view_formats
+            .iter()
+            .map(|view_format| {
+                if view_format.is_srgb() {
+                    format.add_srgb_suffix()
+                } else {
+                    format.remove_srgb_suffix()
+                }
+            })
+            .collect();
+        let desc = crate::TextureDescriptor {
+            format,
+            view_formats: &view_formats,
+            ..desc.clone()
+        };
+        Ok(self.create_texture_with_data(queue, &desc, order, &transcoded))
+    }
 }
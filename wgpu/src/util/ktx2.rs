@@ -0,0 +1,358 @@
+use std::borrow::Cow;
+
+use super::texture_file::{Reader, TextureFile, TextureFileError};
+use crate::{
+    util::TextureDataOrder, AstcBlock, AstcChannel, Extent3d, TextureDimension, TextureFormat,
+};
+
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+const HEADER_SIZE: usize = 80;
+const LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+
+/// Supercompression scheme applied to the mip levels of a KTX2 file.
+///
+/// See the [KTX2 specification](https://registry.khronos.org/KTX/specs/2.0/ktxspec.v2.html#supercompressionSchemes).
+///
+/// BasisLZ is not supported: it needs transcoding to a GPU format, with the global
+/// data of the file, rather than decompressing each mip level on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Ktx2Supercompression {
+    /// Zstandard.
+    Zstandard,
+    /// ZLIB (deflate with zlib framing).
+    Zlib,
+}
+
+/// Parses a KTX2 file that is not supercompressed.
+///
+/// Files using supercompression are rejected with
+/// [`TextureFileError::UnsupportedSupercompression`]; use [`parse_ktx2_with_decoder`] for them.
+pub fn parse_ktx2(data: &[u8]) -> Result<TextureFile<'_>, TextureFileError> {
+    parse_ktx2_with_decoder(data, |scheme, _, _| {
+        Err(TextureFileError::UnsupportedSupercompression(
+            scheme_to_raw(scheme),
+        ))
+    })
+}
+
+/// Parses a KTX2 file, calling `decode` to decompress each mip level if the file is supercompressed.
+///
+/// `decode` receives the scheme, the compressed bytes of one mip level and the uncompressed
+/// length of that level from the level index. It must return exactly that many bytes.
+///
+/// `wgpu` does not depend on any decompression library itself; applications
+/// bring whichever `zstd`/`zlib` implementation they already use.
+pub fn parse_ktx2_with_decoder(
+    data: &[u8],
+    mut decode: impl FnMut(Ktx2Supercompression, &[u8], usize) -> Result<Vec<u8>, TextureFileError>,
+) -> Result<TextureFile<'_>, TextureFileError> {
+    let reader = Reader::new(data);
+    if reader.bytes(0, KTX2_IDENTIFIER.len())? != KTX2_IDENTIFIER {
+        return Err(TextureFileError::InvalidMagic);
+    }
+
+    let vk_format = reader.u32(12)?;
+    let width = reader.u32(20)?;
+    let height = reader.u32(24)?;
+    let depth = reader.u32(28)?;
+    let layer_count = reader.u32(32)?;
+    let face_count = reader.u32(36)?;
+    let level_count = reader.u32(40)?;
+    let supercompression = match reader.u32(44)? {
+        0 => None,
+        2 => Some(Ktx2Supercompression::Zstandard),
+        3 => Some(Ktx2Supercompression::Zlib),
+        other => return Err(TextureFileError::UnsupportedSupercompression(other)),
+    };
+
+    if width == 0 {
+        return Err(TextureFileError::InvalidHeader("pixel width is zero"));
+    }
+    if face_count != 1 && face_count != 6 {
+        return Err(TextureFileError::InvalidHeader("face count must be 1 or 6"));
+    }
+    if face_count == 6 && (width != height || depth != 0) {
+        return Err(TextureFileError::InvalidHeader(
+            "cube map faces must be square 2D images",
+        ));
+    }
+    if depth != 0 && layer_count != 0 {
+        return Err(TextureFileError::InvalidHeader(
+            "3D textures cannot be arrays",
+        ));
+    }
+
+    let format = vk_format_to_texture_format(vk_format)
+        .ok_or_else(|| TextureFileError::UnsupportedFormat(format!("VkFormat({vk_format})")))?;
+
+    let dimension = if depth != 0 {
+        TextureDimension::D3
+    } else if height != 0 {
+        TextureDimension::D2
+    } else {
+        TextureDimension::D1
+    };
+    let size = Extent3d {
+        width,
+        height: height.max(1),
+        depth_or_array_layers: match dimension {
+            TextureDimension::D3 => depth,
+            _ => layer_count
+                .max(1)
+                .checked_mul(face_count)
+                .ok_or(TextureFileError::InvalidHeader("layer count is too large"))?,
+        },
+    };
+    // A level count of zero asks the loader to generate mipmaps; we only upload what is stored.
+    let mip_level_count = level_count.max(1);
+
+    let mut texels = Vec::new();
+    for level in 0..mip_level_count as usize {
+        let entry = level
+            .checked_mul(LEVEL_INDEX_ENTRY_SIZE)
+            .and_then(|offset| offset.checked_add(HEADER_SIZE))
+            .ok_or(TextureFileError::UnexpectedEof)?;
+        let offset = to_usize(reader.u64(entry)?)?;
+        let length = to_usize(reader.u64(entry + 8)?)?;
+        let uncompressed_length = to_usize(reader.u64(entry + 16)?)?;
+        let bytes = reader.bytes(offset, length)?;
+
+        match supercompression {
+            None => texels.extend_from_slice(bytes),
+            Some(scheme) => {
+                let decoded = decode(scheme, bytes, uncompressed_length)?;
+                if decoded.len() != uncompressed_length {
+                    return Err(TextureFileError::DataSizeMismatch {
+                        expected: uncompressed_length,
+                        actual: decoded.len(),
+                    });
+                }
+                texels.extend_from_slice(&decoded);
+            }
+        }
+    }
+
+    let file = TextureFile {
+        size,
+        mip_level_count,
+        dimension,
+        format,
+        is_cube: face_count == 6,
+        // Each level stores all layers and faces of that level before the next level.
+        order: TextureDataOrder::MipMajor,
+        data: Cow::Owned(texels),
+    };
+    file.validate_data_size()?;
+    Ok(file)
+}
+
+fn scheme_to_raw(scheme: Ktx2Supercompression) -> u32 {
+    match scheme {
+        Ktx2Supercompression::Zstandard => 2,
+        Ktx2Supercompression::Zlib => 3,
+    }
+}
+
+fn to_usize(value: u64) -> Result<usize, TextureFileError> {
+    usize::try_from(value).map_err(|_| TextureFileError::UnexpectedEof)
+}
+
+/// Maps a `VkFormat` value, as stored in KTX2 files, to a [`TextureFormat`].
+fn vk_format_to_texture_format(vk_format: u32) -> Option<TextureFormat> {
+    use TextureFormat as Tf;
+
+    let astc = |block, channel| Some(Tf::Astc { block, channel });
+
+    match vk_format {
+        9 => Some(Tf::R8Unorm),
+        10 => Some(Tf::R8Snorm),
+        13 => Some(Tf::R8Uint),
+        14 => Some(Tf::R8Sint),
+        16 => Some(Tf::Rg8Unorm),
+        17 => Some(Tf::Rg8Snorm),
+        20 => Some(Tf::Rg8Uint),
+        21 => Some(Tf::Rg8Sint),
+        37 => Some(Tf::Rgba8Unorm),
+        38 => Some(Tf::Rgba8Snorm),
+        41 => Some(Tf::Rgba8Uint),
+        42 => Some(Tf::Rgba8Sint),
+        43 => Some(Tf::Rgba8UnormSrgb),
+        44 => Some(Tf::Bgra8Unorm),
+        50 => Some(Tf::Bgra8UnormSrgb),
+        64 => Some(Tf::Rgb10a2Unorm),
+        68 => Some(Tf::Rgb10a2Uint),
+        70 => Some(Tf::R16Unorm),
+        71 => Some(Tf::R16Snorm),
+        74 => Some(Tf::R16Uint),
+        75 => Some(Tf::R16Sint),
+        76 => Some(Tf::R16Float),
+        77 => Some(Tf::Rg16Unorm),
+        78 => Some(Tf::Rg16Snorm),
+        81 => Some(Tf::Rg16Uint),
+        82 => Some(Tf::Rg16Sint),
+        83 => Some(Tf::Rg16Float),
+        91 => Some(Tf::Rgba16Unorm),
+        92 => Some(Tf::Rgba16Snorm),
+        95 => Some(Tf::Rgba16Uint),
+        96 => Some(Tf::Rgba16Sint),
+        97 => Some(Tf::Rgba16Float),
+        98 => Some(Tf::R32Uint),
+        99 => Some(Tf::R32Sint),
+        100 => Some(Tf::R32Float),
+        101 => Some(Tf::Rg32Uint),
+        102 => Some(Tf::Rg32Sint),
+        103 => Some(Tf::Rg32Float),
+        107 => Some(Tf::Rgba32Uint),
+        108 => Some(Tf::Rgba32Sint),
+        109 => Some(Tf::Rgba32Float),
+        122 => Some(Tf::Rg11b10Float),
+        123 => Some(Tf::Rgb9e5Ufloat),
+        124 => Some(Tf::Depth16Unorm),
+        126 => Some(Tf::Depth32Float),
+        127 => Some(Tf::Stencil8),
+        130 => Some(Tf::Depth32FloatStencil8),
+        // BC1 RGB formats are uploaded as BC1 RGBA, they share the same block layout.
+        131 | 133 => Some(Tf::Bc1RgbaUnorm),
+        132 | 134 => Some(Tf::Bc1RgbaUnormSrgb),
+        135 => Some(Tf::Bc2RgbaUnorm),
+        136 => Some(Tf::Bc2RgbaUnormSrgb),
+        137 => Some(Tf::Bc3RgbaUnorm),
+        138 => Some(Tf::Bc3RgbaUnormSrgb),
+        139 => Some(Tf::Bc4RUnorm),
+        140 => Some(Tf::Bc4RSnorm),
+        141 => Some(Tf::Bc5RgUnorm),
+        142 => Some(Tf::Bc5RgSnorm),
+        143 => Some(Tf::Bc6hRgbUfloat),
+        144 => Some(Tf::Bc6hRgbFloat),
+        145 => Some(Tf::Bc7RgbaUnorm),
+        146 => Some(Tf::Bc7RgbaUnormSrgb),
+        147 => Some(Tf::Etc2Rgb8Unorm),
+        148 => Some(Tf::Etc2Rgb8UnormSrgb),
+        149 => Some(Tf::Etc2Rgb8A1Unorm),
+        150 => Some(Tf::Etc2Rgb8A1UnormSrgb),
+        151 => Some(Tf::Etc2Rgba8Unorm),
+        152 => Some(Tf::Etc2Rgba8UnormSrgb),
+        153 => Some(Tf::EacR11Unorm),
+        154 => Some(Tf::EacR11Snorm),
+        155 => Some(Tf::EacRg11Unorm),
+        156 => Some(Tf::EacRg11Snorm),
+        157..=184 => {
+            let index = vk_format - 157;
+            let channel = if index % 2 == 0 {
+                AstcChannel::Unorm
+            } else {
+                AstcChannel::UnormSrgb
+            };
+            astc(astc_block_from_index(index / 2)?, channel)
+        }
+        // VK_EXT_texture_compression_astc_hdr
+        1_000_066_000..=1_000_066_013 => astc(
+            astc_block_from_index(vk_format - 1_000_066_000)?,
+            AstcChannel::Hdr,
+        ),
+        _ => None,
+    }
+}
+
+fn astc_block_from_index(index: u32) -> Option<AstcBlock> {
+    Some(match index {
+        0 => AstcBlock::B4x4,
+        1 => AstcBlock::B5x4,
+        2 => AstcBlock::B5x5,
+        3 => AstcBlock::B6x5,
+        4 => AstcBlock::B6x6,
+        5 => AstcBlock::B8x5,
+        6 => AstcBlock::B8x6,
+        7 => AstcBlock::B8x8,
+        8 => AstcBlock::B10x5,
+        9 => AstcBlock::B10x6,
+        10 => AstcBlock::B10x8,
+        11 => AstcBlock::B10x10,
+        12 => AstcBlock::B12x10,
+        13 => AstcBlock::B12x12,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(vk_format: u32, width: u32, height: u32, levels: &[&[u8]]) -> Vec<u8> {
+        let mut file = KTX2_IDENTIFIER.to_vec();
+        for value in [vk_format, 1, width, height, 0, 0, 1, levels.len() as u32, 0] {
+            file.extend_from_slice(&value.to_le_bytes());
+        }
+        // dfd, kvd and sgd are unused by the loader.
+        file.extend_from_slice(&[0; 32]);
+
+        let mut offset = (HEADER_SIZE + levels.len() * LEVEL_INDEX_ENTRY_SIZE) as u64;
+        for level in levels {
+            let len = level.len() as u64;
+            for value in [offset, len, len] {
+                file.extend_from_slice(&value.to_le_bytes());
+            }
+            offset += len;
+        }
+        for level in levels {
+            file.extend_from_slice(level);
+        }
+        file
+    }
+
+    #[test]
+    fn parse_mip_chain() {
+        let file = header(37, 2, 2, &[&[1; 16], &[2; 4]]);
+        let texture = parse_ktx2(&file).unwrap();
+        assert_eq!(texture.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(texture.mip_level_count, 2);
+        assert_eq!(texture.dimension, TextureDimension::D2);
+        assert_eq!(&texture.data[..16], &[1; 16]);
+        assert_eq!(&texture.data[16..], &[2; 4]);
+    }
+
+    #[test]
+    fn reject_truncated_level() {
+        let file = header(37, 2, 2, &[&[1; 15]]);
+        assert_eq!(
+            parse_ktx2(&file).unwrap_err(),
+            TextureFileError::DataSizeMismatch {
+                expected: 16,
+                actual: 15
+            }
+        );
+    }
+
+    #[test]
+    fn astc_mapping() {
+        assert_eq!(
+            vk_format_to_texture_format(172),
+            Some(TextureFormat::Astc {
+                block: AstcBlock::B8x8,
+                channel: AstcChannel::UnormSrgb
+            })
+        );
+    }
+    #[test]
+    fn reject_basis_lz() {
+        let mut file = header(0, 2, 2, &[&[1; 16]]);
+        file[44..48].copy_from_slice(&1u32.to_le_bytes());
+        assert_eq!(
+            parse_ktx2_with_decoder(&file, |_, _, len| Ok(vec![0; len])).unwrap_err(),
+            TextureFileError::UnsupportedSupercompression(1)
+        );
+    }
+
+    #[test]
+    fn reject_layer_count_overflow() {
+        let mut file = header(37, 2, 2, &[&[1; 16]]);
+        file[32..36].copy_from_slice(&u32::MAX.to_le_bytes());
+        file[36..40].copy_from_slice(&6u32.to_le_bytes());
+        assert_eq!(
+            parse_ktx2(&file).unwrap_err(),
+            TextureFileError::InvalidHeader("layer count is too large")
+        );
+    }
+}
@@ -3,10 +3,25 @@
 //! Nothing in this module is a part of the WebGPU API specification;
 //! they are unique to the `wgpu` library.
 
+#[cfg(feature = "wgsl")]
+mod bc_compress;
 mod belt;
+#[cfg(feature = "dds")]
+mod dds;
 mod device;
 mod encoder;
 mod init;
+#[cfg(feature = "ktx2")]
+mod ktx2;
+#[cfg(feature = "wgsl")]
+mod occlusion_cull;
+#[cfg(feature = "wgsl")]
+mod sort;
+#[cfg(any(feature = "ktx2", feature = "dds"))]
+mod texture_file;
+#[cfg(feature = "transcode")]
+mod transcode;
+mod upload;
 
 use std::sync::Arc;
 use std::{
@@ -15,11 +30,29 @@ use std::{
     ptr::copy_nonoverlapping,
 };
 
+#[cfg(feature = "wgsl")]
+pub use bc_compress::BcCompressor;
 pub use belt::StagingBelt;
+#[cfg(feature = "dds")]
+pub use dds::parse_dds;
 pub use device::{BufferInitDescriptor, DeviceExt, TextureDataOrder};
 pub use encoder::RenderEncoder;
 pub use init::*;
-pub use wgt::{math::*, DispatchIndirectArgs, DrawIndexedIndirectArgs, DrawIndirectArgs};
+#[cfg(feature = "ktx2")]
+pub use ktx2::{parse_ktx2, parse_ktx2_with_decoder, Ktx2Supercompression};
+#[cfg(feature = "wgsl")]
+pub use occlusion_cull::{DepthPyramid, InstanceBounds, OcclusionCullDescriptor, OcclusionCuller};
+#[cfg(feature = "wgsl")]
+pub use sort::{PrefixSum, RadixSort};
+#[cfg(any(feature = "ktx2", feature = "dds"))]
+pub use texture_file::{TextureFile, TextureFileError};
+#[cfg(feature = "transcode")]
+pub use transcode::{transcode_fallback_format, transcode_texture_data, TranscodeError};
+pub use upload::{UploadFuture, UploadManager};
+pub use wgt::{
+    math::*, DispatchIndirectArgs, DrawIndexedIndirectArgs, DrawIndirectArgs,
+    DrawMeshTasksIndirectArgs, VertexBufferIndirectArgs,
+};
 
 /// Treat the given byte slice as a SPIR-V module.
 ///
@@ -0,0 +1,496 @@
+use crate::{
+    util::{BufferInitDescriptor, DeviceExt},
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, Buffer, BufferBindingType, BufferUsages,
+    CommandEncoder, ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor, Device,
+    Extent3d, PipelineLayoutDescriptor, ShaderModuleDescriptor, ShaderSource, ShaderStages,
+    StorageTextureAccess, Texture, TextureAspect, TextureDescriptor, TextureDimension,
+    TextureFormat, TextureSampleType, TextureUsages, TextureView, TextureViewDescriptor,
+    TextureViewDimension,
+};
+
+const PYRAMID_WORKGROUP_SIZE: u32 = 8;
+const CULL_WORKGROUP_SIZE: u32 = 64;
+
+/// Axis-aligned bounding box of an instance, in the space transformed by
+/// [`OcclusionCullDescriptor::view_proj`].
+///
+/// The bounds buffer passed to [`OcclusionCuller::cull`] is a tightly packed
+/// array of these, in the layout returned by [`InstanceBounds::as_bytes`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct InstanceBounds {
+    /// Corner of the box with the smallest coordinates.
+    pub min: [f32; 3],
+    /// Corner of the box with the largest coordinates.
+    pub max: [f32; 3],
+}
+
+impl InstanceBounds {
+    /// Size in bytes of the bounds of one instance in the bounds buffer.
+    pub const SIZE: u64 = 32;
+
+    /// Returns the bytes of the bounds, as read by the culling shader.
+    ///
+    /// Each corner is padded to 16 bytes.
+    pub fn as_bytes(&self) -> [u8; Self::SIZE as usize] {
+        let words = [
+            self.min[0],
+            self.min[1],
+            self.min[2],
+            0.0,
+            self.max[0],
+            self.max[1],
+            self.max[2],
+            0.0,
+        ];
+        let mut bytes = [0; Self::SIZE as usize];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+/// Hierarchical depth buffer built by [`OcclusionCuller::build_pyramid`].
+///
+/// Every texel of a mip level holds the farthest depth of the texels it
+/// covers in the level below, the first level being a copy of the depth buffer.
+#[derive(Debug)]
+pub struct DepthPyramid {
+    texture: Texture,
+    view: TextureView,
+    level_views: Vec<TextureView>,
+    reverse_z: bool,
+}
+
+impl DepthPyramid {
+    /// Returns the `R32Float` texture holding the pyramid.
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Returns whether larger depth values are nearer to the camera.
+    pub fn reverse_z(&self) -> bool {
+        self.reverse_z
+    }
+}
+
+/// Describes a culling dispatch recorded by [`OcclusionCuller::cull`].
+#[derive(Clone, Debug)]
+pub struct OcclusionCullDescriptor<'a> {
+    /// Column-major matrix transforming the instance bounds into clip space.
+    ///
+    /// This is usually the view-projection matrix the pyramid's depth buffer
+    /// was rendered with, which makes the culling conservative for objects
+    /// that were hidden during the previous frame.
+    pub view_proj: [[f32; 4]; 4],
+    /// Number of instances to test.
+    pub instance_count: u32,
+    /// Buffer of [`InstanceBounds`], with the [`BufferUsages::STORAGE`] usage.
+    pub bounds: &'a Buffer,
+    /// Buffer of [`DrawIndexedIndirectArgs`](crate::util::DrawIndexedIndirectArgs),
+    /// one per instance, with the [`BufferUsages::STORAGE`] usage.
+    pub draws: &'a Buffer,
+    /// Buffer receiving the draws of the instances that may be visible,
+    /// tightly packed, with the [`BufferUsages::STORAGE`] and
+    /// [`BufferUsages::INDIRECT`] usages.
+    pub visible_draws: &'a Buffer,
+    /// Buffer receiving the number of draws written to `visible_draws` as a
+    /// `u32`, with the [`BufferUsages::STORAGE`], [`BufferUsages::INDIRECT`]
+    /// and [`BufferUsages::COPY_DST`] usages.
+    pub visible_count: &'a Buffer,
+}
+
+struct CullParams {
+    view_proj: [[f32; 4]; 4],
+    instance_count: u32,
+    reverse_z: u32,
+}
+
+impl CullParams {
+    fn as_bytes(&self) -> [u8; 80] {
+        let mut bytes = [0; 80];
+        let matrix = self
+            .view_proj
+            .iter()
+            .flatten()
+            .map(|value| value.to_le_bytes());
+        let words = [self.instance_count, self.reverse_z]
+            .into_iter()
+            .map(|word| word.to_le_bytes());
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(matrix.chain(words)) {
+            chunk.copy_from_slice(&word);
+        }
+        bytes
+    }
+}
+
+/// GPU-driven occlusion culling against a hierarchical depth buffer.
+///
+/// Every frame, [`OcclusionCuller::build_pyramid`] reduces a depth buffer into
+/// a [`DepthPyramid`], then [`OcclusionCuller::cull`] tests the bounding box of
+/// every instance against it and compacts the draws of the instances that may
+/// be visible into an indirect buffer. The result is meant to be drawn with
+/// [`RenderPass::multi_draw_indexed_indirect_count`], or read back by the user.
+///
+/// The depth buffer must have been created with [`TextureUsages::TEXTURE_BINDING`],
+/// and must not be multisampled. The device must support compute shaders.
+///
+/// [`RenderPass::multi_draw_indexed_indirect_count`]: crate::RenderPass::multi_draw_indexed_indirect_count
+#[derive(Debug)]
+pub struct OcclusionCuller {
+    copy_layout: BindGroupLayout,
+    reduce_layout: BindGroupLayout,
+    cull_layout: BindGroupLayout,
+    copy_depth: ComputePipeline,
+    reduce_max: ComputePipeline,
+    reduce_min: ComputePipeline,
+    cull: ComputePipeline,
+}
+
+fn storage_buffer_entry(binding: u32, read_only: bool) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::COMPUTE,
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn texture_entry(binding: u32, sample_type: TextureSampleType) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::COMPUTE,
+        ty: BindingType::Texture {
+            sample_type,
+            view_dimension: TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: None,
+    }
+}
+
+const LEVEL_ENTRY: BindGroupLayoutEntry = BindGroupLayoutEntry {
+    binding: 2,
+    visibility: ShaderStages::COMPUTE,
+    ty: BindingType::StorageTexture {
+        access: StorageTextureAccess::WriteOnly,
+        format: TextureFormat::R32Float,
+        view_dimension: TextureViewDimension::D2,
+    },
+    count: None,
+};
+
+impl OcclusionCuller {
+    /// Creates the compute pipelines used for building pyramids and culling.
+    pub fn new(device: &Device) -> Self {
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("(wgpu internal) OcclusionCuller shader"),
+            source: ShaderSource::Wgsl(include_str!("occlusion_cull.wgsl").into()),
+        });
+
+        let copy_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("(wgpu internal) OcclusionCuller copy bind group layout"),
+            entries: &[texture_entry(0, TextureSampleType::Depth), LEVEL_ENTRY],
+        });
+        let reduce_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("(wgpu internal) OcclusionCuller reduce bind group layout"),
+            entries: &[
+                texture_entry(1, TextureSampleType::Float { filterable: false }),
+                LEVEL_ENTRY,
+            ],
+        });
+        let cull_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("(wgpu internal) OcclusionCuller cull bind group layout"),
+            entries: &[
+                texture_entry(3, TextureSampleType::Float { filterable: false }),
+                BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_buffer_entry(5, true),
+                storage_buffer_entry(6, true),
+                storage_buffer_entry(7, false),
+                storage_buffer_entry(8, false),
+            ],
+        });
+
+        let pipeline = |bind_group_layout, entry_point| {
+            let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("(wgpu internal) OcclusionCuller pipeline layout"),
+                bind_group_layouts: &[bind_group_layout],
+                push_constant_ranges: &[],
+            });
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some("(wgpu internal) OcclusionCuller pipeline"),
+                layout: Some(&layout),
+                module: &module,
+                entry_point,
+                cache: None,
+            })
+        };
+
+        Self {
+            copy_depth: pipeline(&copy_layout, "copy_depth"),
+            reduce_max: pipeline(&reduce_layout, "reduce_max"),
+            reduce_min: pipeline(&reduce_layout, "reduce_min"),
+            cull: pipeline(&cull_layout, "cull"),
+            copy_layout,
+            reduce_layout,
+            cull_layout,
+        }
+    }
+
+    /// Creates a pyramid for depth buffers of the given size, with a full mip chain.
+    ///
+    /// If `reverse_z` is `true`, larger depth values are considered nearer to the camera.
+    pub fn create_pyramid(
+        &self,
+        device: &Device,
+        width: u32,
+        height: u32,
+        reverse_z: bool,
+    ) -> DepthPyramid {
+        let size = Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let mip_level_count = size.max_mips(TextureDimension::D2);
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("(wgpu internal) DepthPyramid"),
+            size,
+            mip_level_count,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::R32Float,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::STORAGE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&TextureViewDescriptor::default());
+        let level_views = (0..mip_level_count)
+            .map(|mip_level| {
+                texture.create_view(&TextureViewDescriptor {
+                    label: Some("(wgpu internal) DepthPyramid level view"),
+                    base_mip_level: mip_level,
+                    mip_level_count: Some(1),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        DepthPyramid {
+            texture,
+            view,
+            level_views,
+            reverse_z,
+        }
+    }
+
+    /// Records commands copying the first layer of `depth` into `pyramid`, and
+    /// building the rest of its mip levels.
+    ///
+    /// # Panics
+    ///
+    /// - `depth` is not a 2D depth texture of the same size as `pyramid`, created with
+    ///   [`TextureUsages::TEXTURE_BINDING`].
+    pub fn build_pyramid(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        depth: &Texture,
+        pyramid: &DepthPyramid,
+    ) {
+        assert_eq!(depth.dimension(), TextureDimension::D2);
+        assert!(depth.format().has_depth_aspect());
+        assert!(depth.usage().contains(TextureUsages::TEXTURE_BINDING));
+        let size = pyramid.texture.size();
+        assert_eq!(
+            (depth.width(), depth.height()),
+            (size.width, size.height),
+            "depth buffer and pyramid sizes differ"
+        );
+
+        let depth_view = depth.create_view(&TextureViewDescriptor {
+            label: Some("(wgpu internal) OcclusionCuller depth view"),
+            dimension: Some(TextureViewDimension::D2),
+            aspect: TextureAspect::DepthOnly,
+            mip_level_count: Some(1),
+            array_layer_count: Some(1),
+            ..Default::default()
+        });
+        let reduce = if pyramid.reverse_z {
+            &self.reduce_min
+        } else {
+            &self.reduce_max
+        };
+
+        let bind_groups = pyramid
+            .level_views
+            .iter()
+            .enumerate()
+            .map(|(mip_level, level_view)| {
+                if mip_level == 0 {
+                    device.create_bind_group(&BindGroupDescriptor {
+                        label: Some("(wgpu internal) OcclusionCuller copy bind group"),
+                        layout: &self.copy_layout,
+                        entries: &[
+                            BindGroupEntry {
+                                binding: 0,
+                                resource: BindingResource::TextureView(&depth_view),
+                            },
+                            BindGroupEntry {
+                                binding: 2,
+                                resource: BindingResource::TextureView(level_view),
+                            },
+                        ],
+                    })
+                } else {
+                    device.create_bind_group(&BindGroupDescriptor {
+                        label: Some("(wgpu internal) OcclusionCuller reduce bind group"),
+                        layout: &self.reduce_layout,
+                        entries: &[
+                            BindGroupEntry {
+                                binding: 1,
+                                resource: BindingResource::TextureView(
+                                    &pyramid.level_views[mip_level - 1],
+                                ),
+                            },
+                            BindGroupEntry {
+                                binding: 2,
+                                resource: BindingResource::TextureView(level_view),
+                            },
+                        ],
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("(wgpu internal) OcclusionCuller pyramid pass"),
+            timestamp_writes: None,
+        });
+        for (mip_level, bind_group) in bind_groups.iter().enumerate() {
+            pass.set_pipeline(if mip_level == 0 {
+                &self.copy_depth
+            } else {
+                reduce
+            });
+            pass.set_bind_group(0, bind_group, &[]);
+            let level_size = size.mip_level_size(mip_level as u32, TextureDimension::D2);
+            pass.dispatch_workgroups(
+                (level_size.width + PYRAMID_WORKGROUP_SIZE - 1) / PYRAMID_WORKGROUP_SIZE,
+                (level_size.height + PYRAMID_WORKGROUP_SIZE - 1) / PYRAMID_WORKGROUP_SIZE,
+                1,
+            );
+        }
+    }
+
+    /// Records commands testing the instances of `desc` against `pyramid`, and writing the
+    /// draws of the instances that may be visible to `desc.visible_draws`.
+    ///
+    /// Instances are tested against the view frustum, then against the pyramid level where
+    /// their screen-space bounding rectangle covers at most 2x2 texels. Boxes crossing the near
+    /// plane are always considered visible. The order of the visible draws is unspecified.
+    ///
+    /// # Panics
+    ///
+    /// - `desc.visible_count` is missing the [`BufferUsages::COPY_DST`] usage.
+    pub fn cull(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        pyramid: &DepthPyramid,
+        desc: &OcclusionCullDescriptor<'_>,
+    ) {
+        assert!(desc.visible_count.usage().contains(BufferUsages::COPY_DST));
+        encoder.clear_buffer(desc.visible_count, 0, None);
+        if desc.instance_count == 0 {
+            return;
+        }
+
+        let params = CullParams {
+            view_proj: desc.view_proj,
+            instance_count: desc.instance_count,
+            reverse_z: pyramid.reverse_z as u32,
+        };
+        let params = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("(wgpu internal) OcclusionCuller params"),
+            contents: &params.as_bytes(),
+            usage: BufferUsages::UNIFORM,
+        });
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("(wgpu internal) OcclusionCuller cull bind group"),
+            layout: &self.cull_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::TextureView(&pyramid.view),
+                },
+                BindGroupEntry {
+                    binding: 4,
+                    resource: params.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 5,
+                    resource: desc.bounds.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 6,
+                    resource: desc.draws.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 7,
+                    resource: desc.visible_draws.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 8,
+                    resource: desc.visible_count.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("(wgpu internal) OcclusionCuller cull pass"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.cull);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(
+            (desc.instance_count + CULL_WORKGROUP_SIZE - 1) / CULL_WORKGROUP_SIZE,
+            1,
+            1,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cull_params_layout() {
+        let mut view_proj = [[0.0; 4]; 4];
+        view_proj[1][0] = 2.0;
+        let bytes = CullParams {
+            view_proj,
+            instance_count: 7,
+            reverse_z: 1,
+        }
+        .as_bytes();
+        // Second column, first row.
+        assert_eq!(bytes[16..20], 2.0f32.to_le_bytes());
+        assert_eq!(bytes[64..68], 7u32.to_le_bytes());
+        assert_eq!(bytes[68..72], 1u32.to_le_bytes());
+        assert_eq!(bytes[72..], [0; 8]);
+    }
+}
@@ -0,0 +1,163 @@
+// Hierarchical-Z occlusion culling.
+//
+// The pyramid stores, for every texel of every mip level, the farthest depth
+// of the texels it covers in the level below. An instance is occluded if the
+// nearest point of its screen-space bounding rectangle is farther than the
+// pyramid at a level where the rectangle covers only a few texels.
+
+@group(0) @binding(0)
+var depth: texture_depth_2d;
+@group(0) @binding(1)
+var previous_level: texture_2d<f32>;
+@group(0) @binding(2)
+var level: texture_storage_2d<r32float, write>;
+
+@compute @workgroup_size(8, 8)
+fn copy_depth(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= textureDimensions(level)) {
+        return;
+    }
+    textureStore(level, id.xy, vec4<f32>(textureLoad(depth, id.xy, 0)));
+}
+
+// Returns the range of texels of `previous_level` covered by `coord` in
+// `level`, which may be 3 texels wide when the previous size is odd.
+fn covered_range(coord: vec2<u32>) -> array<vec2<u32>, 2> {
+    let src_size = textureDimensions(previous_level);
+    let dst_size = textureDimensions(level);
+    let start = coord * src_size / dst_size;
+    let end = min(((coord + 1u) * src_size + dst_size - 1u) / dst_size, src_size);
+    return array<vec2<u32>, 2>(start, end);
+}
+
+@compute @workgroup_size(8, 8)
+fn reduce_max(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= textureDimensions(level)) {
+        return;
+    }
+    let range = covered_range(id.xy);
+    var farthest = 0.0;
+    for (var y = range[0].y; y < range[1].y; y++) {
+        for (var x = range[0].x; x < range[1].x; x++) {
+            farthest = max(farthest, textureLoad(previous_level, vec2<u32>(x, y), 0).r);
+        }
+    }
+    textureStore(level, id.xy, vec4<f32>(farthest));
+}
+
+@compute @workgroup_size(8, 8)
+fn reduce_min(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= textureDimensions(level)) {
+        return;
+    }
+    let range = covered_range(id.xy);
+    var farthest = 1.0;
+    for (var y = range[0].y; y < range[1].y; y++) {
+        for (var x = range[0].x; x < range[1].x; x++) {
+            farthest = min(farthest, textureLoad(previous_level, vec2<u32>(x, y), 0).r);
+        }
+    }
+    textureStore(level, id.xy, vec4<f32>(farthest));
+}
+
+struct CullParams {
+    view_proj: mat4x4<f32>,
+    instance_count: u32,
+    // Non-zero if larger depth values are nearer.
+    reverse_z: u32,
+}
+
+struct Bounds {
+    min: vec3<f32>,
+    max: vec3<f32>,
+}
+
+struct DrawIndexedIndirect {
+    index_count: u32,
+    instance_count: u32,
+    first_index: u32,
+    base_vertex: i32,
+    first_instance: u32,
+}
+
+// The bindings don't overlap with the pyramid bindings, so that all entry
+// points can live in the same module.
+@group(0) @binding(3)
+var pyramid: texture_2d<f32>;
+@group(0) @binding(4)
+var<uniform> params: CullParams;
+@group(0) @binding(5)
+var<storage> bounds: array<Bounds>;
+@group(0) @binding(6)
+var<storage> draws: array<DrawIndexedIndirect>;
+@group(0) @binding(7)
+var<storage, read_write> visible_draws: array<DrawIndexedIndirect>;
+@group(0) @binding(8)
+var<storage, read_write> visible_count: atomic<u32>;
+
+// Returns whether the bounding box may be visible.
+fn test_bounds(aabb: Bounds) -> bool {
+    var uv_min = vec2<f32>(1.0);
+    var uv_max = vec2<f32>(0.0);
+    var nearest = select(1.0, 0.0, params.reverse_z != 0u);
+    for (var i = 0u; i < 8u; i++) {
+        let pick_max = vec3<bool>((i & 1u) != 0u, (i & 2u) != 0u, (i & 4u) != 0u);
+        let corner = select(aabb.min, aabb.max, pick_max);
+        let clip = params.view_proj * vec4<f32>(corner, 1.0);
+        if clip.w <= 0.0 {
+            // The box crosses the near plane.
+            return true;
+        }
+        let ndc = clip.xyz / clip.w;
+        let uv = ndc.xy * vec2<f32>(0.5, -0.5) + 0.5;
+        uv_min = min(uv_min, uv);
+        uv_max = max(uv_max, uv);
+        if params.reverse_z != 0u {
+            nearest = max(nearest, ndc.z);
+        } else {
+            nearest = min(nearest, ndc.z);
+        }
+    }
+
+    // Frustum culling.
+    if any(uv_max < vec2<f32>(0.0)) || any(uv_min > vec2<f32>(1.0)) {
+        return false;
+    }
+    if (params.reverse_z != 0u && nearest < 0.0) || (params.reverse_z == 0u && nearest > 1.0) {
+        return false;
+    }
+    uv_min = clamp(uv_min, vec2<f32>(0.0), vec2<f32>(1.0));
+    uv_max = clamp(uv_max, vec2<f32>(0.0), vec2<f32>(1.0));
+
+    // Pick the level where the rectangle covers about 2x2 texels.
+    let base_size = vec2<f32>(textureDimensions(pyramid, 0));
+    let extent = (uv_max - uv_min) * base_size;
+    let level_count = textureNumLevels(pyramid);
+    let mip = min(u32(ceil(log2(max(max(extent.x, extent.y), 1.0)))), level_count - 1u);
+
+    let size = textureDimensions(pyramid, mip);
+    let lo = min(vec2<u32>(uv_min * vec2<f32>(size)), size - 1u);
+    let hi = min(vec2<u32>(uv_max * vec2<f32>(size)), size - 1u);
+    for (var y = lo.y; y <= hi.y; y++) {
+        for (var x = lo.x; x <= hi.x; x++) {
+            let farthest = textureLoad(pyramid, vec2<u32>(x, y), mip).r;
+            let in_front = select(nearest <= farthest, nearest >= farthest, params.reverse_z != 0u);
+            if in_front {
+                return true;
+            }
+        }
+    }
+    return false;
+}
+
+@compute @workgroup_size(64)
+fn cull(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if index >= params.instance_count {
+        return;
+    }
+    if test_bounds(bounds[index]) {
+        let slot = atomicAdd(&visible_count, 1u);
+        visible_draws[slot] = draws[index];
+    }
+}
@@ -0,0 +1,95 @@
+// Least significant digit radix sort of `u32` keys and values, 4 bits per pass.
+//
+// `WORKGROUP_SIZE` is prepended by `RadixSort`, depending on the device limits.
+// Every pass counts the digits of each block, scans the counts to find where
+// the elements of each digit and block go, and scatters the elements there in
+// their original order.
+
+const RADIX: u32 = 16u;
+
+struct Params {
+    // Number of elements to sort.
+    count: u32,
+    // Number of blocks, which may be dispatched over two dimensions.
+    block_count: u32,
+    // Position of the digit sorted by this pass.
+    shift: u32,
+}
+
+@group(0) @binding(0)
+var<storage> keys_in: array<u32>;
+@group(0) @binding(1)
+var<storage> values_in: array<u32>;
+@group(0) @binding(2)
+var<storage, read_write> keys_out: array<u32>;
+@group(0) @binding(3)
+var<storage, read_write> values_out: array<u32>;
+// Digit-major, the count of `digit` in `block` is at `digit * block_count + block`.
+@group(0) @binding(4)
+var<storage, read_write> histograms: array<u32>;
+@group(0) @binding(5)
+var<uniform> params: Params;
+
+var<workgroup> counts: array<atomic<u32>, RADIX>;
+var<workgroup> digits: array<u32, WORKGROUP_SIZE>;
+
+// Returns the digit of the key at `index`, or `RADIX` past the end of the keys.
+fn load_digit(index: u32) -> u32 {
+    if index < params.count {
+        return (keys_in[index] >> params.shift) & (RADIX - 1u);
+    }
+    return RADIX;
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn histogram(
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let block = workgroup_id.y * num_workgroups.x + workgroup_id.x;
+    if block >= params.block_count {
+        return;
+    }
+    if local_index < RADIX {
+        atomicStore(&counts[local_index], 0u);
+    }
+    workgroupBarrier();
+
+    let digit = load_digit(block * WORKGROUP_SIZE + local_index);
+    if digit < RADIX {
+        atomicAdd(&counts[digit], 1u);
+    }
+    workgroupBarrier();
+
+    if local_index < RADIX {
+        histograms[local_index * params.block_count + block] = atomicLoad(&counts[local_index]);
+    }
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn scatter(
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let block = workgroup_id.y * num_workgroups.x + workgroup_id.x;
+    if block >= params.block_count {
+        return;
+    }
+    let index = block * WORKGROUP_SIZE + local_index;
+    let digit = load_digit(index);
+    digits[local_index] = digit;
+    workgroupBarrier();
+
+    if digit < RADIX {
+        // Counting the preceding elements with the same digit keeps the sort stable.
+        var rank = 0u;
+        for (var i = 0u; i < local_index; i++) {
+            rank += u32(digits[i] == digit);
+        }
+        let destination = histograms[digit * params.block_count + block] + rank;
+        keys_out[destination] = keys_in[index];
+        values_out[destination] = values_in[index];
+    }
+}
@@ -0,0 +1,72 @@
+// Exclusive prefix sum of `u32`s, one element per invocation.
+//
+// `WORKGROUP_SIZE` is prepended by `PrefixSum`, depending on the device limits.
+// Every workgroup scans its block in workgroup memory and writes the total of
+// the block to `block_sums`. Once `block_sums` has been scanned in turn, the
+// scanned totals are added back to the blocks.
+
+struct Params {
+    // Number of elements in `data`.
+    count: u32,
+    // Number of blocks, which may be dispatched over two dimensions.
+    block_count: u32,
+}
+
+@group(0) @binding(0)
+var<storage, read_write> data: array<u32>;
+@group(0) @binding(1)
+var<storage, read_write> block_sums: array<u32>;
+@group(0) @binding(2)
+var<uniform> params: Params;
+
+var<workgroup> sums: array<u32, WORKGROUP_SIZE>;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn scan_blocks(
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let block = workgroup_id.y * num_workgroups.x + workgroup_id.x;
+    if block >= params.block_count {
+        return;
+    }
+    let index = block * WORKGROUP_SIZE + local_index;
+    var value = 0u;
+    if index < params.count {
+        value = data[index];
+    }
+    sums[local_index] = value;
+    workgroupBarrier();
+
+    // Inclusive Hillis-Steele scan.
+    for (var offset = 1u; offset < WORKGROUP_SIZE; offset *= 2u) {
+        var sum = sums[local_index];
+        if local_index >= offset {
+            sum += sums[local_index - offset];
+        }
+        workgroupBarrier();
+        sums[local_index] = sum;
+        workgroupBarrier();
+    }
+
+    if index < params.count {
+        data[index] = sums[local_index] - value;
+    }
+    if local_index == WORKGROUP_SIZE - 1u {
+        block_sums[block] = sums[local_index];
+    }
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn add_block_sums(
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let block = workgroup_id.y * num_workgroups.x + workgroup_id.x;
+    let index = block * WORKGROUP_SIZE + local_index;
+    if block < params.block_count && index < params.count {
+        data[index] += block_sums[block];
+    }
+}
@@ -0,0 +1,454 @@
+use crate::{
+    util::{BufferInitDescriptor, DeviceExt},
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, Buffer, BufferBindingType, BufferDescriptor, BufferUsages,
+    CommandEncoder, ComputePass, ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor,
+    Device, Limits, PipelineLayoutDescriptor, ShaderModule, ShaderModuleDescriptor, ShaderSource,
+    ShaderStages,
+};
+
+/// Number of bits sorted by each pass of [`RadixSort`].
+const RADIX_BITS: u32 = 4;
+const RADIX: u32 = 1 << RADIX_BITS;
+
+/// Returns the largest power of two workgroup size, between 64 and 256, allowed
+/// by `limits` when every invocation uses `shared_bytes` of workgroup memory.
+fn select_workgroup_size(limits: &Limits, shared_bytes: u32) -> u32 {
+    let mut size = 256;
+    while size > 64
+        && (size > limits.max_compute_invocations_per_workgroup
+            || size > limits.max_compute_workgroup_size_x
+            || size * shared_bytes > limits.max_compute_workgroup_storage_size)
+    {
+        size /= 2;
+    }
+    size
+}
+
+fn check_workgroup_size(device: &Device, workgroup_size: u32) {
+    let limits = device.limits();
+    assert!(
+        workgroup_size.is_power_of_two() && workgroup_size >= RADIX,
+        "workgroup size {workgroup_size} must be a power of two of at least {RADIX}"
+    );
+    assert!(
+        workgroup_size <= limits.max_compute_invocations_per_workgroup
+            && workgroup_size <= limits.max_compute_workgroup_size_x,
+        "workgroup size {workgroup_size} exceeds the device limits"
+    );
+}
+
+fn create_module(device: &Device, label: &str, source: &str, workgroup_size: u32) -> ShaderModule {
+    device.create_shader_module(ShaderModuleDescriptor {
+        label: Some(label),
+        source: ShaderSource::Wgsl(
+            format!("const WORKGROUP_SIZE: u32 = {workgroup_size}u;\n{source}").into(),
+        ),
+    })
+}
+
+fn storage_buffer_entry(binding: u32, read_only: bool) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::COMPUTE,
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn uniform_buffer_entry(binding: u32) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::COMPUTE,
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn create_pipelines<const N: usize>(
+    device: &Device,
+    label: &str,
+    module: &ShaderModule,
+    bind_group_layout: &BindGroupLayout,
+    entry_points: [&str; N],
+) -> [ComputePipeline; N] {
+    let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+        label: Some(label),
+        bind_group_layouts: &[bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    entry_points.map(|entry_point| {
+        device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some(label),
+            layout: Some(&layout),
+            module,
+            entry_point,
+            cache: None,
+        })
+    })
+}
+
+fn uniform_buffer(device: &Device, label: &str, words: &[u32]) -> Buffer {
+    // Uniform buffers are padded to 16 bytes.
+    let mut contents = vec![0; 16 * ((words.len() + 3) / 4)];
+    for (chunk, word) in contents.chunks_exact_mut(4).zip(words) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    device.create_buffer_init(&BufferInitDescriptor {
+        label: Some(label),
+        contents: &contents,
+        usage: BufferUsages::UNIFORM,
+    })
+}
+
+/// Dispatches one workgroup per block, spreading them over two dimensions if
+/// they don't fit in one.
+fn dispatch_blocks(pass: &mut ComputePass<'_>, block_count: u32, max_per_dimension: u32) {
+    let x = block_count.min(max_per_dimension);
+    let y = (block_count + x - 1) / x;
+    pass.dispatch_workgroups(x, y, 1);
+}
+
+/// Computes exclusive prefix sums of `u32` storage buffers on the GPU.
+///
+/// Every element is replaced by the wrapping sum of the elements preceding it,
+/// the first element becoming `0`. This is the building block of stream
+/// compaction and of [`RadixSort`].
+#[derive(Debug)]
+pub struct PrefixSum {
+    workgroup_size: u32,
+    bind_group_layout: BindGroupLayout,
+    scan_blocks: ComputePipeline,
+    add_block_sums: ComputePipeline,
+}
+
+impl PrefixSum {
+    /// Creates the compute pipelines, with the largest workgroup size allowed by the
+    /// limits of `device`.
+    pub fn new(device: &Device) -> Self {
+        Self::with_workgroup_size(device, select_workgroup_size(&device.limits(), 4))
+    }
+
+    /// Creates the compute pipelines with the given workgroup size, which may be tuned
+    /// for a given adapter.
+    ///
+    /// # Panics
+    ///
+    /// - `workgroup_size` is not a power of two of at least 16, or exceeds the device limits.
+    pub fn with_workgroup_size(device: &Device, workgroup_size: u32) -> Self {
+        check_workgroup_size(device, workgroup_size);
+        let module = create_module(
+            device,
+            "(wgpu internal) PrefixSum shader",
+            include_str!("scan.wgsl"),
+            workgroup_size,
+        );
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("(wgpu internal) PrefixSum bind group layout"),
+            entries: &[
+                storage_buffer_entry(0, false),
+                storage_buffer_entry(1, false),
+                uniform_buffer_entry(2),
+            ],
+        });
+        let [scan_blocks, add_block_sums] = create_pipelines(
+            device,
+            "(wgpu internal) PrefixSum pipeline",
+            &module,
+            &bind_group_layout,
+            ["scan_blocks", "add_block_sums"],
+        );
+        Self {
+            workgroup_size,
+            bind_group_layout,
+            scan_blocks,
+            add_block_sums,
+        }
+    }
+
+    /// Returns the number of elements scanned by each workgroup.
+    pub fn workgroup_size(&self) -> u32 {
+        self.workgroup_size
+    }
+
+    /// Records commands replacing the first `count` `u32`s of `buffer` by their exclusive
+    /// prefix sum.
+    ///
+    /// `buffer` must have been created with [`BufferUsages::STORAGE`].
+    pub fn scan(&self, device: &Device, encoder: &mut CommandEncoder, buffer: &Buffer, count: u32) {
+        if count == 0 {
+            return;
+        }
+        let max_per_dimension = device.limits().max_compute_workgroups_per_dimension;
+        let block_count = (count + self.workgroup_size - 1) / self.workgroup_size;
+        let block_sums = device.create_buffer(&BufferDescriptor {
+            label: Some("(wgpu internal) PrefixSum block sums"),
+            size: block_count as u64 * 4,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let params = uniform_buffer(
+            device,
+            "(wgpu internal) PrefixSum params",
+            &[count, block_count],
+        );
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("(wgpu internal) PrefixSum bind group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: block_sums.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: params.as_entire_binding(),
+                },
+            ],
+        });
+
+        {
+            let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+                label: Some("(wgpu internal) PrefixSum scan pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.scan_blocks);
+            pass.set_bind_group(0, &bind_group, &[]);
+            dispatch_blocks(&mut pass, block_count, max_per_dimension);
+        }
+
+        if block_count > 1 {
+            self.scan(device, encoder, &block_sums, block_count);
+
+            let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+                label: Some("(wgpu internal) PrefixSum add pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.add_block_sums);
+            pass.set_bind_group(0, &bind_group, &[]);
+            dispatch_blocks(&mut pass, block_count, max_per_dimension);
+        }
+    }
+}
+
+/// Sorts `u32` keys and their `u32` values on the GPU.
+///
+/// This is a stable least significant digit radix sort, sorting 4 bits per pass,
+/// for things like depth sorting particles or building BVHs from Morton codes.
+#[derive(Debug)]
+pub struct RadixSort {
+    prefix_sum: PrefixSum,
+    bind_group_layout: BindGroupLayout,
+    histogram: ComputePipeline,
+    scatter: ComputePipeline,
+}
+
+impl RadixSort {
+    /// Creates the compute pipelines, with the largest workgroup size allowed by the
+    /// limits of `device`.
+    pub fn new(device: &Device) -> Self {
+        Self::with_workgroup_size(device, select_workgroup_size(&device.limits(), 4))
+    }
+
+    /// Creates the compute pipelines with the given workgroup size, which may be tuned
+    /// for a given adapter.
+    ///
+    /// # Panics
+    ///
+    /// - `workgroup_size` is not a power of two of at least 16, or exceeds the device limits.
+    pub fn with_workgroup_size(device: &Device, workgroup_size: u32) -> Self {
+        let prefix_sum = PrefixSum::with_workgroup_size(device, workgroup_size);
+        let module = create_module(
+            device,
+            "(wgpu internal) RadixSort shader",
+            include_str!("radix_sort.wgsl"),
+            workgroup_size,
+        );
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("(wgpu internal) RadixSort bind group layout"),
+            entries: &[
+                storage_buffer_entry(0, true),
+                storage_buffer_entry(1, true),
+                storage_buffer_entry(2, false),
+                storage_buffer_entry(3, false),
+                storage_buffer_entry(4, false),
+                uniform_buffer_entry(5),
+            ],
+        });
+        let [histogram, scatter] = create_pipelines(
+            device,
+            "(wgpu internal) RadixSort pipeline",
+            &module,
+            &bind_group_layout,
+            ["histogram", "scatter"],
+        );
+        Self {
+            prefix_sum,
+            bind_group_layout,
+            histogram,
+            scatter,
+        }
+    }
+
+    /// Returns the number of elements processed by each workgroup.
+    pub fn workgroup_size(&self) -> u32 {
+        self.prefix_sum.workgroup_size
+    }
+
+    /// Records commands sorting the first `count` elements of `keys` in ascending order,
+    /// applying the same permutation to `values`.
+    ///
+    /// Only the lowest `key_bits` bits of the keys are compared, which saves passes when
+    /// the keys are known to be small. Elements with equal keys keep their order.
+    ///
+    /// Both buffers must have been created with [`BufferUsages::STORAGE`]. When `key_bits`
+    /// needs an odd number of 4-bit passes, they also need [`BufferUsages::COPY_DST`].
+    ///
+    /// # Panics
+    ///
+    /// - `key_bits` is 0 or more than 32.
+    pub fn sort(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        keys: &Buffer,
+        values: &Buffer,
+        count: u32,
+        key_bits: u32,
+    ) {
+        assert!(
+            (1..=32).contains(&key_bits),
+            "cannot sort keys of {key_bits} bits"
+        );
+        if count < 2 {
+            return;
+        }
+        let max_per_dimension = device.limits().max_compute_workgroups_per_dimension;
+        let workgroup_size = self.workgroup_size();
+        let block_count = (count + workgroup_size - 1) / workgroup_size;
+        let pass_count = (key_bits + RADIX_BITS - 1) / RADIX_BITS;
+
+        let temp_buffer = |label| {
+            device.create_buffer(&BufferDescriptor {
+                label: Some(label),
+                size: count as u64 * 4,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            })
+        };
+        let temp_keys = temp_buffer("(wgpu internal) RadixSort keys");
+        let temp_values = temp_buffer("(wgpu internal) RadixSort values");
+        let histogram_count = RADIX * block_count;
+        let histograms = device.create_buffer(&BufferDescriptor {
+            label: Some("(wgpu internal) RadixSort histograms"),
+            size: histogram_count as u64 * 4,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        for pass_index in 0..pass_count {
+            let (keys_in, values_in, keys_out, values_out) = if pass_index % 2 == 0 {
+                (keys, values, &temp_keys, &temp_values)
+            } else {
+                (&temp_keys, &temp_values, keys, values)
+            };
+            let params = uniform_buffer(
+                device,
+                "(wgpu internal) RadixSort params",
+                &[count, block_count, pass_index * RADIX_BITS],
+            );
+            let bind_group = device.create_bind_group(&BindGroupDescriptor {
+                label: Some("(wgpu internal) RadixSort bind group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: keys_in.as_entire_binding(),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: values_in.as_entire_binding(),
+                    },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: keys_out.as_entire_binding(),
+                    },
+                    BindGroupEntry {
+                        binding: 3,
+                        resource: values_out.as_entire_binding(),
+                    },
+                    BindGroupEntry {
+                        binding: 4,
+                        resource: histograms.as_entire_binding(),
+                    },
+                    BindGroupEntry {
+                        binding: 5,
+                        resource: params.as_entire_binding(),
+                    },
+                ],
+            });
+
+            {
+                let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("(wgpu internal) RadixSort histogram pass"),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(&self.histogram);
+                pass.set_bind_group(0, &bind_group, &[]);
+                dispatch_blocks(&mut pass, block_count, max_per_dimension);
+            }
+            self.prefix_sum
+                .scan(device, encoder, &histograms, histogram_count);
+            {
+                let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("(wgpu internal) RadixSort scatter pass"),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(&self.scatter);
+                pass.set_bind_group(0, &bind_group, &[]);
+                dispatch_blocks(&mut pass, block_count, max_per_dimension);
+            }
+        }
+
+        if pass_count % 2 == 1 {
+            let size = count as u64 * 4;
+            encoder.copy_buffer_to_buffer(&temp_keys, 0, keys, 0, size);
+            encoder.copy_buffer_to_buffer(&temp_values, 0, values, 0, size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workgroup_size_selection() {
+        let limits = Limits::default();
+        assert_eq!(select_workgroup_size(&limits, 4), 256);
+
+        let limits = Limits {
+            max_compute_invocations_per_workgroup: 128,
+            ..Limits::default()
+        };
+        assert_eq!(select_workgroup_size(&limits, 4), 128);
+
+        let limits = Limits {
+            max_compute_workgroup_storage_size: 1024,
+            ..Limits::default()
+        };
+        assert_eq!(select_workgroup_size(&limits, 8), 128);
+    }
+}
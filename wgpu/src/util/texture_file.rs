@@ -0,0 +1,235 @@
+use std::{borrow::Cow, error, fmt};
+
+use crate::{
+    util::{DeviceExt, TextureDataOrder},
+    Adapter, Device, Extent3d, Features, Label, Queue, Texture, TextureDescriptor,
+    TextureDimension, TextureFormat, TextureUsages,
+};
+
+/// Error produced when parsing a texture container file or creating a texture from one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TextureFileError {
+    /// The file does not start with the magic identifier of the expected container.
+    InvalidMagic,
+    /// The file ended before all headers or image data could be read.
+    UnexpectedEof,
+    /// A header field has a value that is not allowed by the container specification.
+    InvalidHeader(&'static str),
+    /// The pixel format stored in the file has no `wgpu` equivalent.
+    UnsupportedFormat(String),
+    /// The file uses a supercompression scheme that the caller did not provide a decoder for.
+    UnsupportedSupercompression(u32),
+    /// Decoding a supercompressed mip level failed.
+    Supercompression(String),
+    /// The amount of image data in the file does not match the size implied by its header.
+    DataSizeMismatch {
+        /// Number of bytes required by the described texture.
+        expected: usize,
+        /// Number of bytes present in the file.
+        actual: usize,
+    },
+    /// The texture format requires features that are not available.
+    MissingFeatures {
+        /// Format of the texture.
+        format: TextureFormat,
+        /// Features needed to use `format`, which are not available.
+        missing: Features,
+    },
+    /// The adapter does not allow the texture format to be sampled and uploaded to.
+    UnsupportedUsages {
+        /// Format of the texture.
+        format: TextureFormat,
+        /// Usages needed to upload and sample the texture, which are not allowed.
+        missing: TextureUsages,
+    },
+}
+
+impl fmt::Display for TextureFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::InvalidMagic => write!(f, "File identifier is invalid"),
+            Self::UnexpectedEof => write!(f, "Unexpected end of file"),
+            Self::InvalidHeader(reason) => write!(f, "Invalid header: {reason}"),
+            Self::UnsupportedFormat(ref format) => {
+                write!(f, "Pixel format {format} is not supported")
+            }
+            Self::UnsupportedSupercompression(scheme) => {
+                write!(f, "Supercompression scheme {scheme} is not supported")
+            }
+            Self::Supercompression(ref reason) => {
+                write!(f, "Failed to decode supercompressed data: {reason}")
+            }
+            Self::DataSizeMismatch { expected, actual } => write!(
+                f,
+                "Image data is {actual} bytes, but the header describes {expected} bytes"
+            ),
+            Self::MissingFeatures { format, missing } => {
+                write!(f, "Format {format:?} requires missing features {missing:?}")
+            }
+            Self::UnsupportedUsages { format, missing } => write!(
+                f,
+                "Format {format:?} does not support the usages {missing:?} on this adapter"
+            ),
+        }
+    }
+}
+
+impl error::Error for TextureFileError {}
+
+/// A texture parsed from a container file, with all of its mip levels and layers.
+///
+/// Created with [`parse_ktx2`](crate::util::parse_ktx2) or [`parse_dds`](crate::util::parse_dds).
+#[derive(Clone, Debug)]
+pub struct TextureFile<'a> {
+    /// Size of mip level 0. For cube maps, `depth_or_array_layers` counts every face.
+    pub size: Extent3d,
+    /// Number of mip levels stored in the file.
+    pub mip_level_count: u32,
+    /// Dimensionality of the texture.
+    pub dimension: TextureDimension,
+    /// Format of the texel data.
+    pub format: TextureFormat,
+    /// Whether the array layers of the texture are the faces of one or more cube maps.
+    pub is_cube: bool,
+    /// Order in which mip levels and layers are laid out in [`TextureFile::data`].
+    pub order: TextureDataOrder,
+    /// Tightly packed texel data of every mip level and layer.
+    pub data: Cow<'a, [u8]>,
+}
+
+impl<'a> TextureFile<'a> {
+    /// Returns a descriptor for a texture that can hold the contents of this file.
+    pub fn descriptor<'b>(&self, label: Label<'b>, usage: TextureUsages) -> TextureDescriptor<'b> {
+        TextureDescriptor {
+            label,
+            size: self.size,
+            mip_level_count: self.mip_level_count,
+            sample_count: 1,
+            dimension: self.dimension,
+            format: self.format,
+            usage,
+            view_formats: &[],
+        }
+    }
+
+    /// Checks that `adapter` is able to upload and sample a texture of this file's format.
+    ///
+    /// Note that the features reported here must still be requested when creating the device.
+    pub fn check_adapter_support(&self, adapter: &Adapter) -> Result<(), TextureFileError> {
+        let missing = self.format.required_features() - adapter.features();
+        if !missing.is_empty() {
+            return Err(TextureFileError::MissingFeatures {
+                format: self.format,
+                missing,
+            });
+        }
+
+        let required = TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING;
+        let missing = required
+            - adapter
+                .get_texture_format_features(self.format)
+                .allowed_usages;
+        if !missing.is_empty() {
+            return Err(TextureFileError::UnsupportedUsages {
+                format: self.format,
+                missing,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Creates a texture with every mip level and layer of this file uploaded to it.
+    ///
+    /// Returns an error instead of creating the texture if the format requires features
+    /// which were not enabled on `device`.
+    ///
+    /// See [`DeviceExt::create_texture_with_data`] for how `usage` is treated.
+    pub fn create_texture(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        label: Label<'_>,
+        usage: TextureUsages,
+    ) -> Result<Texture, TextureFileError> {
+        let missing = self.format.required_features() - device.features();
+        if !missing.is_empty() {
+            return Err(TextureFileError::MissingFeatures {
+                format: self.format,
+                missing,
+            });
+        }
+
+        let desc = self.descriptor(label, usage);
+        Ok(device.create_texture_with_data(queue, &desc, self.order, &self.data))
+    }
+
+    /// Checks that the amount of data matches what the header describes.
+    pub(super) fn validate_data_size(&self) -> Result<(), TextureFileError> {
+        let expected = self.expected_data_size()?;
+        if expected != self.data.len() {
+            return Err(TextureFileError::DataSizeMismatch {
+                expected,
+                actual: self.data.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn expected_data_size(&self) -> Result<usize, TextureFileError> {
+        let desc = self.descriptor(None, TextureUsages::empty());
+        if self.mip_level_count == 0 || self.mip_level_count > self.size.max_mips(self.dimension) {
+            return Err(TextureFileError::InvalidHeader("invalid mip level count"));
+        }
+        let block_size = self
+            .format
+            .block_copy_size(None)
+            .ok_or_else(|| TextureFileError::UnsupportedFormat(format!("{:?}", self.format)))?;
+        let (block_width, block_height) = self.format.block_dimensions();
+
+        let mut total = 0usize;
+        for mip in 0..self.mip_level_count {
+            let physical = desc
+                .mip_level_size(mip)
+                .ok_or(TextureFileError::InvalidHeader("invalid mip level count"))?
+                .physical_size(self.format);
+            total = ((physical.width / block_width) as usize)
+                .checked_mul((physical.height / block_height) as usize)
+                .and_then(|blocks| blocks.checked_mul(physical.depth_or_array_layers as usize))
+                .and_then(|blocks| blocks.checked_mul(block_size as usize))
+                .and_then(|size| total.checked_add(size))
+                .ok_or(TextureFileError::InvalidHeader("texture is too large"))?;
+        }
+        Ok(total)
+    }
+}
+
+/// Little endian reader over the bytes of a container file.
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub(super) fn bytes(&self, offset: usize, len: usize) -> Result<&'a [u8], TextureFileError> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.data.get(offset..end))
+            .ok_or(TextureFileError::UnexpectedEof)
+    }
+
+    pub(super) fn u32(&self, offset: usize) -> Result<u32, TextureFileError> {
+        let bytes = self.bytes(offset, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub(super) fn u64(&self, offset: usize) -> Result<u64, TextureFileError> {
+        let low = self.u32(offset)? as u64;
+        let high = self.u32(offset + 4)? as u64;
+        Ok(low | (high << 32))
+    }
+}
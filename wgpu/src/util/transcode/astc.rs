@@ -0,0 +1,634 @@
+//! Decoder for 2D ASTC blocks using the LDR profile.
+//!
+//! Texels are written in row-major order. Blocks which are invalid, or which use HDR
+//! endpoints, decode to the error color as required by the specification.
+
+const ERROR_COLOR: [u8; 4] = [255, 0, 255, 255];
+
+/// Quantization levels of weights, indexed by the range bits of the block mode.
+const WEIGHT_LEVELS: [u32; 12] = [2, 3, 4, 5, 6, 8, 10, 12, 16, 20, 24, 32];
+
+/// Quantization levels of color endpoints, from which the largest that fits is used.
+const COLOR_LEVELS: [u32; 21] = [
+    2, 3, 4, 5, 6, 8, 10, 12, 16, 20, 24, 32, 40, 48, 64, 80, 96, 128, 160, 192, 256,
+];
+
+/// How values of a quantization level are stored in the integer sequence encoding.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Bits,
+    Trits,
+    Quints,
+}
+
+/// Returns the encoding and the number of plain bits per value of `levels`.
+fn ise_params(levels: u32) -> (Encoding, u32) {
+    match levels {
+        3 => (Encoding::Trits, 0),
+        5 => (Encoding::Quints, 0),
+        6 | 12 | 24 | 48 | 96 | 192 => (Encoding::Trits, (levels / 3).trailing_zeros()),
+        10 | 20 | 40 | 80 | 160 => (Encoding::Quints, (levels / 5).trailing_zeros()),
+        _ => (Encoding::Bits, levels.trailing_zeros()),
+    }
+}
+
+/// Number of bits used by `count` values of `levels`.
+fn ise_bits(count: u32, levels: u32) -> u32 {
+    let (encoding, bits) = ise_params(levels);
+    bits * count
+        + match encoding {
+            Encoding::Bits => 0,
+            Encoding::Trits => (8 * count + 4) / 5,
+            Encoding::Quints => (7 * count + 2) / 3,
+        }
+}
+
+fn read(bits: u128, start: u32, count: u32) -> u32 {
+    if start >= 128 {
+        return 0;
+    }
+    ((bits >> start) & ((1 << count) - 1)) as u32
+}
+
+fn decode_trits(t: u32) -> [u32; 5] {
+    let (c, t3, t4);
+    if (t >> 2) & 7 == 7 {
+        c = ((t >> 5) & 7) << 2 | (t & 3);
+        t3 = 2;
+        t4 = 2;
+    } else {
+        c = t & 0x1F;
+        if (t >> 5) & 3 == 3 {
+            t3 = (t >> 7) & 1;
+            t4 = 2;
+        } else {
+            t3 = (t >> 5) & 3;
+            t4 = (t >> 7) & 1;
+        }
+    }
+    let (t0, t1, t2);
+    if c & 3 == 3 {
+        t0 = ((c >> 3) & 1) << 1 | ((c >> 2) & !(c >> 3) & 1);
+        t1 = (c >> 4) & 1;
+        t2 = 2;
+    } else if (c >> 2) & 3 == 3 {
+        t0 = c & 3;
+        t1 = 2;
+        t2 = 2;
+    } else {
+        t0 = ((c >> 1) & 1) << 1 | (c & !(c >> 1) & 1);
+        t1 = (c >> 2) & 3;
+        t2 = (c >> 4) & 1;
+    }
+    [t0, t1, t2, t3, t4]
+}
+
+fn decode_quints(q: u32) -> [u32; 3] {
+    if (q >> 1) & 3 == 3 && (q >> 5) & 3 == 0 {
+        let q2 = (q & 1) << 2 | ((q >> 4) & !q & 1) << 1 | ((q >> 3) & !q & 1);
+        return [4, 4, q2];
+    }
+    let (c, q2);
+    if (q >> 1) & 3 == 3 {
+        c = ((q >> 3) & 3) << 3 | (!(q >> 5) & 3) << 1 | (q & 1);
+        q2 = 4;
+    } else {
+        c = q & 0x1F;
+        q2 = (q >> 5) & 3;
+    }
+    if c & 7 == 5 {
+        [(c >> 3) & 3, 4, q2]
+    } else {
+        [c & 7, (c >> 3) & 3, q2]
+    }
+}
+
+/// Decodes an integer sequence of `out.len()` values of `levels`, starting at bit 0 of `bits`.
+fn decode_ise(bits: u128, levels: u32, out: &mut [u32]) {
+    let length = ise_bits(out.len() as u32, levels);
+    let bits = if length >= 128 {
+        bits
+    } else {
+        bits & ((1 << length) - 1)
+    };
+    let (encoding, n) = ise_params(levels);
+    let mut position = 0;
+    let mut next = |count: u32| {
+        let value = read(bits, position, count);
+        position += count;
+        value
+    };
+    match encoding {
+        Encoding::Bits => {
+            for value in out.iter_mut() {
+                *value = next(n);
+            }
+        }
+        Encoding::Trits => {
+            for chunk in out.chunks_mut(5) {
+                let mut m = [0; 5];
+                let mut t = 0;
+                for (i, (shift, count)) in [(0, 2), (2, 2), (4, 1), (5, 2), (7, 1)]
+                    .into_iter()
+                    .enumerate()
+                {
+                    m[i] = next(n);
+                    t |= next(count) << shift;
+                }
+                let trits = decode_trits(t);
+                for (i, value) in chunk.iter_mut().enumerate() {
+                    *value = (trits[i] << n) | m[i];
+                }
+            }
+        }
+        Encoding::Quints => {
+            for chunk in out.chunks_mut(3) {
+                let mut m = [0; 3];
+                let mut q = 0;
+                for (i, (shift, count)) in [(0, 3), (3, 2), (5, 2)].into_iter().enumerate() {
+                    m[i] = next(n);
+                    q |= next(count) << shift;
+                }
+                let quints = decode_quints(q);
+                for (i, value) in chunk.iter_mut().enumerate() {
+                    *value = (quints[i] << n) | m[i];
+                }
+            }
+        }
+    }
+}
+
+/// Repeats the `bits` low bits of `value` to fill `target` bits.
+fn replicate(value: u32, bits: u32, target: u32) -> u32 {
+    if bits == 0 {
+        return 0;
+    }
+    let mut result = 0;
+    let mut filled = 0;
+    while filled < target {
+        result = (result << bits) | value;
+        filled += bits;
+    }
+    result >> (filled - target)
+}
+
+/// Unquantizes a color endpoint value to 8 bits.
+fn unquantize_color(value: u32, levels: u32) -> u32 {
+    let (encoding, n) = ise_params(levels);
+    if encoding == Encoding::Bits {
+        return replicate(value, n, 8);
+    }
+    let d = value >> n;
+    let x = (value & ((1 << n) - 1)) >> 1;
+    let a = if value & 1 != 0 { 0x1FF } else { 0 };
+    let (b, c) = match (encoding, n) {
+        (Encoding::Trits, 1) => (0, 204),
+        (Encoding::Trits, 2) => ((x << 8) | (x << 4) | (x << 2) | (x << 1), 93),
+        (Encoding::Trits, 3) => ((x << 7) | (x << 2) | x, 44),
+        (Encoding::Trits, 4) => ((x << 6) | x, 22),
+        (Encoding::Trits, 5) => ((x << 5) | (x >> 2), 11),
+        (Encoding::Trits, _) => ((x << 4) | (x >> 4), 5),
+        (_, 1) => (0, 113),
+        (_, 2) => ((x << 8) | (x << 3) | (x << 2), 54),
+        (_, 3) => ((x << 7) | (x << 1) | (x >> 1), 26),
+        (_, 4) => ((x << 6) | (x >> 1), 13),
+        _ => ((x << 5) | (x >> 3), 6),
+    };
+    let t = (d * c + b) ^ a;
+    (a & 0x80) | (t >> 2)
+}
+
+/// Unquantizes a weight to the range 0 to 64.
+fn unquantize_weight(value: u32, levels: u32) -> u32 {
+    let (encoding, n) = ise_params(levels);
+    let weight = match (encoding, n) {
+        (Encoding::Bits, _) => replicate(value, n, 6),
+        (Encoding::Trits, 0) => [0, 32, 63][value as usize],
+        (Encoding::Quints, 0) => [0, 16, 32, 47, 63][value as usize],
+        _ => {
+            let d = value >> n;
+            let x = (value & ((1 << n) - 1)) >> 1;
+            let a = if value & 1 != 0 { 0x7F } else { 0 };
+            let (b, c) = match (encoding, n) {
+                (Encoding::Trits, 1) => (0, 50),
+                (Encoding::Trits, 2) => ((x << 6) | (x << 2) | x, 23),
+                (Encoding::Trits, _) => ((x << 5) | x, 11),
+                (_, 1) => (0, 28),
+                _ => ((x << 6) | (x << 1), 13),
+            };
+            let t = (d * c + b) ^ a;
+            (a & 0x20) | (t >> 2)
+        }
+    };
+    if weight > 32 {
+        weight + 1
+    } else {
+        weight
+    }
+}
+
+struct BlockMode {
+    width: u32,
+    height: u32,
+    dual_plane: bool,
+    weight_levels: u32,
+}
+
+fn decode_block_mode(mode: u32) -> Option<BlockMode> {
+    let bit = |i: u32| (mode >> i) & 1;
+    let a = (mode >> 5) & 3;
+    let (width, height, range, high_precision, dual_plane);
+    if mode & 3 != 0 {
+        let b = (mode >> 7) & 3;
+        (width, height) = match (mode >> 2) & 3 {
+            0 => (b + 4, a + 2),
+            1 => (b + 8, a + 2),
+            2 => (a + 2, b + 8),
+            _ if bit(8) == 0 => (a + 2, bit(7) + 6),
+            _ => (bit(7) + 2, a + 2),
+        };
+        range = bit(1) << 2 | bit(0) << 1 | bit(4);
+        high_precision = bit(9);
+        dual_plane = bit(10);
+    } else {
+        let b = (mode >> 9) & 3;
+        (width, height, high_precision, dual_plane) = match (mode >> 7) & 3 {
+            0 => (12, a + 2, bit(9), bit(10)),
+            1 => (a + 2, 12, bit(9), bit(10)),
+            2 => (a + 6, b + 6, 0, 0),
+            _ => match a {
+                0 => (6, 10, bit(9), bit(10)),
+                1 => (10, 6, bit(9), bit(10)),
+                _ => return None,
+            },
+        };
+        range = bit(3) << 2 | bit(2) << 1 | bit(4);
+    }
+    if range < 2 {
+        return None;
+    }
+    Some(BlockMode {
+        width,
+        height,
+        dual_plane: dual_plane != 0,
+        weight_levels: WEIGHT_LEVELS[(range - 2 + 6 * high_precision) as usize],
+    })
+}
+
+fn hash52(mut p: u32) -> u32 {
+    p ^= p >> 15;
+    p = p.wrapping_sub(p << 17);
+    p = p.wrapping_add(p << 7);
+    p = p.wrapping_add(p << 4);
+    p ^= p >> 5;
+    p = p.wrapping_add(p << 16);
+    p ^= p >> 7;
+    p ^= p >> 3;
+    p ^= p << 6;
+    p ^= p >> 17;
+    p
+}
+
+/// Selects the partition of texel (`x`, `y`) for the partition pattern `seed`.
+fn select_partition(seed: u32, x: u32, y: u32, partitions: u32, small_block: bool) -> usize {
+    let (x, y) = if small_block {
+        (x << 1, y << 1)
+    } else {
+        (x, y)
+    };
+    let seed = seed + (partitions - 1) * 1024;
+    let rnum = hash52(seed);
+    let mut seeds: [u32; 8] = std::array::from_fn(|i| (rnum >> (4 * i)) & 0xF);
+    for value in &mut seeds {
+        *value *= *value;
+    }
+    let (sh1, sh2) = if seed & 1 != 0 {
+        (
+            if seed & 2 != 0 { 4 } else { 5 },
+            if partitions == 3 { 6 } else { 5 },
+        )
+    } else {
+        (
+            if partitions == 3 { 6 } else { 5 },
+            if seed & 2 != 0 { 4 } else { 5 },
+        )
+    };
+    for (i, value) in seeds.iter_mut().enumerate() {
+        *value >>= if i % 2 == 0 { sh1 } else { sh2 };
+    }
+
+    // Blocks are 2D, so the terms of the z coordinate are always zero.
+    let a = (seeds[0] * x + seeds[1] * y + (rnum >> 14)) & 0x3F;
+    let b = (seeds[2] * x + seeds[3] * y + (rnum >> 10)) & 0x3F;
+    let c = if partitions >= 3 {
+        (seeds[4] * x + seeds[5] * y + (rnum >> 6)) & 0x3F
+    } else {
+        0
+    };
+    let d = if partitions >= 4 {
+        (seeds[6] * x + seeds[7] * y + (rnum >> 2)) & 0x3F
+    } else {
+        0
+    };
+
+    if a >= b && a >= c && a >= d {
+        0
+    } else if b >= c && b >= d {
+        1
+    } else if c >= d {
+        2
+    } else {
+        3
+    }
+}
+
+fn bit_transfer_signed(a: i32, b: i32) -> (i32, i32) {
+    let b = (b >> 1) | (a & 0x80);
+    let a = (a >> 1) & 0x3F;
+    (if a & 0x20 != 0 { a - 0x40 } else { a }, b)
+}
+
+fn blue_contract(color: [i32; 4]) -> [i32; 4] {
+    [
+        (color[0] + color[2]) >> 1,
+        (color[1] + color[2]) >> 1,
+        color[2],
+        color[3],
+    ]
+}
+
+/// Decodes the endpoints of an LDR color endpoint mode, or `None` for HDR modes.
+fn decode_endpoints(mode: u32, values: &[u32]) -> Option<[[i32; 4]; 2]> {
+    let mut v = [0i32; 8];
+    for (v, &value) in v.iter_mut().zip(values) {
+        *v = value as i32;
+    }
+    let endpoints = match mode {
+        // Luminance.
+        0 => [[v[0], v[0], v[0], 255], [v[1], v[1], v[1], 255]],
+        1 => {
+            let l0 = (v[0] >> 2) | (v[1] & 0xC0);
+            let l1 = (l0 + (v[1] & 0x3F)).min(255);
+            [[l0, l0, l0, 255], [l1, l1, l1, 255]]
+        }
+        // Luminance and alpha.
+        4 => [[v[0], v[0], v[0], v[2]], [v[1], v[1], v[1], v[3]]],
+        5 => {
+            let (v1, v0) = bit_transfer_signed(v[1], v[0]);
+            let (v3, v2) = bit_transfer_signed(v[3], v[2]);
+            let l1 = v0 + v1;
+            [[v0, v0, v0, v2], [l1, l1, l1, v2 + v3]]
+        }
+        // RGB and RGBA with a scale.
+        6 | 10 => {
+            let (a0, a1) = if mode == 10 { (v[4], v[5]) } else { (255, 255) };
+            [
+                [
+                    (v[0] * v[3]) >> 8,
+                    (v[1] * v[3]) >> 8,
+                    (v[2] * v[3]) >> 8,
+                    a0,
+                ],
+                [v[0], v[1], v[2], a1],
+            ]
+        }
+        // RGB and RGBA.
+        8 | 12 => {
+            let (a0, a1) = if mode == 12 { (v[6], v[7]) } else { (255, 255) };
+            let e0 = [v[0], v[2], v[4], a0];
+            let e1 = [v[1], v[3], v[5], a1];
+            if v[1] + v[3] + v[5] >= v[0] + v[2] + v[4] {
+                [e0, e1]
+            } else {
+                [blue_contract(e1), blue_contract(e0)]
+            }
+        }
+        // RGB and RGBA with a base and an offset.
+        9 | 13 => {
+            let mut base = [0; 4];
+            let mut offset = [0; 4];
+            for i in 0..4 {
+                (offset[i], base[i]) = bit_transfer_signed(v[2 * i + 1], v[2 * i]);
+            }
+            if mode == 9 {
+                (base[3], offset[3]) = (255, 0);
+            }
+            let sum: [i32; 4] = std::array::from_fn(|i| base[i] + offset[i]);
+            if offset[0] + offset[1] + offset[2] >= 0 {
+                [base, sum]
+            } else {
+                [blue_contract(sum), blue_contract(base)]
+            }
+        }
+        _ => return None,
+    };
+    Some(endpoints.map(|endpoint| endpoint.map(|c| c.clamp(0, 255))))
+}
+
+/// Decodes a block of `width` by `height` texels.
+pub(super) fn decode_block(block: &[u8], width: u32, height: u32, srgb: bool, out: &mut [[u8; 4]]) {
+    let bits = u128::from_le_bytes(block.try_into().unwrap());
+    if decode(bits, width, height, srgb, out).is_none() {
+        out.fill(ERROR_COLOR);
+    }
+}
+
+fn decode(bits: u128, width: u32, height: u32, srgb: bool, out: &mut [[u8; 4]]) -> Option<()> {
+    let mode = read(bits, 0, 11);
+    if mode & 0x1FF == 0x1FC {
+        // Void extent blocks store a constant color, as UNORM16 in the LDR profile.
+        if mode & 0x200 != 0 {
+            return None;
+        }
+        let color: [u8; 4] =
+            std::array::from_fn(|i| (read(bits, 64 + 16 * i as u32, 16) >> 8) as u8);
+        out.fill(color);
+        return Some(());
+    }
+
+    let block_mode = decode_block_mode(mode)?;
+    let planes = if block_mode.dual_plane { 2 } else { 1 };
+    let weight_count = block_mode.width * block_mode.height * planes;
+    let weight_bits = ise_bits(weight_count, block_mode.weight_levels);
+    if block_mode.width > width
+        || block_mode.height > height
+        || weight_count > 64
+        || !(24..=96).contains(&weight_bits)
+    {
+        return None;
+    }
+
+    let partitions = read(bits, 11, 2) + 1;
+    if partitions == 4 && block_mode.dual_plane {
+        return None;
+    }
+
+    let mut below_weights = 128 - weight_bits;
+    let mut modes = [0; 4];
+    let color_start;
+    if partitions == 1 {
+        modes[0] = read(bits, 13, 4);
+        color_start = 17;
+    } else {
+        let mut encoded = read(bits, 23, 6);
+        if encoded & 3 == 0 {
+            modes = [encoded >> 2; 4];
+        } else {
+            // The remaining bits of the mode selectors are stored below the weights.
+            let extra = 3 * partitions - 4;
+            below_weights -= extra;
+            encoded |= read(bits, below_weights, extra) << 6;
+            let class = (encoded & 3) - 1;
+            for (i, mode) in modes[..partitions as usize].iter_mut().enumerate() {
+                let i = i as u32;
+                let class_offset = (encoded >> (2 + i)) & 1;
+                let low = (encoded >> (2 + partitions + 2 * i)) & 3;
+                *mode = (class + class_offset) << 2 | low;
+            }
+        }
+        color_start = 29;
+    }
+    let plane2_component = if block_mode.dual_plane {
+        below_weights -= 2;
+        Some(read(bits, below_weights, 2) as usize)
+    } else {
+        None
+    };
+
+    let modes = &modes[..partitions as usize];
+    let value_count: u32 = modes.iter().map(|mode| ((mode >> 2) + 1) * 2).sum();
+    if value_count > 18 || below_weights < color_start {
+        return None;
+    }
+    let color_bits = below_weights - color_start;
+    let color_levels = *COLOR_LEVELS
+        .iter()
+        .rev()
+        .find(|&&levels| ise_bits(value_count, levels) <= color_bits)?;
+    let mut values = [0; 18];
+    let values = &mut values[..value_count as usize];
+    decode_ise(bits >> color_start, color_levels, values);
+
+    let mut endpoints = [[[0; 4]; 2]; 4];
+    let mut offset = 0;
+    for (endpoints, &mode) in endpoints.iter_mut().zip(modes) {
+        let count = ((mode >> 2) as usize + 1) * 2;
+        let mut unquantized = [0; 8];
+        for (unquantized, &value) in unquantized.iter_mut().zip(&values[offset..offset + count]) {
+            *unquantized = unquantize_color(value, color_levels);
+        }
+        *endpoints = decode_endpoints(mode, &unquantized)?;
+        offset += count;
+    }
+
+    let mut weights = [0; 64];
+    let weights = &mut weights[..weight_count as usize];
+    decode_ise(bits.reverse_bits(), block_mode.weight_levels, weights);
+    for weight in weights.iter_mut() {
+        *weight = unquantize_weight(*weight, block_mode.weight_levels);
+    }
+
+    let partition_seed = read(bits, 13, 10);
+    let small_block = width * height < 31;
+    // Scale factors mapping texel coordinates to the weight grid, in 1/1024ths.
+    let scale_x = (1024 + width / 2) / (width - 1);
+    let scale_y = (1024 + height / 2) / (height - 1);
+    for y in 0..height {
+        for x in 0..width {
+            let partition = if partitions > 1 {
+                select_partition(partition_seed, x, y, partitions, small_block)
+            } else {
+                0
+            };
+
+            let grid_x = (scale_x * x * (block_mode.width - 1) + 32) >> 6;
+            let grid_y = (scale_y * y * (block_mode.height - 1) + 32) >> 6;
+            let (fx, fy) = (grid_x & 0xF, grid_y & 0xF);
+            let (x0, y0) = (grid_x >> 4, grid_y >> 4);
+            let (x1, y1) = (
+                (x0 + 1).min(block_mode.width - 1),
+                (y0 + 1).min(block_mode.height - 1),
+            );
+            let w11 = (fx * fy + 8) >> 4;
+            let factors = [16 - fx - fy + w11, fx - w11, fy - w11, w11];
+            let grid = [
+                y0 * block_mode.width + x0,
+                y0 * block_mode.width + x1,
+                y1 * block_mode.width + x0,
+                y1 * block_mode.width + x1,
+            ];
+            let weight = |plane: u32| {
+                let sum: u32 = grid
+                    .iter()
+                    .zip(factors)
+                    .map(|(&i, factor)| weights[(i * planes + plane) as usize] * factor)
+                    .sum();
+                (sum + 8) >> 4
+            };
+            let (weight0, weight1) = (weight(0), weight(planes - 1));
+
+            let [e0, e1] = endpoints[partition];
+            let texel = &mut out[(y * width + x) as usize];
+            for channel in 0..4 {
+                let weight = if plane2_component == Some(channel) {
+                    weight1
+                } else {
+                    weight0
+                };
+                let expand = |value: i32| {
+                    let value = value as u32;
+                    if srgb && channel < 3 {
+                        (value << 8) | 0x80
+                    } else {
+                        (value << 8) | value
+                    }
+                };
+                let value =
+                    (expand(e0[channel]) * (64 - weight) + expand(e1[channel]) * weight + 32) >> 6;
+                texel[channel] = (value >> 8) as u8;
+            }
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn void_extent() {
+        // Green, blue and alpha are one, red is zero.
+        let value = 0x1FCu128 | (0xFFF << 12) | (0xFFFF_FFFF_FFFF << 80);
+        let mut out = [[0; 4]; 16];
+        decode_block(&value.to_le_bytes(), 4, 4, false, &mut out);
+        assert!(out.iter().all(|&texel| texel == [0, 255, 255, 255]));
+    }
+
+    #[test]
+    fn luminance_gradient() {
+        // A 4x4 weight grid of 2 bit weights with luminance endpoints 0 and 255.
+        let mut value = 0x42u128 | (0xFF << 25);
+        for i in 0..16 {
+            let weight = (i % 4) as u128;
+            // Weights are stored bit reversed from the top of the block.
+            let reversed = ((weight & 1) << 1) | (weight >> 1);
+            value |= reversed << (126 - 2 * i);
+        }
+        let mut out = [[0; 4]; 16];
+        decode_block(&value.to_le_bytes(), 4, 4, false, &mut out);
+        for (i, texel) in out.iter().enumerate() {
+            let l = [0, 84, 171, 255][i % 4];
+            assert_eq!(*texel, [l, l, l, 255]);
+        }
+    }
+
+    #[test]
+    fn integer_sequence_blocks() {
+        // Every combination of trits and quints has an encoding.
+        let trits: std::collections::HashSet<_> = (0..256).map(decode_trits).collect();
+        assert_eq!(trits.len(), 3usize.pow(5));
+        let quints: std::collections::HashSet<_> = (0..128).map(decode_quints).collect();
+        assert_eq!(quints.len(), 5usize.pow(3));
+    }
+}
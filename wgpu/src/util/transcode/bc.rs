@@ -0,0 +1,1189 @@
+//! BC1 to BC7 block decoders, and a BC7 encoder for re-compressing decoded blocks.
+//!
+//! Every block covers 4x4 texels, which are written in row-major order.
+
+/// Interpolation weights of BC6H and BC7 indices, by index precision.
+const WEIGHTS_2: [u32; 4] = [0, 21, 43, 64];
+const WEIGHTS_3: [u32; 8] = [0, 9, 18, 27, 37, 46, 55, 64];
+const WEIGHTS_4: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+/// Two subset partitions, one bit per texel selecting the subset.
+const PARTITIONS_2: [u16; 64] = [
+    0xCCCC, 0x8888, 0xEEEE, 0xECC8, 0xC880, 0xFEEC, 0xFEC8, 0xEC80, 0xC800, 0xFFEC, 0xFE80, 0xE800,
+    0xFFE8, 0xFF00, 0xFFF0, 0xF000, 0xF710, 0x008E, 0x7100, 0x08CE, 0x008C, 0x7310, 0x3100, 0x8CCE,
+    0x088C, 0x3110, 0x6666, 0x366C, 0x17E8, 0x0FF0, 0x718E, 0x399C, 0xAAAA, 0xF0F0, 0x5A5A, 0x33CC,
+    0x3C3C, 0x55AA, 0x9696, 0xA55A, 0x73CE, 0x13C8, 0x324C, 0x3BDC, 0x6996, 0xC33C, 0x9966, 0x0660,
+    0x0272, 0x04E4, 0x4E40, 0x2720, 0xC936, 0x936C, 0x39C6, 0x639C, 0x9336, 0x9CC6, 0x817E, 0xE718,
+    0xCCF0, 0x0FCC, 0x7744, 0xEE22,
+];
+
+/// Three subset partitions, two bits per texel selecting the subset.
+const PARTITIONS_3: [u32; 64] = [
+    0xAA685050, 0x6A5A5040, 0x5A5A4200, 0x5450A0A8, 0xA5A50000, 0xA0A05050, 0x5555A0A0, 0x5A5A5050,
+    0xAA550000, 0xAA555500, 0xAAAA5500, 0x90909090, 0x94949494, 0xA4A4A4A4, 0xA9A59450, 0x2A0A4250,
+    0xA5945040, 0x0A425054, 0xA5A5A500, 0x55A0A0A0, 0xA8A85454, 0x6A6A4040, 0xA4A45000, 0x1A1A0500,
+    0x0050A4A4, 0xAAA59090, 0x14696914, 0x69691400, 0xA08585A0, 0xAA821414, 0x50A4A450, 0x6A5A0200,
+    0xA9A58000, 0x5090A0A8, 0xA8A09050, 0x24242424, 0x00AA5500, 0x24924924, 0x24499224, 0x50A50A50,
+    0x500AA550, 0xAAAA4444, 0x66660000, 0xA5A0A5A0, 0x50A050A0, 0x69286928, 0x44AAAA44, 0x66666600,
+    0xAA444444, 0x54A854A8, 0x95809580, 0x96969600, 0xA85454A8, 0x80959580, 0xAA141414, 0x96960000,
+    0xAAAA1414, 0xA05050A0, 0xA0A5A5A0, 0x96000000, 0x40804080, 0xA9A8A9A8, 0xAAAAAA44, 0x2A4A5254,
+];
+
+/// Texel whose index has an implicit zero high bit, for the second subset of two subset partitions.
+const ANCHORS_2: [u8; 64] = [
+    15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 2, 8, 2, 2, 8, 8, 15, 2, 8,
+    2, 2, 8, 8, 2, 2, 15, 15, 6, 8, 2, 8, 15, 15, 2, 8, 2, 2, 2, 15, 15, 6, 6, 2, 6, 8, 15, 15, 2,
+    2, 15, 15, 15, 15, 15, 2, 2, 15,
+];
+
+/// Anchor texels of the second and third subsets of three subset partitions.
+const ANCHORS_3: [[u8; 2]; 64] = [
+    [3, 15],
+    [3, 8],
+    [15, 8],
+    [15, 3],
+    [8, 15],
+    [3, 15],
+    [15, 3],
+    [15, 8],
+    [8, 15],
+    [8, 15],
+    [6, 15],
+    [6, 15],
+    [6, 15],
+    [5, 15],
+    [3, 15],
+    [3, 8],
+    [3, 15],
+    [3, 8],
+    [8, 15],
+    [15, 3],
+    [3, 15],
+    [3, 8],
+    [6, 15],
+    [10, 8],
+    [5, 3],
+    [8, 15],
+    [8, 6],
+    [6, 10],
+    [8, 15],
+    [5, 15],
+    [15, 10],
+    [15, 8],
+    [8, 15],
+    [15, 3],
+    [3, 15],
+    [5, 10],
+    [6, 10],
+    [10, 8],
+    [8, 9],
+    [15, 10],
+    [15, 6],
+    [3, 15],
+    [15, 8],
+    [5, 15],
+    [15, 3],
+    [15, 6],
+    [15, 6],
+    [15, 8],
+    [3, 15],
+    [15, 3],
+    [5, 15],
+    [5, 15],
+    [5, 15],
+    [8, 15],
+    [5, 15],
+    [10, 15],
+    [5, 15],
+    [10, 15],
+    [8, 15],
+    [13, 15],
+    [15, 3],
+    [12, 15],
+    [3, 15],
+    [3, 8],
+];
+
+/// Little endian bit reader over a 128 bit block.
+struct Bits {
+    value: u128,
+    position: u32,
+}
+
+impl Bits {
+    fn new(block: &[u8]) -> Self {
+        let mut bytes = [0; 16];
+        bytes[..block.len()].copy_from_slice(block);
+        Self {
+            value: u128::from_le_bytes(bytes),
+            position: 0,
+        }
+    }
+
+    fn read(&mut self, count: u32) -> u32 {
+        let value = (self.value >> self.position) & ((1 << count) - 1);
+        self.position += count;
+        value as u32
+    }
+}
+
+fn interpolate(e0: u32, e1: u32, weight: u32) -> u32 {
+    (e0 * (64 - weight) + e1 * weight + 32) >> 6
+}
+
+fn rgb565(color: u16) -> [u32; 3] {
+    let r = (color >> 11) as u32 & 0x1F;
+    let g = (color >> 5) as u32 & 0x3F;
+    let b = color as u32 & 0x1F;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+/// Decodes the color part shared by BC1, BC2 and BC3.
+///
+/// BC2 and BC3 always interpolate four colors, while BC1 switches to three colors and
+/// transparent black if the endpoints are in descending order.
+fn decode_color(block: &[u8], four_colors: bool, out: &mut [[u8; 4]]) {
+    let c0 = u16::from_le_bytes([block[0], block[1]]);
+    let c1 = u16::from_le_bytes([block[2], block[3]]);
+    let (e0, e1) = (rgb565(c0), rgb565(c1));
+
+    let mut palette = [[0, 0, 0, 255]; 4];
+    for channel in 0..3 {
+        palette[0][channel] = e0[channel] as u8;
+        palette[1][channel] = e1[channel] as u8;
+        if c0 > c1 || four_colors {
+            palette[2][channel] = ((2 * e0[channel] + e1[channel] + 1) / 3) as u8;
+            palette[3][channel] = ((e0[channel] + 2 * e1[channel] + 1) / 3) as u8;
+        } else {
+            palette[2][channel] = ((e0[channel] + e1[channel] + 1) / 2) as u8;
+        }
+    }
+    if c0 <= c1 && !four_colors {
+        palette[3] = [0; 4];
+    }
+
+    let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+    for (i, texel) in out.iter_mut().enumerate() {
+        *texel = palette[(indices >> (2 * i)) as usize & 3];
+    }
+}
+
+/// Decodes a BC4 style block of 3 bit indices into `channel` of `out`.
+fn decode_unorm_channel(block: &[u8], channel: usize, out: &mut [[u8; 4]]) {
+    let (e0, e1) = (block[0] as u32, block[1] as u32);
+    let mut palette = [e0, e1, 0, 0, 0, 0, 0, 255];
+    if e0 > e1 {
+        for i in 1..7 {
+            palette[i as usize + 1] = ((7 - i) * e0 + i * e1 + 3) / 7;
+        }
+    } else {
+        for i in 1..5 {
+            palette[i as usize + 1] = ((5 - i) * e0 + i * e1 + 2) / 5;
+        }
+    }
+
+    let indices = read_u48(&block[2..8]);
+    for (i, texel) in out.iter_mut().enumerate() {
+        texel[channel] = palette[(indices >> (3 * i)) as usize & 7] as u8;
+    }
+}
+
+/// Signed variant of [`decode_unorm_channel`], storing the `i8` bits of each texel.
+fn decode_snorm_channel(block: &[u8], channel: usize, out: &mut [[u8; 4]]) {
+    let e0 = (block[0] as i8).max(-127) as i32;
+    let e1 = (block[1] as i8).max(-127) as i32;
+    let divide = |value: i32, divisor: i32| (value + value.signum() * (divisor / 2)) / divisor;
+    let mut palette = [e0, e1, 0, 0, 0, 0, -127, 127];
+    if e0 > e1 {
+        for i in 1..7 {
+            palette[i as usize + 1] = divide((7 - i) * e0 + i * e1, 7);
+        }
+    } else {
+        for i in 1..5 {
+            palette[i as usize + 1] = divide((5 - i) * e0 + i * e1, 5);
+        }
+    }
+
+    let indices = read_u48(&block[2..8]);
+    for (i, texel) in out.iter_mut().enumerate() {
+        texel[channel] = palette[(indices >> (3 * i)) as usize & 7] as i8 as u8;
+    }
+}
+
+fn read_u48(bytes: &[u8]) -> u64 {
+    let mut value = [0; 8];
+    value[..6].copy_from_slice(bytes);
+    u64::from_le_bytes(value)
+}
+
+pub(super) fn decode_bc1(block: &[u8], out: &mut [[u8; 4]]) {
+    decode_color(block, false, out);
+}
+
+pub(super) fn decode_bc2(block: &[u8], out: &mut [[u8; 4]]) {
+    decode_color(&block[8..], true, out);
+    let alpha = u64::from_le_bytes(block[..8].try_into().unwrap());
+    for (i, texel) in out.iter_mut().enumerate() {
+        texel[3] = ((alpha >> (4 * i)) as u8 & 0xF) * 17;
+    }
+}
+
+pub(super) fn decode_bc3(block: &[u8], out: &mut [[u8; 4]]) {
+    decode_color(&block[8..], true, out);
+    decode_unorm_channel(block, 3, out);
+}
+
+/// Decodes a BC4 block into the red channel, with the `i8` bits of each texel if `signed`.
+pub(super) fn decode_bc4(block: &[u8], signed: bool, out: &mut [[u8; 4]]) {
+    if signed {
+        out.fill([0, 0, 0, 127]);
+        decode_snorm_channel(block, 0, out);
+    } else {
+        out.fill([0, 0, 0, 255]);
+        decode_unorm_channel(block, 0, out);
+    }
+}
+
+/// Decodes a BC5 block into the red and green channels, with the `i8` bits of each texel
+/// if `signed`.
+pub(super) fn decode_bc5(block: &[u8], signed: bool, out: &mut [[u8; 4]]) {
+    if signed {
+        out.fill([0, 0, 0, 127]);
+        decode_snorm_channel(&block[..8], 0, out);
+        decode_snorm_channel(&block[8..], 1, out);
+    } else {
+        out.fill([0, 0, 0, 255]);
+        decode_unorm_channel(&block[..8], 0, out);
+        decode_unorm_channel(&block[8..], 1, out);
+    }
+}
+
+struct Bc7Mode {
+    subsets: usize,
+    partition_bits: u32,
+    rotation_bits: u32,
+    index_selection_bits: u32,
+    color_bits: u32,
+    alpha_bits: u32,
+    endpoint_p_bits: bool,
+    shared_p_bits: bool,
+    index_bits: u32,
+    secondary_index_bits: u32,
+}
+
+const BC7_MODES: [Bc7Mode; 8] = [
+    Bc7Mode {
+        subsets: 3,
+        partition_bits: 4,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 4,
+        alpha_bits: 0,
+        endpoint_p_bits: true,
+        shared_p_bits: false,
+        index_bits: 3,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 2,
+        partition_bits: 6,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 6,
+        alpha_bits: 0,
+        endpoint_p_bits: false,
+        shared_p_bits: true,
+        index_bits: 3,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 3,
+        partition_bits: 6,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 5,
+        alpha_bits: 0,
+        endpoint_p_bits: false,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 2,
+        partition_bits: 6,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 7,
+        alpha_bits: 0,
+        endpoint_p_bits: true,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 1,
+        partition_bits: 0,
+        rotation_bits: 2,
+        index_selection_bits: 1,
+        color_bits: 5,
+        alpha_bits: 6,
+        endpoint_p_bits: false,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 3,
+    },
+    Bc7Mode {
+        subsets: 1,
+        partition_bits: 0,
+        rotation_bits: 2,
+        index_selection_bits: 0,
+        color_bits: 7,
+        alpha_bits: 8,
+        endpoint_p_bits: false,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 2,
+    },
+    Bc7Mode {
+        subsets: 1,
+        partition_bits: 0,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 7,
+        alpha_bits: 7,
+        endpoint_p_bits: true,
+        shared_p_bits: false,
+        index_bits: 4,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 2,
+        partition_bits: 6,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 5,
+        alpha_bits: 5,
+        endpoint_p_bits: true,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 0,
+    },
+];
+
+fn weights(index_bits: u32) -> &'static [u32] {
+    match index_bits {
+        2 => &WEIGHTS_2,
+        3 => &WEIGHTS_3,
+        _ => &WEIGHTS_4,
+    }
+}
+
+/// Returns the subset of `texel` and whether its index is stored with one bit less.
+fn subset(subsets: usize, partition: usize, texel: usize) -> (usize, bool) {
+    match subsets {
+        1 => (0, texel == 0),
+        2 => {
+            let subset = (PARTITIONS_2[partition] >> texel) as usize & 1;
+            (subset, texel == 0 || texel == ANCHORS_2[partition] as usize)
+        }
+        _ => {
+            let subset = (PARTITIONS_3[partition] >> (2 * texel)) as usize & 3;
+            let anchor = texel == 0 || ANCHORS_3[partition].contains(&(texel as u8));
+            (subset, anchor)
+        }
+    }
+}
+
+fn expand(value: u32, bits: u32) -> u32 {
+    (value << (8 - bits)) | (value >> (2 * bits - 8))
+}
+
+pub(super) fn decode_bc7(block: &[u8], out: &mut [[u8; 4]]) {
+    if block[0] == 0 {
+        // Reserved mode.
+        out.fill([0; 4]);
+        return;
+    }
+    let mode_index = block[0].trailing_zeros();
+    let mode = &BC7_MODES[mode_index as usize];
+    let mut bits = Bits::new(block);
+    bits.read(mode_index + 1);
+
+    let partition = bits.read(mode.partition_bits) as usize;
+    let rotation = bits.read(mode.rotation_bits);
+    let index_selection = bits.read(mode.index_selection_bits);
+
+    let endpoint_count = mode.subsets * 2;
+    let mut endpoints = [[0u32; 4]; 6];
+    for channel in 0..3 {
+        for endpoint in &mut endpoints[..endpoint_count] {
+            endpoint[channel] = bits.read(mode.color_bits);
+        }
+    }
+    for endpoint in &mut endpoints[..endpoint_count] {
+        endpoint[3] = bits.read(mode.alpha_bits);
+    }
+
+    let mut color_bits = mode.color_bits;
+    let mut alpha_bits = mode.alpha_bits;
+    if mode.endpoint_p_bits || mode.shared_p_bits {
+        let mut p_bit = 0;
+        for (i, endpoint) in endpoints[..endpoint_count].iter_mut().enumerate() {
+            if mode.endpoint_p_bits || i % 2 == 0 {
+                p_bit = bits.read(1);
+            }
+            for value in endpoint.iter_mut() {
+                *value = (*value << 1) | p_bit;
+            }
+        }
+        color_bits += 1;
+        if alpha_bits > 0 {
+            alpha_bits += 1;
+        }
+    }
+    for endpoint in &mut endpoints[..endpoint_count] {
+        for value in &mut endpoint[..3] {
+            *value = expand(*value, color_bits);
+        }
+        endpoint[3] = if alpha_bits > 0 {
+            expand(endpoint[3], alpha_bits)
+        } else {
+            255
+        };
+    }
+
+    let mut indices = [0u32; 16];
+    for (texel, index) in indices.iter_mut().enumerate() {
+        let (_, anchor) = subset(mode.subsets, partition, texel);
+        *index = bits.read(mode.index_bits - anchor as u32);
+    }
+    let mut secondary_indices = [0u32; 16];
+    if mode.secondary_index_bits > 0 {
+        for (texel, index) in secondary_indices.iter_mut().enumerate() {
+            *index = bits.read(mode.secondary_index_bits - (texel == 0) as u32);
+        }
+    }
+
+    for (texel, out) in out.iter_mut().enumerate() {
+        let (subset, _) = subset(mode.subsets, partition, texel);
+        let (e0, e1) = (endpoints[2 * subset], endpoints[2 * subset + 1]);
+        let (color_weight, alpha_weight) = if mode.secondary_index_bits == 0 {
+            let weight = weights(mode.index_bits)[indices[texel] as usize];
+            (weight, weight)
+        } else {
+            let primary = weights(mode.index_bits)[indices[texel] as usize];
+            let secondary = weights(mode.secondary_index_bits)[secondary_indices[texel] as usize];
+            if index_selection == 0 {
+                (primary, secondary)
+            } else {
+                (secondary, primary)
+            }
+        };
+
+        let mut color = [0u8; 4];
+        for channel in 0..3 {
+            color[channel] = interpolate(e0[channel], e1[channel], color_weight) as u8;
+        }
+        color[3] = interpolate(e0[3], e1[3], alpha_weight) as u8;
+        if rotation > 0 {
+            color.swap(3, rotation as usize - 1);
+        }
+        *out = color;
+    }
+}
+
+struct Bc6hMode {
+    /// Value of the 2 or 5 mode bits.
+    id: u32,
+    transformed: bool,
+    two_regions: bool,
+    endpoint_bits: u32,
+    delta_bits: [u32; 3],
+    /// Fields in the order they are stored, as `(field, first bit, last bit)`.
+    ///
+    /// A few fields are stored in reverse bit order, with the first bit being the highest.
+    layout: &'static [(usize, u32, u32)],
+}
+
+// Fields of BC6H blocks, the endpoints of each channel followed by the partition.
+const RW: usize = 0;
+const GW: usize = 1;
+const BW: usize = 2;
+const RX: usize = 3;
+const GX: usize = 4;
+const BX: usize = 5;
+const RY: usize = 6;
+const GY: usize = 7;
+const BY: usize = 8;
+const RZ: usize = 9;
+const GZ: usize = 10;
+const BZ: usize = 11;
+const D: usize = 12;
+
+const BC6H_MODES: [Bc6hMode; 14] = [
+    Bc6hMode {
+        id: 0,
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 10,
+        delta_bits: [5, 5, 5],
+        layout: &[
+            (GY, 4, 4),
+            (BY, 4, 4),
+            (BZ, 4, 4),
+            (RW, 0, 9),
+            (GW, 0, 9),
+            (BW, 0, 9),
+            (RX, 0, 4),
+            (GZ, 4, 4),
+            (GY, 0, 3),
+            (GX, 0, 4),
+            (BZ, 0, 0),
+            (GZ, 0, 3),
+            (BX, 0, 4),
+            (BZ, 1, 1),
+            (BY, 0, 3),
+            (RY, 0, 4),
+            (BZ, 2, 2),
+            (RZ, 0, 4),
+            (BZ, 3, 3),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 1,
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 7,
+        delta_bits: [6, 6, 6],
+        layout: &[
+            (GY, 5, 5),
+            (GZ, 4, 5),
+            (RW, 0, 6),
+            (BZ, 0, 1),
+            (BY, 4, 4),
+            (GW, 0, 6),
+            (BY, 5, 5),
+            (BZ, 2, 2),
+            (GY, 4, 4),
+            (BW, 0, 6),
+            (BZ, 3, 3),
+            (BZ, 5, 5),
+            (BZ, 4, 4),
+            (RX, 0, 5),
+            (GY, 0, 3),
+            (GX, 0, 5),
+            (GZ, 0, 3),
+            (BX, 0, 5),
+            (BY, 0, 3),
+            (RY, 0, 5),
+            (RZ, 0, 5),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 0b00010,
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 11,
+        delta_bits: [5, 4, 4],
+        layout: &[
+            (RW, 0, 9),
+            (GW, 0, 9),
+            (BW, 0, 9),
+            (RX, 0, 4),
+            (RW, 10, 10),
+            (GY, 0, 3),
+            (GX, 0, 3),
+            (GW, 10, 10),
+            (BZ, 0, 0),
+            (GZ, 0, 3),
+            (BX, 0, 3),
+            (BW, 10, 10),
+            (BZ, 1, 1),
+            (BY, 0, 3),
+            (RY, 0, 4),
+            (BZ, 2, 2),
+            (RZ, 0, 4),
+            (BZ, 3, 3),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 0b00110,
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 11,
+        delta_bits: [4, 5, 4],
+        layout: &[
+            (RW, 0, 9),
+            (GW, 0, 9),
+            (BW, 0, 9),
+            (RX, 0, 3),
+            (RW, 10, 10),
+            (GZ, 4, 4),
+            (GY, 0, 3),
+            (GX, 0, 4),
+            (GW, 10, 10),
+            (GZ, 0, 3),
+            (BX, 0, 3),
+            (BW, 10, 10),
+            (BZ, 1, 1),
+            (BY, 0, 3),
+            (RY, 0, 3),
+            (BZ, 0, 0),
+            (BZ, 2, 2),
+            (RZ, 0, 3),
+            (GY, 4, 4),
+            (BZ, 3, 3),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 0b01010,
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 11,
+        delta_bits: [4, 4, 5],
+        layout: &[
+            (RW, 0, 9),
+            (GW, 0, 9),
+            (BW, 0, 9),
+            (RX, 0, 3),
+            (RW, 10, 10),
+            (BY, 4, 4),
+            (GY, 0, 3),
+            (GX, 0, 3),
+            (GW, 10, 10),
+            (BZ, 0, 0),
+            (GZ, 0, 3),
+            (BX, 0, 4),
+            (BW, 10, 10),
+            (BY, 0, 3),
+            (RY, 0, 3),
+            (BZ, 1, 2),
+            (RZ, 0, 3),
+            (BZ, 4, 4),
+            (BZ, 3, 3),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 0b01110,
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 9,
+        delta_bits: [5, 5, 5],
+        layout: &[
+            (RW, 0, 8),
+            (BY, 4, 4),
+            (GW, 0, 8),
+            (GY, 4, 4),
+            (BW, 0, 8),
+            (BZ, 4, 4),
+            (RX, 0, 4),
+            (GZ, 4, 4),
+            (GY, 0, 3),
+            (GX, 0, 4),
+            (BZ, 0, 0),
+            (GZ, 0, 3),
+            (BX, 0, 4),
+            (BZ, 1, 1),
+            (BY, 0, 3),
+            (RY, 0, 4),
+            (BZ, 2, 2),
+            (RZ, 0, 4),
+            (BZ, 3, 3),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 0b10010,
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 8,
+        delta_bits: [6, 5, 5],
+        layout: &[
+            (RW, 0, 7),
+            (GZ, 4, 4),
+            (BY, 4, 4),
+            (GW, 0, 7),
+            (BZ, 2, 2),
+            (GY, 4, 4),
+            (BW, 0, 7),
+            (BZ, 3, 4),
+            (RX, 0, 5),
+            (GY, 0, 3),
+            (GX, 0, 4),
+            (BZ, 0, 0),
+            (GZ, 0, 3),
+            (BX, 0, 4),
+            (BZ, 1, 1),
+            (BY, 0, 3),
+            (RY, 0, 5),
+            (RZ, 0, 5),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 0b10110,
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 8,
+        delta_bits: [5, 6, 5],
+        layout: &[
+            (RW, 0, 7),
+            (BZ, 0, 0),
+            (BY, 4, 4),
+            (GW, 0, 7),
+            (GY, 5, 5),
+            (GY, 4, 4),
+            (BW, 0, 7),
+            (GZ, 5, 5),
+            (BZ, 4, 4),
+            (RX, 0, 4),
+            (GZ, 4, 4),
+            (GY, 0, 3),
+            (GX, 0, 5),
+            (GZ, 0, 3),
+            (BX, 0, 4),
+            (BZ, 1, 1),
+            (BY, 0, 3),
+            (RY, 0, 4),
+            (BZ, 2, 2),
+            (RZ, 0, 4),
+            (BZ, 3, 3),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 0b11010,
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 8,
+        delta_bits: [5, 5, 6],
+        layout: &[
+            (RW, 0, 7),
+            (BZ, 1, 1),
+            (BY, 4, 4),
+            (GW, 0, 7),
+            (BY, 5, 5),
+            (GY, 4, 4),
+            (BW, 0, 7),
+            (BZ, 5, 5),
+            (BZ, 4, 4),
+            (RX, 0, 4),
+            (GZ, 4, 4),
+            (GY, 0, 3),
+            (GX, 0, 4),
+            (BZ, 0, 0),
+            (GZ, 0, 3),
+            (BX, 0, 5),
+            (BY, 0, 3),
+            (RY, 0, 4),
+            (BZ, 2, 2),
+            (RZ, 0, 4),
+            (BZ, 3, 3),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 0b11110,
+        transformed: false,
+        two_regions: true,
+        endpoint_bits: 6,
+        delta_bits: [6, 6, 6],
+        layout: &[
+            (RW, 0, 5),
+            (GZ, 4, 4),
+            (BZ, 0, 1),
+            (BY, 4, 4),
+            (GW, 0, 5),
+            (GY, 5, 5),
+            (BY, 5, 5),
+            (BZ, 2, 2),
+            (GY, 4, 4),
+            (BW, 0, 5),
+            (GZ, 5, 5),
+            (BZ, 3, 3),
+            (BZ, 5, 5),
+            (BZ, 4, 4),
+            (RX, 0, 5),
+            (GY, 0, 3),
+            (GX, 0, 5),
+            (GZ, 0, 3),
+            (BX, 0, 5),
+            (BY, 0, 3),
+            (RY, 0, 5),
+            (RZ, 0, 5),
+            (D, 0, 4),
+        ],
+    },
+    Bc6hMode {
+        id: 0b00011,
+        transformed: false,
+        two_regions: false,
+        endpoint_bits: 10,
+        delta_bits: [10, 10, 10],
+        layout: &[
+            (RW, 0, 9),
+            (GW, 0, 9),
+            (BW, 0, 9),
+            (RX, 0, 9),
+            (GX, 0, 9),
+            (BX, 0, 9),
+        ],
+    },
+    Bc6hMode {
+        id: 0b00111,
+        transformed: true,
+        two_regions: false,
+        endpoint_bits: 11,
+        delta_bits: [9, 9, 9],
+        layout: &[
+            (RW, 0, 9),
+            (GW, 0, 9),
+            (BW, 0, 9),
+            (RX, 0, 8),
+            (RW, 10, 10),
+            (GX, 0, 8),
+            (GW, 10, 10),
+            (BX, 0, 8),
+            (BW, 10, 10),
+        ],
+    },
+    Bc6hMode {
+        id: 0b01011,
+        transformed: true,
+        two_regions: false,
+        endpoint_bits: 12,
+        delta_bits: [8, 8, 8],
+        layout: &[
+            (RW, 0, 9),
+            (GW, 0, 9),
+            (BW, 0, 9),
+            (RX, 0, 7),
+            (RW, 11, 10),
+            (GX, 0, 7),
+            (GW, 11, 10),
+            (BX, 0, 7),
+            (BW, 11, 10),
+        ],
+    },
+    Bc6hMode {
+        id: 0b01111,
+        transformed: true,
+        two_regions: false,
+        endpoint_bits: 16,
+        delta_bits: [4, 4, 4],
+        layout: &[
+            (RW, 0, 9),
+            (GW, 0, 9),
+            (BW, 0, 9),
+            (RX, 0, 3),
+            (RW, 15, 10),
+            (GX, 0, 3),
+            (GW, 15, 10),
+            (BX, 0, 3),
+            (BW, 15, 10),
+        ],
+    },
+];
+
+fn sign_extend(value: i32, bits: u32) -> i32 {
+    let shift = 32 - bits;
+    (value << shift) >> shift
+}
+
+fn unquantize_bc6h(value: i32, bits: u32, signed: bool) -> i32 {
+    if signed {
+        if bits >= 16 {
+            return value;
+        }
+        let magnitude = value.abs();
+        let unquantized = if magnitude == 0 {
+            0
+        } else if magnitude >= (1 << (bits - 1)) - 1 {
+            0x7FFF
+        } else {
+            ((magnitude << 15) + 0x4000) >> (bits - 1)
+        };
+        unquantized * value.signum()
+    } else if bits >= 15 {
+        value
+    } else if value == 0 {
+        0
+    } else if value == (1 << bits) - 1 {
+        0xFFFF
+    } else {
+        ((value << 15) + 0x4000) >> (bits - 1)
+    }
+}
+
+/// Scales an interpolated value to the bits of a half float.
+fn finish_bc6h(value: i32, signed: bool) -> u16 {
+    if !signed {
+        ((value * 31) >> 6) as u16
+    } else if value < 0 {
+        0x8000 | ((-value * 31) >> 5) as u16
+    } else {
+        ((value * 31) >> 5) as u16
+    }
+}
+
+/// Decodes a BC6H block to the bits of half float RGBA texels.
+pub(super) fn decode_bc6h(block: &[u8], signed: bool, out: &mut [[u16; 4]]) {
+    let mut bits = Bits::new(block);
+    let mut id = bits.read(2);
+    if id >= 2 {
+        id |= bits.read(3) << 2;
+    }
+    let Some(mode) = BC6H_MODES.iter().find(|mode| mode.id == id) else {
+        // Reserved mode.
+        out.fill([0, 0, 0, 0x3C00]);
+        return;
+    };
+
+    let mut fields = [0i32; 13];
+    for &(field, first, last) in mode.layout {
+        if first <= last {
+            for bit in first..=last {
+                fields[field] |= (bits.read(1) as i32) << bit;
+            }
+        } else {
+            for bit in (last..=first).rev() {
+                fields[field] |= (bits.read(1) as i32) << bit;
+            }
+        }
+    }
+
+    let endpoint_count = if mode.two_regions { 4 } else { 2 };
+    let mut endpoints = [[0i32; 3]; 4];
+    for channel in 0..3 {
+        for (i, endpoint) in endpoints[..endpoint_count].iter_mut().enumerate() {
+            endpoint[channel] = fields[RW + channel + 3 * i];
+        }
+        let base = &mut endpoints[0][channel];
+        if signed {
+            *base = sign_extend(*base, mode.endpoint_bits);
+        }
+        let base = *base;
+        for endpoint in &mut endpoints[1..endpoint_count] {
+            let value = &mut endpoint[channel];
+            if mode.transformed || signed {
+                *value = sign_extend(*value, mode.delta_bits[channel]);
+            }
+            if mode.transformed {
+                *value = (*value + base) & ((1 << mode.endpoint_bits) - 1);
+                if signed {
+                    *value = sign_extend(*value, mode.endpoint_bits);
+                }
+            }
+        }
+    }
+    for endpoint in &mut endpoints[..endpoint_count] {
+        for value in endpoint.iter_mut() {
+            *value = unquantize_bc6h(*value, mode.endpoint_bits, signed);
+        }
+    }
+
+    let partition = fields[D] as usize;
+    for (texel, out) in out.iter_mut().enumerate() {
+        let (region, index) = if mode.two_regions {
+            let region = (PARTITIONS_2[partition] >> texel) as usize & 1;
+            let anchor = texel == 0 || texel == ANCHORS_2[partition] as usize;
+            (region, WEIGHTS_3[bits.read(3 - anchor as u32) as usize])
+        } else {
+            (0, WEIGHTS_4[bits.read(4 - (texel == 0) as u32) as usize])
+        };
+        let (e0, e1) = (endpoints[2 * region], endpoints[2 * region + 1]);
+        let weight = index as i32;
+        for channel in 0..3 {
+            let value = (e0[channel] * (64 - weight) + e1[channel] * weight + 32) >> 6;
+            out[channel] = finish_bc6h(value, signed);
+        }
+        out[3] = 0x3C00;
+    }
+}
+
+/// Encodes 4x4 RGBA texels as a BC7 mode 6 block.
+///
+/// Mode 6 has a single subset with 7 bit endpoints and 4 bit indices, which keeps the
+/// encoder simple while matching the quality of RGBA8 closely for smooth content.
+pub(super) fn encode_bc7(texels: &[[u8; 4]; 16]) -> [u8; 16] {
+    // Fit the endpoints along the principal axis of the texels.
+    let mut mean = [0f32; 4];
+    for texel in texels {
+        for channel in 0..4 {
+            mean[channel] += texel[channel] as f32 / 16.0;
+        }
+    }
+    let mut covariance = [[0f32; 4]; 4];
+    for texel in texels {
+        let delta: [f32; 4] = std::array::from_fn(|c| texel[c] as f32 - mean[c]);
+        for i in 0..4 {
+            for j in 0..4 {
+                covariance[i][j] += delta[i] * delta[j];
+            }
+        }
+    }
+    // Start the power iteration from the channel with the largest variance.
+    let largest = (0..4)
+        .max_by(|&a, &b| covariance[a][a].total_cmp(&covariance[b][b]))
+        .unwrap();
+    let mut axis = covariance[largest];
+    for _ in 0..8 {
+        let next: [f32; 4] =
+            std::array::from_fn(|i| (0..4).map(|j| covariance[i][j] * axis[j]).sum());
+        let length = next.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if length < f32::EPSILON {
+            break;
+        }
+        axis = next.map(|v| v / length);
+    }
+    let project =
+        |texel: &[u8; 4]| -> f32 { (0..4).map(|c| (texel[c] as f32 - mean[c]) * axis[c]).sum() };
+    let (min, max) = texels
+        .iter()
+        .map(project)
+        .fold((f32::MAX, f32::MIN), |(min, max), t| {
+            (min.min(t), max.max(t))
+        });
+
+    let mut endpoints = [[0u32; 4]; 2];
+    let mut p_bits = [0u32; 2];
+    for (i, t) in [min, max].into_iter().enumerate() {
+        let color: [f32; 4] = std::array::from_fn(|c| (mean[c] + axis[c] * t).clamp(0.0, 255.0));
+        // Pick the p-bit which lets the 7 bit endpoint get closest to the fitted color.
+        let mut best_error = f32::MAX;
+        for p_bit in 0..2 {
+            let quantized = color.map(|v| (((v - p_bit as f32) / 2.0).round()).clamp(0.0, 127.0));
+            let error = (0..4)
+                .map(|c| {
+                    let delta = color[c] - (quantized[c] * 2.0 + p_bit as f32);
+                    delta * delta
+                })
+                .sum::<f32>();
+            if error < best_error {
+                best_error = error;
+                endpoints[i] = quantized.map(|v| v as u32);
+                p_bits[i] = p_bit;
+            }
+        }
+    }
+
+    let colors = |endpoints: &[[u32; 4]; 2], p_bits: &[u32; 2]| -> [[u32; 4]; 16] {
+        let e0 = endpoints[0].map(|v| (v << 1) | p_bits[0]);
+        let e1 = endpoints[1].map(|v| (v << 1) | p_bits[1]);
+        std::array::from_fn(|index| {
+            std::array::from_fn(|c| interpolate(e0[c], e1[c], WEIGHTS_4[index]))
+        })
+    };
+    let palette = colors(&endpoints, &p_bits);
+    let mut indices = texels.map(|texel| {
+        (0..16)
+            .min_by_key(|&index| {
+                (0..4)
+                    .map(|c| {
+                        let delta = palette[index][c] as i32 - texel[c] as i32;
+                        delta * delta
+                    })
+                    .sum::<i32>()
+            })
+            .unwrap() as u32
+    });
+    // The high bit of the first index is implicitly zero.
+    if indices[0] >= 8 {
+        endpoints.swap(0, 1);
+        p_bits.swap(0, 1);
+        for index in &mut indices {
+            *index = 15 - *index;
+        }
+    }
+
+    let mut value = 1u128 << 6;
+    let mut position = 7;
+    let mut write = |bits: u32, count: u32| {
+        value |= (bits as u128) << position;
+        position += count;
+    };
+    for (&e0, &e1) in endpoints[0].iter().zip(&endpoints[1]) {
+        write(e0, 7);
+        write(e1, 7);
+    }
+    write(p_bits[0], 1);
+    write(p_bits[1], 1);
+    for (texel, &index) in indices.iter().enumerate() {
+        write(index, if texel == 0 { 3 } else { 4 });
+    }
+    value.to_le_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchors_belong_to_their_subset() {
+        for partition in 0..64 {
+            assert_eq!(subset(2, partition, ANCHORS_2[partition] as usize).0, 1);
+            for (i, &anchor) in ANCHORS_3[partition].iter().enumerate() {
+                assert_eq!(subset(3, partition, anchor as usize).0, i + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn bc1_transparent() {
+        // Endpoints in ascending order select the three color mode.
+        let block = [0x00, 0x00, 0xFF, 0xFF, 0b1110_0100, 0, 0, 0];
+        let mut out = [[0; 4]; 16];
+        decode_bc1(&block, &mut out);
+        assert_eq!(out[0], [0, 0, 0, 255]);
+        assert_eq!(out[1], [255, 255, 255, 255]);
+        assert_eq!(out[2], [128, 128, 128, 255]);
+        assert_eq!(out[3], [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn bc4_snorm() {
+        let block = [0x81, 0x7F, 0b1000_1000, 0, 0, 0, 0, 0];
+        let mut out = [[0; 4]; 16];
+        decode_bc4(&block, true, &mut out);
+        assert_eq!(out[0][0] as i8, -127);
+        assert_eq!(out[1][0] as i8, 127);
+        // Descending endpoints interpolate four values between them.
+        assert_eq!(out[2][0] as i8, -76);
+    }
+
+    #[test]
+    fn bc6h_unquantized_endpoints() {
+        // Mode 11 stores two 10 bit endpoints as is, black and the largest value.
+        let mut value = 0b00011u128;
+        for channel in 0..3 {
+            value |= 0x3FF << (35 + 10 * channel);
+        }
+        value |= 0xF << 124;
+        let mut out = [[0; 4]; 16];
+        decode_bc6h(&value.to_le_bytes(), false, &mut out);
+        assert_eq!(out[0], [0, 0, 0, 0x3C00]);
+        assert_eq!(out[15], [0x7BFF, 0x7BFF, 0x7BFF, 0x3C00]);
+    }
+
+    #[test]
+    fn bc7_round_trip() {
+        let texels: [[u8; 4]; 16] =
+            std::array::from_fn(|i| [i as u8 * 16, 255 - i as u8 * 16, 64, 255]);
+        let mut out = [[0; 4]; 16];
+        decode_bc7(&encode_bc7(&texels), &mut out);
+        for (texel, decoded) in texels.iter().zip(out) {
+            for channel in 0..4 {
+                assert!((texel[channel] as i32 - decoded[channel] as i32).abs() <= 4);
+            }
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! ETC2 and EAC block decoders.
+//!
+//! Every block covers 4x4 texels, which are written in row-major order. Blocks are big
+//! endian and number their texels in column-major order.
+
+/// Intensity modifiers of ETC1 style subblocks, as the small and large magnitude of each table.
+const MODIFIERS: [[i32; 2]; 8] = [
+    [2, 8],
+    [5, 17],
+    [9, 29],
+    [13, 42],
+    [18, 60],
+    [24, 80],
+    [33, 106],
+    [47, 183],
+];
+
+/// Distances between the paint colors of the T and H modes.
+const DISTANCES: [i32; 8] = [3, 6, 11, 16, 23, 32, 41, 64];
+
+/// Modifier tables of EAC blocks.
+const EAC_MODIFIERS: [[i32; 8]; 16] = [
+    [-3, -6, -9, -15, 2, 5, 8, 14],
+    [-3, -7, -10, -13, 2, 6, 9, 12],
+    [-2, -5, -8, -13, 1, 4, 7, 12],
+    [-2, -4, -6, -13, 1, 3, 5, 12],
+    [-3, -6, -8, -12, 2, 5, 7, 11],
+    [-3, -7, -9, -11, 2, 6, 8, 10],
+    [-4, -7, -8, -11, 3, 6, 7, 10],
+    [-3, -5, -8, -11, 2, 4, 7, 10],
+    [-2, -6, -8, -10, 1, 5, 7, 9],
+    [-2, -5, -8, -10, 1, 4, 7, 9],
+    [-2, -4, -8, -10, 1, 3, 7, 9],
+    [-2, -5, -7, -10, 1, 4, 6, 9],
+    [-3, -4, -7, -10, 2, 3, 6, 9],
+    [-1, -2, -3, -10, 0, 1, 2, 9],
+    [-4, -6, -8, -9, 3, 5, 7, 8],
+    [-3, -5, -7, -9, 2, 4, 6, 8],
+];
+
+fn extend4(value: u32) -> i32 {
+    ((value << 4) | value) as i32
+}
+
+fn extend5(value: i32) -> i32 {
+    (value << 3) | (value >> 2)
+}
+
+fn clamp(value: i32) -> u8 {
+    value.clamp(0, 255) as u8
+}
+
+/// Index of the texel at column-major position `i` in the row-major output.
+fn texel_index(i: usize) -> usize {
+    (i % 4) * 4 + i / 4
+}
+
+/// Decodes an ETC2 RGB block.
+///
+/// With `punchthrough`, the differential bit is the opaque bit of the RGB8A1 formats.
+pub(super) fn decode_etc2_rgb(block: &[u8], punchthrough: bool, out: &mut [[u8; 4]]) {
+    let high = u32::from_be_bytes([block[0], block[1], block[2], block[3]]);
+    let indices = u32::from_be_bytes([block[4], block[5], block[6], block[7]]);
+    let differential = (high >> 1) & 1 != 0;
+    let flip = high & 1 != 0;
+    let transparent = punchthrough && !differential;
+
+    if !punchthrough && !differential {
+        let base = [
+            [(high >> 28) & 0xF, (high >> 20) & 0xF, (high >> 12) & 0xF].map(extend4),
+            [(high >> 24) & 0xF, (high >> 16) & 0xF, (high >> 8) & 0xF].map(extend4),
+        ];
+        let tables = [(high >> 5) & 7, (high >> 2) & 7];
+        return decode_subblocks(base, tables, flip, indices, false, out);
+    }
+
+    let r = (high >> 27) as i32 & 0x1F;
+    let g = (high >> 19) as i32 & 0x1F;
+    let b = (high >> 11) as i32 & 0x1F;
+    // Deltas are three bit two's complement numbers.
+    let delta = |shift: u32| ((((high >> shift) & 7) << 29) as i32) >> 29;
+    let (r2, g2, b2) = (r + delta(24), g + delta(16), b + delta(8));
+
+    if !(0..32).contains(&r2) {
+        // T mode.
+        let c0 = [
+            ((high >> 27) & 3) << 2 | ((high >> 24) & 3),
+            (high >> 20) & 0xF,
+            (high >> 16) & 0xF,
+        ]
+        .map(extend4);
+        let c1 = [(high >> 12) & 0xF, (high >> 8) & 0xF, (high >> 4) & 0xF].map(extend4);
+        let distance = DISTANCES[(((high >> 2) & 3) << 1 | (high & 1)) as usize];
+        let paint = [c0, c1.map(|c| c + distance), c1, c1.map(|c| c - distance)];
+        decode_paint(paint, indices, transparent, out);
+    } else if !(0..32).contains(&g2) {
+        // H mode.
+        let c0 = [
+            (high >> 27) & 0xF,
+            ((high >> 24) & 7) << 1 | ((high >> 20) & 1),
+            ((high >> 19) & 1) << 3 | ((high >> 15) & 7),
+        ];
+        let c1 = [(high >> 11) & 0xF, (high >> 7) & 0xF, (high >> 3) & 0xF];
+        let packed = |c: [u32; 3]| (c[0] << 8) | (c[1] << 4) | c[2];
+        let distance_index =
+            ((high >> 2) & 1) << 2 | (high & 1) << 1 | (packed(c0) >= packed(c1)) as u32;
+        let distance = DISTANCES[distance_index as usize];
+        let (c0, c1) = (c0.map(extend4), c1.map(extend4));
+        let paint = [
+            c0.map(|c| c + distance),
+            c0.map(|c| c - distance),
+            c1.map(|c| c + distance),
+            c1.map(|c| c - distance),
+        ];
+        decode_paint(paint, indices, transparent, out);
+    } else if !(0..32).contains(&b2) {
+        // Planar mode, which is always opaque.
+        let extend6 = |value: u32| ((value << 2) | (value >> 4)) as i32;
+        let extend7 = |value: u32| ((value << 1) | (value >> 6)) as i32;
+        let origin = [
+            extend6((high >> 25) & 0x3F),
+            extend7(((high >> 24) & 1) << 6 | ((high >> 17) & 0x3F)),
+            extend6(((high >> 16) & 1) << 5 | ((high >> 11) & 3) << 3 | ((high >> 7) & 7)),
+        ];
+        let horizontal = [
+            extend6(((high >> 2) & 0x1F) << 1 | (high & 1)),
+            extend7((indices >> 25) & 0x7F),
+            extend6((indices >> 19) & 0x3F),
+        ];
+        let vertical = [
+            extend6((indices >> 13) & 0x3F),
+            extend7((indices >> 6) & 0x7F),
+            extend6(indices & 0x3F),
+        ];
+        for (i, texel) in out.iter_mut().enumerate() {
+            let (x, y) = (i as i32 % 4, i as i32 / 4);
+            for channel in 0..3 {
+                let value = x * (horizontal[channel] - origin[channel])
+                    + y * (vertical[channel] - origin[channel])
+                    + 4 * origin[channel]
+                    + 2;
+                texel[channel] = clamp(value >> 2);
+            }
+            texel[3] = 255;
+        }
+    } else {
+        let base = [[r, g, b].map(extend5), [r2, g2, b2].map(extend5)];
+        let tables = [(high >> 5) & 7, (high >> 2) & 7];
+        decode_subblocks(base, tables, flip, indices, transparent, out);
+    }
+}
+
+/// Decodes the two subblocks of the individual and differential modes.
+fn decode_subblocks(
+    base: [[i32; 3]; 2],
+    tables: [u32; 2],
+    flip: bool,
+    indices: u32,
+    transparent: bool,
+    out: &mut [[u8; 4]],
+) {
+    for i in 0..16 {
+        let (x, y) = (i / 4, i % 4);
+        let subblock = if flip { y / 2 } else { x / 2 };
+        let [small, large] = MODIFIERS[tables[subblock] as usize];
+        let modifier = match ((indices >> (16 + i)) & 1, (indices >> i) & 1) {
+            (0, 0) if transparent => 0,
+            (0, 0) => small,
+            (0, _) => large,
+            (_, 0) if transparent => {
+                out[texel_index(i)] = [0; 4];
+                continue;
+            }
+            (_, 0) => -small,
+            _ => -large,
+        };
+        let color = base[subblock].map(|c| clamp(c + modifier));
+        out[texel_index(i)] = [color[0], color[1], color[2], 255];
+    }
+}
+
+/// Decodes the texels of the T and H modes, which select one of four paint colors.
+fn decode_paint(paint: [[i32; 3]; 4], indices: u32, transparent: bool, out: &mut [[u8; 4]]) {
+    for i in 0..16 {
+        let index = ((indices >> (16 + i)) & 1) << 1 | ((indices >> i) & 1);
+        out[texel_index(i)] = if transparent && index == 2 {
+            [0; 4]
+        } else {
+            let color = paint[index as usize].map(clamp);
+            [color[0], color[1], color[2], 255]
+        };
+    }
+}
+
+/// Calls `f` with the column-major texel position and modifier of every texel of an EAC block.
+fn eac_modifiers(block: &[u8], mut f: impl FnMut(usize, i32)) {
+    let value = u64::from_be_bytes(block[..8].try_into().unwrap());
+    let table = &EAC_MODIFIERS[(value >> 48) as usize & 0xF];
+    for i in 0..16 {
+        f(i, table[(value >> (45 - 3 * i)) as usize & 7]);
+    }
+}
+
+/// Decodes an 8 bit EAC block into the alpha channel.
+pub(super) fn decode_eac_alpha(block: &[u8], out: &mut [[u8; 4]]) {
+    let base = block[0] as i32;
+    let multiplier = (block[1] >> 4) as i32;
+    eac_modifiers(block, |i, modifier| {
+        out[texel_index(i)][3] = clamp(base + modifier * multiplier);
+    });
+}
+
+/// Decodes an 11 bit EAC block into `channel`, with the `i8` bits of each texel if `signed`.
+pub(super) fn decode_eac_r11(block: &[u8], signed: bool, channel: usize, out: &mut [[u8; 4]]) {
+    let multiplier = (block[1] >> 4) as i32;
+    eac_modifiers(block, |i, modifier| {
+        // A multiplier of zero scales the modifiers by 1/8 instead.
+        let offset = if multiplier == 0 {
+            modifier
+        } else {
+            modifier * multiplier * 8
+        };
+        out[texel_index(i)][channel] = if signed {
+            let base = (block[0] as i8).max(-127) as i32;
+            let value = (base * 8 + offset).clamp(-1023, 1023);
+            ((value * 127 + value.signum() * 511) / 1023) as i8 as u8
+        } else {
+            let value = (block[0] as i32 * 8 + 4 + offset).clamp(0, 2047);
+            ((value * 255 + 1023) / 2047) as u8
+        };
+    });
+}
+
+pub(super) fn decode_etc2_rgba(block: &[u8], out: &mut [[u8; 4]]) {
+    decode_etc2_rgb(&block[8..], false, out);
+    decode_eac_alpha(block, out);
+}
+
+/// Decodes an EAC R11 block, with the `i8` bits of each texel if `signed`.
+pub(super) fn decode_eac_r(block: &[u8], signed: bool, out: &mut [[u8; 4]]) {
+    out.fill([0, 0, 0, if signed { 127 } else { 255 }]);
+    decode_eac_r11(block, signed, 0, out);
+}
+
+/// Decodes an EAC RG11 block, with the `i8` bits of each texel if `signed`.
+pub(super) fn decode_eac_rg(block: &[u8], signed: bool, out: &mut [[u8; 4]]) {
+    out.fill([0, 0, 0, if signed { 127 } else { 255 }]);
+    decode_eac_r11(&block[..8], signed, 0, out);
+    decode_eac_r11(&block[8..], signed, 1, out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn etc2_individual() {
+        // Both subblocks have the base color 0x88 and the smallest modifiers. The top right
+        // texel uses the large negative modifier.
+        let block = [0x88, 0x88, 0x88, 0x00, 0x10, 0x00, 0x10, 0x00];
+        let mut out = [[0; 4]; 16];
+        decode_etc2_rgb(&block, false, &mut out);
+        assert_eq!(out[0], [138, 138, 138, 255]);
+        assert_eq!(out[3], [128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn etc2_punchthrough() {
+        // Differential mode without the opaque bit makes index 2 transparent.
+        let block = [0x80, 0x80, 0x80, 0x00, 0x00, 0x01, 0x00, 0x00];
+        let mut out = [[0; 4]; 16];
+        decode_etc2_rgb(&block, true, &mut out);
+        assert_eq!(out[0], [0; 4]);
+        assert_eq!(out[1], [132, 132, 132, 255]);
+    }
+
+    #[test]
+    fn eac_alpha() {
+        let block = [128, 0x10, 0x92, 0x49, 0x24, 0x92, 0x49, 0x24];
+        let mut out = [[0; 4]; 16];
+        decode_eac_alpha(&block, &mut out);
+        assert!(out.iter().all(|texel| texel[3] == 130));
+    }
+}
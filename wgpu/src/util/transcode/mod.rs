@@ -0,0 +1,385 @@
+//! CPU transcoding of block compressed texture data.
+//!
+//! Lets applications ship a single compressed format and convert it at load time for devices
+//! that do not support it, at the cost of decoding every texel on the CPU.
+
+mod astc;
+mod bc;
+mod etc;
+
+use std::{error, fmt};
+
+use crate::{AstcChannel, Extent3d, Features, TextureFormat};
+
+/// Largest number of texels in a block of any supported format (ASTC 12x12).
+const MAX_BLOCK_TEXELS: usize = 144;
+
+/// Error produced when transcoding texture data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TranscodeError {
+    /// The source format cannot be decoded on the CPU.
+    UnsupportedSource(TextureFormat),
+    /// The source format cannot be converted to the target format without changing the meaning
+    /// of its values.
+    UnsupportedTarget {
+        /// Format of the data being transcoded.
+        source: TextureFormat,
+        /// Format that was requested.
+        target: TextureFormat,
+    },
+    /// The amount of data does not match the size of the subresource.
+    DataSizeMismatch {
+        /// Number of bytes required by the described subresource.
+        expected: usize,
+        /// Number of bytes provided.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for TranscodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::UnsupportedSource(format) => {
+                write!(f, "Format {format:?} can not be transcoded")
+            }
+            Self::UnsupportedTarget { source, target } => {
+                write!(f, "Format {source:?} can not be transcoded to {target:?}")
+            }
+            Self::DataSizeMismatch { expected, actual } => write!(
+                f,
+                "Image data is {actual} bytes, but the texture requires {expected} bytes"
+            ),
+        }
+    }
+}
+
+impl error::Error for TranscodeError {}
+
+/// How the values of a format are interpreted, which must be preserved when transcoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Unorm,
+    Snorm,
+    Float,
+}
+
+fn source_kind(format: TextureFormat) -> Option<Kind> {
+    match format {
+        TextureFormat::Bc1RgbaUnorm
+        | TextureFormat::Bc1RgbaUnormSrgb
+        | TextureFormat::Bc2RgbaUnorm
+        | TextureFormat::Bc2RgbaUnormSrgb
+        | TextureFormat::Bc3RgbaUnorm
+        | TextureFormat::Bc3RgbaUnormSrgb
+        | TextureFormat::Bc4RUnorm
+        | TextureFormat::Bc5RgUnorm
+        | TextureFormat::Bc7RgbaUnorm
+        | TextureFormat::Bc7RgbaUnormSrgb
+        | TextureFormat::Etc2Rgb8Unorm
+        | TextureFormat::Etc2Rgb8UnormSrgb
+        | TextureFormat::Etc2Rgb8A1Unorm
+        | TextureFormat::Etc2Rgb8A1UnormSrgb
+        | TextureFormat::Etc2Rgba8Unorm
+        | TextureFormat::Etc2Rgba8UnormSrgb
+        | TextureFormat::EacR11Unorm
+        | TextureFormat::EacRg11Unorm
+        | TextureFormat::Astc {
+            channel: AstcChannel::Unorm | AstcChannel::UnormSrgb,
+            ..
+        } => Some(Kind::Unorm),
+        TextureFormat::Bc4RSnorm
+        | TextureFormat::Bc5RgSnorm
+        | TextureFormat::EacR11Snorm
+        | TextureFormat::EacRg11Snorm => Some(Kind::Snorm),
+        TextureFormat::Bc6hRgbUfloat | TextureFormat::Bc6hRgbFloat => Some(Kind::Float),
+        _ => None,
+    }
+}
+
+fn target_kind(format: TextureFormat) -> Option<Kind> {
+    match format {
+        TextureFormat::Rgba8Unorm
+        | TextureFormat::Rgba8UnormSrgb
+        | TextureFormat::Bc7RgbaUnorm
+        | TextureFormat::Bc7RgbaUnormSrgb => Some(Kind::Unorm),
+        TextureFormat::Rgba8Snorm => Some(Kind::Snorm),
+        TextureFormat::Rgba16Float => Some(Kind::Float),
+        _ => None,
+    }
+}
+
+/// Returns the format that a texture of `format` and `size` should be transcoded to on a device
+/// with `features`, or `None` if `format` can not be transcoded.
+///
+/// This does not check whether `format` itself is supported. Color data is transcoded to BC7
+/// when [`Features::TEXTURE_COMPRESSION_BC`] is available and the size is a multiple of the BC7
+/// block size, and to uncompressed RGBA otherwise.
+pub fn transcode_fallback_format(
+    format: TextureFormat,
+    size: Extent3d,
+    features: Features,
+) -> Option<TextureFormat> {
+    let fallback = match source_kind(format)? {
+        Kind::Unorm
+            if features.contains(Features::TEXTURE_COMPRESSION_BC)
+                && size.width % 4 == 0
+                && size.height % 4 == 0 =>
+        {
+            TextureFormat::Bc7RgbaUnorm
+        }
+        Kind::Unorm => TextureFormat::Rgba8Unorm,
+        Kind::Snorm => TextureFormat::Rgba8Snorm,
+        Kind::Float => TextureFormat::Rgba16Float,
+    };
+    Some(if format.is_srgb() {
+        fallback.add_srgb_suffix()
+    } else {
+        fallback
+    })
+}
+
+/// Decodes one subresource of block compressed `data` in the `source` format and re-encodes it
+/// in the `target` format.
+///
+/// `size` is the logical size of the subresource, which is rounded up to whole blocks to find
+/// the expected length of `data`. Slices and rows are tightly packed in both the input and the
+/// output, matching the layout expected by [`DeviceExt::create_texture_with_data`].
+///
+/// Unorm sources can be transcoded to [`TextureFormat::Rgba8Unorm`] or
+/// [`TextureFormat::Bc7RgbaUnorm`], snorm sources to [`TextureFormat::Rgba8Snorm`], and BC6H to
+/// [`TextureFormat::Rgba16Float`]. sRGB sources require the sRGB variant of the target.
+/// ASTC HDR data is not supported.
+///
+/// [`DeviceExt::create_texture_with_data`]: crate::util::DeviceExt::create_texture_with_data
+pub fn transcode_texture_data(
+    source: TextureFormat,
+    target: TextureFormat,
+    size: Extent3d,
+    data: &[u8],
+) -> Result<Vec<u8>, TranscodeError> {
+    let kind = source_kind(source).ok_or(TranscodeError::UnsupportedSource(source))?;
+    if target_kind(target) != Some(kind) || source.is_srgb() != target.is_srgb() {
+        return Err(TranscodeError::UnsupportedTarget { source, target });
+    }
+
+    let block_size = source.block_copy_size(None).unwrap() as usize;
+    let (block_width, block_height) = source.block_dimensions();
+    let physical = size.physical_size(source);
+    let block_count = (physical.width / block_width) as usize
+        * (physical.height / block_height) as usize
+        * size.depth_or_array_layers as usize;
+    let expected = block_count * block_size;
+    if data.len() != expected {
+        return Err(TranscodeError::DataSizeMismatch {
+            expected,
+            actual: data.len(),
+        });
+    }
+
+    let output = match kind {
+        Kind::Unorm | Kind::Snorm => {
+            let texels = decode_texels(source, size, data, decode_block);
+            if target.block_dimensions() == (1, 1) {
+                texels.into_iter().flatten().collect()
+            } else {
+                encode_bc7(size, &texels)
+            }
+        }
+        Kind::Float => {
+            let signed = source == TextureFormat::Bc6hRgbFloat;
+            let texels = decode_texels(source, size, data, |_, block, out| {
+                bc::decode_bc6h(block, signed, out)
+            });
+            texels
+                .into_iter()
+                .flatten()
+                .flat_map(u16::to_le_bytes)
+                .collect()
+        }
+    };
+    Ok(output)
+}
+
+/// Decodes every block of `data`, cropping the texels to the logical `size`.
+fn decode_texels<T: Copy + Default>(
+    format: TextureFormat,
+    size: Extent3d,
+    data: &[u8],
+    decode: impl Fn(TextureFormat, &[u8], &mut [[T; 4]]),
+) -> Vec<[T; 4]> {
+    let block_size = format.block_copy_size(None).unwrap() as usize;
+    let (block_width, block_height) = format.block_dimensions();
+    let physical = size.physical_size(format);
+    let width_blocks = physical.width / block_width;
+    let height_blocks = physical.height / block_height;
+    let (width, height) = (size.width as usize, size.height as usize);
+
+    let mut texels = vec![[T::default(); 4]; width * height * size.depth_or_array_layers as usize];
+    let mut block_texels = [[T::default(); 4]; MAX_BLOCK_TEXELS];
+    let block_texels = &mut block_texels[..(block_width * block_height) as usize];
+    for (index, block) in data.chunks_exact(block_size).enumerate() {
+        decode(format, block, block_texels);
+
+        let index = index as u32;
+        let slice = (index / (width_blocks * height_blocks)) as usize;
+        let x0 = (index % width_blocks * block_width) as usize;
+        let y0 = (index / width_blocks % height_blocks * block_height) as usize;
+        let block_width = block_width as usize;
+        for y in 0..(block_height as usize).min(height - y0) {
+            let row = (slice * height + y0 + y) * width + x0;
+            let count = block_width.min(width - x0);
+            texels[row..row + count]
+                .copy_from_slice(&block_texels[y * block_width..y * block_width + count]);
+        }
+    }
+    texels
+}
+
+fn decode_block(format: TextureFormat, block: &[u8], out: &mut [[u8; 4]]) {
+    match format {
+        TextureFormat::Bc1RgbaUnorm | TextureFormat::Bc1RgbaUnormSrgb => bc::decode_bc1(block, out),
+        TextureFormat::Bc2RgbaUnorm | TextureFormat::Bc2RgbaUnormSrgb => bc::decode_bc2(block, out),
+        TextureFormat::Bc3RgbaUnorm | TextureFormat::Bc3RgbaUnormSrgb => bc::decode_bc3(block, out),
+        TextureFormat::Bc4RUnorm => bc::decode_bc4(block, false, out),
+        TextureFormat::Bc4RSnorm => bc::decode_bc4(block, true, out),
+        TextureFormat::Bc5RgUnorm => bc::decode_bc5(block, false, out),
+        TextureFormat::Bc5RgSnorm => bc::decode_bc5(block, true, out),
+        TextureFormat::Bc7RgbaUnorm | TextureFormat::Bc7RgbaUnormSrgb => bc::decode_bc7(block, out),
+        TextureFormat::Etc2Rgb8Unorm | TextureFormat::Etc2Rgb8UnormSrgb => {
+            etc::decode_etc2_rgb(block, false, out)
+        }
+        TextureFormat::Etc2Rgb8A1Unorm | TextureFormat::Etc2Rgb8A1UnormSrgb => {
+            etc::decode_etc2_rgb(block, true, out)
+        }
+        TextureFormat::Etc2Rgba8Unorm | TextureFormat::Etc2Rgba8UnormSrgb => {
+            etc::decode_etc2_rgba(block, out)
+        }
+        TextureFormat::EacR11Unorm => etc::decode_eac_r(block, false, out),
+        TextureFormat::EacR11Snorm => etc::decode_eac_r(block, true, out),
+        TextureFormat::EacRg11Unorm => etc::decode_eac_rg(block, false, out),
+        TextureFormat::EacRg11Snorm => etc::decode_eac_rg(block, true, out),
+        TextureFormat::Astc { channel, .. } => {
+            let (width, height) = format.block_dimensions();
+            astc::decode_block(block, width, height, channel == AstcChannel::UnormSrgb, out)
+        }
+        _ => unreachable!("{format:?} is not a supported source format"),
+    }
+}
+
+/// Encodes texels of the given logical size as BC7, repeating the edge texels to fill partial
+/// blocks.
+fn encode_bc7(size: Extent3d, texels: &[[u8; 4]]) -> Vec<u8> {
+    let (width, height) = (size.width as usize, size.height as usize);
+    let mut output = Vec::new();
+    for slice in texels.chunks_exact(width * height) {
+        for block_y in (0..height).step_by(4) {
+            for block_x in (0..width).step_by(4) {
+                let mut block = [[0; 4]; 16];
+                for (index, texel) in block.iter_mut().enumerate() {
+                    let x = (block_x + index % 4).min(width - 1);
+                    let y = (block_y + index / 4).min(height - 1);
+                    *texel = slice[y * width + x];
+                }
+                output.extend_from_slice(&bc::encode_bc7(&block));
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(width: u32, height: u32) -> Extent3d {
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        }
+    }
+
+    #[test]
+    fn bc1_to_rgba8_crops_partial_blocks() {
+        // Both endpoints are pure red, so every texel is red.
+        let block = [0x00, 0xF8, 0x00, 0xF8, 0, 0, 0, 0];
+        let data = [block, block].concat();
+        let output = transcode_texture_data(
+            TextureFormat::Bc1RgbaUnorm,
+            TextureFormat::Rgba8Unorm,
+            size(6, 3),
+            &data,
+        )
+        .unwrap();
+        assert_eq!(output, [255, 0, 0, 255].repeat(18));
+    }
+
+    #[test]
+    fn bc1_to_bc7() {
+        // White is exactly representable by both formats.
+        let block = [0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0];
+        let output = transcode_texture_data(
+            TextureFormat::Bc1RgbaUnorm,
+            TextureFormat::Bc7RgbaUnorm,
+            size(4, 4),
+            &block,
+        )
+        .unwrap();
+        let mut texels = [[0; 4]; 16];
+        bc::decode_bc7(&output, &mut texels);
+        assert_eq!(texels, [[255; 4]; 16]);
+    }
+
+    #[test]
+    fn invalid_requests() {
+        assert_eq!(
+            transcode_texture_data(
+                TextureFormat::Bc1RgbaUnormSrgb,
+                TextureFormat::Rgba8Unorm,
+                size(4, 4),
+                &[0; 8],
+            ),
+            Err(TranscodeError::UnsupportedTarget {
+                source: TextureFormat::Bc1RgbaUnormSrgb,
+                target: TextureFormat::Rgba8Unorm,
+            })
+        );
+        assert_eq!(
+            transcode_texture_data(
+                TextureFormat::Bc1RgbaUnorm,
+                TextureFormat::Rgba8Unorm,
+                size(5, 4),
+                &[0; 8],
+            ),
+            Err(TranscodeError::DataSizeMismatch {
+                expected: 16,
+                actual: 8,
+            })
+        );
+    }
+
+    #[test]
+    fn fallback_formats() {
+        let astc = TextureFormat::Astc {
+            block: crate::AstcBlock::B6x6,
+            channel: AstcChannel::UnormSrgb,
+        };
+        assert_eq!(
+            transcode_fallback_format(astc, size(8, 8), Features::TEXTURE_COMPRESSION_BC),
+            Some(TextureFormat::Bc7RgbaUnormSrgb)
+        );
+        assert_eq!(
+            transcode_fallback_format(astc, size(6, 6), Features::TEXTURE_COMPRESSION_BC),
+            Some(TextureFormat::Rgba8UnormSrgb)
+        );
+        assert_eq!(
+            transcode_fallback_format(TextureFormat::EacR11Snorm, size(4, 4), Features::empty()),
+            Some(TextureFormat::Rgba8Snorm)
+        );
+        assert_eq!(
+            transcode_fallback_format(TextureFormat::Rgba8Unorm, size(4, 4), Features::empty()),
+            None
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use super::belt::Exclusive;
+use crate::{
+    util::align_to, Buffer, BufferAddress, BufferDescriptor, BufferUsages, CommandBuffer,
+    CommandEncoder, Device, Extent3d, ImageCopyBuffer, ImageCopyTexture, ImageDataLayout, MapMode,
+    Queue, SubmissionIndex, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT, MAP_ALIGNMENT,
+};
+use parking_lot::Mutex;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{mpsc, Arc};
+use std::task::{Context, Poll, Waker};
+
+struct Chunk {
+    buffer: Arc<Buffer>,
+    size: BufferAddress,
+    offset: BufferAddress,
+}
+
+#[derive(Debug, Default)]
+struct UploadState {
+    done: bool,
+    wakers: Vec<Waker>,
+}
+
+/// Future resolving once the GPU finished an upload of an [`UploadManager`].
+///
+/// The future works with any async runtime. Like [`Queue::on_submitted_work_done`], it
+/// needs the device to be polled on native.
+#[derive(Debug)]
+pub struct UploadFuture {
+    state: Arc<Mutex<UploadState>>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(UploadFuture: Send, Sync);
+
+impl Future for UploadFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock();
+        if state.done {
+            Poll::Ready(())
+        } else {
+            if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        }
+    }
+}
+
+/// Streams data into buffers and textures through a ring-buffer of staging buffers.
+///
+/// Compared to [`StagingBelt`], it also uploads into textures, padding the rows to
+/// [`COPY_BYTES_PER_ROW_ALIGNMENT`], returns an [`UploadFuture`] for every upload and
+/// recycles the staging buffers on its own once the GPU is done with them.
+///
+/// Using an upload manager goes as follows:
+/// 1. Record uploads into a command encoder with [`UploadManager::write_buffer()`] and
+///    [`UploadManager::write_texture()`].
+/// 2. Submit the finished command encoder, along with any other command buffers,
+///    with [`UploadManager::submit()`].
+/// 3. Await the returned [`UploadFuture`]s where the uploaded data is needed on the CPU
+///    timeline, for example before reusing the source data.
+///
+/// [`StagingBelt`]: super::StagingBelt
+/// [`Queue::on_submitted_work_done`]: crate::Queue::on_submitted_work_done
+pub struct UploadManager {
+    chunk_size: BufferAddress,
+    /// Chunks into which we are accumulating data to be transferred.
+    active_chunks: Vec<Chunk>,
+    /// Chunks that are back from the GPU and mapped for write again.
+    free_chunks: Vec<Chunk>,
+    /// When submitted chunks are mapped again, the map callback sends them here.
+    sender: Exclusive<mpsc::Sender<Chunk>>,
+    /// Free chunks are received here to be put on `self.free_chunks`.
+    receiver: Exclusive<mpsc::Receiver<Chunk>>,
+    /// Completion state shared by the uploads recorded since the last submission.
+    batch: Arc<Mutex<UploadState>>,
+}
+
+impl UploadManager {
+    /// Create a new upload manager.
+    ///
+    /// The `chunk_size` is the unit of internal buffer allocation, like for
+    /// [`StagingBelt::new()`](super::StagingBelt::new). Uploads larger than it get a
+    /// staging buffer of their own.
+    pub fn new(chunk_size: BufferAddress) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        UploadManager {
+            chunk_size,
+            active_chunks: Vec::new(),
+            free_chunks: Vec::new(),
+            sender: Exclusive::new(sender),
+            receiver: Exclusive::new(receiver),
+            batch: Arc::default(),
+        }
+    }
+
+    /// Record an upload of `data` into the `target` buffer at the specified offset.
+    ///
+    /// The length of `data` must be a multiple of [`COPY_BUFFER_ALIGNMENT`].
+    /// The upload is recorded into `encoder`, which must be submitted with
+    /// [`UploadManager::submit()`].
+    pub fn write_buffer(
+        &mut self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        target: &Buffer,
+        offset: BufferAddress,
+        data: &[u8],
+    ) -> UploadFuture {
+        let size = data.len() as BufferAddress;
+        if size != 0 {
+            let (chunk, chunk_offset) = self.allocate(device, size, COPY_BUFFER_ALIGNMENT);
+            chunk
+                .buffer
+                .slice(chunk_offset..chunk_offset + size)
+                .get_mapped_range_mut()
+                .copy_from_slice(data);
+            encoder.copy_buffer_to_buffer(&chunk.buffer, chunk_offset, target, offset, size);
+        }
+        self.future()
+    }
+
+    /// Record an upload of `data` into a region of a texture.
+    ///
+    /// `data` and `data_layout` are interpreted as for [`Queue::write_texture()`], so the
+    /// rows of `data` don't need to be padded. They are copied into the staging buffer with
+    /// rows padded to [`COPY_BYTES_PER_ROW_ALIGNMENT`].
+    ///
+    /// The upload is recorded into `encoder`, which must be submitted with
+    /// [`UploadManager::submit()`].
+    ///
+    /// # Panics
+    ///
+    /// - The aspect of `texture` can't be copied to.
+    /// - `data` is too small for `data_layout` and `size`.
+    pub fn write_texture(
+        &mut self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        texture: ImageCopyTexture<'_>,
+        data: &[u8],
+        data_layout: ImageDataLayout,
+        size: Extent3d,
+    ) -> UploadFuture {
+        let format = texture.texture.format();
+        let block_size = format
+            .block_copy_size(Some(texture.aspect))
+            .expect("The texture aspect can't be copied to");
+        let (block_width, block_height) = format.block_dimensions();
+        let row_size = (size.width + block_width - 1) / block_width * block_size;
+        let rows = (size.height + block_height - 1) / block_height;
+        let bytes_per_row = data_layout.bytes_per_row.unwrap_or(row_size) as usize;
+        let rows_per_image = data_layout.rows_per_image.unwrap_or(rows) as usize;
+        let padded_row_size = align_to(row_size, COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let staging_size = padded_row_size as BufferAddress
+            * rows as BufferAddress
+            * size.depth_or_array_layers as BufferAddress;
+        if staging_size != 0 {
+            let (chunk, chunk_offset) = self.allocate(
+                device,
+                staging_size,
+                COPY_BYTES_PER_ROW_ALIGNMENT as BufferAddress,
+            );
+            {
+                let mut view = chunk
+                    .buffer
+                    .slice(chunk_offset..chunk_offset + staging_size)
+                    .get_mapped_range_mut();
+                let row_size = row_size as usize;
+                for layer in 0..size.depth_or_array_layers as usize {
+                    for row in 0..rows as usize {
+                        let src = data_layout.offset as usize
+                            + (layer * rows_per_image + row) * bytes_per_row;
+                        let dst = (layer * rows as usize + row) * padded_row_size as usize;
+                        view[dst..dst + row_size].copy_from_slice(&data[src..src + row_size]);
+                    }
+                }
+            }
+            encoder.copy_buffer_to_texture(
+                ImageCopyBuffer {
+                    buffer: &chunk.buffer,
+                    layout: ImageDataLayout {
+                        offset: chunk_offset,
+                        bytes_per_row: Some(padded_row_size),
+                        rows_per_image: Some(rows),
+                    },
+                },
+                texture,
+                size,
+            );
+        }
+        self.future()
+    }
+
+    /// Submit `command_buffers`, which must include all encoders the uploads since the last
+    /// call were recorded into, to `queue`.
+    ///
+    /// The [`UploadFuture`]s of these uploads resolve once the GPU finished the submission,
+    /// and their staging buffers are then reused for later uploads.
+    pub fn submit<I: IntoIterator<Item = CommandBuffer>>(
+        &mut self,
+        queue: &Queue,
+        command_buffers: I,
+    ) -> SubmissionIndex {
+        for chunk in &self.active_chunks {
+            chunk.buffer.unmap();
+        }
+        let index = queue.submit(command_buffers);
+
+        let batch = std::mem::take(&mut self.batch);
+        queue.on_submitted_work_done(move || {
+            let wakers = {
+                let mut state = batch.lock();
+                state.done = true;
+                std::mem::take(&mut state.wakers)
+            };
+            for waker in wakers {
+                waker.wake();
+            }
+        });
+
+        for chunk in self.active_chunks.drain(..) {
+            let sender = self.sender.get_mut().clone();
+            chunk
+                .buffer
+                .clone()
+                .slice(..)
+                .map_async(MapMode::Write, move |result| {
+                    if result.is_ok() {
+                        let _ = sender.send(chunk);
+                    }
+                });
+        }
+        index
+    }
+
+    fn future(&self) -> UploadFuture {
+        UploadFuture {
+            state: self.batch.clone(),
+        }
+    }
+
+    /// Sub-allocate `size` bytes at the given alignment from the active chunks, activating
+    /// a free or new chunk if none of them has enough room left.
+    fn allocate(
+        &mut self,
+        device: &Device,
+        size: BufferAddress,
+        alignment: BufferAddress,
+    ) -> (&Chunk, BufferAddress) {
+        let alignment = alignment.max(MAP_ALIGNMENT);
+        let index = if let Some(index) = self
+            .active_chunks
+            .iter()
+            .position(|chunk| align_to(chunk.offset, alignment) + size <= chunk.size)
+        {
+            index
+        } else {
+            self.receive_chunks(); // ensure self.free_chunks is up to date
+
+            let chunk =
+                if let Some(index) = self.free_chunks.iter().position(|chunk| size <= chunk.size) {
+                    self.free_chunks.swap_remove(index)
+                } else {
+                    let size = self.chunk_size.max(align_to(size, MAP_ALIGNMENT));
+                    Chunk {
+                        buffer: Arc::new(device.create_buffer(&BufferDescriptor {
+                            label: Some("(wgpu internal) UploadManager staging buffer"),
+                            size,
+                            usage: BufferUsages::MAP_WRITE | BufferUsages::COPY_SRC,
+                            mapped_at_creation: true,
+                        })),
+                        size,
+                        offset: 0,
+                    }
+                };
+            self.active_chunks.push(chunk);
+            self.active_chunks.len() - 1
+        };
+
+        let chunk = &mut self.active_chunks[index];
+        let offset = align_to(chunk.offset, alignment);
+        chunk.offset = offset + size;
+        (chunk, offset)
+    }
+
+    /// Move all chunks that the GPU is done with (and are now mapped again)
+    /// from `self.receiver` to `self.free_chunks`.
+    fn receive_chunks(&mut self) {
+        while let Ok(mut chunk) = self.receiver.get_mut().try_recv() {
+            chunk.offset = 0;
+            self.free_chunks.push(chunk);
+        }
+    }
+}
+
+impl fmt::Debug for UploadManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadManager")
+            .field("chunk_size", &self.chunk_size)
+            .field("active_chunks", &self.active_chunks.len())
+            .field("free_chunks", &self.free_chunks.len())
+            .finish_non_exhaustive()
+    }
+}